@@ -0,0 +1,422 @@
+//! Offline batch inference over JSONL prompt files
+//!
+//! A [`BatchJob`] reads one request per line from an input file:
+//!
+//! ```json
+//! {"id": "q1", "prompt": "Translate 'bonjour'", "temperature": 0.2, "max_tokens": 64}
+//! ```
+//!
+//! Only `prompt` is required; `id` defaults to `line-N` and the sampling
+//! fields override the job's defaults. Requests run through one provider
+//! with a bounded number in flight, and each finished request is appended to
+//! the output file as a [`BatchRecord`] with its completion and token usage.
+//!
+//! The output file doubles as the checkpoint: rerunning a job skips every id
+//! already recorded without an error, so an interrupted run picks up where
+//! it stopped and failed requests are retried. A retried request appends a
+//! new record, which supersedes its earlier failed one.
+
+use std::collections::HashSet;
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinSet;
+use tokio_stream::StreamExt;
+
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::context::chunks::FinishReason;
+use crate::domain::prompt::CandlePrompt;
+
+/// Requests run at once when no concurrency is set
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+/// Batch job errors
+#[derive(Debug, thiserror::Error)]
+pub enum BatchError {
+    #[error("Batch I/O error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid batch request on line {line}: {message}")]
+    InvalidRequest { line: usize, message: String },
+    #[error("Batch request id '{0}' appears more than once")]
+    DuplicateId(String),
+}
+
+/// One prompt to run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchRequest {
+    /// Identifies the request in the output and checkpoint
+    #[serde(default)]
+    pub id: String,
+    pub prompt: String,
+    /// Overrides the job's temperature
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Overrides the job's token limit
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
+/// Token counts of one completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct BatchUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// Output line for one finished request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchRecord {
+    pub id: String,
+    pub completion: String,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+    #[serde(default)]
+    pub usage: BatchUsage,
+    pub elapsed_ms: u64,
+    /// Set when the provider failed; the request is retried on the next run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Totals for a batch run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchSummary {
+    /// Requests in the input file
+    pub total: usize,
+    /// Requests already completed by an earlier run
+    pub skipped: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub elapsed: Duration,
+}
+
+impl std::fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} completed, {} failed, {} skipped of {} requests in {:.1}s ({} prompt + {} completion tokens)",
+            self.completed,
+            self.failed,
+            self.skipped,
+            self.total,
+            self.elapsed.as_secs_f64(),
+            self.prompt_tokens,
+            self.completion_tokens
+        )
+    }
+}
+
+/// A JSONL prompt file to run through a provider
+#[derive(Debug, Clone)]
+pub struct BatchJob {
+    input: PathBuf,
+    output: PathBuf,
+    concurrency: usize,
+    temperature: f64,
+    max_tokens: Option<u64>,
+}
+
+impl BatchJob {
+    /// Run the requests in `input`, appending results to `output`
+    pub fn new(input: impl Into<PathBuf>, output: impl Into<PathBuf>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            temperature: 0.0,
+            max_tokens: None,
+        }
+    }
+
+    /// Requests in flight at once (at least 1)
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Temperature for requests that do not set one
+    #[must_use]
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Token limit for requests that do not set one
+    #[must_use]
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Run every request not already completed in the output file
+    ///
+    /// # Errors
+    /// Returns `BatchError` if the input cannot be read or parsed, or the
+    /// output cannot be written. Provider failures are recorded per request
+    /// instead.
+    pub async fn run<M>(&self, model: M) -> Result<BatchSummary, BatchError>
+    where
+        M: TextToTextCapable + Clone,
+    {
+        let started = Instant::now();
+        let requests = read_requests(&self.input).await?;
+        let done = read_checkpoint(&self.output).await?;
+        let pending: Vec<BatchRequest> = requests
+            .iter()
+            .filter(|request| !done.ids.contains(&request.id))
+            .cloned()
+            .collect();
+
+        let mut summary = BatchSummary {
+            total: requests.len(),
+            skipped: requests.len() - pending.len(),
+            ..BatchSummary::default()
+        };
+        log::info!(
+            "Batch: {} requests, {} already done, {} to run",
+            summary.total,
+            summary.skipped,
+            pending.len()
+        );
+
+        let mut output = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.output)
+            .await
+            .map_err(|source| self.output_error(source))?;
+        if done.truncated_tail {
+            // Terminate the line an interrupted run left half-written
+            output
+                .write_all(b"\n")
+                .await
+                .map_err(|source| self.output_error(source))?;
+        }
+
+        let defaults = Arc::new(self.clone());
+        let mut pending = pending.into_iter();
+        let mut in_flight = JoinSet::new();
+        loop {
+            while in_flight.len() < self.concurrency
+                && let Some(request) = pending.next()
+            {
+                let model = model.clone();
+                let defaults = Arc::clone(&defaults);
+                in_flight.spawn(async move { defaults.complete(&model, request).await });
+            }
+
+            let Some(joined) = in_flight.join_next().await else {
+                break;
+            };
+            let record = match joined {
+                Ok(record) => record,
+                Err(e) => {
+                    log::error!("Batch request task failed: {}", e);
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            if record.error.is_some() {
+                summary.failed += 1;
+            } else {
+                summary.completed += 1;
+            }
+            summary.prompt_tokens += u64::from(record.usage.prompt_tokens);
+            summary.completion_tokens += u64::from(record.usage.completion_tokens);
+
+            // One line per record, flushed, so an interrupted run loses only what was in flight
+            let mut line = serde_json::to_vec(&record).unwrap_or_default();
+            line.push(b'\n');
+            output
+                .write_all(&line)
+                .await
+                .map_err(|source| self.output_error(source))?;
+            output
+                .flush()
+                .await
+                .map_err(|source| self.output_error(source))?;
+            log::info!(
+                "Batch: {} {} ({}/{})",
+                record.id,
+                if record.error.is_some() {
+                    "failed"
+                } else {
+                    "done"
+                },
+                summary.skipped + summary.completed + summary.failed,
+                summary.total
+            );
+        }
+
+        summary.elapsed = started.elapsed();
+        Ok(summary)
+    }
+
+    /// Run one request to completion
+    async fn complete<M: TextToTextCapable>(
+        &self,
+        model: &M,
+        request: BatchRequest,
+    ) -> BatchRecord {
+        let started = Instant::now();
+        let params = CandleCompletionParams {
+            temperature: request.temperature.unwrap_or(self.temperature),
+            max_tokens: request
+                .max_tokens
+                .or(self.max_tokens)
+                .and_then(NonZeroU64::new),
+            ..Default::default()
+        };
+
+        let mut stream = model.prompt(CandlePrompt::new(request.prompt), &params);
+        let mut record = BatchRecord {
+            id: request.id,
+            completion: String::new(),
+            finish_reason: None,
+            usage: BatchUsage::default(),
+            elapsed_ms: 0,
+            error: None,
+        };
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                CandleCompletionChunk::Text(text) => record.completion.push_str(&text),
+                CandleCompletionChunk::Complete {
+                    text,
+                    finish_reason,
+                    usage,
+                    token_count,
+                    ..
+                } => {
+                    record.completion.push_str(&text);
+                    record.finish_reason = Some(finish_reason_str(finish_reason).to_string());
+                    record.usage = match usage {
+                        Some(usage) => BatchUsage {
+                            prompt_tokens: usage.input_tokens,
+                            completion_tokens: usage.output_tokens,
+                            total_tokens: usage.total_tokens,
+                        },
+                        None => BatchUsage {
+                            prompt_tokens: 0,
+                            completion_tokens: token_count.unwrap_or(0),
+                            total_tokens: token_count.unwrap_or(0),
+                        },
+                    };
+                }
+                CandleCompletionChunk::Error(error) => {
+                    record.error = Some(error);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        record.elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        record
+    }
+
+    fn output_error(&self, source: std::io::Error) -> BatchError {
+        BatchError::Io {
+            path: self.output.clone(),
+            source,
+        }
+    }
+}
+
+fn finish_reason_str(reason: Option<FinishReason>) -> &'static str {
+    match reason {
+        Some(FinishReason::Length) => "length",
+        Some(FinishReason::ContentFilter) => "content_filter",
+        Some(FinishReason::ToolCalls) => "tool_calls",
+        Some(FinishReason::Stop | FinishReason::Error) | None => "stop",
+    }
+}
+
+/// Parse the requests in a JSONL file, skipping blank lines
+///
+/// Requests without an id are named `line-N` after their 1-based line.
+///
+/// # Errors
+/// Returns `BatchError` if the file cannot be read, a line is not a valid
+/// request, or two requests share an id
+pub async fn read_requests(path: &Path) -> Result<Vec<BatchRequest>, BatchError> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| BatchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let mut seen = HashSet::new();
+    let mut requests = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut request: BatchRequest =
+            serde_json::from_str(line).map_err(|e| BatchError::InvalidRequest {
+                line: line_number,
+                message: e.to_string(),
+            })?;
+        if request.id.is_empty() {
+            request.id = format!("line-{}", line_number);
+        }
+        if !seen.insert(request.id.clone()) {
+            return Err(BatchError::DuplicateId(request.id));
+        }
+        requests.push(request);
+    }
+    Ok(requests)
+}
+
+/// What an earlier run left in an output file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Ids recorded without an error
+    pub ids: HashSet<String>,
+    /// Whether the file ends in a partial line
+    pub truncated_tail: bool,
+}
+
+/// Read the checkpoint of an output file
+///
+/// A missing file has none. Unparseable lines, such as one cut short by an
+/// interrupted run, are ignored so their requests run again.
+///
+/// # Errors
+/// Returns `BatchError::Io` if the file exists but cannot be read
+pub async fn read_checkpoint(path: &Path) -> Result<Checkpoint, BatchError> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Checkpoint::default()),
+        Err(source) => {
+            return Err(BatchError::Io {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+
+    Ok(Checkpoint {
+        ids: contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<BatchRecord>(line).ok())
+            .filter(|record| record.error.is_none())
+            .map(|record| record.id)
+            .collect(),
+        truncated_tail: !contents.is_empty() && !contents.ends_with('\n'),
+    })
+}
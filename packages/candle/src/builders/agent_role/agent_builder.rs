@@ -36,6 +36,16 @@ pub struct CandleAgentBuilderImpl {
     pub(super) on_conversation_turn_handler: Option<OnConversationTurnHandler>,
     pub(super) conversation_history: ZeroOneOrMany<(CandleMessageRole, String)>,
     pub(super) stop_sequences: Vec<String>,
+    pub(super) rag: RagConfig,
+    pub(super) memory_decay: DecayWorkerConfig,
+    pub(super) compaction: CompactionConfig,
+    pub(super) usage: UsageTracker,
+    pub(super) vision_model: Option<VisionModel>,
+    pub(super) think: Option<ThinkConfig>,
+    pub(super) tool_guardrails: Option<ToolGuardrails>,
+    pub(super) sandbox_profile: Option<SandboxProfile>,
+    pub(super) native_tools: Vec<NativeTool>,
+    pub(super) system_prompt_template: Option<SystemPromptTemplate>,
 }
 
 impl std::fmt::Debug for CandleAgentBuilderImpl {
@@ -57,6 +67,16 @@ impl std::fmt::Debug for CandleAgentBuilderImpl {
             .field("tools", &self.tools)
             .field("additional_params", &self.additional_params)
             .field("metadata", &self.metadata)
+            .field("rag", &self.rag)
+            .field("memory_decay", &self.memory_decay)
+            .field("compaction", &self.compaction)
+            .field("usage", &self.usage)
+            .field("vision_model", &self.vision_model)
+            .field("think", &self.think)
+            .field("tool_guardrails", &self.tool_guardrails)
+            .field("sandbox_profile", &self.sandbox_profile)
+            .field("native_tools", &self.native_tools)
+            .field("system_prompt_template", &self.system_prompt_template)
             .finish()
     }
 }
@@ -18,6 +18,14 @@ pub(super) fn set_embedding_model(
     builder
 }
 
+pub(super) fn set_vision_model(
+    mut builder: CandleAgentBuilderImpl,
+    model: VisionModel,
+) -> CandleAgentBuilderImpl {
+    builder.vision_model = Some(model);
+    builder
+}
+
 pub(super) fn set_temperature(
     mut builder: CandleAgentBuilderImpl,
     temp: f64,
@@ -116,6 +124,78 @@ pub(super) fn set_stop_sequences(
     builder
 }
 
+pub(super) fn set_rag(
+    mut builder: CandleAgentBuilderImpl,
+    config: RagConfig,
+) -> CandleAgentBuilderImpl {
+    builder.rag = config;
+    builder
+}
+
+pub(super) fn set_think(
+    mut builder: CandleAgentBuilderImpl,
+    config: ThinkConfig,
+) -> CandleAgentBuilderImpl {
+    builder.think = Some(config);
+    builder
+}
+
+pub(super) fn set_tool_guardrails(
+    mut builder: CandleAgentBuilderImpl,
+    guardrails: ToolGuardrails,
+) -> CandleAgentBuilderImpl {
+    builder.tool_guardrails = Some(guardrails);
+    builder
+}
+
+pub(super) fn set_sandbox_profile(
+    mut builder: CandleAgentBuilderImpl,
+    profile: SandboxProfile,
+) -> CandleAgentBuilderImpl {
+    builder.sandbox_profile = Some(profile);
+    builder
+}
+
+pub(super) fn add_native_tool(
+    mut builder: CandleAgentBuilderImpl,
+    tool: NativeTool,
+) -> CandleAgentBuilderImpl {
+    builder.native_tools.push(tool);
+    builder
+}
+
+pub(super) fn set_system_prompt_template(
+    mut builder: CandleAgentBuilderImpl,
+    template: SystemPromptTemplate,
+) -> CandleAgentBuilderImpl {
+    builder.system_prompt_template = Some(template);
+    builder
+}
+
+pub(super) fn set_memory_decay(
+    mut builder: CandleAgentBuilderImpl,
+    config: DecayWorkerConfig,
+) -> CandleAgentBuilderImpl {
+    builder.memory_decay = config;
+    builder
+}
+
+pub(super) fn set_compaction(
+    mut builder: CandleAgentBuilderImpl,
+    config: CompactionConfig,
+) -> CandleAgentBuilderImpl {
+    builder.compaction = config;
+    builder
+}
+
+pub(super) fn set_conversation_history(
+    mut builder: CandleAgentBuilderImpl,
+    history: ZeroOneOrMany<(CandleMessageRole, String)>,
+) -> CandleAgentBuilderImpl {
+    builder.conversation_history = history;
+    builder
+}
+
 pub(super) fn add_stop_sequence_impl(
     mut builder: CandleAgentBuilderImpl,
     sequence: String,
@@ -4,12 +4,14 @@
 //! - builder_methods: Simple builder pattern setters
 //! - handler_registration: Handler wrapping and Arc management
 //! - memory_ops: Memory initialization, context loading, and storage
+//! - typed_generation: Schema-constrained generation into Rust types
 //!
 //! Chat orchestration is delegated to domain::chat::session
 
 mod builder_methods;
 mod handler_registration;
 mod memory_ops;
+mod typed_generation;
 
 use super::*;
 use std::sync::Arc;
@@ -24,6 +26,10 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
         builder_methods::set_embedding_model(self, model)
     }
 
+    fn vision_model(self, model: VisionModel) -> impl CandleAgentBuilder {
+        builder_methods::set_vision_model(self, model)
+    }
+
     fn temperature(self, temp: f64) -> impl CandleAgentBuilder {
         builder_methods::set_temperature(self, temp)
     }
@@ -40,10 +46,46 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
         builder_methods::add_stop_sequence_impl(self, sequence.into())
     }
 
+    fn rag(self, config: RagConfig) -> impl CandleAgentBuilder {
+        builder_methods::set_rag(self, config)
+    }
+
+    fn think(self, config: ThinkConfig) -> impl CandleAgentBuilder {
+        builder_methods::set_think(self, config)
+    }
+
+    fn tool_guardrails(self, guardrails: ToolGuardrails) -> impl CandleAgentBuilder {
+        builder_methods::set_tool_guardrails(self, guardrails)
+    }
+
+    fn sandbox_profile(self, profile: SandboxProfile) -> impl CandleAgentBuilder {
+        builder_methods::set_sandbox_profile(self, profile)
+    }
+
+    fn native_tool(self, tool: NativeTool) -> impl CandleAgentBuilder {
+        builder_methods::add_native_tool(self, tool)
+    }
+
+    fn system_prompt_template(self, template: SystemPromptTemplate) -> impl CandleAgentBuilder {
+        builder_methods::set_system_prompt_template(self, template)
+    }
+
+    fn memory_decay(self, config: DecayWorkerConfig) -> impl CandleAgentBuilder {
+        builder_methods::set_memory_decay(self, config)
+    }
+
+    fn compaction(self, config: CompactionConfig) -> impl CandleAgentBuilder {
+        builder_methods::set_compaction(self, config)
+    }
+
     fn memory_read_timeout(self, timeout_ms: u64) -> impl CandleAgentBuilder {
         builder_methods::set_memory_read_timeout(self, timeout_ms)
     }
 
+    fn usage(&self) -> UsageTracker {
+        self.usage.clone()
+    }
+
     fn system_prompt(self, prompt: impl Into<String>) -> impl CandleAgentBuilder {
         builder_methods::set_system_prompt(self, prompt.into())
     }
@@ -126,9 +168,9 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
 
     fn conversation_history(
         self,
-        _history: impl ConversationHistoryArgs,
+        history: impl ConversationHistoryArgs,
     ) -> impl CandleAgentBuilder {
-        self
+        builder_methods::set_conversation_history(self, history.into_history())
     }
 
     fn chat<F, Fut>(
@@ -149,6 +191,16 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
         let tools: Arc<[ToolInfo]> = Vec::from(self.tools).into();
         let metadata = self.metadata;
         let conversation_history = self.conversation_history;
+        let rag = self.rag;
+        let memory_decay = self.memory_decay;
+        let compaction = self.compaction;
+        let usage = self.usage;
+        let vision_model = self.vision_model;
+        let think = self.think;
+        let tool_guardrails = self.tool_guardrails;
+        let sandbox_profile = self.sandbox_profile;
+        let native_tools = self.native_tools;
+        let system_prompt_template = self.system_prompt_template;
 
         // Extract handlers
         let on_chunk_handler = self.on_chunk_handler;
@@ -165,7 +217,7 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
             move |sender| async move {
                 // Initialize memory manager if embedding model available
                 let memory = if let Some(ref emb_model) = embedding_model {
-                    match memory_ops::initialize_memory_coordinator(emb_model).await {
+                    match memory_ops::initialize_memory_coordinator(emb_model, memory_decay).await {
                         Ok(mgr) => mgr,
                         Err(e) => {
                             let _ = sender.send(CandleMessageChunk::Error(e));
@@ -187,6 +239,15 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
                     memory,
                     tools,
                     metadata,
+                    rag,
+                    usage,
+                    vision_model,
+                    think,
+                    tool_guardrails,
+                    sandbox_profile,
+                    system_prompt_template,
+                    compaction,
+                    native_tools,
                 };
                 let contexts = crate::domain::chat::session::ChatSessionContexts {
                     context_file,
@@ -218,6 +279,16 @@ impl CandleAgentBuilder for CandleAgentBuilderImpl {
         )))
     }
 
+    fn generate_typed<T>(
+        self,
+        message: impl Into<String>,
+    ) -> impl std::future::Future<Output = Result<T, AgentError>> + Send
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send,
+    {
+        typed_generation::generate_typed(self, message.into())
+    }
+
     fn chat_with_message(
         self,
         message: impl Into<String>,
@@ -0,0 +1,63 @@
+//! Schema-constrained generation deserialized into a Rust type
+
+use super::super::*;
+use crate::core::generation::JSON_SCHEMA_PARAM;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+pub(super) async fn generate_typed<T>(
+    builder: CandleAgentBuilderImpl,
+    message: String,
+) -> Result<T, AgentError>
+where
+    T: DeserializeOwned + JsonSchema,
+{
+    // Builder params are strings; the schema goes in as a JSON object
+    let mut additional_params: serde_json::Map<String, serde_json::Value> = builder
+        .additional_params
+        .into_iter()
+        .map(|(k, v)| (k, serde_json::Value::String(v)))
+        .collect();
+    additional_params.insert(
+        JSON_SCHEMA_PARAM.to_string(),
+        schemars::schema_for!(T).to_value(),
+    );
+
+    let params = crate::domain::completion::CandleCompletionParams {
+        temperature: builder.temperature,
+        max_tokens: std::num::NonZeroU64::new(builder.max_tokens),
+        additional_params: Some(serde_json::Value::Object(additional_params)),
+        ..Default::default()
+    };
+    let prompt = CandlePrompt::new(format!("{}\n\nUser: {}", builder.system_prompt, message));
+
+    let completion_stream = builder.text_to_text_model.prompt(prompt, &params);
+    tokio::pin!(completion_stream);
+    let mut output = String::new();
+    while let Some(chunk) = completion_stream.next().await {
+        match chunk {
+            CandleCompletionChunk::Text(text) => output.push_str(&text),
+            CandleCompletionChunk::Complete {
+                text,
+                usage,
+                token_count,
+                elapsed_secs,
+                ..
+            } => {
+                output.push_str(&text);
+                builder
+                    .usage
+                    .record_completion(usage, token_count, elapsed_secs);
+            }
+            CandleCompletionChunk::Error(e) => return Err(AgentError::Model(e)),
+            _ => {}
+        }
+    }
+
+    serde_json::from_str(output.trim()).map_err(|e| {
+        AgentError::Model(format!(
+            "Structured output does not match the requested type: {} (output: {})",
+            e, output
+        ))
+    })
+}
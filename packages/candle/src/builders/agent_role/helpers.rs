@@ -12,6 +12,16 @@ impl CandleAgentRoleAgent {
         Self { state }
     }
 
+    /// Name of the agent
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Token usage of the conversation this agent belongs to
+    pub fn usage(&self) -> ConversationUsage {
+        self.state.usage.snapshot()
+    }
+
     /// Chat method for use in on_conversation_turn closure - enables recursion
     pub fn chat(
         &self,
@@ -27,6 +37,7 @@ impl CandleAgentRoleAgent {
                         token_count: None,
                         elapsed_secs: None,
                         tokens_per_sec: None,
+                        provider: None,
                     };
                     let _ = sender.send(final_chunk);
                 }))
@@ -34,9 +45,74 @@ impl CandleAgentRoleAgent {
             CandleChatLoop::UserPrompt(user_message) | CandleChatLoop::Reprompt(user_message) => {
                 self.run_inference_cycle(user_message)
             }
+            CandleChatLoop::UserPromptWithImages(user_message, images) => {
+                self.run_vision_cycle(user_message, images)
+            }
         }
     }
 
+    fn run_vision_cycle(
+        &self,
+        user_message: String,
+        images: Vec<crate::domain::chat::message::CandleImageAttachment>,
+    ) -> Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>> {
+        use crate::domain::chat::vision::{
+            default_vision_model, describe_attachments, describe_user_turn,
+        };
+
+        let state = self.state.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(
+            move |stream_sender| async move {
+                let Some(model) = state.vision_model.clone().or_else(default_vision_model) else {
+                    let _ = stream_sender.send(CandleMessageChunk::Error(
+                        "No vision model available for image attachments".to_string(),
+                    ));
+                    return;
+                };
+
+                let user_turn = describe_user_turn(&user_message, &images);
+                let mut reply = describe_attachments(model, user_message, images);
+                let mut assistant_response = String::new();
+                while let Some(chunk) = reply.next().await {
+                    match &chunk {
+                        CandleMessageChunk::Text(text) => assistant_response.push_str(text),
+                        CandleMessageChunk::Complete {
+                            usage,
+                            token_count,
+                            elapsed_secs,
+                            ..
+                        } => state
+                            .usage
+                            .record_completion(*usage, *token_count, *elapsed_secs),
+                        _ => {}
+                    }
+                    let final_chunk = if let Some(ref handler) = state.on_chunk_handler {
+                        handler(chunk).await
+                    } else {
+                        chunk
+                    };
+                    let _ = stream_sender.send(final_chunk);
+                }
+
+                if let Some(ref handler) = state.on_conversation_turn_handler {
+                    let mut conversation = CandleAgentConversation::new();
+                    conversation.add_message(user_turn, CandleMessageRole::User);
+                    conversation.add_message(assistant_response, CandleMessageRole::Assistant);
+
+                    let agent = CandleAgentRoleAgent {
+                        state: state.clone(),
+                    };
+                    let handler_stream = handler(&conversation, &agent).await;
+                    tokio::pin!(handler_stream);
+                    while let Some(chunk) = handler_stream.next().await {
+                        let _ = stream_sender.send(chunk);
+                    }
+                }
+            },
+        ))
+    }
+
     fn run_inference_cycle(
         &self,
         user_message: String,
@@ -48,6 +124,7 @@ impl CandleAgentRoleAgent {
                 // Extract handlers from state for recursive inference
                 let on_chunk_handler = state.on_chunk_handler.clone();
                 let on_tool_result_handler = state.on_tool_result_handler.clone();
+                let usage_tracker = state.usage.clone();
 
                 // Initialize tool router
                 let tool_router = {
@@ -75,7 +152,8 @@ impl CandleAgentRoleAgent {
                     let mut router = crate::domain::tool::router::SweetMcpRouter::with_configs(
                         plugin_configs,
                         None,
-                    );
+                    )
+                    .with_guardrails(state.tool_guardrails.clone().unwrap_or_default());
 
                     match router.initialize().await {
                         Ok(()) => Some(router),
@@ -117,9 +195,32 @@ impl CandleAgentRoleAgent {
                 let completion_stream = state.text_to_text_model.prompt(prompt, &params);
                 tokio::pin!(completion_stream);
                 let mut assistant_response = String::new();
+                let mut pending_tool_calls: Vec<(String, String, String)> = Vec::new();
 
                 // Stream chunks
                 while let Some(completion_chunk) = completion_stream.next().await {
+                    // Run the turn's tool calls as one batch before the turn completes
+                    if let (Some(router), CandleCompletionChunk::Complete { .. }) =
+                        (&tool_router, &completion_chunk)
+                    {
+                        let calls = std::mem::take(&mut pending_tool_calls);
+                        for chunk in execute_tool_calls(
+                            calls,
+                            router,
+                            on_tool_result_handler.as_ref(),
+                            &usage_tracker,
+                        )
+                        .await
+                        {
+                            let final_chunk = if let Some(ref handler) = on_chunk_handler {
+                                handler(chunk).await
+                            } else {
+                                chunk
+                            };
+                            let _ = stream_sender.send(final_chunk);
+                        }
+                    }
+
                     let message_chunk = match completion_chunk {
                         CandleCompletionChunk::Text(ref text) => {
                             assistant_response.push_str(text);
@@ -132,6 +233,7 @@ impl CandleAgentRoleAgent {
                             token_count,
                             elapsed_secs,
                             tokens_per_sec,
+                            provider,
                         } => {
                             assistant_response.push_str(text);
 
@@ -146,14 +248,16 @@ impl CandleAgentRoleAgent {
                                 AGENT_STATS
                                     .record_completion(usage.total_tokens as u64, duration_us);
                             }
+                            usage_tracker.record_completion(usage, token_count, elapsed_secs);
 
                             CandleMessageChunk::Complete {
                                 text: text.clone(),
                                 finish_reason: finish_reason.map(|f| format!("{:?}", f)),
-                                usage: usage.map(|u| format!("{:?}", u)),
+                                usage,
                                 token_count,
                                 elapsed_secs,
                                 tokens_per_sec,
+                                provider,
                             }
                         }
                         CandleCompletionChunk::ToolCallStart { id, name } => {
@@ -169,40 +273,11 @@ impl CandleAgentRoleAgent {
                             partial_input,
                         },
                         CandleCompletionChunk::ToolCallComplete { id, name, input } => {
-                            if let Some(ref router) = tool_router {
-                                match serde_json::from_str::<serde_json::Value>(&input) {
-                                    Ok(args_json) => {
-                                        let sweet_args =
-                                            crate::domain::agent::role::convert_serde_to_sweet_json(
-                                                args_json,
-                                            );
-                                        // Use .await instead of block_on
-                                        match router.call_tool(&name, sweet_args).await {
-                                            Ok(response) => {
-                                                // Call tool result handler if configured
-                                                if let Some(ref handler) = on_tool_result_handler {
-                                                    let results = vec![format!("{:?}", response)];
-                                                    handler(&results).await;
-                                                }
-
-                                                CandleMessageChunk::Text(format!(
-                                                    "Tool '{}' executed: {:?}",
-                                                    name, response
-                                                ))
-                                            }
-                                            Err(e) => CandleMessageChunk::Error(format!(
-                                                "Tool '{}' failed: {}",
-                                                name, e
-                                            )),
-                                        }
-                                    }
-                                    Err(e) => {
-                                        CandleMessageChunk::Error(format!("Invalid JSON: {}", e))
-                                    }
-                                }
-                            } else {
-                                CandleMessageChunk::ToolCallComplete { id, name, input }
+                            if tool_router.is_some() {
+                                pending_tool_calls.push((id, name, input));
+                                continue;
                             }
+                            CandleMessageChunk::ToolCallComplete { id, name, input }
                         }
                         CandleCompletionChunk::Error(error) => CandleMessageChunk::Error(error),
                     };
@@ -216,6 +291,24 @@ impl CandleAgentRoleAgent {
                     let _ = stream_sender.send(final_chunk);
                 }
 
+                if let Some(ref router) = tool_router {
+                    for chunk in execute_tool_calls(
+                        pending_tool_calls,
+                        router,
+                        on_tool_result_handler.as_ref(),
+                        &usage_tracker,
+                    )
+                    .await
+                    {
+                        let final_chunk = if let Some(ref handler) = on_chunk_handler {
+                            handler(chunk).await
+                        } else {
+                            chunk
+                        };
+                        let _ = stream_sender.send(final_chunk);
+                    }
+                }
+
                 // CRITICAL: Call handler for recursion
                 if let Some(ref handler) = state.on_conversation_turn_handler {
                     let mut conversation = CandleAgentConversation::new();
@@ -238,6 +331,55 @@ impl CandleAgentRoleAgent {
     }
 }
 
+/// Execute a turn's tool calls and return one message chunk per call, in call order
+async fn execute_tool_calls(
+    calls: Vec<(String, String, String)>,
+    router: &crate::domain::tool::SweetMcpRouter,
+    on_tool_result_handler: Option<&OnToolResultHandler>,
+    usage_tracker: &UsageTracker,
+) -> Vec<CandleMessageChunk> {
+    if calls.is_empty() {
+        return Vec::new();
+    }
+
+    // Outcomes come back in call order, so inputs line up by position
+    let inputs: Vec<String> = calls.iter().map(|(_, _, input)| input.clone()).collect();
+    let outcomes = crate::domain::tool::ToolScheduler::new()
+        .execute_model_calls(router, calls)
+        .await;
+    for (input, outcome) in inputs.iter().zip(&outcomes) {
+        usage_tracker.record_tool_call(crate::domain::chat::usage::ToolCallUsage::from_outcome(
+            input, outcome,
+        ));
+    }
+
+    if let Some(handler) = on_tool_result_handler {
+        let results: Vec<String> = outcomes
+            .iter()
+            .filter_map(|outcome| outcome.result.as_ref().ok())
+            .map(|response| format!("{:?}", response))
+            .collect();
+        if !results.is_empty() {
+            handler(&results).await;
+        }
+    }
+
+    outcomes
+        .into_iter()
+        .map(|outcome| match outcome.result {
+            Ok(response) => CandleMessageChunk::Text(format!(
+                "Tool '{}' executed: {:?}",
+                outcome.name, response
+            )),
+            Err(e) => CandleMessageChunk::Error(format!(
+                "Tool '{}' failed: {}",
+                outcome.name,
+                e.to_tool_error(&outcome.name)
+            )),
+        })
+        .collect()
+}
+
 pub trait ConversationHistoryArgs {
     /// Convert this into conversation history format
     fn into_history(self) -> ZeroOneOrMany<(CandleMessageRole, String)>;
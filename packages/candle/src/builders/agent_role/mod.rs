@@ -7,17 +7,23 @@ mod role_builder;
 mod role_builder_impl;
 mod traits;
 
-pub(crate) use crate::capability::registry::{TextEmbeddingModel, TextToTextModel};
+pub(crate) use crate::capability::registry::{TextEmbeddingModel, TextToTextModel, VisionModel};
 pub(crate) use crate::capability::traits::TextToTextCapable;
 pub(crate) use crate::domain::agent::core::AgentError;
 pub(crate) use crate::domain::agent::role::CandleAgentConversation;
 pub(crate) use crate::domain::chat::CandleChatLoop;
+pub use crate::domain::chat::compaction::CompactionConfig;
+pub use crate::domain::chat::reasoning::{ReasoningStrategy, ThinkConfig};
+pub use crate::domain::chat::system_prompt::SystemPromptTemplate;
 pub(crate) use crate::domain::chat::message::{CandleMessageChunk, CandleMessageRole};
+pub use crate::domain::chat::usage::{ConversationUsage, UsageTracker};
+pub use crate::domain::tool::{NativeTool, SandboxProfile, ToolGuardrails};
 pub(crate) use crate::domain::completion::CandleCompletionChunk;
 pub(crate) use crate::domain::context::provider::{
     CandleContext, CandleDirectory, CandleFile, CandleFiles, CandleGithub,
 };
 pub(crate) use crate::domain::prompt::CandlePrompt;
+pub use crate::memory::{DecayWorkerConfig, RagConfig};
 pub use agent_builder::{AgentDebugInfo, CandleAgentBuilderImpl};
 pub(crate) use cyrup_sugars::ZeroOneOrMany;
 pub use helpers::{CandleAgentRoleAgent, CandleFluentAi, ConversationHistoryArgs};
@@ -71,4 +77,7 @@ pub struct AgentBuilderState {
     pub on_chunk_handler: Option<OnChunkHandler>,
     pub on_tool_result_handler: Option<OnToolResultHandler>,
     pub on_conversation_turn_handler: Option<OnConversationTurnHandler>,
+    pub usage: UsageTracker,
+    pub vision_model: Option<VisionModel>,
+    pub tool_guardrails: Option<ToolGuardrails>,
 }
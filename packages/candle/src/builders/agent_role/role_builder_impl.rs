@@ -72,6 +72,16 @@ impl CandleAgentRoleBuilder for CandleAgentRoleBuilderImpl {
             on_conversation_turn_handler: self.on_conversation_turn_handler,
             conversation_history: self.conversation_history,
             stop_sequences: self.stop_sequences,
+            rag: RagConfig::default(),
+            memory_decay: DecayWorkerConfig::default(),
+            compaction: CompactionConfig::default(),
+            native_tools: Vec::new(),
+            usage: UsageTracker::new(),
+            vision_model: None,
+            think: None,
+            tool_guardrails: None,
+            sandbox_profile: None,
+            system_prompt_template: None,
         }
     }
 
@@ -294,6 +304,16 @@ impl CandleAgentRoleBuilder for CandleAgentRoleBuilderImpl {
             on_conversation_turn_handler: self.on_conversation_turn_handler,
             conversation_history: self.conversation_history,
             stop_sequences: self.stop_sequences,
+            rag: RagConfig::default(),
+            memory_decay: DecayWorkerConfig::default(),
+            compaction: CompactionConfig::default(),
+            native_tools: Vec::new(),
+            usage: UsageTracker::new(),
+            vision_model: None,
+            think: None,
+            tool_guardrails: None,
+            sandbox_profile: None,
+            system_prompt_template: None,
         }
     }
 }
@@ -139,6 +139,11 @@ pub trait CandleAgentBuilder: Sized + Send + Sync {
     #[must_use]
     fn embedding_model(self, model: TextEmbeddingModel) -> impl CandleAgentBuilder;
 
+    /// Set vision model for turns with image attachments - EXACT syntax: .vision_model(VisionModel)
+    /// Defaults to the first registered vision model
+    #[must_use]
+    fn vision_model(self, model: VisionModel) -> impl CandleAgentBuilder;
+
     /// Set temperature - EXACT syntax: .temperature(1.0)
     #[must_use]
     fn temperature(self, temp: f64) -> impl CandleAgentBuilder;
@@ -155,10 +160,54 @@ pub trait CandleAgentBuilder: Sized + Send + Sync {
     #[must_use]
     fn add_stop_sequence(self, sequence: impl Into<String>) -> impl CandleAgentBuilder;
 
+    /// Configure memory retrieval - EXACT syntax: .rag(RagConfig::default().with_top_k(3))
+    /// Relevant memories are injected into each turn's prompt with provenance markers
+    #[must_use]
+    fn rag(self, config: RagConfig) -> impl CandleAgentBuilder;
+
+    /// Reason before answering - EXACT syntax: .think(ThinkConfig::default().with_beam_width(3))
+    /// Thoughts are scored by the MCP reasoner and the best path is added to the prompt
+    #[must_use]
+    fn think(self, config: ThinkConfig) -> impl CandleAgentBuilder;
+
+    /// Check tool calls before they run - EXACT syntax: .tool_guardrails(ToolGuardrails::new().with_policy(policy))
+    /// Rejected calls are reported to the model as structured tool errors
+    #[must_use]
+    fn tool_guardrails(self, guardrails: ToolGuardrails) -> impl CandleAgentBuilder;
+
+    /// Sandbox every tool under one profile - EXACT syntax: .sandbox_profile(SandboxProfile::read_only_workspace("/repo"))
+    /// Sets the network policy, file system roots and shell restrictions of plugins and code execution together
+    #[must_use]
+    fn sandbox_profile(self, profile: SandboxProfile) -> impl CandleAgentBuilder;
+
+    /// Register a Rust function as a tool - EXACT syntax: .native_tool(NativeTool::new("weather", "Forecast for a city", forecast))
+    /// Offered to the model next to the plugin and MCP tools; arguments are checked against the derived schema
+    #[must_use]
+    fn native_tool(self, tool: NativeTool) -> impl CandleAgentBuilder;
+
+    /// Template the system prompt - EXACT syntax: .system_prompt_template(SystemPromptTemplate::new("Today is {{ date }}."))
+    /// Rendered every turn with the date, tools, retrieved memories and template variables
+    #[must_use]
+    fn system_prompt_template(self, template: SystemPromptTemplate) -> impl CandleAgentBuilder;
+
+    /// Configure memory decay and forgetting - EXACT syntax: .memory_decay(DecayWorkerConfig::default())
+    /// Controls how fast memory importance decays and when stale memories are archived or deleted
+    #[must_use]
+    fn memory_decay(self, config: DecayWorkerConfig) -> impl CandleAgentBuilder;
+
+    /// Summarize old turns near the context limit - EXACT syntax: .compaction(CompactionConfig::default().with_trigger_ratio(0.75))
+    /// Summaries replace the oldest turns in the prompt and are stored in memory
+    #[must_use]
+    fn compaction(self, config: CompactionConfig) -> impl CandleAgentBuilder;
+
     /// Set memory read timeout in milliseconds - EXACT syntax: .memory_read_timeout(5000)
     #[must_use]
     fn memory_read_timeout(self, timeout_ms: u64) -> impl CandleAgentBuilder;
 
+    /// Token usage of this agent's conversation - EXACT syntax: let usage = agent.usage();
+    /// The tracker keeps counting after .chat(...), so read it with .snapshot() at any time
+    fn usage(&self) -> UsageTracker;
+
     /// Set system prompt - EXACT syntax: .system_prompt("...")
     #[must_use]
     fn system_prompt(self, prompt: impl Into<String>) -> impl CandleAgentBuilder;
@@ -244,4 +293,13 @@ pub trait CandleAgentBuilder: Sized + Send + Sync {
         self,
         message: impl Into<String>,
     ) -> Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>>;
+
+    /// Generate a value of type T - EXACT syntax: .generate_typed::<Args>("message").await
+    /// Output is constrained to T's JSON Schema, so it always deserializes
+    fn generate_typed<T>(
+        self,
+        message: impl Into<String>,
+    ) -> impl std::future::Future<Output = Result<T, AgentError>> + Send
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send;
 }
@@ -7,7 +7,9 @@ pub mod registry;
 pub mod traits;
 
 pub mod image_embedding;
+pub mod speech_to_text;
 pub mod text_embedding;
 pub mod text_to_image;
+pub mod text_to_speech;
 pub mod text_to_text;
 pub mod vision;
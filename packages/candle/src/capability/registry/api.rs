@@ -370,6 +370,33 @@ pub fn all_registry_keys() -> Vec<String> {
     keys.into_iter().collect()
 }
 
+/// Get the registry keys of all text-to-text models
+///
+/// Includes runtime-registered models. Keys are sorted so listings are stable.
+///
+/// # Example
+/// ```rust
+/// use cyrup_candle::capability::registry;
+///
+/// for key in registry::text_to_text_registry_keys() {
+///     println!("Chat model: {}", key);
+/// }
+/// ```
+pub fn text_to_text_registry_keys() -> Vec<String> {
+    let mut keys: Vec<String> = TEXT_TO_TEXT_UNIFIED.read().keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+/// Get the registry keys of all vision models
+///
+/// Keys are sorted so listings and the default vision model are stable.
+pub fn vision_registry_keys() -> Vec<String> {
+    let mut keys: Vec<String> = VISION_UNIFIED.read().keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
 /// Check if a registry_key is registered
 ///
 /// Checks all unified registries, including runtime-registered models.
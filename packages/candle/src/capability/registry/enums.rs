@@ -12,7 +12,8 @@ use crate::capability::text_embedding::{
 };
 use crate::capability::text_to_image::{FluxSchnell, StableDiffusion35Turbo};
 use crate::capability::text_to_text::{
-    CandleKimiK2Model, CandlePhi4ReasoningModel, CandleQwen3QuantizedModel,
+    CachedModel, CandleKimiK2Model, CandlePhi4ReasoningModel, CandleQwen3QuantizedModel,
+    ProviderChain,
 };
 use crate::capability::vision::LLaVAModel;
 
@@ -26,6 +27,10 @@ pub enum TextToTextModel {
     KimiK2(Arc<CandleKimiK2Model>),
     Qwen3Quantized(Arc<CandleQwen3QuantizedModel>),
     Phi4Reasoning(Arc<CandlePhi4ReasoningModel>),
+    /// Ordered providers with health-based failover
+    Fallback(Arc<ProviderChain>),
+    /// Replays cached temperature-0 completions
+    Cached(Arc<CachedModel>),
 }
 
 /// Enum for all text embedding models
@@ -78,6 +83,8 @@ impl CandleModel for TextToTextModel {
             Self::KimiK2(m) => m.info(),
             Self::Qwen3Quantized(m) => m.info(),
             Self::Phi4Reasoning(m) => m.info(),
+            Self::Fallback(m) => m.info(),
+            Self::Cached(m) => m.info(),
         }
     }
 }
@@ -123,7 +123,7 @@ pub use enums::{
 pub use api::{
     FromRegistry, all_registry_keys, count_models_by_provider, get, get_by_provider_and_name,
     get_image_embedding, get_model, get_text_embedding, get_text_to_image, get_text_to_text,
-    get_vision, has_model, model_count,
+    get_vision, has_model, model_count, text_to_text_registry_keys, vision_registry_keys,
 };
 
 // Re-export runtime registration functions and types
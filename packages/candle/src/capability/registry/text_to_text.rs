@@ -32,6 +32,8 @@ impl TextToTextCapable for TextToTextModel {
             Self::Phi4Reasoning(m) => {
                 spawn_stream_phi4_reasoning(m.clone(), prompt, params.clone())
             }
+            Self::Fallback(chain) => chain.prompt(prompt, params),
+            Self::Cached(model) => model.prompt(prompt, params),
         }
     }
 }
@@ -76,7 +78,12 @@ macro_rules! impl_text_to_text_spawn {
 
                 let mut stream = pool.prompt(registry_key, prompt, params);
                 use tokio_stream::StreamExt;
-                while let Some(chunk) = stream.next().await {
+                while let Some(mut chunk) = stream.next().await {
+                    if let CandleCompletionChunk::Complete { provider, .. } = &mut chunk
+                        && provider.is_none()
+                    {
+                        *provider = Some(registry_key.to_string());
+                    }
                     if tx.send(chunk).is_err() {
                         break;
                     }
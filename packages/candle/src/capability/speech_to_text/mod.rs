@@ -0,0 +1,19 @@
+//! Speech-to-Text Capability
+//!
+//! Providers that transcribe recorded speech using SpeechToTextCapable trait.
+
+pub mod whisper;
+
+// Re-exports for convenience
+pub use whisper::{CandleWhisperModel, mel_filters};
+
+/// Text recognized in a recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechTranscript {
+    /// Recognized text, empty when nothing was said
+    pub text: String,
+    /// Language of the text (BCP-47), when known
+    pub language: Option<String>,
+    /// Geometric mean of the decoded token probabilities (0.0 to 1.0)
+    pub confidence: Option<f32>,
+}
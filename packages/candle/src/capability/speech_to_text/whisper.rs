@@ -0,0 +1,369 @@
+//! Whisper speech-to-text provider for local inference using Candle ML framework
+//!
+//! Transcribes English speech with openai/whisper-base.en. Weights are
+//! downloaded from HuggingFace on first use and kept loaded afterwards;
+//! decoding is greedy, 30 seconds of audio at a time.
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use candle_core::{D, DType, Device, IndexOp, Tensor};
+use candle_nn::VarBuilder;
+use candle_nn::ops::softmax;
+use candle_transformers::models::whisper::{self, Config, audio, model::Whisper};
+use parking_lot::Mutex;
+use tokenizers::Tokenizer;
+use tokio::sync::OnceCell;
+
+use crate::capability::speech_to_text::SpeechTranscript;
+use crate::capability::traits::{SpeechToTextCapable, TranscriptionFuture};
+use crate::domain::model::CandleModelInfo;
+use crate::domain::model::traits::CandleModel;
+use crate::domain::voice::pcm;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Language of the English-only checkpoint
+const LANGUAGE: &str = "en";
+
+/// Whisper speech-to-text provider using Candle ML framework
+///
+/// Cloning shares the loaded model.
+#[derive(Debug, Clone)]
+pub struct CandleWhisperModel {
+    device: Device,
+    runtime: Arc<OnceCell<Arc<Mutex<WhisperRuntime>>>>,
+}
+
+impl Default for CandleWhisperModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CandleWhisperModel {
+    /// Create a provider on the best available device
+    #[inline]
+    pub fn new() -> Self {
+        let device = crate::core::device_util::detect_best_device().unwrap_or_else(|e| {
+            log::warn!("Device detection failed: {}. Using CPU.", e);
+            Device::Cpu
+        });
+        Self::with_device(device)
+    }
+
+    /// Create a provider that runs on `device`
+    #[inline]
+    pub fn with_device(device: Device) -> Self {
+        Self {
+            device,
+            runtime: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Download and load the model on first use
+    async fn runtime(&self) -> Result<Arc<Mutex<WhisperRuntime>>, BoxError> {
+        self.runtime
+            .get_or_try_init(|| async {
+                let registry_key = self.info().registry_key;
+                let weights_path = self
+                    .huggingface_file(registry_key, "model.safetensors")
+                    .await?;
+                let tokenizer_path = self
+                    .huggingface_file(registry_key, "tokenizer.json")
+                    .await?;
+                let config_path = self.huggingface_file(registry_key, "config.json").await?;
+
+                let config_json = tokio::fs::read_to_string(&config_path)
+                    .await
+                    .map_err(|e| format!("Failed to read config.json: {}", e))?;
+                let config: Config = serde_json::from_str(&config_json)
+                    .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+                let tokenizer = Tokenizer::from_file(&tokenizer_path)
+                    .map_err(|e| format!("Failed to load tokenizer: {}", e))?;
+
+                let device = self.device.clone();
+                let runtime = tokio::task::spawn_blocking(move || {
+                    WhisperRuntime::load(&weights_path, config, tokenizer, device)
+                })
+                .await
+                .map_err(|e| format!("Whisper loading task failed: {}", e))??;
+                Ok::<_, BoxError>(Arc::new(Mutex::new(runtime)))
+            })
+            .await
+            .cloned()
+    }
+}
+
+/// Loaded model and the decoder prompt tokens
+struct WhisperRuntime {
+    model: Whisper,
+    tokenizer: Tokenizer,
+    mel_filters: Vec<f32>,
+    /// Added to the logits; `-inf` for tokens that must never be produced
+    suppress: Tensor,
+    prompt: [u32; 3],
+    eot: u32,
+    device: Device,
+}
+
+impl std::fmt::Debug for WhisperRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WhisperRuntime")
+            .field("device", &self.device)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WhisperRuntime {
+    fn load(
+        weights_path: &std::path::Path,
+        config: Config,
+        tokenizer: Tokenizer,
+        device: Device,
+    ) -> Result<Self, BoxError> {
+        let token = |name: &str| {
+            tokenizer
+                .token_to_id(name)
+                .ok_or_else(|| format!("Tokenizer has no {} token", name))
+        };
+        let sot = token(whisper::SOT_TOKEN)?;
+        let transcribe = token(whisper::TRANSCRIBE_TOKEN)?;
+        let no_timestamps = token(whisper::NO_TIMESTAMPS_TOKEN)?;
+        let eot = token(whisper::EOT_TOKEN)?;
+
+        let suppress: Vec<f32> = (0..config.vocab_size as u32)
+            .map(|id| {
+                if id == no_timestamps || config.suppress_tokens.contains(&id) {
+                    f32::NEG_INFINITY
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let suppress = Tensor::new(suppress.as_slice(), &device)?;
+        let mel_filters = mel_filters(config.num_mel_bins);
+
+        let vb = unsafe {
+            VarBuilder::from_mmaped_safetensors(&[weights_path], DType::F32, &device)
+                .map_err(|e| format!("Failed to load model weights: {}", e))?
+        };
+        let model = Whisper::load(&vb, config)
+            .map_err(|e| format!("Failed to create Whisper model: {}", e))?;
+
+        Ok(Self {
+            model,
+            tokenizer,
+            mel_filters,
+            suppress,
+            prompt: [sot, transcribe, no_timestamps],
+            eot,
+            device,
+        })
+    }
+
+    /// Transcribe 16 kHz mono samples
+    fn transcribe(&mut self, samples: &[f32]) -> Result<SpeechTranscript, BoxError> {
+        let bins = self.model.config.num_mel_bins;
+        let mel = audio::pcm_to_mel(&self.model.config, samples, &self.mel_filters);
+        let frames = mel.len() / bins;
+        let mel = Tensor::from_vec(mel, (1, bins, frames), &self.device)?;
+
+        // The log-mel spectrogram is padded; only frames holding audio are decoded
+        let content_frames = (samples.len() / whisper::HOP_LENGTH).min(frames);
+        let mut text = String::new();
+        let mut logprob_sum = 0.0f64;
+        let mut token_count = 0usize;
+        let mut seek = 0;
+        while seek < content_frames {
+            let segment_frames = (content_frames - seek).min(whisper::N_FRAMES);
+            let segment = mel.narrow(2, seek, segment_frames)?;
+            let (segment_text, segment_logprob, segment_tokens) = self.decode(&segment)?;
+            let segment_text = segment_text.trim();
+            if !segment_text.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(segment_text);
+            }
+            logprob_sum += segment_logprob;
+            token_count += segment_tokens;
+            seek += segment_frames;
+        }
+
+        let confidence = (token_count > 0).then(|| (logprob_sum / token_count as f64).exp() as f32);
+        Ok(SpeechTranscript {
+            text,
+            language: Some(LANGUAGE.to_string()),
+            confidence,
+        })
+    }
+
+    /// Greedily decode one segment of at most 30 seconds
+    ///
+    /// Returns the text, the summed log-probability of the chosen tokens
+    /// (end of text included) and how many were chosen.
+    fn decode(&mut self, mel: &Tensor) -> Result<(String, f64, usize), BoxError> {
+        let features = self.model.encoder.forward(mel, true)?;
+        let max_tokens = self.model.config.max_target_positions / 2;
+
+        let mut tokens = self.prompt.to_vec();
+        let mut logprob_sum = 0.0f64;
+        let mut scored = 0;
+        for step in 0..max_tokens {
+            let input = Tensor::new(tokens.as_slice(), &self.device)?.unsqueeze(0)?;
+            let hidden = self.model.decoder.forward(&input, &features, step == 0)?;
+            let (_, seq_len, _) = hidden.dims3()?;
+            let logits = self
+                .model
+                .decoder
+                .final_linear(&hidden.i((..1, seq_len - 1..))?)?
+                .i(0)?
+                .i(0)?
+                .broadcast_add(&self.suppress)?;
+            let probs = softmax(&logits, D::Minus1)?.to_vec1::<f32>()?;
+
+            let (next, prob) = probs
+                .iter()
+                .copied()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .ok_or("Whisper produced empty logits")?;
+            let next = next as u32;
+            logprob_sum += f64::from(prob.max(f32::MIN_POSITIVE)).ln();
+            scored += 1;
+            if next == self.eot {
+                break;
+            }
+            tokens.push(next);
+        }
+
+        let generated = &tokens[self.prompt.len()..];
+        let text = self
+            .tokenizer
+            .decode(generated, true)
+            .map_err(|e| format!("Failed to decode tokens: {}", e))?;
+        Ok((text, logprob_sum, scored))
+    }
+}
+
+/// Slaney-style mel filterbank matching Whisper's preprocessing
+///
+/// Returns `num_mel_bins` rows of `N_FFT / 2 + 1` weights, row-major, as
+/// expected by [`audio::pcm_to_mel`]. This reproduces
+/// `librosa.filters.mel(sr=16000, n_fft=400, n_mels=num_mel_bins)`.
+#[must_use]
+pub fn mel_filters(num_mel_bins: usize) -> Vec<f32> {
+    const F_SP: f64 = 200.0 / 3.0;
+    const MIN_LOG_HZ: f64 = 1000.0;
+    const MIN_LOG_MEL: f64 = MIN_LOG_HZ / F_SP;
+    let log_step = 6.4f64.ln() / 27.0;
+
+    let hz_to_mel = |hz: f64| {
+        if hz < MIN_LOG_HZ {
+            hz / F_SP
+        } else {
+            MIN_LOG_MEL + (hz / MIN_LOG_HZ).ln() / log_step
+        }
+    };
+    let mel_to_hz = |mel: f64| {
+        if mel < MIN_LOG_MEL {
+            mel * F_SP
+        } else {
+            MIN_LOG_HZ * ((mel - MIN_LOG_MEL) * log_step).exp()
+        }
+    };
+
+    let n_freqs = whisper::N_FFT / 2 + 1;
+    let nyquist = whisper::SAMPLE_RATE as f64 / 2.0;
+    let fft_freqs: Vec<f64> = (0..n_freqs)
+        .map(|i| nyquist * i as f64 / (n_freqs - 1) as f64)
+        .collect();
+
+    let max_mel = hz_to_mel(nyquist);
+    let mel_points: Vec<f64> = (0..num_mel_bins + 2)
+        .map(|i| mel_to_hz(max_mel * i as f64 / (num_mel_bins + 1) as f64))
+        .collect();
+
+    let mut filters = Vec::with_capacity(num_mel_bins * n_freqs);
+    for bin in 0..num_mel_bins {
+        let (lower, center, upper) = (mel_points[bin], mel_points[bin + 1], mel_points[bin + 2]);
+        let norm = 2.0 / (upper - lower);
+        for &freq in &fft_freqs {
+            let rising = (freq - lower) / (center - lower);
+            let falling = (upper - freq) / (upper - center);
+            filters.push((rising.min(falling).max(0.0) * norm) as f32);
+        }
+    }
+    filters
+}
+
+// Static model info for Whisper speech-to-text
+static WHISPER_MODEL_INFO: CandleModelInfo = CandleModelInfo {
+    provider: crate::domain::model::CandleProvider::OpenAI,
+    name: "whisper-base.en",
+    registry_key: "openai/whisper-base.en",
+    quantization_url: None,
+    max_input_tokens: None,
+    max_output_tokens: NonZeroU32::new(224),
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: false,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "whisper-base-en",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: NonZeroU32::new(51864),
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: true,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 600,
+};
+
+impl CandleModel for CandleWhisperModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &WHISPER_MODEL_INFO
+    }
+}
+
+impl SpeechToTextCapable for CandleWhisperModel {
+    fn transcribe(&self, pcm: Vec<f32>, sample_rate: u32) -> TranscriptionFuture<'_> {
+        Box::pin(async move {
+            let samples = pcm::resample(&pcm, sample_rate, self.sample_rate());
+            if samples.is_empty() {
+                return Ok(SpeechTranscript {
+                    text: String::new(),
+                    language: Some(LANGUAGE.to_string()),
+                    confidence: None,
+                });
+            }
+
+            let runtime = self.runtime().await?;
+            tokio::task::spawn_blocking(move || runtime.lock().transcribe(&samples))
+                .await
+                .map_err(|e| format!("Whisper transcription task failed: {}", e))?
+        })
+    }
+
+    fn sample_rate(&self) -> u32 {
+        whisper::SAMPLE_RATE as u32
+    }
+}
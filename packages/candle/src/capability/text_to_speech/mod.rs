@@ -0,0 +1,28 @@
+//! Text-to-Speech Capability
+//!
+//! Providers that synthesize speech using TextToSpeechCapable trait.
+
+pub mod parler;
+
+// Re-exports for convenience
+pub use parler::{CandleParlerTtsModel, DEFAULT_PARLER_VOICE, PARLER_VOICES};
+
+/// Mono audio produced by a speech model
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthesizedSpeech {
+    /// Samples in `[-1, 1]`
+    pub pcm: Vec<f32>,
+    /// Samples per second
+    pub sample_rate: u32,
+}
+
+impl SynthesizedSpeech {
+    /// Playback length of the audio
+    #[must_use]
+    pub fn duration(&self) -> std::time::Duration {
+        if self.sample_rate == 0 {
+            return std::time::Duration::ZERO;
+        }
+        std::time::Duration::from_secs_f64(self.pcm.len() as f64 / f64::from(self.sample_rate))
+    }
+}
@@ -0,0 +1,284 @@
+//! Parler-TTS text-to-speech provider for local inference using Candle ML framework
+//!
+//! Synthesizes speech with parler-tts/parler-tts-mini-v1. Parler is steered
+//! by a natural-language description of the speaker rather than a speaker
+//! embedding, so each voice is one of the named speakers the model was
+//! trained on and the speed and pitch are expressed in that description.
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use candle_core::{DType, Device, IndexOp, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::generation::LogitsProcessor;
+use candle_transformers::models::parler_tts::{Config, Model};
+use parking_lot::Mutex;
+use tokenizers::Tokenizer;
+use tokio::sync::OnceCell;
+
+use crate::capability::text_to_speech::SynthesizedSpeech;
+use crate::capability::traits::{SynthesisFuture, TextToSpeechCapable};
+use crate::domain::model::CandleModelInfo;
+use crate::domain::model::traits::CandleModel;
+use crate::domain::voice::pcm;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Named speakers with consistent voices across generations
+pub const PARLER_VOICES: &[&str] = &["Jon", "Lea", "Gary", "Jenna", "Mike", "Laura"];
+
+/// Voice used when none is requested
+pub const DEFAULT_PARLER_VOICE: &str = "Jon";
+
+/// Audio codec frames generated per second of speech
+const FRAMES_PER_SECOND: usize = 86;
+
+/// Sample rate of the DAC codec decoding the audio
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Upper bound on generated audio, in seconds
+const MAX_SECONDS: usize = 30;
+
+/// Parler-TTS text-to-speech provider using Candle ML framework
+///
+/// Cloning shares the loaded model.
+#[derive(Debug, Clone)]
+pub struct CandleParlerTtsModel {
+    device: Device,
+    runtime: Arc<OnceCell<Arc<Mutex<ParlerRuntime>>>>,
+}
+
+impl Default for CandleParlerTtsModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CandleParlerTtsModel {
+    /// Create a provider on the best available device
+    #[inline]
+    pub fn new() -> Self {
+        let device = crate::core::device_util::detect_best_device().unwrap_or_else(|e| {
+            log::warn!("Device detection failed: {}. Using CPU.", e);
+            Device::Cpu
+        });
+        Self::with_device(device)
+    }
+
+    /// Create a provider that runs on `device`
+    #[inline]
+    pub fn with_device(device: Device) -> Self {
+        Self {
+            device,
+            runtime: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Speaker description for `voice` talking at `speed`, `pitch_semitones`
+    /// off their natural pitch
+    ///
+    /// Parler only knows a few pitch levels, so small shifts are ignored.
+    fn describe(voice: &str, speed: f32, pitch_semitones: f32) -> String {
+        let pace = if speed < 0.85 {
+            "slowly"
+        } else if speed > 1.2 {
+            "quickly"
+        } else {
+            "at a moderate pace"
+        };
+        let pitch = if pitch_semitones <= -4.0 {
+            " in a low-pitched voice"
+        } else if pitch_semitones <= -1.5 {
+            " in a slightly low-pitched voice"
+        } else if pitch_semitones >= 4.0 {
+            " in a high-pitched voice"
+        } else if pitch_semitones >= 1.5 {
+            " in a slightly high-pitched voice"
+        } else {
+            ""
+        };
+        format!(
+            "{voice} speaks {pace}{pitch} with a clear, natural tone. The recording is of very \
+             high quality, with the speaker's voice sounding clear and very close up."
+        )
+    }
+
+    /// Download and load the model on first use
+    async fn runtime(&self) -> Result<Arc<Mutex<ParlerRuntime>>, BoxError> {
+        self.runtime
+            .get_or_try_init(|| async {
+                let registry_key = self.info().registry_key;
+                let weights_path = self
+                    .huggingface_file(registry_key, "model.safetensors")
+                    .await?;
+                let tokenizer_path = self
+                    .huggingface_file(registry_key, "tokenizer.json")
+                    .await?;
+                let config_path = self.huggingface_file(registry_key, "config.json").await?;
+
+                let config_json = tokio::fs::read_to_string(&config_path)
+                    .await
+                    .map_err(|e| format!("Failed to read config.json: {}", e))?;
+                let config: Config = serde_json::from_str(&config_json)
+                    .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+                let tokenizer = Tokenizer::from_file(&tokenizer_path)
+                    .map_err(|e| format!("Failed to load tokenizer: {}", e))?;
+
+                let device = self.device.clone();
+                let runtime = tokio::task::spawn_blocking(move || {
+                    let vb = unsafe {
+                        VarBuilder::from_mmaped_safetensors(&[weights_path], DType::F32, &device)
+                            .map_err(|e| format!("Failed to load model weights: {}", e))?
+                    };
+                    let model = Model::new(&config, vb)
+                        .map_err(|e| format!("Failed to create Parler-TTS model: {}", e))?;
+                    Ok::<_, BoxError>(ParlerRuntime {
+                        model,
+                        tokenizer,
+                        sample_rate: config.audio_encoder.sampling_rate as u32,
+                        device,
+                    })
+                })
+                .await
+                .map_err(|e| format!("Parler-TTS loading task failed: {}", e))??;
+                Ok::<_, BoxError>(Arc::new(Mutex::new(runtime)))
+            })
+            .await
+            .cloned()
+    }
+}
+
+/// Loaded model and tokenizer
+struct ParlerRuntime {
+    model: Model,
+    tokenizer: Tokenizer,
+    sample_rate: u32,
+    device: Device,
+}
+
+impl std::fmt::Debug for ParlerRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParlerRuntime")
+            .field("sample_rate", &self.sample_rate)
+            .field("device", &self.device)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ParlerRuntime {
+    fn tokens(&self, text: &str) -> Result<Tensor, BoxError> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|e| format!("Tokenization failed: {}", e))?;
+        Ok(Tensor::new(encoding.get_ids(), &self.device)?.unsqueeze(0)?)
+    }
+
+    fn synthesize(&mut self, text: &str, description: &str) -> Result<SynthesizedSpeech, BoxError> {
+        let prompt = self.tokens(text)?;
+        let description = self.tokens(description)?;
+
+        // Room for slow speech; generation stops at the end-of-audio token
+        let max_steps =
+            (text.chars().count() * 8 + FRAMES_PER_SECOND).min(MAX_SECONDS * FRAMES_PER_SECOND);
+        let sampler = LogitsProcessor::new(0, None, None);
+        let codes = self
+            .model
+            .generate(&prompt, &description, sampler, max_steps)?
+            .to_dtype(DType::I64)?
+            .unsqueeze(0)?;
+
+        let audio = self.model.audio_encoder.decode_codes(&codes)?;
+        let mut samples = audio.i((0, 0))?.to_dtype(DType::F32)?.to_vec1::<f32>()?;
+        pcm::normalize_peak(&mut samples, 0.9);
+
+        Ok(SynthesizedSpeech {
+            pcm: samples,
+            sample_rate: self.sample_rate,
+        })
+    }
+}
+
+// Static model info for Parler-TTS
+static PARLER_TTS_MODEL_INFO: CandleModelInfo = CandleModelInfo {
+    provider: crate::domain::model::CandleProvider::Community,
+    name: "parler-tts-mini-v1",
+    registry_key: "parler-tts/parler-tts-mini-v1",
+    quantization_url: None,
+    max_input_tokens: None,
+    max_output_tokens: NonZeroU32::new((MAX_SECONDS * FRAMES_PER_SECOND) as u32),
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: false,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "parler-tts-mini-v1",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: true,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 3600,
+};
+
+impl CandleModel for CandleParlerTtsModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &PARLER_TTS_MODEL_INFO
+    }
+}
+
+impl TextToSpeechCapable for CandleParlerTtsModel {
+    fn synthesize(&self, text: &str, voice: Option<&str>, speed: f32) -> SynthesisFuture<'_> {
+        self.synthesize_with_pitch(text, voice, speed, 0.0)
+    }
+
+    fn synthesize_with_pitch(
+        &self,
+        text: &str,
+        voice: Option<&str>,
+        speed: f32,
+        pitch_semitones: f32,
+    ) -> SynthesisFuture<'_> {
+        let text = text.trim().to_string();
+        let voice = voice.unwrap_or(DEFAULT_PARLER_VOICE).to_string();
+        Box::pin(async move {
+            if !PARLER_VOICES.contains(&voice.as_str()) {
+                return Err(format!("Unknown voice: {}", voice).into());
+            }
+            if text.is_empty() {
+                return Ok(SynthesizedSpeech {
+                    pcm: Vec::new(),
+                    sample_rate: SAMPLE_RATE,
+                });
+            }
+
+            let description = Self::describe(&voice, speed, pitch_semitones);
+            let runtime = self.runtime().await?;
+            tokio::task::spawn_blocking(move || runtime.lock().synthesize(&text, &description))
+                .await
+                .map_err(|e| format!("Parler-TTS synthesis task failed: {}", e))?
+        })
+    }
+
+    fn voices(&self) -> Vec<String> {
+        PARLER_VOICES.iter().map(|v| (*v).to_string()).collect()
+    }
+}
@@ -0,0 +1,422 @@
+//! Generation cache for deterministic completions
+//!
+//! With a temperature of zero a model always answers a prompt the same way,
+//! so its completion can be stored and replayed instead of generated again.
+//! A [`CachedModel`] wraps a text-to-text model and does exactly that for
+//! temperature-0 prompts, which makes repeated workflow runs and tests
+//! return in milliseconds. Prompts sampled at any other temperature go
+//! straight to the model.
+//!
+//! Completions live in the agent's `SurrealKV` store, keyed by a SHA-256 of
+//! the model, the prompt and every generation parameter (tools included).
+//! Entries expire after a TTL, and once the cache holds `max_entries` the
+//! oldest entries are evicted. Streams that end in an error or without a
+//! `Complete` chunk are never stored.
+//!
+//! Cache failures are logged and the prompt falls through to the model; the
+//! cache never makes a generation fail.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::capability::registry::TextToTextModel;
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, types::CandleCompletionParams};
+use crate::domain::model::CandleModelInfo;
+use crate::domain::model::traits::CandleModel;
+use crate::domain::prompt::CandlePrompt;
+
+/// Bumped whenever the key derivation or stored format changes
+const CACHE_FORMAT_VERSION: &str = "1";
+
+/// Generation cache errors
+#[derive(Debug, thiserror::Error)]
+pub enum GenerationCacheError {
+    #[error("Generation cache database error: {0}")]
+    Database(String),
+    #[error("Generation cache entry could not be encoded: {0}")]
+    Encoding(String),
+}
+
+/// Generation cache bounds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationCacheConfig {
+    /// How long a stored completion is replayed
+    pub ttl: Duration,
+    /// Most completions kept; the oldest are evicted beyond this
+    pub max_entries: usize,
+    /// Largest encoded completion that is stored
+    pub max_entry_bytes: usize,
+}
+
+impl Default for GenerationCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(7 * 24 * 3600),
+            max_entries: 10_000,
+            max_entry_bytes: 256 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StoredCompletion {
+    chunks: String,
+    expires_at_ms: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct StoredKey {
+    key: String,
+}
+
+/// Completion store backed by `SurrealDB`
+#[derive(Debug, Clone)]
+pub struct GenerationCache {
+    db: Surreal<Any>,
+    config: GenerationCacheConfig,
+}
+
+impl GenerationCache {
+    /// Create a cache over an existing connection (call `initialize` before use)
+    pub fn new(db: Surreal<Any>) -> Self {
+        Self {
+            db,
+            config: GenerationCacheConfig::default(),
+        }
+    }
+
+    /// Open the cache on the shared agent database and define its table
+    ///
+    /// # Errors
+    /// Returns `GenerationCacheError::Database` if the database cannot be
+    /// opened or the schema cannot be defined
+    pub async fn open() -> Result<Self, GenerationCacheError> {
+        let db = crate::domain::init::agent_database()
+            .await
+            .map_err(|e| GenerationCacheError::Database(e.to_string()))?;
+        let cache = Self::new(db);
+        cache.initialize().await?;
+        Ok(cache)
+    }
+
+    /// Replace the TTL and size bounds
+    #[must_use]
+    pub fn with_config(mut self, config: GenerationCacheConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// TTL and size bounds in use
+    pub fn config(&self) -> &GenerationCacheConfig {
+        &self.config
+    }
+
+    /// Define the cache table and indexes
+    ///
+    /// # Errors
+    /// Returns `GenerationCacheError::Database` if the schema cannot be defined
+    pub async fn initialize(&self) -> Result<(), GenerationCacheError> {
+        self.db
+            .query(
+                "
+                DEFINE TABLE IF NOT EXISTS generation_cache SCHEMALESS;
+                DEFINE INDEX IF NOT EXISTS generation_cache_key ON generation_cache FIELDS key UNIQUE;
+                DEFINE INDEX IF NOT EXISTS generation_cache_age ON generation_cache FIELDS created_at_ms;
+                ",
+            )
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to define cache table: {:?}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Stored completion for `key`, if present and not expired
+    ///
+    /// # Errors
+    /// Returns `GenerationCacheError::Database` if the lookup fails, or
+    /// `GenerationCacheError::Encoding` if the entry cannot be decoded
+    pub async fn get(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<CandleCompletionChunk>>, GenerationCacheError> {
+        let mut response = self
+            .db
+            .query("SELECT chunks, expires_at_ms FROM generation_cache WHERE key = $key LIMIT 1")
+            .bind(("key", key.to_string()))
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to read cache entry: {:?}", e))
+            })?;
+
+        let entries: Vec<StoredCompletion> = response.take(0).map_err(|e| {
+            GenerationCacheError::Database(format!("Failed to parse cache entry: {:?}", e))
+        })?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        match entries.into_iter().next() {
+            Some(entry) if entry.expires_at_ms > now => serde_json::from_str(&entry.chunks)
+                .map(Some)
+                .map_err(|e| GenerationCacheError::Encoding(e.to_string())),
+            _ => Ok(None),
+        }
+    }
+
+    /// Store the completion for `key`, evicting expired and excess entries
+    ///
+    /// Completions larger than `max_entry_bytes` are skipped.
+    ///
+    /// # Errors
+    /// Returns `GenerationCacheError::Encoding` if the chunks cannot be
+    /// encoded, or `GenerationCacheError::Database` if they cannot be stored
+    pub async fn put(
+        &self,
+        key: &str,
+        chunks: &[CandleCompletionChunk],
+    ) -> Result<(), GenerationCacheError> {
+        let encoded = serde_json::to_string(chunks)
+            .map_err(|e| GenerationCacheError::Encoding(e.to_string()))?;
+        if encoded.len() > self.config.max_entry_bytes {
+            log::debug!(
+                "Not caching completion of {} bytes (limit {})",
+                encoded.len(),
+                self.config.max_entry_bytes
+            );
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let ttl_ms = i64::try_from(self.config.ttl.as_millis()).unwrap_or(i64::MAX);
+
+        self.db
+            .query(
+                "DELETE generation_cache WHERE key = $key OR expires_at_ms <= $now;
+                CREATE generation_cache CONTENT {
+                    key: $key,
+                    chunks: $chunks,
+                    created_at_ms: $now,
+                    expires_at_ms: $expires_at_ms
+                };",
+            )
+            .bind(("key", key.to_string()))
+            .bind(("chunks", encoded))
+            .bind(("now", now))
+            .bind(("expires_at_ms", now.saturating_add(ttl_ms)))
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to store cache entry: {:?}", e))
+            })?;
+
+        self.evict_excess().await
+    }
+
+    /// Remove every stored completion
+    ///
+    /// # Errors
+    /// Returns `GenerationCacheError::Database` if the entries cannot be removed
+    pub async fn clear(&self) -> Result<(), GenerationCacheError> {
+        self.db
+            .query("DELETE generation_cache")
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to clear cache: {:?}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Drop the oldest entries beyond `max_entries`
+    async fn evict_excess(&self) -> Result<(), GenerationCacheError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT key, created_at_ms FROM generation_cache
+                 ORDER BY created_at_ms DESC START $max_entries",
+            )
+            .bind(("max_entries", self.config.max_entries as i64))
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to find excess entries: {:?}", e))
+            })?;
+
+        let excess: Vec<StoredKey> = response.take(0).map_err(|e| {
+            GenerationCacheError::Database(format!("Failed to parse excess entries: {:?}", e))
+        })?;
+        if excess.is_empty() {
+            return Ok(());
+        }
+
+        let keys: Vec<String> = excess.into_iter().map(|entry| entry.key).collect();
+        self.db
+            .query("DELETE generation_cache WHERE key IN $keys")
+            .bind(("keys", keys))
+            .await
+            .map_err(|e| {
+                GenerationCacheError::Database(format!("Failed to evict entries: {:?}", e))
+            })?;
+        Ok(())
+    }
+}
+
+/// Whether `params` always produce the same completion for a prompt
+pub fn is_deterministic(params: &CandleCompletionParams) -> bool {
+    params.temperature.abs() < f64::EPSILON && params.n.get() == 1
+}
+
+/// Cache key for a prompt to `registry_key` with `params`
+///
+/// Hashes the model, the prompt and the serialized parameters, so any change
+/// to the tools, token limit or provider parameters is a different key.
+pub fn cache_key(
+    registry_key: &str,
+    prompt: &CandlePrompt,
+    params: &CandleCompletionParams,
+) -> String {
+    let mut hasher = Sha256::new();
+    for part in [CACHE_FORMAT_VERSION, registry_key] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update(serde_json::to_vec(prompt).unwrap_or_default());
+    hasher.update([0]);
+    hasher.update(serde_json::to_vec(params).unwrap_or_default());
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Chunks worth storing from a finished stream, or `None` if it must not be cached
+///
+/// Consecutive text chunks are merged, and the timing of the `Complete` chunk
+/// is dropped since a replay takes none of it.
+fn storable(chunks: Vec<CandleCompletionChunk>) -> Option<Vec<CandleCompletionChunk>> {
+    let mut stored: Vec<CandleCompletionChunk> = Vec::with_capacity(chunks.len());
+    let mut completed = false;
+
+    for chunk in chunks {
+        match chunk {
+            CandleCompletionChunk::Error(_) => return None,
+            CandleCompletionChunk::Text(text) => {
+                if let Some(CandleCompletionChunk::Text(previous)) = stored.last_mut() {
+                    previous.push_str(&text);
+                } else {
+                    stored.push(CandleCompletionChunk::Text(text));
+                }
+            }
+            CandleCompletionChunk::Complete {
+                text,
+                finish_reason,
+                usage,
+                token_count,
+                provider,
+                ..
+            } => {
+                completed = true;
+                stored.push(CandleCompletionChunk::Complete {
+                    text,
+                    finish_reason,
+                    usage,
+                    token_count,
+                    elapsed_secs: None,
+                    tokens_per_sec: None,
+                    provider,
+                });
+            }
+            other => stored.push(other),
+        }
+    }
+
+    completed.then_some(stored)
+}
+
+/// Text-to-text model that replays cached temperature-0 completions
+#[derive(Debug, Clone)]
+pub struct CachedModel {
+    model: Arc<dyn TextToTextCapable>,
+    cache: GenerationCache,
+}
+
+impl CachedModel {
+    /// Cache `model`'s deterministic completions in `cache`
+    pub fn new(model: impl TextToTextCapable, cache: GenerationCache) -> Self {
+        Self {
+            model: Arc::new(model),
+            cache,
+        }
+    }
+
+    /// The cache completions are stored in
+    pub fn cache(&self) -> &GenerationCache {
+        &self.cache
+    }
+}
+
+impl From<CachedModel> for TextToTextModel {
+    fn from(model: CachedModel) -> Self {
+        Self::Cached(Arc::new(model))
+    }
+}
+
+impl CandleModel for CachedModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        self.model.info()
+    }
+}
+
+impl TextToTextCapable for CachedModel {
+    fn prompt(
+        &self,
+        prompt: CandlePrompt,
+        params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        if !is_deterministic(params) {
+            return self.model.prompt(prompt, params);
+        }
+
+        let key = cache_key(self.model.info().registry_key, &prompt, params);
+        let model = Arc::clone(&self.model);
+        let cache = self.cache.clone();
+        let params = params.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(move |tx| async move {
+            match cache.get(&key).await {
+                Ok(Some(chunks)) => {
+                    log::debug!("Generation cache hit for {}", key);
+                    for chunk in chunks {
+                        if tx.send(chunk).is_err() {
+                            return;
+                        }
+                    }
+                    return;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Generation cache lookup failed: {}", e),
+            }
+
+            let mut stream = model.prompt(prompt, &params);
+            let mut chunks = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                chunks.push(chunk.clone());
+                if tx.send(chunk).is_err() {
+                    return;
+                }
+            }
+
+            if let Some(chunks) = storable(chunks)
+                && let Err(e) = cache.put(&key, &chunks).await
+            {
+                log::warn!("Generation cache store failed: {}", e);
+            }
+        }))
+    }
+}
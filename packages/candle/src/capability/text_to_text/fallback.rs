@@ -0,0 +1,303 @@
+//! Provider fallback chains
+//!
+//! A [`ProviderChain`] answers each prompt with the first healthy provider of
+//! an ordered list. A provider that fails before producing any output, such
+//! as a model that cannot load for lack of memory or weights, is marked
+//! unhealthy and the prompt is retried on the next provider, so the caller
+//! sees one uninterrupted response. A provider that fails mid-response
+//! cannot be retried transparently: its error is forwarded and the following
+//! prompts of the conversation route around it. Unhealthy providers are
+//! tried again once their cooldown has expired.
+//!
+//! The `provider` of each `Complete` chunk names the provider that answered.
+
+use std::num::NonZeroU64;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::capability::registry::TextToTextModel;
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, types::CandleCompletionParams};
+use crate::domain::model::CandleModelInfo;
+use crate::domain::model::traits::CandleModel;
+use crate::domain::prompt::CandlePrompt;
+
+/// How long an unhealthy provider is skipped before it is tried again
+pub const DEFAULT_PROVIDER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Prompt sent by [`ProviderChain::probe`]
+const PROBE_PROMPT: &str = "ping";
+
+/// Health of one provider as last observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProviderStatus {
+    /// Not used or probed yet
+    #[default]
+    Unknown,
+    /// Last request succeeded
+    Healthy,
+    /// Last request failed
+    Unhealthy,
+}
+
+/// What the chain knows about one provider
+#[derive(Debug, Clone, Default)]
+pub struct ProviderHealth {
+    /// Outcome of the last request or probe
+    pub status: ProviderStatus,
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    /// Error of the last failure
+    pub last_error: Option<String>,
+    /// When the provider was last used or probed
+    pub last_checked: Option<Instant>,
+}
+
+impl ProviderHealth {
+    /// Whether requests may be routed to the provider
+    fn available(&self, cooldown: Duration) -> bool {
+        self.status != ProviderStatus::Unhealthy
+            || self.last_checked.is_none_or(|at| at.elapsed() >= cooldown)
+    }
+
+    fn record_success(&mut self) {
+        self.status = ProviderStatus::Healthy;
+        self.consecutive_failures = 0;
+        self.last_checked = Some(Instant::now());
+    }
+
+    fn record_failure(&mut self, error: &str) {
+        self.status = ProviderStatus::Unhealthy;
+        self.consecutive_failures += 1;
+        self.last_error = Some(error.to_string());
+        self.last_checked = Some(Instant::now());
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ChainProvider {
+    name: String,
+    model: Arc<dyn TextToTextCapable>,
+    health: Arc<Mutex<ProviderHealth>>,
+}
+
+impl ChainProvider {
+    fn new(name: String, model: Arc<dyn TextToTextCapable>) -> Self {
+        Self {
+            name,
+            model,
+            health: Arc::new(Mutex::new(ProviderHealth::default())),
+        }
+    }
+}
+
+/// Ordered text-to-text providers with health-based failover
+///
+/// Clones share provider health, so failover decisions carry across the
+/// turns of a conversation.
+#[derive(Debug, Clone)]
+pub struct ProviderChain {
+    providers: Vec<ChainProvider>,
+    cooldown: Duration,
+}
+
+impl ProviderChain {
+    /// Chain whose primary provider is `model`, named by its registry key
+    pub fn new(model: impl TextToTextCapable) -> Self {
+        let name = model.info().registry_key.to_string();
+        Self::named(name, model)
+    }
+
+    /// Chain whose primary provider is `model`
+    pub fn named(name: impl Into<String>, model: impl TextToTextCapable) -> Self {
+        Self {
+            providers: vec![ChainProvider::new(name.into(), Arc::new(model))],
+            cooldown: DEFAULT_PROVIDER_COOLDOWN,
+        }
+    }
+
+    /// Add a fallback, named by its registry key, after the existing providers
+    #[must_use]
+    pub fn fallback(self, model: impl TextToTextCapable) -> Self {
+        let name = model.info().registry_key.to_string();
+        self.named_fallback(name, model)
+    }
+
+    /// Add a fallback after the existing providers
+    #[must_use]
+    pub fn named_fallback(
+        mut self,
+        name: impl Into<String>,
+        model: impl TextToTextCapable,
+    ) -> Self {
+        self.providers
+            .push(ChainProvider::new(name.into(), Arc::new(model)));
+        self
+    }
+
+    /// How long an unhealthy provider is skipped
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Provider names in fallback order
+    #[must_use]
+    pub fn providers(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    /// Health of every provider in fallback order
+    #[must_use]
+    pub fn health(&self) -> Vec<(String, ProviderHealth)> {
+        self.providers
+            .iter()
+            .map(|p| (p.name.clone(), p.health.lock().clone()))
+            .collect()
+    }
+
+    /// Provider the next prompt is routed to first
+    #[must_use]
+    pub fn active(&self) -> &str {
+        &self.active_provider().name
+    }
+
+    fn active_provider(&self) -> &ChainProvider {
+        self.providers
+            .iter()
+            .find(|p| p.health.lock().available(self.cooldown))
+            .unwrap_or(&self.providers[0])
+    }
+
+    /// Providers in the order a prompt tries them
+    ///
+    /// Available providers come first in chain order. Providers still
+    /// cooling down are kept as a last resort rather than failing outright.
+    fn route(&self) -> Vec<ChainProvider> {
+        let (available, cooling): (Vec<_>, Vec<_>) = self
+            .providers
+            .iter()
+            .cloned()
+            .partition(|p| p.health.lock().available(self.cooldown));
+        available.into_iter().chain(cooling).collect()
+    }
+
+    /// Send a one-token prompt to every provider and record its health
+    ///
+    /// Probing loads each provider's model, so it is meant for startup
+    /// checks and diagnostics rather than routine use.
+    pub async fn probe(&self) -> Vec<(String, ProviderStatus)> {
+        let mut params = CandleCompletionParams::default();
+        params.max_tokens = NonZeroU64::new(1);
+
+        let mut statuses = Vec::with_capacity(self.providers.len());
+        for provider in &self.providers {
+            let mut stream = provider
+                .model
+                .prompt(CandlePrompt::new(PROBE_PROMPT), &params);
+            let mut error = None;
+            while let Some(chunk) = stream.next().await {
+                if let CandleCompletionChunk::Error(e) = chunk {
+                    error = Some(e);
+                    break;
+                }
+            }
+
+            let mut health = provider.health.lock();
+            match error {
+                Some(e) => {
+                    log::warn!("Provider {} failed its health probe: {}", provider.name, e);
+                    health.record_failure(&e);
+                }
+                None => health.record_success(),
+            }
+            statuses.push((provider.name.clone(), health.status));
+        }
+        statuses
+    }
+}
+
+impl From<ProviderChain> for TextToTextModel {
+    fn from(chain: ProviderChain) -> Self {
+        Self::Fallback(Arc::new(chain))
+    }
+}
+
+impl CandleModel for ProviderChain {
+    /// Info of the provider the next prompt is routed to first
+    fn info(&self) -> &'static CandleModelInfo {
+        self.active_provider().model.info()
+    }
+}
+
+impl TextToTextCapable for ProviderChain {
+    fn prompt(
+        &self,
+        prompt: CandlePrompt,
+        params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        let route = self.route();
+        let params = params.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(move |tx| async move {
+            let mut failures = Vec::new();
+
+            for (attempt, provider) in route.iter().enumerate() {
+                let mut stream = provider.model.prompt(prompt.clone(), &params);
+                let mut started = false;
+                let mut error = None;
+
+                while let Some(mut chunk) = stream.next().await {
+                    match &mut chunk {
+                        CandleCompletionChunk::Error(e) => {
+                            error = Some(e.clone());
+                            if !started {
+                                break;
+                            }
+                        }
+                        CandleCompletionChunk::Complete { provider: name, .. } => {
+                            *name = Some(provider.name.clone());
+                        }
+                        _ => started = true,
+                    }
+                    if tx.send(chunk).is_err() {
+                        return;
+                    }
+                }
+
+                match error {
+                    None => {
+                        if attempt > 0 {
+                            log::warn!("Provider {} answered after failover", provider.name);
+                        }
+                        provider.health.lock().record_success();
+                        return;
+                    }
+                    Some(e) => {
+                        provider.health.lock().record_failure(&e);
+                        if started {
+                            log::warn!(
+                                "Provider {} failed mid-response, later prompts fall back: {}",
+                                provider.name,
+                                e
+                            );
+                            return;
+                        }
+                        log::warn!("Provider {} failed, falling back: {}", provider.name, e);
+                        failures.push(format!("{}: {}", provider.name, e));
+                    }
+                }
+            }
+
+            let _ = tx.send(CandleCompletionChunk::Error(format!(
+                "All providers failed: {}",
+                failures.join("; ")
+            )));
+        }))
+    }
+}
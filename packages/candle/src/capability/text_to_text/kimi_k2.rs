@@ -184,6 +184,14 @@ impl crate::capability::traits::TextToTextCapable for CandleKimiK2Model {
             // Format prompt
             let prompt_text = format!("User: {}\nAssistant: ", prompt);
             let max_tokens = params.max_tokens.map(|n| n.get()).unwrap_or(1000);
+            let context_window = crate::core::ContextWindow::from_params(
+                max_context as usize,
+                params.additional_params.as_ref(),
+            )
+            .with_pinned_prefix(1);
+            let structured_output = crate::core::generation::StructuredOutput::from_params(
+                params.additional_params.as_ref(),
+            );
 
             // Convert u64 to u32, capping at u32::MAX if necessary
             let max_tokens_u32 = max_tokens.try_into().unwrap_or_else(|_| {
@@ -289,12 +297,16 @@ impl crate::capability::traits::TextToTextCapable for CandleKimiK2Model {
 
             // Create TextGenerator
             use crate::core::generation::generator::TextGenerator;
-            let text_generator = TextGenerator::new(
+            let mut text_generator = TextGenerator::new(
                 Box::new(quantized_model),
                 tokenizer,
                 device,
                 sampling_config,
-            );
+            )
+            .with_context_window(context_window);
+            if let Some(output) = structured_output {
+                text_generator = text_generator.with_structured_output(output);
+            }
 
             // Set up special tokens
             use crate::core::generation::tokens::SpecialTokens;
@@ -580,6 +592,16 @@ impl crate::capability::traits::TextToTextCapable for LoadedKimiK2Model {
         // Format prompt text
         let prompt_text = format!("User: {}\nAssistant: ", prompt);
         let max_tokens = params.max_tokens.map(|n| n.get()).unwrap_or(1000);
+        let context_window = crate::core::ContextWindow::from_params(
+            KIMI_K2_MODEL_INFO
+                .max_input_tokens
+                .map_or(131072, |t| t.get() as usize),
+            params.additional_params.as_ref(),
+        )
+        .with_pinned_prefix(1);
+        let structured_output = crate::core::generation::StructuredOutput::from_params(
+            params.additional_params.as_ref(),
+        );
 
         // Use Engine's coordinate_generation for automatic metrics and stream conversion
         Box::pin(engine.coordinate_generation(move || {
@@ -616,7 +638,7 @@ impl crate::capability::traits::TextToTextCapable for LoadedKimiK2Model {
 
                 // Create TextGenerator with CACHED model and pre-loaded tokenizer
                 // Use SharedKimiModel wrapper to share the Arc<Mutex<Model>> across generate() calls
-                let text_generator = TextGenerator::new(
+                let mut text_generator = TextGenerator::new(
                     Box::new(SharedKimiModel {
                         model: model.clone(),
                         device: device.clone(),
@@ -625,7 +647,11 @@ impl crate::capability::traits::TextToTextCapable for LoadedKimiK2Model {
                     tokenizer, // ✅ Use pre-loaded tokenizer (no disk I/O)
                     device,
                     sampling_config,
-                );
+                )
+                .with_context_window(context_window);
+                if let Some(output) = structured_output {
+                    text_generator = text_generator.with_structured_output(output);
+                }
 
                 // Set up special tokens for Kimi K2
                 let special_tokens = SpecialTokens {
@@ -2,11 +2,15 @@
 //!
 //! Models capable of generating text completions from text prompts.
 
+pub mod cache;
+pub mod fallback;
 pub mod kimi_k2;
 pub mod phi4_reasoning;
 pub mod qwen3_quantized;
 
 // Re-exports for convenience
+pub use cache::{CachedModel, GenerationCache, GenerationCacheConfig, GenerationCacheError};
+pub use fallback::{DEFAULT_PROVIDER_COOLDOWN, ProviderChain, ProviderHealth, ProviderStatus};
 pub(crate) use kimi_k2::CandleKimiK2Model;
 pub(crate) use phi4_reasoning::CandlePhi4ReasoningModel;
 pub(crate) use qwen3_quantized::CandleQwen3QuantizedModel;
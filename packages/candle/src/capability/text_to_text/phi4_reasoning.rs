@@ -153,6 +153,15 @@ impl crate::capability::traits::TextToTextCapable for CandlePhi4ReasoningModel {
             }
         };
         let max_tokens = params.max_tokens.map(|n| n.get()).unwrap_or(2000);
+        let context_window = crate::core::ContextWindow::from_params(
+            PHI4_REASONING_MODEL_INFO
+                .max_input_tokens
+                .map_or(32768, |t| t.get() as usize),
+            params.additional_params.as_ref(),
+        );
+        let structured_output = crate::core::generation::StructuredOutput::from_params(
+            params.additional_params.as_ref(),
+        );
 
         // Use Engine's coordinate_generation for automatic metrics and stream conversion
         Box::pin(engine.coordinate_generation(move || {
@@ -272,12 +281,16 @@ impl crate::capability::traits::TextToTextCapable for CandlePhi4ReasoningModel {
                     .with_presence_penalty(0.0);
 
                 // Create TextGenerator with real model
-                let text_generator = TextGenerator::new(
+                let mut text_generator = TextGenerator::new(
                     Box::new(quantized_model),
                     tokenizer,
                     device,
                     sampling_config,
-                );
+                )
+                .with_context_window(context_window);
+                if let Some(output) = structured_output {
+                    text_generator = text_generator.with_structured_output(output);
+                }
 
                 // Set up special tokens for Phi-4
                 let special_tokens = SpecialTokens {
@@ -519,6 +532,15 @@ impl crate::capability::traits::TextToTextCapable for LoadedPhi4ReasoningModel {
             }
         };
         let max_tokens = params.max_tokens.map(|n| n.get()).unwrap_or(2000);
+        let context_window = crate::core::ContextWindow::from_params(
+            self.info()
+                .max_input_tokens
+                .map_or(32768, |t| t.get() as usize),
+            params.additional_params.as_ref(),
+        );
+        let structured_output = crate::core::generation::StructuredOutput::from_params(
+            params.additional_params.as_ref(),
+        );
 
         // Use Engine's coordinate_generation for automatic metrics and stream conversion
         Box::pin(engine.coordinate_generation(move || {
@@ -569,7 +591,7 @@ impl crate::capability::traits::TextToTextCapable for LoadedPhi4ReasoningModel {
 
                 // Create TextGenerator with CACHED model and pre-loaded tokenizer
                 // Use SharedModel wrapper to share the Arc<Mutex<Model>> across generate() calls
-                let text_generator = TextGenerator::new(
+                let mut text_generator = TextGenerator::new(
                     Box::new(SharedPhiModel {
                         model: model.clone(),
                         device: device.clone(),
@@ -578,7 +600,11 @@ impl crate::capability::traits::TextToTextCapable for LoadedPhi4ReasoningModel {
                     tokenizer, // ✅ Use pre-loaded tokenizer (no disk I/O)
                     device,
                     sampling_config,
-                );
+                )
+                .with_context_window(context_window);
+                if let Some(output) = structured_output {
+                    text_generator = text_generator.with_structured_output(output);
+                }
                 let special_tokens = SpecialTokens {
                     bos_token_id: None, // Phi doesn't use BOS
                     eos_token_id: eos_token_id_final,
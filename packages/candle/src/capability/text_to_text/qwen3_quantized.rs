@@ -9,17 +9,19 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 use crate::async_stream;
-use crate::core::generation::TokenOutputStream;
+use crate::core::generation::{
+    CandleResult, OutputConstraint, StructuredOutput, TokenOutputStream,
+};
 use candle_core::quantized::gguf_file;
-use candle_core::{Device, Tensor};
+use candle_core::{DType, Device, Tensor};
 use candle_transformers::generation::{LogitsProcessor, Sampling};
 use candle_transformers::models::quantized_qwen3::ModelWeights as Qwen3Model;
 use tokio_stream::Stream;
 
-use crate::core::{Engine, EngineConfig};
+use crate::core::{ContextOverflowPolicy, ContextWindow, Engine, EngineConfig};
 
 use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
-use crate::domain::context::CandleStringChunk;
+use crate::domain::context::{CandleStringChunk, GenerationStats};
 use crate::domain::model::{info::CandleModelInfo, traits::CandleModel};
 use crate::domain::prompt::CandlePrompt;
 
@@ -55,7 +57,8 @@ impl CandleQwen3QuantizedModel {
         let engine_config = EngineConfig::new("qwen3-quantized", "candle-qwen")
             .with_streaming()
             .with_max_tokens(32768) // From QWEN3_QUANTIZED_MODEL_INFO
-            .with_temperature(0.0); // Greedy sampling for deterministic output
+            .with_temperature(0.0) // Greedy sampling for deterministic output
+            .with_context_window(ContextWindow::new(32768, ContextOverflowPolicy::default()));
 
         let engine = Arc::new(Engine::new(engine_config)?);
 
@@ -258,6 +261,9 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
             prompt.content
         );
         let max_tokens = params.max_tokens.map(|n| n.get()).unwrap_or(1000);
+        let overflow_policy = ContextOverflowPolicy::from_params(params.additional_params.as_ref());
+        let structured_output = StructuredOutput::from_params(params.additional_params.as_ref());
+        let window_engine = Arc::clone(&engine);
 
         // Use Engine's coordinate_generation for automatic metrics and stream conversion
         Box::pin(engine.coordinate_generation(move || {
@@ -276,6 +282,34 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                     }
                 };
 
+                // Fit the prompt into the context window
+                let fitted =
+                    match window_engine.fit_prompt(tokens, max_tokens as usize, overflow_policy) {
+                        Ok(fitted) => fitted,
+                        Err(e) => {
+                            let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                            return;
+                        }
+                    };
+                let tokens = fitted.tokens;
+                let max_tokens = fitted.max_new_tokens as u32;
+                let start_time = std::time::Instant::now();
+
+                // Compile the requested schema or grammar against the vocabulary
+                let mut output_constraint = match structured_output
+                    .as_ref()
+                    .map(|output| OutputConstraint::new(output, &tokenizer))
+                    .transpose()
+                {
+                    Ok(constraint) => {
+                        constraint.map(|constraint| constraint.with_eos_token(Some(eos_token_id)))
+                    }
+                    Err(e) => {
+                        let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                        return;
+                    }
+                };
+
                 // Create LogitsProcessor for sampling
                 let seed = 299792458;
                 let mut logits_processor = {
@@ -345,6 +379,15 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                     }
                 };
 
+                // Mask tokens outside the structured output before any scaling
+                let logits = match constrain_logits(logits, output_constraint.as_ref()) {
+                    Ok(l) => l,
+                    Err(e) => {
+                        let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                        return;
+                    }
+                };
+
                 // Apply temperature scaling
                 let logits = if temperature != 1.0 {
                     match logits / temperature {
@@ -394,6 +437,7 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                 };
 
                 all_tokens.push(next_token);
+                advance_constraint(output_constraint.as_mut(), next_token);
 
                 // Send first token
                 if let Some(t) = tos.next_token(next_token).ok().flatten() {
@@ -402,7 +446,11 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
 
                 // Continue generation
                 for index in 0..max_tokens {
-                    if next_token == eos_token_id {
+                    if next_token == eos_token_id
+                        || output_constraint
+                            .as_ref()
+                            .is_some_and(OutputConstraint::is_complete)
+                    {
                         break;
                     }
 
@@ -448,6 +496,15 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                         }
                     };
 
+                    // Mask tokens outside the structured output before any scaling
+                    let logits = match constrain_logits(logits, output_constraint.as_ref()) {
+                        Ok(l) => l,
+                        Err(e) => {
+                            let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                            return;
+                        }
+                    };
+
                     // Apply temperature scaling
                     let logits = if temperature != 1.0 {
                         match logits / temperature {
@@ -497,6 +554,7 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                     };
 
                     all_tokens.push(next_token);
+                    advance_constraint(output_constraint.as_mut(), next_token);
 
                     // Send token through stream using TokenOutputStream
                     if let Some(t) = tos.next_token(next_token).ok().flatten() {
@@ -510,6 +568,21 @@ impl crate::capability::traits::TextToTextCapable for LoadedQwen3QuantizedModel
                 {
                     let _ = tx.send(CandleStringChunk::text(t));
                 }
+
+                let tokens_generated = (all_tokens.len() - tokens.len()) as u32;
+                let elapsed_secs = start_time.elapsed().as_secs_f64();
+                let _ = tx.send(CandleStringChunk::final_with_stats(GenerationStats {
+                    tokens_generated,
+                    input_tokens: fitted.usage.prompt_tokens as u32,
+                    evicted_tokens: fitted.usage.evicted_tokens as u32,
+                    elapsed_secs,
+                    tokens_per_sec: if elapsed_secs > 0.0 {
+                        f64::from(tokens_generated) / elapsed_secs
+                    } else {
+                        0.0
+                    },
+                    draft_acceptance_rate: None,
+                }));
             })
         }))
     }
@@ -537,3 +610,22 @@ impl Default for CandleQwen3QuantizedModel {
         Self::new().unwrap_or_else(|e| panic!("Failed to initialize Qwen3 Quantized model: {}", e))
     }
 }
+
+/// Mask logits outside the structured output, if one was requested
+fn constrain_logits(logits: Tensor, constraint: Option<&OutputConstraint>) -> CandleResult<Tensor> {
+    let Some(constraint) = constraint else {
+        return Ok(logits);
+    };
+    let mut values = logits.to_dtype(DType::F32)?.to_vec1::<f32>()?;
+    constraint.mask_logits(&mut values)?;
+    Ok(Tensor::new(values, logits.device())?)
+}
+
+/// Record a sampled token against the structured output
+fn advance_constraint(constraint: Option<&mut OutputConstraint>, token: u32) {
+    if let Some(constraint) = constraint
+        && !constraint.advance(token)
+    {
+        log::warn!("Token {} breaks the structured output", token);
+    }
+}
@@ -11,6 +11,8 @@
 
 use std::pin::Pin;
 
+use crate::capability::speech_to_text::SpeechTranscript;
+use crate::capability::text_to_speech::SynthesizedSpeech;
 use crate::domain::completion::CandleCompletionChunk;
 use crate::domain::completion::types::CandleCompletionParams;
 use crate::domain::context::chunks::CandleStringChunk;
@@ -43,6 +45,32 @@ pub type BatchEmbeddingFuture<'a> = Pin<
     >,
 >;
 
+/// Type alias for speech transcription future
+pub type TranscriptionFuture<'a> = Pin<
+    Box<
+        dyn std::future::Future<
+                Output = std::result::Result<
+                    SpeechTranscript,
+                    Box<dyn std::error::Error + Send + Sync>,
+                >,
+            > + Send
+            + 'a,
+    >,
+>;
+
+/// Type alias for speech synthesis future
+pub type SynthesisFuture<'a> = Pin<
+    Box<
+        dyn std::future::Future<
+                Output = std::result::Result<
+                    SynthesizedSpeech,
+                    Box<dyn std::error::Error + Send + Sync>,
+                >,
+            > + Send
+            + 'a,
+    >,
+>;
+
 /// Trait for models capable of text-to-text generation
 pub trait TextToTextCapable: CandleModel {
     /// Generate completion from prompt - the actual work method
@@ -257,3 +285,40 @@ pub trait TextToImageCapable: CandleModel {
         50
     }
 }
+
+/// Trait for models capable of speech-to-text transcription
+pub trait SpeechToTextCapable: CandleModel {
+    /// Transcribe mono PCM samples recorded at `sample_rate`
+    fn transcribe(&self, pcm: Vec<f32>, sample_rate: u32) -> TranscriptionFuture<'_>;
+
+    /// Sample rate the model consumes; other rates are resampled
+    fn sample_rate(&self) -> u32 {
+        16_000
+    }
+}
+
+/// Trait for models capable of text-to-speech synthesis
+pub trait TextToSpeechCapable: CandleModel {
+    /// Synthesize `text` with the named voice (model default when `None`)
+    ///
+    /// `speed` is a rate multiplier where 1.0 is the voice's natural pace.
+    fn synthesize(&self, text: &str, voice: Option<&str>, speed: f32) -> SynthesisFuture<'_>;
+
+    /// Synthesize like [`TextToSpeechCapable::synthesize`] with the voice's
+    /// pitch shifted by `pitch_semitones`
+    ///
+    /// Providers without pitch control speak at the natural pitch.
+    fn synthesize_with_pitch(
+        &self,
+        text: &str,
+        voice: Option<&str>,
+        speed: f32,
+        pitch_semitones: f32,
+    ) -> SynthesisFuture<'_> {
+        let _ = pitch_semitones;
+        self.synthesize(text, voice, speed)
+    }
+
+    /// Names of the voices accepted by [`TextToSpeechCapable::synthesize`]
+    fn voices(&self) -> Vec<String>;
+}
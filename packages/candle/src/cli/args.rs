@@ -4,12 +4,89 @@
 
 use std::path::PathBuf;
 
+use crate::core::context_window::ContextOverflowPolicy;
+use crate::core::device_util::{DeviceConfig, DeviceSpec};
+use crate::extensions::bridge::ExtensionHost;
+
+/// Top-level command selected on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CliCommand {
+    /// Interactive or one-shot chat (default)
+    #[default]
+    Chat,
+    /// OpenAI-compatible HTTP server (`serve`)
+    Serve,
+    /// Print detected accelerators and the planned layer placement (`devices`)
+    Devices,
+    /// Run a JSONL file of prompts non-interactively (`batch`)
+    Batch,
+    /// Score models against an eval suite (`eval`)
+    Eval,
+    /// Answer a Raycast or Alfred query, or write the extension bundle (`extension`)
+    Extension,
+    /// Manage scheduled workflows, or run them in the foreground (`schedule`)
+    Schedule,
+}
+
+/// What `schedule` does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScheduleAction {
+    /// List schedules with their next run (default)
+    #[default]
+    List,
+    /// Register a schedule from --name, --message and one of --cron, --every,
+    /// --webhook or --on-notification
+    Add,
+    /// Delete a schedule and its run history
+    Remove,
+    /// Resume a paused schedule
+    Enable,
+    /// Pause a schedule without deleting it
+    Disable,
+    /// Run a schedule once now
+    Run,
+    /// Show recent runs, of one schedule or of all
+    Runs,
+    /// Run due schedules and listen for triggers until interrupted
+    Daemon,
+}
+
+impl std::str::FromStr for ScheduleAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "list" => Ok(Self::List),
+            "add" => Ok(Self::Add),
+            "remove" => Ok(Self::Remove),
+            "enable" => Ok(Self::Enable),
+            "disable" => Ok(Self::Disable),
+            "run" => Ok(Self::Run),
+            "runs" => Ok(Self::Runs),
+            "daemon" => Ok(Self::Daemon),
+            other => Err(format!("Unknown schedule action: {}", other)),
+        }
+    }
+}
+
 /// CLI arguments for the chat application
 #[derive(Debug, Clone)]
 pub struct CliArgs {
+    /// Command to run (defaults to chat)
+    pub command: CliCommand,
+
     /// Model to use for inference (optional - will prompt if not provided)
     pub model: Option<String>,
 
+    /// Models to fall back to, in order, when the model fails (repeatable)
+    pub fallback_models: Vec<String>,
+
+    /// Device to run models on: auto, cpu, metal[:N] or cuda[:N]
+    pub device: Option<String>,
+
+    /// Devices to shard large checkpoints across, layer-wise in order
+    pub shard_devices: Vec<String>,
+
     /// Agent role name (defaults to "CYRUP.ai")
     pub agent_role: String,
 
@@ -44,14 +121,87 @@ pub struct CliArgs {
     /// Config file path
     pub config: Option<PathBuf>,
 
+    /// Stored session to continue (id or unique id prefix)
+    pub resume: Option<String>,
+
+    /// What to do when a resumed conversation exceeds the context window
+    pub context_overflow: ContextOverflowPolicy,
+
+    /// Voice mode: speak turns into the microphone and hear the replies
+    pub voice: bool,
+
     /// Verbose logging
     pub verbose: bool,
+
+    /// Host for `serve` to bind (defaults to 127.0.0.1)
+    pub host: String,
+
+    /// Port for `serve` to listen on (defaults to 8080)
+    pub port: u16,
+
+    /// JSONL file of prompts for `batch`
+    pub input: Option<PathBuf>,
+
+    /// JSONL file `batch` appends results to and resumes from
+    pub output: Option<PathBuf>,
+
+    /// Prompts `batch` runs at once (defaults to 4)
+    pub concurrency: usize,
+
+    /// Eval suite file (TOML or JSON) for `eval`
+    pub suite: Option<PathBuf>,
+
+    /// Further models `eval` scores alongside --model (repeatable)
+    pub compare_models: Vec<String>,
+
+    /// Model that grades `judge` cases in `eval`
+    pub judge_model: Option<String>,
+
+    /// Launcher `extension` answers for: raycast or alfred (defaults to raycast)
+    pub extension_host: ExtensionHost,
+
+    /// Images attached to the `extension` query (repeatable)
+    pub images: Vec<String>,
+
+    /// Directory `extension` writes the launcher's extension bundle to
+    pub bundle: Option<PathBuf>,
+
+    /// What `schedule` does (defaults to list)
+    pub schedule_action: ScheduleAction,
+
+    /// Schedule name or id `schedule` acts on
+    pub schedule_target: Option<String>,
+
+    /// Name of the schedule `schedule add` registers
+    pub schedule_name: Option<String>,
+
+    /// Cron expression for `schedule add`
+    pub cron: Option<String>,
+
+    /// Interval for `schedule add`, e.g. 15m
+    pub every: Option<String>,
+
+    /// Webhook path that triggers the schedule `schedule add` registers
+    pub webhook: Option<String>,
+
+    /// Secret a webhook must present in its X-Webhook-Secret header
+    pub webhook_secret: Option<String>,
+
+    /// MCP notification method that triggers the schedule `schedule add` registers
+    pub on_notification: Option<String>,
+
+    /// Stdio MCP server command (with arguments) whose notifications trigger the schedule
+    pub mcp_server: Option<String>,
 }
 
 impl Default for CliArgs {
     fn default() -> Self {
         Self {
+            command: CliCommand::Chat,
             model: None,
+            fallback_models: Vec::new(),
+            device: None,
+            shard_devices: Vec::new(),
             agent_role: "CYRUP.ai".to_string(),
             system_prompt: None,
             documents: Vec::new(),
@@ -63,7 +213,30 @@ impl Default for CliArgs {
             interactive: true,
             message: None,
             config: None,
+            resume: None,
+            context_overflow: ContextOverflowPolicy::default(),
+            voice: false,
             verbose: false,
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            input: None,
+            output: None,
+            concurrency: crate::batch::DEFAULT_BATCH_CONCURRENCY,
+            suite: None,
+            compare_models: Vec::new(),
+            judge_model: None,
+            extension_host: ExtensionHost::default(),
+            images: Vec::new(),
+            bundle: None,
+            schedule_action: ScheduleAction::default(),
+            schedule_target: None,
+            schedule_name: None,
+            cron: None,
+            every: None,
+            webhook: None,
+            webhook_secret: None,
+            on_notification: None,
+            mcp_server: None,
         }
     }
 }
@@ -87,12 +260,176 @@ impl CliArgs {
 
         while i < args.len() {
             match args[i].as_str() {
+                "serve" if i == 1 => {
+                    cli_args.command = CliCommand::Serve;
+                    cli_args.interactive = false;
+                }
+                "devices" if i == 1 => {
+                    cli_args.command = CliCommand::Devices;
+                    cli_args.interactive = false;
+                }
+                "batch" if i == 1 => {
+                    cli_args.command = CliCommand::Batch;
+                    cli_args.interactive = false;
+                }
+                "eval" if i == 1 => {
+                    cli_args.command = CliCommand::Eval;
+                    cli_args.interactive = false;
+                }
+                "extension" if i == 1 => {
+                    cli_args.command = CliCommand::Extension;
+                    cli_args.interactive = false;
+                }
+                "schedule" if i == 1 => {
+                    cli_args.command = CliCommand::Schedule;
+                    cli_args.interactive = false;
+                }
+                action
+                    if i == 2
+                        && cli_args.command == CliCommand::Schedule
+                        && action.parse::<ScheduleAction>().is_ok() =>
+                {
+                    if let Ok(action) = action.parse::<ScheduleAction>() {
+                        cli_args.schedule_action = action;
+                    }
+                }
+                target
+                    if i == 3
+                        && cli_args.command == CliCommand::Schedule
+                        && !target.starts_with('-') =>
+                {
+                    cli_args.schedule_target = Some(target.to_string());
+                }
+                "--name" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.schedule_name = Some(args[i].clone());
+                    }
+                }
+                "--cron" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.cron = Some(args[i].clone());
+                    }
+                }
+                "--every" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.every = Some(args[i].clone());
+                    }
+                }
+                "--webhook" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.webhook = Some(args[i].clone());
+                    }
+                }
+                "--webhook-secret" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.webhook_secret = Some(args[i].clone());
+                    }
+                }
+                "--on-notification" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.on_notification = Some(args[i].clone());
+                    }
+                }
+                "--mcp-server" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.mcp_server = Some(args[i].clone());
+                    }
+                }
+                "--extension-host" => {
+                    i += 1;
+                    if i < args.len()
+                        && let Ok(host) = args[i].parse::<ExtensionHost>()
+                    {
+                        cli_args.extension_host = host;
+                    }
+                }
+                "--image" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.images.push(args[i].clone());
+                    }
+                }
+                "--bundle" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.bundle = Some(PathBuf::from(&args[i]));
+                    }
+                }
+                "--suite" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.suite = Some(PathBuf::from(&args[i]));
+                    }
+                }
+                "--compare-model" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.compare_models.push(args[i].clone());
+                    }
+                }
+                "--judge-model" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.judge_model = Some(args[i].clone());
+                    }
+                }
+                "--input" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.input = Some(PathBuf::from(&args[i]));
+                    }
+                }
+                "--output" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.output = Some(PathBuf::from(&args[i]));
+                    }
+                }
+                "--concurrency" => {
+                    i += 1;
+                    if i < args.len()
+                        && let Ok(concurrency) = args[i].parse::<usize>()
+                    {
+                        cli_args.concurrency = concurrency;
+                    }
+                }
                 "-m" | "--model" => {
                     i += 1;
                     if i < args.len() {
                         cli_args.model = Some(args[i].clone());
                     }
                 }
+                "--fallback-model" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.fallback_models.push(args[i].clone());
+                    }
+                }
+                "--device" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.device = Some(args[i].clone());
+                    }
+                }
+                "--shard-devices" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.shard_devices.extend(
+                            args[i]
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|device| !device.is_empty())
+                                .map(str::to_string),
+                        );
+                    }
+                }
                 "-r" | "--role" => {
                     i += 1;
                     if i < args.len() {
@@ -160,6 +497,37 @@ impl CliArgs {
                         cli_args.config = Some(PathBuf::from(&args[i]));
                     }
                 }
+                "--host" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.host = args[i].clone();
+                    }
+                }
+                "--port" => {
+                    i += 1;
+                    if i < args.len()
+                        && let Ok(port) = args[i].parse::<u16>()
+                    {
+                        cli_args.port = port;
+                    }
+                }
+                "--resume" => {
+                    i += 1;
+                    if i < args.len() {
+                        cli_args.resume = Some(args[i].clone());
+                    }
+                }
+                "--context-overflow" => {
+                    i += 1;
+                    if i < args.len()
+                        && let Ok(policy) = args[i].parse::<ContextOverflowPolicy>()
+                    {
+                        cli_args.context_overflow = policy;
+                    }
+                }
+                "--voice" => {
+                    cli_args.voice = true;
+                }
                 "-v" | "--verbose" => {
                     cli_args.verbose = true;
                 }
@@ -198,6 +566,107 @@ impl CliArgs {
             return Err("Memory read timeout must be greater than 0".to_string());
         }
 
+        if !self.fallback_models.is_empty() && self.model.is_none() {
+            return Err("Fallback models require --model".to_string());
+        }
+
+        self.device_config()?;
+
+        if self.command == CliCommand::Batch {
+            if self.model.is_none() {
+                return Err("Batch mode requires --model".to_string());
+            }
+            if self.input.is_none() || self.output.is_none() {
+                return Err("Batch mode requires --input and --output".to_string());
+            }
+            if self.concurrency == 0 {
+                return Err("Concurrency must be greater than 0".to_string());
+            }
+        }
+
+        if self.command == CliCommand::Eval {
+            if self.model.is_none() {
+                return Err("Eval mode requires --model".to_string());
+            }
+            if self.suite.is_none() {
+                return Err("Eval mode requires --suite".to_string());
+            }
+        }
+
+        if self.command == CliCommand::Extension && self.message.is_none() && self.bundle.is_none()
+        {
+            return Err("Extension mode requires --message or --bundle".to_string());
+        }
+
+        if self.command == CliCommand::Schedule {
+            match self.schedule_action {
+                ScheduleAction::Add => {
+                    if self.schedule_name.is_none() || self.message.is_none() {
+                        return Err("schedule add requires --name and --message".to_string());
+                    }
+                    let triggers = [
+                        self.cron.is_some(),
+                        self.every.is_some(),
+                        self.webhook.is_some(),
+                        self.on_notification.is_some(),
+                    ];
+                    if triggers.iter().filter(|set| **set).count() != 1 {
+                        return Err(
+                            "schedule add requires one of --cron, --every, --webhook or --on-notification"
+                                .to_string(),
+                        );
+                    }
+                    if self.on_notification.is_some() && self.mcp_server.is_none() {
+                        return Err("--on-notification requires --mcp-server".to_string());
+                    }
+                }
+                ScheduleAction::Remove
+                | ScheduleAction::Enable
+                | ScheduleAction::Disable
+                | ScheduleAction::Run => {
+                    if self.schedule_target.is_none() {
+                        return Err(
+                            "This schedule action requires a schedule name or id".to_string()
+                        );
+                    }
+                }
+                ScheduleAction::List | ScheduleAction::Runs | ScheduleAction::Daemon => {}
+            }
+        }
+
+        if self.voice && !self.interactive {
+            return Err("Voice mode requires an interactive session".to_string());
+        }
+
         Ok(())
     }
+
+    /// Device configuration selected by `--device` and `--shard-devices`
+    pub fn device_config(&self) -> Result<DeviceConfig, String> {
+        let device = match &self.device {
+            Some(device) => device.parse::<DeviceSpec>()?,
+            None => DeviceSpec::Auto,
+        };
+        let shard_devices = self
+            .shard_devices
+            .iter()
+            .map(|device| device.parse::<DeviceSpec>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if shard_devices.contains(&DeviceSpec::Auto) {
+            return Err("Shard devices must be named explicitly, not auto".to_string());
+        }
+        if let Some(duplicate) = shard_devices
+            .iter()
+            .enumerate()
+            .find_map(|(i, device)| shard_devices[..i].contains(device).then_some(device))
+        {
+            return Err(format!("Shard device {} is listed twice", duplicate));
+        }
+
+        Ok(DeviceConfig {
+            device,
+            shard_devices,
+        })
+    }
 }
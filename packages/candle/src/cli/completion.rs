@@ -21,6 +21,8 @@ pub const CHAT_COMMANDS: &[&str] = &[
     "/history",
     "/export",
     "/import",
+    "/sessions",
+    "/image",
 ];
 
 /// Model completer with fuzzy matching
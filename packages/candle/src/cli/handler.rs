@@ -5,7 +5,7 @@
 
 use super::completion::CommandCompleter;
 use super::config::CliConfig;
-use crate::domain::chat::CandleChatLoop;
+use crate::domain::chat::{CandleChatLoop, CandleImageAttachment};
 use std::fs;
 use std::path::Path;
 
@@ -15,6 +15,9 @@ pub enum InputHandlerResult {
     /// Continue with chat message
     Chat(String),
 
+    /// Continue with chat message and attached images
+    ChatWithImages(String, Vec<CandleImageAttachment>),
+
     /// Execute command
     Command(CommandResult),
 
@@ -43,6 +46,12 @@ pub enum CommandResult {
     /// History cleared
     HistoryCleared,
 
+    /// List stored sessions (resolved by the runner against the transcript store)
+    Sessions,
+
+    /// Image attached to the next message
+    ImageAttached(String),
+
     /// Error message
     Error(String),
 }
@@ -51,12 +60,16 @@ pub enum CommandResult {
 #[derive(Clone)]
 pub struct InputHandler {
     config: CliConfig,
+    pending_images: Vec<CandleImageAttachment>,
 }
 
 impl InputHandler {
     /// Create new input handler
     pub fn new(config: CliConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            pending_images: Vec::new(),
+        }
     }
 
     /// Process user input and return action to take
@@ -73,9 +86,18 @@ impl InputHandler {
         }
 
         // Regular chat message - add to history
-        self.config.add_to_history(trimmed.to_string());
+        self.chat(trimmed.to_string())
+    }
+
+    /// Record a chat message and send it with any attached images
+    fn chat(&mut self, message: String) -> InputHandlerResult {
+        self.config.add_to_history(message.clone());
 
-        InputHandlerResult::Chat(trimmed.to_string())
+        if self.pending_images.is_empty() {
+            InputHandlerResult::Chat(message)
+        } else {
+            InputHandlerResult::ChatWithImages(message, std::mem::take(&mut self.pending_images))
+        }
     }
 
     /// Handle command input
@@ -99,6 +121,8 @@ impl InputHandler {
             "/tokens" => self.handle_tokens(&args),
             "/export" => self.handle_export(&args),
             "/import" => self.handle_import(&args),
+            "/sessions" => InputHandlerResult::Command(CommandResult::Sessions),
+            "/image" => self.handle_image(&args),
             _ => InputHandlerResult::Command(CommandResult::Error(format!(
                 "Unknown command: {}",
                 command
@@ -122,6 +146,8 @@ Available Commands:
   /tokens <n>     - Set max tokens
   /export <file>  - Export configuration
   /import <file>  - Import configuration
+  /sessions       - List saved sessions (resume with --resume <id>)
+  /image <path> [message] - Attach an image (file or URL) to the next message
 
 Chat Commands:
   Type any message to chat with the AI
@@ -308,6 +334,33 @@ Chat Commands:
         }
     }
 
+    /// Handle /image command
+    ///
+    /// With a message after the path the turn is sent right away; otherwise
+    /// the image waits for the next message.
+    fn handle_image(&mut self, args: &[String]) -> InputHandlerResult {
+        let Some((source, message)) = args.split_first() else {
+            return InputHandlerResult::Command(CommandResult::Error(
+                "Usage: /image <path|url> [message]".to_string(),
+            ));
+        };
+
+        let image = CandleImageAttachment::new(source.clone());
+        if !image.is_url() && !Path::new(source).is_file() {
+            return InputHandlerResult::Command(CommandResult::Error(format!(
+                "Image not found: {}",
+                source
+            )));
+        }
+        self.pending_images.push(image);
+
+        if message.is_empty() {
+            InputHandlerResult::Command(CommandResult::ImageAttached(source.clone()))
+        } else {
+            self.chat(message.join(" "))
+        }
+    }
+
     /// Get current config
     pub fn config(&self) -> &CliConfig {
         &self.config
@@ -322,6 +375,9 @@ Chat Commands:
     pub fn to_chat_loop(&self, input: InputHandlerResult) -> Option<CandleChatLoop> {
         match input {
             InputHandlerResult::Chat(message) => Some(CandleChatLoop::UserPrompt(message)),
+            InputHandlerResult::ChatWithImages(message, images) => {
+                Some(CandleChatLoop::UserPromptWithImages(message, images))
+            }
             InputHandlerResult::Exit => Some(CandleChatLoop::Break),
             _ => None,
         }
@@ -376,4 +432,51 @@ mod tests {
             _ => panic!("Expected ConfigChanged result"),
         }
     }
+
+    #[test]
+    fn test_handle_sessions_command() {
+        let mut handler = InputHandler::new(CliConfig::new());
+        let result = handler.handle("/sessions");
+
+        assert!(matches!(
+            result,
+            InputHandlerResult::Command(CommandResult::Sessions)
+        ));
+    }
+
+    #[test]
+    fn test_handle_image_command() {
+        let mut handler = InputHandler::new(CliConfig::new());
+
+        assert!(matches!(
+            handler.handle("/image /no/such/picture.png"),
+            InputHandlerResult::Command(CommandResult::Error(_))
+        ));
+        assert!(matches!(
+            handler.handle("/image https://example.com/cat.jpg"),
+            InputHandlerResult::Command(CommandResult::ImageAttached(_))
+        ));
+
+        match handler.handle("What breed is this?") {
+            InputHandlerResult::ChatWithImages(message, images) => {
+                assert_eq!(message, "What breed is this?");
+                assert_eq!(images.len(), 1);
+                assert!(images[0].is_url());
+            }
+            _ => panic!("Expected ChatWithImages result"),
+        }
+
+        // Attachments are consumed by the message they were sent with
+        assert!(matches!(
+            handler.handle("And now?"),
+            InputHandlerResult::Chat(_)
+        ));
+        match handler.handle("/image https://example.com/dog.png Describe it") {
+            InputHandlerResult::ChatWithImages(message, images) => {
+                assert_eq!(message, "Describe it");
+                assert_eq!(images[0].source, "https://example.com/dog.png");
+            }
+            _ => panic!("Expected ChatWithImages result"),
+        }
+    }
 }
@@ -10,11 +10,16 @@ pub mod config;
 pub mod handler;
 pub mod prompt;
 pub mod runner;
+pub mod schedules;
+pub mod sessions;
+pub mod voice;
 
 // Re-export main types for convenience
-pub use args::CliArgs;
+pub use args::{CliArgs, CliCommand, ScheduleAction};
 pub use completion::{CommandCompleter, ModelCompleter};
 pub use config::CliConfig;
 pub use handler::InputHandler;
 pub use prompt::PromptBuilder;
 pub use runner::CliRunner;
+pub use sessions::SessionRecorder;
+pub use voice::{CliVoiceService, CommandVoiceService, SentenceChunker, VoiceMode, VoiceSettings};
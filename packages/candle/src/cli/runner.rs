@@ -6,14 +6,29 @@
 use anyhow::{Context, Result};
 use std::io::Write;
 
-use super::args::CliArgs;
+use super::args::{CliArgs, CliCommand, ScheduleAction};
 use super::config::CliConfig;
 use super::handler::{CommandResult, InputHandler, InputHandlerResult};
+use super::sessions::{SessionRecorder, format_sessions};
+use super::voice::{CliVoiceService, VoiceMode, VoiceSettings};
 
 use crate::builders::agent_role::{CandleAgentBuilder, CandleAgentRoleBuilder, CandleFluentAi};
 use crate::domain::chat::CandleChatLoop;
+use crate::domain::chat::transcript::{TranscriptEntry, TranscriptRole, fit_history};
+use crate::domain::chat::usage::ConversationUsage;
+use crate::domain::chat::vision::describe_user_turn;
 use crate::util::input_resolver::resolve_input;
 
+/// Context window assumed when the model does not report one
+const DEFAULT_CONTEXT_TOKENS: usize = 8192;
+
+/// Decoder depth assumed when planning a placement; model info has no layer count
+const PLANNING_LAYERS: usize = 32;
+
+/// System prompt for launcher queries when none is given
+const EXTENSION_SYSTEM_PROMPT: &str = "You answer questions typed into a launcher. \
+Be brief and format the answer as Markdown. Link images with ![alt](path or URL).";
+
 /// CLI runner for interactive chat
 pub struct CliRunner {
     args: CliArgs,
@@ -50,22 +65,61 @@ impl CliRunner {
     pub async fn run(&mut self) -> Result<()> {
         use tokio_stream::StreamExt;
 
+        // Place models on the requested devices before any of them load
+        let devices = self
+            .args
+            .device_config()
+            .map_err(|e| anyhow::anyhow!("Invalid device configuration: {}", e))?;
+        crate::core::device_util::configure_devices(devices);
+
+        match self.args.command {
+            CliCommand::Serve => return self.serve().await,
+            CliCommand::Batch => return self.batch().await,
+            CliCommand::Eval => return self.eval().await,
+            CliCommand::Extension => return self.extension().await,
+            CliCommand::Schedule => return self.schedule().await,
+            CliCommand::Devices => {
+                self.print_devices();
+                return Ok(());
+            }
+            CliCommand::Chat => {}
+        }
+
         // Initialize pool maintenance thread (lazy init)
         crate::capability::registry::pool::init_maintenance();
 
-        // Setup Ctrl+C handler for IMMEDIATE exit
-        ctrlc::set_handler(move || {
-            eprintln!("\n\nExiting...");
-            std::process::exit(0);
-        })
-        .map_err(|e| anyhow::anyhow!("Failed to set Ctrl-C handler: {}", e))?;
-
         // Clean, minimal banner
         println!("\n╭─────────────────────────────────────╮");
         println!("│  🤖  Interactive AI Chat           │");
         println!("╰─────────────────────────────────────╯");
         println!("\nType /help for commands • Ctrl+C to exit\n");
 
+        // Persist this conversation; a resumed transcript is replayed into the prompt
+        let (recorder, history) =
+            match SessionRecorder::start(self.args.model.as_deref(), self.args.resume.as_deref())
+                .await
+            {
+                Ok((recorder, history)) => {
+                    if self.args.resume.is_some() {
+                        println!(
+                            "Resuming session {} ({} messages)\n",
+                            recorder.session_id(),
+                            history.len()
+                        );
+                    } else {
+                        println!("Session {}\n", recorder.session_id());
+                    }
+                    (Some(std::sync::Arc::new(recorder)), history)
+                }
+                Err(e) if self.args.resume.is_some() => {
+                    return Err(anyhow::anyhow!("Failed to resume session: {}", e));
+                }
+                Err(e) => {
+                    log::warn!("Conversation persistence disabled: {}", e);
+                    (None, Vec::new())
+                }
+            };
+
         // Resolve system prompt using smart input resolution
         let system_prompt = if let Some(ref prompt_input) = self.args.system_prompt {
             resolve_input(prompt_input)
@@ -88,158 +142,268 @@ You are a master at refactoring code, remembering to check for code that ALREADY
             )
         };
 
+        let system_prompt = if history.is_empty() {
+            system_prompt
+        } else {
+            // Older turns stay searchable in memory; the prompt only gets what fits
+            let policy = self.args.context_overflow;
+            let fitted = fit_history(&history, self.history_budget(), policy).map_err(|e| {
+                anyhow::anyhow!("Resumed session exceeds the context window: {}", e)
+            })?;
+            if fitted.dropped > 0 {
+                println!(
+                    "Context window full: {} older messages {} ({})\n",
+                    fitted.dropped,
+                    if fitted.summary.is_some() {
+                        "summarized"
+                    } else {
+                        "dropped"
+                    },
+                    policy
+                );
+            }
+            format!(
+                "{}\n\n# Previous conversation\n\n{}",
+                system_prompt,
+                fitted.render()
+            )
+        };
+
         // Use async closure with direct tokio stdin reading (prepare handler first)
         let handler = std::sync::Arc::new(std::sync::Mutex::new(self.handler.clone()));
+        let turn_recorder = recorder.clone();
 
-        // Build agent and compute stream directly in each branch to avoid opaque type mismatch
-        let stream = if let Some(registry_key) = &self.args.model {
-            use crate::capability::registry::{self, TextToTextModel};
+        // Voice mode replaces stdin with the microphone and speaks each reply
+        let voice = if self.args.voice {
+            Some(std::sync::Arc::new(Self::start_voice()?))
+        } else {
+            None
+        };
 
-            let text_model = registry::get::<TextToTextModel>(registry_key)
-                .ok_or_else(|| anyhow::anyhow!("Model not found in registry: {}", registry_key))?;
+        // Build agent and compute stream directly in each branch to avoid opaque type mismatch
+        let (usage, stream) = if let Some(registry_key) = &self.args.model {
+            let text_model = self.text_model(registry_key)?;
 
-            CandleFluentAi::agent_role(&self.args.agent_role)
+            let chunk_voice = voice.clone();
+            let agent = CandleFluentAi::agent_role(&self.args.agent_role)
                 .into_agent()
                 .model(text_model)
                 .temperature(self.args.temperature)
                 .system_prompt(system_prompt.clone())
                 .memory_read_timeout(self.args.memory_read_timeout)
                 .max_tokens(self.args.max_tokens.unwrap_or(2000))
-                .on_chunk(|chunk| async move {
-                    use crate::domain::chat::message::CandleMessageChunk;
-                    if let CandleMessageChunk::Text(ref text) = chunk {
-                        print!("{}", text);
-                        let _ = std::io::stdout().flush();
+                .on_chunk(move |chunk| {
+                    if let Some(voice) = &chunk_voice {
+                        voice.observe(&chunk);
                     }
-                    chunk
-                })
-                .chat(move |_conversation| {
-                    let handler = handler.clone();
                     async move {
-                        use tokio::io::{AsyncBufReadExt, BufReader};
-
-                        print!("\n> You: ");
-                        let _ = std::io::stdout().flush();
-
-                        let stdin = tokio::io::stdin();
-                        let mut reader = BufReader::new(stdin);
-                        let mut input = String::new();
-
-                        match reader.read_line(&mut input).await {
-                            Ok(0) => CandleChatLoop::Break, // EOF
-                            Ok(_) => {
-                                let input = input.trim();
-
-                                // Handle input via InputHandler
-                                let handler_result = match handler.lock() {
-                                    Ok(mut h) => h.handle(input),
-                                    Err(_) => InputHandlerResult::Exit,
-                                };
-
-                                match handler_result {
-                                    InputHandlerResult::Exit => {
-                                        println!("Goodbye!");
-                                        CandleChatLoop::Break
-                                    }
-                                    InputHandlerResult::Command(cmd_result) => {
-                                        let output = Self::format_command_result(&cmd_result);
-                                        println!("{}", output);
-                                        CandleChatLoop::Reprompt(String::new())
-                                    }
-                                    InputHandlerResult::None => {
-                                        CandleChatLoop::Reprompt(String::new())
+                        use crate::domain::chat::message::CandleMessageChunk;
+                        if let CandleMessageChunk::Text(ref text) = chunk {
+                            print!("{}", text);
+                            let _ = std::io::stdout().flush();
+                        }
+                        chunk
+                    }
+                });
+            let usage = agent.usage();
+            let stream = agent.chat(move |_conversation| {
+                let handler = handler.clone();
+                let recorder = turn_recorder.clone();
+                let voice = voice.clone();
+                async move {
+                    match Self::read_input(voice.as_deref()).await {
+                        Ok(None) => CandleChatLoop::Break, // EOF
+                        Ok(Some(input)) => {
+                            let input = input.trim();
+
+                            // Handle input via InputHandler
+                            let handler_result = match handler.lock() {
+                                Ok(mut h) => h.handle(input),
+                                Err(_) => InputHandlerResult::Exit,
+                            };
+
+                            match handler_result {
+                                InputHandlerResult::Exit => {
+                                    println!("Goodbye!");
+                                    CandleChatLoop::Break
+                                }
+                                InputHandlerResult::Command(CommandResult::Sessions) => {
+                                    println!("{}", Self::list_sessions(recorder.as_deref()).await);
+                                    CandleChatLoop::Reprompt(String::new())
+                                }
+                                InputHandlerResult::Command(cmd_result) => {
+                                    let output = Self::format_command_result(&cmd_result);
+                                    println!("{}", output);
+                                    CandleChatLoop::Reprompt(String::new())
+                                }
+                                InputHandlerResult::None => CandleChatLoop::Reprompt(String::new()),
+                                InputHandlerResult::Chat(message) => {
+                                    if let Some(recorder) = &recorder {
+                                        recorder
+                                            .record(TranscriptEntry::message(
+                                                TranscriptRole::User,
+                                                message.clone(),
+                                            ))
+                                            .await;
                                     }
-                                    InputHandlerResult::Chat(message) => {
-                                        CandleChatLoop::UserPrompt(message)
+                                    CandleChatLoop::UserPrompt(message)
+                                }
+                                InputHandlerResult::ChatWithImages(message, images) => {
+                                    if let Some(recorder) = &recorder {
+                                        recorder
+                                            .record(TranscriptEntry::message(
+                                                TranscriptRole::User,
+                                                describe_user_turn(&message, &images),
+                                            ))
+                                            .await;
                                     }
+                                    CandleChatLoop::UserPromptWithImages(message, images)
                                 }
                             }
-                            Err(e) => {
-                                eprintln!("Input error: {}", e);
-                                CandleChatLoop::Break
-                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Input error: {}", e);
+                            CandleChatLoop::Break
                         }
                     }
-                })?
+                }
+            })?;
+            (usage, stream)
         } else {
-            CandleFluentAi::agent_role(&self.args.agent_role)
+            let chunk_voice = voice.clone();
+            let agent = CandleFluentAi::agent_role(&self.args.agent_role)
                 .into_agent()
                 .temperature(self.args.temperature)
                 .system_prompt(system_prompt.clone())
                 .memory_read_timeout(self.args.memory_read_timeout)
                 .max_tokens(self.args.max_tokens.unwrap_or(2000))
-                .on_chunk(|chunk| async move {
-                    use crate::domain::chat::message::CandleMessageChunk;
-                    if let CandleMessageChunk::Text(ref text) = chunk {
-                        print!("{}", text);
-                        let _ = std::io::stdout().flush();
+                .on_chunk(move |chunk| {
+                    if let Some(voice) = &chunk_voice {
+                        voice.observe(&chunk);
                     }
-                    chunk
-                })
-                .chat(move |_conversation| {
-                    let handler = handler.clone();
                     async move {
-                        use tokio::io::{AsyncBufReadExt, BufReader};
-
-                        print!("\n> You: ");
-                        let _ = std::io::stdout().flush();
-
-                        let stdin = tokio::io::stdin();
-                        let mut reader = BufReader::new(stdin);
-                        let mut input = String::new();
-
-                        match reader.read_line(&mut input).await {
-                            Ok(0) => CandleChatLoop::Break, // EOF
-                            Ok(_) => {
-                                let input = input.trim();
-
-                                // Handle input via InputHandler
-                                let handler_result = match handler.lock() {
-                                    Ok(mut h) => h.handle(input),
-                                    Err(_) => InputHandlerResult::Exit,
-                                };
-
-                                match handler_result {
-                                    InputHandlerResult::Exit => {
-                                        println!("Goodbye!");
-                                        CandleChatLoop::Break
-                                    }
-                                    InputHandlerResult::Command(cmd_result) => {
-                                        let output = Self::format_command_result(&cmd_result);
-                                        println!("{}", output);
-                                        CandleChatLoop::Reprompt(String::new())
-                                    }
-                                    InputHandlerResult::None => {
-                                        CandleChatLoop::Reprompt(String::new())
+                        use crate::domain::chat::message::CandleMessageChunk;
+                        if let CandleMessageChunk::Text(ref text) = chunk {
+                            print!("{}", text);
+                            let _ = std::io::stdout().flush();
+                        }
+                        chunk
+                    }
+                });
+            let usage = agent.usage();
+            let stream = agent.chat(move |_conversation| {
+                let handler = handler.clone();
+                let recorder = turn_recorder.clone();
+                let voice = voice.clone();
+                async move {
+                    match Self::read_input(voice.as_deref()).await {
+                        Ok(None) => CandleChatLoop::Break, // EOF
+                        Ok(Some(input)) => {
+                            let input = input.trim();
+
+                            // Handle input via InputHandler
+                            let handler_result = match handler.lock() {
+                                Ok(mut h) => h.handle(input),
+                                Err(_) => InputHandlerResult::Exit,
+                            };
+
+                            match handler_result {
+                                InputHandlerResult::Exit => {
+                                    println!("Goodbye!");
+                                    CandleChatLoop::Break
+                                }
+                                InputHandlerResult::Command(CommandResult::Sessions) => {
+                                    println!("{}", Self::list_sessions(recorder.as_deref()).await);
+                                    CandleChatLoop::Reprompt(String::new())
+                                }
+                                InputHandlerResult::Command(cmd_result) => {
+                                    let output = Self::format_command_result(&cmd_result);
+                                    println!("{}", output);
+                                    CandleChatLoop::Reprompt(String::new())
+                                }
+                                InputHandlerResult::None => CandleChatLoop::Reprompt(String::new()),
+                                InputHandlerResult::Chat(message) => {
+                                    if let Some(recorder) = &recorder {
+                                        recorder
+                                            .record(TranscriptEntry::message(
+                                                TranscriptRole::User,
+                                                message.clone(),
+                                            ))
+                                            .await;
                                     }
-                                    InputHandlerResult::Chat(message) => {
-                                        CandleChatLoop::UserPrompt(message)
+                                    CandleChatLoop::UserPrompt(message)
+                                }
+                                InputHandlerResult::ChatWithImages(message, images) => {
+                                    if let Some(recorder) = &recorder {
+                                        recorder
+                                            .record(TranscriptEntry::message(
+                                                TranscriptRole::User,
+                                                describe_user_turn(&message, &images),
+                                            ))
+                                            .await;
                                     }
+                                    CandleChatLoop::UserPromptWithImages(message, images)
                                 }
                             }
-                            Err(e) => {
-                                eprintln!("Input error: {}", e);
-                                CandleChatLoop::Break
-                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Input error: {}", e);
+                            CandleChatLoop::Break
                         }
                     }
-                })?
+                }
+            })?;
+            (usage, stream)
         };
         tokio::pin!(stream);
 
+        // Setup Ctrl+C handler for IMMEDIATE exit
+        let exit_usage = usage.clone();
+        ctrlc::set_handler(move || {
+            eprintln!("\n\nExiting...");
+            Self::print_usage_summary(&exit_usage.snapshot());
+            std::process::exit(0);
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to set Ctrl-C handler: {}", e))?;
+
         // Consume stream
         println!("\n💭 ");
+        let mut assistant_text = String::new();
         while let Some(chunk) = stream.next().await {
             use crate::domain::chat::message::CandleMessageChunk;
             match chunk {
-                CandleMessageChunk::Text(_) => {
+                CandleMessageChunk::Text(text) => {
                     // Text already printed via on_chunk handler
+                    assistant_text.push_str(&text);
                 }
-                CandleMessageChunk::Complete { text, .. } => {
+                CandleMessageChunk::Complete {
+                    text,
+                    token_count,
+                    provider,
+                    ..
+                } => {
                     if !text.is_empty() {
                         print!("{}", text);
                     }
+                    if let Some(provider) = provider
+                        && !self.args.fallback_models.is_empty()
+                        && self.primary_provider() != Some(provider.as_str())
+                    {
+                        print!("\n\n↪ answered by fallback model {}", provider);
+                    }
                     println!("\n");
+
+                    assistant_text.push_str(&text);
+                    if let Some(recorder) = &recorder {
+                        let mut entry = TranscriptEntry::message(
+                            TranscriptRole::Assistant,
+                            std::mem::take(&mut assistant_text),
+                        );
+                        if let Some(tokens) = token_count {
+                            entry = entry.with_tokens(tokens);
+                        }
+                        recorder.record(entry).await;
+                    }
                 }
                 CandleMessageChunk::Error(err) => {
                     eprintln!("\n❌ {}", err);
@@ -247,17 +411,614 @@ You are a master at refactoring code, remembering to check for code that ALREADY
                 CandleMessageChunk::ToolCallStart { name, .. } => {
                     println!("\n🔧 {}", name);
                 }
-                CandleMessageChunk::ToolCallComplete { .. } => {}
+                CandleMessageChunk::ToolCallComplete { id, name, input } => {
+                    if let Some(recorder) = &recorder {
+                        recorder
+                            .record(TranscriptEntry::tool_call(id, name, input))
+                            .await;
+                    }
+                }
                 _ => {}
             }
         }
 
+        Self::print_usage_summary(&usage.snapshot());
+
         // Save config on exit
         self.save_config()?;
 
         Ok(())
     }
 
+    /// Run the OpenAI-compatible HTTP server until it fails
+    #[cfg(feature = "api")]
+    async fn serve(&self) -> Result<()> {
+        use crate::capability::registry;
+        use crate::server::{OpenAiServer, ServerState};
+
+        // Initialize pool maintenance thread (lazy init)
+        registry::pool::init_maintenance();
+
+        if let Some(registry_key) = &self.args.model
+            && registry::get::<registry::TextToTextModel>(registry_key).is_none()
+        {
+            return Err(anyhow::anyhow!(
+                "Model not found in registry: {}",
+                registry_key
+            ));
+        }
+
+        let state = ServerState {
+            default_model: self.args.model.clone(),
+            temperature: self.args.temperature,
+            max_tokens: self.args.max_tokens.unwrap_or(2000),
+        };
+        let server = OpenAiServer::new(&self.args.host, self.args.port, state)
+            .map_err(|e| anyhow::anyhow!("Invalid listen address: {}", e))?;
+
+        println!(
+            "Serving OpenAI-compatible API on http://{}/v1",
+            server.addr()
+        );
+        for key in registry::text_to_text_registry_keys() {
+            println!("  model: {}", key);
+        }
+
+        server
+            .start()
+            .await
+            .map_err(|e| anyhow::anyhow!("Server error: {}", e))
+    }
+
+    /// Run the OpenAI-compatible HTTP server (unavailable without the `api` feature)
+    #[cfg(not(feature = "api"))]
+    async fn serve(&self) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "The serve command requires the `api` feature"
+        ))
+    }
+
+    /// Run the `--input` prompts through the model into `--output`
+    async fn batch(&self) -> Result<()> {
+        use crate::batch::BatchJob;
+
+        crate::capability::registry::pool::init_maintenance();
+
+        let (Some(registry_key), Some(input), Some(output)) =
+            (&self.args.model, &self.args.input, &self.args.output)
+        else {
+            return Err(anyhow::anyhow!(
+                "Batch mode requires --model, --input and --output"
+            ));
+        };
+        let model = self.text_model(registry_key)?;
+
+        let mut job = BatchJob::new(input, output)
+            .with_concurrency(self.args.concurrency)
+            .with_temperature(self.args.temperature);
+        if let Some(max_tokens) = self.args.max_tokens {
+            job = job.with_max_tokens(max_tokens);
+        }
+
+        println!(
+            "Batch: {} -> {} with {} ({} at a time)",
+            input.display(),
+            output.display(),
+            registry_key,
+            self.args.concurrency
+        );
+        let summary = job
+            .run(model)
+            .await
+            .map_err(|e| anyhow::anyhow!("Batch failed: {}", e))?;
+        println!("{}", summary);
+
+        if summary.failed > 0 {
+            return Err(anyhow::anyhow!(
+                "{} requests failed; rerun to retry them",
+                summary.failed
+            ));
+        }
+        Ok(())
+    }
+
+    /// Score the selected models against an eval suite and print the report
+    ///
+    /// With `--output`, the report is also written there as JSON and next to
+    /// it as markdown.
+    async fn eval(&self) -> Result<()> {
+        use crate::capability::registry::{self, TextToTextModel};
+        use crate::eval::{EvalRunner, EvalSuite};
+
+        crate::capability::registry::pool::init_maintenance();
+
+        let (Some(registry_key), Some(suite_path)) = (&self.args.model, &self.args.suite) else {
+            return Err(anyhow::anyhow!("Eval mode requires --model and --suite"));
+        };
+        let lookup = |key: &str| {
+            registry::get::<TextToTextModel>(key)
+                .ok_or_else(|| anyhow::anyhow!("Model not found in registry: {}", key))
+        };
+
+        let suite = EvalSuite::load(suite_path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load eval suite: {}", e))?;
+
+        let mut runner = EvalRunner::new()
+            .with_named_provider(registry_key.clone(), self.text_model(registry_key)?)
+            .with_temperature(self.args.temperature);
+        for key in &self.args.compare_models {
+            runner = runner.with_named_provider(key.clone(), lookup(key)?);
+        }
+        if let Some(key) = &self.args.judge_model {
+            runner = runner.with_judge(lookup(key)?);
+        }
+        if let Some(max_tokens) = self.args.max_tokens {
+            runner = runner.with_max_tokens(max_tokens);
+        }
+
+        println!(
+            "Eval: {} ({} cases) against {}",
+            suite.name,
+            suite.cases.len(),
+            runner.providers().join(", ")
+        );
+        let report = runner
+            .run(&suite)
+            .await
+            .map_err(|e| anyhow::anyhow!("Eval failed: {}", e))?;
+        let markdown = report.to_markdown();
+        println!("{}", markdown);
+
+        if let Some(output) = &self.args.output {
+            let json = report
+                .to_json()
+                .map_err(|e| anyhow::anyhow!("Failed to serialize eval report: {}", e))?;
+            tokio::fs::write(output, json)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output.display(), e))?;
+            let markdown_path = output.with_extension("md");
+            tokio::fs::write(&markdown_path, markdown)
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to write {}: {}", markdown_path.display(), e)
+                })?;
+            println!(
+                "Report written to {} and {}",
+                output.display(),
+                markdown_path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Answer a Raycast or Alfred query on stdout, or write the bundle with `--bundle`
+    ///
+    /// Raycast gets one JSON frame per line while the answer streams; Alfred
+    /// gets a single script filter result once it is complete.
+    async fn extension(&self) -> Result<()> {
+        use crate::extensions::bridge::{
+            ExtensionBridge, ExtensionHost, ExtensionManifest, ExtensionQuery, ExtensionResponse,
+        };
+        use tokio_stream::StreamExt;
+
+        let host = self.args.extension_host;
+        if let Some(dir) = &self.args.bundle {
+            let command =
+                std::env::current_exe().context("Failed to locate the candle executable")?;
+            let mut args = Vec::new();
+            if let Some(model) = &self.args.model {
+                args.extend(["--model".to_string(), model.clone()]);
+            }
+            let bundle = ExtensionManifest::new(command)
+                .with_args(args)
+                .write_bundle(host, dir)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to write the {} extension: {}", host, e))?;
+            println!("Wrote the {} extension to {}", host, bundle.display());
+            return Ok(());
+        }
+
+        crate::capability::registry::pool::init_maintenance();
+
+        let print = |response: &ExtensionResponse| {
+            let output = match host {
+                ExtensionHost::Raycast => response.to_raycast().to_string(),
+                ExtensionHost::Alfred => {
+                    serde_json::to_string(&response.to_alfred()).unwrap_or_default()
+                }
+            };
+            println!("{}", output);
+            let _ = std::io::stdout().flush();
+        };
+
+        // Launchers only show stdout, so setup failures are answered there too
+        let text_model = match self.args.model.as_deref().map(|key| self.text_model(key)) {
+            None => None,
+            Some(Ok(model)) => Some(model),
+            Some(Err(e)) => {
+                print(&ExtensionResponse {
+                    error: Some(e.to_string()),
+                    done: true,
+                    ..Default::default()
+                });
+                return Ok(());
+            }
+        };
+        let system_prompt = match &self.args.system_prompt {
+            Some(prompt_input) => resolve_input(prompt_input)
+                .await
+                .context("Failed to resolve system prompt")?,
+            None => EXTENSION_SYSTEM_PROMPT.to_string(),
+        };
+
+        let role = self.args.agent_role.clone();
+        let temperature = self.args.temperature;
+        let max_tokens = self.args.max_tokens.unwrap_or(2000);
+        let memory_read_timeout = self.args.memory_read_timeout;
+        let bridge = ExtensionBridge::new(move |turn: CandleChatLoop| {
+            let agent = CandleFluentAi::agent_role(&role)
+                .into_agent()
+                .temperature(temperature)
+                .system_prompt(system_prompt.clone())
+                .memory_read_timeout(memory_read_timeout)
+                .max_tokens(max_tokens);
+            // Build and chat in each branch to avoid opaque type mismatch
+            let stream = match &text_model {
+                Some(model) => agent.model(model.clone()).chat(move |_conversation| {
+                    let turn = turn.clone();
+                    async move { turn }
+                }),
+                None => agent.chat(move |_conversation| {
+                    let turn = turn.clone();
+                    async move { turn }
+                }),
+            };
+            stream.unwrap_or_else(|e| ExtensionBridge::failed(format!("Chat failed: {}", e)))
+        });
+
+        let query = ExtensionQuery::new(host, self.args.message.clone().unwrap_or_default())
+            .with_images(self.args.images.iter().cloned());
+        match host {
+            ExtensionHost::Raycast => {
+                let mut frames = bridge.stream(query);
+                while let Some(response) = frames.next().await {
+                    print(&response);
+                }
+            }
+            ExtensionHost::Alfred => print(&bridge.respond(query).await),
+        }
+        Ok(())
+    }
+
+    /// Manage scheduled workflows, or run due ones until Ctrl+C with `schedule daemon`
+    ///
+    /// Run outputs are written under the user's data directory as artifacts.
+    async fn schedule(&self) -> Result<()> {
+        use super::schedules::{format_runs, format_schedules, scheduled_agent};
+        use crate::workflow::scheduler::{
+            ScheduleSpec, ScheduleStore, Scheduler, WorkflowSchedule,
+        };
+
+        crate::capability::registry::pool::init_maintenance();
+
+        let store = ScheduleStore::open()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to open schedule store: {}", e))?;
+        let target = match &self.args.schedule_target {
+            Some(name_or_id) => Some(
+                store
+                    .resolve(name_or_id)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}", e))?,
+            ),
+            None => None,
+        };
+        let artifacts_dir = dirs::data_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("cyrup")
+            .join("runs");
+        let scheduler =
+            Scheduler::new(store.clone(), scheduled_agent).with_artifacts_dir(artifacts_dir);
+
+        match (self.args.schedule_action, target) {
+            (ScheduleAction::Add, _) => {
+                let (Some(name), Some(prompt)) = (&self.args.schedule_name, &self.args.message)
+                else {
+                    return Err(anyhow::anyhow!(
+                        "schedule add requires --name and --message"
+                    ));
+                };
+                let spec = if let Some(expression) = &self.args.cron {
+                    ScheduleSpec::cron(expression)
+                } else if let Some(interval) = &self.args.every {
+                    ScheduleSpec::parse_interval(interval)
+                } else if let Some(path) = &self.args.webhook {
+                    ScheduleSpec::webhook(path, self.args.webhook_secret.clone())
+                } else if let Some(method) = &self.args.on_notification {
+                    let server = self
+                        .args
+                        .mcp_server
+                        .as_deref()
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect();
+                    ScheduleSpec::notification(server, method)
+                } else {
+                    return Err(anyhow::anyhow!(
+                        "schedule add requires one of --cron, --every, --webhook or --on-notification"
+                    ));
+                }
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+                let mut schedule = WorkflowSchedule::new(name.clone(), spec, prompt.clone())
+                    .with_agent_role(self.args.agent_role.clone())
+                    .with_temperature(self.args.temperature);
+                if let Some(model) = &self.args.model {
+                    schedule = schedule.with_model(model.clone());
+                }
+                if let Some(system_prompt) = &self.args.system_prompt {
+                    let system_prompt = resolve_input(system_prompt)
+                        .await
+                        .context("Failed to resolve system prompt")?;
+                    schedule = schedule.with_system_prompt(system_prompt);
+                }
+                if let Some(max_tokens) = self.args.max_tokens {
+                    schedule = schedule.with_max_tokens(max_tokens);
+                }
+
+                store
+                    .add(&schedule)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to add schedule: {}", e))?;
+                println!(
+                    "Scheduled {} ({}), id {}",
+                    schedule.name, schedule.spec, schedule.schedule_id
+                );
+            }
+            (ScheduleAction::List, _) => {
+                let schedules = store
+                    .list()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to list schedules: {}", e))?;
+                println!("{}", format_schedules(&schedules));
+            }
+            (ScheduleAction::Runs, target) => {
+                let runs = store
+                    .runs(target.as_ref().map(|s| s.schedule_id.as_str()), 20)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to list runs: {}", e))?;
+                println!("{}", format_runs(&runs));
+            }
+            (ScheduleAction::Daemon, _) => {
+                use crate::workflow::triggers::TriggerDispatcher;
+
+                let scheduler = std::sync::Arc::new(scheduler);
+                let dispatcher = TriggerDispatcher::new(scheduler.clone()).on_run(|run| {
+                    println!("{}", format_runs(std::slice::from_ref(run)));
+                });
+                let subscriptions = dispatcher
+                    .subscribe_all()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to subscribe to MCP servers: {}", e))?;
+
+                #[cfg(feature = "api")]
+                let (stop_webhooks, webhooks) = {
+                    let addr = format!("{}:{}", self.args.host, self.args.port)
+                        .parse::<std::net::SocketAddr>()
+                        .map_err(|e| anyhow::anyhow!("Invalid webhook address: {}", e))?;
+                    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+                    let webhook_dispatcher = dispatcher.clone();
+                    // A listener that cannot bind should not take the scheduler down with it
+                    let server = tokio::spawn(async move {
+                        let shutdown = async move {
+                            let _ = stopped.await;
+                        };
+                        if let Err(e) = crate::workflow::triggers::serve_webhooks(
+                            webhook_dispatcher,
+                            addr,
+                            shutdown,
+                        )
+                        .await
+                        {
+                            log::warn!("Webhook listener stopped: {}", e);
+                        }
+                    });
+                    println!("Webhook triggers on http://{}", addr);
+                    (stop, server)
+                };
+
+                println!("Running scheduled workflows • Ctrl+C to stop");
+                scheduler
+                    .run_until(tokio::signal::ctrl_c(), |run| {
+                        println!("{}", format_runs(std::slice::from_ref(run)));
+                    })
+                    .await;
+
+                for subscription in subscriptions {
+                    subscription.abort();
+                }
+                #[cfg(feature = "api")]
+                {
+                    let _ = stop_webhooks.send(());
+                    let _ = webhooks.await;
+                }
+            }
+            (action, Some(schedule)) => match action {
+                ScheduleAction::Remove => {
+                    store
+                        .remove(&schedule.schedule_id)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to remove schedule: {}", e))?;
+                    println!("Removed {}", schedule.name);
+                }
+                ScheduleAction::Enable | ScheduleAction::Disable => {
+                    let enabled = action == ScheduleAction::Enable;
+                    store
+                        .set_enabled(&schedule, enabled)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to update schedule: {}", e))?;
+                    println!(
+                        "{} {}",
+                        if enabled { "Enabled" } else { "Paused" },
+                        schedule.name
+                    );
+                }
+                _ => {
+                    let run = scheduler
+                        .run(&schedule)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to record run: {}", e))?;
+                    println!(
+                        "{}\n\n{}",
+                        run.output,
+                        format_runs(std::slice::from_ref(&run))
+                    );
+                    if let Some(error) = run.error {
+                        return Err(anyhow::anyhow!("Scheduled run failed: {}", error));
+                    }
+                }
+            },
+            (_, None) => {
+                return Err(anyhow::anyhow!(
+                    "This schedule action requires a schedule name or id"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Print detected accelerators and where the model's layers would go
+    fn print_devices(&self) {
+        use crate::capability::registry::{self, TextToTextModel};
+        use crate::core::device_util::{AcceleratorInfo, DeviceSpec, detect_accelerators};
+        use crate::core::placement::{format_bytes, plan_layer_placement};
+        use crate::domain::model::traits::CandleModel;
+
+        let config = crate::core::device_util::device_config();
+        let accelerators = detect_accelerators();
+
+        println!("Detected devices:");
+        for accelerator in &accelerators {
+            let memory = match (accelerator.free_bytes, accelerator.total_bytes) {
+                (Some(free), Some(total)) => {
+                    format!("{} free of {}", format_bytes(free), format_bytes(total))
+                }
+                (None, Some(total)) => format!("{} total", format_bytes(total)),
+                _ => "memory unknown".to_string(),
+            };
+            println!(
+                "  {:<8} {} ({})",
+                accelerator.spec.to_string(),
+                accelerator.name,
+                memory
+            );
+        }
+        println!("\nModels load on: {}", config.primary());
+
+        let Some(registry_key) = self.args.model.as_deref() else {
+            println!("Pass --model to plan a layer placement");
+            return;
+        };
+        let Some(model) = registry::get::<TextToTextModel>(registry_key) else {
+            println!("Model not found in registry: {}", registry_key);
+            return;
+        };
+
+        // Shard devices in order, else the single device models load on
+        let wanted = if config.shard_devices.is_empty() {
+            let primary = match config.primary() {
+                DeviceSpec::Auto => accelerators
+                    .iter()
+                    .map(|accelerator| accelerator.spec)
+                    .find(DeviceSpec::is_gpu)
+                    .unwrap_or(DeviceSpec::Cpu),
+                spec => spec,
+            };
+            vec![primary]
+        } else {
+            config.shard_devices.clone()
+        };
+        let devices: Vec<AcceleratorInfo> = wanted
+            .into_iter()
+            .map(|spec| {
+                accelerators
+                    .iter()
+                    .find(|accelerator| accelerator.spec == spec)
+                    .cloned()
+                    .unwrap_or_else(|| AcceleratorInfo {
+                        spec,
+                        name: "not detected".to_string(),
+                        total_bytes: None,
+                        free_bytes: None,
+                    })
+            })
+            .collect();
+
+        let model_bytes = model.info().est_memory_allocation_mb as u64 * 1024 * 1024;
+        println!(
+            "\nPlanned placement for {} ({}, assuming {} layers):",
+            registry_key,
+            format_bytes(model_bytes),
+            PLANNING_LAYERS
+        );
+        match plan_layer_placement(PLANNING_LAYERS, model_bytes, &devices) {
+            Ok(placement) => {
+                print!("{}", placement);
+                if !placement.fits() {
+                    println!("The model does not fit; add devices with --shard-devices");
+                }
+            }
+            Err(e) => println!("  {}", e),
+        }
+    }
+
+    /// Start voice mode with local speech models or the configured commands
+    fn start_voice() -> Result<VoiceMode<CliVoiceService>> {
+        let service = CliVoiceService::from_env()
+            .map_err(|e| anyhow::anyhow!("Voice mode unavailable: {}", e))?;
+        println!("🎙️  Voice mode: speak after the prompt, talk over a reply to interrupt it\n");
+        Ok(VoiceMode::start(service, VoiceSettings::default()))
+    }
+
+    /// Read the next user turn from the microphone in voice mode, else stdin
+    ///
+    /// Returns `None` at end of input.
+    async fn read_input(
+        voice: Option<&VoiceMode<CliVoiceService>>,
+    ) -> std::io::Result<Option<String>> {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        print!("\n> You: ");
+        let _ = std::io::stdout().flush();
+
+        if let Some(voice) = voice {
+            let heard = voice
+                .next_utterance()
+                .await
+                .map_err(std::io::Error::other)?;
+            println!("{}", heard);
+            return Ok(Some(heard));
+        }
+
+        let mut input = String::new();
+        match BufReader::new(tokio::io::stdin())
+            .read_line(&mut input)
+            .await?
+        {
+            0 => Ok(None),
+            _ => Ok(Some(input)),
+        }
+    }
+
+    /// Print the conversation's token usage, if any turn ran
+    fn print_usage_summary(usage: &ConversationUsage) {
+        if !usage.is_empty() {
+            println!("\n📊 Usage: {}", usage);
+        }
+    }
+
     /// Format command result for display
     fn format_command_result(result: &CommandResult) -> String {
         match result {
@@ -272,10 +1033,83 @@ You are a master at refactoring code, remembering to check for code that ALREADY
             }
             CommandResult::ConfigChanged(msg) => msg.clone(),
             CommandResult::HistoryCleared => "History cleared".to_string(),
+            CommandResult::Sessions => "Listing saved sessions".to_string(),
+            CommandResult::ImageAttached(source) => {
+                format!("Attached {} (sent with your next message)", source)
+            }
             CommandResult::Error(err) => format!("Error: {}", err),
         }
     }
 
+    /// Render the `/sessions` listing
+    async fn list_sessions(recorder: Option<&SessionRecorder>) -> String {
+        match recorder {
+            Some(recorder) => match recorder.list().await {
+                Ok(sessions) => format_sessions(&sessions, Some(recorder.session_id())),
+                Err(e) => format!("Error: {}", e),
+            },
+            None => "Error: conversation persistence is unavailable".to_string(),
+        }
+    }
+
+    /// The model registered under `registry_key`
+    ///
+    /// With `--fallback-model`s configured, the model is wrapped in a
+    /// provider chain that fails over to them in order.
+    fn text_model(
+        &self,
+        registry_key: &str,
+    ) -> Result<crate::capability::registry::TextToTextModel> {
+        use crate::capability::registry::{self, TextToTextModel};
+        use crate::capability::text_to_text::ProviderChain;
+
+        let lookup = |key: &str| {
+            registry::get::<TextToTextModel>(key)
+                .ok_or_else(|| anyhow::anyhow!("Model not found in registry: {}", key))
+        };
+
+        let model = lookup(registry_key)?;
+        if self.args.fallback_models.is_empty() {
+            return Ok(model);
+        }
+
+        let mut chain = ProviderChain::new(model);
+        for key in &self.args.fallback_models {
+            chain = chain.fallback(lookup(key)?);
+        }
+        log::info!("Provider chain: {}", chain.providers().join(" -> "));
+        Ok(TextToTextModel::from(chain))
+    }
+
+    /// Registry key the selected model reports as its provider
+    fn primary_provider(&self) -> Option<&'static str> {
+        use crate::capability::registry::{self, TextToTextModel};
+        use crate::domain::model::traits::CandleModel;
+
+        self.args
+            .model
+            .as_deref()
+            .and_then(registry::get::<TextToTextModel>)
+            .map(|model| model.info().registry_key)
+    }
+
+    /// Tokens of resumed history to put in the prompt
+    ///
+    /// Half the selected model's context window, leaving the rest for the
+    /// system prompt, memory context and the new turn.
+    fn history_budget(&self) -> usize {
+        use crate::capability::registry::{self, TextToTextModel};
+        use crate::domain::model::traits::CandleModel;
+
+        self.args
+            .model
+            .as_deref()
+            .and_then(registry::get::<TextToTextModel>)
+            .and_then(|model| model.info().max_input_tokens)
+            .map_or(DEFAULT_CONTEXT_TOKENS, |tokens| tokens.get() as usize)
+            / 2
+    }
+
     /// Save config to disk
     fn save_config(&self) -> Result<()> {
         self.config
@@ -0,0 +1,111 @@
+//! Scheduled workflow support for the CLI
+//!
+//! `schedule` subcommands store schedules built from the usual agent flags
+//! and run them through agents assembled here, so a scheduled run sees the
+//! same model, role and tools an interactive one would.
+
+use std::sync::Arc;
+
+use crate::builders::agent_role::{CandleAgentBuilder, CandleAgentRoleBuilder, CandleFluentAi};
+use crate::capability::registry::{self, TextToTextModel};
+use crate::workflow::agents::{WorkflowAgent, agent_fn};
+use crate::workflow::scheduler::{RunRecord, SchedulerError, WorkflowSchedule};
+
+/// Tokens generated per run when the schedule sets no limit
+const DEFAULT_RUN_MAX_TOKENS: u64 = 2000;
+
+/// Agent answering `schedule`'s prompt with its stored settings
+///
+/// # Errors
+/// Returns `SchedulerError::Agent` if the schedule's model is not registered
+pub fn scheduled_agent(
+    schedule: &WorkflowSchedule,
+) -> Result<Arc<dyn WorkflowAgent>, SchedulerError> {
+    let model = schedule
+        .model
+        .as_deref()
+        .map(|key| {
+            registry::get::<TextToTextModel>(key).ok_or_else(|| {
+                SchedulerError::Agent(format!("Model not found in registry: {}", key))
+            })
+        })
+        .transpose()?;
+    let schedule = schedule.clone();
+
+    Ok(agent_fn(schedule.name.clone(), move |prompt| {
+        let builder = CandleFluentAi::agent_role(schedule.agent_role.clone())
+            .into_agent()
+            .temperature(schedule.temperature)
+            .system_prompt(schedule.system_prompt.clone().unwrap_or_default())
+            .max_tokens(schedule.max_tokens.unwrap_or(DEFAULT_RUN_MAX_TOKENS));
+        match &model {
+            Some(model) => builder.model(model.clone()).chat_with_message(prompt),
+            None => builder.chat_with_message(prompt),
+        }
+    }))
+}
+
+/// Local time of a Unix millisecond timestamp, or `-`
+fn format_time(timestamp_ms: Option<i64>) -> String {
+    timestamp_ms
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Render schedules for `schedule list`
+pub fn format_schedules(schedules: &[WorkflowSchedule]) -> String {
+    if schedules.is_empty() {
+        return "No schedules (add one with `schedule add`)".to_string();
+    }
+
+    let mut lines = vec!["Schedules:".to_string()];
+    for schedule in schedules {
+        let next = if schedule.enabled {
+            format_time(schedule.next_run_ms)
+        } else {
+            "paused".to_string()
+        };
+        lines.push(format!(
+            "  {}  {:<20}  {:<24}  next {:<16}  last {}",
+            &schedule.schedule_id[..schedule.schedule_id.len().min(8)],
+            schedule.name,
+            schedule.spec.to_string(),
+            next,
+            format_time(schedule.last_run_ms)
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Render run history for `schedule runs`
+pub fn format_runs(runs: &[RunRecord]) -> String {
+    if runs.is_empty() {
+        return "No runs recorded".to_string();
+    }
+
+    let mut lines = vec!["Recent runs:".to_string()];
+    for run in runs {
+        let mut line = format!(
+            "  {}  {:<20}  {}  {:<7}  {:>6.1}s  {} tool calls",
+            &run.run_id[..run.run_id.len().min(8)],
+            run.schedule_name,
+            format_time(Some(run.started_at_ms)),
+            run.status,
+            run.elapsed_ms() as f64 / 1000.0,
+            run.tool_calls.len()
+        );
+        if let Some(error) = &run.error {
+            line.push_str(&format!("  error: {}", error));
+        }
+        for artifact in &run.artifacts {
+            line.push_str(&format!("\n      {}", artifact));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,92 @@
+//! Conversation persistence for the CLI
+//!
+//! This module records each chat turn into the transcript store so sessions can be
+//! listed with `/sessions` and continued with `--resume <session>`.
+
+use crate::domain::chat::transcript::{
+    SessionSummary, TranscriptEntry, TranscriptError, TranscriptStore,
+};
+
+/// Number of sessions shown by `/sessions`
+pub const SESSION_LIST_LIMIT: usize = 20;
+
+/// Records the current CLI conversation into the transcript store
+#[derive(Debug, Clone)]
+pub struct SessionRecorder {
+    store: TranscriptStore,
+    session_id: String,
+}
+
+impl SessionRecorder {
+    /// Start a new session, or continue `resume` (an id or unique id prefix)
+    ///
+    /// Returns the recorder and the entries already stored for the session.
+    pub async fn start(
+        model: Option<&str>,
+        resume: Option<&str>,
+    ) -> Result<(Self, Vec<TranscriptEntry>), TranscriptError> {
+        let store = TranscriptStore::open().await?;
+
+        let (session_id, history) = match resume {
+            Some(id) => {
+                let session = store.resolve_session(id).await?;
+                let history = store.load(&session.session_id).await?;
+                (session.session_id, history)
+            }
+            None => (store.create_session(model).await?, Vec::new()),
+        };
+
+        Ok((Self { store, session_id }, history))
+    }
+
+    /// Id of the session being recorded
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Append an entry, logging instead of failing the chat on errors
+    pub async fn record(&self, entry: TranscriptEntry) {
+        if let Err(e) = self.store.append(&self.session_id, &entry).await {
+            log::warn!("Failed to persist transcript entry: {}", e);
+        }
+    }
+
+    /// Most recently updated sessions
+    pub async fn list(&self) -> Result<Vec<SessionSummary>, TranscriptError> {
+        self.store.list_sessions(SESSION_LIST_LIMIT).await
+    }
+}
+
+/// Format sessions for display, marking the current one
+pub fn format_sessions(sessions: &[SessionSummary], current: Option<&str>) -> String {
+    if sessions.is_empty() {
+        return "No saved sessions".to_string();
+    }
+
+    let mut lines = vec!["Saved sessions (resume with --resume <id>):".to_string()];
+    for session in sessions {
+        let marker = if current == Some(session.session_id.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        let updated = chrono::DateTime::from_timestamp_millis(session.updated_at_ms)
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let title = if session.title.is_empty() {
+            "(empty)"
+        } else {
+            session.title.as_str()
+        };
+        lines.push(format!(
+            "{} {}  {}  {:>3} msgs  {:>6} tokens  {}",
+            marker,
+            &session.session_id[..session.session_id.len().min(8)],
+            updated,
+            session.message_count,
+            session.total_tokens,
+            title
+        ));
+    }
+    lines.join("\n")
+}
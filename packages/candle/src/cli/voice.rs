@@ -0,0 +1,554 @@
+//! Voice mode for the interactive chat
+//!
+//! With `--voice` the user turn is captured from the microphone through a
+//! [`VoiceService`] and the streamed response is spoken back one sentence at a
+//! time, so speech starts before generation finishes. While a sentence is
+//! playing the microphone stays open in short windows; when the user starts
+//! talking the playback is dropped (barge-in) and what they said becomes the
+//! next user turn.
+//!
+//! [`CliVoiceService`] runs the local speech models of [`LocalVoiceService`]
+//! by default. Setting [`LISTEN_COMMAND_ENV`] switches to
+//! [`CommandVoiceService`], which drives external speech programs, so any
+//! text-to-speech and speech-to-text tool can back voice mode instead.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use sweetmcp_voice_tools::ssml;
+use sweetmcp_voice_tools::{
+    ListenEvent, ListenParams, ListenResult, ListenStreamParams, SpeakParams, SynthesisResult,
+    SynthesizeParams, TranscribeFileParams, TranscriptOptions, VoiceConfig, VoiceError,
+    VoiceResult, VoiceService,
+};
+use tokio::sync::{mpsc, watch};
+
+use crate::domain::chat::message::CandleMessageChunk;
+use crate::domain::voice::LocalVoiceService;
+
+/// Environment variable holding the text-to-speech command
+pub const SPEAK_COMMAND_ENV: &str = "CANDLE_VOICE_SPEAK_COMMAND";
+
+/// Environment variable holding the speech-to-text command
+pub const LISTEN_COMMAND_ENV: &str = "CANDLE_VOICE_LISTEN_COMMAND";
+
+/// Seconds of each listen window while waiting for a user turn, for
+/// services that cannot detect the end of speech
+pub const UTTERANCE_SECS: u32 = 8;
+
+/// Longest user turn in seconds; the turn ends earlier when the user stops
+/// talking
+pub const MAX_UTTERANCE_SECS: u32 = 60;
+
+/// Seconds of each listen window while checking for barge-in
+pub const BARGE_IN_SECS: u32 = 1;
+
+/// Splits streamed text into speakable sentences
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace, or at a line
+/// break. Text after the last boundary is held until more arrives or the turn
+/// finishes.
+#[derive(Debug, Default)]
+pub struct SentenceChunker {
+    buffer: String,
+}
+
+impl SentenceChunker {
+    /// Create an empty chunker
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add streamed text and return the sentences it completed
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        self.buffer.push_str(text);
+
+        let mut sentences = Vec::new();
+        let mut start = 0;
+        let mut chars = self.buffer.char_indices().peekable();
+        while let Some((index, ch)) = chars.next() {
+            let end = match ch {
+                '\n' => index + 1,
+                '.' | '!' | '?' => match chars.peek() {
+                    Some((next, following)) if following.is_whitespace() => *next,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            if let Some(sentence) = speakable(&self.buffer[start..end]) {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+
+        self.buffer.drain(..start);
+        sentences
+    }
+
+    /// Return whatever text is left at the end of a turn
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = std::mem::take(&mut self.buffer);
+        speakable(&rest)
+    }
+}
+
+/// Strip markdown markers that should not be read aloud
+fn speakable(text: &str) -> Option<String> {
+    let cleaned: String = text
+        .chars()
+        .filter(|ch| !matches!(ch, '*' | '#' | '`'))
+        .collect();
+    let cleaned = cleaned.trim();
+    (!cleaned.is_empty()).then(|| cleaned.to_string())
+}
+
+/// Voice mode settings
+#[derive(Debug, Clone)]
+pub struct VoiceSettings {
+    /// Voice to speak with (service default when unset)
+    pub voice_id: Option<String>,
+    /// Microphone to listen on
+    pub microphone_id: String,
+    /// Stop speaking when the user talks over the response
+    pub barge_in: bool,
+    /// Silence that ends a user turn, in milliseconds (service default when
+    /// unset)
+    pub silence_timeout_ms: Option<u32>,
+}
+
+impl Default for VoiceSettings {
+    fn default() -> Self {
+        let config = VoiceConfig::default();
+        Self {
+            voice_id: config.default_voice,
+            microphone_id: config
+                .default_microphone
+                .unwrap_or_else(|| "default".to_string()),
+            barge_in: true,
+            silence_timeout_ms: None,
+        }
+    }
+}
+
+impl VoiceSettings {
+    fn listen_params(&self, duration_seconds: u32) -> ListenParams {
+        ListenParams {
+            microphone_id: self.microphone_id.clone(),
+            duration_seconds,
+            wake_word: None,
+            options: TranscriptOptions::default(),
+        }
+    }
+
+    fn utterance_params(&self) -> ListenStreamParams {
+        ListenStreamParams {
+            microphone_id: self.microphone_id.clone(),
+            max_duration_seconds: MAX_UTTERANCE_SECS,
+            silence_timeout_ms: self.silence_timeout_ms,
+            vad_sensitivity: None,
+            partial_interval_ms: None,
+            wake_word: None,
+            options: TranscriptOptions::default(),
+        }
+    }
+}
+
+/// Speak one sentence, stopping early if the user starts talking
+///
+/// Returns what the user said when they interrupted, or `None` when the
+/// sentence played to the end.
+pub async fn speak_with_barge_in<V: VoiceService>(
+    service: &V,
+    settings: &VoiceSettings,
+    text: String,
+) -> VoiceResult<Option<String>> {
+    let speak = service.speak(SpeakParams {
+        text,
+        voice_id: settings.voice_id.clone(),
+        speed: None,
+        pitch: None,
+        volume: None,
+        ssml: false,
+    });
+    if !settings.barge_in {
+        return speak.await.map(|()| None);
+    }
+
+    tokio::select! {
+        result = speak => result.map(|()| None),
+        heard = hear_interruption(service, settings) => Ok(Some(heard)),
+    }
+}
+
+/// Listen in short windows until the user says something
+///
+/// A microphone failure disables barge-in for the sentence instead of
+/// cutting the speech short.
+async fn hear_interruption<V: VoiceService>(service: &V, settings: &VoiceSettings) -> String {
+    loop {
+        match service.listen(settings.listen_params(BARGE_IN_SECS)).await {
+            Ok(ListenResult { text, .. }) if !text.trim().is_empty() => {
+                return text.trim().to_string();
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log::debug!("Barge-in listening failed: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+enum SpeechEvent {
+    Text(String),
+    EndOfTurn,
+}
+
+/// Voice input and output for a chat session
+///
+/// Feed every response chunk to [`VoiceMode::observe`] and take user turns
+/// from [`VoiceMode::next_utterance`]. Speech runs on a background task so
+/// chunk handling never waits for playback.
+pub struct VoiceMode<V> {
+    service: Arc<V>,
+    settings: VoiceSettings,
+    events: mpsc::UnboundedSender<SpeechEvent>,
+    speaking: Arc<watch::Sender<bool>>,
+    interruption: Arc<Mutex<Option<String>>>,
+}
+
+impl<V: VoiceService + 'static> VoiceMode<V> {
+    /// Start the speaker task; must be called inside a tokio runtime
+    pub fn start(service: V, settings: VoiceSettings) -> Self {
+        let service = Arc::new(service);
+        let (events, receiver) = mpsc::unbounded_channel();
+        let speaking = Arc::new(watch::Sender::new(false));
+        let interruption = Arc::new(Mutex::new(None));
+
+        tokio::spawn(run_speaker(
+            service.clone(),
+            settings.clone(),
+            receiver,
+            speaking.clone(),
+            interruption.clone(),
+        ));
+
+        Self {
+            service,
+            settings,
+            events,
+            speaking,
+            interruption,
+        }
+    }
+
+    /// Queue a response chunk for speech
+    ///
+    /// `Complete` and `Error` chunks end the turn; other non-text chunks are
+    /// ignored.
+    pub fn observe(&self, chunk: &CandleMessageChunk) {
+        match chunk {
+            CandleMessageChunk::Text(text) => self.push_text(text),
+            CandleMessageChunk::Complete { text, .. } => {
+                self.push_text(text);
+                self.end_turn();
+            }
+            CandleMessageChunk::Error(_) => self.end_turn(),
+            _ => {}
+        }
+    }
+
+    /// Wait for the current response to finish speaking, then return the
+    /// next thing the user says
+    ///
+    /// If the user interrupted the response, that speech is returned
+    /// without listening again.
+    pub async fn next_utterance(&self) -> VoiceResult<String> {
+        let mut speaking = self.speaking.subscribe();
+        // The sender lives as long as self, so waiting cannot fail
+        let _ = speaking.wait_for(|speaking| !*speaking).await;
+
+        if let Some(heard) = self.interruption.lock().take() {
+            return Ok(heard);
+        }
+
+        loop {
+            // Partial transcripts are not shown; the turn ends at the first
+            // pause longer than the silence timeout
+            let (events, _) = mpsc::unbounded_channel();
+            let result = self
+                .service
+                .listen_stream(self.settings.utterance_params(), events)
+                .await?;
+            let text = result.text.trim();
+            if !text.is_empty() {
+                return Ok(text.to_string());
+            }
+        }
+    }
+
+    fn push_text(&self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        // Marked before the speaker task sees the text so a turn that is
+        // requested right away still waits for the speech
+        self.speaking.send_replace(true);
+        let _ = self.events.send(SpeechEvent::Text(text.to_string()));
+    }
+
+    fn end_turn(&self) {
+        let _ = self.events.send(SpeechEvent::EndOfTurn);
+    }
+}
+
+async fn run_speaker<V: VoiceService>(
+    service: Arc<V>,
+    settings: VoiceSettings,
+    mut events: mpsc::UnboundedReceiver<SpeechEvent>,
+    speaking: Arc<watch::Sender<bool>>,
+    interruption: Arc<Mutex<Option<String>>>,
+) {
+    let mut chunker = SentenceChunker::new();
+    let mut interrupted = false;
+
+    while let Some(event) = events.recv().await {
+        let end_of_turn = matches!(event, SpeechEvent::EndOfTurn);
+        let sentences = match event {
+            SpeechEvent::Text(text) => chunker.push(&text),
+            SpeechEvent::EndOfTurn => chunker.finish().into_iter().collect(),
+        };
+
+        // The rest of an interrupted turn is discarded
+        for sentence in sentences {
+            if interrupted {
+                break;
+            }
+            match speak_with_barge_in(service.as_ref(), &settings, sentence).await {
+                Ok(Some(heard)) => {
+                    *interruption.lock() = Some(heard);
+                    interrupted = true;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Speech synthesis failed: {}", e),
+            }
+        }
+
+        if end_of_turn {
+            chunker = SentenceChunker::new();
+            interrupted = false;
+            speaking.send_replace(false);
+        }
+    }
+}
+
+/// [`VoiceService`] backed by external speech commands
+///
+/// The speak command receives the text as its last argument. The listen
+/// command must record from the microphone and print the transcript to
+/// stdout; `{microphone}` and `{seconds}` in its arguments are replaced with
+/// the requested device and duration. Commands are split on whitespace.
+/// Playback is killed when a barge-in drops the speak future.
+#[derive(Debug, Clone)]
+pub struct CommandVoiceService {
+    speak_command: Vec<String>,
+    listen_command: Vec<String>,
+}
+
+impl CommandVoiceService {
+    /// Create a service from a speak and a listen command line
+    #[must_use]
+    pub fn new(speak_command: &str, listen_command: &str) -> Self {
+        Self {
+            speak_command: split_command(speak_command),
+            listen_command: split_command(listen_command),
+        }
+    }
+
+    /// Create a service from [`SPEAK_COMMAND_ENV`] and [`LISTEN_COMMAND_ENV`]
+    ///
+    /// The speak command defaults to `say` on macOS and `espeak-ng`
+    /// elsewhere. There is no default speech-to-text program, so the listen
+    /// command is required.
+    pub fn from_env() -> VoiceResult<Self> {
+        let speak_command = std::env::var(SPEAK_COMMAND_ENV).unwrap_or_else(|_| {
+            if cfg!(target_os = "macos") {
+                "say".to_string()
+            } else {
+                "espeak-ng".to_string()
+            }
+        });
+        let listen_command = std::env::var(LISTEN_COMMAND_ENV).map_err(|_| {
+            VoiceError::ServiceUnavailable(format!(
+                "set {} to a speech-to-text command that prints the transcript",
+                LISTEN_COMMAND_ENV
+            ))
+        })?;
+        Ok(Self::new(&speak_command, &listen_command))
+    }
+}
+
+fn split_command(command: &str) -> Vec<String> {
+    command.split_whitespace().map(str::to_string).collect()
+}
+
+/// Run a command to completion and return its stdout
+async fn run_command(command: &[String], trailing: Option<&str>) -> VoiceResult<String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| VoiceError::ServiceUnavailable("empty voice command".to_string()))?;
+
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .args(trailing)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(VoiceError::ServiceUnavailable(format!(
+            "{} exited with {}: {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl VoiceService for CommandVoiceService {
+    /// External commands take plain text, so SSML markup is stripped and
+    /// prosody is left to the command
+    async fn speak(&self, params: SpeakParams) -> VoiceResult<()> {
+        let text = ssml::plain_text(&params.speech_parts()?);
+        run_command(&self.speak_command, Some(&text))
+            .await
+            .map(drop)
+            .map_err(|e| VoiceError::SynthesisFailed(e.to_string()))
+    }
+
+    async fn listen(&self, params: ListenParams) -> VoiceResult<ListenResult> {
+        let seconds = params.duration_seconds.to_string();
+        let command: Vec<String> = self
+            .listen_command
+            .iter()
+            .map(|arg| {
+                arg.replace("{microphone}", &params.microphone_id)
+                    .replace("{seconds}", &seconds)
+            })
+            .collect();
+
+        let transcript = run_command(&command, None)
+            .await
+            .map_err(|e| VoiceError::TranscriptionFailed(e.to_string()))?;
+        Ok(ListenResult {
+            text: transcript.trim().to_string(),
+            wake_word_detected: None,
+            confidence: None,
+            language: None,
+            language_confidence: None,
+            segments: None,
+            words: None,
+        })
+    }
+
+    /// External commands cannot tell when the user stops talking, so this
+    /// records one [`UTTERANCE_SECS`] window and reports no progress
+    async fn listen_stream(
+        &self,
+        params: ListenStreamParams,
+        _events: mpsc::UnboundedSender<ListenEvent>,
+    ) -> VoiceResult<ListenResult> {
+        let mut params = ListenParams::from(params);
+        params.duration_seconds = params.duration_seconds.min(UTTERANCE_SECS);
+        self.listen(params).await
+    }
+
+    async fn list_voices(&self) -> VoiceResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_microphones(&self) -> VoiceResult<Vec<String>> {
+        Ok(vec!["default".to_string()])
+    }
+}
+
+/// [`VoiceService`] used by the CLI
+///
+/// Local models unless external speech commands are configured.
+#[derive(Debug, Clone)]
+pub enum CliVoiceService {
+    /// Whisper and Parler-TTS running in-process
+    Local(LocalVoiceService),
+    /// External speech programs
+    Command(CommandVoiceService),
+}
+
+impl CliVoiceService {
+    /// Use [`CommandVoiceService::from_env`] when [`LISTEN_COMMAND_ENV`] is
+    /// set, else a [`LocalVoiceService`] on the best available device
+    pub fn from_env() -> VoiceResult<Self> {
+        if std::env::var_os(LISTEN_COMMAND_ENV).is_some() {
+            CommandVoiceService::from_env().map(Self::Command)
+        } else {
+            Ok(Self::Local(LocalVoiceService::new()))
+        }
+    }
+}
+
+impl VoiceService for CliVoiceService {
+    async fn speak(&self, params: SpeakParams) -> VoiceResult<()> {
+        match self {
+            Self::Local(service) => service.speak(params).await,
+            Self::Command(service) => service.speak(params).await,
+        }
+    }
+
+    async fn listen(&self, params: ListenParams) -> VoiceResult<ListenResult> {
+        match self {
+            Self::Local(service) => service.listen(params).await,
+            Self::Command(service) => service.listen(params).await,
+        }
+    }
+
+    async fn listen_stream(
+        &self,
+        params: ListenStreamParams,
+        events: mpsc::UnboundedSender<ListenEvent>,
+    ) -> VoiceResult<ListenResult> {
+        match self {
+            Self::Local(service) => service.listen_stream(params, events).await,
+            Self::Command(service) => service.listen_stream(params, events).await,
+        }
+    }
+
+    async fn transcribe_file(&self, params: TranscribeFileParams) -> VoiceResult<ListenResult> {
+        match self {
+            Self::Local(service) => service.transcribe_file(params).await,
+            Self::Command(service) => service.transcribe_file(params).await,
+        }
+    }
+
+    async fn synthesize(&self, params: SynthesizeParams) -> VoiceResult<SynthesisResult> {
+        match self {
+            Self::Local(service) => service.synthesize(params).await,
+            Self::Command(service) => service.synthesize(params).await,
+        }
+    }
+
+    async fn list_voices(&self) -> VoiceResult<Vec<String>> {
+        match self {
+            Self::Local(service) => service.list_voices().await,
+            Self::Command(service) => service.list_voices().await,
+        }
+    }
+
+    async fn list_microphones(&self) -> VoiceResult<Vec<String>> {
+        match self {
+            Self::Local(service) => service.list_microphones().await,
+            Self::Command(service) => service.list_microphones().await,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Context window management with configurable overflow strategies
+//!
+//! Prompts are checked against the model's context window before the prefill
+//! pass. When the prompt plus the requested completion does not fit, the
+//! configured [`ContextOverflowPolicy`] decides what happens:
+//! - `SlidingWindow` drops the oldest tokens after a pinned prefix
+//! - `Summarize` is applied to the conversation text before tokenization
+//!   (see `domain::chat::compaction` and `domain::chat::transcript::fit_history`);
+//!   tokens that still do not fit are dropped like `SlidingWindow`
+//! - `Abort` rejects the request with [`ContextOverflowError`]
+//!
+//! Every fit produces a [`TurnTokenUsage`] describing the turn's token budget.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Key in `CandleCompletionParams::additional_params` selecting the policy
+pub const CONTEXT_OVERFLOW_PARAM: &str = "context_overflow";
+
+/// Rough characters-per-token ratio used when no tokenizer is at hand
+const CHARS_PER_TOKEN: usize = 4;
+
+/// What to do when a prompt does not fit the context window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextOverflowPolicy {
+    /// Drop the oldest tokens until the prompt fits
+    #[default]
+    SlidingWindow,
+    /// Compress older conversation turns into a summary, keeping details in memory
+    Summarize,
+    /// Fail the request
+    Abort,
+}
+
+impl ContextOverflowPolicy {
+    /// Policy name as accepted by [`FromStr`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SlidingWindow => "sliding_window",
+            Self::Summarize => "summarize",
+            Self::Abort => "abort",
+        }
+    }
+
+    /// Policy requested through [`CONTEXT_OVERFLOW_PARAM`] in `additional_params`
+    ///
+    /// Unknown names are logged and ignored.
+    pub fn from_params(additional_params: Option<&serde_json::Value>) -> Option<Self> {
+        additional_params
+            .and_then(|params| params.get(CONTEXT_OVERFLOW_PARAM))
+            .and_then(|value| value.as_str())
+            .and_then(|name| name.parse().map_err(|e: String| log::warn!("{}", e)).ok())
+    }
+}
+
+impl fmt::Display for ContextOverflowPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContextOverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "sliding_window" | "sliding" | "window" => Ok(Self::SlidingWindow),
+            "summarize" | "summary" => Ok(Self::Summarize),
+            "abort" | "error" => Ok(Self::Abort),
+            other => Err(format!(
+                "Unknown context overflow policy '{}' (expected sliding_window, summarize or abort)",
+                other
+            )),
+        }
+    }
+}
+
+/// Prompt rejected by the `Abort` policy
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Prompt needs {required} tokens but the context window leaves room for {available}")]
+pub struct ContextOverflowError {
+    /// Prompt tokens requested
+    pub required: usize,
+    /// Prompt tokens the window allows
+    pub available: usize,
+}
+
+/// Token accounting for one turn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnTokenUsage {
+    /// Prompt tokens sent to the model after overflow handling
+    pub prompt_tokens: usize,
+    /// Prompt tokens dropped by the overflow policy
+    pub evicted_tokens: usize,
+    /// Completion tokens allowed for this turn
+    pub completion_budget: usize,
+    /// Size of the context window
+    pub context_window: usize,
+}
+
+impl TurnTokenUsage {
+    /// Fraction of the window used by prompt and completion budget, in percent
+    pub fn utilization(&self) -> f64 {
+        if self.context_window > 0 {
+            (self.prompt_tokens + self.completion_budget) as f64 / self.context_window as f64
+                * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A prompt fitted to the context window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FittedPrompt {
+    /// Prompt tokens to prefill
+    pub tokens: Vec<u32>,
+    /// Completion tokens allowed
+    pub max_new_tokens: usize,
+    /// Token accounting for the turn
+    pub usage: TurnTokenUsage,
+}
+
+/// Context window size and overflow handling for a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextWindow {
+    /// Total tokens the model can attend to
+    pub max_tokens: usize,
+    /// What to do when a prompt does not fit
+    pub policy: ContextOverflowPolicy,
+    /// Leading prompt tokens never evicted (BOS, system preamble)
+    pub pinned_prefix: usize,
+}
+
+impl ContextWindow {
+    /// Create a window of `max_tokens` with the given policy
+    pub fn new(max_tokens: usize, policy: ContextOverflowPolicy) -> Self {
+        Self {
+            max_tokens,
+            policy,
+            pinned_prefix: 0,
+        }
+    }
+
+    /// Window of `max_tokens` using the policy named by `additional_params`
+    ///
+    /// Falls back to the default policy when none is requested.
+    pub fn from_params(max_tokens: usize, additional_params: Option<&serde_json::Value>) -> Self {
+        Self::new(
+            max_tokens,
+            ContextOverflowPolicy::from_params(additional_params).unwrap_or_default(),
+        )
+    }
+
+    /// Keep the first `tokens` prompt tokens when evicting
+    #[must_use]
+    pub fn with_pinned_prefix(mut self, tokens: usize) -> Self {
+        self.pinned_prefix = tokens;
+        self
+    }
+
+    /// Use a different overflow policy
+    #[must_use]
+    pub fn with_policy(mut self, policy: ContextOverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Prompt tokens available once `max_new_tokens` are reserved
+    ///
+    /// At most half the window is reserved for the completion so that a large
+    /// `max_tokens` cannot crowd out the prompt entirely.
+    pub fn prompt_budget(&self, max_new_tokens: usize) -> usize {
+        self.max_tokens - max_new_tokens.min(self.max_tokens / 2)
+    }
+
+    /// Fit `tokens` plus a completion of up to `max_new_tokens` into the window
+    ///
+    /// # Errors
+    /// Returns `ContextOverflowError` when the prompt does not fit and the
+    /// policy is `Abort`
+    pub fn fit(
+        &self,
+        mut tokens: Vec<u32>,
+        max_new_tokens: usize,
+    ) -> Result<FittedPrompt, ContextOverflowError> {
+        let budget = self.prompt_budget(max_new_tokens);
+        let mut evicted = 0;
+
+        if tokens.len() > budget {
+            if self.policy == ContextOverflowPolicy::Abort {
+                return Err(ContextOverflowError {
+                    required: tokens.len(),
+                    available: budget,
+                });
+            }
+            let pinned = self.pinned_prefix.min(budget);
+            evicted = tokens.len() - budget;
+            tokens.drain(pinned..pinned + evicted);
+        }
+
+        let max_new_tokens = max_new_tokens.min(self.max_tokens - tokens.len());
+        Ok(FittedPrompt {
+            usage: TurnTokenUsage {
+                prompt_tokens: tokens.len(),
+                evicted_tokens: evicted,
+                completion_budget: max_new_tokens,
+                context_window: self.max_tokens,
+            },
+            tokens,
+            max_new_tokens,
+        })
+    }
+}
+
+/// Estimate the token count of text without a tokenizer
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
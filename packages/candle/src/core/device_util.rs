@@ -4,19 +4,138 @@
 //! 1. CUDA (if available) - NVIDIA GPUs
 //! 2. Metal (if available) - Apple Silicon/AMD GPUs on macOS  
 //! 3. CPU (fallback) - Always available
+//!
+//! The choice can be made explicit with [`configure_devices`] (the CLI's
+//! `--device cpu|metal|cuda:N`), which every model loader honours through
+//! [`detect_best_device`]. [`detect_accelerators`] lists what the machine
+//! offers, with memory, for diagnostics and layer placement planning.
+
+use std::fmt;
+use std::str::FromStr;
 
 use candle_core::Device;
 #[cfg(feature = "cuda")]
 use candle_core::utils::cuda_is_available;
 #[cfg(feature = "metal")]
 use candle_core::utils::metal_is_available;
-use log::info;
-#[cfg(any(feature = "cuda", feature = "metal"))]
-use log::warn;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+/// Compute device requested by configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeviceSpec {
+    /// Best available: CUDA, then Metal, then CPU
+    #[default]
+    Auto,
+    /// Host CPU
+    Cpu,
+    /// Metal GPU by ordinal
+    Metal(usize),
+    /// CUDA GPU by ordinal
+    Cuda(usize),
+}
+
+impl DeviceSpec {
+    /// Whether the spec names a GPU
+    pub fn is_gpu(&self) -> bool {
+        matches!(self, Self::Metal(_) | Self::Cuda(_))
+    }
+}
+
+impl fmt::Display for DeviceSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Cpu => write!(f, "cpu"),
+            Self::Metal(ordinal) => write!(f, "metal:{}", ordinal),
+            Self::Cuda(ordinal) => write!(f, "cuda:{}", ordinal),
+        }
+    }
+}
+
+impl FromStr for DeviceSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let (kind, ordinal) = match s.split_once(':') {
+            Some((kind, ordinal)) => {
+                let ordinal = ordinal
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid device ordinal in '{}'", s))?;
+                (kind, Some(ordinal))
+            }
+            None => (s.as_str(), None),
+        };
+
+        match (kind, ordinal) {
+            ("auto", None) => Ok(Self::Auto),
+            ("cpu", None) => Ok(Self::Cpu),
+            ("metal", ordinal) => Ok(Self::Metal(ordinal.unwrap_or(0))),
+            ("cuda", ordinal) => Ok(Self::Cuda(ordinal.unwrap_or(0))),
+            _ => Err(format!(
+                "Unknown device '{}' (expected auto, cpu, metal[:N] or cuda[:N])",
+                s
+            )),
+        }
+    }
+}
+
+/// Process-wide device selection
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeviceConfig {
+    /// Device models load on
+    pub device: DeviceSpec,
+    /// Devices to shard large checkpoints across, layer-wise in order
+    ///
+    /// When set, models load on the first shard device.
+    pub shard_devices: Vec<DeviceSpec>,
+}
+
+impl DeviceConfig {
+    /// Device that holds the first layers, where models are loaded
+    pub fn primary(&self) -> DeviceSpec {
+        self.shard_devices.first().copied().unwrap_or(self.device)
+    }
+}
+
+static DEVICE_CONFIG: Lazy<RwLock<DeviceConfig>> =
+    Lazy::new(|| RwLock::new(DeviceConfig::default()));
+
+/// Set the devices models load on from now on
+///
+/// Models that are already loaded stay where they are.
+pub fn configure_devices(config: DeviceConfig) {
+    info!("Device configuration: {:?}", config);
+    *DEVICE_CONFIG.write() = config;
+}
+
+/// Current device configuration
+pub fn device_config() -> DeviceConfig {
+    DEVICE_CONFIG.read().clone()
+}
+
+/// Open the device named by `spec`
+///
+/// `Auto` picks the best available device.
+///
+/// # Errors
+/// Returns the backend error if the device does not exist or this build
+/// lacks its feature (`cuda` / `metal`)
+pub fn open_device(spec: DeviceSpec) -> candle_core::Result<Device> {
+    match spec {
+        DeviceSpec::Auto => detect_available_device(),
+        DeviceSpec::Cpu => Ok(Device::Cpu),
+        DeviceSpec::Metal(ordinal) => Device::new_metal(ordinal),
+        DeviceSpec::Cuda(ordinal) => Device::new_cuda(ordinal),
+    }
+}
 
 /// Detects and returns the best available compute device.
 ///
-/// Priority order:
+/// An explicitly configured device (see [`configure_devices`]) is used when
+/// it can be opened; otherwise, and for `auto`, the priority order is:
 /// 1. CUDA GPU (ordinal 0) if CUDA feature enabled
 /// 2. Metal GPU (ordinal 0) if Metal feature enabled
 /// 3. CPU as fallback
@@ -25,6 +144,24 @@ use log::warn;
 /// - `Ok(Device)` - Best available device
 /// - `Err(_)` - Only if GPU initialization fails (falls back to CPU with warning)
 pub fn detect_best_device() -> candle_core::Result<Device> {
+    let spec = DEVICE_CONFIG.read().primary();
+    if spec != DeviceSpec::Auto {
+        match open_device(spec) {
+            Ok(device) => {
+                info!("Using configured device {} for inference", spec);
+                return Ok(device);
+            }
+            Err(e) => warn!(
+                "Configured device {} failed to initialize: {}. Falling back to automatic selection.",
+                spec, e
+            ),
+        }
+    }
+    detect_available_device()
+}
+
+/// Best available device, ignoring the configuration
+fn detect_available_device() -> candle_core::Result<Device> {
     #[cfg(feature = "cuda")]
     {
         if cuda_is_available() {
@@ -64,3 +201,102 @@ pub fn detect_best_device() -> candle_core::Result<Device> {
     info!("Using CPU for inference (no GPU available or GPU initialization failed)");
     Ok(Device::Cpu)
 }
+
+/// A compute device found on this machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceleratorInfo {
+    /// How to select the device
+    pub spec: DeviceSpec,
+    /// Device name reported by the driver
+    pub name: String,
+    /// Total memory in bytes, when known
+    pub total_bytes: Option<u64>,
+    /// Memory available for new allocations in bytes, when known
+    pub free_bytes: Option<u64>,
+}
+
+impl AcceleratorInfo {
+    /// Bytes a placement may use: free memory, else total memory
+    pub fn capacity_bytes(&self) -> Option<u64> {
+        self.free_bytes.or(self.total_bytes)
+    }
+}
+
+/// List the CPU and every GPU this build can use, with memory
+///
+/// GPUs appear only when the matching feature (`cuda` / `metal`) is enabled.
+pub fn detect_accelerators() -> Vec<AcceleratorInfo> {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    let mut accelerators = vec![AcceleratorInfo {
+        spec: DeviceSpec::Cpu,
+        name: format!("CPU ({} threads)", num_cpus::get()),
+        total_bytes: Some(system.total_memory()),
+        free_bytes: Some(system.available_memory()),
+    }];
+
+    #[cfg(feature = "cuda")]
+    accelerators.extend(detect_cuda_devices());
+
+    #[cfg(feature = "metal")]
+    accelerators.extend(detect_metal_devices());
+
+    accelerators
+}
+
+#[cfg(feature = "cuda")]
+fn detect_cuda_devices() -> Vec<AcceleratorInfo> {
+    use cudarc::driver::CudaContext;
+
+    if !cuda_is_available() {
+        return Vec::new();
+    }
+    let count = match CudaContext::device_count() {
+        Ok(count) => usize::try_from(count).unwrap_or(0),
+        Err(e) => {
+            warn!("Failed to count CUDA devices: {}", e);
+            return Vec::new();
+        }
+    };
+
+    (0..count)
+        .filter_map(|ordinal| match CudaContext::new(ordinal) {
+            Ok(context) => {
+                let memory = context.mem_get_info().ok();
+                Some(AcceleratorInfo {
+                    spec: DeviceSpec::Cuda(ordinal),
+                    name: context
+                        .name()
+                        .unwrap_or_else(|_| format!("CUDA device {}", ordinal)),
+                    total_bytes: memory.map(|(_, total)| total as u64),
+                    free_bytes: memory.map(|(free, _)| free as u64),
+                })
+            }
+            Err(e) => {
+                warn!("Failed to open CUDA device {}: {}", ordinal, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "metal")]
+fn detect_metal_devices() -> Vec<AcceleratorInfo> {
+    if !metal_is_available() {
+        return Vec::new();
+    }
+
+    metal::Device::all()
+        .iter()
+        .enumerate()
+        .map(|(ordinal, device)| {
+            let total = device.recommended_max_working_set_size();
+            AcceleratorInfo {
+                spec: DeviceSpec::Metal(ordinal),
+                name: device.name().to_string(),
+                total_bytes: Some(total),
+                free_bytes: Some(total.saturating_sub(device.current_allocated_size())),
+            }
+        })
+        .collect()
+}
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio_stream::Stream;
 
+use super::context_window::{ContextOverflowPolicy, ContextWindow, FittedPrompt, TurnTokenUsage};
 use crate::domain::context::chunks::{CandleCompletionChunk, CandleStringChunk};
 use crate::domain::model::CandleUsage;
 
@@ -65,6 +66,10 @@ pub enum EngineError {
     #[error("Internal error: {0}")]
     /// An unexpected internal error occurred
     InternalError(String),
+
+    #[error("Context window exceeded: {0}")]
+    /// The prompt does not fit the context window and the policy is abort
+    ContextOverflow(String),
 }
 
 /// Result type for engine operations
@@ -89,6 +94,9 @@ pub struct EngineConfig {
     pub enable_streaming: bool,
     /// Custom endpoint URL override
     pub endpoint_url: Option<String>,
+    /// Context window and overflow policy for local models
+    #[serde(default)]
+    pub context_window: Option<ContextWindow>,
 }
 
 impl Default for EngineConfig {
@@ -103,6 +111,7 @@ impl Default for EngineConfig {
             temperature: Some(0.0), // Global default: greedy sampling for deterministic output
             enable_streaming: false,
             endpoint_url: None,
+            context_window: None,
         }
     }
 }
@@ -166,6 +175,14 @@ impl EngineConfig {
         self
     }
 
+    /// Set the context window and overflow policy
+    #[must_use]
+    #[inline]
+    pub fn with_context_window(mut self, context_window: ContextWindow) -> Self {
+        self.context_window = Some(context_window);
+        self
+    }
+
     /// Validate configuration
     #[inline]
     pub fn validate(&self) -> EngineResult<()> {
@@ -268,6 +285,50 @@ impl Engine {
         self.is_healthy.store(healthy, Ordering::Relaxed);
     }
 
+    /// Fit prompt tokens into the configured context window
+    ///
+    /// Without a configured window the prompt is returned unchanged. `policy`
+    /// overrides the configured overflow policy for this request.
+    ///
+    /// # Errors
+    /// Returns `EngineError::ContextOverflow` when the prompt does not fit and
+    /// the policy is abort
+    pub fn fit_prompt(
+        &self,
+        tokens: Vec<u32>,
+        max_new_tokens: usize,
+        policy: Option<ContextOverflowPolicy>,
+    ) -> EngineResult<FittedPrompt> {
+        let Some(mut window) = self.config.context_window else {
+            return Ok(FittedPrompt {
+                usage: TurnTokenUsage {
+                    prompt_tokens: tokens.len(),
+                    evicted_tokens: 0,
+                    completion_budget: max_new_tokens,
+                    context_window: 0,
+                },
+                tokens,
+                max_new_tokens,
+            });
+        };
+
+        if let Some(policy) = policy {
+            window.policy = policy;
+        }
+        let fitted = window
+            .fit(tokens, max_new_tokens)
+            .map_err(|e| EngineError::ContextOverflow(e.to_string()))?;
+        if fitted.usage.evicted_tokens > 0 {
+            log::warn!(
+                "Context window of {} tokens exceeded: dropped {} oldest prompt tokens ({})",
+                window.max_tokens,
+                fitted.usage.evicted_tokens,
+                window.policy
+            );
+        }
+        Ok(fitted)
+    }
+
     /// Coordinate text generation with metrics and streaming management
     ///
     /// Provides orchestration services for providers:
@@ -399,13 +460,14 @@ impl Engine {
                                 Some(crate::domain::context::chunks::FinishReason::Stop)
                             },
                             usage: Some(CandleUsage {
-                                input_tokens: 0,
+                                input_tokens: gen_stats.input_tokens,
                                 output_tokens: gen_stats.tokens_generated,
-                                total_tokens: gen_stats.tokens_generated,
+                                total_tokens: gen_stats.input_tokens + gen_stats.tokens_generated,
                             }),
                             token_count: Some(gen_stats.tokens_generated),
                             elapsed_secs: Some(gen_stats.elapsed_secs),
                             tokens_per_sec: Some(gen_stats.tokens_per_sec),
+                            provider: None,
                         }
                     }
                     CandleStringChunk {
@@ -426,6 +488,7 @@ impl Engine {
                             token_count: None,
                             elapsed_secs: None,
                             tokens_per_sec: None,
+                            provider: None,
                         }
                     }
                 };
@@ -16,10 +16,14 @@ use super::{
     config::SamplingConfig,
     metrics::SimdMetrics,
     models::CandleModel,
+    replay::ReplayRecorder,
+    speculative::{DraftModel, SpeculativeConfig},
     stats::GenerationStatistics,
+    structured::{OutputConstraint, StructuredOutput},
     tokens::{SpecialTokens, TokenHistory},
     types::CandleResult,
 };
+use crate::core::context_window::ContextWindow;
 
 // Import constraint types for schema-based generation
 use cyrup_simd::logits::constraints::{JsonConstraint, json::JsonState};
@@ -56,6 +60,21 @@ pub struct TextGenerator {
 
     /// Current JSON constraint state
     pub constraint_state: Option<JsonState>,
+
+    /// Optional draft model for speculative decoding
+    pub draft: Option<DraftModel>,
+
+    /// Optional context window applied to the prompt before prefill
+    pub context_window: Option<ContextWindow>,
+
+    /// Optional JSON Schema or grammar the output must match
+    pub structured_output: Option<StructuredOutput>,
+
+    /// Live constraint for `structured_output` during generation
+    pub output_constraint: Option<OutputConstraint>,
+
+    /// Optional recorder capturing the turn for deterministic replay
+    pub replay: Option<ReplayRecorder>,
 }
 impl TextGenerator {
     /// Create new TextGenerator
@@ -77,7 +96,74 @@ impl TextGenerator {
             simd_metrics: SimdMetrics::new(),
             constraint: None,
             constraint_state: None,
+            draft: None,
+            context_window: None,
+            structured_output: None,
+            output_constraint: None,
+            replay: None,
+        }
+    }
+
+    /// Fit prompts into `context_window` using its overflow policy
+    #[must_use]
+    pub fn with_context_window(mut self, context_window: ContextWindow) -> Self {
+        self.context_window = Some(context_window);
+        self
+    }
+
+    /// Constrain output to a JSON Schema or GBNF grammar
+    ///
+    /// Tokens that would leave the schema or grammar are masked before
+    /// sampling, and generation stops once the output is complete.
+    #[must_use]
+    pub fn with_structured_output(mut self, output: StructuredOutput) -> Self {
+        self.structured_output = Some(output);
+        self
+    }
+
+    /// Record the prompt, seed and streamed tokens of each turn
+    ///
+    /// Recorded turns can be replayed with `ReplayHarness` to check that a
+    /// change leaves the output identical.
+    #[must_use]
+    pub fn with_replay_recorder(mut self, recorder: ReplayRecorder) -> Self {
+        self.replay = Some(recorder);
+        self
+    }
+
+    /// Pair the generator with a draft model for speculative decoding
+    ///
+    /// The draft must share the target's tokenizer. Generation then proposes
+    /// `config.draft_tokens` tokens per step with the draft and verifies them
+    /// with the target model.
+    ///
+    /// # Errors
+    /// Returns `CandleModelError::InvalidConfiguration` if the draft and target
+    /// vocabulary sizes differ
+    pub fn with_draft_model(
+        mut self,
+        draft: Box<dyn CandleModel>,
+        config: SpeculativeConfig,
+    ) -> CandleResult<Self> {
+        if draft.vocab_size() != self.model.vocab_size() {
+            return Err(
+                crate::domain::model::error::CandleModelError::InvalidConfiguration(
+                    format!(
+                        "Draft model vocabulary ({}) does not match target vocabulary ({})",
+                        draft.vocab_size(),
+                        self.model.vocab_size()
+                    )
+                    .into(),
+                ),
+            );
         }
+        self.draft = Some(DraftModel::new(draft, config));
+        Ok(self)
+    }
+
+    /// Check if speculative decoding is enabled
+    pub fn has_draft_model(&self) -> bool {
+        self.draft.is_some()
     }
 
     /// Emit final chunk with generation statistics
@@ -90,8 +176,12 @@ impl TextGenerator {
 
         let stats = GenerationStats {
             tokens_generated: self.stats.total_tokens as u32,
+            input_tokens: self.stats.input_tokens as u32,
+            evicted_tokens: self.stats.evicted_tokens as u32,
             elapsed_secs: self.stats.total_duration.as_secs_f64(),
             tokens_per_sec: self.stats.tokens_per_second(),
+            draft_acceptance_rate: (self.stats.draft_tokens > 0)
+                .then(|| self.stats.acceptance_rate()),
         };
 
         log::debug!(
@@ -104,6 +194,13 @@ impl TextGenerator {
         let _ = tx.send(CandleStringChunk::final_with_stats(stats));
     }
 
+    /// Add a streamed token to the replay recording, if one is attached
+    pub(super) fn record_replay_token(&self, token: u32, text: &str) {
+        if let Some(replay) = &self.replay {
+            replay.record_token(token, text);
+        }
+    }
+
     /// Generate text using tokio stream with SIMD acceleration
     pub fn generate(
         mut self,
@@ -119,6 +216,9 @@ impl TextGenerator {
                 special_tokens.eos_token_id
             );
             self.stats.start_generation();
+            if let Some(replay) = &self.replay {
+                replay.begin_turn(&prompt, self.config.seed, max_tokens);
+            }
 
             // Encode prompt to tokens using tokenizer (fast CPU operation)
             log::info!(">>> Encoding prompt...");
@@ -135,7 +235,59 @@ impl TextGenerator {
                 }
             };
 
+            // Apply the context window's overflow policy before prefill
+            let (tokens, max_tokens) = match &self.context_window {
+                Some(window) => match window.fit(tokens, max_tokens as usize) {
+                    Ok(fitted) => {
+                        if fitted.usage.evicted_tokens > 0 {
+                            log::warn!(
+                                "Prompt exceeds context window of {} tokens: dropped {} oldest tokens",
+                                window.max_tokens,
+                                fitted.usage.evicted_tokens
+                            );
+                        }
+                        self.stats
+                            .set_evicted_tokens(fitted.usage.evicted_tokens as u64);
+                        (fitted.tokens, fitted.max_new_tokens as u32)
+                    }
+                    Err(e) => {
+                        log::error!("Context window error: {}", e);
+                        let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                        self.emit_final_stats(&tx);
+                        return;
+                    }
+                },
+                None => (tokens, max_tokens),
+            };
+
             self.stats.set_input_tokens(tokens.len() as u64);
+
+            if let Some(output) = &self.structured_output {
+                match OutputConstraint::new(output, &self.tokenizer) {
+                    Ok(constraint) => {
+                        self.output_constraint =
+                            Some(constraint.with_eos_token(special_tokens.eos_token_id));
+                    }
+                    Err(e) => {
+                        log::error!("Structured output error: {}", e);
+                        let _ = tx.send(CandleStringChunk::text(format!("ERROR: {}", e)));
+                        self.emit_final_stats(&tx);
+                        return;
+                    }
+                }
+            }
+
+            if let Some(draft) = self.draft.take() {
+                if let Err(e) = self
+                    .generate_speculative(draft, tokens, max_tokens, &special_tokens, &tx)
+                    .await
+                {
+                    log::error!("Speculative generation error: {}", e);
+                }
+                self.emit_final_stats(&tx);
+                return;
+            }
+
             let mut all_tokens = tokens.clone();
             let mut position = 0;
 
@@ -225,6 +377,7 @@ impl TextGenerator {
             match self.tokenizer.decode(&[next_token], false) {
                 Ok(token_str) => {
                     log::info!("✅ Sending first token: '{}'", token_str);
+                    self.record_replay_token(next_token, &token_str);
                     let _ = tx.send(CandleStringChunk::text(token_str));
                 }
                 Err(e) => {
@@ -235,7 +388,8 @@ impl TextGenerator {
             };
 
             // Check termination AFTER sending first token
-            if self.should_stop(next_token, &special_tokens) {
+            if self.should_stop(next_token, &special_tokens) || self.is_structured_output_complete()
+            {
                 log::info!(
                     "STOP: First token was EOS ({}), stopping generation",
                     next_token
@@ -310,6 +464,7 @@ impl TextGenerator {
                 // Decode and emit individual token - fast CPU operation
                 match self.tokenizer.decode(&[next_token], false) {
                     Ok(token_str) => {
+                        self.record_replay_token(next_token, &token_str);
                         let _ = tx.send(CandleStringChunk::text(token_str)); // Individual token streaming
                     }
                     Err(e) => {
@@ -319,6 +474,10 @@ impl TextGenerator {
                 };
 
                 self.stats.add_tokens(1);
+
+                if self.is_structured_output_complete() {
+                    break; // Output matches the schema and cannot be extended
+                }
             }
 
             self.emit_final_stats(&tx);
@@ -336,11 +495,18 @@ impl TextGenerator {
         let token_history = self.token_history.clone();
         let constraint = self.constraint.clone();
         let constraint_state = self.constraint_state.clone();
+        let output_constraint = self.output_constraint.clone();
 
         // Wrap all SIMD operations in spawn_blocking for CPU-intensive work
         let result = tokio::task::spawn_blocking(move || -> CandleResult<u32> {
             let mut logits = logits_owned;
 
+            // Mask tokens outside the structured output first so that top-k
+            // and top-p only ever choose between valid continuations
+            if let Some(output_constraint) = &output_constraint {
+                output_constraint.mask_logits(&mut logits)?;
+            }
+
             // Apply temperature scaling with SIMD
             if config.temperature != 1.0 {
                 scale_temperature(&mut logits, config.temperature).map_err(|e| {
@@ -492,11 +658,17 @@ impl TextGenerator {
 
     /// Check if generation has active constraints
     pub fn has_constraints(&self) -> bool {
-        self.constraint.is_some() && self.constraint_state.is_some()
+        (self.constraint.is_some() && self.constraint_state.is_some())
+            || self.output_constraint.is_some()
     }
 
     /// Update constraint state after token generation
     pub fn update_constraint_state(&mut self, token: u32) -> anyhow::Result<bool> {
+        if let Some(output_constraint) = &mut self.output_constraint
+            && !output_constraint.advance(token)
+        {
+            anyhow::bail!("Token {} breaks the structured output", token);
+        }
         if let (Some(constraint), Some(state)) = (&self.constraint, &mut self.constraint_state) {
             constraint.update(state, token)
         } else {
@@ -509,9 +681,16 @@ impl TextGenerator {
         if let (Some(constraint), Some(state)) = (&self.constraint, &self.constraint_state) {
             constraint.is_done(state)
         } else {
-            false // No constraints, not constraint-complete
+            self.is_structured_output_complete()
         }
     }
+
+    /// Check if the structured output is complete and cannot be extended
+    pub fn is_structured_output_complete(&self) -> bool {
+        self.output_constraint
+            .as_ref()
+            .is_some_and(OutputConstraint::is_complete)
+    }
 }
 
 impl std::fmt::Debug for TextGenerator {
@@ -523,6 +702,9 @@ impl std::fmt::Debug for TextGenerator {
             .field("stats", &self.stats)
             .field("simd_metrics", &self.simd_metrics)
             .field("has_constraint", &self.constraint.is_some())
+            .field("draft", &self.draft)
+            .field("context_window", &self.context_window)
+            .field("structured_output", &self.structured_output)
             .finish()
     }
 }
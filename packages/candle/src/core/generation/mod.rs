@@ -14,6 +14,9 @@
 //! - [`stats`] - Generation statistics and performance monitoring
 //! - [`metrics`] - SIMD-specific performance metrics
 //! - [`models`] - Model integration and wrapper functionality
+//! - [`replay`] - Deterministic record and replay of generation turns
+//! - [`speculative`] - Speculative decoding with a draft model
+//! - [`structured`] - JSON Schema and GBNF constrained output
 //! - [`generator`] - Core text generation engine
 //!
 //! ## Usage Example
@@ -50,7 +53,10 @@ pub mod config;
 pub mod generator;
 pub mod metrics;
 pub mod models;
+pub mod replay;
+pub mod speculative;
 pub mod stats;
+pub mod structured;
 pub mod token_output_stream;
 pub mod tokens;
 pub mod types;
@@ -65,7 +71,13 @@ pub use models::{
     CandleLlamaModel, CandleModel, CandleQuantizedLlamaModel, CandleQuantizedMixFormerModel,
     CandleQuantizedPhiModel,
 };
+pub use replay::{
+    REPLAY_FORMAT_VERSION, ReplayError, ReplayHarness, ReplayLog, ReplayRecorder, ReplayToolResult,
+    ReplayTurn,
+};
+pub use speculative::{DEFAULT_DRAFT_TOKENS, DraftModel, SpeculativeConfig};
 pub use stats::GenerationStatistics;
+pub use structured::{GRAMMAR_PARAM, JSON_SCHEMA_PARAM, OutputConstraint, StructuredOutput};
 pub use token_output_stream::TokenOutputStream;
 pub use tokens::{SpecialTokens, TokenHistory, TokenProb};
 pub use types::{CandleResult, LogitsBuffer, SAMPLING_CACHE_SIZE, SIMD_THRESHOLD};
@@ -68,6 +68,44 @@ pub trait CandleModel: Send + Sync {
     fn config(&self) -> Option<&CandleConfig> {
         None
     }
+
+    /// Run `tokens` starting at `position` and return logits for every one of
+    /// them, shaped `[tokens.len(), vocab_size]` (async)
+    ///
+    /// Used to verify draft tokens during speculative decoding. The default
+    /// feeds the tokens one at a time through [`CandleModel::forward`], which
+    /// every model's KV cache supports; models that can score a whole sequence
+    /// in one pass should override it.
+    fn forward_all<'a>(
+        &'a mut self,
+        tokens: &'a [u32],
+        position: usize,
+    ) -> Pin<Box<dyn Future<Output = CandleResult<Tensor>> + Send + 'a>> {
+        Box::pin(async move {
+            if tokens.is_empty() {
+                return Err(CandleModelError::InvalidConfiguration(
+                    "forward_all requires at least one token".into(),
+                ));
+            }
+            let mut rows = Vec::with_capacity(tokens.len());
+            for (offset, &token) in tokens.iter().enumerate() {
+                let input = Tensor::new(&[token], self.device())?.unsqueeze(0)?;
+                let logits = self.forward(&input, position + offset).await?;
+                rows.push(logits.squeeze(0)?);
+            }
+            Ok(Tensor::stack(&rows, 0)?)
+        })
+    }
+
+    /// Drop everything held in the KV cache
+    ///
+    /// The next forward pass must start again at position 0. Speculative
+    /// decoding uses this to rewind past rejected draft tokens.
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        Err(CandleModelError::OperationNotSupported(
+            "Model does not support clearing its KV cache".into(),
+        ))
+    }
 }
 
 /// Llama model wrapper for Candle integration
@@ -236,6 +274,16 @@ impl CandleModel for CandleLlamaModel {
     fn config(&self) -> Option<&CandleConfig> {
         Some(&self.config)
     }
+
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        let ModelArchitecture::Llama(llama_config) = &self.config.architecture else {
+            return Err(CandleModelError::InvalidConfiguration(
+                "Expected Llama architecture in config".into(),
+            ));
+        };
+        self.cache = Cache::new(true, self.config.dtype, llama_config, &self.device)?;
+        Ok(())
+    }
 }
 
 /// Quantized Llama model wrapper for GGUF models
@@ -361,6 +409,11 @@ impl CandleModel for CandleQuantizedLlamaModel {
     fn config(&self) -> Option<&CandleConfig> {
         Some(&self.config)
     }
+
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        // A forward pass at position 0 replaces the cached keys/values
+        Ok(())
+    }
 }
 
 /// Quantized MixFormer (Phi) model wrapper for GGUF models
@@ -539,6 +592,11 @@ impl CandleModel for CandleQuantizedMixFormerModel {
     fn vocab_size(&self) -> usize {
         self.vocab_size
     }
+
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        self.model_weights.clear_kv_cache();
+        Ok(())
+    }
 }
 
 /// Wrapper for quantized Phi-3/Phi-4 models loaded from GGUF files
@@ -708,6 +766,11 @@ impl CandleModel for CandleQuantizedPhiModel {
     fn vocab_size(&self) -> usize {
         self.vocab_size
     }
+
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        // A forward pass at position 0 replaces the cached keys/values
+        Ok(())
+    }
 }
 
 /// Helper function to discover model files from a multi-file model index
@@ -0,0 +1,360 @@
+//! Deterministic generation record and replay
+//!
+//! A [`ReplayRecorder`] attached to a `TextGenerator` captures, per turn, the
+//! prompt, the sampling seed, every streamed token and the text it decoded
+//! to. Tool results executed during the turn are added by the caller. The
+//! resulting [`ReplayLog`] is saved as a JSON replay file.
+//!
+//! [`ReplayHarness`] re-runs a recorded conversation through fresh
+//! generators and fails on the first turn whose output differs, which turns
+//! a recorded conversation into a regression test for prompt and template
+//! changes.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tokio_stream::StreamExt;
+
+use super::generator::TextGenerator;
+use super::tokens::SpecialTokens;
+use crate::domain::tool::ToolCallOutcome;
+
+/// Current replay file format
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// Replay file and verification errors
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    /// Replay file could not be read or written
+    #[error("Replay file I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Replay file is not a valid replay log
+    #[error("Invalid replay file: {0}")]
+    Format(#[from] serde_json::Error),
+
+    /// Replay file was written by an incompatible version
+    #[error("Unsupported replay format version {0} (expected {REPLAY_FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+
+    /// Replay produced a different number of turns
+    #[error("Replay has {replayed} turns, recording has {recorded}")]
+    TurnCount {
+        /// Turns in the recording
+        recorded: usize,
+        /// Turns produced by the replay
+        replayed: usize,
+    },
+
+    /// A replayed turn differs from the recording
+    #[error("Turn {turn} diverged: {detail}")]
+    Diverged {
+        /// Zero-based index of the first differing turn
+        turn: usize,
+        /// What differed
+        detail: String,
+    },
+}
+
+/// Result of one tool call made during a turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayToolResult {
+    /// Id of the call
+    pub id: String,
+    /// Tool that was invoked
+    pub name: String,
+    /// Raw arguments the model emitted
+    pub arguments: String,
+    /// Tool response when the call succeeded
+    pub output: Option<Value>,
+    /// Why the call failed
+    pub error: Option<String>,
+}
+
+impl ReplayToolResult {
+    /// Capture a finished call given the raw input the model emitted
+    #[must_use]
+    pub fn from_outcome(input: &str, outcome: &ToolCallOutcome) -> Self {
+        let (output, error) = match &outcome.result {
+            Ok(value) => (Some(value.clone()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            id: outcome.id.clone(),
+            name: outcome.name.clone(),
+            arguments: input.to_string(),
+            output,
+            error,
+        }
+    }
+}
+
+/// Everything needed to reproduce one generation turn
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    /// Prompt passed to the generator
+    pub prompt: String,
+    /// Sampling seed, if one was configured
+    pub seed: Option<u64>,
+    /// Token budget of the turn
+    pub max_tokens: u32,
+    /// Streamed token ids in order
+    pub tokens: Vec<u32>,
+    /// Text the streamed tokens decoded to
+    pub text: String,
+    /// Tool calls executed after the turn, in execution order
+    pub tool_results: Vec<ReplayToolResult>,
+}
+
+impl ReplayTurn {
+    /// Describe the first difference from `replayed`, if any
+    fn diff(&self, replayed: &Self) -> Option<String> {
+        if self.prompt != replayed.prompt {
+            return Some("prompt changed".to_string());
+        }
+        if self.seed != replayed.seed {
+            return Some(format!(
+                "seed {:?} replayed as {:?}",
+                self.seed, replayed.seed
+            ));
+        }
+        if let Some(index) = first_difference(&self.tokens, &replayed.tokens) {
+            return Some(format!(
+                "token {} is {:?}, recorded {:?}",
+                index,
+                replayed.tokens.get(index),
+                self.tokens.get(index)
+            ));
+        }
+        if self.text != replayed.text {
+            return Some(format!(
+                "text {:?} differs from recorded {:?}",
+                replayed.text, self.text
+            ));
+        }
+        if let Some(index) = first_difference(&self.tool_results, &replayed.tool_results) {
+            let name = self
+                .tool_results
+                .get(index)
+                .or_else(|| replayed.tool_results.get(index))
+                .map_or("", |result| result.name.as_str());
+            return Some(format!("tool result {} ({}) differs", index, name));
+        }
+        None
+    }
+}
+
+/// A recorded conversation, one entry per generation turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    /// Replay file format version
+    pub version: u32,
+    /// Recorded turns in order
+    pub turns: Vec<ReplayTurn>,
+}
+
+impl Default for ReplayLog {
+    fn default() -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            turns: Vec::new(),
+        }
+    }
+}
+
+impl ReplayLog {
+    /// Read a replay file
+    ///
+    /// # Errors
+    /// Returns `ReplayError` if the file cannot be read, is not valid JSON or
+    /// has an unsupported format version
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let log: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if log.version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(log.version));
+        }
+        Ok(log)
+    }
+
+    /// Write the log as a pretty-printed replay file
+    ///
+    /// # Errors
+    /// Returns `ReplayError::Io` if the file cannot be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check that `replayed` reproduces this recording exactly
+    ///
+    /// Turns are compared in order on prompt, seed, tokens, text and tool
+    /// results.
+    ///
+    /// # Errors
+    /// Returns `ReplayError::Diverged` for the first differing turn, or
+    /// `ReplayError::TurnCount` if every shared turn matches but the number
+    /// of turns differs
+    pub fn verify(&self, replayed: &Self) -> Result<(), ReplayError> {
+        for (turn, (recorded, replayed)) in self.turns.iter().zip(&replayed.turns).enumerate() {
+            if let Some(detail) = recorded.diff(replayed) {
+                return Err(ReplayError::Diverged { turn, detail });
+            }
+        }
+        if self.turns.len() != replayed.turns.len() {
+            return Err(ReplayError::TurnCount {
+                recorded: self.turns.len(),
+                replayed: replayed.turns.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Shared handle recording a conversation
+///
+/// Clones share the same log, so the generator recording tokens and the
+/// session recording tool results append to one conversation.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    inner: Arc<Mutex<ReplayLog>>,
+}
+
+impl ReplayRecorder {
+    /// Create an empty recorder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new turn
+    pub fn begin_turn(&self, prompt: &str, seed: Option<u64>, max_tokens: u32) {
+        self.inner.lock().turns.push(ReplayTurn {
+            prompt: prompt.to_string(),
+            seed,
+            max_tokens,
+            ..ReplayTurn::default()
+        });
+    }
+
+    /// Add a streamed token and its decoded text to the current turn
+    pub fn record_token(&self, token: u32, text: &str) {
+        if let Some(turn) = self.inner.lock().turns.last_mut() {
+            turn.tokens.push(token);
+            turn.text.push_str(text);
+        }
+    }
+
+    /// Add an executed tool call to the current turn
+    pub fn record_tool_result(&self, result: ReplayToolResult) {
+        if let Some(turn) = self.inner.lock().turns.last_mut() {
+            turn.tool_results.push(result);
+        }
+    }
+
+    /// Log recorded so far
+    #[must_use]
+    pub fn snapshot(&self) -> ReplayLog {
+        self.inner.lock().clone()
+    }
+
+    /// Return the log recorded so far and start again from empty
+    pub fn take(&self) -> ReplayLog {
+        std::mem::take(&mut *self.inner.lock())
+    }
+
+    /// Write the log recorded so far to a replay file
+    ///
+    /// # Errors
+    /// Returns `ReplayError::Io` if the file cannot be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        self.snapshot().save(path)
+    }
+}
+
+/// Re-runs a recorded conversation and asserts identical output
+#[derive(Debug, Clone)]
+pub struct ReplayHarness {
+    recording: ReplayLog,
+}
+
+impl ReplayHarness {
+    /// Replay `recording`
+    #[must_use]
+    pub fn new(recording: ReplayLog) -> Self {
+        Self { recording }
+    }
+
+    /// Replay the conversation in a replay file
+    ///
+    /// # Errors
+    /// Returns `ReplayError` if the file cannot be loaded
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Ok(Self::new(ReplayLog::load(path)?))
+    }
+
+    /// The conversation being replayed
+    #[must_use]
+    pub fn recording(&self) -> &ReplayLog {
+        &self.recording
+    }
+
+    /// Regenerate every recorded turn and verify the output
+    ///
+    /// `generator_for` builds a fresh generator per turn and should apply
+    /// the turn's recorded seed. Tools are not re-run: the recorded tool
+    /// results are carried into the replayed turn, and anything that depends
+    /// on them is covered by the following turn's prompt.
+    ///
+    /// # Errors
+    /// Returns `ReplayError::Diverged` for the first turn whose output differs
+    /// from the recording
+    pub async fn verify<F>(
+        &self,
+        mut generator_for: F,
+        special_tokens: SpecialTokens,
+    ) -> Result<ReplayLog, ReplayError>
+    where
+        F: FnMut(&ReplayTurn) -> TextGenerator,
+    {
+        let recorder = ReplayRecorder::new();
+        for (turn, recorded) in self.recording.turns.iter().enumerate() {
+            let generator = generator_for(recorded).with_replay_recorder(recorder.clone());
+            let stream = generator.generate(
+                recorded.prompt.clone(),
+                recorded.max_tokens,
+                special_tokens.clone(),
+            );
+            tokio::pin!(stream);
+            while stream.next().await.is_some() {}
+
+            for result in &recorded.tool_results {
+                recorder.record_tool_result(result.clone());
+            }
+            let replayed = recorder.snapshot();
+            if let Some(detail) = replayed
+                .turns
+                .get(turn)
+                .and_then(|replayed| recorded.diff(replayed))
+            {
+                return Err(ReplayError::Diverged { turn, detail });
+            }
+        }
+
+        let replayed = recorder.take();
+        self.recording.verify(&replayed)?;
+        Ok(replayed)
+    }
+}
+
+/// Index of the first element that differs, including a length mismatch
+fn first_difference<T: PartialEq>(recorded: &[T], replayed: &[T]) -> Option<usize> {
+    recorded
+        .iter()
+        .zip(replayed)
+        .position(|(a, b)| a != b)
+        .or_else(|| (recorded.len() != replayed.len()).then(|| recorded.len().min(replayed.len())))
+}
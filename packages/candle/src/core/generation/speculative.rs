@@ -0,0 +1,258 @@
+//! Speculative decoding with a draft model
+//!
+//! A small draft model that shares the target's tokenizer proposes `k` tokens
+//! per step. The target scores the proposal with [`CandleModel::forward_all`],
+//! keeps the longest prefix its own sampler agrees with and adds one token of
+//! its own, so every verification step emits between 1 and `k + 1` tokens.
+//!
+//! Verification samples through [`TextGenerator::sample_token`], so the emitted
+//! text is the same as non-speculative decoding. When tokens are rejected,
+//! both models clear their KV caches and re-run the accepted context in one
+//! batched pass.
+
+use candle_core::{Device, Tensor};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::generator::TextGenerator;
+use super::models::CandleModel;
+use super::tokens::SpecialTokens;
+use super::types::CandleResult;
+use crate::domain::context::chunks::CandleStringChunk;
+use crate::domain::model::error::CandleModelError;
+
+/// Default number of tokens the draft model proposes per step
+pub const DEFAULT_DRAFT_TOKENS: usize = 4;
+
+/// Speculative decoding settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeculativeConfig {
+    /// Tokens proposed by the draft model per verification step (`k`)
+    pub draft_tokens: usize,
+}
+
+impl SpeculativeConfig {
+    /// Create a config proposing `draft_tokens` tokens per step (at least 1)
+    pub fn new(draft_tokens: usize) -> Self {
+        Self {
+            draft_tokens: draft_tokens.max(1),
+        }
+    }
+}
+
+impl Default for SpeculativeConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_DRAFT_TOKENS)
+    }
+}
+
+/// Draft model paired with a [`TextGenerator`]
+pub struct DraftModel {
+    /// The small model proposing tokens
+    model: Box<dyn CandleModel>,
+
+    /// Speculative decoding settings
+    config: SpeculativeConfig,
+
+    /// Number of context tokens held in the draft's KV cache
+    cached: usize,
+}
+
+impl DraftModel {
+    /// Create a draft model with the given settings
+    pub fn new(model: Box<dyn CandleModel>, config: SpeculativeConfig) -> Self {
+        Self {
+            model,
+            config,
+            cached: 0,
+        }
+    }
+
+    /// Get the speculative decoding settings
+    pub fn config(&self) -> SpeculativeConfig {
+        self.config
+    }
+
+    /// Get the draft model
+    pub fn model(&self) -> &dyn CandleModel {
+        self.model.as_ref()
+    }
+
+    /// Propose up to `count` tokens following `context`
+    ///
+    /// Feeds the context tokens the draft has not seen yet, then decodes
+    /// greedily, stopping early at EOS.
+    async fn propose(
+        &mut self,
+        context: &[u32],
+        count: usize,
+        special_tokens: &SpecialTokens,
+    ) -> CandleResult<Vec<u32>> {
+        let mut proposal = Vec::with_capacity(count);
+        if count == 0 {
+            return Ok(proposal);
+        }
+
+        let pending = &context[self.cached..];
+        let mut logits = last_row(&self.model.forward_all(pending, self.cached).await?)?;
+        self.cached = context.len();
+
+        loop {
+            let token = greedy_token(&logits)?;
+            proposal.push(token);
+            if proposal.len() == count || special_tokens.is_eos(token) {
+                return Ok(proposal);
+            }
+            logits = last_row(&self.model.forward_all(&[token], self.cached).await?)?;
+            self.cached += 1;
+        }
+    }
+}
+
+impl std::fmt::Debug for DraftModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DraftModel")
+            .field("device", self.model.device())
+            .field("config", &self.config)
+            .field("cached", &self.cached)
+            .finish()
+    }
+}
+
+impl TextGenerator {
+    /// Speculative generation loop used by [`TextGenerator::generate`]
+    ///
+    /// Streams each accepted token through `tx` and records acceptance
+    /// statistics; the caller emits the final statistics chunk.
+    pub(super) async fn generate_speculative(
+        &mut self,
+        mut draft: DraftModel,
+        prompt_tokens: Vec<u32>,
+        max_tokens: u32,
+        special_tokens: &SpecialTokens,
+        tx: &UnboundedSender<CandleStringChunk>,
+    ) -> CandleResult<()> {
+        let max_tokens = max_tokens as usize;
+        let prompt_len = prompt_tokens.len();
+        let mut all_tokens = prompt_tokens;
+
+        // Prefill both models with the prompt
+        let input = token_tensor(&all_tokens, self.model.device())?;
+        let logits = self.model.forward(&input, 0).await?.squeeze(0)?;
+        self.stats.record_forward_pass();
+        let draft_input = token_tensor(&all_tokens, draft.model.device())?;
+        draft.model.forward(&draft_input, 0).await?;
+        draft.cached = all_tokens.len();
+
+        let first = self
+            .sample_token(&logits.to_vec1::<f32>()?, &all_tokens)
+            .await?;
+        if max_tokens == 0 || !self.emit_token(first, &mut all_tokens, special_tokens, tx)? {
+            return Ok(());
+        }
+
+        loop {
+            let generated = all_tokens.len() - prompt_len;
+            if generated >= max_tokens {
+                return Ok(());
+            }
+
+            // The target's cache holds everything except the newest token;
+            // leave room for the target's own token at the end of the step
+            let base = all_tokens.len() - 1;
+            let count = draft.config.draft_tokens.min(max_tokens - generated - 1);
+            let proposal = draft.propose(&all_tokens, count, special_tokens).await?;
+
+            let mut verify = Vec::with_capacity(proposal.len() + 1);
+            verify.push(all_tokens[base]);
+            verify.extend_from_slice(&proposal);
+            let rows = self
+                .model
+                .forward_all(&verify, base)
+                .await?
+                .to_vec2::<f32>()?;
+            self.stats.record_forward_pass();
+
+            let mut accepted = 0;
+            for (index, row) in rows.iter().enumerate() {
+                let token = self.sample_token(row, &all_tokens).await?;
+                if !self.emit_token(token, &mut all_tokens, special_tokens, tx)? {
+                    self.stats
+                        .record_speculative_step(proposal.len() as u64, accepted as u64);
+                    return Ok(());
+                }
+                if proposal.get(index) != Some(&token) {
+                    break;
+                }
+                accepted += 1;
+            }
+            self.stats
+                .record_speculative_step(proposal.len() as u64, accepted as u64);
+
+            // Both caches must end up holding all tokens but the newest one
+            let keep = all_tokens.len() - 1;
+            if accepted < proposal.len() {
+                rewind_cache(self.model.as_mut(), &all_tokens[..keep]).await?;
+            }
+            if draft.cached > keep {
+                rewind_cache(draft.model.as_mut(), &all_tokens[..keep]).await?;
+                draft.cached = keep;
+            }
+        }
+    }
+
+    /// Append a sampled token and stream it, returning `false` at EOS or once
+    /// the structured output is complete
+    fn emit_token(
+        &mut self,
+        token: u32,
+        all_tokens: &mut Vec<u32>,
+        special_tokens: &SpecialTokens,
+        tx: &UnboundedSender<CandleStringChunk>,
+    ) -> CandleResult<bool> {
+        if self.should_stop(token, special_tokens) {
+            return Ok(false);
+        }
+
+        all_tokens.push(token);
+        self.token_history.push(token);
+        if let Err(e) = self.update_constraint_state(token) {
+            log::warn!("Failed to update constraint state: {}", e);
+        }
+
+        let text = self.tokenizer.decode(&[token], false).map_err(|e| {
+            CandleModelError::Internal(format!("Token decoding error: {}", e).into())
+        })?;
+        self.record_replay_token(token, &text);
+        let _ = tx.send(CandleStringChunk::text(text));
+        self.stats.add_tokens(1);
+        Ok(!self.is_structured_output_complete())
+    }
+}
+
+/// Build a `[1, len]` input tensor from token ids
+fn token_tensor(tokens: &[u32], device: &Device) -> CandleResult<Tensor> {
+    Ok(Tensor::new(tokens, device)?.unsqueeze(0)?)
+}
+
+/// Logits for the last position of a `[len, vocab]` tensor
+fn last_row(logits: &Tensor) -> CandleResult<Vec<f32>> {
+    let rows = logits.dim(0)?;
+    Ok(logits.get(rows - 1)?.to_vec1::<f32>()?)
+}
+
+/// Most likely token under the draft's logits
+fn greedy_token(logits: &[f32]) -> CandleResult<u32> {
+    cyrup_simd::argmax(logits)
+        .map(|token| token as u32)
+        .map_err(|e| CandleModelError::OperationNotSupported(e.to_string().into()))
+}
+
+/// Reset a model's KV cache to hold exactly `kept`
+async fn rewind_cache(model: &mut dyn CandleModel, kept: &[u32]) -> CandleResult<()> {
+    model.clear_cache()?;
+    if !kept.is_empty() {
+        let input = token_tensor(kept, model.device())?;
+        model.forward(&input, 0).await?;
+    }
+    Ok(())
+}
@@ -19,6 +19,9 @@ pub struct GenerationStatistics {
     /// Number of input tokens processed
     pub input_tokens: u64,
 
+    /// Prompt tokens dropped to fit the context window
+    pub evicted_tokens: u64,
+
     /// Total generation time
     pub total_duration: Duration,
 
@@ -46,6 +49,15 @@ pub struct GenerationStatistics {
     /// Number of cache misses during generation
     pub cache_misses: u64,
 
+    /// Tokens proposed by the draft model during speculative decoding
+    pub draft_tokens: u64,
+
+    /// Draft tokens the target model accepted
+    pub accepted_draft_tokens: u64,
+
+    /// Number of speculative verification steps
+    pub speculative_steps: u64,
+
     /// Start time of generation for ongoing tracking
     generation_start: Option<Instant>,
 }
@@ -55,6 +67,7 @@ impl GenerationStatistics {
         Self {
             total_tokens: 0,
             input_tokens: 0,
+            evicted_tokens: 0,
             total_duration: Duration::ZERO,
             prompt_processing_duration: Duration::ZERO,
             token_generation_duration: Duration::ZERO,
@@ -64,6 +77,9 @@ impl GenerationStatistics {
             peak_memory_bytes: 0,
             cache_hits: 0,
             cache_misses: 0,
+            draft_tokens: 0,
+            accepted_draft_tokens: 0,
+            speculative_steps: 0,
             generation_start: None,
         }
     }
@@ -104,6 +120,11 @@ impl GenerationStatistics {
         self.input_tokens = count;
     }
 
+    /// Set the number of prompt tokens dropped to fit the context window
+    pub fn set_evicted_tokens(&mut self, count: u64) {
+        self.evicted_tokens = count;
+    }
+
     /// Set prompt processing duration
     pub fn set_prompt_processing_duration(&mut self, duration: Duration) {
         self.prompt_processing_duration = duration;
@@ -131,6 +152,13 @@ impl GenerationStatistics {
         self.cache_misses += 1;
     }
 
+    /// Record one speculative decoding step
+    pub fn record_speculative_step(&mut self, drafted: u64, accepted: u64) {
+        self.speculative_steps += 1;
+        self.draft_tokens += drafted;
+        self.accepted_draft_tokens += accepted.min(drafted);
+    }
+
     /// Calculate tokens per second for total generation
     pub fn tokens_per_second(&self) -> f64 {
         if self.total_duration.as_secs_f64() > 0.0 {
@@ -178,15 +206,38 @@ impl GenerationStatistics {
         }
     }
 
+    /// Calculate draft token acceptance rate percentage
+    pub fn acceptance_rate(&self) -> f64 {
+        if self.draft_tokens > 0 {
+            (self.accepted_draft_tokens as f64 / self.draft_tokens as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Average tokens emitted per speculative step, counting the target's own token
+    pub fn tokens_per_speculative_step(&self) -> f64 {
+        if self.speculative_steps > 0 {
+            (self.accepted_draft_tokens + self.speculative_steps) as f64
+                / self.speculative_steps as f64
+        } else {
+            0.0
+        }
+    }
+
     /// Get efficiency summary as formatted string
     pub fn efficiency_summary(&self) -> String {
-        format!(
+        let mut summary = format!(
             "Tokens/sec: {:.2} | SIMD: {:.1}% | Cache: {:.1}% | Memory: {:.1}MB",
             self.tokens_per_second(),
             self.simd_utilization(),
             self.cache_hit_rate(),
             self.peak_memory_bytes as f64 / 1_048_576.0 // Convert to MB
-        )
+        );
+        if self.draft_tokens > 0 {
+            summary.push_str(&format!(" | Draft: {:.1}%", self.acceptance_rate()));
+        }
+        summary
     }
 
     /// Reset all statistics
@@ -244,6 +295,19 @@ mod tests {
         assert_eq!(stats.cache_hit_rate(), 90.0);
     }
 
+    #[test]
+    fn test_acceptance_rate() {
+        let mut stats = GenerationStatistics::new();
+        assert_eq!(stats.acceptance_rate(), 0.0);
+
+        stats.record_speculative_step(4, 4);
+        stats.record_speculative_step(4, 2);
+
+        assert_eq!(stats.speculative_steps, 2);
+        assert_eq!(stats.acceptance_rate(), 75.0);
+        assert_eq!(stats.tokens_per_speculative_step(), 4.0);
+    }
+
     #[test]
     fn test_efficiency_summary() {
         let mut stats = GenerationStatistics::new();
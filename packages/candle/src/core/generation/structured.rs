@@ -0,0 +1,176 @@
+//! Structured output constrained by a JSON Schema or GBNF grammar
+//!
+//! An [`OutputConstraint`] masks every token that would take the output
+//! outside the requested schema or grammar, so constrained generation always
+//! produces text that parses. The DFA comes from `cyrup_simd::serde_constraints`.
+//!
+//! Providers read the request from `CandleCompletionParams::additional_params`:
+//! - [`JSON_SCHEMA_PARAM`]: a JSON Schema, as an object or a JSON string
+//! - [`GRAMMAR_PARAM`]: GBNF grammar source (non-recursive)
+
+use cyrup_simd::logits::constraints::{
+    GenerationConstraint, SchemaConstraint, SchemaConstraintState,
+};
+use schemars::JsonSchema;
+use serde_json::Value;
+use tokenizers::Tokenizer;
+
+use super::types::CandleResult;
+use crate::domain::model::error::CandleModelError;
+
+/// Key in `additional_params` holding a JSON Schema
+pub const JSON_SCHEMA_PARAM: &str = "json_schema";
+
+/// Key in `additional_params` holding a GBNF grammar
+pub const GRAMMAR_PARAM: &str = "grammar";
+
+/// Requested shape of the generated text
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructuredOutput {
+    /// JSON matching a JSON Schema
+    JsonSchema(Value),
+    /// Text matching a GBNF grammar
+    Grammar(String),
+}
+
+impl StructuredOutput {
+    /// JSON matching the schema derived from `T`
+    pub fn for_type<T: JsonSchema>() -> Self {
+        Self::JsonSchema(schemars::schema_for!(T).to_value())
+    }
+
+    /// Text matching a GBNF grammar
+    pub fn grammar(grammar: impl Into<String>) -> Self {
+        Self::Grammar(grammar.into())
+    }
+
+    /// Structured output requested in `additional_params`, if any
+    ///
+    /// A schema given as a string is parsed as JSON; an unparsable schema is
+    /// logged and ignored.
+    pub fn from_params(additional_params: Option<&Value>) -> Option<Self> {
+        let params = additional_params?;
+        if let Some(schema) = params.get(JSON_SCHEMA_PARAM) {
+            return match schema {
+                Value::String(text) => match serde_json::from_str(text) {
+                    Ok(schema) => Some(Self::JsonSchema(schema)),
+                    Err(e) => {
+                        log::warn!("Ignoring invalid '{}' param: {}", JSON_SCHEMA_PARAM, e);
+                        None
+                    }
+                },
+                schema => Some(Self::JsonSchema(schema.clone())),
+            };
+        }
+        params
+            .get(GRAMMAR_PARAM)
+            .and_then(Value::as_str)
+            .map(Self::grammar)
+    }
+
+    /// `additional_params` entry requesting this output
+    pub fn to_params(&self) -> Value {
+        match self {
+            Self::JsonSchema(schema) => serde_json::json!({ JSON_SCHEMA_PARAM: schema }),
+            Self::Grammar(grammar) => serde_json::json!({ GRAMMAR_PARAM: grammar }),
+        }
+    }
+}
+
+/// Live constraint tracking generated tokens against a [`StructuredOutput`]
+#[derive(Debug, Clone)]
+pub struct OutputConstraint {
+    constraint: SchemaConstraint,
+    state: SchemaConstraintState,
+    /// Model EOS token, allowed once the output is complete
+    eos_token_id: Option<u32>,
+}
+
+impl OutputConstraint {
+    /// Compile `output` against the tokenizer's vocabulary
+    ///
+    /// # Errors
+    /// Returns `CandleModelError::InvalidConfiguration` if the schema or grammar
+    /// cannot be compiled
+    pub fn new(output: &StructuredOutput, tokenizer: &Tokenizer) -> CandleResult<Self> {
+        let constraint = match output {
+            StructuredOutput::JsonSchema(schema) => {
+                cyrup_simd::serde_constraints::constraint_for_schema(&schema.to_string(), tokenizer)
+            }
+            StructuredOutput::Grammar(grammar) => {
+                cyrup_simd::serde_constraints::constraint_for_gbnf(grammar, tokenizer)
+            }
+        }
+        .map_err(|e| {
+            CandleModelError::InvalidConfiguration(
+                format!("Invalid structured output: {:#}", e).into(),
+            )
+        })?;
+
+        Ok(Self {
+            state: constraint.new_state(),
+            constraint,
+            eos_token_id: None,
+        })
+    }
+
+    /// Allow the model's EOS token once the output is complete
+    #[must_use]
+    pub fn with_eos_token(mut self, eos_token_id: Option<u32>) -> Self {
+        self.eos_token_id = eos_token_id;
+        self
+    }
+
+    /// Set every logit that would break the output to negative infinity
+    ///
+    /// # Errors
+    /// Returns `CandleModelError::Internal` if no token can continue the output
+    pub fn mask_logits(&self, logits: &mut [f32]) -> CandleResult<()> {
+        let allowed = self.constraint.get_allowed_tokens(&self.state);
+        let eos = self.eos_token_id.filter(|_| self.is_satisfied());
+
+        let mut remaining = 0;
+        for (token, logit) in logits.iter_mut().enumerate() {
+            let token = token as u32;
+            if eos == Some(token) || allowed.is_some_and(|next| next.contains_key(&token)) {
+                remaining += 1;
+            } else {
+                *logit = f32::NEG_INFINITY;
+            }
+        }
+
+        if remaining == 0 {
+            return Err(CandleModelError::Internal(
+                "No token can continue the structured output".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Record a generated token, returning `false` if it breaks the output
+    ///
+    /// The model's EOS token is accepted without a transition once the output
+    /// is complete.
+    pub fn advance(&mut self, token: u32) -> bool {
+        if self.eos_token_id == Some(token) && self.is_satisfied() {
+            return true;
+        }
+        self.constraint
+            .update(&mut self.state, token)
+            .unwrap_or(false)
+    }
+
+    /// Whether the output so far is a complete match
+    pub fn is_satisfied(&self) -> bool {
+        self.state.is_complete()
+    }
+
+    /// Whether the output is complete and cannot be extended
+    pub fn is_complete(&self) -> bool {
+        self.is_satisfied()
+            && self
+                .constraint
+                .get_allowed_tokens(&self.state)
+                .is_none_or(|next| next.is_empty())
+    }
+}
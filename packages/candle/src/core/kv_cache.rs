@@ -0,0 +1,294 @@
+//! Paged key/value cache for attention layers
+//!
+//! Keys and values are stored per layer in fixed-size pages along the sequence
+//! dimension instead of one growing tensor. Appending a token only copies the
+//! partially filled last page, rewinding drops whole pages plus one partial
+//! page, and with a page limit the oldest pages are released so the cache
+//! behaves as a sliding window over long conversations.
+//!
+//! Tensors use the `[batch, kv_heads, seq_len, head_dim]` layout of the
+//! candle-transformers attention blocks.
+
+use std::collections::VecDeque;
+
+use candle_core::Tensor;
+use thiserror::Error;
+
+/// Default number of token positions per page
+pub const DEFAULT_PAGE_SIZE: usize = 256;
+
+/// Sequence dimension of cached key/value tensors
+const SEQ_DIM: usize = 2;
+
+/// Paged KV cache errors
+#[derive(Debug, Error)]
+pub enum KvCacheError {
+    #[error("Layer {layer} out of range (cache has {layers} layers)")]
+    LayerOutOfRange { layer: usize, layers: usize },
+    #[error("KV cache full: {requested} tokens requested, capacity is {capacity}")]
+    CapacityExceeded { requested: usize, capacity: usize },
+    #[error("Tensor operation failed: {0}")]
+    Tensor(#[from] candle_core::Error),
+}
+
+/// Paged KV cache configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KvCacheConfig {
+    /// Number of attention layers
+    pub num_layers: usize,
+    /// Token positions per page
+    pub page_size: usize,
+    /// Pages kept per layer (None = unbounded)
+    pub max_pages: Option<usize>,
+    /// Evict the oldest pages instead of failing when `max_pages` is reached
+    pub sliding_window: bool,
+}
+
+impl KvCacheConfig {
+    /// Unbounded cache for `num_layers` layers with the default page size
+    pub fn new(num_layers: usize) -> Self {
+        Self {
+            num_layers,
+            page_size: DEFAULT_PAGE_SIZE,
+            max_pages: None,
+            sliding_window: false,
+        }
+    }
+
+    /// Set the page size (at least 1)
+    #[must_use]
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Limit the cache to `max_tokens` positions, rounded up to whole pages
+    #[must_use]
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_pages = Some(max_tokens.div_ceil(self.page_size).max(1));
+        self
+    }
+
+    /// Evict the oldest pages when the cache is full
+    #[must_use]
+    pub fn with_sliding_window(mut self) -> Self {
+        self.sliding_window = true;
+        self
+    }
+
+    /// Token capacity per layer, if bounded
+    pub fn capacity_tokens(&self) -> Option<usize> {
+        self.max_pages.map(|pages| pages * self.page_size)
+    }
+}
+
+/// One page of keys and values
+#[derive(Debug, Clone)]
+struct KvPage {
+    keys: Tensor,
+    values: Tensor,
+    len: usize,
+}
+
+/// Paged key/value storage for every layer of a model
+#[derive(Debug, Clone)]
+pub struct PagedKvCache {
+    config: KvCacheConfig,
+    layers: Vec<VecDeque<KvPage>>,
+    /// Tokens dropped from the front of the window
+    evicted_tokens: usize,
+}
+
+impl PagedKvCache {
+    /// Create an empty cache
+    pub fn new(config: KvCacheConfig) -> Self {
+        Self {
+            layers: vec![VecDeque::new(); config.num_layers],
+            config,
+            evicted_tokens: 0,
+        }
+    }
+
+    /// Cache configuration
+    pub fn config(&self) -> &KvCacheConfig {
+        &self.config
+    }
+
+    /// Append keys and values for `layer`
+    ///
+    /// # Errors
+    /// Returns `KvCacheError::CapacityExceeded` when a bounded cache without
+    /// sliding window is full, or `KvCacheError::Tensor` on shape mismatches
+    pub fn append(
+        &mut self,
+        layer: usize,
+        keys: &Tensor,
+        values: &Tensor,
+    ) -> Result<(), KvCacheError> {
+        let page_size = self.config.page_size;
+        let incoming = keys.dim(SEQ_DIM)?;
+        if let (Some(capacity), false) = (self.config.capacity_tokens(), self.config.sliding_window)
+        {
+            let requested = self.layer_len(layer)? + incoming;
+            if requested > capacity {
+                return Err(KvCacheError::CapacityExceeded {
+                    requested,
+                    capacity,
+                });
+            }
+        }
+
+        let pages = self.layer_mut(layer)?;
+        let mut offset = 0;
+        while offset < incoming {
+            let take = match pages.back_mut() {
+                Some(page) if page.len < page_size => {
+                    let take = (page_size - page.len).min(incoming - offset);
+                    page.keys =
+                        Tensor::cat(&[&page.keys, &keys.narrow(SEQ_DIM, offset, take)?], SEQ_DIM)?;
+                    page.values = Tensor::cat(
+                        &[&page.values, &values.narrow(SEQ_DIM, offset, take)?],
+                        SEQ_DIM,
+                    )?;
+                    page.len += take;
+                    take
+                }
+                _ => {
+                    let take = page_size.min(incoming - offset);
+                    pages.push_back(KvPage {
+                        keys: keys.narrow(SEQ_DIM, offset, take)?.contiguous()?,
+                        values: values.narrow(SEQ_DIM, offset, take)?.contiguous()?,
+                        len: take,
+                    });
+                    take
+                }
+            };
+            offset += take;
+        }
+
+        if let Some(max_pages) = self.config.max_pages {
+            let mut dropped = 0;
+            let pages = self.layer_mut(layer)?;
+            while pages.len() > max_pages {
+                dropped += pages.pop_front().map_or(0, |page| page.len);
+            }
+            if layer == 0 {
+                self.evicted_tokens += dropped;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keys and values for `layer` as contiguous tensors, or `None` when empty
+    ///
+    /// # Errors
+    /// Returns `KvCacheError::LayerOutOfRange` or `KvCacheError::Tensor`
+    pub fn keys_values(&self, layer: usize) -> Result<Option<(Tensor, Tensor)>, KvCacheError> {
+        let pages = self.layer(layer)?;
+        if pages.is_empty() {
+            return Ok(None);
+        }
+        let keys: Vec<&Tensor> = pages.iter().map(|page| &page.keys).collect();
+        let values: Vec<&Tensor> = pages.iter().map(|page| &page.values).collect();
+        Ok(Some((
+            Tensor::cat(&keys, SEQ_DIM)?,
+            Tensor::cat(&values, SEQ_DIM)?,
+        )))
+    }
+
+    /// Append to `layer` and return the full keys and values for attention
+    ///
+    /// # Errors
+    /// See [`PagedKvCache::append`]
+    pub fn append_and_get(
+        &mut self,
+        layer: usize,
+        keys: &Tensor,
+        values: &Tensor,
+    ) -> Result<(Tensor, Tensor), KvCacheError> {
+        self.append(layer, keys, values)?;
+        // An empty cache after appending means the input itself was empty
+        Ok(self
+            .keys_values(layer)?
+            .unwrap_or_else(|| (keys.clone(), values.clone())))
+    }
+
+    /// Token positions cached in the window (layer 0)
+    pub fn len(&self) -> usize {
+        self.layers
+            .first()
+            .map_or(0, |pages| pages.iter().map(|page| page.len).sum())
+    }
+
+    /// Check if the cache holds no tokens
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tokens evicted from the front of the window so far
+    pub fn evicted_tokens(&self) -> usize {
+        self.evicted_tokens
+    }
+
+    /// Absolute position of the next token (cached plus evicted)
+    pub fn next_position(&self) -> usize {
+        self.evicted_tokens + self.len()
+    }
+
+    /// Pages currently allocated across all layers
+    pub fn page_count(&self) -> usize {
+        self.layers.iter().map(VecDeque::len).sum()
+    }
+
+    /// Keep only the first `len` cached positions in every layer
+    ///
+    /// # Errors
+    /// Returns `KvCacheError::Tensor` if a partial page cannot be narrowed
+    pub fn truncate(&mut self, len: usize) -> Result<(), KvCacheError> {
+        for pages in &mut self.layers {
+            let mut kept = 0;
+            let mut keep_pages = 0;
+            for page in pages.iter_mut() {
+                if kept >= len {
+                    break;
+                }
+                if kept + page.len > len {
+                    let take = len - kept;
+                    page.keys = page.keys.narrow(SEQ_DIM, 0, take)?;
+                    page.values = page.values.narrow(SEQ_DIM, 0, take)?;
+                    page.len = take;
+                }
+                kept += page.len;
+                keep_pages += 1;
+            }
+            pages.truncate(keep_pages);
+        }
+        Ok(())
+    }
+
+    /// Drop every cached page and reset eviction accounting
+    pub fn clear(&mut self) {
+        for pages in &mut self.layers {
+            pages.clear();
+        }
+        self.evicted_tokens = 0;
+    }
+
+    fn layer(&self, layer: usize) -> Result<&VecDeque<KvPage>, KvCacheError> {
+        let layers = self.layers.len();
+        self.layers
+            .get(layer)
+            .ok_or(KvCacheError::LayerOutOfRange { layer, layers })
+    }
+
+    fn layer_mut(&mut self, layer: usize) -> Result<&mut VecDeque<KvPage>, KvCacheError> {
+        let layers = self.layers.len();
+        self.layers
+            .get_mut(layer)
+            .ok_or(KvCacheError::LayerOutOfRange { layer, layers })
+    }
+
+    fn layer_len(&self, layer: usize) -> Result<usize, KvCacheError> {
+        Ok(self.layer(layer)?.iter().map(|page| page.len).sum())
+    }
+}
@@ -183,6 +183,9 @@ where
 
 // Re-export commonly used types
 // REMOVED: pub use futures::stream::Stream; - ALL FUTURES ELIMINATED!
+/// Context window sizing and overflow policies
+pub mod context_window;
+
 /// GPU device detection utilities
 pub mod device_util;
 
@@ -192,9 +195,15 @@ pub mod engine;
 /// Advanced constrained generation with sampling strategies
 pub mod generation;
 
+/// Paged key/value cache for attention layers
+pub mod kv_cache;
+
 /// Unified model configuration system for hundreds of models
 pub mod model_config;
 
+/// Layer-wise placement of large checkpoints across devices
+pub mod placement;
+
 /// SIMD adapter functions for bridging cyrup_simd with generation types
 pub mod simd_adapters;
 
@@ -202,9 +211,16 @@ pub mod simd_adapters;
 pub mod tokenizer;
 
 // Re-export core types
+pub use context_window::{
+    CONTEXT_OVERFLOW_PARAM, ContextOverflowError, ContextOverflowPolicy, ContextWindow,
+    FittedPrompt, TurnTokenUsage, estimate_tokens,
+};
+pub use device_util::{AcceleratorInfo, DeviceConfig, DeviceSpec};
 pub use engine::*;
 pub use generation::*;
+pub use kv_cache::{KvCacheConfig, KvCacheError, PagedKvCache};
 pub use model_config::*;
+pub use placement::{LayerPlacement, LayerShard, PlacementError, plan_layer_placement};
 pub use simd_adapters::{
     should_use_simd, simd_argmax_with_bounds, simd_error_to_fallback_strategy,
     simd_softmax_with_cache, simd_temperature_scale,
@@ -0,0 +1,193 @@
+//! Layer-wise placement of large checkpoints across devices
+//!
+//! A checkpoint too large for one GPU can be split by decoder layer: the
+//! first layers live on the first device, the next on the second, and so on,
+//! with activations handed from one device to the next. [`plan_layer_placement`]
+//! decides the split, giving each device a contiguous run of layers in
+//! proportion to the memory it has free (keeping [`PLACEMENT_HEADROOM_PERCENT`]
+//! for activations and the KV cache).
+//!
+//! Executing a split needs per-layer device support in the model. Models
+//! without it load entirely on the plan's first device, which is also where
+//! [`crate::core::device_util::detect_best_device`] puts them.
+
+use std::fmt;
+use std::ops::Range;
+
+use thiserror::Error;
+
+use super::device_util::{AcceleratorInfo, DeviceSpec};
+
+/// Share of a device's free memory a placement may fill
+pub const PLACEMENT_HEADROOM_PERCENT: u64 = 90;
+
+/// Placement planning errors
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PlacementError {
+    #[error("No devices to place layers on")]
+    NoDevices,
+    #[error("Model has no layers to place")]
+    NoLayers,
+}
+
+/// A run of layers assigned to one device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerShard {
+    pub device: DeviceSpec,
+    /// Layer indices held by the device
+    pub layers: Range<usize>,
+    /// Estimated weight bytes of those layers
+    pub bytes: u64,
+    /// Bytes the device may fill, when its memory is known
+    pub capacity_bytes: Option<u64>,
+}
+
+impl LayerShard {
+    /// Whether the layers fit the device, or its memory is unknown
+    pub fn fits(&self) -> bool {
+        self.capacity_bytes
+            .is_none_or(|capacity| self.bytes <= capacity)
+    }
+}
+
+/// Devices and the layers each holds, in layer order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerPlacement {
+    pub shards: Vec<LayerShard>,
+}
+
+impl LayerPlacement {
+    /// Whether every shard fits its device
+    pub fn fits(&self) -> bool {
+        self.shards.iter().all(LayerShard::fits)
+    }
+
+    /// Device holding `layer`
+    pub fn device_for_layer(&self, layer: usize) -> Option<DeviceSpec> {
+        self.shards
+            .iter()
+            .find(|shard| shard.layers.contains(&layer))
+            .map(|shard| shard.device)
+    }
+
+    /// Device holding the first layers, where single-device models load
+    pub fn primary(&self) -> Option<DeviceSpec> {
+        self.shards.first().map(|shard| shard.device)
+    }
+}
+
+impl fmt::Display for LayerPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for shard in &self.shards {
+            write!(
+                f,
+                "{:<8} layers {:>3}-{:<3} {:>9}",
+                shard.device.to_string(),
+                shard.layers.start,
+                shard.layers.end.saturating_sub(1),
+                format_bytes(shard.bytes)
+            )?;
+            match shard.capacity_bytes {
+                Some(capacity) if shard.fits() => {
+                    writeln!(f, " of {} usable", format_bytes(capacity))?
+                }
+                Some(capacity) => {
+                    writeln!(f, " of {} usable - DOES NOT FIT", format_bytes(capacity))?
+                }
+                None => writeln!(f, " (memory unknown)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split `num_layers` layers totalling `model_bytes` across `devices`
+///
+/// Each device gets a contiguous run of layers in device order, sized in
+/// proportion to its usable memory; when any device's memory is unknown the
+/// layers are split evenly. Devices that end up with no layers are left out.
+///
+/// # Errors
+/// Returns `PlacementError` if there are no devices or no layers
+pub fn plan_layer_placement(
+    num_layers: usize,
+    model_bytes: u64,
+    devices: &[AcceleratorInfo],
+) -> Result<LayerPlacement, PlacementError> {
+    if devices.is_empty() {
+        return Err(PlacementError::NoDevices);
+    }
+    if num_layers == 0 {
+        return Err(PlacementError::NoLayers);
+    }
+
+    let capacities: Vec<Option<u64>> = devices
+        .iter()
+        .map(|device| {
+            device
+                .capacity_bytes()
+                .map(|bytes| bytes / 100 * PLACEMENT_HEADROOM_PERCENT)
+        })
+        .collect();
+    let weights: Vec<u64> = match capacities.iter().copied().collect::<Option<Vec<u64>>>() {
+        Some(known) if known.iter().any(|&bytes| bytes > 0) => known,
+        _ => vec![1; devices.len()],
+    };
+
+    let counts = apportion(num_layers, &weights);
+    let bytes_per_layer = model_bytes / num_layers as u64;
+    let mut shards = Vec::new();
+    let mut start = 0;
+    for ((device, capacity), count) in devices.iter().zip(capacities).zip(counts) {
+        if count == 0 {
+            continue;
+        }
+        let end = start + count;
+        // The last shard takes the rounding remainder of the byte estimate
+        let bytes = if end == num_layers {
+            model_bytes - bytes_per_layer * start as u64
+        } else {
+            bytes_per_layer * count as u64
+        };
+        shards.push(LayerShard {
+            device: device.spec,
+            layers: start..end,
+            bytes,
+            capacity_bytes: capacity,
+        });
+        start = end;
+    }
+
+    Ok(LayerPlacement { shards })
+}
+
+/// Split `total` into integer parts proportional to `weights` (largest remainder)
+fn apportion(total: usize, weights: &[u64]) -> Vec<usize> {
+    let sum: u128 = weights.iter().map(|&w| u128::from(w)).sum();
+    let mut counts = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+    for (index, &weight) in weights.iter().enumerate() {
+        let exact = total as u128 * u128::from(weight);
+        counts.push((exact / sum) as usize);
+        remainders.push((exact % sum, index));
+    }
+
+    let assigned: usize = counts.iter().sum();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, index) in remainders.iter().take(total - assigned) {
+        counts[index] += 1;
+    }
+    counts
+}
+
+/// Bytes as GiB or MiB with one decimal
+pub fn format_bytes(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GIB {
+        format!("{:.1} GiB", bytes / GIB)
+    } else {
+        format!("{:.1} MiB", bytes / MIB)
+    }
+}
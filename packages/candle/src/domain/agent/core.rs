@@ -259,6 +259,7 @@ impl<M: Model + Clone + Send + 'static + Default> Agent<M> {
                 api: None,
                 cache: crate::memory::utils::config::CacheConfig::default(),
                 logging: crate::memory::utils::config::LoggingConfig::default(),
+                decay: crate::memory::DecayWorkerConfig::default(),
             })
             .await
             {
@@ -368,6 +369,7 @@ impl<M: Model + Clone + Send + 'static + Default> Agent<M> {
                 api: None,
                 cache: crate::memory::utils::config::CacheConfig::default(),
                 logging: crate::memory::utils::config::LoggingConfig::default(),
+                decay: crate::memory::DecayWorkerConfig::default(),
             })
             .await
             {
@@ -0,0 +1,322 @@
+//! Conversation history compaction
+//!
+//! A chat prompt carries the conversation so far. When the prompt nears the
+//! model's context window, [`compact_history`] asks the session's own model
+//! to summarize the oldest turns and puts that summary in their place, so
+//! the newest turns stay verbatim and the prompt keeps fitting. The session
+//! also stores each summary in memory, where it can be retrieved once it has
+//! left the prompt.
+//!
+//! Compaction starts when the estimated prompt size passes
+//! [`CompactionConfig::trigger_ratio`] of the prompt budget, and summarizes
+//! just enough turns to bring it back down to
+//! [`CompactionConfig::target_ratio`].
+
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::capability::traits::TextToTextCapable;
+use crate::core::context_window::estimate_tokens;
+use crate::domain::chat::message::CandleMessageRole;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::prompt::CandlePrompt;
+
+/// Memory tag of stored conversation summaries
+pub const SUMMARY_TAG: &str = "message_type.summary";
+
+/// Context window assumed when neither the config nor the model sets one
+const FALLBACK_CONTEXT_WINDOW: usize = 4096;
+
+/// Prompt tokens per message on top of its content, for the role prefix
+const MESSAGE_OVERHEAD_TOKENS: usize = 2;
+
+/// Summarization failures
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CompactionError {
+    #[error("Summarization failed: {0}")]
+    Provider(String),
+    #[error("Summarization returned an empty summary")]
+    EmptySummary,
+}
+
+/// When and how far conversation history is compacted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompactionConfig {
+    /// Summarize old turns at all
+    pub enabled: bool,
+    /// Fraction of the prompt budget the prompt may fill before compacting
+    pub trigger_ratio: f32,
+    /// Fraction of the prompt budget the prompt is compacted down to
+    pub target_ratio: f32,
+    /// Newest messages that are never summarized
+    pub keep_recent: usize,
+    /// Longest summary the model may write, in tokens
+    pub max_summary_tokens: u32,
+    /// Context window in tokens; the model's input limit when unset
+    pub context_window: Option<usize>,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trigger_ratio: 0.8,
+            target_ratio: 0.5,
+            keep_recent: 4,
+            max_summary_tokens: 512,
+            context_window: None,
+        }
+    }
+}
+
+impl CompactionConfig {
+    /// Never summarize; history that does not fit is cut by the context window
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Set the fill ratio that starts compaction, between 0.1 and 1.0
+    #[must_use]
+    pub fn with_trigger_ratio(mut self, ratio: f32) -> Self {
+        self.trigger_ratio = ratio.clamp(0.1, 1.0);
+        self.target_ratio = self.target_ratio.min(self.trigger_ratio);
+        self
+    }
+
+    /// Set the fill ratio compaction aims for, at most the trigger ratio
+    #[must_use]
+    pub fn with_target_ratio(mut self, ratio: f32) -> Self {
+        self.target_ratio = ratio.clamp(0.0, self.trigger_ratio);
+        self
+    }
+
+    /// Set how many of the newest messages are always kept verbatim
+    #[must_use]
+    pub fn with_keep_recent(mut self, messages: usize) -> Self {
+        self.keep_recent = messages;
+        self
+    }
+
+    /// Set the longest summary, at least 16 tokens
+    #[must_use]
+    pub fn with_max_summary_tokens(mut self, tokens: u32) -> Self {
+        self.max_summary_tokens = tokens.max(16);
+        self
+    }
+
+    /// Use a context window other than the model's input limit
+    #[must_use]
+    pub fn with_context_window(mut self, tokens: usize) -> Self {
+        self.context_window = Some(tokens);
+        self
+    }
+
+    /// Context window for a model whose input limit is `model_max_tokens`
+    pub fn window_for(&self, model_max_tokens: Option<u32>) -> usize {
+        self.context_window
+            .or_else(|| model_max_tokens.map(|tokens| tokens as usize))
+            .unwrap_or(FALLBACK_CONTEXT_WINDOW)
+    }
+}
+
+/// Conversation history as it is sent in the prompt
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveHistory {
+    /// Summary standing in for turns compacted away
+    pub summary: Option<String>,
+    /// Messages kept verbatim, oldest first
+    pub messages: Vec<(CandleMessageRole, String)>,
+}
+
+impl LiveHistory {
+    /// History of `messages` without a summary
+    pub fn new(messages: Vec<(CandleMessageRole, String)>) -> Self {
+        Self {
+            summary: None,
+            messages,
+        }
+    }
+
+    /// Whether there is nothing to render
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_none() && self.messages.is_empty()
+    }
+
+    /// Append a message
+    pub fn push(&mut self, role: CandleMessageRole, content: impl Into<String>) {
+        self.messages.push((role, content.into()));
+    }
+
+    /// Estimated prompt tokens of the rendered history
+    pub fn estimated_tokens(&self) -> usize {
+        self.summary_tokens() + self.messages.iter().map(message_cost).sum::<usize>()
+    }
+
+    fn summary_tokens(&self) -> usize {
+        self.summary.as_deref().map_or(0, |summary| {
+            estimate_tokens(summary) + MESSAGE_OVERHEAD_TOKENS
+        })
+    }
+
+    /// Replace the oldest `count` messages with `summary`
+    ///
+    /// The summary is expected to cover any previous summary as well.
+    pub fn compact(&mut self, count: usize, summary: String) {
+        self.messages.drain(..count.min(self.messages.len()));
+        self.summary = Some(summary);
+    }
+
+    /// Render as prompt history, summary first
+    ///
+    /// Uses the `User:` / `Assistant:` layout of the chat prompt and of
+    /// [`crate::domain::chat::transcript::render_history`].
+    pub fn render(&self) -> String {
+        let recent = render_messages(&self.messages);
+        match &self.summary {
+            Some(summary) if recent.is_empty() => format!("Summary of earlier turns:\n{summary}"),
+            Some(summary) => format!("Summary of earlier turns:\n{summary}\n\n{recent}"),
+            None => recent,
+        }
+    }
+}
+
+/// Number of oldest messages to summarize so the prompt fits again
+///
+/// `overhead_tokens` is the rest of the prompt (system prompt, memories and
+/// the new user message) and `budget_tokens` the prompt tokens the window
+/// allows. Returns 0 when compaction is disabled, the prompt is under the
+/// trigger, or only the protected recent messages are left.
+pub fn plan_compaction(
+    history: &LiveHistory,
+    overhead_tokens: usize,
+    budget_tokens: usize,
+    config: &CompactionConfig,
+) -> usize {
+    if !config.enabled {
+        return 0;
+    }
+    let total = overhead_tokens + history.estimated_tokens();
+    let trigger = (budget_tokens as f64 * f64::from(config.trigger_ratio)) as usize;
+    if total <= trigger {
+        return 0;
+    }
+
+    let target = (budget_tokens as f64 * f64::from(config.target_ratio)) as usize;
+    let eligible = history.messages.len().saturating_sub(config.keep_recent);
+    // The new summary replaces the previous one
+    let mut remaining = total - history.summary_tokens() + config.max_summary_tokens as usize;
+    let mut count = 0;
+    while count < eligible && remaining > target {
+        remaining -= message_cost(&history.messages[count]);
+        count += 1;
+    }
+    count
+}
+
+/// Ask `model` to summarize `messages`, folding in `previous_summary`
+///
+/// # Errors
+/// Returns `CompactionError` when the model fails or writes nothing
+pub async fn summarize_messages(
+    model: &dyn TextToTextCapable,
+    previous_summary: Option<&str>,
+    messages: &[(CandleMessageRole, String)],
+    config: &CompactionConfig,
+) -> Result<String, CompactionError> {
+    let params = CandleCompletionParams {
+        temperature: 0.2,
+        max_tokens: std::num::NonZeroU64::new(u64::from(config.max_summary_tokens)),
+        ..Default::default()
+    };
+    let prompt = summary_prompt(previous_summary, messages, config.max_summary_tokens);
+
+    let mut stream = model.prompt(CandlePrompt::new(prompt), &params);
+    let mut summary = String::new();
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            CandleCompletionChunk::Text(text) => summary.push_str(&text),
+            CandleCompletionChunk::Complete { text, .. } => summary.push_str(&text),
+            CandleCompletionChunk::Error(e) => return Err(CompactionError::Provider(e)),
+            _ => {}
+        }
+    }
+
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err(CompactionError::EmptySummary);
+    }
+    Ok(summary.to_string())
+}
+
+/// Summarize the oldest turns of `history` if the prompt nears the budget
+///
+/// Returns the new summary when turns were compacted. On error the history
+/// is left unchanged.
+///
+/// # Errors
+/// Returns `CompactionError` when summarization fails
+pub async fn compact_history(
+    model: &dyn TextToTextCapable,
+    history: &mut LiveHistory,
+    overhead_tokens: usize,
+    budget_tokens: usize,
+    config: &CompactionConfig,
+) -> Result<Option<String>, CompactionError> {
+    let count = plan_compaction(history, overhead_tokens, budget_tokens, config);
+    if count == 0 {
+        return Ok(None);
+    }
+    let summary = summarize_messages(
+        model,
+        history.summary.as_deref(),
+        &history.messages[..count],
+        config,
+    )
+    .await?;
+    history.compact(count, summary.clone());
+    Ok(Some(summary))
+}
+
+fn message_cost(message: &(CandleMessageRole, String)) -> usize {
+    estimate_tokens(&message.1) + MESSAGE_OVERHEAD_TOKENS
+}
+
+fn render_messages(messages: &[(CandleMessageRole, String)]) -> String {
+    messages
+        .iter()
+        .filter_map(|(role, content)| match role {
+            CandleMessageRole::User => Some(format!("User: {content}")),
+            CandleMessageRole::Assistant => Some(format!("Assistant: {content}")),
+            CandleMessageRole::System | CandleMessageRole::Tool => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn summary_prompt(
+    previous_summary: Option<&str>,
+    messages: &[(CandleMessageRole, String)],
+    max_tokens: u32,
+) -> String {
+    // Roughly three words per four tokens
+    let max_words = max_tokens as usize * 3 / 4;
+    let mut prompt = format!(
+        "Summarize the conversation below so it can stand in for it later. \
+         Keep names, facts the user shared, decisions, open questions and \
+         commitments; leave out greetings and filler. Write short bullet \
+         points, at most {max_words} words in total, and nothing else."
+    );
+    if let Some(summary) = previous_summary {
+        prompt.push_str(&format!(
+            "\n\nSummary of the conversation before this part:\n{summary}"
+        ));
+    }
+    prompt.push_str(&format!(
+        "\n\nConversation:\n{}\n\nSummary:",
+        render_messages(messages)
+    ));
+    prompt
+}
@@ -2,6 +2,8 @@
 
 use std::fmt;
 
+use super::message::types::CandleImageAttachment;
+
 /// Controls the flow of a chat conversation in the unified domain system.
 ///
 /// This enum is used to control the flow of a chat conversation, allowing for breaking out of loops,
@@ -19,6 +21,10 @@ pub enum CandleChatLoop {
     /// Prompt the user for input and continue the conversation.
     /// The String contains the prompt message.
     UserPrompt(String),
+
+    /// Send a user message together with attached images.
+    /// The turn is answered by a vision model.
+    UserPromptWithImages(String, Vec<CandleImageAttachment>),
 }
 
 impl fmt::Display for CandleChatLoop {
@@ -29,6 +35,13 @@ impl fmt::Display for CandleChatLoop {
             CandleChatLoop::UserPrompt(prompt) => {
                 write!(f, "CandleChatLoop::UserPrompt({prompt:?})")
             }
+            CandleChatLoop::UserPromptWithImages(prompt, images) => {
+                write!(
+                    f,
+                    "CandleChatLoop::UserPromptWithImages({prompt:?}, {} images)",
+                    images.len()
+                )
+            }
         }
     }
 }
@@ -48,5 +61,13 @@ mod tests {
             CandleChatLoop::UserPrompt("What's next?".to_string()).to_string(),
             "CandleChatLoop::UserPrompt(\"What's next?\")"
         );
+        assert_eq!(
+            CandleChatLoop::UserPromptWithImages(
+                "Describe".to_string(),
+                vec![CandleImageAttachment::new("cat.png")]
+            )
+            .to_string(),
+            "CandleChatLoop::UserPromptWithImages(\"Describe\", 1 images)"
+        );
     }
 }
@@ -15,6 +15,9 @@ pub mod types {
     use cyrup_sugars::prelude::MessageChunk;
     use serde::{Deserialize, Serialize};
 
+    use super::media::CandleImageMediaType;
+    use crate::domain::model::CandleUsage;
+
     /// Represents a Candle chat message with role and content
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct CandleMessage {
@@ -144,7 +147,7 @@ pub mod types {
         pub fn complete(
             text: impl Into<String>,
             finish_reason: Option<String>,
-            usage: Option<String>,
+            usage: Option<CandleUsage>,
         ) -> Self {
             Self::Complete {
                 text: text.into(),
@@ -153,6 +156,7 @@ pub mod types {
                 token_count: None,
                 elapsed_secs: None,
                 tokens_per_sec: None,
+                provider: None,
             }
         }
 
@@ -200,6 +204,40 @@ pub mod types {
         }
     }
 
+    /// An image attached to a user turn
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct CandleImageAttachment {
+        /// Local file path or http(s) URL of the image
+        pub source: String,
+        /// Image format, guessed from the file extension
+        pub media_type: CandleImageMediaType,
+    }
+
+    impl CandleImageAttachment {
+        /// Attach the image at a local path or URL
+        pub fn new(source: impl Into<String>) -> Self {
+            let source = source.into();
+            let extension = source
+                .rsplit_once('.')
+                .map(|(_, extension)| extension.to_ascii_lowercase())
+                .unwrap_or_default();
+            let media_type = match extension.as_str() {
+                "jpg" | "jpeg" => CandleImageMediaType::Jpeg,
+                "png" => CandleImageMediaType::Png,
+                "gif" => CandleImageMediaType::Gif,
+                "webp" => CandleImageMediaType::WebP,
+                _ => CandleImageMediaType::Other(extension),
+            };
+            Self { source, media_type }
+        }
+
+        /// Check if the image is fetched from a URL rather than read from disk
+        #[must_use]
+        pub fn is_url(&self) -> bool {
+            self.source.starts_with("http://") || self.source.starts_with("https://")
+        }
+    }
+
     /// A chunk of a streaming Candle message
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub enum CandleMessageChunk {
@@ -227,10 +265,13 @@ pub mod types {
         Complete {
             text: String,
             finish_reason: Option<String>,
-            usage: Option<String>,
+            /// Prompt and completion token counts for the turn
+            usage: Option<CandleUsage>,
             token_count: Option<u32>,
             elapsed_secs: Option<f64>,
             tokens_per_sec: Option<f64>,
+            /// Name of the provider that produced the response
+            provider: Option<String>,
         },
 
         /// Error occurred during streaming
@@ -266,10 +307,11 @@ pub mod types {
                         output.push_str(reason);
                         output.push(']');
                     }
-                    if let Some(usage_info) = usage {
-                        output.push_str(" (");
-                        output.push_str(usage_info);
-                        output.push(')');
+                    if let Some(usage) = usage {
+                        output.push_str(&format!(
+                            " ({} prompt + {} completion tokens)",
+                            usage.input_tokens, usage.output_tokens
+                        ));
                     }
                     write!(f, "{output}")
                 }
@@ -547,7 +589,7 @@ pub use media::{
     CandleVideoMediaType,
 };
 pub use types::{
-    CandleMessage, CandleMessageChunk, CandleMessageRole, CandleMessageType,
+    CandleImageAttachment, CandleMessage, CandleMessageChunk, CandleMessageRole, CandleMessageType,
     CandleSearchChatMessage,
 };
 
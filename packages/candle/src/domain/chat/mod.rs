@@ -19,6 +19,7 @@
 //! for thread-safe state management.
 
 pub mod commands;
+pub mod compaction;
 pub mod config;
 pub mod conversation;
 pub mod export;
@@ -29,16 +30,22 @@ pub mod r#loop;
 pub mod macros;
 pub mod message;
 pub mod realtime;
+pub mod reasoning;
 pub mod search;
 pub mod session;
+pub mod system_prompt;
 pub mod templates;
+pub mod transcript;
 pub mod types;
+pub mod usage;
+pub mod vision;
 
 // Re-export types with corrected names to avoid ambiguous glob re-exports
 pub use commands::{
     CommandExecutor as CandleCommandExecutor, CommandRegistry as CandleCommandRegistry,
     ImmutableChatCommand as CandleImmutableChatCommand,
 };
+pub use compaction::{CompactionConfig, CompactionError, LiveHistory, compact_history};
 pub use config::{CandleChatConfig, CandlePersonalityConfig};
 pub use conversation::CandleConversationEvent as CandleConversation;
 pub use export::{ExportData as CandleExportData, ExportFormat as CandleExportFormat};
@@ -57,8 +64,11 @@ pub use message::message_processing::{
     validate_message as candle_validate_message,
     validate_message_sync as candle_validate_message_sync,
 };
-pub use message::types::{CandleMessage, CandleMessageChunk, CandleMessageRole};
+pub use message::types::{
+    CandleImageAttachment, CandleMessage, CandleMessageChunk, CandleMessageRole,
+};
 pub use realtime::RealTimeSystem as CandleRealTimeSystem;
+pub use reasoning::{ReasoningPath, ReasoningStrategy, ThinkConfig, ThoughtScorer};
 pub use search::{
     CandleConversationTag, CandleConversationTagger, CandleEnhancedHistoryManager,
     CandleTaggingStatistics, ChatSearchIndex as CandleChatSearchIndex,
@@ -73,11 +83,16 @@ pub use templates::{
     ChatTemplate as CandleChatTemplate, TemplateCategory as CandleTemplateCategory,
     TemplateManager as CandleTemplateManager,
 };
+pub use transcript::{
+    FittedHistory, SessionSummary, TranscriptEntry, TranscriptError, TranscriptRole,
+    TranscriptStore,
+};
 pub use types::responses::{
     FinalResponse as CandleFinalResponse, FunctionCall as CandleFunctionCall,
     OpenAIFunctionCallResponse as CandleOpenAIFunctionCallResponse, ToolCall as CandleToolCall,
     ToolSelectionResponse as CandleToolSelectionResponse,
 };
+pub use usage::{ConversationUsage, TokenPricing, ToolCallUsage, UsageTracker};
 
 // ============================================================================
 // TYPE MIGRATION MAPPING
@@ -0,0 +1,321 @@
+//! Intermediate reasoning through the MCP reasoner
+//!
+//! Before answering, an agent configured with [`ThinkConfig`] explores
+//! several chains of thought. The text model proposes candidate next steps,
+//! the `mcp-reasoner` plugin scores each one against its parent with the
+//! configured strategy, and only the best `beam_width` chains survive to the
+//! next step. The winning [`ReasoningPath`] is handed to the final prompt.
+//!
+//! The plugin only scores thoughts; the search itself is driven here, so the
+//! MCTS strategies change how steps are scored rather than how many the
+//! model generates.
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::agent::role::convert_serde_to_sweet_json;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::prompt::CandlePrompt;
+use crate::domain::tool::SweetMcpRouter;
+
+/// Tool name the reasoner plugin is registered under
+pub const REASONER_TOOL: &str = "mcp-reasoner";
+
+/// Search strategy the reasoner scores thoughts with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReasoningStrategy {
+    /// Keep the best-scoring chains at every step
+    #[default]
+    #[serde(rename = "beam_search")]
+    BeamSearch,
+    /// Monte Carlo tree search
+    #[serde(rename = "mcts")]
+    Mcts,
+    /// MCTS with the 0.02 alpha exploration variant
+    #[serde(rename = "mcts_002_alpha")]
+    Mcts002Alpha,
+    /// MCTS with the alternative 0.02 alpha exploration variant
+    #[serde(rename = "mcts_002alt_alpha")]
+    Mcts002AltAlpha,
+}
+
+/// Settings of the `think()` step
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThinkConfig {
+    /// How the reasoner scores thoughts
+    pub strategy: ReasoningStrategy,
+    /// Chains kept after each step, and candidates proposed per chain
+    pub beam_width: usize,
+    /// Reasoning steps before answering
+    pub max_steps: u32,
+    /// Simulations per step for the MCTS strategies
+    pub num_simulations: Option<u32>,
+    /// Stream the selected thoughts to the caller before the answer
+    pub show_thoughts: bool,
+    /// Store the selected thoughts in memory
+    pub store_in_memory: bool,
+}
+
+impl Default for ThinkConfig {
+    fn default() -> Self {
+        Self {
+            strategy: ReasoningStrategy::BeamSearch,
+            beam_width: 2,
+            max_steps: 3,
+            num_simulations: None,
+            show_thoughts: false,
+            store_in_memory: true,
+        }
+    }
+}
+
+impl ThinkConfig {
+    /// Set the scoring strategy
+    #[must_use]
+    pub fn with_strategy(mut self, strategy: ReasoningStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the beam width, at least 1
+    #[must_use]
+    pub fn with_beam_width(mut self, beam_width: usize) -> Self {
+        self.beam_width = beam_width.max(1);
+        self
+    }
+
+    /// Set the number of reasoning steps, at least 1
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Set the MCTS simulations per step
+    #[must_use]
+    pub fn with_num_simulations(mut self, num_simulations: u32) -> Self {
+        self.num_simulations = Some(num_simulations);
+        self
+    }
+
+    /// Stream or hide the selected thoughts
+    #[must_use]
+    pub fn with_show_thoughts(mut self, show_thoughts: bool) -> Self {
+        self.show_thoughts = show_thoughts;
+        self
+    }
+
+    /// Store the selected thoughts in memory or not
+    #[must_use]
+    pub fn with_store_in_memory(mut self, store_in_memory: bool) -> Self {
+        self.store_in_memory = store_in_memory;
+        self
+    }
+}
+
+/// One thought submitted to the reasoner, in the plugin's request format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThoughtStep {
+    pub thought: String,
+    pub thought_number: u32,
+    pub total_thoughts: u32,
+    pub next_thought_needed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    pub strategy_type: ReasoningStrategy,
+    pub beam_width: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_simulations: Option<u32>,
+}
+
+/// The reasoner's verdict on one thought
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThoughtScore {
+    /// Node the thought was stored as, parent of its follow-up steps
+    #[serde(default)]
+    pub node_id: Option<String>,
+    /// Higher is better
+    pub score: f64,
+}
+
+/// Scores thoughts for the reasoning search
+pub trait ThoughtScorer: Send + Sync {
+    /// Score `step` as a child of `step.parent_id`
+    fn score<'a>(&'a self, step: &'a ThoughtStep) -> BoxFuture<'a, Result<ThoughtScore, String>>;
+}
+
+impl ThoughtScorer for SweetMcpRouter {
+    fn score<'a>(&'a self, step: &'a ThoughtStep) -> BoxFuture<'a, Result<ThoughtScore, String>> {
+        Box::pin(async move {
+            let args = serde_json::to_value(step).map_err(|e| e.to_string())?;
+            let result = self
+                .call_tool(REASONER_TOOL, convert_serde_to_sweet_json(args))
+                .await
+                .map_err(|e| e.to_string())?;
+            parse_thought_score(&result)
+        })
+    }
+}
+
+/// Extract the score from a reasoner tool result
+///
+/// Accepts the plugin response itself, an MCP `content` list whose first
+/// text item holds it, or a sandbox execution result whose `stdout` does.
+pub fn parse_thought_score(result: &Value) -> Result<ThoughtScore, String> {
+    if result.get("score").is_some() {
+        return serde_json::from_value(result.clone())
+            .map_err(|e| format!("Invalid reasoner response: {e}"));
+    }
+
+    let embedded = result
+        .pointer("/content/0/text")
+        .or_else(|| result.get("stdout"))
+        .and_then(Value::as_str);
+    match embedded {
+        Some(text) => {
+            let inner: Value = serde_json::from_str(text.trim())
+                .map_err(|e| format!("Invalid reasoner response: {e}"))?;
+            if inner.get("score").is_none() {
+                return Err(format!("Reasoner response has no score: {text}"));
+            }
+            parse_thought_score(&inner)
+        }
+        None => Err(format!("Reasoner response has no score: {result}")),
+    }
+}
+
+/// The chain of thoughts selected by the search
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReasoningPath {
+    /// Thoughts in order
+    pub thoughts: Vec<String>,
+    /// Reasoner score of the last thought
+    pub score: f64,
+}
+
+impl ReasoningPath {
+    /// Whether the search produced no thoughts
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.thoughts.is_empty()
+    }
+
+    /// Thoughts as a numbered list
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.thoughts
+            .iter()
+            .enumerate()
+            .map(|(i, thought)| format!("{}. {thought}", i + 1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Beam {
+    path: ReasoningPath,
+    node_id: Option<String>,
+}
+
+fn thought_prompt(question: &str, thoughts: &[String], step: u32, total: u32) -> String {
+    let mut prompt = format!(
+        "Reason step by step about how to answer the question below. \
+         Write only reasoning step {step} of {total} as one or two sentences, \
+         without answering yet.\n\nQuestion: {question}"
+    );
+    if !thoughts.is_empty() {
+        prompt.push_str("\n\nPrevious steps:");
+        for (i, thought) in thoughts.iter().enumerate() {
+            prompt.push_str(&format!("\n{}. {thought}", i + 1));
+        }
+    }
+    prompt.push_str(&format!("\n\nStep {step}:"));
+    prompt
+}
+
+async fn generate_thought(
+    model: &dyn TextToTextCapable,
+    prompt: String,
+    params: &CandleCompletionParams,
+) -> Result<String, String> {
+    let mut stream = model.prompt(CandlePrompt::new(prompt), params);
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            CandleCompletionChunk::Text(t) => text.push_str(&t),
+            CandleCompletionChunk::Complete { text: t, .. } => text.push_str(&t),
+            CandleCompletionChunk::Error(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(text.trim().to_string())
+}
+
+/// Search for the best chain of thoughts about `question`
+///
+/// At each step every kept chain proposes `beam_width` candidate thoughts,
+/// duplicates are dropped, and the `beam_width` best-scored chains are kept.
+/// Fails if the model or the scorer fails.
+pub async fn think(
+    model: &dyn TextToTextCapable,
+    scorer: &dyn ThoughtScorer,
+    question: &str,
+    config: &ThinkConfig,
+    params: &CandleCompletionParams,
+) -> Result<ReasoningPath, String> {
+    let beam_width = config.beam_width.max(1);
+    let total = config.max_steps.max(1);
+    let mut beams = vec![Beam::default()];
+
+    for step in 1..=total {
+        let mut candidates: Vec<Beam> = Vec::new();
+        for beam in &beams {
+            let mut proposed: Vec<String> = Vec::with_capacity(beam_width);
+            for _ in 0..beam_width {
+                let prompt = thought_prompt(question, &beam.path.thoughts, step, total);
+                let thought = generate_thought(model, prompt, params).await?;
+                if thought.is_empty() || proposed.contains(&thought) {
+                    continue;
+                }
+                proposed.push(thought.clone());
+
+                let request = ThoughtStep {
+                    thought: thought.clone(),
+                    thought_number: step,
+                    total_thoughts: total,
+                    next_thought_needed: step < total,
+                    parent_id: beam.node_id.clone(),
+                    strategy_type: config.strategy,
+                    beam_width,
+                    num_simulations: config.num_simulations,
+                };
+                let scored = scorer.score(&request).await?;
+
+                let mut thoughts = beam.path.thoughts.clone();
+                thoughts.push(thought);
+                candidates.push(Beam {
+                    path: ReasoningPath {
+                        thoughts,
+                        score: scored.score,
+                    },
+                    node_id: scored.node_id,
+                });
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+        candidates.sort_by(|a, b| b.path.score.total_cmp(&a.path.score));
+        candidates.truncate(beam_width);
+        beams = candidates;
+    }
+
+    Ok(beams.swap_remove(0).path)
+}
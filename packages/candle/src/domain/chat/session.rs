@@ -16,28 +16,38 @@ use crate::domain::context::provider::{
 
 // Import domain types
 use crate::builders::agent_role::CandleAgentRoleAgent;
+use crate::core::context_window::{ContextOverflowPolicy, ContextWindow, estimate_tokens};
 use crate::domain::agent::core::AGENT_STATS;
 use crate::domain::agent::role::CandleAgentConversation;
 use crate::domain::agent::role::convert_serde_to_sweet_json;
 use crate::domain::chat::{
+    compaction::{CompactionConfig, LiveHistory, SUMMARY_TAG, compact_history},
     config::{CandleChatConfig, CandleModelConfig},
     r#loop::CandleChatLoop,
-    message::{CandleMessageChunk, CandleMessageRole},
+    message::{CandleImageAttachment, CandleMessageChunk, CandleMessageRole},
+    reasoning::{ReasoningPath, ThinkConfig, think},
+    system_prompt::{CompiledSystemPrompt, PromptTurn, SystemPromptTemplate},
+    usage::{ToolCallUsage, UsageTracker},
+    vision::{default_vision_model, describe_attachments, describe_user_turn},
 };
 use crate::domain::completion::CandleCompletionChunk;
 use crate::domain::completion::CandleCompletionParams;
 use crate::domain::prompt::CandlePrompt;
-use crate::domain::tool::SweetMcpRouter;
 use crate::domain::tool::router::PluginConfig;
+use crate::domain::tool::{
+    NativeTool, SandboxProfile, SweetMcpRouter, ToolGuardrails, ToolScheduler,
+};
 
 use crate::builders::agent_role::AgentBuilderState;
-use crate::capability::registry::TextToTextModel;
+use crate::capability::registry::{TextToTextModel, VisionModel};
 use crate::capability::traits::TextToTextCapable;
-use crate::domain::memory::primitives::node::MemoryNode as DomainMemoryNode;
 use crate::domain::memory::primitives::types::MemoryTypeEnum as DomainMemoryTypeEnum;
+use crate::domain::model::traits::CandleModel;
 use crate::memory::MemoryMetadata;
+use crate::memory::RagConfig;
 use crate::memory::core::manager::coordinator::MemoryCoordinator;
 use crate::memory::core::manager::surreal::MemoryManager; // Trait must be in scope
+use crate::memory::ops::retrieval::{apply_context_budgets, format_retrieved_context};
 use crate::memory::primitives::node::MemoryNode as CoreMemoryNode;
 use crate::memory::primitives::types::{MemoryContent, MemoryTypeEnum as CoreMemoryTypeEnum};
 
@@ -65,6 +75,15 @@ pub struct ChatSessionConfig<S> {
     pub memory: Arc<MemoryCoordinator>,
     pub tools: Arc<[ToolInfo]>,
     pub metadata: HashMap<String, String, S>,
+    pub rag: RagConfig,
+    pub usage: UsageTracker,
+    pub vision_model: Option<VisionModel>,
+    pub think: Option<ThinkConfig>,
+    pub tool_guardrails: Option<ToolGuardrails>,
+    pub sandbox_profile: Option<SandboxProfile>,
+    pub system_prompt_template: Option<SystemPromptTemplate>,
+    pub compaction: CompactionConfig,
+    pub native_tools: Vec<NativeTool>,
 }
 
 /// Context sources bundle for chat session
@@ -84,31 +103,6 @@ pub struct ChatSessionHandlers {
 
 // Helper functions for memory operations
 
-fn format_memory_context(memories: &[DomainMemoryNode], max_chars: usize) -> String {
-    let mut result = String::from("## Relevant Context\n\n");
-    let mut current_len = result.len();
-
-    for memory in memories {
-        let content = memory.content().to_string();
-        let source = memory
-            .metadata
-            .custom
-            .get("source")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-        let entry = format!("- [{source}]: {content}\n");
-
-        if current_len + entry.len() > max_chars {
-            break;
-        }
-
-        result.push_str(&entry);
-        current_len += entry.len();
-    }
-
-    result
-}
-
 /// Load documents from a context stream into memory using `MemoryManager` API
 async fn load_context_stream(
     stream: Pin<Box<dyn Stream<Item = crate::domain::context::CandleDocument> + Send>>,
@@ -152,12 +146,16 @@ fn process_break_loop() -> CandleMessageChunk {
         token_count: None,
         elapsed_secs: None,
         tokens_per_sec: None,
+        provider: None,
     }
 }
 
-/// Initialize tool router with reasoner plugin
+/// Initialize tool router with reasoner plugin and the agent's native tools
 async fn initialize_tool_router(
     sender: &tokio::sync::mpsc::UnboundedSender<CandleMessageChunk>,
+    guardrails: Option<&ToolGuardrails>,
+    sandbox_profile: Option<&SandboxProfile>,
+    native_tools: &[NativeTool],
 ) -> Option<SweetMcpRouter> {
     let reasoner_schema = convert_serde_to_sweet_json(serde_json::json!({
         "type": "object",
@@ -178,7 +176,12 @@ async fn initialize_tool_router(
     };
 
     let plugin_configs = vec![default_plugin_config];
-    let mut router = SweetMcpRouter::with_configs(plugin_configs, None);
+    let mut router = SweetMcpRouter::with_configs(plugin_configs, None)
+        .with_native_tools(native_tools.iter().cloned())
+        .with_guardrails(guardrails.cloned().unwrap_or_default());
+    if let Some(profile) = sandbox_profile {
+        router = router.with_sandbox_profile(profile.clone());
+    }
 
     match router.initialize().await {
         Ok(()) => Some(router),
@@ -190,26 +193,56 @@ async fn initialize_tool_router(
     }
 }
 
-/// Search memory and format context
-async fn search_and_format_memory(memory: &Arc<MemoryCoordinator>, user_message: &str) -> String {
-    match memory.search_memories(user_message, 10, None).await {
-        Ok(memories) => {
-            if memories.is_empty() {
-                String::new()
-            } else {
-                format_memory_context(&memories, 2000)
-            }
-        }
+/// Prompt token budgets of the indexed contexts, by context id
+fn context_token_budgets(
+    context_directory: Option<&CandleContext<CandleDirectory>>,
+    context_github: Option<&CandleContext<CandleGithub>>,
+) -> HashMap<String, usize> {
+    let mut budgets = HashMap::new();
+    if let Some(ctx) = context_directory {
+        budgets.insert(ctx.context_id(), ctx.indexing().token_budget);
+    }
+    if let Some(ctx) = context_github {
+        budgets.insert(ctx.context_id(), ctx.indexing().token_budget);
+    }
+    budgets
+}
+
+/// Retrieve relevant memories and format them with provenance markers
+///
+/// Memories indexed from a context are held to that context's budget in
+/// `context_budgets`.
+async fn retrieve_memory_context(
+    memory: &Arc<MemoryCoordinator>,
+    user_message: &str,
+    rag: &RagConfig,
+    context_budgets: &HashMap<String, usize>,
+) -> String {
+    match memory.retrieve_relevant(user_message, rag).await {
+        Ok(memories) => format_retrieved_context(
+            &apply_context_budgets(memories, context_budgets),
+            rag.token_budget,
+        ),
         Err(e) => {
-            log::warn!("Memory search failed: {e:?}");
+            log::warn!("Memory retrieval failed: {e:?}");
             String::new()
         }
     }
 }
 
 /// Build system prompt with personality traits and custom instructions
-fn build_system_prompt(model_config: &CandleModelConfig, chat_config: &CandleChatConfig) -> String {
-    let mut system_prompt = model_config.system_prompt.clone().unwrap_or_default();
+///
+/// `rendered` is the turn's templated system prompt, used in place of the
+/// configured static one.
+fn build_system_prompt(
+    model_config: &CandleModelConfig,
+    chat_config: &CandleChatConfig,
+    rendered: Option<&str>,
+) -> String {
+    let mut system_prompt = rendered.map_or_else(
+        || model_config.system_prompt.clone().unwrap_or_default(),
+        str::to_string,
+    );
 
     if let Some(custom) = &chat_config.personality.custom_instructions {
         system_prompt.push_str("\n\n");
@@ -228,23 +261,25 @@ fn build_system_prompt(model_config: &CandleModelConfig, chat_config: &CandleCha
     system_prompt
 }
 
-/// Build prompt with personality and memory context
+/// Build prompt with personality, memory context and conversation history
 fn build_prompt_with_context(
-    model_config: &CandleModelConfig,
-    chat_config: &CandleChatConfig,
+    system_prompt: &str,
     memory_context: &str,
+    history: &str,
     user_message: &str,
 ) -> String {
-    let system_prompt = build_system_prompt(model_config, chat_config);
-
-    if memory_context.is_empty() {
-        format!("{system_prompt}\n\nUser: {user_message}")
-    } else {
-        format!("{system_prompt}\n\n{memory_context}\n\nUser: {user_message}")
+    let mut prompt = system_prompt.to_string();
+    for section in [memory_context, history] {
+        if !section.is_empty() {
+            prompt.push_str("\n\n");
+            prompt.push_str(section);
+        }
     }
+    let _ = write!(prompt, "\n\nUser: {user_message}");
+    prompt
 }
 
-/// Load all context sources in parallel
+/// Load file contexts and index directory and GitHub contexts in parallel
 fn load_all_contexts<S>(
     memory: &Arc<MemoryCoordinator>,
     metadata: &HashMap<String, String, S>,
@@ -306,7 +341,16 @@ where
             if let Some(aid) = agent_id {
                 meta.insert("agent_id".to_string(), aid);
             }
-            load_context_stream(ctx.load(), mem, meta, "context_directory").await;
+            match ctx.refresh_for(&mem, &meta).await {
+                Ok(report) => log::info!(
+                    "Indexed {}: {} files indexed, {} unchanged, {} removed",
+                    report.context_id,
+                    report.indexed_files,
+                    report.unchanged_files,
+                    report.removed_files
+                ),
+                Err(e) => log::warn!("Failed to index context_directory: {e}"),
+            }
         }));
     }
 
@@ -322,7 +366,16 @@ where
             if let Some(aid) = agent_id {
                 meta.insert("agent_id".to_string(), aid);
             }
-            load_context_stream(ctx.load(), mem, meta, "context_github").await;
+            match ctx.refresh_for(&mem, &meta).await {
+                Ok(report) => log::info!(
+                    "Indexed {}: {} files indexed, {} unchanged, {} removed",
+                    report.context_id,
+                    report.indexed_files,
+                    report.unchanged_files,
+                    report.removed_files
+                ),
+                Err(e) => log::warn!("Failed to index context_github: {e}"),
+            }
         }));
     }
 
@@ -338,11 +391,29 @@ async fn stream_and_process_chunks(
     tool_router: Option<&SweetMcpRouter>,
     on_chunk_handler: Option<&OnChunkHandler>,
     on_tool_result_handler: Option<&OnToolResultHandler>,
+    usage_tracker: &UsageTracker,
 ) -> String {
     tokio::pin!(completion_stream);
     let mut assistant_response = String::new();
+    let mut pending_tool_calls: Vec<(String, String, String)> = Vec::new();
 
     while let Some(completion_chunk) = completion_stream.next().await {
+        // Run the turn's tool calls as one batch before the turn completes
+        if let (Some(router), CandleCompletionChunk::Complete { .. }) =
+            (tool_router, &completion_chunk)
+        {
+            for chunk in execute_tool_calls(
+                std::mem::take(&mut pending_tool_calls),
+                router,
+                on_tool_result_handler,
+                usage_tracker,
+            )
+            .await
+            {
+                emit_chunk(chunk, sender, on_chunk_handler).await;
+            }
+        }
+
         let message_chunk = match completion_chunk {
             CandleCompletionChunk::Text(ref text) => {
                 assistant_response.push_str(text);
@@ -355,6 +426,7 @@ async fn stream_and_process_chunks(
                 token_count,
                 elapsed_secs,
                 tokens_per_sec,
+                provider,
             } => {
                 assistant_response.push_str(text);
 
@@ -368,14 +440,16 @@ async fn stream_and_process_chunks(
                     let duration_us = (elapsed_secs.unwrap_or(0.0) * 1_000_000.0) as u64;
                     AGENT_STATS.record_completion(u64::from(usage.total_tokens), duration_us);
                 }
+                usage_tracker.record_completion(usage, token_count, elapsed_secs);
 
                 CandleMessageChunk::Complete {
                     text: text.clone(),
                     finish_reason: finish_reason.map(|f| format!("{f:?}")),
-                    usage: usage.map(|u| format!("{u:?}")),
+                    usage,
                     token_count,
                     elapsed_secs,
                     tokens_per_sec,
+                    provider,
                 }
             }
             CandleCompletionChunk::ToolCallStart { id, name } => {
@@ -390,8 +464,12 @@ async fn stream_and_process_chunks(
                 name,
                 partial_input,
             },
-            CandleCompletionChunk::ToolCallComplete { id: _, name, input } => {
-                execute_tool_call(&name, &input, tool_router, on_tool_result_handler).await
+            CandleCompletionChunk::ToolCallComplete { id, name, input } => {
+                if tool_router.is_some() {
+                    pending_tool_calls.push((id, name, input));
+                    continue;
+                }
+                CandleMessageChunk::ToolCallComplete { id, name, input }
             }
             CandleCompletionChunk::Error(error) => CandleMessageChunk::Error(error),
         };
@@ -400,17 +478,39 @@ async fn stream_and_process_chunks(
             tokio::time::sleep(chat_config.behavior.response_delay).await;
         }
 
-        let final_chunk = if let Some(handler) = on_chunk_handler {
-            handler(message_chunk).await
-        } else {
-            message_chunk
-        };
-        let _ = sender.send(final_chunk);
+        emit_chunk(message_chunk, sender, on_chunk_handler).await;
+    }
+
+    if let Some(router) = tool_router {
+        for chunk in execute_tool_calls(
+            pending_tool_calls,
+            router,
+            on_tool_result_handler,
+            usage_tracker,
+        )
+        .await
+        {
+            emit_chunk(chunk, sender, on_chunk_handler).await;
+        }
     }
 
     assistant_response
 }
 
+/// Apply the chunk handler if configured and send the chunk
+async fn emit_chunk(
+    chunk: CandleMessageChunk,
+    sender: &tokio::sync::mpsc::UnboundedSender<CandleMessageChunk>,
+    on_chunk_handler: Option<&OnChunkHandler>,
+) {
+    let final_chunk = if let Some(handler) = on_chunk_handler {
+        handler(chunk).await
+    } else {
+        chunk
+    };
+    let _ = sender.send(final_chunk);
+}
+
 /// Store conversation turn in memory
 fn store_conversation_in_memory<S: std::hash::BuildHasher>(
     system_prompt: &str,
@@ -497,6 +597,77 @@ fn store_conversation_in_memory<S: std::hash::BuildHasher>(
     });
 }
 
+/// Store the reasoning path selected for a turn in memory
+fn store_reasoning_in_memory<S: std::hash::BuildHasher>(
+    user_message: &str,
+    path: &ReasoningPath,
+    memory: &Arc<MemoryCoordinator>,
+    metadata: &HashMap<String, String, S>,
+) {
+    let reasoning_meta = MemoryMetadata {
+        user_id: metadata.get("user_id").cloned(),
+        agent_id: metadata.get("agent_id").cloned(),
+        context: "chat".to_string(),
+        importance: 0.6,
+        keywords: vec![],
+        category: "reasoning".to_string(),
+        source: Some("mcp-reasoner".to_string()),
+        created_at: chrono::Utc::now(),
+        last_accessed_at: None,
+        embedding: None,
+        custom: serde_json::json!({ "score": path.score }),
+        tags: vec!["message_type.reasoning".to_string()],
+    };
+
+    let memory_clone = memory.clone();
+    let reasoning = format!("Reasoning for: {user_message}\n{}", path.render());
+    tokio::spawn(async move {
+        if let Err(e) = memory_clone
+            .add_memory(
+                reasoning,
+                DomainMemoryTypeEnum::Procedural,
+                Some(reasoning_meta),
+            )
+            .await
+        {
+            log::error!("Failed to store reasoning memory: {e:?}");
+        }
+    });
+}
+
+/// Store a summary of compacted conversation turns in memory
+fn store_summary_in_memory<S: std::hash::BuildHasher>(
+    summary: &str,
+    memory: &Arc<MemoryCoordinator>,
+    metadata: &HashMap<String, String, S>,
+) {
+    let summary_meta = MemoryMetadata {
+        user_id: metadata.get("user_id").cloned(),
+        agent_id: metadata.get("agent_id").cloned(),
+        context: "chat".to_string(),
+        importance: 0.7,
+        keywords: vec![],
+        category: "conversation_summary".to_string(),
+        source: Some("compaction".to_string()),
+        created_at: chrono::Utc::now(),
+        last_accessed_at: None,
+        embedding: None,
+        custom: serde_json::Value::Object(serde_json::Map::new()),
+        tags: vec![SUMMARY_TAG.to_string()],
+    };
+
+    let memory_clone = memory.clone();
+    let summary = format!("Summary of earlier conversation:\n{summary}");
+    tokio::spawn(async move {
+        if let Err(e) = memory_clone
+            .add_memory(summary, DomainMemoryTypeEnum::Semantic, Some(summary_meta))
+            .await
+        {
+            log::error!("Failed to store conversation summary: {e:?}");
+        }
+    });
+}
+
 /// Invoke conversation turn handler if configured
 #[allow(clippy::too_many_arguments)]
 async fn invoke_turn_handler_if_configured(
@@ -507,6 +678,9 @@ async fn invoke_turn_handler_if_configured(
     provider: &TextToTextModel,
     tools: &Arc<[ToolInfo]>,
     on_conversation_turn_handler: Option<&OnConversationTurnHandler>,
+    usage_tracker: &UsageTracker,
+    vision_model: Option<&VisionModel>,
+    tool_guardrails: Option<&ToolGuardrails>,
 ) {
     if let Some(handler) = on_conversation_turn_handler {
         let mut conversation = CandleAgentConversation::new();
@@ -531,6 +705,9 @@ async fn invoke_turn_handler_if_configured(
             on_chunk_handler: None,
             on_tool_result_handler: None,
             on_conversation_turn_handler: Some(handler.clone()),
+            usage: usage_tracker.clone(),
+            vision_model: vision_model.cloned(),
+            tool_guardrails: tool_guardrails.cloned(),
         });
 
         let agent = CandleAgentRoleAgent::new(builder_state);
@@ -543,37 +720,52 @@ async fn invoke_turn_handler_if_configured(
     }
 }
 
-/// Execute a tool call and return the result as a message chunk
-async fn execute_tool_call(
-    name: &str,
-    input: &str,
-    tool_router: Option<&SweetMcpRouter>,
+/// Execute a turn's tool calls and return one message chunk per call, in call order
+async fn execute_tool_calls(
+    calls: Vec<(String, String, String)>,
+    router: &SweetMcpRouter,
     on_tool_result_handler: Option<&OnToolResultHandler>,
-) -> CandleMessageChunk {
-    if let Some(router) = tool_router {
-        match serde_json::from_str::<serde_json::Value>(input) {
-            Ok(args_json) => {
-                let sweet_args = convert_serde_to_sweet_json(args_json);
-                match router.call_tool(name, sweet_args).await {
-                    Ok(response) => {
-                        if let Some(handler) = on_tool_result_handler {
-                            let results = vec![format!("{response:?}")];
-                            handler(&results).await;
-                        }
-                        CandleMessageChunk::Text(format!("Tool '{name}' executed: {response:?}"))
-                    }
-                    Err(e) => CandleMessageChunk::Error(format!("Tool '{name}' failed: {e}")),
-                }
-            }
-            Err(e) => CandleMessageChunk::Error(format!("Invalid JSON: {e}")),
-        }
-    } else {
-        CandleMessageChunk::ToolCallComplete {
-            id: String::new(),
-            name: name.to_string(),
-            input: input.to_string(),
+    usage_tracker: &UsageTracker,
+) -> Vec<CandleMessageChunk> {
+    if calls.is_empty() {
+        return Vec::new();
+    }
+
+    // Outcomes come back in call order, so inputs line up by position
+    let inputs: Vec<String> = calls.iter().map(|(_, _, input)| input.clone()).collect();
+    let outcomes = ToolScheduler::new()
+        .execute_model_calls(router, calls)
+        .await;
+    for (input, outcome) in inputs.iter().zip(&outcomes) {
+        usage_tracker.record_tool_call(ToolCallUsage::from_outcome(input, outcome));
+    }
+
+    if let Some(handler) = on_tool_result_handler {
+        let results: Vec<String> = outcomes
+            .iter()
+            .filter_map(|outcome| outcome.result.as_ref().ok())
+            .map(|response| format!("{response:?}"))
+            .collect();
+        if !results.is_empty() {
+            handler(&results).await;
         }
     }
+
+    outcomes
+        .into_iter()
+        .map(|outcome| {
+            let name = outcome.name;
+            match outcome.result {
+                Ok(response) => {
+                    CandleMessageChunk::Text(format!("Tool '{name}' executed: {response:?}"))
+                }
+                Err(e) => CandleMessageChunk::Error(format!(
+                    "Tool '{name}' failed: {}",
+                    e.to_tool_error(&name)
+                )),
+            }
+        })
+        .collect()
 }
 
 /// Handle user prompt/reprompt processing with full conversation flow
@@ -585,11 +777,22 @@ async fn handle_user_prompt<S: std::hash::BuildHasher>(
     model_config: &CandleModelConfig,
     provider: &TextToTextModel,
     memory: &Arc<MemoryCoordinator>,
+    rag: &RagConfig,
+    context_budgets: &HashMap<String, usize>,
     tools: &Arc<[ToolInfo]>,
     metadata: &HashMap<String, String, S>,
     on_chunk_handler: Option<&OnChunkHandler>,
     on_tool_result_handler: Option<&OnToolResultHandler>,
     on_conversation_turn_handler: Option<&OnConversationTurnHandler>,
+    usage_tracker: &UsageTracker,
+    vision_model: Option<&VisionModel>,
+    think_config: Option<&ThinkConfig>,
+    tool_guardrails: Option<&ToolGuardrails>,
+    sandbox_profile: Option<&SandboxProfile>,
+    system_prompt: Option<&CompiledSystemPrompt<'_>>,
+    history: &mut LiveHistory,
+    compaction: &CompactionConfig,
+    native_tools: &[NativeTool],
 ) {
     // Validate message length
     if user_message.len() > chat_config.max_message_length {
@@ -603,18 +806,12 @@ async fn handle_user_prompt<S: std::hash::BuildHasher>(
     }
 
     // Initialize tool router
-    let tool_router = initialize_tool_router(sender).await;
+    let tool_router =
+        initialize_tool_router(sender, tool_guardrails, sandbox_profile, native_tools).await;
     if tool_router.is_none() {
         return; // Error already sent
     }
 
-    // Search memory and build prompt
-    let memory_context = search_and_format_memory(memory, &user_message).await;
-    let full_prompt =
-        build_prompt_with_context(model_config, chat_config, &memory_context, &user_message);
-
-    // Call provider
-    let prompt = CandlePrompt::new(full_prompt);
     let mut params = CandleCompletionParams {
         temperature: f64::from(model_config.temperature),
         max_tokens: model_config
@@ -623,15 +820,83 @@ async fn handle_user_prompt<S: std::hash::BuildHasher>(
         ..Default::default()
     };
 
-    // Add tools
+    // Retrieve relevant memories
+    let mut memory_context =
+        retrieve_memory_context(memory, &user_message, rag, context_budgets).await;
+
+    // Reason through the question before answering
+    if let (Some(config), Some(router)) = (think_config, tool_router.as_ref()) {
+        match think(provider, router, &user_message, config, &params).await {
+            Ok(path) if !path.is_empty() => {
+                let rendered = path.render();
+                if config.show_thoughts {
+                    let thoughts = format!("<thinking>\n{rendered}\n</thinking>\n\n");
+                    emit_chunk(CandleMessageChunk::Text(thoughts), sender, on_chunk_handler).await;
+                }
+                if config.store_in_memory {
+                    store_reasoning_in_memory(&user_message, &path, memory, metadata);
+                }
+                if !memory_context.is_empty() {
+                    memory_context.push_str("\n\n");
+                }
+                let _ = write!(memory_context, "## Reasoning\n{rendered}");
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Reasoning step failed, answering directly: {e}"),
+        }
+    }
+
+    // Collect tools
+    let mut all_tools: Vec<ToolInfo> = tools.to_vec();
     if let Some(ref router) = tool_router {
-        let mut all_tools: Vec<ToolInfo> = tools.to_vec();
-        let auto_generated_tools = router.get_available_tools().await;
-        all_tools.extend(auto_generated_tools);
+        all_tools.extend(router.get_available_tools().await);
+    }
 
-        if !all_tools.is_empty() {
-            params.tools = Some(ZeroOneOrMany::from(all_tools));
+    // Render the system prompt template for this turn
+    let rendered = system_prompt.and_then(|template| {
+        let turn = PromptTurn {
+            tools: &all_tools,
+            memories: &memory_context,
+        };
+        match template.render(&turn) {
+            Ok(rendered) => Some(rendered),
+            Err(e) => {
+                log::warn!("{e}; using the static system prompt");
+                None
+            }
         }
+    });
+    if rendered.is_some() && system_prompt.is_some_and(CompiledSystemPrompt::renders_memories) {
+        memory_context.clear();
+    }
+    let system_prompt = build_system_prompt(model_config, chat_config, rendered.as_deref());
+
+    // Summarize the oldest turns when the prompt nears the context limit
+    let window = ContextWindow::new(
+        compaction.window_for(provider.max_input_tokens()),
+        ContextOverflowPolicy::Summarize,
+    );
+    let budget = window.prompt_budget(params.max_tokens.map_or(0, |t| t.get() as usize));
+    let overhead = estimate_tokens(&system_prompt)
+        + estimate_tokens(&memory_context)
+        + estimate_tokens(&user_message);
+    match compact_history(provider, history, overhead, budget, compaction).await {
+        Ok(Some(summary)) => store_summary_in_memory(&summary, memory, metadata),
+        Ok(None) => {}
+        Err(e) => log::warn!("History compaction failed, sending it in full: {e}"),
+    }
+
+    // Build prompt and call provider
+    let full_prompt = build_prompt_with_context(
+        &system_prompt,
+        &memory_context,
+        &history.render(),
+        &user_message,
+    );
+    let prompt = CandlePrompt::new(full_prompt);
+
+    if !all_tools.is_empty() {
+        params.tools = Some(ZeroOneOrMany::from(all_tools));
     }
 
     // Stream and process completion chunks
@@ -643,12 +908,12 @@ async fn handle_user_prompt<S: std::hash::BuildHasher>(
         tool_router.as_ref(),
         on_chunk_handler,
         on_tool_result_handler,
+        usage_tracker,
     )
     .await;
 
     // Store conversation in memory including system prompt
     if !assistant_response.is_empty() {
-        let system_prompt = build_system_prompt(model_config, chat_config);
         store_conversation_in_memory(
             &system_prompt,
             &user_message,
@@ -667,6 +932,87 @@ async fn handle_user_prompt<S: std::hash::BuildHasher>(
         provider,
         tools,
         on_conversation_turn_handler,
+        usage_tracker,
+        vision_model,
+        tool_guardrails,
+    )
+    .await;
+}
+
+/// Handle a user prompt with image attachments through the vision model
+#[allow(clippy::too_many_arguments)]
+async fn handle_image_prompt<S: std::hash::BuildHasher>(
+    user_message: String,
+    images: Vec<CandleImageAttachment>,
+    sender: &tokio::sync::mpsc::UnboundedSender<CandleMessageChunk>,
+    chat_config: &CandleChatConfig,
+    model_config: &CandleModelConfig,
+    provider: &TextToTextModel,
+    memory: &Arc<MemoryCoordinator>,
+    tools: &Arc<[ToolInfo]>,
+    metadata: &HashMap<String, String, S>,
+    on_chunk_handler: Option<&OnChunkHandler>,
+    on_conversation_turn_handler: Option<&OnConversationTurnHandler>,
+    usage_tracker: &UsageTracker,
+    vision_model: Option<&VisionModel>,
+    tool_guardrails: Option<&ToolGuardrails>,
+    system_prompt: Option<&CompiledSystemPrompt<'_>>,
+) {
+    let Some(model) = vision_model.cloned().or_else(default_vision_model) else {
+        let _ = sender.send(CandleMessageChunk::Error(
+            "No vision model available for image attachments".to_string(),
+        ));
+        return;
+    };
+
+    let user_turn = describe_user_turn(&user_message, &images);
+    let mut reply = describe_attachments(model, user_message, images);
+    let mut assistant_response = String::new();
+    while let Some(chunk) = reply.next().await {
+        match &chunk {
+            CandleMessageChunk::Text(text) => assistant_response.push_str(text),
+            CandleMessageChunk::Complete {
+                usage,
+                token_count,
+                elapsed_secs,
+                ..
+            } => usage_tracker.record_completion(*usage, *token_count, *elapsed_secs),
+            _ => {}
+        }
+        emit_chunk(chunk, sender, on_chunk_handler).await;
+    }
+
+    if !assistant_response.is_empty() {
+        let rendered = system_prompt.and_then(|template| {
+            template
+                .render(&PromptTurn {
+                    tools,
+                    memories: "",
+                })
+                .map_err(|e| log::warn!("{e}; using the static system prompt"))
+                .ok()
+        });
+        let system_prompt = build_system_prompt(model_config, chat_config, rendered.as_deref());
+        store_conversation_in_memory(
+            &system_prompt,
+            &user_turn,
+            &assistant_response,
+            memory,
+            metadata,
+        );
+    }
+
+    invoke_turn_handler_if_configured(
+        &user_turn,
+        &assistant_response,
+        sender,
+        model_config,
+        provider,
+        tools,
+        on_conversation_turn_handler,
+        usage_tracker,
+        vision_model,
+        tool_guardrails,
     )
     .await;
 }
@@ -693,6 +1039,15 @@ where
                 memory,
                 tools,
                 metadata,
+                rag,
+                usage,
+                vision_model,
+                think: think_config,
+                tool_guardrails,
+                sandbox_profile,
+                system_prompt_template,
+                compaction,
+                native_tools,
             } = config;
             let ChatSessionContexts {
                 context_file,
@@ -706,6 +1061,22 @@ where
                 on_conversation_turn_handler,
             } = handlers;
 
+            // Compile the system prompt template once for the conversation
+            let system_prompt = match system_prompt_template
+                .as_ref()
+                .map(SystemPromptTemplate::compile)
+            {
+                Some(Err(e)) => {
+                    let _ = sender.send(CandleMessageChunk::Error(e.to_string()));
+                    return;
+                }
+                Some(Ok(compiled)) => Some(compiled),
+                None => None,
+            };
+
+            let context_budgets =
+                context_token_budgets(context_directory.as_ref(), context_github.as_ref());
+
             // Load context documents from all sources in parallel using tokio::spawn
             let load_tasks = load_all_contexts(
                 &memory,
@@ -733,9 +1104,10 @@ where
                 ZeroOneOrMany::Many(items) => items,
             };
 
-            for (role, message) in history_vec {
-                initial_conversation.add_message(message, role);
+            for (role, message) in &history_vec {
+                initial_conversation.add_message(message.clone(), *role);
             }
+            let mut live_history = LiveHistory::new(history_vec);
 
             // Execute async handler to get CandleChatLoop result
             let chat_loop_result = handler(&initial_conversation).await;
@@ -754,11 +1126,42 @@ where
                         &model_config,
                         &provider,
                         &memory,
+                        &rag,
+                        &context_budgets,
                         &tools,
                         &metadata,
                         on_chunk_handler.as_ref(),
                         on_tool_result_handler.as_ref(),
                         on_conversation_turn_handler.as_ref(),
+                        &usage,
+                        vision_model.as_ref(),
+                        think_config.as_ref(),
+                        tool_guardrails.as_ref(),
+                        sandbox_profile.as_ref(),
+                        system_prompt.as_ref(),
+                        &mut live_history,
+                        &compaction,
+                        &native_tools,
+                    )
+                    .await;
+                }
+                CandleChatLoop::UserPromptWithImages(user_message, images) => {
+                    handle_image_prompt(
+                        user_message,
+                        images,
+                        &sender,
+                        &chat_config,
+                        &model_config,
+                        &provider,
+                        &memory,
+                        &tools,
+                        &metadata,
+                        on_chunk_handler.as_ref(),
+                        on_conversation_turn_handler.as_ref(),
+                        &usage,
+                        vision_model.as_ref(),
+                        tool_guardrails.as_ref(),
+                        system_prompt.as_ref(),
                     )
                     .await;
                 }
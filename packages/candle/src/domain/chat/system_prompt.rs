@@ -0,0 +1,191 @@
+//! System prompt templates
+//!
+//! A `SystemPromptTemplate` is a Jinja-style template (rendered with
+//! `minijinja`) that replaces the agent's static system prompt. It is
+//! compiled once per conversation and rendered again for every turn, so it
+//! can refer to values that change between turns.
+//!
+//! Every render sees:
+//! - `date` (`2025-01-31`), `time` (`14:05 UTC`) and `now` (RFC 3339)
+//! - `tools`: the tools offered this turn, each with `name` and `description`
+//! - `memories`: the memories retrieved for this turn, already formatted
+//! - `user` and any other variables set on the template
+//!
+//! Partials registered with `with_partial` are pulled in with
+//! `{% include "name" %}`. When the template (or one of its partials) uses
+//! `memories`, the retrieved memories are placed where the template puts
+//! them instead of being appended after the system prompt.
+
+use std::collections::BTreeMap;
+
+use minijinja::Environment;
+use serde::Serialize;
+use serde_json::Value;
+use sweet_mcp_type::ToolInfo;
+
+/// Name the main template is registered under
+const MAIN_TEMPLATE: &str = "system_prompt";
+
+/// Variable holding the memories retrieved for the turn
+pub const MEMORIES_VARIABLE: &str = "memories";
+
+/// Errors from compiling or rendering a system prompt template
+#[derive(Debug, thiserror::Error)]
+pub enum SystemPromptError {
+    #[error("Failed to compile system prompt template '{name}': {source}")]
+    Compile {
+        name: String,
+        source: minijinja::Error,
+    },
+    #[error("Failed to render system prompt template: {0}")]
+    Render(minijinja::Error),
+}
+
+/// Templated system prompt with partials and static variables
+#[derive(Debug, Clone, Default)]
+pub struct SystemPromptTemplate {
+    source: String,
+    partials: BTreeMap<String, String>,
+    variables: BTreeMap<String, Value>,
+}
+
+impl SystemPromptTemplate {
+    /// Create a template from its source
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Register a partial, included with `{% include "name" %}`
+    #[must_use]
+    pub fn with_partial(mut self, name: impl Into<String>, source: impl Into<String>) -> Self {
+        self.partials.insert(name.into(), source.into());
+        self
+    }
+
+    /// Set a variable available to every render
+    ///
+    /// Values that fail to serialize are set to `none`.
+    #[must_use]
+    pub fn with_variable(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+        self.variables.insert(
+            name.into(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+
+    /// Set the `user` variable, e.g. a profile with name, locale and preferences
+    #[must_use]
+    pub fn with_user_profile(self, profile: impl Serialize) -> Self {
+        self.with_variable("user", profile)
+    }
+
+    /// Template source
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Parse the template and its partials
+    ///
+    /// # Errors
+    /// Returns `SystemPromptError::Compile` naming the template or partial
+    /// with a syntax error
+    pub fn compile(&self) -> Result<CompiledSystemPrompt<'_>, SystemPromptError> {
+        let mut env = Environment::new();
+        for (name, source) in self
+            .partials
+            .iter()
+            .map(|(name, source)| (name.as_str(), source.as_str()))
+            .chain(std::iter::once((MAIN_TEMPLATE, self.source.as_str())))
+        {
+            env.add_template(name, source)
+                .map_err(|source| SystemPromptError::Compile {
+                    name: name.to_string(),
+                    source,
+                })?;
+        }
+
+        let uses_memories = env.templates().any(|(_, template)| {
+            template
+                .undeclared_variables(false)
+                .contains(MEMORIES_VARIABLE)
+        });
+
+        Ok(CompiledSystemPrompt {
+            env,
+            variables: &self.variables,
+            uses_memories,
+        })
+    }
+}
+
+/// Values that change from one turn to the next
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PromptTurn<'a> {
+    /// Tools offered to the model this turn
+    pub tools: &'a [ToolInfo],
+    /// Retrieved memories, formatted for the prompt
+    pub memories: &'a str,
+}
+
+/// Tool entry exposed to templates
+#[derive(Serialize)]
+struct TemplateTool<'a> {
+    name: &'a str,
+    description: &'a str,
+}
+
+/// A template compiled for the length of a conversation
+#[derive(Debug)]
+pub struct CompiledSystemPrompt<'a> {
+    env: Environment<'a>,
+    variables: &'a BTreeMap<String, Value>,
+    uses_memories: bool,
+}
+
+impl CompiledSystemPrompt<'_> {
+    /// Whether the template places the retrieved memories itself
+    #[must_use]
+    pub fn renders_memories(&self) -> bool {
+        self.uses_memories
+    }
+
+    /// Render the system prompt for one turn
+    ///
+    /// # Errors
+    /// Returns `SystemPromptError::Render` if rendering fails, e.g. a partial
+    /// is missing or a filter is applied to the wrong type
+    pub fn render(&self, turn: &PromptTurn<'_>) -> Result<String, SystemPromptError> {
+        let now = chrono::Utc::now();
+        let tools: Vec<TemplateTool<'_>> = turn
+            .tools
+            .iter()
+            .map(|tool| TemplateTool {
+                name: &tool.name,
+                description: tool.description.as_deref().unwrap_or_default(),
+            })
+            .collect();
+
+        let mut context: BTreeMap<&str, minijinja::Value> = self
+            .variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), minijinja::Value::from_serialize(value)))
+            .collect();
+        context.insert("date", now.format("%Y-%m-%d").to_string().into());
+        context.insert("time", now.format("%H:%M UTC").to_string().into());
+        context.insert("now", now.to_rfc3339().into());
+        context.insert("tools", minijinja::Value::from_serialize(&tools));
+        context.insert(MEMORIES_VARIABLE, turn.memories.into());
+
+        self.env
+            .get_template(MAIN_TEMPLATE)
+            .and_then(|template| template.render(context))
+            .map(|rendered| rendered.trim().to_string())
+            .map_err(SystemPromptError::Render)
+    }
+}
@@ -0,0 +1,486 @@
+//! Persistent conversation transcripts
+//!
+//! Chat sessions are stored in the agent's `SurrealKV` store so a conversation
+//! can be listed and resumed in a later process. Each session keeps:
+//! - a summary row (`chat_session`) with title, model and token totals
+//! - ordered transcript rows (`chat_message`) for messages and tool calls
+
+use serde::{Deserialize, Serialize};
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+
+use crate::core::context_window::{ContextOverflowError, ContextOverflowPolicy, estimate_tokens};
+
+/// Transcript storage errors
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptError {
+    #[error("Transcript database error: {0}")]
+    Database(String),
+    #[error("Session not found: {0}")]
+    SessionNotFound(String),
+    #[error("Session id '{0}' matches more than one session")]
+    AmbiguousSession(String),
+}
+
+/// Who produced a transcript entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptRole {
+    System,
+    User,
+    Assistant,
+    Tool,
+}
+
+/// A tool call recorded in a transcript
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptToolCall {
+    pub id: String,
+    pub name: String,
+    pub input: String,
+}
+
+/// One message or tool call in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub role: TranscriptRole,
+    pub content: String,
+    #[serde(default)]
+    pub tool_call: Option<TranscriptToolCall>,
+    /// Tokens generated for this entry, when the provider reported them
+    #[serde(default)]
+    pub tokens: Option<u32>,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: i64,
+}
+
+impl TranscriptEntry {
+    /// Plain message entry stamped with the current time
+    pub fn message(role: TranscriptRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+            tool_call: None,
+            tokens: None,
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Tool call entry stamped with the current time
+    pub fn tool_call(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        input: impl Into<String>,
+    ) -> Self {
+        Self {
+            tool_call: Some(TranscriptToolCall {
+                id: id.into(),
+                name: name.into(),
+                input: input.into(),
+            }),
+            ..Self::message(TranscriptRole::Tool, String::new())
+        }
+    }
+
+    /// Attach a token count
+    #[must_use]
+    pub fn with_tokens(mut self, tokens: u32) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+}
+
+/// Summary of a stored session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    /// First user message, truncated
+    pub title: String,
+    pub model: Option<String>,
+    pub created_at_ms: i64,
+    pub updated_at_ms: i64,
+    pub message_count: u64,
+    pub total_tokens: u64,
+}
+
+/// Longest title kept for a session, in characters
+const TITLE_MAX_CHARS: usize = 60;
+
+/// Longest line kept per summarized entry, in characters
+const SUMMARY_LINE_MAX_CHARS: usize = 120;
+
+/// Resumed history fitted to a token budget
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FittedHistory {
+    /// Digest of the entries that did not fit (`Summarize` policy only)
+    pub summary: Option<String>,
+    /// Newest entries kept verbatim, oldest first
+    pub recent: Vec<TranscriptEntry>,
+    /// Number of older entries left out of `recent`
+    pub dropped: usize,
+}
+
+impl FittedHistory {
+    /// Render as prompt history, summary first
+    pub fn render(&self) -> String {
+        let recent = render_history(&self.recent);
+        match &self.summary {
+            Some(summary) if recent.is_empty() => format!("Summary of earlier turns:\n{}", summary),
+            Some(summary) => format!("Summary of earlier turns:\n{}\n\n{}", summary, recent),
+            None => recent,
+        }
+    }
+}
+
+/// Conversation transcript store backed by `SurrealDB`
+#[derive(Debug, Clone)]
+pub struct TranscriptStore {
+    db: Surreal<Any>,
+}
+
+impl TranscriptStore {
+    /// Create a store over an existing connection (call `initialize` before use)
+    pub fn new(db: Surreal<Any>) -> Self {
+        Self { db }
+    }
+
+    /// Open the store on the shared agent database and define its tables
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::Database` if the database cannot be opened or
+    /// the schema cannot be defined
+    pub async fn open() -> Result<Self, TranscriptError> {
+        let db = crate::domain::init::agent_database()
+            .await
+            .map_err(|e| TranscriptError::Database(e.to_string()))?;
+        let store = Self::new(db);
+        store.initialize().await?;
+        Ok(store)
+    }
+
+    /// Define the transcript tables and indexes
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::Database` if the schema cannot be defined
+    pub async fn initialize(&self) -> Result<(), TranscriptError> {
+        self.db
+            .query(
+                "
+                DEFINE TABLE IF NOT EXISTS chat_session SCHEMALESS;
+                DEFINE INDEX IF NOT EXISTS chat_session_id ON chat_session FIELDS session_id UNIQUE;
+                DEFINE TABLE IF NOT EXISTS chat_message SCHEMALESS;
+                DEFINE INDEX IF NOT EXISTS chat_message_order ON chat_message FIELDS session_id, seq;
+                ",
+            )
+            .await
+            .map_err(|e| {
+                TranscriptError::Database(format!("Failed to define transcript tables: {:?}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Start a new session and return its id
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::Database` if the session cannot be stored
+    pub async fn create_session(&self, model: Option<&str>) -> Result<String, TranscriptError> {
+        let session_id = uuid::Uuid::new_v4().simple().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        self.db
+            .query(
+                "CREATE chat_session CONTENT {
+                    session_id: $session_id,
+                    title: '',
+                    model: $model,
+                    created_at_ms: $now,
+                    updated_at_ms: $now,
+                    message_count: 0,
+                    total_tokens: 0
+                }",
+            )
+            .bind(("session_id", session_id.clone()))
+            .bind(("model", model.map(str::to_string)))
+            .bind(("now", now))
+            .await
+            .map_err(|e| TranscriptError::Database(format!("Failed to create session: {:?}", e)))?;
+
+        Ok(session_id)
+    }
+
+    /// Append an entry to a session and update its totals
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::SessionNotFound` for an unknown session, or
+    /// `TranscriptError::Database` if the entry cannot be stored
+    pub async fn append(
+        &self,
+        session_id: &str,
+        entry: &TranscriptEntry,
+    ) -> Result<(), TranscriptError> {
+        let session = self.session(session_id).await?;
+        let title = if session.title.is_empty() && entry.role == TranscriptRole::User {
+            session_title(&entry.content)
+        } else {
+            session.title
+        };
+        let tool_call = entry
+            .tool_call
+            .as_ref()
+            .map(serde_json::to_value)
+            .transpose()
+            .map_err(|e| TranscriptError::Database(e.to_string()))?;
+
+        self.db
+            .query(
+                "CREATE chat_message CONTENT {
+                    session_id: $session_id,
+                    seq: $seq,
+                    role: $role,
+                    content: $content,
+                    tool_call: $tool_call,
+                    tokens: $tokens,
+                    timestamp_ms: $timestamp_ms
+                };
+                UPDATE chat_session SET
+                    title = $title,
+                    updated_at_ms = $timestamp_ms,
+                    message_count = $seq + 1,
+                    total_tokens += $token_delta
+                WHERE session_id = $session_id;",
+            )
+            .bind(("session_id", session_id.to_string()))
+            .bind(("seq", session.message_count))
+            .bind(("role", role_name(entry.role)))
+            .bind(("content", entry.content.clone()))
+            .bind(("tool_call", tool_call))
+            .bind(("tokens", entry.tokens))
+            .bind(("token_delta", u64::from(entry.tokens.unwrap_or(0))))
+            .bind(("timestamp_ms", entry.timestamp_ms))
+            .bind(("title", title))
+            .await
+            .map_err(|e| TranscriptError::Database(format!("Failed to append entry: {:?}", e)))?;
+
+        Ok(())
+    }
+
+    /// Most recently updated sessions first
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::Database` if the query fails
+    pub async fn list_sessions(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<SessionSummary>, TranscriptError> {
+        let mut response = self
+            .db
+            .query("SELECT * OMIT id FROM chat_session ORDER BY updated_at_ms DESC LIMIT $limit")
+            .bind(("limit", limit as i64))
+            .await
+            .map_err(|e| TranscriptError::Database(format!("Failed to list sessions: {:?}", e)))?;
+
+        response
+            .take(0)
+            .map_err(|e| TranscriptError::Database(format!("Failed to parse sessions: {:?}", e)))
+    }
+
+    /// Find a session by its id or a unique id prefix
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::SessionNotFound` if nothing matches,
+    /// `TranscriptError::AmbiguousSession` if a prefix matches several sessions,
+    /// or `TranscriptError::Database` if the query fails
+    pub async fn resolve_session(
+        &self,
+        id_or_prefix: &str,
+    ) -> Result<SessionSummary, TranscriptError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT * OMIT id FROM chat_session
+                 WHERE string::starts_with(session_id, $prefix) LIMIT 2",
+            )
+            .bind(("prefix", id_or_prefix.to_string()))
+            .await
+            .map_err(|e| TranscriptError::Database(format!("Failed to find session: {:?}", e)))?;
+
+        let mut matches: Vec<SessionSummary> = response
+            .take(0)
+            .map_err(|e| TranscriptError::Database(format!("Failed to parse session: {:?}", e)))?;
+
+        if let Some(exact) = matches.iter().position(|s| s.session_id == id_or_prefix) {
+            return Ok(matches.swap_remove(exact));
+        }
+        match matches.len() {
+            0 => Err(TranscriptError::SessionNotFound(id_or_prefix.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => Err(TranscriptError::AmbiguousSession(id_or_prefix.to_string())),
+        }
+    }
+
+    /// Load a session's entries in order
+    ///
+    /// # Errors
+    /// Returns `TranscriptError::Database` if the query fails
+    pub async fn load(&self, session_id: &str) -> Result<Vec<TranscriptEntry>, TranscriptError> {
+        let mut response = self
+            .db
+            .query(
+                "SELECT role, content, tool_call, tokens, timestamp_ms, seq FROM chat_message
+                 WHERE session_id = $session_id ORDER BY seq ASC",
+            )
+            .bind(("session_id", session_id.to_string()))
+            .await
+            .map_err(|e| {
+                TranscriptError::Database(format!("Failed to load transcript: {:?}", e))
+            })?;
+
+        response
+            .take(0)
+            .map_err(|e| TranscriptError::Database(format!("Failed to parse transcript: {:?}", e)))
+    }
+
+    /// Fetch one session's summary by exact id
+    async fn session(&self, session_id: &str) -> Result<SessionSummary, TranscriptError> {
+        let mut response = self
+            .db
+            .query("SELECT * OMIT id FROM chat_session WHERE session_id = $session_id LIMIT 1")
+            .bind(("session_id", session_id.to_string()))
+            .await
+            .map_err(|e| TranscriptError::Database(format!("Failed to read session: {:?}", e)))?;
+
+        let sessions: Vec<SessionSummary> = response
+            .take(0)
+            .map_err(|e| TranscriptError::Database(format!("Failed to parse session: {:?}", e)))?;
+
+        sessions
+            .into_iter()
+            .next()
+            .ok_or_else(|| TranscriptError::SessionNotFound(session_id.to_string()))
+    }
+}
+
+/// Render stored entries as prompt history for a resumed conversation
+///
+/// Uses the same `User:` / `Assistant:` layout as the chat prompt; system
+/// entries and tool calls are left out.
+pub fn render_history(entries: &[TranscriptEntry]) -> String {
+    entries
+        .iter()
+        .filter_map(|entry| match entry.role {
+            TranscriptRole::User => Some(format!("User: {}", entry.content)),
+            TranscriptRole::Assistant => Some(format!("Assistant: {}", entry.content)),
+            TranscriptRole::System | TranscriptRole::Tool => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Fit stored entries into `budget_tokens` of prompt history
+///
+/// The newest messages are kept verbatim. What happens to the older ones
+/// depends on `policy`: `SlidingWindow` drops them, `Summarize` keeps a short
+/// line per message (the full turns stay in the memory system) and `Abort`
+/// fails.
+///
+/// # Errors
+/// Returns `ContextOverflowError` when the history does not fit and the policy
+/// is `Abort`
+pub fn fit_history(
+    entries: &[TranscriptEntry],
+    budget_tokens: usize,
+    policy: ContextOverflowPolicy,
+) -> Result<FittedHistory, ContextOverflowError> {
+    let messages: Vec<&TranscriptEntry> = entries
+        .iter()
+        .filter(|entry| matches!(entry.role, TranscriptRole::User | TranscriptRole::Assistant))
+        .collect();
+    let cost = |entry: &TranscriptEntry| estimate_tokens(&entry.content) + 2;
+    let required: usize = messages.iter().copied().map(cost).sum();
+
+    if required <= budget_tokens {
+        return Ok(FittedHistory {
+            summary: None,
+            recent: messages.into_iter().cloned().collect(),
+            dropped: 0,
+        });
+    }
+    if policy == ContextOverflowPolicy::Abort {
+        return Err(ContextOverflowError {
+            required,
+            available: budget_tokens,
+        });
+    }
+
+    // A summary gets a quarter of the budget; verbatim turns get the rest
+    let verbatim_budget = if policy == ContextOverflowPolicy::Summarize {
+        budget_tokens - budget_tokens / 4
+    } else {
+        budget_tokens
+    };
+    let mut used = 0;
+    let mut split = messages.len();
+    while split > 0 && used + cost(messages[split - 1]) <= verbatim_budget {
+        split -= 1;
+        used += cost(messages[split]);
+    }
+
+    let summary = (policy == ContextOverflowPolicy::Summarize)
+        .then(|| summarize_entries(&messages[..split], budget_tokens - used))
+        .filter(|summary| !summary.is_empty());
+
+    Ok(FittedHistory {
+        summary,
+        recent: messages[split..].iter().copied().cloned().collect(),
+        dropped: split,
+    })
+}
+
+/// One line per entry, newest kept first when the budget runs out
+fn summarize_entries(entries: &[&TranscriptEntry], budget_tokens: usize) -> String {
+    let mut used = 0;
+    let mut lines = Vec::new();
+    for entry in entries.iter().rev() {
+        let speaker = if entry.role == TranscriptRole::User {
+            "User"
+        } else {
+            "Assistant"
+        };
+        let first_line = entry.content.lines().next().unwrap_or_default().trim();
+        let mut line = format!("- {}: ", speaker);
+        if first_line.chars().count() > SUMMARY_LINE_MAX_CHARS {
+            line.extend(first_line.chars().take(SUMMARY_LINE_MAX_CHARS - 1));
+            line.push('…');
+        } else {
+            line.push_str(first_line);
+        }
+
+        used += estimate_tokens(&line) + 1;
+        if used > budget_tokens {
+            break;
+        }
+        lines.push(line);
+    }
+    lines.reverse();
+    lines.join("\n")
+}
+
+/// Title for a session from its first user message
+pub fn session_title(message: &str) -> String {
+    let line = message.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= TITLE_MAX_CHARS {
+        return line.to_string();
+    }
+    let mut title: String = line.chars().take(TITLE_MAX_CHARS - 1).collect();
+    title.push('…');
+    title
+}
+
+fn role_name(role: TranscriptRole) -> &'static str {
+    match role {
+        TranscriptRole::System => "system",
+        TranscriptRole::User => "user",
+        TranscriptRole::Assistant => "assistant",
+        TranscriptRole::Tool => "tool",
+    }
+}
@@ -0,0 +1,230 @@
+//! Token usage and cost accounting per conversation
+//!
+//! Every completed model turn reports its prompt and completion token counts
+//! and timing on the `Complete` chunk. A [`UsageTracker`] shared by the agent
+//! and its chat session folds those into a [`ConversationUsage`], together
+//! with one [`ToolCallUsage`] per executed tool call.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::estimate_tokens;
+use crate::domain::model::CandleUsage;
+use crate::domain::tool::ToolCallOutcome;
+
+/// Prices used to turn token counts into a cost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenPricing {
+    /// Cost per million prompt tokens
+    pub input_per_million: f64,
+    /// Cost per million completion tokens
+    pub output_per_million: f64,
+}
+
+impl TokenPricing {
+    /// Create pricing from per-million-token rates
+    #[must_use]
+    pub const fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    /// Cost of `usage` at these rates
+    #[must_use]
+    pub fn cost(&self, usage: &CandleUsage) -> f64 {
+        (f64::from(usage.input_tokens) * self.input_per_million
+            + f64::from(usage.output_tokens) * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Context consumed by one tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallUsage {
+    /// Id of the call
+    pub id: String,
+    /// Tool that was invoked
+    pub name: String,
+    /// Estimated tokens of the arguments the model emitted
+    pub argument_tokens: u32,
+    /// Estimated tokens of the tool response fed back to the model
+    pub result_tokens: u32,
+    /// Time spent running the tool in seconds
+    pub elapsed_secs: f64,
+    /// Whether the call succeeded
+    pub succeeded: bool,
+}
+
+impl ToolCallUsage {
+    /// Measure a finished call given the raw input the model emitted
+    #[must_use]
+    pub fn from_outcome(input: &str, outcome: &ToolCallOutcome) -> Self {
+        let result_tokens = outcome
+            .result
+            .as_ref()
+            .map_or(0, |response| estimate_tokens(&response.to_string()));
+        Self {
+            id: outcome.id.clone(),
+            name: outcome.name.clone(),
+            argument_tokens: saturating_u32(estimate_tokens(input)),
+            result_tokens: saturating_u32(result_tokens),
+            elapsed_secs: outcome.elapsed.as_secs_f64(),
+            succeeded: outcome.result.is_ok(),
+        }
+    }
+
+    /// Argument and result tokens together
+    #[must_use]
+    pub fn total_tokens(&self) -> u32 {
+        self.argument_tokens.saturating_add(self.result_tokens)
+    }
+}
+
+/// Aggregated usage of one conversation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConversationUsage {
+    /// Completed model turns
+    pub turns: u32,
+    /// Prompt and completion tokens summed over all turns
+    pub tokens: CandleUsage,
+    /// Time spent generating in seconds
+    pub generation_secs: f64,
+    /// Every executed tool call, in execution order
+    pub tool_calls: Vec<ToolCallUsage>,
+}
+
+impl ConversationUsage {
+    /// Add one completed model turn
+    pub fn record_turn(&mut self, usage: CandleUsage, elapsed_secs: Option<f64>) {
+        self.turns += 1;
+        self.tokens += usage;
+        self.generation_secs += elapsed_secs.unwrap_or(0.0);
+    }
+
+    /// Add one executed tool call
+    pub fn record_tool_call(&mut self, call: ToolCallUsage) {
+        self.tool_calls.push(call);
+    }
+
+    /// Completion tokens per second of generation time
+    #[must_use]
+    pub fn tokens_per_sec(&self) -> f64 {
+        if self.generation_secs > 0.0 {
+            f64::from(self.tokens.output_tokens) / self.generation_secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Time spent running tools in seconds
+    #[must_use]
+    pub fn tool_secs(&self) -> f64 {
+        self.tool_calls.iter().map(|call| call.elapsed_secs).sum()
+    }
+
+    /// Cost of the conversation's model tokens
+    #[must_use]
+    pub fn cost(&self, pricing: &TokenPricing) -> f64 {
+        pricing.cost(&self.tokens)
+    }
+
+    /// Whether nothing has been recorded
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.turns == 0 && self.tool_calls.is_empty()
+    }
+}
+
+impl fmt::Display for ConversationUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} turns • {} prompt + {} completion = {} tokens • {:.1}s generating ({:.1} tok/s)",
+            self.turns,
+            self.tokens.input_tokens,
+            self.tokens.output_tokens,
+            self.tokens.total_tokens,
+            self.generation_secs,
+            self.tokens_per_sec()
+        )?;
+
+        // calls, failures, tokens, seconds per tool name
+        let mut by_tool: BTreeMap<&str, (u32, u32, u32, f64)> = BTreeMap::new();
+        for call in &self.tool_calls {
+            let totals = by_tool.entry(call.name.as_str()).or_default();
+            totals.0 += 1;
+            totals.1 += u32::from(!call.succeeded);
+            totals.2 = totals.2.saturating_add(call.total_tokens());
+            totals.3 += call.elapsed_secs;
+        }
+        for (name, (calls, failures, tokens, secs)) in by_tool {
+            write!(
+                f,
+                "\n  🔧 {name}: {calls} calls ({failures} failed) • ~{tokens} tokens • {secs:.2}s"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared handle accumulating a conversation's usage
+///
+/// Clones share the same counters, so the agent and the session streaming
+/// its turns see one total.
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+    inner: Arc<Mutex<ConversationUsage>>,
+}
+
+impl UsageTracker {
+    /// Create an empty tracker
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one completed model turn
+    pub fn record_turn(&self, usage: CandleUsage, elapsed_secs: Option<f64>) {
+        self.inner.lock().record_turn(usage, elapsed_secs);
+    }
+
+    /// Add the turn reported by a `Complete` chunk
+    ///
+    /// Providers that only report `token_count` are counted as completion
+    /// tokens with no prompt tokens.
+    pub fn record_completion(
+        &self,
+        usage: Option<CandleUsage>,
+        token_count: Option<u32>,
+        elapsed_secs: Option<f64>,
+    ) {
+        let usage = usage.unwrap_or_else(|| CandleUsage::new(0, token_count.unwrap_or(0)));
+        self.record_turn(usage, elapsed_secs);
+    }
+
+    /// Add one executed tool call
+    pub fn record_tool_call(&self, call: ToolCallUsage) {
+        self.inner.lock().record_tool_call(call);
+    }
+
+    /// Usage recorded so far
+    #[must_use]
+    pub fn snapshot(&self) -> ConversationUsage {
+        self.inner.lock().clone()
+    }
+
+    /// Return the usage recorded so far and start again from zero
+    pub fn take(&self) -> ConversationUsage {
+        std::mem::take(&mut *self.inner.lock())
+    }
+}
+
+fn saturating_u32(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,115 @@
+//! Turns with image attachments
+//!
+//! A user turn that carries images is answered by a vision model instead of
+//! the agent's text model. Each image is described against the user's
+//! message and the streamed answer is surfaced as ordinary message chunks,
+//! so chunk handlers, usage tracking and the CLI treat it like any other
+//! reply.
+
+use std::path::Path;
+use std::pin::Pin;
+
+use cyrup_sugars::prelude::MessageChunk;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::capability::registry::{self, VisionModel};
+use crate::capability::traits::VisionCapable;
+use crate::domain::chat::message::{CandleImageAttachment, CandleMessageChunk};
+use crate::domain::model::CandleUsage;
+use crate::domain::model::traits::CandleModel;
+
+/// Vision model used when the agent has none configured
+///
+/// The first registered vision model by registry key.
+#[must_use]
+pub fn default_vision_model() -> Option<VisionModel> {
+    registry::vision_registry_keys()
+        .first()
+        .and_then(|key| registry::get::<VisionModel>(key))
+}
+
+/// Render a user message with its attachments for transcripts and memory
+#[must_use]
+pub fn describe_user_turn(prompt: &str, images: &[CandleImageAttachment]) -> String {
+    let mut turn = prompt.to_string();
+    for image in images {
+        turn.push_str(&format!("\n[image: {}]", image.source));
+    }
+    turn
+}
+
+/// Answer a prompt about attached images, streaming the reply
+///
+/// Several images are answered one after another, each under an
+/// `[image N: source]` heading. A missing file or a failing image yields an
+/// `Error` chunk and the remaining images are still described. The stream
+/// ends with one `Complete` chunk carrying the summed token usage.
+pub fn describe_attachments(
+    model: VisionModel,
+    prompt: String,
+    images: Vec<CandleImageAttachment>,
+) -> Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>> {
+    let provider = model.info().registry_key.to_string();
+    Box::pin(crate::async_stream::spawn_stream(
+        move |sender| async move {
+            let labelled = images.len() > 1;
+            let mut usage = CandleUsage::default();
+            let mut elapsed_secs = 0.0;
+
+            for (index, image) in images.iter().enumerate() {
+                if labelled {
+                    let separator = if index == 0 { "" } else { "\n\n" };
+                    let _ = sender.send(CandleMessageChunk::Text(format!(
+                        "{}[image {}: {}]\n",
+                        separator,
+                        index + 1,
+                        image.source
+                    )));
+                }
+
+                if !image.is_url() && !Path::new(&image.source).is_file() {
+                    let _ = sender.send(CandleMessageChunk::Error(format!(
+                        "Image not found: {}",
+                        image.source
+                    )));
+                    continue;
+                }
+
+                let mut stream = if image.is_url() {
+                    model.describe_url(&image.source, &prompt)
+                } else {
+                    model.describe_image(&image.source, &prompt)
+                };
+                while let Some(chunk) = stream.next().await {
+                    if let Some(error) = chunk.error() {
+                        let _ = sender.send(CandleMessageChunk::Error(format!(
+                            "{}: {}",
+                            image.source,
+                            error.trim_start_matches("Error: ")
+                        )));
+                        break;
+                    }
+                    if let Some(stats) = &chunk.stats {
+                        usage += CandleUsage::new(stats.input_tokens, stats.tokens_generated);
+                        elapsed_secs += stats.elapsed_secs;
+                    }
+                    if !chunk.text.is_empty() {
+                        let _ = sender.send(CandleMessageChunk::Text(chunk.text));
+                    }
+                }
+            }
+
+            let tokens_per_sec =
+                (elapsed_secs > 0.0).then(|| f64::from(usage.output_tokens) / elapsed_secs);
+            let _ = sender.send(CandleMessageChunk::Complete {
+                text: String::new(),
+                finish_reason: Some("stop".to_string()),
+                usage: Some(usage),
+                token_count: Some(usage.output_tokens),
+                elapsed_secs: Some(elapsed_secs),
+                tokens_per_sec,
+                provider: Some(provider),
+            });
+        },
+    ))
+}
@@ -58,6 +58,8 @@ pub enum CandleCompletionChunk {
         token_count: Option<u32>,
         elapsed_secs: Option<f64>,
         tokens_per_sec: Option<f64>,
+        /// Name of the provider that produced the response
+        provider: Option<String>,
     },
 
     /// Error occurred during streaming
@@ -42,10 +42,19 @@ impl MessageChunk for CandleUnitChunk {
 pub struct GenerationStats {
     /// Total tokens generated (output tokens only)
     pub tokens_generated: u32,
+    /// Prompt tokens prefilled after context window handling
+    #[serde(default)]
+    pub input_tokens: u32,
+    /// Prompt tokens dropped to fit the context window
+    #[serde(default)]
+    pub evicted_tokens: u32,
     /// Wall-clock time spent generating tokens in seconds
     pub elapsed_secs: f64,
     /// Throughput: tokens generated per second
     pub tokens_per_sec: f64,
+    /// Percentage of draft tokens accepted, when speculative decoding ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_acceptance_rate: Option<f64>,
 }
 
 /// Streaming text chunk from `TextGenerator` to Engine layer
@@ -15,6 +15,7 @@ use std::time::SystemTime;
 use tokio_stream::Stream;
 use uuid::Uuid;
 
+use super::indexing::{CandleIndexReport, CandleIndexingConfig, context_id, refresh_index};
 use super::processor::CandleStreamingContextProcessor;
 use super::types::{
     CandleContextError, CandleContextEvent, CandleDirectory, CandleFile, CandleFiles, CandleGithub,
@@ -22,6 +23,7 @@ use super::types::{
     CandleImmutableGithubContext,
 };
 use crate::domain::context::CandleDocument as Document;
+use crate::memory::core::manager::coordinator::MemoryCoordinator;
 
 /// Context wrapper with zero Arc usage
 #[derive(Debug)]
@@ -240,11 +242,68 @@ impl CandleContext<CandleDirectory> {
             recursive: true,
             extensions: Vec::new(),
             max_depth: None,
+            indexing: CandleIndexingConfig::default(),
             memory_integration: None,
         };
         Self::new(CandleContextSourceType::Directory(directory_context))
     }
 
+    /// Set chunking and the prompt token budget of the directory's index
+    #[must_use]
+    pub fn with_indexing(mut self, indexing: CandleIndexingConfig) -> Self {
+        if let CandleContextSourceType::Directory(directory_context) = &mut self.source {
+            directory_context.indexing = indexing;
+        }
+        self
+    }
+
+    /// Chunking and prompt budget settings of the directory's index
+    pub fn indexing(&self) -> CandleIndexingConfig {
+        match &self.source {
+            CandleContextSourceType::Directory(directory_context) => directory_context.indexing,
+            _ => CandleIndexingConfig::default(),
+        }
+    }
+
+    /// Stable id tagging the memories indexed from this directory
+    pub fn context_id(&self) -> String {
+        match &self.source {
+            CandleContextSourceType::Directory(directory_context) => {
+                context_id("directory", &directory_context.path)
+            }
+            _ => context_id("directory", ""),
+        }
+    }
+
+    /// Index the directory into memory, re-embedding only changed files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index manifest cannot be written.
+    pub async fn refresh(
+        &self,
+        memory: &MemoryCoordinator,
+    ) -> Result<CandleIndexReport, CandleContextError> {
+        self.refresh_for(memory, &HashMap::new()).await
+    }
+
+    /// Index the directory, storing chunks under the session's user and agent
+    pub(crate) async fn refresh_for<S: std::hash::BuildHasher>(
+        &self,
+        memory: &MemoryCoordinator,
+        metadata: &HashMap<String, String, S>,
+    ) -> Result<CandleIndexReport, CandleContextError> {
+        refresh_index(
+            &self.context_id(),
+            "context_directory",
+            self.clone().load(),
+            memory,
+            &self.indexing(),
+            metadata,
+        )
+        .await
+    }
+
     /// Load documents asynchronously with streaming - returns unwrapped values
     #[inline]
     pub fn load(self) -> Pin<Box<dyn Stream<Item = Document> + Send>> {
@@ -366,11 +425,91 @@ impl CandleContext<CandleGithub> {
             branch: "main".to_string(),
             pattern: pattern_str,
             auth_token: None,
+            indexing: CandleIndexingConfig::default(),
             memory_integration: None,
         };
         Self::new(CandleContextSourceType::Github(github_context))
     }
 
+    /// Set the repository the glob pattern is matched in
+    #[must_use]
+    pub fn with_repository(mut self, repository_url: impl Into<String>) -> Self {
+        if let CandleContextSourceType::Github(github_context) = &mut self.source {
+            github_context.repository_url = repository_url.into();
+        }
+        self
+    }
+
+    /// Set the branch to check out (default `main`)
+    #[must_use]
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        if let CandleContextSourceType::Github(github_context) = &mut self.source {
+            github_context.branch = branch.into();
+        }
+        self
+    }
+
+    /// Set chunking and the prompt token budget of the repository's index
+    #[must_use]
+    pub fn with_indexing(mut self, indexing: CandleIndexingConfig) -> Self {
+        if let CandleContextSourceType::Github(github_context) = &mut self.source {
+            github_context.indexing = indexing;
+        }
+        self
+    }
+
+    /// Chunking and prompt budget settings of the repository's index
+    pub fn indexing(&self) -> CandleIndexingConfig {
+        match &self.source {
+            CandleContextSourceType::Github(github_context) => github_context.indexing,
+            _ => CandleIndexingConfig::default(),
+        }
+    }
+
+    /// Stable id tagging the memories indexed from this repository pattern
+    pub fn context_id(&self) -> String {
+        match &self.source {
+            CandleContextSourceType::Github(github_context) => context_id(
+                "github",
+                &format!(
+                    "{}@{}:{}",
+                    github_context.repository_url, github_context.branch, github_context.pattern
+                ),
+            ),
+            _ => context_id("github", ""),
+        }
+    }
+
+    /// Pull the repository and index matching files into memory,
+    /// re-embedding only changed files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index manifest cannot be written.
+    pub async fn refresh(
+        &self,
+        memory: &MemoryCoordinator,
+    ) -> Result<CandleIndexReport, CandleContextError> {
+        self.refresh_for(memory, &HashMap::new()).await
+    }
+
+    /// Index the repository, storing chunks under the session's user and agent
+    pub(crate) async fn refresh_for<S: std::hash::BuildHasher>(
+        &self,
+        memory: &MemoryCoordinator,
+        metadata: &HashMap<String, String, S>,
+    ) -> Result<CandleIndexReport, CandleContextError> {
+        refresh_index(
+            &self.context_id(),
+            "context_github",
+            self.clone().load(),
+            memory,
+            &self.indexing(),
+            metadata,
+        )
+        .await
+    }
+
     /// Get cache directory for GitHub repositories
     fn get_github_cache_dir() -> PathBuf {
         std::env::var("HOME")
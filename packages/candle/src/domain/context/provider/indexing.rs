@@ -0,0 +1,326 @@
+//! Incremental indexing of directory and GitHub contexts into memory
+//!
+//! Every document of a context is split into line-aligned chunks, and each
+//! chunk is embedded and stored by the memory coordinator, tagged with the
+//! context id so prompt assembly can hold the context to its token budget.
+//! A manifest of per-file SHA-256 content hashes, kept under the user's
+//! cache directory, records the memories each file produced: a refresh only
+//! re-embeds files whose content changed and deletes the chunks of files
+//! that are gone.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio_stream::{Stream, StreamExt};
+
+use super::types::CandleContextError;
+use crate::core::estimate_tokens;
+use crate::domain::context::CandleDocument as Document;
+use crate::domain::memory::primitives::types::MemoryTypeEnum;
+use crate::memory::MemoryMetadata;
+use crate::memory::core::manager::coordinator::MemoryCoordinator;
+use crate::memory::ops::retrieval::CONTEXT_ID_KEY;
+
+/// Chunking and prompt budget settings of an indexed context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandleIndexingConfig {
+    /// Target size of one chunk in tokens
+    pub chunk_tokens: usize,
+    /// Lines repeated at the start of the next chunk
+    pub overlap_lines: usize,
+    /// Prompt tokens the context's memories may use per turn
+    pub token_budget: usize,
+}
+
+impl Default for CandleIndexingConfig {
+    fn default() -> Self {
+        Self {
+            chunk_tokens: 256,
+            overlap_lines: 2,
+            token_budget: 512,
+        }
+    }
+}
+
+impl CandleIndexingConfig {
+    /// Set the target chunk size in tokens (at least 1)
+    #[must_use]
+    pub fn with_chunk_tokens(mut self, chunk_tokens: usize) -> Self {
+        self.chunk_tokens = chunk_tokens.max(1);
+        self
+    }
+
+    /// Set the lines shared between consecutive chunks
+    #[must_use]
+    pub fn with_overlap_lines(mut self, overlap_lines: usize) -> Self {
+        self.overlap_lines = overlap_lines;
+        self
+    }
+
+    /// Set the prompt token budget of the context
+    #[must_use]
+    pub fn with_token_budget(mut self, token_budget: usize) -> Self {
+        self.token_budget = token_budget;
+        self
+    }
+}
+
+/// One chunk of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandleChunk {
+    /// First line of the chunk, 1-based
+    pub start_line: usize,
+    /// Last line of the chunk, 1-based
+    pub end_line: usize,
+    /// Chunk text, headed by its path and line range
+    pub text: String,
+}
+
+/// What a refresh changed in a context's index
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandleIndexReport {
+    /// Context the report is for
+    pub context_id: String,
+    /// Files indexed for the first time or re-indexed after a change
+    pub indexed_files: usize,
+    /// Files skipped because their content hash is unchanged
+    pub unchanged_files: usize,
+    /// Files gone from the source whose chunks were deleted
+    pub removed_files: usize,
+    /// Chunks stored by this refresh
+    pub stored_chunks: usize,
+    /// Chunks that could not be stored or deleted
+    pub failed_chunks: usize,
+}
+
+/// Split a document into chunks of about `config.chunk_tokens` tokens
+///
+/// Chunks end at line breaks; a single line longer than the target becomes a
+/// chunk of its own. Each chunk is headed `<path> (lines a-b)` so its
+/// embedding and provenance both name where it came from.
+pub fn chunk_document(path: &str, text: &str, config: &CandleIndexingConfig) -> Vec<CandleChunk> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < lines.len() {
+        let mut end = start;
+        let mut tokens = 0;
+        while end < lines.len() {
+            let cost = estimate_tokens(lines[end]) + 1;
+            if end > start && tokens + cost > config.chunk_tokens {
+                break;
+            }
+            tokens += cost;
+            end += 1;
+        }
+
+        let body = lines[start..end].join("\n");
+        if !body.trim().is_empty() {
+            chunks.push(CandleChunk {
+                start_line: start + 1,
+                end_line: end,
+                text: format!("{path} (lines {}-{end})\n{body}", start + 1),
+            });
+        }
+
+        if end == lines.len() {
+            break;
+        }
+        // Overlap only while the next chunk still starts past this one
+        start = end.saturating_sub(config.overlap_lines).max(start + 1);
+    }
+    chunks
+}
+
+/// Memories one file produced, keyed by its content hash
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IndexedFile {
+    hash: String,
+    memory_ids: Vec<String>,
+}
+
+/// Per-context record of indexed files
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexManifest {
+    files: BTreeMap<String, IndexedFile>,
+}
+
+impl IndexManifest {
+    /// Load the manifest of a context; a missing or unreadable one is empty
+    async fn load(path: &Path) -> Self {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable index manifest {}: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write the manifest, replacing the previous one atomically
+    async fn save(&self, path: &Path) -> Result<(), CandleContextError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| CandleContextError::IoError(e.to_string()))?;
+        }
+        let bytes =
+            serde_json::to_vec(self).map_err(|e| CandleContextError::MemoryError(e.to_string()))?;
+        let staging = path.with_extension("json.tmp");
+        tokio::fs::write(&staging, bytes)
+            .await
+            .map_err(|e| CandleContextError::IoError(e.to_string()))?;
+        tokio::fs::rename(&staging, path)
+            .await
+            .map_err(|e| CandleContextError::IoError(e.to_string()))
+    }
+}
+
+/// Stable id of a context, derived from what it indexes
+pub(crate) fn context_id(kind: &str, identity: &str) -> String {
+    format!("{kind}:{}", &sha256_hex(identity)[..16])
+}
+
+/// Directory holding the index manifests
+fn manifest_dir() -> PathBuf {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_or_else(
+            |_| PathBuf::from("/tmp/cyrup/context-index"),
+            |home| PathBuf::from(home).join(".cache/cyrup/context-index"),
+        )
+}
+
+fn sha256_hex(text: &str) -> String {
+    let digest = Sha256::digest(text.as_bytes());
+    digest
+        .iter()
+        .fold(String::with_capacity(digest.len() * 2), |mut s, b| {
+            let _ = write!(&mut s, "{b:02x}");
+            s
+        })
+}
+
+/// Bring a context's memories in line with its current documents
+///
+/// Files missing from a load that produced at least one document are
+/// dropped from the index. `tag` is added to every stored chunk;
+/// `metadata` supplies the `user_id` and `agent_id` they are stored under.
+pub(crate) async fn refresh_index<S: std::hash::BuildHasher>(
+    context_id: &str,
+    tag: &str,
+    documents: Pin<Box<dyn Stream<Item = Document> + Send>>,
+    memory: &MemoryCoordinator,
+    config: &CandleIndexingConfig,
+    metadata: &HashMap<String, String, S>,
+) -> Result<CandleIndexReport, CandleContextError> {
+    let manifest_path = manifest_dir().join(format!("{}.json", context_id.replace(':', "_")));
+    let mut manifest = IndexManifest::load(&manifest_path).await;
+    let mut report = CandleIndexReport {
+        context_id: context_id.to_string(),
+        ..CandleIndexReport::default()
+    };
+    let mut seen = HashSet::new();
+
+    tokio::pin!(documents);
+    while let Some(doc) = documents.next().await {
+        let Some(path) = doc
+            .additional_props
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+        else {
+            log::warn!("Skipping context document without a path in {context_id}");
+            continue;
+        };
+        let hash = sha256_hex(&doc.data);
+        seen.insert(path.clone());
+
+        if manifest
+            .files
+            .get(&path)
+            .is_some_and(|file| file.hash == hash)
+        {
+            report.unchanged_files += 1;
+            continue;
+        }
+
+        if let Some(stale) = manifest.files.remove(&path) {
+            report.failed_chunks += delete_memories(memory, &stale.memory_ids).await;
+        }
+
+        let mut memory_ids = Vec::new();
+        for chunk in chunk_document(&path, &doc.data, config) {
+            let chunk_meta = MemoryMetadata {
+                user_id: metadata.get("user_id").cloned(),
+                agent_id: metadata.get("agent_id").cloned(),
+                context: "session_context".to_string(),
+                importance: 0.5,
+                keywords: vec![],
+                category: "context".to_string(),
+                source: Some(path.clone()),
+                created_at: chrono::Utc::now(),
+                last_accessed_at: None,
+                embedding: None,
+                custom: serde_json::json!({ CONTEXT_ID_KEY: context_id }),
+                tags: vec![tag.to_string()],
+            };
+            match memory
+                .add_memory(chunk.text, MemoryTypeEnum::Semantic, Some(chunk_meta))
+                .await
+            {
+                Ok(node) => {
+                    memory_ids.push(node.id().to_string());
+                    report.stored_chunks += 1;
+                }
+                Err(e) => {
+                    log::warn!("Failed to index chunk of {path} in {context_id}: {e:?}");
+                    report.failed_chunks += 1;
+                }
+            }
+        }
+        manifest
+            .files
+            .insert(path, IndexedFile { hash, memory_ids });
+        report.indexed_files += 1;
+    }
+
+    // A load that produced nothing (a failed clone, an unreadable directory)
+    // must not wipe the index
+    let removed: Vec<String> = if seen.is_empty() {
+        Vec::new()
+    } else {
+        manifest
+            .files
+            .keys()
+            .filter(|path| !seen.contains(*path))
+            .cloned()
+            .collect()
+    };
+    for path in removed {
+        if let Some(file) = manifest.files.remove(&path) {
+            report.failed_chunks += delete_memories(memory, &file.memory_ids).await;
+            report.removed_files += 1;
+        }
+    }
+
+    manifest.save(&manifest_path).await?;
+    Ok(report)
+}
+
+/// Delete stored chunks; returns how many could not be deleted
+async fn delete_memories(memory: &MemoryCoordinator, ids: &[String]) -> usize {
+    let mut failed = 0;
+    for id in ids {
+        if let Err(e) = memory.delete_memory(id).await {
+            log::warn!("Failed to delete stale context chunk {id}: {e:?}");
+            failed += 1;
+        }
+    }
+    failed
+}
@@ -8,10 +8,12 @@
 //! parallel processing, real-time event streaming, comprehensive error handling.
 
 pub mod context_impl;
+pub mod indexing;
 pub mod processor;
 pub mod types;
 
 // Re-export all public types to maintain API compatibility
 pub use context_impl::*;
+pub use indexing::{CandleChunk, CandleIndexReport, CandleIndexingConfig, chunk_document};
 pub use processor::*;
 pub use types::*;
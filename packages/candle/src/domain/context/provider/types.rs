@@ -12,6 +12,8 @@ use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use tokio_stream::Stream;
 
+use super::indexing::CandleIndexingConfig;
+
 /// Marker types for `CandleContext`
 /// Marker type for file-based Candle context operations. Used in typestate pattern to ensure compile-time safety for file context providers.
 #[derive(Debug, Clone)]
@@ -268,6 +270,8 @@ pub struct CandleImmutableDirectoryContext {
     pub extensions: Vec<String>,
     /// Maximum depth for traversal
     pub max_depth: Option<usize>,
+    /// Chunking and prompt budget of the directory's index
+    pub indexing: CandleIndexingConfig,
     /// Memory integration layer
     pub memory_integration: Option<CandleMemoryIntegration>,
 }
@@ -283,6 +287,8 @@ pub struct CandleImmutableGithubContext {
     pub pattern: String,
     /// Authentication token (if needed)
     pub auth_token: Option<String>,
+    /// Chunking and prompt budget of the repository's index
+    pub indexing: CandleIndexingConfig,
     /// Memory integration layer
     pub memory_integration: Option<CandleMemoryIntegration>,
 }
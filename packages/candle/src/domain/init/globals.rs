@@ -42,7 +42,8 @@
 //! - `CYRUP_MEMORY_MAX_ACTIVE` - Maximum active memories (default: 1000000)
 //!
 //! #### Security Configuration
-//! - `CYRUP_MEMORY_ENABLE_ENCRYPTION` - Enable encryption (default: false)
+//! - `CYRUP_MEMORY_ENABLE_ENCRYPTION` - Seal sensitive memories at rest (default: false)
+//! - `CYRUP_MEMORY_KEY` - Base64 encoded 32-byte key for sealed memories (default: OS keychain)
 //! - `CYRUP_MEMORY_MAX_FAILED_ATTEMPTS` - Security lockout threshold (default: 5)
 //!
 //! #### Monitoring Configuration
@@ -7,7 +7,9 @@ use tokio::sync::{Mutex, mpsc};
 
 use crate::domain::memory::MemoryConfig;
 use crate::memory::core::manager::surreal::SurrealDBMemoryManager;
-use surrealdb::engine::any;
+use crate::memory::core::privacy::MemoryCipher;
+use surrealdb::Surreal;
+use surrealdb::engine::any::{self, Any};
 
 use crate::domain::core::DomainInitError;
 
@@ -65,7 +67,16 @@ pub async fn initialize_memory_service_with_config(
         .map_err(|e| DomainInitError::DatabaseInitializationFailed(e.to_string()))?;
 
     // Create the real memory manager with SurrealDB connection and default embeddings from registry
-    let manager = SurrealDBMemoryManager::new(db);
+    let mut manager = SurrealDBMemoryManager::new(db);
+
+    // Seal sensitive memories at rest when encryption is enabled; without a
+    // key, storing a sensitive memory fails instead of writing plain text
+    if config.security.enable_encryption {
+        match MemoryCipher::resolve() {
+            Ok(cipher) => manager = manager.with_cipher(Arc::new(cipher)),
+            Err(e) => log::warn!("Sensitive memories are unavailable: {}", e),
+        }
+    }
 
     // Initialize the memory schema and indexes
     manager
@@ -76,6 +87,51 @@ pub async fn initialize_memory_service_with_config(
     Ok(manager)
 }
 
+/// Shared connection to the agent's `SurrealKV` store
+static AGENT_DATABASE: tokio::sync::OnceCell<Surreal<Any>> = tokio::sync::OnceCell::const_new();
+
+/// Connect to the agent's `SurrealKV` store at `<cache>/cyrup/agent.db`
+///
+/// The connection is opened once per process and shared, so the memory
+/// coordinator and the conversation transcript store never race for the
+/// on-disk lock.
+///
+/// # Errors
+///
+/// Returns `DomainInitError` if:
+/// - The database directory cannot be created
+/// - Database connection to `SurrealDB` fails
+/// - Namespace or database selection fails
+pub async fn agent_database() -> Result<Surreal<Any>, DomainInitError> {
+    AGENT_DATABASE
+        .get_or_try_init(|| async {
+            let db_path = dirs::cache_dir()
+                .unwrap_or_else(|| std::path::PathBuf::from("."))
+                .join("cyrup")
+                .join("agent.db");
+
+            if let Some(parent) = db_path.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                    DomainInitError::DatabaseConnectionFailed(format!(
+                        "Failed to create database directory: {e}"
+                    ))
+                })?;
+            }
+
+            let db = any::connect(format!("surrealkv://{}", db_path.display()))
+                .await
+                .map_err(|e| DomainInitError::DatabaseConnectionFailed(e.to_string()))?;
+            db.use_ns("candle")
+                .use_db("agent")
+                .await
+                .map_err(|e| DomainInitError::DatabaseInitializationFailed(e.to_string()))?;
+
+            Ok(db)
+        })
+        .await
+        .cloned()
+}
+
 /// Get default memory configuration
 ///
 /// # Returns
@@ -0,0 +1,354 @@
+//! Tool call guardrails
+//!
+//! Every model-proposed tool call passes through `ToolGuardrails` before the
+//! router runs it:
+//! 1. the arguments are validated against the tool's JSON input schema
+//! 2. each `ToolPolicy` allows the call, denies it, or asks for confirmation
+//! 3. calls that need confirmation are put to the `ConfirmationHandler`
+//!    (a CLI prompt, an API webhook, ...)
+//!
+//! A call stopped at any step is never run. The router reports it as a
+//! `RouterError`, which `RouterError::to_tool_error` turns into a structured
+//! error the model can read and react to.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use jsonschema::Validator;
+use serde_json::Value;
+
+use super::router::RouterError;
+
+/// What a policy wants done with a tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Let the call run
+    Allow,
+    /// Refuse the call, with the reason reported to the model
+    Deny(String),
+    /// Run the call only if the confirmation handler approves it
+    Confirm(String),
+}
+
+/// Rule applied to every tool call before it runs
+pub trait ToolPolicy: Send + Sync {
+    /// Decide what to do with a call to `tool_name` with `arguments`
+    fn evaluate(&self, tool_name: &str, arguments: &Value) -> PolicyDecision;
+}
+
+/// Tool call awaiting user confirmation
+#[derive(Debug, Clone)]
+pub struct ConfirmationRequest {
+    /// Tool the model wants to call
+    pub tool_name: String,
+    /// Arguments the model supplied
+    pub arguments: Value,
+    /// Why the call needs confirmation
+    pub reason: String,
+}
+
+/// Async callback approving (`true`) or rejecting (`false`) a tool call
+pub type ConfirmationHandler =
+    Arc<dyn Fn(ConfirmationRequest) -> Pin<Box<dyn Future<Output = bool> + Send>> + Send + Sync>;
+
+/// Confirmation handler that asks on the terminal
+///
+/// Prints the call to stderr and reads a `y`/`yes` answer from stdin;
+/// anything else, including a closed stdin, rejects the call.
+#[must_use]
+pub fn cli_confirmation() -> ConfirmationHandler {
+    Arc::new(|request: ConfirmationRequest| {
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                let mut stderr = std::io::stderr();
+                let _ = write!(
+                    stderr,
+                    "Allow tool '{}' ({})?\n  arguments: {}\n[y/N] ",
+                    request.tool_name, request.reason, request.arguments
+                );
+                let _ = stderr.flush();
+
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer).is_ok()
+                    && matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+            })
+            .await
+            .unwrap_or(false)
+        })
+    })
+}
+
+/// Checks applied to tool calls before they are routed
+#[derive(Clone)]
+pub struct ToolGuardrails {
+    validate_arguments: bool,
+    policies: Vec<Arc<dyn ToolPolicy>>,
+    confirmation: Option<ConfirmationHandler>,
+}
+
+impl std::fmt::Debug for ToolGuardrails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolGuardrails")
+            .field("validate_arguments", &self.validate_arguments)
+            .field("policies", &self.policies.len())
+            .field("confirmation", &self.confirmation.is_some())
+            .finish()
+    }
+}
+
+impl Default for ToolGuardrails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolGuardrails {
+    /// Validate arguments against tool schemas, with no policies
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            validate_arguments: true,
+            policies: Vec::new(),
+            confirmation: None,
+        }
+    }
+
+    /// Pass arguments to tools without checking them against their schema
+    #[must_use]
+    pub fn without_argument_validation(mut self) -> Self {
+        self.validate_arguments = false;
+        self
+    }
+
+    /// Apply `policy` to every call; policies run in the order they are added
+    #[must_use]
+    pub fn with_policy(mut self, policy: impl ToolPolicy + 'static) -> Self {
+        self.policies.push(Arc::new(policy));
+        self
+    }
+
+    /// Ask `handler` about calls a policy wants confirmed
+    ///
+    /// Without a handler such calls are denied.
+    #[must_use]
+    pub fn with_confirmation(mut self, handler: ConfirmationHandler) -> Self {
+        self.confirmation = Some(handler);
+        self
+    }
+
+    /// Whether any check is configured
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.validate_arguments || !self.policies.is_empty()
+    }
+
+    /// Check a call to `tool_name`, whose input schema is `schema`
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidArguments` if the arguments do not match
+    /// the schema, and `RouterError::Denied` if a policy or the confirmation
+    /// handler refuses the call
+    pub async fn check(
+        &self,
+        tool_name: &str,
+        schema: Option<&Value>,
+        arguments: &Value,
+    ) -> Result<(), RouterError> {
+        if self.validate_arguments
+            && let Some(schema) = schema
+        {
+            validate_arguments(tool_name, schema, arguments)?;
+        }
+
+        let mut confirm = Vec::new();
+        for policy in &self.policies {
+            match policy.evaluate(tool_name, arguments) {
+                PolicyDecision::Allow => {}
+                PolicyDecision::Deny(reason) => return Err(RouterError::Denied(reason)),
+                PolicyDecision::Confirm(reason) => confirm.push(reason),
+            }
+        }
+        if confirm.is_empty() {
+            return Ok(());
+        }
+
+        let reason = confirm.join("; ");
+        let Some(handler) = &self.confirmation else {
+            return Err(RouterError::Denied(format!(
+                "{reason}; confirmation is required but no confirmation handler is configured"
+            )));
+        };
+
+        let approved = handler(ConfirmationRequest {
+            tool_name: tool_name.to_string(),
+            arguments: arguments.clone(),
+            reason: reason.clone(),
+        })
+        .await;
+        if approved {
+            Ok(())
+        } else {
+            Err(RouterError::Denied(format!(
+                "{reason}; the user declined the call"
+            )))
+        }
+    }
+}
+
+/// Check `arguments` against a tool's input schema
+///
+/// Schemas that fail to compile are skipped with a warning rather than
+/// blocking every call to the tool.
+fn validate_arguments(
+    tool_name: &str,
+    schema: &Value,
+    arguments: &Value,
+) -> Result<(), RouterError> {
+    let validator = match Validator::options().build(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            log::warn!("Skipping argument validation for tool '{tool_name}': invalid schema: {e}");
+            return Ok(());
+        }
+    };
+
+    let errors: Vec<String> = validator
+        .iter_errors(arguments)
+        .map(|e| e.to_string())
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(RouterError::InvalidArguments(format!(
+            "Arguments for '{tool_name}' do not match its schema: {}",
+            errors.join(", ")
+        )))
+    }
+}
+
+/// Deny file system tools access to paths outside a workspace
+///
+/// Each guarded tool lists the arguments that hold paths. Relative paths are
+/// resolved against the workspace root, and `..` components are resolved
+/// lexically, so `../etc/passwd` is caught without touching the disk.
+#[derive(Debug, Clone)]
+pub struct WorkspacePolicy {
+    root: PathBuf,
+    guarded: HashMap<String, Vec<String>>,
+}
+
+impl WorkspacePolicy {
+    /// Restrict guarded tools to paths under `root`
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: normalize(&root.into()),
+            guarded: HashMap::new(),
+        }
+    }
+
+    /// Guard `tool_name`, whose `path_arguments` hold file paths
+    ///
+    /// String arguments and arrays of strings are both checked.
+    #[must_use]
+    pub fn guard<I, S>(mut self, tool_name: impl Into<String>, path_arguments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.guarded.insert(
+            tool_name.into(),
+            path_arguments.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+
+    /// Whether `path` resolves to a location under the workspace root
+    #[must_use]
+    pub fn contains(&self, path: &str) -> bool {
+        normalize(&self.root.join(path)).starts_with(&self.root)
+    }
+}
+
+impl ToolPolicy for WorkspacePolicy {
+    fn evaluate(&self, tool_name: &str, arguments: &Value) -> PolicyDecision {
+        let Some(path_arguments) = self.guarded.get(tool_name) else {
+            return PolicyDecision::Allow;
+        };
+
+        for name in path_arguments {
+            let paths: Vec<&str> = match arguments.get(name) {
+                Some(Value::String(path)) => vec![path.as_str()],
+                Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+            if let Some(path) = paths.into_iter().find(|path| !self.contains(path)) {
+                return PolicyDecision::Deny(format!(
+                    "'{name}' path '{path}' is outside the workspace {}",
+                    self.root.display()
+                ));
+            }
+        }
+
+        PolicyDecision::Allow
+    }
+}
+
+/// Resolve `.` and `..` components without consulting the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Require confirmation before calling any of a set of tools
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmTools {
+    tools: HashMap<String, String>,
+}
+
+impl ConfirmTools {
+    /// Confirm nothing until tools are added
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require confirmation for `tool_name`, explaining why with `reason`
+    #[must_use]
+    pub fn tool(mut self, tool_name: impl Into<String>, reason: impl Into<String>) -> Self {
+        self.tools.insert(tool_name.into(), reason.into());
+        self
+    }
+}
+
+impl ToolPolicy for ConfirmTools {
+    fn evaluate(&self, tool_name: &str, _arguments: &Value) -> PolicyDecision {
+        match self.tools.get(tool_name) {
+            Some(reason) => PolicyDecision::Confirm(reason.clone()),
+            None => PolicyDecision::Allow,
+        }
+    }
+}
+
+impl<F> ToolPolicy for F
+where
+    F: Fn(&str, &Value) -> PolicyDecision + Send + Sync,
+{
+    fn evaluate(&self, tool_name: &str, arguments: &Value) -> PolicyDecision {
+        self(tool_name, arguments)
+    }
+}
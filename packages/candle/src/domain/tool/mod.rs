@@ -1,19 +1,39 @@
 //! Tool Interface
 //!
 //! This module provides tool routing and execution using `SweetMCP`.
-//! Tools are executed via WASM plugins and Cylo backends.
+//! Tools are executed via WASM plugins, native Rust functions, remote MCP servers
+//! and Cylo backends.
 //! Users never directly call tools - they prompt naturally and the LLM
 //! decides which tools to call, similar to `OpenAI` function calling.
 //!
 //! Key components:
-//! - `SweetMcpRouter`: Tool routing and execution via WASM/Cylo
+//! - `SweetMcpRouter`: Tool routing and execution via WASM/Cylo/native/remote MCP
+//! - `NativeTool`: async Rust functions as tools, with schemas derived from their argument type
+//! - `RemoteMcpServer`: stdio, Streamable HTTP and UDS server attachment
+//! - `ToolScheduler`: concurrent, dependency-ordered execution of tool call batches
+//! - `ToolGuardrails`: argument validation, policies and confirmation before a call runs
+//! - `SandboxProfile`: network, file system and shell posture shared with `Cylo` backends
 //! - OpenAI-style function calling experience
 //! - Full `tokio_stream::Stream` compatibility
 
+pub mod guardrails;
+pub mod native;
+pub mod remote;
 pub mod router;
+pub mod scheduler;
 
 // Re-export the SweetMCP router - NEW PREFERRED APPROACH
+pub use guardrails::{
+    ConfirmTools, ConfirmationHandler, ConfirmationRequest, PolicyDecision, ToolGuardrails,
+    ToolPolicy, WorkspacePolicy, cli_confirmation,
+};
+pub use native::NativeTool;
+pub use remote::{McpTransport, RemoteMcpServer};
 pub use router::{RouterError, SweetMcpRouter, ToolRoute};
+pub use scheduler::{ScheduledToolCall, ToolCallOutcome, ToolScheduler};
+
+// Sandbox profiles are defined by Cylo so backends and tools share one posture
+pub use cylo::{FsRoot, SandboxProfile, ShellPolicy};
 
 // Re-export SweetMCP types for external compatibility
 pub use mcp_client_traits::{McpClient, McpToolOperations};
@@ -0,0 +1,158 @@
+//! Native Rust tools
+//!
+//! A [`NativeTool`] wraps a plain async Rust function so the model can call
+//! it like any plugin or `MCP` tool. The input schema is derived from the
+//! function's argument type with `schemars`, arguments are deserialized into
+//! that type before the call, and the result is serialized back to JSON.
+//!
+//! ```rust,no_run
+//! use cyrup_candle::domain::tool::NativeTool;
+//! use schemars::JsonSchema;
+//! use serde::Deserialize;
+//!
+//! /// Arguments of the weather tool
+//! #[derive(Deserialize, JsonSchema)]
+//! struct Forecast {
+//!     /// City to look up
+//!     city: String,
+//!     /// Days ahead, today if left out
+//!     days: Option<u32>,
+//! }
+//!
+//! let tool = NativeTool::new(
+//!     "weather",
+//!     "Forecast for a city",
+//!     |args: Forecast| async move {
+//!         Ok::<_, String>(format!("Sunny in {} for {} days", args.city, args.days.unwrap_or(1)))
+//!     },
+//! );
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sweet_mcp_type::ToolInfo;
+
+use super::router::RouterError;
+use crate::domain::agent::role::convert_serde_to_sweet_json;
+
+type NativeHandler =
+    Arc<dyn Fn(Value) -> BoxFuture<'static, Result<Value, RouterError>> + Send + Sync>;
+
+/// A Rust async function exposed as a tool
+#[derive(Clone)]
+pub struct NativeTool {
+    name: String,
+    description: String,
+    input_schema: Value,
+    handler: NativeHandler,
+}
+
+impl NativeTool {
+    /// Tool calling `function` with arguments deserialized into `A`
+    ///
+    /// `A` should be a struct, since models pass arguments as an object; a
+    /// tool without arguments takes an empty struct. The input schema is
+    /// generated from `A`, including its doc comments as field descriptions.
+    /// The function's `Ok` value is serialized as the tool's result and its
+    /// `Err` is reported to the model as a failed call.
+    pub fn new<A, R, E, F, Fut>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        function: F,
+    ) -> Self
+    where
+        A: DeserializeOwned + JsonSchema + Send + 'static,
+        R: Serialize + Send + 'static,
+        E: fmt::Display + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+    {
+        let name = name.into();
+        let tool_name = name.clone();
+        let handler: NativeHandler = Arc::new(
+            move |arguments: Value| -> BoxFuture<'static, Result<Value, RouterError>> {
+                let args = serde_json::from_value::<A>(arguments)
+                    .map_err(|e| RouterError::InvalidArguments(format!("{tool_name}: {e}")));
+                let call = args.map(&function);
+                let tool_name = tool_name.clone();
+                Box::pin(async move {
+                    let result = call?
+                        .await
+                        .map_err(|e| RouterError::ExecutionFailed(format!("{tool_name}: {e}")))?;
+                    serde_json::to_value(result).map_err(|e| {
+                        RouterError::ExecutionFailed(format!(
+                            "{tool_name}: unserializable result: {e}"
+                        ))
+                    })
+                })
+            },
+        );
+
+        Self {
+            name,
+            description: description.into(),
+            input_schema: tool_schema(schemars::schema_for!(A).to_value()),
+            handler,
+        }
+    }
+
+    /// Tool name the model calls
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// What the tool does, as shown to the model
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// JSON schema of the arguments
+    pub fn input_schema(&self) -> &Value {
+        &self.input_schema
+    }
+
+    /// Tool description offered to the model
+    pub fn tool_info(&self) -> ToolInfo {
+        ToolInfo {
+            name: self.name.clone(),
+            description: Some(self.description.clone()),
+            input_schema: convert_serde_to_sweet_json(self.input_schema.clone()),
+        }
+    }
+
+    /// Run the tool with JSON `arguments`
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidArguments` if the arguments do not
+    /// deserialize, or `RouterError::ExecutionFailed` if the function fails
+    /// or its result cannot be serialized
+    pub async fn call(&self, arguments: Value) -> Result<Value, RouterError> {
+        (self.handler)(arguments).await
+    }
+}
+
+impl fmt::Debug for NativeTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeTool")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("input_schema", &self.input_schema)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Schema as sent to the model, without the generator's `$schema` and `title`
+fn tool_schema(mut schema: Value) -> Value {
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("title");
+    }
+    schema
+}
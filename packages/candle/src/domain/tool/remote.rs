@@ -0,0 +1,224 @@
+//! Remote `MCP` servers attached to the tool router
+//!
+//! External servers are reached through the sweetmcp client crates:
+//! - stdio commands via `StdioClient`
+//! - Streamable HTTP endpoints via `SseClient`
+//! - Unix domain sockets via `UdsClient`
+//!
+//! Their tools are exposed as `<namespace>__<tool>` so they never collide with
+//! built-in tools or with each other.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mcp_client_traits::McpClient;
+use sweet_mcp_type::{Implementation, JsonValue, ToolInfo};
+
+use super::router::RouterError;
+
+/// Separator between a server's namespace and its own tool name
+pub const NAMESPACE_SEPARATOR: &str = "__";
+
+/// Transport used to reach a remote `MCP` server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpTransport {
+    /// Spawn a command and speak newline-delimited JSON-RPC over stdio
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    },
+    /// POST JSON-RPC to a Streamable HTTP endpoint
+    StreamableHttp {
+        url: String,
+        headers: HashMap<String, String>,
+    },
+    /// Newline-delimited JSON-RPC over a Unix domain socket
+    Uds { path: PathBuf },
+}
+
+/// A remote `MCP` server to attach to the router
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteMcpServer {
+    /// Prefix for this server's tool names
+    pub namespace: String,
+    /// How to reach the server
+    pub transport: McpTransport,
+}
+
+impl RemoteMcpServer {
+    /// Server launched as a stdio subprocess
+    pub fn stdio(
+        namespace: impl Into<String>,
+        command: impl Into<String>,
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            namespace: namespace.into(),
+            transport: McpTransport::Stdio {
+                command: command.into(),
+                args: args.into_iter().map(Into::into).collect(),
+                env: HashMap::new(),
+            },
+        }
+    }
+
+    /// Server behind a Streamable HTTP endpoint (e.g. `https://host/mcp`)
+    pub fn streamable_http(namespace: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            transport: McpTransport::StreamableHttp {
+                url: url.into(),
+                headers: HashMap::new(),
+            },
+        }
+    }
+
+    /// Server listening on a Unix domain socket
+    pub fn uds(namespace: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            transport: McpTransport::Uds { path: path.into() },
+        }
+    }
+
+    /// Infer the transport from a target string
+    ///
+    /// `http://` and `https://` URLs use Streamable HTTP, `unix://` URLs and
+    /// `.sock` paths use a Unix domain socket, and anything else is treated as
+    /// a whitespace-separated stdio command line.
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidArguments` if the target is empty
+    pub fn parse(namespace: impl Into<String>, target: &str) -> Result<Self, RouterError> {
+        let target = target.trim();
+        if target.starts_with("http://") || target.starts_with("https://") {
+            return Ok(Self::streamable_http(namespace, target));
+        }
+        if let Some(path) = target.strip_prefix("unix://") {
+            return Ok(Self::uds(namespace, path));
+        }
+        if std::path::Path::new(target)
+            .extension()
+            .is_some_and(|extension| extension == "sock")
+        {
+            return Ok(Self::uds(namespace, target));
+        }
+
+        let mut parts = target.split_whitespace();
+        let command = parts.next().ok_or_else(|| {
+            RouterError::InvalidArguments("MCP server target must not be empty".to_string())
+        })?;
+        Ok(Self::stdio(namespace, command, parts))
+    }
+
+    /// Add an environment variable (stdio) or header (Streamable HTTP)
+    #[must_use]
+    pub fn with_setting(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        match &mut self.transport {
+            McpTransport::Stdio { env, .. } => {
+                env.insert(key.into(), value.into());
+            }
+            McpTransport::StreamableHttp { headers, .. } => {
+                headers.insert(key.into(), value.into());
+            }
+            McpTransport::Uds { .. } => {}
+        }
+        self
+    }
+
+    /// Check the namespace can be split back out of a tool name
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidArguments` for an empty namespace or one
+    /// containing the separator
+    pub fn validate(&self) -> Result<(), RouterError> {
+        if self.namespace.is_empty() || self.namespace.contains(NAMESPACE_SEPARATOR) {
+            return Err(RouterError::InvalidArguments(format!(
+                "MCP server namespace '{}' must be non-empty and must not contain '{NAMESPACE_SEPARATOR}'",
+                self.namespace
+            )));
+        }
+        Ok(())
+    }
+
+    /// Connect, perform the `initialize` handshake and list the server's tools
+    ///
+    /// # Errors
+    /// Returns `RouterError::BackendError` if the server cannot be reached or
+    /// rejects the handshake
+    pub(crate) async fn connect(&self) -> Result<(RemoteClient, Vec<ToolInfo>), RouterError> {
+        self.validate()?;
+        let unavailable = |e: &dyn std::fmt::Display| {
+            RouterError::BackendError(format!("MCP server '{}' unreachable: {e}", self.namespace))
+        };
+
+        let client: Arc<dyn McpClient> = match &self.transport {
+            McpTransport::Stdio { command, args, env } => {
+                let env: Vec<(&str, &str)> =
+                    env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+                Arc::new(
+                    sweetmcp_stdio_client::StdioClient::new(command, args, &env)
+                        .await
+                        .map_err(|e| unavailable(&e))?,
+                )
+            }
+            McpTransport::StreamableHttp { url, headers } => {
+                let mut client = sweetmcp_sse_client::SseClient::streamable_http(url)
+                    .map_err(|e| unavailable(&e))?;
+                for (key, value) in headers {
+                    client = client.with_header(key, value);
+                }
+                Arc::new(client)
+            }
+            #[cfg(unix)]
+            McpTransport::Uds { path } => Arc::new(
+                sweetmcp_stdio_client::UdsClient::connect(path)
+                    .await
+                    .map_err(|e| unavailable(&e))?,
+            ),
+            #[cfg(not(unix))]
+            McpTransport::Uds { .. } => {
+                return Err(RouterError::BackendError(
+                    "Unix domain sockets are not supported on this platform".to_string(),
+                ));
+            }
+        };
+
+        let client_info = Implementation {
+            name: "paraphym-candle".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        client
+            .initialize(JsonValue::Object(Box::default()), client_info)
+            .await
+            .map_err(|e| unavailable(&e))?;
+        let tools = client.list_tools().await.map_err(|e| unavailable(&e))?;
+
+        Ok((RemoteClient(client), tools))
+    }
+}
+
+/// Connected client for a remote server
+#[derive(Clone)]
+pub(crate) struct RemoteClient(pub(crate) Arc<dyn McpClient>);
+
+impl std::fmt::Debug for RemoteClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RemoteClient")
+    }
+}
+
+/// Build the tool name exposed for `tool` on the server `namespace`
+#[must_use]
+pub fn namespaced_tool_name(namespace: &str, tool: &str) -> String {
+    format!("{namespace}{NAMESPACE_SEPARATOR}{tool}")
+}
+
+/// Split an exposed tool name into `(namespace, tool)`
+#[must_use]
+pub fn split_namespaced_tool(name: &str) -> Option<(&str, &str)> {
+    name.split_once(NAMESPACE_SEPARATOR)
+        .filter(|(namespace, tool)| !namespace.is_empty() && !tool.is_empty())
+}
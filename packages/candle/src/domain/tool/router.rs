@@ -2,7 +2,8 @@
 //!
 //! This module provides the unified tool routing interface described in `TOOL_CALLING.md`.
 //! It implements Stage 3 (Function Calling) of the chat loop architecture, providing
-//! transparent routing between `SweetMCP` plugins, container `MCP` tools, and `Cylo` execution.
+//! transparent routing between `SweetMCP` plugins, native Rust tools, remote `MCP` servers,
+//! and `Cylo` execution.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -11,8 +12,13 @@ use serde_json::Value;
 use std::pin::Pin;
 use tokio_stream::Stream;
 
+use super::guardrails::ToolGuardrails;
+use super::native::NativeTool;
+use super::remote::{RemoteClient, RemoteMcpServer, namespaced_tool_name};
 use crate::domain::context::chunks::CandleJsonChunk;
-use cylo::{BackendConfig, Cylo, ExecutionRequest, ExecutionResult, create_backend};
+use cylo::{
+    BackendConfig, Cylo, ExecutionRequest, ExecutionResult, SandboxProfile, create_backend,
+};
 use sweet_mcp_type::{JsonValue, ToolInfo};
 
 /// `SweetMCP` Tool Router
@@ -29,6 +35,16 @@ pub struct SweetMcpRouter {
     plugin_configs: Vec<PluginConfig>,
     /// `Cylo` backend configuration (optional)
     cylo_config: Option<CyloBackendConfig>,
+    /// Rust functions registered as tools (user-provided)
+    native_tools: Vec<NativeTool>,
+    /// Remote `MCP` servers to attach (user-provided)
+    remote_servers: Vec<RemoteMcpServer>,
+    /// Connected remote clients: namespace -> client
+    remote_clients: Arc<tokio::sync::RwLock<HashMap<String, RemoteClient>>>,
+    /// Checks applied to every call before it is routed
+    guardrails: ToolGuardrails,
+    /// Sandbox posture applied to every `Cylo` execution (optional)
+    sandbox_profile: Option<SandboxProfile>,
 }
 
 /// Tool execution route strategy
@@ -41,6 +57,10 @@ pub enum ToolRoute {
         backend_type: String,
         config: String,
     },
+    /// Call a registered Rust function
+    Native { tool_name: String },
+    /// Forward to a remote `MCP` server
+    McpServer { namespace: String, tool_name: String },
 }
 
 /// Configuration for a WASM plugin tool
@@ -55,7 +75,7 @@ pub struct PluginConfig {
 /// Configuration for Cylo execution backend
 #[derive(Debug, Clone)]
 pub struct CyloBackendConfig {
-    pub backend_type: String, // "Apple", "LandLock", "FireCracker"
+    pub backend_type: String, // "Apple", "LandLock", "FireCracker", "Windows"
     pub config_value: String, // e.g., "python:alpine3.20" or "/tmp/sandbox"
 }
 
@@ -70,6 +90,34 @@ pub enum RouterError {
     ExecutionFailed(String),
     #[error("Backend error: {0}")]
     BackendError(String),
+    #[error("Tool call denied: {0}")]
+    Denied(String),
+}
+
+impl RouterError {
+    /// Stable identifier of the error kind
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ToolNotFound(_) => "tool_not_found",
+            Self::InvalidArguments(_) => "invalid_arguments",
+            Self::ExecutionFailed(_) => "execution_failed",
+            Self::BackendError(_) => "backend_error",
+            Self::Denied(_) => "denied",
+        }
+    }
+
+    /// Structured error fed back to the model in place of the tool's result
+    #[must_use]
+    pub fn to_tool_error(&self, tool_name: &str) -> Value {
+        serde_json::json!({
+            "error": {
+                "tool": tool_name,
+                "kind": self.kind(),
+                "message": self.to_string(),
+            }
+        })
+    }
 }
 
 impl SweetMcpRouter {
@@ -90,16 +138,69 @@ impl SweetMcpRouter {
             tool_routes: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
             plugin_configs,
             cylo_config,
+            native_tools: Vec::new(),
+            remote_servers: Vec::new(),
+            remote_clients: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            guardrails: ToolGuardrails::new(),
+            sandbox_profile: None,
         }
     }
 
+    /// Check calls with `guardrails` before routing them
+    ///
+    /// By default arguments are validated against each tool's input schema
+    /// and no policies apply.
+    #[must_use]
+    pub fn with_guardrails(mut self, guardrails: ToolGuardrails) -> Self {
+        self.guardrails = guardrails;
+        self
+    }
+
+    /// Run plugins and code execution under `profile`
+    ///
+    /// The profile's network policy, file system roots and shell policy apply
+    /// to every `Cylo` backend the router creates, and plugins receive the
+    /// profile as configuration. Without a profile backends use their defaults.
+    #[must_use]
+    pub fn with_sandbox_profile(mut self, profile: SandboxProfile) -> Self {
+        self.sandbox_profile = Some(profile);
+        self
+    }
+
+    /// Register Rust functions as tools; they are offered by `initialize`
+    #[must_use]
+    pub fn with_native_tools(mut self, tools: impl IntoIterator<Item = NativeTool>) -> Self {
+        self.native_tools.extend(tools);
+        self
+    }
+
+    /// Register a Rust function as a tool; it is offered by the next `initialize`
+    pub fn add_native_tool(&mut self, tool: NativeTool) {
+        self.native_tools.push(tool);
+    }
+
+    /// Attach remote `MCP` servers; they are connected by `initialize`
+    #[must_use]
+    pub fn with_remote_servers(mut self, servers: Vec<RemoteMcpServer>) -> Self {
+        self.remote_servers.extend(servers);
+        self
+    }
+
+    /// Attach a remote `MCP` server; it is connected by the next `initialize`
+    pub fn add_remote_server(&mut self, server: RemoteMcpServer) {
+        self.remote_servers.push(server);
+    }
+
     /// Initialize router by discovering available tools
     ///
     /// Stage 1 (Discovery) - Scan all available tool sources:
     /// - `SweetMCP` `WASM` plugins
-    /// - Container `MCP` servers
+    /// - Native Rust tools
+    /// - Remote `MCP` servers (stdio, Streamable HTTP, UDS)
     /// - Native `Cylo` execution capabilities
     ///
+    /// Remote servers that cannot be reached are skipped with a warning.
+    ///
     /// # Errors
     /// Returns `RouterError` if plugin discovery or tool registration fails
     pub async fn initialize(&mut self) -> Result<(), RouterError> {
@@ -112,6 +213,12 @@ impl SweetMcpRouter {
         // Add native code execution tools
         self.add_native_execution_tools(&mut tools, &mut routes);
 
+        // Register Rust function tools
+        self.register_native_tools(&mut tools, &mut routes);
+
+        // Connect remote MCP servers and merge their tools
+        let clients = self.discover_remote_servers(&mut tools, &mut routes).await;
+
         // Store discovered tools and routes
         {
             let mut available_tools = self.available_tools.write().await;
@@ -121,6 +228,10 @@ impl SweetMcpRouter {
             let mut tool_routes = self.tool_routes.write().await;
             *tool_routes = routes;
         }
+        {
+            let mut remote_clients = self.remote_clients.write().await;
+            *remote_clients = clients;
+        }
 
         Ok(())
     }
@@ -129,9 +240,13 @@ impl SweetMcpRouter {
     ///
     /// Stage 3 (Function Calling) - Transparent tool execution via routing.
     /// The `LLM` calls tools naturally without knowing execution method.
+    /// The call is checked by the router's guardrails first and never runs
+    /// if they reject it.
     ///
     /// # Errors
-    /// Returns `RouterError::ToolNotFound` if tool doesn't exist or execution fails
+    /// Returns `RouterError::ToolNotFound` if tool doesn't exist,
+    /// `RouterError::InvalidArguments` or `RouterError::Denied` if the
+    /// guardrails reject the call, or an error if execution fails
     pub async fn call_tool(&self, tool_name: &str, args: JsonValue) -> Result<Value, RouterError> {
         // Find the tool route
         let route = {
@@ -142,6 +257,20 @@ impl SweetMcpRouter {
                 .ok_or_else(|| RouterError::ToolNotFound(tool_name.to_string()))?
         };
 
+        if self.guardrails.is_active() {
+            let schema = {
+                let tools = self.available_tools.read().await;
+                tools
+                    .iter()
+                    .find(|tool| tool.name == tool_name)
+                    .map(|tool| Self::convert_sweet_json_to_serde(tool.input_schema.clone()))
+            };
+            let arguments = Self::convert_sweet_json_to_serde(args.clone());
+            self.guardrails
+                .check(tool_name, schema.as_ref(), &arguments)
+                .await?;
+        }
+
         // Execute via appropriate route
         match route {
             ToolRoute::SweetMcpPlugin { plugin_path } => {
@@ -154,6 +283,11 @@ impl SweetMcpRouter {
                 self.execute_cylo_backend(&backend_type, &config, args)
                     .await
             }
+            ToolRoute::Native { tool_name } => self.execute_native_tool(&tool_name, args).await,
+            ToolRoute::McpServer {
+                namespace,
+                tool_name,
+            } => self.execute_remote_tool(&namespace, &tool_name, args).await,
         }
     }
 
@@ -184,12 +318,7 @@ impl SweetMcpRouter {
                         let _ = tx.send(CandleJsonChunk(result));
                     }
                     Err(e) => {
-                        let error_value = Value::Object(
-                            [("error".to_string(), Value::String(e.to_string()))]
-                                .into_iter()
-                                .collect::<serde_json::Map<_, _>>(),
-                        );
-                        let _ = tx.send(CandleJsonChunk(error_value));
+                        let _ = tx.send(CandleJsonChunk(e.to_tool_error(&tool_name)));
                     }
                 }
             });
@@ -262,6 +391,126 @@ impl SweetMcpRouter {
         }
     }
 
+    /// Register the Rust function tools, skipping names already taken
+    fn register_native_tools(
+        &self,
+        tools: &mut Vec<ToolInfo>,
+        routes: &mut HashMap<String, ToolRoute>,
+    ) {
+        for tool in &self.native_tools {
+            if routes.contains_key(tool.name()) {
+                log::warn!(
+                    "Skipping native tool '{}': name already registered",
+                    tool.name()
+                );
+                continue;
+            }
+
+            routes.insert(
+                tool.name().to_string(),
+                ToolRoute::Native {
+                    tool_name: tool.name().to_string(),
+                },
+            );
+            tools.push(tool.tool_info());
+        }
+    }
+
+    /// Connect each remote `MCP` server and register its tools under its namespace
+    async fn discover_remote_servers(
+        &self,
+        tools: &mut Vec<ToolInfo>,
+        routes: &mut HashMap<String, ToolRoute>,
+    ) -> HashMap<String, RemoteClient> {
+        let mut clients = HashMap::new();
+
+        for server in &self.remote_servers {
+            if clients.contains_key(&server.namespace) {
+                log::warn!("Skipping duplicate MCP server namespace '{}'", server.namespace);
+                continue;
+            }
+
+            let (client, remote_tools) = match server.connect().await {
+                Ok(connected) => connected,
+                Err(e) => {
+                    log::warn!("Skipping MCP server '{}': {e}", server.namespace);
+                    continue;
+                }
+            };
+
+            for tool in remote_tools {
+                let name = namespaced_tool_name(&server.namespace, &tool.name);
+                if routes.contains_key(&name) {
+                    log::warn!("Skipping MCP tool '{name}': name already registered");
+                    continue;
+                }
+
+                routes.insert(
+                    name.clone(),
+                    ToolRoute::McpServer {
+                        namespace: server.namespace.clone(),
+                        tool_name: tool.name,
+                    },
+                );
+                tools.push(ToolInfo {
+                    name,
+                    description: tool.description,
+                    input_schema: tool.input_schema,
+                });
+            }
+
+            clients.insert(server.namespace.clone(), client);
+        }
+
+        clients
+    }
+
+    /// Forward a tool call to the remote `MCP` server owning `namespace`
+    async fn execute_remote_tool(
+        &self,
+        namespace: &str,
+        tool_name: &str,
+        args: JsonValue,
+    ) -> Result<Value, RouterError> {
+        let client = {
+            let clients = self.remote_clients.read().await;
+            clients.get(namespace).cloned().ok_or_else(|| {
+                RouterError::BackendError(format!("MCP server '{namespace}' is not connected"))
+            })?
+        };
+
+        let response = client
+            .0
+            .call_tool(tool_name, args)
+            .await
+            .map_err(|e| RouterError::ExecutionFailed(e.to_string()))?;
+
+        if let Some(error) = response.error {
+            return Err(RouterError::ExecutionFailed(format!(
+                "{tool_name} failed ({}): {}",
+                error.code, error.message
+            )));
+        }
+
+        Ok(response
+            .result
+            .map_or(Value::Null, Self::convert_sweet_json_to_serde))
+    }
+
+    /// Call a registered Rust function tool
+    async fn execute_native_tool(
+        &self,
+        tool_name: &str,
+        args: JsonValue,
+    ) -> Result<Value, RouterError> {
+        let tool = self
+            .native_tools
+            .iter()
+            .find(|tool| tool.name() == tool_name)
+            .ok_or_else(|| RouterError::ToolNotFound(tool_name.to_string()))?;
+        tool.call(Self::convert_sweet_json_to_serde(args)).await
+    }
+
     /// Execute `SweetMCP` `WASM` plugin
     async fn execute_sweetmcp_plugin(
         &self,
@@ -270,7 +519,7 @@ impl SweetMcpRouter {
     ) -> Result<Value, RouterError> {
         // Create Cylo backend for SweetMCP plugin execution
         let cylo_env = Cylo::SweetMcpPlugin(plugin_path.to_string());
-        let config = BackendConfig::new("sweetmcp_plugin");
+        let config = self.backend_config("sweetmcp_plugin");
 
         let backend = create_backend(&cylo_env, config)
             .map_err(|e| RouterError::BackendError(e.to_string()))?;
@@ -300,6 +549,7 @@ impl SweetMcpRouter {
             "Apple" => Cylo::Apple(config.to_string()),
             "LandLock" => Cylo::LandLock(config.to_string()),
             "FireCracker" => Cylo::FireCracker(config.to_string()),
+            "Windows" => Cylo::Windows(config.to_string()),
             "SweetMcpPlugin" => Cylo::SweetMcpPlugin(config.to_string()),
             _ => {
                 return Err(RouterError::BackendError(format!(
@@ -308,13 +558,23 @@ impl SweetMcpRouter {
             }
         };
 
-        let backend_config = BackendConfig::new(backend_type);
+        let backend_config = self.backend_config(backend_type);
         let backend = create_backend(&cylo_env, backend_config)
             .map_err(|e| RouterError::BackendError(e.to_string()))?;
 
         // Convert JsonValue args to ExecutionRequest
         let request = Self::json_args_to_execution_request(args)?;
 
+        // Shell code is screened by the profile's shell policy
+        if let Some(profile) = &self.sandbox_profile
+            && matches!(request.language.as_str(), "bash" | "sh")
+        {
+            profile
+                .shell
+                .check(&request.code)
+                .map_err(RouterError::Denied)?;
+        }
+
         // Execute via backend
         let result_handle = backend.execute_code(request);
         let result = result_handle
@@ -325,6 +585,14 @@ impl SweetMcpRouter {
         Ok(Self::execution_result_to_json(&result))
     }
 
+    /// Backend configuration for `name`, under the sandbox profile if any
+    fn backend_config(&self, name: &str) -> BackendConfig {
+        match &self.sandbox_profile {
+            Some(profile) => profile.backend_config(name),
+            None => BackendConfig::new(name),
+        }
+    }
+
     /// Convert `JsonValue` arguments to `ExecutionRequest`
     fn json_args_to_execution_request(args: JsonValue) -> Result<ExecutionRequest, RouterError> {
         // Convert sweet_mcp_type::JsonValue to serde_json::Value first
@@ -361,10 +629,15 @@ impl SweetMcpRouter {
     /// Convert `ExecutionResult` to JSON `Value`
     fn execution_result_to_json(result: &ExecutionResult) -> Value {
         serde_json::json!({
-            "success": result.exit_code == 0,
+            "success": result.is_success(),
             "exit_code": result.exit_code,
+            "signal": result.termination_signal(),
+            "failure": result.failure(),
             "stdout": result.stdout,
             "stderr": result.stderr,
+            "stdout_truncated": result.stdout_truncated,
+            "stderr_truncated": result.stderr_truncated,
+            "seccomp_violation": result.seccomp_violation,
             "duration_ms": result.duration.as_millis(),
             "resource_usage": {
                 "peak_memory": result.resource_usage.peak_memory,
@@ -432,6 +705,11 @@ impl SweetMcpRouter {
             tool_routes: Arc::clone(&self.tool_routes),
             plugin_configs: self.plugin_configs.clone(),
             cylo_config: self.cylo_config.clone(),
+            native_tools: self.native_tools.clone(),
+            remote_servers: self.remote_servers.clone(),
+            remote_clients: Arc::clone(&self.remote_clients),
+            guardrails: self.guardrails.clone(),
+            sandbox_profile: self.sandbox_profile.clone(),
         }
     }
 }
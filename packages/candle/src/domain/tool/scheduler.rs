@@ -0,0 +1,329 @@
+//! Tool call scheduling
+//!
+//! When a model emits several tool calls in one turn they are collected into a
+//! batch and handed to `ToolScheduler`, which:
+//! - runs independent calls concurrently, bounded by `max_concurrency`
+//! - starts a call only after every call it depends on has succeeded
+//! - returns outcomes in the order the model emitted the calls
+//!
+//! Dependencies are declared by the model through the reserved
+//! `DEPENDS_ON_KEY` argument, which is stripped before the tool is invoked.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde_json::Value;
+use sweet_mcp_type::JsonValue;
+
+use super::router::{RouterError, SweetMcpRouter};
+use crate::domain::agent::role::convert_serde_to_sweet_json;
+
+/// Reserved argument listing the ids of calls that must finish first
+pub const DEPENDS_ON_KEY: &str = "$depends_on";
+
+/// A tool call waiting to be scheduled
+#[derive(Debug, Clone)]
+pub struct ScheduledToolCall {
+    /// Call id, unique within the batch
+    pub id: String,
+    /// Tool to invoke
+    pub name: String,
+    /// Arguments passed to the tool
+    pub arguments: JsonValue,
+    /// Ids of calls in the same batch that must succeed before this one runs
+    pub depends_on: Vec<String>,
+}
+
+impl ScheduledToolCall {
+    /// Create a call with no dependencies
+    pub fn new(id: impl Into<String>, name: impl Into<String>, arguments: JsonValue) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            arguments,
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Require `id` to succeed before this call runs
+    #[must_use]
+    pub fn after(mut self, id: impl Into<String>) -> Self {
+        self.depends_on.push(id.into());
+        self
+    }
+
+    /// Build a call from the raw JSON input a model emitted
+    ///
+    /// A `DEPENDS_ON_KEY` entry (a string or an array of strings) is removed
+    /// from the arguments and recorded as dependencies.
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidArguments` if `input` is not valid JSON or
+    /// the dependency list is malformed
+    pub fn from_model_call(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        input: &str,
+    ) -> Result<Self, RouterError> {
+        let mut args: Value = serde_json::from_str(input)
+            .map_err(|e| RouterError::InvalidArguments(format!("Invalid JSON: {e}")))?;
+
+        let depends_on = match args
+            .as_object_mut()
+            .and_then(|map| map.remove(DEPENDS_ON_KEY))
+        {
+            None => Vec::new(),
+            Some(Value::String(id)) => vec![id],
+            Some(Value::Array(ids)) => ids
+                .into_iter()
+                .map(|id| match id {
+                    Value::String(id) => Ok(id),
+                    other => Err(RouterError::InvalidArguments(format!(
+                        "'{DEPENDS_ON_KEY}' entries must be call ids, got {other}"
+                    ))),
+                })
+                .collect::<Result<_, _>>()?,
+            Some(other) => {
+                return Err(RouterError::InvalidArguments(format!(
+                    "'{DEPENDS_ON_KEY}' must be a call id or a list of call ids, got {other}"
+                )));
+            }
+        };
+
+        Ok(Self {
+            id: id.into(),
+            name: name.into(),
+            arguments: convert_serde_to_sweet_json(args),
+            depends_on,
+        })
+    }
+}
+
+/// Result of one scheduled call
+#[derive(Debug)]
+pub struct ToolCallOutcome {
+    /// Id of the call
+    pub id: String,
+    /// Tool that was invoked
+    pub name: String,
+    /// Tool response, or why the call failed or was never run
+    pub result: Result<Value, RouterError>,
+    /// Time spent running the tool (zero if it never ran)
+    pub elapsed: Duration,
+}
+
+/// Dependency-aware executor for a batch of tool calls
+#[derive(Debug, Clone, Copy)]
+pub struct ToolScheduler {
+    max_concurrency: usize,
+}
+
+impl Default for ToolScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolScheduler {
+    /// Default number of calls allowed in flight at once
+    pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+    /// Create a scheduler with `DEFAULT_MAX_CONCURRENCY`
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_concurrency: Self::DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+
+    /// Limit the number of calls in flight at once (at least one)
+    #[must_use]
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Maximum number of calls in flight at once
+    #[must_use]
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Execute `calls` through the tool router
+    pub async fn execute_with_router(
+        &self,
+        router: &SweetMcpRouter,
+        calls: Vec<ScheduledToolCall>,
+    ) -> Vec<ToolCallOutcome> {
+        self.execute(calls, |name, args| async move {
+            router.call_tool(&name, args).await
+        })
+        .await
+    }
+
+    /// Execute raw `(id, name, input)` tool calls emitted by a model
+    ///
+    /// Calls without an id are numbered `call_1`, `call_2`, ... by position so
+    /// they can be referenced from `DEPENDS_ON_KEY`. Calls with unparseable
+    /// input report an error in their slot without blocking the rest.
+    pub async fn execute_model_calls(
+        &self,
+        router: &SweetMcpRouter,
+        calls: impl IntoIterator<Item = (String, String, String)>,
+    ) -> Vec<ToolCallOutcome> {
+        let mut outcomes: Vec<Option<ToolCallOutcome>> = Vec::new();
+        let mut scheduled = Vec::new();
+        for (position, (id, name, input)) in calls.into_iter().enumerate() {
+            let id = if id.is_empty() {
+                format!("call_{}", position + 1)
+            } else {
+                id
+            };
+            match ScheduledToolCall::from_model_call(id.clone(), name.clone(), &input) {
+                Ok(call) => {
+                    scheduled.push(call);
+                    outcomes.push(None);
+                }
+                Err(e) => outcomes.push(Some(ToolCallOutcome {
+                    id,
+                    name,
+                    result: Err(e),
+                    elapsed: Duration::ZERO,
+                })),
+            }
+        }
+
+        let mut executed = self
+            .execute_with_router(router, scheduled)
+            .await
+            .into_iter();
+        outcomes
+            .into_iter()
+            .filter_map(|outcome| outcome.or_else(|| executed.next()))
+            .collect()
+    }
+
+    /// Execute `calls` with `run`, returning one outcome per call in input order
+    ///
+    /// Calls whose dependencies fail, are unknown, or form a cycle are not run
+    /// and report an error instead.
+    pub async fn execute<F, Fut>(
+        &self,
+        calls: Vec<ScheduledToolCall>,
+        run: F,
+    ) -> Vec<ToolCallOutcome>
+    where
+        F: Fn(String, JsonValue) -> Fut,
+        Fut: Future<Output = Result<Value, RouterError>>,
+    {
+        let count = calls.len();
+        let mut index_by_id: HashMap<&str, usize> = HashMap::with_capacity(count);
+        for (index, call) in calls.iter().enumerate() {
+            index_by_id.entry(call.id.as_str()).or_insert(index);
+        }
+
+        let mut results: Vec<Option<Result<Value, RouterError>>> =
+            (0..count).map(|_| None).collect();
+        let mut elapsed = vec![Duration::ZERO; count];
+        let mut pending = vec![0_usize; count];
+        let mut dependencies: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); count];
+
+        for (index, call) in calls.iter().enumerate() {
+            for dependency in &call.depends_on {
+                match index_by_id.get(dependency.as_str()) {
+                    Some(&other) if other != index => {
+                        pending[index] += 1;
+                        dependencies[index].push(other);
+                        dependents[other].push(index);
+                    }
+                    Some(_) => {
+                        results[index] = Some(Err(RouterError::InvalidArguments(format!(
+                            "Tool call '{}' depends on itself",
+                            call.id
+                        ))));
+                    }
+                    None => {
+                        results[index] = Some(Err(RouterError::InvalidArguments(format!(
+                            "Tool call '{}' depends on unknown call '{dependency}'",
+                            call.id
+                        ))));
+                    }
+                }
+            }
+        }
+
+        // Settled calls release their dependents; ready calls wait for a slot
+        let mut settled: VecDeque<usize> = (0..count)
+            .filter(|&index| results[index].is_some())
+            .collect();
+        let mut ready: VecDeque<usize> = (0..count)
+            .filter(|&index| pending[index] == 0 && results[index].is_none())
+            .collect();
+        let mut in_flight = FuturesUnordered::new();
+
+        loop {
+            while let Some(index) = settled.pop_front() {
+                for &dependent in &dependents[index] {
+                    pending[dependent] -= 1;
+                    if pending[dependent] > 0 || results[dependent].is_some() {
+                        continue;
+                    }
+                    let failed = dependencies[dependent]
+                        .iter()
+                        .find(|&&dependency| matches!(results[dependency], Some(Err(_))));
+                    if let Some(&failed) = failed {
+                        results[dependent] = Some(Err(RouterError::ExecutionFailed(format!(
+                            "Skipped because tool call '{}' failed",
+                            calls[failed].id
+                        ))));
+                        settled.push_back(dependent);
+                    } else {
+                        ready.push_back(dependent);
+                    }
+                }
+            }
+
+            while in_flight.len() < self.max_concurrency {
+                let Some(index) = ready.pop_front() else {
+                    break;
+                };
+                let call = &calls[index];
+                let future = run(call.name.clone(), call.arguments.clone());
+                in_flight.push(async move {
+                    let started = Instant::now();
+                    let result = future.await;
+                    (index, result, started.elapsed())
+                });
+            }
+
+            match in_flight.next().await {
+                Some((index, result, duration)) => {
+                    results[index] = Some(result);
+                    elapsed[index] = duration;
+                    settled.push_back(index);
+                }
+                None => break,
+            }
+        }
+
+        calls
+            .into_iter()
+            .zip(results)
+            .zip(elapsed)
+            .map(|((call, result), elapsed)| ToolCallOutcome {
+                result: result.unwrap_or_else(|| {
+                    Err(RouterError::InvalidArguments(format!(
+                        "Tool call '{}' is blocked by a dependency cycle",
+                        call.id
+                    )))
+                }),
+                id: call.id,
+                name: call.name,
+                elapsed,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,107 @@
+//! Audio file decoding and encoding
+//!
+//! Recordings arrive as WAV, MP3 or Ogg files and decode to mono `f32`
+//! samples at the file's own rate; speech models resample as needed.
+//! Synthesized speech is written as 16-bit PCM WAV, which every player reads.
+
+use std::io::Cursor;
+
+use sweetmcp_voice_tools::{VoiceError, VoiceResult};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::domain::voice::pcm;
+
+/// Media type of files written by [`encode_wav`]
+pub const WAV_MIME_TYPE: &str = "audio/wav";
+
+/// Decode an audio file to mono samples
+///
+/// The container is detected from the contents; `extension` (such as
+/// `"mp3"`) only speeds up detection. Returns the samples and their rate.
+pub fn decode(bytes: Vec<u8>, extension: Option<&str>) -> VoiceResult<(Vec<f32>, u32)> {
+    let invalid = |e: SymphoniaError| VoiceError::InvalidAudio(e.to_string());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
+    }
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(invalid)?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| VoiceError::InvalidAudio("no audio track".to_string()))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| VoiceError::InvalidAudio("unknown sample rate".to_string()))?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(invalid)?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(e) => return Err(invalid(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet costs a few milliseconds of audio, not the file
+            Err(SymphoniaError::DecodeError(e)) => {
+                log::debug!("Skipping undecodable audio packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(invalid(e)),
+        };
+        let spec = *decoded.spec();
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend(pcm::downmix(buffer.samples(), spec.channels.count()));
+    }
+    Ok((samples, sample_rate))
+}
+
+/// Encode mono samples as a 16-bit PCM WAV file
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> VoiceResult<Vec<u8>> {
+    let failed = |e: hound::Error| VoiceError::SynthesisFailed(e.to_string());
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut file = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut file, spec).map_err(failed)?;
+    for &sample in samples {
+        let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        writer.write_sample(sample).map_err(failed)?;
+    }
+    writer.finalize().map_err(failed)?;
+    Ok(file.into_inner())
+}
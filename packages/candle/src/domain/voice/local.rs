@@ -0,0 +1,705 @@
+//! Local [`VoiceService`] running speech models on this machine
+//!
+//! [`LocalVoiceService`] records from a microphone and plays through the
+//! default speaker with cpal, transcribes with Whisper and speaks with
+//! Parler-TTS, both through the candle engine. Models download on first use
+//! and run on the best available device unless one is given, so the `speak`
+//! and `listen` tools work without any external program or service.
+//! Audio files can be transcribed and speech written to WAV files as well,
+//! for hosts without sound hardware.
+//!
+//! Streaming listens run the microphone through a
+//! [`VoiceActivityDetector`], re-transcribe the speech heard so far for
+//! partial results and stop at the end of the utterance.
+//!
+//! When diarization or word timestamps are requested, recordings are split
+//! at pauses and transcribed segment by segment; see [`segmentation`]. The
+//! Whisper checkpoint only knows English, so language detection always
+//! reports `en` without a confidence.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use base64::{Engine as _, engine::general_purpose};
+use candle_core::Device;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, Stream, StreamConfig};
+use parking_lot::Mutex;
+use sweetmcp_voice_tools::{
+    ListenEvent, ListenParams, ListenResult, ListenStreamParams, SpeakParams, SpeechPart,
+    SynthesisResult, SynthesizeParams, TranscribeFileParams, TranscriptOptions, TranscriptSegment,
+    VadEvent, VoiceActivityDetector, VoiceConfig, VoiceError, VoiceResult, VoiceService,
+};
+use tokio::sync::mpsc;
+
+use crate::capability::speech_to_text::CandleWhisperModel;
+use crate::capability::text_to_speech::{CandleParlerTtsModel, SynthesizedSpeech};
+use crate::capability::traits::{SpeechToTextCapable, TextToSpeechCapable};
+use crate::domain::voice::{audio_file, pcm, segmentation};
+
+/// Microphone name that selects the system default input
+pub const DEFAULT_MICROPHONE: &str = "default";
+
+/// Longest recording accepted by [`VoiceService::listen`], in seconds
+pub const MAX_LISTEN_SECONDS: u32 = 300;
+
+/// Recordings quieter than this RMS level are treated as silence
+///
+/// Whisper tends to invent short phrases for silent input, so silence is
+/// never sent to the model.
+pub const SILENCE_RMS: f32 = 0.01;
+
+/// Audio kept from before voice activity is detected, in milliseconds
+const PRE_ROLL_MS: usize = 300;
+
+/// How often blocking audio loops check for completion or cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Time left for the output device to drain its buffer after the last sample
+const PLAYBACK_DRAIN: Duration = Duration::from_millis(150);
+
+/// Voice service backed by local Whisper and Parler-TTS models
+///
+/// Cloning shares the loaded models.
+#[derive(Debug, Clone)]
+pub struct LocalVoiceService {
+    speech_to_text: CandleWhisperModel,
+    text_to_speech: CandleParlerTtsModel,
+}
+
+impl Default for LocalVoiceService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalVoiceService {
+    /// Create a service running both models on the best available device
+    pub fn new() -> Self {
+        Self {
+            speech_to_text: CandleWhisperModel::new(),
+            text_to_speech: CandleParlerTtsModel::new(),
+        }
+    }
+
+    /// Create a service running both models on `device`
+    pub fn with_device(device: Device) -> Self {
+        Self {
+            speech_to_text: CandleWhisperModel::with_device(device.clone()),
+            text_to_speech: CandleParlerTtsModel::with_device(device),
+        }
+    }
+
+    /// Synthesize speech parts one after another after checking the voice
+    ///
+    /// Rate and pitch steer Parler's speaker description, volume scales the
+    /// samples and breaks become silence.
+    async fn synthesize_speech(
+        &self,
+        parts: Vec<SpeechPart>,
+        voice: Option<&str>,
+    ) -> VoiceResult<SynthesizedSpeech> {
+        if let Some(voice) = voice
+            && !self.text_to_speech.voices().iter().any(|v| v == voice)
+        {
+            return Err(VoiceError::InvalidVoiceId(voice.to_string()));
+        }
+
+        enum Piece {
+            Speech(SynthesizedSpeech),
+            Silence(Duration),
+        }
+
+        let mut pieces = Vec::with_capacity(parts.len());
+        let mut sample_rate = None;
+        for part in parts {
+            match part {
+                SpeechPart::Speech { text, prosody } => {
+                    let mut speech = self
+                        .text_to_speech
+                        .synthesize_with_pitch(&text, voice, prosody.rate, prosody.pitch_semitones)
+                        .await
+                        .map_err(|e| VoiceError::SynthesisFailed(e.to_string()))?;
+                    if prosody.volume != 1.0 {
+                        for sample in &mut speech.pcm {
+                            *sample = (*sample * prosody.volume).clamp(-1.0, 1.0);
+                        }
+                    }
+                    sample_rate.get_or_insert(speech.sample_rate);
+                    pieces.push(Piece::Speech(speech));
+                }
+                SpeechPart::Break(pause) => pieces.push(Piece::Silence(pause)),
+            }
+        }
+
+        // Speech sets the rate of the output; silence alone needs the
+        // model's rate
+        let sample_rate = match sample_rate {
+            Some(rate) => rate,
+            None => {
+                self.text_to_speech
+                    .synthesize("", voice, 1.0)
+                    .await
+                    .map_err(|e| VoiceError::SynthesisFailed(e.to_string()))?
+                    .sample_rate
+            }
+        };
+        let mut pcm = Vec::new();
+        for piece in pieces {
+            match piece {
+                Piece::Speech(speech) => {
+                    pcm.extend(pcm::resample(&speech.pcm, speech.sample_rate, sample_rate))
+                }
+                Piece::Silence(pause) => {
+                    let silence = (pause.as_secs_f64() * f64::from(sample_rate)).round();
+                    pcm.resize(pcm.len() + silence as usize, 0.0);
+                }
+            }
+        }
+        Ok(SynthesizedSpeech { pcm, sample_rate })
+    }
+
+    /// Transcribe a recording and check it for the wake word
+    async fn transcribe(
+        &self,
+        samples: Vec<f32>,
+        sample_rate: u32,
+        wake_word: Option<&str>,
+        options: TranscriptOptions,
+    ) -> VoiceResult<ListenResult> {
+        if options.wants_segments() {
+            return self
+                .transcribe_segments(samples, sample_rate, wake_word, options)
+                .await;
+        }
+
+        let transcript = self
+            .speech_to_text
+            .transcribe(samples, sample_rate)
+            .await
+            .map_err(|e| VoiceError::TranscriptionFailed(e.to_string()))?;
+        Ok(ListenResult {
+            wake_word_detected: wake_word_detected(wake_word, &transcript.text),
+            text: transcript.text,
+            confidence: transcript.confidence,
+            language: transcript.language,
+            language_confidence: None,
+            segments: None,
+            words: None,
+        })
+    }
+
+    /// Transcribe a recording one segment at a time, labelling speakers and
+    /// timing words as requested
+    async fn transcribe_segments(
+        &self,
+        samples: Vec<f32>,
+        sample_rate: u32,
+        wake_word: Option<&str>,
+        options: TranscriptOptions,
+    ) -> VoiceResult<ListenResult> {
+        let sensitivity = VoiceConfig::default().vad_sensitivity;
+        let (samples, ranges, speakers) = tokio::task::spawn_blocking(move || {
+            let mut ranges = segmentation::speech_segments(&samples, sample_rate, sensitivity);
+            if ranges.is_empty() {
+                ranges.push(0..samples.len());
+            }
+            let speakers = if options.diarize {
+                let features: Vec<_> = ranges
+                    .iter()
+                    .map(|range| segmentation::voice_features(&samples[range.clone()], sample_rate))
+                    .collect();
+                segmentation::label_speakers(&features)
+            } else {
+                Vec::new()
+            };
+            (samples, ranges, speakers)
+        })
+        .await
+        .map_err(|e| VoiceError::Other(e.into()))?;
+
+        let rate = sample_rate as f32;
+        let mut segments = Vec::with_capacity(ranges.len());
+        let mut words = Vec::new();
+        let mut language = None;
+        // Confidence of the whole transcript, weighted by segment length
+        let (mut weighted_confidence, mut confidence_weight) = (0.0f32, 0.0f32);
+        for (index, range) in ranges.into_iter().enumerate() {
+            let start_seconds = range.start as f32 / rate;
+            let end_seconds = range.end as f32 / rate;
+            let transcript = self
+                .speech_to_text
+                .transcribe(samples[range].to_vec(), sample_rate)
+                .await
+                .map_err(|e| VoiceError::TranscriptionFailed(e.to_string()))?;
+            language = language.or(transcript.language);
+            if transcript.text.is_empty() {
+                continue;
+            }
+
+            if let Some(confidence) = transcript.confidence {
+                let weight = transcript.text.len() as f32;
+                weighted_confidence += confidence * weight;
+                confidence_weight += weight;
+            }
+            if options.word_timestamps {
+                words.extend(segmentation::time_words(
+                    &transcript.text,
+                    start_seconds,
+                    end_seconds,
+                    transcript.confidence,
+                ));
+            }
+            segments.push(TranscriptSegment {
+                start_seconds,
+                end_seconds,
+                text: transcript.text,
+                speaker: speakers
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .map(segmentation::speaker_label),
+            });
+        }
+
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(ListenResult {
+            wake_word_detected: wake_word_detected(wake_word, &text),
+            text,
+            confidence: (confidence_weight > 0.0).then(|| weighted_confidence / confidence_weight),
+            language,
+            language_confidence: None,
+            segments: Some(segments),
+            words: options.word_timestamps.then_some(words),
+        })
+    }
+}
+
+/// Result of a recording with nothing said
+fn silent_result(wake_word: Option<&str>, options: TranscriptOptions) -> ListenResult {
+    ListenResult {
+        text: String::new(),
+        wake_word_detected: wake_word_detected(wake_word, ""),
+        confidence: None,
+        language: None,
+        language_confidence: None,
+        segments: options.wants_segments().then(Vec::new),
+        words: options.word_timestamps.then(Vec::new),
+    }
+}
+
+fn wake_word_detected(wake_word: Option<&str>, text: &str) -> Option<bool> {
+    wake_word.map(|word| text.to_lowercase().contains(&word.to_lowercase()))
+}
+
+impl VoiceService for LocalVoiceService {
+    async fn speak(&self, params: SpeakParams) -> VoiceResult<()> {
+        let speech = self
+            .synthesize_speech(params.speech_parts()?, params.voice_id.as_deref())
+            .await?;
+        if speech.pcm.is_empty() {
+            return Ok(());
+        }
+
+        let stop = StopOnDrop::new();
+        let flag = stop.flag();
+        tokio::task::spawn_blocking(move || play(&speech.pcm, speech.sample_rate, &flag))
+            .await
+            .map_err(|e| VoiceError::Other(e.into()))?
+    }
+
+    async fn listen(&self, params: ListenParams) -> VoiceResult<ListenResult> {
+        if params.duration_seconds == 0 || params.duration_seconds > MAX_LISTEN_SECONDS {
+            return Err(VoiceError::InvalidDuration(params.duration_seconds));
+        }
+
+        let stop = StopOnDrop::new();
+        let flag = stop.flag();
+        let microphone = params.microphone_id.clone();
+        let duration = Duration::from_secs(u64::from(params.duration_seconds));
+        let (samples, sample_rate) = tokio::task::spawn_blocking(move || {
+            let mut samples = Vec::new();
+            let sample_rate = capture(&microphone, duration, &flag, |_, chunk| {
+                samples.extend(chunk);
+            })?;
+            Ok::<_, VoiceError>((samples, sample_rate))
+        })
+        .await
+        .map_err(|e| VoiceError::Other(e.into()))??;
+
+        if pcm::rms(&samples) < SILENCE_RMS {
+            return Ok(silent_result(params.wake_word.as_deref(), params.options));
+        }
+        self.transcribe(
+            samples,
+            sample_rate,
+            params.wake_word.as_deref(),
+            params.options,
+        )
+        .await
+    }
+
+    async fn listen_stream(
+        &self,
+        params: ListenStreamParams,
+        events: mpsc::UnboundedSender<ListenEvent>,
+    ) -> VoiceResult<ListenResult> {
+        if params.max_duration_seconds == 0 || params.max_duration_seconds > MAX_LISTEN_SECONDS {
+            return Err(VoiceError::InvalidDuration(params.max_duration_seconds));
+        }
+        let silence_timeout = params.silence_timeout();
+        let partial_interval = params.partial_interval();
+        let sensitivity = params
+            .vad_sensitivity
+            .unwrap_or_else(|| VoiceConfig::default().vad_sensitivity);
+
+        let stop = StopOnDrop::new();
+        let flag = stop.flag();
+        let microphone = params.microphone_id.clone();
+        let duration = Duration::from_secs(u64::from(params.max_duration_seconds));
+        let (chunks_tx, mut chunks) = mpsc::unbounded_channel();
+        let recording = tokio::task::spawn_blocking(move || {
+            capture(&microphone, duration, &flag, |sample_rate, chunk| {
+                let _ = chunks_tx.send((sample_rate, chunk));
+            })
+        });
+
+        let mut vad = None;
+        let mut recent = Vec::new();
+        let mut speech: Option<Vec<f32>> = None;
+        let mut sample_rate = 0;
+        let mut last_partial = Instant::now();
+        let mut partial = String::new();
+        while let Some((rate, chunk)) = chunks.recv().await {
+            sample_rate = rate;
+            let vad = vad.get_or_insert_with(|| {
+                VoiceActivityDetector::new(rate, sensitivity, silence_timeout)
+            });
+
+            // Audio just before the detector fires holds the start of the
+            // first word
+            recent.extend_from_slice(&chunk);
+            let keep = rate as usize * PRE_ROLL_MS / 1000 + chunk.len();
+            if recent.len() > keep {
+                recent.drain(..recent.len() - keep);
+            }
+
+            let mut started = false;
+            let mut ended = false;
+            for event in vad.push(&chunk) {
+                match event {
+                    VadEvent::SpeechStarted if speech.is_none() => {
+                        speech = Some(recent.clone());
+                        started = true;
+                        last_partial = Instant::now();
+                        let _ = events.send(ListenEvent::SpeechStarted);
+                    }
+                    VadEvent::SpeechStarted => {}
+                    VadEvent::SpeechEnded => ended = true,
+                }
+            }
+            let Some(speech) = speech.as_mut() else {
+                continue;
+            };
+            if !started {
+                speech.extend_from_slice(&chunk);
+            }
+            if ended {
+                let _ = events.send(ListenEvent::SpeechEnded);
+                break;
+            }
+
+            if last_partial.elapsed() >= partial_interval {
+                let transcript = self
+                    .speech_to_text
+                    .transcribe(speech.clone(), rate)
+                    .await
+                    .map_err(|e| VoiceError::TranscriptionFailed(e.to_string()))?;
+                if !transcript.text.is_empty() && transcript.text != partial {
+                    partial = transcript.text;
+                    let _ = events.send(ListenEvent::Partial {
+                        text: partial.clone(),
+                    });
+                }
+                last_partial = Instant::now();
+            }
+        }
+
+        drop(stop);
+        recording.await.map_err(|e| VoiceError::Other(e.into()))??;
+
+        match speech {
+            Some(speech) => {
+                self.transcribe(
+                    speech,
+                    sample_rate,
+                    params.wake_word.as_deref(),
+                    params.options,
+                )
+                .await
+            }
+            None => Ok(silent_result(params.wake_word.as_deref(), params.options)),
+        }
+    }
+
+    async fn transcribe_file(&self, params: TranscribeFileParams) -> VoiceResult<ListenResult> {
+        let (bytes, extension) = match (&params.path, &params.audio_base64) {
+            (Some(path), None) => {
+                let extension = Path::new(path)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(str::to_lowercase);
+                (tokio::fs::read(path).await?, extension)
+            }
+            (None, Some(encoded)) => {
+                let bytes = general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|e| VoiceError::InvalidAudio(format!("invalid base64: {}", e)))?;
+                (bytes, None)
+            }
+            _ => {
+                return Err(VoiceError::InvalidAudio(
+                    "exactly one of path and audio_base64 is required".to_string(),
+                ));
+            }
+        };
+
+        let (samples, sample_rate) =
+            tokio::task::spawn_blocking(move || audio_file::decode(bytes, extension.as_deref()))
+                .await
+                .map_err(|e| VoiceError::Other(e.into()))??;
+
+        if pcm::rms(&samples) < SILENCE_RMS {
+            return Ok(silent_result(params.wake_word.as_deref(), params.options));
+        }
+        self.transcribe(
+            samples,
+            sample_rate,
+            params.wake_word.as_deref(),
+            params.options,
+        )
+        .await
+    }
+
+    async fn synthesize(&self, params: SynthesizeParams) -> VoiceResult<SynthesisResult> {
+        let speech = self
+            .synthesize_speech(params.speech_parts()?, params.voice_id.as_deref())
+            .await?;
+        let wav = audio_file::encode_wav(&speech.pcm, speech.sample_rate)?;
+
+        let (path, audio_base64) = match params.output_path {
+            Some(path) => {
+                tokio::fs::write(&path, &wav).await?;
+                (Some(path), None)
+            }
+            None => (None, Some(general_purpose::STANDARD.encode(&wav))),
+        };
+        Ok(SynthesisResult {
+            path,
+            audio_base64,
+            mime_type: audio_file::WAV_MIME_TYPE.to_string(),
+            sample_rate: speech.sample_rate,
+            duration_seconds: speech.duration().as_secs_f32(),
+        })
+    }
+
+    async fn list_voices(&self) -> VoiceResult<Vec<String>> {
+        Ok(self.text_to_speech.voices())
+    }
+
+    async fn list_microphones(&self) -> VoiceResult<Vec<String>> {
+        tokio::task::spawn_blocking(|| {
+            let host = cpal::default_host();
+            let devices = host
+                .input_devices()
+                .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?;
+
+            let mut names = vec![DEFAULT_MICROPHONE.to_string()];
+            names.extend(devices.filter_map(|device| device.name().ok()));
+            Ok(names)
+        })
+        .await
+        .map_err(|e| VoiceError::Other(e.into()))?
+    }
+}
+
+/// Raises a flag when dropped, so blocking audio loops stop with the
+/// future that started them
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl StopOnDrop {
+    fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    fn flag(&self) -> Arc<AtomicBool> {
+        self.0.clone()
+    }
+}
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Find an input device by name, or the default one
+fn input_device(host: &cpal::Host, microphone: &str) -> VoiceResult<cpal::Device> {
+    let device = if microphone == DEFAULT_MICROPHONE {
+        host.default_input_device()
+    } else {
+        host.input_devices()
+            .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?
+            .find(|device| device.name().is_ok_and(|name| name == microphone))
+    };
+    device.ok_or_else(|| VoiceError::InvalidMicrophone(microphone.to_string()))
+}
+
+/// Capture mono audio for `duration` or until `stop` is raised
+///
+/// Samples are handed to `sink` with their rate in batches as they arrive.
+/// Returns the sample rate.
+fn capture(
+    microphone: &str,
+    duration: Duration,
+    stop: &AtomicBool,
+    mut sink: impl FnMut(u32, Vec<f32>),
+) -> VoiceResult<u32> {
+    let host = cpal::default_host();
+    let device = input_device(&host, microphone)?;
+    let supported = device
+        .default_input_config()
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+    let channels = usize::from(config.channels);
+    let sample_rate = config.sample_rate.0;
+
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let stream = match sample_format {
+        SampleFormat::F32 => input_stream::<f32>(&device, &config, captured.clone()),
+        SampleFormat::I16 => input_stream::<i16>(&device, &config, captured.clone()),
+        SampleFormat::U16 => input_stream::<u16>(&device, &config, captured.clone()),
+        other => Err(VoiceError::ServiceUnavailable(format!(
+            "unsupported microphone sample format {:?}",
+            other
+        ))),
+    }?;
+    stream
+        .play()
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?;
+
+    let deadline = Instant::now() + duration;
+    loop {
+        let done = Instant::now() >= deadline || stop.load(Ordering::Relaxed);
+        if !done {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        let interleaved = std::mem::take(&mut *captured.lock());
+        if !interleaved.is_empty() {
+            sink(sample_rate, pcm::downmix(&interleaved, channels));
+        }
+        if done {
+            return Ok(sample_rate);
+        }
+    }
+}
+
+fn input_stream<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    captured: Arc<Mutex<Vec<f32>>>,
+) -> VoiceResult<Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                captured
+                    .lock()
+                    .extend(data.iter().map(|&sample| f32::from_sample(sample)));
+            },
+            |e| log::warn!("Microphone stream error: {}", e),
+            None,
+        )
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))
+}
+
+/// Play mono samples on the default output device until they finish or
+/// `stop` is raised
+fn play(samples: &[f32], sample_rate: u32, stop: &AtomicBool) -> VoiceResult<()> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| VoiceError::ServiceUnavailable("no audio output device".to_string()))?;
+    let supported = device
+        .default_output_config()
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+
+    let samples: Arc<[f32]> = pcm::resample(samples, sample_rate, config.sample_rate.0).into();
+    let position = Arc::new(AtomicUsize::new(0));
+    let stream = match sample_format {
+        SampleFormat::F32 => {
+            output_stream::<f32>(&device, &config, samples.clone(), position.clone())
+        }
+        SampleFormat::I16 => {
+            output_stream::<i16>(&device, &config, samples.clone(), position.clone())
+        }
+        SampleFormat::U16 => {
+            output_stream::<u16>(&device, &config, samples.clone(), position.clone())
+        }
+        other => Err(VoiceError::ServiceUnavailable(format!(
+            "unsupported speaker sample format {:?}",
+            other
+        ))),
+    }?;
+    stream
+        .play()
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))?;
+
+    while position.load(Ordering::Relaxed) < samples.len() {
+        if stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    std::thread::sleep(PLAYBACK_DRAIN);
+    Ok(())
+}
+
+fn output_stream<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    samples: Arc<[f32]>,
+    position: Arc<AtomicUsize>,
+) -> VoiceResult<Stream>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let channels = usize::from(config.channels);
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let index = position.fetch_add(1, Ordering::Relaxed);
+                    let value = T::from_sample(samples.get(index).copied().unwrap_or(0.0));
+                    frame.fill(value);
+                }
+            },
+            |e| log::warn!("Speaker stream error: {}", e),
+            None,
+        )
+        .map_err(|e| VoiceError::ServiceUnavailable(e.to_string()))
+}
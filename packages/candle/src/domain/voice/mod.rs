@@ -1,16 +1,23 @@
 //! Voice processing module
 //!
 //! This module provides functionality for handling voice-related operations,
-//! including audio processing and transcription services.
+//! including audio processing and transcription services, and
+//! [`LocalVoiceService`], which backs the `speak` and `listen` tools with
+//! local speech models.
 
 use std::future::Future;
 use std::pin::Pin;
 
 pub mod audio;
+pub mod audio_file;
+pub mod local;
+pub mod pcm;
+pub mod segmentation;
 pub mod transcription;
 
 // Re-export types for public API
 pub use audio::{Audio, AudioMediaType, ContentFormat as AudioContentFormat};
+pub use local::LocalVoiceService;
 use serde::{Deserialize, Serialize};
 pub use transcription::{Transcription, TranscriptionRequest, TranscriptionResponse};
 
@@ -0,0 +1,64 @@
+//! PCM sample helpers
+//!
+//! Microphones and speakers run at whatever rate and channel count the
+//! hardware prefers, while speech models want mono audio at a fixed rate.
+//! These helpers convert between the two on plain `f32` samples in `[-1, 1]`.
+
+/// Average interleaved frames of `channels` samples down to mono
+#[must_use]
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resample mono audio from `from_rate` to `to_rate` by linear interpolation
+///
+/// Good enough for speech, whose energy sits well below either Nyquist
+/// frequency; not meant for music.
+#[must_use]
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() || from_rate == 0 || to_rate == 0 {
+        return samples.to_vec();
+    }
+
+    let step = f64::from(from_rate) / f64::from(to_rate);
+    let len = (samples.len() as f64 / step).round() as usize;
+    let last = samples.len() - 1;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = (position as usize).min(last);
+            let next = (index + 1).min(last);
+            let fraction = (position - index as f64) as f32;
+            samples[index] + (samples[next] - samples[index]) * fraction
+        })
+        .collect()
+}
+
+/// Root mean square level of the samples
+#[must_use]
+pub fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let energy: f64 = samples.iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+    (energy / samples.len() as f64).sqrt() as f32
+}
+
+/// Scale samples so the loudest one peaks at `peak`
+///
+/// Silent input is left untouched.
+pub fn normalize_peak(samples: &mut [f32], peak: f32) {
+    let max = samples.iter().fold(0.0f32, |acc, &s| acc.max(s.abs()));
+    if max > f32::EPSILON {
+        let gain = peak / max;
+        for sample in samples {
+            *sample *= gain;
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Speech segments, speaker labels and word timing for transcripts
+//!
+//! Whisper transcribes a recording as one block of text. For meeting
+//! transcripts the recording is first split at pauses with a
+//! [`VoiceActivityDetector`], so each segment can be transcribed and timed on
+//! its own. Segments are grouped by voice using their pitch and spectral
+//! brightness, which tells clearly different voices apart but not similar
+//! ones. Words are timed by spreading each segment's duration over its
+//! words by length.
+
+use std::ops::Range;
+use std::time::Duration;
+
+use sweetmcp_voice_tools::{VadEvent, VoiceActivityDetector, WordTimestamp};
+
+use crate::domain::voice::pcm;
+
+/// Pause that ends a segment, in milliseconds
+pub const SEGMENT_PAUSE_MS: usize = 500;
+
+/// Audio kept around each segment so word edges are not cut, in milliseconds
+const SEGMENT_PADDING_MS: usize = 200;
+
+/// Rate audio is analysed at for voice features
+const ANALYSIS_RATE: u32 = 8_000;
+
+/// Pitch analysis window and hop, in milliseconds
+const PITCH_WINDOW_MS: usize = 40;
+const PITCH_HOP_MS: usize = 100;
+
+/// Range of speaking pitch searched, in Hz
+const MIN_PITCH_HZ: usize = 60;
+const MAX_PITCH_HZ: usize = 400;
+
+/// Windows quieter than this RMS level are not analysed for pitch
+const MIN_PITCH_RMS: f32 = 0.01;
+
+/// Normalized autocorrelation a window needs to count as voiced
+const VOICING_THRESHOLD: f32 = 0.5;
+
+/// Share of the best correlation the first peak needs to be taken as the period
+const OCTAVE_TOLERANCE: f32 = 0.9;
+
+/// Differences in log pitch and brightness counted as one unit of distance
+const PITCH_SCALE: f32 = 0.25;
+const BRIGHTNESS_SCALE: f32 = 0.3;
+
+/// Segments closer than this to a speaker's average voice belong to them
+const SPEAKER_DISTANCE: f32 = 1.0;
+
+/// Split a recording into stretches of speech separated by pauses
+///
+/// Returns sample ranges in order, padded slightly and never overlapping.
+/// `sensitivity` is passed to the [`VoiceActivityDetector`].
+pub fn speech_segments(samples: &[f32], sample_rate: u32, sensitivity: f32) -> Vec<Range<usize>> {
+    let frame_len = (sample_rate as usize / 50).max(1);
+    let pause = sample_rate as usize * SEGMENT_PAUSE_MS / 1000;
+    let padding = sample_rate as usize * SEGMENT_PADDING_MS / 1000;
+    let mut vad = VoiceActivityDetector::new(
+        sample_rate,
+        sensitivity,
+        Duration::from_millis(SEGMENT_PAUSE_MS as u64),
+    );
+
+    let mut segments: Vec<Range<usize>> = Vec::new();
+    let mut start = None;
+    for (index, frame) in samples.chunks_exact(frame_len).enumerate() {
+        let position = (index + 1) * frame_len;
+        for event in vad.push(frame) {
+            match event {
+                VadEvent::SpeechStarted => {
+                    let previous_end = segments.last().map_or(0, |segment| segment.end);
+                    start = Some(position.saturating_sub(padding).max(previous_end));
+                }
+                VadEvent::SpeechEnded => {
+                    if let Some(start) = start.take() {
+                        let end = (position.saturating_sub(pause) + padding).min(samples.len());
+                        segments.push(start..end.max(start));
+                    }
+                }
+            }
+        }
+    }
+    if let Some(start) = start {
+        segments.push(start..samples.len());
+    }
+    segments.retain(|segment| !segment.is_empty());
+    segments
+}
+
+/// Acoustic summary of one voice
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoiceFeatures {
+    /// Median speaking pitch in Hz
+    pub pitch_hz: f32,
+    /// Energy of the signal's changes relative to its energy, from 0.0 for
+    /// dull to 4.0 for bright voices
+    pub brightness: f32,
+}
+
+impl VoiceFeatures {
+    /// Distance to another voice; below 1.0 is likely the same speaker
+    pub fn distance(&self, other: &VoiceFeatures) -> f32 {
+        let pitch = (self.pitch_hz / other.pitch_hz).ln() / PITCH_SCALE;
+        let brightness = (self.brightness - other.brightness) / BRIGHTNESS_SCALE;
+        pitch.hypot(brightness)
+    }
+}
+
+/// Measure the voice in a stretch of speech
+///
+/// Returns `None` when no voiced sound is found, such as for whispers and
+/// noise.
+pub fn voice_features(samples: &[f32], sample_rate: u32) -> Option<VoiceFeatures> {
+    let samples = pcm::resample(samples, sample_rate, ANALYSIS_RATE);
+    let rate = ANALYSIS_RATE as usize;
+    let window = rate * PITCH_WINDOW_MS / 1000;
+    let hop = rate * PITCH_HOP_MS / 1000;
+    let lags = rate / MAX_PITCH_HZ..rate / MIN_PITCH_HZ;
+
+    let mut pitches = Vec::new();
+    let mut start = 0;
+    while start + window + lags.end <= samples.len() {
+        let frame = &samples[start..start + window + lags.end];
+        start += hop;
+        if pcm::rms(&frame[..window]) < MIN_PITCH_RMS {
+            continue;
+        }
+        let energy: f32 = frame[..window].iter().map(|s| s * s).sum();
+        let correlations: Vec<f32> = lags
+            .clone()
+            .map(|lag| {
+                let shifted = &frame[lag..lag + window];
+                let product: f32 = frame[..window]
+                    .iter()
+                    .zip(shifted)
+                    .map(|(a, b)| a * b)
+                    .sum();
+                let shifted_energy: f32 = shifted.iter().map(|s| s * s).sum();
+                product / (energy * shifted_energy).sqrt().max(f32::EPSILON)
+            })
+            .collect();
+        let best = correlations.iter().copied().fold(f32::MIN, f32::max);
+        if best < VOICING_THRESHOLD {
+            continue;
+        }
+
+        // Multiples of the period correlate as well as the period itself, so
+        // take the first peak close to the best one
+        let Some(mut index) = correlations
+            .iter()
+            .position(|&correlation| correlation >= best * OCTAVE_TOLERANCE)
+        else {
+            continue;
+        };
+        while index + 1 < correlations.len() && correlations[index + 1] > correlations[index] {
+            index += 1;
+        }
+        pitches.push(rate as f32 / (lags.start + index) as f32);
+    }
+    if pitches.is_empty() {
+        return None;
+    }
+    pitches.sort_by(f32::total_cmp);
+    let pitch_hz = pitches[pitches.len() / 2];
+
+    let energy: f32 = samples.iter().map(|s| s * s).sum();
+    let changes: f32 = samples.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+    Some(VoiceFeatures {
+        pitch_hz,
+        brightness: changes / energy.max(f32::EPSILON),
+    })
+}
+
+/// Group segments by voice
+///
+/// Returns a speaker index for every segment, numbered in order of first
+/// appearance, or `None` for segments without a measurable voice. Each
+/// segment joins the speaker whose average voice is nearest, or starts a
+/// new speaker when none is within [`VoiceFeatures::distance`] 1.0.
+pub fn label_speakers(features: &[Option<VoiceFeatures>]) -> Vec<Option<usize>> {
+    // Running sums of log pitch and brightness, and the segment count
+    let mut speakers: Vec<(f32, f32, f32)> = Vec::new();
+    features
+        .iter()
+        .map(|features| {
+            let features = features.as_ref()?;
+            let nearest = speakers
+                .iter()
+                .enumerate()
+                .map(|(index, &(log_pitch, brightness, count))| {
+                    let average = VoiceFeatures {
+                        pitch_hz: (log_pitch / count).exp(),
+                        brightness: brightness / count,
+                    };
+                    (index, features.distance(&average))
+                })
+                .filter(|&(_, distance)| distance < SPEAKER_DISTANCE)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            let index = match nearest {
+                Some((index, _)) => index,
+                None => {
+                    speakers.push((0.0, 0.0, 0.0));
+                    speakers.len() - 1
+                }
+            };
+            let speaker = &mut speakers[index];
+            speaker.0 += features.pitch_hz.ln();
+            speaker.1 += features.brightness;
+            speaker.2 += 1.0;
+            Some(index)
+        })
+        .collect()
+}
+
+/// Label of the speaker with the given index, starting at "SPEAKER_1"
+pub fn speaker_label(index: usize) -> String {
+    format!("SPEAKER_{}", index + 1)
+}
+
+/// Time the words of a segment by spreading its duration over them
+///
+/// Each word gets a share proportional to its length, so the timing is an
+/// estimate that is accurate at the segment edges.
+pub fn time_words(
+    text: &str,
+    start_seconds: f32,
+    end_seconds: f32,
+    confidence: Option<f32>,
+) -> Vec<WordTimestamp> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total: usize = words.iter().map(|word| word.chars().count()).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let per_char = (end_seconds - start_seconds).max(0.0) / total as f32;
+    let mut elapsed = 0;
+    words
+        .into_iter()
+        .map(|word| {
+            let word_start = start_seconds + elapsed as f32 * per_char;
+            elapsed += word.chars().count();
+            WordTimestamp {
+                word: word.to_string(),
+                start_seconds: word_start,
+                end_seconds: start_seconds + elapsed as f32 * per_char,
+                confidence,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,275 @@
+//! Graders deciding whether an answer passes its case
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, LazyLock};
+
+use jsonschema::Validator;
+use regex::Regex;
+use tokio_stream::StreamExt;
+
+use super::EvalError;
+use super::suite::EvalCase;
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::prompt::CandlePrompt;
+
+/// Pending result of grading one answer
+pub type GradeFuture<'a> = Pin<Box<dyn Future<Output = Result<Grade, EvalError>> + Send + 'a>>;
+
+/// Outcome of grading one answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grade {
+    /// Score from 0 to 1
+    pub score: f64,
+    pub passed: bool,
+    /// Why the answer passed or failed
+    pub reason: String,
+}
+
+impl Grade {
+    /// Full marks
+    pub fn pass(reason: impl Into<String>) -> Self {
+        Self {
+            score: 1.0,
+            passed: true,
+            reason: reason.into(),
+        }
+    }
+
+    /// No marks
+    pub fn fail(reason: impl Into<String>) -> Self {
+        Self {
+            score: 0.0,
+            passed: false,
+            reason: reason.into(),
+        }
+    }
+
+    /// `score`, clamped to 0..=1, passing at or above `pass_score`
+    pub fn scored(score: f64, pass_score: f64, reason: impl Into<String>) -> Self {
+        let score = score.clamp(0.0, 1.0);
+        Self {
+            score,
+            passed: score >= pass_score,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Decides whether an answer to a case passes
+///
+/// Implement this for checks the built-in graders do not cover and register
+/// it with [`super::EvalRunner::with_grader`]; cases select it with a
+/// `custom` grader of the same name, whose `config` the grader can read from
+/// `case.grader`.
+pub trait Grader: Send + Sync {
+    /// Grade `output`, the provider's answer to `case`
+    fn grade<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> GradeFuture<'a>;
+}
+
+/// Passes answers equal to the expected text, ignoring surrounding whitespace
+#[derive(Debug, Clone)]
+pub struct ExactMatchGrader {
+    expected: String,
+    ignore_case: bool,
+}
+
+impl ExactMatchGrader {
+    pub fn new(expected: impl Into<String>, ignore_case: bool) -> Self {
+        Self {
+            expected: expected.into(),
+            ignore_case,
+        }
+    }
+}
+
+impl Grader for ExactMatchGrader {
+    fn grade<'a>(&'a self, _case: &'a EvalCase, output: &'a str) -> GradeFuture<'a> {
+        let answer = output.trim();
+        let expected = self.expected.trim();
+        let matched = if self.ignore_case {
+            answer.to_lowercase() == expected.to_lowercase()
+        } else {
+            answer == expected
+        };
+
+        let grade = if matched {
+            Grade::pass("Exact match")
+        } else {
+            Grade::fail(format!("Expected {expected:?}, got {answer:?}"))
+        };
+        Box::pin(async move { Ok(grade) })
+    }
+}
+
+/// Passes answers matching a regular expression
+#[derive(Debug, Clone)]
+pub struct RegexGrader {
+    regex: Regex,
+}
+
+impl RegexGrader {
+    /// # Errors
+    /// Returns `regex::Error` if `pattern` is not a valid regular expression
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: Regex::new(pattern)?,
+        })
+    }
+}
+
+impl Grader for RegexGrader {
+    fn grade<'a>(&'a self, _case: &'a EvalCase, output: &'a str) -> GradeFuture<'a> {
+        let grade = if self.regex.is_match(output) {
+            Grade::pass(format!("Matches /{}/", self.regex))
+        } else {
+            Grade::fail(format!("Does not match /{}/", self.regex))
+        };
+        Box::pin(async move { Ok(grade) })
+    }
+}
+
+/// Passes answers that are JSON valid against a schema
+///
+/// A markdown code fence around the JSON is ignored.
+pub struct JsonSchemaGrader {
+    validator: Validator,
+}
+
+impl std::fmt::Debug for JsonSchemaGrader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonSchemaGrader").finish_non_exhaustive()
+    }
+}
+
+impl JsonSchemaGrader {
+    /// # Errors
+    /// Returns a message if `schema` is not a valid JSON schema
+    pub fn new(schema: &serde_json::Value) -> Result<Self, String> {
+        let validator = Validator::options()
+            .build(schema)
+            .map_err(|e| e.to_string())?;
+        Ok(Self { validator })
+    }
+}
+
+impl Grader for JsonSchemaGrader {
+    fn grade<'a>(&'a self, _case: &'a EvalCase, output: &'a str) -> GradeFuture<'a> {
+        let grade = match serde_json::from_str::<serde_json::Value>(strip_code_fence(output)) {
+            Err(e) => Grade::fail(format!("Not valid JSON: {e}")),
+            Ok(value) => {
+                let errors: Vec<String> = self
+                    .validator
+                    .iter_errors(&value)
+                    .map(|e| e.to_string())
+                    .collect();
+                if errors.is_empty() {
+                    Grade::pass("Valid against the schema")
+                } else {
+                    Grade::fail(format!("Schema violations: {}", errors.join(", ")))
+                }
+            }
+        };
+        Box::pin(async move { Ok(grade) })
+    }
+}
+
+/// Text inside a markdown code fence, or the trimmed text when there is none
+fn strip_code_fence(output: &str) -> &str {
+    let trimmed = output.trim();
+    let Some(fenced) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    // Drop the info string (e.g. `json`) on the opening line
+    let body = fenced.split_once('\n').map_or("", |(_, body)| body);
+    body.strip_suffix("```").unwrap_or(body).trim()
+}
+
+/// `SCORE: n` line of a judge's reply
+static JUDGE_SCORE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)score\s*[:=]\s*(\d+(?:\.\d+)?)").expect("judge score pattern is valid")
+});
+
+/// Has a second provider score answers against criteria
+///
+/// The judge rates the answer from 0 to 10 at temperature 0; the grade's
+/// score is that rating divided by 10.
+#[derive(Debug, Clone)]
+pub struct JudgeGrader {
+    judge: Arc<dyn TextToTextCapable>,
+    criteria: String,
+    pass_score: f64,
+}
+
+impl JudgeGrader {
+    pub fn new(
+        judge: Arc<dyn TextToTextCapable>,
+        criteria: impl Into<String>,
+        pass_score: f64,
+    ) -> Self {
+        Self {
+            judge,
+            criteria: criteria.into(),
+            pass_score,
+        }
+    }
+
+    fn judge_prompt(&self, case: &EvalCase, output: &str) -> String {
+        format!(
+            "You are grading an assistant's answer.\n\n\
+             Question:\n{}\n\n\
+             Criteria:\n{}\n\n\
+             Answer:\n{}\n\n\
+             Rate how well the answer meets the criteria from 0 to 10. \
+             Reply with \"SCORE: <number>\" on the first line and a one-sentence reason on the second.",
+            case.prompt, self.criteria, output
+        )
+    }
+}
+
+impl Grader for JudgeGrader {
+    fn grade<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> GradeFuture<'a> {
+        Box::pin(async move {
+            let params = CandleCompletionParams {
+                temperature: 0.0,
+                ..Default::default()
+            };
+            let mut stream = self
+                .judge
+                .prompt(CandlePrompt::new(self.judge_prompt(case, output)), &params);
+
+            let mut reply = String::new();
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    CandleCompletionChunk::Text(text) => reply.push_str(&text),
+                    CandleCompletionChunk::Complete { text, .. } => reply.push_str(&text),
+                    CandleCompletionChunk::Error(error) => {
+                        return Err(EvalError::Grading(format!("Judge failed: {error}")));
+                    }
+                    _ => {}
+                }
+            }
+
+            let (rating, reason) = parse_judge_reply(&reply).ok_or_else(|| {
+                EvalError::Grading(format!("Judge reply has no score: {:?}", reply.trim()))
+            })?;
+            Ok(Grade::scored(rating / 10.0, self.pass_score, reason))
+        })
+    }
+}
+
+/// Rating and reason from a judge's reply
+fn parse_judge_reply(reply: &str) -> Option<(f64, String)> {
+    let captures = JUDGE_SCORE.captures(reply)?;
+    let rating: f64 = captures.get(1)?.as_str().parse().ok()?;
+    let score_end = captures.get(0)?.end();
+
+    let reason = reply[score_end..].trim().trim_start_matches("/10").trim();
+    let reason = if reason.is_empty() {
+        format!("Judge rated {rating}/10")
+    } else {
+        format!("Judge rated {rating}/10: {reason}")
+    };
+    Some((rating, reason))
+}
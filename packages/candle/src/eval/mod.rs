@@ -0,0 +1,58 @@
+//! Evaluation harness for text-to-text providers
+//!
+//! An [`EvalSuite`] lists test cases, each a prompt, a description of the
+//! expected behavior and a grader deciding whether an answer passes.
+//! Suites are TOML (or JSON) files:
+//!
+//! ```toml
+//! name = "basics"
+//!
+//! [[cases]]
+//! id = "add"
+//! prompt = "What is 2 + 2? Reply with the number only."
+//! grader = { type = "exact_match", expected = "4" }
+//!
+//! [[cases]]
+//! id = "haiku"
+//! prompt = "Write a haiku about rust."
+//! expectation = "Three lines in a 5-7-5 syllable pattern about rust"
+//! grader = { type = "judge", pass_score = 0.7 }
+//! ```
+//!
+//! An [`EvalRunner`] runs a suite against one or more providers and grades
+//! every answer, producing an [`EvalReport`] that renders as JSON or
+//! markdown. Built-in graders check exact matches, regular expressions and
+//! JSON schema validity, or ask a second provider to judge the answer;
+//! custom graders are registered on the runner by name.
+
+mod grader;
+mod report;
+mod runner;
+mod suite;
+
+use std::path::PathBuf;
+
+pub use grader::{
+    ExactMatchGrader, Grade, GradeFuture, Grader, JsonSchemaGrader, JudgeGrader, RegexGrader,
+};
+pub use report::{CaseResult, EvalReport, ProviderReport};
+pub use runner::EvalRunner;
+pub use suite::{EvalCase, EvalSuite, GraderSpec};
+
+/// Evaluation errors
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    #[error("Eval I/O error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Invalid eval suite: {0}")]
+    InvalidSuite(String),
+    #[error("Invalid grader for case '{case}': {message}")]
+    InvalidGrader { case: String, message: String },
+    #[error("No providers to evaluate")]
+    NoProviders,
+    #[error("Grading failed: {0}")]
+    Grading(String),
+}
@@ -0,0 +1,149 @@
+//! Scored eval reports in JSON and markdown
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one case for one provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub id: String,
+    /// Kind of grader that scored the answer
+    pub grader: String,
+    /// The provider's answer
+    pub output: String,
+    /// Score from 0 to 1; 0 when the case errored
+    pub score: f64,
+    pub passed: bool,
+    /// Why the answer passed or failed
+    pub reason: String,
+    /// Set when the provider or grader failed, so the answer went ungraded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// Every case result for one provider, with totals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderReport {
+    pub provider: String,
+    /// Mean case score, counting errored cases as 0
+    pub score: f64,
+    pub passed: usize,
+    /// Cases graded as failing
+    pub failed: usize,
+    /// Cases the provider or grader could not complete
+    pub errored: usize,
+    pub cases: Vec<CaseResult>,
+}
+
+impl ProviderReport {
+    /// Report over `cases`, computing the totals
+    pub fn new(provider: impl Into<String>, cases: Vec<CaseResult>) -> Self {
+        let passed = cases.iter().filter(|case| case.passed).count();
+        let errored = cases.iter().filter(|case| case.error.is_some()).count();
+        let score = if cases.is_empty() {
+            0.0
+        } else {
+            cases.iter().map(|case| case.score).sum::<f64>() / cases.len() as f64
+        };
+        Self {
+            provider: provider.into(),
+            score,
+            passed,
+            failed: cases.len() - passed - errored,
+            errored,
+            cases,
+        }
+    }
+}
+
+/// Results of running a suite against one or more providers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub suite: String,
+    pub generated_at: DateTime<Utc>,
+    /// One report per provider, in the order they were run
+    pub providers: Vec<ProviderReport>,
+}
+
+impl EvalReport {
+    /// Report stamped with the current time
+    pub fn new(suite: impl Into<String>, providers: Vec<ProviderReport>) -> Self {
+        Self {
+            suite: suite.into(),
+            generated_at: Utc::now(),
+            providers,
+        }
+    }
+
+    /// Report for `provider`
+    pub fn provider(&self, provider: &str) -> Option<&ProviderReport> {
+        self.providers
+            .iter()
+            .find(|report| report.provider == provider)
+    }
+
+    /// Pretty-printed JSON
+    ///
+    /// # Errors
+    /// Returns `serde_json::Error` if serialization fails
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Markdown with a summary table and a table of cases per provider
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let _ = writeln!(markdown, "# Eval report: {}\n", self.suite);
+        let _ = writeln!(
+            markdown,
+            "Generated {}\n",
+            self.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+
+        markdown.push_str("| Provider | Score | Passed | Failed | Errors |\n");
+        markdown.push_str("|---|---:|---:|---:|---:|\n");
+        for report in &self.providers {
+            let _ = writeln!(
+                markdown,
+                "| {} | {:.2} | {}/{} | {} | {} |",
+                table_cell(&report.provider),
+                report.score,
+                report.passed,
+                report.cases.len(),
+                report.failed,
+                report.errored
+            );
+        }
+
+        for report in &self.providers {
+            let _ = writeln!(markdown, "\n## {}\n", report.provider);
+            markdown.push_str("| Case | Grader | Result | Score | Reason |\n");
+            markdown.push_str("|---|---|---|---:|---|\n");
+            for case in &report.cases {
+                let (result, reason) = match &case.error {
+                    Some(error) => ("error", error.as_str()),
+                    None if case.passed => ("pass", case.reason.as_str()),
+                    None => ("fail", case.reason.as_str()),
+                };
+                let _ = writeln!(
+                    markdown,
+                    "| {} | {} | {} | {:.2} | {} |",
+                    table_cell(&case.id),
+                    table_cell(&case.grader),
+                    result,
+                    case.score,
+                    table_cell(reason)
+                );
+            }
+        }
+        markdown
+    }
+}
+
+/// Text safe to put in one markdown table cell
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
@@ -0,0 +1,257 @@
+//! Running suites against providers
+
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio_stream::StreamExt;
+
+use super::EvalError;
+use super::grader::{ExactMatchGrader, Grader, JsonSchemaGrader, JudgeGrader, RegexGrader};
+use super::report::{CaseResult, EvalReport, ProviderReport};
+use super::suite::{EvalCase, EvalSuite, GraderSpec};
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::prompt::CandlePrompt;
+
+/// Runs eval suites against providers and grades the answers
+///
+/// Cases run one at a time, in suite order, for each provider in turn.
+#[derive(Clone, Default)]
+pub struct EvalRunner {
+    providers: Vec<(String, Arc<dyn TextToTextCapable>)>,
+    judge: Option<Arc<dyn TextToTextCapable>>,
+    graders: HashMap<String, Arc<dyn Grader>>,
+    temperature: f64,
+    max_tokens: Option<u64>,
+}
+
+impl std::fmt::Debug for EvalRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvalRunner")
+            .field("providers", &self.providers())
+            .field("judge", &self.judge.as_ref().map(|j| j.info().registry_key))
+            .field("graders", &self.graders.keys().collect::<Vec<_>>())
+            .field("temperature", &self.temperature)
+            .field("max_tokens", &self.max_tokens)
+            .finish()
+    }
+}
+
+impl EvalRunner {
+    /// Runner with no providers, sampling at temperature 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider, named by its registry key
+    #[must_use]
+    pub fn with_provider(self, model: impl TextToTextCapable) -> Self {
+        let name = model.info().registry_key.to_string();
+        self.with_named_provider(name, model)
+    }
+
+    /// Add a provider under `name`
+    #[must_use]
+    pub fn with_named_provider(
+        mut self,
+        name: impl Into<String>,
+        model: impl TextToTextCapable,
+    ) -> Self {
+        self.providers.push((name.into(), Arc::new(model)));
+        self
+    }
+
+    /// Provider that scores answers for `judge` graders
+    #[must_use]
+    pub fn with_judge(mut self, model: impl TextToTextCapable) -> Self {
+        self.judge = Some(Arc::new(model));
+        self
+    }
+
+    /// Register a grader that `custom` cases select by `name`
+    #[must_use]
+    pub fn with_grader(mut self, name: impl Into<String>, grader: impl Grader + 'static) -> Self {
+        self.graders.insert(name.into(), Arc::new(grader));
+        self
+    }
+
+    /// Temperature for cases that do not set one
+    #[must_use]
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Token limit for cases that do not set one
+    #[must_use]
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Names of the providers, in run order
+    pub fn providers(&self) -> Vec<&str> {
+        self.providers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Run every case of `suite` against every provider
+    ///
+    /// Graders are built before anything runs, so a bad regex or schema, a
+    /// judge case without a judge, or an unregistered custom grader fails
+    /// fast. Provider and judge failures are recorded on the case instead.
+    ///
+    /// # Errors
+    /// Returns `EvalError` if there are no providers or a grader cannot be built
+    pub async fn run(&self, suite: &EvalSuite) -> Result<EvalReport, EvalError> {
+        if self.providers.is_empty() {
+            return Err(EvalError::NoProviders);
+        }
+        let graders = suite
+            .cases
+            .iter()
+            .map(|case| self.grader_for(case))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut reports = Vec::with_capacity(self.providers.len());
+        for (name, model) in &self.providers {
+            log::info!(
+                "Eval '{}': {} cases against {}",
+                suite.name,
+                suite.cases.len(),
+                name
+            );
+            let mut results = Vec::with_capacity(suite.cases.len());
+            for (case, grader) in suite.cases.iter().zip(&graders) {
+                let result = self.run_case(model.as_ref(), case, grader.as_ref()).await;
+                log::info!(
+                    "Eval {} / {}: {}",
+                    name,
+                    case.id,
+                    match (&result.error, result.passed) {
+                        (Some(_), _) => "error",
+                        (None, true) => "pass",
+                        (None, false) => "fail",
+                    }
+                );
+                results.push(result);
+            }
+            reports.push(ProviderReport::new(name.clone(), results));
+        }
+
+        Ok(EvalReport::new(suite.name.clone(), reports))
+    }
+
+    /// Grader for `case`'s spec
+    fn grader_for(&self, case: &EvalCase) -> Result<Arc<dyn Grader>, EvalError> {
+        let invalid = |message: String| EvalError::InvalidGrader {
+            case: case.id.clone(),
+            message,
+        };
+
+        let grader: Arc<dyn Grader> = match &case.grader {
+            GraderSpec::ExactMatch {
+                expected,
+                ignore_case,
+            } => Arc::new(ExactMatchGrader::new(expected.clone(), *ignore_case)),
+            GraderSpec::Regex { pattern } => {
+                Arc::new(RegexGrader::new(pattern).map_err(|e| invalid(e.to_string()))?)
+            }
+            GraderSpec::JsonSchema { schema } => {
+                Arc::new(JsonSchemaGrader::new(schema).map_err(invalid)?)
+            }
+            GraderSpec::Judge {
+                criteria,
+                pass_score,
+            } => {
+                let judge = self
+                    .judge
+                    .clone()
+                    .ok_or_else(|| invalid("judge grading needs a judge provider".to_string()))?;
+                let criteria = criteria
+                    .clone()
+                    .or_else(|| case.expectation.clone())
+                    .ok_or_else(|| {
+                        invalid("judge grading needs criteria or an expectation".to_string())
+                    })?;
+                Arc::new(JudgeGrader::new(judge, criteria, *pass_score))
+            }
+            GraderSpec::Custom { name, .. } => self
+                .graders
+                .get(name)
+                .cloned()
+                .ok_or_else(|| invalid(format!("no grader registered as '{name}'")))?,
+        };
+        Ok(grader)
+    }
+
+    /// Answer and grade one case
+    async fn run_case(
+        &self,
+        model: &dyn TextToTextCapable,
+        case: &EvalCase,
+        grader: &dyn Grader,
+    ) -> CaseResult {
+        let started = Instant::now();
+        let mut result = CaseResult {
+            id: case.id.clone(),
+            grader: case.grader.kind().to_string(),
+            output: String::new(),
+            score: 0.0,
+            passed: false,
+            reason: String::new(),
+            error: None,
+            elapsed_ms: 0,
+        };
+
+        match self.answer(model, case).await {
+            Ok(output) => {
+                match grader.grade(case, &output).await {
+                    Ok(grade) => {
+                        result.score = grade.score;
+                        result.passed = grade.passed;
+                        result.reason = grade.reason;
+                    }
+                    Err(e) => result.error = Some(e.to_string()),
+                }
+                result.output = output;
+            }
+            Err(error) => result.error = Some(error),
+        }
+
+        result.elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        result
+    }
+
+    /// The provider's full answer to `case`, or its error
+    async fn answer(
+        &self,
+        model: &dyn TextToTextCapable,
+        case: &EvalCase,
+    ) -> Result<String, String> {
+        let params = CandleCompletionParams {
+            temperature: case.temperature.unwrap_or(self.temperature),
+            max_tokens: case
+                .max_tokens
+                .or(self.max_tokens)
+                .and_then(NonZeroU64::new),
+            ..Default::default()
+        };
+
+        let mut stream = model.prompt(CandlePrompt::new(case.prompt.clone()), &params);
+        let mut output = String::new();
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                CandleCompletionChunk::Text(text) => output.push_str(&text),
+                CandleCompletionChunk::Complete { text, .. } => output.push_str(&text),
+                CandleCompletionChunk::Error(error) => return Err(error),
+                _ => {}
+            }
+        }
+        Ok(output)
+    }
+}
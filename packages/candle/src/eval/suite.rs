@@ -0,0 +1,158 @@
+//! Eval suite definitions and loading
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::EvalError;
+
+/// Score a judge must give for a case to pass when none is set
+const DEFAULT_JUDGE_PASS_SCORE: f64 = 0.7;
+
+fn default_judge_pass_score() -> f64 {
+    DEFAULT_JUDGE_PASS_SCORE
+}
+
+/// How a case's answer is graded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GraderSpec {
+    /// The answer, trimmed, equals `expected`
+    ExactMatch {
+        expected: String,
+        #[serde(default)]
+        ignore_case: bool,
+    },
+    /// The answer matches `pattern` somewhere
+    Regex { pattern: String },
+    /// The answer is JSON valid against `schema`
+    JsonSchema { schema: serde_json::Value },
+    /// A judge provider scores the answer from 0 to 1 against `criteria`,
+    /// or the case's expectation when no criteria are given
+    Judge {
+        #[serde(default)]
+        criteria: Option<String>,
+        #[serde(default = "default_judge_pass_score")]
+        pass_score: f64,
+    },
+    /// A grader registered on the runner under `name`
+    Custom {
+        name: String,
+        #[serde(default)]
+        config: serde_json::Value,
+    },
+}
+
+impl GraderSpec {
+    /// Short name of the grader kind, as shown in reports
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::ExactMatch { .. } => "exact_match",
+            Self::Regex { .. } => "regex",
+            Self::JsonSchema { .. } => "json_schema",
+            Self::Judge { .. } => "judge",
+            Self::Custom { name, .. } => name,
+        }
+    }
+}
+
+/// One prompt and how its answer is graded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalCase {
+    /// Identifies the case in reports
+    pub id: String,
+    pub prompt: String,
+    /// What a good answer does, in prose; given to judge graders
+    #[serde(default)]
+    pub expectation: Option<String>,
+    pub grader: GraderSpec,
+    /// Overrides the runner's temperature
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Overrides the runner's token limit
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
+/// Named set of eval cases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalSuite {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub cases: Vec<EvalCase>,
+}
+
+impl EvalSuite {
+    /// Load a suite file, read as JSON when it ends in `.json` and TOML otherwise
+    ///
+    /// # Errors
+    /// Returns `EvalError` if the file cannot be read or is not a valid suite
+    pub async fn load(path: &Path) -> Result<Self, EvalError> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|source| EvalError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        if is_json {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        }
+    }
+
+    /// Parse a TOML suite
+    ///
+    /// # Errors
+    /// Returns `EvalError::InvalidSuite` if the suite does not parse or validate
+    pub fn from_toml(contents: &str) -> Result<Self, EvalError> {
+        let suite: Self =
+            toml::from_str(contents).map_err(|e| EvalError::InvalidSuite(e.to_string()))?;
+        suite.validate()?;
+        Ok(suite)
+    }
+
+    /// Parse a JSON suite
+    ///
+    /// # Errors
+    /// Returns `EvalError::InvalidSuite` if the suite does not parse or validate
+    pub fn from_json(contents: &str) -> Result<Self, EvalError> {
+        let suite: Self =
+            serde_json::from_str(contents).map_err(|e| EvalError::InvalidSuite(e.to_string()))?;
+        suite.validate()?;
+        Ok(suite)
+    }
+
+    /// Check the suite has cases and their ids are unique
+    ///
+    /// # Errors
+    /// Returns `EvalError::InvalidSuite` describing the first problem found
+    pub fn validate(&self) -> Result<(), EvalError> {
+        if self.cases.is_empty() {
+            return Err(EvalError::InvalidSuite(format!(
+                "suite '{}' has no cases",
+                self.name
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for case in &self.cases {
+            if case.id.is_empty() {
+                return Err(EvalError::InvalidSuite("case with an empty id".to_string()));
+            }
+            if !seen.insert(case.id.as_str()) {
+                return Err(EvalError::InvalidSuite(format!(
+                    "case id '{}' appears more than once",
+                    case.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,43 +4,61 @@ use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AlfredScriptFilterOutput {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub skipknowledge: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rerun: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub variables: Option<HashMap<String, String>>,
     pub items: Vec<AlfredItem>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AlfredItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub uid: Option<String>,
     pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub autocomplete: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon: Option<AlfredIcon>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub valid: Option<bool>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mods: HashMap<String, AlfredModifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub text: Option<AlfredText>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quicklookurl: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AlfredIcon {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
     pub icon_type: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AlfredModifier {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub valid: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AlfredText {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub copy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub largetype: Option<String>,
 }
 
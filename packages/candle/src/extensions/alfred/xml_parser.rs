@@ -71,6 +71,7 @@ pub fn xml_to_json(xml_output: AlfredXmlOutput) -> AlfredScriptFilterOutput {
                     })
                     .collect(),
                 text: None,
+                quicklookurl: None,
             })
             .collect(),
         skipknowledge: None,
@@ -0,0 +1,335 @@
+use std::path::{Path, PathBuf};
+
+use plist::{Dictionary, Value as PlistValue};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use super::request::ExtensionHost;
+use crate::extensions::common::types::Result;
+
+/// Raycast command that asks the agent
+const RAYCAST_COMMAND: &str = "ask";
+
+/// Description of the extension bundle that forwards launcher queries to candle
+///
+/// The generated Raycast extension and Alfred workflow run `command` with
+/// `args`, followed by `extension --extension-host <host> --message <query>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionManifest {
+    /// Package name, lowercase with dashes
+    pub name: String,
+    /// Title shown in the launcher
+    pub title: String,
+    /// One-line description
+    pub description: String,
+    /// Author handle, used for the Raycast author and Alfred bundle id
+    pub author: String,
+    /// Alfred keyword that starts a query
+    pub keyword: String,
+    /// Bundle version
+    pub version: String,
+    /// Executable answering queries
+    pub command: PathBuf,
+    /// Arguments passed before the extension arguments, such as `--model`
+    pub args: Vec<String>,
+}
+
+impl ExtensionManifest {
+    /// Manifest for an extension running `command`
+    pub fn new(command: impl Into<PathBuf>) -> Self {
+        Self {
+            name: "candle-agent".to_string(),
+            title: "Ask Candle".to_string(),
+            description: "Ask a local agent and get a markdown answer".to_string(),
+            author: "cyrup".to_string(),
+            keyword: "ask".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            command: command.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Set the package name and launcher title
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>, title: impl Into<String>) -> Self {
+        self.name = name.into();
+        self.title = title.into();
+        self
+    }
+
+    /// Set the description
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Set the author handle
+    #[must_use]
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Set the Alfred keyword
+    #[must_use]
+    pub fn with_keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keyword = keyword.into();
+        self
+    }
+
+    /// Pass `args` to the command before the extension arguments
+    #[must_use]
+    pub fn with_args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.args.extend(args);
+        self
+    }
+
+    /// Alfred bundle id, e.g. `com.cyrup.candle-agent`
+    pub fn bundle_id(&self) -> String {
+        format!("com.{}.{}", self.author, self.name)
+    }
+
+    /// Arguments the launcher passes, followed by `--message <query>`
+    fn extension_args(&self, host: ExtensionHost) -> Vec<String> {
+        let mut args = self.args.clone();
+        args.extend([
+            "extension".to_string(),
+            "--extension-host".to_string(),
+            host.to_string(),
+        ]);
+        args
+    }
+
+    /// Raycast `package.json`
+    pub fn raycast_package_json(&self) -> Value {
+        json!({
+            "$schema": "https://www.raycast.com/schemas/extension.json",
+            "name": self.name,
+            "title": self.title,
+            "description": self.description,
+            "icon": "command-icon.png",
+            "author": self.author,
+            "license": "MIT",
+            "version": self.version,
+            "categories": ["Productivity"],
+            "commands": [{
+                "name": RAYCAST_COMMAND,
+                "title": self.title,
+                "description": self.description,
+                "mode": "view",
+                "arguments": [
+                    {
+                        "name": "query",
+                        "placeholder": "Question",
+                        "type": "text",
+                        "required": true
+                    },
+                    {
+                        "name": "image",
+                        "placeholder": "Image path",
+                        "type": "text",
+                        "required": false
+                    }
+                ]
+            }],
+            "dependencies": {
+                "@raycast/api": "^1.80.0"
+            },
+            "devDependencies": {
+                "@types/node": "^20.0.0",
+                "@types/react": "^18.0.0",
+                "typescript": "^5.0.0"
+            },
+            "scripts": {
+                "build": "ray build -e dist",
+                "dev": "ray develop"
+            }
+        })
+    }
+
+    /// Source of the Raycast command, `src/ask.tsx`
+    ///
+    /// It runs the command and shows each JSON frame it prints as the
+    /// detail view's markdown, so the answer renders while it streams.
+    pub fn raycast_command_source(&self) -> String {
+        let command = json!(self.command.to_string_lossy());
+        let args = json!(self.extension_args(ExtensionHost::Raycast));
+        format!(
+            r#"import {{ Detail }} from "@raycast/api";
+import {{ spawn }} from "child_process";
+import {{ createInterface }} from "readline";
+import {{ useEffect, useState }} from "react";
+
+const COMMAND = {command};
+const ARGS: string[] = {args};
+
+type Frame = {{ markdown: string; isLoading: boolean }};
+
+export default function Command(props: {{ arguments: {{ query: string; image?: string }} }}) {{
+  const {{ query, image }} = props.arguments;
+  const [frame, setFrame] = useState<Frame>({{ markdown: "", isLoading: true }});
+
+  useEffect(() => {{
+    const args = [...ARGS, "--message", query];
+    if (image) {{
+      args.push("--image", image);
+    }}
+    const child = spawn(COMMAND, args);
+    createInterface({{ input: child.stdout }}).on("line", (line) => {{
+      try {{
+        setFrame(JSON.parse(line));
+      }} catch {{
+        // Not a frame
+      }}
+    }});
+    child.on("error", (error) => setFrame({{ markdown: `> ❌ ${{error.message}}`, isLoading: false }}));
+    child.on("close", () => setFrame((current) => ({{ ...current, isLoading: false }})));
+    return () => {{
+      child.kill();
+    }};
+  }}, [query, image]);
+
+  return <Detail markdown={{frame.markdown}} isLoading={{frame.isLoading}} />;
+}}
+"#
+        )
+    }
+
+    /// Alfred workflow `info.plist`
+    ///
+    /// A script filter on the keyword runs the command once typing pauses,
+    /// and actioning the answer copies it to the clipboard.
+    pub fn alfred_info_plist(&self) -> PlistValue {
+        let filter_uid = self.uid("scriptfilter");
+        let clipboard_uid = self.uid("clipboard");
+
+        let mut filter_config = Dictionary::new();
+        filter_config.insert("keyword".into(), self.keyword.clone().into());
+        filter_config.insert("title".into(), self.title.clone().into());
+        filter_config.insert("subtext".into(), self.description.clone().into());
+        filter_config.insert("runningsubtext".into(), "Thinking…".into());
+        filter_config.insert("script".into(), self.alfred_script().into());
+        // Bash, with the query passed as $1
+        filter_config.insert("type".into(), 0.into());
+        filter_config.insert("scriptargtype".into(), 1.into());
+        filter_config.insert("argumenttype".into(), 0.into());
+        filter_config.insert("withspace".into(), true.into());
+        filter_config.insert("alfredfiltersresults".into(), false.into());
+        // Wait until typing stops instead of asking on every keystroke
+        filter_config.insert("queuemode".into(), 1.into());
+        filter_config.insert("queuedelaymode".into(), 2.into());
+        filter_config.insert("queuedelaycustom".into(), 3.into());
+        filter_config.insert("queuedelayimmediatelyinitially".into(), false.into());
+
+        let mut clipboard_config = Dictionary::new();
+        clipboard_config.insert("clipboardtext".into(), "{query}".into());
+        clipboard_config.insert("autopaste".into(), false.into());
+        clipboard_config.insert("transient".into(), false.into());
+
+        let objects = vec![
+            workflow_object(
+                "alfred.workflow.input.scriptfilter",
+                &filter_uid,
+                filter_config,
+            ),
+            workflow_object(
+                "alfred.workflow.output.clipboard",
+                &clipboard_uid,
+                clipboard_config,
+            ),
+        ];
+
+        let mut connection = Dictionary::new();
+        connection.insert("destinationuid".into(), clipboard_uid.into());
+        connection.insert("modifiers".into(), 0.into());
+        connection.insert("modifiersubtext".into(), "".into());
+        connection.insert("vitoclose".into(), false.into());
+        let mut connections = Dictionary::new();
+        connections.insert(
+            filter_uid,
+            PlistValue::Array(vec![PlistValue::Dictionary(connection)]),
+        );
+
+        let mut info = Dictionary::new();
+        info.insert("bundleid".into(), self.bundle_id().into());
+        info.insert("name".into(), self.title.clone().into());
+        info.insert("description".into(), self.description.clone().into());
+        info.insert("createdby".into(), self.author.clone().into());
+        info.insert("version".into(), self.version.clone().into());
+        info.insert("readme".into(), "".into());
+        info.insert("webaddress".into(), "".into());
+        info.insert("objects".into(), PlistValue::Array(objects));
+        info.insert("connections".into(), PlistValue::Dictionary(connections));
+        info.insert("uidata".into(), PlistValue::Dictionary(Dictionary::new()));
+        PlistValue::Dictionary(info)
+    }
+
+    /// Bash script the Alfred script filter runs
+    fn alfred_script(&self) -> String {
+        let mut words = vec![shell_quote(&self.command.to_string_lossy())];
+        words.extend(
+            self.extension_args(ExtensionHost::Alfred)
+                .iter()
+                .map(|arg| shell_quote(arg)),
+        );
+        words.push("--message".to_string());
+        words.push("\"$1\"".to_string());
+        words.join(" ")
+    }
+
+    /// Stable UUID for a workflow object, so regenerating keeps connections
+    fn uid(&self, object: &str) -> String {
+        let digest = Sha256::digest(format!("{}/{}", self.bundle_id(), object).as_bytes());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        uuid::Builder::from_sha1_bytes(bytes)
+            .into_uuid()
+            .to_string()
+            .to_uppercase()
+    }
+
+    /// Write the bundle for `host` into `dir` and return the bundle's directory
+    ///
+    /// Raycast gets `<name>-raycast/` with `package.json` and `src/ask.tsx`,
+    /// ready for `npm install && npm run dev`. Alfred gets
+    /// `<name>-alfred/info.plist`; copy that directory into Alfred's
+    /// workflows folder or zip it as a `.alfredworkflow` to import it.
+    pub async fn write_bundle(&self, host: ExtensionHost, dir: &Path) -> Result<PathBuf> {
+        let bundle = dir.join(format!("{}-{}", self.name, host));
+        match host {
+            ExtensionHost::Raycast => {
+                fs::create_dir_all(bundle.join("src")).await?;
+                let package = serde_json::to_string_pretty(&self.raycast_package_json())?;
+                fs::write(bundle.join("package.json"), package).await?;
+                fs::write(
+                    bundle.join("src").join(format!("{RAYCAST_COMMAND}.tsx")),
+                    self.raycast_command_source(),
+                )
+                .await?;
+            }
+            ExtensionHost::Alfred => {
+                fs::create_dir_all(&bundle).await?;
+                let mut plist = Vec::new();
+                self.alfred_info_plist().to_writer_xml(&mut plist)?;
+                fs::write(bundle.join("info.plist"), plist).await?;
+            }
+        }
+        Ok(bundle)
+    }
+}
+
+fn workflow_object(object_type: &str, uid: &str, config: Dictionary) -> PlistValue {
+    let mut object = Dictionary::new();
+    object.insert("type".into(), object_type.into());
+    object.insert("uid".into(), uid.into());
+    object.insert("version".into(), 3.into());
+    object.insert("config".into(), PlistValue::Dictionary(config));
+    PlistValue::Dictionary(object)
+}
+
+/// Quote `word` for bash
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
@@ -0,0 +1,48 @@
+//! Answer Raycast and Alfred queries with an agent
+//!
+//! The rest of [`crate::extensions`] runs Raycast script commands and Alfred
+//! workflows from candle. The bridge works the other way around: a launcher
+//! extension runs the `candle extension` command with the user's query, the
+//! query goes through a configured agent (tools included), and the answer is
+//! written back in the format the launcher renders.
+//!
+//! - Raycast reads one JSON frame per line and shows the latest frame's
+//!   markdown in a detail view, so the answer appears while it streams.
+//! - Alfred reads a single script filter result once the answer is complete.
+//!
+//! [`ExtensionManifest`] writes the extension bundle that wires each
+//! launcher up to the command.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cyrup_candle::domain::chat::CandleChatLoop;
+//! use cyrup_candle::extensions::bridge::{ExtensionBridge, ExtensionHost, ExtensionQuery};
+//! use cyrup_candle::prelude::{CandleAgentBuilder, CandleAgentRoleBuilder, CandleFluentAi};
+//!
+//! # async fn run() {
+//! let bridge = ExtensionBridge::new(|turn: CandleChatLoop| {
+//!     CandleFluentAi::agent_role("launcher")
+//!         .into_agent()
+//!         .system_prompt("Answer briefly in Markdown.")
+//!         .chat(move |_| {
+//!             let turn = turn.clone();
+//!             async move { turn }
+//!         })
+//!         .unwrap_or_else(|e| ExtensionBridge::failed(e.to_string()))
+//! });
+//!
+//! let query = ExtensionQuery::new(ExtensionHost::Alfred, "What is a monad?");
+//! let response = bridge.respond(query).await;
+//! println!("{}", serde_json::to_string(&response.to_alfred()).unwrap_or_default());
+//! # }
+//! ```
+
+pub mod manifest;
+pub mod request;
+pub mod response;
+pub mod runner;
+
+pub use manifest::ExtensionManifest;
+pub use request::{ExtensionHost, ExtensionQuery};
+pub use response::{ExtensionImage, ExtensionResponse};
+pub use runner::{DEFAULT_EXTENSION_TIMEOUT, ExtensionAgent, ExtensionBridge};
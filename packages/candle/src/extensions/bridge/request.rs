@@ -0,0 +1,119 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::chat::CandleChatLoop;
+use crate::domain::chat::message::CandleImageAttachment;
+use crate::extensions::common::types::ExtensionError;
+
+/// File extensions of arguments picked up as image attachments
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Launcher that sent a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtensionHost {
+    /// Raycast, streaming markdown frames
+    #[default]
+    Raycast,
+    /// Alfred, reading one script filter result
+    Alfred,
+}
+
+impl fmt::Display for ExtensionHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtensionHost::Raycast => write!(f, "raycast"),
+            ExtensionHost::Alfred => write!(f, "alfred"),
+        }
+    }
+}
+
+impl FromStr for ExtensionHost {
+    type Err = ExtensionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "raycast" => Ok(ExtensionHost::Raycast),
+            "alfred" => Ok(ExtensionHost::Alfred),
+            other => Err(ExtensionError::UnknownHost(other.to_string())),
+        }
+    }
+}
+
+/// A query from a launcher, with any images the user passed along
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionQuery {
+    /// Launcher the answer is rendered for
+    pub host: ExtensionHost,
+    /// What the user typed
+    pub text: String,
+    /// Local paths or URLs of attached images
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+impl ExtensionQuery {
+    /// Query without attachments
+    pub fn new(host: ExtensionHost, text: impl Into<String>) -> Self {
+        Self {
+            host,
+            text: text.into(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Build a query from the arguments a launcher passes to its script
+    ///
+    /// Arguments that name an image file or URL become attachments, the
+    /// rest are joined into the query text. Raycast passes each argument
+    /// separately and Alfred passes the whole query as one.
+    pub fn from_args(host: ExtensionHost, args: &[String]) -> Self {
+        let (images, words): (Vec<&String>, Vec<&String>) =
+            args.iter().partition(|arg| is_image_source(arg));
+        Self {
+            host,
+            text: words
+                .iter()
+                .map(|word| word.trim())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+            images: images.into_iter().cloned().collect(),
+        }
+    }
+
+    /// Attach images by local path or URL
+    #[must_use]
+    pub fn with_images(mut self, images: impl IntoIterator<Item = String>) -> Self {
+        self.images.extend(images);
+        self
+    }
+
+    /// Chat turn sending this query to an agent
+    pub fn to_chat_loop(&self) -> CandleChatLoop {
+        if self.images.is_empty() {
+            CandleChatLoop::UserPrompt(self.text.clone())
+        } else {
+            CandleChatLoop::UserPromptWithImages(
+                self.text.clone(),
+                self.images
+                    .iter()
+                    .map(|source| CandleImageAttachment::new(source.as_str()))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Whether `arg` is a single path or URL to an image
+fn is_image_source(arg: &str) -> bool {
+    let arg = arg.trim();
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        return false;
+    }
+    arg.rsplit_once('.').is_some_and(|(_, extension)| {
+        IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+    })
+}
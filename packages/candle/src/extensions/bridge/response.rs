@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::domain::chat::message::CandleMessageChunk;
+use crate::extensions::alfred::{AlfredIcon, AlfredItem, AlfredScriptFilterOutput, AlfredText};
+
+/// Longest Alfred item title before it is cut off
+const ALFRED_TITLE_LIMIT: usize = 100;
+
+/// An image the answer links with `![alt](source)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionImage {
+    /// Alt text, possibly empty
+    pub alt: String,
+    /// Local path or URL of the image
+    pub source: String,
+}
+
+impl ExtensionImage {
+    /// Whether the image is fetched from a URL rather than read from disk
+    pub fn is_remote(&self) -> bool {
+        self.source.starts_with("http://") || self.source.starts_with("https://")
+    }
+}
+
+/// An agent's answer to an extension query, as far as it has streamed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionResponse {
+    /// Markdown answer so far
+    pub markdown: String,
+    /// Names of the tools the agent called, in call order
+    pub tool_calls: Vec<String>,
+    /// Error that ended or interrupted the answer
+    pub error: Option<String>,
+    /// Whether the answer is complete
+    pub done: bool,
+}
+
+impl ExtensionResponse {
+    /// Fold one streamed chunk into the answer
+    ///
+    /// Returns whether the chunk changed what the launcher shows.
+    pub fn apply(&mut self, chunk: &CandleMessageChunk) -> bool {
+        match chunk {
+            CandleMessageChunk::Text(text) => {
+                self.markdown.push_str(text);
+                !text.is_empty()
+            }
+            CandleMessageChunk::Complete { text, .. } => {
+                self.markdown.push_str(text);
+                self.done = true;
+                true
+            }
+            CandleMessageChunk::ToolCallStart { name, .. } => {
+                self.tool_calls.push(name.clone());
+                true
+            }
+            CandleMessageChunk::Error(error) => {
+                self.error = Some(error.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Images linked from the answer, in order of appearance
+    pub fn images(&self) -> Vec<ExtensionImage> {
+        markdown_images(&self.markdown)
+    }
+
+    /// Full markdown document: tools used, the answer, then any error
+    pub fn to_markdown(&self) -> String {
+        let mut document = String::new();
+        if !self.tool_calls.is_empty() {
+            let tools = self
+                .tool_calls
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            document.push_str(&format!("> 🔧 {tools}\n\n"));
+        }
+        document.push_str(&self.markdown);
+        if let Some(error) = &self.error {
+            if !self.markdown.is_empty() {
+                document.push_str("\n\n");
+            }
+            document.push_str(&format!("> ❌ {error}"));
+        }
+        document
+    }
+
+    /// Frame for the Raycast detail view, printed as one JSON line
+    pub fn to_raycast(&self) -> Value {
+        let images = self
+            .images()
+            .into_iter()
+            .map(|image| image.source)
+            .collect::<Vec<_>>();
+        json!({
+            "markdown": self.to_markdown(),
+            "isLoading": !self.done,
+            "metadata": {
+                "tools": self.tool_calls,
+                "images": images,
+                "error": self.error,
+            }
+        })
+    }
+
+    /// Script filter result listing the answer followed by its images
+    ///
+    /// Actioning the answer passes its markdown on; ⌘C copies it and ⌘L
+    /// shows it in large type.
+    pub fn to_alfred(&self) -> AlfredScriptFilterOutput {
+        let images = self.images();
+        let mut items = Vec::with_capacity(images.len() + 1);
+
+        if self.markdown.trim().is_empty() {
+            let (title, subtitle) = match &self.error {
+                Some(error) => ("Error".to_string(), error.clone()),
+                None if self.done => ("No answer".to_string(), String::new()),
+                None => ("Thinking…".to_string(), String::new()),
+            };
+            items.push(AlfredItem {
+                uid: None,
+                title,
+                subtitle: Some(subtitle),
+                arg: None,
+                autocomplete: None,
+                icon: None,
+                valid: Some(false),
+                mods: Default::default(),
+                text: None,
+                quicklookurl: None,
+            });
+        } else {
+            let subtitle = match (&self.error, self.tool_calls.is_empty()) {
+                (Some(error), _) => error.clone(),
+                (None, false) => format!("Used {}", self.tool_calls.join(", ")),
+                (None, true) => "⌘C to copy, ⌘L for large type".to_string(),
+            };
+            items.push(AlfredItem {
+                uid: None,
+                title: alfred_title(&self.markdown),
+                subtitle: Some(subtitle),
+                arg: Some(self.markdown.clone()),
+                autocomplete: None,
+                icon: None,
+                valid: Some(true),
+                mods: Default::default(),
+                text: Some(AlfredText {
+                    copy: Some(self.markdown.clone()),
+                    largetype: Some(self.markdown.clone()),
+                }),
+                quicklookurl: images.first().map(|image| image.source.clone()),
+            });
+        }
+
+        for image in images {
+            let title = if image.alt.is_empty() {
+                image
+                    .source
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&image.source)
+                    .to_string()
+            } else {
+                image.alt.clone()
+            };
+            let icon = (!image.is_remote()).then(|| AlfredIcon {
+                path: Some(image.source.clone()),
+                icon_type: None,
+            });
+            items.push(AlfredItem {
+                uid: None,
+                title,
+                subtitle: Some(image.source.clone()),
+                arg: Some(image.source.clone()),
+                autocomplete: None,
+                icon,
+                valid: Some(true),
+                mods: Default::default(),
+                text: None,
+                quicklookurl: Some(image.source),
+            });
+        }
+
+        AlfredScriptFilterOutput {
+            skipknowledge: Some(true),
+            rerun: None,
+            variables: None,
+            items,
+        }
+    }
+}
+
+/// First line of the answer without markdown markers, cut to fit a title
+fn alfred_title(markdown: &str) -> String {
+    let line = markdown
+        .lines()
+        .map(|line| line.trim().trim_start_matches(['#', '>', '-', '*']).trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if line.chars().count() <= ALFRED_TITLE_LIMIT {
+        return line.to_string();
+    }
+    let mut title = line
+        .chars()
+        .take(ALFRED_TITLE_LIMIT - 1)
+        .collect::<String>();
+    title.push('…');
+    title
+}
+
+/// `![alt](source)` images in `markdown`
+///
+/// A title after the source, as in `![alt](source "title")`, is dropped.
+fn markdown_images(markdown: &str) -> Vec<ExtensionImage> {
+    let mut images = Vec::new();
+    let mut rest = markdown;
+    while let Some(start) = rest.find("![") {
+        rest = &rest[start + 2..];
+        let Some(alt_end) = rest.find("](") else {
+            break;
+        };
+        let alt = &rest[..alt_end];
+        let after_alt = &rest[alt_end + 2..];
+        let Some(source_end) = after_alt.find(')') else {
+            break;
+        };
+        if alt.contains('\n') {
+            continue;
+        }
+        let source = after_alt[..source_end]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_matches(['<', '>']);
+        if !source.is_empty() {
+            images.push(ExtensionImage {
+                alt: alt.to_string(),
+                source: source.to_string(),
+            });
+        }
+        rest = &after_alt[source_end + 1..];
+    }
+    images
+}
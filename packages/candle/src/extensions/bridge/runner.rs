@@ -0,0 +1,124 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_stream::{Stream, StreamExt};
+
+use super::request::ExtensionQuery;
+use super::response::ExtensionResponse;
+use crate::domain::chat::CandleChatLoop;
+use crate::domain::chat::message::CandleMessageChunk;
+
+/// Longest an agent may take to answer before the bridge gives up
+pub const DEFAULT_EXTENSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+type ChunkStream = Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>>;
+type ResponseStream = Pin<Box<dyn Stream<Item = ExtensionResponse> + Send>>;
+
+/// Agent that answers extension queries
+///
+/// Implemented for closures that stream the answer to a chat turn, which
+/// usually build a fresh agent with its tools for every query.
+pub trait ExtensionAgent: Send + Sync + 'static {
+    /// Stream the answer to `turn`
+    fn respond(&self, turn: CandleChatLoop) -> ChunkStream;
+}
+
+impl<F> ExtensionAgent for F
+where
+    F: Fn(CandleChatLoop) -> ChunkStream + Send + Sync + 'static,
+{
+    fn respond(&self, turn: CandleChatLoop) -> ChunkStream {
+        self(turn)
+    }
+}
+
+/// Runs launcher queries through an agent and streams the rendered answer
+#[derive(Clone)]
+pub struct ExtensionBridge {
+    agent: Arc<dyn ExtensionAgent>,
+    timeout: Duration,
+}
+
+impl ExtensionBridge {
+    /// Bridge answering queries with `agent`
+    pub fn new(agent: impl ExtensionAgent) -> Self {
+        Self {
+            agent: Arc::new(agent),
+            timeout: DEFAULT_EXTENSION_TIMEOUT,
+        }
+    }
+
+    /// Give up on an answer after `timeout`, keeping what streamed so far
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Stream of a single error chunk, for agents that fail to start
+    pub fn failed(error: impl Into<String>) -> ChunkStream {
+        let error = error.into();
+        Box::pin(crate::async_stream::spawn_stream(
+            move |sender| async move {
+                let _ = sender.send(CandleMessageChunk::Error(error));
+            },
+        ))
+    }
+
+    /// Answer `query`, yielding the whole response after every change
+    ///
+    /// The last response yielded is always marked done, including when the
+    /// agent fails or runs out of time.
+    pub fn stream(&self, query: ExtensionQuery) -> ResponseStream {
+        let agent = self.agent.clone();
+        let timeout = self.timeout;
+
+        Box::pin(crate::async_stream::spawn_stream(
+            move |sender| async move {
+                let mut response = ExtensionResponse::default();
+                if query.text.trim().is_empty() && query.images.is_empty() {
+                    response.error = Some("Type a question to ask".to_string());
+                    response.done = true;
+                    let _ = sender.send(response);
+                    return;
+                }
+
+                let deadline = tokio::time::Instant::now() + timeout;
+                let mut chunks = agent.respond(query.to_chat_loop());
+                loop {
+                    match tokio::time::timeout_at(deadline, chunks.next()).await {
+                        Ok(Some(chunk)) => {
+                            if !response.apply(&chunk) {
+                                continue;
+                            }
+                            // Stop once the answer is final or the launcher stopped reading
+                            if sender.send(response.clone()).is_err() || response.done {
+                                return;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(_) => {
+                            response.error = Some(format!("Timed out after {:?}", timeout));
+                            break;
+                        }
+                    }
+                }
+
+                // The agent ended without a final chunk, or timed out
+                response.done = true;
+                let _ = sender.send(response);
+            },
+        ))
+    }
+
+    /// Answer `query` and return the complete response
+    pub async fn respond(&self, query: ExtensionQuery) -> ExtensionResponse {
+        let mut stream = self.stream(query);
+        let mut last = ExtensionResponse::default();
+        while let Some(response) = stream.next().await {
+            last = response;
+        }
+        last
+    }
+}
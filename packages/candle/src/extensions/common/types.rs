@@ -37,6 +37,9 @@ pub enum ExtensionError {
 
     #[error("Async join error: {0}")]
     JoinError(String),
+
+    #[error("Unknown extension host: {0} (expected raycast or alfred)")]
+    UnknownHost(String),
 }
 
 impl From<plist::Error> for ExtensionError {
@@ -1,4 +1,5 @@
 pub mod alfred;
+pub mod bridge;
 pub mod common;
 pub mod raycast;
 
@@ -31,6 +31,8 @@ pub mod macros;
 // Candle-specific modules (minimal set for core functionality)
 /// Async stream utilities using tokio streams
 pub mod async_stream;
+/// Offline batch inference over JSONL prompt files
+pub mod batch;
 /// Candle builders for zero-allocation construction patterns
 pub mod builders;
 /// Candle macros for ARCHITECTURE.md syntax support
@@ -43,6 +45,8 @@ pub mod cli;
 pub mod core;
 /// Candle domain types (replaces cyrup_domain dependency)
 pub mod domain;
+/// Evaluation suites, graders and scored reports for providers
+pub mod eval;
 /// Extension integration for Raycast and Alfred (macOS)
 pub mod extensions;
 /// Image processing utilities
@@ -53,6 +57,8 @@ pub mod memory;
 pub mod prompt;
 /// Shared Tokio runtime for avoiding multiple runtime creation
 pub mod runtime;
+/// OpenAI-compatible HTTP server for local models
+pub mod server;
 /// Utility modules for common operations
 pub mod util;
 /// Real workflow execution system with streams-only architecture
@@ -85,7 +91,10 @@ pub mod prelude {
         image_generation::{
             ImageGenerationChunk, ImageGenerationConfig, ImageGenerationModel, tensor_to_image,
         },
-        tool::{RouterError, SweetMcpRouter, ToolInfo, ToolRoute},
+        tool::{
+            McpTransport, RemoteMcpServer, RouterError, ScheduledToolCall, SweetMcpRouter,
+            ToolCallOutcome, ToolInfo, ToolRoute, ToolScheduler,
+        },
     };
     // Real workflow execution types - streams-only architecture
     pub use crate::workflow::{CandleExecutableWorkflow, CandleWorkflowStep, candle_workflow};
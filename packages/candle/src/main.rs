@@ -1,7 +1,8 @@
 //! Candle Chat CLI - Interactive AI chat application
 //!
 //! This binary demonstrates the CLI module with model selection, configuration,
-//! and interactive chat capabilities.
+//! and interactive chat capabilities. `cyrup serve` instead starts an
+//! OpenAI-compatible HTTP server for the local models.
 
 use log::error;
 use rustls::crypto::aws_lc_rs;
@@ -35,7 +36,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    // Run the interactive chat
+    // Run the interactive chat (or the server for `serve`)
     if let Err(e) = runner.run().await {
         error!("CLI error: {}", e);
         std::process::exit(1);
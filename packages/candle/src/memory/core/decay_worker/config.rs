@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::policy::{CompactionPolicy, DecayFunction};
+
 /// Configuration for background decay worker
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecayWorkerConfig {
@@ -14,6 +16,14 @@ pub struct DecayWorkerConfig {
     /// Minimum memory age before applying decay (hours)
     /// Prevents thrashing on fresh memories
     pub min_age_hours: u64,
+
+    /// How importance decays with age and recall
+    #[serde(default)]
+    pub function: DecayFunction,
+
+    /// When low-relevance memories are archived or deleted
+    #[serde(default)]
+    pub compaction: CompactionPolicy,
 }
 
 impl Default for DecayWorkerConfig {
@@ -22,6 +32,8 @@ impl Default for DecayWorkerConfig {
             cycle_interval_secs: 3600, // 1 hour between cycles
             batch_size: 500,           // Process 500 memories per batch
             min_age_hours: 1,          // Apply decay to memories older than 1 hour
+            function: DecayFunction::default(),
+            compaction: CompactionPolicy::default(),
         }
     }
 }
@@ -7,9 +7,16 @@
 //! - Causal edge strengths
 //!
 //! This eliminates expensive on-read decay calculations from the hot path.
+//! Memories that decay below the compaction threshold are archived or
+//! deleted.
 
 mod config;
+mod policy;
 mod worker;
 
 pub use config::DecayWorkerConfig;
+pub use policy::{
+    ACCESS_COUNT_KEY, ARCHIVED_AT_KEY, CompactionAction, CompactionPolicy, DECAYED_AT_KEY,
+    DecayFunction, MIN_IMPORTANCE, access_count, is_archived,
+};
 pub use worker::DecayWorker;
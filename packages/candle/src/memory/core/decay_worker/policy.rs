@@ -0,0 +1,155 @@
+//! Decay functions and the forgetting policy
+//!
+//! A memory's importance decays with time since it was last decayed. The
+//! configured [`DecayFunction`] decides how fast; the usage-boosted variant
+//! slows decay for memories that keep being recalled. Once a memory is old
+//! enough and its importance has fallen below the [`CompactionPolicy`]
+//! threshold, the decay worker archives or deletes it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::memory::primitives::node::MemoryNode;
+
+/// Custom metadata key counting how often a memory was recalled
+pub const ACCESS_COUNT_KEY: &str = "access_count";
+
+/// Custom metadata key holding when decay was last applied (RFC 3339)
+pub const DECAYED_AT_KEY: &str = "decayed_at";
+
+/// Custom metadata key marking a memory as archived (RFC 3339)
+///
+/// Archived memories are kept in storage but no longer decayed or recalled.
+pub const ARCHIVED_AT_KEY: &str = "archived_at";
+
+/// Importance never decays below this floor
+pub const MIN_IMPORTANCE: f32 = 0.01;
+
+/// How importance decays over time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DecayFunction {
+    /// `importance * e^(-rate * days)`
+    Exponential {
+        /// Decay rate per day
+        rate: f64,
+    },
+    /// Exponential decay slowed by recall
+    ///
+    /// The effective rate is `rate / (1 + boost * ln(1 + access_count))`, so
+    /// each recall lengthens the memory's half-life with diminishing returns.
+    UsageBoosted {
+        /// Decay rate per day of a memory that was never recalled
+        rate: f64,
+        /// How strongly recall slows decay
+        boost: f64,
+    },
+}
+
+impl Default for DecayFunction {
+    fn default() -> Self {
+        Self::Exponential { rate: 0.1 }
+    }
+}
+
+impl DecayFunction {
+    /// Base decay rate per day
+    #[must_use]
+    pub fn rate(&self) -> f64 {
+        match self {
+            Self::Exponential { rate } | Self::UsageBoosted { rate, .. } => *rate,
+        }
+    }
+
+    /// The same function with a different base rate
+    #[must_use]
+    pub fn with_rate(self, rate: f64) -> Self {
+        match self {
+            Self::Exponential { .. } => Self::Exponential { rate },
+            Self::UsageBoosted { boost, .. } => Self::UsageBoosted { rate, boost },
+        }
+    }
+
+    /// Decay rate per day of a memory recalled `access_count` times
+    #[must_use]
+    pub fn effective_rate(&self, access_count: u64) -> f64 {
+        match self {
+            Self::Exponential { rate } => *rate,
+            Self::UsageBoosted { rate, boost } => {
+                rate / (1.0 + boost * (access_count as f64).ln_1p())
+            }
+        }
+    }
+
+    /// Multiplier applied to importance after `days` without decay
+    #[must_use]
+    pub fn factor(&self, days: f64, access_count: u64) -> f64 {
+        (-self.effective_rate(access_count) * days.max(0.0)).exp()
+    }
+
+    /// Importance after decaying for `days`, clamped to [`MIN_IMPORTANCE`]
+    #[must_use]
+    pub fn decay(&self, importance: f32, days: f64, access_count: u64) -> f32 {
+        ((f64::from(importance) * self.factor(days, access_count)) as f32).max(MIN_IMPORTANCE)
+    }
+}
+
+/// What happens to a memory that has been forgotten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionAction {
+    /// Keep the memory in storage but stop decaying and recalling it
+    #[default]
+    Archive,
+    /// Delete the memory
+    Delete,
+}
+
+/// When the decay worker forgets a memory
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompactionPolicy {
+    /// Whether low-relevance memories are compacted at all
+    pub enabled: bool,
+    /// Archive or delete forgotten memories
+    pub action: CompactionAction,
+    /// Memories whose decayed importance falls below this are forgotten
+    pub relevance_threshold: f32,
+    /// Memories younger than this are never forgotten (days)
+    pub min_age_days: f64,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: CompactionAction::Archive,
+            relevance_threshold: 0.05,
+            min_age_days: 30.0,
+        }
+    }
+}
+
+impl CompactionPolicy {
+    /// Action to take for a memory with `relevance` that is `age_days` old
+    #[must_use]
+    pub fn action_for(&self, relevance: f32, age_days: f64) -> Option<CompactionAction> {
+        (self.enabled && age_days >= self.min_age_days && relevance < self.relevance_threshold)
+            .then_some(self.action)
+    }
+}
+
+/// How often a memory was recalled, from its custom metadata
+#[must_use]
+pub fn access_count(memory: &MemoryNode) -> u64 {
+    memory
+        .metadata
+        .custom
+        .get(ACCESS_COUNT_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+/// Whether the decay worker has archived a memory
+#[must_use]
+pub fn is_archived(memory: &MemoryNode) -> bool {
+    memory.metadata.custom.contains_key(ARCHIVED_AT_KEY)
+}
@@ -5,8 +5,9 @@
 //! 2. Query batch of memories using cursor pagination
 //! 3. Apply temporal decay to memory nodes (importance + quantum coherence)
 //! 4. Query and decay associated entanglement/causal edges
-//! 5. Persist changes to SurrealDB
-//! 6. Repeat with next batch
+//! 5. Archive or delete memories that decayed below the compaction threshold
+//! 6. Persist changes to SurrealDB
+//! 7. Repeat with next batch
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -20,6 +21,7 @@ use crate::memory::core::manager::surreal::trait_def::MemoryManager;
 use crate::memory::utils::Result;
 
 use super::config::DecayWorkerConfig;
+use super::policy::{ARCHIVED_AT_KEY, CompactionAction, is_archived};
 
 /// Background worker for temporal decay processing
 #[derive(Debug)]
@@ -131,7 +133,7 @@ impl DecayWorker {
         Ok(processed_count)
     }
 
-    /// Process a single memory: apply decay to node and edges
+    /// Process a single memory: apply decay to node and edges, then compact
     async fn process_memory(
         &self,
         memory_node: &crate::memory::core::primitives::node::MemoryNode,
@@ -141,10 +143,48 @@ impl DecayWorker {
             .coordinator
             .convert_memory_to_domain_node(memory_node)?;
 
-        self.coordinator
+        // Archived memories are kept as they are
+        if is_archived(&domain_memory) {
+            return Ok(());
+        }
+
+        let relevance = self
+            .coordinator
             .apply_temporal_decay(&mut domain_memory)
             .await?;
 
+        // Step 2: Forget memories that decayed below the compaction threshold
+        let age_days = Utc::now()
+            .signed_duration_since(memory_node.created_at)
+            .num_seconds() as f64
+            / 86400.0;
+        match self.config.compaction.action_for(relevance, age_days) {
+            Some(CompactionAction::Delete) => {
+                self.coordinator
+                    .surreal_manager
+                    .delete_memory(&memory_node.id)
+                    .await?;
+                log::debug!(
+                    "Deleted memory {} (relevance {:.3})",
+                    memory_node.id,
+                    relevance
+                );
+                return Ok(());
+            }
+            Some(CompactionAction::Archive) => {
+                domain_memory.set_custom_metadata(
+                    ARCHIVED_AT_KEY,
+                    serde_json::Value::from(Utc::now().to_rfc3339()),
+                );
+                log::debug!(
+                    "Archived memory {} (relevance {:.3})",
+                    memory_node.id,
+                    relevance
+                );
+            }
+            None => {}
+        }
+
         // Convert back and persist
         let updated_memory = self
             .coordinator
@@ -154,10 +194,10 @@ impl DecayWorker {
             .update_memory(updated_memory)
             .await?;
 
-        // Step 2: Apply decay to entanglement edges
+        // Step 3: Apply decay to entanglement edges
         self.decay_entanglement_edges(&memory_node.id).await?;
 
-        // Step 3: Apply decay to causal edges
+        // Step 4: Apply decay to causal edges
         self.decay_causal_edges(&memory_node.id).await?;
 
         Ok(())
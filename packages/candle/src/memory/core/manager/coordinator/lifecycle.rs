@@ -11,6 +11,7 @@ use crate::domain::memory::cognitive::types::CognitiveState;
 use crate::memory::cognitive::committee::ModelCommitteeEvaluator;
 use crate::memory::cognitive::quantum::{QuantumRouter, QuantumState};
 use crate::memory::core::cognitive_queue::CognitiveProcessingQueue;
+use crate::memory::core::decay_worker::{DecayFunction, DecayWorker, DecayWorkerConfig};
 use crate::memory::core::manager::surreal::SurrealDBMemoryManager;
 use crate::memory::repository::MemoryRepository;
 use crate::memory::utils::{Error, Result};
@@ -38,7 +39,7 @@ pub struct MemoryCoordinator {
     pub(super) lazy_eval_strategy: LazyEvalStrategy,
     pub(super) evaluation_cache: Cache<String, f64>,
     // TEMPORAL DECAY:
-    pub(in crate::memory::core) decay_rate: f64,
+    pub(in crate::memory::core) decay_function: DecayFunction,
     pub(super) decay_shutdown_tx: Option<tokio::sync::watch::Sender<bool>>,
 }
 
@@ -47,6 +48,20 @@ impl MemoryCoordinator {
     pub async fn new(
         surreal_manager: Arc<SurrealDBMemoryManager>,
         embedding_model: TextEmbeddingModel,
+    ) -> Result<Self> {
+        Self::with_decay_config(
+            surreal_manager,
+            embedding_model,
+            DecayWorkerConfig::default(),
+        )
+        .await
+    }
+
+    /// Create a coordinator whose decay worker uses `decay_config`
+    pub async fn with_decay_config(
+        surreal_manager: Arc<SurrealDBMemoryManager>,
+        embedding_model: TextEmbeddingModel,
+        decay_config: DecayWorkerConfig,
     ) -> Result<Self> {
         // Initialize committee evaluator with error handling
         // Note: ModelCommitteeEvaluator::new() is async and returns Result<Self, CognitiveError>
@@ -81,6 +96,13 @@ impl MemoryCoordinator {
 
         log::info!("Started {} cognitive worker tasks", num_workers);
 
+        // Ephemeral memories never outlive the process that created them
+        match surreal_manager.purge_ephemeral().await {
+            Ok(0) => {}
+            Ok(purged) => log::info!("Purged {} ephemeral memories from a previous run", purged),
+            Err(e) => log::warn!("Failed to purge ephemeral memories: {:?}", e),
+        }
+
         // Load entanglement graph from database into memory (prebuilt graph pattern)
         // This enables entanglement boost during search without query overhead
         let entanglement_links = match surreal_manager.load_all_entanglement_edges().await {
@@ -126,19 +148,13 @@ impl MemoryCoordinator {
                 .max_capacity(10_000)
                 .time_to_live(Duration::from_secs(300))
                 .build(),
-            decay_rate: 0.1,
+            decay_function: decay_config.function,
             decay_shutdown_tx: Some(shutdown_tx),
         };
 
         // Spawn decay worker for background temporal decay processing
         let coordinator_arc = Arc::new(coordinator);
-        let decay_config = crate::memory::core::decay_worker::DecayWorkerConfig::default();
-
-        let decay_worker = crate::memory::core::decay_worker::DecayWorker::new(
-            coordinator_arc.clone(),
-            decay_config,
-            shutdown_rx,
-        );
+        let decay_worker = DecayWorker::new(coordinator_arc.clone(), decay_config, shutdown_rx);
 
         tokio::spawn(async move {
             log::info!("Decay worker started");
@@ -167,14 +183,19 @@ impl MemoryCoordinator {
                 "Decay rate must be between 0.0 and 1.0".into(),
             ));
         }
-        self.decay_rate = rate;
+        self.decay_function = self.decay_function.with_rate(rate);
         log::info!("Temporal decay rate updated to {}", rate);
         Ok(())
     }
 
     /// Get current decay rate
     pub fn get_decay_rate(&self) -> f64 {
-        self.decay_rate
+        self.decay_function.rate()
+    }
+
+    /// Get the function used to decay memory importance
+    pub fn decay_function(&self) -> DecayFunction {
+        self.decay_function
     }
 
     /// Shutdown all cognitive worker tasks gracefully
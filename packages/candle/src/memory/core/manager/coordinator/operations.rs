@@ -6,7 +6,9 @@ use std::time::{Duration, Instant};
 use crate::domain::memory::primitives::node::MemoryNode;
 use crate::memory::MemoryMetadata;
 use crate::memory::core::cognitive_queue::{CognitiveTask, CognitiveTaskType};
+use crate::memory::core::decay_worker::{ACCESS_COUNT_KEY, access_count};
 use crate::memory::core::manager::surreal::trait_def::MemoryManager;
+use crate::memory::core::privacy::PRIVACY_KEY;
 use crate::memory::utils::Result;
 
 use super::lifecycle::MemoryCoordinator;
@@ -57,6 +59,18 @@ impl MemoryCoordinator {
 
             // Refresh last_accessed_at to mark as recently seen
             domain_memory.stats.record_read();
+            domain_memory.set_custom_metadata(
+                ACCESS_COUNT_KEY,
+                serde_json::Value::from(access_count(&domain_memory) + 1),
+            );
+
+            // Re-adding content with an explicit privacy tier re-tiers it
+            if let Some(privacy) = metadata
+                .as_ref()
+                .and_then(|metadata| metadata.custom.get(PRIVACY_KEY))
+            {
+                domain_memory.set_custom_metadata(PRIVACY_KEY, privacy.clone());
+            }
 
             // Update importance to reflect re-occurrence (boost by 10%)
             let current_importance = domain_memory.importance();
@@ -108,6 +122,22 @@ impl MemoryCoordinator {
                 Arc::new(serde_json::Value::String(metadata.context.clone())),
             );
 
+            if let Some(ref source) = metadata.source {
+                custom_map.insert(
+                    Arc::from("source"),
+                    Arc::new(serde_json::Value::String(source.clone())),
+                );
+            }
+
+            // Caller-provided custom fields never override the ones above
+            if let Some(custom) = metadata.custom.as_object() {
+                for (key, value) in custom {
+                    custom_map
+                        .entry(Arc::from(key.as_str()))
+                        .or_insert_with(|| Arc::new(value.clone()));
+                }
+            }
+
             // Apply metadata
             domain_memory.metadata = Arc::new(
                 crate::domain::memory::primitives::node::MemoryNodeMetadata {
@@ -1,17 +1,127 @@
 //! Search and retrieval operations for memories
 
+use std::collections::HashMap;
 use std::time::SystemTime;
 
 use futures_util::StreamExt;
 
 use crate::domain::memory::primitives::node::MemoryNode;
+use crate::memory::core::decay_worker::{ACCESS_COUNT_KEY, ARCHIVED_AT_KEY, is_archived};
 use crate::memory::core::manager::surreal::trait_def::MemoryManager;
 use crate::memory::core::ops::filter::MemoryFilter;
+use crate::memory::core::ops::retrieval::augmentation::{
+    CANDIDATE_FACTOR, CONTEXT_ID_KEY, RagConfig, RetrievedMemory, rank_by_similarity,
+};
+use crate::memory::core::privacy::MemoryPrivacy;
 use crate::memory::utils::Result;
 
 use super::lifecycle::MemoryCoordinator;
 
 impl MemoryCoordinator {
+    /// Retrieve memories relevant to `query` for prompt augmentation
+    ///
+    /// Embeds the query, fetches `top_k * CANDIDATE_FACTOR` vector candidates
+    /// from SurrealDB and re-scores them with SIMD cosine similarity, keeping
+    /// the best `top_k` at or above the relevance threshold. Archived
+    /// memories are skipped, as are sensitive ones unless `config` permits
+    /// them, and each recalled memory's access count is
+    /// bumped in the background so usage-boosted decay keeps it longer.
+    pub async fn retrieve_relevant(
+        &self,
+        query: &str,
+        config: &RagConfig,
+    ) -> Result<Vec<RetrievedMemory>> {
+        if !config.is_enabled() || query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_embedding = self.generate_embedding(query).await?;
+        let candidates: Vec<_> = self
+            .surreal_manager
+            .search_by_vector(query_embedding.clone(), config.top_k * CANDIDATE_FACTOR)
+            .collect()
+            .await;
+
+        let mut nodes = HashMap::new();
+        let scored = candidates
+            .into_iter()
+            .filter_map(|candidate| match candidate {
+                Ok(node) => {
+                    if node.metadata.custom.get(ARCHIVED_AT_KEY).is_some() {
+                        return None;
+                    }
+                    if !MemoryPrivacy::of(&node).is_injected(config.include_sensitive) {
+                        return None;
+                    }
+                    let embedding = node.embedding.clone()?;
+                    nodes.insert(node.id.clone(), node.clone());
+                    let source = node.metadata.source.clone().or_else(|| {
+                        node.metadata
+                            .custom
+                            .get("source")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    });
+                    let context = node
+                        .metadata
+                        .custom
+                        .get(CONTEXT_ID_KEY)
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string);
+                    let memory = RetrievedMemory {
+                        id: node.id,
+                        content: node.content.text,
+                        source,
+                        memory_type: node.memory_type.to_string(),
+                        relevance: 0.0,
+                        context,
+                    };
+                    Some((memory, embedding))
+                }
+                Err(e) => {
+                    log::warn!("Failed to retrieve memory candidate: {}", e);
+                    None
+                }
+            });
+
+        let retrieved: Vec<RetrievedMemory> = rank_by_similarity(&query_embedding, scored, config)
+            .into_iter()
+            .map(|(memory, relevance)| RetrievedMemory {
+                relevance,
+                ..memory
+            })
+            .collect();
+
+        let recalled: Vec<_> = retrieved
+            .iter()
+            .filter_map(|memory| nodes.remove(&memory.id))
+            .collect();
+        let surreal_manager = self.surreal_manager.clone();
+        tokio::spawn(async move {
+            for mut node in recalled {
+                let count = node
+                    .metadata
+                    .custom
+                    .get(ACCESS_COUNT_KEY)
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                if node
+                    .metadata
+                    .set_custom(ACCESS_COUNT_KEY, count + 1)
+                    .is_err()
+                {
+                    continue;
+                }
+                node.update_last_accessed();
+                if let Err(e) = surreal_manager.update_memory(node).await {
+                    log::warn!("Failed to record memory access: {}", e);
+                }
+            }
+        });
+
+        Ok(retrieved)
+    }
+
     /// Search memories by content using vector similarity
     ///
     /// This method:
@@ -165,6 +275,9 @@ impl MemoryCoordinator {
             match memory_result {
                 Ok(memory_node) => {
                     let domain_memory = self.convert_memory_to_domain_node(&memory_node)?;
+                    if is_archived(&domain_memory) {
+                        continue;
+                    }
                     result_memories.push(domain_memory);
                 }
                 Err(e) => {
@@ -1,5 +1,6 @@
 //! Temporal decay operations for memory importance
 
+use crate::memory::core::decay_worker::{DECAYED_AT_KEY, access_count};
 use crate::memory::utils::{Error, Result};
 
 use super::lifecycle::MemoryCoordinator;
@@ -7,48 +8,48 @@ use super::lifecycle::MemoryCoordinator;
 impl MemoryCoordinator {
     /// Apply temporal decay to memory importance based on age
     ///
-    /// This method calculates exponential decay based on memory age and updates:
-    /// - Memory importance score (reduced over time)
-    /// - Quantum coherence level (simulating decoherence)
+    /// Importance decays over the time since decay was last applied (or since
+    /// creation), using the configured decay function and the memory's recall
+    /// count. Returns the decayed importance.
     pub(in crate::memory::core) async fn apply_temporal_decay(
         &self,
         memory: &mut crate::domain::memory::primitives::node::MemoryNode,
-    ) -> Result<()> {
-        // Convert SystemTime to DateTime<Utc> for calculation
-        let created_time = chrono::DateTime::<chrono::Utc>::from(memory.base_memory.created_at);
+    ) -> Result<f32> {
         let now = chrono::Utc::now();
+        let created_time = chrono::DateTime::<chrono::Utc>::from(memory.base_memory.created_at);
+        let decayed_at = memory
+            .metadata
+            .custom
+            .get(DECAYED_AT_KEY)
+            .and_then(|v| v.as_str())
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .map_or(created_time, |t| t.with_timezone(&chrono::Utc));
+
+        // Days since decay was last applied, with fractional precision
+        let days_elapsed = now.signed_duration_since(decayed_at).num_seconds() as f64 / 86400.0;
+        let access_count = access_count(memory);
 
-        // Calculate age of memory
-        let age = now.signed_duration_since(created_time);
-
-        // Calculate days old with fractional precision
-        let days_old = age.num_seconds() as f64 / 86400.0; // seconds per day
-
-        // Apply exponential decay: e^(-decay_rate * days)
-        // Using self.decay_rate for configurability
-        let decay = (-self.decay_rate * days_old).exp();
-
-        // Apply decay to importance (ensure it doesn't go below minimum threshold)
         let current_importance = memory.importance();
-        let new_importance = (current_importance * decay as f32).max(0.01); // Minimum threshold 0.01
+        let new_importance =
+            self.decay_function
+                .decay(current_importance, days_elapsed, access_count);
 
         // Update memory importance using existing setter
         memory
             .set_importance(new_importance)
             .map_err(|e| Error::Internal(format!("Failed to set decayed importance: {}", e)))?;
-
-        // Update last_accessed_at in metadata to track access patterns
-        memory.stats.record_read();
+        memory.set_custom_metadata(DECAYED_AT_KEY, serde_json::Value::from(now.to_rfc3339()));
 
         log::trace!(
-            "Temporal decay applied to {}: importance {} -> {} (age: {:.2} days)",
+            "Temporal decay applied to {}: importance {} -> {} ({:.2} days, {} recalls)",
             memory.id(),
             current_importance,
             new_importance,
-            days_old
+            days_elapsed,
+            access_count
         );
 
-        Ok(())
+        Ok(new_importance)
     }
 
     /// Apply temporal decay to core memory node (for internal use)
@@ -67,11 +68,10 @@ impl MemoryCoordinator {
         // Calculate days old with fractional precision
         let days_old = age.num_seconds() as f64 / 86400.0;
 
-        // Apply exponential decay
-        let decay = (-self.decay_rate * days_old).exp();
-
         // Apply decay to importance
-        memory.metadata.importance = (memory.metadata.importance as f64 * decay).max(0.01) as f32;
+        memory.metadata.importance =
+            self.decay_function
+                .decay(memory.metadata.importance, days_old, 0);
 
         // Update last_accessed_at
         memory.metadata.last_accessed_at = Some(now);
@@ -9,6 +9,7 @@ use surrealdb::Surreal;
 use surrealdb::engine::any::Any;
 
 use crate::capability::registry::TextEmbeddingModel;
+use crate::memory::core::privacy::{self, MemoryCipher, MemoryPrivacy};
 use crate::memory::migration::{
     BuiltinMigrations, DataExporter, DataImporter, ExportFormat, ImportFormat, MigrationManager,
 };
@@ -25,6 +26,7 @@ use super::types::ExportData;
 pub struct SurrealDBMemoryManager {
     pub(in crate::memory::core) db: Surreal<Any>,
     pub(super) embedding_model: Option<TextEmbeddingModel>,
+    pub(super) cipher: Option<Arc<MemoryCipher>>,
 }
 
 impl SurrealDBMemoryManager {
@@ -33,6 +35,7 @@ impl SurrealDBMemoryManager {
         Self {
             db,
             embedding_model: None,
+            cipher: None,
         }
     }
 
@@ -41,6 +44,7 @@ impl SurrealDBMemoryManager {
         Self {
             db,
             embedding_model: Some(embedding_model),
+            cipher: None,
         }
     }
 
@@ -49,9 +53,25 @@ impl SurrealDBMemoryManager {
         Self {
             db,
             embedding_model: Some((*embedding_model).clone()),
+            cipher: None,
         }
     }
 
+    /// Seal sensitive memories with `cipher` before they are stored
+    ///
+    /// Without a cipher, storing a sensitive memory fails and sealed memories
+    /// cannot be read.
+    #[must_use]
+    pub fn with_cipher(mut self, cipher: Arc<MemoryCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Whether sensitive memories can be stored
+    pub fn has_cipher(&self) -> bool {
+        self.cipher.is_some()
+    }
+
     /// Get a reference to the underlying database connection
     pub fn database(&self) -> &Surreal<Any> {
         &self.db
@@ -216,6 +236,9 @@ impl SurrealDBMemoryManager {
     }
 
     /// Export all memories and relationships to a file
+    ///
+    /// Ephemeral and sensitive memories are left out, along with the
+    /// relationships that reference them.
     pub async fn export_memories(&self, path: &Path, format: ExportFormat) -> Result<()> {
         // Fetch all memories
         let query = "SELECT * FROM memory";
@@ -229,7 +252,14 @@ impl SurrealDBMemoryManager {
             .take(0)
             .map_err(|e| Error::Database(format!("Failed to parse memories: {:?}", e)))?;
 
-        let memories: Vec<MemoryNode> = memory_schemas.into_iter().map(Self::from_schema).collect();
+        let (memories, redacted): (Vec<MemoryNode>, Vec<MemoryNode>) = memory_schemas
+            .into_iter()
+            .map(|schema| Self::from_schema(schema, self.cipher.as_deref()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .partition(|memory| MemoryPrivacy::of(memory).is_exported(false));
+        let redacted: std::collections::HashSet<String> =
+            redacted.into_iter().map(|memory| memory.id).collect();
 
         // Fetch all relationships
         let query = "SELECT * FROM relationship";
@@ -240,8 +270,14 @@ impl SurrealDBMemoryManager {
             .map_err(|e| Error::Database(format!("Export query failed: {:?}", e)))?;
 
         let relationships: Vec<MemoryRelationship> = response
-            .take(0)
-            .map_err(|e| Error::Database(format!("Failed to parse relationships: {:?}", e)))?;
+            .take::<Vec<MemoryRelationship>>(0)
+            .map_err(|e| Error::Database(format!("Failed to parse relationships: {:?}", e)))?
+            .into_iter()
+            .filter(|relationship| {
+                !redacted.contains(&relationship.source_id)
+                    && !redacted.contains(&relationship.target_id)
+            })
+            .collect();
 
         // Create export data structure
         let export_data = ExportData {
@@ -309,7 +345,8 @@ impl SurrealDBMemoryManager {
         // Insert memories
         for memory in import_data.memories {
             // Use CREATE to insert with explicit ID
-            let content = super::types::MemoryNodeCreateContent::from(&memory);
+            let content =
+                super::types::MemoryNodeCreateContent::new(&memory, self.cipher.as_deref())?;
 
             let query = "
                 CREATE memory CONTENT {
@@ -370,8 +407,11 @@ impl SurrealDBMemoryManager {
         Ok(())
     }
 
-    /// Convert SurrealDB schema to domain MemoryNode
-    pub(super) fn from_schema(schema: MemoryNodeSchema) -> MemoryNode {
+    /// Convert SurrealDB schema to domain MemoryNode, opening sealed content
+    pub(super) fn from_schema(
+        schema: MemoryNodeSchema,
+        cipher: Option<&MemoryCipher>,
+    ) -> Result<MemoryNode> {
         use crate::memory::core::primitives::metadata::MemoryMetadata;
         use crate::memory::core::primitives::types::MemoryContent;
         use crate::memory::monitoring::operations::OperationStatus;
@@ -385,9 +425,11 @@ impl SurrealDBMemoryManager {
         metadata.embedding = schema.metadata.embedding.clone();
         metadata.custom = schema.metadata.custom.clone();
 
-        MemoryNode {
+        let content = privacy::content_from_rest(&schema.content, cipher)?;
+
+        Ok(MemoryNode {
             id: id_str,
-            content: MemoryContent::new(&schema.content),
+            content: MemoryContent::new(&content),
             content_hash: schema.content_hash,
             memory_type: schema.memory_type,
             created_at: schema.metadata.created_at,
@@ -396,6 +438,6 @@ impl SurrealDBMemoryManager {
             evaluation_status: OperationStatus::Success,
             metadata,
             relevance_score: None,
-        }
+        })
     }
 }
@@ -25,6 +25,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn create_memory(&self, memory: MemoryNode) -> PendingMemory {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let embedding_model = self.embedding_model.clone();
 
         tokio::spawn(async move {
@@ -42,7 +43,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     memory_with_embedding.metadata.embedding = Some(embedding);
                 }
 
-                let content = MemoryNodeCreateContent::from(&memory_with_embedding);
+                let content =
+                    MemoryNodeCreateContent::new(&memory_with_embedding, cipher.as_deref())?;
 
                 let query = "
                     CREATE memory CONTENT {
@@ -75,8 +77,10 @@ impl MemoryManager for SurrealDBMemoryManager {
                 result
                     .into_iter()
                     .next()
-                    .map(SurrealDBMemoryManager::from_schema)
                     .ok_or_else(|| Error::Other("Failed to create memory".to_string()))
+                    .and_then(|schema| {
+                        SurrealDBMemoryManager::from_schema(schema, cipher.as_deref())
+                    })
             }
             .await;
 
@@ -89,6 +93,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn get_memory(&self, id: &str) -> MemoryQuery {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let id = id.to_string();
 
         tokio::spawn(async move {
@@ -105,10 +110,11 @@ impl MemoryManager for SurrealDBMemoryManager {
                     .take(0)
                     .map_err(|e| Error::Database(format!("{:?}", e)))?;
 
-                Ok(results
+                results
                     .into_iter()
                     .next()
-                    .map(SurrealDBMemoryManager::from_schema))
+                    .map(|schema| SurrealDBMemoryManager::from_schema(schema, cipher.as_deref()))
+                    .transpose()
             }
             .await;
 
@@ -121,10 +127,11 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn update_memory(&self, memory: MemoryNode) -> PendingMemory {
         let (tx, rx) = tokio::sync::oneshot::channel();
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let result = async {
-                let content = MemoryNodeCreateContent::from(&memory);
+                let content = MemoryNodeCreateContent::new(&memory, cipher.as_deref())?;
 
                 let query = "
                     UPDATE $id SET
@@ -153,8 +160,10 @@ impl MemoryManager for SurrealDBMemoryManager {
                 result
                     .into_iter()
                     .next()
-                    .map(SurrealDBMemoryManager::from_schema)
                     .ok_or_else(|| Error::Other("Failed to update memory".to_string()))
+                    .and_then(|schema| {
+                        SurrealDBMemoryManager::from_schema(schema, cipher.as_deref())
+                    })
             }
             .await;
 
@@ -196,6 +205,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn search_by_vector(&self, vector: Vec<f32>, limit: usize) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let vector_json = serde_json::to_string(&vector).unwrap_or_default();
@@ -215,8 +225,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -233,6 +243,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn search_by_content(&self, text: &str) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let search_text = text.to_string();
 
         tokio::spawn(async move {
@@ -246,8 +257,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -264,6 +275,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn query_by_type(&self, memory_type: MemoryTypeEnum) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let type_str = format!("{:?}", memory_type);
@@ -277,8 +289,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -295,6 +307,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn list_all_memories(&self, limit: usize, offset: usize) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let query = "SELECT * FROM memory START $offset LIMIT $limit ORDER BY created_at DESC";
@@ -308,8 +321,9 @@ impl MemoryManager for SurrealDBMemoryManager {
                 Ok(mut response) => match response.take::<Vec<MemoryNodeSchema>>(0) {
                     Ok(results) => {
                         for schema in results {
-                            let memory = SurrealDBMemoryManager::from_schema(schema);
-                            if tx.send(Ok(memory)).await.is_err() {
+                            let memory =
+                                SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                            if tx.send(memory).await.is_err() {
                                 break;
                             }
                         }
@@ -627,6 +641,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn get_entangled_memories(&self, memory_id: &str) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let memory_id = memory_id.to_string();
 
         tokio::spawn(async move {
@@ -637,8 +652,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -659,6 +674,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     ) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let memory_id = memory_id.to_string();
         let type_str = format!("{:?}", entanglement_type);
 
@@ -673,8 +689,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -696,6 +712,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     ) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let start_id = start_memory_id.to_string();
 
         tokio::spawn(async move {
@@ -716,8 +733,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -739,6 +756,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     ) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let ids_json = serde_json::to_string(&seed_memory_ids).unwrap_or_default();
@@ -757,8 +775,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -775,6 +793,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn get_causal_predecessors(&self, memory_id: &str) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let memory_id = memory_id.to_string();
 
         tokio::spawn(async move {
@@ -787,8 +806,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -805,6 +824,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn get_causal_successors(&self, memory_id: &str) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let memory_id = memory_id.to_string();
 
         tokio::spawn(async move {
@@ -817,8 +837,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -835,6 +855,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn trace_causal_chain_forward(&self, start_memory_id: &str, max_depth: usize) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let start_id = start_memory_id.to_string();
 
         tokio::spawn(async move {
@@ -853,8 +874,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -871,6 +892,7 @@ impl MemoryManager for SurrealDBMemoryManager {
     fn trace_causal_chain_backward(&self, start_memory_id: &str, max_depth: usize) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
         let start_id = start_memory_id.to_string();
 
         tokio::spawn(async move {
@@ -889,8 +911,8 @@ impl MemoryManager for SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
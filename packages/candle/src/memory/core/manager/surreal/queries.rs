@@ -4,6 +4,7 @@
 //! including hybrid search, text search, metadata querying, and content-based deduplication.
 
 use crate::capability::traits::TextEmbeddingCapable;
+use crate::memory::core::privacy::MemoryPrivacy;
 use crate::memory::primitives::MemoryNode;
 use crate::memory::schema::memory_schema::MemoryNodeSchema;
 use crate::memory::utils::error::Error;
@@ -35,6 +36,7 @@ impl SurrealDBMemoryManager {
     ) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let vector_json = serde_json::to_string(&query_vector).unwrap_or_default();
@@ -101,8 +103,8 @@ impl SurrealDBMemoryManager {
                     );
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -138,6 +140,7 @@ impl SurrealDBMemoryManager {
     ) -> MemoryStream {
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         tokio::spawn(async move {
             let vector_json = serde_json::to_string(&query_vector).unwrap_or_default();
@@ -205,8 +208,8 @@ impl SurrealDBMemoryManager {
                     );
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -242,6 +245,7 @@ impl SurrealDBMemoryManager {
         metadata_filters: std::collections::HashMap<String, serde_json::Value>,
     ) -> Result<MemoryStream> {
         let db = self.db.clone();
+        let cipher = self.cipher.clone();
 
         let (tx, rx) = tokio::sync::mpsc::channel(100);
 
@@ -273,8 +277,8 @@ impl SurrealDBMemoryManager {
                     let results: Vec<MemoryNodeSchema> = response.take(0).unwrap_or_default();
 
                     for schema in results {
-                        let memory = SurrealDBMemoryManager::from_schema(schema);
-                        if tx.send(Ok(memory)).await.is_err() {
+                        let memory = SurrealDBMemoryManager::from_schema(schema, cipher.as_deref());
+                        if tx.send(memory).await.is_err() {
                             break;
                         }
                     }
@@ -304,7 +308,10 @@ impl SurrealDBMemoryManager {
             .take(0)
             .map_err(|e| Error::Database(format!("{:?}", e)))?;
 
-        Ok(results.into_iter().map(Self::from_schema).collect())
+        results
+            .into_iter()
+            .map(|schema| Self::from_schema(schema, self.cipher.as_deref()))
+            .collect()
     }
 
     /// Check if a document exists by content hash
@@ -361,7 +368,11 @@ impl SurrealDBMemoryManager {
             .take(0)
             .map_err(|e| Error::Database(format!("Failed to parse hash query results: {:?}", e)))?;
 
-        Ok(results.into_iter().next().map(Self::from_schema))
+        results
+            .into_iter()
+            .next()
+            .map(|schema| Self::from_schema(schema, self.cipher.as_deref()))
+            .transpose()
     }
 
     /// Update document age/timestamp by content hash
@@ -402,6 +413,31 @@ impl SurrealDBMemoryManager {
         Ok(!results.is_empty())
     }
 
+    /// Delete every ephemeral memory
+    ///
+    /// Ephemeral memories only live for the process that created them, so a
+    /// coordinator purges the ones a previous process left behind at startup.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of memories deleted
+    /// * `Err(Error)` - Database delete failed
+    pub async fn purge_ephemeral(&self) -> Result<usize> {
+        let query = "DELETE memory WHERE metadata.custom.privacy = $privacy RETURN BEFORE";
+
+        let mut response = self
+            .db
+            .query(query)
+            .bind(("privacy", MemoryPrivacy::Ephemeral.as_str()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to purge ephemeral memories: {:?}", e)))?;
+
+        let deleted: Vec<serde_json::Value> = response
+            .take(0)
+            .map_err(|e| Error::Database(format!("Failed to parse purge results: {:?}", e)))?;
+
+        Ok(deleted.len())
+    }
+
     /// Load all entanglement edges from database into memory
     ///
     /// This method queries both `entangled` and `caused` RELATION tables to build
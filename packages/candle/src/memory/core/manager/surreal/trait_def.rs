@@ -4,14 +4,28 @@
 //! providing a unified interface for CRUD operations, search, and
 //! quantum entanglement features.
 
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+
+use futures_util::StreamExt;
+
 use crate::domain::memory::cognitive::types::{CognitiveState, EntanglementType};
+use crate::memory::core::privacy::MemoryPrivacy;
+use crate::memory::migration::transfer::{
+    self, MemoryExportFormat, MemoryImportReport, MemoryRecord,
+};
 use crate::memory::primitives::{MemoryNode, MemoryRelationship};
+use crate::memory::utils::Result;
 
 use super::futures::{
     MemoryQuery, MemoryStream, PendingDeletion, PendingEntanglementEdge, PendingMemory,
     PendingQuantumSignature, PendingQuantumUpdate, PendingRelationship, RelationshipStream,
 };
 
+/// Memories fetched per page while exporting
+const EXPORT_PAGE_SIZE: usize = 500;
+
 /// Core memory management trait defining operations for storing, retrieving, and managing memory nodes
 pub trait MemoryManager: Send + Sync {
     // === Core Memory CRUD Operations ===
@@ -127,6 +141,96 @@ pub trait MemoryManager: Send + Sync {
 
     /// Traverse causal chain backward to find root causes
     fn trace_causal_chain_backward(&self, start_memory_id: &str, max_depth: usize) -> MemoryStream;
+
+    // === Import / Export ===
+
+    /// Export every memory, embeddings included, to `path`
+    ///
+    /// The file is stamped with the current export format version so later
+    /// versions can migrate it on import. Ephemeral and sensitive memories are
+    /// left out. Resolves to the number of exported memories.
+    fn export_memories<'a>(
+        &'a self,
+        path: &'a Path,
+        format: MemoryExportFormat,
+    ) -> Pin<Box<dyn Future<Output = Result<usize>> + Send + 'a>> {
+        self.export_memories_with(path, format, false)
+    }
+
+    /// Export memories like [`export_memories`](Self::export_memories),
+    /// including sensitive memories in plain text when `include_sensitive`
+    /// is set
+    ///
+    /// Ephemeral memories are never exported.
+    fn export_memories_with<'a>(
+        &'a self,
+        path: &'a Path,
+        format: MemoryExportFormat,
+        include_sensitive: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<usize>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut records = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page: Vec<Result<MemoryNode>> = self
+                    .list_all_memories(EXPORT_PAGE_SIZE, offset)
+                    .collect()
+                    .await;
+                let page_len = page.len();
+                offset += page_len;
+                for memory in page {
+                    let memory = memory?;
+                    if MemoryPrivacy::of(&memory).is_exported(include_sensitive) {
+                        records.push(MemoryRecord::from_node(&memory)?);
+                    }
+                }
+                if page_len < EXPORT_PAGE_SIZE {
+                    break;
+                }
+            }
+
+            let count = records.len();
+            transfer::write_records(path, format, records).await?;
+            log::info!("Exported {} memories to {}", count, path.display());
+            Ok(count)
+        })
+    }
+
+    /// Import a memory export written by [`export_memories`](Self::export_memories)
+    ///
+    /// Older format versions are migrated forward. Memories keep their ids,
+    /// so importing into a store that already holds them overwrites them.
+    fn import_memories<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<MemoryImportReport>> + Send + 'a>> {
+        Box::pin(async move {
+            let (source_version, records) = transfer::read_records(path).await?;
+            let mut report = MemoryImportReport {
+                source_version,
+                ..MemoryImportReport::default()
+            };
+
+            for record in records {
+                let memory = record.into_node()?;
+                if self.get_memory(&memory.id).await?.is_some() {
+                    self.update_memory(memory).await?;
+                    report.updated += 1;
+                } else {
+                    self.create_memory(memory).await?;
+                    report.created += 1;
+                }
+            }
+
+            log::info!(
+                "Imported {} memories from {} (format version {})",
+                report.created + report.updated,
+                path.display(),
+                source_version
+            );
+            Ok(report)
+        })
+    }
 }
 
 // Blanket implementation for Arc<T> to enable trait methods on Arc-wrapped managers
@@ -5,11 +5,14 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::memory::core::privacy::{self, MemoryCipher};
 use crate::memory::primitives::MemoryNode;
 use crate::memory::primitives::MemoryRelationship;
 use crate::memory::primitives::types::MemoryTypeEnum;
 use crate::memory::schema::memory_schema::MemoryMetadataSchema;
 
+use super::Result;
+
 /// Content structure for creating/updating memory nodes (without ID)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct MemoryNodeCreateContent {
@@ -19,10 +22,11 @@ pub(super) struct MemoryNodeCreateContent {
     pub metadata: MemoryMetadataSchema,
 }
 
-impl From<&MemoryNode> for MemoryNodeCreateContent {
-    fn from(memory: &MemoryNode) -> Self {
-        Self {
-            content: memory.content.text.clone(),
+impl MemoryNodeCreateContent {
+    /// Row content for `memory`, sealing sensitive content with `cipher`
+    pub(super) fn new(memory: &MemoryNode, cipher: Option<&MemoryCipher>) -> Result<Self> {
+        Ok(Self {
+            content: privacy::content_at_rest(memory, cipher)?,
             content_hash: memory.content_hash,
             memory_type: memory.memory_type,
             metadata: MemoryMetadataSchema {
@@ -35,7 +39,7 @@ impl From<&MemoryNode> for MemoryNodeCreateContent {
                 embedding: memory.metadata.embedding.clone(),
                 custom: memory.metadata.custom.clone(),
             },
-        }
+        })
     }
 }
 
@@ -7,6 +7,7 @@ pub mod decay_worker;
 pub mod manager;
 pub mod ops;
 pub mod primitives;
+pub mod privacy;
 pub mod schema;
 pub mod systems;
 
@@ -29,6 +30,7 @@ pub use ops::filter::{MemoryFilter, MemoryFilterBuilder, TimeRange}; /* Keep ops
 // Main operations types - explicit to avoid conflicts
 pub use ops::query::{MemoryQuery, MemoryQueryExecutor, MemoryQueryResult, SortOrder}; /* Keep ops::MemoryQuery as primary */
 pub use ops::repository;
+pub use ops::retrieval::{RagConfig, RetrievedMemory};
 pub use ops::storage;
 pub use primitives::metadata::MemoryMetadata;
 // Alias the conflicting primitives types
@@ -39,6 +41,8 @@ pub use primitives::metadata::{
 pub use primitives::node::MemoryNode;
 pub use primitives::relationship::MemoryRelationship;
 pub use primitives::types::{BaseMemory, MemoryContent, MemoryTypeEnum, RelationshipType};
+// Privacy tiers and encryption at rest
+pub use privacy::{MemoryCipher, MemoryPrivacy};
 
 // Type alias for backward compatibility
 pub type MemoryType = MemoryTypeEnum;
@@ -49,4 +53,6 @@ pub use systems::*;
 pub use cognitive_queue::{CognitiveProcessingQueue, CognitiveTask, CognitiveTaskType};
 pub use cognitive_worker::CognitiveWorker;
 // Decay worker exports
-pub use decay_worker::{DecayWorker, DecayWorkerConfig};
+pub use decay_worker::{
+    CompactionAction, CompactionPolicy, DecayFunction, DecayWorker, DecayWorkerConfig,
+};
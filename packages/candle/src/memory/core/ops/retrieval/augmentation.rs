@@ -0,0 +1,207 @@
+//! Retrieval-augmented context injection
+//!
+//! Before each chat turn the user message is embedded, vector candidates are
+//! fetched from the memory store, and each candidate is re-scored against the
+//! query with SIMD cosine similarity. Memories above the relevance threshold
+//! are injected into the prompt with provenance markers until the token
+//! budget is spent. Memories indexed from a context source can additionally
+//! be held to that context's own token budget. Sensitive memories are only
+//! injected when the configuration explicitly permits them.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::estimate_tokens;
+
+/// Candidates fetched from the store per requested memory
+pub const CANDIDATE_FACTOR: usize = 3;
+
+/// Heading of the injected context block
+const CONTEXT_HEADING: &str = "## Retrieved Memories";
+
+/// Custom metadata key naming the indexed context a memory was chunked from
+pub const CONTEXT_ID_KEY: &str = "context_id";
+
+/// Retrieval-augmented generation settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RagConfig {
+    /// Maximum memories injected per turn (0 disables retrieval)
+    pub top_k: usize,
+    /// Minimum cosine similarity for a memory to be injected
+    pub min_relevance: f32,
+    /// Prompt tokens available for injected memories
+    pub token_budget: usize,
+    /// Whether memories in the sensitive privacy tier may be injected
+    #[serde(default)]
+    pub include_sensitive: bool,
+}
+
+impl Default for RagConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            min_relevance: 0.5,
+            token_budget: 512,
+            include_sensitive: false,
+        }
+    }
+}
+
+impl RagConfig {
+    /// Configuration that never retrieves
+    pub fn disabled() -> Self {
+        Self {
+            top_k: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Check if retrieval runs at all
+    pub fn is_enabled(&self) -> bool {
+        self.top_k > 0 && self.token_budget > 0
+    }
+
+    /// Set the maximum number of injected memories
+    #[must_use]
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Set the minimum cosine similarity, clamped to [-1, 1]
+    #[must_use]
+    pub fn with_min_relevance(mut self, min_relevance: f32) -> Self {
+        self.min_relevance = min_relevance.clamp(-1.0, 1.0);
+        self
+    }
+
+    /// Set the token budget for injected memories
+    #[must_use]
+    pub fn with_token_budget(mut self, token_budget: usize) -> Self {
+        self.token_budget = token_budget;
+        self
+    }
+
+    /// Permit or forbid injecting sensitive memories
+    #[must_use]
+    pub fn with_sensitive(mut self, include_sensitive: bool) -> Self {
+        self.include_sensitive = include_sensitive;
+        self
+    }
+}
+
+/// A memory selected for injection, with its provenance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievedMemory {
+    /// Memory record id
+    pub id: String,
+    /// Memory text
+    pub content: String,
+    /// Where the memory came from (file, conversation, ...)
+    pub source: Option<String>,
+    /// Memory type name
+    pub memory_type: String,
+    /// Cosine similarity to the query
+    pub relevance: f32,
+    /// Indexed context the memory was chunked from, if any
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+/// Score candidates against the query embedding and keep the best matches
+///
+/// Candidates whose embedding dimension differs from the query are skipped.
+/// The result is sorted by descending relevance and holds at most
+/// `config.top_k` entries at or above `config.min_relevance`.
+pub fn rank_by_similarity<T>(
+    query_embedding: &[f32],
+    candidates: impl IntoIterator<Item = (T, Vec<f32>)>,
+    config: &RagConfig,
+) -> Vec<(T, f32)> {
+    let mut ranked: Vec<(T, f32)> = candidates
+        .into_iter()
+        .filter(|(_, embedding)| !embedding.is_empty() && embedding.len() == query_embedding.len())
+        .map(|(candidate, embedding)| {
+            let relevance = cyrup_simd::cosine_similarity(query_embedding, &embedding);
+            (candidate, relevance)
+        })
+        .filter(|(_, relevance)| relevance.is_finite() && *relevance >= config.min_relevance)
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked.truncate(config.top_k);
+    ranked
+}
+
+/// Drop memories of a context once that context's token budget is spent
+///
+/// `budgets` maps context ids to the prompt tokens their memories may use.
+/// Memories keep their order; those from contexts without a budget, or from
+/// no context at all, are always kept.
+pub fn apply_context_budgets<S: BuildHasher>(
+    memories: Vec<RetrievedMemory>,
+    budgets: &HashMap<String, usize, S>,
+) -> Vec<RetrievedMemory> {
+    if budgets.is_empty() {
+        return memories;
+    }
+
+    let mut used: HashMap<&str, usize> = HashMap::new();
+    memories
+        .into_iter()
+        .filter(|memory| {
+            let Some((context, budget)) = memory
+                .context
+                .as_deref()
+                .and_then(|context| budgets.get_key_value(context))
+            else {
+                return true;
+            };
+            let spent = used.entry(context.as_str()).or_insert(0);
+            let cost = estimate_tokens(&memory.content);
+            if *spent + cost > *budget {
+                return false;
+            }
+            *spent += cost;
+            true
+        })
+        .collect()
+}
+
+/// Render memories as a prompt block with provenance markers
+///
+/// Each entry is headed `[n] <id> | source: <source> | <type> | relevance <score>`
+/// so the model can cite it. Entries that would exceed `token_budget` are
+/// skipped; an empty string is returned when nothing fits.
+pub fn format_retrieved_context(memories: &[RetrievedMemory], token_budget: usize) -> String {
+    let mut context = format!("{CONTEXT_HEADING}\n");
+    let mut used = estimate_tokens(&context);
+    let mut injected = 0;
+
+    for memory in memories {
+        let entry = format!(
+            "\n[{}] {} | source: {} | {} | relevance {:.2}\n{}\n",
+            injected + 1,
+            memory.id,
+            memory.source.as_deref().unwrap_or("unknown"),
+            memory.memory_type,
+            memory.relevance,
+            memory.content.trim()
+        );
+        let cost = estimate_tokens(&entry);
+        if used + cost > token_budget {
+            continue;
+        }
+        context.push_str(&entry);
+        used += cost;
+        injected += 1;
+    }
+
+    if injected == 0 {
+        String::new()
+    } else {
+        context
+    }
+}
@@ -5,8 +5,10 @@
 //! - **Temporal**: Time-based with exponential decay
 //! - **Hybrid**: Combines multiple strategies with cognitive filtering
 //! - **Manager**: Orchestrates strategies with unified API
+//! - **Augmentation**: Relevance-filtered context injection for chat prompts
 
 // Submodules
+pub mod augmentation;
 pub mod hybrid;
 pub mod manager;
 pub mod semantic;
@@ -15,6 +17,10 @@ pub mod temporal;
 pub mod types;
 
 // Re-export all public types to maintain API compatibility
+pub use augmentation::{
+    CONTEXT_ID_KEY, RagConfig, RetrievedMemory, apply_context_budgets, format_retrieved_context,
+    rank_by_similarity,
+};
 pub use hybrid::HybridRetrieval;
 pub use manager::RetrievalManager;
 pub use semantic::SemanticRetrieval;
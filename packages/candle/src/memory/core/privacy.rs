@@ -0,0 +1,290 @@
+//! Memory privacy tiers and encryption at rest
+//!
+//! Every memory carries a [`MemoryPrivacy`] tier in its custom metadata under
+//! [`PRIVACY_KEY`]:
+//!
+//! - `ephemeral`: lives for one process; never exported, and purged the next
+//!   time a memory coordinator starts
+//! - `standard`: the default; stored as plain text
+//! - `sensitive`: content is sealed with AES-256-GCM before it is written, and
+//!   is left out of exports and retrieval-augmented prompts unless the caller
+//!   explicitly permits it
+//!
+//! Only the content is sealed. Embeddings are stored as-is so vector search
+//! keeps working, which also means content search cannot match sealed
+//! memories.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::memory::primitives::metadata::MemoryMetadata;
+use crate::memory::primitives::node::MemoryNode;
+use crate::memory::utils::error::{Error, Result};
+
+/// Custom metadata key holding a memory's privacy tier
+pub const PRIVACY_KEY: &str = "privacy";
+
+/// Environment variable holding the base64 encoded 32-byte memory key
+pub const MEMORY_KEY_ENV: &str = "CYRUP_MEMORY_KEY";
+
+/// OS keychain service the memory key is stored under
+pub const KEYCHAIN_SERVICE: &str = "cyrup-memory";
+
+/// OS keychain account the memory key is stored under
+pub const KEYCHAIN_ACCOUNT: &str = "encryption-key";
+
+/// Prefix marking sealed content
+pub const SEALED_PREFIX: &str = "enc:v1:";
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// AES-256 key length in bytes
+const KEY_LEN: usize = 32;
+
+/// How carefully a memory is handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryPrivacy {
+    /// Kept for the current process only
+    Ephemeral,
+    /// Stored in plain text, exported and injected freely
+    #[default]
+    Standard,
+    /// Encrypted at rest and redacted unless explicitly permitted
+    Sensitive,
+}
+
+impl MemoryPrivacy {
+    /// Tier name as stored in metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ephemeral => "ephemeral",
+            Self::Standard => "standard",
+            Self::Sensitive => "sensitive",
+        }
+    }
+
+    /// Tier recorded in custom metadata
+    ///
+    /// Memories without a tier are standard. Unknown values are treated as
+    /// sensitive so a typo never weakens protection.
+    pub fn from_custom(custom: &Value) -> Self {
+        match custom.get(PRIVACY_KEY) {
+            None | Some(Value::Null) => Self::Standard,
+            Some(value) => serde_json::from_value(value.clone()).unwrap_or(Self::Sensitive),
+        }
+    }
+
+    /// Tier of `memory`
+    pub fn of(memory: &MemoryNode) -> Self {
+        Self::from_custom(&memory.metadata.custom)
+    }
+
+    /// Record this tier in `metadata`
+    pub fn apply(self, metadata: &mut MemoryMetadata) {
+        let tier = Value::String(self.as_str().to_string());
+        match &mut metadata.custom {
+            Value::Object(map) => {
+                map.insert(PRIVACY_KEY.to_string(), tier);
+            }
+            custom => *custom = serde_json::json!({ PRIVACY_KEY: tier }),
+        }
+    }
+
+    /// Whether the memory's content is sealed before it is stored
+    pub fn is_sealed_at_rest(self) -> bool {
+        self == Self::Sensitive
+    }
+
+    /// Whether the memory is written to exports
+    pub fn is_exported(self, include_sensitive: bool) -> bool {
+        match self {
+            Self::Ephemeral => false,
+            Self::Standard => true,
+            Self::Sensitive => include_sensitive,
+        }
+    }
+
+    /// Whether the memory may be injected into prompts
+    pub fn is_injected(self, include_sensitive: bool) -> bool {
+        self != Self::Sensitive || include_sensitive
+    }
+}
+
+/// Whether `content` was produced by [`MemoryCipher::seal`]
+pub fn is_sealed(content: &str) -> bool {
+    content.starts_with(SEALED_PREFIX)
+}
+
+/// AES-256-GCM cipher for sensitive memory content
+#[derive(Clone)]
+pub struct MemoryCipher {
+    cipher: Aes256Gcm,
+}
+
+impl std::fmt::Debug for MemoryCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryCipher").finish_non_exhaustive()
+    }
+}
+
+impl MemoryCipher {
+    /// Cipher using a raw 32-byte key
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// Cipher using a base64 encoded 32-byte key
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if the key is not valid base64 or not 32 bytes
+    pub fn from_base64(key: &str) -> Result<Self> {
+        let bytes = BASE64
+            .decode(key.trim())
+            .map_err(|e| Error::Encryption(format!("memory key is not base64: {}", e)))?;
+        let key: [u8; KEY_LEN] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            Error::Encryption(format!(
+                "memory key must be {} bytes, got {}",
+                KEY_LEN,
+                bytes.len()
+            ))
+        })?;
+        Ok(Self::new(&key))
+    }
+
+    /// Cipher using the key in [`MEMORY_KEY_ENV`], if set
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if the variable holds an invalid key
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var(MEMORY_KEY_ENV) {
+            Ok(key) if !key.trim().is_empty() => Self::from_base64(&key).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Cipher using the key stored in the OS keychain
+    ///
+    /// A fresh random key is generated and stored on first use.
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if the keychain is unavailable or holds an
+    /// invalid key
+    pub fn from_keychain() -> Result<Self> {
+        let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+            .map_err(|e| Error::Encryption(format!("keychain unavailable: {}", e)))?;
+
+        match entry.get_password() {
+            Ok(key) => Self::from_base64(&key),
+            Err(keyring::Error::NoEntry) => {
+                let key = Aes256Gcm::generate_key(OsRng);
+                entry
+                    .set_password(&BASE64.encode(key))
+                    .map_err(|e| Error::Encryption(format!("failed to store memory key: {}", e)))?;
+                log::info!("Generated memory encryption key in the OS keychain");
+                Ok(Self {
+                    cipher: Aes256Gcm::new(&key),
+                })
+            }
+            Err(e) => Err(Error::Encryption(format!(
+                "failed to read memory key from keychain: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Cipher using [`MEMORY_KEY_ENV`] when set, otherwise the OS keychain
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if no usable key can be found or created
+    pub fn resolve() -> Result<Self> {
+        match Self::from_env()? {
+            Some(cipher) => Ok(cipher),
+            None => Self::from_keychain(),
+        }
+    }
+
+    /// Encrypt `plaintext` under a fresh nonce
+    ///
+    /// The result is [`SEALED_PREFIX`] followed by the base64 encoded nonce
+    /// and ciphertext.
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if encryption fails
+    pub fn seal(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| Error::Encryption(format!("failed to seal memory: {}", e)))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(sealed)))
+    }
+
+    /// Decrypt content produced by [`seal`](Self::seal)
+    ///
+    /// # Errors
+    /// Returns `Error::Encryption` if `sealed` is malformed or was sealed
+    /// under a different key
+    pub fn open(&self, sealed: &str) -> Result<String> {
+        let encoded = sealed
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or_else(|| Error::Encryption("content is not sealed".to_string()))?;
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| Error::Encryption(format!("sealed content is not base64: {}", e)))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(Error::Encryption("sealed content is truncated".to_string()));
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| Error::Encryption("failed to open sealed memory".to_string()))?;
+        String::from_utf8(plaintext)
+            .map_err(|e| Error::Encryption(format!("sealed memory is not UTF-8: {}", e)))
+    }
+}
+
+/// Content to store for `memory`: sealed when its tier requires it
+///
+/// # Errors
+/// Returns `Error::Encryption` if a sensitive memory has no cipher to seal it
+pub fn content_at_rest(memory: &MemoryNode, cipher: Option<&MemoryCipher>) -> Result<String> {
+    if !MemoryPrivacy::of(memory).is_sealed_at_rest() {
+        return Ok(memory.content.text.clone());
+    }
+    match cipher {
+        Some(cipher) => cipher.seal(&memory.content.text),
+        None => Err(Error::Encryption(format!(
+            "memory {} is sensitive but no encryption key is configured (set {} or use the OS keychain)",
+            memory.id, MEMORY_KEY_ENV
+        ))),
+    }
+}
+
+/// Readable content of stored `content`, opening it when sealed
+///
+/// # Errors
+/// Returns `Error::Encryption` if the content is sealed and cannot be opened
+pub fn content_from_rest(content: &str, cipher: Option<&MemoryCipher>) -> Result<String> {
+    if !is_sealed(content) {
+        return Ok(content.to_string());
+    }
+    match cipher {
+        Some(cipher) => cipher.open(content),
+        None => Err(Error::Encryption(
+            "memory is sealed but no encryption key is configured".to_string(),
+        )),
+    }
+}
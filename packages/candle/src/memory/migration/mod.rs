@@ -7,6 +7,7 @@ pub mod converter;
 pub mod exporter;
 pub mod importer;
 pub mod schema_migrations;
+pub mod transfer;
 pub mod validator;
 
 // Re-export main types
@@ -23,6 +24,7 @@ use sha2::{Digest, Sha256};
 use surrealdb::Surreal;
 use surrealdb::engine::any::Any;
 use tokio::sync::oneshot;
+pub use transfer::{MEMORY_EXPORT_VERSION, MemoryExportFormat, MemoryImportReport, MemoryRecord};
 pub use validator::*;
 
 /// Result type for migration operations
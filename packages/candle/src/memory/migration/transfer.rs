@@ -0,0 +1,506 @@
+//! Memory export and import
+//!
+//! Memories are written as versioned [`MemoryRecord`]s so they can be backed
+//! up or moved between machines, embeddings included. Two formats are
+//! supported:
+//!
+//! - JSONL: a [`MemoryExportHeader`] line followed by one record per line
+//! - Parquet (`parquet` feature): one row per record, with the format version
+//!   stored in the schema metadata under [`PARQUET_VERSION_KEY`]
+//!
+//! Files written by older versions are upgraded on import by the forward
+//! migrations in [`migrate_record`]. Version 0 is the unstamped JSON array of
+//! memory nodes written by [`DataExporter`](super::DataExporter).
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{MigrationError, Result};
+use crate::memory::primitives::metadata::MemoryMetadata;
+use crate::memory::primitives::node::MemoryNode;
+use crate::memory::primitives::types::{MemoryContent, MemoryTypeEnum};
+
+/// Current memory export format version
+pub const MEMORY_EXPORT_VERSION: u32 = 1;
+
+/// Format marker in the JSONL header line
+pub const MEMORY_EXPORT_FORMAT: &str = "cyrup-memory";
+
+/// Parquet schema metadata key holding the format version
+pub const PARQUET_VERSION_KEY: &str = "cyrup.memory.version";
+
+/// File format of a memory export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryExportFormat {
+    /// Header line followed by one JSON record per line
+    Jsonl,
+    /// Columnar Parquet file, requires the `parquet` feature
+    Parquet,
+}
+
+impl MemoryExportFormat {
+    /// Format implied by a file extension (`.jsonl`, `.parquet`)
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    /// Conventional file extension
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// First line of a JSONL memory export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryExportHeader {
+    /// Always [`MEMORY_EXPORT_FORMAT`]
+    pub format: String,
+    /// Version the records were written with
+    pub version: u32,
+    /// When the export was written
+    pub exported_at: DateTime<Utc>,
+    /// Number of records that follow
+    pub count: usize,
+}
+
+impl MemoryExportHeader {
+    /// Header for `count` records in the current version
+    #[must_use]
+    pub fn new(count: usize) -> Self {
+        Self {
+            format: MEMORY_EXPORT_FORMAT.to_string(),
+            version: MEMORY_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            count,
+        }
+    }
+}
+
+/// One exported memory in the current format version
+///
+/// Only the memory's text content is exported; the embedding is kept once,
+/// outside the metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryRecord {
+    /// Memory id, preserved on import
+    pub id: String,
+    /// Text content
+    pub content: String,
+    /// Memory type (`semantic`, `episodic`, `procedural`, `working`, `long_term`)
+    pub memory_type: String,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+    /// Embedding vector
+    pub embedding: Option<Vec<f32>>,
+    /// Memory metadata without its embedding
+    pub metadata: Value,
+}
+
+impl MemoryRecord {
+    /// Capture a memory for export
+    ///
+    /// # Errors
+    /// Returns `MigrationError::SerializationError` if the metadata cannot be
+    /// serialized
+    pub fn from_node(memory: &MemoryNode) -> Result<Self> {
+        let mut metadata = memory.metadata.clone();
+        let embedding = memory
+            .embedding
+            .clone()
+            .or_else(|| metadata.embedding.take());
+        metadata.embedding = None;
+
+        Ok(Self {
+            id: memory.id.clone(),
+            content: memory.content.text.clone(),
+            memory_type: memory.memory_type.to_string(),
+            created_at: memory.created_at,
+            updated_at: memory.updated_at,
+            embedding,
+            metadata: serde_json::to_value(metadata)?,
+        })
+    }
+
+    /// Rebuild the memory this record was exported from
+    ///
+    /// # Errors
+    /// Returns `MigrationError::ValidationFailed` for an unknown memory type
+    /// or metadata that does not match the current schema
+    pub fn into_node(self) -> Result<MemoryNode> {
+        let memory_type = MemoryTypeEnum::from_string(&self.memory_type)
+            .map_err(|e| MigrationError::ValidationFailed(format!("{}: {}", self.id, e)))?;
+        let metadata: MemoryMetadata = serde_json::from_value(self.metadata).map_err(|e| {
+            MigrationError::ValidationFailed(format!("{}: invalid metadata: {}", self.id, e))
+        })?;
+
+        let mut content = MemoryContent::new(&self.content);
+        content.embedding = self.embedding.clone();
+
+        let mut memory = MemoryNode::with_id(self.id, memory_type, content);
+        memory.created_at = self.created_at;
+        memory.updated_at = self.updated_at;
+        memory.embedding = self.embedding;
+        memory.metadata = metadata;
+        memory.metadata.embedding = memory.embedding.clone();
+        Ok(memory)
+    }
+}
+
+/// Counts from importing a memory export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryImportReport {
+    /// Format version of the imported file
+    pub source_version: u32,
+    /// Memories that did not exist yet
+    pub created: usize,
+    /// Existing memories overwritten by the import
+    pub updated: usize,
+}
+
+/// A forward migration from `from` to `from + 1`
+type RecordMigration = fn(Value) -> Result<Value>;
+
+/// Forward migrations indexed by source version
+const MIGRATIONS: &[(u32, RecordMigration)] = &[(0, migrate_v0_to_v1)];
+
+/// Upgrade a raw record written in `version` to [`MEMORY_EXPORT_VERSION`]
+///
+/// # Errors
+/// Returns `MigrationError::SchemaMismatch` for records newer than this
+/// build understands, and the failing migration's error otherwise
+pub fn migrate_record(version: u32, mut record: Value) -> Result<Value> {
+    if version > MEMORY_EXPORT_VERSION {
+        return Err(MigrationError::SchemaMismatch {
+            expected: format!("memory export version <= {}", MEMORY_EXPORT_VERSION),
+            found: version.to_string(),
+        });
+    }
+    for current in version..MEMORY_EXPORT_VERSION {
+        let (_, migration) = MIGRATIONS
+            .iter()
+            .find(|(from, _)| *from == current)
+            .ok_or_else(|| {
+                MigrationError::UnsupportedFormat(format!(
+                    "no migration from memory export version {}",
+                    current
+                ))
+            })?;
+        record = migration(record)?;
+    }
+    Ok(record)
+}
+
+/// Version 0 records are serialized memory nodes
+fn migrate_v0_to_v1(record: Value) -> Result<Value> {
+    let memory: MemoryNode = serde_json::from_value(record)?;
+    let record = MemoryRecord::from_node(&memory)?;
+    Ok(serde_json::to_value(record)?)
+}
+
+/// Write records in `format`
+///
+/// # Errors
+/// Returns `MigrationError` if the file cannot be written
+pub async fn write_records(
+    path: &Path,
+    format: MemoryExportFormat,
+    records: Vec<MemoryRecord>,
+) -> Result<()> {
+    match format {
+        MemoryExportFormat::Jsonl => {
+            tokio::fs::write(path, encode_jsonl(&records)?).await?;
+            Ok(())
+        }
+        MemoryExportFormat::Parquet => {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || parquet_io::write(&path, &records))
+                .await
+                .map_err(|e| MigrationError::IoError(std::io::Error::other(e)))?
+        }
+    }
+}
+
+/// Read and migrate the records of an export file
+///
+/// The format is detected from the file contents: Parquet by its magic
+/// bytes, a JSON array as a version 0 export, anything else as JSONL.
+///
+/// # Errors
+/// Returns `MigrationError` if the file cannot be read, is malformed or was
+/// written by a newer version
+pub async fn read_records(path: &Path) -> Result<(u32, Vec<MemoryRecord>)> {
+    if !tokio::fs::try_exists(path).await? {
+        return Err(MigrationError::FileNotFound(path.display().to_string()));
+    }
+    let bytes = tokio::fs::read(path).await?;
+
+    let (version, raw) = if bytes.starts_with(b"PAR1") {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || parquet_io::read(&path))
+            .await
+            .map_err(|e| MigrationError::IoError(std::io::Error::other(e)))??
+    } else {
+        decode_json(&bytes)?
+    };
+
+    let records = raw
+        .into_iter()
+        .map(|record| Ok(serde_json::from_value(migrate_record(version, record)?)?))
+        .collect::<Result<Vec<MemoryRecord>>>()?;
+    Ok((version, records))
+}
+
+/// Encode records as a header line plus one line per record
+///
+/// # Errors
+/// Returns `MigrationError::SerializationError` if a record cannot be
+/// serialized
+pub fn encode_jsonl(records: &[MemoryRecord]) -> Result<Vec<u8>> {
+    let mut out = serde_json::to_vec(&MemoryExportHeader::new(records.len()))?;
+    out.push(b'\n');
+    for record in records {
+        serde_json::to_writer(&mut out, record)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+/// Split a JSON export into its version and raw records
+///
+/// # Errors
+/// Returns `MigrationError` if the header is missing or a line is not JSON
+pub fn decode_json(bytes: &[u8]) -> Result<(u32, Vec<Value>)> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| MigrationError::UnsupportedFormat(format!("not UTF-8: {}", e)))?;
+
+    if text.trim_start().starts_with('[') {
+        return Ok((0, serde_json::from_str(text)?));
+    }
+
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header: MemoryExportHeader = match lines.next() {
+        Some(line) => serde_json::from_str(line)?,
+        None => {
+            return Err(MigrationError::UnsupportedFormat(
+                "empty export".to_string(),
+            ));
+        }
+    };
+    if header.format != MEMORY_EXPORT_FORMAT {
+        return Err(MigrationError::UnsupportedFormat(header.format));
+    }
+
+    let records = lines
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<Vec<Value>, _>>()?;
+    if records.len() != header.count {
+        return Err(MigrationError::ValidationFailed(format!(
+            "header announces {} memories, file has {}",
+            header.count,
+            records.len()
+        )));
+    }
+    Ok((header.version, records))
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_io {
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use arrow_array::builder::{Float32Builder, ListBuilder};
+    use arrow_array::{
+        Array, ArrayRef, Float32Array, ListArray, RecordBatch, StringArray,
+        TimestampMillisecondArray,
+    };
+    use arrow_schema::{DataType, Field, Schema, TimeUnit};
+    use chrono::{DateTime, Utc};
+    use parquet::arrow::ArrowWriter;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde_json::Value;
+
+    use super::{MEMORY_EXPORT_VERSION, MemoryRecord, PARQUET_VERSION_KEY};
+    use crate::memory::migration::{MigrationError, Result};
+
+    fn parquet_error(e: impl std::fmt::Display) -> MigrationError {
+        MigrationError::UnsupportedFormat(format!("Parquet: {}", e))
+    }
+
+    fn timestamp_type() -> DataType {
+        DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+    }
+
+    fn schema() -> Schema {
+        let metadata = HashMap::from([(
+            PARQUET_VERSION_KEY.to_string(),
+            MEMORY_EXPORT_VERSION.to_string(),
+        )]);
+        Schema::new_with_metadata(
+            vec![
+                Field::new("id", DataType::Utf8, false),
+                Field::new("content", DataType::Utf8, false),
+                Field::new("memory_type", DataType::Utf8, false),
+                Field::new("created_at", timestamp_type(), false),
+                Field::new("updated_at", timestamp_type(), false),
+                Field::new(
+                    "embedding",
+                    DataType::List(Arc::new(Field::new("item", DataType::Float32, true))),
+                    true,
+                ),
+                Field::new("metadata", DataType::Utf8, false),
+            ],
+            metadata,
+        )
+    }
+
+    pub(super) fn write(path: &Path, records: &[MemoryRecord]) -> Result<()> {
+        let schema = Arc::new(schema());
+
+        let mut embeddings = ListBuilder::new(Float32Builder::new());
+        for record in records {
+            match &record.embedding {
+                Some(embedding) => {
+                    embeddings.values().append_slice(embedding);
+                    embeddings.append(true);
+                }
+                None => embeddings.append(false),
+            }
+        }
+        let metadata = records
+            .iter()
+            .map(|record| serde_json::to_string(&record.metadata))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let timestamps = |at: fn(&MemoryRecord) -> DateTime<Utc>| {
+            TimestampMillisecondArray::from(
+                records
+                    .iter()
+                    .map(|record| at(record).timestamp_millis())
+                    .collect::<Vec<_>>(),
+            )
+            .with_timezone("UTC")
+        };
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| r.id.as_str()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| r.content.as_str()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| r.memory_type.as_str()),
+            )),
+            Arc::new(timestamps(|r| r.created_at)),
+            Arc::new(timestamps(|r| r.updated_at)),
+            Arc::new(embeddings.finish()),
+            Arc::new(StringArray::from(metadata)),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(parquet_error)?;
+
+        let mut writer =
+            ArrowWriter::try_new(File::create(path)?, schema, None).map_err(parquet_error)?;
+        writer.write(&batch).map_err(parquet_error)?;
+        writer.close().map_err(parquet_error)?;
+        Ok(())
+    }
+
+    fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T> {
+        batch
+            .column_by_name(name)
+            .and_then(|column| column.as_any().downcast_ref::<T>())
+            .ok_or_else(|| parquet_error(format!("missing or mistyped column `{}`", name)))
+    }
+
+    fn timestamp(array: &TimestampMillisecondArray, row: usize) -> Result<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(array.value(row))
+            .ok_or_else(|| parquet_error(format!("timestamp out of range in row {}", row)))
+    }
+
+    pub(super) fn read(path: &Path) -> Result<(u32, Vec<Value>)> {
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(path)?).map_err(parquet_error)?;
+        let version = builder
+            .schema()
+            .metadata()
+            .get(PARQUET_VERSION_KEY)
+            .and_then(|version| version.parse::<u32>().ok())
+            .ok_or_else(|| parquet_error(format!("missing `{}` metadata", PARQUET_VERSION_KEY)))?;
+
+        let mut records = Vec::new();
+        for batch in builder.build().map_err(parquet_error)? {
+            let batch = batch.map_err(parquet_error)?;
+            let ids = column::<StringArray>(&batch, "id")?;
+            let contents = column::<StringArray>(&batch, "content")?;
+            let memory_types = column::<StringArray>(&batch, "memory_type")?;
+            let created = column::<TimestampMillisecondArray>(&batch, "created_at")?;
+            let updated = column::<TimestampMillisecondArray>(&batch, "updated_at")?;
+            let embeddings = column::<ListArray>(&batch, "embedding")?;
+            let metadata = column::<StringArray>(&batch, "metadata")?;
+
+            for row in 0..batch.num_rows() {
+                let embedding = if embeddings.is_null(row) {
+                    None
+                } else {
+                    let values = embeddings.value(row);
+                    let values = values
+                        .as_any()
+                        .downcast_ref::<Float32Array>()
+                        .ok_or_else(|| parquet_error("embedding is not a float32 list"))?;
+                    Some(values.values().to_vec())
+                };
+                let record = MemoryRecord {
+                    id: ids.value(row).to_string(),
+                    content: contents.value(row).to_string(),
+                    memory_type: memory_types.value(row).to_string(),
+                    created_at: timestamp(created, row)?,
+                    updated_at: timestamp(updated, row)?,
+                    embedding,
+                    metadata: serde_json::from_str(metadata.value(row))?,
+                };
+                records.push(serde_json::to_value(record)?);
+            }
+        }
+        Ok((version, records))
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+mod parquet_io {
+    use std::path::Path;
+
+    use serde_json::Value;
+
+    use super::MemoryRecord;
+    use crate::memory::migration::{MigrationError, Result};
+
+    fn disabled() -> MigrationError {
+        MigrationError::UnsupportedFormat(
+            "Parquet support requires the `parquet` feature".to_string(),
+        )
+    }
+
+    pub(super) fn write(_path: &Path, _records: &[MemoryRecord]) -> Result<()> {
+        Err(disabled())
+    }
+
+    pub(super) fn read(_path: &Path) -> Result<(u32, Vec<Value>)> {
+        Err(disabled())
+    }
+}
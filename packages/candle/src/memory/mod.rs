@@ -23,9 +23,11 @@ pub use api::APIServer;
 // Re-export core memory submodules for backward compatibility
 pub use self::core::SurrealDBMemoryManager as SurrealMemoryManager;
 pub use self::core::{
-    MemoryMetadata, MemoryNode, MemoryRelationship, SurrealDBMemoryManager, filter,
+    CompactionAction, CompactionPolicy, DecayFunction, DecayWorkerConfig, MemoryCipher,
+    MemoryMetadata, MemoryNode, MemoryPrivacy, MemoryRelationship, RagConfig, RetrievedMemory,
+    SurrealDBMemoryManager, filter,
     manager::{MemoryManager, coordinator::MemoryCoordinator},
-    ops, primitives, repository, storage,
+    ops, primitives, privacy, repository, storage,
 };
 
 // Re-export manager module for compatibility
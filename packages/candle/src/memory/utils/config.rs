@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::memory::core::DecayWorkerConfig;
+
 /// Main configuration for the memory system
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MemoryConfig {
@@ -15,6 +17,9 @@ pub struct MemoryConfig {
     pub cache: CacheConfig,
     /// Logging configuration
     pub logging: LoggingConfig,
+    /// Importance decay and forgetting of stale memories
+    #[serde(default)]
+    pub decay: DecayWorkerConfig,
 }
 
 /// Database configuration
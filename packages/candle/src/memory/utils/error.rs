@@ -75,6 +75,9 @@ pub enum Error {
     #[error("Already exists: {0}")]
     AlreadyExists(String),
 
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -146,6 +149,10 @@ impl axum::response::IntoResponse for Error {
             ),
             Error::NotImplemented(e) => (StatusCode::NOT_IMPLEMENTED, e),
             Error::AlreadyExists(e) => (StatusCode::CONFLICT, e),
+            Error::Encryption(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Encryption error: {}", e),
+            ),
             Error::SerializationError(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Serialization error: {}", e),
@@ -186,6 +193,26 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<crate::memory::migration::MigrationError> for Error {
+    fn from(err: crate::memory::migration::MigrationError) -> Self {
+        Error::Migration(err.to_string())
+    }
+}
+
+impl From<cyrup_simd::ann::AnnError> for Error {
+    fn from(err: cyrup_simd::ann::AnnError) -> Self {
+        use cyrup_simd::ann::AnnError;
+        match err {
+            AnnError::DimensionMismatch { .. } => Error::InvalidInput(err.to_string()),
+            AnnError::InvalidConfig(e) => Error::InvalidConfig(e),
+            AnnError::Io(e) => Error::Io(e.to_string()),
+            AnnError::Corrupt(_) | AnnError::UnsupportedVersion(_) => {
+                Error::BinarySerialization(err.to_string())
+            }
+        }
+    }
+}
+
 impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
     fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
         Error::ModelError(err.to_string())
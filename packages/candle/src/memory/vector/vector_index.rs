@@ -4,11 +4,10 @@
 //! lock-free concurrent access, and blazing-fast search performance.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::Path;
 
+use cyrup_simd::ann::{AnnMetric, HnswConfig, HnswIndex};
 use cyrup_simd::cosine_similarity;
-use dashmap::DashMap;
-use instant_distance::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::memory::utils::Result;
@@ -87,24 +86,9 @@ impl FlatIndex {
     /// Calculate distance between two vectors
     fn calculate_distance(&self, a: &[f32], b: &[f32]) -> f32 {
         match self.config.metric {
-            DistanceMetric::Euclidean => a
-                .iter()
-                .zip(b.iter())
-                .map(|(x, y)| (x - y).powi(2))
-                .sum::<f32>()
-                .sqrt(),
-            DistanceMetric::Cosine => {
-                let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
-                let norm_a: f32 = a.iter().map(|x| x.powi(2)).sum::<f32>().sqrt();
-                let norm_b: f32 = b.iter().map(|x| x.powi(2)).sum::<f32>().sqrt();
-
-                if norm_a == 0.0 || norm_b == 0.0 {
-                    0.0
-                } else {
-                    1.0 - (dot_product / (norm_a * norm_b))
-                }
-            }
-            DistanceMetric::DotProduct => -a.iter().zip(b.iter()).map(|(x, y)| x * y).sum::<f32>(),
+            DistanceMetric::Euclidean => euclidean_distance(a, b),
+            DistanceMetric::Cosine => cosine_distance(a, b),
+            DistanceMetric::DotProduct => dot_product_distance(a, b),
         }
     }
 }
@@ -163,72 +147,54 @@ impl VectorIndex for FlatIndex {
     }
 }
 
-/// Custom point type for instant-distance HNSW
-#[derive(Debug, Clone)]
-pub struct VectorPoint {
-    pub data: Vec<f32>,
-}
-
-/// Space implementation for different distance metrics
-#[derive(Debug, Clone)]
-pub struct ConfigurableSpace {
-    metric: DistanceMetric,
-}
-
-impl ConfigurableSpace {
-    pub fn new(metric: DistanceMetric) -> Self {
-        Self { metric }
-    }
-
-    pub fn distance(&self, a: &VectorPoint, b: &VectorPoint) -> f32 {
-        match self.metric {
-            DistanceMetric::Cosine => cosine_distance(&a.data, &b.data),
-            DistanceMetric::Euclidean => euclidean_distance(&a.data, &b.data),
-            DistanceMetric::DotProduct => dot_product_distance(&a.data, &b.data),
-        }
-    }
-}
-
-impl instant_distance::Point for VectorPoint {
-    fn distance(&self, other: &Self) -> f32 {
-        euclidean_distance(&self.data, &other.data)
-    }
-}
-
-/// HNSW (Hierarchical Navigable Small World) index with production-ready implementation
+/// HNSW (Hierarchical Navigable Small World) index backed by `cyrup_simd::ann`
+///
+/// Vectors are linked into the graph as they are added and unlinked as they
+/// are removed, so the index never needs a rebuild. Recognized `parameters`
+/// are `m`, `ef_construction`, `ef` (search beam width) and `seed`.
 pub struct HNSWIndex {
     config: VectorIndexConfig,
-    /// Core HNSW index using instant-distance
-    hnsw: Option<instant_distance::HnswMap<VectorPoint, usize>>,
-    /// Lock-free mapping from string IDs to internal indices
-    id_to_index: Arc<DashMap<String, usize>>,
-    /// Lock-free mapping from internal indices to string IDs
-    index_to_id: Arc<DashMap<usize, String>>,
-    /// Lock-free vector storage
-    vectors: Arc<DashMap<usize, VectorPoint>>,
-    /// Atomic counter for generating internal indices
-    next_index: std::sync::atomic::AtomicUsize,
-    /// Space configuration for distance metric
-    space: ConfigurableSpace,
+    index: HnswIndex,
 }
 
 impl HNSWIndex {
-    /// Create a new HNSW index with production configuration
+    /// Create a new HNSW index
     ///
-    /// # Performance
-    /// Zero allocation initialization with optimal HNSW parameters
+    /// Out-of-range parameters are clamped to the smallest valid value.
     pub fn new(config: VectorIndexConfig) -> Self {
-        let space = ConfigurableSpace::new(config.metric);
+        let index = match HnswIndex::new(
+            config.dimensions.max(1),
+            ann_metric(config.metric),
+            hnsw_config(&config),
+        ) {
+            Ok(index) => index,
+            Err(e) => unreachable!("clamped HNSW parameters are valid: {e}"),
+        };
+        Self { config, index }
+    }
 
-        Self {
-            config,
-            hnsw: None,
-            id_to_index: Arc::new(DashMap::new()),
-            index_to_id: Arc::new(DashMap::new()),
-            vectors: Arc::new(DashMap::new()),
-            next_index: std::sync::atomic::AtomicUsize::new(0),
-            space,
+    /// Load an index saved with [`save`](Self::save)
+    ///
+    /// Fails if the saved index does not match the dimensions and metric of
+    /// `config`.
+    pub fn load(config: VectorIndexConfig, path: impl AsRef<Path>) -> Result<Self> {
+        let index = HnswIndex::load(path)?;
+        if index.dimensions() != config.dimensions || index.metric() != ann_metric(config.metric) {
+            return Err(crate::memory::utils::error::Error::InvalidConfig(format!(
+                "Saved index has {} dimensions and {:?} metric, expected {} and {:?}",
+                index.dimensions(),
+                index.metric(),
+                config.dimensions,
+                config.metric
+            )));
         }
+        Ok(Self { config, index })
+    }
+
+    /// Save the index to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.index.save(path)?;
+        Ok(())
     }
 
     /// Get the index configuration
@@ -236,129 +202,41 @@ impl HNSWIndex {
         &self.config
     }
 
-    /// Get optimal HNSW parameters from configuration
-    fn get_hnsw_params(&self) -> HNSWParams {
-        // Extract parameters from config or use sensible defaults
-        let m = self
-            .config
-            .parameters
-            .get("m")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(16); // Default M parameter
-
-        let ef_construction = self
-            .config
-            .parameters
-            .get("ef_construction")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(200); // Default ef_construction
-
-        let max_m = self
-            .config
-            .parameters
-            .get("max_m")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(m); // Default max_m = m
-
-        let max_m0 = self
-            .config
-            .parameters
-            .get("max_m0")
-            .and_then(|v| v.as_u64())
-            .map(|v| v as usize)
-            .unwrap_or(m * 2); // Default max_m0 = m * 2
-
-        let ml = self
-            .config
-            .parameters
-            .get("ml")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(1.0 / (2.0_f64.ln())); // Default mL
-
-        HNSWParams {
-            m,
-            ef_construction,
-            max_m,
-            max_m0,
-            ml,
-        }
-    }
-
-    /// Rebuild the HNSW index with current vectors
-    fn rebuild_hnsw(&mut self) -> Result<()> {
-        if self.vectors.is_empty() {
-            // No vectors to build index with
-            self.hnsw = None;
-            return Ok(());
-        }
-
-        // Collect all vectors for building
-        let points: Vec<VectorPoint> = self
-            .vectors
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect();
-
-        if points.is_empty() {
-            self.hnsw = None;
-            return Ok(());
-        }
-
-        // Build HNSW with optimal parameters
-        let params = self.get_hnsw_params();
-
-        // Note: instant-distance Builder only supports ef_construction and ml
-        // m, max_m, max_m0 are stored in config for future use or other HNSW implementations
-        let builder = Builder::default()
-            .ef_construction(params.ef_construction)
-            .ml(params.ml as f32);
-
-        // The m, max_m, max_m0 parameters are available in params but instant-distance
-        // uses its own internal defaults for these values
-
-        // Create values vector with indices
-        let values: Vec<usize> = (0..points.len()).collect();
-        let hnsw = builder.build(points, values);
-
-        self.hnsw = Some(hnsw);
+    /// Apply the configured search beam width
+    pub fn optimize_with_config(&mut self) -> Result<()> {
+        self.index
+            .set_ef_search(hnsw_config(&self.config).ef_search);
         Ok(())
     }
+}
 
-    /// Get next available internal index
-    fn next_internal_index(&self) -> usize {
-        self.next_index
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
-    }
-
-    /// Update index with configuration parameters
-    pub fn optimize_with_config(&mut self) -> Result<()> {
-        // Rebuild with current configuration
-        self.rebuild_hnsw()
+fn ann_metric(metric: DistanceMetric) -> AnnMetric {
+    match metric {
+        DistanceMetric::Cosine => AnnMetric::Cosine,
+        DistanceMetric::Euclidean => AnnMetric::Euclidean,
+        DistanceMetric::DotProduct => AnnMetric::DotProduct,
     }
 }
 
-/// HNSW configuration parameters
-#[derive(Debug, Clone)]
-struct HNSWParams {
-    /// Number of bi-directional links created for every new element during construction (M)
-    /// Note: instant-distance uses its own internal M value, but this is kept for config compatibility
-    #[allow(dead_code)]
-    m: usize,
-    /// Size of the dynamic candidate list (ef_construction)
-    ef_construction: usize,
-    /// Maximum number of bi-directional links for each node (max_m)
-    /// Note: instant-distance uses its own internal max_m value, but this is kept for config compatibility
-    #[allow(dead_code)]
-    max_m: usize,
-    /// Maximum number of bi-directional links for each node at layer 0 (max_m0)
-    /// Note: instant-distance uses its own internal max_m0 value, but this is kept for config compatibility
-    #[allow(dead_code)]
-    max_m0: usize,
-    /// Level generation factor (mL)
-    ml: f64,
+/// HNSW parameters from the index configuration, with defaults
+fn hnsw_config(config: &VectorIndexConfig) -> HnswConfig {
+    let param = |name: &str| {
+        config
+            .parameters
+            .get(name)
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+    };
+
+    let defaults = HnswConfig::default();
+    HnswConfig {
+        m: param("m").unwrap_or(defaults.m).max(2),
+        ef_construction: param("ef_construction")
+            .unwrap_or(defaults.ef_construction)
+            .max(1),
+        ef_search: param("ef").unwrap_or(defaults.ef_search).max(1),
+        seed: param("seed").map_or(defaults.seed, |v| v as u64),
+    }
 }
 
 impl VectorIndex for HNSWIndex {
@@ -372,43 +250,19 @@ impl VectorIndex for HNSWIndex {
         }
 
         // Check if ID already exists
-        if self.id_to_index.contains_key(&id) {
+        if self.index.contains(&id) {
             return Err(crate::memory::utils::error::Error::InvalidInput(format!(
                 "Vector with ID '{}' already exists",
                 id
             )));
         }
 
-        // Generate internal index
-        let internal_index = self.next_internal_index();
-
-        // Create vector point
-        let point = VectorPoint { data: vector };
-
-        // Store mappings and vector
-        self.id_to_index.insert(id.clone(), internal_index);
-        self.index_to_id.insert(internal_index, id);
-        self.vectors.insert(internal_index, point);
-
-        // Trigger rebuild if we have enough vectors for efficiency
-        let vector_count = self.vectors.len();
-        if vector_count > 0 && (vector_count & (vector_count - 1)) == 0 {
-            // Rebuild on powers of 2 for optimal performance
-            self.rebuild_hnsw()?;
-        }
-
+        self.index.insert(id, vector)?;
         Ok(())
     }
 
     fn remove(&mut self, id: &str) -> Result<()> {
-        if let Some((_, internal_index)) = self.id_to_index.remove(id) {
-            self.index_to_id.remove(&internal_index);
-            self.vectors.remove(&internal_index);
-
-            // Mark for rebuild since instant-distance doesn't support dynamic removal
-            // This is a limitation of the current HNSW implementation
-            self.hnsw = None;
-
+        if self.index.remove(id) {
             Ok(())
         } else {
             Err(crate::memory::utils::error::Error::NotFound(format!(
@@ -427,71 +281,16 @@ impl VectorIndex for HNSWIndex {
             )));
         }
 
-        if self.vectors.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        // Create query point
-        let query_point = VectorPoint {
-            data: query.to_vec(),
-        };
-
-        match &self.hnsw {
-            Some(hnsw) => {
-                // Use HNSW for fast approximate search
-                let _ef = self
-                    .config
-                    .parameters
-                    .get("ef")
-                    .and_then(|v| v.as_u64())
-                    .map(|v| v as usize)
-                    .unwrap_or(std::cmp::max(k, 16)); // Default ef = max(k, 16)
-
-                let mut search = instant_distance::Search::default();
-                let search_results = hnsw.search(&query_point, &mut search);
-
-                // Convert internal indices back to string IDs
-                let mut results = Vec::with_capacity(search_results.len());
-
-                for item in search_results {
-                    let index_key = item.pid.into_inner() as usize;
-                    if let Some(id_entry) = self.index_to_id.get(&index_key) {
-                        results.push((id_entry.clone(), item.distance));
-                    }
-                }
-
-                Ok(results)
-            }
-            None => {
-                // Fallback to brute force search if HNSW not built
-                let mut distances: Vec<(String, f32)> = Vec::new();
-
-                for entry in self.vectors.iter() {
-                    let internal_index = *entry.key();
-                    let vector_point = entry.value();
-
-                    if let Some(id_entry) = self.index_to_id.get(&internal_index) {
-                        let distance = self.space.distance(&query_point, vector_point);
-                        distances.push((id_entry.clone(), distance));
-                    }
-                }
-
-                // Sort by distance
-                distances
-                    .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-                distances.truncate(k);
-
-                Ok(distances)
-            }
-        }
+        Ok(self.index.search(query, k)?)
     }
 
     fn len(&self) -> usize {
-        self.vectors.len()
+        self.index.len()
     }
 
     fn build(&mut self) -> Result<()> {
-        self.rebuild_hnsw()
+        // The graph is maintained incrementally
+        Ok(())
     }
 }
 
@@ -635,6 +434,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hnsw_index_save_and_load() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let config = VectorIndexConfig {
+            metric: DistanceMetric::Euclidean,
+            dimensions: 2,
+            index_type: IndexType::HNSW,
+            parameters: HashMap::new(),
+        };
+
+        let mut index = HNSWIndex::new(config.clone());
+        index.add("near".to_string(), vec![1.0, 1.0])?;
+        index.add("far".to_string(), vec![9.0, 9.0])?;
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("memories.hnsw");
+        index.save(&path)?;
+
+        let loaded = HNSWIndex::load(config.clone(), &path)?;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.search(&[0.0, 0.0], 1)?[0].0, "near");
+
+        let mismatched = VectorIndexConfig {
+            dimensions: 3,
+            ..config
+        };
+        assert!(HNSWIndex::load(mismatched, &path).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_distance_functions() {
         let a = vec![1.0, 0.0, 0.0];
@@ -59,6 +59,15 @@ impl VectorRepository {
             parameters: HashMap::new(),
         };
 
+        Self::with_index_config(default_config)
+    }
+
+    /// Create a repository whose collections use `default_config`
+    ///
+    /// Pass [`IndexType::HNSW`](crate::memory::vector::IndexType::HNSW) for
+    /// sublinear search over large collections. Each collection overrides the
+    /// dimensions and metric it was created with.
+    pub fn with_index_config(default_config: VectorIndexConfig) -> Self {
         Self {
             collections: Arc::new(RwLock::new(HashMap::new())),
             default_config,
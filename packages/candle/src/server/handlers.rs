@@ -0,0 +1,264 @@
+//! HTTP handlers for the OpenAI-compatible API
+//! This module turns OpenAI chat requests into local provider completions
+
+use std::convert::Infallible;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::extract::rejection::JsonRejection;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Json, Response};
+use tokio_stream::StreamExt;
+
+use super::ServerState;
+use super::models::{
+    ApiError, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, Choice,
+    ChunkChoice, Delta, ModelList, ModelObject, ResponseMessage, Usage, render_prompt,
+};
+use crate::capability::registry::{self, TextToTextModel};
+use crate::capability::traits::TextToTextCapable;
+use crate::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use crate::domain::context::chunks::FinishReason;
+use crate::domain::model::traits::CandleModel;
+use crate::domain::prompt::CandlePrompt;
+
+/// List the locally available chat models
+pub async fn list_models(State(_state): State<Arc<ServerState>>) -> Json<ModelList> {
+    let created = chrono::Utc::now().timestamp();
+    let data = registry::text_to_text_registry_keys()
+        .into_iter()
+        .filter_map(|key| {
+            let model = registry::get::<TextToTextModel>(&key)?;
+            Some(ModelObject {
+                id: key,
+                object: "model".to_string(),
+                created,
+                owned_by: model.info().provider_str().to_string(),
+            })
+        })
+        .collect();
+
+    Json(ModelList {
+        object: "list".to_string(),
+        data,
+    })
+}
+
+/// Create a chat completion, streamed over SSE when `stream` is set
+pub async fn chat_completions(
+    State(state): State<Arc<ServerState>>,
+    body: Result<Json<ChatCompletionRequest>, JsonRejection>,
+) -> Result<Response, ApiError> {
+    let Json(request) = body.map_err(|e| ApiError::invalid_request(e.body_text()))?;
+
+    if request.messages.is_empty() {
+        return Err(ApiError::invalid_request("'messages' must not be empty"));
+    }
+    if let Some(temperature) = request.temperature
+        && !(0.0..=2.0).contains(&temperature)
+    {
+        return Err(ApiError::invalid_request(format!(
+            "'temperature' must be between 0 and 2, got {}",
+            temperature
+        )));
+    }
+
+    let (model_id, model) = resolve_model(&state, &request.model)?;
+    let params = CandleCompletionParams {
+        temperature: request.temperature.unwrap_or(state.temperature),
+        max_tokens: NonZeroU64::new(
+            request
+                .max_completion_tokens
+                .or(request.max_tokens)
+                .unwrap_or(state.max_tokens),
+        ),
+        stream: request.stream,
+        ..Default::default()
+    };
+    let prompt = CandlePrompt::new(render_prompt(&request.messages));
+    let completion = model.prompt(prompt, &params);
+
+    let id = format!("chatcmpl-{}", uuid::Uuid::new_v4().simple());
+    let created = chrono::Utc::now().timestamp();
+
+    if request.stream {
+        Ok(stream_completion(completion, id, created, model_id).into_response())
+    } else {
+        Ok(Json(collect_completion(completion, id, created, model_id).await?).into_response())
+    }
+}
+
+/// Look up the requested model, falling back to the server default
+fn resolve_model(
+    state: &ServerState,
+    requested: &str,
+) -> Result<(String, TextToTextModel), ApiError> {
+    let key = if requested.is_empty() {
+        state
+            .default_model
+            .as_deref()
+            .ok_or_else(|| ApiError::invalid_request("'model' is required"))?
+    } else {
+        requested
+    };
+
+    registry::get::<TextToTextModel>(key)
+        .map(|model| (key.to_string(), model))
+        .ok_or_else(|| ApiError::model_not_found(key))
+}
+
+/// Drain a completion into a single `chat.completion` response
+async fn collect_completion(
+    completion: std::pin::Pin<Box<dyn tokio_stream::Stream<Item = CandleCompletionChunk> + Send>>,
+    id: String,
+    created: i64,
+    model: String,
+) -> Result<ChatCompletionResponse, ApiError> {
+    tokio::pin!(completion);
+    let mut content = String::new();
+    let mut finish_reason = None;
+    let mut usage = Usage::default();
+
+    while let Some(chunk) = completion.next().await {
+        match chunk {
+            CandleCompletionChunk::Text(text) => content.push_str(&text),
+            CandleCompletionChunk::Complete {
+                text,
+                finish_reason: reason,
+                usage: reported,
+                token_count,
+                ..
+            } => {
+                content.push_str(&text);
+                finish_reason = Some(finish_reason_str(reason));
+                usage = usage_from(reported, token_count);
+            }
+            CandleCompletionChunk::Error(error) => return Err(ApiError::server_error(error)),
+            _ => {}
+        }
+    }
+
+    Ok(ChatCompletionResponse {
+        id,
+        object: "chat.completion".to_string(),
+        created,
+        model,
+        choices: vec![Choice {
+            index: 0,
+            message: ResponseMessage {
+                role: "assistant".to_string(),
+                content,
+            },
+            finish_reason: finish_reason.or_else(|| Some("stop".to_string())),
+        }],
+        usage,
+    })
+}
+
+/// Stream a completion as `chat.completion.chunk` SSE events ending in `[DONE]`
+fn stream_completion(
+    completion: std::pin::Pin<Box<dyn tokio_stream::Stream<Item = CandleCompletionChunk> + Send>>,
+    id: String,
+    created: i64,
+    model: String,
+) -> Sse<impl tokio_stream::Stream<Item = Result<Event, Infallible>>> {
+    let events = crate::async_stream::spawn_stream(move |sender| async move {
+        let chunk_event = |delta: Delta, finish_reason: Option<String>| {
+            let chunk = ChatCompletionChunk {
+                id: id.clone(),
+                object: "chat.completion.chunk".to_string(),
+                created,
+                model: model.clone(),
+                choices: vec![ChunkChoice {
+                    index: 0,
+                    delta,
+                    finish_reason,
+                }],
+            };
+            Event::default().json_data(chunk).unwrap_or_default()
+        };
+
+        let _ = sender.send(Ok(chunk_event(
+            Delta {
+                role: Some("assistant".to_string()),
+                content: None,
+            },
+            None,
+        )));
+
+        tokio::pin!(completion);
+        let mut finished = false;
+        while let Some(chunk) = completion.next().await {
+            match chunk {
+                CandleCompletionChunk::Text(text) if !text.is_empty() => {
+                    let delta = Delta {
+                        role: None,
+                        content: Some(text),
+                    };
+                    let _ = sender.send(Ok(chunk_event(delta, None)));
+                }
+                CandleCompletionChunk::Complete {
+                    text,
+                    finish_reason,
+                    ..
+                } => {
+                    if !text.is_empty() {
+                        let delta = Delta {
+                            role: None,
+                            content: Some(text),
+                        };
+                        let _ = sender.send(Ok(chunk_event(delta, None)));
+                    }
+                    let reason = finish_reason_str(finish_reason);
+                    let _ = sender.send(Ok(chunk_event(Delta::default(), Some(reason))));
+                    finished = true;
+                }
+                CandleCompletionChunk::Error(error) => {
+                    let body = ApiError::server_error(error).body;
+                    let _ = sender.send(Ok(Event::default().json_data(body).unwrap_or_default()));
+                    finished = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        if !finished {
+            let _ = sender.send(Ok(chunk_event(Delta::default(), Some("stop".to_string()))));
+        }
+        let _ = sender.send(Ok(Event::default().data("[DONE]")));
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// OpenAI `finish_reason` string for a provider finish reason
+fn finish_reason_str(reason: Option<FinishReason>) -> String {
+    match reason {
+        Some(FinishReason::Length) => "length",
+        Some(FinishReason::ContentFilter) => "content_filter",
+        Some(FinishReason::ToolCalls) => "tool_calls",
+        Some(FinishReason::Stop | FinishReason::Error) | None => "stop",
+    }
+    .to_string()
+}
+
+/// Usage from provider-reported counts, falling back to the generated token count
+fn usage_from(usage: Option<crate::domain::model::CandleUsage>, token_count: Option<u32>) -> Usage {
+    match usage {
+        Some(usage) => Usage {
+            prompt_tokens: usage.input_tokens,
+            completion_tokens: usage.output_tokens,
+            total_tokens: usage.total_tokens,
+        },
+        None => {
+            let completion_tokens = token_count.unwrap_or(0);
+            Usage {
+                prompt_tokens: 0,
+                completion_tokens,
+                total_tokens: completion_tokens,
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! OpenAI-compatible HTTP server backed by the local providers
+//! This module is feature-gated with the "api" feature
+//!
+//! Serves `POST /v1/chat/completions` (with SSE streaming) and `GET /v1/models`
+//! so existing OpenAI clients and UIs can talk to locally registered models.
+
+#[cfg(feature = "api")]
+pub mod handlers;
+#[cfg(feature = "api")]
+pub mod models;
+#[cfg(feature = "api")]
+pub mod routes;
+
+#[cfg(feature = "api")]
+use std::net::SocketAddr;
+#[cfg(feature = "api")]
+use std::sync::Arc;
+
+#[cfg(feature = "api")]
+use axum::Router;
+
+/// Settings shared by all request handlers
+#[derive(Debug, Clone)]
+pub struct ServerState {
+    /// Registry key used when a request omits `model`
+    pub default_model: Option<String>,
+    /// Temperature used when a request omits `temperature`
+    pub temperature: f64,
+    /// Token limit used when a request omits `max_tokens`
+    pub max_tokens: u64,
+}
+
+impl Default for ServerState {
+    fn default() -> Self {
+        Self {
+            default_model: None,
+            temperature: 0.0,
+            max_tokens: 2000,
+        }
+    }
+}
+
+/// OpenAI-compatible API server
+#[cfg(feature = "api")]
+pub struct OpenAiServer {
+    /// Address to listen on
+    addr: SocketAddr,
+    /// Router with all OpenAI endpoints
+    router: Router,
+}
+
+#[cfg(feature = "api")]
+impl OpenAiServer {
+    /// Create a new server listening on `host:port`
+    pub fn new(
+        host: &str,
+        port: u16,
+        state: ServerState,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let addr = format!("{}:{}", host, port).parse::<SocketAddr>()?;
+        let router = routes::create_router(Arc::new(state));
+
+        Ok(Self { addr, router })
+    }
+
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Start the server and serve until it fails
+    pub async fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
+        log::info!("OpenAI-compatible server listening on {}", self.addr);
+
+        let listener = tokio::net::TcpListener::bind(&self.addr).await?;
+        axum::serve(listener, self.router.clone()).await?;
+
+        Ok(())
+    }
+}
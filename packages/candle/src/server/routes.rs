@@ -0,0 +1,23 @@
+//! API routes for the OpenAI-compatible server
+//! This module defines the HTTP routes and endpoints
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+use tower_http::cors::CorsLayer;
+
+use super::ServerState;
+use super::handlers::{chat_completions, list_models};
+
+/// Create the OpenAI-compatible router
+pub fn create_router(state: Arc<ServerState>) -> Router {
+    Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .route("/v1/models", get(list_models))
+        // Browser-based UIs call the API cross-origin
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
@@ -0,0 +1,504 @@
+//! Multi-agent orchestration combinators
+//!
+//! Agents are composed into multi-agent systems with three patterns:
+//! - [`parallel`]: every agent answers the same prompt concurrently, then the
+//!   responses are aggregated
+//! - [`router`]: a classifier agent picks the agent that handles the prompt
+//! - [`debate`]: participants argue over several rounds, optionally settled
+//!   by a judge
+//!
+//! Every combinator streams [`AgentChunk`]s - `CandleMessageChunk`s tagged
+//! with the agent that produced them - and implements [`Op`], so it composes
+//! with the rest of the workflow module.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cyrup_candle::prelude::{CandleAgentBuilder, CandleAgentRoleBuilder, CandleFluentAi};
+//! use cyrup_candle::workflow::agents::{agent_fn, debate};
+//!
+//! let agent = |name: &'static str, role: &'static str| {
+//!     agent_fn(name, move |prompt| {
+//!         CandleFluentAi::agent_role(name)
+//!             .into_agent()
+//!             .system_prompt(role)
+//!             .chat_with_message(prompt)
+//!     })
+//! };
+//!
+//! let stream = debate(2)
+//!     .participant(agent("optimist", "Argue for the proposal."))
+//!     .participant(agent("skeptic", "Argue against the proposal."))
+//!     .judge(agent("judge", "Decide which side argued better."))
+//!     .execute("Should we rewrite the parser?".to_string());
+//! ```
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use cyrup_sugars::prelude::MessageChunk;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::builders::agent_role::CandleAgentRoleAgent;
+use crate::domain::chat::CandleChatLoop;
+use crate::domain::chat::message::CandleMessageChunk;
+use crate::workflow::ops::Op;
+
+/// Tag of chunks produced by a [`parallel`] combinator itself
+pub const PARALLEL_AGENT: &str = "parallel";
+
+/// Tag of chunks produced by a [`router`] combinator itself
+pub const ROUTER_AGENT: &str = "router";
+
+/// Tag of chunks produced by a [`debate`] combinator itself
+pub const DEBATE_AGENT: &str = "debate";
+
+type ChunkStream = Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>>;
+type AgentChunkStream = Pin<Box<dyn Stream<Item = AgentChunk> + Send>>;
+type AggregateFn = Arc<dyn Fn(&[AgentResponse]) -> String + Send + Sync>;
+
+/// An agent that can take part in an orchestration
+pub trait WorkflowAgent: Send + Sync + 'static {
+    /// Name used to tag the agent's chunks
+    fn name(&self) -> &str;
+
+    /// Stream the agent's response to `prompt`
+    fn respond(&self, prompt: String) -> ChunkStream;
+}
+
+impl WorkflowAgent for CandleAgentRoleAgent {
+    fn name(&self) -> &str {
+        CandleAgentRoleAgent::name(self)
+    }
+
+    fn respond(&self, prompt: String) -> ChunkStream {
+        self.chat(CandleChatLoop::UserPrompt(prompt))
+    }
+}
+
+/// Agent backed by a closure, created with [`agent_fn`]
+pub struct FnAgent<F> {
+    name: String,
+    respond: F,
+}
+
+impl<F> WorkflowAgent for FnAgent<F>
+where
+    F: Fn(String) -> ChunkStream + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn respond(&self, prompt: String) -> ChunkStream {
+        (self.respond)(prompt)
+    }
+}
+
+/// Wrap a closure as a named agent
+///
+/// Agent builders are consumed by `chat_with_message`, so the closure
+/// usually builds a fresh agent for every prompt.
+pub fn agent_fn<F>(name: impl Into<String>, respond: F) -> Arc<dyn WorkflowAgent>
+where
+    F: Fn(String) -> ChunkStream + Send + Sync + 'static,
+{
+    Arc::new(FnAgent {
+        name: name.into(),
+        respond,
+    })
+}
+
+/// A message chunk tagged with the agent that produced it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentChunk {
+    /// Name of the originating agent or combinator
+    pub agent: String,
+    /// The chunk itself
+    pub chunk: CandleMessageChunk,
+}
+
+impl AgentChunk {
+    /// Tag `chunk` with `agent`
+    pub fn new(agent: impl Into<String>, chunk: CandleMessageChunk) -> Self {
+        Self {
+            agent: agent.into(),
+            chunk,
+        }
+    }
+}
+
+impl MessageChunk for AgentChunk {
+    fn bad_chunk(error: String) -> Self {
+        Self::new(String::new(), CandleMessageChunk::Error(error))
+    }
+
+    fn error(&self) -> Option<&str> {
+        self.chunk.error()
+    }
+}
+
+/// One agent's complete response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentResponse {
+    /// Name of the agent
+    pub agent: String,
+    /// Response text
+    pub text: String,
+}
+
+/// How [`parallel`] combines the agents' responses
+#[derive(Clone)]
+pub enum Aggregation {
+    /// Join the responses under per-agent headings
+    Concatenate,
+    /// Combine the responses with a function
+    Custom(AggregateFn),
+    /// Ask an agent to merge the responses; its chunks are streamed
+    Synthesize(Arc<dyn WorkflowAgent>),
+}
+
+/// Concurrent fan-out to several agents, created with [`parallel`]
+#[derive(Clone)]
+pub struct AgentParallel {
+    agents: Vec<Arc<dyn WorkflowAgent>>,
+    aggregation: Aggregation,
+}
+
+/// Run every agent on the same prompt concurrently
+///
+/// Chunks stream in arrival order. Once every agent has finished, the
+/// responses are aggregated (by default concatenated) into a final
+/// `Complete` chunk tagged [`PARALLEL_AGENT`].
+pub fn parallel(agents: impl IntoIterator<Item = Arc<dyn WorkflowAgent>>) -> AgentParallel {
+    AgentParallel {
+        agents: agents.into_iter().collect(),
+        aggregation: Aggregation::Concatenate,
+    }
+}
+
+impl AgentParallel {
+    /// Set how responses are combined
+    #[must_use]
+    pub fn aggregate(mut self, aggregation: Aggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Combine responses with a function
+    #[must_use]
+    pub fn aggregate_with<F>(self, aggregate: F) -> Self
+    where
+        F: Fn(&[AgentResponse]) -> String + Send + Sync + 'static,
+    {
+        self.aggregate(Aggregation::Custom(Arc::new(aggregate)))
+    }
+
+    /// Stream all agents' responses to `input`, then the aggregate
+    pub fn execute(&self, input: String) -> AgentChunkStream {
+        let agents = self.agents.clone();
+        let aggregation = self.aggregation.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(move |tx| async move {
+            let handles: Vec<_> = agents
+                .iter()
+                .map(|agent| {
+                    let agent = agent.clone();
+                    let input = input.clone();
+                    let tx = tx.clone();
+                    tokio::spawn(async move { forward(agent.as_ref(), input, &tx).await })
+                })
+                .collect();
+
+            let mut responses = Vec::with_capacity(agents.len());
+            for (agent, handle) in agents.iter().zip(handles) {
+                let text = match handle.await {
+                    Ok(Some(text)) => text,
+                    // The consumer went away
+                    Ok(None) => return,
+                    Err(e) => {
+                        log::warn!("Parallel agent '{}' panicked: {}", agent.name(), e);
+                        String::new()
+                    }
+                };
+                responses.push(AgentResponse {
+                    agent: agent.name().to_string(),
+                    text,
+                });
+            }
+
+            let aggregate = match aggregation {
+                Aggregation::Concatenate => render_responses(&responses),
+                Aggregation::Custom(aggregate) => aggregate(&responses),
+                Aggregation::Synthesize(synthesizer) => {
+                    let prompt = format!(
+                        "Combine these responses to the request into one answer.\n\n\
+                         Request: {input}\n\n{}",
+                        render_responses(&responses)
+                    );
+                    forward(synthesizer.as_ref(), prompt, &tx).await;
+                    return;
+                }
+            };
+            let _ = tx.send(AgentChunk::new(PARALLEL_AGENT, complete(aggregate)));
+        }))
+    }
+}
+
+impl Op<String, AgentChunk> for AgentParallel {
+    fn call(&self, input: String) -> AgentChunkStream {
+        self.execute(input)
+    }
+}
+
+/// Classifier-based dispatch, created with [`router`]
+#[derive(Clone)]
+pub struct AgentRouter {
+    classifier: Arc<dyn WorkflowAgent>,
+    routes: Vec<(String, Arc<dyn WorkflowAgent>)>,
+    fallback: Option<Arc<dyn WorkflowAgent>>,
+}
+
+/// Let `classifier` pick which route handles each prompt
+///
+/// The classifier is asked to reply with one route name; the route named
+/// earliest in its reply (case-insensitive) receives the original prompt.
+/// Both the classifier's and the chosen agent's chunks are streamed.
+pub fn router<N>(
+    classifier: Arc<dyn WorkflowAgent>,
+    routes: impl IntoIterator<Item = (N, Arc<dyn WorkflowAgent>)>,
+) -> AgentRouter
+where
+    N: Into<String>,
+{
+    AgentRouter {
+        classifier,
+        routes: routes
+            .into_iter()
+            .map(|(name, agent)| (name.into(), agent))
+            .collect(),
+        fallback: None,
+    }
+}
+
+impl AgentRouter {
+    /// Add a route
+    #[must_use]
+    pub fn route(mut self, name: impl Into<String>, agent: Arc<dyn WorkflowAgent>) -> Self {
+        self.routes.push((name.into(), agent));
+        self
+    }
+
+    /// Agent handling prompts the classifier did not assign to any route
+    #[must_use]
+    pub fn fallback(mut self, agent: Arc<dyn WorkflowAgent>) -> Self {
+        self.fallback = Some(agent);
+        self
+    }
+
+    /// Classify `input` and stream the chosen agent's response
+    pub fn execute(&self, input: String) -> AgentChunkStream {
+        let router = self.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(move |tx| async move {
+            let names: Vec<&str> = router
+                .routes
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect();
+            let prompt = format!(
+                "Classify the request into exactly one of these routes: {}.\n\
+                 Reply with the route name only.\n\nRequest: {input}",
+                names.join(", ")
+            );
+            let Some(reply) = forward(router.classifier.as_ref(), prompt, &tx).await else {
+                return;
+            };
+
+            let agent = match select_route(&reply, &names) {
+                Some(index) => &router.routes[index].1,
+                None => match &router.fallback {
+                    Some(fallback) => fallback,
+                    None => {
+                        let _ = tx.send(AgentChunk::new(
+                            ROUTER_AGENT,
+                            CandleMessageChunk::Error(format!(
+                                "Classifier reply '{}' matches no route",
+                                reply.trim()
+                            )),
+                        ));
+                        return;
+                    }
+                },
+            };
+            forward(agent.as_ref(), input, &tx).await;
+        }))
+    }
+}
+
+impl Op<String, AgentChunk> for AgentRouter {
+    fn call(&self, input: String) -> AgentChunkStream {
+        self.execute(input)
+    }
+}
+
+/// Multi-round argument between agents, created with [`debate`]
+#[derive(Clone)]
+pub struct AgentDebate {
+    rounds: usize,
+    participants: Vec<Arc<dyn WorkflowAgent>>,
+    judge: Option<Arc<dyn WorkflowAgent>>,
+}
+
+/// Debate a prompt for `rounds` rounds (at least one)
+///
+/// Participants speak in order each round and see every earlier argument.
+/// A judge, if set, reads the full transcript and gives the final answer.
+pub fn debate(rounds: usize) -> AgentDebate {
+    AgentDebate {
+        rounds: rounds.max(1),
+        participants: Vec::new(),
+        judge: None,
+    }
+}
+
+impl AgentDebate {
+    /// Add a participant
+    #[must_use]
+    pub fn participant(mut self, agent: Arc<dyn WorkflowAgent>) -> Self {
+        self.participants.push(agent);
+        self
+    }
+
+    /// Add several participants
+    #[must_use]
+    pub fn participants(
+        mut self,
+        agents: impl IntoIterator<Item = Arc<dyn WorkflowAgent>>,
+    ) -> Self {
+        self.participants.extend(agents);
+        self
+    }
+
+    /// Agent that settles the debate
+    #[must_use]
+    pub fn judge(mut self, agent: Arc<dyn WorkflowAgent>) -> Self {
+        self.judge = Some(agent);
+        self
+    }
+
+    /// Stream every argument, then the judge's verdict
+    pub fn execute(&self, input: String) -> AgentChunkStream {
+        let debate = self.clone();
+
+        Box::pin(crate::async_stream::spawn_stream(move |tx| async move {
+            if debate.participants.is_empty() {
+                let _ = tx.send(AgentChunk::new(
+                    DEBATE_AGENT,
+                    CandleMessageChunk::Error("Debate has no participants".to_string()),
+                ));
+                return;
+            }
+
+            let mut transcript: Vec<AgentResponse> = Vec::new();
+            for round in 1..=debate.rounds {
+                for participant in &debate.participants {
+                    let prompt = if transcript.is_empty() {
+                        format!(
+                            "Debate topic: {input}\n\nRound {round} of {}. Give your opening argument.",
+                            debate.rounds
+                        )
+                    } else {
+                        format!(
+                            "Debate topic: {input}\n\nArguments so far:\n\n{}\
+                             Round {round} of {}. Respond to the other arguments and \
+                             strengthen your position.",
+                            render_responses(&transcript),
+                            debate.rounds
+                        )
+                    };
+                    let Some(text) = forward(participant.as_ref(), prompt, &tx).await else {
+                        return;
+                    };
+                    transcript.push(AgentResponse {
+                        agent: participant.name().to_string(),
+                        text,
+                    });
+                }
+            }
+
+            if let Some(judge) = &debate.judge {
+                let prompt = format!(
+                    "Debate topic: {input}\n\nTranscript:\n\n{}\
+                     Weigh the arguments and give the final answer.",
+                    render_responses(&transcript)
+                );
+                forward(judge.as_ref(), prompt, &tx).await;
+            }
+        }))
+    }
+}
+
+impl Op<String, AgentChunk> for AgentDebate {
+    fn call(&self, input: String) -> AgentChunkStream {
+        self.execute(input)
+    }
+}
+
+/// Stream `agent`'s response tagged with its name and return the response text
+///
+/// Returns `None` once the consumer has gone away.
+async fn forward(
+    agent: &dyn WorkflowAgent,
+    prompt: String,
+    tx: &UnboundedSender<AgentChunk>,
+) -> Option<String> {
+    let mut stream = agent.respond(prompt);
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        if let CandleMessageChunk::Text(part) | CandleMessageChunk::Complete { text: part, .. } =
+            &chunk
+        {
+            text.push_str(part);
+        }
+        tx.send(AgentChunk::new(agent.name(), chunk)).ok()?;
+    }
+    Some(text)
+}
+
+/// Index of the route named earliest in `reply`, preferring longer names on ties
+fn select_route(reply: &str, routes: &[&str]) -> Option<usize> {
+    let reply = reply.to_lowercase();
+    routes
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| !name.is_empty())
+        .filter_map(|(index, name)| {
+            reply
+                .find(&name.to_lowercase())
+                .map(|position| (position, std::cmp::Reverse(name.len()), index))
+        })
+        .min()
+        .map(|(_, _, index)| index)
+}
+
+/// Render responses under `### <agent>` headings
+fn render_responses(responses: &[AgentResponse]) -> String {
+    responses
+        .iter()
+        .map(|response| format!("### {}\n{}\n\n", response.agent, response.text.trim()))
+        .collect()
+}
+
+fn complete(text: String) -> CandleMessageChunk {
+    CandleMessageChunk::Complete {
+        text,
+        finish_reason: Some("stop".to_string()),
+        usage: None,
+        token_count: None,
+        elapsed_secs: None,
+        tokens_per_sec: None,
+        provider: None,
+    }
+}
@@ -5,9 +5,12 @@
 //! AsyncStream<Out> without Future/Result wrapping in execution paths.
 //!
 //! ## Core Components
+//! - **agents**: Multi-agent orchestration (parallel, router, debate)
 //! - **core**: CandleWorkflowStep trait and CandleExecutableWorkflow struct
 //! - **ops**: Zero-cost operation combinators and transformations
 //! - **parallel**: Thread-based parallel execution combinators  
+//! - **scheduler**: Cron and interval schedules with persisted run history
+//! - **triggers**: Webhook and `MCP` notification triggers for schedules
 //! - **macros**: Compile-time variadic parallel execution macros
 //!
 //! ## Architecture Principles
@@ -18,15 +21,31 @@
 //! - Extensive inlining for blazing-fast performance
 //! - Lock-free design for maximum throughput
 
+pub mod agents;
 pub mod core;
 pub mod macros;
 pub mod ops;
 pub mod parallel;
+pub mod scheduler;
+pub mod triggers;
 
 // Re-export candle core types for ergonomic imports
 pub use core::{CandleExecutableWorkflow, CandleWorkflowStep, candle_workflow};
 
+// Re-export multi-agent orchestration types
+pub use agents::{
+    AgentChunk, AgentDebate, AgentParallel, AgentResponse, AgentRouter, Aggregation,
+    WorkflowAgent, agent_fn,
+};
+
 // Re-export main public macro and types
 pub use macros::parallel;
 pub use ops::{DynOp, Op, map, passthrough, then};
 pub use parallel::{ParallelBuilder, ParallelN};
+
+// Re-export scheduling types
+pub use scheduler::{
+    RunRecord, RunStatus, ScheduleSpec, ScheduleStore, Scheduler, SchedulerError,
+    WorkflowSchedule,
+};
+pub use triggers::{TriggerDispatcher, TriggerEvent, render_prompt};
@@ -0,0 +1,883 @@
+//! Scheduled and background workflow runs
+//!
+//! A [`WorkflowSchedule`] pairs a prompt with a [`ScheduleSpec`] - a cron
+//! expression, a fixed interval or an event - and the agent settings to answer
+//! it with.
+//! Schedules and their history are stored in the agent's `SurrealKV` store:
+//! - `workflow_schedule` rows hold each schedule and when it is next due
+//! - `workflow_run` rows record every run with its status, output, tool calls
+//!   and artifacts
+//!
+//! Webhook and notification specs are never due; they run when
+//! [`triggers`](crate::workflow::triggers) match an incoming event.
+//!
+//! A [`Scheduler`] polls the store and runs due schedules headlessly through a
+//! [`WorkflowAgent`], so agents built with the fluent API keep their tool
+//! access. Runs missed while no scheduler was polling are not replayed: an
+//! overdue schedule runs once and is then rescheduled from the current time.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use cyrup_candle::prelude::{CandleAgentBuilder, CandleAgentRoleBuilder, CandleFluentAi};
+//! use cyrup_candle::workflow::agents::agent_fn;
+//! use cyrup_candle::workflow::scheduler::{ScheduleSpec, ScheduleStore, Scheduler, WorkflowSchedule};
+//!
+//! # async fn example() -> Result<(), cyrup_candle::workflow::scheduler::SchedulerError> {
+//! let store = ScheduleStore::open().await?;
+//! store
+//!     .add(&WorkflowSchedule::new(
+//!         "standup",
+//!         ScheduleSpec::cron("0 9 * * MON-FRI")?,
+//!         "Summarize yesterday's commits in this repository",
+//!     ))
+//!     .await?;
+//!
+//! let scheduler = Scheduler::new(store, |schedule: &WorkflowSchedule| {
+//!     let role = schedule.agent_role.clone();
+//!     Ok(agent_fn(schedule.name.clone(), move |prompt| {
+//!         CandleFluentAi::agent_role(role.clone())
+//!             .into_agent()
+//!             .chat_with_message(prompt)
+//!     }))
+//! });
+//! scheduler.run_until(tokio::signal::ctrl_c(), |_| {}).await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use tokio_stream::StreamExt;
+
+use crate::domain::chat::message::CandleMessageChunk;
+use crate::workflow::agents::WorkflowAgent;
+
+/// How often a scheduler looks for due schedules when no interval is set
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest a scheduled run may take when no timeout is set
+pub const DEFAULT_RUN_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Agent role of schedules that do not name one
+pub const DEFAULT_AGENT_ROLE: &str = "CYRUP.ai";
+
+type AgentFactory =
+    Arc<dyn Fn(&WorkflowSchedule) -> Result<Arc<dyn WorkflowAgent>, SchedulerError> + Send + Sync>;
+
+/// Scheduler errors
+#[derive(Debug, thiserror::Error)]
+pub enum SchedulerError {
+    #[error("Scheduler database error: {0}")]
+    Database(String),
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+    #[error("Schedule not found: {0}")]
+    ScheduleNotFound(String),
+    #[error("Schedule id '{0}' matches more than one schedule")]
+    AmbiguousSchedule(String),
+    #[error("A schedule named '{0}' already exists")]
+    DuplicateName(String),
+    #[error("Agent unavailable: {0}")]
+    Agent(String),
+    #[error("Trigger listener error: {0}")]
+    Listener(String),
+    #[error("Notification subscription failed: {0}")]
+    Subscription(String),
+}
+
+/// When a schedule runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ScheduleSpec {
+    /// Cron expression with a seconds field, evaluated in local time
+    Cron { expression: String },
+    /// Fixed interval between runs
+    Interval { every_secs: u64 },
+    /// Run when a webhook is posted to `path` on the trigger listener
+    Webhook {
+        path: String,
+        /// Value the `X-Webhook-Secret` header must carry, if any
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    /// Run when the `MCP` server started by `server` sends a `method` notification
+    Notification {
+        /// Command and arguments of a stdio `MCP` server
+        server: Vec<String>,
+        method: String,
+    },
+}
+
+impl ScheduleSpec {
+    /// Cron schedule
+    ///
+    /// Standard five-field expressions (`min hour day month weekday`) get a
+    /// leading seconds field of `0`. Weekdays are safest written by name
+    /// (`MON-FRI`), since numbered weekdays start at 1 for Sunday.
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::InvalidSchedule` if the expression does not parse
+    pub fn cron(expression: &str) -> Result<Self, SchedulerError> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let expression = if fields.len() == 5 {
+            format!("0 {}", fields.join(" "))
+        } else {
+            fields.join(" ")
+        };
+        parse_cron(&expression)?;
+        Ok(Self::Cron { expression })
+    }
+
+    /// Schedule repeating every `interval`, rounded down to whole seconds
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::InvalidSchedule` for intervals under a second
+    pub fn every(interval: Duration) -> Result<Self, SchedulerError> {
+        match interval.as_secs() {
+            0 => Err(SchedulerError::InvalidSchedule(
+                "interval must be at least one second".to_string(),
+            )),
+            every_secs => Ok(Self::Interval { every_secs }),
+        }
+    }
+
+    /// Interval written as a count with an `s`, `m`, `h` or `d` suffix, e.g. `15m`
+    ///
+    /// A bare count is read as seconds.
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::InvalidSchedule` if the interval does not parse
+    pub fn parse_interval(text: &str) -> Result<Self, SchedulerError> {
+        let text = text.trim();
+        let invalid = || SchedulerError::InvalidSchedule(format!("invalid interval '{}'", text));
+
+        let (count, unit) = text.split_at(
+            text.find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(text.len()),
+        );
+        let count: u64 = count.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 3_600,
+            "d" => 86_400,
+            _ => return Err(invalid()),
+        };
+        let secs = count.checked_mul(unit_secs).ok_or_else(invalid)?;
+        Self::every(Duration::from_secs(secs))
+    }
+
+    /// Run on webhooks posted to `path`, optionally guarded by `secret`
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::InvalidSchedule` if `path` does not start with `/`
+    pub fn webhook(path: &str, secret: Option<String>) -> Result<Self, SchedulerError> {
+        let path = path.trim();
+        if !path.starts_with('/') {
+            return Err(SchedulerError::InvalidSchedule(format!(
+                "webhook path '{}' must start with '/'",
+                path
+            )));
+        }
+        Ok(Self::Webhook {
+            path: webhook_path(path),
+            secret: secret.filter(|secret| !secret.is_empty()),
+        })
+    }
+
+    /// Run on `method` notifications from the stdio `MCP` server `server`
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::InvalidSchedule` if `server` or `method` is empty
+    pub fn notification(server: Vec<String>, method: &str) -> Result<Self, SchedulerError> {
+        if server.is_empty() || method.trim().is_empty() {
+            return Err(SchedulerError::InvalidSchedule(
+                "notification triggers need a server command and a method".to_string(),
+            ));
+        }
+        Ok(Self::Notification {
+            server,
+            method: method.trim().to_string(),
+        })
+    }
+
+    /// Whether runs are started by events rather than the clock
+    pub fn is_event(&self) -> bool {
+        matches!(self, Self::Webhook { .. } | Self::Notification { .. })
+    }
+
+    /// First run time after `after_ms` (Unix milliseconds)
+    ///
+    /// `None` when a cron expression has no further times, and always for
+    /// event-driven specs.
+    pub fn next_after(&self, after_ms: i64) -> Option<i64> {
+        match self {
+            Self::Cron { expression } => {
+                let schedule = parse_cron(expression).ok()?;
+                let after = Local.timestamp_millis_opt(after_ms).single()?;
+                schedule
+                    .after(&after)
+                    .next()
+                    .map(|next| next.timestamp_millis())
+            }
+            Self::Interval { every_secs } => {
+                let every_ms = i64::try_from(*every_secs).ok()?.checked_mul(1_000)?;
+                after_ms.checked_add(every_ms)
+            }
+            Self::Webhook { .. } | Self::Notification { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ScheduleSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cron { expression } => write!(f, "cron {}", expression),
+            Self::Interval { every_secs } => {
+                let (count, unit) = [(86_400, "d"), (3_600, "h"), (60, "m")]
+                    .into_iter()
+                    .find(|(unit_secs, _)| every_secs % unit_secs == 0)
+                    .map_or((*every_secs, "s"), |(unit_secs, unit)| {
+                        (every_secs / unit_secs, unit)
+                    });
+                write!(f, "every {}{}", count, unit)
+            }
+            Self::Webhook { path, .. } => write!(f, "webhook {}", path),
+            Self::Notification { server, method } => {
+                write!(f, "on {} from {}", method, server.join(" "))
+            }
+        }
+    }
+}
+
+impl FromStr for ScheduleSpec {
+    type Err = SchedulerError;
+
+    /// An interval such as `15m`, or otherwise a cron expression
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with(|c: char| c.is_ascii_digit()) && !s.contains(char::is_whitespace) {
+            Self::parse_interval(s)
+        } else {
+            Self::cron(s)
+        }
+    }
+}
+
+/// `path` without trailing slashes, as webhook paths are compared
+pub(crate) fn webhook_path(path: &str) -> String {
+    match path.trim().trim_end_matches('/') {
+        "" => "/".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+fn parse_cron(expression: &str) -> Result<cron::Schedule, SchedulerError> {
+    cron::Schedule::from_str(expression).map_err(|e| {
+        SchedulerError::InvalidSchedule(format!("invalid cron expression '{}': {}", expression, e))
+    })
+}
+
+/// A prompt run on a schedule or when a trigger fires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowSchedule {
+    pub schedule_id: String,
+    /// Unique name the schedule is managed by
+    pub name: String,
+    pub spec: ScheduleSpec,
+    /// Prompt sent to the agent on every run
+    ///
+    /// Triggered runs render it as a template over the event first; see
+    /// [`render_prompt`](crate::workflow::triggers::render_prompt).
+    pub prompt: String,
+    pub agent_role: String,
+    /// Registry key of the model; the builder's default when unset
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    #[serde(default)]
+    pub temperature: f64,
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    /// Disabled schedules are kept but never run
+    pub enabled: bool,
+    pub created_at_ms: i64,
+    /// When the schedule is next due (Unix ms); `None` once it has no further times
+    #[serde(default)]
+    pub next_run_ms: Option<i64>,
+    #[serde(default)]
+    pub last_run_ms: Option<i64>,
+}
+
+impl WorkflowSchedule {
+    /// Enabled schedule first due at the spec's next time after now
+    pub fn new(name: impl Into<String>, spec: ScheduleSpec, prompt: impl Into<String>) -> Self {
+        let now = chrono::Utc::now().timestamp_millis();
+        Self {
+            schedule_id: uuid::Uuid::new_v4().simple().to_string(),
+            name: name.into(),
+            next_run_ms: spec.next_after(now),
+            spec,
+            prompt: prompt.into(),
+            agent_role: DEFAULT_AGENT_ROLE.to_string(),
+            model: None,
+            system_prompt: None,
+            temperature: 0.0,
+            max_tokens: None,
+            enabled: true,
+            created_at_ms: now,
+            last_run_ms: None,
+        }
+    }
+
+    /// Answer with the model registered under `registry_key`
+    pub fn with_model(mut self, registry_key: impl Into<String>) -> Self {
+        self.model = Some(registry_key.into());
+        self
+    }
+
+    /// Answer as `agent_role`
+    pub fn with_agent_role(mut self, agent_role: impl Into<String>) -> Self {
+        self.agent_role = agent_role.into();
+        self
+    }
+
+    /// Configure the agent with `system_prompt`
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Sample at `temperature`
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Generate at most `max_tokens` per run
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Whether the schedule should run at `now_ms`
+    pub fn is_due(&self, now_ms: i64) -> bool {
+        self.enabled && self.next_run_ms.is_some_and(|next| next <= now_ms)
+    }
+}
+
+/// Outcome of a scheduled run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Success,
+    Failure,
+}
+
+impl std::fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        })
+    }
+}
+
+/// A tool call the agent made during a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunToolCall {
+    pub name: String,
+    pub input: String,
+}
+
+/// History entry for one run of a schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub run_id: String,
+    pub schedule_id: String,
+    pub schedule_name: String,
+    pub status: RunStatus,
+    /// Text the agent produced, including partial output of failed runs
+    pub output: String,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<RunToolCall>,
+    /// Files written for the run
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+    pub started_at_ms: i64,
+    pub finished_at_ms: i64,
+}
+
+impl RunRecord {
+    /// Wall time of the run in milliseconds
+    pub fn elapsed_ms(&self) -> i64 {
+        self.finished_at_ms - self.started_at_ms
+    }
+}
+
+/// Run `schedule`'s prompt through `agent`, giving up after `timeout`
+///
+/// The record is not stored; [`Scheduler::run`] adds artifacts and persists it.
+pub async fn run_agent(
+    agent: &dyn WorkflowAgent,
+    schedule: &WorkflowSchedule,
+    timeout: Duration,
+) -> RunRecord {
+    let started_at_ms = chrono::Utc::now().timestamp_millis();
+    let mut output = String::new();
+    let mut tool_calls = Vec::new();
+    let mut error = None;
+
+    let mut stream = agent.respond(schedule.prompt.clone());
+    let collect = async {
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                CandleMessageChunk::Text(text) => output.push_str(&text),
+                CandleMessageChunk::ToolCallComplete { name, input, .. } => {
+                    tool_calls.push(RunToolCall { name, input });
+                }
+                CandleMessageChunk::Complete { text, .. } if output.is_empty() => output = text,
+                CandleMessageChunk::Error(message) => {
+                    error = Some(message);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    };
+    if tokio::time::timeout(timeout, collect).await.is_err() {
+        error = Some(format!("run timed out after {}s", timeout.as_secs()));
+    }
+
+    RunRecord {
+        run_id: uuid::Uuid::new_v4().simple().to_string(),
+        schedule_id: schedule.schedule_id.clone(),
+        schedule_name: schedule.name.clone(),
+        status: if error.is_some() {
+            RunStatus::Failure
+        } else {
+            RunStatus::Success
+        },
+        output,
+        error,
+        tool_calls,
+        artifacts: Vec::new(),
+        started_at_ms,
+        finished_at_ms: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// `SurrealKV`-backed schedules and run history
+#[derive(Debug, Clone)]
+pub struct ScheduleStore {
+    db: Surreal<Any>,
+}
+
+impl ScheduleStore {
+    /// Create a store over an existing connection (call `initialize` before use)
+    pub fn new(db: Surreal<Any>) -> Self {
+        Self { db }
+    }
+
+    /// Open the store on the shared agent database and define its tables
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the database cannot be opened or
+    /// the schema cannot be defined
+    pub async fn open() -> Result<Self, SchedulerError> {
+        let db = crate::domain::init::agent_database()
+            .await
+            .map_err(|e| SchedulerError::Database(e.to_string()))?;
+        let store = Self::new(db);
+        store.initialize().await?;
+        Ok(store)
+    }
+
+    /// Define the schedule and run tables and indexes
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the schema cannot be defined
+    pub async fn initialize(&self) -> Result<(), SchedulerError> {
+        self.db
+            .query(
+                "
+                DEFINE TABLE IF NOT EXISTS workflow_schedule SCHEMALESS;
+                DEFINE INDEX IF NOT EXISTS workflow_schedule_id ON workflow_schedule FIELDS schedule_id UNIQUE;
+                DEFINE INDEX IF NOT EXISTS workflow_schedule_name ON workflow_schedule FIELDS name UNIQUE;
+                DEFINE TABLE IF NOT EXISTS workflow_run SCHEMALESS;
+                DEFINE INDEX IF NOT EXISTS workflow_run_order ON workflow_run FIELDS schedule_id, started_at_ms;
+                ",
+            )
+            .await
+            .map_err(|e| {
+                SchedulerError::Database(format!("Failed to define scheduler tables: {:?}", e))
+            })?;
+        Ok(())
+    }
+
+    /// Store a new schedule
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::DuplicateName` if the name is taken, or
+    /// `SchedulerError::Database` if the schedule cannot be stored
+    pub async fn add(&self, schedule: &WorkflowSchedule) -> Result<(), SchedulerError> {
+        if self
+            .list()
+            .await?
+            .iter()
+            .any(|existing| existing.name == schedule.name)
+        {
+            return Err(SchedulerError::DuplicateName(schedule.name.clone()));
+        }
+
+        self.db
+            .query("CREATE workflow_schedule CONTENT $schedule")
+            .bind(("schedule", schedule.clone()))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to add schedule: {:?}", e)))?;
+        Ok(())
+    }
+
+    /// Every schedule, by name
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the query fails
+    pub async fn list(&self) -> Result<Vec<WorkflowSchedule>, SchedulerError> {
+        let mut response = self
+            .db
+            .query("SELECT * OMIT id FROM workflow_schedule ORDER BY name")
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to list schedules: {:?}", e)))?;
+
+        response
+            .take(0)
+            .map_err(|e| SchedulerError::Database(format!("Failed to parse schedules: {:?}", e)))
+    }
+
+    /// Find a schedule by name, id or unique id prefix
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::ScheduleNotFound` if nothing matches,
+    /// `SchedulerError::AmbiguousSchedule` if a prefix matches several
+    /// schedules, or `SchedulerError::Database` if the query fails
+    pub async fn resolve(&self, name_or_id: &str) -> Result<WorkflowSchedule, SchedulerError> {
+        let schedules = self.list().await?;
+        if let Some(exact) = schedules
+            .iter()
+            .find(|s| s.name == name_or_id || s.schedule_id == name_or_id)
+        {
+            return Ok(exact.clone());
+        }
+
+        let mut matches: Vec<_> = schedules
+            .into_iter()
+            .filter(|s| s.schedule_id.starts_with(name_or_id))
+            .collect();
+        match matches.len() {
+            0 => Err(SchedulerError::ScheduleNotFound(name_or_id.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => Err(SchedulerError::AmbiguousSchedule(name_or_id.to_string())),
+        }
+    }
+
+    /// Delete a schedule and its run history
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the rows cannot be deleted
+    pub async fn remove(&self, schedule_id: &str) -> Result<(), SchedulerError> {
+        self.db
+            .query(
+                "DELETE workflow_schedule WHERE schedule_id = $schedule_id;
+                 DELETE workflow_run WHERE schedule_id = $schedule_id;",
+            )
+            .bind(("schedule_id", schedule_id.to_string()))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to remove schedule: {:?}", e)))?;
+        Ok(())
+    }
+
+    /// Enable or disable a schedule
+    ///
+    /// Enabling reschedules from now, so a long-paused schedule does not run
+    /// immediately.
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the schedule cannot be updated
+    pub async fn set_enabled(
+        &self,
+        schedule: &WorkflowSchedule,
+        enabled: bool,
+    ) -> Result<(), SchedulerError> {
+        let next_run_ms = if enabled {
+            schedule
+                .spec
+                .next_after(chrono::Utc::now().timestamp_millis())
+        } else {
+            schedule.next_run_ms
+        };
+
+        self.db
+            .query(
+                "UPDATE workflow_schedule SET enabled = $enabled, next_run_ms = $next_run_ms
+                 WHERE schedule_id = $schedule_id",
+            )
+            .bind(("schedule_id", schedule.schedule_id.clone()))
+            .bind(("enabled", enabled))
+            .bind(("next_run_ms", next_run_ms))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to update schedule: {:?}", e)))?;
+        Ok(())
+    }
+
+    /// Record that a schedule ran at `ran_at_ms` and is next due at `next_run_ms`
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the schedule cannot be updated
+    pub async fn mark_run(
+        &self,
+        schedule_id: &str,
+        ran_at_ms: i64,
+        next_run_ms: Option<i64>,
+    ) -> Result<(), SchedulerError> {
+        self.db
+            .query(
+                "UPDATE workflow_schedule SET last_run_ms = $ran_at_ms, next_run_ms = $next_run_ms
+                 WHERE schedule_id = $schedule_id",
+            )
+            .bind(("schedule_id", schedule_id.to_string()))
+            .bind(("ran_at_ms", ran_at_ms))
+            .bind(("next_run_ms", next_run_ms))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to reschedule: {:?}", e)))?;
+        Ok(())
+    }
+
+    /// Append a run to the history
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the run cannot be stored
+    pub async fn record_run(&self, run: &RunRecord) -> Result<(), SchedulerError> {
+        self.db
+            .query("CREATE workflow_run CONTENT $run")
+            .bind(("run", run.clone()))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to record run: {:?}", e)))?;
+        Ok(())
+    }
+
+    /// Most recent runs first, of one schedule or of all
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the query fails
+    pub async fn runs(
+        &self,
+        schedule_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<RunRecord>, SchedulerError> {
+        let query = match schedule_id {
+            Some(_) => {
+                "SELECT * OMIT id FROM workflow_run WHERE schedule_id = $schedule_id
+                 ORDER BY started_at_ms DESC LIMIT $limit"
+            }
+            None => "SELECT * OMIT id FROM workflow_run ORDER BY started_at_ms DESC LIMIT $limit",
+        };
+        let mut response = self
+            .db
+            .query(query)
+            .bind(("schedule_id", schedule_id.map(str::to_string)))
+            .bind(("limit", limit as i64))
+            .await
+            .map_err(|e| SchedulerError::Database(format!("Failed to list runs: {:?}", e)))?;
+
+        response
+            .take(0)
+            .map_err(|e| SchedulerError::Database(format!("Failed to parse runs: {:?}", e)))
+    }
+}
+
+/// Runs due schedules headlessly and records their history
+pub struct Scheduler {
+    store: ScheduleStore,
+    agent_for: AgentFactory,
+    poll_interval: Duration,
+    run_timeout: Duration,
+    artifacts_dir: Option<PathBuf>,
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("poll_interval", &self.poll_interval)
+            .field("run_timeout", &self.run_timeout)
+            .field("artifacts_dir", &self.artifacts_dir)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Scheduler {
+    /// Scheduler over `store` that builds each run's agent with `agent_for`
+    ///
+    /// An error from `agent_for` is recorded as a failed run.
+    pub fn new<F>(store: ScheduleStore, agent_for: F) -> Self
+    where
+        F: Fn(&WorkflowSchedule) -> Result<Arc<dyn WorkflowAgent>, SchedulerError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            store,
+            agent_for: Arc::new(agent_for),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            run_timeout: DEFAULT_RUN_TIMEOUT,
+            artifacts_dir: None,
+        }
+    }
+
+    /// Look for due schedules every `poll_interval`
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Fail runs that take longer than `run_timeout`
+    pub fn with_run_timeout(mut self, run_timeout: Duration) -> Self {
+        self.run_timeout = run_timeout;
+        self
+    }
+
+    /// Write each run's output to `<dir>/<schedule>/<run id>.md`
+    pub fn with_artifacts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.artifacts_dir = Some(dir.into());
+        self
+    }
+
+    /// Store the scheduler reads schedules from
+    pub fn store(&self) -> &ScheduleStore {
+        &self.store
+    }
+
+    /// Run every due schedule once, earliest due first
+    ///
+    /// Each schedule is rescheduled before it runs, so a run that crashes the
+    /// process is not repeated on every restart.
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if schedules cannot be read or updated
+    pub async fn run_due(&self) -> Result<Vec<RunRecord>, SchedulerError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut due: Vec<_> = self
+            .store
+            .list()
+            .await?
+            .into_iter()
+            .filter(|schedule| schedule.is_due(now))
+            .collect();
+        due.sort_by_key(|schedule| schedule.next_run_ms);
+
+        let mut runs = Vec::with_capacity(due.len());
+        for schedule in due {
+            self.store
+                .mark_run(&schedule.schedule_id, now, schedule.spec.next_after(now))
+                .await?;
+            runs.push(self.run(&schedule).await?);
+        }
+        Ok(runs)
+    }
+
+    /// Run `schedule` now, whether or not it is due, and record the run
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the run cannot be recorded
+    pub async fn run(&self, schedule: &WorkflowSchedule) -> Result<RunRecord, SchedulerError> {
+        let mut run = match (self.agent_for)(schedule) {
+            Ok(agent) => run_agent(agent.as_ref(), schedule, self.run_timeout).await,
+            Err(e) => {
+                let now = chrono::Utc::now().timestamp_millis();
+                RunRecord {
+                    run_id: uuid::Uuid::new_v4().simple().to_string(),
+                    schedule_id: schedule.schedule_id.clone(),
+                    schedule_name: schedule.name.clone(),
+                    status: RunStatus::Failure,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                    tool_calls: Vec::new(),
+                    artifacts: Vec::new(),
+                    started_at_ms: now,
+                    finished_at_ms: now,
+                }
+            }
+        };
+
+        if let Some(dir) = &self.artifacts_dir
+            && !run.output.is_empty()
+        {
+            match write_output(dir, &run).await {
+                Ok(path) => run.artifacts.push(path.display().to_string()),
+                Err(e) => log::warn!(
+                    "Failed to write output of scheduled run {}: {}",
+                    run.run_id,
+                    e
+                ),
+            }
+        }
+
+        self.store.record_run(&run).await?;
+        Ok(run)
+    }
+
+    /// Run due schedules every poll interval until `shutdown` completes
+    ///
+    /// `on_run` sees each finished run. Store errors are logged and retried
+    /// on the next poll.
+    pub async fn run_until<S, F>(&self, shutdown: S, mut on_run: F)
+    where
+        S: Future,
+        F: FnMut(&RunRecord),
+    {
+        tokio::pin!(shutdown);
+        loop {
+            match self.run_due().await {
+                Ok(runs) => runs.iter().for_each(&mut on_run),
+                Err(e) => log::warn!("Scheduler poll failed: {}", e),
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => return,
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+}
+
+/// Write a run's output under `dir`, in a directory named after its schedule
+async fn write_output(dir: &Path, run: &RunRecord) -> std::io::Result<PathBuf> {
+    let slug: String = run
+        .schedule_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let dir = dir.join(slug);
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let path = dir.join(format!("{}.md", run.run_id));
+    tokio::fs::write(&path, &run.output).await?;
+    Ok(path)
+}
@@ -0,0 +1,393 @@
+//! Event-driven workflow triggers
+//!
+//! Schedules with a webhook or notification [`ScheduleSpec`] run when a
+//! matching [`TriggerEvent`] arrives instead of on the clock:
+//! - webhooks posted to the local listener started by [`serve_webhooks`]
+//!   (`api` feature)
+//! - notifications from stdio `MCP` servers, followed by
+//!   [`TriggerDispatcher::subscribe`]
+//!
+//! Before a triggered run the schedule's prompt is rendered as a template
+//! over the event: `{{payload}}` is the webhook body or notification params,
+//! `{{payload.pull_request.title}}` a field within it (array elements by
+//! index), and `{{path}}` or `{{method}}` what fired the trigger. Strings are
+//! inserted as-is, other values as JSON, and missing fields as nothing.
+//!
+//! Triggered runs go through the [`Scheduler`], so they share its agents,
+//! artifacts and run history.
+
+use std::sync::Arc;
+
+use serde_json::{Value, json};
+use sweet_mcp_type::Implementation;
+use tokio::task::JoinHandle;
+
+use crate::workflow::scheduler::{
+    RunRecord, ScheduleSpec, Scheduler, SchedulerError, WorkflowSchedule, webhook_path,
+};
+
+/// Header carrying a webhook trigger's secret
+pub const WEBHOOK_SECRET_HEADER: &str = "x-webhook-secret";
+
+type RunCallback = Arc<dyn Fn(&RunRecord) + Send + Sync>;
+
+/// Something that happened which may fire triggers
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerEvent {
+    /// A request posted to the webhook listener
+    Webhook {
+        path: String,
+        /// JSON body, or the body as a string when it is not JSON
+        payload: Value,
+        /// Secret presented in [`WEBHOOK_SECRET_HEADER`]
+        secret: Option<String>,
+    },
+    /// A notification sent by a stdio `MCP` server
+    Notification {
+        /// Command and arguments the server was started with
+        server: Vec<String>,
+        method: String,
+        params: Value,
+    },
+}
+
+impl TriggerEvent {
+    /// Whether this event fires schedules with `spec`
+    pub fn matches(&self, spec: &ScheduleSpec) -> bool {
+        match (self, spec) {
+            (
+                Self::Webhook { path, secret, .. },
+                ScheduleSpec::Webhook {
+                    path: wanted,
+                    secret: required,
+                },
+            ) => {
+                webhook_path(path) == *wanted
+                    && required.as_deref().is_none_or(|required| {
+                        secret
+                            .as_deref()
+                            .is_some_and(|secret| secrets_match(secret, required))
+                    })
+            }
+            (
+                Self::Notification { server, method, .. },
+                ScheduleSpec::Notification {
+                    server: wanted_server,
+                    method: wanted_method,
+                },
+            ) => server == wanted_server && method == wanted_method,
+            _ => false,
+        }
+    }
+
+    /// Values templates can refer to
+    fn context(&self) -> Value {
+        match self {
+            Self::Webhook { path, payload, .. } => json!({ "path": path, "payload": payload }),
+            Self::Notification { method, params, .. } => {
+                json!({ "method": method, "payload": params })
+            }
+        }
+    }
+}
+
+/// Compare secrets without returning early on the first differing byte
+fn secrets_match(presented: &str, required: &str) -> bool {
+    presented.len() == required.len()
+        && presented
+            .bytes()
+            .zip(required.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Render `template` with `{{...}}` placeholders filled from `event`
+///
+/// An unterminated `{{` is left as written.
+pub fn render_prompt(template: &str, event: &TriggerEvent) -> String {
+    let context = event.context();
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&lookup(&context, rest[start + 2..start + 2 + len].trim()));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Text of the value at dotted `path` in `context`
+fn lookup(context: &Value, path: &str) -> String {
+    let value = path.split('.').try_fold(context, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => value.get(key),
+    });
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Runs the schedules an event fires
+#[derive(Clone)]
+pub struct TriggerDispatcher {
+    scheduler: Arc<Scheduler>,
+    on_run: Option<RunCallback>,
+}
+
+impl std::fmt::Debug for TriggerDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TriggerDispatcher")
+            .field("scheduler", &self.scheduler)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TriggerDispatcher {
+    /// Dispatcher running triggered schedules through `scheduler`
+    pub fn new(scheduler: Arc<Scheduler>) -> Self {
+        Self {
+            scheduler,
+            on_run: None,
+        }
+    }
+
+    /// Call `on_run` with each finished triggered run
+    pub fn on_run<F>(mut self, on_run: F) -> Self
+    where
+        F: Fn(&RunRecord) + Send + Sync + 'static,
+    {
+        self.on_run = Some(Arc::new(on_run));
+        self
+    }
+
+    /// Enabled schedules `event` fires
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if schedules cannot be read
+    pub async fn matching(
+        &self,
+        event: &TriggerEvent,
+    ) -> Result<Vec<WorkflowSchedule>, SchedulerError> {
+        Ok(self
+            .scheduler
+            .store()
+            .list()
+            .await?
+            .into_iter()
+            .filter(|schedule| schedule.enabled && event.matches(&schedule.spec))
+            .collect())
+    }
+
+    /// Start a run of every schedule `event` fires, in the background
+    ///
+    /// # Returns
+    /// The schedules started; empty when nothing matched
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if schedules cannot be read
+    pub async fn fire(&self, event: TriggerEvent) -> Result<Vec<WorkflowSchedule>, SchedulerError> {
+        let schedules = self.matching(&event).await?;
+        for schedule in &schedules {
+            let dispatcher = self.clone();
+            let schedule = schedule.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                if let Err(e) = dispatcher.run(&schedule, &event).await {
+                    log::warn!("Triggered run of {} failed: {}", schedule.name, e);
+                }
+            });
+        }
+        Ok(schedules)
+    }
+
+    /// Run `schedule` for `event` and wait for the run to finish
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if the run cannot be recorded
+    pub async fn run(
+        &self,
+        schedule: &WorkflowSchedule,
+        event: &TriggerEvent,
+    ) -> Result<RunRecord, SchedulerError> {
+        let invocation = WorkflowSchedule {
+            prompt: render_prompt(&schedule.prompt, event),
+            ..schedule.clone()
+        };
+        self.scheduler
+            .store()
+            .mark_run(
+                &schedule.schedule_id,
+                chrono::Utc::now().timestamp_millis(),
+                None,
+            )
+            .await?;
+
+        let run = self.scheduler.run(&invocation).await?;
+        if let Some(on_run) = &self.on_run {
+            on_run(&run);
+        }
+        Ok(run)
+    }
+
+    /// Subscribe to every server named by an enabled notification trigger
+    ///
+    /// Servers are collected when this is called; triggers for other servers
+    /// added later need a new subscription.
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Database` if schedules cannot be read
+    pub async fn subscribe_all(&self) -> Result<Vec<JoinHandle<()>>, SchedulerError> {
+        let mut servers: Vec<Vec<String>> = Vec::new();
+        for schedule in self.scheduler.store().list().await? {
+            if let ScheduleSpec::Notification { server, .. } = schedule.spec
+                && schedule.enabled
+                && !servers.contains(&server)
+            {
+                servers.push(server);
+            }
+        }
+
+        Ok(servers
+            .into_iter()
+            .map(|server| {
+                let dispatcher = self.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = dispatcher.subscribe(server.clone()).await {
+                        log::warn!("Stopped following {}: {}", server.join(" "), e);
+                    }
+                })
+            })
+            .collect())
+    }
+
+    /// Start the stdio `MCP` server `server` and fire triggers for its
+    /// notifications until it exits
+    ///
+    /// # Errors
+    /// Returns `SchedulerError::Subscription` if the server cannot be started,
+    /// rejects the handshake or its output cannot be read
+    pub async fn subscribe(&self, server: Vec<String>) -> Result<(), SchedulerError> {
+        let (command, args) = server
+            .split_first()
+            .ok_or_else(|| SchedulerError::Subscription("no server command".to_string()))?;
+        let client_info = Implementation {
+            name: "paraphym-candle".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        let session = sweetmcp_stdio_client::StdioClient::connect_and_initialize(
+            command,
+            args,
+            &[],
+            client_info,
+            json!({}),
+        )
+        .await
+        .map_err(|e| SchedulerError::Subscription(format!("{}: {}", command, e)))?;
+
+        while let Some((method, params)) = session
+            .next_notification()
+            .await
+            .map_err(|e| SchedulerError::Subscription(format!("{}: {}", command, e)))?
+        {
+            let event = TriggerEvent::Notification {
+                server: server.clone(),
+                method,
+                params,
+            };
+            if let Err(e) = self.fire(event).await {
+                log::warn!("Failed to dispatch notification from {}: {}", command, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Router posting every request path to the dispatcher as a webhook
+///
+/// Answers `202` with the triggered schedule names, or `404` when no enabled
+/// trigger matches the path and secret.
+#[cfg(feature = "api")]
+pub fn webhook_router(dispatcher: TriggerDispatcher) -> axum::Router {
+    use axum::routing::post;
+
+    axum::Router::new()
+        .route("/", post(handle_webhook))
+        .route("/{*path}", post(handle_webhook))
+        .with_state(dispatcher)
+}
+
+/// Listen for webhooks on `addr` until `shutdown` completes
+///
+/// # Errors
+/// Returns `SchedulerError::Listener` if the address cannot be bound or the
+/// server fails
+#[cfg(feature = "api")]
+pub async fn serve_webhooks<S>(
+    dispatcher: TriggerDispatcher,
+    addr: std::net::SocketAddr,
+    shutdown: S,
+) -> Result<(), SchedulerError>
+where
+    S: std::future::Future<Output = ()> + Send + 'static,
+{
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| SchedulerError::Listener(format!("Failed to bind {}: {}", addr, e)))?;
+    log::info!("Webhook triggers listening on {}", addr);
+
+    axum::serve(listener, webhook_router(dispatcher))
+        .with_graceful_shutdown(shutdown)
+        .await
+        .map_err(|e| SchedulerError::Listener(e.to_string()))
+}
+
+#[cfg(feature = "api")]
+async fn handle_webhook(
+    axum::extract::State(dispatcher): axum::extract::State<TriggerDispatcher>,
+    uri: axum::http::Uri,
+    headers: axum::http::HeaderMap,
+    body: axum::body::Bytes,
+) -> (axum::http::StatusCode, axum::Json<Value>) {
+    use axum::http::StatusCode;
+
+    let payload = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()))
+    };
+    let secret = headers
+        .get(WEBHOOK_SECRET_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let event = TriggerEvent::Webhook {
+        path: uri.path().to_string(),
+        payload,
+        secret,
+    };
+
+    match dispatcher.fire(event).await {
+        Ok(started) if started.is_empty() => (
+            StatusCode::NOT_FOUND,
+            axum::Json(json!({ "error": "no trigger matches this webhook" })),
+        ),
+        Ok(started) => {
+            let names: Vec<&str> = started.iter().map(|s| s.name.as_str()).collect();
+            (
+                StatusCode::ACCEPTED,
+                axum::Json(json!({ "triggered": names })),
+            )
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            axum::Json(json!({ "error": e.to_string() })),
+        ),
+    }
+}
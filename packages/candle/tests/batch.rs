@@ -0,0 +1,233 @@
+//! Tests for offline batch inference
+
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use cyrup_candle::batch::*;
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider, CandleUsage};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use tokio_stream::Stream;
+
+static SCRIPTED_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "scripted",
+    registry_key: "test/scripted",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "scripted",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Echoes each prompt upper-cased, failing prompts that contain "fail"
+#[derive(Debug, Clone, Default)]
+struct EchoModel {
+    seen: Arc<Mutex<Vec<(String, f64)>>>,
+}
+
+impl EchoModel {
+    fn prompts(&self) -> Vec<String> {
+        let mut prompts: Vec<String> = self
+            .seen
+            .lock()
+            .expect("lock")
+            .iter()
+            .map(|(prompt, _)| prompt.clone())
+            .collect();
+        prompts.sort();
+        prompts
+    }
+}
+
+impl CandleModel for EchoModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &SCRIPTED_INFO
+    }
+}
+
+impl TextToTextCapable for EchoModel {
+    fn prompt(
+        &self,
+        prompt: CandlePrompt,
+        params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        self.seen
+            .lock()
+            .expect("lock")
+            .push((prompt.content.clone(), params.temperature));
+        let chunks = if prompt.content.contains("fail") {
+            vec![CandleCompletionChunk::Error("model crashed".to_string())]
+        } else {
+            vec![
+                CandleCompletionChunk::Text(prompt.content.to_uppercase()),
+                CandleCompletionChunk::Complete {
+                    text: String::new(),
+                    finish_reason: None,
+                    usage: Some(CandleUsage::new(3, 2)),
+                    token_count: None,
+                    elapsed_secs: None,
+                    tokens_per_sec: None,
+                    provider: None,
+                },
+            ]
+        };
+        Box::pin(tokio_stream::iter(chunks))
+    }
+}
+
+async fn read_records(path: &std::path::Path) -> Vec<BatchRecord> {
+    tokio::fs::read_to_string(path)
+        .await
+        .expect("output file")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("record"))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_requests_are_parsed_with_default_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("prompts.jsonl");
+    tokio::fs::write(
+        &input,
+        "{\"id\": \"a\", \"prompt\": \"one\", \"temperature\": 0.5}\n\n{\"prompt\": \"two\"}\n",
+    )
+    .await?;
+
+    let requests = read_requests(&input).await?;
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].id, "a");
+    assert_eq!(requests[0].temperature, Some(0.5));
+    assert_eq!(requests[1].id, "line-3");
+
+    tokio::fs::write(
+        &input,
+        "{\"id\": \"a\", \"prompt\": \"x\"}\n{\"id\": \"a\", \"prompt\": \"y\"}\n",
+    )
+    .await?;
+    assert!(matches!(
+        read_requests(&input).await,
+        Err(BatchError::DuplicateId(id)) if id == "a"
+    ));
+
+    tokio::fs::write(&input, "{\"id\": \"a\"}\n").await?;
+    assert!(matches!(
+        read_requests(&input).await,
+        Err(BatchError::InvalidRequest { line: 1, .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_writes_completions_and_usage() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("prompts.jsonl");
+    let output = dir.path().join("results.jsonl");
+    let lines: Vec<String> = (0..6)
+        .map(|n| format!("{{\"id\": \"q{n}\", \"prompt\": \"prompt {n}\"}}"))
+        .collect();
+    tokio::fs::write(&input, lines.join("\n")).await?;
+
+    let model = EchoModel::default();
+    let summary = BatchJob::new(&input, &output)
+        .with_concurrency(3)
+        .with_temperature(0.3)
+        .run(model.clone())
+        .await?;
+
+    assert_eq!(summary.total, 6);
+    assert_eq!(summary.completed, 6);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.prompt_tokens, 18);
+    assert_eq!(summary.completion_tokens, 12);
+    assert!(
+        model
+            .seen
+            .lock()
+            .expect("lock")
+            .iter()
+            .all(|(_, temperature)| (*temperature - 0.3).abs() < f64::EPSILON)
+    );
+
+    let records = read_records(&output).await;
+    assert_eq!(records.len(), 6);
+    let first = records.iter().find(|r| r.id == "q0").expect("q0 recorded");
+    assert_eq!(first.completion, "PROMPT 0");
+    assert_eq!(first.finish_reason.as_deref(), Some("stop"));
+    assert_eq!(first.usage.total_tokens, 5);
+    assert!(first.error.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rerun_resumes_and_retries_failures() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let input = dir.path().join("prompts.jsonl");
+    let output = dir.path().join("results.jsonl");
+    tokio::fs::write(
+        &input,
+        "{\"id\": \"ok\", \"prompt\": \"fine\"}\n{\"id\": \"bad\", \"prompt\": \"fail\"}\n{\"id\": \"cut\", \"prompt\": \"cut short\"}\n",
+    )
+    .await?;
+    // An earlier run finished "ok" and was interrupted while writing "cut"
+    tokio::fs::write(
+        &output,
+        "{\"id\":\"ok\",\"completion\":\"FINE\",\"elapsed_ms\":1}\n{\"id\":\"cut\",\"compl",
+    )
+    .await?;
+
+    let checkpoint = read_checkpoint(&output).await?;
+    assert!(checkpoint.ids.contains("ok"));
+    assert!(!checkpoint.ids.contains("cut"));
+    assert!(checkpoint.truncated_tail);
+
+    let model = EchoModel::default();
+    let summary = BatchJob::new(&input, &output).run(model.clone()).await?;
+    assert_eq!(summary.skipped, 1);
+    assert_eq!(summary.completed, 1);
+    assert_eq!(summary.failed, 1);
+    assert_eq!(model.prompts(), vec!["cut short", "fail"]);
+
+    // Only the failed request runs again
+    let model = EchoModel::default();
+    let summary = BatchJob::new(&input, &output).run(model.clone()).await?;
+    assert_eq!(summary.skipped, 2);
+    assert_eq!(model.prompts(), vec!["fail"]);
+
+    let checkpoint = read_checkpoint(&output).await?;
+    assert!(!checkpoint.truncated_tail);
+    assert!(!checkpoint.ids.contains("bad"));
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use cyrup_candle::capability::speech_to_text::mel_filters;
+use cyrup_candle::capability::text_to_speech::SynthesizedSpeech;
+
+/// Frequency bins of Whisper's 400-point FFT
+const FREQ_BINS: usize = 201;
+
+#[test]
+fn test_mel_filters_match_whisper_preprocessing() {
+    let filters = mel_filters(80);
+    assert_eq!(filters.len(), 80 * FREQ_BINS);
+    assert!(filters.iter().all(|w| *w >= 0.0));
+
+    // First filter from librosa.filters.mel(sr=16000, n_fft=400, n_mels=80)
+    assert_eq!(filters[0], 0.0);
+    assert!((filters[1] - 0.024_862_594).abs() < 1e-6);
+    assert_eq!(filters[2], 0.0);
+
+    // Every filter covers some frequencies; high ones are wider and lower
+    for row in filters.chunks(FREQ_BINS) {
+        assert!(row.iter().any(|w| *w > 0.0));
+    }
+    let last = &filters[79 * FREQ_BINS..];
+    assert_eq!(last.iter().filter(|w| **w > 0.0).count(), 14);
+
+    assert_eq!(mel_filters(128).len(), 128 * FREQ_BINS);
+}
+
+#[test]
+fn test_synthesized_speech_duration() {
+    let speech = SynthesizedSpeech {
+        pcm: vec![0.0; 22_050],
+        sample_rate: 44_100,
+    };
+    assert_eq!(speech.duration(), std::time::Duration::from_millis(500));
+}
@@ -0,0 +1,245 @@
+//! Tests for the generation cache
+
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use cyrup_candle::StreamExt;
+use cyrup_candle::capability::text_to_text::cache::*;
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use surrealdb::engine::any::connect;
+use tokio_stream::Stream;
+
+static SCRIPTED_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "scripted",
+    registry_key: "test/scripted",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "scripted",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Streams a numbered answer per call, or an error
+#[derive(Debug, Clone)]
+struct CountingModel {
+    calls: Arc<AtomicUsize>,
+    fail: bool,
+}
+
+impl CountingModel {
+    fn new() -> Self {
+        Self {
+            calls: Arc::new(AtomicUsize::new(0)),
+            fail: false,
+        }
+    }
+
+    fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::new()
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+impl CandleModel for CountingModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &SCRIPTED_INFO
+    }
+}
+
+impl TextToTextCapable for CountingModel {
+    fn prompt(
+        &self,
+        _prompt: CandlePrompt,
+        _params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        let chunks = if self.fail {
+            vec![CandleCompletionChunk::Error(
+                "model unavailable".to_string(),
+            )]
+        } else {
+            vec![
+                CandleCompletionChunk::Text(format!("answer {call}")),
+                CandleCompletionChunk::Text(" done".to_string()),
+                CandleCompletionChunk::Complete {
+                    text: String::new(),
+                    finish_reason: None,
+                    usage: None,
+                    token_count: Some(3),
+                    elapsed_secs: Some(0.5),
+                    tokens_per_sec: Some(6.0),
+                    provider: None,
+                },
+            ]
+        };
+        Box::pin(tokio_stream::iter(chunks))
+    }
+}
+
+async fn temp_cache(
+    dir: &tempfile::TempDir,
+) -> Result<GenerationCache, Box<dyn std::error::Error>> {
+    let db = connect(format!(
+        "surrealkv://{}",
+        dir.path().join("agent.db").display()
+    ))
+    .await?;
+    db.use_ns("candle").use_db("agent").await?;
+    let cache = GenerationCache::new(db);
+    cache.initialize().await?;
+    Ok(cache)
+}
+
+async fn ask(
+    model: &impl TextToTextCapable,
+    prompt: &str,
+    params: &CandleCompletionParams,
+) -> String {
+    model
+        .prompt(CandlePrompt::new(prompt), params)
+        .collect::<Vec<_>>()
+        .await
+        .iter()
+        .filter_map(|chunk| match chunk {
+            CandleCompletionChunk::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn sampled(temperature: f64) -> CandleCompletionParams {
+    let mut params = CandleCompletionParams::default();
+    params.temperature = temperature;
+    params
+}
+
+#[test]
+fn test_cache_key_covers_model_prompt_and_params() {
+    let prompt = CandlePrompt::new("hello");
+    let params = CandleCompletionParams::default();
+    let key = cache_key("test/scripted", &prompt, &params);
+
+    assert_eq!(key.len(), 64);
+    assert_eq!(key, cache_key("test/scripted", &prompt, &params));
+    assert_ne!(key, cache_key("test/other", &prompt, &params));
+    assert_ne!(
+        key,
+        cache_key("test/scripted", &CandlePrompt::new("hello!"), &params)
+    );
+
+    let mut limited = params.clone();
+    limited.max_tokens = std::num::NonZeroU64::new(16);
+    assert_ne!(key, cache_key("test/scripted", &prompt, &limited));
+
+    assert!(is_deterministic(&params));
+    assert!(!is_deterministic(&sampled(0.7)));
+}
+
+#[tokio::test]
+async fn test_deterministic_prompts_are_replayed() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let inner = CountingModel::new();
+    let model = CachedModel::new(inner.clone(), temp_cache(&dir).await?);
+    let greedy = CandleCompletionParams::default();
+
+    assert_eq!(ask(&model, "hello", &greedy).await, "answer 1 done");
+    assert_eq!(ask(&model, "hello", &greedy).await, "answer 1 done");
+    assert_eq!(inner.calls(), 1);
+
+    // A different prompt is generated
+    assert_eq!(ask(&model, "goodbye", &greedy).await, "answer 2 done");
+    assert_eq!(inner.calls(), 2);
+
+    // Sampled prompts always reach the model
+    assert_eq!(ask(&model, "hello", &sampled(0.7)).await, "answer 3 done");
+    assert_eq!(ask(&model, "hello", &sampled(0.7)).await, "answer 4 done");
+    assert_eq!(inner.calls(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_generations_are_not_cached() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let inner = CountingModel::failing();
+    let model = CachedModel::new(inner.clone(), temp_cache(&dir).await?);
+    let greedy = CandleCompletionParams::default();
+
+    ask(&model, "hello", &greedy).await;
+    ask(&model, "hello", &greedy).await;
+    assert_eq!(inner.calls(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_entries_expire_and_are_bounded() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let cache = temp_cache(&dir).await?.with_config(GenerationCacheConfig {
+        ttl: Duration::from_millis(50),
+        max_entries: 2,
+        ..GenerationCacheConfig::default()
+    });
+    let chunks = vec![CandleCompletionChunk::Text("cached".to_string())];
+
+    cache.put("a", &chunks).await?;
+    assert!(cache.get("a").await?.is_some());
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(cache.get("a").await?.is_none());
+
+    let cache = cache.with_config(GenerationCacheConfig {
+        max_entries: 2,
+        ..GenerationCacheConfig::default()
+    });
+    for key in ["b", "c", "d"] {
+        cache.put(key, &chunks).await?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert!(cache.get("b").await?.is_none());
+    assert!(cache.get("c").await?.is_some());
+    assert!(cache.get("d").await?.is_some());
+
+    cache.clear().await?;
+    assert!(cache.get("d").await?.is_none());
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Tests for provider fallback chains
+
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use cyrup_candle::StreamExt;
+use cyrup_candle::capability::registry::TextToTextModel;
+use cyrup_candle::capability::text_to_text::{ProviderChain, ProviderStatus};
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use tokio_stream::Stream;
+
+static SCRIPTED_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "scripted",
+    registry_key: "test/scripted",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "scripted",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Replays the same chunks for every prompt and counts calls
+#[derive(Debug, Clone)]
+struct ScriptedModel {
+    chunks: Vec<CandleCompletionChunk>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ScriptedModel {
+    fn new(chunks: Vec<CandleCompletionChunk>) -> Self {
+        Self {
+            chunks,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn answering(text: &str) -> Self {
+        Self::new(vec![
+            CandleCompletionChunk::Text(text.to_string()),
+            complete(),
+        ])
+    }
+
+    fn failing(error: &str) -> Self {
+        Self::new(vec![CandleCompletionChunk::Error(error.to_string())])
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+impl CandleModel for ScriptedModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &SCRIPTED_INFO
+    }
+}
+
+impl TextToTextCapable for ScriptedModel {
+    fn prompt(
+        &self,
+        _prompt: CandlePrompt,
+        _params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Box::pin(tokio_stream::iter(self.chunks.clone()))
+    }
+}
+
+fn complete() -> CandleCompletionChunk {
+    CandleCompletionChunk::Complete {
+        text: String::new(),
+        finish_reason: None,
+        usage: None,
+        token_count: None,
+        elapsed_secs: None,
+        tokens_per_sec: None,
+        provider: None,
+    }
+}
+
+async fn ask(model: &impl TextToTextCapable) -> Vec<CandleCompletionChunk> {
+    model
+        .prompt(
+            CandlePrompt::new("hello"),
+            &CandleCompletionParams::default(),
+        )
+        .collect()
+        .await
+}
+
+fn text(chunks: &[CandleCompletionChunk]) -> String {
+    chunks
+        .iter()
+        .filter_map(|chunk| match chunk {
+            CandleCompletionChunk::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn answered_by(chunks: &[CandleCompletionChunk]) -> Option<String> {
+    chunks.iter().find_map(|chunk| match chunk {
+        CandleCompletionChunk::Complete { provider, .. } => provider.clone(),
+        _ => None,
+    })
+}
+
+#[tokio::test]
+async fn test_load_failure_falls_back_transparently() {
+    let primary = ScriptedModel::failing("out of memory");
+    let chain = ProviderChain::named("primary", primary.clone())
+        .named_fallback("backup", ScriptedModel::answering("hi"));
+
+    let chunks = ask(&chain).await;
+
+    assert_eq!(text(&chunks), "hi");
+    assert!(
+        chunks
+            .iter()
+            .all(|c| !matches!(c, CandleCompletionChunk::Error(_)))
+    );
+    assert_eq!(answered_by(&chunks).as_deref(), Some("backup"));
+
+    let health = chain.health();
+    assert_eq!(health[0].1.status, ProviderStatus::Unhealthy);
+    assert_eq!(health[0].1.last_error.as_deref(), Some("out of memory"));
+    assert_eq!(health[1].1.status, ProviderStatus::Healthy);
+    assert_eq!(chain.active(), "backup");
+    assert_eq!(primary.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_unhealthy_provider_is_skipped_until_cooldown_expires() {
+    let primary = ScriptedModel::failing("missing weights");
+    let chain = ProviderChain::named("primary", primary.clone())
+        .named_fallback("backup", ScriptedModel::answering("hi"));
+
+    ask(&chain).await;
+    ask(&chain).await;
+    assert_eq!(primary.calls(), 1);
+
+    let retrying = chain.clone().with_cooldown(Duration::ZERO);
+    ask(&retrying).await;
+    assert_eq!(primary.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_mid_response_failure_is_forwarded_and_later_turns_fail_over() {
+    let primary = ScriptedModel::new(vec![
+        CandleCompletionChunk::Text("partial".to_string()),
+        CandleCompletionChunk::Error("device lost".to_string()),
+    ]);
+    let backup = ScriptedModel::answering("recovered");
+    let chain =
+        ProviderChain::named("primary", primary.clone()).named_fallback("backup", backup.clone());
+
+    let first = ask(&chain).await;
+    assert_eq!(text(&first), "partial");
+    assert!(matches!(
+        first.last(),
+        Some(CandleCompletionChunk::Error(e)) if e == "device lost"
+    ));
+    assert_eq!(backup.calls(), 0);
+
+    let second = ask(&chain).await;
+    assert_eq!(text(&second), "recovered");
+    assert_eq!(answered_by(&second).as_deref(), Some("backup"));
+    assert_eq!(primary.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_all_providers_failing_reports_every_error() {
+    let chain = ProviderChain::named("primary", ScriptedModel::failing("oom"))
+        .named_fallback("backup", ScriptedModel::failing("no weights"));
+
+    let chunks = ask(&chain).await;
+
+    match chunks.as_slice() {
+        [CandleCompletionChunk::Error(e)] => {
+            assert!(e.contains("primary: oom"), "{e}");
+            assert!(e.contains("backup: no weights"), "{e}");
+        }
+        other => panic!("expected a single error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_probe_records_provider_health() {
+    let chain = ProviderChain::named("primary", ScriptedModel::answering("pong"))
+        .named_fallback("backup", ScriptedModel::failing("no weights"));
+
+    let statuses = chain.probe().await;
+
+    assert_eq!(
+        statuses,
+        vec![
+            ("primary".to_string(), ProviderStatus::Healthy),
+            ("backup".to_string(), ProviderStatus::Unhealthy),
+        ]
+    );
+    assert_eq!(chain.active(), "primary");
+}
+
+#[tokio::test]
+async fn test_chain_is_a_text_to_text_model() {
+    let chain = ProviderChain::new(ScriptedModel::failing("oom"))
+        .named_fallback("backup", ScriptedModel::answering("hi"));
+    assert_eq!(chain.providers(), vec!["test/scripted", "backup"]);
+
+    let model = TextToTextModel::from(chain);
+    assert_eq!(model.info().registry_key, "test/scripted");
+
+    let chunks = ask(&model).await;
+    assert_eq!(answered_by(&chunks).as_deref(), Some("backup"));
+}
@@ -91,3 +91,295 @@ fn test_validate_memory_timeout() {
     args.memory_read_timeout = 5000;
     assert!(args.validate().is_ok());
 }
+
+#[test]
+fn test_parse_serve() {
+    let args: Vec<String> = ["program", "serve", "--port", "9000", "--host", "0.0.0.0"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.command, CliCommand::Serve);
+    assert_eq!(cli_args.host, "0.0.0.0");
+    assert_eq!(cli_args.port, 9000);
+    assert!(!cli_args.interactive);
+
+    // `serve` is only a command in first position
+    let args = vec![
+        "program".to_string(),
+        "--model".to_string(),
+        "serve".to_string(),
+    ];
+    assert_eq!(CliArgs::from_args(&args).command, CliCommand::Chat);
+}
+
+#[test]
+fn test_parse_resume() {
+    let args = vec![
+        "program".to_string(),
+        "--resume".to_string(),
+        "3fa2".to_string(),
+    ];
+    assert_eq!(CliArgs::from_args(&args).resume, Some("3fa2".to_string()));
+    assert_eq!(CliArgs::default().resume, None);
+}
+
+#[test]
+fn test_parse_context_overflow() {
+    use cyrup_candle::core::context_window::ContextOverflowPolicy;
+
+    let args = vec![
+        "program".to_string(),
+        "--context-overflow".to_string(),
+        "summarize".to_string(),
+    ];
+    assert_eq!(
+        CliArgs::from_args(&args).context_overflow,
+        ContextOverflowPolicy::Summarize
+    );
+    assert_eq!(
+        CliArgs::default().context_overflow,
+        ContextOverflowPolicy::SlidingWindow
+    );
+}
+
+#[test]
+fn test_parse_voice() {
+    let args = vec!["program".to_string(), "--voice".to_string()];
+    let parsed = CliArgs::from_args(&args);
+    assert!(parsed.voice);
+    assert!(parsed.validate().is_ok());
+    assert!(!CliArgs::default().voice);
+
+    let args = vec![
+        "program".to_string(),
+        "--voice".to_string(),
+        "--message".to_string(),
+        "hi".to_string(),
+    ];
+    assert!(CliArgs::from_args(&args).validate().is_err());
+}
+
+#[test]
+fn test_parse_fallback_models_in_order() {
+    let args: Vec<String> = [
+        "program",
+        "--model",
+        "primary",
+        "--fallback-model",
+        "second",
+        "--fallback-model",
+        "third",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.fallback_models, vec!["second", "third"]);
+    assert!(cli_args.validate().is_ok());
+
+    let without_model = CliArgs {
+        model: None,
+        ..cli_args
+    };
+    assert!(without_model.validate().is_err());
+}
+
+#[test]
+fn test_parse_devices() {
+    use cyrup_candle::core::DeviceSpec;
+
+    let args: Vec<String> = [
+        "program",
+        "--device",
+        "cuda:1",
+        "--shard-devices",
+        "cuda:0, cuda:1",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    let config = cli_args.device_config().expect("valid devices");
+    assert_eq!(config.device, DeviceSpec::Cuda(1));
+    assert_eq!(
+        config.shard_devices,
+        vec![DeviceSpec::Cuda(0), DeviceSpec::Cuda(1)]
+    );
+    assert_eq!(config.primary(), DeviceSpec::Cuda(0));
+
+    let unknown = CliArgs {
+        device: Some("tpu".to_string()),
+        ..CliArgs::default()
+    };
+    assert!(unknown.validate().is_err());
+
+    let repeated = CliArgs {
+        shard_devices: vec!["metal".to_string(), "metal:0".to_string()],
+        ..CliArgs::default()
+    };
+    assert!(repeated.validate().is_err());
+
+    let args: Vec<String> = ["program", "devices"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(CliArgs::from_args(&args).command, CliCommand::Devices);
+}
+
+#[test]
+fn test_parse_eval() {
+    let args: Vec<String> = [
+        "program",
+        "eval",
+        "--suite",
+        "suite.toml",
+        "--model",
+        "a",
+        "--compare-model",
+        "b",
+        "--compare-model",
+        "c",
+        "--judge-model",
+        "j",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.command, CliCommand::Eval);
+    assert!(!cli_args.interactive);
+    assert_eq!(cli_args.suite, Some(std::path::PathBuf::from("suite.toml")));
+    assert_eq!(cli_args.compare_models, vec!["b", "c"]);
+    assert_eq!(cli_args.judge_model.as_deref(), Some("j"));
+    assert!(cli_args.validate().is_ok());
+
+    let no_suite = CliArgs {
+        suite: None,
+        ..cli_args
+    };
+    assert!(no_suite.validate().is_err());
+}
+
+#[test]
+fn test_parse_extension() {
+    use cyrup_candle::extensions::bridge::ExtensionHost;
+
+    let args: Vec<String> = [
+        "program",
+        "extension",
+        "--extension-host",
+        "alfred",
+        "--message",
+        "what is this?",
+        "--image",
+        "shot.png",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.command, CliCommand::Extension);
+    assert_eq!(cli_args.extension_host, ExtensionHost::Alfred);
+    assert_eq!(cli_args.images, vec!["shot.png"]);
+    assert!(cli_args.validate().is_ok());
+
+    let bundle_only: Vec<String> = ["program", "extension", "--bundle", "out"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let bundle_args = CliArgs::from_args(&bundle_only);
+    assert_eq!(bundle_args.extension_host, ExtensionHost::Raycast);
+    assert!(bundle_args.validate().is_ok());
+
+    let no_query = CliArgs {
+        bundle: None,
+        ..bundle_args
+    };
+    assert!(no_query.validate().is_err());
+}
+
+#[test]
+fn test_parse_schedule() {
+    use cyrup_candle::cli::ScheduleAction;
+
+    let args: Vec<String> = [
+        "program",
+        "schedule",
+        "add",
+        "--name",
+        "standup",
+        "--cron",
+        "0 9 * * MON-FRI",
+        "--message",
+        "Summarize yesterday's commits",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.command, CliCommand::Schedule);
+    assert_eq!(cli_args.schedule_action, ScheduleAction::Add);
+    assert_eq!(cli_args.schedule_name.as_deref(), Some("standup"));
+    assert_eq!(cli_args.cron.as_deref(), Some("0 9 * * MON-FRI"));
+    assert!(cli_args.documents.is_empty());
+    assert!(cli_args.validate().is_ok());
+
+    let both = CliArgs {
+        every: Some("15m".to_string()),
+        ..cli_args.clone()
+    };
+    assert!(both.validate().is_err());
+
+    let remove: Vec<String> = ["program", "schedule", "remove", "standup"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let remove_args = CliArgs::from_args(&remove);
+    assert_eq!(remove_args.schedule_action, ScheduleAction::Remove);
+    assert_eq!(remove_args.schedule_target.as_deref(), Some("standup"));
+    assert!(remove_args.validate().is_ok());
+
+    let no_target = CliArgs {
+        schedule_target: None,
+        ..remove_args
+    };
+    assert!(no_target.validate().is_err());
+}
+
+#[test]
+fn test_parse_schedule_triggers() {
+    let args: Vec<String> = [
+        "program",
+        "schedule",
+        "add",
+        "--name",
+        "on-push",
+        "--webhook",
+        "/hooks/push",
+        "--webhook-secret",
+        "s3cret",
+        "--message",
+        "Review {{payload.ref}}",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let cli_args = CliArgs::from_args(&args);
+    assert_eq!(cli_args.webhook.as_deref(), Some("/hooks/push"));
+    assert_eq!(cli_args.webhook_secret.as_deref(), Some("s3cret"));
+    assert!(cli_args.validate().is_ok());
+
+    let notification = CliArgs {
+        webhook: None,
+        on_notification: Some("notifications/resources/updated".to_string()),
+        ..cli_args
+    };
+    assert!(notification.validate().is_err());
+
+    let with_server = CliArgs {
+        mcp_server: Some("fs-server --watch".to_string()),
+        ..notification
+    };
+    assert!(with_server.validate().is_ok());
+}
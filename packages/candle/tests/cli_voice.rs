@@ -0,0 +1,160 @@
+//! Tests for CLI voice mode
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use cyrup_candle::cli::voice::{SentenceChunker, VoiceMode, VoiceSettings, speak_with_barge_in};
+use cyrup_candle::domain::chat::message::CandleMessageChunk;
+use parking_lot::Mutex;
+use sweetmcp_voice_tools::{ListenParams, ListenResult, SpeakParams, VoiceResult, VoiceService};
+
+/// Scripted voice service: speech takes `speak_delay`, and listening returns
+/// the scripted transcripts in order (silence once they run out)
+#[derive(Clone, Default)]
+struct MockVoice {
+    speak_delay: Duration,
+    heard: Arc<Mutex<Vec<String>>>,
+    spoken: Arc<Mutex<Vec<String>>>,
+    listens: Arc<AtomicUsize>,
+}
+
+impl MockVoice {
+    fn new(speak_delay: Duration, heard: &[&str]) -> Self {
+        Self {
+            speak_delay,
+            heard: Arc::new(Mutex::new(
+                heard.iter().rev().map(|s| s.to_string()).collect(),
+            )),
+            ..Self::default()
+        }
+    }
+}
+
+impl VoiceService for MockVoice {
+    async fn speak(&self, params: SpeakParams) -> VoiceResult<()> {
+        tokio::time::sleep(self.speak_delay).await;
+        self.spoken.lock().push(params.text);
+        Ok(())
+    }
+
+    async fn listen(&self, _params: ListenParams) -> VoiceResult<ListenResult> {
+        self.listens.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        Ok(ListenResult {
+            text: self.heard.lock().pop().unwrap_or_default(),
+            wake_word_detected: None,
+            confidence: None,
+            language: None,
+            language_confidence: None,
+            segments: None,
+            words: None,
+        })
+    }
+
+    async fn list_voices(&self) -> VoiceResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_microphones(&self) -> VoiceResult<Vec<String>> {
+        Ok(vec!["default".to_string()])
+    }
+}
+
+fn complete(text: &str) -> CandleMessageChunk {
+    CandleMessageChunk::Complete {
+        text: text.to_string(),
+        finish_reason: None,
+        usage: None,
+        token_count: None,
+        elapsed_secs: None,
+        tokens_per_sec: None,
+        provider: None,
+    }
+}
+
+#[test]
+fn test_chunker_splits_streamed_sentences() {
+    let mut chunker = SentenceChunker::new();
+    assert!(chunker.push("Hello there").is_empty());
+    assert_eq!(chunker.push(". How are"), ["Hello there."]);
+    assert_eq!(chunker.push(" you? Pi is 3.14"), ["How are you?"]);
+    assert_eq!(
+        chunker.push(" today!\n- **item**\n"),
+        ["Pi is 3.14 today!", "- item"]
+    );
+    assert_eq!(chunker.push("Trailing"), Vec::<String>::new());
+    assert_eq!(chunker.finish().as_deref(), Some("Trailing"));
+    assert_eq!(chunker.finish(), None);
+}
+
+#[tokio::test]
+async fn test_speak_completes_without_interruption() {
+    let voice = MockVoice::new(Duration::from_millis(30), &[]);
+    let heard = speak_with_barge_in(&voice, &VoiceSettings::default(), "Hi.".to_string())
+        .await
+        .expect("speak");
+    assert_eq!(heard, None);
+    assert_eq!(*voice.spoken.lock(), ["Hi."]);
+}
+
+#[tokio::test]
+async fn test_barge_in_stops_speech() {
+    let voice = MockVoice::new(Duration::from_secs(10), &["", "wait, stop"]);
+    let heard = speak_with_barge_in(&voice, &VoiceSettings::default(), "Long.".to_string())
+        .await
+        .expect("speak");
+    assert_eq!(heard.as_deref(), Some("wait, stop"));
+    assert!(voice.spoken.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_barge_in_disabled_never_listens() {
+    let voice = MockVoice::new(Duration::from_millis(30), &["ignored"]);
+    let settings = VoiceSettings {
+        barge_in: false,
+        ..VoiceSettings::default()
+    };
+    let heard = speak_with_barge_in(&voice, &settings, "Hi.".to_string())
+        .await
+        .expect("speak");
+    assert_eq!(heard, None);
+    assert_eq!(voice.listens.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_voice_mode_speaks_turn_before_listening() {
+    let settings = VoiceSettings {
+        barge_in: false,
+        ..VoiceSettings::default()
+    };
+    let voice = MockVoice::new(Duration::from_millis(20), &["", "next question"]);
+    let mode = VoiceMode::start(voice.clone(), settings);
+
+    mode.observe(&CandleMessageChunk::Text(
+        "First sentence. Second".to_string(),
+    ));
+    mode.observe(&complete(" sentence."));
+
+    let utterance = mode.next_utterance().await.expect("utterance");
+    assert_eq!(utterance, "next question");
+    assert_eq!(
+        *voice.spoken.lock(),
+        ["First sentence.", "Second sentence."]
+    );
+}
+
+#[tokio::test]
+async fn test_voice_mode_interruption_becomes_next_turn() {
+    let voice = MockVoice::new(Duration::from_secs(10), &["actually, never mind"]);
+    let mode = VoiceMode::start(voice.clone(), VoiceSettings::default());
+
+    mode.observe(&CandleMessageChunk::Text(
+        "A very long answer. More.".to_string(),
+    ));
+    mode.observe(&complete(""));
+
+    let utterance = mode.next_utterance().await.expect("utterance");
+    assert_eq!(utterance, "actually, never mind");
+    assert!(voice.spoken.lock().is_empty());
+}
@@ -0,0 +1,83 @@
+//! Tests for context window fitting and overflow policies
+
+use cyrup_candle::core::context_window::*;
+
+#[test]
+fn test_prompt_that_fits_is_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+    let window = ContextWindow::new(100, ContextOverflowPolicy::Abort);
+    let fitted = window.fit((0..40).collect(), 20)?;
+
+    assert_eq!(fitted.tokens.len(), 40);
+    assert_eq!(fitted.max_new_tokens, 20);
+    assert_eq!(
+        fitted.usage,
+        TurnTokenUsage {
+            prompt_tokens: 40,
+            evicted_tokens: 0,
+            completion_budget: 20,
+            context_window: 100,
+        }
+    );
+    assert_eq!(fitted.usage.utilization(), 60.0);
+    Ok(())
+}
+
+#[test]
+fn test_sliding_window_keeps_pinned_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let window = ContextWindow::new(10, ContextOverflowPolicy::SlidingWindow).with_pinned_prefix(2);
+    let fitted = window.fit((0..12).collect(), 4)?;
+
+    // 6 prompt tokens fit once 4 are reserved for the completion
+    assert_eq!(fitted.tokens, vec![0, 1, 8, 9, 10, 11]);
+    assert_eq!(fitted.usage.evicted_tokens, 6);
+    assert_eq!(fitted.max_new_tokens, 4);
+    Ok(())
+}
+
+#[test]
+fn test_completion_reserve_is_capped_at_half_the_window() -> Result<(), Box<dyn std::error::Error>>
+{
+    let window = ContextWindow::new(10, ContextOverflowPolicy::SlidingWindow);
+    assert_eq!(window.prompt_budget(1000), 5);
+
+    let fitted = window.fit((0..8).collect(), 1000)?;
+    assert_eq!(fitted.tokens, vec![3, 4, 5, 6, 7]);
+    assert_eq!(fitted.max_new_tokens, 5);
+    Ok(())
+}
+
+#[test]
+fn test_abort_rejects_oversized_prompt() {
+    let window = ContextWindow::new(10, ContextOverflowPolicy::Abort);
+    assert_eq!(
+        window.fit((0..12).collect(), 4),
+        Err(ContextOverflowError {
+            required: 12,
+            available: 6
+        })
+    );
+}
+
+#[test]
+fn test_policy_from_params() {
+    let params = serde_json::json!({ "context_overflow": "summary" });
+    assert_eq!(
+        ContextOverflowPolicy::from_params(Some(&params)),
+        Some(ContextOverflowPolicy::Summarize)
+    );
+    assert_eq!(
+        ContextWindow::from_params(
+            4096,
+            Some(&serde_json::json!({ "context_overflow": "nope" }))
+        )
+        .policy,
+        ContextOverflowPolicy::SlidingWindow
+    );
+    assert_eq!(ContextOverflowPolicy::from_params(None), None);
+    assert_eq!(
+        "sliding-window".parse::<ContextOverflowPolicy>(),
+        Ok(ContextOverflowPolicy::SlidingWindow)
+    );
+    assert!("compress".parse::<ContextOverflowPolicy>().is_err());
+    assert_eq!(estimate_tokens("abcdefgh"), 2);
+}
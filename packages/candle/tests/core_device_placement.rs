@@ -0,0 +1,80 @@
+//! Tests for device selection and layer placement planning
+
+use cyrup_candle::core::device_util::{AcceleratorInfo, DeviceSpec};
+use cyrup_candle::core::placement::*;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+
+fn gpu(ordinal: usize, free_gib: u64) -> AcceleratorInfo {
+    AcceleratorInfo {
+        spec: DeviceSpec::Cuda(ordinal),
+        name: format!("test gpu {ordinal}"),
+        total_bytes: Some(free_gib * GIB),
+        free_bytes: Some(free_gib * GIB),
+    }
+}
+
+#[test]
+fn test_device_spec_round_trips() {
+    for (text, spec) in [
+        ("auto", DeviceSpec::Auto),
+        ("cpu", DeviceSpec::Cpu),
+        ("metal", DeviceSpec::Metal(0)),
+        ("CUDA:2", DeviceSpec::Cuda(2)),
+    ] {
+        assert_eq!(text.parse::<DeviceSpec>(), Ok(spec));
+        assert_eq!(spec.to_string().parse::<DeviceSpec>(), Ok(spec));
+    }
+    assert!("cuda:x".parse::<DeviceSpec>().is_err());
+    assert!("cpu:1".parse::<DeviceSpec>().is_err());
+    assert!("tpu".parse::<DeviceSpec>().is_err());
+}
+
+#[test]
+fn test_layers_split_in_proportion_to_free_memory() {
+    let devices = [gpu(0, 24), gpu(1, 8)];
+    let placement = plan_layer_placement(32, 20 * GIB, &devices).expect("placement");
+
+    assert_eq!(placement.shards.len(), 2);
+    assert_eq!(placement.shards[0].layers, 0..24);
+    assert_eq!(placement.shards[1].layers, 24..32);
+    assert_eq!(
+        placement
+            .shards
+            .iter()
+            .map(|shard| shard.bytes)
+            .sum::<u64>(),
+        20 * GIB
+    );
+    assert!(placement.fits());
+    assert_eq!(placement.primary(), Some(DeviceSpec::Cuda(0)));
+    assert_eq!(placement.device_for_layer(23), Some(DeviceSpec::Cuda(0)));
+    assert_eq!(placement.device_for_layer(24), Some(DeviceSpec::Cuda(1)));
+    assert_eq!(placement.device_for_layer(32), None);
+}
+
+#[test]
+fn test_unknown_memory_splits_evenly_and_oversize_does_not_fit() {
+    let unknown = AcceleratorInfo {
+        spec: DeviceSpec::Metal(0),
+        name: "test metal".to_string(),
+        total_bytes: None,
+        free_bytes: None,
+    };
+    let placement = plan_layer_placement(5, 5 * GIB, &[gpu(0, 1), unknown]).expect("placement");
+    assert_eq!(placement.shards[0].layers, 0..3);
+    assert_eq!(placement.shards[1].layers, 3..5);
+    // Three layers of 1 GiB do not fit 90% of a 1 GiB device
+    assert!(!placement.shards[0].fits());
+    assert!(placement.shards[1].fits());
+    assert!(!placement.fits());
+
+    assert_eq!(
+        plan_layer_placement(0, GIB, &[gpu(0, 1)]),
+        Err(PlacementError::NoLayers)
+    );
+    assert_eq!(
+        plan_layer_placement(8, GIB, &[]),
+        Err(PlacementError::NoDevices)
+    );
+}
@@ -0,0 +1,205 @@
+//! Tests for deterministic generation record and replay
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use candle_core::{Device, Tensor};
+use cyrup_candle::StreamExt;
+use cyrup_candle::core::generation::{
+    CandleModel, CandleResult, ReplayError, ReplayHarness, ReplayLog, ReplayRecorder,
+    ReplayToolResult, ReplayTurn, SamplingConfig, SpecialTokens, TextGenerator,
+};
+use tokenizers::Tokenizer;
+use tokenizers::models::wordlevel::WordLevel;
+
+const VOCAB: u32 = 8;
+
+/// Predicts `last + step`
+struct CountingModel {
+    device: Device,
+    step: u32,
+}
+
+impl CandleModel for CountingModel {
+    fn forward<'a>(
+        &'a mut self,
+        input: &'a Tensor,
+        _position: usize,
+    ) -> Pin<Box<dyn Future<Output = CandleResult<Tensor>> + Send + '_>> {
+        Box::pin(async move {
+            let ids = input.to_vec2::<u32>()?;
+            let last = ids[0].last().copied().unwrap_or(0);
+            let mut logits = vec![0.0f32; VOCAB as usize];
+            logits[((last + self.step) % VOCAB) as usize] = 1.0;
+            Ok(Tensor::from_vec(logits, (1, VOCAB as usize), &self.device)?)
+        })
+    }
+
+    fn device(&self) -> &Device {
+        &self.device
+    }
+
+    fn vocab_size(&self) -> usize {
+        VOCAB as usize
+    }
+}
+
+fn tokenizer() -> Tokenizer {
+    let vocab: HashMap<String, u32> = (0..VOCAB).map(|id| (format!("t{id}"), id)).collect();
+    let model = WordLevel::builder()
+        .vocab(vocab)
+        .unk_token("t0".to_string())
+        .build()
+        .expect("word level tokenizer");
+    Tokenizer::new(model)
+}
+
+fn generator(step: u32, seed: Option<u64>) -> TextGenerator {
+    let config = seed.map_or_else(
+        || SamplingConfig::new(1.0),
+        |seed| SamplingConfig::new(1.0).with_seed(seed),
+    );
+    TextGenerator::new(
+        Box::new(CountingModel {
+            device: Device::Cpu,
+            step,
+        }),
+        tokenizer(),
+        Device::Cpu,
+        config,
+    )
+}
+
+async fn run(generator: TextGenerator, prompt: &str, max_tokens: u32) -> String {
+    let mut stream =
+        Box::pin(generator.generate(prompt.to_string(), max_tokens, SpecialTokens::new()));
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk.text);
+    }
+    text
+}
+
+/// Record a two-turn conversation with one tool call after the first turn
+async fn record() -> ReplayLog {
+    let recorder = ReplayRecorder::new();
+    run(
+        generator(1, Some(7)).with_replay_recorder(recorder.clone()),
+        "t1",
+        4,
+    )
+    .await;
+    recorder.record_tool_result(ReplayToolResult {
+        id: "call_1".to_string(),
+        name: "lookup".to_string(),
+        arguments: r#"{"key":"t3"}"#.to_string(),
+        output: Some(serde_json::json!("t5")),
+        error: None,
+    });
+    run(
+        generator(1, Some(7)).with_replay_recorder(recorder.clone()),
+        "t5",
+        3,
+    )
+    .await;
+    recorder.take()
+}
+
+#[tokio::test]
+async fn test_recorder_captures_seed_tokens_and_tool_results() {
+    let log = record().await;
+
+    assert_eq!(log.turns.len(), 2);
+    let first = &log.turns[0];
+    assert_eq!(first.prompt, "t1");
+    assert_eq!(first.seed, Some(7));
+    assert_eq!(first.max_tokens, 4);
+    assert_eq!(first.tokens, vec![2, 3, 4, 5]);
+    assert_eq!(first.text, "t2t3t4t5");
+    assert_eq!(first.tool_results.len(), 1);
+    assert_eq!(log.turns[1].tokens, vec![6, 7, 0]);
+    assert!(log.turns[1].tool_results.is_empty());
+}
+
+#[tokio::test]
+async fn test_replay_file_round_trips_and_reproduces() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("conversation.replay.json");
+    record().await.save(&path).expect("save replay");
+
+    let harness = ReplayHarness::from_file(&path).expect("load replay");
+    let replayed = harness
+        .verify(|turn| generator(1, turn.seed), SpecialTokens::new())
+        .await
+        .expect("identical replay");
+
+    assert_eq!(&replayed, harness.recording());
+}
+
+#[tokio::test]
+async fn test_replay_reports_first_divergent_turn() {
+    let harness = ReplayHarness::new(record().await);
+
+    let result = harness
+        .verify(|turn| generator(2, turn.seed), SpecialTokens::new())
+        .await;
+
+    match result {
+        Err(ReplayError::Diverged { turn, detail }) => {
+            assert_eq!(turn, 0);
+            assert!(detail.starts_with("token 0"), "{detail}");
+        }
+        other => panic!("expected divergence, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_replay_detects_changed_seed() {
+    let harness = ReplayHarness::new(record().await);
+
+    let result = harness
+        .verify(|_| generator(1, None), SpecialTokens::new())
+        .await;
+
+    assert!(matches!(result, Err(ReplayError::Diverged { turn: 0, .. })));
+}
+
+#[test]
+fn test_verify_reports_missing_turns() {
+    let turn = ReplayTurn {
+        prompt: "t1".to_string(),
+        tokens: vec![2],
+        text: "t2".to_string(),
+        ..ReplayTurn::default()
+    };
+    let recorded = ReplayLog {
+        turns: vec![turn.clone(), turn.clone()],
+        ..ReplayLog::default()
+    };
+    let replayed = ReplayLog {
+        turns: vec![turn],
+        ..ReplayLog::default()
+    };
+
+    assert!(matches!(
+        recorded.verify(&replayed),
+        Err(ReplayError::TurnCount {
+            recorded: 2,
+            replayed: 1
+        })
+    ));
+    assert!(recorded.verify(&recorded.clone()).is_ok());
+}
+
+#[test]
+fn test_load_rejects_unknown_version() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("future.replay.json");
+    std::fs::write(&path, r#"{"version":99,"turns":[]}"#).expect("write replay");
+
+    assert!(matches!(
+        ReplayLog::load(&path),
+        Err(ReplayError::UnsupportedVersion(99))
+    ));
+}
@@ -0,0 +1,143 @@
+//! Tests for speculative decoding with a draft model
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use candle_core::{Device, Tensor};
+use cyrup_candle::StreamExt;
+use cyrup_candle::core::generation::{
+    CandleModel, CandleResult, SamplingConfig, SpecialTokens, SpeculativeConfig, TextGenerator,
+};
+use tokenizers::Tokenizer;
+use tokenizers::models::wordlevel::WordLevel;
+
+const VOCAB: u32 = 8;
+
+/// Predicts `last + 1`; a `sloppy` model predicts `last + 2` after multiples of 3
+struct CountingModel {
+    device: Device,
+    sloppy: bool,
+}
+
+impl CountingModel {
+    fn boxed(sloppy: bool) -> Box<dyn CandleModel> {
+        Box::new(Self {
+            device: Device::Cpu,
+            sloppy,
+        })
+    }
+}
+
+impl CandleModel for CountingModel {
+    fn forward<'a>(
+        &'a mut self,
+        input: &'a Tensor,
+        _position: usize,
+    ) -> Pin<Box<dyn Future<Output = CandleResult<Tensor>> + Send + '_>> {
+        Box::pin(async move {
+            let ids = input.to_vec2::<u32>()?;
+            let last = ids[0].last().copied().unwrap_or(0);
+            let step = if self.sloppy && last % 3 == 0 { 2 } else { 1 };
+            let mut logits = vec![0.0f32; VOCAB as usize];
+            logits[((last + step) % VOCAB) as usize] = 1.0;
+            Ok(Tensor::from_vec(logits, (1, VOCAB as usize), &self.device)?)
+        })
+    }
+
+    fn device(&self) -> &Device {
+        &self.device
+    }
+
+    fn vocab_size(&self) -> usize {
+        VOCAB as usize
+    }
+
+    fn clear_cache(&mut self) -> CandleResult<()> {
+        Ok(())
+    }
+}
+
+fn tokenizer() -> Result<Tokenizer, Box<dyn std::error::Error>> {
+    let vocab: HashMap<String, u32> = (0..VOCAB).map(|id| (format!("t{id}"), id)).collect();
+    let model = WordLevel::builder()
+        .vocab(vocab)
+        .unk_token("t0".to_string())
+        .build()?;
+    Ok(Tokenizer::new(model))
+}
+
+async fn generate(generator: TextGenerator) -> (String, Option<f64>) {
+    let mut stream = Box::pin(generator.generate("t1".to_string(), 12, SpecialTokens::new()));
+    let mut text = String::new();
+    let mut acceptance = None;
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk.text);
+        if let Some(stats) = chunk.stats {
+            acceptance = stats.draft_acceptance_rate;
+        }
+    }
+    (text, acceptance)
+}
+
+#[tokio::test]
+async fn test_speculative_output_matches_plain_decoding() -> Result<(), Box<dyn std::error::Error>>
+{
+    let plain = TextGenerator::new(
+        CountingModel::boxed(false),
+        tokenizer()?,
+        Device::Cpu,
+        SamplingConfig::new(1.0),
+    );
+    let speculative = TextGenerator::new(
+        CountingModel::boxed(false),
+        tokenizer()?,
+        Device::Cpu,
+        SamplingConfig::new(1.0),
+    )
+    .with_draft_model(CountingModel::boxed(true), SpeculativeConfig::new(3))?;
+    assert!(speculative.has_draft_model());
+
+    let (expected, no_draft) = generate(plain).await;
+    let (actual, acceptance) = generate(speculative).await;
+
+    assert_eq!(actual, expected);
+    assert_eq!(no_draft, None);
+    // The draft is wrong after every multiple of 3, so some but not all proposals survive
+    let acceptance = acceptance.ok_or("missing acceptance rate")?;
+    assert!(acceptance > 0.0 && acceptance < 100.0, "{acceptance}");
+    Ok(())
+}
+
+#[test]
+fn test_draft_vocabulary_must_match() -> Result<(), Box<dyn std::error::Error>> {
+    struct WideModel(Device);
+    impl CandleModel for WideModel {
+        fn forward<'a>(
+            &'a mut self,
+            input: &'a Tensor,
+            _position: usize,
+        ) -> Pin<Box<dyn Future<Output = CandleResult<Tensor>> + Send + '_>> {
+            Box::pin(async move { Ok(input.clone()) })
+        }
+        fn device(&self) -> &Device {
+            &self.0
+        }
+        fn vocab_size(&self) -> usize {
+            VOCAB as usize * 2
+        }
+    }
+
+    let generator = TextGenerator::new(
+        CountingModel::boxed(false),
+        tokenizer()?,
+        Device::Cpu,
+        SamplingConfig::new(1.0),
+    );
+    let result = generator.with_draft_model(
+        Box::new(WideModel(Device::Cpu)),
+        SpeculativeConfig::default(),
+    );
+    assert!(result.is_err());
+    Ok(())
+}
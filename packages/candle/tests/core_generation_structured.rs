@@ -0,0 +1,122 @@
+//! Tests for JSON Schema and grammar constrained generation
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use candle_core::{Device, Tensor};
+use cyrup_candle::StreamExt;
+use cyrup_candle::core::generation::{
+    CandleModel, CandleResult, SamplingConfig, SpecialTokens, StructuredOutput, TextGenerator,
+};
+use tokenizers::Tokenizer;
+use tokenizers::models::wordlevel::WordLevel;
+
+const WORDS: [&str; 7] = ["<unk>", "maybe", "y", "es", "no", "true", "false"];
+
+/// Always prefers "maybe", then the remaining words in vocabulary order
+struct StubbornModel(Device);
+
+impl CandleModel for StubbornModel {
+    fn forward<'a>(
+        &'a mut self,
+        _input: &'a Tensor,
+        _position: usize,
+    ) -> Pin<Box<dyn Future<Output = CandleResult<Tensor>> + Send + '_>> {
+        Box::pin(async move {
+            let logits: Vec<f32> = (0..WORDS.len()).map(|id| -(id as f32)).collect();
+            Ok(Tensor::from_vec(logits, (1, WORDS.len()), &self.0)?)
+        })
+    }
+
+    fn device(&self) -> &Device {
+        &self.0
+    }
+
+    fn vocab_size(&self) -> usize {
+        WORDS.len()
+    }
+}
+
+fn generator() -> Result<TextGenerator, Box<dyn std::error::Error>> {
+    let vocab: HashMap<String, u32> = WORDS
+        .iter()
+        .enumerate()
+        .map(|(id, word)| (word.to_string(), id as u32))
+        .collect();
+    let model = WordLevel::builder()
+        .vocab(vocab)
+        .unk_token("<unk>".to_string())
+        .build()?;
+    Ok(TextGenerator::new(
+        Box::new(StubbornModel(Device::Cpu)),
+        Tokenizer::new(model),
+        Device::Cpu,
+        SamplingConfig::new(1.0),
+    ))
+}
+
+async fn generate(generator: TextGenerator) -> String {
+    let mut stream = Box::pin(generator.generate("maybe".to_string(), 10, SpecialTokens::new()));
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        text.push_str(&chunk.text);
+    }
+    text
+}
+
+#[tokio::test]
+async fn test_grammar_constrains_output() -> Result<(), Box<dyn std::error::Error>> {
+    let unconstrained = generate(generator()?).await;
+    assert!(unconstrained.starts_with("maybemaybe"), "{unconstrained}");
+
+    let constrained = generator()?
+        .with_structured_output(StructuredOutput::grammar(r#"root ::= "yes" | "no""#));
+    // Generation stops once the grammar is satisfied
+    assert_eq!(generate(constrained).await, "yes");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_schema_constrains_output() -> Result<(), Box<dyn std::error::Error>> {
+    let constrained = generator()?.with_structured_output(StructuredOutput::for_type::<bool>());
+    assert_eq!(generate(constrained).await, "true");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_grammar_reports_error() -> Result<(), Box<dyn std::error::Error>> {
+    let constrained =
+        generator()?.with_structured_output(StructuredOutput::grammar("root ::= undefined"));
+    let text = generate(constrained).await;
+    assert!(text.starts_with("ERROR:"), "{text}");
+    Ok(())
+}
+
+#[test]
+fn test_structured_output_from_params() {
+    let schema = serde_json::json!({ "type": "boolean" });
+
+    let output = StructuredOutput::JsonSchema(schema.clone());
+    assert_eq!(
+        StructuredOutput::from_params(Some(&output.to_params())),
+        Some(output)
+    );
+
+    // Agent builder params arrive as strings
+    let params = serde_json::json!({ "json_schema": schema.to_string() });
+    assert_eq!(
+        StructuredOutput::from_params(Some(&params)),
+        Some(StructuredOutput::JsonSchema(schema))
+    );
+
+    let params = serde_json::json!({ "grammar": "root ::= \"a\"" });
+    assert_eq!(
+        StructuredOutput::from_params(Some(&params)),
+        Some(StructuredOutput::grammar("root ::= \"a\""))
+    );
+
+    let params = serde_json::json!({ "json_schema": "{not json" });
+    assert_eq!(StructuredOutput::from_params(Some(&params)), None);
+    assert_eq!(StructuredOutput::from_params(None), None);
+}
@@ -0,0 +1,88 @@
+//! Tests for the paged key/value cache
+
+use candle_core::{DType, Device, Tensor};
+use cyrup_candle::core::kv_cache::*;
+
+/// `[1, 2, len, 4]` tensor filled with `value`
+fn kv(len: usize, value: f32) -> Result<Tensor, candle_core::Error> {
+    Tensor::full(value, (1, 2, len, 4), &Device::Cpu)?.to_dtype(DType::F32)
+}
+
+#[test]
+fn test_append_spans_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cache = PagedKvCache::new(KvCacheConfig::new(2).with_page_size(4));
+
+    for layer in 0..2 {
+        cache.append(layer, &kv(3, 1.0)?, &kv(3, 1.0)?)?;
+        cache.append(layer, &kv(6, 2.0)?, &kv(6, 2.0)?)?;
+    }
+
+    assert_eq!(cache.len(), 9);
+    // 9 positions over pages of 4 in each of 2 layers
+    assert_eq!(cache.page_count(), 6);
+    let (keys, values) = cache.keys_values(1)?.ok_or("layer 1 is empty")?;
+    assert_eq!(keys.dims(), &[1, 2, 9, 4]);
+    assert_eq!(values.dims(), &[1, 2, 9, 4]);
+    assert_eq!(keys.get(0)?.get(0)?.get(3)?.to_vec1::<f32>()?, vec![2.0; 4]);
+    Ok(())
+}
+
+#[test]
+fn test_sliding_window_evicts_oldest_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let config = KvCacheConfig::new(1)
+        .with_page_size(4)
+        .with_max_tokens(8)
+        .with_sliding_window();
+    assert_eq!(config.capacity_tokens(), Some(8));
+    let mut cache = PagedKvCache::new(config);
+
+    cache.append(0, &kv(4, 1.0)?, &kv(4, 1.0)?)?;
+    cache.append(0, &kv(4, 2.0)?, &kv(4, 2.0)?)?;
+    let (keys, _) = cache.append_and_get(0, &kv(2, 3.0)?, &kv(2, 3.0)?)?;
+
+    assert_eq!(cache.len(), 6);
+    assert_eq!(cache.evicted_tokens(), 4);
+    assert_eq!(cache.next_position(), 10);
+    assert_eq!(keys.dims(), &[1, 2, 6, 4]);
+    assert_eq!(keys.get(0)?.get(0)?.get(0)?.to_vec1::<f32>()?, vec![2.0; 4]);
+    Ok(())
+}
+
+#[test]
+fn test_bounded_cache_without_sliding_window_is_full() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cache = PagedKvCache::new(KvCacheConfig::new(1).with_page_size(4).with_max_tokens(4));
+
+    cache.append(0, &kv(4, 1.0)?, &kv(4, 1.0)?)?;
+    assert!(matches!(
+        cache.append(0, &kv(1, 1.0)?, &kv(1, 1.0)?),
+        Err(KvCacheError::CapacityExceeded {
+            requested: 5,
+            capacity: 4
+        })
+    ));
+    assert!(matches!(
+        cache.append(3, &kv(1, 1.0)?, &kv(1, 1.0)?),
+        Err(KvCacheError::LayerOutOfRange {
+            layer: 3,
+            layers: 1
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_truncate_and_clear() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cache = PagedKvCache::new(KvCacheConfig::new(1).with_page_size(4));
+    cache.append(0, &kv(10, 1.0)?, &kv(10, 1.0)?)?;
+
+    cache.truncate(5)?;
+    assert_eq!(cache.len(), 5);
+    assert_eq!(cache.page_count(), 2);
+    cache.append(0, &kv(1, 2.0)?, &kv(1, 2.0)?)?;
+    assert_eq!(cache.len(), 6);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(cache.keys_values(0)?.is_none());
+    Ok(())
+}
@@ -0,0 +1,210 @@
+//! Tests for conversation history compaction
+
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::chat::compaction::{
+    CompactionConfig, CompactionError, LiveHistory, compact_history, plan_compaction,
+};
+use cyrup_candle::domain::chat::message::CandleMessageRole;
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use parking_lot::Mutex;
+use tokio_stream::Stream;
+
+static SUMMARIZER_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "summarizer",
+    registry_key: "test/summarizer",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "summarizer",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Answers every prompt with a fixed reply and records the prompts
+#[derive(Debug, Clone)]
+struct Summarizer {
+    reply: Result<&'static str, &'static str>,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+impl Summarizer {
+    fn new(reply: Result<&'static str, &'static str>) -> Self {
+        Self {
+            reply,
+            prompts: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl CandleModel for Summarizer {
+    fn info(&self) -> &'static CandleModelInfo {
+        &SUMMARIZER_INFO
+    }
+}
+
+impl TextToTextCapable for Summarizer {
+    fn prompt(
+        &self,
+        prompt: CandlePrompt,
+        _params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        self.prompts.lock().push(prompt.to_string());
+        let chunk = match self.reply {
+            Ok(text) => CandleCompletionChunk::Text(text.to_string()),
+            Err(e) => CandleCompletionChunk::Error(e.to_string()),
+        };
+        Box::pin(tokio_stream::iter(vec![chunk]))
+    }
+}
+
+/// `turns` user/assistant exchanges of 40 characters (10 tokens) each
+fn history(turns: usize) -> LiveHistory {
+    let mut history = LiveHistory::default();
+    for turn in 0..turns {
+        history.push(
+            CandleMessageRole::User,
+            format!("{:<40}", format!("question {turn}")),
+        );
+        history.push(
+            CandleMessageRole::Assistant,
+            format!("{:<40}", format!("answer {turn}")),
+        );
+    }
+    history
+}
+
+#[test]
+fn test_plan_waits_for_trigger_and_keeps_recent_turns() {
+    // 10 messages at 12 tokens each
+    let history = history(5);
+    assert_eq!(history.estimated_tokens(), 120);
+    let config = CompactionConfig::default()
+        .with_trigger_ratio(0.8)
+        .with_target_ratio(0.5)
+        .with_keep_recent(4)
+        .with_max_summary_tokens(16);
+
+    // 120 of 200 is under the trigger
+    assert_eq!(plan_compaction(&history, 0, 200, &config), 0);
+    // 140 of 150 is over; 140 - 12n + 16 <= 75 needs 7, but 4 are kept
+    assert_eq!(plan_compaction(&history, 20, 150, &config), 6);
+    assert_eq!(
+        plan_compaction(&history, 20, 150, &config.with_keep_recent(8)),
+        2
+    );
+    assert_eq!(
+        plan_compaction(&history, 20, 150, &CompactionConfig::disabled()),
+        0
+    );
+}
+
+#[test]
+fn test_config_clamps_ratios_and_picks_window() {
+    let config = CompactionConfig::default()
+        .with_target_ratio(0.9)
+        .with_trigger_ratio(2.0);
+    assert!((config.trigger_ratio - 1.0).abs() < f32::EPSILON);
+    assert!((config.target_ratio - 0.8).abs() < f32::EPSILON);
+    let config = config.with_trigger_ratio(0.6);
+    assert!((config.target_ratio - 0.6).abs() < f32::EPSILON);
+
+    assert_eq!(config.window_for(Some(8192)), 8192);
+    assert_eq!(
+        config.with_context_window(1000).window_for(Some(8192)),
+        1000
+    );
+    assert_eq!(config.window_for(None), 4096);
+}
+
+#[tokio::test]
+async fn test_compaction_replaces_oldest_turns_with_summary() {
+    let model = Summarizer::new(Ok("  - The user is planning a trip to Oslo\n"));
+    let mut live = history(3);
+    live.summary = Some("- Earlier they asked about trains".to_string());
+    let config = CompactionConfig::default()
+        .with_keep_recent(2)
+        .with_max_summary_tokens(16);
+
+    let summary = compact_history(&model, &mut live, 40, 100, &config)
+        .await
+        .expect("summarized");
+    assert_eq!(
+        summary.as_deref(),
+        Some("- The user is planning a trip to Oslo")
+    );
+    assert_eq!(live.messages.len(), 2);
+    assert!(live.render().starts_with(
+        "Summary of earlier turns:\n- The user is planning a trip to Oslo\n\nUser: question 2"
+    ));
+
+    // The previous summary and the summarized turns went to the model
+    let prompts = model.prompts.lock();
+    assert_eq!(prompts.len(), 1);
+    assert!(prompts[0].contains("Earlier they asked about trains"));
+    assert!(prompts[0].contains("User: question 0"));
+    assert!(!prompts[0].contains("question 2"));
+}
+
+#[tokio::test]
+async fn test_failed_summary_leaves_history_alone() {
+    let config = CompactionConfig::default().with_keep_recent(0);
+    let mut live = history(3);
+    let before = live.clone();
+
+    let err = compact_history(
+        &Summarizer::new(Err("model offline")),
+        &mut live,
+        0,
+        10,
+        &config,
+    )
+    .await
+    .expect_err("provider error");
+    assert_eq!(err, CompactionError::Provider("model offline".to_string()));
+    let err = compact_history(&Summarizer::new(Ok("  ")), &mut live, 0, 10, &config)
+        .await
+        .expect_err("empty summary");
+    assert_eq!(err, CompactionError::EmptySummary);
+    assert_eq!(live, before);
+
+    // Nothing to do when the prompt fits
+    let untouched = compact_history(&Summarizer::new(Ok("unused")), &mut live, 0, 1000, &config)
+        .await
+        .expect("no compaction");
+    assert_eq!(untouched, None);
+}
@@ -0,0 +1,269 @@
+//! Tests for the reasoning step driven by the MCP reasoner
+
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::chat::reasoning::{
+    ReasoningPath, ReasoningStrategy, ThinkConfig, ThoughtScore, ThoughtScorer, ThoughtStep,
+    parse_thought_score, think,
+};
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use serde_json::json;
+use tokio_stream::Stream;
+
+static CYCLING_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "cycling",
+    registry_key: "test/cycling",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "cycling",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Answers each prompt with the next thought of a fixed cycle
+#[derive(Debug, Clone)]
+struct CyclingModel {
+    thoughts: Vec<&'static str>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl CyclingModel {
+    fn new(thoughts: Vec<&'static str>) -> Self {
+        Self {
+            thoughts,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl CandleModel for CyclingModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &CYCLING_INFO
+    }
+}
+
+impl TextToTextCapable for CyclingModel {
+    fn prompt(
+        &self,
+        _prompt: CandlePrompt,
+        _params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        let thought = self.thoughts[call % self.thoughts.len()];
+        Box::pin(tokio_stream::iter(vec![
+            CandleCompletionChunk::Text(format!(" {thought} ")),
+            CandleCompletionChunk::Complete {
+                text: String::new(),
+                finish_reason: None,
+                usage: None,
+                token_count: None,
+                elapsed_secs: None,
+                tokens_per_sec: None,
+                provider: None,
+            },
+        ]))
+    }
+}
+
+/// Scores thoughts from a table and records every request
+#[derive(Default)]
+struct TableScorer {
+    scores: HashMap<&'static str, f64>,
+    requests: Mutex<Vec<ThoughtStep>>,
+}
+
+impl TableScorer {
+    fn new(scores: &[(&'static str, f64)]) -> Self {
+        Self {
+            scores: scores.iter().copied().collect(),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl ThoughtScorer for TableScorer {
+    fn score<'a>(&'a self, step: &'a ThoughtStep) -> BoxFuture<'a, Result<ThoughtScore, String>> {
+        Box::pin(async move {
+            self.requests.lock().push(step.clone());
+            let score = *self
+                .scores
+                .get(step.thought.as_str())
+                .ok_or_else(|| format!("unknown thought {}", step.thought))?;
+            Ok(ThoughtScore {
+                node_id: Some(format!("node-{}", step.thought)),
+                score,
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_think_keeps_best_scored_path() {
+    let model = CyclingModel::new(vec!["alpha", "beta", "gamma", "delta"]);
+    let scorer = TableScorer::new(&[
+        ("alpha", 0.2),
+        ("beta", 0.9),
+        ("gamma", 0.5),
+        ("delta", 0.95),
+    ]);
+    let config = ThinkConfig::default().with_beam_width(2).with_max_steps(2);
+
+    let path = think(
+        &model,
+        &scorer,
+        "question",
+        &config,
+        &CandleCompletionParams::default(),
+    )
+    .await
+    .expect("reasoning path");
+
+    assert_eq!(path.thoughts, vec!["beta", "delta"]);
+    assert_eq!(path.score, 0.95);
+    assert_eq!(path.render(), "1. beta\n2. delta");
+
+    let requests = scorer.requests.lock();
+    assert_eq!(requests.len(), 6);
+    let delta = requests
+        .iter()
+        .find(|r| r.thought == "delta")
+        .expect("delta scored");
+    assert_eq!(delta.parent_id.as_deref(), Some("node-beta"));
+    assert_eq!(delta.thought_number, 2);
+    assert!(!delta.next_thought_needed);
+    assert!(requests[0].parent_id.is_none());
+    assert!(requests[0].next_thought_needed);
+}
+
+#[tokio::test]
+async fn test_think_drops_duplicate_candidates() {
+    let model = CyclingModel::new(vec!["same"]);
+    let scorer = TableScorer::new(&[("same", 0.5)]);
+    let config = ThinkConfig::default().with_beam_width(3).with_max_steps(2);
+
+    let path = think(
+        &model,
+        &scorer,
+        "question",
+        &config,
+        &CandleCompletionParams::default(),
+    )
+    .await
+    .expect("reasoning path");
+
+    assert_eq!(path.thoughts, vec!["same", "same"]);
+    assert_eq!(model.calls.load(Ordering::SeqCst), 6);
+    assert_eq!(scorer.requests.lock().len(), 2);
+}
+
+#[tokio::test]
+async fn test_think_fails_when_scorer_fails() {
+    let model = CyclingModel::new(vec!["unscored"]);
+    let scorer = TableScorer::default();
+
+    let result = think(
+        &model,
+        &scorer,
+        "question",
+        &ThinkConfig::default(),
+        &CandleCompletionParams::default(),
+    )
+    .await;
+
+    assert_eq!(result, Err("unknown thought unscored".to_string()));
+}
+
+#[test]
+fn test_thought_step_uses_reasoner_request_format() {
+    let step = ThoughtStep {
+        thought: "t".to_string(),
+        thought_number: 1,
+        total_thoughts: 3,
+        next_thought_needed: true,
+        parent_id: None,
+        strategy_type: ReasoningStrategy::Mcts002AltAlpha,
+        beam_width: 2,
+        num_simulations: Some(50),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&step).expect("serialize step"),
+        json!({
+            "thought": "t",
+            "thoughtNumber": 1,
+            "totalThoughts": 3,
+            "nextThoughtNeeded": true,
+            "strategyType": "mcts_002alt_alpha",
+            "beamWidth": 2,
+            "numSimulations": 50
+        })
+    );
+}
+
+#[test]
+fn test_parse_thought_score_accepts_wrapped_responses() {
+    let direct = json!({ "nodeId": "n1", "score": 0.7, "thoughtNumber": 1 });
+    let expected = ThoughtScore {
+        node_id: Some("n1".to_string()),
+        score: 0.7,
+    };
+    assert_eq!(parse_thought_score(&direct), Ok(expected.clone()));
+
+    let content = json!({ "content": [{ "type": "text", "text": direct.to_string() }] });
+    assert_eq!(parse_thought_score(&content), Ok(expected.clone()));
+
+    let sandboxed = json!({ "success": true, "exit_code": 0, "stdout": direct.to_string() });
+    assert_eq!(parse_thought_score(&sandboxed), Ok(expected));
+
+    assert!(parse_thought_score(&json!({ "stdout": "not json" })).is_err());
+    assert!(parse_thought_score(&json!({ "success": false })).is_err());
+}
+
+#[test]
+fn test_think_config_defaults_hide_thoughts() {
+    let config = ThinkConfig::default();
+    assert_eq!(config.strategy, ReasoningStrategy::BeamSearch);
+    assert!(!config.show_thoughts);
+    assert!(config.store_in_memory);
+    assert_eq!(ThinkConfig::default().with_beam_width(0).beam_width, 1);
+    assert!(ReasoningPath::default().is_empty());
+}
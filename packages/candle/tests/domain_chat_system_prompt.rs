@@ -0,0 +1,107 @@
+//! Tests for templated system prompts
+
+use cyrup_candle::domain::agent::role::convert_serde_to_sweet_json;
+use cyrup_candle::domain::chat::system_prompt::*;
+use serde_json::json;
+use sweet_mcp_type::ToolInfo;
+
+fn tool(name: &str, description: &str) -> ToolInfo {
+    ToolInfo {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        input_schema: convert_serde_to_sweet_json(json!({"type": "object"})),
+    }
+}
+
+#[test]
+fn test_variables_and_user_profile_are_rendered() {
+    let template = SystemPromptTemplate::new(
+        "You help {{ user.name }} ({{ user.locale }}). Team: {{ team }}. Today is {{ date }}.",
+    )
+    .with_user_profile(json!({"name": "Ada", "locale": "en-GB"}))
+    .with_variable("team", "platform");
+
+    let compiled = template.compile().expect("template compiles");
+    let rendered = compiled
+        .render(&PromptTurn::default())
+        .expect("template renders");
+
+    assert!(rendered.starts_with("You help Ada (en-GB). Team: platform. Today is "));
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    assert!(rendered.ends_with(&format!("{today}.")));
+}
+
+#[test]
+fn test_partials_render_the_tool_list() {
+    let template = SystemPromptTemplate::new("You are an assistant.\n{% include \"tools\" %}")
+        .with_partial(
+            "tools",
+            "{% for tool in tools %}- {{ tool.name }}: {{ tool.description }}\n{% endfor %}",
+        );
+    let tools = [
+        tool("search", "Search the web"),
+        tool("read_file", "Read a file"),
+    ];
+
+    let compiled = template.compile().expect("template compiles");
+    let rendered = compiled
+        .render(&PromptTurn {
+            tools: &tools,
+            memories: "",
+        })
+        .expect("template renders");
+
+    assert_eq!(
+        rendered,
+        "You are an assistant.\n- search: Search the web\n- read_file: Read a file"
+    );
+}
+
+#[test]
+fn test_memories_are_placed_by_templates_that_use_them() {
+    let plain = SystemPromptTemplate::new("Be brief.");
+    assert!(
+        !plain
+            .compile()
+            .expect("template compiles")
+            .renders_memories()
+    );
+
+    // Memories used only in a partial still count
+    let template = SystemPromptTemplate::new("Be brief.{% include \"recall\" %}").with_partial(
+        "recall",
+        "{% if memories %}\n\nYou remember:\n{{ memories }}{% endif %}",
+    );
+    let compiled = template.compile().expect("template compiles");
+    assert!(compiled.renders_memories());
+
+    let without = compiled
+        .render(&PromptTurn::default())
+        .expect("template renders");
+    assert_eq!(without, "Be brief.");
+
+    let with = compiled
+        .render(&PromptTurn {
+            tools: &[],
+            memories: "- prefers metric units",
+        })
+        .expect("template renders");
+    assert_eq!(with, "Be brief.\n\nYou remember:\n- prefers metric units");
+}
+
+#[test]
+fn test_compile_errors_name_the_broken_template() {
+    let template = SystemPromptTemplate::new("Fine").with_partial("broken", "{% if %}");
+
+    let Err(SystemPromptError::Compile { name, .. }) = template.compile() else {
+        panic!("broken partial should fail to compile");
+    };
+    assert_eq!(name, "broken");
+
+    let missing = SystemPromptTemplate::new("{% include \"absent\" %}");
+    let compiled = missing.compile().expect("template compiles");
+    assert!(matches!(
+        compiled.render(&PromptTurn::default()),
+        Err(SystemPromptError::Render(_))
+    ));
+}
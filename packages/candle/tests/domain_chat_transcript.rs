@@ -0,0 +1,131 @@
+//! Tests for persistent conversation transcripts
+
+use cyrup_candle::domain::chat::transcript::*;
+use surrealdb::engine::any::connect;
+
+async fn temp_store(
+    dir: &tempfile::TempDir,
+) -> Result<TranscriptStore, Box<dyn std::error::Error>> {
+    let db = connect(format!(
+        "surrealkv://{}",
+        dir.path().join("agent.db").display()
+    ))
+    .await?;
+    db.use_ns("candle").use_db("agent").await?;
+    let store = TranscriptStore::new(db);
+    store.initialize().await?;
+    Ok(store)
+}
+
+#[test]
+fn test_session_title_truncates_first_line() {
+    assert_eq!(
+        session_title("  Fix the build\nmore detail"),
+        "Fix the build"
+    );
+
+    let long = "x".repeat(100);
+    let title = session_title(&long);
+    assert_eq!(title.chars().count(), 60);
+    assert!(title.ends_with('…'));
+}
+
+#[test]
+fn test_render_history_skips_system_and_tools() {
+    let entries = vec![
+        TranscriptEntry::message(TranscriptRole::System, "be nice"),
+        TranscriptEntry::message(TranscriptRole::User, "hi"),
+        TranscriptEntry::tool_call("call_1", "search", "{}"),
+        TranscriptEntry::message(TranscriptRole::Assistant, "hello"),
+    ];
+    assert_eq!(render_history(&entries), "User: hi\n\nAssistant: hello");
+}
+
+#[test]
+fn test_fit_history_applies_overflow_policy() -> Result<(), Box<dyn std::error::Error>> {
+    use cyrup_candle::core::context_window::ContextOverflowPolicy;
+
+    let entries: Vec<_> = (0..10)
+        .map(|turn| {
+            let role = if turn % 2 == 0 {
+                TranscriptRole::User
+            } else {
+                TranscriptRole::Assistant
+            };
+            TranscriptEntry::message(role, format!("turn {turn} {}", "x".repeat(32)))
+        })
+        .collect();
+
+    let all = fit_history(&entries, 10_000, ContextOverflowPolicy::Abort)?;
+    assert_eq!(all.recent.len(), 10);
+    assert_eq!(all.render(), render_history(&entries));
+
+    let sliding = fit_history(&entries, 40, ContextOverflowPolicy::SlidingWindow)?;
+    assert!(sliding.summary.is_none());
+    assert_eq!(sliding.dropped + sliding.recent.len(), 10);
+    assert_eq!(sliding.recent.last(), entries.last());
+
+    let summarized = fit_history(&entries, 40, ContextOverflowPolicy::Summarize)?;
+    let summary = summarized.summary.as_deref().ok_or("missing summary")?;
+    assert!(summarized.recent.len() < sliding.recent.len());
+    assert!(summary.starts_with("- "));
+    assert!(summarized.render().starts_with("Summary of earlier turns:"));
+
+    assert!(fit_history(&entries, 40, ContextOverflowPolicy::Abort).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_round_trip_and_resume_by_prefix() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let store = temp_store(&dir).await?;
+
+    let session_id = store.create_session(Some("qwen-3")).await?;
+    store
+        .append(
+            &session_id,
+            &TranscriptEntry::message(TranscriptRole::User, "What is Rust?"),
+        )
+        .await?;
+    store
+        .append(
+            &session_id,
+            &TranscriptEntry::tool_call("call_1", "search", r#"{"q":"rust"}"#),
+        )
+        .await?;
+    store
+        .append(
+            &session_id,
+            &TranscriptEntry::message(TranscriptRole::Assistant, "A language.").with_tokens(12),
+        )
+        .await?;
+
+    let session = store.resolve_session(&session_id[..8]).await?;
+    assert_eq!(session.session_id, session_id);
+    assert_eq!(session.title, "What is Rust?");
+    assert_eq!(session.model.as_deref(), Some("qwen-3"));
+    assert_eq!(session.message_count, 3);
+    assert_eq!(session.total_tokens, 12);
+
+    let entries = store.load(&session_id).await?;
+    let roles: Vec<_> = entries.iter().map(|e| e.role).collect();
+    assert_eq!(
+        roles,
+        [
+            TranscriptRole::User,
+            TranscriptRole::Tool,
+            TranscriptRole::Assistant
+        ]
+    );
+    assert_eq!(
+        entries[1].tool_call.as_ref().map(|call| call.name.as_str()),
+        Some("search")
+    );
+
+    assert_eq!(store.list_sessions(10).await?.len(), 1);
+    assert!(matches!(
+        store.resolve_session("no-such-session").await,
+        Err(TranscriptError::SessionNotFound(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Tests for per-conversation token usage and cost accounting
+
+use std::time::Duration;
+
+use cyrup_candle::domain::chat::usage::{
+    ConversationUsage, TokenPricing, ToolCallUsage, UsageTracker,
+};
+use cyrup_candle::domain::model::CandleUsage;
+use cyrup_candle::domain::tool::{RouterError, ToolCallOutcome};
+
+fn outcome(name: &str, result: Result<serde_json::Value, RouterError>) -> ToolCallOutcome {
+    ToolCallOutcome {
+        id: format!("call_{name}"),
+        name: name.to_string(),
+        result,
+        elapsed: Duration::from_millis(250),
+    }
+}
+
+#[test]
+fn test_turns_accumulate() {
+    let mut usage = ConversationUsage::default();
+    assert!(usage.is_empty());
+
+    usage.record_turn(CandleUsage::new(100, 40), Some(2.0));
+    usage.record_turn(CandleUsage::new(150, 60), Some(3.0));
+    usage.record_turn(CandleUsage::new(10, 0), None);
+
+    assert_eq!(usage.turns, 3);
+    assert_eq!(usage.tokens, CandleUsage::new(260, 100));
+    assert_eq!(usage.tokens.total_tokens, 360);
+    assert!((usage.generation_secs - 5.0).abs() < f64::EPSILON);
+    assert!((usage.tokens_per_sec() - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_cost_uses_per_million_rates() {
+    let mut usage = ConversationUsage::default();
+    usage.record_turn(CandleUsage::new(2_000_000, 500_000), Some(1.0));
+
+    let pricing = TokenPricing::new(0.5, 2.0);
+    assert!((usage.cost(&pricing) - 2.0).abs() < 1e-9);
+    assert!(usage.cost(&TokenPricing::default()).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_tool_calls_are_measured() {
+    let ok = ToolCallUsage::from_outcome(
+        r#"{"query": "rust borrow checker"}"#,
+        &outcome("search", Ok(serde_json::json!({"hits": ["a", "b", "c"]}))),
+    );
+    assert_eq!(ok.id, "call_search");
+    assert!(ok.succeeded);
+    assert!(ok.argument_tokens > 0);
+    assert!(ok.result_tokens > 0);
+    assert!((ok.elapsed_secs - 0.25).abs() < 1e-9);
+
+    let failed = ToolCallUsage::from_outcome(
+        "{}",
+        &outcome(
+            "search",
+            Err(RouterError::ExecutionFailed("timeout".to_string())),
+        ),
+    );
+    assert!(!failed.succeeded);
+    assert_eq!(failed.result_tokens, 0);
+
+    let mut usage = ConversationUsage::default();
+    usage.record_tool_call(ok);
+    usage.record_tool_call(failed);
+    assert!(!usage.is_empty());
+    assert!((usage.tool_secs() - 0.5).abs() < 1e-9);
+
+    let summary = usage.to_string();
+    assert!(summary.contains("0 turns"), "{summary}");
+    assert!(summary.contains("search: 2 calls (1 failed)"), "{summary}");
+}
+
+#[test]
+fn test_tracker_clones_share_totals() {
+    let tracker = UsageTracker::new();
+    let agent_handle = tracker.clone();
+
+    tracker.record_completion(Some(CandleUsage::new(12, 8)), Some(8), Some(0.5));
+    // Providers without usage still count their completion tokens
+    tracker.record_completion(None, Some(5), None);
+    tracker.record_completion(None, None, None);
+
+    let usage = agent_handle.snapshot();
+    assert_eq!(usage.turns, 3);
+    assert_eq!(usage.tokens, CandleUsage::new(12, 13));
+
+    assert_eq!(agent_handle.take(), usage);
+    assert!(tracker.snapshot().is_empty());
+}
@@ -0,0 +1,84 @@
+//! Tests for turns with image attachments
+
+use cyrup_candle::StreamExt;
+use cyrup_candle::capability::registry::{self, VisionModel};
+use cyrup_candle::domain::chat::message::CandleImageMediaType;
+use cyrup_candle::domain::chat::vision::{
+    default_vision_model, describe_attachments, describe_user_turn,
+};
+use cyrup_candle::domain::chat::{CandleImageAttachment, CandleMessageChunk};
+
+#[test]
+fn test_attachment_media_type_and_source() {
+    let local = CandleImageAttachment::new("photos/Cat.JPG");
+    assert_eq!(local.media_type, CandleImageMediaType::Jpeg);
+    assert!(!local.is_url());
+
+    let remote = CandleImageAttachment::new("https://example.com/chart.png");
+    assert_eq!(remote.media_type, CandleImageMediaType::Png);
+    assert!(remote.is_url());
+
+    assert_eq!(
+        CandleImageAttachment::new("scan.tiff").media_type,
+        CandleImageMediaType::Other("tiff".to_string())
+    );
+}
+
+#[test]
+fn test_user_turn_lists_attachments() {
+    let images = [
+        CandleImageAttachment::new("a.png"),
+        CandleImageAttachment::new("https://example.com/b.webp"),
+    ];
+    assert_eq!(
+        describe_user_turn("Compare these", &images),
+        "Compare these\n[image: a.png]\n[image: https://example.com/b.webp]"
+    );
+    assert_eq!(describe_user_turn("Plain", &[]), "Plain");
+}
+
+#[test]
+fn test_default_vision_model_is_registered() {
+    let keys = registry::vision_registry_keys();
+    assert!(!keys.is_empty());
+    assert!(registry::get::<VisionModel>(&keys[0]).is_some());
+    assert!(default_vision_model().is_some());
+}
+
+#[tokio::test]
+async fn test_missing_images_report_errors_then_complete() {
+    let model = default_vision_model().expect("vision model");
+    let images = vec![
+        CandleImageAttachment::new("/no/such/first.png"),
+        CandleImageAttachment::new("/no/such/second.png"),
+    ];
+
+    let chunks: Vec<CandleMessageChunk> = describe_attachments(model, "Describe".into(), images)
+        .collect()
+        .await;
+
+    let headings: Vec<&str> = chunks
+        .iter()
+        .filter_map(|chunk| match chunk {
+            CandleMessageChunk::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        headings,
+        [
+            "[image 1: /no/such/first.png]\n",
+            "\n\n[image 2: /no/such/second.png]\n"
+        ]
+    );
+
+    let errors = chunks.iter().filter(|chunk| chunk.is_error()).count();
+    assert_eq!(errors, 2);
+
+    match chunks.last() {
+        Some(CandleMessageChunk::Complete { usage, .. }) => {
+            assert_eq!(usage.map(|usage| usage.total_tokens), Some(0));
+        }
+        other => panic!("Expected Complete chunk, got {other:?}"),
+    }
+}
@@ -0,0 +1,50 @@
+//! Tests for chunking of indexed context documents
+
+use cyrup_candle::domain::context::{CandleIndexingConfig, chunk_document};
+
+fn numbered_lines(count: usize) -> String {
+    (1..=count)
+        .map(|i| format!("line number {i:02} here"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_chunks_overlap_and_cover_document() {
+    let config = CandleIndexingConfig::default()
+        .with_chunk_tokens(30)
+        .with_overlap_lines(2);
+    let chunks = chunk_document("src/lib.rs", &numbered_lines(20), &config);
+
+    let ranges: Vec<(usize, usize)> = chunks
+        .iter()
+        .map(|chunk| (chunk.start_line, chunk.end_line))
+        .collect();
+    assert_eq!(
+        ranges,
+        [(1, 5), (4, 8), (7, 11), (10, 14), (13, 17), (16, 20)]
+    );
+    assert!(
+        chunks[0]
+            .text
+            .starts_with("src/lib.rs (lines 1-5)\nline number 01 here")
+    );
+}
+
+#[test]
+fn test_oversized_lines_still_make_progress() {
+    // Every line exceeds the target and the overlap exceeds the chunk
+    let config = CandleIndexingConfig::default()
+        .with_chunk_tokens(1)
+        .with_overlap_lines(5);
+    let chunks = chunk_document("notes.md", &numbered_lines(20), &config);
+    assert_eq!(chunks.len(), 20);
+    assert_eq!(chunks[19].start_line, 20);
+}
+
+#[test]
+fn test_blank_documents_produce_no_chunks() {
+    let config = CandleIndexingConfig::default();
+    assert!(chunk_document("empty.txt", "", &config).is_empty());
+    assert!(chunk_document("blank.txt", "\n   \n\n", &config).is_empty());
+}
@@ -0,0 +1,125 @@
+//! Tests for tool call argument validation, policies and confirmation
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cyrup_candle::domain::tool::RouterError;
+use cyrup_candle::domain::tool::guardrails::*;
+use serde_json::{Value, json};
+
+fn write_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "path": {"type": "string"},
+            "content": {"type": "string"}
+        },
+        "required": ["path", "content"]
+    })
+}
+
+fn confirm_with(answer: bool, asked: Arc<AtomicUsize>) -> ConfirmationHandler {
+    Arc::new(move |_request: ConfirmationRequest| {
+        asked.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { answer })
+    })
+}
+
+#[tokio::test]
+async fn test_arguments_are_validated_against_schema() {
+    let guardrails = ToolGuardrails::new();
+    let schema = write_schema();
+
+    let valid = json!({"path": "notes.txt", "content": "hi"});
+    assert!(
+        guardrails
+            .check("write_file", Some(&schema), &valid)
+            .await
+            .is_ok()
+    );
+
+    let missing = json!({"path": "notes.txt"});
+    let result = guardrails
+        .check("write_file", Some(&schema), &missing)
+        .await;
+    assert!(matches!(result, Err(RouterError::InvalidArguments(_))));
+
+    let unchecked = ToolGuardrails::new().without_argument_validation();
+    assert!(
+        unchecked
+            .check("write_file", Some(&schema), &missing)
+            .await
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_workspace_policy_denies_paths_outside_root() {
+    let policy = WorkspacePolicy::new("/work/project").guard("write_file", ["path"]);
+
+    assert!(policy.contains("src/main.rs"));
+    assert!(policy.contains("/work/project/a/../b.txt"));
+    assert!(!policy.contains("../secrets.txt"));
+    assert!(!policy.contains("/etc/passwd"));
+
+    let allowed = policy.evaluate("write_file", &json!({"path": "docs/readme.md"}));
+    assert_eq!(allowed, PolicyDecision::Allow);
+
+    let denied = policy.evaluate("write_file", &json!({"path": "../../etc/passwd"}));
+    assert!(matches!(denied, PolicyDecision::Deny(_)));
+
+    // Tools that are not guarded are left alone
+    let other = policy.evaluate("read_file", &json!({"path": "/etc/passwd"}));
+    assert_eq!(other, PolicyDecision::Allow);
+}
+
+#[tokio::test]
+async fn test_confirmation_handler_decides_dangerous_calls() {
+    let policy = ConfirmTools::new().tool("shell", "runs arbitrary commands");
+    let args = json!({"command": "ls"});
+
+    let unconfirmed = ToolGuardrails::new()
+        .without_argument_validation()
+        .with_policy(policy.clone());
+    let result = unconfirmed.check("shell", None, &args).await;
+    assert!(matches!(result, Err(RouterError::Denied(_))));
+
+    let asked = Arc::new(AtomicUsize::new(0));
+    let approving = ToolGuardrails::new()
+        .with_policy(policy.clone())
+        .with_confirmation(confirm_with(true, Arc::clone(&asked)));
+    assert!(approving.check("shell", None, &args).await.is_ok());
+    assert!(approving.check("echo", None, &args).await.is_ok());
+    assert_eq!(asked.load(Ordering::SeqCst), 1);
+
+    let declining = ToolGuardrails::new()
+        .with_policy(policy)
+        .with_confirmation(confirm_with(false, Arc::clone(&asked)));
+    let result = declining.check("shell", None, &args).await;
+    assert!(matches!(result, Err(RouterError::Denied(_))));
+    assert_eq!(asked.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_denials_become_structured_tool_errors() {
+    let guardrails = ToolGuardrails::new().with_policy(|tool: &str, _args: &Value| match tool {
+        "delete_everything" => PolicyDecision::Deny("destructive".to_string()),
+        _ => PolicyDecision::Allow,
+    });
+
+    let Err(error) = guardrails
+        .check("delete_everything", None, &json!({}))
+        .await
+    else {
+        panic!("closure policy should deny the call");
+    };
+
+    let structured = error.to_tool_error("delete_everything");
+    assert_eq!(structured["error"]["kind"], "denied");
+    assert_eq!(structured["error"]["tool"], "delete_everything");
+    assert!(
+        structured["error"]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("destructive"))
+    );
+}
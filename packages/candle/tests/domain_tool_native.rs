@@ -0,0 +1,91 @@
+//! Tests for native Rust tools registered with the tool router
+
+use cyrup_candle::domain::agent::role::convert_serde_to_sweet_json;
+use cyrup_candle::domain::tool::{NativeTool, RouterError, SweetMcpRouter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Arguments of the add tool
+#[derive(Deserialize, JsonSchema)]
+struct AddArgs {
+    /// First addend
+    a: i64,
+    /// Second addend
+    b: i64,
+    /// Fail instead of adding
+    #[serde(default)]
+    fail: bool,
+}
+
+#[derive(Serialize)]
+struct Sum {
+    sum: i64,
+}
+
+fn add_tool() -> NativeTool {
+    NativeTool::new("add", "Add two integers", |args: AddArgs| async move {
+        if args.fail {
+            return Err("asked to fail");
+        }
+        Ok(Sum {
+            sum: args.a + args.b,
+        })
+    })
+}
+
+#[test]
+fn test_schema_is_derived_from_arguments() {
+    let tool = add_tool();
+    let schema = tool.input_schema();
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["a"]["description"], "First addend");
+    assert_eq!(schema["required"], json!(["a", "b"]));
+    assert!(schema.get("$schema").is_none());
+    assert!(schema.get("title").is_none());
+
+    let info = tool.tool_info();
+    assert_eq!(info.name, "add");
+    assert_eq!(info.description.as_deref(), Some("Add two integers"));
+}
+
+#[tokio::test]
+async fn test_call_deserializes_arguments_and_serializes_result() {
+    let tool = add_tool();
+    assert_eq!(
+        tool.call(json!({"a": 2, "b": 3})).await.ok(),
+        Some(json!({"sum": 5}))
+    );
+    assert!(matches!(
+        tool.call(json!({"a": "two"})).await,
+        Err(RouterError::InvalidArguments(message)) if message.starts_with("add: ")
+    ));
+    assert!(matches!(
+        tool.call(json!({"a": 1, "b": 1, "fail": true})).await,
+        Err(RouterError::ExecutionFailed(message)) if message == "add: asked to fail"
+    ));
+}
+
+#[tokio::test]
+async fn test_router_offers_and_routes_native_tools() -> Result<(), RouterError> {
+    let mut router = SweetMcpRouter::new().with_native_tools([
+        add_tool(),
+        // Same name as an earlier tool; skipped
+        NativeTool::new(
+            "add",
+            "Shadowed",
+            |_: AddArgs| async move { Ok::<_, String>(0) },
+        ),
+    ]);
+    router.initialize().await?;
+
+    let tools = router.get_available_tools().await;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].description.as_deref(), Some("Add two integers"));
+
+    let result = router
+        .call_tool("add", convert_serde_to_sweet_json(json!({"a": 40, "b": 2})))
+        .await?;
+    assert_eq!(result, json!({"sum": 42}));
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Tests for remote MCP server attachment in the tool router
+
+use cyrup_candle::domain::tool::remote::*;
+use cyrup_candle::domain::tool::SweetMcpRouter;
+
+#[test]
+fn test_parse_infers_transport() -> Result<(), Box<dyn std::error::Error>> {
+    let http = RemoteMcpServer::parse("search", "https://example.com/mcp")?;
+    assert!(matches!(
+        http.transport,
+        McpTransport::StreamableHttp { ref url, .. } if url == "https://example.com/mcp"
+    ));
+
+    let uds = RemoteMcpServer::parse("local", "unix:///run/sweetmcp.sock")?;
+    assert!(matches!(
+        uds.transport,
+        McpTransport::Uds { ref path } if path.to_str() == Some("/run/sweetmcp.sock")
+    ));
+    assert!(matches!(
+        RemoteMcpServer::parse("local", "/tmp/mcp.sock")?.transport,
+        McpTransport::Uds { .. }
+    ));
+
+    let stdio = RemoteMcpServer::parse("fs", "npx -y @modelcontextprotocol/server-filesystem /tmp")?
+        .with_setting("DEBUG", "1");
+    match stdio.transport {
+        McpTransport::Stdio { command, args, env } => {
+            assert_eq!(command, "npx");
+            assert_eq!(args, vec!["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]);
+            assert_eq!(env.get("DEBUG").map(String::as_str), Some("1"));
+        }
+        other => panic!("expected stdio transport, got {other:?}"),
+    }
+
+    assert!(RemoteMcpServer::parse("empty", "   ").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_namespacing_round_trips() {
+    let name = namespaced_tool_name("github", "create_issue");
+    assert_eq!(name, "github__create_issue");
+    assert_eq!(split_namespaced_tool(&name), Some(("github", "create_issue")));
+    assert_eq!(split_namespaced_tool("plain_tool"), None);
+
+    assert!(RemoteMcpServer::uds("bad__ns", "/tmp/x.sock").validate().is_err());
+    assert!(RemoteMcpServer::uds("", "/tmp/x.sock").validate().is_err());
+}
+
+#[tokio::test]
+async fn test_unreachable_server_is_skipped() -> Result<(), Box<dyn std::error::Error>> {
+    let mut router = SweetMcpRouter::new().with_remote_servers(vec![RemoteMcpServer::uds(
+        "missing",
+        "/nonexistent/paraphym-test.sock",
+    )]);
+
+    router.initialize().await?;
+    assert!(router.get_available_tools().await.is_empty());
+    assert!(router
+        .call_tool("missing__anything", sweet_mcp_type::JsonValue::from("x"))
+        .await
+        .is_err());
+    Ok(())
+}
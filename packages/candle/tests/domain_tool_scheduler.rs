@@ -0,0 +1,127 @@
+//! Tests for dependency-ordered parallel tool call execution
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use cyrup_candle::domain::tool::RouterError;
+use cyrup_candle::domain::tool::scheduler::*;
+use sweet_mcp_type::JsonValue;
+use tokio::sync::Mutex;
+
+fn call(id: &str) -> ScheduledToolCall {
+    ScheduledToolCall::new(id, format!("tool_{id}"), JsonValue::from(id))
+}
+
+#[test]
+fn test_from_model_call_strips_dependencies() -> Result<(), Box<dyn std::error::Error>> {
+    let call = ScheduledToolCall::from_model_call(
+        "b",
+        "search",
+        r#"{"query": "rust", "$depends_on": ["a"]}"#,
+    )?;
+    assert_eq!(call.depends_on, vec!["a".to_string()]);
+
+    let single = ScheduledToolCall::from_model_call("c", "search", r#"{"$depends_on": "b"}"#)?;
+    assert_eq!(single.depends_on, vec!["b".to_string()]);
+
+    assert!(ScheduledToolCall::from_model_call("d", "search", "{not json").is_err());
+    assert!(ScheduledToolCall::from_model_call("e", "search", r#"{"$depends_on": 3}"#).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_independent_calls_run_concurrently_within_bound() {
+    let active = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let calls = (0..6).map(|i| call(&i.to_string())).collect();
+
+    let outcomes = ToolScheduler::new()
+        .with_max_concurrency(3)
+        .execute(calls, |name, _args| {
+            let active = active.clone();
+            let peak = peak.clone();
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                Ok(serde_json::json!(name))
+            }
+        })
+        .await;
+
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+    let ids: Vec<_> = outcomes.iter().map(|o| o.id.as_str()).collect();
+    assert_eq!(ids, ["0", "1", "2", "3", "4", "5"]);
+    assert!(outcomes.iter().all(|o| o.result.is_ok()));
+}
+
+#[tokio::test]
+async fn test_dependencies_run_first_and_results_keep_call_order() {
+    let started = Arc::new(Mutex::new(Vec::new()));
+    // "slow" finishes last but is emitted first; "after" waits for it
+    let calls = vec![call("slow"), call("after").after("slow"), call("fast")];
+
+    let outcomes = ToolScheduler::new()
+        .execute(calls, |name, _args| {
+            let started = started.clone();
+            async move {
+                started.lock().await.push(name.clone());
+                if name == "tool_slow" {
+                    tokio::time::sleep(Duration::from_millis(30)).await;
+                }
+                Ok(serde_json::json!(name))
+            }
+        })
+        .await;
+
+    let started = started.lock().await.clone();
+    assert_eq!(started.len(), 3);
+    assert_eq!(started.last().map(String::as_str), Some("tool_after"));
+    let ids: Vec<_> = outcomes.iter().map(|o| o.id.as_str()).collect();
+    assert_eq!(ids, ["slow", "after", "fast"]);
+    assert!(outcomes[0].elapsed >= Duration::from_millis(30));
+    assert!(outcomes[2].elapsed < outcomes[0].elapsed);
+}
+
+#[tokio::test]
+async fn test_failed_unknown_and_cyclic_dependencies_are_not_run() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let calls = vec![
+        call("broken"),
+        call("child").after("broken"),
+        call("grandchild").after("child"),
+        call("orphan").after("missing"),
+        call("x").after("y"),
+        call("y").after("x"),
+        call("ok"),
+    ];
+
+    let outcomes = ToolScheduler::new()
+        .execute(calls, |name, _args| {
+            let runs = runs.clone();
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                if name == "tool_broken" {
+                    Err(RouterError::ExecutionFailed("boom".to_string()))
+                } else {
+                    Ok(serde_json::json!(name))
+                }
+            }
+        })
+        .await;
+
+    // Only "broken" and "ok" ever reach the tool
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    let failed: Vec<_> = outcomes
+        .iter()
+        .filter(|o| o.result.is_err())
+        .map(|o| o.id.as_str())
+        .collect();
+    assert_eq!(
+        failed,
+        ["broken", "child", "grandchild", "orphan", "x", "y"]
+    );
+    assert!(outcomes[6].result.is_ok());
+}
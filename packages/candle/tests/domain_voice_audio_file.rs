@@ -0,0 +1,44 @@
+use cyrup_candle::domain::voice::audio_file::{decode, encode_wav};
+use sweetmcp_voice_tools::VoiceError;
+
+/// `count` samples of a 440 Hz tone at `rate`
+fn tone(count: usize, rate: u32) -> Vec<f32> {
+    (0..count)
+        .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / rate as f32).sin() * 0.5)
+        .collect()
+}
+
+#[test]
+fn test_wav_round_trip() {
+    let samples = tone(2205, 22_050);
+    let wav = encode_wav(&samples, 22_050).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+
+    let (decoded, rate) = decode(wav, Some("wav")).unwrap();
+    assert_eq!(rate, 22_050);
+    assert_eq!(decoded.len(), samples.len());
+    for (a, b) in decoded.iter().zip(&samples) {
+        assert!((a - b).abs() < 1e-3, "{} vs {}", a, b);
+    }
+}
+
+#[test]
+fn test_decode_detects_format_without_hint() {
+    let wav = encode_wav(&tone(1600, 16_000), 16_000).unwrap();
+    let (decoded, rate) = decode(wav, None).unwrap();
+    assert_eq!((decoded.len(), rate), (1600, 16_000));
+}
+
+#[test]
+fn test_encode_clamps_out_of_range_samples() {
+    let wav = encode_wav(&[2.0, -2.0], 8_000).unwrap();
+    let (decoded, _) = decode(wav, None).unwrap();
+    assert!((decoded[0] - 1.0).abs() < 1e-3);
+    assert!((decoded[1] + 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_decode_rejects_non_audio() {
+    let result = decode(b"definitely not audio".to_vec(), Some("mp3"));
+    assert!(matches!(result, Err(VoiceError::InvalidAudio(_))));
+}
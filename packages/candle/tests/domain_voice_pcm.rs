@@ -0,0 +1,45 @@
+use cyrup_candle::domain::voice::pcm::{downmix, normalize_peak, resample, rms};
+
+#[test]
+fn test_downmix_averages_frames() {
+    let stereo = [1.0, 0.0, 0.5, 0.5, -1.0, 1.0];
+    assert_eq!(downmix(&stereo, 2), vec![0.5, 0.5, 0.0]);
+    assert_eq!(downmix(&stereo, 1), stereo.to_vec());
+}
+
+#[test]
+fn test_resample_changes_length_and_keeps_shape() {
+    let samples: Vec<f32> = (0..48_000)
+        .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 48_000.0).sin())
+        .collect();
+
+    let down = resample(&samples, 48_000, 16_000);
+    assert_eq!(down.len(), 16_000);
+    for (i, sample) in down.iter().enumerate().step_by(97) {
+        let expected = (i as f32 * 440.0 * std::f32::consts::TAU / 16_000.0).sin();
+        assert!((sample - expected).abs() < 0.01, "sample {i}");
+    }
+
+    let up = resample(&[0.0, 1.0], 1, 4);
+    assert_eq!(up, vec![0.0, 0.25, 0.5, 0.75, 1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(
+        resample(&samples[..10], 16_000, 16_000),
+        samples[..10].to_vec()
+    );
+    assert!(resample(&[], 48_000, 16_000).is_empty());
+}
+
+#[test]
+fn test_rms_and_normalize_peak() {
+    assert_eq!(rms(&[]), 0.0);
+    assert!((rms(&[0.5, -0.5, 0.5, -0.5]) - 0.5).abs() < 1e-6);
+
+    let mut samples = vec![0.1, -0.2, 0.05];
+    normalize_peak(&mut samples, 0.9);
+    assert!((samples[1] + 0.9).abs() < 1e-6);
+    assert!((samples[0] - 0.45).abs() < 1e-6);
+
+    let mut silence = vec![0.0; 4];
+    normalize_peak(&mut silence, 0.9);
+    assert_eq!(silence, vec![0.0; 4]);
+}
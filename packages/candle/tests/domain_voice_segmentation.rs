@@ -0,0 +1,71 @@
+use cyrup_candle::domain::voice::segmentation::{
+    VoiceFeatures, label_speakers, speaker_label, speech_segments, time_words, voice_features,
+};
+
+const RATE: u32 = 16_000;
+
+/// A voiced sound: a pitched tone with a couple of harmonics
+fn voice(millis: usize, pitch_hz: f32) -> Vec<f32> {
+    (0..RATE as usize * millis / 1000)
+        .map(|i| {
+            let t = i as f32 / RATE as f32;
+            let phase = t * pitch_hz * std::f32::consts::TAU;
+            0.2 * phase.sin() + 0.1 * (2.0 * phase).sin() + 0.05 * (3.0 * phase).sin()
+        })
+        .collect()
+}
+
+fn silence(millis: usize) -> Vec<f32> {
+    vec![0.0; RATE as usize * millis / 1000]
+}
+
+#[test]
+fn test_speech_segments_split_at_pauses() {
+    let mut samples = silence(500);
+    samples.extend(voice(1000, 120.0));
+    samples.extend(silence(1000));
+    samples.extend(voice(800, 220.0));
+
+    let segments = speech_segments(&samples, RATE, 0.5);
+    assert_eq!(segments.len(), 2, "{segments:?}");
+
+    let seconds = |sample: usize| sample as f32 / RATE as f32;
+    assert!((seconds(segments[0].start) - 0.5).abs() < 0.3);
+    assert!((seconds(segments[0].end) - 1.5).abs() < 0.3);
+    assert!((seconds(segments[1].start) - 2.5).abs() < 0.3);
+    assert_eq!(segments[1].end, samples.len());
+    assert!(segments[0].end <= segments[1].start);
+
+    assert!(speech_segments(&silence(2000), RATE, 0.5).is_empty());
+}
+
+#[test]
+fn test_voice_features_and_speaker_labels() {
+    let low = voice_features(&voice(1000, 120.0), RATE).expect("low voice");
+    let high = voice_features(&voice(1000, 220.0), RATE).expect("high voice");
+    assert!((low.pitch_hz - 120.0).abs() < 10.0, "{low:?}");
+    assert!((high.pitch_hz - 220.0).abs() < 15.0, "{high:?}");
+    assert!(low.distance(&high) > 1.0);
+    assert!(voice_features(&silence(1000), RATE).is_none());
+
+    let similar = VoiceFeatures {
+        pitch_hz: low.pitch_hz * 1.05,
+        brightness: low.brightness,
+    };
+    let speakers = label_speakers(&[Some(low), Some(high), None, Some(similar)]);
+    assert_eq!(speakers, [Some(0), Some(1), None, Some(0)]);
+    assert_eq!(speaker_label(0), "SPEAKER_1");
+}
+
+#[test]
+fn test_time_words_spreads_segment_by_length() {
+    let words = time_words("hi there", 1.0, 2.4, Some(0.8));
+    assert_eq!(words.len(), 2);
+    assert_eq!(words[0].word, "hi");
+    assert!((words[0].start_seconds - 1.0).abs() < 1e-6);
+    assert!((words[0].end_seconds - 1.4).abs() < 1e-6);
+    assert!((words[1].end_seconds - 2.4).abs() < 1e-6);
+    assert_eq!(words[1].confidence, Some(0.8));
+
+    assert!(time_words("   ", 0.0, 1.0, None).is_empty());
+}
@@ -0,0 +1,268 @@
+//! Tests for the eval harness
+
+use std::num::NonZeroU32;
+use std::pin::Pin;
+
+use cyrup_candle::capability::traits::TextToTextCapable;
+use cyrup_candle::domain::completion::{CandleCompletionChunk, CandleCompletionParams};
+use cyrup_candle::domain::model::traits::CandleModel;
+use cyrup_candle::domain::model::{CandleModelInfo, CandleProvider};
+use cyrup_candle::domain::prompt::CandlePrompt;
+use cyrup_candle::eval::*;
+use tokio_stream::Stream;
+
+static SCRIPTED_INFO: CandleModelInfo = CandleModelInfo {
+    provider: CandleProvider::AlibabaNLP,
+    name: "scripted",
+    registry_key: "test/scripted",
+    quantization_url: None,
+    max_input_tokens: NonZeroU32::new(4096),
+    max_output_tokens: None,
+    input_price: None,
+    output_price: None,
+    supports_vision: false,
+    supports_function_calling: false,
+    supports_streaming: true,
+    supports_embeddings: false,
+    requires_max_tokens: false,
+    supports_thinking: false,
+    optimal_thinking_budget: None,
+    system_prompt_prefix: None,
+    real_name: None,
+    model_type: None,
+    model_id: "scripted",
+    quantization: "none",
+    patch: None,
+    embedding_dimension: None,
+    vocab_size: None,
+    image_size: None,
+    image_mean: None,
+    image_std: None,
+    default_temperature: None,
+    default_top_k: None,
+    default_top_p: None,
+    supports_kv_cache: false,
+    supports_flash_attention: false,
+    use_bf16: false,
+    default_steps: None,
+    default_guidance_scale: None,
+    time_shift: None,
+    est_memory_allocation_mb: 0,
+};
+
+/// Answers from a fixed table of prompt fragments, failing anything else
+#[derive(Debug, Clone)]
+struct TableModel {
+    answers: Vec<(&'static str, &'static str)>,
+}
+
+impl TableModel {
+    fn new(answers: &[(&'static str, &'static str)]) -> Self {
+        Self {
+            answers: answers.to_vec(),
+        }
+    }
+}
+
+impl CandleModel for TableModel {
+    fn info(&self) -> &'static CandleModelInfo {
+        &SCRIPTED_INFO
+    }
+}
+
+impl TextToTextCapable for TableModel {
+    fn prompt(
+        &self,
+        prompt: CandlePrompt,
+        _params: &CandleCompletionParams,
+    ) -> Pin<Box<dyn Stream<Item = CandleCompletionChunk> + Send>> {
+        let chunk = self
+            .answers
+            .iter()
+            .find(|(fragment, _)| prompt.content.contains(fragment))
+            .map_or_else(
+                || CandleCompletionChunk::Error("no answer".to_string()),
+                |(_, answer)| CandleCompletionChunk::Text((*answer).to_string()),
+            );
+        Box::pin(tokio_stream::iter(vec![chunk]))
+    }
+}
+
+const SUITE: &str = r#"
+name = "basics"
+
+[[cases]]
+id = "add"
+prompt = "What is 2 + 2?"
+grader = { type = "exact_match", expected = "4" }
+
+[[cases]]
+id = "greeting"
+prompt = "Say hello"
+grader = { type = "regex", pattern = "(?i)^hello" }
+
+[[cases]]
+id = "person"
+prompt = "Describe a person as JSON"
+
+[cases.grader]
+type = "json_schema"
+schema = { type = "object", required = ["name", "age"], properties = { age = { type = "integer" } } }
+
+[[cases]]
+id = "haiku"
+prompt = "Write a haiku"
+expectation = "Three lines in a 5-7-5 pattern"
+grader = { type = "judge" }
+"#;
+
+fn judge() -> TableModel {
+    TableModel::new(&[("Write a haiku", "SCORE: 8\nGood imagery.")])
+}
+
+#[test]
+fn test_suite_parses_and_validates() {
+    let suite = EvalSuite::from_toml(SUITE).expect("valid suite");
+    assert_eq!(suite.name, "basics");
+    assert_eq!(suite.cases.len(), 4);
+    assert_eq!(suite.cases[2].grader.kind(), "json_schema");
+    assert!(matches!(
+        suite.cases[3].grader,
+        GraderSpec::Judge { criteria: None, pass_score } if (pass_score - 0.7).abs() < f64::EPSILON
+    ));
+
+    let json = serde_json::to_string(&suite).expect("serializes");
+    assert_eq!(EvalSuite::from_json(&json).expect("round trips"), suite);
+
+    let duplicate = r#"
+name = "dup"
+[[cases]]
+id = "a"
+prompt = "x"
+grader = { type = "regex", pattern = "x" }
+[[cases]]
+id = "a"
+prompt = "y"
+grader = { type = "regex", pattern = "y" }
+"#;
+    assert!(matches!(
+        EvalSuite::from_toml(duplicate),
+        Err(EvalError::InvalidSuite(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_runner_grades_every_provider() {
+    let suite = EvalSuite::from_toml(SUITE).expect("valid suite");
+    let good = TableModel::new(&[
+        ("2 + 2", " 4\n"),
+        ("Say hello", "Hello there"),
+        ("person", "```json\n{\"name\": \"Ada\", \"age\": 36}\n```"),
+        ("Write a haiku", "rust on the old gate"),
+    ]);
+    let bad = TableModel::new(&[
+        ("2 + 2", "five"),
+        ("Say hello", "Goodbye"),
+        ("person", "{\"name\": \"Ada\", \"age\": \"old\"}"),
+    ]);
+
+    let report = EvalRunner::new()
+        .with_named_provider("good", good)
+        .with_named_provider("bad", bad)
+        .with_judge(judge())
+        .run(&suite)
+        .await
+        .expect("suite runs");
+
+    let good = report.provider("good").expect("good report");
+    assert_eq!((good.passed, good.failed, good.errored), (4, 0, 0));
+    let haiku = &good.cases[3];
+    assert!((haiku.score - 0.8).abs() < 1e-9);
+    assert_eq!(haiku.reason, "Judge rated 8/10: Good imagery.");
+
+    let bad = report.provider("bad").expect("bad report");
+    assert_eq!((bad.passed, bad.failed, bad.errored), (0, 3, 1));
+    assert_eq!(bad.cases[0].reason, "Expected \"4\", got \"five\"");
+    assert!(bad.cases[2].reason.starts_with("Schema violations"));
+    assert_eq!(bad.cases[3].error.as_deref(), Some("no answer"));
+    assert!(bad.score.abs() < f64::EPSILON);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&report.to_json().expect("serializes")).expect("valid json");
+    assert_eq!(json["providers"][0]["passed"], 4);
+
+    let markdown = report.to_markdown();
+    assert!(markdown.starts_with("# Eval report: basics"));
+    assert!(markdown.contains("| good | 1.00 | 4/4 | 0 | 0 |"));
+    assert!(markdown.contains("| haiku | judge | error | 0.00 | no answer |"));
+}
+
+#[tokio::test]
+async fn test_graders_are_checked_before_running() {
+    let suite = EvalSuite::from_toml(SUITE).expect("valid suite");
+    let model = TableModel::new(&[]);
+
+    let missing_judge = EvalRunner::new()
+        .with_provider(model.clone())
+        .run(&suite)
+        .await;
+    assert!(matches!(
+        missing_judge,
+        Err(EvalError::InvalidGrader { case, .. }) if case == "haiku"
+    ));
+
+    assert!(matches!(
+        EvalRunner::new().run(&suite).await,
+        Err(EvalError::NoProviders)
+    ));
+}
+
+/// Passes answers no longer than the case's configured limit
+struct MaxLength;
+
+impl Grader for MaxLength {
+    fn grade<'a>(&'a self, case: &'a EvalCase, output: &'a str) -> GradeFuture<'a> {
+        Box::pin(async move {
+            let GraderSpec::Custom { config, .. } = &case.grader else {
+                return Err(EvalError::Grading("not a custom case".to_string()));
+            };
+            let limit = config["max"].as_u64().unwrap_or(0) as usize;
+            Ok(if output.len() <= limit {
+                Grade::pass("short enough")
+            } else {
+                Grade::fail(format!("{} characters", output.len()))
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_graders_are_selected_by_name() {
+    let suite = EvalSuite::from_json(
+        r#"{"name": "custom", "cases": [
+            {"id": "short", "prompt": "brief", "grader": {"type": "custom", "name": "max_length", "config": {"max": 5}}},
+            {"id": "long", "prompt": "ramble", "grader": {"type": "custom", "name": "max_length", "config": {"max": 5}}}
+        ]}"#,
+    )
+    .expect("valid suite");
+    let model = TableModel::new(&[("brief", "ok"), ("ramble", "far too long")]);
+
+    let unregistered = EvalRunner::new()
+        .with_provider(model.clone())
+        .run(&suite)
+        .await;
+    assert!(matches!(unregistered, Err(EvalError::InvalidGrader { .. })));
+
+    let report = EvalRunner::new()
+        .with_provider(model)
+        .with_grader("max_length", MaxLength)
+        .run(&suite)
+        .await
+        .expect("suite runs");
+    let provider = report
+        .provider("test/scripted")
+        .expect("named by registry key");
+    assert!(provider.cases[0].passed);
+    assert_eq!(provider.cases[1].reason, "12 characters");
+    assert_eq!(provider.cases[1].grader, "max_length");
+}
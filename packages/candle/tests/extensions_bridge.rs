@@ -0,0 +1,238 @@
+//! Tests for the Raycast and Alfred extension bridge
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use cyrup_candle::domain::chat::CandleChatLoop;
+use cyrup_candle::domain::chat::message::CandleMessageChunk;
+use cyrup_candle::extensions::bridge::{
+    ExtensionBridge, ExtensionHost, ExtensionImage, ExtensionManifest, ExtensionQuery,
+    ExtensionResponse,
+};
+use cyrup_candle::{Stream, StreamExt};
+
+type ChunkStream = Pin<Box<dyn Stream<Item = CandleMessageChunk> + Send>>;
+
+fn complete(text: &str) -> CandleMessageChunk {
+    CandleMessageChunk::Complete {
+        text: text.to_string(),
+        finish_reason: Some("stop".to_string()),
+        usage: None,
+        token_count: None,
+        elapsed_secs: None,
+        tokens_per_sec: None,
+        provider: None,
+    }
+}
+
+#[test]
+fn test_query_from_launcher_args() {
+    let args: Vec<String> = [
+        "describe",
+        "/tmp/shot.PNG",
+        "briefly",
+        "https://x.io/a.webp",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let query = ExtensionQuery::from_args(ExtensionHost::Raycast, &args);
+    assert_eq!(query.text, "describe briefly");
+    assert_eq!(query.images, vec!["/tmp/shot.PNG", "https://x.io/a.webp"]);
+    assert!(matches!(
+        query.to_chat_loop(),
+        CandleChatLoop::UserPromptWithImages(ref text, ref images)
+            if text == "describe briefly" && images.len() == 2
+    ));
+
+    // Alfred passes the whole query as one argument
+    let alfred = ExtensionQuery::from_args(
+        ExtensionHost::Alfred,
+        &["what is in report.png".to_string()],
+    );
+    assert_eq!(alfred.text, "what is in report.png");
+    assert_eq!(
+        alfred.to_chat_loop(),
+        CandleChatLoop::UserPrompt("what is in report.png".to_string())
+    );
+
+    assert_eq!(
+        "Alfred".parse::<ExtensionHost>().ok(),
+        Some(ExtensionHost::Alfred)
+    );
+    assert!("spotlight".parse::<ExtensionHost>().is_err());
+}
+
+#[test]
+fn test_response_renders_for_raycast() {
+    let mut response = ExtensionResponse::default();
+    assert!(response.apply(&CandleMessageChunk::ToolCallStart {
+        id: "1".to_string(),
+        name: "web_search".to_string(),
+    }));
+    assert!(response.apply(&CandleMessageChunk::Text("# Cats\n".to_string())));
+    assert!(!response.apply(&CandleMessageChunk::Text(String::new())));
+
+    let frame = response.to_raycast();
+    assert_eq!(frame["isLoading"], true);
+    assert_eq!(frame["markdown"], "> 🔧 `web_search`\n\n# Cats\n");
+
+    response.apply(&complete("![a cat](https://x.io/cat.png \"Cat\")"));
+    let frame = response.to_raycast();
+    assert_eq!(frame["isLoading"], false);
+    assert_eq!(frame["metadata"]["images"][0], "https://x.io/cat.png");
+    assert_eq!(
+        response.images(),
+        vec![ExtensionImage {
+            alt: "a cat".to_string(),
+            source: "https://x.io/cat.png".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_response_renders_for_alfred() {
+    let response = ExtensionResponse {
+        markdown: "## Answer\n\nSee ![](/tmp/chart.png)".to_string(),
+        done: true,
+        ..Default::default()
+    };
+    let output = response.to_alfred();
+    assert_eq!(output.items.len(), 2);
+
+    let answer = &output.items[0];
+    assert_eq!(answer.title, "Answer");
+    assert_eq!(answer.arg.as_deref(), Some(response.markdown.as_str()));
+    assert_eq!(answer.quicklookurl.as_deref(), Some("/tmp/chart.png"));
+    let image = &output.items[1];
+    assert_eq!(image.title, "chart.png");
+    assert_eq!(
+        image.icon.as_ref().and_then(|icon| icon.path.as_deref()),
+        Some("/tmp/chart.png")
+    );
+
+    // Unset fields are left out rather than sent as null
+    let json = serde_json::to_value(&output).expect("serializes");
+    assert!(json["items"][0].get("uid").is_none());
+    assert!(json.get("rerun").is_none());
+
+    let failed = ExtensionResponse {
+        error: Some("model unavailable".to_string()),
+        done: true,
+        ..Default::default()
+    };
+    let output = failed.to_alfred();
+    assert_eq!(output.items[0].title, "Error");
+    assert_eq!(output.items[0].valid, Some(false));
+}
+
+#[tokio::test]
+async fn test_bridge_streams_answer_frames() {
+    let turns = Arc::new(Mutex::new(Vec::new()));
+    let seen = turns.clone();
+    let bridge = ExtensionBridge::new(move |turn: CandleChatLoop| -> ChunkStream {
+        if let Ok(mut turns) = seen.lock() {
+            turns.push(turn);
+        }
+        Box::pin(cyrup_candle::from_iter(vec![
+            CandleMessageChunk::Text("Hello".to_string()),
+            complete(" world"),
+        ]))
+    });
+
+    let frames: Vec<ExtensionResponse> = bridge
+        .stream(ExtensionQuery::new(ExtensionHost::Raycast, "greet me"))
+        .collect()
+        .await;
+    assert_eq!(frames.len(), 2);
+    assert!(!frames[0].done);
+    assert_eq!(frames[1].markdown, "Hello world");
+    assert!(frames[1].done);
+    assert_eq!(
+        turns.lock().map(|turns| turns.clone()).unwrap_or_default(),
+        vec![CandleChatLoop::UserPrompt("greet me".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_bridge_finishes_on_timeout_and_empty_query() {
+    // Agent that sends part of an answer and then stalls
+    let bridge = ExtensionBridge::new(|_turn: CandleChatLoop| -> ChunkStream {
+        Box::pin(cyrup_candle::spawn_stream(|sender| async move {
+            let _ = sender.send(CandleMessageChunk::Text("partial".to_string()));
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }))
+    })
+    .with_timeout(Duration::from_millis(50));
+
+    let response = bridge
+        .respond(ExtensionQuery::new(ExtensionHost::Alfred, "slow"))
+        .await;
+    assert!(response.done);
+    assert_eq!(response.markdown, "partial");
+    assert!(response.error.is_some_and(|e| e.contains("Timed out")));
+
+    let empty = bridge
+        .respond(ExtensionQuery::new(ExtensionHost::Alfred, "  "))
+        .await;
+    assert!(empty.done);
+    assert!(empty.error.is_some());
+}
+
+#[tokio::test]
+async fn test_manifest_writes_bundles() {
+    let manifest = ExtensionManifest::new("/opt/candle/bin/candle")
+        .with_args(["--model".to_string(), "it's-a-model".to_string()])
+        .with_keyword("ai");
+
+    let package = manifest.raycast_package_json();
+    assert_eq!(package["name"], "candle-agent");
+    assert_eq!(package["commands"][0]["mode"], "view");
+    assert_eq!(package["commands"][0]["arguments"][0]["name"], "query");
+    let source = manifest.raycast_command_source();
+    assert!(source.contains(r#"const COMMAND = "/opt/candle/bin/candle";"#));
+    assert!(source.contains(r#""--extension-host","raycast""#));
+
+    let plist = manifest.alfred_info_plist();
+    let info = plist.as_dictionary().expect("dictionary");
+    assert_eq!(
+        info.get("bundleid").and_then(|v| v.as_string()),
+        Some("com.cyrup.candle-agent")
+    );
+    let objects = info
+        .get("objects")
+        .and_then(|v| v.as_array())
+        .expect("objects");
+    let filter = objects[0].as_dictionary().expect("script filter");
+    let config = filter
+        .get("config")
+        .and_then(|v| v.as_dictionary())
+        .expect("config");
+    assert_eq!(
+        config.get("keyword").and_then(|v| v.as_string()),
+        Some("ai")
+    );
+    assert_eq!(
+        config.get("script").and_then(|v| v.as_string()),
+        Some(
+            r#"'/opt/candle/bin/candle' '--model' 'it'\''s-a-model' 'extension' '--extension-host' 'alfred' --message "$1""#
+        )
+    );
+    // Object ids are stable, so regenerating keeps the connection intact
+    assert_eq!(manifest.alfred_info_plist(), plist);
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let raycast = manifest
+        .write_bundle(ExtensionHost::Raycast, dir.path())
+        .await
+        .expect("raycast bundle");
+    assert!(raycast.join("package.json").exists());
+    assert!(raycast.join("src/ask.tsx").exists());
+    let alfred = manifest
+        .write_bundle(ExtensionHost::Alfred, dir.path())
+        .await
+        .expect("alfred bundle");
+    let written = plist::Value::from_file(alfred.join("info.plist")).expect("valid plist");
+    assert_eq!(written, plist);
+}
@@ -0,0 +1,121 @@
+//! Tests for memory decay functions and the forgetting policy
+
+use cyrup_candle::memory::{CompactionAction, CompactionPolicy, DecayFunction, DecayWorkerConfig};
+
+#[test]
+fn test_exponential_decay_halves_after_half_life() {
+    let rate = std::f64::consts::LN_2 / 10.0;
+    let decay = DecayFunction::Exponential { rate };
+
+    let decayed = decay.decay(0.8, 10.0, 0);
+    assert!((decayed - 0.4).abs() < 1e-4, "{decayed}");
+    // Recall does not matter for plain exponential decay
+    assert_eq!(decay.decay(0.8, 10.0, 50), decayed);
+}
+
+#[test]
+fn test_decay_is_additive_over_intervals() {
+    let decay = DecayFunction::Exponential { rate: 0.1 };
+
+    let once = decay.decay(0.9, 6.0, 0);
+    let twice = decay.decay(decay.decay(0.9, 2.0, 0), 4.0, 0);
+    assert!((once - twice).abs() < 1e-5, "{once} vs {twice}");
+}
+
+#[test]
+fn test_usage_boost_slows_decay() {
+    let decay = DecayFunction::UsageBoosted {
+        rate: 0.2,
+        boost: 1.0,
+    };
+
+    assert_eq!(decay.effective_rate(0), 0.2);
+    let never_recalled = decay.decay(0.8, 10.0, 0);
+    let recalled = decay.decay(0.8, 10.0, 20);
+    assert!(recalled > never_recalled, "{recalled} <= {never_recalled}");
+    assert!(decay.effective_rate(100) < decay.effective_rate(20));
+}
+
+#[test]
+fn test_decay_never_drops_below_floor_or_grows() {
+    let decay = DecayFunction::Exponential { rate: 0.5 };
+
+    assert_eq!(decay.decay(0.9, 1_000.0, 0), 0.01);
+    // Clock skew must not raise importance
+    assert_eq!(decay.decay(0.9, -3.0, 0), 0.9);
+}
+
+#[test]
+fn test_with_rate_keeps_boost() {
+    let decay = DecayFunction::UsageBoosted {
+        rate: 0.1,
+        boost: 2.0,
+    }
+    .with_rate(0.3);
+
+    assert_eq!(
+        decay,
+        DecayFunction::UsageBoosted {
+            rate: 0.3,
+            boost: 2.0
+        }
+    );
+    assert_eq!(decay.rate(), 0.3);
+}
+
+#[test]
+fn test_compaction_forgets_only_old_low_relevance_memories() {
+    let policy = CompactionPolicy {
+        enabled: true,
+        action: CompactionAction::Delete,
+        relevance_threshold: 0.1,
+        min_age_days: 7.0,
+    };
+
+    assert_eq!(
+        policy.action_for(0.05, 30.0),
+        Some(CompactionAction::Delete)
+    );
+    assert_eq!(policy.action_for(0.05, 3.0), None);
+    assert_eq!(policy.action_for(0.5, 30.0), None);
+
+    let disabled = CompactionPolicy {
+        enabled: false,
+        ..policy
+    };
+    assert_eq!(disabled.action_for(0.05, 30.0), None);
+}
+
+#[test]
+fn test_decay_config_defaults_when_omitted() -> Result<(), serde_json::Error> {
+    let config: DecayWorkerConfig = serde_json::from_str(
+        r#"{ "cycle_interval_secs": 60, "batch_size": 10, "min_age_hours": 0 }"#,
+    )?;
+    assert_eq!(config.function, DecayFunction::default());
+    assert_eq!(config.compaction, CompactionPolicy::default());
+    assert_eq!(config.compaction.action, CompactionAction::Archive);
+
+    let config: DecayWorkerConfig = serde_json::from_str(
+        r#"{
+            "cycle_interval_secs": 60,
+            "batch_size": 10,
+            "min_age_hours": 0,
+            "function": { "kind": "usage_boosted", "rate": 0.2, "boost": 1.5 },
+            "compaction": {
+                "enabled": true,
+                "action": "delete",
+                "relevance_threshold": 0.02,
+                "min_age_days": 90.0
+            }
+        }"#,
+    )?;
+    assert_eq!(
+        config.function,
+        DecayFunction::UsageBoosted {
+            rate: 0.2,
+            boost: 1.5
+        }
+    );
+    assert_eq!(config.compaction.action, CompactionAction::Delete);
+    Ok(())
+}
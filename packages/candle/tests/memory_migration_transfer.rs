@@ -0,0 +1,151 @@
+//! Tests for memory export files and their forward migrations
+
+use std::path::Path;
+
+use cyrup_candle::memory::MemoryNode;
+use cyrup_candle::memory::migration::MigrationError;
+use cyrup_candle::memory::migration::transfer::{
+    MEMORY_EXPORT_VERSION, MemoryExportFormat, MemoryRecord, decode_json, encode_jsonl,
+    migrate_record, read_records, write_records,
+};
+use cyrup_candle::memory::primitives::types::{MemoryContent, MemoryTypeEnum};
+
+fn memory(text: &str) -> MemoryNode {
+    MemoryNode::new(MemoryTypeEnum::Episodic, MemoryContent::new(text))
+        .with_embedding(vec![0.25, -1.0, 3.5])
+        .with_importance(0.7)
+}
+
+#[test]
+fn test_record_round_trips_memory_with_embedding() {
+    let original = memory("met Ada at the conference");
+    let record = MemoryRecord::from_node(&original).expect("record");
+
+    assert_eq!(record.memory_type, "episodic");
+    assert_eq!(record.embedding, Some(vec![0.25, -1.0, 3.5]));
+    assert!(record.metadata["embedding"].is_null());
+
+    let restored = record.into_node().expect("memory");
+    assert_eq!(restored.id, original.id);
+    assert_eq!(restored.content.text, original.content.text);
+    assert_eq!(restored.content_hash, original.content_hash);
+    assert_eq!(restored.memory_type, MemoryTypeEnum::Episodic);
+    assert_eq!(restored.created_at, original.created_at);
+    assert_eq!(restored.embedding, original.embedding);
+    assert_eq!(restored.metadata.importance, 0.7);
+}
+
+#[test]
+fn test_jsonl_is_stamped_with_current_version() {
+    let records = vec![
+        MemoryRecord::from_node(&memory("first")).expect("record"),
+        MemoryRecord::from_node(&memory("second")).expect("record"),
+    ];
+    let bytes = encode_jsonl(&records).expect("encode");
+
+    let text = String::from_utf8(bytes.clone()).expect("utf-8");
+    let header: serde_json::Value =
+        serde_json::from_str(text.lines().next().expect("header")).expect("header json");
+    assert_eq!(header["format"], "cyrup-memory");
+    assert_eq!(header["version"], MEMORY_EXPORT_VERSION);
+    assert_eq!(header["count"], 2);
+    assert_eq!(text.lines().count(), 3);
+
+    let (version, raw) = decode_json(&bytes).expect("decode");
+    assert_eq!(version, MEMORY_EXPORT_VERSION);
+    assert_eq!(raw.len(), 2);
+}
+
+#[test]
+fn test_truncated_jsonl_is_rejected() {
+    let records = vec![MemoryRecord::from_node(&memory("only")).expect("record")];
+    let bytes = encode_jsonl(&records).expect("encode");
+    let header = bytes.split(|b| *b == b'\n').next().expect("header");
+
+    assert!(matches!(
+        decode_json(header),
+        Err(MigrationError::ValidationFailed(_))
+    ));
+}
+
+#[test]
+fn test_legacy_node_export_is_migrated() {
+    let original = memory("legacy");
+    let legacy = serde_json::to_vec(&vec![original.clone()]).expect("legacy export");
+
+    let (version, raw) = decode_json(&legacy).expect("decode");
+    assert_eq!(version, 0);
+
+    let migrated = migrate_record(version, raw[0].clone()).expect("migrate");
+    let record: MemoryRecord = serde_json::from_value(migrated).expect("current record");
+    assert_eq!(record.id, original.id);
+    assert_eq!(record.memory_type, "episodic");
+    assert_eq!(record.embedding, original.embedding);
+}
+
+#[test]
+fn test_newer_versions_are_rejected() {
+    let result = migrate_record(MEMORY_EXPORT_VERSION + 1, serde_json::json!({}));
+    assert!(matches!(result, Err(MigrationError::SchemaMismatch { .. })));
+}
+
+#[test]
+fn test_format_from_extension() {
+    assert_eq!(
+        MemoryExportFormat::from_path(Path::new("backup.JSONL")),
+        Some(MemoryExportFormat::Jsonl)
+    );
+    assert_eq!(
+        MemoryExportFormat::from_path(Path::new("backup.parquet")),
+        Some(MemoryExportFormat::Parquet)
+    );
+    assert_eq!(MemoryExportFormat::from_path(Path::new("backup.csv")), None);
+}
+
+async fn round_trip(format: MemoryExportFormat) {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join(format!("memories.{}", format.extension()));
+    let records = vec![
+        MemoryRecord::from_node(&memory("with embedding")).expect("record"),
+        MemoryRecord::from_node(&MemoryNode::new(
+            MemoryTypeEnum::Semantic,
+            MemoryContent::new("without embedding"),
+        ))
+        .expect("record"),
+    ];
+
+    write_records(&path, format, records.clone())
+        .await
+        .expect("write export");
+    let (version, read) = read_records(&path).await.expect("read export");
+
+    assert_eq!(version, MEMORY_EXPORT_VERSION);
+    assert_eq!(read.len(), records.len());
+    for (read, written) in read.iter().zip(&records) {
+        assert_eq!(read.id, written.id);
+        assert_eq!(read.content, written.content);
+        assert_eq!(read.embedding, written.embedding);
+        assert_eq!(read.metadata, written.metadata);
+        assert_eq!(
+            read.created_at.timestamp_millis(),
+            written.created_at.timestamp_millis()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_jsonl_file_round_trip() {
+    round_trip(MemoryExportFormat::Jsonl).await;
+}
+
+#[cfg(feature = "parquet")]
+#[tokio::test]
+async fn test_parquet_file_round_trip() {
+    round_trip(MemoryExportFormat::Parquet).await;
+}
+
+#[tokio::test]
+async fn test_missing_file_is_reported() {
+    let result = read_records(Path::new("/nonexistent/memories.jsonl")).await;
+    assert!(matches!(result, Err(MigrationError::FileNotFound(_))));
+}
@@ -0,0 +1,107 @@
+//! Tests for memory privacy tiers and encryption at rest
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use cyrup_candle::memory::MemoryNode;
+use cyrup_candle::memory::ops::retrieval::RagConfig;
+use cyrup_candle::memory::primitives::types::{MemoryContent, MemoryTypeEnum};
+use cyrup_candle::memory::privacy::{
+    MemoryCipher, MemoryPrivacy, PRIVACY_KEY, content_at_rest, content_from_rest, is_sealed,
+};
+
+fn memory(text: &str, privacy: Option<MemoryPrivacy>) -> MemoryNode {
+    let mut memory = MemoryNode::new(MemoryTypeEnum::Semantic, MemoryContent::new(text));
+    if let Some(privacy) = privacy {
+        privacy.apply(&mut memory.metadata);
+    }
+    memory
+}
+
+#[test]
+fn test_tier_defaults_to_standard_and_fails_closed() {
+    assert_eq!(
+        MemoryPrivacy::of(&memory("plain", None)),
+        MemoryPrivacy::Standard
+    );
+    assert_eq!(
+        MemoryPrivacy::of(&memory("secret", Some(MemoryPrivacy::Sensitive))),
+        MemoryPrivacy::Sensitive
+    );
+
+    let mut typo = memory("secret", None);
+    typo.metadata.custom = serde_json::json!({ PRIVACY_KEY: "sensitve" });
+    assert_eq!(MemoryPrivacy::of(&typo), MemoryPrivacy::Sensitive);
+}
+
+#[test]
+fn test_apply_keeps_other_custom_metadata() {
+    let mut memory = memory("note", None);
+    memory.metadata.custom = serde_json::json!({ "source": "notes.md" });
+    MemoryPrivacy::Ephemeral.apply(&mut memory.metadata);
+
+    assert_eq!(memory.metadata.custom["source"], "notes.md");
+    assert_eq!(memory.metadata.custom[PRIVACY_KEY], "ephemeral");
+}
+
+#[test]
+fn test_export_and_injection_rules() {
+    assert!(!MemoryPrivacy::Ephemeral.is_exported(true));
+    assert!(MemoryPrivacy::Standard.is_exported(false));
+    assert!(!MemoryPrivacy::Sensitive.is_exported(false));
+    assert!(MemoryPrivacy::Sensitive.is_exported(true));
+
+    assert!(MemoryPrivacy::Ephemeral.is_injected(false));
+    assert!(!MemoryPrivacy::Sensitive.is_injected(false));
+    assert!(MemoryPrivacy::Sensitive.is_injected(true));
+
+    assert!(!RagConfig::default().include_sensitive);
+    assert!(RagConfig::default().with_sensitive(true).include_sensitive);
+}
+
+#[test]
+fn test_seal_round_trips_and_rejects_other_keys() {
+    let cipher = MemoryCipher::new(&[7; 32]);
+    let sealed = cipher.seal("my passport number").expect("seal");
+
+    assert!(is_sealed(&sealed));
+    assert!(!sealed.contains("passport"));
+    assert_ne!(sealed, cipher.seal("my passport number").expect("seal"));
+    assert_eq!(cipher.open(&sealed).expect("open"), "my passport number");
+
+    let other = MemoryCipher::new(&[8; 32]);
+    assert!(other.open(&sealed).is_err());
+    assert!(cipher.open("enc:v1:AAAA").is_err());
+}
+
+#[test]
+fn test_only_sensitive_content_is_sealed_at_rest() {
+    let cipher = MemoryCipher::new(&[1; 32]);
+
+    let standard = memory("shopping list", None);
+    assert_eq!(
+        content_at_rest(&standard, Some(&cipher)).expect("content"),
+        "shopping list"
+    );
+
+    let sensitive = memory("bank pin 1234", Some(MemoryPrivacy::Sensitive));
+    let stored = content_at_rest(&sensitive, Some(&cipher)).expect("content");
+    assert!(is_sealed(&stored));
+    assert_eq!(
+        content_from_rest(&stored, Some(&cipher)).expect("content"),
+        "bank pin 1234"
+    );
+
+    assert!(content_at_rest(&sensitive, None).is_err());
+    assert!(content_from_rest(&stored, None).is_err());
+    assert_eq!(
+        content_from_rest("shopping list", None).expect("content"),
+        "shopping list"
+    );
+}
+
+#[test]
+fn test_base64_key_must_be_32_bytes() {
+    assert!(MemoryCipher::from_base64(&BASE64.encode([3u8; 32])).is_ok());
+    assert!(MemoryCipher::from_base64(&BASE64.encode([3u8; 16])).is_err());
+    assert!(MemoryCipher::from_base64("not base64!").is_err());
+}
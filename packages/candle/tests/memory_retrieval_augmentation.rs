@@ -0,0 +1,116 @@
+//! Tests for retrieval-augmented context injection
+
+use std::collections::HashMap;
+
+use cyrup_candle::memory::ops::retrieval::{
+    RagConfig, RetrievedMemory, apply_context_budgets, format_retrieved_context, rank_by_similarity,
+};
+
+fn memory(id: &str, content: &str, relevance: f32) -> RetrievedMemory {
+    RetrievedMemory {
+        id: id.to_string(),
+        content: content.to_string(),
+        source: Some("notes.md".to_string()),
+        memory_type: "Semantic".to_string(),
+        relevance,
+        context: None,
+    }
+}
+
+fn context_memory(id: &str, content: &str, context: &str) -> RetrievedMemory {
+    RetrievedMemory {
+        context: Some(context.to_string()),
+        ..memory(id, content, 0.9)
+    }
+}
+
+#[test]
+fn test_rank_filters_and_orders_by_similarity() {
+    let query = [1.0, 0.0, 0.0];
+    let candidates = vec![
+        ("orthogonal", vec![0.0, 1.0, 0.0]),
+        ("close", vec![0.9, 0.1, 0.0]),
+        ("exact", vec![2.0, 0.0, 0.0]),
+        ("wrong_dimension", vec![1.0, 0.0]),
+        ("decent", vec![0.6, 0.4, 0.0]),
+    ];
+
+    let config = RagConfig::default().with_min_relevance(0.5).with_top_k(2);
+    let ranked = rank_by_similarity(&query, candidates, &config);
+
+    let ids: Vec<&str> = ranked.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, ["exact", "close"]);
+    assert!((ranked[0].1 - 1.0).abs() < 1e-4);
+    assert!(ranked[1].1 < ranked[0].1);
+}
+
+#[test]
+fn test_format_includes_provenance_markers() {
+    let memories = [
+        memory("memory:a", "The deploy key lives in vault.", 0.91),
+        memory("memory:b", "Staging runs on port 8080.", 0.72),
+    ];
+
+    let context = format_retrieved_context(&memories, 512);
+    assert!(context.starts_with("## Retrieved Memories"));
+    assert!(context.contains("[1] memory:a | source: notes.md | Semantic | relevance 0.91"));
+    assert!(context.contains("[2] memory:b | source: notes.md | Semantic | relevance 0.72"));
+    assert!(context.contains("Staging runs on port 8080."));
+}
+
+#[test]
+fn test_format_respects_token_budget() {
+    let long = "word ".repeat(200);
+    let memories = [
+        memory("memory:long", &long, 0.95),
+        memory("memory:short", "Short fact.", 0.80),
+    ];
+
+    // The long memory does not fit; the short one is numbered first
+    let context = format_retrieved_context(&memories, 40);
+    assert!(!context.contains("memory:long"));
+    assert!(context.contains("[1] memory:short"));
+
+    assert_eq!(format_retrieved_context(&memories, 5), "");
+    assert_eq!(format_retrieved_context(&[], 512), "");
+}
+
+#[test]
+fn test_disabled_config() {
+    assert!(RagConfig::default().is_enabled());
+    assert!(!RagConfig::disabled().is_enabled());
+    assert!(!RagConfig::default().with_token_budget(0).is_enabled());
+    assert_eq!(
+        RagConfig::default().with_min_relevance(3.0).min_relevance,
+        1.0
+    );
+}
+
+#[test]
+fn test_context_budgets_limit_each_context() {
+    let chunk = "token ".repeat(40);
+    let memories = vec![
+        context_memory("memory:repo1", &chunk, "repo"),
+        memory("memory:note", &chunk, 0.85),
+        context_memory("memory:repo2", &chunk, "repo"),
+        context_memory("memory:docs1", &chunk, "docs"),
+        context_memory("memory:repo3", "Short.", "repo"),
+    ];
+
+    // One long chunk fits the repo budget; the short one still fits after it
+    let budgets = HashMap::from([("repo".to_string(), 64)]);
+    let kept = apply_context_budgets(memories.clone(), &budgets);
+    let ids: Vec<&str> = kept.iter().map(|memory| memory.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        [
+            "memory:repo1",
+            "memory:note",
+            "memory:docs1",
+            "memory:repo3"
+        ]
+    );
+
+    let kept = apply_context_budgets(memories.clone(), &HashMap::new());
+    assert_eq!(kept.len(), memories.len());
+}
@@ -0,0 +1,57 @@
+//! Tests for the OpenAI-compatible server request handling
+#![cfg(feature = "api")]
+
+use cyrup_candle::server::models::*;
+
+#[test]
+fn test_request_accepts_string_and_part_content() -> Result<(), Box<dyn std::error::Error>> {
+    let request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+        "model": "unsloth/Kimi-K2-Instruct-GGUF",
+        "messages": [
+            {"role": "system", "content": "Be brief."},
+            {"role": "user", "content": [
+                {"type": "text", "text": "Hello"},
+                {"type": "image_url", "image_url": {"url": "http://x"}}
+            ]}
+        ],
+        "stream": true
+    }))?;
+
+    assert!(request.stream);
+    assert_eq!(request.temperature, None);
+    assert_eq!(
+        request.messages[1]
+            .content
+            .as_ref()
+            .map(MessageContent::text),
+        Some("Hello".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_render_prompt_matches_chat_layout() -> Result<(), Box<dyn std::error::Error>> {
+    let messages: Vec<ChatMessage> = serde_json::from_value(serde_json::json!([
+        {"role": "user", "content": "Hi"},
+        {"role": "system", "content": "Be brief."},
+        {"role": "assistant", "content": "Hello!"},
+        {"role": "user", "content": "Name a color"}
+    ]))?;
+
+    assert_eq!(
+        render_prompt(&messages),
+        "Be brief.\n\nUser: Hi\n\nAssistant: Hello!\n\nUser: Name a color"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_errors_use_openai_shape() -> Result<(), Box<dyn std::error::Error>> {
+    let error = ApiError::model_not_found("missing");
+    assert_eq!(error.status.as_u16(), 404);
+
+    let body = serde_json::to_value(&error.body)?;
+    assert_eq!(body["error"]["type"], "invalid_request_error");
+    assert_eq!(body["error"]["code"], "model_not_found");
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Tests for multi-agent orchestration combinators
+
+use std::sync::{Arc, Mutex};
+
+use cyrup_candle::StreamExt;
+use cyrup_candle::domain::chat::message::CandleMessageChunk;
+use cyrup_candle::workflow::agents::{
+    AgentChunk, Aggregation, PARALLEL_AGENT, ROUTER_AGENT, WorkflowAgent, agent_fn, debate,
+    parallel, router,
+};
+
+/// Agent that replies with a fixed text, streamed as two chunks
+fn scripted(name: &'static str, reply: &'static str) -> Arc<dyn WorkflowAgent> {
+    agent_fn(name, move |_prompt| {
+        let (head, tail) = reply.split_at(reply.len() / 2);
+        Box::pin(cyrup_candle::from_iter(vec![
+            CandleMessageChunk::Text(head.to_string()),
+            CandleMessageChunk::Text(tail.to_string()),
+        ]))
+    })
+}
+
+/// Agent that records every prompt it receives
+fn recording(name: &'static str, prompts: Arc<Mutex<Vec<String>>>) -> Arc<dyn WorkflowAgent> {
+    agent_fn(name, move |prompt| {
+        if let Ok(mut prompts) = prompts.lock() {
+            prompts.push(prompt);
+        }
+        Box::pin(cyrup_candle::from_iter(vec![CandleMessageChunk::Text(
+            format!("{name} argues"),
+        )]))
+    })
+}
+
+async fn collect(
+    stream: std::pin::Pin<Box<dyn cyrup_candle::Stream<Item = AgentChunk> + Send>>,
+) -> Vec<AgentChunk> {
+    stream.collect().await
+}
+
+fn text_of(chunks: &[AgentChunk], agent: &str) -> String {
+    chunks
+        .iter()
+        .filter(|chunk| chunk.agent == agent)
+        .filter_map(|chunk| match &chunk.chunk {
+            CandleMessageChunk::Text(text) | CandleMessageChunk::Complete { text, .. } => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_parallel_tags_chunks_and_aggregates() {
+    let chunks = collect(
+        parallel([
+            scripted("alpha", "first answer"),
+            scripted("beta", "second answer"),
+        ])
+        .execute("question".to_string()),
+    )
+    .await;
+
+    assert_eq!(text_of(&chunks, "alpha"), "first answer");
+    assert_eq!(text_of(&chunks, "beta"), "second answer");
+
+    // The aggregate comes last, in agent order
+    let last = chunks.last().expect("aggregate chunk");
+    assert_eq!(last.agent, PARALLEL_AGENT);
+    assert_eq!(
+        text_of(&chunks, PARALLEL_AGENT),
+        "### alpha\nfirst answer\n\n### beta\nsecond answer\n\n"
+    );
+}
+
+#[tokio::test]
+async fn test_parallel_custom_and_synthesized_aggregation() {
+    let agents = || [scripted("alpha", "yes"), scripted("beta", "no")];
+
+    let chunks = collect(
+        parallel(agents())
+            .aggregate_with(|responses| format!("{} votes", responses.len()))
+            .execute("vote".to_string()),
+    )
+    .await;
+    assert_eq!(text_of(&chunks, PARALLEL_AGENT), "2 votes");
+
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let chunks = collect(
+        parallel(agents())
+            .aggregate(Aggregation::Synthesize(recording(
+                "editor",
+                prompts.clone(),
+            )))
+            .execute("vote".to_string()),
+    )
+    .await;
+    assert_eq!(text_of(&chunks, "editor"), "editor argues");
+    assert!(text_of(&chunks, PARALLEL_AGENT).is_empty());
+    let prompts = prompts.lock().expect("prompts");
+    assert!(prompts[0].contains("### alpha\nyes"), "{}", prompts[0]);
+    assert!(prompts[0].contains("### beta\nno"), "{}", prompts[0]);
+}
+
+#[tokio::test]
+async fn test_router_dispatches_on_classifier_reply() {
+    let routes = || {
+        [
+            ("billing", scripted("billing_agent", "refund issued")),
+            ("support", scripted("support_agent", "try restarting")),
+        ]
+    };
+
+    let chunks = collect(
+        router(scripted("classifier", "Route: Support."), routes())
+            .execute("my app crashes".to_string()),
+    )
+    .await;
+    assert_eq!(text_of(&chunks, "classifier"), "Route: Support.");
+    assert_eq!(text_of(&chunks, "support_agent"), "try restarting");
+    assert!(text_of(&chunks, "billing_agent").is_empty());
+
+    // No route matches and there is no fallback
+    let chunks =
+        collect(router(scripted("classifier", "unsure"), routes()).execute("hello".to_string()))
+            .await;
+    let last = chunks.last().expect("error chunk");
+    assert_eq!(last.agent, ROUTER_AGENT);
+    assert!(matches!(last.chunk, CandleMessageChunk::Error(_)));
+
+    let chunks = collect(
+        router(scripted("classifier", "unsure"), routes())
+            .fallback(scripted("generalist", "happy to help"))
+            .execute("hello".to_string()),
+    )
+    .await;
+    assert_eq!(text_of(&chunks, "generalist"), "happy to help");
+}
+
+#[tokio::test]
+async fn test_debate_rounds_share_the_transcript() {
+    let prompts = Arc::new(Mutex::new(Vec::new()));
+    let judge_prompts = Arc::new(Mutex::new(Vec::new()));
+
+    let chunks = collect(
+        debate(2)
+            .participant(recording("pro", prompts.clone()))
+            .participant(recording("con", prompts.clone()))
+            .judge(recording("judge", judge_prompts.clone()))
+            .execute("tabs or spaces".to_string()),
+    )
+    .await;
+
+    let speakers: Vec<&str> = chunks.iter().map(|chunk| chunk.agent.as_str()).collect();
+    assert_eq!(speakers, ["pro", "con", "pro", "con", "judge"]);
+
+    let prompts = prompts.lock().expect("prompts");
+    assert_eq!(prompts.len(), 4);
+    assert!(prompts[0].contains("opening argument"));
+    assert!(prompts[1].contains("### pro\npro argues"));
+    assert!(prompts[3].contains("Round 2 of 2"));
+    assert!(prompts[3].contains("### con\ncon argues"));
+
+    let judge_prompts = judge_prompts.lock().expect("judge prompts");
+    assert_eq!(judge_prompts[0].matches("argues").count(), 4);
+}
@@ -0,0 +1,138 @@
+//! Tests for scheduled workflow runs
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use cyrup_candle::domain::chat::message::CandleMessageChunk;
+use cyrup_candle::workflow::agents::{WorkflowAgent, agent_fn};
+use cyrup_candle::workflow::scheduler::{RunStatus, ScheduleSpec, WorkflowSchedule, run_agent};
+
+/// Agent that streams `chunks` in order
+fn scripted(chunks: Vec<CandleMessageChunk>) -> Arc<dyn WorkflowAgent> {
+    agent_fn("scripted", move |_prompt| {
+        Box::pin(cyrup_candle::from_iter(chunks.clone()))
+    })
+}
+
+#[test]
+fn test_interval_parsing_and_display() {
+    assert_eq!(
+        ScheduleSpec::parse_interval("15m").expect("interval"),
+        ScheduleSpec::Interval { every_secs: 900 }
+    );
+    assert_eq!(
+        ScheduleSpec::parse_interval("90").expect("interval"),
+        ScheduleSpec::Interval { every_secs: 90 }
+    );
+    assert_eq!(
+        ScheduleSpec::parse_interval("2d")
+            .expect("interval")
+            .to_string(),
+        "every 2d"
+    );
+    assert_eq!(
+        ScheduleSpec::parse_interval("90s")
+            .expect("interval")
+            .to_string(),
+        "every 90s"
+    );
+
+    assert!(ScheduleSpec::parse_interval("0m").is_err());
+    assert!(ScheduleSpec::parse_interval("5w").is_err());
+    assert!(ScheduleSpec::parse_interval("m").is_err());
+}
+
+#[test]
+fn test_cron_accepts_five_fields_and_rejects_garbage() {
+    let spec = ScheduleSpec::cron("30 9 * * MON-FRI").expect("cron");
+    assert_eq!(
+        spec,
+        ScheduleSpec::Cron {
+            expression: "0 30 9 * * MON-FRI".to_string()
+        }
+    );
+    assert!(ScheduleSpec::cron("every tuesday").is_err());
+
+    assert_eq!(
+        "15m".parse::<ScheduleSpec>().expect("spec"),
+        ScheduleSpec::Interval { every_secs: 900 }
+    );
+    assert!(matches!(
+        "*/5 * * * *".parse::<ScheduleSpec>().expect("spec"),
+        ScheduleSpec::Cron { .. }
+    ));
+}
+
+#[test]
+fn test_next_run_times() {
+    let every = ScheduleSpec::parse_interval("1h").expect("interval");
+    assert_eq!(every.next_after(1_000), Some(1_000 + 3_600_000));
+
+    let minutely = ScheduleSpec::cron("* * * * *").expect("cron");
+    let next = minutely.next_after(1_700_000_000_000).expect("next run");
+    assert!(next > 1_700_000_000_000);
+    assert!(next <= 1_700_000_060_000);
+    assert_eq!(next % 60_000, 0);
+}
+
+#[test]
+fn test_schedule_is_due_only_when_enabled_and_past_next_run() {
+    let mut schedule = WorkflowSchedule::new(
+        "digest",
+        ScheduleSpec::parse_interval("1h").expect("interval"),
+        "Summarize my inbox",
+    )
+    .with_model("qwen-3")
+    .with_max_tokens(256);
+    let next = schedule.next_run_ms.expect("next run");
+
+    assert!(!schedule.is_due(next - 1));
+    assert!(schedule.is_due(next));
+
+    schedule.enabled = false;
+    assert!(!schedule.is_due(next));
+}
+
+#[tokio::test]
+async fn test_run_agent_records_output_and_tool_calls() {
+    let schedule = WorkflowSchedule::new(
+        "digest",
+        ScheduleSpec::parse_interval("1h").expect("interval"),
+        "Summarize my inbox",
+    );
+    let agent = scripted(vec![
+        CandleMessageChunk::ToolCallComplete {
+            id: "call-1".to_string(),
+            name: "fetch".to_string(),
+            input: r#"{"url":"https://example.com"}"#.to_string(),
+        },
+        CandleMessageChunk::Text("All ".to_string()),
+        CandleMessageChunk::Text("quiet".to_string()),
+    ]);
+
+    let run = run_agent(agent.as_ref(), &schedule, Duration::from_secs(5)).await;
+    assert_eq!(run.status, RunStatus::Success);
+    assert_eq!(run.output, "All quiet");
+    assert_eq!(run.schedule_id, schedule.schedule_id);
+    assert_eq!(run.tool_calls.len(), 1);
+    assert_eq!(run.tool_calls[0].name, "fetch");
+    assert!(run.error.is_none());
+}
+
+#[tokio::test]
+async fn test_run_agent_records_failures() {
+    let schedule = WorkflowSchedule::new(
+        "digest",
+        ScheduleSpec::parse_interval("1h").expect("interval"),
+        "Summarize my inbox",
+    );
+    let agent = scripted(vec![
+        CandleMessageChunk::Text("partial".to_string()),
+        CandleMessageChunk::Error("model crashed".to_string()),
+    ]);
+
+    let run = run_agent(agent.as_ref(), &schedule, Duration::from_secs(5)).await;
+    assert_eq!(run.status, RunStatus::Failure);
+    assert_eq!(run.output, "partial");
+    assert_eq!(run.error.as_deref(), Some("model crashed"));
+}
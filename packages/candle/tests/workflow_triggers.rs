@@ -0,0 +1,100 @@
+//! Tests for webhook and notification triggers
+
+use serde_json::json;
+
+use cyrup_candle::workflow::scheduler::{ScheduleSpec, WorkflowSchedule};
+use cyrup_candle::workflow::triggers::{TriggerEvent, render_prompt};
+
+fn webhook(path: &str, secret: Option<&str>) -> TriggerEvent {
+    TriggerEvent::Webhook {
+        path: path.to_string(),
+        payload: json!({
+            "ref": "refs/heads/main",
+            "commits": [{"id": "abc123", "message": "Fix parser"}],
+            "forced": false
+        }),
+        secret: secret.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_render_prompt_fills_payload_fields() {
+    let event = webhook("/hooks/push", None);
+
+    assert_eq!(
+        render_prompt(
+            "Review {{ payload.commits.0.id }} ({{payload.commits.0.message}}) on {{payload.ref}} via {{path}}",
+            &event
+        ),
+        "Review abc123 (Fix parser) on refs/heads/main via /hooks/push"
+    );
+    assert_eq!(
+        render_prompt(
+            "forced={{payload.forced}} missing=[{{payload.nope}}]",
+            &event
+        ),
+        "forced=false missing=[]"
+    );
+    assert_eq!(render_prompt("left {{ open", &event), "left {{ open");
+}
+
+#[test]
+fn test_notification_context_exposes_method_and_params() {
+    let event = TriggerEvent::Notification {
+        server: vec!["fs-server".to_string()],
+        method: "notifications/resources/updated".to_string(),
+        params: json!({"uri": "file:///notes.md"}),
+    };
+
+    assert_eq!(
+        render_prompt("{{method}}: {{payload.uri}} / {{payload}}", &event),
+        r#"notifications/resources/updated: file:///notes.md / {"uri":"file:///notes.md"}"#
+    );
+}
+
+#[test]
+fn test_webhook_matching_checks_path_and_secret() {
+    let open = ScheduleSpec::webhook("/hooks/push/", None).expect("spec");
+    assert_eq!(
+        open,
+        ScheduleSpec::Webhook {
+            path: "/hooks/push".to_string(),
+            secret: None
+        }
+    );
+    assert!(webhook("/hooks/push", None).matches(&open));
+    assert!(webhook("/hooks/push/", Some("anything")).matches(&open));
+    assert!(!webhook("/hooks/pull", None).matches(&open));
+
+    let guarded = ScheduleSpec::webhook("/hooks/push", Some("s3cret".to_string())).expect("spec");
+    assert!(webhook("/hooks/push", Some("s3cret")).matches(&guarded));
+    assert!(!webhook("/hooks/push", Some("s3cre7")).matches(&guarded));
+    assert!(!webhook("/hooks/push", None).matches(&guarded));
+
+    assert!(ScheduleSpec::webhook("hooks/push", None).is_err());
+}
+
+#[test]
+fn test_notification_matching_and_event_specs_are_never_due() {
+    let server = vec!["fs-server".to_string(), "--watch".to_string()];
+    let spec = ScheduleSpec::notification(server.clone(), "notifications/resources/updated")
+        .expect("spec");
+    assert_eq!(
+        spec.to_string(),
+        "on notifications/resources/updated from fs-server --watch"
+    );
+
+    let event = TriggerEvent::Notification {
+        server: server.clone(),
+        method: "notifications/resources/updated".to_string(),
+        params: json!(null),
+    };
+    assert!(event.matches(&spec));
+    assert!(!event.matches(&ScheduleSpec::webhook("/hooks/push", None).expect("spec")));
+    assert!(ScheduleSpec::notification(Vec::new(), "notifications/x").is_err());
+
+    let schedule = WorkflowSchedule::new("notes", spec, "Summarize {{payload.uri}}");
+    assert!(schedule.spec.is_event());
+    assert_eq!(schedule.next_run_ms, None);
+    assert!(!schedule.is_due(i64::MAX));
+}
@@ -15,11 +15,15 @@ use std::collections::HashMap;
 use std::process::{Command, Stdio};
 use std::time::{Duration, Instant};
 
+use tokio::sync::mpsc::Sender;
+
 use crate::AsyncTaskBuilder;
 use crate::backends::AsyncTask;
+use crate::backends::{artifacts, languages, limits, streaming};
 use crate::backends::{
-    BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionRequest,
-    ExecutionResult, HealthStatus, ResourceUsage,
+    BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionEvent,
+    ExecutionRequest, ExecutionResult, ExecutionStream, HealthStatus, Language, LimitExceeded,
+    NetworkPolicy, ResourceUsage, SessionLaunch,
 };
 
 /// Apple containerization backend
@@ -195,12 +199,14 @@ impl AppleBackend {
     ///
     /// # Arguments
     /// * `request` - Execution request with code and configuration
+    /// * `events` - When set, output is streamed here instead of buffered
     ///
     /// # Returns
     /// AsyncTask that resolves to execution result
     fn execute_in_container(
         image: String,
         request: ExecutionRequest,
+        events: Option<Sender<ExecutionEvent>>,
     ) -> AsyncTask<BackendResult<ExecutionResult>> {
         AsyncTaskBuilder::new(async move {
             let start_time = Instant::now();
@@ -233,11 +239,32 @@ impl AppleBackend {
                 cmd.args(["-e", &format!("{key}={value}")]);
             }
 
+            // Mount a host directory as the working directory so requested
+            // artifacts survive the container's removal
+            let artifact_dir = if request.artifacts.is_empty() {
+                None
+            } else {
+                let dir = tempfile::tempdir().map_err(|e| BackendError::FileSystemFailed {
+                    details: format!("Failed to create artifact directory: {e}"),
+                })?;
+                let target = request.working_dir.as_deref().unwrap_or("/workspace");
+                cmd.args(["--volume", &format!("{}:{target}", dir.path().display())]);
+                cmd.args(["-w", target]);
+                Some(dir)
+            };
+
             // Set working directory if specified
-            if let Some(workdir) = &request.working_dir {
+            if let Some(workdir) = &request.working_dir
+                && artifact_dir.is_none()
+            {
                 cmd.args(["-w", workdir]);
             }
 
+            // Keep stdin attached when there is input to feed
+            if request.stdin_data().is_some() {
+                cmd.arg("--interactive");
+            }
+
             // Add timeout handling
             cmd.args(["--timeout", &format!("{}s", request.timeout.as_secs())]);
 
@@ -255,45 +282,40 @@ impl AppleBackend {
                 details: format!("Failed to spawn container: {e}"),
             })?;
 
-            // Write input if provided
-            if let Some(input) = &request.input
-                && let Some(stdin) = child.stdin.take()
-            {
-                use std::io::Write;
-                let mut stdin = stdin;
-                stdin
-                    .write_all(input.as_bytes())
-                    .map_err(|e| BackendError::ProcessFailed {
-                        details: format!("Failed to write to container stdin: {e}"),
-                    })?;
-            }
+            // Feed stdin in the background so large inputs cannot deadlock against output
+            let stdin_writer = streaming::spawn_stdin_writer(&mut child, request.stdin_data());
 
-            // Wait for completion with timeout
+            // Wait for completion, enforcing the wall-clock deadline and output caps
             let timeout_duration = request.timeout;
+            let wait_limits = request.limits.clone();
+            let output = tokio::task::spawn_blocking(move || match &events {
+                Some(events) => {
+                    limits::stream_with_limits(child, timeout_duration, &wait_limits, events)
+                }
+                None => limits::wait_with_limits(child, timeout_duration, &wait_limits),
+            })
+            .await
+            .map_err(|_| BackendError::ProcessFailed {
+                details: "Container process task failed".to_string(),
+            })?
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Container execution failed: {e}"),
+            })?;
 
-            // Use a different approach - spawn a task that can kill the process
-            let child_handle = tokio::spawn(async move { child.wait_with_output() });
+            if let Some(writer) = stdin_writer
+                && let Ok(Err(e)) = writer.join()
+            {
+                log::warn!("Failed to write to container stdin: {e}");
+            }
 
-            let output = match tokio::time::timeout(timeout_duration, child_handle).await {
-                Ok(Ok(Ok(output))) => output,
-                Ok(Ok(Err(e))) => {
-                    return Err(BackendError::ProcessFailed {
-                        details: format!("Container execution failed: {e}"),
-                    });
-                }
-                Ok(Err(_)) => {
-                    return Err(BackendError::ProcessFailed {
-                        details: "Container process task failed".to_string(),
-                    });
-                }
-                Err(_) => {
-                    // Timeout occurred - the process is still running but we can't kill it
-                    // from here since it's been moved into the task
-                    return Err(BackendError::ExecutionTimeout {
-                        seconds: timeout_duration.as_secs(),
-                    });
-                }
-            };
+            // The CLI was killed at the deadline; make sure the container goes with it
+            if output.timed_out {
+                let _ = Command::new("container")
+                    .args(["kill", &container_name])
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+            }
 
             let duration = start_time.elapsed();
 
@@ -302,8 +324,27 @@ impl AppleBackend {
                 .await
                 .unwrap_or_default();
 
-            Ok(ExecutionResult {
-                exit_code: output.status.code().unwrap_or(-1),
+            let exit_code = output
+                .status
+                .and_then(|status| status.code())
+                .unwrap_or(limits::TIMEOUT_EXIT_CODE);
+            let signal = output.status.and_then(|status| {
+                use std::os::unix::process::ExitStatusExt;
+                status.signal()
+            });
+
+            let limit_exceeded = if output.timed_out {
+                Some(LimitExceeded::WallClock)
+            } else if output.stdout_truncated {
+                Some(LimitExceeded::Stdout)
+            } else if output.stderr_truncated {
+                Some(LimitExceeded::Stderr)
+            } else {
+                limits::classify_termination(exit_code, signal, &resource_usage, &request.limits)
+            };
+
+            let mut result = ExecutionResult {
+                exit_code,
                 stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
                 stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
                 duration,
@@ -315,11 +356,44 @@ impl AppleBackend {
                     meta.insert("container_name".to_string(), container_name);
                     meta
                 },
-            })
+                limit_exceeded,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal,
+                stdout_truncated: output.stdout_truncated,
+                stderr_truncated: output.stderr_truncated,
+                seccomp_violation: None,
+            };
+
+            if let Some(dir) = &artifact_dir {
+                artifacts::collect_dir(dir.path(), &request.artifacts, &request.limits)
+                    .apply(&mut result);
+            }
+            languages::surface_compile_error(&mut result);
+
+            Ok(result)
         })
         .spawn()
     }
 
+    /// Reject network policies the container CLI cannot enforce
+    ///
+    /// # Arguments
+    /// * `network` - Requested egress policy
+    ///
+    /// # Returns
+    /// Ok(()) for full egress, Err otherwise
+    fn check_network_policy(network: &NetworkPolicy) -> BackendResult<()> {
+        match network {
+            NetworkPolicy::Full => Ok(()),
+            _ => Err(BackendError::NotAvailable {
+                backend: "Apple",
+                reason: "restricted network policies are only enforced on Linux backends"
+                    .to_string(),
+            }),
+        }
+    }
+
     /// Prepare execution command for specific language
     ///
     /// # Arguments
@@ -338,33 +412,42 @@ impl AppleBackend {
             "javascript" | "js" | "node" => {
                 Ok(vec!["node".to_string(), "-e".to_string(), code.to_string()])
             }
-            "rust" => {
-                // For Rust, we need to create a temporary file and compile
-                Ok(vec![
+            "bash" | "sh" => Ok(vec!["sh".to_string(), "-c".to_string(), code.to_string()]),
+            // Compiled and toolchain-launched languages build under /tmp
+            other => match Language::parse(other) {
+                Some(language) => Ok(vec![
                     "sh".to_string(),
                     "-c".to_string(),
-                    format!(
-                        "echo '{}' > /tmp/main.rs && cd /tmp && rustc main.rs && ./main",
-                        code.replace('\'', "'\"'\"'")
-                    ),
-                ])
-            }
-            "bash" | "sh" => Ok(vec!["sh".to_string(), "-c".to_string(), code.to_string()]),
-            "go" => Ok(vec![
-                "sh".to_string(),
-                "-c".to_string(),
-                format!(
-                    "echo '{}' > /tmp/main.go && cd /tmp && go run main.go",
-                    code.replace('\'', "'\"'\"'")
-                ),
-            ]),
-            _ => Err(BackendError::UnsupportedLanguage {
-                backend: "Apple",
-                language: language.to_string(),
-            }),
+                    language.script(code),
+                ]),
+                None => Err(BackendError::UnsupportedLanguage {
+                    backend: "Apple",
+                    language: language.to_string(),
+                }),
+            },
         }
     }
 
+    /// Image to run `language` in
+    ///
+    /// A `image.<language>` backend option wins; otherwise, when the
+    /// `per_language_images` option is `true`, the language's default image is
+    /// used instead of the instance image.
+    fn image_for(&self, language: &str) -> String {
+        let Some(language) = Language::parse(language) else {
+            return self.image.clone();
+        };
+
+        let options = &self.config.backend_specific;
+        if let Some(image) = options.get(&format!("image.{}", language.name())) {
+            return image.clone();
+        }
+        if options.get("per_language_images").is_some_and(|v| v == "true") {
+            return language.default_image().to_string();
+        }
+        self.image.clone()
+    }
+
     /// Parse resource usage from container stats
     ///
     /// # Arguments
@@ -409,9 +492,14 @@ impl AppleBackend {
 
 impl ExecutionBackend for AppleBackend {
     fn execute_code(&self, request: ExecutionRequest) -> AsyncTask<ExecutionResult> {
-        let image = self.image.clone();
+        let image = self.image_for(&request.language);
         let backend_name = self.backend_type();
 
+        if let Err(e) = Self::check_network_policy(request.network_policy(&self.config)) {
+            return AsyncTaskBuilder::new(async move { ExecutionResult::failure(-1, e.to_string()) })
+                .spawn();
+        }
+
         AsyncTaskBuilder::new(async move {
             // Ensure image is available
             match Self::ensure_image_available(image.clone()).await {
@@ -428,7 +516,7 @@ impl ExecutionBackend for AppleBackend {
             }
 
             // Execute in container
-            match Self::execute_in_container(image, request).await {
+            match Self::execute_in_container(image, request, None).await {
                 Ok(Ok(result)) => result,
                 Ok(Err(e)) => {
                     ExecutionResult::failure(-1, format!("{backend_name} execution failed: {e}"))
@@ -442,6 +530,74 @@ impl ExecutionBackend for AppleBackend {
         .spawn()
     }
 
+    fn execute_code_streaming(&self, request: ExecutionRequest) -> ExecutionStream {
+        let image = self.image_for(&request.language);
+        let backend_name = self.backend_type();
+
+        if let Err(e) = Self::check_network_policy(request.network_policy(&self.config)) {
+            return streaming::replay_result(
+                AsyncTaskBuilder::new(async move { ExecutionResult::failure(-1, e.to_string()) })
+                    .spawn(),
+            );
+        }
+
+        let (tx, rx) = streaming::channel();
+
+        tokio::spawn(async move {
+            let result = match Self::ensure_image_available(image.clone()).await {
+                Ok(Ok(())) => {
+                    match Self::execute_in_container(image, request, Some(tx.clone())).await {
+                        Ok(Ok(result)) => result,
+                        Ok(Err(e)) => ExecutionResult::failure(
+                            -1,
+                            format!("{backend_name} execution failed: {e}"),
+                        ),
+                        Err(e) => ExecutionResult::failure(
+                            -1,
+                            format!("{backend_name} execution task failed: {e}"),
+                        ),
+                    }
+                }
+                Ok(Err(e)) => ExecutionResult::failure(-1, format!("Failed to prepare image: {e}")),
+                Err(e) => ExecutionResult::failure(-1, format!("Failed to prepare image task: {e}")),
+            };
+
+            // Output was already streamed; only failure messages remain buffered
+            streaming::send_result(&tx, &result).await;
+        });
+
+        rx
+    }
+
+    fn session_command(
+        &self,
+        argv: &[String],
+        limits: &crate::backends::ResourceLimits,
+    ) -> BackendResult<SessionLaunch> {
+        Self::check_network_policy(&self.config.default_network)?;
+
+        let mut cmd = Command::new("container");
+        cmd.args([
+            "run",
+            "--rm",
+            "--interactive",
+            "--name",
+            &format!("cylo-session-{}", uuid::Uuid::new_v4().simple()),
+        ]);
+
+        if let Some(memory) = limits.max_memory {
+            cmd.args(["--memory", &format!("{memory}b")]);
+        }
+
+        cmd.arg(&self.image);
+        cmd.args(argv);
+
+        Ok(SessionLaunch {
+            command: cmd,
+            workspace: None,
+        })
+    }
+
     fn health_check(&self) -> AsyncTask<HealthStatus> {
         let image = self.image.clone();
 
@@ -463,7 +619,7 @@ impl ExecutionBackend for AppleBackend {
             let test_request = ExecutionRequest::new("echo 'health check'", "bash")
                 .with_timeout(Duration::from_secs(10));
 
-            match Self::execute_in_container(image.clone(), test_request).await {
+            match Self::execute_in_container(image.clone(), test_request, None).await {
                 Ok(Ok(result)) if result.is_success() => {
                     HealthStatus::healthy("Apple containerization backend operational")
                         .with_metric("cli_available", "true")
@@ -540,6 +696,13 @@ impl ExecutionBackend for AppleBackend {
             "bash",
             "sh",
             "go",
+            "typescript",
+            "ts",
+            "ruby",
+            "java",
+            "c",
+            "cpp",
+            "c++",
         ]
     }
 }
@@ -580,6 +743,10 @@ mod tests {
         let bash_cmd = AppleBackend::prepare_execution_command("bash", "echo hello").unwrap();
         assert_eq!(bash_cmd, vec!["sh", "-c", "echo hello"]);
 
+        let ruby_cmd = AppleBackend::prepare_execution_command("ruby", "puts 'hi'").unwrap();
+        assert_eq!(ruby_cmd[..2], ["sh", "-c"]);
+        assert!(ruby_cmd[2].contains("exec ruby"));
+
         let unsupported = AppleBackend::prepare_execution_command("cobol", "some code");
         assert!(unsupported.is_err());
     }
@@ -0,0 +1,273 @@
+// ============================================================================
+// File: packages/cylo/src/backends/artifacts.rs
+// ----------------------------------------------------------------------------
+// Artifact collection from sandboxes after execution.
+//
+// Provides:
+// - Glob matching of sandbox-relative paths against ExecutionRequest::artifacts
+// - Per-file and total size caps from ResourceLimits
+// - Small files returned inline (base64), large ones copied to host temp files
+// - Local directory walking that never follows symlinks out of the sandbox
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{ExecutionResult, ResourceLimits};
+
+/// Files up to this size are returned inline as base64
+pub const INLINE_ARTIFACT_BYTES: u64 = 64 * 1024;
+
+/// Metadata key listing artifacts skipped because of size caps
+pub const SKIPPED_METADATA_KEY: &str = "artifacts_skipped";
+
+/// File collected from the sandbox
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Path relative to the sandbox working directory
+    pub path: String,
+
+    /// Size in bytes
+    pub size: u64,
+
+    /// File content
+    pub content: ArtifactContent,
+}
+
+/// How an artifact's content is delivered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArtifactContent {
+    /// Base64-encoded content
+    Base64 { data: String },
+
+    /// Copy on the host; the caller owns and removes it
+    File { path: PathBuf },
+}
+
+impl Artifact {
+    /// Read the artifact's bytes regardless of delivery
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        match &self.content {
+            ArtifactContent::Base64 { data } => BASE64
+                .decode(data)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            ArtifactContent::File { path } => std::fs::read(path),
+        }
+    }
+}
+
+/// Outcome of an artifact collection pass
+#[derive(Debug, Default)]
+pub struct Collected {
+    /// Artifacts within the caps
+    pub artifacts: Vec<Artifact>,
+
+    /// Matching paths dropped because of the size caps
+    pub skipped: Vec<String>,
+}
+
+impl Collected {
+    /// Attach the artifacts to `result`, listing skipped paths in its metadata
+    pub fn apply(self, result: &mut ExecutionResult) {
+        if !self.skipped.is_empty() {
+            result
+                .metadata
+                .insert(SKIPPED_METADATA_KEY.to_string(), self.skipped.join(","));
+        }
+        result.artifacts = self.artifacts;
+    }
+}
+
+/// Whether `path` matches any of `patterns`
+///
+/// `*` does not cross directory separators; use `**` to match across them.
+pub fn matches(patterns: &[String], path: &str) -> bool {
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    patterns.iter().any(|pattern| {
+        Pattern::new(pattern)
+            .map(|pattern| pattern.matches_with(path, options))
+            .unwrap_or(false)
+    })
+}
+
+/// Collect artifacts from a candidate listing
+///
+/// # Arguments
+/// * `candidates` - Sandbox-relative paths with their sizes
+/// * `patterns` - Globs from the execution request
+/// * `limits` - Per-file and total size caps
+/// * `fetch` - Reads a candidate's bytes
+pub fn collect<F>(
+    mut candidates: Vec<(String, u64)>,
+    patterns: &[String],
+    limits: &ResourceLimits,
+    mut fetch: F,
+) -> Collected
+where
+    F: FnMut(&str) -> std::io::Result<Vec<u8>>,
+{
+    let mut collected = Collected::default();
+    if patterns.is_empty() {
+        return collected;
+    }
+
+    candidates.sort();
+    let mut total = 0u64;
+
+    for (path, size) in candidates {
+        if !matches(patterns, &path) {
+            continue;
+        }
+
+        let over_file = limits.max_artifact_bytes.is_some_and(|max| size > max);
+        let over_total = limits
+            .max_artifacts_total_bytes
+            .is_some_and(|max| total.saturating_add(size) > max);
+        if over_file || over_total {
+            collected.skipped.push(path);
+            continue;
+        }
+
+        match fetch(&path).and_then(|bytes| store(&path, bytes)) {
+            Ok(artifact) => {
+                total += artifact.size;
+                collected.artifacts.push(artifact);
+            }
+            Err(e) => {
+                log::warn!("Failed to collect artifact {}: {}", path, e);
+                collected.skipped.push(path);
+            }
+        }
+    }
+
+    collected
+}
+
+/// Collect artifacts from a host directory mounted into the sandbox
+pub fn collect_dir(root: &Path, patterns: &[String], limits: &ResourceLimits) -> Collected {
+    if patterns.is_empty() {
+        return Collected::default();
+    }
+
+    let mut candidates = Vec::new();
+    walk(root, root, &mut candidates);
+    collect(candidates, patterns, limits, |path| std::fs::read(root.join(path)))
+}
+
+/// List regular files below `dir`, skipping symlinks so nothing outside the
+/// sandbox can be reached
+fn walk(root: &Path, dir: &Path, out: &mut Vec<(String, u64)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            walk(root, &path, out);
+        } else if metadata.is_file()
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            out.push((relative.to_string_lossy().into_owned(), metadata.len()));
+        }
+    }
+}
+
+/// Package bytes as an inline or temp-file artifact
+fn store(path: &str, bytes: Vec<u8>) -> std::io::Result<Artifact> {
+    let size = bytes.len() as u64;
+
+    let content = if size <= INLINE_ARTIFACT_BYTES {
+        ArtifactContent::Base64 {
+            data: BASE64.encode(&bytes),
+        }
+    } else {
+        let dir = std::env::temp_dir()
+            .join("cylo-artifacts")
+            .join(uuid::Uuid::new_v4().simple().to_string());
+        std::fs::create_dir_all(&dir)?;
+
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_else(|| "artifact".into());
+        let host_path = dir.join(file_name);
+        std::fs::write(&host_path, &bytes)?;
+
+        ArtifactContent::File { path: host_path }
+    };
+
+    Ok(Artifact {
+        path: path.to_string(),
+        size,
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_does_not_cross_directories() {
+        let patterns = vec!["*.csv".to_string(), "out/**/*.png".to_string()];
+        assert!(matches(&patterns, "data.csv"));
+        assert!(!matches(&patterns, "nested/data.csv"));
+        assert!(matches(&patterns, "out/a/b/plot.png"));
+    }
+
+    #[test]
+    fn caps_skip_oversized_files() {
+        let limits = ResourceLimits {
+            max_artifact_bytes: Some(4),
+            max_artifacts_total_bytes: Some(6),
+            ..ResourceLimits::default()
+        };
+        let candidates = vec![
+            ("a.txt".to_string(), 3),
+            ("b.txt".to_string(), 3),
+            ("big.txt".to_string(), 10),
+        ];
+
+        let collected = collect(candidates, &["*.txt".to_string()], &limits, |path| {
+            Ok(vec![b'x'; if path == "big.txt" { 10 } else { 3 }])
+        });
+
+        assert_eq!(collected.artifacts.len(), 2);
+        assert_eq!(collected.skipped, vec!["big.txt".to_string()]);
+        assert_eq!(collected.artifacts[0].read().unwrap(), b"xxx");
+    }
+
+    #[test]
+    fn collects_from_directory_without_following_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("out")).unwrap();
+        std::fs::write(dir.path().join("out/result.csv"), "a,b\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("/etc/hostname", dir.path().join("out/leak.csv")).unwrap();
+
+        let collected = collect_dir(
+            dir.path(),
+            &["out/*.csv".to_string()],
+            &ResourceLimits::default(),
+        );
+
+        assert_eq!(collected.artifacts.len(), 1);
+        assert_eq!(collected.artifacts[0].path, "out/result.csv");
+        assert_eq!(collected.artifacts[0].size, 4);
+    }
+}
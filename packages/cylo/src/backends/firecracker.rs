@@ -29,9 +29,12 @@ use tokio::time::timeout;
 
 use crate::async_task::AsyncTaskBuilder;
 use crate::backends::AsyncTask;
+use crate::backends::artifacts::{self, Collected};
+use crate::backends::{languages, limits};
+use crate::backends::network::{NftScope, NftTable};
 use crate::backends::{
     BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionRequest,
-    ExecutionResult, HealthStatus, ResourceUsage,
+    ExecutionResult, HealthStatus, Language, LimitExceeded, NetworkPolicy, ResourceUsage,
 };
 
 /// FireCracker backend for secure code execution
@@ -71,6 +74,9 @@ struct FireCrackerConfig {
     /// Network configuration
     network_enabled: bool,
 
+    /// Host tap device backing the guest NIC
+    tap_device: String,
+
     /// Metadata configuration
     metadata_enabled: bool,
 }
@@ -84,6 +90,7 @@ impl Default for FireCrackerConfig {
             memory_size_mb: 512,
             vcpu_count: 1,
             network_enabled: false,
+            tap_device: "tap0".to_string(),
             metadata_enabled: true,
         }
     }
@@ -653,6 +660,10 @@ impl FireCrackerBackend {
             fc_config.network_enabled = network_enabled.parse().unwrap_or(false);
         }
 
+        if let Some(tap_device) = config.backend_specific.get("tap_device") {
+            fc_config.tap_device = tap_device.clone();
+        }
+
         Ok(fc_config)
     }
 
@@ -829,12 +840,16 @@ impl FireCrackerBackend {
     /// # Arguments
     /// * `vm` - VM instance
     /// * `fc_config` - FireCracker configuration
+    /// * `network` - Egress policy shaping the guest NIC
+    /// * `max_bandwidth` - NIC rate limit in bytes/sec
     ///
     /// # Returns
     /// AsyncTask that resolves when VM is started
     fn start_vm(
         vm: VMInstance,
         fc_config: FireCrackerConfig,
+        network: NetworkPolicy,
+        max_bandwidth: Option<u64>,
     ) -> AsyncTask<BackendResult<VMInstance>> {
         AsyncTaskBuilder::new().spawn(move || async move {
             // Start FireCracker process
@@ -938,13 +953,22 @@ impl FireCrackerBackend {
 
             // Configure network interface (if network enabled)
             if fc_config.network_enabled {
-                let network_config = serde_json::json!({
+                let mut network_config = serde_json::json!({
                     "iface_id": "eth0",
-                    "host_dev_name": "tap0",
+                    "host_dev_name": fc_config.tap_device,
                     "guest_mac": "AA:FC:00:00:00:01",
-                    "allow_mmds_requests": true
+                    "allow_mmds_requests": network.allows_egress()
                 });
 
+                // Token bucket refilled every second caps guest throughput
+                if let Some(bandwidth) = max_bandwidth {
+                    let limiter = serde_json::json!({
+                        "bandwidth": { "size": bandwidth, "refill_time": 1000 }
+                    });
+                    network_config["rx_rate_limiter"] = limiter.clone();
+                    network_config["tx_rate_limiter"] = limiter;
+                }
+
                 let network_request = HttpRequest::put(
                     &format!(
                         "http://unix:{}/network-interfaces/eth0",
@@ -1164,6 +1188,8 @@ impl FireCrackerBackend {
             let (exit_code, stdout, stderr) = tokio::task::spawn_blocking({
                 let ssh_cfg = ssh_config.clone();
                 let guest_script = guest_script_path.clone();
+                let limit_prefix = limits::shell_limit_prefix(&request.limits, request.timeout);
+                let stdin_data = request.stdin_data().map(<[u8]>::to_vec);
                 move || -> BackendResult<(i32, String, String)> {
                     let session = Self::create_ssh_session(&ssh_cfg)?;
                     let mut channel = session.channel_session().map_err(|e| {
@@ -1173,11 +1199,20 @@ impl FireCrackerBackend {
                     })?;
 
                     channel
-                        .exec(&format!("bash {}", guest_script))
+                        .exec(&format!("{}bash {}", limit_prefix, guest_script))
                         .map_err(|e| BackendError::ProcessFailed {
                             details: format!("Exec failed: {}", e),
                         })?;
 
+                    if let Some(data) = &stdin_data {
+                        channel.write_all(data).map_err(|e| BackendError::ProcessFailed {
+                            details: format!("Write stdin failed: {}", e),
+                        })?;
+                    }
+                    channel.send_eof().map_err(|e| BackendError::ProcessFailed {
+                        details: format!("Close stdin failed: {}", e),
+                    })?;
+
                     let mut stdout = String::new();
                     channel.read_to_string(&mut stdout).map_err(|e| {
                         BackendError::ProcessFailed {
@@ -1212,6 +1247,27 @@ impl FireCrackerBackend {
                 details: format!("Task join failed: {}", e),
             })??;
 
+            // Collect requested artifacts over SSH while the VM is still up
+            let collected = if request.artifacts.is_empty() {
+                Collected::default()
+            } else {
+                let ssh_cfg = ssh_config.clone();
+                let root = request.working_dir.clone().unwrap_or_else(|| ".".to_string());
+                let patterns = request.artifacts.clone();
+                let artifact_limits = request.limits.clone();
+                tokio::task::spawn_blocking(move || {
+                    Self::collect_guest_artifacts(&ssh_cfg, &root, &patterns, &artifact_limits)
+                })
+                .await
+                .map_err(|e| BackendError::ProcessFailed {
+                    details: format!("Task join failed: {}", e),
+                })?
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to collect artifacts from VM {}: {}", vm.vm_id, e);
+                    Collected::default()
+                })
+            };
+
             // Cleanup temp script
             let _ = fs::remove_file(&script_path);
 
@@ -1264,7 +1320,12 @@ impl FireCrackerBackend {
 
             let duration = start_time.elapsed();
 
-            Ok(ExecutionResult {
+            // `timeout -s KILL` in the guest exits with 137 once the deadline passes
+            let limit_exceeded = (exit_code == limits::TIMEOUT_EXIT_CODE
+                || (exit_code == 137 && duration >= request.timeout))
+                .then_some(LimitExceeded::WallClock);
+
+            let mut result = ExecutionResult {
                 exit_code,
                 stdout,
                 stderr,
@@ -1277,10 +1338,75 @@ impl FireCrackerBackend {
                     meta.insert("execution_method".to_string(), "SSH".to_string());
                     meta
                 },
-            })
+                limit_exceeded,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal: None,
+                stdout_truncated: false,
+                stderr_truncated: false,
+                seccomp_violation: None,
+            };
+            collected.apply(&mut result);
+            languages::surface_compile_error(&mut result);
+
+            Ok(limits::enforce_result_limits(result, &request.limits))
         })
     }
 
+    /// Copy files matching `patterns` below `root` out of the guest
+    ///
+    /// # Arguments
+    /// * `ssh_config` - Guest SSH connection
+    /// * `root` - Guest directory the patterns are relative to
+    /// * `patterns` - Artifact globs from the request
+    /// * `limits` - Per-file and total size caps
+    fn collect_guest_artifacts(
+        ssh_config: &SshConfig,
+        root: &str,
+        patterns: &[String],
+        limits: &crate::backends::ResourceLimits,
+    ) -> BackendResult<Collected> {
+        let session = Self::create_ssh_session(ssh_config)?;
+
+        // List regular files (symlinks are not followed) with their sizes
+        let mut channel = session.channel_session().map_err(|e| BackendError::ProcessFailed {
+            details: format!("Failed to create channel: {}", e),
+        })?;
+        channel
+            .exec(&format!(
+                "cd '{}' && find . -type f -printf '%s %P\\n'",
+                root.replace('\'', "'\"'\"'")
+            ))
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Exec failed: {}", e),
+            })?;
+
+        let mut listing = String::new();
+        channel
+            .read_to_string(&mut listing)
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Read listing failed: {}", e),
+            })?;
+        let _ = channel.wait_close();
+
+        let candidates = listing
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .filter_map(|(size, path)| Some((path.to_string(), size.parse().ok()?)))
+            .collect();
+
+        Ok(artifacts::collect(candidates, patterns, limits, |path| {
+            let (mut remote, _) = session
+                .scp_recv(&Path::new(root).join(path))
+                .map_err(std::io::Error::other)?;
+            let mut bytes = Vec::new();
+            remote.read_to_end(&mut bytes)?;
+            let _ = remote.send_eof();
+            let _ = remote.wait_close();
+            Ok(bytes)
+        }))
+    }
+
     /// Prepare execution script for the VM
     ///
     /// # Arguments
@@ -1302,27 +1428,19 @@ impl FireCrackerBackend {
                     request.code.replace('\'', "'\"'\"'")
                 )
             }
-            "rust" => {
-                format!(
-                    "#!/bin/bash\necho '{}' > /tmp/main.rs && cd /tmp && rustc main.rs && ./main",
-                    request.code.replace('\'', "'\"'\"'")
-                )
-            }
             "bash" | "sh" => {
                 format!("#!/bin/bash\n{}", request.code)
             }
-            "go" => {
-                format!(
-                    "#!/bin/bash\necho '{}' > /tmp/main.go && cd /tmp && go run main.go",
-                    request.code.replace('\'', "'\"'\"'")
-                )
-            }
-            _ => {
-                return Err(BackendError::UnsupportedLanguage {
-                    backend: "FireCracker",
-                    language: request.language.clone(),
-                });
-            }
+            // Compiled and toolchain-launched languages build under /tmp
+            other => match Language::parse(other) {
+                Some(language) => format!("#!/bin/bash\n{}", language.script(&request.code)),
+                None => {
+                    return Err(BackendError::UnsupportedLanguage {
+                        backend: "FireCracker",
+                        language: request.language.clone(),
+                    });
+                }
+            },
         };
 
         Ok(script)
@@ -1402,8 +1520,33 @@ impl ExecutionBackend for FireCrackerBackend {
                 );
             }
 
+            // Filter guest egress on the host before the guest boots; the tap
+            // device stays reachable from the host so SSH keeps working
+            let network = request.network_policy(&backend_config).clone();
+            let _network_guard = if fc_config.network_enabled && network != NetworkPolicy::Full {
+                let table = network.resolve().and_then(|allowed| {
+                    NftTable::install(
+                        &vm.vm_id,
+                        &NftScope::Interface(fc_config.tap_device.clone()),
+                        &allowed,
+                    )
+                });
+                match table {
+                    Ok(table) => Some(table),
+                    Err(e) => {
+                        return ExecutionResult::failure(
+                            -1,
+                            format!("Failed to apply network policy: {}", e),
+                        );
+                    }
+                }
+            } else {
+                None
+            };
+
             // Start VM
-            let started_vm = match Self::start_vm(vm, fc_config).await {
+            let max_bandwidth = request.limits.max_network_bandwidth;
+            let started_vm = match Self::start_vm(vm, fc_config, network, max_bandwidth).await {
                 Ok(vm) => vm,
                 Err(e) => {
                     return ExecutionResult::failure(-1, format!("Failed to start VM: {}", e));
@@ -1517,6 +1660,13 @@ impl ExecutionBackend for FireCrackerBackend {
             "bash",
             "sh",
             "go",
+            "typescript",
+            "ts",
+            "ruby",
+            "java",
+            "c",
+            "cpp",
+            "c++",
         ]
     }
 }
@@ -1548,6 +1698,10 @@ mod tests {
         let script = FireCrackerBackend::prepare_execution_script(&request).expect("Failed to prepare JavaScript execution script");
         assert!(script.contains("node"));
 
+        let request = ExecutionRequest::new("public class App {}", "java");
+        let script = FireCrackerBackend::prepare_execution_script(&request).expect("Failed to prepare Java execution script");
+        assert!(script.contains("javac") && script.contains("App.java"));
+
         let request = ExecutionRequest::new("some code", "cobol");
         assert!(FireCrackerBackend::prepare_execution_script(&request).is_err());
     }
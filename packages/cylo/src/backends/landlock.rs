@@ -7,6 +7,7 @@
 // for filesystem access control and sandboxing. Provides:
 // - Kernel-level security enforcement
 // - Filesystem access restrictions
+// - Seccomp-BPF syscall filtering with per-language profiles
 // - Process isolation and privilege dropping
 // - Zero-overhead sandboxing
 // ============================================================================
@@ -19,12 +20,16 @@ use std::process::{Command, Stdio};
 use std::time::{Duration, Instant, SystemTime};
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::Sender;
 
 use crate::async_task::AsyncTaskBuilder;
 use crate::backends::AsyncTask;
+use crate::backends::network::{CgroupScope, NftScope, NftTable};
+use crate::backends::{artifacts, languages, limits, seccomp, streaming};
 use crate::backends::{
-    BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionRequest,
-    ExecutionResult, HealthStatus, ResourceUsage,
+    BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionEvent,
+    ExecutionRequest, ExecutionResult, ExecutionStream, HealthStatus, Language, LimitExceeded,
+    NetworkPolicy, ResourceUsage, SeccompProfile, SessionLaunch,
 };
 
 /// LandLock backend for secure code execution
@@ -253,58 +258,24 @@ impl LandLockBackend {
             })?;
         }
 
-        // Create temporary files for code execution
-        match request.language.as_str() {
-            "python" | "python3" => {
-                let code_file = exec_dir.join("main.py");
-                fs::write(&code_file, &request.code).map_err(|e| {
-                    BackendError::FileSystemFailed {
-                        details: format!("Failed to write Python code file: {}", e),
-                    }
-                })?;
-            }
-            "rust" => {
-                let code_file = exec_dir.join("main.rs");
-                fs::write(&code_file, &request.code).map_err(|e| {
-                    BackendError::FileSystemFailed {
-                        details: format!("Failed to write Rust code file: {}", e),
-                    }
-                })?;
-            }
-            "javascript" | "js" | "node" => {
-                let code_file = exec_dir.join("main.js");
-                fs::write(&code_file, &request.code).map_err(|e| {
-                    BackendError::FileSystemFailed {
-                        details: format!("Failed to write JavaScript code file: {}", e),
-                    }
-                })?;
-            }
-            "go" => {
-                let code_file = exec_dir.join("main.go");
-                fs::write(&code_file, &request.code).map_err(|e| {
-                    BackendError::FileSystemFailed {
-                        details: format!("Failed to write Go code file: {}", e),
-                    }
-                })?;
-            }
-            _ => {
-                // For shell scripts and other languages, write to a generic file
-                let code_file = exec_dir.join("code");
-                fs::write(&code_file, &request.code).map_err(|e| {
-                    BackendError::FileSystemFailed {
-                        details: format!("Failed to write code file: {}", e),
-                    }
-                })?;
-
-                // Make executable for shell scripts
-                if matches!(request.language.as_str(), "bash" | "sh") {
-                    fs::set_permissions(&code_file, fs::Permissions::from_mode(0o755)).map_err(
-                        |e| BackendError::FileSystemFailed {
-                            details: format!("Failed to set executable permissions: {}", e),
-                        },
-                    )?;
+        // Write the source under the name its toolchain expects
+        let language =
+            Language::parse(&request.language).ok_or_else(|| BackendError::UnsupportedLanguage {
+                backend: "LandLock",
+                language: request.language.clone(),
+            })?;
+        let code_file = exec_dir.join(language.source_file(&request.code));
+        fs::write(&code_file, &request.code).map_err(|e| BackendError::FileSystemFailed {
+            details: format!("Failed to write {} code file: {}", language.name(), e),
+        })?;
+
+        // Make executable for shell scripts
+        if language == Language::Bash {
+            fs::set_permissions(&code_file, fs::Permissions::from_mode(0o755)).map_err(|e| {
+                BackendError::FileSystemFailed {
+                    details: format!("Failed to set executable permissions: {}", e),
                 }
-            }
+            })?;
         }
 
         Ok(exec_dir)
@@ -315,6 +286,9 @@ impl LandLockBackend {
     /// # Arguments
     /// * `request` - Execution request
     /// * `exec_dir` - Execution directory path
+    /// * `network` - Network egress policy
+    /// * `seccomp` - Syscall filter for the request's language
+    /// * `events` - When set, output is streamed here instead of buffered
     ///
     /// # Returns
     /// AsyncTask that resolves to execution result
@@ -322,68 +296,32 @@ impl LandLockBackend {
         jail_path: PathBuf,
         request: ExecutionRequest,
         exec_dir: PathBuf,
+        network: NetworkPolicy,
+        seccomp: SeccompProfile,
+        events: Option<Sender<ExecutionEvent>>,
     ) -> AsyncTask<BackendResult<ExecutionResult>> {
         AsyncTaskBuilder::new().spawn(move || async move {
             let start_time = Instant::now();
 
             // Prepare execution command
-            let (program, args) = Self::prepare_execution_command(&request.language, &exec_dir)?;
+            let (program, args) = Self::prepare_execution_command(&request.language, &request.code)?;
 
             // Build sandboxed command using bwrap (bubblewrap) as LandLock enforcement
-            let mut cmd = Command::new("bwrap");
-
-            // Basic sandboxing arguments
-            cmd.args(&[
-                "--ro-bind",
-                "/usr",
-                "/usr", // Read-only system binaries
-                "--ro-bind",
-                "/lib",
-                "/lib", // Read-only system libraries
-                "--ro-bind",
-                "/lib64",
-                "/lib64", // Read-only system libraries
-                "--ro-bind",
-                "/bin",
-                "/bin", // Read-only system binaries
-                "--ro-bind",
-                "/sbin",
-                "/sbin", // Read-only system binaries
-                "--tmpfs",
-                "/tmp", // Temporary filesystem
-                "--proc",
-                "/proc", // Process filesystem
-                "--dev",
-                "/dev", // Device filesystem
-                "--bind",
-                exec_dir.to_str().unwrap_or(""),
-                "/workspace", // Writable workspace
-                "--chdir",
-                "/workspace",    // Change to workspace
-                "--unshare-all", // Unshare all namespaces
-                "--share-net",   // Share network (if needed)
-            ]);
-
-            // Add resource limits
-            if let Some(memory) = request.limits.max_memory {
-                // Convert to MB for ulimit
-                let memory_mb = memory / (1024 * 1024);
-                cmd.args(&[
-                    "--",
-                    "bash",
-                    "-c",
-                    &format!(
-                        "ulimit -v {} && exec {} {}",
-                        memory_mb,
-                        program,
-                        args.join(" ")
-                    ),
-                ]);
-            } else {
-                cmd.arg("--");
-                cmd.arg(&program);
-                cmd.args(&args);
-            }
+            let mut cmd = Self::sandbox_command(
+                &exec_dir,
+                &program,
+                &args,
+                &request.limits,
+                &network,
+                &seccomp,
+            )?;
+
+            // Held until the process exits so the allow-list stays in force
+            let exec_id = exec_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let _network_guard = Self::apply_network_policy(&mut cmd, &exec_id, &network)?;
 
             // Set environment variables
             for (key, value) in &request.env_vars {
@@ -463,39 +401,31 @@ impl LandLockBackend {
                 ResourceUsage::default()
             });
 
-            // Write input if provided
-            if let Some(input) = &request.input {
-                if let Some(stdin) = child.stdin.take() {
-                    use std::io::Write;
-                    let mut stdin = stdin;
-                    stdin
-                        .write_all(input.as_bytes())
-                        .map_err(|e| BackendError::ProcessFailed {
-                            details: format!("Failed to write to process stdin: {}", e),
-                        })?;
-                }
-            }
+            // Feed stdin in the background so large inputs cannot deadlock against output
+            let stdin_writer = streaming::spawn_stdin_writer(&mut child, request.stdin_data());
 
-            // Wait for completion with timeout
+            // Wait for completion, enforcing the wall-clock deadline and output caps
             let timeout_duration = request.timeout;
-            let result =
-                tokio::time::timeout(timeout_duration, async { child.wait_with_output() }).await;
-
-            let output = match result {
-                Ok(Ok(output)) => output,
-                Ok(Err(e)) => {
-                    return Err(BackendError::ProcessFailed {
-                        details: format!("Process execution failed: {}", e),
-                    });
+            let wait_limits = request.limits.clone();
+            let output = tokio::task::spawn_blocking(move || match &events {
+                Some(events) => {
+                    limits::stream_with_limits(child, timeout_duration, &wait_limits, events)
                 }
-                Err(_) => {
-                    // Kill the process on timeout
-                    let _ = child.kill();
-                    return Err(BackendError::ExecutionTimeout {
-                        seconds: timeout_duration.as_secs(),
-                    });
-                }
-            };
+                None => limits::wait_with_limits(child, timeout_duration, &wait_limits),
+            })
+            .await
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Process wait task failed: {}", e),
+            })?
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Process execution failed: {}", e),
+            })?;
+
+            if let Some(writer) = stdin_writer
+                && let Ok(Err(e)) = writer.join()
+            {
+                log::warn!("Failed to write to process stdin: {}", e);
+            }
 
             let duration = start_time.elapsed();
 
@@ -517,11 +447,37 @@ impl LandLockBackend {
                 }
             };
 
+            let (exit_code, signal) = match output.status {
+                Some(status) => {
+                    use std::os::unix::process::ExitStatusExt;
+                    (status.code().unwrap_or(-1), status.signal())
+                }
+                None => (limits::TIMEOUT_EXIT_CODE, None),
+            };
+
+            let limit_exceeded = if output.timed_out {
+                Some(LimitExceeded::WallClock)
+            } else if output.stdout_truncated {
+                Some(LimitExceeded::Stdout)
+            } else if output.stderr_truncated {
+                Some(LimitExceeded::Stderr)
+            } else {
+                limits::classify_termination(exit_code, signal, &resource_usage, &request.limits)
+            };
+
+            // Collect requested artifacts before the workspace is removed
+            let artifact_root = match &request.working_dir {
+                Some(workdir) => exec_dir.join(workdir.trim_start_matches('/')),
+                None => exec_dir.clone(),
+            };
+            let collected =
+                artifacts::collect_dir(&artifact_root, &request.artifacts, &request.limits);
+
             // Clean up execution directory
             let _ = fs::remove_dir_all(&exec_dir);
 
-            Ok(ExecutionResult {
-                exit_code: output.status.code().unwrap_or(-1),
+            let mut result = ExecutionResult {
+                exit_code,
                 stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
                 stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
                 duration,
@@ -533,44 +489,155 @@ impl LandLockBackend {
                     meta.insert("exec_dir".to_string(), exec_dir.display().to_string());
                     meta
                 },
-            })
+                limit_exceeded,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal,
+                stdout_truncated: output.stdout_truncated,
+                stderr_truncated: output.stderr_truncated,
+                seccomp_violation: None,
+            };
+            collected.apply(&mut result);
+            languages::surface_compile_error(&mut result);
+            seccomp::record_violation(&mut result, &seccomp);
+
+            Ok(result)
         })
     }
 
+    /// Build a bubblewrap command running `program` with `exec_dir` as workspace
+    ///
+    /// # Arguments
+    /// * `exec_dir` - Writable directory mounted at /workspace
+    /// * `program` - Program to run inside the sandbox
+    /// * `args` - Program arguments
+    /// * `limits` - Resource limits applied via rlimits
+    /// * `network` - Egress policy; only `DenyAll` leaves the network namespace unshared
+    /// * `seccomp` - Syscall filter bwrap installs before exec
+    ///
+    /// # Returns
+    /// Configured command (stdio not yet set), or error if the filter does not compile
+    fn sandbox_command(
+        exec_dir: &Path,
+        program: &str,
+        args: &[String],
+        limits: &crate::backends::ResourceLimits,
+        network: &NetworkPolicy,
+        seccomp: &SeccompProfile,
+    ) -> BackendResult<Command> {
+        let mut cmd = Command::new("bwrap");
+
+        // Basic sandboxing arguments
+        cmd.args(&[
+            "--ro-bind",
+            "/usr",
+            "/usr", // Read-only system binaries
+            "--ro-bind",
+            "/lib",
+            "/lib", // Read-only system libraries
+            "--ro-bind",
+            "/lib64",
+            "/lib64", // Read-only system libraries
+            "--ro-bind",
+            "/bin",
+            "/bin", // Read-only system binaries
+            "--ro-bind",
+            "/sbin",
+            "/sbin", // Read-only system binaries
+            "--tmpfs",
+            "/tmp", // Temporary filesystem
+            "--proc",
+            "/proc", // Process filesystem
+            "--dev",
+            "/dev", // Device filesystem
+            "--bind",
+            exec_dir.to_str().unwrap_or(""),
+            "/workspace", // Writable workspace
+            "--chdir",
+            "/workspace",    // Change to workspace
+            "--unshare-all", // Unshare all namespaces
+        ]);
+
+        // Without --share-net the sandbox gets a fresh namespace with loopback only;
+        // allow-lists share the host namespace and are filtered by nftables
+        if network.allows_egress() {
+            cmd.arg("--share-net");
+        }
+
+        seccomp::apply_profile(&mut cmd, seccomp)?;
+
+        cmd.arg("--");
+        cmd.arg(program);
+        cmd.args(args);
+
+        // Apply CPU, memory, process and file-size quotas (inherited through bwrap)
+        limits::apply_rlimits(&mut cmd, limits);
+
+        Ok(cmd)
+    }
+
+    /// Enforce an allow-list policy on a sandbox command
+    ///
+    /// Places the process in a dedicated cgroup and loads an nftables table
+    /// that drops egress from that cgroup except to the allowed ranges.
+    /// Returns guards that must be held until the process exits.
+    ///
+    /// # Arguments
+    /// * `cmd` - Sandbox command built by `sandbox_command`
+    /// * `exec_id` - Unique execution identifier
+    /// * `network` - Egress policy
+    ///
+    /// # Returns
+    /// Guards for allow-lists, `None` for other policies
+    fn apply_network_policy(
+        cmd: &mut Command,
+        exec_id: &str,
+        network: &NetworkPolicy,
+    ) -> BackendResult<Option<(CgroupScope, NftTable)>> {
+        if !matches!(network, NetworkPolicy::AllowList { .. }) {
+            return Ok(None);
+        }
+
+        let allowed = network.resolve()?;
+        let cgroup = CgroupScope::create(exec_id)?;
+        let table = NftTable::install(
+            exec_id,
+            &NftScope::Cgroup(cgroup.relative_path().to_string()),
+            &allowed,
+        )?;
+        cgroup.attach(cmd);
+
+        Ok(Some((cgroup, table)))
+    }
+
     /// Prepare execution command for specific language
     ///
     /// # Arguments
     /// * `language` - Programming language
-    /// * `exec_dir` - Execution directory path
+    /// * `code` - Source code (names Java sources)
     ///
     /// # Returns
     /// Command program and arguments
     fn prepare_execution_command(
         language: &str,
-        exec_dir: &Path,
+        code: &str,
     ) -> BackendResult<(String, Vec<String>)> {
-        match language.to_lowercase().as_str() {
-            "python" | "python3" => Ok(("python3".to_string(), vec!["main.py".to_string()])),
-            "javascript" | "js" | "node" => Ok(("node".to_string(), vec!["main.js".to_string()])),
-            "rust" => {
-                // Compile and run Rust code
-                Ok((
-                    "bash".to_string(),
-                    vec![
-                        "-c".to_string(),
-                        "rustc main.rs -o main && ./main".to_string(),
-                    ],
-                ))
-            }
-            "bash" | "sh" => Ok(("bash".to_string(), vec!["code".to_string()])),
-            "go" => Ok((
-                "bash".to_string(),
-                vec!["-c".to_string(), "go run main.go".to_string()],
-            )),
-            _ => Err(BackendError::UnsupportedLanguage {
+        let language =
+            Language::parse(language).ok_or_else(|| BackendError::UnsupportedLanguage {
                 backend: "LandLock",
                 language: language.to_string(),
-            }),
+            })?;
+        let source = language.source_file(code);
+
+        match language {
+            Language::Python => Ok(("python3".to_string(), vec![source])),
+            Language::JavaScript => Ok(("node".to_string(), vec![source])),
+            Language::Bash => Ok(("bash".to_string(), vec![source])),
+            // Compiled and toolchain-launched languages build in the workspace
+            _ => Ok((
+                "bash".to_string(),
+                vec!["-c".to_string(), language.build_and_run(".", code)],
+            )),
         }
     }
 
@@ -776,6 +843,8 @@ impl ExecutionBackend for LandLockBackend {
     fn execute_code(&self, request: ExecutionRequest) -> AsyncTask<ExecutionResult> {
         let jail_path = self.jail_path.clone();
         let backend_name = self.backend_type();
+        let network = request.network_policy(&self.config).clone();
+        let seccomp = self.config.seccomp.profile_for(&request.language).clone();
 
         AsyncTaskBuilder::new().spawn(move || async move {
             // Setup jail environment
@@ -790,7 +859,9 @@ impl ExecutionBackend for LandLockBackend {
             };
 
             // Execute with LandLock sandboxing
-            match Self::execute_with_landlock(jail_path, request, exec_dir).await {
+            match Self::execute_with_landlock(jail_path, request, exec_dir, network, seccomp, None)
+                .await
+            {
                 Ok(result) => result,
                 Err(e) => ExecutionResult::failure(
                     -1,
@@ -800,6 +871,109 @@ impl ExecutionBackend for LandLockBackend {
         })
     }
 
+    fn execute_code_streaming(&self, request: ExecutionRequest) -> ExecutionStream {
+        let jail_path = self.jail_path.clone();
+        let backend_name = self.backend_type();
+        let network = request.network_policy(&self.config).clone();
+        let seccomp = self.config.seccomp.profile_for(&request.language).clone();
+        let (tx, rx) = streaming::channel();
+
+        let exec_dir = self.setup_jail_environment(&request);
+
+        tokio::spawn(async move {
+            let result = match exec_dir {
+                Ok(exec_dir) => {
+                    match Self::execute_with_landlock(
+                        jail_path,
+                        request,
+                        exec_dir,
+                        network,
+                        seccomp,
+                        Some(tx.clone()),
+                    )
+                    .await
+                    {
+                        Ok(Ok(result)) => result,
+                        Ok(Err(e)) => ExecutionResult::failure(
+                            -1,
+                            format!("{} execution failed: {}", backend_name, e),
+                        ),
+                        Err(e) => ExecutionResult::failure(
+                            -1,
+                            format!("{} execution task failed: {}", backend_name, e),
+                        ),
+                    }
+                }
+                Err(e) => ExecutionResult::failure(
+                    -1,
+                    format!("Failed to setup jail environment: {}", e),
+                ),
+            };
+
+            // Output was already streamed; only failure messages remain buffered
+            streaming::send_result(&tx, &result).await;
+        });
+
+        rx
+    }
+
+    fn session_command(
+        &self,
+        argv: &[String],
+        limits: &crate::backends::ResourceLimits,
+    ) -> BackendResult<SessionLaunch> {
+        let (program, args) = argv.split_first().ok_or_else(|| BackendError::InvalidConfig {
+            backend: "LandLock",
+            details: "empty session command".to_string(),
+        })?;
+
+        let workspace = self.jail_path.join(format!(
+            "session-{}-{}",
+            uuid::Uuid::new_v4().simple(),
+            std::process::id()
+        ));
+        fs::create_dir_all(&workspace).map_err(|e| BackendError::FileSystemFailed {
+            details: format!("Failed to create session directory: {}", e),
+        })?;
+        fs::set_permissions(&workspace, fs::Permissions::from_mode(0o700)).map_err(|e| {
+            BackendError::FileSystemFailed {
+                details: format!("Failed to set directory permissions: {}", e),
+            }
+        })?;
+
+        // RLIMIT_CPU is cumulative over the process lifetime, which would kill a
+        // long-lived session; per-snippet wall-clock timeouts bound it instead
+        let session_limits = crate::backends::ResourceLimits {
+            max_cpu_time: None,
+            ..limits.clone()
+        };
+
+        // Sessions outlive a single call, so only policies that need no
+        // per-process guards are supported
+        let network = &self.config.default_network;
+        if matches!(network, NetworkPolicy::AllowList { .. }) {
+            return Err(BackendError::NotAvailable {
+                backend: "LandLock",
+                reason: "allow-list network policies are not supported for sessions".to_string(),
+            });
+        }
+
+        // Interpreters such as python3 and node name their language
+        let seccomp = self.config.seccomp.profile_for(program);
+
+        Ok(SessionLaunch {
+            command: Self::sandbox_command(
+                &workspace,
+                program,
+                args,
+                &session_limits,
+                network,
+                seccomp,
+            )?,
+            workspace: Some(workspace),
+        })
+    }
+
     fn health_check(&self) -> AsyncTask<HealthStatus> {
         let jail_path = self.jail_path.clone();
         let features = self.landlock_features.clone();
@@ -900,6 +1074,13 @@ impl ExecutionBackend for LandLockBackend {
             "bash",
             "sh",
             "go",
+            "typescript",
+            "ts",
+            "ruby",
+            "java",
+            "c",
+            "cpp",
+            "c++",
         ]
     }
 }
@@ -923,17 +1104,18 @@ mod tests {
 
     #[test]
     fn execution_command_preparation() {
-        let exec_dir = PathBuf::from("/tmp/test");
-
-        let (prog, args) = LandLockBackend::prepare_execution_command("python", &exec_dir).unwrap();
+        let (prog, args) = LandLockBackend::prepare_execution_command("python", "").unwrap();
         assert_eq!(prog, "python3");
         assert_eq!(args, vec!["main.py"]);
 
-        let (prog, args) = LandLockBackend::prepare_execution_command("rust", &exec_dir).unwrap();
+        let (prog, args) = LandLockBackend::prepare_execution_command("rust", "").unwrap();
         assert_eq!(prog, "bash");
         assert!(args[1].contains("rustc"));
 
-        let unsupported = LandLockBackend::prepare_execution_command("cobol", &exec_dir);
+        let (_, args) = LandLockBackend::prepare_execution_command("cpp", "").unwrap();
+        assert!(args[1].contains("c++ -O2"));
+
+        let unsupported = LandLockBackend::prepare_execution_command("cobol", "");
         assert!(unsupported.is_err());
     }
 
@@ -0,0 +1,304 @@
+// ============================================================================
+// File: packages/cylo/src/backends/languages.rs
+// ----------------------------------------------------------------------------
+// Language runtimes shared by the sandbox backends.
+//
+// Provides:
+// - Language names, aliases and detection from file extensions
+// - Per-language default container images and toolchain commands
+// - Compile-then-run shell scripts for compiled languages
+// - Compile-error surfacing in ExecutionResult
+// ============================================================================
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::ExecutionResult;
+use crate::backends::project::shell_quote;
+
+/// Exit code a build script uses when compilation fails
+pub const COMPILE_FAILED_EXIT_CODE: i32 = 98;
+
+/// Line a build script writes to stderr after the compiler's diagnostics
+const COMPILE_FAILED_MARKER: &str = "cylo: compilation failed";
+
+/// Language with a known runtime or toolchain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Python,
+    JavaScript,
+    /// Run with deno, falling back to tsx
+    TypeScript,
+    Ruby,
+    Rust,
+    Go,
+    /// Single-file program compiled with the JDK's javac
+    Java,
+    C,
+    Cpp,
+    Bash,
+}
+
+impl Language {
+    /// Every supported language
+    pub const ALL: [Language; 10] = [
+        Language::Python,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Ruby,
+        Language::Rust,
+        Language::Go,
+        Language::Java,
+        Language::C,
+        Language::Cpp,
+        Language::Bash,
+    ];
+
+    /// Parse a language name or alias (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "python" | "python3" | "py" => Some(Self::Python),
+            "javascript" | "js" | "node" => Some(Self::JavaScript),
+            "typescript" | "ts" | "tsx" | "deno" => Some(Self::TypeScript),
+            "ruby" | "rb" => Some(Self::Ruby),
+            "rust" | "rs" => Some(Self::Rust),
+            "go" | "golang" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "c" => Some(Self::C),
+            "cpp" | "c++" | "cxx" => Some(Self::Cpp),
+            "bash" | "sh" => Some(Self::Bash),
+            _ => None,
+        }
+    }
+
+    /// Detect the language from a file extension (without the dot)
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "py" => Some(Self::Python),
+            "js" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "rb" => Some(Self::Ruby),
+            "rs" => Some(Self::Rust),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "c" => Some(Self::C),
+            "cc" | "cpp" | "cxx" | "c++" => Some(Self::Cpp),
+            "sh" | "bash" => Some(Self::Bash),
+            _ => None,
+        }
+    }
+
+    /// Detect the language of a source file from its extension
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Canonical name, as accepted by `ExecutionRequest::language`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Ruby => "ruby",
+            Self::Rust => "rust",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::Bash => "bash",
+        }
+    }
+
+    /// Whether the source is compiled before it runs
+    pub fn is_compiled(&self) -> bool {
+        matches!(self, Self::Rust | Self::Go | Self::Java | Self::C | Self::Cpp)
+    }
+
+    /// Container image with this language's runtime or toolchain
+    pub fn default_image(&self) -> &'static str {
+        match self {
+            Self::Python => "python:3.12-alpine",
+            Self::JavaScript => "node:22-alpine",
+            Self::TypeScript => "denoland/deno:alpine",
+            Self::Ruby => "ruby:3.3-alpine",
+            Self::Rust => "rust:alpine",
+            Self::Go => "golang:alpine",
+            Self::Java => "eclipse-temurin:21-jdk-alpine",
+            Self::C | Self::Cpp => "gcc:14",
+            Self::Bash => "bash:5",
+        }
+    }
+
+    /// File name the source is written to
+    ///
+    /// Java requires the file to be named after its public class, so the
+    /// name is taken from `code` (falling back to `Main.java`).
+    pub fn source_file(&self, code: &str) -> String {
+        match self {
+            Self::Python => "main.py".to_string(),
+            Self::JavaScript => "main.js".to_string(),
+            Self::TypeScript => "main.ts".to_string(),
+            Self::Ruby => "main.rb".to_string(),
+            Self::Rust => "main.rs".to_string(),
+            Self::Go => "main.go".to_string(),
+            Self::Java => format!("{}.java", java_main_class(code)),
+            Self::C => "main.c".to_string(),
+            Self::Cpp => "main.cpp".to_string(),
+            Self::Bash => "main.sh".to_string(),
+        }
+    }
+
+    /// Shell commands that compile (if needed) and run the source in `dir`
+    ///
+    /// A failed compilation exits with `COMPILE_FAILED_EXIT_CODE` after the
+    /// compiler's diagnostics, which `surface_compile_error` picks up. The run
+    /// step `exec`s the program so the request's timeout and limits apply to
+    /// it directly.
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the source; may be a shell variable such as `$build`
+    /// * `code` - Source code, used to name Java sources
+    pub fn build_and_run(&self, dir: &str, code: &str) -> String {
+        let source = format!("\"{dir}/{}\"", self.source_file(code));
+        let binary = format!("\"{dir}/main\"");
+
+        let compile = match self {
+            Self::Rust => Some(format!("rustc -o {binary} {source}")),
+            Self::Go => Some(format!(
+                "GOCACHE=\"${{GOCACHE:-/tmp/cylo-go-cache}}\" go build -o {binary} {source}"
+            )),
+            Self::Java => Some(format!("javac -d \"{dir}\" {source}")),
+            Self::C => Some(format!("cc -O2 -std=c11 -o {binary} {source} -lm")),
+            Self::Cpp => Some(format!("c++ -O2 -std=c++17 -o {binary} {source}")),
+            _ => None,
+        };
+
+        let run = match self {
+            Self::Python => format!("exec python3 {source}"),
+            Self::JavaScript => format!("exec node {source}"),
+            Self::TypeScript => format!(
+                "if command -v deno >/dev/null 2>&1; then exec deno run -A --quiet {source}; \
+                 else exec tsx {source}; fi"
+            ),
+            Self::Ruby => format!("exec ruby {source}"),
+            Self::Java => format!("exec java -cp \"{dir}\" {}", java_main_class(code)),
+            Self::Rust | Self::Go | Self::C | Self::Cpp => format!("exec {binary}"),
+            Self::Bash => format!("exec bash {source}"),
+        };
+
+        match compile {
+            Some(compile) => format!(
+                "if ! {compile}; then echo '{COMPILE_FAILED_MARKER}' >&2; \
+                 exit {COMPILE_FAILED_EXIT_CODE}; fi\n{run}"
+            ),
+            None => run,
+        }
+    }
+
+    /// Self-contained POSIX shell script that writes `code` to a fresh build
+    /// directory under /tmp, then compiles and runs it
+    ///
+    /// The program runs from the caller's working directory, so files it
+    /// writes there can still be collected as artifacts.
+    pub fn script(&self, code: &str) -> String {
+        format!(
+            "build=$(mktemp -d /tmp/cylo-build.XXXXXX) || exit 1\n\
+             printf '%s' {} > \"$build/{}\"\n\
+             {}",
+            shell_quote(code),
+            self.source_file(code),
+            self.build_and_run("$build", code)
+        )
+    }
+}
+
+/// Move compiler diagnostics into `ExecutionResult::compile_error`
+///
+/// Applies only to results of `Language::build_and_run` scripts that failed
+/// at the compile step; the marker line is stripped from stderr.
+pub fn surface_compile_error(result: &mut ExecutionResult) {
+    if result.exit_code != COMPILE_FAILED_EXIT_CODE {
+        return;
+    }
+    let Some(position) = result.stderr.rfind(COMPILE_FAILED_MARKER) else {
+        return;
+    };
+
+    let end = position + COMPILE_FAILED_MARKER.len();
+    let end = if result.stderr[end..].starts_with('\n') {
+        end + 1
+    } else {
+        end
+    };
+    result.stderr.replace_range(position..end, "");
+    result.compile_error = Some(result.stderr.trim().to_string());
+}
+
+/// Name of the class declared `public class X`, else `Main`
+fn java_main_class(code: &str) -> &str {
+    code.split("public class ")
+        .nth(1)
+        .and_then(|rest| {
+            let name = rest
+                .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .next()?;
+            (!name.is_empty()).then_some(name)
+        })
+        .unwrap_or("Main")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_and_extensions_resolve() {
+        assert_eq!(Language::parse("TS"), Some(Language::TypeScript));
+        assert_eq!(Language::parse("c++"), Some(Language::Cpp));
+        assert_eq!(Language::parse("cobol"), None);
+
+        assert_eq!(Language::from_path("src/app.rb"), Some(Language::Ruby));
+        assert_eq!(Language::from_path("Main.java"), Some(Language::Java));
+        assert_eq!(Language::from_path("prog.cc"), Some(Language::Cpp));
+        assert_eq!(Language::from_path("README"), None);
+
+        for language in Language::ALL {
+            assert_eq!(Language::parse(language.name()), Some(language));
+        }
+    }
+
+    #[test]
+    fn java_source_is_named_after_public_class() {
+        let code = "public class Hello {\n  public static void main(String[] a) {}\n}";
+        assert_eq!(Language::Java.source_file(code), "Hello.java");
+        assert!(Language::Java.build_and_run(".", code).ends_with("java -cp \".\" Hello"));
+        assert_eq!(Language::Java.source_file("class X {}"), "Main.java");
+    }
+
+    #[test]
+    fn compile_failures_are_surfaced() {
+        let script = Language::C.script("int main() { return 0 }");
+        assert!(script.contains("cc -O2"));
+        assert!(script.contains(&format!("exit {COMPILE_FAILED_EXIT_CODE}")));
+
+        let mut result = ExecutionResult::failure(
+            COMPILE_FAILED_EXIT_CODE,
+            format!("main.c:1:25: error: expected ';'\n{COMPILE_FAILED_MARKER}\n"),
+        );
+        surface_compile_error(&mut result);
+        assert_eq!(
+            result.compile_error.as_deref(),
+            Some("main.c:1:25: error: expected ';'")
+        );
+        assert!(!result.stderr.contains(COMPILE_FAILED_MARKER));
+
+        let mut runtime_failure = ExecutionResult::failure(1, "segfault");
+        surface_compile_error(&mut runtime_failure);
+        assert!(runtime_failure.compile_error.is_none());
+    }
+}
@@ -0,0 +1,486 @@
+// ============================================================================
+// File: packages/cylo/src/backends/limits.rs
+// ----------------------------------------------------------------------------
+// Per-execution resource quota enforcement shared by all backends.
+//
+// Provides:
+// - rlimit application for locally spawned processes (CPU, memory, processes)
+// - ulimit/timeout shell wrapping for guests we only reach through a shell
+// - Bounded stdout/stderr capture (or streaming) with wall-clock deadline enforcement,
+//   for std children and backend-launched processes alike
+// - Classification of terminations into exceeded-limit reasons
+// ============================================================================
+
+use std::io::Read;
+use std::process::{Child, ExitStatus};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::Sender;
+
+use crate::backends::{
+    ExecutionEvent, ExecutionResult, LimitExceeded, ResourceLimits, ResourceUsage,
+};
+
+/// Exit code reported when the wall-clock deadline kills an execution
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Signal delivered by the kernel when RLIMIT_CPU is exceeded
+const SIGXCPU: i32 = 24;
+
+/// Signal used to kill processes that exceed a limit
+const SIGKILL: i32 = 9;
+
+/// Fraction of the memory limit at which a SIGKILL is attributed to memory
+const MEMORY_KILL_THRESHOLD: f64 = 0.9;
+
+/// Poll interval while waiting for a child to exit
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Apply CPU, memory, process and file-size rlimits to a command before it spawns
+///
+/// The limits are inherited by every descendant, including sandbox wrappers
+/// such as bubblewrap.
+#[cfg(unix)]
+pub fn apply_rlimits(cmd: &mut std::process::Command, limits: &ResourceLimits) {
+    use std::os::unix::process::CommandExt;
+
+    use nix::sys::resource::{Resource, setrlimit};
+
+    let limits = limits.clone();
+
+    // SAFETY: setrlimit is async-signal-safe and we do not allocate in the closure
+    unsafe {
+        cmd.pre_exec(move || {
+            let apply = |resource: Resource, value: Option<u64>| -> std::io::Result<()> {
+                if let Some(value) = value {
+                    setrlimit(resource, value, value).map_err(std::io::Error::from)?;
+                }
+                Ok(())
+            };
+
+            apply(Resource::RLIMIT_CPU, limits.max_cpu_time)?;
+            apply(Resource::RLIMIT_AS, limits.max_memory)?;
+            apply(Resource::RLIMIT_NPROC, limits.max_processes.map(u64::from))?;
+            apply(Resource::RLIMIT_FSIZE, limits.max_file_size)?;
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+pub fn apply_rlimits(_cmd: &mut std::process::Command, _limits: &ResourceLimits) {}
+
+/// Build a shell prefix that applies the limits via `ulimit` and a `timeout` wrapper
+///
+/// Used for guests (VMs, containers) where we cannot call setrlimit directly.
+/// The returned string ends with a space and should be followed by the command.
+pub fn shell_limit_prefix(limits: &ResourceLimits, wall_clock: Duration) -> String {
+    let mut ulimits = Vec::new();
+
+    if let Some(cpu) = limits.max_cpu_time {
+        ulimits.push(format!("ulimit -t {cpu}"));
+    }
+    if let Some(memory) = limits.max_memory {
+        ulimits.push(format!("ulimit -v {}", memory / 1024));
+    }
+    if let Some(processes) = limits.max_processes {
+        ulimits.push(format!("ulimit -u {processes}"));
+    }
+    if let Some(file_size) = limits.max_file_size {
+        // ulimit -f counts 512-byte blocks in POSIX shells
+        ulimits.push(format!("ulimit -f {}", file_size / 512));
+    }
+
+    let timeout = format!("timeout -s KILL {} ", wall_clock.as_secs().max(1));
+
+    if ulimits.is_empty() {
+        timeout
+    } else {
+        format!("{} && {}", ulimits.join(" && "), timeout)
+    }
+}
+
+/// Truncate captured output to `max` bytes, returning whether truncation occurred
+pub fn cap_output(bytes: &[u8], max: Option<u64>) -> (String, bool) {
+    match max {
+        Some(max) if bytes.len() as u64 > max => (
+            String::from_utf8_lossy(&bytes[..max as usize]).into_owned(),
+            true,
+        ),
+        _ => (String::from_utf8_lossy(bytes).into_owned(), false),
+    }
+}
+
+/// Output captured from a child run under `wait_with_limits`
+#[derive(Debug)]
+pub struct LimitedOutput {
+    /// Exit status, or `None` when the child was killed at the deadline
+    pub status: Option<ExitStatus>,
+    /// Captured stdout (at most `max_stdout_bytes`)
+    pub stdout: Vec<u8>,
+    /// Captured stderr (at most `max_stderr_bytes`)
+    pub stderr: Vec<u8>,
+    /// Whether stdout exceeded its cap
+    pub stdout_truncated: bool,
+    /// Whether stderr exceeded its cap
+    pub stderr_truncated: bool,
+    /// Whether the wall-clock deadline was reached
+    pub timed_out: bool,
+}
+
+/// Process supervised by `wait_with_limits`
+///
+/// Implemented for `std::process::Child` and for processes a backend launches
+/// itself, such as the Windows backend's job-contained processes.
+pub trait SupervisedChild {
+    /// Close stdin and take the stdout and stderr pipes
+    fn take_output(&mut self) -> (Option<Box<dyn Read + Send>>, Option<Box<dyn Read + Send>>);
+
+    /// Exit status, if the process has exited
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>>;
+
+    /// Kill the process at the deadline and reap it
+    fn kill(&mut self);
+}
+
+impl SupervisedChild for Child {
+    fn take_output(&mut self) -> (Option<Box<dyn Read + Send>>, Option<Box<dyn Read + Send>>) {
+        drop(self.stdin.take());
+        (
+            self.stdout
+                .take()
+                .map(|stream| Box::new(stream) as Box<dyn Read + Send>),
+            self.stderr
+                .take()
+                .map(|stream| Box::new(stream) as Box<dyn Read + Send>),
+        )
+    }
+
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        Child::try_wait(self)
+    }
+
+    fn kill(&mut self) {
+        let _ = Child::kill(self);
+        let _ = self.wait();
+    }
+}
+
+/// Wait for a child while enforcing the wall-clock deadline and output caps
+///
+/// Output beyond the cap is drained and discarded so the child never blocks on a
+/// full pipe. The child is killed when the deadline passes.
+pub fn wait_with_limits(
+    child: impl SupervisedChild,
+    timeout: Duration,
+    limits: &ResourceLimits,
+) -> std::io::Result<LimitedOutput> {
+    wait_child(child, timeout, limits, None)
+}
+
+/// Like `wait_with_limits`, but forwards output to `events` as it arrives
+///
+/// Chunks are sent as `ExecutionEvent::Stdout`/`Stderr` and are not retained,
+/// so the returned `LimitedOutput` has empty `stdout`/`stderr` buffers. The
+/// caller is responsible for sending the terminal `Exit` event.
+pub fn stream_with_limits(
+    child: impl SupervisedChild,
+    timeout: Duration,
+    limits: &ResourceLimits,
+    events: &Sender<ExecutionEvent>,
+) -> std::io::Result<LimitedOutput> {
+    wait_child(child, timeout, limits, Some(events))
+}
+
+fn wait_child(
+    mut child: impl SupervisedChild,
+    timeout: Duration,
+    limits: &ResourceLimits,
+    events: Option<&Sender<ExecutionEvent>>,
+) -> std::io::Result<LimitedOutput> {
+    // Close stdin so children reading it see EOF instead of blocking until the deadline
+    let (stdout, stderr) = child.take_output();
+
+    let stdout_reader = stdout.map(|stream| {
+        let forward = events.map(|tx| (tx.clone(), OutputStream::Stdout));
+        spawn_capped_reader(stream, limits.max_stdout_bytes, forward)
+    });
+    let stderr_reader = stderr.map(|stream| {
+        let forward = events.map(|tx| (tx.clone(), OutputStream::Stderr));
+        spawn_capped_reader(stream, limits.max_stderr_bytes, forward)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+
+        if Instant::now() >= deadline {
+            timed_out = true;
+            child.kill();
+            break None;
+        }
+
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    };
+
+    let (stdout, stdout_truncated) = join_reader(stdout_reader);
+    let (stderr, stderr_truncated) = join_reader(stderr_reader);
+
+    Ok(LimitedOutput {
+        status,
+        stdout,
+        stderr,
+        stdout_truncated,
+        stderr_truncated,
+        timed_out,
+    })
+}
+
+/// Which output stream a reader thread forwards
+#[derive(Debug, Clone, Copy)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    fn event(self, data: String) -> ExecutionEvent {
+        match self {
+            Self::Stdout => ExecutionEvent::Stdout { data },
+            Self::Stderr => ExecutionEvent::Stderr { data },
+        }
+    }
+}
+
+fn spawn_capped_reader<R: Read + Send + 'static>(
+    mut stream: R,
+    max: Option<u64>,
+    forward: Option<(Sender<ExecutionEvent>, OutputStream)>,
+) -> JoinHandle<(Vec<u8>, bool)> {
+    std::thread::spawn(move || {
+        let mut captured = Vec::new();
+        let mut pending = Vec::new();
+        let mut total = 0usize;
+        let mut truncated = false;
+        let mut chunk = [0u8; 8192];
+
+        loop {
+            let read = match stream.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => read,
+            };
+
+            let room = max
+                .map(|max| (max as usize).saturating_sub(total))
+                .unwrap_or(read);
+            if room < read {
+                truncated = true;
+            }
+            let kept = &chunk[..read.min(room)];
+            total += kept.len();
+
+            match &forward {
+                Some((tx, kind)) if !kept.is_empty() => {
+                    pending.extend_from_slice(kept);
+                    let text = take_utf8_prefix(&mut pending);
+                    // A dropped receiver only means nobody is listening; keep draining
+                    if !text.is_empty() {
+                        let _ = tx.blocking_send(kind.event(text));
+                    }
+                }
+                Some(_) => {}
+                None => captured.extend_from_slice(kept),
+            }
+        }
+
+        if let Some((tx, kind)) = &forward
+            && !pending.is_empty()
+        {
+            let _ = tx.blocking_send(kind.event(String::from_utf8_lossy(&pending).into_owned()));
+        }
+
+        (captured, truncated)
+    })
+}
+
+/// Decode the longest valid UTF-8 prefix of `pending`, keeping a split
+/// multi-byte sequence at the end for the next chunk
+fn take_utf8_prefix(pending: &mut Vec<u8>) -> String {
+    let valid = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        // Incomplete sequence at the end: wait for the rest of it
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        // Genuinely invalid bytes: decode lossily rather than stall
+        Err(_) => pending.len(),
+    };
+
+    let rest = pending.split_off(valid);
+    let text = String::from_utf8_lossy(pending).into_owned();
+    *pending = rest;
+    text
+}
+
+fn join_reader(reader: Option<JoinHandle<(Vec<u8>, bool)>>) -> (Vec<u8>, bool) {
+    reader
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default()
+}
+
+/// Attribute a termination to an exceeded limit, if any
+///
+/// # Arguments
+/// * `exit_code` - Exit code (shell convention `128 + signal` is understood)
+/// * `signal` - Terminating signal when known
+/// * `usage` - Observed resource usage
+/// * `limits` - Limits the execution ran under
+pub fn classify_termination(
+    exit_code: i32,
+    signal: Option<i32>,
+    usage: &ResourceUsage,
+    limits: &ResourceLimits,
+) -> Option<LimitExceeded> {
+    let signal = signal.or_else(|| (exit_code > 128).then_some(exit_code - 128));
+
+    if signal == Some(SIGXCPU) {
+        return Some(LimitExceeded::CpuTime);
+    }
+
+    if let (Some(max_cpu), true) = (limits.max_cpu_time, usage.cpu_time_ms > 0)
+        && usage.cpu_time_ms >= max_cpu * 1000
+    {
+        return Some(LimitExceeded::CpuTime);
+    }
+
+    if let Some(max_memory) = limits.max_memory
+        && usage.peak_memory as f64 >= max_memory as f64 * MEMORY_KILL_THRESHOLD
+        && (signal == Some(SIGKILL) || exit_code != 0)
+    {
+        return Some(LimitExceeded::Memory);
+    }
+
+    if let Some(max_processes) = limits.max_processes
+        && usage.process_count >= max_processes
+        && exit_code != 0
+    {
+        return Some(LimitExceeded::Processes);
+    }
+
+    None
+}
+
+/// Apply output caps and limit classification to a finished result
+///
+/// Backends that capture output without `wait_with_limits` (SSH, plugins) call
+/// this to truncate oversized streams and record the exceeded-limit reason.
+pub fn enforce_result_limits(mut result: ExecutionResult, limits: &ResourceLimits) -> ExecutionResult {
+    let (stdout, stdout_truncated) = cap_output(result.stdout.as_bytes(), limits.max_stdout_bytes);
+    let (stderr, stderr_truncated) = cap_output(result.stderr.as_bytes(), limits.max_stderr_bytes);
+    result.stdout = stdout;
+    result.stderr = stderr;
+    result.stdout_truncated |= stdout_truncated;
+    result.stderr_truncated |= stderr_truncated;
+
+    if result.limit_exceeded.is_none() {
+        result.limit_exceeded = if stdout_truncated {
+            Some(LimitExceeded::Stdout)
+        } else if stderr_truncated {
+            Some(LimitExceeded::Stderr)
+        } else {
+            classify_termination(result.exit_code, None, &result.resource_usage, limits)
+        };
+    }
+
+    result
+}
+
+/// Build the result reported when the wall-clock deadline kills an execution
+pub fn wall_clock_result(
+    stdout: String,
+    stderr: String,
+    duration: Duration,
+    resource_usage: ResourceUsage,
+) -> ExecutionResult {
+    ExecutionResult {
+        exit_code: TIMEOUT_EXIT_CODE,
+        stdout,
+        stderr,
+        duration,
+        resource_usage,
+        metadata: Default::default(),
+        limit_exceeded: Some(LimitExceeded::WallClock),
+        artifacts: Vec::new(),
+        compile_error: None,
+        signal: None,
+        stdout_truncated: false,
+        stderr_truncated: false,
+        seccomp_violation: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_is_capped() {
+        let (text, truncated) = cap_output(b"hello world", Some(5));
+        assert_eq!(text, "hello");
+        assert!(truncated);
+
+        let (text, truncated) = cap_output(b"hi", Some(5));
+        assert_eq!(text, "hi");
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn sigxcpu_is_cpu_limit() {
+        let limits = ResourceLimits::default();
+        let usage = ResourceUsage::default();
+        assert_eq!(
+            classify_termination(128 + SIGXCPU, None, &usage, &limits),
+            Some(LimitExceeded::CpuTime)
+        );
+        assert_eq!(classify_termination(0, None, &usage, &limits), None);
+    }
+
+    #[test]
+    fn shell_prefix_contains_limits() {
+        let limits = ResourceLimits {
+            max_cpu_time: Some(5),
+            max_memory: Some(64 * 1024 * 1024),
+            ..ResourceLimits::default()
+        };
+        let prefix = shell_limit_prefix(&limits, Duration::from_secs(10));
+        assert!(prefix.contains("ulimit -t 5"));
+        assert!(prefix.contains("ulimit -v 65536"));
+        assert!(prefix.ends_with("timeout -s KILL 10 "));
+    }
+
+    #[test]
+    fn split_utf8_sequence_is_held_back() {
+        let mut pending = vec![b'a', 0xC3];
+        assert_eq!(take_utf8_prefix(&mut pending), "a");
+        assert_eq!(pending, vec![0xC3]);
+
+        pending.push(0xA9);
+        assert_eq!(take_utf8_prefix(&mut pending), "é");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn oversized_stdout_flags_limit() {
+        let limits = ResourceLimits {
+            max_stdout_bytes: Some(3),
+            ..ResourceLimits::default()
+        };
+        let result = enforce_result_limits(ExecutionResult::success("abcdef"), &limits);
+        assert_eq!(result.stdout, "abc");
+        assert_eq!(result.limit_exceeded, Some(LimitExceeded::Stdout));
+        assert!(result.stdout_truncated && !result.stderr_truncated);
+    }
+}
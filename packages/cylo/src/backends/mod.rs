@@ -32,6 +32,74 @@ pub trait ExecutionBackend: Send + Sync + std::fmt::Debug {
     /// AsyncTask that resolves to execution result
     fn execute_code(&self, request: ExecutionRequest) -> AsyncTask<ExecutionResult>;
 
+    /// Execute code and stream output as it is produced
+    ///
+    /// Yields `ExecutionEvent::Stdout`/`Stderr` chunks while the program runs,
+    /// followed by exactly one `ExecutionEvent::Exit`. The default
+    /// implementation runs `execute_code` and replays its buffered output;
+    /// backends that own a local process override it to stream incrementally.
+    ///
+    /// # Arguments
+    /// * `request` - Execution request with code, language, and configuration
+    ///
+    /// # Returns
+    /// Receiver of execution events
+    fn execute_code_streaming(&self, request: ExecutionRequest) -> ExecutionStream {
+        streaming::replay_result(self.execute_code(request))
+    }
+
+    /// Execute a multi-file project
+    ///
+    /// Stages the project tree into the sandbox, runs the optional dependency
+    /// install step and then the entrypoint. The default implementation runs
+    /// the project's staging script through `execute_code` as bash, so any
+    /// backend with a shell supports projects without extra code.
+    ///
+    /// # Arguments
+    /// * `project` - Project files, entrypoint and dependency manifest
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to the entrypoint's execution result
+    fn execute_project(&self, project: ProjectRequest) -> AsyncTask<ExecutionResult> {
+        if !self.supports_language("bash") {
+            let backend = self.backend_type();
+            return tokio::spawn(async move {
+                ExecutionResult::failure(-1, format!("{backend} cannot run project scripts"))
+            });
+        }
+
+        match project.to_execution_request() {
+            Ok(request) => self.execute_code(request),
+            Err(e) => tokio::spawn(async move {
+                ExecutionResult::failure(-1, format!("Invalid project request: {e}"))
+            }),
+        }
+    }
+
+    /// Build the sandboxed command hosting a persistent session process
+    ///
+    /// Used by `ReplSession::start`. Backends that cannot keep a process
+    /// alive between executions keep the default, which reports sessions as
+    /// unavailable.
+    ///
+    /// # Arguments
+    /// * `argv` - Interpreter program and arguments running the session driver
+    /// * `limits` - Limits applied for the lifetime of the session
+    ///
+    /// # Returns
+    /// Command to spawn plus any scratch directory to remove afterwards
+    fn session_command(
+        &self,
+        argv: &[String],
+        limits: &ResourceLimits,
+    ) -> BackendResult<SessionLaunch> {
+        let _ = (argv, limits);
+        Err(BackendError::NotAvailable {
+            backend: self.backend_type(),
+            reason: "persistent sessions are not supported".to_string(),
+        })
+    }
+
     /// Perform health check on this backend
     ///
     /// Verifies that the backend is available and functional.
@@ -88,6 +156,10 @@ pub struct ExecutionRequest {
     /// Optional input data for the code
     pub input: Option<String>,
 
+    /// Raw bytes written to the program's stdin (takes precedence over `input`)
+    #[serde(default)]
+    pub stdin: Option<Vec<u8>>,
+
     /// Environment variables to set
     pub env_vars: HashMap<String, String>,
 
@@ -100,6 +172,18 @@ pub struct ExecutionRequest {
     /// Resource limits
     pub limits: ResourceLimits,
 
+    /// Network egress policy (falls back to `BackendConfig::default_network`)
+    #[serde(default)]
+    pub network: Option<NetworkPolicy>,
+
+    /// Globs (relative to the working directory) of files to collect afterwards
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+
+    /// Always execute, bypassing the instance manager's result cache
+    #[serde(default)]
+    pub no_cache: bool,
+
     /// Backend-specific configuration
     pub backend_config: HashMap<String, String>,
 }
@@ -115,20 +199,48 @@ impl ExecutionRequest {
             code: code.into(),
             language: language.into(),
             input: None,
+            stdin: None,
             env_vars: HashMap::new(),
             working_dir: None,
             timeout: Duration::from_secs(30),
             limits: ResourceLimits::default(),
+            network: None,
+            artifacts: Vec::new(),
+            no_cache: false,
             backend_config: HashMap::new(),
         }
     }
 
+    /// Create a request for a source file, detecting the language from its extension
+    ///
+    /// # Returns
+    /// None if the extension does not map to a supported language
+    pub fn for_file<P: AsRef<std::path::Path>, C: Into<String>>(
+        path: P,
+        code: C,
+    ) -> Option<Self> {
+        Language::from_path(path).map(|language| Self::new(code, language.name()))
+    }
+
     /// Set input data for the execution
     pub fn with_input<I: Into<String>>(mut self, input: I) -> Self {
         self.input = Some(input.into());
         self
     }
 
+    /// Set raw stdin bytes for the execution
+    pub fn with_stdin<B: Into<Vec<u8>>>(mut self, stdin: B) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Bytes to feed to the program's stdin, if any
+    pub fn stdin_data(&self) -> Option<&[u8]> {
+        self.stdin
+            .as_deref()
+            .or_else(|| self.input.as_deref().map(str::as_bytes))
+    }
+
     /// Add environment variable
     pub fn with_env<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
         self.env_vars.insert(key.into(), value.into());
@@ -153,6 +265,34 @@ impl ExecutionRequest {
         self
     }
 
+    /// Set the network egress policy
+    pub fn with_network(mut self, network: NetworkPolicy) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Collect files matching `pattern` from the sandbox after execution
+    ///
+    /// `*` stays within one directory; `**` matches across directories.
+    pub fn with_artifacts<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.artifacts.push(pattern.into());
+        self
+    }
+
+    /// Never answer this request from the result cache
+    ///
+    /// For code with side effects or nondeterministic output, such as
+    /// network calls, clocks or random numbers.
+    pub fn without_cache(mut self) -> Self {
+        self.no_cache = true;
+        self
+    }
+
+    /// Network policy in effect for this request under `config`
+    pub fn network_policy<'a>(&'a self, config: &'a BackendConfig) -> &'a NetworkPolicy {
+        self.network.as_ref().unwrap_or(&config.default_network)
+    }
+
     /// Add backend-specific configuration
     pub fn with_backend_config<K: Into<String>, V: Into<String>>(
         mut self,
@@ -167,7 +307,7 @@ impl ExecutionRequest {
 /// Resource limits for execution
 ///
 /// Defines constraints on resource usage during code execution.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResourceLimits {
     /// Maximum memory usage in bytes
     pub max_memory: Option<u64>,
@@ -183,6 +323,22 @@ pub struct ResourceLimits {
 
     /// Maximum network bandwidth in bytes/sec
     pub max_network_bandwidth: Option<u64>,
+
+    /// Maximum captured stdout in bytes (excess is discarded)
+    #[serde(default)]
+    pub max_stdout_bytes: Option<u64>,
+
+    /// Maximum captured stderr in bytes (excess is discarded)
+    #[serde(default)]
+    pub max_stderr_bytes: Option<u64>,
+
+    /// Maximum size of a single collected artifact in bytes (larger files are skipped)
+    #[serde(default)]
+    pub max_artifact_bytes: Option<u64>,
+
+    /// Maximum combined size of collected artifacts in bytes
+    #[serde(default)]
+    pub max_artifacts_total_bytes: Option<u64>,
 }
 
 impl Default for ResourceLimits {
@@ -193,6 +349,10 @@ impl Default for ResourceLimits {
             max_processes: Some(10),                       // 10 processes
             max_file_size: Some(100 * 1024 * 1024),        // 100MB
             max_network_bandwidth: Some(10 * 1024 * 1024), // 10MB/s
+            max_stdout_bytes: Some(1024 * 1024),           // 1MB
+            max_stderr_bytes: Some(1024 * 1024),           // 1MB
+            max_artifact_bytes: Some(10 * 1024 * 1024),    // 10MB
+            max_artifacts_total_bytes: Some(50 * 1024 * 1024), // 50MB
         }
     }
 }
@@ -219,6 +379,110 @@ pub struct ExecutionResult {
 
     /// Any backend-specific metadata
     pub metadata: HashMap<String, String>,
+
+    /// Limit that terminated or truncated the execution, if any
+    #[serde(default)]
+    pub limit_exceeded: Option<LimitExceeded>,
+
+    /// Files collected from the sandbox per `ExecutionRequest::artifacts`
+    #[serde(default)]
+    pub artifacts: Vec<Artifact>,
+
+    /// Compiler diagnostics when the source failed to compile
+    #[serde(default)]
+    pub compile_error: Option<String>,
+
+    /// Signal that terminated the process, when the backend can observe it
+    #[serde(default)]
+    pub signal: Option<i32>,
+
+    /// Captured stdout was cut at `max_stdout_bytes`
+    #[serde(default)]
+    pub stdout_truncated: bool,
+
+    /// Captured stderr was cut at `max_stderr_bytes`
+    #[serde(default)]
+    pub stderr_truncated: bool,
+
+    /// Seccomp profile whose filter killed the process, if any
+    #[serde(default)]
+    pub seccomp_violation: Option<String>,
+}
+
+/// Signal sent by seccomp when a filtered syscall is attempted (Linux numbering;
+/// every sandbox runs a Linux guest)
+pub(crate) const SIGSYS: i32 = 31;
+
+/// Machine-readable reason an execution failed
+///
+/// See [`ExecutionResult::failure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// The source did not compile
+    CompileError,
+    /// The program exited non-zero or was killed by a signal
+    RuntimeError,
+    /// The wall-clock timeout or CPU time limit was reached
+    Timeout,
+    /// The memory limit was reached
+    Oom,
+    /// A sandbox quota or syscall policy stopped the program
+    PolicyViolation,
+}
+
+impl FailureKind {
+    /// Stable identifier, matching the serialized form
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CompileError => "compile_error",
+            Self::RuntimeError => "runtime_error",
+            Self::Timeout => "timeout",
+            Self::Oom => "oom",
+            Self::PolicyViolation => "policy_violation",
+        }
+    }
+}
+
+impl std::fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resource quota that an execution ran into
+///
+/// Reported in `ExecutionResult::limit_exceeded` so callers can tell a quota
+/// violation apart from an ordinary non-zero exit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitExceeded {
+    /// CPU time limit (`max_cpu_time`)
+    CpuTime,
+    /// Memory limit (`max_memory`)
+    Memory,
+    /// Wall-clock timeout (`ExecutionRequest::timeout`)
+    WallClock,
+    /// Captured stdout exceeded `max_stdout_bytes`
+    Stdout,
+    /// Captured stderr exceeded `max_stderr_bytes`
+    Stderr,
+    /// Process count limit (`max_processes`)
+    Processes,
+}
+
+impl LimitExceeded {
+    /// Human-readable reason
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::CpuTime => "CPU time limit exceeded",
+            Self::Memory => "memory limit exceeded",
+            Self::WallClock => "wall-clock timeout exceeded",
+            Self::Stdout => "stdout size limit exceeded",
+            Self::Stderr => "stderr size limit exceeded",
+            Self::Processes => "process limit exceeded",
+        }
+    }
 }
 
 impl ExecutionResult {
@@ -231,6 +495,13 @@ impl ExecutionResult {
             duration: Duration::from_millis(0),
             resource_usage: ResourceUsage::default(),
             metadata: HashMap::new(),
+            limit_exceeded: None,
+            artifacts: Vec::new(),
+            compile_error: None,
+            signal: None,
+            stdout_truncated: false,
+            stderr_truncated: false,
+            seccomp_violation: None,
         }
     }
 
@@ -243,12 +514,69 @@ impl ExecutionResult {
             duration: Duration::from_millis(0),
             resource_usage: ResourceUsage::default(),
             metadata: HashMap::new(),
+            limit_exceeded: None,
+            artifacts: Vec::new(),
+            compile_error: None,
+            signal: None,
+            stdout_truncated: false,
+            stderr_truncated: false,
+            seccomp_violation: None,
         }
     }
 
     /// Check if execution was successful
     pub fn is_success(&self) -> bool {
-        self.exit_code == 0
+        self.exit_code == 0 && self.limit_exceeded.is_none()
+    }
+
+    /// Why the execution failed, or `None` if it succeeded
+    ///
+    /// Compile errors win over everything else; an exceeded limit wins over
+    /// the exit status it caused. A process killed by `SIGSYS` broke the
+    /// syscall filter and counts as a policy violation.
+    pub fn failure(&self) -> Option<FailureKind> {
+        if self.compile_error.is_some() {
+            return Some(FailureKind::CompileError);
+        }
+        if let Some(limit) = self.limit_exceeded {
+            return Some(match limit {
+                LimitExceeded::WallClock | LimitExceeded::CpuTime => FailureKind::Timeout,
+                LimitExceeded::Memory => FailureKind::Oom,
+                LimitExceeded::Stdout | LimitExceeded::Stderr | LimitExceeded::Processes => {
+                    FailureKind::PolicyViolation
+                }
+            });
+        }
+        if self.termination_signal() == Some(SIGSYS) {
+            return Some(FailureKind::PolicyViolation);
+        }
+        (self.exit_code != 0 || self.signal.is_some()).then_some(FailureKind::RuntimeError)
+    }
+
+    /// Terminating signal, falling back to the shell's `128 + signal` exit code
+    pub fn termination_signal(&self) -> Option<i32> {
+        self.signal
+            .or_else(|| (self.exit_code > 128 && self.exit_code < 160).then_some(self.exit_code - 128))
+    }
+
+    /// Wall-clock time of the execution
+    pub fn wall_time(&self) -> Duration {
+        self.duration
+    }
+
+    /// CPU time consumed, as reported by the backend
+    pub fn cpu_time(&self) -> Duration {
+        Duration::from_millis(self.resource_usage.cpu_time_ms)
+    }
+
+    /// Peak memory usage in bytes
+    pub fn peak_memory(&self) -> u64 {
+        self.resource_usage.peak_memory
+    }
+
+    /// Whether stdout or stderr was cut at its size limit
+    pub fn is_truncated(&self) -> bool {
+        self.stdout_truncated || self.stderr_truncated
     }
 
     /// Get combined output (stdout + stderr)
@@ -263,6 +591,45 @@ impl ExecutionResult {
     }
 }
 
+/// Event emitted by `ExecutionBackend::execute_code_streaming`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    /// Chunk of standard output
+    Stdout { data: String },
+
+    /// Chunk of standard error
+    Stderr { data: String },
+
+    /// Execution finished; always the last event on the stream
+    Exit {
+        exit_code: i32,
+        duration: Duration,
+        resource_usage: ResourceUsage,
+        limit_exceeded: Option<LimitExceeded>,
+    },
+}
+
+impl ExecutionEvent {
+    /// Build the terminal event for a finished execution
+    pub fn exit(result: &ExecutionResult) -> Self {
+        Self::Exit {
+            exit_code: result.exit_code,
+            duration: result.duration,
+            resource_usage: result.resource_usage.clone(),
+            limit_exceeded: result.limit_exceeded,
+        }
+    }
+
+    /// Whether this is the terminal `Exit` event
+    pub fn is_exit(&self) -> bool {
+        matches!(self, Self::Exit { .. })
+    }
+}
+
+/// Receiver side of a streaming execution
+pub type ExecutionStream = tokio::sync::mpsc::Receiver<ExecutionEvent>;
+
 /// Resource usage statistics
 ///
 /// Tracks actual resource consumption during execution.
@@ -353,6 +720,14 @@ pub struct BackendConfig {
     /// Default resource limits
     pub default_limits: ResourceLimits,
 
+    /// Default network egress policy
+    #[serde(default)]
+    pub default_network: NetworkPolicy,
+
+    /// Syscall filters, applied by backends that support seccomp
+    #[serde(default)]
+    pub seccomp: SeccompConfig,
+
     /// Backend-specific configuration
     pub backend_specific: HashMap<String, String>,
 }
@@ -365,6 +740,8 @@ impl BackendConfig {
             enabled: true,
             default_timeout: Duration::from_secs(30),
             default_limits: ResourceLimits::default(),
+            default_network: NetworkPolicy::default(),
+            seccomp: SeccompConfig::default(),
             backend_specific: HashMap::new(),
         }
     }
@@ -387,6 +764,18 @@ impl BackendConfig {
         self
     }
 
+    /// Set default network egress policy
+    pub fn with_network(mut self, network: NetworkPolicy) -> Self {
+        self.default_network = network;
+        self
+    }
+
+    /// Set the syscall filters
+    pub fn with_seccomp(mut self, seccomp: SeccompConfig) -> Self {
+        self.seccomp = seccomp;
+        self
+    }
+
     /// Add backend-specific configuration
     pub fn with_config<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
         self.backend_specific.insert(key.into(), value.into());
@@ -503,6 +892,45 @@ pub mod firecracker;
 #[cfg(target_os = "linux")]
 pub use firecracker::FireCrackerBackend;
 
+#[cfg(target_os = "windows")]
+pub mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::WindowsBackend;
+
+// Resource quota enforcement shared by all backends
+pub mod limits;
+
+// Incremental output delivery for streaming executions
+pub mod streaming;
+
+// Multi-file project staging and dependency installation
+pub mod project;
+pub use project::{DependencyManifest, PackageManager, ProjectRequest};
+
+// Artifact collection after execution
+pub mod artifacts;
+pub use artifacts::{Artifact, ArtifactContent};
+
+// Language runtimes, detection and compile-then-run scripts
+pub mod languages;
+pub use languages::Language;
+
+// Network egress policy and its nftables/cgroup enforcement
+pub mod network;
+pub use network::NetworkPolicy;
+
+// Named sandbox profiles combining network, file system and shell posture
+pub mod profile;
+pub use profile::{FsRoot, SandboxProfile, ShellPolicy};
+
+// Seccomp-BPF syscall profiles
+pub mod seccomp;
+pub use seccomp::{SeccompAction, SeccompConfig, SeccompProfile};
+
+// Persistent interpreter sessions (REPL mode)
+pub mod session;
+pub use session::{ReplSession, SessionHandle, SessionLaunch};
+
 // SweetMCP plugin backend (available on all platforms)
 pub mod sweetmcp_plugin;
 pub use sweetmcp_plugin::SweetMcpPluginBackend;
@@ -541,6 +969,12 @@ pub fn create_backend(
             Ok(Box::new(backend))
         }
 
+        #[cfg(target_os = "windows")]
+        crate::execution_env::Cylo::Windows(sandbox_root) => {
+            let backend = WindowsBackend::new(sandbox_root.clone(), config)?;
+            Ok(Box::new(backend))
+        }
+
         // Platform-specific error handling
         #[cfg(not(target_os = "macos"))]
         crate::execution_env::Cylo::Apple(_) => Err(CyloError::platform_unsupported(
@@ -560,6 +994,12 @@ pub fn create_backend(
             "FireCracker is only available on Linux",
         )),
 
+        #[cfg(not(target_os = "windows"))]
+        crate::execution_env::Cylo::Windows(_) => Err(CyloError::platform_unsupported(
+            "Windows",
+            "Job Object and AppContainer sandboxing is only available on Windows",
+        )),
+
         crate::execution_env::Cylo::SweetMcpPlugin(plugin_path) => {
             let backend = SweetMcpPluginBackend::new(plugin_path.clone().into(), config)?;
             Ok(Box::new(backend))
@@ -583,6 +1023,9 @@ pub fn available_backends() -> Vec<&'static str> {
         backends.push("FireCracker");
     }
 
+    #[cfg(target_os = "windows")]
+    backends.push("Windows");
+
     backends
 }
 
@@ -609,6 +1052,25 @@ mod tests {
         assert_eq!(request.working_dir, Some("/tmp".to_string()));
     }
 
+    #[test]
+    fn request_network_overrides_config() {
+        let config = BackendConfig::new("test").with_network(NetworkPolicy::DenyAll);
+        let request = ExecutionRequest::new("print(1)", "python");
+        assert_eq!(request.network_policy(&config), &NetworkPolicy::DenyAll);
+
+        let request = request.with_network(NetworkPolicy::Full);
+        assert_eq!(request.network_policy(&config), &NetworkPolicy::Full);
+    }
+
+    #[test]
+    fn stdin_prefers_raw_bytes() {
+        let request = ExecutionRequest::new("cat", "bash").with_input("text");
+        assert_eq!(request.stdin_data(), Some("text".as_bytes()));
+
+        let request = request.with_stdin(vec![0u8, 1, 2]);
+        assert_eq!(request.stdin_data(), Some(&[0u8, 1, 2][..]));
+    }
+
     #[test]
     fn execution_result_success() {
         let result = ExecutionResult::success("Hello, World!");
@@ -625,6 +1087,32 @@ mod tests {
         assert_eq!(result.stderr, "Error occurred");
     }
 
+    #[test]
+    fn execution_result_failure_kind() {
+        assert_eq!(ExecutionResult::success("ok").failure(), None);
+        assert_eq!(
+            ExecutionResult::failure(1, "boom").failure(),
+            Some(FailureKind::RuntimeError)
+        );
+
+        let mut result = ExecutionResult::failure(137, "");
+        result.limit_exceeded = Some(LimitExceeded::Memory);
+        assert_eq!(result.failure(), Some(FailureKind::Oom));
+
+        result.limit_exceeded = Some(LimitExceeded::WallClock);
+        assert_eq!(result.failure(), Some(FailureKind::Timeout));
+
+        result.compile_error = Some("error[E0425]".to_string());
+        assert_eq!(result.failure(), Some(FailureKind::CompileError));
+
+        let seccomp_kill = ExecutionResult::failure(128 + SIGSYS, "Bad system call");
+        assert_eq!(seccomp_kill.failure(), Some(FailureKind::PolicyViolation));
+        assert_eq!(
+            serde_json::to_value(FailureKind::PolicyViolation).unwrap(),
+            "policy_violation"
+        );
+    }
+
     #[test]
     fn health_status_creation() {
         let healthy = HealthStatus::healthy("All systems operational")
@@ -665,5 +1153,8 @@ mod tests {
             assert!(backends.contains(&"LandLock"));
             assert!(backends.contains(&"FireCracker"));
         }
+
+        #[cfg(target_os = "windows")]
+        assert!(backends.contains(&"Windows"));
     }
 }
@@ -0,0 +1,366 @@
+// ============================================================================
+// File: packages/cylo/src/backends/network.rs
+// ----------------------------------------------------------------------------
+// Network egress policy for Cylo executions.
+//
+// Provides:
+// - NetworkPolicy (deny-all, allow-list of hosts/CIDRs, full egress)
+// - CIDR parsing and host resolution for allow-lists
+// - nftables rulesets scoped to a cgroup (LandLock) or tap device (FireCracker)
+// - RAII guards that remove tables and cgroups when an execution ends
+// ============================================================================
+
+use std::fmt;
+use std::io::Write;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{BackendError, BackendResult};
+
+/// Root of the cgroup v2 hierarchy used for per-execution scopes
+const CGROUP_ROOT: &str = "/sys/fs/cgroup/cylo";
+
+/// Network egress allowed for an execution
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum NetworkPolicy {
+    /// No network access beyond loopback
+    DenyAll,
+
+    /// Egress only to the listed hosts and CIDR ranges
+    AllowList {
+        /// Hostnames resolved when the execution starts
+        #[serde(default)]
+        hosts: Vec<String>,
+        /// CIDR ranges such as `10.0.0.0/8` or `2001:db8::/32`
+        #[serde(default)]
+        cidrs: Vec<String>,
+    },
+
+    /// Unrestricted egress (historical behaviour)
+    #[default]
+    Full,
+}
+
+impl NetworkPolicy {
+    /// Build an allow-list policy
+    pub fn allow_list<H, C>(hosts: H, cidrs: C) -> Self
+    where
+        H: IntoIterator,
+        H::Item: Into<String>,
+        C: IntoIterator,
+        C::Item: Into<String>,
+    {
+        Self::AllowList {
+            hosts: hosts.into_iter().map(Into::into).collect(),
+            cidrs: cidrs.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether the policy permits any egress at all
+    pub fn allows_egress(&self) -> bool {
+        !matches!(self, Self::DenyAll)
+    }
+
+    /// Resolve an allow-list into concrete CIDR ranges
+    ///
+    /// Returns an empty list for `DenyAll` and `Full`.
+    pub fn resolve(&self) -> BackendResult<Vec<Cidr>> {
+        let Self::AllowList { hosts, cidrs } = self else {
+            return Ok(Vec::new());
+        };
+
+        let mut resolved = cidrs
+            .iter()
+            .map(|cidr| cidr.parse::<Cidr>())
+            .collect::<BackendResult<Vec<_>>>()?;
+
+        for host in hosts {
+            let addrs = (host.as_str(), 0)
+                .to_socket_addrs()
+                .map_err(|e| BackendError::NetworkFailed {
+                    details: format!("Failed to resolve allow-listed host '{host}': {e}"),
+                })?;
+            resolved.extend(addrs.map(|addr| Cidr::host(addr.ip())));
+        }
+
+        resolved.sort_by_key(|cidr| cidr.to_string());
+        resolved.dedup();
+        Ok(resolved)
+    }
+}
+
+/// IPv4 or IPv6 network range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// Single-address range
+    pub fn host(addr: IpAddr) -> Self {
+        let prefix = if addr.is_ipv4() { 32 } else { 128 };
+        Self { addr, prefix }
+    }
+
+    /// Whether this is an IPv4 range
+    pub fn is_ipv4(&self) -> bool {
+        self.addr.is_ipv4()
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = BackendError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || BackendError::InvalidConfig {
+            backend: "Network",
+            details: format!("invalid CIDR '{value}'"),
+        };
+
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (value, None),
+        };
+
+        let addr: IpAddr = addr.trim().parse().map_err(|_| invalid())?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.trim().parse::<u8>().map_err(|_| invalid())?,
+            None => max,
+        };
+
+        if prefix > max {
+            return Err(invalid());
+        }
+
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// Where an nftables ruleset applies
+#[derive(Debug, Clone)]
+pub enum NftScope {
+    /// Sockets owned by processes in a cgroup (host network namespace)
+    Cgroup(String),
+    /// Traffic forwarded from a tap device (VM guest)
+    Interface(String),
+}
+
+impl NftScope {
+    fn hook(&self) -> &'static str {
+        match self {
+            Self::Cgroup(_) => "output",
+            Self::Interface(_) => "forward",
+        }
+    }
+
+    fn matcher(&self) -> String {
+        match self {
+            Self::Cgroup(path) => {
+                let level = path.split('/').filter(|part| !part.is_empty()).count();
+                format!("socket cgroupv2 level {level} \"{path}\"")
+            }
+            Self::Interface(name) => format!("iifname \"{name}\""),
+        }
+    }
+}
+
+/// Render an nftables ruleset restricting `scope` to `allowed`
+///
+/// An empty `allowed` list drops all egress from the scope.
+pub fn nft_ruleset(table: &str, scope: &NftScope, allowed: &[Cidr]) -> String {
+    let matcher = scope.matcher();
+    let elements = |v4: bool| {
+        allowed
+            .iter()
+            .filter(|cidr| cidr.is_ipv4() == v4)
+            .map(Cidr::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (v4, v6) = (elements(true), elements(false));
+
+    let mut rules = format!(
+        "table inet {table} {{\n  chain egress {{\n    type filter hook {} priority 0; policy accept;\n",
+        scope.hook()
+    );
+    rules.push_str(&format!("    {matcher} ct state established,related accept\n"));
+    if !v4.is_empty() {
+        rules.push_str(&format!("    {matcher} ip daddr {{ {v4} }} accept\n"));
+    }
+    if !v6.is_empty() {
+        rules.push_str(&format!("    {matcher} ip6 daddr {{ {v6} }} accept\n"));
+    }
+    rules.push_str(&format!("    {matcher} drop\n  }}\n}}\n"));
+    rules
+}
+
+/// nftables table removed when dropped
+#[derive(Debug)]
+pub struct NftTable {
+    name: String,
+}
+
+impl NftTable {
+    /// Load a ruleset restricting `scope` to `allowed`
+    ///
+    /// # Arguments
+    /// * `id` - Unique execution identifier used to name the table
+    /// * `scope` - Cgroup or interface the rules match
+    /// * `allowed` - Destinations that remain reachable
+    pub fn install(id: &str, scope: &NftScope, allowed: &[Cidr]) -> BackendResult<Self> {
+        let name = format!("cylo_{}", id.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+        let ruleset = nft_ruleset(&name, scope, allowed);
+
+        let mut child = Command::new("nft")
+            .args(["-f", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| BackendError::NetworkFailed {
+                details: format!("Failed to run nft: {e}"),
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(ruleset.as_bytes())
+                .map_err(|e| BackendError::NetworkFailed {
+                    details: format!("Failed to pass ruleset to nft: {e}"),
+                })?;
+        }
+
+        let output = child.wait_with_output().map_err(|e| BackendError::NetworkFailed {
+            details: format!("Failed to wait for nft: {e}"),
+        })?;
+        if !output.status.success() {
+            return Err(BackendError::NetworkFailed {
+                details: format!(
+                    "nft rejected network policy: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            });
+        }
+
+        Ok(Self { name })
+    }
+}
+
+impl Drop for NftTable {
+    fn drop(&mut self) {
+        let _ = Command::new("nft")
+            .args(["delete", "table", "inet", &self.name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Per-execution cgroup v2 scope, removed when dropped
+#[derive(Debug)]
+pub struct CgroupScope {
+    path: PathBuf,
+    relative: String,
+}
+
+impl CgroupScope {
+    /// Create `/sys/fs/cgroup/cylo/<id>`
+    pub fn create(id: &str) -> BackendResult<Self> {
+        let path = Path::new(CGROUP_ROOT).join(id);
+        std::fs::create_dir_all(&path).map_err(|e| BackendError::NetworkFailed {
+            details: format!("Failed to create cgroup {}: {e}", path.display()),
+        })?;
+
+        Ok(Self {
+            path,
+            relative: format!("cylo/{id}"),
+        })
+    }
+
+    /// Path relative to the cgroup root, as matched by nftables
+    pub fn relative_path(&self) -> &str {
+        &self.relative
+    }
+
+    /// Move the spawned process into this cgroup before it execs
+    #[cfg(unix)]
+    pub fn attach(&self, cmd: &mut Command) {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::process::CommandExt;
+
+        let mut procs = self.path.join("cgroup.procs").as_os_str().as_bytes().to_vec();
+        procs.push(0);
+
+        // SAFETY: only async-signal-safe libc calls on a buffer prepared before fork
+        unsafe {
+            cmd.pre_exec(move || {
+                let fd = libc::open(procs.as_ptr().cast(), libc::O_WRONLY);
+                if fd < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                // Writing "0" moves the writing process itself
+                let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+                libc::close(fd);
+                if written != 1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+impl Drop for CgroupScope {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cidrs() {
+        assert_eq!("10.0.0.0/8".parse::<Cidr>().unwrap().to_string(), "10.0.0.0/8");
+        assert_eq!("1.2.3.4".parse::<Cidr>().unwrap().to_string(), "1.2.3.4/32");
+        assert_eq!("2001:db8::/32".parse::<Cidr>().unwrap().to_string(), "2001:db8::/32");
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("not-an-ip".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn deny_all_ruleset_drops_everything() {
+        let rules = nft_ruleset("cylo_t", &NftScope::Interface("tap0".to_string()), &[]);
+        assert!(rules.contains("hook forward"));
+        assert!(rules.contains("iifname \"tap0\" drop"));
+        assert!(!rules.contains("daddr"));
+    }
+
+    #[test]
+    fn allow_list_ruleset_accepts_ranges() {
+        let policy = NetworkPolicy::allow_list(Vec::<String>::new(), ["10.1.0.0/16", "::1"]);
+        let allowed = policy.resolve().unwrap();
+        let rules = nft_ruleset("cylo_t", &NftScope::Cgroup("cylo/abc".to_string()), &allowed);
+        assert!(rules.contains("socket cgroupv2 level 2 \"cylo/abc\" ip daddr { 10.1.0.0/16 } accept"));
+        assert!(rules.contains("ip6 daddr { ::1/128 } accept"));
+    }
+
+    #[test]
+    fn default_is_full_egress() {
+        assert_eq!(NetworkPolicy::default(), NetworkPolicy::Full);
+        assert!(!NetworkPolicy::DenyAll.allows_egress());
+    }
+}
@@ -0,0 +1,392 @@
+// ============================================================================
+// File: packages/cylo/src/backends/profile.rs
+// ----------------------------------------------------------------------------
+// Named sandbox postures shared by Cylo and the tools it hosts.
+//
+// Provides:
+// - SandboxProfile (network policy, file system roots and shell restrictions
+//   under one name)
+// - Built-in profiles such as "read-only-workspace" and
+//   "network-isolated-build"
+// - Propagation into BackendConfig and into plugin configuration keys
+// ============================================================================
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{BackendConfig, BackendError, BackendResult, NetworkPolicy};
+
+/// `BackendConfig::backend_specific` key holding the serialized profile
+pub const PROFILE_CONFIG_KEY: &str = "sandbox_profile";
+
+/// Plugin configuration key holding the profile name
+pub const PLUGIN_PROFILE_KEY: &str = "sandbox_profile";
+
+/// Plugin configuration key holding the shell policy as JSON
+pub const PLUGIN_SHELL_KEY: &str = "sandbox_shell";
+
+/// Plugin configuration key holding the file system roots as JSON
+pub const PLUGIN_FS_ROOTS_KEY: &str = "sandbox_fs_roots";
+
+/// Names of the built-in profiles
+pub const BUILTIN_PROFILES: &[&str] = &["read-only-workspace", "network-isolated-build"];
+
+/// Commands the read-only workspace profile lets shell tools run
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "cat", "ls", "grep", "rg", "find", "head", "tail", "wc", "stat", "file", "du", "tree", "sort",
+    "uniq", "diff", "echo", "pwd",
+];
+
+/// Directory tools may use
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsRoot {
+    /// Host path, visible to plugins under the same path
+    pub path: PathBuf,
+    /// Whether tools may modify files under the root
+    #[serde(default)]
+    pub writable: bool,
+}
+
+impl FsRoot {
+    /// Root tools may only read
+    pub fn read_only<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            writable: false,
+        }
+    }
+
+    /// Root tools may read and modify
+    pub fn writable<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            writable: true,
+        }
+    }
+}
+
+/// Restrictions on shell tools such as eval-sh and `execute_bash`
+///
+/// Commands are screened before they run. This is a guard against
+/// accidents, not a security boundary: isolation comes from the profile's
+/// network policy and file system roots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellPolicy {
+    /// Whether shell commands may run at all
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Programs commands may invoke; empty allows any
+    #[serde(default)]
+    pub allowed_commands: Vec<String>,
+    /// Whether output may be redirected into files
+    #[serde(default = "default_true")]
+    pub allow_writes: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ShellPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_commands: Vec::new(),
+            allow_writes: true,
+        }
+    }
+}
+
+impl ShellPolicy {
+    /// Policy refusing every command
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Check a command line against the policy
+    ///
+    /// # Returns
+    /// The reason the command is refused, if it is
+    pub fn check(&self, command: &str) -> Result<(), String> {
+        if !self.enabled {
+            return Err("shell commands are disabled by the sandbox profile".to_string());
+        }
+
+        if !self.allow_writes
+            && let Some(target) = file_redirect(command)
+        {
+            return Err(format!(
+                "writing to '{target}' is not allowed by the sandbox profile"
+            ));
+        }
+
+        if self.allowed_commands.is_empty() {
+            return Ok(());
+        }
+
+        // Substitutions would run programs the allow-list never sees
+        if command.contains("$(") || command.contains('`') {
+            return Err("command substitution is not allowed by the sandbox profile".to_string());
+        }
+        // `2>&1` is a redirection, not a background `&`
+        let command = command.replace(">&", ">");
+        for segment in command.split(['|', ';', '&', '\n']) {
+            let Some(program) = program_name(segment) else {
+                continue;
+            };
+            if !self
+                .allowed_commands
+                .iter()
+                .any(|allowed| allowed == program)
+            {
+                return Err(format!(
+                    "'{program}' is not an allowed command under the sandbox profile"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// First file a command line redirects output into, other than `/dev/null`
+fn file_redirect(command: &str) -> Option<String> {
+    let mut rest = command;
+    while let Some(index) = rest.find('>') {
+        rest = rest[index..].trim_start_matches('>');
+        // `2>&1` duplicates a descriptor rather than opening a file
+        if rest.starts_with('&') {
+            continue;
+        }
+        let target: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| !c.is_whitespace() && !matches!(c, ';' | '|' | '&'))
+            .collect();
+        if target != "/dev/null" {
+            return Some(target);
+        }
+    }
+    None
+}
+
+/// Program a pipeline segment runs, skipping leading `NAME=value` assignments
+fn program_name(segment: &str) -> Option<&str> {
+    let word = segment
+        .split_whitespace()
+        .find(|word| !(word.contains('=') && !word.starts_with('=')))?;
+    Some(word.rsplit('/').next().unwrap_or(word))
+}
+
+/// Network, file system and shell posture for every tool of an agent
+///
+/// One profile is applied everywhere: its network policy becomes the
+/// backends' default egress policy, its roots become the directories WASM
+/// plugins may open, and its shell policy screens shell commands. Plugins also
+/// receive the profile as configuration under the `sandbox_*` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxProfile {
+    /// Profile name, reported to tools
+    pub name: String,
+    /// Network egress allowed
+    #[serde(default)]
+    pub network: NetworkPolicy,
+    /// Directories tools may use; empty grants none
+    #[serde(default)]
+    pub fs_roots: Vec<FsRoot>,
+    /// Restrictions on shell tools
+    #[serde(default)]
+    pub shell: ShellPolicy,
+}
+
+impl SandboxProfile {
+    /// Profile with full egress, no file system roots and an unrestricted shell
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            network: NetworkPolicy::Full,
+            fs_roots: Vec::new(),
+            shell: ShellPolicy::default(),
+        }
+    }
+
+    /// Inspect `workspace` without changing it or reaching the network
+    ///
+    /// The workspace is mounted read-only and shell tools are limited to
+    /// commands that read files.
+    pub fn read_only_workspace<P: Into<PathBuf>>(workspace: P) -> Self {
+        Self::new("read-only-workspace")
+            .with_network(NetworkPolicy::DenyAll)
+            .with_fs_root(FsRoot::read_only(workspace))
+            .with_shell(ShellPolicy {
+                enabled: true,
+                allowed_commands: READ_ONLY_COMMANDS.iter().map(|c| c.to_string()).collect(),
+                allow_writes: false,
+            })
+    }
+
+    /// Build inside `workspace` with no network access
+    ///
+    /// The workspace is writable and shell tools may run any command.
+    pub fn network_isolated_build<P: Into<PathBuf>>(workspace: P) -> Self {
+        Self::new("network-isolated-build")
+            .with_network(NetworkPolicy::DenyAll)
+            .with_fs_root(FsRoot::writable(workspace))
+    }
+
+    /// Built-in profile called `name`, rooted at `workspace`
+    ///
+    /// # Returns
+    /// None if no built-in profile has that name (see [`BUILTIN_PROFILES`])
+    pub fn builtin<P: Into<PathBuf>>(name: &str, workspace: P) -> Option<Self> {
+        match name {
+            "read-only-workspace" => Some(Self::read_only_workspace(workspace)),
+            "network-isolated-build" => Some(Self::network_isolated_build(workspace)),
+            _ => None,
+        }
+    }
+
+    /// Set the network egress policy
+    pub fn with_network(mut self, network: NetworkPolicy) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Add a directory tools may use
+    pub fn with_fs_root(mut self, root: FsRoot) -> Self {
+        self.fs_roots.push(root);
+        self
+    }
+
+    /// Set the shell restrictions
+    pub fn with_shell(mut self, shell: ShellPolicy) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    /// Whether tools may access `path`, and modify it when `write` is set
+    ///
+    /// `..` components are resolved lexically, without touching the disk.
+    pub fn permits_path<P: AsRef<Path>>(&self, path: P, write: bool) -> bool {
+        let path = normalize(path.as_ref());
+        self.fs_roots
+            .iter()
+            .any(|root| (root.writable || !write) && path.starts_with(normalize(&root.path)))
+    }
+
+    /// Backend configuration enforcing the profile
+    ///
+    /// The profile's network policy becomes the default egress policy, and
+    /// the profile itself is carried for backends that apply the rest of it.
+    pub fn backend_config<N: Into<String>>(&self, name: N) -> BackendConfig {
+        let config = BackendConfig::new(name).with_network(self.network.clone());
+        match serde_json::to_string(self) {
+            Ok(profile) => config.with_config(PROFILE_CONFIG_KEY, profile),
+            Err(_) => config,
+        }
+    }
+
+    /// Profile carried by a backend configuration, if any
+    ///
+    /// # Errors
+    /// Returns `BackendError::InvalidConfig` if the carried profile is malformed
+    pub fn from_backend_config(config: &BackendConfig) -> BackendResult<Option<Self>> {
+        let Some(profile) = config.backend_specific.get(PROFILE_CONFIG_KEY) else {
+            return Ok(None);
+        };
+        serde_json::from_str(profile)
+            .map(Some)
+            .map_err(|e| BackendError::InvalidConfig {
+                backend: "SandboxProfile",
+                details: format!("invalid sandbox profile: {e}"),
+            })
+    }
+
+    /// Configuration keys passed to plugins running under the profile
+    pub fn plugin_config(&self) -> Vec<(&'static str, String)> {
+        let mut config = vec![(PLUGIN_PROFILE_KEY, self.name.clone())];
+        if let Ok(shell) = serde_json::to_string(&self.shell) {
+            config.push((PLUGIN_SHELL_KEY, shell));
+        }
+        if let Ok(roots) = serde_json::to_string(&self.fs_roots) {
+            config.push((PLUGIN_FS_ROOTS_KEY, roots));
+        }
+        config
+    }
+}
+
+/// Resolve `.` and `..` components without consulting the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_workspace_screens_shell_commands() {
+        let profile = SandboxProfile::read_only_workspace("/work");
+        let shell = &profile.shell;
+        assert!(shell.check("ls -la | grep src 2>&1").is_ok());
+        assert!(shell.check("FOO=1 /usr/bin/cat a.txt 2>/dev/null").is_ok());
+        assert!(shell.check("rm -rf build").is_err());
+        assert!(shell.check("ls; curl example.com").is_err());
+        assert!(shell.check("echo $(whoami)").is_err());
+        assert!(shell.check("echo hi > notes.txt").is_err());
+        assert!(ShellPolicy::disabled().check("ls").is_err());
+        assert!(ShellPolicy::default().check("echo hi >> notes.txt").is_ok());
+    }
+
+    #[test]
+    fn roots_limit_paths_and_writes() {
+        let profile = SandboxProfile::read_only_workspace("/work");
+        assert!(profile.permits_path("/work/src/lib.rs", false));
+        assert!(!profile.permits_path("/work/src/lib.rs", true));
+        assert!(!profile.permits_path("/work/../etc/passwd", false));
+
+        let build = SandboxProfile::network_isolated_build("/work");
+        assert!(build.permits_path("/work/target", true));
+        assert!(!build.network.allows_egress());
+        assert!(SandboxProfile::new("empty").fs_roots.is_empty());
+    }
+
+    #[test]
+    fn profile_travels_in_backend_config() {
+        let profile = SandboxProfile::builtin("network-isolated-build", "/work").unwrap();
+        let config = profile.backend_config("LandLock");
+        assert_eq!(config.default_network, NetworkPolicy::DenyAll);
+        assert_eq!(
+            SandboxProfile::from_backend_config(&config).unwrap(),
+            Some(profile.clone())
+        );
+        assert_eq!(
+            SandboxProfile::from_backend_config(&BackendConfig::new("LandLock")).unwrap(),
+            None
+        );
+
+        let keys: Vec<_> = profile
+            .plugin_config()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(
+            keys,
+            [PLUGIN_PROFILE_KEY, PLUGIN_SHELL_KEY, PLUGIN_FS_ROOTS_KEY]
+        );
+        assert!(SandboxProfile::builtin("unknown", "/work").is_none());
+    }
+}
@@ -0,0 +1,389 @@
+// ============================================================================
+// File: packages/cylo/src/backends/project.rs
+// ----------------------------------------------------------------------------
+// Multi-file project execution for Cylo backends.
+//
+// Provides:
+// - ProjectRequest describing a source tree, entrypoint, dependencies and network
+// - Path validation so staged files cannot escape the project directory
+// - A self-contained staging script (write files, install deps, run entrypoint)
+//   that every shell-capable backend can execute unchanged
+// ============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{
+    BackendError, BackendResult, ExecutionRequest, NetworkPolicy, ResourceLimits,
+};
+
+/// Exit code reported when the dependency installation step fails
+pub const INSTALL_FAILED_EXIT_CODE: i32 = 97;
+
+/// Directory that pip installs into, added to PYTHONPATH for the entrypoint
+const PYTHON_DEPS_DIR: &str = ".cylo-deps";
+
+/// Multi-file project execution request
+///
+/// Files are staged into a fresh directory inside the sandbox, the optional
+/// dependency manifest is installed, and then the entrypoint is executed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRequest {
+    /// Relative path → file content
+    pub files: BTreeMap<String, String>,
+
+    /// Relative path of the file to execute
+    pub entrypoint: String,
+
+    /// Programming language (python, javascript, rust, go, bash)
+    pub language: String,
+
+    /// Dependency manifest installed before the entrypoint runs
+    #[serde(default)]
+    pub dependencies: Option<DependencyManifest>,
+
+    /// Network egress for the whole run
+    ///
+    /// Allow-list the package registries to permit installation while
+    /// blocking other egress. Under `DenyAll` installs run offline.
+    #[serde(default)]
+    pub network: NetworkPolicy,
+
+    /// Time budget for the install step (added to `timeout`)
+    pub install_timeout: Duration,
+
+    /// Optional stdin data for the entrypoint
+    #[serde(default)]
+    pub stdin: Option<Vec<u8>>,
+
+    /// Environment variables to set
+    pub env_vars: HashMap<String, String>,
+
+    /// Execution timeout for the entrypoint
+    pub timeout: Duration,
+
+    /// Resource limits
+    pub limits: ResourceLimits,
+}
+
+/// Dependency manifest for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyManifest {
+    /// Package manager that installs the manifest
+    pub manager: PackageManager,
+
+    /// Manifest content (requirements.txt, package.json or Cargo.toml)
+    pub content: String,
+}
+
+/// Supported package managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    /// `pip install -r requirements.txt`
+    Pip,
+    /// `npm install` from package.json
+    Npm,
+    /// `cargo fetch` from Cargo.toml
+    Cargo,
+}
+
+impl PackageManager {
+    /// File name the manifest is staged as
+    pub fn manifest_file(&self) -> &'static str {
+        match self {
+            Self::Pip => "requirements.txt",
+            Self::Npm => "package.json",
+            Self::Cargo => "Cargo.toml",
+        }
+    }
+
+    /// Shell command that installs the manifest
+    fn install_command(&self, network: &NetworkPolicy) -> String {
+        let offline = !network.allows_egress();
+        match self {
+            Self::Pip => format!(
+                "python3 -m pip install --quiet --disable-pip-version-check --target {PYTHON_DEPS_DIR} {}-r requirements.txt",
+                if offline { "--no-index " } else { "" }
+            ),
+            Self::Npm => format!(
+                "npm install --no-audit --no-fund --loglevel=error{}",
+                if offline { " --offline" } else { "" }
+            ),
+            Self::Cargo => format!("cargo fetch --quiet{}", if offline { " --offline" } else { "" }),
+        }
+    }
+}
+
+impl ProjectRequest {
+    /// Create a new project request
+    ///
+    /// # Arguments
+    /// * `entrypoint` - Relative path of the file to execute
+    /// * `language` - Programming language
+    pub fn new<E: Into<String>, L: Into<String>>(entrypoint: E, language: L) -> Self {
+        Self {
+            files: BTreeMap::new(),
+            entrypoint: entrypoint.into(),
+            language: language.into(),
+            dependencies: None,
+            network: NetworkPolicy::default(),
+            install_timeout: Duration::from_secs(120),
+            stdin: None,
+            env_vars: HashMap::new(),
+            timeout: Duration::from_secs(30),
+            limits: ResourceLimits::default(),
+        }
+    }
+
+    /// Add a file to the project
+    pub fn with_file<P: Into<String>, C: Into<String>>(mut self, path: P, content: C) -> Self {
+        self.files.insert(path.into(), content.into());
+        self
+    }
+
+    /// Set the dependency manifest
+    pub fn with_dependencies<C: Into<String>>(mut self, manager: PackageManager, content: C) -> Self {
+        self.dependencies = Some(DependencyManifest {
+            manager,
+            content: content.into(),
+        });
+        self
+    }
+
+    /// Set the network egress policy
+    pub fn with_network(mut self, policy: NetworkPolicy) -> Self {
+        self.network = policy;
+        self
+    }
+
+    /// Set the install step time budget
+    pub fn with_install_timeout(mut self, timeout: Duration) -> Self {
+        self.install_timeout = timeout;
+        self
+    }
+
+    /// Set stdin data for the entrypoint
+    pub fn with_stdin<B: Into<Vec<u8>>>(mut self, stdin: B) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Add environment variable
+    pub fn with_env<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set entrypoint timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set resource limits
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Validate paths and entrypoint
+    pub fn validate(&self) -> BackendResult<()> {
+        for path in self.files.keys() {
+            validate_relative_path(path)?;
+        }
+        validate_relative_path(&self.entrypoint)?;
+
+        if !self.files.contains_key(&self.entrypoint) {
+            return Err(invalid(format!(
+                "entrypoint '{}' is not one of the project files",
+                self.entrypoint
+            )));
+        }
+
+        if let Some(manifest) = &self.dependencies
+            && self.files.contains_key(manifest.manager.manifest_file())
+        {
+            return Err(invalid(format!(
+                "'{}' is provided both as a file and as the dependency manifest",
+                manifest.manager.manifest_file()
+            )));
+        }
+
+        entrypoint_command(&self.language, &self.entrypoint, self.dependencies.as_ref())?;
+        Ok(())
+    }
+
+    /// Build the staging script that writes, installs and runs the project
+    pub fn to_script(&self) -> BackendResult<String> {
+        self.validate()?;
+
+        let mut script = String::from("set -e\n");
+        script.push_str("cd \"$(mktemp -d /tmp/cylo-project.XXXXXX)\"\n");
+
+        let mut files: Vec<(&str, &str)> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path.as_str(), content.as_str()))
+            .collect();
+        if let Some(manifest) = &self.dependencies {
+            files.push((manifest.manager.manifest_file(), manifest.content.as_str()));
+        }
+
+        for (path, content) in files {
+            if let Some(parent) = Path::new(path).parent()
+                && !parent.as_os_str().is_empty()
+            {
+                script.push_str(&format!("mkdir -p {}\n", shell_quote(&parent.to_string_lossy())));
+            }
+            script.push_str(&format!(
+                "printf '%s' {} > {}\n",
+                shell_quote(content),
+                shell_quote(path)
+            ));
+        }
+
+        if let Some(manifest) = &self.dependencies {
+            script.push_str(&format!(
+                "{} >&2 || {{ echo 'cylo: dependency installation failed' >&2; exit {INSTALL_FAILED_EXIT_CODE}; }}\n",
+                manifest.manager.install_command(&self.network)
+            ));
+        }
+
+        script.push_str("set +e\n");
+        script.push_str(&format!(
+            "exec {}\n",
+            entrypoint_command(&self.language, &self.entrypoint, self.dependencies.as_ref())?
+        ));
+
+        Ok(script)
+    }
+
+    /// Convert into a bash `ExecutionRequest` running the staging script
+    pub fn to_execution_request(&self) -> BackendResult<ExecutionRequest> {
+        let mut request = ExecutionRequest::new(self.to_script()?, "bash")
+            .with_timeout(self.timeout + self.install_timeout)
+            .with_limits(self.limits.clone());
+
+        request.stdin = self.stdin.clone();
+        request.env_vars = self.env_vars.clone();
+        request.network = Some(self.network.clone());
+        Ok(request)
+    }
+}
+
+/// Shell command that runs the entrypoint for `language`
+fn entrypoint_command(
+    language: &str,
+    entrypoint: &str,
+    dependencies: Option<&DependencyManifest>,
+) -> BackendResult<String> {
+    let entry = shell_quote(entrypoint);
+    let uses = |manager| dependencies.is_some_and(|d| d.manager == manager);
+
+    match language.to_lowercase().as_str() {
+        "python" | "python3" if uses(PackageManager::Pip) => {
+            Ok(format!("env PYTHONPATH={PYTHON_DEPS_DIR} python3 {entry}"))
+        }
+        "python" | "python3" => Ok(format!("python3 {entry}")),
+        "javascript" | "js" | "node" => Ok(format!("node {entry}")),
+        "rust" if uses(PackageManager::Cargo) => Ok("cargo run --quiet --offline".to_string()),
+        "rust" => Ok(format!("sh -c 'rustc -o ./main \"$0\" && ./main' {entry}")),
+        "go" => Ok(format!("go run {entry}")),
+        "typescript" | "ts" | "tsx" | "deno" => Ok(format!(
+            "sh -c 'if command -v deno >/dev/null 2>&1; then exec deno run -A \"$0\"; \
+             else exec tsx \"$0\"; fi' {entry}"
+        )),
+        "ruby" | "rb" => Ok(format!("ruby {entry}")),
+        // JDK 11+ compiles and runs single-file sources directly
+        "java" => Ok(format!("java {entry}")),
+        "c" => Ok(format!("sh -c 'cc -O2 -o ./main \"$0\" -lm && ./main' {entry}")),
+        "cpp" | "c++" | "cxx" => Ok(format!("sh -c 'c++ -O2 -o ./main \"$0\" && ./main' {entry}")),
+        "bash" | "sh" => Ok(format!("bash {entry}")),
+        _ => Err(BackendError::UnsupportedLanguage {
+            backend: "Project",
+            language: language.to_string(),
+        }),
+    }
+}
+
+/// Reject absolute paths and paths that escape the project root
+fn validate_relative_path(path: &str) -> BackendResult<()> {
+    if path.is_empty() {
+        return Err(invalid("empty file path".to_string()));
+    }
+
+    let escapes = Path::new(path).components().any(|component| {
+        !matches!(component, Component::Normal(_) | Component::CurDir)
+    });
+    if escapes {
+        return Err(invalid(format!(
+            "'{path}' must be relative and stay inside the project"
+        )));
+    }
+
+    Ok(())
+}
+
+fn invalid(details: String) -> BackendError {
+    BackendError::InvalidConfig {
+        backend: "Project",
+        details,
+    }
+}
+
+/// Single-quote a string for POSIX shells
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_escaping_paths() {
+        for path in ["../evil.py", "/etc/passwd", "a/../../b"] {
+            let request = ProjectRequest::new("main.py", "python")
+                .with_file("main.py", "print(1)")
+                .with_file(path, "x");
+            assert!(request.validate().is_err(), "{path} should be rejected");
+        }
+    }
+
+    #[test]
+    fn entrypoint_must_exist() {
+        let request = ProjectRequest::new("main.py", "python").with_file("lib.py", "");
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn script_stages_installs_and_runs() {
+        let request = ProjectRequest::new("app/main.py", "python")
+            .with_file("app/main.py", "print('it''s')")
+            .with_dependencies(PackageManager::Pip, "requests\n")
+            .with_network(NetworkPolicy::DenyAll);
+
+        let script = request.to_script().unwrap();
+        assert!(script.contains("mkdir -p 'app'"));
+        assert!(script.contains("> 'app/main.py'"));
+        assert!(script.contains("'print('\\''it'\\'''\\''s'\\'')'"));
+        assert!(script.contains("--no-index -r requirements.txt"));
+        assert!(script.ends_with("exec env PYTHONPATH=.cylo-deps python3 'app/main.py'\n"));
+    }
+
+    #[test]
+    fn execution_request_budget_includes_install() {
+        let request = ProjectRequest::new("main.js", "node")
+            .with_file("main.js", "console.log(1)")
+            .with_timeout(Duration::from_secs(10))
+            .with_install_timeout(Duration::from_secs(5));
+
+        let exec = request.to_execution_request().unwrap();
+        assert_eq!(exec.language, "bash");
+        assert_eq!(exec.timeout, Duration::from_secs(15));
+    }
+}
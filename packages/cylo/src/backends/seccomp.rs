@@ -0,0 +1,505 @@
+// ============================================================================
+// File: packages/cylo/src/backends/seccomp.rs
+// ----------------------------------------------------------------------------
+// Seccomp-BPF syscall filtering for sandboxed executions.
+//
+// Provides:
+// - SeccompProfile (named deny-list of syscalls and the action on a hit)
+// - SeccompConfig (default profile with per-language overrides)
+// - Classic BPF compilation of a profile for the host architecture
+// - Handing compiled filters to bubblewrap, which installs them right
+//   before it execs the sandboxed program
+// ============================================================================
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{BackendError, BackendResult, ExecutionResult, Language, SIGSYS};
+
+/// Syscalls denied by the default profile on every architecture
+///
+/// Debugging other processes, mounting, loading kernel code or BPF
+/// programs, rebooting, changing the clock and entering namespaces.
+const DANGEROUS_SYSCALLS: &[&str] = &[
+    "ptrace",
+    "process_vm_readv",
+    "process_vm_writev",
+    "mount",
+    "umount2",
+    "pivot_root",
+    "chroot",
+    "move_mount",
+    "open_tree",
+    "fsopen",
+    "fsconfig",
+    "fsmount",
+    "fspick",
+    "bpf",
+    "perf_event_open",
+    "kexec_load",
+    "kexec_file_load",
+    "init_module",
+    "finit_module",
+    "delete_module",
+    "reboot",
+    "swapon",
+    "swapoff",
+    "syslog",
+    "acct",
+    "settimeofday",
+    "clock_settime",
+    "clock_adjtime",
+    "adjtimex",
+    "keyctl",
+    "add_key",
+    "request_key",
+    "userfaultfd",
+    "unshare",
+    "setns",
+    "open_by_handle_at",
+    "name_to_handle_at",
+    "quotactl",
+];
+
+/// Port I/O syscalls, which only exist on x86
+#[cfg(target_arch = "x86_64")]
+const ARCH_DANGEROUS_SYSCALLS: &[&str] = &["iopl", "ioperm"];
+#[cfg(not(target_arch = "x86_64"))]
+const ARCH_DANGEROUS_SYSCALLS: &[&str] = &[];
+
+/// What the filter does when a denied syscall is attempted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeccompAction {
+    /// Kill the whole process with `SIGSYS`; reported as a violation
+    #[default]
+    KillProcess,
+    /// Fail the syscall with this errno and let the program carry on
+    Errno(u16),
+}
+
+/// Named set of syscalls a sandboxed program may not make
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeccompProfile {
+    /// Name reported in `ExecutionResult::seccomp_violation`
+    pub name: String,
+
+    /// Denied syscalls by name, as in `man 2 syscalls`
+    pub denied_syscalls: Vec<String>,
+
+    /// Action taken when a denied syscall is attempted
+    #[serde(default)]
+    pub action: SeccompAction,
+}
+
+impl Default for SeccompProfile {
+    fn default() -> Self {
+        Self::deny_dangerous()
+    }
+}
+
+impl SeccompProfile {
+    /// Empty profile; deny syscalls with `deny`
+    pub fn new<N: Into<String>>(name: N) -> Self {
+        Self {
+            name: name.into(),
+            denied_syscalls: Vec::new(),
+            action: SeccompAction::KillProcess,
+        }
+    }
+
+    /// Default profile denying syscalls sandboxed code has no business making
+    pub fn deny_dangerous() -> Self {
+        Self {
+            name: "deny-dangerous".to_string(),
+            denied_syscalls: DANGEROUS_SYSCALLS
+                .iter()
+                .chain(ARCH_DANGEROUS_SYSCALLS)
+                .map(|syscall| syscall.to_string())
+                .collect(),
+            action: SeccompAction::KillProcess,
+        }
+    }
+
+    /// Profile that installs no filter at all
+    pub fn unrestricted() -> Self {
+        Self::new("unrestricted")
+    }
+
+    /// Deny another syscall
+    pub fn deny<S: Into<String>>(mut self, syscall: S) -> Self {
+        let syscall = syscall.into();
+        if !self.denied_syscalls.contains(&syscall) {
+            self.denied_syscalls.push(syscall);
+        }
+        self
+    }
+
+    /// Allow a syscall the profile would otherwise deny
+    pub fn allow(mut self, syscall: &str) -> Self {
+        self.denied_syscalls.retain(|denied| denied != syscall);
+        self
+    }
+
+    /// Set the action taken on a denied syscall
+    pub fn with_action(mut self, action: SeccompAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Whether the profile denies nothing
+    pub fn is_unrestricted(&self) -> bool {
+        self.denied_syscalls.is_empty()
+    }
+
+    /// Compile to a classic BPF program for the host architecture
+    ///
+    /// The program kills processes of a foreign architecture (and x32 on
+    /// x86_64), applies `action` to the denied syscalls and allows the rest.
+    ///
+    /// # Returns
+    /// Raw `sock_filter` array, or error for unknown syscall names and
+    /// unsupported architectures
+    pub fn compile(&self) -> BackendResult<Vec<u8>> {
+        let audit_arch = AUDIT_ARCH.ok_or_else(|| BackendError::NotAvailable {
+            backend: "Seccomp",
+            reason: format!(
+                "seccomp filters are not supported on {}",
+                std::env::consts::ARCH
+            ),
+        })?;
+
+        let mut numbers = Vec::with_capacity(self.denied_syscalls.len());
+        for name in &self.denied_syscalls {
+            let number = syscall_number(name).ok_or_else(|| BackendError::InvalidConfig {
+                backend: "Seccomp",
+                details: format!("Unknown syscall '{}' in profile {}", name, self.name),
+            })?;
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+
+        let denied = match self.action {
+            SeccompAction::KillProcess => SECCOMP_RET_KILL_PROCESS,
+            SeccompAction::Errno(errno) => SECCOMP_RET_ERRNO | u32::from(errno),
+        };
+
+        let mut program = vec![
+            statement(BPF_LD_W_ABS, SECCOMP_DATA_ARCH),
+            jump(BPF_JEQ_K, audit_arch, 1, 0),
+            statement(BPF_RET_K, SECCOMP_RET_KILL_PROCESS),
+            statement(BPF_LD_W_ABS, SECCOMP_DATA_NR),
+        ];
+        if cfg!(target_arch = "x86_64") {
+            program.push(jump(BPF_JGE_K, X32_SYSCALL_BIT, 0, 1));
+            program.push(statement(BPF_RET_K, SECCOMP_RET_KILL_PROCESS));
+        }
+        for number in numbers {
+            program.push(jump(BPF_JEQ_K, number, 0, 1));
+            program.push(statement(BPF_RET_K, denied));
+        }
+        program.push(statement(BPF_RET_K, SECCOMP_RET_ALLOW));
+
+        Ok(program.iter().flat_map(SockFilter::to_bytes).collect())
+    }
+}
+
+/// Seccomp profiles of a backend, chosen by the language being run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeccompConfig {
+    /// Profile for languages without an override
+    pub default: SeccompProfile,
+
+    /// Overrides keyed by canonical language name (see `Language::name`)
+    #[serde(default)]
+    pub languages: HashMap<String, SeccompProfile>,
+}
+
+impl SeccompConfig {
+    /// Config applying `profile` to every language
+    pub fn new(profile: SeccompProfile) -> Self {
+        Self {
+            default: profile,
+            languages: HashMap::new(),
+        }
+    }
+
+    /// Use `profile` for `language` (any alias accepted by `Language::parse`)
+    pub fn with_language(mut self, language: &str, profile: SeccompProfile) -> Self {
+        self.languages.insert(language_key(language), profile);
+        self
+    }
+
+    /// Profile that applies to `language`
+    pub fn profile_for(&self, language: &str) -> &SeccompProfile {
+        self.languages
+            .get(&language_key(language))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Install `profile` on a bubblewrap command
+///
+/// Passes the compiled program to bwrap through an inherited pipe with
+/// `--seccomp`, so it must be called before the `--` that ends bwrap's
+/// options. The pipe's read end lives as long as `cmd`.
+pub fn apply_profile(cmd: &mut Command, profile: &SeccompProfile) -> BackendResult<()> {
+    if profile.is_unrestricted() {
+        return Ok(());
+    }
+    attach_program(cmd, &profile.compile()?)
+}
+
+/// Record a violation of `profile` if the filter killed the process
+pub fn record_violation(result: &mut ExecutionResult, profile: &SeccompProfile) {
+    if !profile.is_unrestricted()
+        && profile.action == SeccompAction::KillProcess
+        && result.termination_signal() == Some(SIGSYS)
+    {
+        result.seccomp_violation = Some(profile.name.clone());
+    }
+}
+
+#[cfg(unix)]
+fn attach_program(cmd: &mut Command, program: &[u8]) -> BackendResult<()> {
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::process::CommandExt;
+
+    let failed = |e: std::io::Error| BackendError::ProcessFailed {
+        details: format!("Failed to pass seccomp filter: {}", e),
+    };
+
+    // Far below the pipe buffer size, so writing cannot block
+    let (reader, mut writer) = std::io::pipe().map_err(failed)?;
+    writer.write_all(program).map_err(failed)?;
+    drop(writer);
+
+    let fd = reader.as_raw_fd();
+    cmd.arg("--seccomp").arg(fd.to_string());
+
+    // SAFETY: fcntl is async-signal-safe and we do not allocate in the closure
+    unsafe {
+        cmd.pre_exec(move || {
+            // Keep the pipe open across exec; it is close-on-exec by default
+            if libc::fcntl(reader.as_raw_fd(), libc::F_SETFD, 0) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn attach_program(_cmd: &mut Command, _program: &[u8]) -> BackendResult<()> {
+    Err(BackendError::NotAvailable {
+        backend: "Seccomp",
+        reason: "seccomp filters require Linux".to_string(),
+    })
+}
+
+fn language_key(language: &str) -> String {
+    Language::parse(language)
+        .map(|language| language.name().to_string())
+        .unwrap_or_else(|| language.to_lowercase())
+}
+
+// Classic BPF opcodes and seccomp constants from linux/filter.h and linux/seccomp.h
+const BPF_LD_W_ABS: u16 = 0x20;
+const BPF_JEQ_K: u16 = 0x15;
+const BPF_JGE_K: u16 = 0x35;
+const BPF_RET_K: u16 = 0x06;
+const SECCOMP_DATA_NR: u32 = 0;
+const SECCOMP_DATA_ARCH: u32 = 4;
+const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+const AUDIT_ARCH: Option<u32> = Some(0xc000_003e);
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+const AUDIT_ARCH: Option<u32> = Some(0xc000_00b7);
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+const AUDIT_ARCH: Option<u32> = None;
+
+/// One `struct sock_filter` instruction
+struct SockFilter {
+    code: u16,
+    jt: u8,
+    jf: u8,
+    k: u32,
+}
+
+impl SockFilter {
+    fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[..2].copy_from_slice(&self.code.to_ne_bytes());
+        bytes[2] = self.jt;
+        bytes[3] = self.jf;
+        bytes[4..].copy_from_slice(&self.k.to_ne_bytes());
+        bytes
+    }
+}
+
+fn statement(code: u16, k: u32) -> SockFilter {
+    SockFilter {
+        code,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+fn jump(code: u16, k: u32, jt: u8, jf: u8) -> SockFilter {
+    SockFilter { code, jt, jf, k }
+}
+
+/// Number of a syscall on the host architecture
+#[cfg(target_os = "linux")]
+fn syscall_number(name: &str) -> Option<u32> {
+    let number = match name {
+        "ptrace" => libc::SYS_ptrace,
+        "process_vm_readv" => libc::SYS_process_vm_readv,
+        "process_vm_writev" => libc::SYS_process_vm_writev,
+        "mount" => libc::SYS_mount,
+        "umount2" => libc::SYS_umount2,
+        "pivot_root" => libc::SYS_pivot_root,
+        "chroot" => libc::SYS_chroot,
+        "move_mount" => libc::SYS_move_mount,
+        "open_tree" => libc::SYS_open_tree,
+        "fsopen" => libc::SYS_fsopen,
+        "fsconfig" => libc::SYS_fsconfig,
+        "fsmount" => libc::SYS_fsmount,
+        "fspick" => libc::SYS_fspick,
+        "bpf" => libc::SYS_bpf,
+        "perf_event_open" => libc::SYS_perf_event_open,
+        "kexec_load" => libc::SYS_kexec_load,
+        "kexec_file_load" => libc::SYS_kexec_file_load,
+        "init_module" => libc::SYS_init_module,
+        "finit_module" => libc::SYS_finit_module,
+        "delete_module" => libc::SYS_delete_module,
+        "reboot" => libc::SYS_reboot,
+        "swapon" => libc::SYS_swapon,
+        "swapoff" => libc::SYS_swapoff,
+        "syslog" => libc::SYS_syslog,
+        "acct" => libc::SYS_acct,
+        "settimeofday" => libc::SYS_settimeofday,
+        "clock_settime" => libc::SYS_clock_settime,
+        "clock_adjtime" => libc::SYS_clock_adjtime,
+        "adjtimex" => libc::SYS_adjtimex,
+        "keyctl" => libc::SYS_keyctl,
+        "add_key" => libc::SYS_add_key,
+        "request_key" => libc::SYS_request_key,
+        "userfaultfd" => libc::SYS_userfaultfd,
+        "unshare" => libc::SYS_unshare,
+        "setns" => libc::SYS_setns,
+        "open_by_handle_at" => libc::SYS_open_by_handle_at,
+        "name_to_handle_at" => libc::SYS_name_to_handle_at,
+        "quotactl" => libc::SYS_quotactl,
+        "personality" => libc::SYS_personality,
+        "socket" => libc::SYS_socket,
+        "connect" => libc::SYS_connect,
+        "bind" => libc::SYS_bind,
+        "listen" => libc::SYS_listen,
+        "accept" => libc::SYS_accept,
+        "accept4" => libc::SYS_accept4,
+        "clone" => libc::SYS_clone,
+        "clone3" => libc::SYS_clone3,
+        "execve" => libc::SYS_execve,
+        "execveat" => libc::SYS_execveat,
+        "kill" => libc::SYS_kill,
+        "tkill" => libc::SYS_tkill,
+        "tgkill" => libc::SYS_tgkill,
+        "setuid" => libc::SYS_setuid,
+        "setgid" => libc::SYS_setgid,
+        "setreuid" => libc::SYS_setreuid,
+        "setregid" => libc::SYS_setregid,
+        "setresuid" => libc::SYS_setresuid,
+        "setresgid" => libc::SYS_setresgid,
+        "prctl" => libc::SYS_prctl,
+        "seccomp" => libc::SYS_seccomp,
+        "io_uring_setup" => libc::SYS_io_uring_setup,
+        "io_uring_enter" => libc::SYS_io_uring_enter,
+        "io_uring_register" => libc::SYS_io_uring_register,
+        #[cfg(target_arch = "x86_64")]
+        "iopl" => libc::SYS_iopl,
+        #[cfg(target_arch = "x86_64")]
+        "ioperm" => libc::SYS_ioperm,
+        _ => return None,
+    };
+    u32::try_from(number).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn syscall_number(_name: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_overrides_match_aliases() {
+        let python = SeccompProfile::deny_dangerous().allow("ptrace");
+        let config = SeccompConfig::default().with_language("py", python.clone());
+
+        assert_eq!(config.profile_for("python3"), &python);
+        assert_eq!(
+            config.profile_for("bash"),
+            &SeccompProfile::deny_dangerous()
+        );
+    }
+
+    #[test]
+    fn profile_editing() {
+        let profile = SeccompProfile::new("custom")
+            .deny("ptrace")
+            .deny("ptrace")
+            .deny("mount")
+            .allow("mount");
+
+        assert_eq!(profile.denied_syscalls, vec!["ptrace".to_string()]);
+        assert!(SeccompProfile::unrestricted().is_unrestricted());
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn compiles_one_check_per_syscall() {
+        let profile = SeccompProfile::new("two").deny("ptrace").deny("bpf");
+        let program = profile.compile().expect("compile profile");
+
+        // Arch check, syscall load, x32 guard, two checks and the final allow
+        let preamble = if cfg!(target_arch = "x86_64") { 6 } else { 4 };
+        assert_eq!(program.len(), (preamble + 2 * 2 + 1) * 8);
+
+        let unknown = SeccompProfile::new("bad").deny("not_a_syscall").compile();
+        assert!(matches!(unknown, Err(BackendError::InvalidConfig { .. })));
+        assert!(SeccompProfile::deny_dangerous().compile().is_ok());
+    }
+
+    #[test]
+    fn kills_are_reported_as_violations() {
+        let profile = SeccompProfile::deny_dangerous();
+
+        let mut killed = ExecutionResult::failure(128 + SIGSYS, "Bad system call");
+        record_violation(&mut killed, &profile);
+        assert_eq!(killed.seccomp_violation.as_deref(), Some("deny-dangerous"));
+
+        let mut failed = ExecutionResult::failure(1, "error");
+        record_violation(&mut failed, &profile);
+        assert_eq!(failed.seccomp_violation, None);
+    }
+}
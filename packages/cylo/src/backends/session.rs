@@ -0,0 +1,368 @@
+// ============================================================================
+// File: packages/cylo/src/backends/session.rs
+// ----------------------------------------------------------------------------
+// Persistent interpreter sessions (REPL mode) for Cylo backends.
+//
+// Provides:
+// - Long-lived python/node driver processes launched inside a backend sandbox
+// - A line-delimited JSON protocol so each snippet shares interpreter state
+// - Per-snippet wall-clock enforcement; a timed-out session is torn down
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::Mutex;
+
+use crate::backends::limits;
+use crate::backends::{
+    AsyncTask, BackendError, BackendResult, ExecutionBackend, ExecutionResult, ResourceLimits,
+    ResourceUsage,
+};
+
+/// Marker field identifying driver responses on the session's stdout
+const RESPONSE_MARKER: &str = "__cylo__";
+
+/// Python driver: executes each request in a shared globals dict
+const PYTHON_DRIVER: &str = r#"
+import sys, io, json, traceback, contextlib
+g = {"__name__": "__main__"}
+for line in sys.stdin:
+    req = json.loads(line)
+    out, err, code = io.StringIO(), io.StringIO(), 0
+    with contextlib.redirect_stdout(out), contextlib.redirect_stderr(err):
+        try:
+            exec(compile(req["code"], "<session>", "exec"), g)
+        except SystemExit as e:
+            code = e.code if isinstance(e.code, int) else 1
+        except BaseException:
+            traceback.print_exc()
+            code = 1
+    sys.__stdout__.write(json.dumps({"__cylo__": True, "stdout": out.getvalue(), "stderr": err.getvalue(), "exit_code": code}) + "\n")
+    sys.__stdout__.flush()
+"#;
+
+/// Node driver: evaluates each request in a shared vm context
+const NODE_DRIVER: &str = r#"
+const vm = require('vm'), util = require('util');
+let out = [], err = [];
+const write = (buf) => (...args) => buf.push(util.format(...args) + '\n');
+const ctx = vm.createContext({
+  console: { log: write(out), info: write(out), debug: write(out), warn: write(err), error: write(err) },
+  require, process, Buffer, setTimeout, clearTimeout, setInterval, clearInterval,
+});
+const rl = require('readline').createInterface({ input: process.stdin });
+let queue = Promise.resolve();
+rl.on('line', (line) => {
+  queue = queue.then(async () => {
+    const req = JSON.parse(line);
+    out = []; err = [];
+    let code = 0;
+    try {
+      const result = vm.runInContext(req.code, ctx, { filename: '<session>' });
+      if (result && typeof result.then === 'function') await result;
+    } catch (e) {
+      err.push(((e && e.stack) || String(e)) + '\n');
+      code = 1;
+    }
+    process.stdout.write(JSON.stringify({ __cylo__: true, stdout: out.join(''), stderr: err.join(''), exit_code: code }) + '\n');
+  });
+});
+"#;
+
+/// Command prepared by a backend to host a session process
+#[derive(Debug)]
+pub struct SessionLaunch {
+    /// Sandboxed command running the driver
+    pub command: std::process::Command,
+
+    /// Scratch directory to remove when the session ends
+    pub workspace: Option<PathBuf>,
+}
+
+/// Public description of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionHandle {
+    /// Unique session identifier
+    pub id: String,
+
+    /// Instance the session runs in
+    pub instance_id: String,
+
+    /// Interpreter language
+    pub language: String,
+}
+
+/// Languages that support persistent sessions
+pub fn session_languages() -> &'static [&'static str] {
+    &["python", "python3", "javascript", "js", "node"]
+}
+
+/// Interpreter argv running the session driver for `language`
+pub fn driver_argv(language: &str) -> BackendResult<Vec<String>> {
+    match language.to_lowercase().as_str() {
+        "python" | "python3" => Ok(vec![
+            "python3".to_string(),
+            "-u".to_string(),
+            "-c".to_string(),
+            PYTHON_DRIVER.to_string(),
+        ]),
+        "javascript" | "js" | "node" => Ok(vec![
+            "node".to_string(),
+            "-e".to_string(),
+            NODE_DRIVER.to_string(),
+        ]),
+        _ => Err(BackendError::UnsupportedLanguage {
+            backend: "Session",
+            language: language.to_string(),
+        }),
+    }
+}
+
+/// Driver response line
+#[derive(Debug, Deserialize)]
+struct DriverResponse {
+    #[serde(rename = "__cylo__")]
+    marker: bool,
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+}
+
+/// Live driver process
+#[derive(Debug)]
+struct SessionProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// Persistent interpreter session
+///
+/// Snippets are executed one at a time; each sees the variables, imports and
+/// definitions left behind by earlier snippets.
+#[derive(Debug)]
+pub struct ReplSession {
+    id: String,
+    language: String,
+    backend: &'static str,
+    limits: ResourceLimits,
+    workspace: Option<PathBuf>,
+    pid: Option<u32>,
+    process: Mutex<Option<SessionProcess>>,
+    last_used: std::sync::Mutex<Instant>,
+}
+
+impl ReplSession {
+    /// Start a session process inside `backend`'s sandbox
+    ///
+    /// # Arguments
+    /// * `backend` - Backend that hosts the session
+    /// * `language` - Interpreter language (python or javascript)
+    /// * `limits` - Limits applied to the session process and its output
+    pub fn start(
+        backend: &dyn ExecutionBackend,
+        language: &str,
+        limits: ResourceLimits,
+    ) -> BackendResult<Self> {
+        let argv = driver_argv(language)?;
+        let launch = backend.session_command(&argv, &limits)?;
+
+        let mut command = tokio::process::Command::from(launch.command);
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+
+        let mut child = command.spawn().map_err(|e| BackendError::ProcessFailed {
+            details: format!("Failed to spawn session process: {e}"),
+        })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| BackendError::ProcessFailed {
+            details: "Session process has no stdin".to_string(),
+        })?;
+        let stdout = child.stdout.take().ok_or_else(|| BackendError::ProcessFailed {
+            details: "Session process has no stdout".to_string(),
+        })?;
+
+        Ok(Self {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            language: language.to_string(),
+            backend: backend.backend_type(),
+            limits,
+            workspace: launch.workspace,
+            pid: child.id(),
+            process: Mutex::new(Some(SessionProcess {
+                child,
+                stdin,
+                stdout: BufReader::new(stdout),
+            })),
+            last_used: std::sync::Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Unique session identifier
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// OS process ID of the interpreter
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    /// Scratch directory removed when the session closes
+    pub fn workspace(&self) -> Option<&Path> {
+        self.workspace.as_deref()
+    }
+
+    /// Interpreter language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Time since the session last executed a snippet
+    pub fn idle_for(&self) -> Duration {
+        self.last_used
+            .lock()
+            .map(|last| last.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Execute a snippet, sharing state with previous snippets
+    ///
+    /// If the snippet exceeds `timeout` the interpreter is killed, since its
+    /// state can no longer be trusted; later calls on this session fail.
+    pub fn execute(self: &Arc<Self>, code: String, timeout: Duration) -> AsyncTask<ExecutionResult> {
+        let session = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let start = Instant::now();
+            if let Ok(mut last) = session.last_used.lock() {
+                *last = start;
+            }
+
+            let mut guard = session.process.lock().await;
+            let Some(process) = guard.as_mut() else {
+                return ExecutionResult::failure(-1, format!("Session {} is closed", session.id));
+            };
+
+            let outcome = tokio::time::timeout(timeout, Self::round_trip(process, &code)).await;
+
+            let result = match outcome {
+                Ok(Ok(mut result)) => {
+                    result.duration = start.elapsed();
+                    result
+                }
+                Ok(Err(e)) => {
+                    Self::terminate(guard.take()).await;
+                    ExecutionResult::failure(-1, format!("Session {} failed: {e}", session.id))
+                }
+                Err(_) => {
+                    Self::terminate(guard.take()).await;
+                    limits::wall_clock_result(
+                        String::new(),
+                        format!("Session {} exceeded {}s and was terminated", session.id, timeout.as_secs()),
+                        start.elapsed(),
+                        ResourceUsage::default(),
+                    )
+                }
+            };
+
+            let mut result = limits::enforce_result_limits(result, &session.limits);
+            result
+                .metadata
+                .insert("backend".to_string(), session.backend.to_string());
+            result.metadata.insert("session_id".to_string(), session.id.clone());
+            result
+        })
+    }
+
+    /// Terminate the interpreter and remove its workspace
+    pub fn close(self: &Arc<Self>) -> AsyncTask<()> {
+        let session = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let process = session.process.lock().await.take();
+            Self::terminate(process).await;
+
+            if let Some(workspace) = &session.workspace {
+                let _ = std::fs::remove_dir_all(workspace);
+            }
+        })
+    }
+
+    /// Send one request and read its response line
+    async fn round_trip(process: &mut SessionProcess, code: &str) -> std::io::Result<ExecutionResult> {
+        let mut request = serde_json::json!({ "code": code }).to_string();
+        request.push('\n');
+        process.stdin.write_all(request.as_bytes()).await?;
+        process.stdin.flush().await?;
+
+        // Anything that bypasses the driver's redirection (e.g. subprocesses
+        // writing to fd 1) is surfaced as stdout ahead of the snippet's own output
+        let mut stray = String::new();
+        loop {
+            let mut line = String::new();
+            if process.stdout.read_line(&mut line).await? == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "session process exited",
+                ));
+            }
+
+            match serde_json::from_str::<DriverResponse>(&line) {
+                Ok(response) if response.marker => {
+                    return Ok(ExecutionResult {
+                        exit_code: response.exit_code,
+                        stdout: stray + &response.stdout,
+                        stderr: response.stderr,
+                        duration: Duration::ZERO,
+                        resource_usage: ResourceUsage::default(),
+                        metadata: Default::default(),
+                        limit_exceeded: None,
+                        artifacts: Vec::new(),
+                        compile_error: None,
+                        signal: None,
+                        stdout_truncated: false,
+                        stderr_truncated: false,
+                        seccomp_violation: None,
+                    });
+                }
+                _ => stray.push_str(&line),
+            }
+        }
+    }
+
+    async fn terminate(process: Option<SessionProcess>) {
+        if let Some(mut process) = process {
+            let _ = process.child.kill().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn driver_for_supported_languages() {
+        assert_eq!(driver_argv("python").unwrap()[0], "python3");
+        assert_eq!(driver_argv("js").unwrap()[0], "node");
+        assert!(driver_argv("rust").is_err());
+    }
+
+    #[test]
+    fn driver_response_requires_marker() {
+        let line = r#"{"__cylo__": true, "stdout": "1\n", "stderr": "", "exit_code": 0}"#;
+        let response: DriverResponse = serde_json::from_str(line).unwrap();
+        assert!(response.marker);
+        assert!(serde_json::from_str::<DriverResponse>("hello").is_err());
+    }
+}
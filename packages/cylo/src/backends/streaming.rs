@@ -0,0 +1,128 @@
+// ============================================================================
+// File: packages/cylo/src/backends/streaming.rs
+// ----------------------------------------------------------------------------
+// Incremental process I/O shared by all backends.
+//
+// Provides:
+// - Channel construction for ExecutionStream
+// - Replay of buffered ExecutionResults as stream events
+// - Background stdin feeding so large inputs never deadlock against output
+// ============================================================================
+
+use std::io::Write;
+use std::process::Child;
+use std::thread::JoinHandle;
+
+use tokio::sync::mpsc::{self, Sender};
+
+use crate::backends::{AsyncTask, ExecutionEvent, ExecutionResult, ExecutionStream};
+
+/// Number of events buffered before readers block on a slow consumer
+pub const STREAM_CHANNEL_CAPACITY: usize = 64;
+
+/// Create a sender/receiver pair for a streaming execution
+pub fn channel() -> (Sender<ExecutionEvent>, ExecutionStream) {
+    mpsc::channel(STREAM_CHANNEL_CAPACITY)
+}
+
+/// Stream the outcome of a buffered execution
+///
+/// Used by backends that cannot observe output until the program finishes.
+pub fn replay_result(task: AsyncTask<ExecutionResult>) -> ExecutionStream {
+    let (tx, rx) = channel();
+
+    tokio::spawn(async move {
+        let result = task.await.unwrap_or_else(|e| {
+            ExecutionResult::failure(-1, format!("Execution task failed: {e}"))
+        });
+        send_result(&tx, &result).await;
+    });
+
+    rx
+}
+
+/// Send any buffered output in `result` followed by the terminal `Exit` event
+pub async fn send_result(tx: &Sender<ExecutionEvent>, result: &ExecutionResult) {
+    if !result.stdout.is_empty() {
+        let _ = tx
+            .send(ExecutionEvent::Stdout {
+                data: result.stdout.clone(),
+            })
+            .await;
+    }
+
+    if !result.stderr.is_empty() {
+        let _ = tx
+            .send(ExecutionEvent::Stderr {
+                data: result.stderr.clone(),
+            })
+            .await;
+    }
+
+    let _ = tx.send(ExecutionEvent::exit(result)).await;
+}
+
+/// Write `data` to the child's stdin on a background thread, then close it
+///
+/// Writing from the calling thread can deadlock when the program fills its
+/// stdout pipe before consuming all input. Returns `None` when there is no
+/// data or stdin was not piped; stdin is closed in either case.
+pub fn spawn_stdin_writer(
+    child: &mut Child,
+    data: Option<&[u8]>,
+) -> Option<JoinHandle<std::io::Result<()>>> {
+    spawn_writer(child.stdin.take()?, data)
+}
+
+/// Write `data` to a pipe on a background thread, then close it
+///
+/// Like `spawn_stdin_writer`, for processes a backend launches itself.
+pub fn spawn_writer<W: Write + Send + 'static>(
+    mut stdin: W,
+    data: Option<&[u8]>,
+) -> Option<JoinHandle<std::io::Result<()>>> {
+    let data = data?.to_vec();
+
+    Some(std::thread::spawn(move || {
+        let result = stdin.write_all(&data);
+        drop(stdin);
+        // A program that exits without reading its input closes the pipe early
+        match result {
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            other => other,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replay_ends_with_exit() {
+        let task = tokio::spawn(async { ExecutionResult::success("hello") });
+        let mut stream = replay_result(task);
+
+        let first = stream.recv().await.expect("stdout event");
+        assert!(matches!(first, ExecutionEvent::Stdout { ref data } if data == "hello"));
+
+        let last = stream.recv().await.expect("exit event");
+        assert!(last.is_exit());
+        assert!(stream.recv().await.is_none());
+    }
+
+    #[test]
+    fn stdin_is_fed_to_child() {
+        let mut child = std::process::Command::new("cat")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("spawn cat");
+
+        let writer = spawn_stdin_writer(&mut child, Some(b"ping")).expect("writer");
+        writer.join().expect("join").expect("write");
+
+        let output = child.wait_with_output().expect("wait");
+        assert_eq!(output.stdout, b"ping");
+    }
+}
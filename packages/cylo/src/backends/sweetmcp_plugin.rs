@@ -51,9 +51,10 @@ pub struct ToolInfo {
     pub description: Option<String>,
 }
 
+use super::limits;
 use super::{
     AsyncTask, ExecutionBackend, ExecutionRequest, ExecutionResult, HealthStatus,
-    BackendConfig, BackendError, BackendResult, ResourceUsage,
+    BackendConfig, BackendError, BackendResult, NetworkPolicy, ResourceUsage, SandboxProfile,
 };
 use crate::execution_env::CyloResult;
 
@@ -93,7 +94,12 @@ impl SweetMcpPluginBackend {
 
         // Load plugin manifest
         let wasm = Wasm::file(&plugin_path);
-        let manifest = Manifest::new([wasm]);
+        let mut manifest = Manifest::new([wasm]).with_allowed_hosts(
+            Self::allowed_hosts(&config.default_network).into_iter(),
+        );
+        if let Some(profile) = SandboxProfile::from_backend_config(&config)? {
+            manifest = Self::apply_profile(manifest, &profile);
+        }
         
         // Create plugin instance
         let mut plugin = Plugin::new(&manifest, [], true)
@@ -133,13 +139,46 @@ impl SweetMcpPluginBackend {
         })
     }
 
+    /// Map a network policy onto Extism's HTTP host allow-list
+    ///
+    /// WASM plugins have no sockets; their only egress is host-mediated HTTP,
+    /// which Extism denies unless the host is listed. CIDR ranges cannot be
+    /// expressed there and are ignored.
+    fn allowed_hosts(network: &NetworkPolicy) -> Vec<String> {
+        match network {
+            NetworkPolicy::DenyAll => Vec::new(),
+            NetworkPolicy::AllowList { hosts, .. } => hosts.clone(),
+            NetworkPolicy::Full => vec!["*".to_string()],
+        }
+    }
+
+    /// Grant a profile's file system roots and pass the profile to the plugin
+    ///
+    /// Roots are mounted under their host path; read-only roots use Extism's
+    /// `ro:` prefix. The plugin reads the rest of the profile, such as its
+    /// shell policy, from its configuration.
+    fn apply_profile(mut manifest: Manifest, profile: &SandboxProfile) -> Manifest {
+        for root in &profile.fs_roots {
+            let host = root.path.display().to_string();
+            let host = if root.writable { host } else { format!("ro:{host}") };
+            manifest = manifest.with_allowed_path(host, &root.path);
+        }
+        for (key, value) in profile.plugin_config() {
+            manifest = manifest.with_config_key(key, value);
+        }
+        manifest
+    }
+
     /// Convert ExecutionRequest to CallToolRequest
     fn execution_to_tool_request(&self, request: &ExecutionRequest) -> CallToolRequest {
         let mut arguments = serde_json::Map::new();
         arguments.insert("code".to_string(), JsonValue::String(request.code.clone()));
 
-        if let Some(input) = &request.input {
-            arguments.insert("input".to_string(), JsonValue::String(input.clone()));
+        if let Some(input) = request.stdin_data() {
+            arguments.insert(
+                "input".to_string(),
+                JsonValue::String(String::from_utf8_lossy(input).into_owned()),
+            );
         }
 
         // Add timeout from Duration (convert to seconds)
@@ -162,6 +201,11 @@ impl SweetMcpPluginBackend {
             arguments.insert("env".to_string(), env_json);
         }
 
+        // Pass quotas through so plugins that spawn interpreters can honour them
+        if let Ok(limits) = serde_json::to_value(&request.limits) {
+            arguments.insert("limits".to_string(), limits);
+        }
+
         CallToolRequest {
             method: "tools/call".to_string(),
             params: CallToolRequestParams {
@@ -186,6 +230,13 @@ impl SweetMcpPluginBackend {
                 duration,
                 resource_usage: ResourceUsage::default(),
                 metadata: HashMap::new(),
+                limit_exceeded: None,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal: None,
+                stdout_truncated: false,
+                stderr_truncated: false,
+                seccomp_violation: None,
             };
         }
 
@@ -217,6 +268,13 @@ impl SweetMcpPluginBackend {
                 duration,
                 resource_usage: ResourceUsage::default(),
                 metadata: HashMap::new(),
+                limit_exceeded: None,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal: None,
+                stdout_truncated: false,
+                stderr_truncated: false,
+                seccomp_violation: None,
             }
         } else {
             // Fallback for plain text results
@@ -227,6 +285,13 @@ impl SweetMcpPluginBackend {
                 duration,
                 resource_usage: ResourceUsage::default(),
                 metadata: HashMap::new(),
+                limit_exceeded: None,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal: None,
+                stdout_truncated: false,
+                stderr_truncated: false,
+                seccomp_violation: None,
             }
         }
     }
@@ -255,14 +320,39 @@ impl ExecutionBackend for SweetMcpPluginBackend {
                         duration,
                         resource_usage: ResourceUsage::default(),
                         metadata: HashMap::new(),
+                        limit_exceeded: None,
+                        artifacts: Vec::new(),
+                        compile_error: None,
+                        signal: None,
+                        stdout_truncated: false,
+                        stderr_truncated: false,
+                        seccomp_violation: None,
                     };
                 }
             };
 
-            // Call the plugin
+            // Call the plugin, cancelling it once the wall-clock deadline passes
             let mut plugin_guard = plugin.lock().await;
-            let response_str = match plugin_guard.call::<String, String>("call", request_json) {
+            let cancel_handle = plugin_guard.cancel_handle();
+            let timeout = request.timeout;
+            let deadline = tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                let _ = cancel_handle.cancel();
+            });
+            let call_result = plugin_guard.call::<String, String>("call", request_json);
+            deadline.abort();
+
+            let response_str = match call_result {
                 Ok(response) => response,
+                Err(_) if start_time.elapsed().unwrap_or_default() >= timeout => {
+                    let duration = start_time.elapsed().unwrap_or_default();
+                    return limits::wall_clock_result(
+                        String::new(),
+                        format!("Plugin execution exceeded {}s wall-clock limit", timeout.as_secs()),
+                        duration,
+                        ResourceUsage::default(),
+                    );
+                }
                 Err(e) => {
                     let duration = start_time.elapsed().unwrap_or_default();
                     return ExecutionResult {
@@ -272,6 +362,13 @@ impl ExecutionBackend for SweetMcpPluginBackend {
                         duration,
                         resource_usage: ResourceUsage::default(),
                         metadata: HashMap::new(),
+                        limit_exceeded: None,
+                        artifacts: Vec::new(),
+                        compile_error: None,
+                        signal: None,
+                        stdout_truncated: false,
+                        stderr_truncated: false,
+                        seccomp_violation: None,
                     };
                 }
             };
@@ -289,12 +386,20 @@ impl ExecutionBackend for SweetMcpPluginBackend {
                         duration,
                         resource_usage: ResourceUsage::default(),
                         metadata: HashMap::new(),
+                        limit_exceeded: None,
+                        artifacts: Vec::new(),
+                        compile_error: None,
+                        signal: None,
+                        stdout_truncated: false,
+                        stderr_truncated: false,
+                        seccomp_violation: None,
                     };
                 }
             };
 
             let duration = start_time.elapsed().unwrap_or_default();
-            backend.tool_result_to_execution(tool_result, duration)
+            let result = backend.tool_result_to_execution(tool_result, duration);
+            limits::enforce_result_limits(result, &request.limits)
         })
     }
 
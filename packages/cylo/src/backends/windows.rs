@@ -0,0 +1,1451 @@
+// ============================================================================
+// File: packages/cylo/src/backends/windows.rs
+// ----------------------------------------------------------------------------
+// Windows backend for secure code execution using Job Objects and AppContainers.
+//
+// Implements ExecutionBackend trait by launching interpreters directly with
+// the Win32 process APIs. Provides:
+// - Job Object quotas for memory, CPU time and process count
+// - Kill-on-close jobs so nothing outlives an execution
+// - AppContainer isolation, or a restricted token where AppContainers cannot
+//   reach the toolchain
+// - Capability-based network control (deny all or full egress)
+// - Per-execution workspaces under a sandbox root
+// ============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString, c_void};
+use std::fs::{self, File};
+use std::io::Read;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
+use std::os::windows::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::ptr::{null, null_mut};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc::Sender;
+use windows_sys::Win32::Foundation::{
+    ERROR_SUCCESS, GENERIC_ALL, HANDLE, HANDLE_FLAG_INHERIT, LocalFree, PSID, SetHandleInformation,
+    TRUE, WAIT_OBJECT_0,
+};
+use windows_sys::Win32::Security::Authorization::{
+    EXPLICIT_ACCESS_W, GRANT_ACCESS, GetNamedSecurityInfoW, NO_MULTIPLE_TRUSTEE, SE_FILE_OBJECT,
+    SetEntriesInAclW, SetNamedSecurityInfoW, TRUSTEE_IS_SID, TRUSTEE_IS_WELL_KNOWN_GROUP,
+    TRUSTEE_W,
+};
+use windows_sys::Win32::Security::Isolation::{
+    CreateAppContainerProfile, DeriveAppContainerSidFromAppContainerName,
+};
+use windows_sys::Win32::Security::{
+    ACL, CreateRestrictedToken, CreateWellKnownSid, DACL_SECURITY_INFORMATION,
+    DISABLE_MAX_PRIVILEGE, FreeSid, LUA_TOKEN, PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES,
+    SECURITY_CAPABILITIES, SID_AND_ATTRIBUTES, SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+    TOKEN_ASSIGN_PRIMARY, TOKEN_DUPLICATE, TOKEN_QUERY, WELL_KNOWN_SID_TYPE,
+    WinCapabilityInternetClientSid, WinCapabilityPrivateNetworkClientServerSid,
+};
+use windows_sys::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_ACTIVE_PROCESS,
+    JOB_OBJECT_LIMIT_DIE_ON_UNHANDLED_EXCEPTION, JOB_OBJECT_LIMIT_JOB_MEMORY,
+    JOB_OBJECT_LIMIT_JOB_TIME, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_UILIMIT_DESKTOP,
+    JOB_OBJECT_UILIMIT_DISPLAYSETTINGS, JOB_OBJECT_UILIMIT_EXITWINDOWS,
+    JOB_OBJECT_UILIMIT_GLOBALATOMS, JOB_OBJECT_UILIMIT_HANDLES, JOB_OBJECT_UILIMIT_READCLIPBOARD,
+    JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS, JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+    JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION, JOBOBJECT_BASIC_UI_RESTRICTIONS,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOBOBJECTINFOCLASS,
+    JobObjectBasicAndIoAccountingInformation, JobObjectBasicUIRestrictions,
+    JobObjectExtendedLimitInformation, QueryInformationJobObject, SetInformationJobObject,
+    TerminateJobObject,
+};
+use windows_sys::Win32::System::Pipes::CreatePipe;
+use windows_sys::Win32::System::Threading::{
+    CREATE_NO_WINDOW, CREATE_SUSPENDED, CREATE_UNICODE_ENVIRONMENT, CreateProcessAsUserW,
+    CreateProcessW, DeleteProcThreadAttributeList, EXTENDED_STARTUPINFO_PRESENT, GetCurrentProcess,
+    GetExitCodeProcess, INFINITE, InitializeProcThreadAttributeList, LPPROC_THREAD_ATTRIBUTE_LIST,
+    OpenProcessToken, PROC_THREAD_ATTRIBUTE_HANDLE_LIST,
+    PROC_THREAD_ATTRIBUTE_SECURITY_CAPABILITIES, PROCESS_INFORMATION, ResumeThread,
+    STARTF_USESTDHANDLES, STARTUPINFOEXW, TerminateProcess, UpdateProcThreadAttribute,
+    WaitForSingleObject,
+};
+
+use crate::AsyncTaskBuilder;
+use crate::backends::AsyncTask;
+use crate::backends::limits::SupervisedChild;
+use crate::backends::{
+    BackendConfig, BackendError, BackendResult, ExecutionBackend, ExecutionEvent, ExecutionRequest,
+    ExecutionResult, ExecutionStream, HealthStatus, Language, LimitExceeded, NetworkPolicy,
+    ResourceLimits, ResourceUsage,
+};
+use crate::backends::{artifacts, languages, limits, streaming};
+
+/// Backend option choosing the isolation mode (`appcontainer` or `restricted`)
+pub const ISOLATION_OPTION: &str = "isolation";
+
+/// `HRESULT_FROM_WIN32(ERROR_ALREADY_EXISTS)`, returned for an existing profile
+const HRESULT_ALREADY_EXISTS: i32 = 0x8007_00B7_u32 as i32;
+
+/// `SE_GROUP_ENABLED`, marking a capability SID as granted
+const SE_GROUP_ENABLED: u32 = 0x0000_0004;
+
+/// `SECURITY_MAX_SID_SIZE`, enough room for any well-known SID
+const SID_BUFFER_BYTES: usize = 68;
+
+/// Job Object times are counted in 100-nanosecond intervals
+const JOB_TIME_UNITS_PER_SEC: i64 = 10_000_000;
+
+/// How processes launched by the backend are isolated
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Isolation {
+    /// Run inside the named AppContainer profile
+    AppContainer(String),
+
+    /// Run with a restricted copy of this process's token
+    RestrictedToken,
+}
+
+impl Isolation {
+    /// Isolation selected by the `isolation` backend option
+    ///
+    /// AppContainers are the default; they are named after the sandbox root so
+    /// every backend sharing a root shares a profile.
+    fn from_config(config: &BackendConfig, sandbox_root: &Path) -> BackendResult<Self> {
+        match config
+            .backend_specific
+            .get(ISOLATION_OPTION)
+            .map(|mode| mode.to_lowercase())
+            .as_deref()
+        {
+            None | Some("appcontainer") => Ok(Self::AppContainer(container_name(sandbox_root))),
+            Some("restricted") | Some("restricted_token") => Ok(Self::RestrictedToken),
+            Some(other) => Err(BackendError::InvalidConfig {
+                backend: "Windows",
+                details: format!(
+                    "Unknown isolation mode '{other}' (expected 'appcontainer' or 'restricted')"
+                ),
+            }),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::AppContainer(_) => "appcontainer",
+            Self::RestrictedToken => "restricted_token",
+        }
+    }
+}
+
+/// AppContainer profile name for a sandbox root
+fn container_name(sandbox_root: &Path) -> String {
+    let digest = Sha256::digest(sandbox_root.to_string_lossy().to_lowercase().as_bytes());
+    let suffix: String = digest[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("cylo.sandbox.{suffix}")
+}
+
+/// Windows backend for secure code execution
+///
+/// Runs each execution in a fresh workspace under the sandbox root, inside a
+/// Job Object carrying the request's limits and an AppContainer (or
+/// restricted token) that keeps it away from the user's files.
+#[derive(Debug, Clone)]
+pub struct WindowsBackend {
+    /// Directory holding per-execution workspaces
+    sandbox_root: PathBuf,
+
+    /// Backend configuration
+    config: BackendConfig,
+
+    /// Isolation applied to launched processes
+    isolation: Isolation,
+}
+
+impl WindowsBackend {
+    /// Create a new Windows backend instance
+    ///
+    /// Creates the sandbox root if needed and, for AppContainer isolation,
+    /// the container profile, granting it access to the root.
+    ///
+    /// # Arguments
+    /// * `sandbox_root` - Absolute directory holding execution workspaces
+    /// * `config` - Backend configuration
+    ///
+    /// # Returns
+    /// New Windows backend instance or error if the sandbox cannot be prepared
+    pub fn new(sandbox_root: String, config: BackendConfig) -> BackendResult<Self> {
+        if !Self::is_platform_supported() {
+            return Err(BackendError::NotAvailable {
+                backend: "Windows",
+                reason: "Job Objects and AppContainers are only available on Windows".to_string(),
+            });
+        }
+
+        let sandbox_root = PathBuf::from(sandbox_root);
+        Self::validate_sandbox_root(&sandbox_root)?;
+
+        let isolation = Isolation::from_config(&config, &sandbox_root)?;
+        if let Isolation::AppContainer(name) = &isolation {
+            let container =
+                AppContainerSid::ensure(name).map_err(|e| BackendError::ContainerFailed {
+                    details: format!("Failed to create AppContainer profile {name}: {e}"),
+                })?;
+            grant_directory_access(&sandbox_root, container.0).map_err(|e| {
+                BackendError::FileSystemFailed {
+                    details: format!(
+                        "Failed to grant {name} access to {}: {e}",
+                        sandbox_root.display()
+                    ),
+                }
+            })?;
+        }
+
+        Ok(Self {
+            sandbox_root,
+            config,
+            isolation,
+        })
+    }
+
+    /// Check if platform supports the Windows backend
+    fn is_platform_supported() -> bool {
+        cfg!(target_os = "windows")
+    }
+
+    /// Validate the sandbox root, creating it if missing
+    ///
+    /// # Arguments
+    /// * `sandbox_root` - Path to validate
+    ///
+    /// # Returns
+    /// Ok(()) if path is usable, Err otherwise
+    fn validate_sandbox_root(sandbox_root: &Path) -> BackendResult<()> {
+        if !sandbox_root.is_absolute() {
+            return Err(BackendError::InvalidConfig {
+                backend: "Windows",
+                details: "Sandbox root must be absolute".to_string(),
+            });
+        }
+
+        if !sandbox_root.exists() {
+            fs::create_dir_all(sandbox_root).map_err(|e| BackendError::InvalidConfig {
+                backend: "Windows",
+                details: format!("Cannot create sandbox root {}: {e}", sandbox_root.display()),
+            })?;
+        }
+
+        if !sandbox_root.is_dir() {
+            return Err(BackendError::InvalidConfig {
+                backend: "Windows",
+                details: format!("Sandbox root {} is not a directory", sandbox_root.display()),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Create the workspace for one execution and write its source
+    ///
+    /// Workspaces inherit the sandbox root's access grant.
+    ///
+    /// # Arguments
+    /// * `request` - Execution request
+    ///
+    /// # Returns
+    /// Path to the execution directory
+    fn setup_workspace(&self, request: &ExecutionRequest) -> BackendResult<PathBuf> {
+        let exec_dir = self.sandbox_root.join(format!(
+            "exec-{}-{}",
+            uuid::Uuid::new_v4().simple(),
+            std::process::id()
+        ));
+
+        fs::create_dir_all(&exec_dir).map_err(|e| BackendError::FileSystemFailed {
+            details: format!("Failed to create execution directory: {e}"),
+        })?;
+
+        if let Some(workdir) = &request.working_dir {
+            let work_path = exec_dir.join(workdir.trim_start_matches(['/', '\\']));
+            fs::create_dir_all(&work_path).map_err(|e| BackendError::FileSystemFailed {
+                details: format!("Failed to create working directory: {e}"),
+            })?;
+        }
+
+        let language = Language::parse(&request.language).ok_or_else(|| {
+            BackendError::UnsupportedLanguage {
+                backend: "Windows",
+                language: request.language.clone(),
+            }
+        })?;
+        let code_file = exec_dir.join(language.source_file(&request.code));
+        fs::write(&code_file, &request.code).map_err(|e| BackendError::FileSystemFailed {
+            details: format!("Failed to write {} code file: {e}", language.name()),
+        })?;
+
+        Ok(exec_dir)
+    }
+
+    /// Execute code in a job-contained, isolated process
+    ///
+    /// # Arguments
+    /// * `isolation` - Isolation for the launched process
+    /// * `request` - Execution request
+    /// * `exec_dir` - Execution directory path
+    /// * `network` - Network egress policy
+    /// * `events` - When set, output is streamed here instead of buffered
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to execution result
+    fn execute_in_sandbox(
+        isolation: Isolation,
+        request: ExecutionRequest,
+        exec_dir: PathBuf,
+        network: NetworkPolicy,
+        events: Option<Sender<ExecutionEvent>>,
+    ) -> AsyncTask<BackendResult<ExecutionResult>> {
+        AsyncTaskBuilder::new(async move {
+            let start_time = Instant::now();
+
+            let (program, args) =
+                Self::prepare_execution_command(&request.language, &request.code)?;
+            let command_line = command_line(&program, &args);
+            let environment = environment_block(&request.env_vars, &exec_dir);
+
+            let job = Arc::new(JobObject::new(&request.limits).map_err(|e| {
+                BackendError::ContainerFailed {
+                    details: format!("Failed to create job object: {e}"),
+                }
+            })?);
+
+            let mut child = spawn_contained(
+                &command_line,
+                &exec_dir,
+                &environment,
+                &isolation,
+                &network,
+                Arc::clone(&job),
+            )
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Failed to spawn sandboxed process: {e}"),
+            })?;
+
+            // Feed stdin in the background so large inputs cannot deadlock against output
+            let stdin_writer = child
+                .stdin
+                .take()
+                .and_then(|stdin| streaming::spawn_writer(stdin, request.stdin_data()));
+
+            // Wait for completion, enforcing the wall-clock deadline and output caps
+            let timeout_duration = request.timeout;
+            let wait_limits = request.limits.clone();
+            let output = tokio::task::spawn_blocking(move || match &events {
+                Some(events) => {
+                    limits::stream_with_limits(child, timeout_duration, &wait_limits, events)
+                }
+                None => limits::wait_with_limits(child, timeout_duration, &wait_limits),
+            })
+            .await
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Process wait task failed: {e}"),
+            })?
+            .map_err(|e| BackendError::ProcessFailed {
+                details: format!("Process execution failed: {e}"),
+            })?;
+
+            if let Some(writer) = stdin_writer
+                && let Ok(Err(e)) = writer.join()
+            {
+                log::warn!("Failed to write to process stdin: {e}");
+            }
+
+            let duration = start_time.elapsed();
+            let resource_usage = job.usage();
+
+            let exit_code = output
+                .status
+                .and_then(|status| status.code())
+                .unwrap_or(limits::TIMEOUT_EXIT_CODE);
+
+            let limit_exceeded = if output.timed_out {
+                Some(LimitExceeded::WallClock)
+            } else if output.stdout_truncated {
+                Some(LimitExceeded::Stdout)
+            } else if output.stderr_truncated {
+                Some(LimitExceeded::Stderr)
+            } else {
+                limits::classify_termination(exit_code, None, &resource_usage, &request.limits)
+            };
+
+            // Collect requested artifacts before the workspace is removed
+            let artifact_root = match &request.working_dir {
+                Some(workdir) => exec_dir.join(workdir.trim_start_matches(['/', '\\'])),
+                None => exec_dir.clone(),
+            };
+            let collected =
+                artifacts::collect_dir(&artifact_root, &request.artifacts, &request.limits);
+
+            let _ = fs::remove_dir_all(&exec_dir);
+
+            let mut result = ExecutionResult {
+                exit_code,
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                duration,
+                resource_usage,
+                metadata: {
+                    let mut meta = HashMap::new();
+                    meta.insert("backend".to_string(), "Windows".to_string());
+                    meta.insert("isolation".to_string(), isolation.name().to_string());
+                    meta.insert("exec_dir".to_string(), exec_dir.display().to_string());
+                    meta
+                },
+                limit_exceeded,
+                artifacts: Vec::new(),
+                compile_error: None,
+                signal: None,
+                stdout_truncated: output.stdout_truncated,
+                stderr_truncated: output.stderr_truncated,
+                seccomp_violation: None,
+            };
+            collected.apply(&mut result);
+            languages::surface_compile_error(&mut result);
+
+            Ok(result)
+        })
+        .spawn()
+    }
+
+    /// Reject network policies the isolation mode cannot enforce
+    ///
+    /// AppContainers have no network unless granted a capability, so `DenyAll`
+    /// and `Full` are enforced; allow-lists would need a host firewall.
+    /// Restricted tokens leave the network untouched, so only `Full` is honest.
+    ///
+    /// # Arguments
+    /// * `isolation` - Isolation mode
+    /// * `network` - Requested egress policy
+    fn check_network_policy(isolation: &Isolation, network: &NetworkPolicy) -> BackendResult<()> {
+        match (isolation, network) {
+            (_, NetworkPolicy::Full) | (Isolation::AppContainer(_), NetworkPolicy::DenyAll) => {
+                Ok(())
+            }
+            (Isolation::AppContainer(_), _) => Err(BackendError::NotAvailable {
+                backend: "Windows",
+                reason: "allow-list network policies are only enforced on Linux backends"
+                    .to_string(),
+            }),
+            (Isolation::RestrictedToken, _) => Err(BackendError::NotAvailable {
+                backend: "Windows",
+                reason: "restricted-token isolation cannot restrict the network; \
+                         use AppContainer isolation"
+                    .to_string(),
+            }),
+        }
+    }
+
+    /// Prepare execution command for specific language
+    ///
+    /// # Arguments
+    /// * `language` - Programming language
+    /// * `code` - Source code (names Java sources)
+    ///
+    /// # Returns
+    /// Command program and arguments
+    fn prepare_execution_command(
+        language: &str,
+        code: &str,
+    ) -> BackendResult<(String, Vec<String>)> {
+        let language =
+            Language::parse(language).ok_or_else(|| BackendError::UnsupportedLanguage {
+                backend: "Windows",
+                language: language.to_string(),
+            })?;
+        let source = language.source_file(code);
+
+        match language {
+            // The python.org installer puts `python`, not `python3`, on PATH
+            Language::Python => Ok(("python".to_string(), vec![source])),
+            Language::JavaScript => Ok(("node".to_string(), vec![source])),
+            Language::Bash => Ok(("bash".to_string(), vec![source])),
+            // Compiled and toolchain-launched languages build through bash (Git Bash, MSYS2)
+            _ => Ok((
+                "bash".to_string(),
+                vec!["-c".to_string(), language.build_and_run(".", code)],
+            )),
+        }
+    }
+}
+
+impl ExecutionBackend for WindowsBackend {
+    fn execute_code(&self, request: ExecutionRequest) -> AsyncTask<ExecutionResult> {
+        let isolation = self.isolation.clone();
+        let backend_name = self.backend_type();
+        let network = request.network_policy(&self.config).clone();
+
+        if let Err(e) = Self::check_network_policy(&isolation, &network) {
+            return AsyncTaskBuilder::new(
+                async move { ExecutionResult::failure(-1, e.to_string()) },
+            )
+            .spawn();
+        }
+
+        let exec_dir = match self.setup_workspace(&request) {
+            Ok(dir) => dir,
+            Err(e) => {
+                return AsyncTaskBuilder::new(async move {
+                    ExecutionResult::failure(-1, format!("Failed to setup workspace: {e}"))
+                })
+                .spawn();
+            }
+        };
+
+        AsyncTaskBuilder::new(async move {
+            match Self::execute_in_sandbox(isolation, request, exec_dir, network, None).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(e)) => {
+                    ExecutionResult::failure(-1, format!("{backend_name} execution failed: {e}"))
+                }
+                Err(e) => ExecutionResult::failure(
+                    -1,
+                    format!("{backend_name} execution task failed: {e}"),
+                ),
+            }
+        })
+        .spawn()
+    }
+
+    fn execute_code_streaming(&self, request: ExecutionRequest) -> ExecutionStream {
+        let isolation = self.isolation.clone();
+        let backend_name = self.backend_type();
+        let network = request.network_policy(&self.config).clone();
+
+        if let Err(e) = Self::check_network_policy(&isolation, &network) {
+            return streaming::replay_result(
+                AsyncTaskBuilder::new(async move { ExecutionResult::failure(-1, e.to_string()) })
+                    .spawn(),
+            );
+        }
+
+        let exec_dir = self.setup_workspace(&request);
+        let (tx, rx) = streaming::channel();
+
+        tokio::spawn(async move {
+            let result = match exec_dir {
+                Ok(exec_dir) => {
+                    match Self::execute_in_sandbox(
+                        isolation,
+                        request,
+                        exec_dir,
+                        network,
+                        Some(tx.clone()),
+                    )
+                    .await
+                    {
+                        Ok(Ok(result)) => result,
+                        Ok(Err(e)) => ExecutionResult::failure(
+                            -1,
+                            format!("{backend_name} execution failed: {e}"),
+                        ),
+                        Err(e) => ExecutionResult::failure(
+                            -1,
+                            format!("{backend_name} execution task failed: {e}"),
+                        ),
+                    }
+                }
+                Err(e) => ExecutionResult::failure(-1, format!("Failed to setup workspace: {e}")),
+            };
+
+            // Output was already streamed; only failure messages remain buffered
+            streaming::send_result(&tx, &result).await;
+        });
+
+        rx
+    }
+
+    fn health_check(&self) -> AsyncTask<HealthStatus> {
+        let backend = self.clone();
+
+        AsyncTaskBuilder::new(async move {
+            if let Err(e) = Self::validate_sandbox_root(&backend.sandbox_root) {
+                return HealthStatus::unhealthy(format!("Sandbox root validation failed: {e}"))
+                    .with_metric("sandbox_root_valid", "false");
+            }
+
+            if let Isolation::AppContainer(name) = &backend.isolation
+                && let Err(e) = AppContainerSid::derive(name)
+            {
+                return HealthStatus::unhealthy(format!("AppContainer profile unavailable: {e}"))
+                    .with_metric("appcontainer", "false");
+            }
+
+            if let Err(e) = JobObject::new(&backend.config.default_limits) {
+                return HealthStatus::unhealthy(format!("Job object creation failed: {e}"))
+                    .with_metric("job_objects", "false");
+            }
+
+            let test_request = ExecutionRequest::new("print('health check')", "python")
+                .with_timeout(Duration::from_secs(10));
+
+            match backend.setup_workspace(&test_request) {
+                Ok(exec_dir) => {
+                    let _ = fs::remove_dir_all(&exec_dir);
+
+                    HealthStatus::healthy("Windows backend operational")
+                        .with_metric("sandbox_root_valid", "true")
+                        .with_metric("job_objects", "true")
+                        .with_metric("isolation", backend.isolation.name())
+                }
+                Err(e) => HealthStatus::unhealthy(format!("Test environment setup failed: {e}"))
+                    .with_metric("test_setup", "failed"),
+            }
+        })
+        .spawn()
+    }
+
+    fn cleanup(&self) -> AsyncTask<crate::execution_env::CyloResult<()>> {
+        let sandbox_root = self.sandbox_root.clone();
+
+        AsyncTaskBuilder::new(async move {
+            // Clean up any leftover execution directories
+            if let Ok(entries) = fs::read_dir(&sandbox_root) {
+                for entry in entries.filter_map(Result::ok) {
+                    if entry.file_name().to_string_lossy().starts_with("exec-") {
+                        let _ = fs::remove_dir_all(entry.path());
+                    }
+                }
+            }
+
+            Ok(())
+        })
+        .spawn()
+    }
+
+    fn get_config(&self) -> &BackendConfig {
+        &self.config
+    }
+
+    fn backend_type(&self) -> &'static str {
+        "Windows"
+    }
+
+    fn supports_language(&self, language: &str) -> bool {
+        self.supported_languages().contains(&language)
+    }
+
+    fn supported_languages(&self) -> &[&'static str] {
+        &[
+            "python",
+            "python3",
+            "javascript",
+            "js",
+            "node",
+            "rust",
+            "bash",
+            "sh",
+            "go",
+            "typescript",
+            "ts",
+            "ruby",
+            "java",
+            "c",
+            "cpp",
+            "c++",
+        ]
+    }
+}
+
+/// Job Object holding every process of one execution
+///
+/// Closing the job kills whatever is still running in it.
+#[derive(Debug)]
+struct JobObject(OwnedHandle);
+
+impl JobObject {
+    /// Create a kill-on-close job enforcing `limits`
+    ///
+    /// Memory is capped for the whole job, CPU time counts user time across
+    /// all processes, and processes beyond the limit fail to start. The job
+    /// also blocks clipboard, desktop and system-setting access.
+    fn new(limits: &ResourceLimits) -> std::io::Result<Self> {
+        // SAFETY: null attributes and name create an unnamed job with default security
+        let handle = unsafe { CreateJobObjectW(null(), null()) };
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: handle is a freshly created job handle we own
+        let job = Self(unsafe { OwnedHandle::from_raw_handle(handle) });
+
+        // SAFETY: the structure is plain data for which all-zero is valid
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        let basic = &mut info.BasicLimitInformation;
+        basic.LimitFlags =
+            JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE | JOB_OBJECT_LIMIT_DIE_ON_UNHANDLED_EXCEPTION;
+        if let Some(memory) = limits.max_memory {
+            basic.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+            info.JobMemoryLimit = usize::try_from(memory).unwrap_or(usize::MAX);
+        }
+        if let Some(cpu_time) = limits.max_cpu_time {
+            basic.LimitFlags |= JOB_OBJECT_LIMIT_JOB_TIME;
+            basic.PerJobUserTimeLimit = i64::try_from(cpu_time)
+                .unwrap_or(i64::MAX)
+                .saturating_mul(JOB_TIME_UNITS_PER_SEC);
+        }
+        if let Some(processes) = limits.max_processes {
+            basic.LimitFlags |= JOB_OBJECT_LIMIT_ACTIVE_PROCESS;
+            basic.ActiveProcessLimit = processes;
+        }
+        job.set(JobObjectExtendedLimitInformation, &info)?;
+
+        let ui = JOBOBJECT_BASIC_UI_RESTRICTIONS {
+            UIRestrictionsClass: JOB_OBJECT_UILIMIT_DESKTOP
+                | JOB_OBJECT_UILIMIT_DISPLAYSETTINGS
+                | JOB_OBJECT_UILIMIT_EXITWINDOWS
+                | JOB_OBJECT_UILIMIT_GLOBALATOMS
+                | JOB_OBJECT_UILIMIT_HANDLES
+                | JOB_OBJECT_UILIMIT_READCLIPBOARD
+                | JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS
+                | JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+        };
+        job.set(JobObjectBasicUIRestrictions, &ui)?;
+
+        Ok(job)
+    }
+
+    fn handle(&self) -> HANDLE {
+        self.0.as_raw_handle()
+    }
+
+    fn set<T>(&self, class: JOBOBJECTINFOCLASS, info: &T) -> std::io::Result<()> {
+        // SAFETY: `info` is the structure documented for `class` and outlives the call
+        let ok = unsafe {
+            SetInformationJobObject(
+                self.handle(),
+                class,
+                (info as *const T).cast(),
+                std::mem::size_of::<T>() as u32,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn query<T>(&self, class: JOBOBJECTINFOCLASS) -> Option<T> {
+        // SAFETY: job information structures are plain data for which all-zero is valid
+        let mut info: T = unsafe { std::mem::zeroed() };
+        // SAFETY: `info` is the structure documented for `class` and is large enough
+        let ok = unsafe {
+            QueryInformationJobObject(
+                self.handle(),
+                class,
+                (&mut info as *mut T).cast(),
+                std::mem::size_of::<T>() as u32,
+                null_mut(),
+            )
+        };
+        (ok != 0).then_some(info)
+    }
+
+    /// Place a (suspended) process in the job
+    fn assign(&self, process: &OwnedHandle) -> std::io::Result<()> {
+        // SAFETY: both handles are valid for the duration of the call
+        if unsafe { AssignProcessToJobObject(self.handle(), process.as_raw_handle()) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Kill every process in the job
+    fn terminate(&self, exit_code: u32) {
+        // SAFETY: the job handle is valid; terminating an empty job is harmless
+        unsafe { TerminateJobObject(self.handle(), exit_code) };
+    }
+
+    /// Resource usage accumulated by every process that ran in the job
+    fn usage(&self) -> ResourceUsage {
+        let accounting = self.query::<JOBOBJECT_BASIC_AND_IO_ACCOUNTING_INFORMATION>(
+            JobObjectBasicAndIoAccountingInformation,
+        );
+        let limits =
+            self.query::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>(JobObjectExtendedLimitInformation);
+
+        let mut usage = ResourceUsage::default();
+        if let Some(accounting) = accounting {
+            let basic = accounting.BasicInfo;
+            let cpu_units = (basic.TotalUserTime + basic.TotalKernelTime).max(0) as u64;
+            usage.cpu_time_ms = cpu_units / (JOB_TIME_UNITS_PER_SEC as u64 / 1000);
+            usage.process_count = basic.TotalProcesses;
+            usage.disk_bytes_read = accounting.IoInfo.ReadTransferCount;
+            usage.disk_bytes_written = accounting.IoInfo.WriteTransferCount;
+        }
+        if let Some(limits) = limits {
+            usage.peak_memory = limits.PeakJobMemoryUsed as u64;
+        }
+        usage
+    }
+}
+
+/// Process launched into a job, supervised by `limits::wait_with_limits`
+struct JobChild {
+    process: OwnedHandle,
+    job: Arc<JobObject>,
+    stdin: Option<File>,
+    stdout: Option<File>,
+    stderr: Option<File>,
+}
+
+impl SupervisedChild for JobChild {
+    fn take_output(&mut self) -> (Option<Box<dyn Read + Send>>, Option<Box<dyn Read + Send>>) {
+        drop(self.stdin.take());
+        (
+            self.stdout
+                .take()
+                .map(|stream| Box::new(stream) as Box<dyn Read + Send>),
+            self.stderr
+                .take()
+                .map(|stream| Box::new(stream) as Box<dyn Read + Send>),
+        )
+    }
+
+    fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+        // SAFETY: the process handle is valid; a zero timeout only polls
+        if unsafe { WaitForSingleObject(self.process.as_raw_handle(), 0) } != WAIT_OBJECT_0 {
+            return Ok(None);
+        }
+
+        let mut code = 0u32;
+        // SAFETY: the process has exited and `code` is a valid out pointer
+        if unsafe { GetExitCodeProcess(self.process.as_raw_handle(), &mut code) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // Background processes would otherwise hold the output pipes open
+        self.job.terminate(code);
+        Ok(Some(ExitStatus::from_raw(code)))
+    }
+
+    fn kill(&mut self) {
+        self.job.terminate(limits::TIMEOUT_EXIT_CODE as u32);
+        // SAFETY: the process handle is valid; the job was just terminated
+        unsafe { WaitForSingleObject(self.process.as_raw_handle(), INFINITE) };
+    }
+}
+
+/// SID of an AppContainer profile
+struct AppContainerSid(PSID);
+
+impl AppContainerSid {
+    /// Create the profile `name` if it does not exist and return its SID
+    fn ensure(name: &str) -> std::io::Result<Self> {
+        let wide_name = wide(OsStr::new(name));
+        let description = wide(OsStr::new("Cylo code execution sandbox"));
+        let mut sid: PSID = null_mut();
+        // SAFETY: strings are NUL-terminated and `sid` is a valid out pointer
+        let result = unsafe {
+            CreateAppContainerProfile(
+                wide_name.as_ptr(),
+                description.as_ptr(),
+                description.as_ptr(),
+                null(),
+                0,
+                &mut sid,
+            )
+        };
+
+        match result {
+            0.. => Ok(Self(sid)),
+            HRESULT_ALREADY_EXISTS => Self::derive(name),
+            error => Err(std::io::Error::other(format!(
+                "CreateAppContainerProfile failed with 0x{:08x}",
+                error as u32
+            ))),
+        }
+    }
+
+    /// SID of the existing profile `name`
+    fn derive(name: &str) -> std::io::Result<Self> {
+        let wide_name = wide(OsStr::new(name));
+        let mut sid: PSID = null_mut();
+        // SAFETY: the name is NUL-terminated and `sid` is a valid out pointer
+        let result =
+            unsafe { DeriveAppContainerSidFromAppContainerName(wide_name.as_ptr(), &mut sid) };
+        if result < 0 {
+            return Err(std::io::Error::other(format!(
+                "DeriveAppContainerSidFromAppContainerName failed with 0x{:08x}",
+                result as u32
+            )));
+        }
+        Ok(Self(sid))
+    }
+}
+
+impl Drop for AppContainerSid {
+    fn drop(&mut self) {
+        // SAFETY: the SID was allocated by the AppContainer APIs, which pair with FreeSid
+        unsafe { FreeSid(self.0) };
+    }
+}
+
+/// Well-known capability SID granted to an AppContainer
+struct CapabilitySid(Box<[u8; SID_BUFFER_BYTES]>);
+
+impl CapabilitySid {
+    fn new(kind: WELL_KNOWN_SID_TYPE) -> std::io::Result<Self> {
+        let mut buffer = Box::new([0u8; SID_BUFFER_BYTES]);
+        let mut size = SID_BUFFER_BYTES as u32;
+        // SAFETY: the buffer holds SECURITY_MAX_SID_SIZE bytes, as `size` says
+        let ok =
+            unsafe { CreateWellKnownSid(kind, null_mut(), buffer.as_mut_ptr().cast(), &mut size) };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self(buffer))
+    }
+
+    fn as_psid(&self) -> PSID {
+        self.0.as_ptr().cast_mut().cast()
+    }
+}
+
+/// Capabilities an AppContainer needs for `network`
+///
+/// `DenyAll` grants none, leaving the container without network access.
+fn network_capabilities(network: &NetworkPolicy) -> std::io::Result<Vec<CapabilitySid>> {
+    match network {
+        NetworkPolicy::Full => Ok(vec![
+            CapabilitySid::new(WinCapabilityInternetClientSid)?,
+            CapabilitySid::new(WinCapabilityPrivateNetworkClientServerSid)?,
+        ]),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Grant an AppContainer full access to `dir` and everything created under it
+fn grant_directory_access(dir: &Path, sid: PSID) -> std::io::Result<()> {
+    let path = wide(dir.as_os_str());
+    let mut dacl: *mut ACL = null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = null_mut();
+    // SAFETY: the path is NUL-terminated and the out pointers are valid
+    let status = unsafe {
+        GetNamedSecurityInfoW(
+            path.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            null_mut(),
+            null_mut(),
+            &mut dacl,
+            null_mut(),
+            &mut descriptor,
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return Err(std::io::Error::from_raw_os_error(status as i32));
+    }
+
+    let access = EXPLICIT_ACCESS_W {
+        grfAccessPermissions: GENERIC_ALL,
+        grfAccessMode: GRANT_ACCESS,
+        grfInheritance: SUB_CONTAINERS_AND_OBJECTS_INHERIT,
+        Trustee: TRUSTEE_W {
+            pMultipleTrustee: null_mut(),
+            MultipleTrusteeOperation: NO_MULTIPLE_TRUSTEE,
+            TrusteeForm: TRUSTEE_IS_SID,
+            TrusteeType: TRUSTEE_IS_WELL_KNOWN_GROUP,
+            ptstrName: sid.cast(),
+        },
+    };
+    let mut new_dacl: *mut ACL = null_mut();
+    // SAFETY: `dacl` points into `descriptor`, which is freed only afterwards
+    let status = unsafe { SetEntriesInAclW(1, &access, dacl, &mut new_dacl) };
+    // SAFETY: the descriptor was allocated by GetNamedSecurityInfoW
+    unsafe { LocalFree(descriptor) };
+    if status != ERROR_SUCCESS {
+        return Err(std::io::Error::from_raw_os_error(status as i32));
+    }
+
+    // SAFETY: the path is NUL-terminated and `new_dacl` is a valid ACL
+    let status = unsafe {
+        SetNamedSecurityInfoW(
+            path.as_ptr(),
+            SE_FILE_OBJECT,
+            DACL_SECURITY_INFORMATION,
+            null_mut(),
+            null_mut(),
+            new_dacl,
+            null(),
+        )
+    };
+    // SAFETY: the ACL was allocated by SetEntriesInAclW
+    unsafe { LocalFree(new_dacl.cast()) };
+    if status != ERROR_SUCCESS {
+        return Err(std::io::Error::from_raw_os_error(status as i32));
+    }
+
+    Ok(())
+}
+
+/// Restricted copy of this process's token: privileges stripped and
+/// administrator membership filtered as for a non-elevated user
+fn restricted_token() -> std::io::Result<OwnedHandle> {
+    let mut token: HANDLE = null_mut();
+    // SAFETY: the pseudo-handle needs no closing and `token` is a valid out pointer
+    let ok = unsafe {
+        OpenProcessToken(
+            GetCurrentProcess(),
+            TOKEN_DUPLICATE | TOKEN_ASSIGN_PRIMARY | TOKEN_QUERY,
+            &mut token,
+        )
+    };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: OpenProcessToken returned a handle we own
+    let token = unsafe { OwnedHandle::from_raw_handle(token) };
+
+    let mut restricted: HANDLE = null_mut();
+    // SAFETY: no SIDs or privileges are listed, so the null arrays are never read
+    let ok = unsafe {
+        CreateRestrictedToken(
+            token.as_raw_handle(),
+            DISABLE_MAX_PRIVILEGE | LUA_TOKEN,
+            0,
+            null(),
+            0,
+            null(),
+            0,
+            null(),
+            &mut restricted,
+        )
+    };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: CreateRestrictedToken returned a handle we own
+    Ok(unsafe { OwnedHandle::from_raw_handle(restricted) })
+}
+
+/// Process/thread attribute list for `STARTUPINFOEXW`
+struct AttributeList(Vec<usize>);
+
+impl AttributeList {
+    fn new(count: u32) -> std::io::Result<Self> {
+        let mut size = 0usize;
+        // SAFETY: a null list only queries the required size
+        unsafe { InitializeProcThreadAttributeList(null_mut(), count, 0, &mut size) };
+
+        let mut list = Self(vec![0usize; size.div_ceil(std::mem::size_of::<usize>())]);
+        // SAFETY: the buffer holds at least `size` bytes and is pointer-aligned
+        if unsafe { InitializeProcThreadAttributeList(list.as_ptr(), count, 0, &mut size) } == 0 {
+            // Nothing to delete: the list was never initialised
+            std::mem::forget(list);
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(list)
+    }
+
+    fn as_ptr(&mut self) -> LPPROC_THREAD_ATTRIBUTE_LIST {
+        self.0.as_mut_ptr().cast()
+    }
+
+    /// Set `attribute` to the `size` bytes at `value`
+    ///
+    /// # Safety
+    /// `value` must stay valid until the process has been created.
+    unsafe fn set<T>(
+        &mut self,
+        attribute: u32,
+        value: *const T,
+        size: usize,
+    ) -> std::io::Result<()> {
+        // SAFETY: the list is initialised; the caller keeps `value` alive
+        let ok = unsafe {
+            UpdateProcThreadAttribute(
+                self.as_ptr(),
+                0,
+                attribute as usize,
+                value.cast(),
+                size,
+                null_mut(),
+                null(),
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for AttributeList {
+    fn drop(&mut self) {
+        // SAFETY: the list was initialised in `new`
+        unsafe { DeleteProcThreadAttributeList(self.as_ptr()) };
+    }
+}
+
+/// Anonymous pipe whose ends are inheritable until marked otherwise
+fn pipe() -> std::io::Result<(OwnedHandle, OwnedHandle)> {
+    let attributes = SECURITY_ATTRIBUTES {
+        nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: null_mut(),
+        bInheritHandle: TRUE,
+    };
+    let (mut read, mut write): (HANDLE, HANDLE) = (null_mut(), null_mut());
+    // SAFETY: the out pointers and attributes are valid for the call
+    if unsafe { CreatePipe(&mut read, &mut write, &attributes, 0) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: CreatePipe returned two handles we own
+    Ok(unsafe {
+        (
+            OwnedHandle::from_raw_handle(read),
+            OwnedHandle::from_raw_handle(write),
+        )
+    })
+}
+
+/// Keep our end of a pipe out of the child
+fn keep_private(handle: &OwnedHandle) -> std::io::Result<()> {
+    // SAFETY: the handle is valid for the duration of the call
+    if unsafe { SetHandleInformation(handle.as_raw_handle(), HANDLE_FLAG_INHERIT, 0) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Launch `command_line` isolated and suspended, place it in `job`, then resume it
+///
+/// Only the three stdio pipe ends are inherited. The process cannot run a
+/// single instruction before its job limits apply.
+fn spawn_contained(
+    command_line: &str,
+    exec_dir: &Path,
+    environment: &[u16],
+    isolation: &Isolation,
+    network: &NetworkPolicy,
+    job: Arc<JobObject>,
+) -> std::io::Result<JobChild> {
+    let (stdin_read, stdin_write) = pipe()?;
+    let (stdout_read, stdout_write) = pipe()?;
+    let (stderr_read, stderr_write) = pipe()?;
+    for ours in [&stdin_write, &stdout_read, &stderr_read] {
+        keep_private(ours)?;
+    }
+
+    let mut attributes = AttributeList::new(2)?;
+    let inherited = [
+        stdin_read.as_raw_handle(),
+        stdout_write.as_raw_handle(),
+        stderr_write.as_raw_handle(),
+    ];
+    // SAFETY: `inherited` lives until the end of this function
+    unsafe {
+        attributes.set(
+            PROC_THREAD_ATTRIBUTE_HANDLE_LIST,
+            inherited.as_ptr(),
+            std::mem::size_of_val(&inherited),
+        )?;
+    }
+
+    // Everything referenced by the security capabilities must outlive process creation
+    let container;
+    let capabilities;
+    let mut granted: Vec<SID_AND_ATTRIBUTES>;
+    let security: SECURITY_CAPABILITIES;
+    let token = match isolation {
+        Isolation::AppContainer(name) => {
+            container = AppContainerSid::derive(name)?;
+            capabilities = network_capabilities(network)?;
+            granted = capabilities
+                .iter()
+                .map(|capability| SID_AND_ATTRIBUTES {
+                    Sid: capability.as_psid(),
+                    Attributes: SE_GROUP_ENABLED,
+                })
+                .collect();
+            security = SECURITY_CAPABILITIES {
+                AppContainerSid: container.0,
+                Capabilities: granted.as_mut_ptr(),
+                CapabilityCount: granted.len() as u32,
+                Reserved: 0,
+            };
+            // SAFETY: `security` and everything it points to live until the end of this function
+            unsafe {
+                attributes.set(
+                    PROC_THREAD_ATTRIBUTE_SECURITY_CAPABILITIES,
+                    &security,
+                    std::mem::size_of::<SECURITY_CAPABILITIES>(),
+                )?;
+            }
+            None
+        }
+        Isolation::RestrictedToken => Some(restricted_token()?),
+    };
+
+    // SAFETY: the structure is plain data for which all-zero is valid
+    let mut startup: STARTUPINFOEXW = unsafe { std::mem::zeroed() };
+    startup.StartupInfo.cb = std::mem::size_of::<STARTUPINFOEXW>() as u32;
+    startup.StartupInfo.dwFlags = STARTF_USESTDHANDLES;
+    startup.StartupInfo.hStdInput = stdin_read.as_raw_handle();
+    startup.StartupInfo.hStdOutput = stdout_write.as_raw_handle();
+    startup.StartupInfo.hStdError = stderr_write.as_raw_handle();
+    startup.lpAttributeList = attributes.as_ptr();
+
+    let mut command_line = wide(OsStr::new(command_line));
+    let directory = wide(exec_dir.as_os_str());
+    let flags = CREATE_SUSPENDED
+        | CREATE_NO_WINDOW
+        | CREATE_UNICODE_ENVIRONMENT
+        | EXTENDED_STARTUPINFO_PRESENT;
+    // SAFETY: the structure is plain data for which all-zero is valid
+    let mut info: PROCESS_INFORMATION = unsafe { std::mem::zeroed() };
+
+    // SAFETY: every pointer refers to a live, NUL-terminated buffer or structure
+    let created = unsafe {
+        match &token {
+            Some(token) => CreateProcessAsUserW(
+                token.as_raw_handle(),
+                null(),
+                command_line.as_mut_ptr(),
+                null(),
+                null(),
+                TRUE,
+                flags,
+                environment.as_ptr().cast::<c_void>(),
+                directory.as_ptr(),
+                &startup.StartupInfo,
+                &mut info,
+            ),
+            None => CreateProcessW(
+                null(),
+                command_line.as_mut_ptr(),
+                null(),
+                null(),
+                TRUE,
+                flags,
+                environment.as_ptr().cast::<c_void>(),
+                directory.as_ptr(),
+                &startup.StartupInfo,
+                &mut info,
+            ),
+        }
+    };
+    if created == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // SAFETY: CreateProcess returned process and thread handles we own
+    let process = unsafe { OwnedHandle::from_raw_handle(info.hProcess) };
+    let thread = unsafe { OwnedHandle::from_raw_handle(info.hThread) };
+
+    if let Err(e) = job.assign(&process) {
+        // SAFETY: the suspended process has not run; terminating it is always safe
+        unsafe { TerminateProcess(process.as_raw_handle(), 1) };
+        return Err(e);
+    }
+    // SAFETY: the thread handle is valid and the thread is suspended
+    if unsafe { ResumeThread(thread.as_raw_handle()) } == u32::MAX {
+        let error = std::io::Error::last_os_error();
+        job.terminate(1);
+        return Err(error);
+    }
+    drop(thread);
+
+    // The child holds its own copies now; ours would keep the pipes from closing
+    drop((stdin_read, stdout_write, stderr_write));
+
+    Ok(JobChild {
+        process,
+        job,
+        stdin: Some(File::from(stdin_write)),
+        stdout: Some(File::from(stdout_read)),
+        stderr: Some(File::from(stderr_read)),
+    })
+}
+
+/// NUL-terminated UTF-16 copy of `value`
+fn wide(value: &OsStr) -> Vec<u16> {
+    value.encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// Command line for `program` and `args`, quoted for the MSVC runtime parser
+fn command_line(program: &str, args: &[String]) -> String {
+    let mut line = String::new();
+    for arg in std::iter::once(program).chain(args.iter().map(String::as_str)) {
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        push_quoted(&mut line, arg);
+    }
+    line
+}
+
+/// Append `arg` so `CommandLineToArgvW` reads it back unchanged
+fn push_quoted(line: &mut String, arg: &str) {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\u{b}', '"']) {
+        line.push_str(arg);
+        return;
+    }
+
+    line.push('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // Backslashes before a quote are escaped, then the quote itself
+                line.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                line.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                line.extend(std::iter::repeat_n('\\', backslashes));
+                line.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    // Backslashes before the closing quote are escaped too
+    line.extend(std::iter::repeat_n('\\', backslashes * 2));
+    line.push('"');
+}
+
+/// Environment block for the child: ours, with `TEMP`/`TMP` pointing into the
+/// workspace and the request's variables on top
+///
+/// Windows expects the block sorted case-insensitively with one entry per
+/// name, which keying on the uppercased name provides.
+fn environment_block(overrides: &HashMap<String, String>, exec_dir: &Path) -> Vec<u16> {
+    let mut vars: BTreeMap<String, (OsString, OsString)> = std::env::vars_os()
+        .map(|(key, value)| (key.to_string_lossy().to_uppercase(), (key, value)))
+        .collect();
+
+    // AppContainers cannot write to the user's temp directory
+    for key in ["TEMP", "TMP"] {
+        vars.insert(
+            key.to_string(),
+            (OsString::from(key), exec_dir.as_os_str().to_owned()),
+        );
+    }
+    for (key, value) in overrides {
+        vars.insert(key.to_uppercase(), (key.into(), value.into()));
+    }
+
+    let mut block = Vec::new();
+    for (key, value) in vars.values() {
+        block.extend(key.encode_wide());
+        block.push(u16::from(b'='));
+        block.extend(value.encode_wide());
+        block.push(0);
+    }
+    if block.is_empty() {
+        block.push(0);
+    }
+    block.push(0);
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_round_trip_msvc_quoting() {
+        assert_eq!(
+            command_line("python", &["main.py".to_string()]),
+            "python main.py"
+        );
+        assert_eq!(
+            command_line("bash", &["-c".to_string(), "echo \"hi\" there".to_string()]),
+            r#"bash -c "echo \"hi\" there""#
+        );
+        assert_eq!(
+            command_line("node", &[r"C:\My Files\".to_string(), String::new()]),
+            r#"node "C:\My Files\\" """#
+        );
+    }
+
+    #[test]
+    fn environment_block_overrides_and_terminates() {
+        let dir = PathBuf::from(r"C:\cylo\exec-1");
+        let overrides = HashMap::from([("temp".to_string(), r"D:\t".to_string())]);
+        let block = String::from_utf16(&environment_block(&overrides, &dir)).unwrap();
+
+        assert!(block.ends_with("\0\0"));
+        assert!(block.contains("TMP=C:\\cylo\\exec-1\0"));
+        assert!(block.contains("temp=D:\\t\0"));
+        assert!(!block.contains("TEMP="));
+    }
+
+    #[test]
+    fn isolation_comes_from_config() {
+        let root = Path::new(r"C:\cylo");
+        let config = BackendConfig::new("test");
+        assert!(matches!(
+            Isolation::from_config(&config, root),
+            Ok(Isolation::AppContainer(name)) if name.starts_with("cylo.sandbox.")
+        ));
+
+        let restricted = config.clone().with_config(ISOLATION_OPTION, "restricted");
+        assert_eq!(
+            Isolation::from_config(&restricted, root).unwrap(),
+            Isolation::RestrictedToken
+        );
+
+        let unknown = config.with_config(ISOLATION_OPTION, "none");
+        assert!(Isolation::from_config(&unknown, root).is_err());
+    }
+
+    #[test]
+    fn network_policy_support() {
+        let container = Isolation::AppContainer(container_name(Path::new(r"C:\cylo")));
+        let allow = NetworkPolicy::allow_list(["example.com"], Vec::<String>::new());
+
+        assert!(WindowsBackend::check_network_policy(&container, &NetworkPolicy::DenyAll).is_ok());
+        assert!(WindowsBackend::check_network_policy(&container, &NetworkPolicy::Full).is_ok());
+        assert!(WindowsBackend::check_network_policy(&container, &allow).is_err());
+        assert!(
+            WindowsBackend::check_network_policy(
+                &Isolation::RestrictedToken,
+                &NetworkPolicy::DenyAll
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn execution_command_preparation() {
+        let (prog, args) = WindowsBackend::prepare_execution_command("python", "").unwrap();
+        assert_eq!(prog, "python");
+        assert_eq!(args, vec!["main.py"]);
+
+        let (prog, args) = WindowsBackend::prepare_execution_command("rust", "").unwrap();
+        assert_eq!(prog, "bash");
+        assert!(args[1].contains("rustc"));
+
+        assert!(WindowsBackend::prepare_execution_command("cobol", "").is_err());
+    }
+
+    #[tokio::test]
+    async fn wall_clock_deadline_terminates_job() {
+        let root = std::env::temp_dir().join("cylo_test_windows");
+        let config = BackendConfig::new("test").with_config(ISOLATION_OPTION, "restricted");
+        let Ok(backend) = WindowsBackend::new(root.display().to_string(), config) else {
+            return;
+        };
+
+        let request = ExecutionRequest::new("import time\ntime.sleep(30)", "python")
+            .with_timeout(Duration::from_secs(1));
+        let result = backend.execute_code(request).await.unwrap();
+        if !result.stderr.contains("Failed to spawn") {
+            assert_eq!(result.limit_exceeded, Some(LimitExceeded::WallClock));
+            assert_eq!(result.exit_code, limits::TIMEOUT_EXIT_CODE);
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -7,6 +7,7 @@
 // - Cylo::LandLock("/path/to/jail").instance("name")
 // - Cylo::FireCracker("rust:alpine3.20").instance("name")
 // - Cylo::Apple("python:alpine3.20").instance("name")
+// - Cylo::Windows("C:\\cylo\\sandbox").instance("name")
 //
 // Zero allocation patterns with string interning and efficient enum dispatch.
 // ============================================================================
@@ -21,6 +22,7 @@ use serde::{Deserialize, Serialize};
 /// - LandLock: Linux kernel-based sandboxing with filesystem restrictions
 /// - FireCracker: Lightweight microVMs for complete isolation
 /// - Apple: Apple's containerization framework for macOS
+/// - Windows: Job Objects and AppContainers on Windows
 /// - SweetMcpPlugin: WASM-based SweetMCP plugin execution
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Cylo {
@@ -36,6 +38,10 @@ pub enum Cylo {
     /// Example: Cylo::Apple("python:alpine3.20")
     Apple(String),
 
+    /// Windows Job Object + AppContainer backend with sandbox root directory
+    /// Example: Cylo::Windows("C:\\cylo\\sandbox")
+    Windows(String),
+
     /// SweetMCP plugin execution with plugin path
     /// Example: Cylo::SweetMcpPlugin("./plugins/eval-py.wasm")
     SweetMcpPlugin(String),
@@ -71,6 +77,7 @@ impl Cylo {
     /// - LandLock: Validates path exists and is accessible
     /// - FireCracker: Validates image format and registry accessibility
     /// - Apple: Validates image format and platform compatibility
+    /// - Windows: Validates the sandbox root is an absolute Windows path
     ///
     /// # Returns
     /// Ok(()) if configuration is valid, Err(CyloError) otherwise
@@ -133,6 +140,24 @@ impl Cylo {
                 Ok(())
             }
 
+            Cylo::Windows(sandbox_root) => {
+                if sandbox_root.is_empty() {
+                    return Err(CyloError::InvalidConfiguration {
+                        backend: "Windows",
+                        message: "Sandbox root cannot be empty",
+                    });
+                }
+
+                if !is_windows_absolute(sandbox_root) {
+                    return Err(CyloError::InvalidConfiguration {
+                        backend: "Windows",
+                        message: "Sandbox root must be absolute (e.g., 'C:\\cylo\\sandbox')",
+                    });
+                }
+
+                Ok(())
+            }
+
             Cylo::SweetMcpPlugin(plugin_path) => {
                 if plugin_path.is_empty() {
                     return Err(CyloError::InvalidConfiguration {
@@ -161,6 +186,7 @@ impl Cylo {
             Cylo::LandLock(_) => "LandLock",
             Cylo::FireCracker(_) => "FireCracker",
             Cylo::Apple(_) => "Apple",
+            Cylo::Windows(_) => "Windows",
             Cylo::SweetMcpPlugin(_) => "SweetMcpPlugin",
         }
     }
@@ -172,6 +198,7 @@ impl Cylo {
             Cylo::LandLock(path) => path,
             Cylo::FireCracker(image) => image,
             Cylo::Apple(image) => image,
+            Cylo::Windows(sandbox_root) => sandbox_root,
             Cylo::SweetMcpPlugin(plugin_path) => plugin_path,
         }
     }
@@ -183,6 +210,7 @@ impl fmt::Display for Cylo {
             Cylo::LandLock(path) => write!(f, "LandLock({path})"),
             Cylo::FireCracker(image) => write!(f, "FireCracker({image})"),
             Cylo::Apple(image) => write!(f, "Apple({image})"),
+            Cylo::Windows(sandbox_root) => write!(f, "Windows({sandbox_root})"),
             Cylo::SweetMcpPlugin(plugin_path) => write!(f, "SweetMcpPlugin({plugin_path})"),
         }
     }
@@ -288,6 +316,14 @@ pub enum CyloError {
     #[error("Instance '{name}' not found in registry")]
     InstanceNotFound { name: String },
 
+    /// Session not found (never created, closed, or expired)
+    #[error("Session '{id}' not found")]
+    SessionNotFound { id: String },
+
+    /// Journal entry not found
+    #[error("Journal entry '{id}' not found")]
+    JournalEntryNotFound { id: String },
+
     /// Instance with the same name already exists
     #[error("Instance '{name}' already exists with different configuration")]
     InstanceConflict { name: String },
@@ -435,6 +471,7 @@ pub fn validate_instance_name(name: &str) -> CyloResult<()> {
 /// Different backends have different validation requirements:
 /// - LandLock: Path must be absolute and exist
 /// - FireCracker/Apple: Image specification must include tag
+/// - Windows: Sandbox root must be an absolute Windows path
 ///
 /// # Arguments
 /// * `env` - The Cylo environment to validate
@@ -477,6 +514,19 @@ pub fn validate_environment_spec(env: &Cylo) -> CyloResult<()> {
 
             Ok(())
         }
+        Cylo::Windows(sandbox_root) => {
+            if sandbox_root.is_empty() {
+                return Err(CyloError::validation("Windows sandbox root cannot be empty"));
+            }
+
+            if !is_windows_absolute(sandbox_root) {
+                return Err(CyloError::validation(
+                    "Windows sandbox root must be absolute (e.g., 'C:\\cylo\\sandbox')",
+                ));
+            }
+
+            Ok(())
+        }
         Cylo::SweetMcpPlugin(plugin_path) => {
             if plugin_path.is_empty() {
                 return Err(CyloError::validation("Plugin path cannot be empty"));
@@ -493,6 +543,18 @@ pub fn validate_environment_spec(env: &Cylo) -> CyloResult<()> {
     }
 }
 
+/// Whether `path` is an absolute Windows path: `C:\...`, `C:/...` or `\\server\share`
+///
+/// Checked textually so specs can be validated on any host.
+fn is_windows_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    drive || path.starts_with("\\\\")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -541,6 +603,20 @@ mod tests {
         assert!(empty.validate().is_err());
     }
 
+    #[test]
+    fn windows_sandbox_root_validation() {
+        let cylo = Cylo::Windows("C:\\cylo\\sandbox".to_string());
+        assert_eq!(cylo.backend_type(), "Windows");
+        assert!(cylo.validate().is_ok());
+        assert!(validate_environment_spec(&cylo).is_ok());
+
+        assert!(Cylo::Windows("D:/sandbox".to_string()).validate().is_ok());
+        assert!(Cylo::Windows("\\\\server\\share".to_string()).validate().is_ok());
+        assert!(Cylo::Windows("sandbox".to_string()).validate().is_err());
+        assert!(Cylo::Windows("/tmp/sandbox".to_string()).validate().is_err());
+        assert!(Cylo::Windows("".to_string()).validate().is_err());
+    }
+
     #[test]
     fn image_validation() {
         // Valid image with tag
@@ -7,16 +7,33 @@
 // - Named instance registration and lookup
 // - Thread-safe access with lock-free operations where possible
 // - Instance lifecycle management and health monitoring
+// - Health supervision with automatic instance replacement
+// - Persistent interpreter sessions with idle expiry
+// - Warm pool of pre-started sandboxes for short snippets
+// - Opt-in cache of results for deterministic snippets
+// - Execution journal with history queries and replay
+// - Persisted instance records with crash recovery
 // - Automatic cleanup and resource management
 // ============================================================================
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, SystemTime};
 
 use crate::async_task::{AsyncTask, AsyncTaskBuilder};
-use crate::backends::{BackendConfig, ExecutionBackend, HealthStatus, create_backend};
-use crate::execution_env::{CyloError, CyloInstance, CyloResult};
+use crate::backends::{
+    BackendConfig, ExecutionBackend, ExecutionRequest, ExecutionResult, HealthStatus, ReplSession,
+    SessionHandle, create_backend,
+};
+use crate::execution_env::{Cylo, CyloError, CyloInstance, CyloResult};
+use crate::journal::{ExecutionJournal, JournalEntry, JournalQuery, ReplayOutcome};
+use crate::metadata::{InstanceRecord, MetadataManager};
+use crate::result_cache::{CacheKey, ResultCache, ResultCacheConfig, ResultCacheMetrics};
+use crate::state::PipelineEvent;
+use crate::supervisor::{RecoveryState, SupervisorConfig, SupervisorHandle, SupervisorReport};
+use crate::warm_pool::{WarmPool, WarmPoolConfig, WarmPoolMetrics};
 
 /// Thread-safe instance manager for Cylo execution environments
 ///
@@ -36,6 +53,37 @@ pub struct InstanceManager {
 
     /// Maximum idle time before cleanup
     max_idle_time: Duration,
+
+    /// Registry of persistent interpreter sessions
+    sessions: Arc<RwLock<HashMap<String, ManagedSession>>>,
+
+    /// Maximum idle time before a session is torn down
+    session_idle_timeout: Duration,
+
+    /// Pre-started sandboxes served to eligible `execute` calls
+    warm_pool: Arc<WarmPool>,
+
+    /// Results replayed to repeated `execute` calls, when enabled
+    result_cache: Arc<ResultCache>,
+
+    /// Record of every execution made through the manager
+    journal: Arc<ExecutionJournal>,
+
+    /// Where instance records are persisted, if anywhere
+    metadata: Option<Arc<MetadataManager>>,
+
+    /// Failure history of unhealthy instances, kept by the supervisor
+    recovery: Arc<RwLock<HashMap<String, RecoveryState>>>,
+}
+
+/// Session registered with the manager
+#[derive(Debug)]
+struct ManagedSession {
+    /// Instance hosting the session
+    instance_id: String,
+
+    /// The live session
+    session: Arc<ReplSession>,
 }
 
 /// Managed instance wrapper with metadata
@@ -44,6 +92,12 @@ struct ManagedInstance {
     /// The backend instance
     backend: Arc<dyn ExecutionBackend>,
 
+    /// Configuration the instance was created from
+    spec: CyloInstance,
+
+    /// Registration timestamp
+    registered_at: SystemTime,
+
     /// Last access timestamp
     last_accessed: SystemTime,
 
@@ -57,6 +111,17 @@ struct ManagedInstance {
     ref_count: u32,
 }
 
+/// Outcome of reconciling persisted instance records
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Stale instances registered again from their record
+    pub adopted: Vec<String>,
+    /// Stale instances whose resources were reclaimed and record deleted
+    pub cleaned: Vec<String>,
+    /// Instances still live, here or in another process, left untouched
+    pub skipped: Vec<String>,
+}
+
 impl InstanceManager {
     /// Create a new instance manager
     ///
@@ -68,6 +133,13 @@ impl InstanceManager {
             default_config: BackendConfig::new("default"),
             health_check_interval: Duration::from_secs(60),
             max_idle_time: Duration::from_secs(300), // 5 minutes
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            session_idle_timeout: Duration::from_secs(600), // 10 minutes
+            warm_pool: Arc::new(WarmPool::new(disabled_pool())),
+            result_cache: Arc::new(ResultCache::new(disabled_cache())),
+            journal: Arc::new(ExecutionJournal::in_memory()),
+            metadata: None,
+            recovery: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -90,9 +162,62 @@ impl InstanceManager {
             default_config: config,
             health_check_interval,
             max_idle_time,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            session_idle_timeout: Duration::from_secs(600),
+            warm_pool: Arc::new(WarmPool::new(disabled_pool())),
+            result_cache: Arc::new(ResultCache::new(disabled_cache())),
+            journal: Arc::new(ExecutionJournal::in_memory()),
+            metadata: None,
+            recovery: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Set the idle time after which sessions are torn down
+    pub fn with_session_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.session_idle_timeout = timeout;
+        self
+    }
+
+    /// Enable the warm pool
+    ///
+    /// Eligible `execute` calls are served by pre-started interpreters; each
+    /// one is discarded after use and replaced with a fresh sandbox.
+    pub fn with_warm_pool(mut self, config: WarmPoolConfig) -> Self {
+        self.warm_pool = Arc::new(WarmPool::new(config));
+        self
+    }
+
+    /// Enable the result cache
+    ///
+    /// Successful `execute` results are kept for `config.ttl` and returned
+    /// for later requests with the same instance, language, code and
+    /// environment instead of running them again. Requests marked
+    /// `no_cache` always run. Only enable this where snippets are
+    /// deterministic.
+    pub fn with_result_cache(mut self, config: ResultCacheConfig) -> Self {
+        self.result_cache = Arc::new(ResultCache::new(config));
+        self
+    }
+
+    /// Record executions in `journal` (e.g. one opened on a file)
+    pub fn with_journal(mut self, journal: ExecutionJournal) -> Self {
+        self.journal = Arc::new(journal);
+        self
+    }
+
+    /// Persist instance records through `metadata`
+    ///
+    /// Each registered instance is written to the metadata manager's
+    /// instance records directory along with its session processes and
+    /// scratch directories, and deleted again when the instance is removed.
+    /// Call `reconcile` after construction to recover instances left behind
+    /// by a previous process. A records directory should be used by one
+    /// manager at a time.
+    pub fn with_persistence(mut self, metadata: MetadataManager) -> Self {
+        self.metadata = Some(Arc::new(metadata));
+        self
+    }
+
     /// Register a new named instance
     ///
     /// Creates and registers a backend instance for the specified
@@ -105,6 +230,8 @@ impl InstanceManager {
     /// AsyncTask that resolves when instance is registered
     pub fn register_instance(&self, instance: CyloInstance) -> AsyncTask<CyloResult<()>> {
         let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let metadata = self.metadata.clone();
         let default_config = self.default_config.clone();
 
         AsyncTaskBuilder::new(async move {
@@ -132,6 +259,8 @@ impl InstanceManager {
 
             let managed_instance = ManagedInstance {
                 backend: Arc::from(backend),
+                spec: instance.clone(),
+                registered_at: SystemTime::now(),
                 last_accessed: SystemTime::now(),
                 last_health: health_result,
                 last_health_check: Some(SystemTime::now()),
@@ -147,6 +276,14 @@ impl InstanceManager {
                 instances.insert(instance.id(), managed_instance);
             }
 
+            let id = instance.id();
+            persist_records(
+                metadata.as_deref(),
+                &instances_lock,
+                &sessions_lock,
+                |candidate| candidate == id,
+            );
+
             Ok(())
         })
         .spawn()
@@ -296,6 +433,10 @@ impl InstanceManager {
     /// AsyncTask that resolves when instance is removed
     pub fn remove_instance(&self, instance_id: &str) -> AsyncTask<CyloResult<()>> {
         let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let warm_pool = Arc::clone(&self.warm_pool);
+        let result_cache = Arc::clone(&self.result_cache);
+        let metadata = self.metadata.clone();
         let instance_id = instance_id.to_string();
 
         AsyncTaskBuilder::new(async move {
@@ -316,11 +457,25 @@ impl InstanceManager {
                     attempts += 1;
                 }
 
+                // Tear down sessions and warm sandboxes hosted by this instance
+                let sessions = take_sessions(&sessions_lock, |managed| {
+                    managed.instance_id == instance_id
+                })?;
+                for session in sessions
+                    .into_iter()
+                    .chain(warm_pool.drain(Some(&instance_id)))
+                {
+                    let _ = session.close().await;
+                }
+                result_cache.invalidate(Some(&instance_id));
+
                 // Perform cleanup
                 if let Err(e) = managed.backend.cleanup().await {
                     // Log cleanup error but don't fail the removal
                     log::warn!("Failed to cleanup instance {}: {}", instance_id, e);
                 }
+
+                forget_record(metadata.as_deref(), &instance_id);
             }
 
             Ok(())
@@ -369,62 +524,83 @@ impl InstanceManager {
         let instances_lock = Arc::clone(&self.instances);
 
         AsyncTaskBuilder::new(async move {
-            let mut results = HashMap::new();
-
-            // Get list of instances to check
-            let instance_list = {
-                let instances = instances_lock.read().map_err(|e| {
-                    CyloError::internal(format!("Failed to acquire read lock: {e}"))
-                })?;
-
-                instances
-                    .iter()
-                    .map(|(id, managed)| (id.clone(), managed.backend.clone()))
-                    .collect::<Vec<_>>()
-            };
+            let results = probe_instances(&instances_lock).await?;
+            Ok(results.into_iter().collect())
+        })
+        .spawn()
+    }
 
-            // Perform health checks concurrently
-            let mut health_tasks = Vec::new();
+    /// Default supervisor settings, probing at the manager's health check interval
+    pub fn supervisor_config(&self) -> SupervisorConfig {
+        SupervisorConfig::default().with_interval(self.health_check_interval)
+    }
 
-            for (instance_id, backend) in instance_list {
-                let id = instance_id.clone();
-                let health_task = AsyncTaskBuilder::new(async move {
-                    let health = backend.health_check().await;
-                    (id, health)
-                })
-                .spawn();
-                health_tasks.push(health_task);
-            }
+    /// Probe every instance once and replace the ones that keep failing
+    ///
+    /// An instance is replaced once it has failed `failure_threshold` probes
+    /// in a row: a fresh backend is built from its spec and health checked,
+    /// then swapped in, and the sessions and warm sandboxes of the old
+    /// backend are torn down. Instances with executions in flight are left
+    /// for a later pass. A failed replacement is retried after the config's
+    /// exponential backoff. Findings are sent to `events` when given.
+    ///
+    /// # Arguments
+    /// * `config` - Failure threshold and backoff
+    /// * `events` - Receiver of `PipelineEvent`s describing the pass
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to what the pass found and did
+    pub fn supervise_once(
+        &self,
+        config: &SupervisorConfig,
+        events: Option<&Sender<PipelineEvent>>,
+    ) -> AsyncTask<CyloResult<SupervisorReport>> {
+        let manager = self.clone_for_supervisor();
+        let config = config.clone();
+        let events = events.cloned();
+
+        AsyncTaskBuilder::new(async move { manager.supervise(&config, events.as_ref()).await })
+            .spawn()
+    }
 
-            // Collect results
-            for task in health_tasks {
-                match task.await {
-                    Ok((instance_id, health)) => {
-                        match health {
-                            Ok(health_status) => {
-                                results.insert(instance_id, health_status);
-                            }
-                            Err(_) => {
-                                // Health check failed, insert unhealthy status
-                                results.insert(
-                                    instance_id,
-                                    HealthStatus::unhealthy("Health check failed"),
-                                );
-                            }
-                        }
-                    }
-                    Err(_) => {
-                        // Task failed, skip this instance
+    /// Run `supervise_once` every `config.interval` until the handle is stopped
+    ///
+    /// The first pass runs one interval after starting.
+    ///
+    /// # Arguments
+    /// * `config` - Probe interval, failure threshold and backoff
+    /// * `events` - Receiver of `PipelineEvent`s from every pass
+    ///
+    /// # Returns
+    /// Handle that stops the loop when stopped or dropped
+    pub fn start_supervisor(
+        &self,
+        config: SupervisorConfig,
+        events: Option<Sender<PipelineEvent>>,
+    ) -> SupervisorHandle {
+        let manager = self.clone_for_supervisor();
+
+        SupervisorHandle::new(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.interval.max(Duration::from_millis(10)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                match manager.supervise(&config, events.as_ref()).await {
+                    Ok(report) if !report.replaced.is_empty() || !report.failed.is_empty() => {
+                        log::info!(
+                            "Supervisor replaced {:?}, failed to replace {:?}",
+                            report.replaced,
+                            report.failed
+                        );
                     }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Instance supervision pass failed: {}", e),
                 }
-
-                // Note: Health status is already stored in results HashMap
-                // The stored health status in instances is updated when instances are accessed
             }
-
-            Ok(results)
-        })
-        .spawn()
+        }))
     }
 
     /// Clean up idle instances
@@ -436,12 +612,27 @@ impl InstanceManager {
     /// AsyncTask that resolves with count of cleaned up instances
     pub fn cleanup_idle_instances(&self) -> AsyncTask<CyloResult<u32>> {
         let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let warm_pool = Arc::clone(&self.warm_pool);
+        let metadata = self.metadata.clone();
         let max_idle_time = self.max_idle_time;
 
         AsyncTaskBuilder::new(async move {
             let now = SystemTime::now();
             let mut to_remove = Vec::new();
 
+            // Instances hosting live sessions are reaped with their sessions
+            let hosting: std::collections::HashSet<String> = {
+                let sessions = sessions_lock.read().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire read lock: {e}"))
+                })?;
+
+                sessions
+                    .values()
+                    .map(|managed| managed.instance_id.clone())
+                    .collect()
+            };
+
             // Identify idle instances
             {
                 let instances = instances_lock.read().map_err(|e| {
@@ -453,7 +644,10 @@ impl InstanceManager {
                         .duration_since(managed.last_accessed)
                         .unwrap_or(Duration::from_secs(0));
 
-                    if idle_time > max_idle_time && managed.ref_count == 0 {
+                    if idle_time > max_idle_time
+                        && managed.ref_count == 0
+                        && !hosting.contains(instance_id)
+                    {
                         to_remove.push(instance_id.clone());
                     }
                 }
@@ -471,12 +665,18 @@ impl InstanceManager {
                 };
 
                 if let Some(managed) = managed_instance {
+                    for session in warm_pool.drain(Some(&instance_id)) {
+                        let _ = session.close().await;
+                    }
+
                     // Perform cleanup
                     if let Err(e) = managed.backend.cleanup().await {
                         log::warn!("Failed to cleanup idle instance {}: {}", instance_id, e);
                     } else {
                         removed_count += 1;
                     }
+
+                    forget_record(metadata.as_deref(), &instance_id);
                 }
             }
 
@@ -485,6 +685,539 @@ impl InstanceManager {
         .spawn()
     }
 
+    /// Create a persistent interpreter session on a registered instance
+    ///
+    /// Subsequent `execute_in_session` calls share interpreter state, so
+    /// variables, imports and definitions survive between executions.
+    ///
+    /// # Arguments
+    /// * `instance_id` - Instance that hosts the session
+    /// * `language` - Interpreter language (python or javascript)
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to the new session's handle
+    pub fn create_session(
+        &self,
+        instance_id: &str,
+        language: &str,
+    ) -> AsyncTask<CyloResult<SessionHandle>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let metadata = self.metadata.clone();
+        let instance_id = instance_id.to_string();
+        let language = language.to_string();
+        let limits = self.default_config.default_limits.clone();
+
+        AsyncTaskBuilder::new(async move {
+            let backend = {
+                let mut instances = instances_lock.write().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                })?;
+
+                let managed = instances.get_mut(&instance_id).ok_or_else(|| {
+                    CyloError::InstanceNotFound {
+                        name: instance_id.clone(),
+                    }
+                })?;
+                managed.last_accessed = SystemTime::now();
+                managed.backend.clone()
+            };
+
+            let session = ReplSession::start(backend.as_ref(), &language, limits)?;
+            let handle = SessionHandle {
+                id: session.id().to_string(),
+                instance_id: instance_id.clone(),
+                language: session.language().to_string(),
+            };
+
+            {
+                let mut sessions = sessions_lock.write().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                })?;
+
+                sessions.insert(
+                    handle.id.clone(),
+                    ManagedSession {
+                        instance_id: instance_id.clone(),
+                        session: Arc::new(session),
+                    },
+                );
+            }
+
+            persist_records(
+                metadata.as_deref(),
+                &instances_lock,
+                &sessions_lock,
+                |candidate| candidate == instance_id,
+            );
+
+            Ok(handle)
+        })
+        .spawn()
+    }
+
+    /// Execute code in an existing session
+    ///
+    /// # Arguments
+    /// * `session_id` - Session identifier from `create_session`
+    /// * `code` - Snippet to execute
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to the snippet's execution result
+    pub fn execute_in_session(
+        &self,
+        session_id: &str,
+        code: &str,
+    ) -> AsyncTask<CyloResult<ExecutionResult>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let journal = Arc::clone(&self.journal);
+        let session_id = session_id.to_string();
+        let code = code.to_string();
+        let timeout = self.default_config.default_timeout;
+
+        AsyncTaskBuilder::new(async move {
+            let (instance_id, session) = {
+                let sessions = sessions_lock.read().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire read lock: {e}"))
+                })?;
+
+                let managed = sessions.get(&session_id).ok_or_else(|| {
+                    CyloError::SessionNotFound {
+                        id: session_id.clone(),
+                    }
+                })?;
+                (managed.instance_id.clone(), Arc::clone(&managed.session))
+            };
+
+            // Keep the hosting instance from being reaped as idle
+            let backend = match instances_lock.write() {
+                Ok(mut instances) => instances.get_mut(&instance_id).map(|managed| {
+                    managed.last_accessed = SystemTime::now();
+                    managed.backend.backend_type()
+                }),
+                Err(_) => None,
+            };
+
+            let request =
+                ExecutionRequest::new(code.clone(), session.language()).with_timeout(timeout);
+            let result = session.execute(code, timeout).await?;
+
+            let entry = JournalEntry::new(
+                &instance_id,
+                backend.unwrap_or("unknown"),
+                &request,
+                &result,
+            )
+            .in_session(session_id);
+            if let Err(e) = journal.record(entry) {
+                log::warn!("Failed to journal session execution: {}", e);
+            }
+
+            Ok(result)
+        })
+        .spawn()
+    }
+
+    /// Tear down a session
+    ///
+    /// # Arguments
+    /// * `session_id` - Session identifier from `create_session`
+    ///
+    /// # Returns
+    /// AsyncTask that resolves when the session's interpreter has exited
+    pub fn close_session(&self, session_id: &str) -> AsyncTask<CyloResult<()>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let metadata = self.metadata.clone();
+        let session_id = session_id.to_string();
+
+        AsyncTaskBuilder::new(async move {
+            let managed = {
+                let mut sessions = sessions_lock.write().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                })?;
+
+                sessions.remove(&session_id)
+            };
+
+            match managed {
+                Some(managed) => {
+                    managed.session.close().await?;
+                    persist_records(
+                        metadata.as_deref(),
+                        &instances_lock,
+                        &sessions_lock,
+                        |candidate| candidate == managed.instance_id,
+                    );
+                    Ok(())
+                }
+                None => Err(CyloError::SessionNotFound { id: session_id }),
+            }
+        })
+        .spawn()
+    }
+
+    /// Get handles for all live sessions
+    ///
+    /// # Returns
+    /// Vector of session handles
+    pub fn list_sessions(&self) -> CyloResult<Vec<SessionHandle>> {
+        let sessions = self
+            .sessions
+            .read()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+        Ok(sessions
+            .iter()
+            .map(|(id, managed)| SessionHandle {
+                id: id.clone(),
+                instance_id: managed.instance_id.clone(),
+                language: managed.session.language().to_string(),
+            })
+            .collect())
+    }
+
+    /// Tear down sessions idle longer than the session idle timeout
+    ///
+    /// # Returns
+    /// AsyncTask that resolves with count of closed sessions
+    pub fn cleanup_idle_sessions(&self) -> AsyncTask<CyloResult<u32>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let metadata = self.metadata.clone();
+        let idle_timeout = self.session_idle_timeout;
+
+        AsyncTaskBuilder::new(async move {
+            let expired = take_sessions(&sessions_lock, |managed| {
+                managed.session.idle_for() > idle_timeout
+            })?;
+
+            let count = expired.len() as u32;
+            for session in expired {
+                let _ = session.close().await;
+            }
+
+            if count > 0 {
+                persist_records(metadata.as_deref(), &instances_lock, &sessions_lock, |_| {
+                    true
+                });
+            }
+
+            Ok(count)
+        })
+        .spawn()
+    }
+
+    /// Execute a request on a registered instance
+    ///
+    /// Eligible requests (see `WarmPool::is_eligible`) are served by a
+    /// pre-started sandbox when one is idle; the sandbox is then recycled in
+    /// the background. Everything else, and every pool miss, takes the normal
+    /// `execute_code` path, and a miss refills the pool for next time.
+    ///
+    /// With the result cache enabled, a request matching a cached successful
+    /// result is answered from the cache without running; it is still
+    /// journaled, with `result_cache = hit` in its result metadata.
+    ///
+    /// # Arguments
+    /// * `instance_id` - Instance to execute on
+    /// * `request` - Execution request
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to the execution result
+    pub fn execute(
+        &self,
+        instance_id: &str,
+        request: ExecutionRequest,
+    ) -> AsyncTask<CyloResult<ExecutionResult>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let warm_pool = Arc::clone(&self.warm_pool);
+        let result_cache = Arc::clone(&self.result_cache);
+        let journal = Arc::clone(&self.journal);
+        let instance_id = instance_id.to_string();
+
+        AsyncTaskBuilder::new(async move {
+            let cache_key = result_cache
+                .is_cacheable(&request)
+                .then(|| CacheKey::new(&instance_id, &request));
+
+            let (backend, cached) = {
+                let mut instances = instances_lock.write().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                })?;
+
+                let managed = instances.get_mut(&instance_id).ok_or_else(|| {
+                    CyloError::InstanceNotFound {
+                        name: instance_id.clone(),
+                    }
+                })?;
+                managed.last_accessed = SystemTime::now();
+                let cached = cache_key.as_ref().and_then(|key| result_cache.get(key));
+                if cached.is_none() {
+                    // Counted as in flight, so the supervisor does not replace it mid-run
+                    managed.ref_count += 1;
+                }
+                (managed.backend.clone(), cached)
+            };
+
+            let backend_type = backend.backend_type();
+            let result = match cached {
+                Some(result) => result,
+                None => {
+                    let result =
+                        dispatch(backend, warm_pool, &instance_id, request.clone()).await;
+                    if let Ok(mut instances) = instances_lock.write()
+                        && let Some(managed) = instances.get_mut(&instance_id)
+                    {
+                        managed.ref_count = managed.ref_count.saturating_sub(1);
+                    }
+                    let result = result?;
+                    if let Some(key) = cache_key {
+                        result_cache.insert(key, &result);
+                    }
+                    result
+                }
+            };
+
+            let entry = JournalEntry::new(&instance_id, backend_type, &request, &result);
+            if let Err(e) = journal.record(entry) {
+                log::warn!("Failed to journal execution: {}", e);
+            }
+
+            Ok(result)
+        })
+        .spawn()
+    }
+
+    /// Query the execution journal
+    ///
+    /// # Arguments
+    /// * `query` - Filters to apply
+    ///
+    /// # Returns
+    /// Matching entries, oldest first
+    pub fn journal_history(&self, query: &JournalQuery) -> CyloResult<Vec<JournalEntry>> {
+        self.journal.query(query)
+    }
+
+    /// Re-execute a journaled request
+    ///
+    /// The recorded request is run unchanged on the instance it originally
+    /// ran on, always through the backend's own `execute_code` so the warm
+    /// pool cannot change how it runs. The replay is journaled as well.
+    ///
+    /// # Arguments
+    /// * `entry_id` - Journal entry to replay
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to the original entry, the replay's entry and its result
+    pub fn replay(&self, entry_id: &str) -> AsyncTask<CyloResult<ReplayOutcome>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let journal = Arc::clone(&self.journal);
+        let entry_id = entry_id.to_string();
+
+        AsyncTaskBuilder::new(async move {
+            let original = journal.get(&entry_id)?;
+            if let Some(session_id) = &original.session_id {
+                return Err(CyloError::validation(format!(
+                    "Entry {entry_id} ran in session {session_id} and depends on its earlier state"
+                )));
+            }
+
+            let backend = {
+                let mut instances = instances_lock.write().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                })?;
+
+                let managed = instances.get_mut(&original.instance_id).ok_or_else(|| {
+                    CyloError::InstanceNotFound {
+                        name: original.instance_id.clone(),
+                    }
+                })?;
+                managed.last_accessed = SystemTime::now();
+                managed.backend.clone()
+            };
+
+            let result = backend.execute_code(original.request.clone()).await?;
+            let replay = JournalEntry::new(
+                &original.instance_id,
+                backend.backend_type(),
+                &original.request,
+                &result,
+            )
+            .replaying(&original.id);
+            journal.record(replay.clone())?;
+
+            Ok(ReplayOutcome {
+                original,
+                replay,
+                result,
+            })
+        })
+        .spawn()
+    }
+
+    /// Pre-start sandboxes for `language` on a registered instance
+    ///
+    /// # Arguments
+    /// * `instance_id` - Instance that hosts the sandboxes
+    /// * `language` - Interpreter language (python or javascript)
+    ///
+    /// # Returns
+    /// AsyncTask that resolves with the number of sandboxes started
+    pub fn prewarm(&self, instance_id: &str, language: &str) -> AsyncTask<CyloResult<usize>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let warm_pool = Arc::clone(&self.warm_pool);
+        let instance_id = instance_id.to_string();
+        let language = language.to_string();
+
+        AsyncTaskBuilder::new(async move {
+            let backend = {
+                let instances = instances_lock.read().map_err(|e| {
+                    CyloError::internal(format!("Failed to acquire read lock: {e}"))
+                })?;
+
+                instances
+                    .get(&instance_id)
+                    .map(|managed| managed.backend.clone())
+                    .ok_or_else(|| CyloError::InstanceNotFound {
+                        name: instance_id.clone(),
+                    })?
+            };
+
+            Ok(warm_pool.fill(backend.as_ref(), &instance_id, &language))
+        })
+        .spawn()
+    }
+
+    /// Warm pool hit rate, recycle time and occupancy
+    pub fn warm_pool_metrics(&self) -> WarmPoolMetrics {
+        self.warm_pool.metrics()
+    }
+
+    /// Result cache hit rate, evictions and occupancy
+    pub fn result_cache_metrics(&self) -> ResultCacheMetrics {
+        self.result_cache.metrics()
+    }
+
+    /// Drop cached results, for one instance or for all
+    ///
+    /// # Returns
+    /// Number of results dropped
+    pub fn invalidate_results(&self, instance_id: Option<&str>) -> usize {
+        self.result_cache.invalidate(instance_id)
+    }
+
+    /// Recover instances left behind by a previous process
+    ///
+    /// Loads the persisted instance records and, for every record whose
+    /// owning process has exited, kills its orphaned session processes,
+    /// removes its scratch directories and runs the backend's cleanup. The
+    /// instance is then re-registered if its spec is still valid and the
+    /// backend reports healthy; otherwise its mounts are released and its
+    /// record is deleted. Does nothing without `with_persistence`.
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to what was adopted, cleaned and skipped
+    pub fn reconcile(&self) -> AsyncTask<CyloResult<RecoveryReport>> {
+        self.recover(true)
+    }
+
+    /// Reclaim the resources of every stale instance record
+    ///
+    /// Like `reconcile`, but never re-registers an instance: each record
+    /// whose owning process has exited is cleaned up and deleted.
+    ///
+    /// # Returns
+    /// AsyncTask that resolves to what was cleaned and skipped
+    pub fn gc(&self) -> AsyncTask<CyloResult<RecoveryReport>> {
+        self.recover(false)
+    }
+
+    fn recover(&self, adopt: bool) -> AsyncTask<CyloResult<RecoveryReport>> {
+        let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let metadata = self.metadata.clone();
+        let default_config = self.default_config.clone();
+
+        AsyncTaskBuilder::new(async move {
+            let mut report = RecoveryReport::default();
+            let Some(metadata) = metadata else {
+                return Ok(report);
+            };
+
+            let records = metadata.load_instance_records().map_err(|e| {
+                CyloError::internal(format!("Failed to load instance records: {e}"))
+            })?;
+
+            for record in records {
+                let id = record.instance.id();
+                let registered = instances_lock
+                    .read()
+                    .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?
+                    .contains_key(&id);
+
+                if registered || !is_stale(&record) {
+                    report.skipped.push(id);
+                    continue;
+                }
+
+                // The owner is gone, so everything it started for the instance is orphaned
+                reclaim_processes(&record);
+                let backend = create_backend(&record.instance.env, default_config.clone())
+                    .ok()
+                    .map(Arc::<dyn ExecutionBackend>::from);
+                if let Some(backend) = &backend
+                    && let Err(e) = backend.cleanup().await
+                {
+                    log::warn!("Failed to cleanup stale instance {}: {}", id, e);
+                }
+
+                if adopt
+                    && let Some(backend) = backend
+                    && let Some(managed) = adopt_instance(&record, backend).await
+                {
+                    let inserted = {
+                        let mut instances = instances_lock.write().map_err(|e| {
+                            CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                        })?;
+
+                        match instances.entry(id.clone()) {
+                            std::collections::hash_map::Entry::Occupied(_) => false,
+                            std::collections::hash_map::Entry::Vacant(vacant) => {
+                                vacant.insert(managed);
+                                true
+                            }
+                        }
+                    };
+
+                    if inserted {
+                        persist_records(
+                            Some(metadata.as_ref()),
+                            &instances_lock,
+                            &sessions_lock,
+                            |candidate| candidate == id,
+                        );
+                        log::info!("Re-adopted instance {} from a previous process", id);
+                        report.adopted.push(id);
+                    } else {
+                        report.skipped.push(id);
+                    }
+                    continue;
+                }
+
+                release_mounts(&record);
+                forget_record(Some(metadata.as_ref()), &id);
+                log::info!("Cleaned up stale instance {}", id);
+                report.cleaned.push(id);
+            }
+
+            Ok(report)
+        })
+        .spawn()
+    }
+
     /// Shutdown the instance manager
     ///
     /// Cleanly shuts down all registered instances and clears
@@ -494,8 +1227,17 @@ impl InstanceManager {
     /// AsyncTask that resolves when shutdown is complete
     pub fn shutdown(&self) -> AsyncTask<CyloResult<()>> {
         let instances_lock = Arc::clone(&self.instances);
+        let sessions_lock = Arc::clone(&self.sessions);
+        let warm_pool = Arc::clone(&self.warm_pool);
+        let metadata = self.metadata.clone();
 
         AsyncTaskBuilder::new(async move {
+            // Tear down sessions and warm sandboxes before the instances hosting them
+            let sessions = take_sessions(&sessions_lock, |_| true)?;
+            for session in sessions.into_iter().chain(warm_pool.drain(None)) {
+                let _ = session.close().await;
+            }
+
             // Get all instances
             let all_instances = {
                 let mut instances = instances_lock.write().map_err(|e| {
@@ -510,10 +1252,12 @@ impl InstanceManager {
 
             for (instance_id, managed) in all_instances {
                 let id = instance_id.clone();
+                let metadata = metadata.clone();
                 let cleanup_task = AsyncTaskBuilder::new(async move {
                     if let Err(e) = managed.backend.cleanup().await {
                         log::warn!("Failed to cleanup instance {} during shutdown: {}", id, e);
                     }
+                    forget_record(metadata.as_deref(), &id);
                 })
                 .spawn();
                 cleanup_tasks.push(cleanup_task);
@@ -530,12 +1274,491 @@ impl InstanceManager {
     }
 }
 
+impl InstanceManager {
+    /// Handle sharing this manager's registries, for background tasks
+    fn clone_for_supervisor(&self) -> Self {
+        Self {
+            instances: Arc::clone(&self.instances),
+            default_config: self.default_config.clone(),
+            health_check_interval: self.health_check_interval,
+            max_idle_time: self.max_idle_time,
+            sessions: Arc::clone(&self.sessions),
+            session_idle_timeout: self.session_idle_timeout,
+            warm_pool: Arc::clone(&self.warm_pool),
+            journal: Arc::clone(&self.journal),
+            metadata: self.metadata.clone(),
+            recovery: Arc::clone(&self.recovery),
+        }
+    }
+
+    /// One supervision pass, see `supervise_once`
+    async fn supervise(
+        &self,
+        config: &SupervisorConfig,
+        events: Option<&Sender<PipelineEvent>>,
+    ) -> CyloResult<SupervisorReport> {
+        let probes = probe_instances(&self.instances).await?;
+        let now = SystemTime::now();
+        let mut report = SupervisorReport::default();
+        let mut to_replace = Vec::new();
+
+        {
+            let mut recovery = self
+                .recovery
+                .write()
+                .map_err(|e| CyloError::internal(format!("Failed to acquire write lock: {e}")))?;
+            let instances = self
+                .instances
+                .read()
+                .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+            // Forget instances removed since the last pass
+            recovery.retain(|instance_id, _| instances.contains_key(instance_id));
+
+            for (instance_id, health) in probes {
+                if health.is_healthy {
+                    if recovery.remove(&instance_id).is_some() {
+                        emit(
+                            events,
+                            PipelineEvent::InstanceRecovered {
+                                instance_id: instance_id.clone(),
+                            },
+                        );
+                    }
+                    report.healthy.push(instance_id);
+                    continue;
+                }
+
+                let state = recovery.entry(instance_id.clone()).or_default();
+                state.consecutive_failures += 1;
+                emit(
+                    events,
+                    PipelineEvent::InstanceUnhealthy {
+                        instance_id: instance_id.clone(),
+                        message: health.message,
+                        consecutive_failures: state.consecutive_failures,
+                    },
+                );
+
+                let in_flight = instances
+                    .get(&instance_id)
+                    .is_some_and(|managed| managed.ref_count > 0);
+                if state.consecutive_failures < config.failure_threshold {
+                    report.unhealthy.push(instance_id);
+                } else if state.backing_off(now) {
+                    report.backing_off.push(instance_id);
+                } else if in_flight {
+                    report.deferred.push(instance_id);
+                } else {
+                    to_replace.push(instance_id);
+                }
+            }
+        }
+
+        for instance_id in to_replace {
+            match self.replace_instance(&instance_id).await {
+                Ok(true) => {
+                    let attempt = self
+                        .recovery
+                        .write()
+                        .map_err(|e| {
+                            CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                        })?
+                        .remove(&instance_id)
+                        .map_or(1, |state| state.failed_replacements + 1);
+                    emit(
+                        events,
+                        PipelineEvent::InstanceReplaced {
+                            instance_id: instance_id.clone(),
+                            attempt,
+                        },
+                    );
+                    report.replaced.push(instance_id);
+                }
+                Ok(false) => report.deferred.push(instance_id),
+                Err(e) => {
+                    log::warn!(
+                        "Failed to replace unhealthy instance {}: {}",
+                        instance_id,
+                        e
+                    );
+                    let retry_in = {
+                        let mut recovery = self.recovery.write().map_err(|e| {
+                            CyloError::internal(format!("Failed to acquire write lock: {e}"))
+                        })?;
+                        let state = recovery.entry(instance_id.clone()).or_default();
+                        state.failed_replacements += 1;
+                        let retry_in = config.backoff(state.failed_replacements);
+                        state.retry_at = Some(SystemTime::now() + retry_in);
+                        retry_in
+                    };
+                    emit(
+                        events,
+                        PipelineEvent::InstanceReplacementFailed {
+                            instance_id: instance_id.clone(),
+                            error: e.to_string(),
+                            retry_in,
+                        },
+                    );
+                    report.failed.push(instance_id);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Swap a fresh backend built from the instance's spec in for the old one
+    ///
+    /// Returns `false` without replacing anything if an execution started
+    /// while the new backend was being built.
+    async fn replace_instance(&self, instance_id: &str) -> CyloResult<bool> {
+        let spec = {
+            let instances = self
+                .instances
+                .read()
+                .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+            instances
+                .get(instance_id)
+                .map(|managed| managed.spec.clone())
+                .ok_or_else(|| CyloError::InstanceNotFound {
+                    name: instance_id.to_string(),
+                })?
+        };
+
+        let backend: Arc<dyn ExecutionBackend> =
+            Arc::from(create_backend(&spec.env, self.default_config.clone())?);
+        let health = match backend.health_check().await {
+            Ok(health) if health.is_healthy => health,
+            Ok(health) => {
+                let _ = backend.cleanup().await;
+                return Err(CyloError::backend_unavailable(
+                    backend.backend_type(),
+                    format!(
+                        "Replacement for instance {} is unhealthy: {}",
+                        instance_id, health.message
+                    ),
+                ));
+            }
+            Err(e) => {
+                let _ = backend.cleanup().await;
+                return Err(CyloError::backend_unavailable(
+                    backend.backend_type(),
+                    format!("Health check failed for replacement of {instance_id}: {e}"),
+                ));
+            }
+        };
+
+        let old_backend = {
+            let mut instances = self
+                .instances
+                .write()
+                .map_err(|e| CyloError::internal(format!("Failed to acquire write lock: {e}")))?;
+
+            match instances.get_mut(instance_id) {
+                Some(managed) if managed.ref_count == 0 => {
+                    managed.last_health = Some(health);
+                    managed.last_health_check = Some(SystemTime::now());
+                    Some(std::mem::replace(&mut managed.backend, backend.clone()))
+                }
+                _ => None,
+            }
+        };
+        let Some(old_backend) = old_backend else {
+            let _ = backend.cleanup().await;
+            return Ok(false);
+        };
+
+        // Sessions and warm sandboxes ran on the old backend
+        let sessions = take_sessions(&self.sessions, |managed| managed.instance_id == instance_id)?;
+        for session in sessions
+            .into_iter()
+            .chain(self.warm_pool.drain(Some(instance_id)))
+        {
+            let _ = session.close().await;
+        }
+
+        if let Err(e) = old_backend.cleanup().await {
+            log::warn!("Failed to cleanup replaced instance {}: {}", instance_id, e);
+        }
+
+        persist_records(
+            self.metadata.as_deref(),
+            &self.instances,
+            &self.sessions,
+            |candidate| candidate == instance_id,
+        );
+
+        Ok(true)
+    }
+}
+
 impl Default for InstanceManager {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Run `request` on `backend`, serving it from the warm pool when eligible
+async fn dispatch(
+    backend: Arc<dyn ExecutionBackend>,
+    warm_pool: Arc<WarmPool>,
+    instance_id: &str,
+    request: ExecutionRequest,
+) -> CyloResult<ExecutionResult> {
+    if !warm_pool.is_eligible(&request) {
+        return Ok(backend.execute_code(request).await?);
+    }
+
+    let instance_id = instance_id.to_string();
+    let Some(session) = warm_pool.checkout(&instance_id, &request.language) else {
+        let result = backend.execute_code(request.clone()).await?;
+        tokio::spawn(async move {
+            warm_pool.fill(backend.as_ref(), &instance_id, &request.language);
+        });
+        return Ok(result);
+    };
+
+    let mut result = session.execute(request.code, request.timeout).await?;
+    result
+        .metadata
+        .insert("warm_pool".to_string(), "hit".to_string());
+
+    tokio::spawn(async move {
+        warm_pool.recycle(backend.as_ref(), &instance_id, session).await;
+    });
+
+    Ok(result)
+}
+
+/// Health check every registered instance concurrently and record the results
+async fn probe_instances(
+    instances_lock: &RwLock<HashMap<String, ManagedInstance>>,
+) -> CyloResult<Vec<(String, HealthStatus)>> {
+    // Get list of instances to check
+    let instance_list = {
+        let instances = instances_lock
+            .read()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+        instances
+            .iter()
+            .map(|(id, managed)| (id.clone(), managed.backend.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // Perform health checks concurrently
+    let health_tasks = instance_list
+        .into_iter()
+        .map(|(instance_id, backend)| {
+            AsyncTaskBuilder::new(async move {
+                let health = backend.health_check().await;
+                (instance_id, health)
+            })
+            .spawn()
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::with_capacity(health_tasks.len());
+    for task in health_tasks {
+        // A probe task that failed to run leaves the instance unchecked
+        let Ok((instance_id, health)) = task.await else {
+            continue;
+        };
+        let health = health.unwrap_or_else(|_| HealthStatus::unhealthy("Health check failed"));
+        results.push((instance_id, health));
+    }
+
+    // Record the results on the instances still registered
+    {
+        let mut instances = instances_lock
+            .write()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire write lock: {e}")))?;
+
+        for (instance_id, health) in &results {
+            if let Some(managed) = instances.get_mut(instance_id) {
+                managed.last_health = Some(health.clone());
+                managed.last_health_check = Some(SystemTime::now());
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Send `event` to the supervisor's listener, if there is one
+fn emit(events: Option<&Sender<PipelineEvent>>, event: PipelineEvent) {
+    if let Some(events) = events {
+        let _ = events.send(event);
+    }
+}
+
+/// Pool configuration used until `with_warm_pool` is called
+fn disabled_pool() -> WarmPoolConfig {
+    WarmPoolConfig {
+        size_per_key: 0,
+        ..WarmPoolConfig::default()
+    }
+}
+
+fn disabled_cache() -> ResultCacheConfig {
+    ResultCacheConfig {
+        max_entries: 0,
+        ..ResultCacheConfig::default()
+    }
+}
+
+/// Remove and return the sessions matching `predicate`
+fn take_sessions(
+    sessions_lock: &RwLock<HashMap<String, ManagedSession>>,
+    predicate: impl Fn(&ManagedSession) -> bool,
+) -> CyloResult<Vec<Arc<ReplSession>>> {
+    let mut sessions = sessions_lock
+        .write()
+        .map_err(|e| CyloError::internal(format!("Failed to acquire write lock: {e}")))?;
+
+    let ids: Vec<String> = sessions
+        .iter()
+        .filter(|(_, managed)| predicate(managed))
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    Ok(ids
+        .into_iter()
+        .filter_map(|id| sessions.remove(&id))
+        .map(|managed| managed.session)
+        .collect())
+}
+
+/// Rewrite the persisted records of the instances matching `predicate`
+///
+/// Persistence is best-effort: failures are logged and never fail the
+/// operation that triggered them.
+fn persist_records(
+    metadata: Option<&MetadataManager>,
+    instances_lock: &RwLock<HashMap<String, ManagedInstance>>,
+    sessions_lock: &RwLock<HashMap<String, ManagedSession>>,
+    predicate: impl Fn(&str) -> bool,
+) {
+    let Some(metadata) = metadata else {
+        return;
+    };
+    let (Ok(instances), Ok(sessions)) = (instances_lock.read(), sessions_lock.read()) else {
+        log::warn!("Failed to acquire read lock while persisting instance records");
+        return;
+    };
+
+    for (instance_id, managed) in instances.iter().filter(|(id, _)| predicate(id)) {
+        let hosted: Vec<&ReplSession> = sessions
+            .values()
+            .filter(|session| &session.instance_id == instance_id)
+            .map(|session| session.session.as_ref())
+            .collect();
+
+        let record = InstanceRecord {
+            instance: managed.spec.clone(),
+            owner_pid: std::process::id(),
+            pids: hosted.iter().filter_map(|session| session.pid()).collect(),
+            mount_points: mount_points(&managed.spec.env),
+            scratch_dirs: hosted
+                .iter()
+                .filter_map(|session| session.workspace().map(Path::to_path_buf))
+                .collect(),
+            registered_at: managed.registered_at,
+        };
+
+        if let Err(e) = metadata.save_instance_record(&record) {
+            log::warn!("Failed to persist instance {}: {}", instance_id, e);
+        }
+    }
+}
+
+/// Delete the persisted record of an instance
+fn forget_record(metadata: Option<&MetadataManager>, instance_id: &str) {
+    if let Some(metadata) = metadata
+        && let Err(e) = metadata.remove_instance_record(instance_id)
+    {
+        log::warn!("Failed to delete record of instance {}: {}", instance_id, e);
+    }
+}
+
+/// Mount points backing an instance of `env`
+fn mount_points(env: &Cylo) -> Vec<PathBuf> {
+    match env {
+        Cylo::LandLock(jail_path) => vec![PathBuf::from(jail_path)],
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a record belongs to a process that has exited
+///
+/// A record carrying this process's ID but not registered here was written
+/// by an earlier process that happened to get the same ID.
+fn is_stale(record: &InstanceRecord) -> bool {
+    record.owner_pid == std::process::id() || !process_alive(record.owner_pid)
+}
+
+/// Whether a process with `pid` exists
+fn process_alive(pid: u32) -> bool {
+    let Some(pid) = i32::try_from(pid).ok().filter(|pid| *pid > 0) else {
+        return false;
+    };
+
+    // Signal 0 only checks that the process exists and may be signalled
+    let alive = unsafe { nix::libc::kill(pid, 0) == 0 };
+    alive || nix::errno::Errno::last() == nix::errno::Errno::EPERM
+}
+
+/// Kill the session processes and remove the scratch directories of a stale record
+fn reclaim_processes(record: &InstanceRecord) {
+    for &pid in &record.pids {
+        if process_alive(pid) {
+            unsafe {
+                nix::libc::kill(pid as i32, nix::libc::SIGKILL);
+            }
+        }
+    }
+
+    for dir in &record.scratch_dirs {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// Unmount the ramdisks backing a stale record
+fn release_mounts(record: &InstanceRecord) {
+    for mount_point in &record.mount_points {
+        if crate::ramdisk::is_mounted(mount_point).unwrap_or(false)
+            && let Err(e) = crate::ramdisk::remove_ramdisk(mount_point)
+        {
+            log::warn!("Failed to unmount {}: {}", mount_point.display(), e);
+        }
+    }
+}
+
+/// Register a stale record's instance again if it is still usable
+async fn adopt_instance(
+    record: &InstanceRecord,
+    backend: Arc<dyn ExecutionBackend>,
+) -> Option<ManagedInstance> {
+    record.instance.validate().ok()?;
+
+    let health = backend.health_check().await.ok()?;
+    if !health.is_healthy {
+        return None;
+    }
+
+    Some(ManagedInstance {
+        backend,
+        spec: record.instance.clone(),
+        registered_at: record.registered_at,
+        last_accessed: SystemTime::now(),
+        last_health: Some(health),
+        last_health_check: Some(SystemTime::now()),
+        ref_count: 0,
+    })
+}
+
 /// Global instance manager singleton
 static GLOBAL_INSTANCE_MANAGER: std::sync::OnceLock<InstanceManager> = std::sync::OnceLock::new();
 
@@ -687,6 +1910,93 @@ mod tests {
         assert_eq!(cleaned_count, 0);
     }
 
+    #[tokio::test]
+    async fn session_on_missing_instance() {
+        let manager = InstanceManager::new();
+
+        let result = manager
+            .create_session("nonexistent", "python")
+            .await
+            .expect("Failed to join async task in test");
+        assert!(matches!(result, Err(CyloError::InstanceNotFound { .. })));
+
+        let result = manager
+            .execute_in_session("missing", "x = 1")
+            .await
+            .expect("Failed to join async task in test");
+        assert!(matches!(result, Err(CyloError::SessionNotFound { .. })));
+        assert!(manager.list_sessions().expect("list sessions").is_empty());
+    }
+
+    #[tokio::test]
+    async fn warm_pool_disabled_by_default() {
+        let manager = InstanceManager::new();
+
+        let result = manager
+            .execute("nonexistent", ExecutionRequest::new("print(1)", "python"))
+            .await
+            .expect("Failed to join async task in test");
+        assert!(matches!(result, Err(CyloError::InstanceNotFound { .. })));
+
+        let metrics = manager.warm_pool_metrics();
+        assert_eq!(metrics.hits + metrics.misses, 0);
+        assert_eq!(metrics.idle, 0);
+    }
+
+    #[tokio::test]
+    async fn replay_of_unknown_entry() {
+        let manager = InstanceManager::new();
+
+        let result = manager
+            .replay("missing")
+            .await
+            .expect("Failed to join async task in test");
+        assert!(matches!(result, Err(CyloError::JournalEntryNotFound { .. })));
+        assert!(
+            manager
+                .journal_history(&JournalQuery::default())
+                .expect("query journal")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn gc_reclaims_stale_instance_records() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let scratch = dir.path().join("session-workspace");
+        std::fs::create_dir_all(&scratch).expect("create scratch dir");
+
+        let jail = dir.path().join("jail").to_string_lossy().into_owned();
+        let stale = InstanceRecord {
+            instance: Cylo::LandLock(jail).instance("orphaned"),
+            // Not a valid PID, so the owner can never be alive
+            owner_pid: u32::MAX,
+            pids: Vec::new(),
+            mount_points: Vec::new(),
+            scratch_dirs: vec![scratch.clone()],
+            registered_at: SystemTime::now(),
+        };
+        let metadata = MetadataManager::new(dir.path());
+        metadata.save_instance_record(&stale).expect("save record");
+
+        let manager = InstanceManager::new().with_persistence(MetadataManager::new(dir.path()));
+        let report = manager
+            .gc()
+            .await
+            .expect("Failed to join async task in test")
+            .expect("gc");
+
+        assert_eq!(report.cleaned, vec![stale.instance.id()]);
+        assert!(report.adopted.is_empty());
+        assert!(!scratch.exists());
+        assert!(
+            metadata
+                .load_instance_records()
+                .expect("load records")
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn shutdown() {
         let manager = InstanceManager::new();
@@ -713,4 +2023,240 @@ mod tests {
         assert_eq!(manager.health_check_interval, Duration::from_secs(30));
         assert_eq!(manager.max_idle_time, Duration::from_secs(600));
     }
+
+    /// Backend whose health is set by the test
+    #[derive(Debug)]
+    struct FlakyBackend {
+        healthy: Arc<std::sync::atomic::AtomicBool>,
+        config: BackendConfig,
+    }
+
+    impl ExecutionBackend for FlakyBackend {
+        fn execute_code(&self, _request: ExecutionRequest) -> AsyncTask<ExecutionResult> {
+            AsyncTaskBuilder::new(async { ExecutionResult::failure(-1, "not executable") }).spawn()
+        }
+
+        fn health_check(&self) -> AsyncTask<HealthStatus> {
+            let healthy = self.healthy.load(std::sync::atomic::Ordering::SeqCst);
+            AsyncTaskBuilder::new(async move {
+                if healthy {
+                    HealthStatus::healthy("ok")
+                } else {
+                    HealthStatus::unhealthy("down")
+                }
+            })
+            .spawn()
+        }
+
+        fn cleanup(&self) -> AsyncTask<CyloResult<()>> {
+            AsyncTaskBuilder::new(async { Ok(()) }).spawn()
+        }
+
+        fn get_config(&self) -> &BackendConfig {
+            &self.config
+        }
+
+        fn backend_type(&self) -> &'static str {
+            "Flaky"
+        }
+
+        fn supports_language(&self, _language: &str) -> bool {
+            false
+        }
+
+        fn supported_languages(&self) -> &[&'static str] {
+            &[]
+        }
+    }
+
+    /// Register a flaky instance whose replacement always fails to build
+    fn insert_flaky(
+        manager: &InstanceManager,
+        ref_count: u32,
+    ) -> (String, Arc<std::sync::atomic::AtomicBool>) {
+        let healthy = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let spec = Cylo::SweetMcpPlugin("/nonexistent/plugin.wasm".to_string()).instance("flaky");
+        let instance_id = spec.id();
+        let managed = ManagedInstance {
+            backend: Arc::new(FlakyBackend {
+                healthy: Arc::clone(&healthy),
+                config: BackendConfig::new("flaky"),
+            }),
+            spec,
+            registered_at: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            last_health: None,
+            last_health_check: None,
+            ref_count,
+        };
+        manager
+            .instances
+            .write()
+            .expect("lock instances")
+            .insert(instance_id.clone(), managed);
+        (instance_id, healthy)
+    }
+
+    /// Backend that succeeds and counts its executions
+    #[derive(Debug)]
+    struct CountingBackend {
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+        config: BackendConfig,
+    }
+
+    impl ExecutionBackend for CountingBackend {
+        fn execute_code(&self, request: ExecutionRequest) -> AsyncTask<ExecutionResult> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            AsyncTaskBuilder::new(async move { ExecutionResult::success(request.code) }).spawn()
+        }
+
+        fn health_check(&self) -> AsyncTask<HealthStatus> {
+            AsyncTaskBuilder::new(async { HealthStatus::healthy("ok") }).spawn()
+        }
+
+        fn cleanup(&self) -> AsyncTask<CyloResult<()>> {
+            AsyncTaskBuilder::new(async { Ok(()) }).spawn()
+        }
+
+        fn get_config(&self) -> &BackendConfig {
+            &self.config
+        }
+
+        fn backend_type(&self) -> &'static str {
+            "Counting"
+        }
+
+        fn supports_language(&self, _language: &str) -> bool {
+            true
+        }
+
+        fn supported_languages(&self) -> &[&'static str] {
+            &[]
+        }
+    }
+
+    #[tokio::test]
+    async fn result_cache_serves_repeated_requests() {
+        let manager = InstanceManager::new().with_result_cache(ResultCacheConfig::default());
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let spec = Cylo::SweetMcpPlugin("/nonexistent/plugin.wasm".to_string()).instance("cached");
+        let instance_id = spec.id();
+        let managed = ManagedInstance {
+            backend: Arc::new(CountingBackend {
+                runs: Arc::clone(&runs),
+                config: BackendConfig::new("counting"),
+            }),
+            spec,
+            registered_at: SystemTime::now(),
+            last_accessed: SystemTime::now(),
+            last_health: None,
+            last_health_check: None,
+            ref_count: 0,
+        };
+        manager
+            .instances
+            .write()
+            .expect("lock instances")
+            .insert(instance_id.clone(), managed);
+
+        let run = |request: ExecutionRequest| {
+            let task = manager.execute(&instance_id, request);
+            async move {
+                task.await
+                    .expect("Failed to join async task in test")
+                    .expect("execute")
+            }
+        };
+        let first = run(ExecutionRequest::new("1 + 1", "rust")).await;
+        let second = run(ExecutionRequest::new("1 + 1", "rust")).await;
+        let uncached = run(ExecutionRequest::new("1 + 1", "rust").without_cache()).await;
+
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(!first.metadata.contains_key("result_cache"));
+        assert_eq!(second.stdout, first.stdout);
+        assert_eq!(
+            second.metadata.get("result_cache").map(String::as_str),
+            Some("hit")
+        );
+        assert!(!uncached.metadata.contains_key("result_cache"));
+        assert_eq!(manager.result_cache_metrics().hits, 1);
+        assert_eq!(manager.invalidate_results(Some(&instance_id)), 1);
+    }
+
+    #[tokio::test]
+    async fn supervisor_backs_off_failed_replacements() {
+        let manager = InstanceManager::new();
+        let (instance_id, healthy) = insert_flaky(&manager, 0);
+        let config = SupervisorConfig::default()
+            .with_failure_threshold(2)
+            .with_backoff(Duration::from_secs(60), Duration::from_secs(600));
+        let (sender, events) = std::sync::mpsc::channel();
+
+        let pass = |manager: &InstanceManager| manager.supervise_once(&config, Some(&sender));
+        let first = pass(&manager)
+            .await
+            .expect("Failed to join async task in test")
+            .expect("supervise");
+        assert_eq!(first.unhealthy, vec![instance_id.clone()]);
+
+        let second = pass(&manager)
+            .await
+            .expect("Failed to join async task in test")
+            .expect("supervise");
+        assert_eq!(second.failed, vec![instance_id.clone()]);
+
+        let third = pass(&manager)
+            .await
+            .expect("Failed to join async task in test")
+            .expect("supervise");
+        assert_eq!(third.backing_off, vec![instance_id.clone()]);
+
+        healthy.store(true, std::sync::atomic::Ordering::SeqCst);
+        let fourth = pass(&manager)
+            .await
+            .expect("Failed to join async task in test")
+            .expect("supervise");
+        assert_eq!(fourth.healthy, vec![instance_id.clone()]);
+
+        let events: Vec<PipelineEvent> = events.try_iter().collect();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                PipelineEvent::InstanceUnhealthy { consecutive_failures: 1, .. },
+                PipelineEvent::InstanceUnhealthy { consecutive_failures: 2, .. },
+                PipelineEvent::InstanceReplacementFailed { retry_in, .. },
+                PipelineEvent::InstanceUnhealthy { consecutive_failures: 3, .. },
+                PipelineEvent::InstanceRecovered { .. },
+            ] if *retry_in == Duration::from_secs(60)
+        ));
+        assert!(
+            manager
+                .get_instance_health(&instance_id)
+                .expect("health status")
+                .is_some_and(|health| health.is_healthy)
+        );
+    }
+
+    #[tokio::test]
+    async fn supervisor_defers_instances_in_use() {
+        let manager = InstanceManager::new();
+        let (instance_id, _) = insert_flaky(&manager, 1);
+        let config = SupervisorConfig::default().with_failure_threshold(1);
+
+        let report = manager
+            .supervise_once(&config, None)
+            .await
+            .expect("Failed to join async task in test")
+            .expect("supervise");
+        assert_eq!(report.deferred, vec![instance_id]);
+        assert!(report.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn supervisor_handle_stops_loop() {
+        let manager = InstanceManager::new();
+        let handle = manager.start_supervisor(manager.supervisor_config(), None);
+        assert!(handle.is_running());
+        handle.stop();
+    }
 }
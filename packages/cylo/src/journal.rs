@@ -0,0 +1,395 @@
+// ============================================================================
+// File: packages/cylo/src/journal.rs
+// ----------------------------------------------------------------------------
+// Append-only execution journal for auditing and replay.
+//
+// Provides:
+// - One JournalEntry per execution (code hash, language, backend, duration,
+//   exit status, resource usage) plus the full request for replay
+// - JSON-lines persistence that is only ever appended to
+// - History queries filtered by instance, language, code hash and outcome
+// ============================================================================
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::backends::{ExecutionRequest, ExecutionResult, LimitExceeded, ResourceUsage};
+use crate::execution_env::{CyloError, CyloResult};
+
+/// One recorded execution
+///
+/// The full request is kept so the execution can be replayed; this includes
+/// the code, stdin and environment variables exactly as submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique entry identifier
+    pub id: String,
+
+    /// When the execution finished
+    pub timestamp: DateTime<Utc>,
+
+    /// Instance the request ran on
+    pub instance_id: String,
+
+    /// Backend type of that instance
+    pub backend: String,
+
+    /// Programming language
+    pub language: String,
+
+    /// SHA-256 of the code, hex encoded
+    pub code_hash: String,
+
+    /// Wall-clock duration
+    pub duration: Duration,
+
+    /// Exit code
+    pub exit_code: i32,
+
+    /// Quota that terminated or truncated the execution, if any
+    #[serde(default)]
+    pub limit_exceeded: Option<LimitExceeded>,
+
+    /// Resource usage reported by the backend
+    pub resource_usage: ResourceUsage,
+
+    /// SHA-256 of stdout, for comparing replays
+    pub stdout_hash: String,
+
+    /// Session the snippet ran in; session snippets depend on earlier state
+    /// and cannot be replayed on their own
+    #[serde(default)]
+    pub session_id: Option<String>,
+
+    /// Entry this execution replayed
+    #[serde(default)]
+    pub replay_of: Option<String>,
+
+    /// The request as submitted
+    pub request: ExecutionRequest,
+}
+
+impl JournalEntry {
+    /// Build an entry from a finished execution
+    pub fn new(
+        instance_id: &str,
+        backend: &str,
+        request: &ExecutionRequest,
+        result: &ExecutionResult,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            timestamp: Utc::now(),
+            instance_id: instance_id.to_string(),
+            backend: backend.to_string(),
+            language: request.language.clone(),
+            code_hash: sha256_hex(request.code.as_bytes()),
+            duration: result.duration,
+            exit_code: result.exit_code,
+            limit_exceeded: result.limit_exceeded,
+            resource_usage: result.resource_usage.clone(),
+            stdout_hash: sha256_hex(result.stdout.as_bytes()),
+            session_id: None,
+            replay_of: None,
+            request: request.clone(),
+        }
+    }
+
+    /// Mark the entry as a session snippet
+    pub fn in_session<S: Into<String>>(mut self, session_id: S) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Mark the entry as a replay of `original`
+    pub fn replaying<S: Into<String>>(mut self, original: S) -> Self {
+        self.replay_of = Some(original.into());
+        self
+    }
+
+    /// Whether the execution exited cleanly within its quotas
+    pub fn is_success(&self) -> bool {
+        self.exit_code == 0 && self.limit_exceeded.is_none()
+    }
+
+    /// Whether `other` produced the same exit status and stdout
+    pub fn same_outcome(&self, other: &JournalEntry) -> bool {
+        self.exit_code == other.exit_code
+            && self.limit_exceeded == other.limit_exceeded
+            && self.stdout_hash == other.stdout_hash
+    }
+}
+
+/// Outcome of replaying a journal entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayOutcome {
+    /// The entry that was replayed
+    pub original: JournalEntry,
+
+    /// The entry recorded for the replay
+    pub replay: JournalEntry,
+
+    /// The replay's result
+    pub result: ExecutionResult,
+}
+
+impl ReplayOutcome {
+    /// Whether the replay reproduced the original exit status and stdout
+    pub fn reproduced(&self) -> bool {
+        self.original.same_outcome(&self.replay)
+    }
+}
+
+/// Filter for journal history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JournalQuery {
+    /// Only entries for this instance
+    pub instance_id: Option<String>,
+
+    /// Only entries in this language
+    pub language: Option<String>,
+
+    /// Only entries whose code has this hash
+    pub code_hash: Option<String>,
+
+    /// Only failed executions
+    pub failures_only: bool,
+
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Return at most this many (most recent) entries
+    pub limit: Option<usize>,
+}
+
+impl JournalQuery {
+    fn matches(&self, entry: &JournalEntry) -> bool {
+        self.instance_id.as_ref().is_none_or(|id| *id == entry.instance_id)
+            && self
+                .language
+                .as_ref()
+                .is_none_or(|language| language.eq_ignore_ascii_case(&entry.language))
+            && self.code_hash.as_ref().is_none_or(|hash| *hash == entry.code_hash)
+            && (!self.failures_only || !entry.is_success())
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+    }
+}
+
+/// Append-only execution journal
+#[derive(Debug)]
+pub struct ExecutionJournal {
+    /// Backing file, if persistent
+    path: Option<PathBuf>,
+
+    /// Entries in recording order
+    entries: RwLock<Vec<JournalEntry>>,
+
+    /// Append handle for the backing file
+    file: Mutex<Option<File>>,
+}
+
+impl ExecutionJournal {
+    /// Create a journal kept only in memory
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: RwLock::new(Vec::new()),
+            file: Mutex::new(None),
+        }
+    }
+
+    /// Open (or create) a journal backed by a JSON-lines file
+    ///
+    /// Existing entries are loaded; new entries are appended.
+    pub fn open<P: AsRef<Path>>(path: P) -> CyloResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CyloError::internal(format!("Failed to create journal directory: {e}"))
+            })?;
+        }
+
+        let mut entries = Vec::new();
+        if path.exists() {
+            let file = File::open(&path)
+                .map_err(|e| CyloError::internal(format!("Failed to open journal: {e}")))?;
+            for (number, line) in BufReader::new(file).lines().enumerate() {
+                let line =
+                    line.map_err(|e| CyloError::internal(format!("Failed to read journal: {e}")))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<JournalEntry>(&line) {
+                    Ok(entry) => entries.push(entry),
+                    Err(e) => log::warn!(
+                        "Skipping malformed journal line {} in {}: {}",
+                        number + 1,
+                        path.display(),
+                        e
+                    ),
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| CyloError::internal(format!("Failed to open journal: {e}")))?;
+
+        Ok(Self {
+            path: Some(path),
+            entries: RwLock::new(entries),
+            file: Mutex::new(Some(file)),
+        })
+    }
+
+    /// Backing file, if persistent
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Append an entry
+    pub fn record(&self, entry: JournalEntry) -> CyloResult<()> {
+        {
+            let mut file = self
+                .file
+                .lock()
+                .map_err(|e| CyloError::internal(format!("Failed to acquire journal lock: {e}")))?;
+
+            if let Some(file) = file.as_mut() {
+                let mut line = serde_json::to_string(&entry).map_err(|e| {
+                    CyloError::internal(format!("Failed to serialize journal entry: {e}"))
+                })?;
+                line.push('\n');
+                file.write_all(line.as_bytes())
+                    .and_then(|()| file.flush())
+                    .map_err(|e| CyloError::internal(format!("Failed to append to journal: {e}")))?;
+            }
+        }
+
+        self.entries
+            .write()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire write lock: {e}")))?
+            .push(entry);
+        Ok(())
+    }
+
+    /// Look up an entry by ID
+    pub fn get(&self, id: &str) -> CyloResult<JournalEntry> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+        entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .cloned()
+            .ok_or_else(|| CyloError::JournalEntryNotFound { id: id.to_string() })
+    }
+
+    /// Entries matching `query`, oldest first
+    pub fn query(&self, query: &JournalQuery) -> CyloResult<Vec<JournalEntry>> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|e| CyloError::internal(format!("Failed to acquire read lock: {e}")))?;
+
+        let mut matched: Vec<JournalEntry> = entries
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .cloned()
+            .collect();
+
+        if let Some(limit) = query.limit {
+            let skip = matched.len().saturating_sub(limit);
+            matched.drain(..skip);
+        }
+        Ok(matched)
+    }
+
+    /// Number of recorded entries
+    pub fn len(&self) -> usize {
+        self.entries.read().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    /// Whether nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(instance: &str, code: &str, exit_code: i32) -> JournalEntry {
+        let request = ExecutionRequest::new(code, "python");
+        let mut result = ExecutionResult::success("out");
+        result.exit_code = exit_code;
+        JournalEntry::new(instance, "LandLock", &request, &result)
+    }
+
+    #[test]
+    fn query_filters_and_limits() {
+        let journal = ExecutionJournal::in_memory();
+        journal.record(entry("a", "print(1)", 0)).unwrap();
+        journal.record(entry("a", "raise", 1)).unwrap();
+        journal.record(entry("b", "print(1)", 0)).unwrap();
+
+        let failures = journal
+            .query(&JournalQuery {
+                failures_only: true,
+                ..JournalQuery::default()
+            })
+            .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].request.code, "raise");
+
+        let same_code = journal
+            .query(&JournalQuery {
+                code_hash: Some(sha256_hex(b"print(1)")),
+                limit: Some(1),
+                ..JournalQuery::default()
+            })
+            .unwrap();
+        assert_eq!(same_code.len(), 1);
+        assert_eq!(same_code[0].instance_id, "b");
+    }
+
+    #[test]
+    fn persisted_entries_survive_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+
+        let recorded = entry("a", "print(1)", 0);
+        ExecutionJournal::open(&path).unwrap().record(recorded.clone()).unwrap();
+
+        let reopened = ExecutionJournal::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        let loaded = reopened.get(&recorded.id).unwrap();
+        assert_eq!(loaded.code_hash, recorded.code_hash);
+        assert!(loaded.same_outcome(&recorded));
+        assert!(matches!(
+            reopened.get("missing"),
+            Err(CyloError::JournalEntryNotFound { .. })
+        ));
+    }
+}
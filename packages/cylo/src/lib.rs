@@ -50,18 +50,36 @@ pub mod backends;
 pub use backends::{
     // Backend implementations
     AppleBackend,
+    Artifact,
+    ArtifactContent,
     BackendConfig,
     // Trait
     ExecutionBackend,
+    ExecutionEvent,
     ExecutionRequest,
     ExecutionResult,
+    ExecutionStream,
+    FailureKind,
+    FsRoot,
     HealthStatus,
+    Language,
+    LimitExceeded,
+    NetworkPolicy,
+    PackageManager,
+    ProjectRequest,
+    ReplSession,
+    ResourceLimits,
+    SandboxProfile,
+    SessionHandle,
+    ShellPolicy,
     // Factory function
     create_backend,
 };
 // Platform-specific backends
 #[cfg(target_os = "linux")]
 pub use backends::{FireCrackerBackend, LandLockBackend};
+#[cfg(target_os = "windows")]
+pub use backends::WindowsBackend;
 
 // ============================================================================
 // Platform detection and capabilities
@@ -81,6 +99,7 @@ pub use platform::{
     has_landlock,
     is_apple_silicon,
     is_linux,
+    is_windows,
 };
 
 // ============================================================================
@@ -104,7 +123,7 @@ pub mod ramdisk;
 pub use ramdisk::{create_ramdisk, create_secure_ramdisk, get_watched_dir, remove_ramdisk};
 
 pub mod metadata;
-pub use metadata::MetadataManager;
+pub use metadata::{InstanceRecord, MetadataManager};
 
 pub mod sandbox;
 
@@ -131,8 +150,20 @@ pub use macos::MacosRamdisk;
 
 pub mod instance_manager;
 pub use instance_manager::{
-    InstanceManager, global_instance_manager, init_global_instance_manager,
+    InstanceManager, RecoveryReport, global_instance_manager, init_global_instance_manager,
 };
+
+pub mod supervisor;
+pub use supervisor::{SupervisorConfig, SupervisorHandle, SupervisorReport};
+
+pub mod journal;
+pub use journal::{ExecutionJournal, JournalEntry, JournalQuery, ReplayOutcome};
+
+pub mod warm_pool;
+pub use warm_pool::{WarmPool, WarmPoolConfig, WarmPoolMetrics};
+
+pub mod result_cache;
+pub use result_cache::{CacheKey, ResultCache, ResultCacheConfig, ResultCacheMetrics};
 // ============================================================================
 // Asynchronous task utilities
 // ============================================================================
@@ -1,17 +1,29 @@
 use std::fs::File;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use log::{error, info};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
 use xattr::FileExt;
 
+use crate::execution_env::CyloInstance;
+
 /// Metadata keys used for source code files
 pub const XATTR_NAMESPACE: &str = "user.ironexec";
 pub const XATTR_LANGUAGE: &str = "user.ironexec.language";
 pub const XATTR_LAST_EXECUTED: &str = "user.ironexec.last_executed";
 pub const XATTR_EXECUTION_COUNT: &str = "user.ironexec.execution_count";
 
+/// Directory under the repository holding instance records
+pub const INSTANCE_RECORDS_DIR: &str = ".parallm/instances";
+
 /// Manages source code metadata using extended attributes
+///
+/// Also persists records of the execution instances registered by an
+/// `InstanceManager`, so resources they leave behind after a host restart
+/// can be found and reclaimed.
+#[derive(Debug)]
 pub struct MetadataManager {
     /// Base directory for the repository
     repo_path: PathBuf,
@@ -218,6 +230,89 @@ fn main() -> io::Result<()> {{
             execution_count,
         }))
     }
+
+    /// Directory holding one JSON record per registered instance
+    pub fn instance_records_dir(&self) -> PathBuf {
+        self.repo_path.join(INSTANCE_RECORDS_DIR)
+    }
+
+    /// Writes the record for an instance, replacing any previous one
+    pub fn save_instance_record(&self, record: &InstanceRecord) -> io::Result<()> {
+        let dir = self.instance_records_dir();
+        std::fs::create_dir_all(&dir)?;
+
+        let path = self.instance_record_path(&record.instance.id());
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_vec_pretty(record).map_err(io::Error::other)?;
+
+        // Write then rename so a crash never leaves a truncated record
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// Loads every instance record, skipping ones that cannot be parsed
+    pub fn load_instance_records(&self) -> io::Result<Vec<InstanceRecord>> {
+        let dir = self.instance_records_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut records = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            match std::fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+            {
+                Ok(record) => records.push(record),
+                Err(e) => warn!(
+                    "Skipping unreadable instance record {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Deletes the record for an instance; a missing record is not an error
+    pub fn remove_instance_record(&self, instance_id: &str) -> io::Result<()> {
+        match std::fs::remove_file(self.instance_record_path(instance_id)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn instance_record_path(&self, instance_id: &str) -> PathBuf {
+        // Instance IDs are `Backend:name`; keep file names portable
+        self.instance_records_dir()
+            .join(format!("{}.json", instance_id.replace(':', "_")))
+    }
+}
+
+/// Persisted description of an instance registered with an `InstanceManager`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceRecord {
+    /// Backend and spec the instance was created from
+    pub instance: CyloInstance,
+    /// Process that registered the instance
+    pub owner_pid: u32,
+    /// Processes started for the instance, such as session interpreters
+    #[serde(default)]
+    pub pids: Vec<u32>,
+    /// Mount points (e.g. ramdisks) backing the instance
+    #[serde(default)]
+    pub mount_points: Vec<PathBuf>,
+    /// Scratch directories to remove if the instance is abandoned
+    #[serde(default)]
+    pub scratch_dirs: Vec<PathBuf>,
+    /// When the instance was registered
+    pub registered_at: SystemTime,
 }
 
 /// Metadata associated with a source code file
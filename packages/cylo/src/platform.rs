@@ -179,6 +179,14 @@ pub struct SecurityFeatures {
 
     /// Secure Enclave (macOS)
     pub secure_enclave: bool,
+
+    /// Job Objects for resource limits (Windows)
+    #[serde(default)]
+    pub job_objects: bool,
+
+    /// AppContainer isolation, Windows 8 and later (Windows)
+    #[serde(default)]
+    pub app_container: bool,
 }
 
 /// Network capabilities
@@ -427,6 +435,22 @@ impl PlatformInfo {
             });
         }
 
+        // Windows backend
+        if capabilities.security.job_objects {
+            let isolation = if capabilities.security.app_container {
+                "appcontainer"
+            } else {
+                "restricted"
+            };
+            backends.push(BackendAvailability {
+                name: "Windows".to_string(),
+                available: true,
+                reason: format!("Job Objects with {isolation} isolation are available"),
+                capabilities: HashMap::from([("isolation".to_string(), isolation.to_string())]),
+                performance_rating: 80,
+            });
+        }
+
         backends
     }
 
@@ -463,6 +487,25 @@ impl PlatformInfo {
         None
     }
 
+    #[cfg(target_os = "windows")]
+    fn detect_windows_version() -> Option<String> {
+        use std::process::Command;
+
+        // `ver` prints e.g. "Microsoft Windows [Version 10.0.22631.4169]"
+        Command::new("cmd")
+            .args(["/C", "ver"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                let text = String::from_utf8_lossy(&output.stdout);
+                let start = text.find("Version ")? + "Version ".len();
+                let version = &text[start..];
+                let end = version.find(']').unwrap_or(version.len());
+                Some(version[..end].trim().to_string())
+            })
+    }
+
     fn detect_virtualization_support(_os: &OperatingSystem) -> VirtualizationSupport {
         VirtualizationSupport {
             hardware_virtualization: Self::has_hardware_virtualization(),
@@ -491,6 +534,13 @@ impl PlatformInfo {
             app_sandbox: matches!(os, OperatingSystem::MacOS { .. }),
             secure_enclave: matches!(os, OperatingSystem::MacOS { .. })
                 && Self::has_secure_enclave(),
+            job_objects: matches!(os, OperatingSystem::Windows { .. }),
+            app_container: match os {
+                OperatingSystem::Windows { version } => {
+                    supports_app_container(version.as_deref())
+                }
+                _ => false,
+            },
         }
     }
 
@@ -726,6 +776,27 @@ pub fn has_kvm() -> bool {
     detect_platform().capabilities.virtualization.kvm_available
 }
 
+/// Check if running on Windows
+pub fn is_windows() -> bool {
+    matches!(detect_platform().os, OperatingSystem::Windows { .. })
+}
+
+/// Whether a Windows version (`major.minor...`) supports AppContainers
+///
+/// AppContainers arrived with Windows 8 (6.2). An unknown version is assumed
+/// to be recent.
+fn supports_app_container(version: Option<&str>) -> bool {
+    let Some(version) = version else {
+        return true;
+    };
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(major), Some(minor)) => (major, minor) >= (6, 2),
+        (Some(major), None) => major > 6,
+        _ => true,
+    }
+}
+
 /// Get recommended backend for current platform
 pub fn get_recommended_backend() -> Option<String> {
     detect_platform().performance.recommended_backend.clone()
@@ -763,6 +834,9 @@ mod tests {
 
         #[cfg(target_os = "macos")]
         assert!(matches!(info.os, OperatingSystem::MacOS { .. }));
+
+        #[cfg(target_os = "windows")]
+        assert!(matches!(info.os, OperatingSystem::Windows { .. }));
     }
 
     #[test]
@@ -785,9 +859,18 @@ mod tests {
         let _ = is_linux();
         let _ = has_landlock();
         let _ = has_kvm();
+        let _ = is_windows();
         let _ = get_recommended_backend();
     }
 
+    #[test]
+    fn app_container_needs_windows_8() {
+        assert!(supports_app_container(Some("10.0.22631.4169")));
+        assert!(supports_app_container(Some("6.2.9200")));
+        assert!(!supports_app_container(Some("6.1.7601")));
+        assert!(supports_app_container(None));
+    }
+
     #[test]
     fn platform_specific_detection() {
         let info = detect_platform();
@@ -808,5 +891,8 @@ mod tests {
                     .any(|b| b.name == "FireCracker")
             );
         }
+
+        #[cfg(target_os = "windows")]
+        assert!(info.available_backends.iter().any(|b| b.name == "Windows"));
     }
 }
@@ -0,0 +1,374 @@
+// ============================================================================
+// File: packages/cylo/src/result_cache.rs
+// ----------------------------------------------------------------------------
+// Opt-in cache of execution results for deterministic snippets.
+//
+// Provides:
+// - Results keyed by (instance, language, code hash, environment spec digest)
+// - Successful results only; failures and limit hits always run again
+// - TTL expiry and a bounded entry count with oldest-first eviction
+// - Per-request opt-out through `ExecutionRequest::no_cache`
+// - Hit, miss and eviction metrics
+// ============================================================================
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::backends::{ExecutionRequest, ExecutionResult};
+
+/// Result cache sizing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultCacheConfig {
+    /// Cached results kept at most; 0 disables the cache
+    pub max_entries: usize,
+
+    /// How long a cached result is served
+    pub ttl: Duration,
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1024,
+            ttl: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Snapshot of result cache counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResultCacheMetrics {
+    /// Requests answered from the cache
+    pub hits: u64,
+
+    /// Cacheable requests that had to run
+    pub misses: u64,
+
+    /// Entries dropped to make room for new ones
+    pub evictions: u64,
+
+    /// Results currently cached, including expired ones not yet swept
+    pub entries: usize,
+}
+
+impl ResultCacheMetrics {
+    /// Fraction of cacheable requests served from the cache (0.0 when nothing was served)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Identity of a cacheable execution
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Instance the request ran on
+    pub instance_id: String,
+
+    /// Language, lowercased
+    pub language: String,
+
+    /// SHA-256 of the code
+    pub code_hash: String,
+
+    /// SHA-256 of everything else that shapes the run: stdin, environment,
+    /// working directory, timeout, limits, network, artifacts and backend
+    /// configuration
+    pub env_digest: String,
+}
+
+impl CacheKey {
+    /// Key of `request` run on `instance_id`
+    pub fn new(instance_id: &str, request: &ExecutionRequest) -> Self {
+        Self {
+            instance_id: instance_id.to_string(),
+            language: request.language.to_lowercase(),
+            code_hash: sha256_hex(request.code.as_bytes()),
+            env_digest: env_digest(request),
+        }
+    }
+}
+
+/// Digest of the request's environment spec
+///
+/// Maps are hashed in sorted order so equal requests always agree.
+fn env_digest(request: &ExecutionRequest) -> String {
+    let mut hasher = Sha256::new();
+    let mut field = |name: &str, value: &[u8]| {
+        hasher.update(name.as_bytes());
+        hasher.update((value.len() as u64).to_le_bytes());
+        hasher.update(value);
+    };
+
+    field("stdin", request.stdin_data().unwrap_or_default());
+    for (key, value) in request.env_vars.iter().collect::<BTreeMap<_, _>>() {
+        field("env", key.as_bytes());
+        field("env_value", value.as_bytes());
+    }
+    field(
+        "working_dir",
+        request
+            .working_dir
+            .as_deref()
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    field("timeout", &request.timeout.as_millis().to_le_bytes());
+    field(
+        "limits",
+        &serde_json::to_vec(&request.limits).unwrap_or_default(),
+    );
+    field(
+        "network",
+        &serde_json::to_vec(&request.network).unwrap_or_default(),
+    );
+    for pattern in &request.artifacts {
+        field("artifact", pattern.as_bytes());
+    }
+    for (key, value) in request.backend_config.iter().collect::<BTreeMap<_, _>>() {
+        field("backend", key.as_bytes());
+        field("backend_value", value.as_bytes());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[derive(Debug)]
+struct CachedResult {
+    result: ExecutionResult,
+    stored_at: Instant,
+    /// Insertion order, for eviction
+    seq: u64,
+}
+
+/// Cache of successful execution results
+#[derive(Debug)]
+pub struct ResultCache {
+    config: ResultCacheConfig,
+    entries: Mutex<HashMap<CacheKey, CachedResult>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    inserts: AtomicU64,
+}
+
+impl ResultCache {
+    /// Create an empty cache
+    pub fn new(config: ResultCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            inserts: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether `request` may be answered from, and stored in, the cache
+    pub fn is_cacheable(&self, request: &ExecutionRequest) -> bool {
+        self.config.max_entries > 0 && !request.no_cache
+    }
+
+    /// Cached result for `key`, recording a hit or miss
+    ///
+    /// The result is marked with `result_cache = hit` in its metadata.
+    pub fn get(&self, key: &CacheKey) -> Option<ExecutionResult> {
+        let result = self
+            .entries
+            .lock()
+            .ok()
+            .and_then(|mut entries| match entries.get(key) {
+                Some(cached) if cached.stored_at.elapsed() < self.config.ttl => {
+                    Some(cached.result.clone())
+                }
+                Some(_) => {
+                    entries.remove(key);
+                    None
+                }
+                None => None,
+            });
+
+        match result {
+            Some(mut result) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                result
+                    .metadata
+                    .insert("result_cache".to_string(), "hit".to_string());
+                Some(result)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store `result` under `key` if it succeeded
+    ///
+    /// When full, expired entries are swept first and then the oldest
+    /// entries are evicted.
+    pub fn insert(&self, key: CacheKey, result: &ExecutionResult) {
+        if self.config.max_entries == 0 || !result.is_success() {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        if !entries.contains_key(&key) && entries.len() >= self.config.max_entries {
+            let ttl = self.config.ttl;
+            entries.retain(|_, cached| cached.stored_at.elapsed() < ttl);
+        }
+        while !entries.contains_key(&key) && entries.len() >= self.config.max_entries {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.seq)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            entries.remove(&oldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        entries.insert(
+            key,
+            CachedResult {
+                result: result.clone(),
+                stored_at: Instant::now(),
+                seq: self.inserts.fetch_add(1, Ordering::Relaxed),
+            },
+        );
+    }
+
+    /// Drop cached results, for one instance or for all
+    ///
+    /// # Returns
+    /// Number of results dropped
+    pub fn invalidate(&self, instance_id: Option<&str>) -> usize {
+        let Ok(mut entries) = self.entries.lock() else {
+            return 0;
+        };
+        let before = entries.len();
+        entries.retain(|key, _| instance_id.is_some_and(|wanted| wanted != key.instance_id));
+        before - entries.len()
+    }
+
+    /// Current counters
+    pub fn metrics(&self) -> ResultCacheMetrics {
+        ResultCacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self
+                .entries
+                .lock()
+                .map(|entries| entries.len())
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(max_entries: usize) -> ResultCache {
+        ResultCache::new(ResultCacheConfig {
+            max_entries,
+            ..ResultCacheConfig::default()
+        })
+    }
+
+    #[test]
+    fn key_covers_code_and_environment() {
+        let request = ExecutionRequest::new("print(1)", "Python").with_env("A", "1");
+        let key = CacheKey::new("inst", &request);
+
+        assert_eq!(key, CacheKey::new("inst", &request.clone()));
+        assert_eq!(key.language, "python");
+        assert_ne!(key, CacheKey::new("other", &request));
+        assert_ne!(
+            key,
+            CacheKey::new("inst", &request.clone().with_env("A", "2"))
+        );
+        assert_ne!(
+            key,
+            CacheKey::new(
+                "inst",
+                &ExecutionRequest::new("print(2)", "python").with_env("A", "1")
+            )
+        );
+        assert_ne!(key, CacheKey::new("inst", &request.clone().with_stdin("x")));
+    }
+
+    #[test]
+    fn serves_successes_only() {
+        let cache = cache(8);
+        let request = ExecutionRequest::new("print(1)", "python");
+        let key = CacheKey::new("inst", &request);
+
+        cache.insert(key.clone(), &ExecutionResult::failure(1, "boom"));
+        assert!(cache.get(&key).is_none());
+
+        cache.insert(key.clone(), &ExecutionResult::success("1\n"));
+        let hit = cache.get(&key).expect("cached result");
+        assert_eq!(hit.stdout, "1\n");
+        assert_eq!(
+            hit.metadata.get("result_cache").map(String::as_str),
+            Some("hit")
+        );
+
+        let metrics = cache.metrics();
+        assert_eq!((metrics.hits, metrics.misses, metrics.entries), (1, 1, 1));
+    }
+
+    #[test]
+    fn no_cache_and_disabled_cache_bypass() {
+        let request = ExecutionRequest::new("print(1)", "python");
+
+        assert!(cache(8).is_cacheable(&request));
+        assert!(!cache(8).is_cacheable(&request.clone().without_cache()));
+        assert!(!cache(0).is_cacheable(&request));
+    }
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let cache = cache(2);
+        let keys: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|code| CacheKey::new("inst", &ExecutionRequest::new(*code, "python")))
+            .collect();
+
+        for key in &keys {
+            cache.insert(key.clone(), &ExecutionResult::success(""));
+        }
+
+        assert!(cache.get(&keys[0]).is_none());
+        assert!(cache.get(&keys[2]).is_some());
+        assert_eq!(cache.metrics().evictions, 1);
+        assert_eq!(cache.invalidate(Some("inst")), 2);
+        assert_eq!(cache.metrics().entries, 0);
+    }
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use log::{error, info, warn};
 
@@ -23,6 +24,36 @@ pub enum PipelineEvent {
         /// The code to execute
         code: String,
     },
+    /// A managed instance failed its health probe
+    InstanceUnhealthy {
+        /// The instance probed
+        instance_id: String,
+        /// Why the probe failed
+        message: String,
+        /// Failed probes in a row, including this one
+        consecutive_failures: u32,
+    },
+    /// An unhealthy instance was replaced with a fresh backend
+    InstanceReplaced {
+        /// The instance replaced
+        instance_id: String,
+        /// Replacement attempts it took, including this one
+        attempt: u32,
+    },
+    /// Replacing an unhealthy instance failed
+    InstanceReplacementFailed {
+        /// The instance that could not be replaced
+        instance_id: String,
+        /// Why the replacement failed
+        error: String,
+        /// Wait before the next attempt
+        retry_in: Duration,
+    },
+    /// An instance that had failed probes passed one again
+    InstanceRecovered {
+        /// The instance that recovered
+        instance_id: String,
+    },
 }
 
 /// States of the execution flow state machine
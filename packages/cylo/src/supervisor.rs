@@ -0,0 +1,164 @@
+// ============================================================================
+// File: packages/cylo/src/supervisor.rs
+// ----------------------------------------------------------------------------
+// Health supervision settings and results for the instance manager.
+//
+// The supervisor probes every registered instance on an interval and
+// replaces instances that keep failing with a fresh backend built from the
+// same spec. Replacements that fail are retried with exponential backoff.
+// ============================================================================
+
+use std::time::{Duration, SystemTime};
+
+use tokio::task::JoinHandle;
+
+/// Settings of the health supervisor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupervisorConfig {
+    /// Time between probes of all instances
+    pub interval: Duration,
+
+    /// Consecutive failed probes before an instance is replaced
+    pub failure_threshold: u32,
+
+    /// Wait before retrying after the first failed replacement
+    pub initial_backoff: Duration,
+
+    /// Longest wait between replacement attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            failure_threshold: 2,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(300), // 5 minutes
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Set the time between probes
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how many consecutive failed probes trigger a replacement, at least 1
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures.max(1);
+        self
+    }
+
+    /// Set the first and the longest wait between replacement attempts
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Wait before the next replacement after `failed_attempts` failures
+    ///
+    /// Doubles with every failure, starting at `initial_backoff` and capped
+    /// at `max_backoff`.
+    pub fn backoff(&self, failed_attempts: u32) -> Duration {
+        let doublings = failed_attempts.saturating_sub(1).min(31);
+        self.initial_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_backoff)
+    }
+}
+
+/// What one supervision pass found and did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupervisorReport {
+    /// Instances that passed their probe
+    pub healthy: Vec<String>,
+    /// Unhealthy instances below the failure threshold, probed again next pass
+    pub unhealthy: Vec<String>,
+    /// Unhealthy instances left alone while executions are in flight
+    pub deferred: Vec<String>,
+    /// Unhealthy instances waiting out the backoff of a failed replacement
+    pub backing_off: Vec<String>,
+    /// Instances replaced with a fresh backend
+    pub replaced: Vec<String>,
+    /// Instances whose replacement failed this pass
+    pub failed: Vec<String>,
+}
+
+/// Failure history of one instance, kept between passes
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RecoveryState {
+    /// Failed probes in a row
+    pub(crate) consecutive_failures: u32,
+
+    /// Failed replacements in a row
+    pub(crate) failed_replacements: u32,
+
+    /// No replacement is attempted before this time
+    pub(crate) retry_at: Option<SystemTime>,
+}
+
+impl RecoveryState {
+    /// Whether a failed replacement's backoff is still running
+    pub(crate) fn backing_off(&self, now: SystemTime) -> bool {
+        self.retry_at.is_some_and(|retry_at| now < retry_at)
+    }
+}
+
+/// Running supervisor loop, stopped when the handle is stopped or dropped
+#[derive(Debug)]
+pub struct SupervisorHandle {
+    task: JoinHandle<()>,
+}
+
+impl SupervisorHandle {
+    pub(crate) fn new(task: JoinHandle<()>) -> Self {
+        Self { task }
+    }
+
+    /// Whether the loop is still running
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stop the loop; a pass in progress is abandoned
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for SupervisorHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let config = SupervisorConfig::default()
+            .with_backoff(Duration::from_secs(2), Duration::from_secs(20));
+
+        assert_eq!(config.backoff(1), Duration::from_secs(2));
+        assert_eq!(config.backoff(2), Duration::from_secs(4));
+        assert_eq!(config.backoff(4), Duration::from_secs(16));
+        assert_eq!(config.backoff(5), Duration::from_secs(20));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn threshold_and_backoff_are_clamped() {
+        let config = SupervisorConfig::default()
+            .with_failure_threshold(0)
+            .with_backoff(Duration::from_secs(10), Duration::from_secs(1));
+
+        assert_eq!(config.failure_threshold, 1);
+        assert_eq!(config.max_backoff, Duration::from_secs(10));
+    }
+}
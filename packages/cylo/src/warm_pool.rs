@@ -0,0 +1,299 @@
+// ============================================================================
+// File: packages/cylo/src/warm_pool.rs
+// ----------------------------------------------------------------------------
+// Warm pool of pre-forked interpreter sandboxes keyed by (instance, language).
+//
+// Provides:
+// - Pre-started interpreter processes so short snippets skip backend startup
+// - Checkout for eligible requests, with a cold-path fallback on a miss
+// - Recycling after every checkout: the used sandbox and its workspace are
+//   discarded and a fresh one is started, so no state leaks between requests
+// - Hit-rate and recycle-time metrics
+// ============================================================================
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::backends::{
+    ExecutionBackend, ExecutionRequest, ReplSession, ResourceLimits, session::session_languages,
+};
+
+/// Warm pool sizing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmPoolConfig {
+    /// Idle sandboxes kept per (instance, language); 0 disables the pool
+    pub size_per_key: usize,
+
+    /// Limits every pooled sandbox is started with
+    ///
+    /// Only requests with exactly these limits are served from the pool.
+    pub limits: ResourceLimits,
+}
+
+impl Default for WarmPoolConfig {
+    fn default() -> Self {
+        Self {
+            size_per_key: 2,
+            limits: ResourceLimits::default(),
+        }
+    }
+}
+
+/// Snapshot of warm pool counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WarmPoolMetrics {
+    /// Requests served by a pooled sandbox
+    pub hits: u64,
+
+    /// Eligible requests that found no idle sandbox
+    pub misses: u64,
+
+    /// Sandboxes replaced after a checkout
+    pub recycles: u64,
+
+    /// Replacements that failed to start
+    pub recycle_failures: u64,
+
+    /// Cumulative time spent tearing down and restarting sandboxes
+    pub total_recycle_time: Duration,
+
+    /// Sandboxes currently idle in the pool
+    pub idle: usize,
+}
+
+impl WarmPoolMetrics {
+    /// Fraction of eligible requests served warm (0.0 when nothing was served)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+
+    /// Mean time per recycle
+    pub fn average_recycle_time(&self) -> Duration {
+        match u32::try_from(self.recycles) {
+            Ok(0) | Err(_) => Duration::ZERO,
+            Ok(recycles) => self.total_recycle_time / recycles,
+        }
+    }
+}
+
+/// Pool of pre-started interpreter sandboxes
+#[derive(Debug)]
+pub struct WarmPool {
+    config: WarmPoolConfig,
+    idle: Mutex<HashMap<(String, String), Vec<Arc<ReplSession>>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    recycles: AtomicU64,
+    recycle_failures: AtomicU64,
+    recycle_nanos: AtomicU64,
+}
+
+impl WarmPool {
+    /// Create an empty pool
+    pub fn new(config: WarmPoolConfig) -> Self {
+        Self {
+            config,
+            idle: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            recycles: AtomicU64::new(0),
+            recycle_failures: AtomicU64::new(0),
+            recycle_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether `request` can run in a pooled sandbox
+    ///
+    /// Pooled sandboxes are started ahead of time, so anything that would
+    /// change how the sandbox is set up sends the request down the cold path.
+    pub fn is_eligible(&self, request: &ExecutionRequest) -> bool {
+        self.config.size_per_key > 0
+            && session_languages().contains(&request.language.to_lowercase().as_str())
+            && request.stdin_data().is_none()
+            && request.env_vars.is_empty()
+            && request.working_dir.is_none()
+            && request.network.is_none()
+            && request.artifacts.is_empty()
+            && request.backend_config.is_empty()
+            && request.limits == self.config.limits
+    }
+
+    /// Take an idle sandbox for `language` on `instance_id`, recording a hit or miss
+    pub fn checkout(&self, instance_id: &str, language: &str) -> Option<Arc<ReplSession>> {
+        let session = self.idle.lock().ok().and_then(|mut idle| {
+            idle.get_mut(&key(instance_id, language))
+                .and_then(Vec::pop)
+        });
+
+        let counter = if session.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        session
+    }
+
+    /// Start sandboxes until the key holds `size_per_key` idle entries
+    ///
+    /// # Returns
+    /// Number of sandboxes started
+    pub fn fill(&self, backend: &dyn ExecutionBackend, instance_id: &str, language: &str) -> usize {
+        let missing = self
+            .config
+            .size_per_key
+            .saturating_sub(self.idle_count(instance_id, language));
+
+        let mut started = 0;
+        for _ in 0..missing {
+            match ReplSession::start(backend, language, self.config.limits.clone()) {
+                Ok(session) => {
+                    self.push(instance_id, language, Arc::new(session));
+                    started += 1;
+                }
+                Err(e) => {
+                    log::debug!("Warm pool cannot pre-start {} on {}: {}", language, instance_id, e);
+                    break;
+                }
+            }
+        }
+        started
+    }
+
+    /// Discard a checked-out sandbox and start a fresh replacement
+    ///
+    /// Closing the session removes its workspace, so the replacement starts
+    /// from a clean filesystem and interpreter.
+    pub async fn recycle(
+        &self,
+        backend: &dyn ExecutionBackend,
+        instance_id: &str,
+        used: Arc<ReplSession>,
+    ) {
+        let start = Instant::now();
+        let language = used.language().to_string();
+        let _ = used.close().await;
+
+        match ReplSession::start(backend, &language, self.config.limits.clone()) {
+            Ok(session) => {
+                if self.idle_count(instance_id, &language) < self.config.size_per_key {
+                    self.push(instance_id, &language, Arc::new(session));
+                } else {
+                    let _ = Arc::new(session).close().await;
+                }
+                self.recycles.fetch_add(1, Ordering::Relaxed);
+                self.recycle_nanos.fetch_add(
+                    u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX),
+                    Ordering::Relaxed,
+                );
+            }
+            Err(e) => {
+                log::warn!("Failed to recycle warm {} sandbox on {}: {}", language, instance_id, e);
+                self.recycle_failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Remove idle sandboxes, for one instance or for all
+    pub fn drain(&self, instance_id: Option<&str>) -> Vec<Arc<ReplSession>> {
+        let Ok(mut idle) = self.idle.lock() else {
+            return Vec::new();
+        };
+
+        let keys: Vec<_> = idle
+            .keys()
+            .filter(|(id, _)| instance_id.is_none_or(|wanted| wanted == id))
+            .cloned()
+            .collect();
+
+        keys.into_iter()
+            .filter_map(|key| idle.remove(&key))
+            .flatten()
+            .collect()
+    }
+
+    /// Current counters
+    pub fn metrics(&self) -> WarmPoolMetrics {
+        WarmPoolMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            recycles: self.recycles.load(Ordering::Relaxed),
+            recycle_failures: self.recycle_failures.load(Ordering::Relaxed),
+            total_recycle_time: Duration::from_nanos(self.recycle_nanos.load(Ordering::Relaxed)),
+            idle: self
+                .idle
+                .lock()
+                .map(|idle| idle.values().map(Vec::len).sum())
+                .unwrap_or(0),
+        }
+    }
+
+    fn idle_count(&self, instance_id: &str, language: &str) -> usize {
+        self.idle
+            .lock()
+            .map(|idle| idle.get(&key(instance_id, language)).map_or(0, Vec::len))
+            .unwrap_or(0)
+    }
+
+    fn push(&self, instance_id: &str, language: &str, session: Arc<ReplSession>) {
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.entry(key(instance_id, language)).or_default().push(session);
+        }
+    }
+}
+
+/// Pool key with the language normalized so aliases share sandboxes
+fn key(instance_id: &str, language: &str) -> (String, String) {
+    let language = match language.to_lowercase().as_str() {
+        "python" | "python3" => "python",
+        "javascript" | "js" | "node" => "javascript",
+        other => return (instance_id.to_string(), other.to_string()),
+    };
+    (instance_id.to_string(), language.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_plain_snippets_are_eligible() {
+        let pool = WarmPool::new(WarmPoolConfig::default());
+
+        assert!(pool.is_eligible(&ExecutionRequest::new("print(1)", "python")));
+        assert!(!pool.is_eligible(&ExecutionRequest::new("fn main() {}", "rust")));
+        assert!(!pool.is_eligible(&ExecutionRequest::new("input()", "python").with_stdin("x")));
+        assert!(!pool.is_eligible(
+            &ExecutionRequest::new("print(1)", "python").with_artifacts("*.csv")
+        ));
+    }
+
+    #[test]
+    fn empty_pool_records_misses() {
+        let pool = WarmPool::new(WarmPoolConfig::default());
+
+        assert!(pool.checkout("inst", "python3").is_none());
+        assert!(pool.checkout("inst", "node").is_none());
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.misses, 2);
+        assert_eq!(metrics.hit_rate(), 0.0);
+        assert_eq!(metrics.average_recycle_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn language_aliases_share_a_key() {
+        assert_eq!(key("a", "python3"), key("a", "Python"));
+        assert_eq!(key("a", "js"), key("a", "node"));
+        assert_ne!(key("a", "python"), key("b", "python"));
+    }
+}
@@ -110,6 +110,16 @@ fn bench_argmax_by_size(c: &mut Criterion) {
             });
         }
 
+        // Benchmark SVE (only callable on SVE hardware)
+        if get_cpu_features() == CpuFeatures::Sve {
+            group.bench_with_input(BenchmarkId::new("sve", size), &size, |b, _| {
+                b.iter(|| {
+                    let result = (*ARGMAX_DISPATCH).call_with_feature(black_box(&logits), CpuFeatures::Sve);
+                    black_box(result)
+                })
+            });
+        }
+
         // Benchmark runtime dispatch
         group.bench_with_input(BenchmarkId::new("dispatch", size), &size, |b, _| {            b.iter(|| {
                 let result = ARGMAX_DISPATCH.call(black_box(&logits));
@@ -0,0 +1,81 @@
+//! Fused logits processing benchmarks
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use cyrup_simd::config::ProcessorConfig;
+use cyrup_simd::context::ProcessingContext;
+use cyrup_simd::logits::processor::DefaultLogitsProcessor;
+use cyrup_simd::process_logits_fused;
+use rand::Rng;
+use std::hint::black_box;
+
+/// Generate test data for benchmarking
+fn generate_test_data(size: usize) -> Vec<f32> {
+    let mut rng = rand::rng();
+    (0..size).map(|_| rng.random_range(-10.0..10.0)).collect()
+}
+
+fn bench_logits_pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("logits_pipeline");
+    let sizes = [1024, 32_000, 128_256];
+
+    let config = ProcessorConfig::default()
+        .with_repetition_penalty(1.1)
+        .with_frequency_penalty(0.2)
+        .with_presence_penalty(0.1);
+    let mut rng = rand::rng();
+
+    for &size in &sizes {
+        let logits = generate_test_data(size);
+        let history: Vec<u32> = (0..256).map(|_| rng.random_range(0..size as u32)).collect();
+        let scenarios = [
+            ("top_k", ProcessingContext::new().with_top_k(Some(50))),
+            ("top_p", ProcessingContext::new().with_top_p(Some(0.9))),
+            (
+                "top_k_top_p",
+                ProcessingContext::new()
+                    .with_top_k(Some(50))
+                    .with_top_p(Some(0.9)),
+            ),
+        ];
+
+        for (name, context) in scenarios {
+            let context = context
+                .with_temperature(0.7)
+                .with_token_history(history.clone());
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("staged/{name}"), size),
+                &size,
+                |b, _| {
+                    let mut processor = DefaultLogitsProcessor::with_config(config.clone());
+                    b.iter(|| {
+                        let mut data = logits.clone();
+                        let result = processor.process(black_box(&mut data), black_box(&context));
+                        black_box((result, data))
+                    })
+                },
+            );
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("fused/{name}"), size),
+                &size,
+                |b, _| {
+                    b.iter(|| {
+                        let mut data = logits.clone();
+                        let result = process_logits_fused(
+                            black_box(&mut data),
+                            black_box(&context),
+                            black_box(&config),
+                        );
+                        black_box((result, data))
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_logits_pipeline);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+//! Similarity benchmarks across instruction sets
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use cyrup_simd::runtime::{CpuFeatures, get_cpu_features};
+use cyrup_simd::similarity::quantized::dot_product_i8_with_feature;
+use cyrup_simd::similarity::{CosineSimilarity, PortableSimdSimilarity, for_features};
+use rand::Rng;
+use std::hint::black_box;
+
+/// Embedding sizes of common models
+const DIMENSIONS: [usize; 4] = [128, 384, 768, 1536];
+
+/// Generate test data for benchmarking
+fn generate_test_data(size: usize) -> Vec<f32> {
+    let mut rng = rand::rng();
+    (0..size).map(|_| rng.random_range(-1.0..1.0)).collect()
+}
+
+fn generate_test_data_i8(size: usize) -> Vec<i8> {
+    let mut rng = rand::rng();
+    (0..size).map(|_| rng.random_range(-127..=127)).collect()
+}
+
+fn bench_cosine_similarity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cosine_similarity");
+    eprintln!("Detected CPU features: {:?}", get_cpu_features());
+
+    let features = [
+        CpuFeatures::Scalar,
+        CpuFeatures::Sse41,
+        CpuFeatures::Avx2,
+        CpuFeatures::Avx512,
+        CpuFeatures::Neon,
+        CpuFeatures::Sve,
+    ];
+
+    for &size in &DIMENSIONS {
+        let a = generate_test_data(size);
+        let b = generate_test_data(size);
+
+        for feature in features {
+            // Implementations the CPU cannot run are skipped
+            let Some(implementation) = for_features(feature) else {
+                continue;
+            };
+            group.bench_with_input(
+                BenchmarkId::new(implementation.name(), size),
+                &size,
+                |bench, _| {
+                    bench.iter(|| {
+                        black_box(implementation.cosine_similarity(black_box(&a), black_box(&b)))
+                    })
+                },
+            );
+        }
+
+        let portable = PortableSimdSimilarity::new();
+        group.bench_with_input(BenchmarkId::new("portable", size), &size, |bench, _| {
+            bench.iter(|| black_box(portable.cosine_similarity(black_box(&a), black_box(&b))))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_dot_product_i8(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dot_product_i8");
+
+    for &size in &DIMENSIONS {
+        let a = generate_test_data_i8(size);
+        let b = generate_test_data_i8(size);
+
+        for (name, feature) in [
+            ("scalar", CpuFeatures::Scalar),
+            ("avx512_vnni", CpuFeatures::Avx512Vnni),
+        ] {
+            if dot_product_i8_with_feature(&a, &b, feature).is_err() {
+                continue;
+            }
+            group.bench_with_input(BenchmarkId::new(name, size), &size, |bench, _| {
+                bench.iter(|| {
+                    black_box(dot_product_i8_with_feature(
+                        black_box(&a),
+                        black_box(&b),
+                        feature,
+                    ))
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_cosine_similarity, bench_dot_product_i8);
+criterion_main!(benches);
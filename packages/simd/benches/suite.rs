@@ -0,0 +1,25 @@
+//! The `paraphym-simd bench` suite under criterion
+//!
+//! Runs the same cases as the CLI so `cargo bench --features bench --bench
+//! suite` and the JSON reports measure identical work.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use cyrup_simd::benchmark::suite::{Kernel, SuiteConfig, cases};
+
+fn bench_suite(c: &mut Criterion) {
+    for kernel in Kernel::ALL {
+        let mut group = c.benchmark_group(kernel.name());
+        let config = SuiteConfig::default().with_kernels(vec![kernel]);
+
+        for mut case in cases(&config) {
+            group.bench_function(BenchmarkId::new(case.variant.clone(), case.size), |b| {
+                b.iter(|| case.run())
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_suite);
+criterion_main!(benches);
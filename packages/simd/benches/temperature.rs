@@ -96,6 +96,21 @@ fn bench_temperature_scaling(c: &mut Criterion) {
             });
         }
 
+        // Benchmark SVE implementation (only callable on SVE hardware)
+        if cpu_features == CpuFeatures::Sve {
+            group.bench_with_input(BenchmarkId::new("sve", size), &size, |b, _| {
+                b.iter(|| {
+                    let mut data = logits.clone();
+                    let result = (*TEMPERATURE_DISPATCH).call_with_feature(
+                        black_box(&mut data),
+                        black_box(temperature),
+                        CpuFeatures::Sve,
+                    );
+                    black_box(result)
+                })
+            });
+        }
+
         // Benchmark runtime dispatch (default API)
         group.bench_with_input(BenchmarkId::new("dispatch", size), &size, |b, _| {
             b.iter(|| {
@@ -0,0 +1,445 @@
+//! Hierarchical Navigable Small World graph
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::{AnnError, AnnMetric, AnnResult, HnswConfig};
+
+/// Highest layer a node can be assigned to
+const MAX_LEVEL: usize = 16;
+
+/// A node slot and its distance to the current query
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scored {
+    distance: f32,
+    slot: u32,
+}
+
+impl Eq for Scored {}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.slot.cmp(&other.slot))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Node {
+    pub(super) key: String,
+    pub(super) vector: Vec<f32>,
+    /// Neighbor slots per layer, from layer 0 up to the node's level
+    pub(super) links: Vec<Vec<u32>>,
+}
+
+/// HNSW index over string keys
+///
+/// Deleting a node reconnects every node that linked to it, so the graph
+/// stays navigable without periodic rebuilds. Deletes scan the graph for
+/// those nodes and cost `O(n)`; inserts and searches are logarithmic.
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    pub(super) config: HnswConfig,
+    pub(super) metric: AnnMetric,
+    pub(super) dimensions: usize,
+    pub(super) nodes: Vec<Option<Node>>,
+    pub(super) keys: FxHashMap<String, u32>,
+    pub(super) free: Vec<u32>,
+    pub(super) entry: Option<u32>,
+    pub(super) rng: u64,
+}
+
+impl HnswIndex {
+    /// Create an empty index for vectors of `dimensions`
+    pub fn new(dimensions: usize, metric: AnnMetric, config: HnswConfig) -> AnnResult<Self> {
+        config.validate()?;
+        if dimensions == 0 {
+            return Err(AnnError::InvalidConfig(
+                "dimensions must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            metric,
+            dimensions,
+            nodes: Vec::new(),
+            keys: FxHashMap::default(),
+            free: Vec::new(),
+            entry: None,
+            rng: config.seed,
+        })
+    }
+
+    /// Dimensions of the indexed vectors
+    #[inline]
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Distance metric of the index
+    #[inline]
+    pub fn metric(&self) -> AnnMetric {
+        self.metric
+    }
+
+    /// Construction and search parameters
+    #[inline]
+    pub fn config(&self) -> &HnswConfig {
+        &self.config
+    }
+
+    /// Set the default search beam width
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        self.config.ef_search = ef_search.max(1);
+    }
+
+    /// Number of indexed vectors
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the index holds no vectors
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Whether `key` is indexed
+    #[inline]
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains_key(key)
+    }
+
+    /// Vector indexed under `key`
+    pub fn get(&self, key: &str) -> Option<&[f32]> {
+        let slot = *self.keys.get(key)?;
+        Some(&self.node(slot).vector)
+    }
+
+    /// Keys of all indexed vectors, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.keys().map(String::as_str)
+    }
+
+    /// Index `vector` under `key`, replacing any vector already indexed there
+    pub fn insert(&mut self, key: impl Into<String>, vector: Vec<f32>) -> AnnResult<()> {
+        self.check_dimensions(&vector)?;
+        let key = key.into();
+        self.remove(&key);
+
+        let level = self.random_level();
+        let query = vector.clone();
+        let slot = self.allocate(Node {
+            key: key.clone(),
+            vector,
+            links: vec![Vec::new(); level + 1],
+        });
+        self.keys.insert(key, slot);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(slot);
+            return Ok(());
+        };
+
+        let top = self.node(entry).links.len() - 1;
+        let mut closest = self.scored(&query, entry);
+        for layer in (level + 1..=top).rev() {
+            closest = self.greedy_closest(&query, closest, layer);
+        }
+
+        let mut entry_points = vec![closest];
+        for layer in (0..=level.min(top)).rev() {
+            let found =
+                self.search_layer(&query, &entry_points, self.config.ef_construction, layer);
+            let neighbors = self.select_neighbors(&found, self.max_links(layer));
+            for &neighbor in &neighbors {
+                self.link(neighbor, slot, layer);
+            }
+            self.node_mut(slot).links[layer] = neighbors;
+            entry_points = found;
+        }
+
+        if level > top {
+            self.entry = Some(slot);
+        }
+        Ok(())
+    }
+
+    /// Remove the vector indexed under `key`, returning whether it existed
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(slot) = self.keys.remove(key) else {
+            return false;
+        };
+        let Some(removed) = self.nodes[slot as usize].take() else {
+            return false;
+        };
+        self.free.push(slot);
+
+        for (layer, removed_links) in removed.links.iter().enumerate() {
+            let referrers: Vec<u32> = self
+                .live_slots()
+                .filter(|&s| {
+                    self.node(s)
+                        .links
+                        .get(layer)
+                        .is_some_and(|links| links.contains(&slot))
+                })
+                .collect();
+
+            for referrer in referrers {
+                let mut candidates: FxHashSet<u32> = self.node(referrer).links[layer]
+                    .iter()
+                    .copied()
+                    .filter(|&s| s != slot)
+                    .collect();
+                candidates.extend(removed_links.iter().copied().filter(|&s| s != referrer));
+
+                let base = &self.node(referrer).vector;
+                let mut scored: Vec<Scored> = candidates
+                    .into_iter()
+                    .map(|s| self.scored(base, s))
+                    .collect();
+                scored.sort();
+                let links = self.select_neighbors(&scored, self.max_links(layer));
+                self.node_mut(referrer).links[layer] = links;
+            }
+        }
+
+        if self.entry == Some(slot) {
+            self.entry = self
+                .live_slots()
+                .max_by_key(|&s| (self.node(s).links.len(), Reverse(s)));
+        }
+        true
+    }
+
+    /// The `k` nearest neighbors of `query` with their distances, closest first
+    pub fn search(&self, query: &[f32], k: usize) -> AnnResult<Vec<(String, f32)>> {
+        self.search_with_ef(query, k, self.config.ef_search)
+    }
+
+    /// [`search`](Self::search) with an explicit beam width
+    ///
+    /// The beam is widened to at least `k`.
+    pub fn search_with_ef(
+        &self,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+    ) -> AnnResult<Vec<(String, f32)>> {
+        self.check_dimensions(query)?;
+        let Some(entry) = self.entry else {
+            return Ok(Vec::new());
+        };
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let top = self.node(entry).links.len() - 1;
+        let mut closest = self.scored(query, entry);
+        for layer in (1..=top).rev() {
+            closest = self.greedy_closest(query, closest, layer);
+        }
+
+        let found = self.search_layer(query, &[closest], ef.max(k), 0);
+        Ok(found
+            .into_iter()
+            .take(k)
+            .map(|s| (self.node(s.slot).key.clone(), s.distance))
+            .collect())
+    }
+
+    fn check_dimensions(&self, vector: &[f32]) -> AnnResult<()> {
+        if vector.len() == self.dimensions {
+            Ok(())
+        } else {
+            Err(AnnError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: vector.len(),
+            })
+        }
+    }
+
+    #[inline]
+    fn node(&self, slot: u32) -> &Node {
+        match &self.nodes[slot as usize] {
+            Some(node) => node,
+            None => unreachable!("link to freed slot {slot}"),
+        }
+    }
+
+    #[inline]
+    fn node_mut(&mut self, slot: u32) -> &mut Node {
+        match &mut self.nodes[slot as usize] {
+            Some(node) => node,
+            None => unreachable!("link to freed slot {slot}"),
+        }
+    }
+
+    fn live_slots(&self) -> impl Iterator<Item = u32> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.is_some())
+            .map(|(slot, _)| slot as u32)
+    }
+
+    fn allocate(&mut self, node: Node) -> u32 {
+        match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot as usize] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                (self.nodes.len() - 1) as u32
+            }
+        }
+    }
+
+    #[inline]
+    fn scored(&self, query: &[f32], slot: u32) -> Scored {
+        Scored {
+            distance: self.metric.distance(query, &self.node(slot).vector),
+            slot,
+        }
+    }
+
+    #[inline]
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    /// Geometric level with normalization factor `1 / ln(m)`
+    fn random_level(&mut self) -> usize {
+        // splitmix64
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let ml = 1.0 / (self.config.m as f64).ln();
+        ((-uniform.ln() * ml).floor() as usize).min(MAX_LEVEL)
+    }
+
+    fn greedy_closest(&self, query: &[f32], mut closest: Scored, layer: usize) -> Scored {
+        loop {
+            let mut improved = false;
+            for &neighbor in &self.node(closest.slot).links[layer] {
+                let candidate = self.scored(query, neighbor);
+                if candidate.distance < closest.distance {
+                    closest = candidate;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return closest;
+            }
+        }
+    }
+
+    /// Beam search on one layer, returning up to `ef` nodes closest first
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[Scored],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Scored> {
+        let mut visited: FxHashSet<u32> = entry_points.iter().map(|s| s.slot).collect();
+        let mut candidates: BinaryHeap<Reverse<Scored>> =
+            entry_points.iter().copied().map(Reverse).collect();
+        let mut results: BinaryHeap<Scored> = entry_points.iter().copied().collect();
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            let furthest = results.peek().map_or(f32::INFINITY, |s| s.distance);
+            if current.distance > furthest && results.len() >= ef {
+                break;
+            }
+
+            for &neighbor in &self.node(current.slot).links[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = self.scored(query, neighbor);
+                let furthest = results.peek().map_or(f32::INFINITY, |s| s.distance);
+                if results.len() < ef || candidate.distance < furthest {
+                    candidates.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Neighbor selection heuristic
+    ///
+    /// Keeps a candidate only if it is closer to the base than to every
+    /// neighbor already kept, which spreads links across directions. Pruned
+    /// candidates fill any remaining room.
+    fn select_neighbors(&self, candidates: &[Scored], max: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(max);
+        let mut pruned: Vec<u32> = Vec::new();
+
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = &self.node(candidate.slot).vector;
+            let diverse = selected.iter().all(|&kept| {
+                self.metric.distance(vector, &self.node(kept).vector) > candidate.distance
+            });
+            if diverse {
+                selected.push(candidate.slot);
+            } else {
+                pruned.push(candidate.slot);
+            }
+        }
+
+        let room = max.saturating_sub(selected.len());
+        selected.extend(pruned.into_iter().take(room));
+        selected
+    }
+
+    /// Add a link from `from` to `to`, pruning `from`'s links if over capacity
+    fn link(&mut self, from: u32, to: u32, layer: usize) {
+        let max = self.max_links(layer);
+        let links = &mut self.node_mut(from).links[layer];
+        links.push(to);
+        if links.len() <= max {
+            return;
+        }
+
+        let base = &self.node(from).vector;
+        let mut scored: Vec<Scored> = self.node(from).links[layer]
+            .iter()
+            .map(|&s| self.scored(base, s))
+            .collect();
+        scored.sort();
+        let pruned = self.select_neighbors(&scored, max);
+        self.node_mut(from).links[layer] = pruned;
+    }
+}
@@ -0,0 +1,148 @@
+//! Approximate nearest neighbor search
+//!
+//! [`HnswIndex`] is a Hierarchical Navigable Small World graph over string
+//! keys. Inserts and deletes are incremental, searches visit a logarithmic
+//! number of nodes tuned by the `ef` beam width, and the whole graph can be
+//! saved to and loaded from disk without rebuilding.
+//!
+//! Cosine distance is computed with the crate's runtime-selected
+//! [`cosine_similarity`](crate::similarity::cosine_similarity) kernel.
+
+mod hnsw;
+mod persist;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use hnsw::HnswIndex;
+
+/// Errors of approximate nearest neighbor indexes
+#[derive(Debug, Error)]
+pub enum AnnError {
+    /// A vector does not have the index's dimensions
+    #[error("Vector dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch {
+        /// Dimensions of the index
+        expected: usize,
+        /// Dimensions of the vector
+        actual: usize,
+    },
+
+    /// Invalid index configuration
+    #[error("Invalid index configuration: {0}")]
+    InvalidConfig(String),
+
+    /// Reading or writing an index file failed
+    #[error("Index I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An index file is not a valid index
+    #[error("Corrupt index file: {0}")]
+    Corrupt(String),
+
+    /// An index file was written by an unsupported format version
+    #[error("Unsupported index format version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// Result type for approximate nearest neighbor operations
+pub type AnnResult<T> = Result<T, AnnError>;
+
+/// Distance between vectors; smaller is closer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnMetric {
+    /// `1 - cosine similarity`
+    #[default]
+    Cosine,
+    /// L2 distance
+    Euclidean,
+    /// Negative dot product
+    DotProduct,
+}
+
+impl AnnMetric {
+    /// Distance between `a` and `b`
+    #[inline]
+    pub fn distance(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => 1.0 - crate::similarity::cosine_similarity(a, b),
+            Self::Euclidean => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f32>()
+                .sqrt(),
+            Self::DotProduct => -a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>(),
+        }
+    }
+}
+
+/// HNSW construction and search parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HnswConfig {
+    /// Links per node on the upper layers; layer 0 keeps twice as many
+    pub m: usize,
+    /// Beam width while inserting; higher builds a better graph more slowly
+    pub ef_construction: usize,
+    /// Default beam width while searching; higher is more accurate and slower
+    pub ef_search: usize,
+    /// Seed of the level generator, for reproducible graphs
+    pub seed: u64,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+            seed: 0x5eed,
+        }
+    }
+}
+
+impl HnswConfig {
+    /// Set the links per node
+    #[must_use]
+    pub fn with_m(mut self, m: usize) -> Self {
+        self.m = m;
+        self
+    }
+
+    /// Set the insert beam width
+    #[must_use]
+    pub fn with_ef_construction(mut self, ef_construction: usize) -> Self {
+        self.ef_construction = ef_construction;
+        self
+    }
+
+    /// Set the default search beam width
+    #[must_use]
+    pub fn with_ef_search(mut self, ef_search: usize) -> Self {
+        self.ef_search = ef_search;
+        self
+    }
+
+    /// Set the level generator seed
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn validate(&self) -> AnnResult<()> {
+        if self.m < 2 {
+            return Err(AnnError::InvalidConfig(format!(
+                "m must be at least 2, got {}",
+                self.m
+            )));
+        }
+        if self.ef_construction == 0 || self.ef_search == 0 {
+            return Err(AnnError::InvalidConfig(
+                "ef_construction and ef_search must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+//! On-disk format of [`HnswIndex`]
+//!
+//! Little-endian binary: a magic tag and format version, the index
+//! parameters, then every node slot with its key, vector and links per
+//! layer. Freed slots are kept so that links stay valid without renumbering.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use super::hnsw::{HnswIndex, Node};
+use super::{AnnError, AnnMetric, AnnResult, HnswConfig};
+
+const MAGIC: &[u8; 8] = b"CYRHNSW\0";
+
+/// Current on-disk format version
+const FORMAT_VERSION: u32 = 1;
+
+const NO_ENTRY: u64 = u64::MAX;
+
+impl HnswIndex {
+    /// Write the index to `path`
+    ///
+    /// The file is written next to `path` and renamed into place, so a crash
+    /// never leaves a truncated index behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> AnnResult<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        self.write_to(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read an index written by [`save`](Self::save)
+    pub fn load(path: impl AsRef<Path>) -> AnnResult<Self> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Serialize the index into `writer`
+    pub fn write_to(&self, writer: &mut impl Write) -> AnnResult<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&[metric_tag(self.metric)])?;
+        for value in [
+            self.dimensions as u64,
+            self.config.m as u64,
+            self.config.ef_construction as u64,
+            self.config.ef_search as u64,
+            self.config.seed,
+            self.rng,
+            self.entry.map_or(NO_ENTRY, u64::from),
+            self.nodes.len() as u64,
+        ] {
+            write_u64(writer, value)?;
+        }
+
+        for node in &self.nodes {
+            let Some(node) = node else {
+                writer.write_all(&[0])?;
+                continue;
+            };
+            writer.write_all(&[1])?;
+            write_u64(writer, node.key.len() as u64)?;
+            writer.write_all(node.key.as_bytes())?;
+            write_u64(writer, node.links.len() as u64)?;
+            for value in &node.vector {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            for links in &node.links {
+                write_u64(writer, links.len() as u64)?;
+                for slot in links {
+                    writer.write_all(&slot.to_le_bytes())?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Deserialize an index from `reader`
+    ///
+    /// Every link is checked to point at a live node, so a damaged file is
+    /// rejected rather than producing an index that panics on search.
+    pub fn read_from(reader: &mut impl Read) -> AnnResult<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(AnnError::Corrupt("not an HNSW index".to_string()));
+        }
+        let version = read_u32(reader)?;
+        if version != FORMAT_VERSION {
+            return Err(AnnError::UnsupportedVersion(version));
+        }
+
+        let metric = match read_u8(reader)? {
+            0 => AnnMetric::Cosine,
+            1 => AnnMetric::Euclidean,
+            2 => AnnMetric::DotProduct,
+            tag => return Err(AnnError::Corrupt(format!("unknown metric {tag}"))),
+        };
+        let dimensions = read_len(reader)?;
+        let config = HnswConfig {
+            m: read_len(reader)?,
+            ef_construction: read_len(reader)?,
+            ef_search: read_len(reader)?,
+            seed: read_u64(reader)?,
+        };
+        let rng = read_u64(reader)?;
+        let entry = read_u64(reader)?;
+        let slots = read_len(reader)?;
+        if slots > u32::MAX as usize {
+            return Err(AnnError::Corrupt(format!(
+                "{slots} slots exceed the u32 range"
+            )));
+        }
+
+        let mut index =
+            Self::new(dimensions, metric, config).map_err(|e| AnnError::Corrupt(e.to_string()))?;
+        index.rng = rng;
+        index.nodes = Vec::with_capacity(slots.min(1 << 20));
+        let mut keys = FxHashMap::default();
+
+        for slot in 0..slots {
+            if read_u8(reader)? == 0 {
+                index.nodes.push(None);
+                index.free.push(slot as u32);
+                continue;
+            }
+
+            let key_len = read_len(reader)?;
+            let mut key = Vec::with_capacity(key_len.min(1 << 12));
+            reader.by_ref().take(key_len as u64).read_to_end(&mut key)?;
+            if key.len() != key_len {
+                return Err(AnnError::Corrupt(format!(
+                    "key of slot {slot} is truncated"
+                )));
+            }
+            let key = String::from_utf8(key)
+                .map_err(|_| AnnError::Corrupt(format!("key of slot {slot} is not UTF-8")))?;
+
+            let layers = read_len(reader)?;
+            if layers == 0 {
+                return Err(AnnError::Corrupt(format!("slot {slot} has no layers")));
+            }
+            let mut vector = Vec::with_capacity(dimensions);
+            for _ in 0..dimensions {
+                vector.push(f32::from_le_bytes(read_array(reader)?));
+            }
+            let mut links = Vec::with_capacity(layers.min(64));
+            for _ in 0..layers {
+                let count = read_len(reader)?;
+                let mut layer = Vec::with_capacity(count.min(1 << 12));
+                for _ in 0..count {
+                    let target = read_u32(reader)?;
+                    if target as usize >= slots {
+                        return Err(AnnError::Corrupt(format!(
+                            "slot {slot} links to missing slot {target}"
+                        )));
+                    }
+                    layer.push(target);
+                }
+                links.push(layer);
+            }
+
+            if keys.insert(key.clone(), slot as u32).is_some() {
+                return Err(AnnError::Corrupt(format!("duplicate key {key}")));
+            }
+            index.nodes.push(Some(Node { key, vector, links }));
+        }
+
+        for (slot, node) in index.nodes.iter().enumerate() {
+            let Some(node) = node else { continue };
+            for (layer, links) in node.links.iter().enumerate() {
+                let dangling = links.iter().find(|&&target| {
+                    index.nodes[target as usize]
+                        .as_ref()
+                        .is_none_or(|n| n.links.len() <= layer)
+                });
+                if let Some(target) = dangling {
+                    return Err(AnnError::Corrupt(format!(
+                        "slot {slot} links to slot {target} absent from layer {layer}"
+                    )));
+                }
+            }
+        }
+
+        index.entry = match entry {
+            NO_ENTRY => None,
+            slot if (slot as usize) < slots && index.nodes[slot as usize].is_some() => {
+                Some(slot as u32)
+            }
+            slot => return Err(AnnError::Corrupt(format!("invalid entry point {slot}"))),
+        };
+        if index.entry.is_none() != keys.is_empty() {
+            return Err(AnnError::Corrupt(
+                "entry point does not match nodes".to_string(),
+            ));
+        }
+        index.keys = keys;
+        Ok(index)
+    }
+}
+
+fn metric_tag(metric: AnnMetric) -> u8 {
+    match metric {
+        AnnMetric::Cosine => 0,
+        AnnMetric::Euclidean => 1,
+        AnnMetric::DotProduct => 2,
+    }
+}
+
+fn write_u64(writer: &mut impl Write, value: u64) -> AnnResult<()> {
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> AnnResult<[u8; N]> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u8(reader: &mut impl Read) -> AnnResult<u8> {
+    Ok(read_array::<1>(reader)?[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> AnnResult<u32> {
+    Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn read_u64(reader: &mut impl Read) -> AnnResult<u64> {
+    Ok(u64::from_le_bytes(read_array(reader)?))
+}
+
+fn read_len(reader: &mut impl Read) -> AnnResult<usize> {
+    let value = read_u64(reader)?;
+    usize::try_from(value).map_err(|_| AnnError::Corrupt(format!("length {value} too large")))
+}
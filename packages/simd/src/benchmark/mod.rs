@@ -1,4 +1,15 @@
 //! Benchmarking utilities for SIMD operations
+//!
+//! With the `bench` feature, [`suite`] measures the similarity, softmax and
+//! logits kernels across input sizes and CPU feature levels; `paraphym-simd
+//! bench --json` prints the resulting [`SuiteReport`], which
+//! [`find_regressions`] compares against a saved baseline.
+
+pub mod report;
+#[cfg(feature = "bench")]
+pub mod suite;
+
+pub use report::{CaseResult, Regression, SuiteReport, find_regressions};
 
 use std::io::Write;
 use std::time::{Duration, Instant};
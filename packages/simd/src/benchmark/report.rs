@@ -0,0 +1,157 @@
+//! Machine-readable benchmark results and regression checks
+//!
+//! A [`SuiteReport`] is what `paraphym-simd bench --json` prints. Saving the
+//! report of one release and passing it back as `--baseline` for the next
+//! flags every case whose median time grew by more than the threshold.
+//! Reports are only comparable when taken on the same machine.
+
+use serde::{Deserialize, Serialize};
+
+/// Timing of one kernel variant at one input size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseResult {
+    /// Kernel family: `similarity`, `softmax` or `logits`
+    pub kernel: String,
+    /// Implementation measured, such as `avx2`, `scalar` or `dispatch`
+    pub variant: String,
+    /// Input length in elements
+    pub size: usize,
+    /// Number of timed samples
+    pub samples: usize,
+    /// Calls per sample
+    pub iterations: u64,
+    /// Median time per call in nanoseconds
+    pub median_ns: f64,
+    /// Mean time per call in nanoseconds
+    pub mean_ns: f64,
+    /// Fastest sample's time per call in nanoseconds
+    pub min_ns: f64,
+    /// Input elements processed per second, from the median
+    pub elements_per_sec: f64,
+}
+
+impl CaseResult {
+    /// Stable identifier used to match cases across reports
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!("{}/{}/{}", self.kernel, self.variant, self.size)
+    }
+}
+
+/// Results of a whole suite run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuiteReport {
+    /// Crate version that produced the report
+    pub version: String,
+    /// Target architecture, such as `x86_64`
+    pub arch: String,
+    /// Best CPU feature level detected at runtime
+    pub cpu_features: String,
+    /// One entry per measured case
+    pub results: Vec<CaseResult>,
+}
+
+impl SuiteReport {
+    /// Result with the given [`CaseResult::id`]
+    #[must_use]
+    pub fn case(&self, id: &str) -> Option<&CaseResult> {
+        self.results.iter().find(|result| result.id() == id)
+    }
+}
+
+/// A case that got slower than its baseline allows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Regression {
+    /// [`CaseResult::id`] of the case
+    pub id: String,
+    /// Median time per call in the baseline
+    pub baseline_ns: f64,
+    /// Median time per call now
+    pub current_ns: f64,
+    /// Relative slowdown, 0.25 meaning 25% slower
+    pub slowdown: f64,
+}
+
+/// Cases of `current` whose median is more than `threshold` slower than in `baseline`
+///
+/// `threshold` is relative, so 0.1 tolerates up to 10% noise. Cases missing
+/// from either report are ignored. The slowest regressions come first.
+#[must_use]
+pub fn find_regressions(
+    baseline: &SuiteReport,
+    current: &SuiteReport,
+    threshold: f64,
+) -> Vec<Regression> {
+    let mut regressions: Vec<Regression> = current
+        .results
+        .iter()
+        .filter_map(|result| {
+            let id = result.id();
+            let before = baseline.case(&id)?;
+            if before.median_ns <= 0.0 {
+                return None;
+            }
+            let slowdown = result.median_ns / before.median_ns - 1.0;
+            (slowdown > threshold).then(|| Regression {
+                id,
+                baseline_ns: before.median_ns,
+                current_ns: result.median_ns,
+                slowdown,
+            })
+        })
+        .collect();
+
+    regressions.sort_by(|a, b| b.slowdown.total_cmp(&a.slowdown));
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(variant: &str, median_ns: f64) -> CaseResult {
+        CaseResult {
+            kernel: "softmax".to_string(),
+            variant: variant.to_string(),
+            size: 1024,
+            samples: 10,
+            iterations: 100,
+            median_ns,
+            mean_ns: median_ns,
+            min_ns: median_ns,
+            elements_per_sec: 1024.0 / median_ns * 1e9,
+        }
+    }
+
+    fn report(results: Vec<CaseResult>) -> SuiteReport {
+        SuiteReport {
+            version: crate::VERSION.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpu_features: "Avx2".to_string(),
+            results,
+        }
+    }
+
+    #[test]
+    fn test_regressions_above_threshold() {
+        let baseline = report(vec![case("scalar", 100.0), case("avx2", 100.0)]);
+        let current = report(vec![
+            case("scalar", 105.0),
+            case("avx2", 150.0),
+            case("avx512", 10.0),
+        ]);
+
+        let regressions = find_regressions(&baseline, &current, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].id, "softmax/avx2/1024");
+        assert!((regressions[0].slowdown - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_report_json_round_trip() {
+        let original = report(vec![case("dispatch", 42.0)]);
+        let json = serde_json::to_string(&original).expect("serialize report");
+        let parsed: SuiteReport = serde_json::from_str(&json).expect("parse report");
+        assert_eq!(parsed, original);
+    }
+}
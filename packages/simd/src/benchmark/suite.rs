@@ -0,0 +1,440 @@
+//! Benchmark suite over the similarity, softmax and logits kernels
+//!
+//! [`cases`] builds one [`BenchCase`] per kernel variant, input size and CPU
+//! feature level the machine supports. The `paraphym-simd bench` command
+//! times them with [`measure`], and the `suite` criterion bench runs the same
+//! cases under `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use super::report::{CaseResult, SuiteReport};
+use crate::config::ProcessorConfig;
+use crate::context::ProcessingContext;
+use crate::logits::process_logits_fused;
+use crate::logits::processor::DefaultLogitsProcessor;
+use crate::ops::softmax::SOFTMAX_DISPATCH;
+use crate::runtime::{CpuFeatures, get_cpu_features};
+use crate::similarity::{cosine_similarity, for_features};
+
+/// Feature levels tried for every kernel; unsupported ones are skipped
+const FEATURE_LEVELS: [CpuFeatures; 6] = [
+    CpuFeatures::Scalar,
+    CpuFeatures::Sse41,
+    CpuFeatures::Avx2,
+    CpuFeatures::Avx512,
+    CpuFeatures::Neon,
+    CpuFeatures::Sve,
+];
+
+/// Kernel families covered by the suite
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    /// Cosine similarity of two embeddings
+    Similarity,
+    /// Softmax over a logits vector
+    Softmax,
+    /// Full logits pipeline: penalties, temperature, top-k and top-p
+    Logits,
+}
+
+impl Kernel {
+    /// Every kernel family
+    pub const ALL: [Kernel; 3] = [Kernel::Similarity, Kernel::Softmax, Kernel::Logits];
+
+    /// Name used in case ids and on the command line
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Similarity => "similarity",
+            Self::Softmax => "softmax",
+            Self::Logits => "logits",
+        }
+    }
+
+    /// Parse a kernel name
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kernel| kernel.name() == name)
+    }
+
+    /// Input sizes measured when none are configured
+    ///
+    /// Common embedding dimensions for similarity, and vocabulary sizes of
+    /// current models for the logits pipeline.
+    #[must_use]
+    pub const fn default_sizes(self) -> &'static [usize] {
+        match self {
+            Self::Similarity => &[128, 384, 768, 1536],
+            Self::Softmax => &[256, 4096, 65_536],
+            Self::Logits => &[1024, 32_000, 128_256],
+        }
+    }
+}
+
+/// What the suite measures and for how long
+#[derive(Debug, Clone)]
+pub struct SuiteConfig {
+    /// Kernel families to run
+    pub kernels: Vec<Kernel>,
+    /// Input sizes for every kernel, or each kernel's defaults
+    pub sizes: Option<Vec<usize>>,
+    /// Only run cases whose id contains this text
+    pub filter: Option<String>,
+    /// Timed samples per case
+    pub samples: usize,
+    /// Minimum duration of one sample
+    pub sample_time: Duration,
+}
+
+impl Default for SuiteConfig {
+    fn default() -> Self {
+        Self {
+            kernels: Kernel::ALL.to_vec(),
+            sizes: None,
+            filter: None,
+            samples: 15,
+            sample_time: Duration::from_millis(10),
+        }
+    }
+}
+
+impl SuiteConfig {
+    /// Run only these kernel families
+    #[must_use]
+    pub fn with_kernels(mut self, kernels: Vec<Kernel>) -> Self {
+        self.kernels = kernels;
+        self
+    }
+
+    /// Measure these input sizes for every kernel
+    #[must_use]
+    pub fn with_sizes(mut self, sizes: Vec<usize>) -> Self {
+        self.sizes = Some(sizes);
+        self
+    }
+
+    /// Only run cases whose id contains `filter`
+    #[must_use]
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Set the number of timed samples per case, at least 1
+    #[must_use]
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Set the minimum duration of one sample
+    #[must_use]
+    pub fn with_sample_time(mut self, sample_time: Duration) -> Self {
+        self.sample_time = sample_time;
+        self
+    }
+
+    fn sizes_for(&self, kernel: Kernel) -> Vec<usize> {
+        self.sizes
+            .clone()
+            .unwrap_or_else(|| kernel.default_sizes().to_vec())
+    }
+}
+
+/// One kernel variant at one input size, with its input data prepared
+pub struct BenchCase {
+    /// Kernel family
+    pub kernel: Kernel,
+    /// Implementation measured, such as `avx2` or `dispatch`
+    pub variant: String,
+    /// Input length in elements
+    pub size: usize,
+    run: Box<dyn FnMut()>,
+}
+
+impl BenchCase {
+    fn new(
+        kernel: Kernel,
+        variant: impl Into<String>,
+        size: usize,
+        run: impl FnMut() + 'static,
+    ) -> Self {
+        Self {
+            kernel,
+            variant: variant.into(),
+            size,
+            run: Box::new(run),
+        }
+    }
+
+    /// Identifier matching [`CaseResult::id`]
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!("{}/{}/{}", self.kernel.name(), self.variant, self.size)
+    }
+
+    /// Call the kernel once
+    pub fn run(&mut self) {
+        (self.run)();
+    }
+}
+
+impl std::fmt::Debug for BenchCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BenchCase")
+            .field("kernel", &self.kernel)
+            .field("variant", &self.variant)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Every case selected by `config` that this CPU can run
+#[must_use]
+pub fn cases(config: &SuiteConfig) -> Vec<BenchCase> {
+    let mut cases = Vec::new();
+    for &kernel in &config.kernels {
+        for size in config.sizes_for(kernel) {
+            match kernel {
+                Kernel::Similarity => similarity_cases(size, &mut cases),
+                Kernel::Softmax => softmax_cases(size, &mut cases),
+                Kernel::Logits => logits_cases(size, &mut cases),
+            }
+        }
+    }
+
+    if let Some(filter) = &config.filter {
+        cases.retain(|case| case.id().contains(filter.as_str()));
+    }
+    cases
+}
+
+/// Time a case
+///
+/// Calls per sample are doubled until one sample lasts `sample_time`, which
+/// also warms the caches, then `samples` samples are timed.
+pub fn measure(case: &mut BenchCase, config: &SuiteConfig) -> CaseResult {
+    let mut iterations: u64 = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            case.run();
+        }
+        if start.elapsed() >= config.sample_time || iterations >= 1 << 30 {
+            break;
+        }
+        iterations *= 2;
+    }
+
+    let samples = config.samples.max(1);
+    let mut per_call: Vec<f64> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                case.run();
+            }
+            start.elapsed().as_nanos() as f64 / iterations as f64
+        })
+        .collect();
+    per_call.sort_by(f64::total_cmp);
+
+    let middle = samples / 2;
+    let median_ns = if samples % 2 == 1 {
+        per_call[middle]
+    } else {
+        (per_call[middle - 1] + per_call[middle]) / 2.0
+    };
+    let mean_ns = per_call.iter().sum::<f64>() / samples as f64;
+
+    CaseResult {
+        kernel: case.kernel.name().to_string(),
+        variant: case.variant.clone(),
+        size: case.size,
+        samples,
+        iterations,
+        median_ns,
+        mean_ns,
+        min_ns: per_call[0],
+        elements_per_sec: if median_ns > 0.0 {
+            case.size as f64 * 1e9 / median_ns
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Build and time every case selected by `config`
+#[must_use]
+pub fn run_suite(config: &SuiteConfig) -> SuiteReport {
+    let results = cases(config)
+        .iter_mut()
+        .map(|case| measure(case, config))
+        .collect();
+    new_report(results)
+}
+
+/// Report of `results` for this crate version and machine
+#[must_use]
+pub fn new_report(results: Vec<CaseResult>) -> SuiteReport {
+    SuiteReport {
+        version: crate::VERSION.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_features: format!("{:?}", get_cpu_features()),
+        results,
+    }
+}
+
+fn random_values(size: usize, range: f32) -> Vec<f32> {
+    let mut rng = rand::rng();
+    (0..size).map(|_| rng.random_range(-range..range)).collect()
+}
+
+fn similarity_cases(size: usize, cases: &mut Vec<BenchCase>) {
+    let a = random_values(size, 1.0);
+    let b = random_values(size, 1.0);
+
+    for feature in FEATURE_LEVELS {
+        let Some(implementation) = for_features(feature) else {
+            continue;
+        };
+        let (a, b) = (a.clone(), b.clone());
+        cases.push(BenchCase::new(
+            Kernel::Similarity,
+            implementation.name(),
+            size,
+            move || {
+                black_box(implementation.cosine_similarity(black_box(&a), black_box(&b)));
+            },
+        ));
+    }
+
+    cases.push(BenchCase::new(
+        Kernel::Similarity,
+        "dispatch",
+        size,
+        move || {
+            black_box(cosine_similarity(black_box(&a), black_box(&b)));
+        },
+    ));
+}
+
+fn softmax_cases(size: usize, cases: &mut Vec<BenchCase>) {
+    let logits = random_values(size, 10.0);
+
+    for feature in FEATURE_LEVELS {
+        if SOFTMAX_DISPATCH.call_with_feature(&[1.0], feature).is_err() {
+            continue;
+        }
+        let logits = logits.clone();
+        cases.push(BenchCase::new(
+            Kernel::Softmax,
+            feature_name(feature),
+            size,
+            move || {
+                let _ = black_box(SOFTMAX_DISPATCH.call_with_feature(black_box(&logits), feature));
+            },
+        ));
+    }
+
+    cases.push(BenchCase::new(
+        Kernel::Softmax,
+        "dispatch",
+        size,
+        move || {
+            let _ = black_box(SOFTMAX_DISPATCH.call(black_box(&logits)));
+        },
+    ));
+}
+
+fn logits_cases(size: usize, cases: &mut Vec<BenchCase>) {
+    let logits = random_values(size, 10.0);
+    let mut rng = rand::rng();
+    let history: Vec<u32> = (0..256)
+        .map(|_| rng.random_range(0..size.max(1) as u32))
+        .collect();
+    let config = ProcessorConfig::default()
+        .with_repetition_penalty(1.1)
+        .with_frequency_penalty(0.2)
+        .with_presence_penalty(0.1);
+    let context = ProcessingContext::new()
+        .with_temperature(0.7)
+        .with_top_k(Some(50))
+        .with_top_p(Some(0.9))
+        .with_token_history(history);
+
+    {
+        let (logits, context, config) = (logits.clone(), context.clone(), config.clone());
+        let mut scratch = logits.clone();
+        cases.push(BenchCase::new(Kernel::Logits, "fused", size, move || {
+            scratch.copy_from_slice(&logits);
+            let _ = black_box(process_logits_fused(
+                black_box(&mut scratch),
+                &context,
+                &config,
+            ));
+        }));
+    }
+
+    let mut processor = DefaultLogitsProcessor::with_config(config);
+    let mut scratch = logits.clone();
+    cases.push(BenchCase::new(Kernel::Logits, "staged", size, move || {
+        scratch.copy_from_slice(&logits);
+        let _ = black_box(processor.process(black_box(&mut scratch), &context));
+    }));
+}
+
+fn feature_name(feature: CpuFeatures) -> &'static str {
+    match feature {
+        CpuFeatures::Scalar => "scalar",
+        CpuFeatures::Neon => "neon",
+        CpuFeatures::Sse41 => "sse41",
+        CpuFeatures::Avx2 => "avx2",
+        CpuFeatures::Avx512 => "avx512",
+        CpuFeatures::Sve => "sve",
+        CpuFeatures::Avx512Vnni => "avx512_vnni",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases_follow_config() {
+        let config = SuiteConfig::default()
+            .with_kernels(vec![Kernel::Softmax, Kernel::Similarity])
+            .with_sizes(vec![64]);
+        let cases = cases(&config);
+
+        assert!(cases.iter().any(|case| case.id() == "softmax/scalar/64"));
+        assert!(
+            cases
+                .iter()
+                .any(|case| case.id() == "similarity/dispatch/64")
+        );
+        assert!(cases.iter().all(|case| case.kernel != Kernel::Logits));
+
+        let filtered = super::cases(&config.with_filter("softmax/dispatch"));
+        assert_eq!(filtered.len(), 1);
+    }
+
+    #[test]
+    fn test_run_suite_reports_every_case() {
+        let config = SuiteConfig::default()
+            .with_kernels(vec![Kernel::Logits])
+            .with_sizes(vec![256])
+            .with_samples(3)
+            .with_sample_time(Duration::from_micros(100));
+        let report = run_suite(&config);
+
+        assert_eq!(report.results.len(), 2);
+        for result in &report.results {
+            assert_eq!(result.samples, 3);
+            assert!(result.median_ns > 0.0);
+            assert!(result.min_ns <= result.median_ns);
+        }
+    }
+}
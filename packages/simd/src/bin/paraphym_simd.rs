@@ -0,0 +1,245 @@
+//! `paraphym-simd` - benchmark the SIMD kernels and track regressions
+//!
+//! ```text
+//! paraphym-simd bench --json > baseline.json
+//! paraphym-simd bench --baseline baseline.json --threshold 10
+//! ```
+
+use std::io::Write;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use cyrup_simd::benchmark::suite::{self, Kernel, SuiteConfig};
+use cyrup_simd::benchmark::{SuiteReport, find_regressions};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+const USAGE: &str = "\
+Usage: paraphym-simd bench [OPTIONS]
+
+Times the similarity, softmax and logits kernels across input sizes and the
+CPU feature levels this machine supports.
+
+Options:
+  --json               Print the report as JSON instead of a table
+  --kernel <NAME>      similarity, softmax or logits; repeatable (default: all)
+  --sizes <N,N,...>    Input sizes for every kernel (default: per kernel)
+  --filter <TEXT>      Only run cases whose id contains TEXT
+  --samples <N>        Timed samples per case (default: 15)
+  --sample-ms <MS>     Minimum duration of one sample (default: 10)
+  --output <FILE>      Also write the JSON report to FILE
+  --baseline <FILE>    Compare against a saved JSON report; exit 1 on regressions
+  --threshold <PCT>    Allowed median slowdown in percent (default: 10)
+  -h, --help           Print this help";
+
+/// Parsed `bench` options
+struct BenchArgs {
+    config: SuiteConfig,
+    json: bool,
+    output: Option<String>,
+    baseline: Option<String>,
+    threshold: f64,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("bench") => {}
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    let bench = match parse_bench_args(&args[1..]) {
+        Ok(Some(bench)) => bench,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run_bench(&bench) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Parse the options after `bench`; `None` when help was asked for
+fn parse_bench_args(args: &[String]) -> Result<Option<BenchArgs>, String> {
+    let mut config = SuiteConfig::default();
+    let mut kernels = Vec::new();
+    let mut bench = BenchArgs {
+        config: SuiteConfig::default(),
+        json: false,
+        output: None,
+        baseline: None,
+        threshold: 0.1,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{arg} needs a value"))
+        };
+        match arg.as_str() {
+            "--json" => bench.json = true,
+            "--kernel" => {
+                let name = value()?;
+                kernels.push(Kernel::parse(&name).ok_or_else(|| format!("unknown kernel {name}"))?);
+            }
+            "--sizes" => {
+                let sizes = value()?
+                    .split(',')
+                    .map(|size| size.trim().parse::<usize>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("invalid --sizes: {e}"))?;
+                config = config.with_sizes(sizes);
+            }
+            "--filter" => config = config.with_filter(value()?),
+            "--samples" => {
+                let samples = value()?
+                    .parse()
+                    .map_err(|e| format!("invalid --samples: {e}"))?;
+                config = config.with_samples(samples);
+            }
+            "--sample-ms" => {
+                let millis = value()?
+                    .parse()
+                    .map_err(|e| format!("invalid --sample-ms: {e}"))?;
+                config = config.with_sample_time(Duration::from_millis(millis));
+            }
+            "--output" => bench.output = Some(value()?),
+            "--baseline" => bench.baseline = Some(value()?),
+            "--threshold" => {
+                let percent: f64 = value()?
+                    .parse()
+                    .map_err(|e| format!("invalid --threshold: {e}"))?;
+                bench.threshold = percent / 100.0;
+            }
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unknown option {other}")),
+        }
+    }
+
+    if !kernels.is_empty() {
+        config = config.with_kernels(kernels);
+    }
+    bench.config = config;
+    Ok(Some(bench))
+}
+
+/// Run the suite and report; `false` when the baseline shows regressions
+fn run_bench(bench: &BenchArgs) -> Result<bool, String> {
+    // Read the baseline first so a bad path fails before the long run
+    let baseline = bench
+        .baseline
+        .as_deref()
+        .map(|path| {
+            let json = std::fs::read_to_string(path)
+                .map_err(|e| format!("cannot read baseline {path}: {e}"))?;
+            serde_json::from_str::<SuiteReport>(&json)
+                .map_err(|e| format!("invalid baseline {path}: {e}"))
+        })
+        .transpose()?;
+
+    let mut cases = suite::cases(&bench.config);
+    let total = cases.len();
+    let mut results = Vec::with_capacity(total);
+    for (index, case) in cases.iter_mut().enumerate() {
+        eprintln!("[{}/{}] {}", index + 1, total, case.id());
+        results.push(suite::measure(case, &bench.config));
+    }
+    let report = suite::new_report(results);
+
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    if let Some(path) = &bench.output {
+        std::fs::write(path, &json).map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+    if bench.json {
+        println!("{json}");
+    } else {
+        print_report(&report).map_err(|e| e.to_string())?;
+    }
+
+    let Some(baseline) = baseline else {
+        return Ok(true);
+    };
+    if baseline.arch != report.arch || baseline.cpu_features != report.cpu_features {
+        eprintln!(
+            "warning: baseline was taken on {} ({}), this run on {} ({})",
+            baseline.arch, baseline.cpu_features, report.arch, report.cpu_features
+        );
+    }
+
+    let regressions = find_regressions(&baseline, &report, bench.threshold);
+    if regressions.is_empty() {
+        eprintln!(
+            "No regressions beyond {:.1}% against version {}",
+            bench.threshold * 100.0,
+            baseline.version
+        );
+        return Ok(true);
+    }
+    for regression in &regressions {
+        eprintln!(
+            "REGRESSION {}: {:.1} ns -> {:.1} ns (+{:.1}%)",
+            regression.id,
+            regression.baseline_ns,
+            regression.current_ns,
+            regression.slowdown * 100.0
+        );
+    }
+    Ok(false)
+}
+
+/// Print the report as a table
+fn print_report(report: &SuiteReport) -> std::io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
+    writeln!(
+        &mut stdout,
+        "\n=== cyrup_simd {} on {} ({}) ===",
+        report.version, report.arch, report.cpu_features
+    )?;
+    stdout.reset()?;
+
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Blue)).set_bold(true))?;
+    writeln!(
+        &mut stdout,
+        "{:<36} | {:>12} | {:>12} | {:>14}",
+        "Case", "Median (ns)", "Min (ns)", "Elements/s"
+    )?;
+    stdout.reset()?;
+    writeln!(&mut stdout, "{:-<84}", "")?;
+
+    for result in &report.results {
+        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+        writeln!(
+            &mut stdout,
+            "{:<36} | {:>12.1} | {:>12.1} | {:>14.3e}",
+            result.id(),
+            result.median_ns,
+            result.min_ns,
+            result.elements_per_sec
+        )?;
+    }
+    stdout.reset()?;
+    writeln!(&mut stdout)?;
+
+    Ok(())
+}
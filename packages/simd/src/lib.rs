@@ -9,7 +9,8 @@
 //! - **Vectorized Similarity**: Parallel cosine similarity with runtime CPU feature detection
 //! - **Zero Allocation**: Pre-allocated buffers and stack-based temporary storage
 //! - **Adaptive Selection**: Automatic SIMD vs scalar selection based on vector size
-//! - **Platform Support**: `x86_64` `AVX2`, `ARM64` `NEON` with portable fallbacks
+//! - **Platform Support**: `x86_64` `AVX2`/`AVX-512`/`AVX-512 VNNI`, `ARM64` `NEON`/`SVE` with
+//!   portable fallbacks
 //!
 //! ## Usage Examples
 //!
@@ -27,6 +28,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 // Public modules
+pub mod ann;
 pub mod benchmark;
 pub mod config;
 pub mod constants;
@@ -46,7 +48,9 @@ pub mod utils;
 pub use constants::{SIMD_WIDTH_8, VERSION};
 pub use error::{SimdError, SimdResult};
 // Re-export logits operations
-pub use logits::{apply_penalties_simd, prepare_nucleus_sampling_simd, topk_filtering_simd};
+pub use logits::{
+    apply_penalties_simd, prepare_nucleus_sampling_simd, process_logits_fused, topk_filtering_simd,
+};
 // Re-export ops (temperature and softmax operations)
 pub use ops::{argmax, scale_temperature, softmax};
 // Re-export runtime CPU detection
@@ -0,0 +1,410 @@
+//! GBNF grammar to regex conversion
+//!
+//! Compiles llama.cpp-style GBNF grammars into regular expressions so they can
+//! drive the same DFA-backed [`SchemaConstraint`](super::SchemaConstraint) as
+//! JSON schemas. Rule references are inlined, which limits grammars to regular
+//! languages: recursive rules are rejected with an error.
+//!
+//! Supported syntax:
+//! - rules: `name ::= body`, with `root` as the start rule
+//! - literals `"..."`, character classes `[a-z]` / `[^"]`, and `.`
+//! - grouping `( ... )`, alternation `|`, repetition `*` `+` `?` `{m}` `{m,}` `{m,n}`
+//! - `#` comments
+//! - escapes `\n` `\r` `\t` `\\` `\"` `\[` `\]` `\-` `\xHH` `\uHHHH` `\UHHHHHHHH`
+
+use anyhow::{Result as AnyResult, anyhow, bail};
+use regex::escape;
+use std::collections::HashMap;
+
+/// Name of the start rule
+const ROOT_RULE: &str = "root";
+
+/// Parsed grammar expression
+#[derive(Debug, Clone)]
+enum Node {
+    /// Literal text
+    Literal(String),
+    /// Character class, already in regex syntax
+    Class(String),
+    /// Any single character
+    Any,
+    /// Reference to another rule
+    Rule(String),
+    /// Items matched in order
+    Sequence(Vec<Node>),
+    /// One of several alternatives
+    Alternatives(Vec<Node>),
+    /// Bounded or unbounded repetition
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+/// Convert a GBNF grammar into a regex pattern matching its `root` rule
+///
+/// # Arguments
+/// * `grammar` - GBNF grammar source
+///
+/// # Returns
+/// * `Ok(String)` - Regex pattern equivalent to the grammar
+/// * `Err(anyhow::Error)` - On syntax errors, undefined or recursive rules,
+///   or a missing `root` rule
+///
+/// # Example
+/// ```rust
+/// use cyrup_simd::logits::constraints::regex_from_gbnf;
+///
+/// let regex = regex_from_gbnf(r#"root ::= "yes" | "no""#)?;
+/// assert_eq!(regex, "(?:yes|no)");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn regex_from_gbnf(grammar: &str) -> AnyResult<String> {
+    let rules = GbnfParser::new(grammar).parse()?;
+    if !rules.contains_key(ROOT_RULE) {
+        bail!("GBNF grammar has no '{ROOT_RULE}' rule");
+    }
+
+    let mut compiler = Compiler {
+        rules: &rules,
+        stack: Vec::new(),
+        compiled: HashMap::new(),
+    };
+    compiler.rule(ROOT_RULE)
+}
+
+/// Recursive-descent parser for GBNF source
+struct GbnfParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> GbnfParser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn parse(mut self) -> AnyResult<HashMap<String, Node>> {
+        let mut rules = HashMap::new();
+        loop {
+            self.skip_space();
+            if self.peek().is_none() {
+                return Ok(rules);
+            }
+            let name = self.identifier()?;
+            self.skip_space();
+            if !self.src[self.pos..].starts_with("::=") {
+                bail!("Expected '::=' after rule '{name}' at offset {}", self.pos);
+            }
+            self.pos += 3;
+            let body = self.alternatives()?;
+            if rules.insert(name.clone(), body).is_some() {
+                bail!("GBNF rule '{name}' is defined more than once");
+            }
+        }
+    }
+
+    fn alternatives(&mut self) -> AnyResult<Node> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.remove(0)
+        } else {
+            Node::Alternatives(alternatives)
+        })
+    }
+
+    fn sequence(&mut self) -> AnyResult<Node> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_space();
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                Some(_) if self.at_rule_start() => break,
+                Some(_) => items.push(self.item()?),
+            }
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Node::Sequence(items)
+        })
+    }
+
+    fn item(&mut self) -> AnyResult<Node> {
+        let mut node = self.primary()?;
+        loop {
+            self.skip_space();
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => {
+                    self.pos += 1;
+                    let (min, max) = self.bounds()?;
+                    node = Node::Repeat {
+                        node: Box::new(node),
+                        min,
+                        max,
+                    };
+                    continue;
+                }
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max,
+            };
+        }
+    }
+
+    fn primary(&mut self) -> AnyResult<Node> {
+        match self.next_char() {
+            Some('"') => self.literal(),
+            Some('[') => self.class(),
+            Some('.') => Ok(Node::Any),
+            Some('(') => {
+                let node = self.alternatives()?;
+                self.skip_space();
+                if self.next_char() != Some(')') {
+                    bail!("Unclosed group at offset {}", self.pos);
+                }
+                Ok(node)
+            }
+            Some(c) if is_word_char(c) => {
+                self.pos -= c.len_utf8();
+                Ok(Node::Rule(self.identifier()?))
+            }
+            Some(c) => bail!("Unexpected '{c}' at offset {}", self.pos - c.len_utf8()),
+            None => bail!("Unexpected end of grammar"),
+        }
+    }
+
+    fn literal(&mut self) -> AnyResult<Node> {
+        let mut text = String::new();
+        loop {
+            match self.next_char() {
+                Some('"') => return Ok(Node::Literal(text)),
+                Some('\\') => text.push(self.escape()?),
+                Some(c) => text.push(c),
+                None => bail!("Unterminated string literal"),
+            }
+        }
+    }
+
+    fn class(&mut self) -> AnyResult<Node> {
+        let mut regex = String::from("[");
+        if self.peek() == Some('^') {
+            self.pos += 1;
+            regex.push('^');
+        }
+        loop {
+            let start = match self.next_char() {
+                Some(']') => break,
+                Some('\\') => self.escape()?,
+                Some(c) => c,
+                None => bail!("Unterminated character class"),
+            };
+            push_class_char(&mut regex, start);
+
+            let rest = &self.src[self.pos..];
+            if rest.starts_with('-') && !rest[1..].starts_with(']') {
+                self.pos += 1;
+                let end = match self.next_char() {
+                    Some('\\') => self.escape()?,
+                    Some(c) => c,
+                    None => bail!("Unterminated character class"),
+                };
+                if end < start {
+                    bail!("Invalid character range '{start}-{end}'");
+                }
+                regex.push('-');
+                push_class_char(&mut regex, end);
+            }
+        }
+        regex.push(']');
+        Ok(Node::Class(regex))
+    }
+
+    fn escape(&mut self) -> AnyResult<char> {
+        let c = self
+            .next_char()
+            .ok_or_else(|| anyhow!("Unterminated escape sequence"))?;
+        let digits = match c {
+            'n' => return Ok('\n'),
+            'r' => return Ok('\r'),
+            't' => return Ok('\t'),
+            'x' => 2,
+            'u' => 4,
+            'U' => 8,
+            _ => return Ok(c),
+        };
+        let end = self.pos + digits;
+        let hex = self
+            .src
+            .get(self.pos..end)
+            .ok_or_else(|| anyhow!("Truncated '\\{c}' escape"))?;
+        let code = u32::from_str_radix(hex, 16)
+            .map_err(|_| anyhow!("Invalid hex digits '{hex}' in escape"))?;
+        self.pos = end;
+        char::from_u32(code).ok_or_else(|| anyhow!("Invalid code point U+{code:X}"))
+    }
+
+    fn bounds(&mut self) -> AnyResult<(usize, Option<usize>)> {
+        let body_end = self.src[self.pos..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unclosed repetition bounds"))?;
+        let body = &self.src[self.pos..self.pos + body_end];
+        self.pos += body_end + 1;
+
+        let parse = |s: &str| {
+            s.trim()
+                .parse::<usize>()
+                .map_err(|_| anyhow!("Invalid repetition bounds '{{{body}}}'"))
+        };
+        let (min, max) = match body.split_once(',') {
+            None => {
+                let n = parse(body)?;
+                (n, Some(n))
+            }
+            Some((min, max)) if max.trim().is_empty() => (parse(min)?, None),
+            Some((min, max)) => (parse(min)?, Some(parse(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            bail!("Invalid repetition bounds '{{{body}}}'");
+        }
+        Ok((min, max))
+    }
+
+    fn identifier(&mut self) -> AnyResult<String> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_word_char) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            bail!("Expected rule name at offset {start}");
+        }
+        Ok(self.src[start..self.pos].to_string())
+    }
+
+    /// Whether the upcoming text is `name ::=`, i.e. the next rule
+    fn at_rule_start(&self) -> bool {
+        let rest = &self.src[self.pos..];
+        let name_len = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+        name_len > 0 && rest[name_len..].trim_start().starts_with("::=")
+    }
+
+    /// Skip whitespace, newlines and comments
+    fn skip_space(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                self.pos += self.src[self.pos..]
+                    .find('\n')
+                    .unwrap_or(self.src.len() - self.pos);
+            } else if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+}
+
+/// Inlines rule references into a single regex
+struct Compiler<'a> {
+    rules: &'a HashMap<String, Node>,
+    /// Rules being compiled, for recursion detection
+    stack: Vec<&'a str>,
+    /// Finished rules
+    compiled: HashMap<&'a str, String>,
+}
+
+impl<'a> Compiler<'a> {
+    fn rule(&mut self, name: &'a str) -> AnyResult<String> {
+        if let Some(regex) = self.compiled.get(name) {
+            return Ok(regex.clone());
+        }
+        if self.stack.contains(&name) {
+            bail!(
+                "GBNF rule '{name}' is recursive ({} -> {name}); only non-recursive grammars are supported",
+                self.stack.join(" -> ")
+            );
+        }
+        let (key, node) = self
+            .rules
+            .get_key_value(name)
+            .ok_or_else(|| anyhow!("GBNF rule '{name}' is not defined"))?;
+
+        self.stack.push(key);
+        let regex = self.node(node)?;
+        self.stack.pop();
+        self.compiled.insert(key, regex.clone());
+        Ok(regex)
+    }
+
+    fn node(&mut self, node: &'a Node) -> AnyResult<String> {
+        Ok(match node {
+            Node::Literal(text) => escape(text),
+            Node::Class(class) => class.clone(),
+            Node::Any => "(?s:.)".to_string(),
+            Node::Rule(name) => format!("(?:{})", self.rule(name)?),
+            Node::Sequence(items) => items
+                .iter()
+                .map(|item| self.node(item))
+                .collect::<AnyResult<String>>()?,
+            Node::Alternatives(alternatives) => format!(
+                "(?:{})",
+                alternatives
+                    .iter()
+                    .map(|alternative| self.node(alternative))
+                    .collect::<AnyResult<Vec<_>>>()?
+                    .join("|")
+            ),
+            Node::Repeat { node, min, max } => {
+                let quantifier = match (min, max) {
+                    (0, None) => "*".to_string(),
+                    (1, None) => "+".to_string(),
+                    (0, Some(1)) => "?".to_string(),
+                    (min, None) => format!("{{{min},}}"),
+                    (min, Some(max)) if min == max => format!("{{{min}}}"),
+                    (min, Some(max)) => format!("{{{min},{max}}}"),
+                };
+                format!("(?:{}){quantifier}", self.node(node)?)
+            }
+        })
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Append a character to a regex class, escaping class metacharacters
+fn push_class_char(regex: &mut String, c: char) {
+    match c {
+        '\\' | ']' | '[' | '^' | '-' | '&' | '~' => {
+            regex.push('\\');
+            regex.push(c);
+        }
+        '\n' => regex.push_str("\\n"),
+        '\r' => regex.push_str("\\r"),
+        '\t' => regex.push_str("\\t"),
+        _ => regex.push(c),
+    }
+}
@@ -7,6 +7,7 @@ use std::fmt::Debug;
 
 use anyhow::Result as AnyResult;
 
+pub mod gbnf;
 pub mod json;
 pub mod schema;
 pub mod schema_index;
@@ -14,6 +15,7 @@ pub mod schema_parser;
 pub mod processor;
 pub mod types;
 
+pub use gbnf::regex_from_gbnf;
 pub use json::JsonConstraint;
 pub use schema::{
     PredefinedSchema, SchemaConstraintBuilder, SchemaFactory, SchemaState, SchemaType, presets
@@ -90,8 +90,9 @@ impl SchemaIndex {
         while let Some(current_state) = state_queue.pop() {
             let current_id = current_state.as_u32();
 
-            // Check if this is a final state
-            if dfa.is_match_state(current_state) {
+            // A state is final when the input may end there; dense DFA matches
+            // are delayed by one byte, so look at the end-of-input transition
+            if dfa.is_match_state(dfa.next_eoi_state(current_state)) {
                 self.final_states.insert(current_id);
             }
 
@@ -137,8 +138,10 @@ impl SchemaIndex {
         current_state: AutomataStateId,
         token_bytes: &[u8],
     ) -> AnyResult<Option<AutomataStateId>> {
+        // Tokens without bytes never advance the output; allowing them would
+        // let constrained generation stall in place
         if token_bytes.is_empty() {
-            return Ok(Some(current_state));
+            return Ok(None);
         }
 
         let mut state = current_state;
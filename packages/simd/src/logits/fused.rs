@@ -0,0 +1,342 @@
+//! Fused logits processing
+//!
+//! [`process_logits_fused`] produces the same logits as
+//! [`DefaultLogitsProcessor`](super::processor::DefaultLogitsProcessor) -
+//! temperature, then penalties, then top-k, then top-p over the top-k
+//! survivors - but in one vectorized sweep over the vocabulary instead of a
+//! pass per stage. Each chunk of eight logits is scaled, the penalties of
+//! tokens in the history are patched in, and the running softmax statistics
+//! and best candidates are updated while the chunk is still in registers.
+//! Filtering then costs a single masking pass, and only when a token is
+//! actually filtered out.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use wide::f32x8;
+
+use crate::config::ProcessorConfig;
+use crate::context::ProcessingContext;
+use crate::logits::{LogitsError, LogitsResult};
+
+const LANES: usize = 8;
+
+/// Candidates tracked for nucleus filtering without top-k
+///
+/// Peaked distributions reach `top_p` well within this many tokens. Flatter
+/// ones fall back to ranking the whole vocabulary.
+const NUCLEUS_CANDIDATES: usize = 256;
+
+/// Shifted logits below this contribute nothing to the softmax sum
+const EXP_FLOOR: f32 = -87.0;
+
+/// A token and its processed logit, ordered by logit then lower index
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    logit: f32,
+    index: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.logit
+            .total_cmp(&other.logit)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Penalties of the tokens in the history, sorted by token
+struct Penalties {
+    /// `(token, occurrences)` of every distinct in-vocabulary token
+    tokens: Vec<(u32, u32)>,
+    repetition: f32,
+    frequency: f32,
+    presence: f32,
+}
+
+impl Penalties {
+    fn new(history: &[u32], vocab_size: usize, config: &ProcessorConfig) -> Self {
+        let active = config.repetition_penalty != 1.0
+            || config.frequency_penalty != 0.0
+            || config.presence_penalty != 0.0;
+
+        let mut tokens = Vec::new();
+        if active {
+            let mut sorted: Vec<u32> = history
+                .iter()
+                .copied()
+                .filter(|&t| (t as usize) < vocab_size)
+                .collect();
+            sorted.sort_unstable();
+            for token in sorted {
+                match tokens.last_mut() {
+                    Some((last, count)) if *last == token => *count += 1,
+                    _ => tokens.push((token, 1)),
+                }
+            }
+        }
+
+        Self {
+            tokens,
+            repetition: config.repetition_penalty,
+            frequency: config.frequency_penalty,
+            presence: config.presence_penalty,
+        }
+    }
+
+    /// Apply the penalties of tokens in `base..base + len` to `values`
+    ///
+    /// `cursor` is the next entry of `tokens` to apply; chunks must be
+    /// visited in order.
+    #[inline]
+    fn apply(&self, values: &mut [f32; LANES], base: usize, len: usize, cursor: &mut usize) {
+        while let Some(&(token, count)) = self.tokens.get(*cursor) {
+            let lane = token as usize - base;
+            if lane >= len {
+                return;
+            }
+            let value = &mut values[lane];
+            if self.repetition != 1.0 {
+                if *value > 0.0 {
+                    *value /= self.repetition;
+                } else {
+                    *value *= self.repetition;
+                }
+            }
+            *value -= self.frequency * count as f32 + self.presence;
+            *cursor += 1;
+        }
+    }
+}
+
+/// Statistics gathered by the sweep
+struct Sweep {
+    /// Largest processed logit
+    max: f32,
+    /// `sum(exp(logit - max))` over the whole vocabulary, when tracked
+    sum: f64,
+    track_sum: bool,
+    /// Best `capacity` candidates, worst on top
+    best: BinaryHeap<Reverse<Candidate>>,
+    capacity: usize,
+}
+
+impl Sweep {
+    fn new(capacity: usize, track_sum: bool) -> Self {
+        Self {
+            max: f32::NEG_INFINITY,
+            sum: 0.0,
+            track_sum,
+            best: BinaryHeap::with_capacity(capacity + 1),
+            capacity,
+        }
+    }
+
+    #[inline]
+    fn observe(&mut self, chunk: f32x8, base: usize, len: usize) {
+        let values = chunk.to_array();
+        let chunk_max = values[..len]
+            .iter()
+            .fold(f32::NEG_INFINITY, |acc, &x| acc.max(x));
+        if chunk_max == f32::NEG_INFINITY {
+            return;
+        }
+
+        if self.track_sum {
+            if chunk_max > self.max {
+                if self.sum > 0.0 {
+                    self.sum *= f64::from(self.max - chunk_max).exp();
+                }
+                self.max = chunk_max;
+            }
+            let shifted = (chunk - f32x8::splat(self.max)).max(f32x8::splat(EXP_FLOOR));
+            let exps = shifted.exp().to_array();
+            self.sum += exps[..len].iter().map(|&e| f64::from(e)).sum::<f64>();
+        } else {
+            self.max = self.max.max(chunk_max);
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        let full = self.best.len() >= self.capacity;
+        if full
+            && self
+                .best
+                .peek()
+                .is_some_and(|worst| chunk_max <= worst.0.logit)
+        {
+            return;
+        }
+        for (lane, &logit) in values[..len].iter().enumerate() {
+            let candidate = Candidate {
+                logit,
+                index: (base + lane) as u32,
+            };
+            if self.best.len() < self.capacity {
+                self.best.push(Reverse(candidate));
+            } else if self.best.peek().is_some_and(|worst| candidate > worst.0) {
+                self.best.pop();
+                self.best.push(Reverse(candidate));
+            }
+        }
+    }
+
+    /// Candidates best first
+    fn ranked(self) -> Vec<Candidate> {
+        self.best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|r| r.0)
+            .collect()
+    }
+}
+
+/// Smallest logit of the nucleus among `ranked` candidates, if reached
+fn nucleus_cutoff(ranked: &[Candidate], max: f32, total: f64, top_p: f64) -> Option<f32> {
+    let mut cumulative = 0.0f64;
+    for candidate in ranked {
+        cumulative += f64::from(candidate.logit - max).exp() / total;
+        if cumulative >= top_p {
+            return Some(candidate.logit);
+        }
+    }
+    None
+}
+
+/// Nucleus cutoff ranking every logit, for distributions too flat for the
+/// candidate heap
+fn full_nucleus_cutoff(logits: &[f32], max: f32, top_p: f64) -> f32 {
+    let mut sorted: Vec<f32> = logits.to_vec();
+    sorted.sort_unstable_by(|a, b| b.total_cmp(a));
+    let total: f64 = sorted.iter().map(|&x| f64::from(x - max).exp()).sum();
+
+    let mut cumulative = 0.0f64;
+    for &logit in &sorted {
+        cumulative += f64::from(logit - max).exp() / total;
+        if cumulative >= top_p {
+            return logit;
+        }
+    }
+    f32::NEG_INFINITY
+}
+
+/// Set every logit below `threshold` to negative infinity
+fn mask_below(logits: &mut [f32], threshold: f32) {
+    let limit = f32x8::splat(threshold);
+    let masked = f32x8::splat(f32::NEG_INFINITY);
+
+    let mut chunks = logits.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        let mut values = [0.0f32; LANES];
+        values.copy_from_slice(chunk);
+        let v = f32x8::new(values);
+        chunk.copy_from_slice(&v.cmp_lt(limit).blend(masked, v).to_array());
+    }
+    for x in chunks.into_remainder() {
+        if *x < threshold {
+            *x = f32::NEG_INFINITY;
+        }
+    }
+}
+
+/// Apply penalties, temperature, top-k and nucleus filtering in one sweep
+///
+/// Temperature, top-k and top-p come from `context`, falling back to
+/// `config` for top-k and top-p; penalties come from `config`. Filtered
+/// tokens are set to negative infinity. Tokens tied with the last kept token
+/// are kept as well.
+///
+/// # Errors
+///
+/// Returns [`LogitsError::SamplingError`] if every logit ends up negative
+/// infinity.
+pub fn process_logits_fused(
+    logits: &mut [f32],
+    context: &ProcessingContext,
+    config: &ProcessorConfig,
+) -> LogitsResult<()> {
+    if logits.is_empty() {
+        return Ok(());
+    }
+
+    let vocab_size = logits.len();
+    let top_k = context
+        .top_k
+        .or(config.top_k)
+        .filter(|&k| k > 0 && k < vocab_size);
+    let top_p = context
+        .top_p
+        .or(config.top_p)
+        .filter(|&p| p > 0.0 && p < 1.0)
+        .map(f64::from);
+    let temperature = context.temperature;
+    let inv_temp = if temperature != 1.0 && temperature > 0.0 {
+        1.0 / temperature
+    } else {
+        1.0
+    };
+
+    let penalties = Penalties::new(&context.token_history, vocab_size, config);
+    let capacity = match (top_k, top_p) {
+        (Some(k), _) => k,
+        (None, Some(_)) => NUCLEUS_CANDIDATES.min(vocab_size),
+        (None, None) => 0,
+    };
+    let mut sweep = Sweep::new(capacity, top_k.is_none() && top_p.is_some());
+
+    let scale = f32x8::splat(inv_temp);
+    let mut cursor = 0;
+    for (chunk_index, chunk) in logits.chunks_mut(LANES).enumerate() {
+        let base = chunk_index * LANES;
+        let len = chunk.len();
+        let mut values = [f32::NEG_INFINITY; LANES];
+        values[..len].copy_from_slice(chunk);
+
+        let mut scaled = (f32x8::new(values) * scale).to_array();
+        penalties.apply(&mut scaled, base, len, &mut cursor);
+        chunk.copy_from_slice(&scaled[..len]);
+        sweep.observe(f32x8::new(scaled), base, len);
+    }
+
+    if sweep.max == f32::NEG_INFINITY {
+        return Err(LogitsError::SamplingError(
+            "All logits are negative infinity".to_string(),
+        ));
+    }
+
+    let max = sweep.max;
+    let sum = sweep.sum;
+    let ranked = sweep.ranked();
+    let threshold = match (top_k, top_p) {
+        (None, None) => None,
+        (Some(_), None) => ranked.last().map(|c| c.logit),
+        (Some(_), Some(p)) => {
+            let total: f64 = ranked.iter().map(|c| f64::from(c.logit - max).exp()).sum();
+            nucleus_cutoff(&ranked, max, total, p).or(ranked.last().map(|c| c.logit))
+        }
+        (None, Some(p)) => Some(
+            nucleus_cutoff(&ranked, max, sum, p)
+                .unwrap_or_else(|| full_nucleus_cutoff(logits, max, p)),
+        ),
+    };
+
+    if let Some(threshold) = threshold {
+        mask_below(logits, threshold);
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@
 use smallvec::SmallVec;
 
 pub mod constraints;
+mod fused;
 mod nucleus;
 mod penalties;
 pub mod processing;
@@ -10,6 +11,7 @@ pub mod processor;
 pub mod simd;
 pub mod topk;
 
+pub use fused::process_logits_fused;
 pub use nucleus::*;
 pub use penalties::*;
 pub use processing::*;
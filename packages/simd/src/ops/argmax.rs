@@ -211,6 +211,47 @@ unsafe fn neon_argmax(logits: &[f32]) -> SimdResult<usize> {
     scalar_argmax(logits)
 }
 
+/// SVE argmax: a vectorized max reduction, then a scan for its first index
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "sve")]
+unsafe fn sve_argmax(logits: &[f32]) -> SimdResult<usize> {
+    use std::arch::asm;
+
+    if logits.is_empty() {
+        return Err(crate::error::SimdError::InvalidInput(
+            "Logits slice is empty".to_string(),
+        ));
+    }
+
+    let max: f32;
+    unsafe {
+        asm!(
+            "mov z0.s, {first:s}",
+            "mov {i}, #0",
+            "whilelo p0.s, {i}, {len}",
+            "2:",
+            "ld1w {{ z1.s }}, p0/z, [{ptr}, {i}, lsl #2]",
+            "fmaxnm z0.s, p0/m, z0.s, z1.s",
+            "incw {i}",
+            "whilelo p0.s, {i}, {len}",
+            "b.first 2b",
+            "ptrue p0.s",
+            "fmaxnmv {max:s}, p0, z0.s",
+            first = in(vreg) logits[0],
+            ptr = in(reg) logits.as_ptr(),
+            len = in(reg) logits.len(),
+            i = out(reg) _,
+            max = out(vreg) max,
+            out("v0") _,
+            out("v1") _,
+            out("p0") _,
+            options(nostack, pure, readonly),
+        );
+    }
+
+    Ok(logits.iter().position(|&x| x == max).unwrap_or(0))
+}
+
 fn create_argmax_dispatch() -> ArgmaxDispatch {
     ArgmaxDispatch {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -233,6 +274,11 @@ fn create_argmax_dispatch() -> ArgmaxDispatch {
         #[cfg(not(target_arch = "aarch64"))]
         neon: None,
 
+        #[cfg(target_arch = "aarch64")]
+        sve: Some(sve_argmax),
+        #[cfg(not(target_arch = "aarch64"))]
+        sve: None,
+
         scalar: scalar_argmax,
     }
 }
@@ -357,6 +357,9 @@ fn create_softmax_dispatch() -> SoftmaxDispatch {
         #[cfg(not(target_arch = "aarch64"))]
         neon: None,
 
+        // Softmax has no SVE kernel yet; SVE CPUs use the NEON one
+        sve: None,
+
         scalar: scalar_softmax,
     }
 }
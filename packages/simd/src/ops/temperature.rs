@@ -200,6 +200,51 @@ unsafe fn neon_temperature_scale(logits: &mut [f32], temperature: f32) -> SimdRe
     Ok(())
 }
 
+/// SVE temperature scaling; predicated loads cover the tail, so there is no
+/// scalar remainder loop
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "sve")]
+unsafe fn sve_temperature_scale(logits: &mut [f32], temperature: f32) -> SimdResult<()> {
+    use std::arch::asm;
+
+    if logits.is_empty() {
+        return Err(crate::error::SimdError::InvalidInput(
+            "Logits slice is empty".to_string(),
+        ));
+    }
+
+    if temperature <= 0.0 {
+        return Err(crate::error::SimdError::InvalidInput(
+            "Temperature must be positive".to_string(),
+        ));
+    }
+
+    unsafe {
+        asm!(
+            "mov z1.s, {inv:s}",
+            "mov {i}, #0",
+            "whilelo p0.s, {i}, {len}",
+            "2:",
+            "ld1w {{ z0.s }}, p0/z, [{ptr}, {i}, lsl #2]",
+            "fmul z0.s, p0/m, z0.s, z1.s",
+            "st1w {{ z0.s }}, p0, [{ptr}, {i}, lsl #2]",
+            "incw {i}",
+            "whilelo p0.s, {i}, {len}",
+            "b.first 2b",
+            inv = in(vreg) 1.0 / temperature,
+            ptr = in(reg) logits.as_mut_ptr(),
+            len = in(reg) logits.len(),
+            i = out(reg) _,
+            out("v0") _,
+            out("v1") _,
+            out("p0") _,
+            options(nostack),
+        );
+    }
+
+    Ok(())
+}
+
 /// Applies temperature scaling to logits using the best available implementation.
 /// Temperature scaling adjusts the logits by dividing them by the temperature value.
 /// Lower temperature (<1.0) makes the distribution sharper (more confident),
@@ -249,6 +294,11 @@ fn create_temperature_dispatch() -> TemperatureDispatch {
         #[cfg(not(target_arch = "aarch64"))]
         neon: None,
 
+        #[cfg(target_arch = "aarch64")]
+        sve: Some(sve_temperature_scale),
+        #[cfg(not(target_arch = "aarch64"))]
+        sve: None,
+
         scalar: scalar_temperature_scale,
     }
 }
@@ -17,6 +17,10 @@ pub enum CpuFeatures {
     Avx2 = 3,
     /// x86 AVX512 support (highest performance)
     Avx512 = 4,
+    /// ARM SVE support (scalable vectors of at least 128 bits)
+    Sve = 5,
+    /// x86 AVX512 with VNNI int8 dot products
+    Avx512Vnni = 6,
 }
 
 impl CpuFeatures {
@@ -24,18 +28,23 @@ impl CpuFeatures {
     #[inline]
     #[must_use]
     pub const fn has_simd(self) -> bool {
-        matches!(self, Self::Neon | Self::Sse41 | Self::Avx2 | Self::Avx512)
+        matches!(
+            self,
+            Self::Neon | Self::Sse41 | Self::Avx2 | Self::Avx512 | Self::Sve | Self::Avx512Vnni
+        )
     }
 
     /// Get SIMD vector width in f32 elements
+    ///
+    /// SVE vector length is chosen by the hardware; this is its 128-bit minimum.
     #[inline]
     #[must_use]
     pub const fn vector_width(self) -> usize {
         match self {
             Self::Scalar => 1,
-            Self::Neon | Self::Sse41 => 4,
+            Self::Neon | Self::Sse41 | Self::Sve => 4,
             Self::Avx2 => 8,
-            Self::Avx512 => 16,
+            Self::Avx512 | Self::Avx512Vnni => 16,
         }
     }
 
@@ -45,9 +54,9 @@ impl CpuFeatures {
     pub const fn chunk_size(self) -> usize {
         match self {
             Self::Scalar => 1,
-            Self::Neon | Self::Sse41 => 16, // 4 vectors of 4 elements
-            Self::Avx2 => 32,               // 4 vectors of 8 elements
-            Self::Avx512 => 64,             // 4 vectors of 16 elements
+            Self::Neon | Self::Sse41 | Self::Sve => 16, // 4 vectors of 4 elements
+            Self::Avx2 => 32,                           // 4 vectors of 8 elements
+            Self::Avx512 | Self::Avx512Vnni => 64,      // 4 vectors of 16 elements
         }
     }
 }
@@ -76,11 +85,14 @@ pub fn get_cpu_features() -> CpuFeatures {
 /// Detect available CPU features at runtime
 #[cold]
 fn detect_cpu_features() -> CpuFeatures {
-    // Priority order: AVX512 > AVX2 > SSE4.1 > NEON > Scalar
+    // Priority order: AVX512 VNNI > AVX512 > AVX2 > SSE4.1 > SVE > NEON > Scalar
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
         if *HAS_AVX512 {
+            if is_x86_feature_detected!("avx512bw") && is_x86_feature_detected!("avx512vnni") {
+                return CpuFeatures::Avx512Vnni;
+            }
             return CpuFeatures::Avx512;
         }
         if is_x86_feature_detected!("avx2") {
@@ -93,6 +105,9 @@ fn detect_cpu_features() -> CpuFeatures {
 
     #[cfg(target_arch = "aarch64")]
     {
+        if std::arch::is_aarch64_feature_detected!("sve") {
+            return CpuFeatures::Sve;
+        }
         if std::arch::is_aarch64_feature_detected!("neon") {
             return CpuFeatures::Neon;
         }
@@ -120,6 +135,8 @@ pub struct TemperatureDispatch {
     pub sse41: Option<TemperatureScaleFn>,
     /// ARM NEON optimized temperature scaling function
     pub neon: Option<TemperatureScaleFn>,
+    /// ARM SVE optimized temperature scaling function, falling back to NEON
+    pub sve: Option<TemperatureScaleFn>,
     /// Scalar fallback temperature scaling function
     pub scalar: TemperatureScaleFn,
 }
@@ -134,6 +151,8 @@ pub struct SoftmaxDispatch {
     pub sse41: Option<SoftmaxFn>,
     /// ARM NEON optimized softmax function
     pub neon: Option<SoftmaxFn>,
+    /// ARM SVE optimized softmax function, falling back to NEON
+    pub sve: Option<SoftmaxFn>,
     /// Scalar fallback softmax function
     pub scalar: SoftmaxFn,
 }
@@ -148,6 +167,8 @@ pub struct ArgmaxDispatch {
     pub sse41: Option<ArgmaxFn>,
     /// ARM NEON optimized argmax function
     pub neon: Option<ArgmaxFn>,
+    /// ARM SVE optimized argmax function, falling back to NEON
+    pub sve: Option<ArgmaxFn>,
     /// Scalar fallback argmax function
     pub scalar: ArgmaxFn,
 }
@@ -157,9 +178,10 @@ impl TemperatureDispatch {
     #[inline]
     pub fn get_fn(&self) -> TemperatureScaleFn {
         match get_cpu_features() {
-            CpuFeatures::Avx512 => self.avx512.unwrap_or(self.scalar),
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.unwrap_or(self.scalar),
             CpuFeatures::Avx2 => self.avx2.unwrap_or(self.scalar),
             CpuFeatures::Sse41 => self.sse41.unwrap_or(self.scalar),
+            CpuFeatures::Sve => self.sve.or(self.neon).unwrap_or(self.scalar),
             CpuFeatures::Neon => self.neon.unwrap_or(self.scalar),
             CpuFeatures::Scalar => self.scalar,
         }
@@ -181,7 +203,7 @@ impl TemperatureDispatch {
         feature: CpuFeatures,
     ) -> crate::error::SimdResult<()> {
         let func = match feature {
-            CpuFeatures::Avx512 => self.avx512.ok_or_else(|| {
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.ok_or_else(|| {
                 crate::error::SimdError::UnsupportedOperation(
                     "AVX-512 not available on this platform".to_string(),
                 )
@@ -201,6 +223,11 @@ impl TemperatureDispatch {
                     "NEON not available on this platform".to_string(),
                 )
             })?,
+            CpuFeatures::Sve => self.sve.ok_or_else(|| {
+                crate::error::SimdError::UnsupportedOperation(
+                    "SVE not available on this platform".to_string(),
+                )
+            })?,
             CpuFeatures::Scalar => self.scalar,
         };
         unsafe { func(logits, temperature) }
@@ -212,9 +239,10 @@ impl SoftmaxDispatch {
     #[inline]
     pub fn get_fn(&self) -> SoftmaxFn {
         match get_cpu_features() {
-            CpuFeatures::Avx512 => self.avx512.unwrap_or(self.scalar),
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.unwrap_or(self.scalar),
             CpuFeatures::Avx2 => self.avx2.unwrap_or(self.scalar),
             CpuFeatures::Sse41 => self.sse41.unwrap_or(self.scalar),
+            CpuFeatures::Sve => self.sve.or(self.neon).unwrap_or(self.scalar),
             CpuFeatures::Neon => self.neon.unwrap_or(self.scalar),
             CpuFeatures::Scalar => self.scalar,
         }
@@ -235,7 +263,7 @@ impl SoftmaxDispatch {
         feature: CpuFeatures,
     ) -> crate::error::SimdResult<Vec<f32>> {
         let func = match feature {
-            CpuFeatures::Avx512 => self.avx512.ok_or_else(|| {
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.ok_or_else(|| {
                 crate::error::SimdError::UnsupportedOperation(
                     "AVX-512 not available on this platform".to_string(),
                 )
@@ -255,6 +283,11 @@ impl SoftmaxDispatch {
                     "NEON not available on this platform".to_string(),
                 )
             })?,
+            CpuFeatures::Sve => self.sve.ok_or_else(|| {
+                crate::error::SimdError::UnsupportedOperation(
+                    "SVE not available on this platform".to_string(),
+                )
+            })?,
             CpuFeatures::Scalar => self.scalar,
         };
         unsafe { func(logits) }
@@ -266,9 +299,10 @@ impl ArgmaxDispatch {
     #[inline]
     pub fn get_fn(&self) -> ArgmaxFn {
         match get_cpu_features() {
-            CpuFeatures::Avx512 => self.avx512.unwrap_or(self.scalar),
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.unwrap_or(self.scalar),
             CpuFeatures::Avx2 => self.avx2.unwrap_or(self.scalar),
             CpuFeatures::Sse41 => self.sse41.unwrap_or(self.scalar),
+            CpuFeatures::Sve => self.sve.or(self.neon).unwrap_or(self.scalar),
             CpuFeatures::Neon => self.neon.unwrap_or(self.scalar),
             CpuFeatures::Scalar => self.scalar,
         }
@@ -289,7 +323,7 @@ impl ArgmaxDispatch {
         feature: CpuFeatures,
     ) -> crate::error::SimdResult<usize> {
         let func = match feature {
-            CpuFeatures::Avx512 => self.avx512.ok_or_else(|| {
+            CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni => self.avx512.ok_or_else(|| {
                 crate::error::SimdError::UnsupportedOperation(
                     "AVX-512 not available on this platform".to_string(),
                 )
@@ -309,6 +343,11 @@ impl ArgmaxDispatch {
                     "NEON not available on this platform".to_string(),
                 )
             })?,
+            CpuFeatures::Sve => self.sve.ok_or_else(|| {
+                crate::error::SimdError::UnsupportedOperation(
+                    "SVE not available on this platform".to_string(),
+                )
+            })?,
             CpuFeatures::Scalar => self.scalar,
         };
         unsafe { func(logits) }
@@ -390,6 +429,8 @@ mod tests {
                 | CpuFeatures::Sse41
                 | CpuFeatures::Avx2
                 | CpuFeatures::Avx512
+                | CpuFeatures::Sve
+                | CpuFeatures::Avx512Vnni
         ));
     }
 
@@ -400,6 +441,8 @@ mod tests {
         assert_eq!(CpuFeatures::Sse41.vector_width(), 4);
         assert_eq!(CpuFeatures::Avx2.vector_width(), 8);
         assert_eq!(CpuFeatures::Avx512.vector_width(), 16);
+        assert_eq!(CpuFeatures::Sve.vector_width(), 4);
+        assert_eq!(CpuFeatures::Avx512Vnni.vector_width(), 16);
     }
 
     #[test]
@@ -29,7 +29,7 @@ use tokenizers::Tokenizer;
 
 use crate::logits::constraints::{
     JsonConstraint,
-    SchemaConstraint, SchemaVocabulary, regex_from_schema, regex_from_value,
+    SchemaConstraint, SchemaVocabulary, regex_from_gbnf, regex_from_schema, regex_from_value,
 };
 
 /// Create a JSON constraint from a serde type with `JsonSchema` derive
@@ -116,6 +116,40 @@ pub fn constraint_for_schema(
         .context("Failed to create schema constraint from JSON")
 }
 
+/// Create a constraint from a GBNF grammar
+///
+/// The grammar's `root` rule defines the accepted output. Rules are inlined,
+/// so the grammar must be non-recursive.
+///
+/// # Arguments
+/// * `grammar` - GBNF grammar source
+/// * `tokenizer` - Tokenizer for token-to-text conversion
+///
+/// # Returns
+/// * `Ok(SchemaConstraint)` - Constraint that accepts output matching the grammar
+/// * `Err(anyhow::Error)` - If the grammar is invalid or recursive
+///
+/// # Example
+/// ```rust,no_run
+/// use cyrup_simd::serde_constraints::constraint_for_gbnf;
+///
+/// let grammar = r#"
+///     root   ::= "{" ws "\"answer\":" ws answer ws "}"
+///     answer ::= "\"yes\"" | "\"no\""
+///     ws     ::= [ \t\n]*
+/// "#;
+///
+/// let constraint = constraint_for_gbnf(grammar, &tokenizer)?;
+/// ```
+pub fn constraint_for_gbnf(grammar: &str, tokenizer: &Tokenizer) -> AnyResult<SchemaConstraint> {
+    let vocabulary = Arc::new(SchemaVocabulary::from_tokenizer(tokenizer)?);
+    let regex_pattern = regex_from_gbnf(grammar)
+        .context("Failed to generate regex from GBNF grammar")?;
+
+    SchemaConstraint::new(&regex_pattern, vocabulary, false)
+        .context("Failed to create constraint from GBNF grammar")
+}
+
 /// Create a basic JSON syntax constraint (no schema validation)
 ///
 /// This function creates a constraint that only validates JSON syntax
@@ -5,6 +5,7 @@
 
 
 pub mod metrics;
+pub mod quantized;
 mod scalar;
 mod simd;
 mod traits;
@@ -13,7 +14,9 @@ use std::sync::Arc;
 
 use lazy_static::lazy_static;
 pub use metrics::SimilarityMetricsSnapshot;
+pub use quantized::{cosine_similarity_i8, dot_product_i8, quantize_i8};
 pub use scalar::ScalarSimilarity;
+pub use simd::for_features;
 pub use simd::portable::PortableSimdSimilarity;
 pub use traits::{CosineSimilarity, RuntimeSelectable, SimilarityBuilder};
 
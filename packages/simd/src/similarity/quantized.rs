@@ -0,0 +1,135 @@
+//! Int8 quantized similarity
+//!
+//! Embeddings quantized to `i8` take a quarter of the memory of `f32` ones.
+//! On CPUs with AVX-512 VNNI one instruction multiplies and accumulates 64
+//! element pairs; elsewhere a scalar loop that the compiler vectorizes is
+//! used.
+
+#[cfg(any(test, feature = "bench"))]
+use crate::error::{SimdError, SimdResult};
+#[cfg(any(test, feature = "bench"))]
+use crate::runtime::CpuFeatures;
+
+/// Quantize a vector symmetrically to int8
+///
+/// Returns the quantized values and the scale that maps them back:
+/// `vector[i] ≈ values[i] as f32 * scale`.
+pub fn quantize_i8(vector: &[f32]) -> (Vec<i8>, f32) {
+    let max_abs = vector.iter().fold(0.0f32, |acc, x| acc.max(x.abs()));
+    if max_abs == 0.0 || !max_abs.is_finite() {
+        return (vec![0; vector.len()], 0.0);
+    }
+
+    let scale = max_abs / 127.0;
+    let values = vector
+        .iter()
+        .map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+        .collect();
+    (values, scale)
+}
+
+/// Dot product of two int8 vectors
+///
+/// The product is exact as long as it fits in an `i32`, which holds for any
+/// vectors of up to 131072 elements.
+///
+/// # Panics
+/// If the input vectors have different lengths
+#[inline]
+pub fn dot_product_i8(a: &[i8], b: &[i8]) -> i32 {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Vectors must have the same length for dot product"
+    );
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if has_vnni() {
+        // SAFETY: AVX512F, AVX512BW and AVX512 VNNI were detected at runtime
+        return unsafe { super::simd::x86::dot_i8(a, b) };
+    }
+
+    scalar_dot_and_norms(a, b).0
+}
+
+/// Cosine similarity of two int8 vectors
+///
+/// Quantization scales cancel out, so vectors quantized with different
+/// scales can be compared directly.
+///
+/// # Panics
+/// If the input vectors have different lengths
+#[inline]
+pub fn cosine_similarity_i8(a: &[i8], b: &[i8]) -> f32 {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Vectors must have the same length for cosine similarity"
+    );
+
+    let (dot, norm_a, norm_b) = dot_and_norms(a, b);
+    let norm_product = (norm_a as f32 * norm_b as f32).sqrt();
+    if norm_product <= f32::EPSILON {
+        0.0
+    } else {
+        (dot as f32 / norm_product).clamp(-1.0, 1.0)
+    }
+}
+
+/// Dot product with a specific CPU feature level (for benchmarking)
+///
+/// Only [`CpuFeatures::Scalar`] and [`CpuFeatures::Avx512Vnni`] have int8
+/// kernels.
+#[cfg(any(test, feature = "bench"))]
+pub fn dot_product_i8_with_feature(a: &[i8], b: &[i8], feature: CpuFeatures) -> SimdResult<i32> {
+    if a.len() != b.len() {
+        return Err(SimdError::InvalidInput(format!(
+            "Vector lengths differ: {} and {}",
+            a.len(),
+            b.len()
+        )));
+    }
+
+    match feature {
+        CpuFeatures::Scalar => Ok(scalar_dot_and_norms(a, b).0),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        CpuFeatures::Avx512Vnni if has_vnni() => {
+            // SAFETY: AVX512F, AVX512BW and AVX512 VNNI were detected at runtime
+            Ok(unsafe { super::simd::x86::dot_i8(a, b) })
+        }
+        _ => Err(SimdError::UnsupportedOperation(format!(
+            "No {feature:?} int8 dot product on this CPU"
+        ))),
+    }
+}
+
+/// Whether the AVX-512 VNNI kernels can run on this CPU
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+fn has_vnni() -> bool {
+    crate::runtime::get_cpu_features() == crate::runtime::CpuFeatures::Avx512Vnni
+}
+
+#[inline]
+fn dot_and_norms(a: &[i8], b: &[i8]) -> (i32, i32, i32) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if has_vnni() {
+        // SAFETY: AVX512F, AVX512BW and AVX512 VNNI were detected at runtime
+        return unsafe { super::simd::x86::dot_and_norms_i8(a, b) };
+    }
+
+    scalar_dot_and_norms(a, b)
+}
+
+fn scalar_dot_and_norms(a: &[i8], b: &[i8]) -> (i32, i32, i32) {
+    let mut dot = 0i32;
+    let mut norm_a = 0i32;
+    let mut norm_b = 0i32;
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (i32::from(x), i32::from(y));
+        dot = dot.wrapping_add(x * y);
+        norm_a = norm_a.wrapping_add(x * x);
+        norm_b = norm_b.wrapping_add(y * y);
+    }
+    (dot, norm_a, norm_b)
+}
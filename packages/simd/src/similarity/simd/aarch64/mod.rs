@@ -1,5 +1,7 @@
 //! ARM AArch64 SIMD optimizations
 
 pub mod neon;
+pub mod sve;
 
 pub use neon::{NeonSimilarity, is_neon_available};
+pub use sve::{SveSimilarity, is_sve_available};
@@ -0,0 +1,179 @@
+//! ARM SVE SIMD implementation for AArch64 architectures
+//!
+//! SVE vectors are 128 to 2048 bits wide depending on the core. The kernel is
+//! written against the runtime vector length with predicated loads, so the
+//! same code uses the full width of every implementation and needs no scalar
+//! tail. Stable Rust has no SVE intrinsics, so the loop is inline assembly.
+
+use std::arch::asm;
+use std::sync::Arc;
+
+use crate::similarity::metrics::{MetricsGuard, SimilarityMetrics, SimilarityMetricsSnapshot};
+use crate::similarity::scalar::ScalarSimilarity;
+use crate::similarity::traits::{CosineSimilarity, RuntimeSelectable, WithMetrics};
+
+/// SVE-optimized similarity implementation for ARM64
+pub struct SveSimilarity {
+    metrics: Arc<SimilarityMetrics>,
+}
+
+impl Default for SveSimilarity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SveSimilarity {
+    /// Create a new SVE similarity instance
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            metrics: Arc::new(SimilarityMetrics::default()),
+        }
+    }
+
+    /// Dot product and squared norms using SVE instructions
+    #[target_feature(enable = "sve")]
+    unsafe fn process_sve(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+        let dot: f32;
+        let norm_a: f32;
+        let norm_b: f32;
+
+        unsafe {
+            asm!(
+                "mov z0.s, #0",
+                "mov z1.s, #0",
+                "mov z2.s, #0",
+                "mov {i}, #0",
+                "whilelo p0.s, {i}, {len}",
+                "b.none 3f",
+                "2:",
+                "ld1w {{ z3.s }}, p0/z, [{a}, {i}, lsl #2]",
+                "ld1w {{ z4.s }}, p0/z, [{b}, {i}, lsl #2]",
+                "fmla z0.s, p0/m, z3.s, z4.s",
+                "fmla z1.s, p0/m, z3.s, z3.s",
+                "fmla z2.s, p0/m, z4.s, z4.s",
+                "incw {i}",
+                "whilelo p0.s, {i}, {len}",
+                "b.first 2b",
+                "3:",
+                "ptrue p0.s",
+                "faddv s0, p0, z0.s",
+                "faddv s1, p0, z1.s",
+                "faddv s2, p0, z2.s",
+                a = in(reg) a.as_ptr(),
+                b = in(reg) b.as_ptr(),
+                len = in(reg) a.len(),
+                i = out(reg) _,
+                out("v0") dot,
+                out("v1") norm_a,
+                out("v2") norm_b,
+                out("v3") _,
+                out("v4") _,
+                out("p0") _,
+                options(nostack, pure, readonly),
+            );
+        }
+
+        (dot, norm_a, norm_b)
+    }
+
+    /// Check if the current vector length is suitable for SVE
+    #[inline]
+    fn is_suitable_length(len: usize) -> bool {
+        len >= 4 // At least one minimum-width SVE vector
+    }
+}
+
+impl CosineSimilarity for SveSimilarity {
+    #[inline]
+    fn cosine_similarity(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return 0.0; // Invalid input, return zero similarity
+        }
+
+        let _guard = MetricsGuard::new(&self.metrics, a.len());
+
+        let (dot, norm_a, norm_b) = if Self::is_suitable_length(a.len()) && is_sve_available() {
+            // SAFETY: SVE support was checked at runtime
+            unsafe { Self::process_sve(a, b) }
+        } else {
+            ScalarSimilarity::dot_and_norms(a, b)
+        };
+
+        // Compute cosine similarity with numerical stability
+        let norm_product = (norm_a * norm_b).sqrt();
+        if norm_product <= f32::EPSILON {
+            0.0 // Handle zero vectors
+        } else {
+            // Clamp result to [-1, 1] to handle floating-point precision issues
+            (dot / norm_product).clamp(-1.0, 1.0)
+        }
+    }
+}
+
+impl WithMetrics for SveSimilarity {
+    fn metrics(&self) -> SimilarityMetricsSnapshot {
+        self.metrics.get_metrics()
+    }
+
+    fn reset_metrics(&self) {
+        self.metrics.reset();
+    }
+}
+
+impl RuntimeSelectable for SveSimilarity {
+    fn name(&self) -> &'static str {
+        "sve"
+    }
+
+    fn optimal_vector_length(&self) -> usize {
+        4 // One minimum-width SVE vector
+    }
+}
+
+/// Check if SVE is available at runtime
+#[inline]
+pub fn is_sve_available() -> bool {
+    std::arch::is_aarch64_feature_detected!("sve")
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_sve_matches_scalar() {
+        if !is_sve_available() {
+            return;
+        }
+        let sim = SveSimilarity::new();
+
+        // Lengths around common vector widths exercise the predicated tail
+        for len in [4, 7, 16, 33, 67, 384] {
+            let a: Vec<f32> = (0..len).map(|i| (i as f32 * 0.37).sin()).collect();
+            let b: Vec<f32> = (0..len).map(|i| (i as f32 * 0.11).cos()).collect();
+
+            let (dot, norm_a, norm_b) = ScalarSimilarity::dot_and_norms(&a, &b);
+            let expected = dot / (norm_a * norm_b).sqrt();
+            assert_relative_eq!(sim.cosine_similarity(&a, &b), expected, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let sim = SveSimilarity::new();
+
+        // Zero vectors
+        let a = [0.0; 8];
+        let b = [1.0; 8];
+        assert_eq!(sim.cosine_similarity(&a, &b), 0.0);
+
+        // Small vectors use the scalar path
+        let a = [1.0, 2.0];
+        let b = [3.0, 4.0];
+        assert_relative_eq!(sim.cosine_similarity(&a, &b), 0.983_869_9, epsilon = 1e-6);
+    }
+}
@@ -15,7 +15,9 @@ use std::sync::Arc;
 
 use lazy_static::lazy_static;
 
+use super::scalar::ScalarSimilarity;
 use super::traits::RuntimeSelectable;
+use crate::runtime::CpuFeatures;
 
 /// Get the best available REAL SIMD implementation for the current CPU
 pub fn best_available() -> Arc<dyn RuntimeSelectable> {
@@ -45,6 +47,11 @@ pub fn best_available() -> Arc<dyn RuntimeSelectable> {
 
             #[cfg(target_arch = "aarch64")]
             {
+                // Prefer SVE, which uses the core's full vector length
+                if aarch64::is_sve_available() {
+                    return Arc::new(aarch64::SveSimilarity::new());
+                }
+
                 if aarch64::is_neon_available() {
                     return Arc::new(aarch64::NeonSimilarity::new());
                 }
@@ -57,3 +64,30 @@ pub fn best_available() -> Arc<dyn RuntimeSelectable> {
 
     BEST_IMPL.clone()
 }
+
+/// Get the implementation for a specific CPU feature level, if it is usable
+/// on this CPU
+///
+/// Benchmarks and tests use this to compare instruction sets side by side.
+pub fn for_features(features: CpuFeatures) -> Option<Arc<dyn RuntimeSelectable>> {
+    match features {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        CpuFeatures::Avx512 | CpuFeatures::Avx512Vnni if x86::is_avx512f_available() => {
+            Some(Arc::new(x86::Avx512Similarity::new()))
+        }
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        CpuFeatures::Avx2 if x86::is_avx2_available() => Some(Arc::new(x86::Avx2Similarity::new())),
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        CpuFeatures::Sse41 if x86::is_sse41_available() => {
+            Some(Arc::new(x86::Sse41Similarity::new()))
+        }
+        #[cfg(target_arch = "aarch64")]
+        CpuFeatures::Sve if aarch64::is_sve_available() => {
+            Some(Arc::new(aarch64::SveSimilarity::new()))
+        }
+        #[cfg(target_arch = "aarch64")]
+        CpuFeatures::Neon => Some(Arc::new(aarch64::NeonSimilarity::new())),
+        CpuFeatures::Scalar => Some(Arc::new(ScalarSimilarity::new())),
+        _ => None,
+    }
+}
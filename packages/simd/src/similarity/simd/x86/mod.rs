@@ -6,12 +6,14 @@
 mod avx2;
 mod avx512;
 mod sse41;
+mod vnni;
 
 use std::sync::Arc;
 
 pub use avx2::Avx2Similarity;
 pub use avx512::Avx512Similarity;
 pub use sse41::Sse41Similarity;
+pub(crate) use vnni::{dot_and_norms_i8, dot_i8};
 
 use crate::similarity::metrics::{MetricsGuard, SimilarityMetrics, SimilarityMetricsSnapshot};
 use crate::similarity::traits::{CosineSimilarity, RuntimeSelectable, WithMetrics};
@@ -0,0 +1,91 @@
+//! AVX-512 VNNI kernels for int8 vectors
+//!
+//! `vpdpbusd` multiplies 64 unsigned by signed byte pairs and accumulates
+//! them into 16 i32 lanes in one instruction. Both operands here are signed,
+//! so the left one is biased into unsigned range by flipping its sign bit
+//! (`x + 128`) and the bias is removed afterwards: `a·b = (a + 128)·b - 128·Σb`.
+//! Accumulation wraps, so results are exact whenever the true value fits in
+//! an `i32`.
+
+use std::arch::x86_64::*;
+
+/// Lanes of one AVX-512 register of bytes
+const LANES: usize = 64;
+
+/// Dot product of two int8 vectors of equal length
+#[target_feature(enable = "avx512f,avx512bw,avx512vnni")]
+pub(crate) unsafe fn dot_i8(a: &[i8], b: &[i8]) -> i32 {
+    let bias = _mm512_set1_epi8(i8::MIN);
+    let ones = _mm512_set1_epi8(1);
+    let mut dot = _mm512_setzero_si512();
+    let mut sum_b = _mm512_setzero_si512();
+
+    let len = a.len();
+    let mut i = 0;
+    while i < len {
+        let (va, vb) = unsafe { load_pair(a, b, i) };
+        dot = _mm512_dpbusd_epi32(dot, _mm512_xor_si512(va, bias), vb);
+        sum_b = _mm512_dpbusd_epi32(sum_b, ones, vb);
+        i += LANES;
+    }
+
+    unbias(_mm512_reduce_add_epi32(dot), _mm512_reduce_add_epi32(sum_b))
+}
+
+/// Dot product and squared norms of two int8 vectors of equal length
+#[target_feature(enable = "avx512f,avx512bw,avx512vnni")]
+pub(crate) unsafe fn dot_and_norms_i8(a: &[i8], b: &[i8]) -> (i32, i32, i32) {
+    let bias = _mm512_set1_epi8(i8::MIN);
+    let ones = _mm512_set1_epi8(1);
+    let mut dot = _mm512_setzero_si512();
+    let mut norm_a = _mm512_setzero_si512();
+    let mut norm_b = _mm512_setzero_si512();
+    let mut sum_a = _mm512_setzero_si512();
+    let mut sum_b = _mm512_setzero_si512();
+
+    let len = a.len();
+    let mut i = 0;
+    while i < len {
+        let (va, vb) = unsafe { load_pair(a, b, i) };
+        let ua = _mm512_xor_si512(va, bias);
+        let ub = _mm512_xor_si512(vb, bias);
+        dot = _mm512_dpbusd_epi32(dot, ua, vb);
+        norm_a = _mm512_dpbusd_epi32(norm_a, ua, va);
+        norm_b = _mm512_dpbusd_epi32(norm_b, ub, vb);
+        sum_a = _mm512_dpbusd_epi32(sum_a, ones, va);
+        sum_b = _mm512_dpbusd_epi32(sum_b, ones, vb);
+        i += LANES;
+    }
+
+    let sum_a = _mm512_reduce_add_epi32(sum_a);
+    let sum_b = _mm512_reduce_add_epi32(sum_b);
+    (
+        unbias(_mm512_reduce_add_epi32(dot), sum_b),
+        unbias(_mm512_reduce_add_epi32(norm_a), sum_a),
+        unbias(_mm512_reduce_add_epi32(norm_b), sum_b),
+    )
+}
+
+/// Load 64 bytes of each vector at `offset`, zero-filling past the end
+#[target_feature(enable = "avx512f,avx512bw")]
+#[inline]
+unsafe fn load_pair(a: &[i8], b: &[i8], offset: usize) -> (__m512i, __m512i) {
+    let remaining = a.len() - offset;
+    let mask: __mmask64 = if remaining >= LANES {
+        u64::MAX
+    } else {
+        (1u64 << remaining) - 1
+    };
+    unsafe {
+        (
+            _mm512_maskz_loadu_epi8(mask, a.as_ptr().add(offset)),
+            _mm512_maskz_loadu_epi8(mask, b.as_ptr().add(offset)),
+        )
+    }
+}
+
+/// Remove the `+128` bias of the left operand
+#[inline]
+fn unbias(biased: i32, sum_right: i32) -> i32 {
+    biased.wrapping_sub(sum_right.wrapping_mul(128))
+}
@@ -0,0 +1,200 @@
+use cyrup_simd::ann::{AnnError, AnnMetric, HnswConfig, HnswIndex};
+
+const DIMENSIONS: usize = 16;
+
+/// Deterministic pseudo-random vectors
+fn vectors(count: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            (0..DIMENSIONS)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    ((state >> 33) as f32 / (1u64 << 31) as f32) * 2.0 - 1.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn build(metric: AnnMetric, data: &[Vec<f32>]) -> HnswIndex {
+    let mut index =
+        HnswIndex::new(DIMENSIONS, metric, HnswConfig::default()).expect("valid config");
+    for (i, vector) in data.iter().enumerate() {
+        index
+            .insert(format!("v{i}"), vector.clone())
+            .expect("insert");
+    }
+    index
+}
+
+fn brute_force(metric: AnnMetric, data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+    let mut scored: Vec<(usize, f32)> = data
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i, metric.distance(query, v)))
+        .collect();
+    scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+    scored
+        .into_iter()
+        .take(k)
+        .map(|(i, _)| format!("v{i}"))
+        .collect()
+}
+
+#[test]
+fn test_hnsw_recall_matches_brute_force() {
+    for metric in [AnnMetric::Cosine, AnnMetric::Euclidean] {
+        let data = vectors(600, 1);
+        let index = build(metric, &data);
+        assert_eq!(index.len(), 600);
+
+        let queries = vectors(30, 2);
+        let mut hits = 0;
+        for query in &queries {
+            let expected = brute_force(metric, &data, query, 10);
+            let found = index.search(query, 10).expect("search");
+            assert_eq!(found.len(), 10);
+            assert!(found.windows(2).all(|w| w[0].1 <= w[1].1));
+            hits += found
+                .iter()
+                .filter(|(key, _)| expected.contains(key))
+                .count();
+        }
+
+        let recall = hits as f32 / (queries.len() * 10) as f32;
+        assert!(recall >= 0.9, "{metric:?} recall {recall}");
+    }
+}
+
+#[test]
+fn test_hnsw_finds_exact_vector_first() {
+    let data = vectors(200, 3);
+    let index = build(AnnMetric::Cosine, &data);
+
+    let found = index.search(&data[42], 1).expect("search");
+    assert_eq!(found[0].0, "v42");
+    assert!(found[0].1.abs() < 1e-5);
+}
+
+#[test]
+fn test_hnsw_delete_keeps_graph_navigable() {
+    let data = vectors(300, 4);
+    let mut index = build(AnnMetric::Euclidean, &data);
+
+    for i in (0..300).step_by(2) {
+        assert!(index.remove(&format!("v{i}")));
+    }
+    assert!(!index.remove("v0"));
+    assert_eq!(index.len(), 150);
+
+    for i in (1..300).step_by(2) {
+        let found = index.search_with_ef(&data[i], 5, 100).expect("search");
+        assert_eq!(found[0].0, format!("v{i}"));
+        assert!(
+            found
+                .iter()
+                .all(|(key, _)| key[1..].parse::<usize>().is_ok_and(|n| n % 2 == 1))
+        );
+    }
+
+    // Freed slots are reused
+    index.insert("again", data[0].clone()).expect("insert");
+    assert_eq!(index.search(&data[0], 1).expect("search")[0].0, "again");
+}
+
+#[test]
+fn test_hnsw_insert_replaces_existing_key() {
+    let mut index =
+        HnswIndex::new(2, AnnMetric::Euclidean, HnswConfig::default()).expect("valid config");
+    index.insert("a", vec![0.0, 0.0]).expect("insert");
+    index.insert("b", vec![10.0, 10.0]).expect("insert");
+    index.insert("a", vec![10.0, 10.5]).expect("replace");
+
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get("a"), Some(&[10.0, 10.5][..]));
+    assert_eq!(index.search(&[10.0, 10.6], 1).expect("search")[0].0, "a");
+}
+
+#[test]
+fn test_hnsw_removing_everything_empties_index() {
+    let data = vectors(20, 5);
+    let mut index = build(AnnMetric::Cosine, &data);
+    for i in 0..20 {
+        index.remove(&format!("v{i}"));
+    }
+
+    assert!(index.is_empty());
+    assert!(index.search(&data[0], 3).expect("search").is_empty());
+}
+
+#[test]
+fn test_hnsw_rejects_bad_input() {
+    assert!(matches!(
+        HnswIndex::new(4, AnnMetric::Cosine, HnswConfig::default().with_m(1)),
+        Err(AnnError::InvalidConfig(_))
+    ));
+
+    let mut index =
+        HnswIndex::new(4, AnnMetric::Cosine, HnswConfig::default()).expect("valid config");
+    assert!(matches!(
+        index.insert("short", vec![1.0, 2.0]),
+        Err(AnnError::DimensionMismatch {
+            expected: 4,
+            actual: 2
+        })
+    ));
+    assert!(matches!(
+        index.search(&[1.0], 1),
+        Err(AnnError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_hnsw_round_trips_through_disk() {
+    let data = vectors(250, 6);
+    let mut index = build(AnnMetric::Cosine, &data);
+    index.remove("v7");
+
+    let path = std::env::temp_dir().join(format!("cyrup-ann-{}.hnsw", std::process::id()));
+    index.save(&path).expect("save");
+    let loaded = HnswIndex::load(&path).expect("load");
+    std::fs::remove_file(&path).expect("cleanup");
+
+    assert_eq!(loaded.len(), index.len());
+    assert_eq!(loaded.metric(), AnnMetric::Cosine);
+    assert_eq!(loaded.config(), index.config());
+    assert!(!loaded.contains("v7"));
+    for query in vectors(10, 7) {
+        assert_eq!(
+            loaded.search(&query, 5).expect("search"),
+            index.search(&query, 5).expect("search")
+        );
+    }
+}
+
+#[test]
+fn test_hnsw_rejects_damaged_files() {
+    let index = build(AnnMetric::Euclidean, &vectors(50, 8));
+    let mut bytes = Vec::new();
+    index.write_to(&mut bytes).expect("serialize");
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(matches!(
+        HnswIndex::read_from(&mut wrong_magic.as_slice()),
+        Err(AnnError::Corrupt(_))
+    ));
+
+    let mut future = bytes.clone();
+    future[8..12].copy_from_slice(&99u32.to_le_bytes());
+    assert!(matches!(
+        HnswIndex::read_from(&mut future.as_slice()),
+        Err(AnnError::UnsupportedVersion(99))
+    ));
+
+    let truncated = &bytes[..bytes.len() / 2];
+    assert!(HnswIndex::read_from(&mut &truncated[..]).is_err());
+}
@@ -0,0 +1,96 @@
+use cyrup_simd::logits::constraints::{
+    GenerationConstraint, SchemaConstraint, SchemaVocabulary, regex_from_gbnf,
+};
+use regex::Regex;
+use std::sync::Arc;
+
+fn full_match(grammar: &str) -> Regex {
+    let pattern =
+        regex_from_gbnf(grammar).unwrap_or_else(|e| panic!("Grammar should compile: {e}"));
+    Regex::new(&format!("^(?:{pattern})$"))
+        .unwrap_or_else(|e| panic!("Invalid regex {pattern}: {e}"))
+}
+
+#[test]
+fn test_rules_are_inlined() {
+    let regex = full_match(
+        r#"
+        # A tiny JSON object with one enum field
+        root   ::= "{" ws "\"answer\":" ws answer ws "}"
+        answer ::= "\"yes\"" | "\"no\""
+        ws     ::= [ \t\n]*
+        "#,
+    );
+
+    assert!(regex.is_match(r#"{"answer":"yes"}"#));
+    assert!(regex.is_match("{ \"answer\": \"no\"\n}"));
+    assert!(!regex.is_match(r#"{"answer":"maybe"}"#));
+}
+
+#[test]
+fn test_classes_groups_and_repetition() {
+    let regex = full_match(r#"root ::= ("-"? [1-9] [0-9]{0,2}) ("," [a-z_]+)* [^x]?"#);
+
+    assert!(regex.is_match("42"));
+    assert!(regex.is_match("-999,ab_c,d"));
+    assert!(regex.is_match("7!"));
+    assert!(!regex.is_match("0"));
+    assert!(!regex.is_match("12345"));
+    assert!(!regex.is_match("5x"));
+}
+
+#[test]
+fn test_escapes_are_literal() {
+    let regex = full_match(r#"root ::= "a.b" "\x41" "é" [\]\-]"#);
+
+    assert!(regex.is_match("a.bAé]"));
+    assert!(regex.is_match("a.bAé-"));
+    assert!(!regex.is_match("axbAé]"));
+}
+
+#[test]
+fn test_invalid_grammars_are_rejected() {
+    // Recursion is not a regular language
+    assert!(regex_from_gbnf(r#"root ::= "(" root ")" | "x""#).is_err());
+    assert!(regex_from_gbnf(r#"root ::= item  item ::= "a" | root"#).is_err());
+    assert!(regex_from_gbnf(r#"root ::= missing"#).is_err());
+    assert!(regex_from_gbnf(r#"other ::= "x""#).is_err());
+    assert!(regex_from_gbnf(r#"root ::= "unterminated"#).is_err());
+    assert!(regex_from_gbnf(r#"root ::= [z-a]"#).is_err());
+    assert!(regex_from_gbnf(r#"root ::= "a"{3,1}"#).is_err());
+}
+
+#[test]
+fn test_grammar_drives_schema_constraint() {
+    let vocabulary = Arc::new(SchemaVocabulary::from_tokens(
+        vec![
+            b"<eos>".to_vec(),
+            b"yes".to_vec(),
+            b"no".to_vec(),
+            b"maybe".to_vec(),
+        ],
+        0,
+    ));
+    let pattern = regex_from_gbnf(r#"root ::= "yes" | "no""#)
+        .unwrap_or_else(|e| panic!("Grammar should compile: {e}"));
+    let constraint = SchemaConstraint::new(&pattern, vocabulary, false)
+        .unwrap_or_else(|e| panic!("Constraint should build: {e}"));
+
+    let mut state = constraint.new_state();
+    let allowed = |state, token| {
+        constraint
+            .try_next(state, token)
+            .unwrap_or_else(|e| panic!("try_next failed: {e}"))
+    };
+    assert!(allowed(&state, 1));
+    assert!(allowed(&state, 2));
+    assert!(!allowed(&state, 3));
+    assert!(
+        !allowed(&state, 0),
+        "EOS is invalid before the grammar matches"
+    );
+
+    assert!(constraint.update(&mut state, 1).unwrap_or(false));
+    assert!(constraint.is_done(&state));
+    assert!(allowed(&state, 0), "EOS is valid once the grammar matches");
+}
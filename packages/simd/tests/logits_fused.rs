@@ -0,0 +1,135 @@
+use cyrup_simd::config::ProcessorConfig;
+use cyrup_simd::context::ProcessingContext;
+use cyrup_simd::logits::LogitsError;
+use cyrup_simd::logits::processor::DefaultLogitsProcessor;
+use cyrup_simd::process_logits_fused;
+
+/// Deterministic pseudo-random logits in `[-scale, scale)`
+fn logits(size: usize, seed: u64, scale: f32) -> Vec<f32> {
+    let mut state = seed;
+    (0..size)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32 * 2.0 - 1.0) * scale
+        })
+        .collect()
+}
+
+fn assert_matches_default(input: &[f32], context: &ProcessingContext, config: &ProcessorConfig) {
+    let mut expected = input.to_vec();
+    DefaultLogitsProcessor::with_config(config.clone())
+        .process(&mut expected, context)
+        .expect("default processing");
+
+    let mut fused = input.to_vec();
+    process_logits_fused(&mut fused, context, config).expect("fused processing");
+
+    for (i, (e, f)) in expected.iter().zip(&fused).enumerate() {
+        if e.is_finite() || f.is_finite() {
+            assert!((e - f).abs() <= 1e-5, "logit {i}: expected {e}, got {f}");
+        }
+    }
+}
+
+#[test]
+fn test_fused_matches_default_processor() {
+    let history = vec![3, 17, 3, 250, 3, 17, 499, 4096];
+    let contexts = [
+        ProcessingContext::new(),
+        ProcessingContext::new().with_temperature(0.7),
+        ProcessingContext::new().with_top_k(Some(40)),
+        ProcessingContext::new().with_top_p(Some(0.9)),
+        ProcessingContext::new()
+            .with_temperature(1.3)
+            .with_top_k(Some(50))
+            .with_top_p(Some(0.8)),
+    ];
+    let configs = [
+        ProcessorConfig::default(),
+        ProcessorConfig::default()
+            .with_repetition_penalty(1.3)
+            .with_frequency_penalty(0.4)
+            .with_presence_penalty(0.2),
+    ];
+
+    for size in [5, 37, 500] {
+        let input = logits(size, size as u64, 6.0);
+        for context in &contexts {
+            let context = context.clone().with_token_history(history.clone());
+            for config in &configs {
+                assert_matches_default(&input, &context, config);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fused_top_k_keeps_k_best() {
+    let input = logits(1000, 11, 4.0);
+    let context = ProcessingContext::new().with_top_k(Some(10));
+    let mut fused = input.clone();
+    process_logits_fused(&mut fused, &context, &ProcessorConfig::default()).expect("fused");
+
+    let mut sorted = input.clone();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let kept: Vec<f32> = fused.iter().copied().filter(|x| x.is_finite()).collect();
+    assert_eq!(kept.len(), 10);
+    assert!(kept.iter().all(|x| *x >= sorted[9]));
+}
+
+#[test]
+fn test_fused_top_p_on_flat_distribution() {
+    // Far more tokens than nucleus candidates are needed to reach top-p
+    let input = logits(4000, 12, 0.5);
+    let context = ProcessingContext::new().with_top_p(Some(0.95));
+    assert_matches_default(&input, &context, &ProcessorConfig::default());
+}
+
+#[test]
+fn test_fused_top_p_on_peaked_distribution() {
+    let mut input = logits(4000, 13, 1.0);
+    input[1234] = 30.0;
+    input[3999] = 29.0;
+    let context = ProcessingContext::new().with_top_p(Some(0.9));
+    let mut fused = input.clone();
+    process_logits_fused(&mut fused, &context, &ProcessorConfig::default()).expect("fused");
+
+    let kept: Vec<usize> = (0..fused.len()).filter(|&i| fused[i].is_finite()).collect();
+    assert_eq!(kept, vec![1234, 3999]);
+}
+
+#[test]
+fn test_fused_frequency_penalty_covers_whole_vocabulary() {
+    let input = vec![1.0; 2000];
+    let context = ProcessingContext::new().with_token_history(vec![1500, 1500, 7]);
+    let config = ProcessorConfig::default().with_frequency_penalty(0.5);
+    let mut fused = input.clone();
+    process_logits_fused(&mut fused, &context, &config).expect("fused");
+
+    assert_eq!(fused[1500], 0.0);
+    assert_eq!(fused[7], 0.5);
+    assert_eq!(fused[8], 1.0);
+}
+
+#[test]
+fn test_fused_rejects_fully_masked_logits() {
+    let mut input = vec![f32::NEG_INFINITY; 20];
+    let result = process_logits_fused(
+        &mut input,
+        &ProcessingContext::new(),
+        &ProcessorConfig::default(),
+    );
+    assert!(matches!(result, Err(LogitsError::SamplingError(_))));
+
+    let mut empty: Vec<f32> = Vec::new();
+    assert!(
+        process_logits_fused(
+            &mut empty,
+            &ProcessingContext::new(),
+            &ProcessorConfig::default()
+        )
+        .is_ok()
+    );
+}
@@ -130,10 +130,15 @@ fn test_builder_api() {
         "Should have processed 1 token"
     );
     
-    // Note: Boolean constraints do not set is_complete() after matching a single token
-    // as the DFA may accept further input. This is expected behavior - the constraint
-    // validates tokens but doesn't mark the state as complete until reaching an explicit
-    // end state in the DFA, which doesn't occur for simple boolean patterns like (true|false).
+    // "true" is a complete match, so the state is final and EOS becomes valid
+    assert!(
+        progressing_state.is_complete(),
+        "State should be complete after matching 'true'"
+    );
+    assert!(
+        boolean_constraint.is_done(&progressing_state),
+        "Constraint should be done after matching 'true'"
+    );
 
     // Test get_allowed_tokens() 
     let fresh_state = boolean_constraint.new_state();
@@ -0,0 +1,64 @@
+use cyrup_simd::similarity::{
+    cosine_similarity, cosine_similarity_i8, dot_product_i8, quantize_i8,
+};
+
+/// Deterministic pseudo-random int8 vector
+fn vector_i8(len: usize, seed: u64) -> Vec<i8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 56) as u8 as i8
+        })
+        .collect()
+}
+
+fn reference_dot(a: &[i8], b: &[i8]) -> i32 {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| i32::from(x) * i32::from(y))
+        .sum()
+}
+
+#[test]
+fn test_dot_product_i8_matches_reference() {
+    // Lengths around the 64-byte register width exercise the masked tail
+    for len in [0, 1, 63, 64, 65, 127, 384, 1000, 4096] {
+        let a = vector_i8(len, 1);
+        let b = vector_i8(len, 2);
+        assert_eq!(dot_product_i8(&a, &b), reference_dot(&a, &b), "len {len}");
+    }
+}
+
+#[test]
+fn test_dot_product_i8_extremes() {
+    let a = vec![i8::MIN; 1000];
+    let b = vec![i8::MAX; 1000];
+    assert_eq!(dot_product_i8(&a, &b), -128 * 127 * 1000);
+    assert_eq!(dot_product_i8(&a, &a), 128 * 128 * 1000);
+}
+
+#[test]
+fn test_cosine_similarity_i8_tracks_f32() {
+    let a: Vec<f32> = (0..384).map(|i| (i as f32 * 0.37).sin()).collect();
+    let b: Vec<f32> = (0..384).map(|i| (i as f32 * 0.11).cos() + 0.3).collect();
+
+    let (qa, scale_a) = quantize_i8(&a);
+    let (qb, _) = quantize_i8(&b);
+    assert!(scale_a > 0.0);
+    assert!((qa[10] as f32 * scale_a - a[10]).abs() <= scale_a);
+
+    let exact = cosine_similarity(&a, &b);
+    assert!((cosine_similarity_i8(&qa, &qb) - exact).abs() < 0.01);
+    assert!((cosine_similarity_i8(&qa, &qa) - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_quantize_zero_vector() {
+    let (values, scale) = quantize_i8(&[0.0; 5]);
+    assert_eq!(values, vec![0; 5]);
+    assert_eq!(scale, 0.0);
+    assert_eq!(cosine_similarity_i8(&values, &values), 0.0);
+}
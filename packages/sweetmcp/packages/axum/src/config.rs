@@ -8,6 +8,7 @@ use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
 use crate::db::DatabaseConfig;
+use crate::plugin::pool::PoolConfig;
 
 /// Initialize the logger with the specified path and level
 pub fn init_logger(path: Option<&str>, level: Option<&str>) -> Result<()> {
@@ -232,6 +233,9 @@ pub struct PluginConfig {
     /// Optional environment configuration for the plugin runtime.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub env: Option<EnvConfig>,
+    /// Optional warm instance pool settings; defaults apply when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<PoolConfig>,
 }
 
 /// Represents the environment configuration for a plugin runtime.
@@ -400,6 +404,10 @@ impl Config {
             if plugin.path.is_empty() {
                 return Err(anyhow!("Plugin path cannot be empty"));
             }
+            if let Some(pool) = &plugin.pool {
+                pool.validate()
+                    .with_context(|| format!("Invalid pool for plugin '{}'", plugin.name))?;
+            }
         }
         
         Ok(())
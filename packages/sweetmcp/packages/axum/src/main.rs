@@ -0,0 +1,37 @@
+//! `sweet` - the standalone MCP server hosting WebAssembly plugins
+//!
+//! The gateway forwards MCP requests to this server (by default at
+//! `http://localhost:8080/rpc`, started with `sweet serve --http 127.0.0.1:8080`).
+
+use anyhow::{Context, Result};
+use sweetmcp_axum::{
+    Config, init_logger, parse_config,
+    plugin::load_plugins,
+    ui::{self, Commands},
+};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = ui::parse_cli_args();
+
+    init_logger(cli.log_path.as_deref(), Some(&cli.log_level))?;
+
+    let content = std::fs::read_to_string(&cli.config)
+        .with_context(|| format!("Failed to read config file: {}", cli.config.display()))?;
+    let config: Config = parse_config(&content, &cli.config)?;
+
+    // Only serving needs the plugins loaded
+    let plugins = match cli.command {
+        Commands::Serve(_) => config.plugins.as_slice(),
+        _ => &[],
+    };
+    let plugin_manager = load_plugins(plugins, cli.insecure_skip_signature).await;
+    plugin_manager.set_initialized();
+    log::info!(
+        "Loaded {} plugins providing {} tools",
+        plugin_manager.plugin_count(),
+        plugin_manager.tool_count()
+    );
+
+    ui::run_ui_with_state(cli, config, plugin_manager).await
+}
@@ -9,6 +9,7 @@ use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use tokio::sync::oneshot;
 
+use super::pool::{self, PluginPool};
 use crate::{
     config::PluginConfig,
     container_registry::pull_and_extract_oci_image,
@@ -19,8 +20,8 @@ use crate::{
 /// Lock-free implementation using DashMap for blazing-fast concurrent access.
 #[derive(Clone, RpcResource)]
 pub struct PluginManager {
-    /// Lock-free plugin storage using DashMap for concurrent access
-    pub plugins: Arc<DashMap<String, Plugin>>,
+    /// Lock-free storage of each plugin's warm instance pool
+    pub plugins: Arc<DashMap<String, Arc<PluginPool>>>,
     /// Lock-free cache to map tool names to plugin names
     pub tool_to_plugin: Arc<DashMap<String, String>>,
     /// Lock-free cache to map prompt names to plugin names and prompt metadata
@@ -150,6 +151,19 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Instance pool of a plugin, cloned out so no map guard is held
+    pub fn pool(&self, plugin_name: &str) -> Option<Arc<PluginPool>> {
+        self.plugins.get(plugin_name).map(|entry| entry.value().clone())
+    }
+
+    /// Instance pool statistics in the Prometheus text format
+    pub fn render_metrics(&self) -> String {
+        let mut pools: Vec<Arc<PluginPool>> =
+            self.plugins.iter().map(|entry| entry.value().clone()).collect();
+        pools.sort_by(|a, b| a.name().cmp(b.name()));
+        pool::render_prometheus(&pools)
+    }
+
     /// Get plugin manager statistics
     /// 
     /// Returns counts of active plugins and tools
@@ -285,7 +299,7 @@ pub async fn load_plugins(
                 manifest = manifest.with_config_key(key, value);
             }
         }
-        let mut plugin = match PluginBuilder::new(&manifest).with_wasi(true).build() {
+        let mut plugin = match pool::build_instance(&manifest) {
             Ok(p) => p,
             Err(e) => {
                 log::error!(
@@ -441,8 +455,16 @@ pub async fn load_plugins(
             }
         }
 
-        // Store the plugin itself using lock-free DashMap
-        manager.plugins.insert(plugin_name.clone(), plugin);
+        // Keep the discovery instance warm in the plugin's pool
+        let pool = Arc::new(PluginPool::new(
+            plugin_name.clone(),
+            manifest,
+            plugin_cfg.pool.clone().unwrap_or_default(),
+            plugin,
+        ));
+        pool.recycle_idle();
+        pool.spawn_recycler();
+        manager.plugins.insert(plugin_name.clone(), pool);
         log::info!("Loaded plugin {} successfully", plugin_name);
     }
 
@@ -1,8 +1,12 @@
 pub mod build;
 pub mod bulk_operations;
 pub mod manager;
+pub mod pool;
 pub mod service;
+pub mod voice;
 
 // Re-export key items
 pub use build::{PluginBuildStrategy, build_all_plugins_in_dir, build_single_plugin_at_path};
 pub use manager::{PluginManager, load_plugins};
+pub use pool::{Lease, PluginPool, PoolConfig};
+pub use voice::{register_voice_host_functions, set_voice_host};
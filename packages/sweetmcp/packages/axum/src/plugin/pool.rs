@@ -0,0 +1,537 @@
+//! Warm instance pools for WASM plugins
+//!
+//! Every plugin keeps a pool of ready instances so concurrent calls run in
+//! parallel instead of queueing on a single instance. A call checks out the
+//! most recently returned instance, keeping the warmest ones busy, and creates
+//! a new one only when none is idle and the pool is below `max_instances`.
+//! Calls of one tool can be limited further with `tool_concurrency`.
+//!
+//! Instances are replaced after `recycle_after_calls` calls or
+//! `max_instance_age_secs` seconds, and a periodic sweep drops aged idle
+//! instances and refills the pool to `min_instances`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use dashmap::DashMap;
+use extism::{Manifest, Plugin, PluginBuilder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Instance pool settings for one plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PoolConfig {
+    /// Instances kept warm even when idle
+    pub min_instances: usize,
+    /// Most instances alive at once; further calls wait for one to return
+    pub max_instances: usize,
+    /// Calls of a tool running at once, by tool name
+    pub tool_concurrency: HashMap<String, usize>,
+    /// Limit for tools not in `tool_concurrency`; `max_instances` when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_tool_concurrency: Option<usize>,
+    /// Replace an instance after this many calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle_after_calls: Option<u64>,
+    /// Replace an instance once it is this many seconds old
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_instance_age_secs: Option<u64>,
+    /// Seconds between sweeps of idle instances
+    pub recycle_interval_secs: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            min_instances: 1,
+            max_instances: 4,
+            tool_concurrency: HashMap::new(),
+            default_tool_concurrency: None,
+            recycle_after_calls: None,
+            max_instance_age_secs: None,
+            recycle_interval_secs: 60,
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Check the limits are usable
+    pub fn validate(&self) -> Result<()> {
+        if self.max_instances == 0 {
+            return Err(anyhow!("pool max_instances must be at least 1"));
+        }
+        if self.min_instances > self.max_instances {
+            return Err(anyhow!(
+                "pool min_instances ({}) exceeds max_instances ({})",
+                self.min_instances,
+                self.max_instances
+            ));
+        }
+        if self.default_tool_concurrency == Some(0) {
+            return Err(anyhow!("pool default_tool_concurrency must be at least 1"));
+        }
+        if let Some((tool, _)) = self.tool_concurrency.iter().find(|(_, limit)| **limit == 0) {
+            return Err(anyhow!(
+                "pool tool_concurrency for '{}' must be at least 1",
+                tool
+            ));
+        }
+        if self.recycle_after_calls == Some(0) || self.max_instance_age_secs == Some(0) {
+            return Err(anyhow!(
+                "pool recycle_after_calls and max_instance_age_secs must be at least 1"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Calls of `tool` allowed at once
+    fn tool_limit(&self, tool: &str) -> usize {
+        self.tool_concurrency
+            .get(tool)
+            .copied()
+            .or(self.default_tool_concurrency)
+            .unwrap_or(self.max_instances)
+            .max(1)
+    }
+}
+
+/// Create a plugin instance from its manifest, with the host functions every
+/// plugin gets
+pub fn build_instance(manifest: &Manifest) -> Result<Plugin, extism::Error> {
+    let builder = PluginBuilder::new(manifest).with_wasi(true);
+    let builder = super::voice::register_voice_host_functions(builder);
+    builder.build()
+}
+
+/// An instance with its age and use
+struct PooledInstance {
+    plugin: Plugin,
+    created: Instant,
+    calls: u64,
+}
+
+/// Running totals exported as metrics
+#[derive(Default)]
+struct PoolCounters {
+    created: AtomicU64,
+    recycled: AtomicU64,
+    build_failures: AtomicU64,
+    checkouts: AtomicU64,
+    wait_micros: AtomicU64,
+    waiting: AtomicUsize,
+    busy: AtomicUsize,
+}
+
+/// Counts a checkout as waiting until dropped, including when the caller
+/// gives up
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        waiting.fetch_add(1, Ordering::Relaxed);
+        Self(waiting)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Warm instances of one plugin
+pub struct PluginPool {
+    name: String,
+    manifest: Manifest,
+    config: PoolConfig,
+    /// Idle instances, most recently returned last
+    idle: Mutex<Vec<PooledInstance>>,
+    /// One permit per instance that may be alive
+    slots: Arc<Semaphore>,
+    /// Per-tool call limits, created on first use
+    tool_slots: DashMap<String, Arc<Semaphore>>,
+    counters: PoolCounters,
+}
+
+impl PluginPool {
+    /// Pool for a plugin, starting with `seed`, an instance already created
+    /// from `manifest`
+    pub fn new(name: String, manifest: Manifest, config: PoolConfig, seed: Plugin) -> Self {
+        let counters = PoolCounters::default();
+        counters.created.store(1, Ordering::Relaxed);
+        Self {
+            name,
+            manifest,
+            slots: Arc::new(Semaphore::new(config.max_instances.max(1))),
+            config,
+            idle: Mutex::new(vec![PooledInstance {
+                plugin: seed,
+                created: Instant::now(),
+                calls: 0,
+            }]),
+            tool_slots: DashMap::new(),
+            counters,
+        }
+    }
+
+    /// Name of the pooled plugin
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Take an instance for one call, waiting for a free slot
+    ///
+    /// With `tool`, the call also counts against that tool's concurrency
+    /// limit. The instance returns to the pool when the lease is dropped.
+    pub async fn checkout(self: &Arc<Self>, tool: Option<&str>) -> Result<Lease> {
+        let started = Instant::now();
+        let waiting = WaitingGuard::new(&self.counters.waiting);
+        let permits = async {
+            // Wait on the tool first so a saturated tool holds no instance slots
+            let tool_permit = match tool {
+                Some(tool) => Some(self.tool_slots(tool).acquire_owned().await?),
+                None => None,
+            };
+            let slot = self.slots.clone().acquire_owned().await?;
+            Ok::<_, tokio::sync::AcquireError>((slot, tool_permit))
+        }
+        .await;
+        drop(waiting);
+        let (slot, tool_permit) =
+            permits.map_err(|_| anyhow!("Plugin pool '{}' is closed", self.name))?;
+        self.counters
+            .wait_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+
+        let instance = match self.take_idle() {
+            Some(instance) => instance,
+            None => self.create()?,
+        };
+        self.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+        self.counters.busy.fetch_add(1, Ordering::Relaxed);
+        Ok(Lease {
+            pool: self.clone(),
+            instance: Some(instance),
+            discard: false,
+            _slot: slot,
+            _tool_permit: tool_permit,
+        })
+    }
+
+    /// Drop aged idle instances and refill the pool to `min_instances`
+    pub fn recycle_idle(&self) {
+        let missing = {
+            let Ok(mut idle) = self.idle.lock() else {
+                return;
+            };
+            let before = idle.len();
+            idle.retain(|instance| !self.expired(instance));
+            let recycled = before - idle.len();
+            if recycled > 0 {
+                self.counters
+                    .recycled
+                    .fetch_add(recycled as u64, Ordering::Relaxed);
+                log::debug!(
+                    "Recycled {} idle instances of plugin {}",
+                    recycled,
+                    self.name
+                );
+            }
+            let alive = idle.len() + self.counters.busy.load(Ordering::Relaxed);
+            self.config.min_instances.saturating_sub(alive)
+        };
+
+        // Build outside the lock so checkouts are not held up
+        for _ in 0..missing {
+            match self.create() {
+                Ok(instance) => {
+                    if let Ok(mut idle) = self.idle.lock() {
+                        idle.insert(0, instance);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to warm plugin {}: {}", self.name, e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Sweep idle instances every `recycle_interval_secs` until the pool is
+    /// dropped
+    pub fn spawn_recycler(self: &Arc<Self>) {
+        let pool = Arc::downgrade(self);
+        let period = Duration::from_secs(self.config.recycle_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                pool.recycle_idle();
+            }
+        });
+    }
+
+    /// Most recently returned idle instance that is still fresh
+    fn take_idle(&self) -> Option<PooledInstance> {
+        let mut idle = self.idle.lock().ok()?;
+        while let Some(instance) = idle.pop() {
+            if !self.expired(&instance) {
+                return Some(instance);
+            }
+            self.counters.recycled.fetch_add(1, Ordering::Relaxed);
+        }
+        None
+    }
+
+    fn create(&self) -> Result<PooledInstance> {
+        let plugin = build_instance(&self.manifest).map_err(|e| {
+            self.counters.build_failures.fetch_add(1, Ordering::Relaxed);
+            anyhow!("Failed to create instance of plugin '{}': {}", self.name, e)
+        })?;
+        self.counters.created.fetch_add(1, Ordering::Relaxed);
+        Ok(PooledInstance {
+            plugin,
+            created: Instant::now(),
+            calls: 0,
+        })
+    }
+
+    fn expired(&self, instance: &PooledInstance) -> bool {
+        self.config
+            .recycle_after_calls
+            .is_some_and(|max| instance.calls >= max)
+            || self
+                .config
+                .max_instance_age_secs
+                .is_some_and(|max| instance.created.elapsed() >= Duration::from_secs(max))
+    }
+
+    fn tool_slots(&self, tool: &str) -> Arc<Semaphore> {
+        self.tool_slots
+            .entry(tool.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.config.tool_limit(tool))))
+            .clone()
+    }
+
+    /// Return or drop an instance at the end of a lease
+    fn release(&self, mut instance: PooledInstance, discard: bool) {
+        self.counters.busy.fetch_sub(1, Ordering::Relaxed);
+        instance.calls += 1;
+        if discard || self.expired(&instance) {
+            self.counters.recycled.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.push(instance);
+        }
+    }
+}
+
+/// A plugin instance checked out for one call
+pub struct Lease {
+    pool: Arc<PluginPool>,
+    instance: Option<PooledInstance>,
+    discard: bool,
+    _slot: OwnedSemaphorePermit,
+    _tool_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Lease {
+    /// Drop the instance instead of returning it, e.g. after it trapped
+    pub fn discard(&mut self) {
+        self.discard = true;
+    }
+}
+
+impl Deref for Lease {
+    type Target = Plugin;
+
+    fn deref(&self) -> &Plugin {
+        match &self.instance {
+            Some(instance) => &instance.plugin,
+            None => unreachable!("instance is only taken on drop"),
+        }
+    }
+}
+
+impl DerefMut for Lease {
+    fn deref_mut(&mut self) -> &mut Plugin {
+        match &mut self.instance {
+            Some(instance) => &mut instance.plugin,
+            None => unreachable!("instance is only taken on drop"),
+        }
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            self.pool.release(instance, self.discard);
+        }
+    }
+}
+
+/// Pool statistics of every plugin in the Prometheus text format
+pub fn render_prometheus(pools: &[Arc<PluginPool>]) -> String {
+    let mut out = String::new();
+    let header = |out: &mut String, name: &str, help: &str, kind: &str| {
+        let _ = writeln!(out, "# HELP sweetmcp_plugin_pool_{} {}", name, help);
+        let _ = writeln!(out, "# TYPE sweetmcp_plugin_pool_{} {}", name, kind);
+    };
+
+    header(
+        &mut out,
+        "instances",
+        "Live plugin instances by state",
+        "gauge",
+    );
+    for pool in pools {
+        let idle = pool.idle.lock().map(|idle| idle.len()).unwrap_or(0);
+        let busy = pool.counters.busy.load(Ordering::Relaxed);
+        let plugin = escape(&pool.name);
+        let _ = writeln!(
+            out,
+            "sweetmcp_plugin_pool_instances{{plugin=\"{}\",state=\"idle\"}} {}",
+            plugin, idle
+        );
+        let _ = writeln!(
+            out,
+            "sweetmcp_plugin_pool_instances{{plugin=\"{}\",state=\"busy\"}} {}",
+            plugin, busy
+        );
+    }
+
+    let per_pool: [(&str, &str, &str, fn(&PluginPool) -> String); 7] = [
+        (
+            "max_instances",
+            "Most instances a plugin may have alive",
+            "gauge",
+            |pool| pool.config.max_instances.to_string(),
+        ),
+        (
+            "waiting",
+            "Calls waiting for an instance",
+            "gauge",
+            |pool| pool.counters.waiting.load(Ordering::Relaxed).to_string(),
+        ),
+        (
+            "instances_created_total",
+            "Plugin instances created",
+            "counter",
+            |pool| pool.counters.created.load(Ordering::Relaxed).to_string(),
+        ),
+        (
+            "instances_recycled_total",
+            "Plugin instances dropped for age, use or errors",
+            "counter",
+            |pool| pool.counters.recycled.load(Ordering::Relaxed).to_string(),
+        ),
+        (
+            "build_failures_total",
+            "Plugin instances that failed to start",
+            "counter",
+            |pool| {
+                pool.counters
+                    .build_failures
+                    .load(Ordering::Relaxed)
+                    .to_string()
+            },
+        ),
+        (
+            "checkouts_total",
+            "Calls given a plugin instance",
+            "counter",
+            |pool| pool.counters.checkouts.load(Ordering::Relaxed).to_string(),
+        ),
+        (
+            "wait_seconds_total",
+            "Time calls spent waiting for an instance",
+            "counter",
+            |pool| (pool.counters.wait_micros.load(Ordering::Relaxed) as f64 / 1e6).to_string(),
+        ),
+    ];
+    for (name, help, kind, value) in per_pool {
+        header(&mut out, name, help, kind);
+        for pool in pools {
+            let _ = writeln!(
+                out,
+                "sweetmcp_plugin_pool_{}{{plugin=\"{}\"}} {}",
+                name,
+                escape(&pool.name),
+                value(pool)
+            );
+        }
+    }
+
+    header(
+        &mut out,
+        "tool_in_flight",
+        "Calls of a tool running or holding an instance",
+        "gauge",
+    );
+    for pool in pools {
+        for (tool, limit, available) in tool_usage(pool) {
+            let _ = writeln!(
+                out,
+                "sweetmcp_plugin_pool_tool_in_flight{{plugin=\"{}\",tool=\"{}\"}} {}",
+                escape(&pool.name),
+                escape(&tool),
+                limit.saturating_sub(available)
+            );
+        }
+    }
+    header(
+        &mut out,
+        "tool_max_concurrency",
+        "Calls of a tool allowed at once",
+        "gauge",
+    );
+    for pool in pools {
+        for (tool, limit, _) in tool_usage(pool) {
+            let _ = writeln!(
+                out,
+                "sweetmcp_plugin_pool_tool_max_concurrency{{plugin=\"{}\",tool=\"{}\"}} {}",
+                escape(&pool.name),
+                escape(&tool),
+                limit
+            );
+        }
+    }
+
+    out
+}
+
+/// Limit and free permits of every tool called so far, by tool name
+fn tool_usage(pool: &PluginPool) -> Vec<(String, usize, usize)> {
+    let mut usage: Vec<_> = pool
+        .tool_slots
+        .iter()
+        .map(|entry| {
+            (
+                entry.key().clone(),
+                pool.config.tool_limit(entry.key()),
+                entry.value().available_permits(),
+            )
+        })
+        .collect();
+    usage.sort();
+    usage
+}
+
+/// Escape a Prometheus label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
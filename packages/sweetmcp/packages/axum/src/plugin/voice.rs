@@ -0,0 +1,37 @@
+//! Voice backend behind the voice plugin's host functions
+//!
+//! Every plugin is built with the `voice_request` host function so the
+//! `voice` plugin can speak and listen through the gateway's audio devices.
+//! The backend defaults to local Whisper and Parler-TTS models from
+//! `cyrup_candle`, which download on the first voice request.
+
+use std::sync::OnceLock;
+
+use cyrup_candle::domain::voice::LocalVoiceService;
+use extism::PluginBuilder;
+use sweetmcp_voice_tools::VoiceHost;
+
+static VOICE_HOST: OnceLock<VoiceHost> = OnceLock::new();
+
+/// Install the voice backend served to plugins
+///
+/// Must be called before plugins are loaded. Returns `false` when a backend
+/// is already in place.
+pub fn set_voice_host(host: VoiceHost) -> bool {
+    VOICE_HOST.set(host).is_ok()
+}
+
+/// Voice backend served to plugins, installing the local one on first use
+///
+/// Must be called within a tokio runtime.
+pub fn voice_host() -> &'static VoiceHost {
+    VOICE_HOST.get_or_init(|| {
+        log::info!("Using local Whisper/Parler-TTS voice service for plugins");
+        VoiceHost::new(LocalVoiceService::new())
+    })
+}
+
+/// Register the voice host functions with a plugin under construction
+pub fn register_voice_host_functions(builder: PluginBuilder) -> PluginBuilder {
+    voice_host().register(builder)
+}
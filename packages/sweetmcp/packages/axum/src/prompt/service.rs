@@ -61,13 +61,19 @@ pub fn prompts_get_pending(
         };
 
         let template_content: String = {
-            // Lock-free access to plugin using DashMap
-            let mut plugin_entry = match plugin_manager.plugins.get_mut(&plugin_name) {
-                Some(entry) => entry,
-                None => {
+            let Some(pool) = plugin_manager.pool(&plugin_name) else {
+                let _ = tx.send(Err(HandlerError::new(format!(
+                    "Internal error: Plugin '{}' not found",
+                    plugin_name
+                ))));
+                return;
+            };
+            let mut plugin_entry = match pool.checkout(None).await {
+                Ok(plugin) => plugin,
+                Err(e) => {
                     let _ = tx.send(Err(HandlerError::new(format!(
-                        "Internal error: Plugin '{}' not found",
-                        plugin_name
+                        "Internal error: {}",
+                        e
                     ))));
                     return;
                 }
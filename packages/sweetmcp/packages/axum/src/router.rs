@@ -129,6 +129,8 @@ pub async fn run_server(
 
     if serve_args.daemon {
         run_daemon(plugin_manager, serve_args).await
+    } else if let Some(bind_addr) = &serve_args.http {
+        run_http_server(plugin_manager, bind_addr).await
     } else {
         run_stdio_server(plugin_manager).await
     }
@@ -166,6 +168,7 @@ async fn run_stdio_server(plugin_manager: PluginManager) -> Result<()> {
     let stdin = tokio::io::stdin();
     let reader = BufReader::new(stdin);
     let mut lines = reader.lines();
+    let mut stdout = tokio::io::stdout();
 
     info!("Ready to process JSON-RPC messages");
 
@@ -198,44 +201,43 @@ async fn run_stdio_server(plugin_manager: PluginManager) -> Result<()> {
 
                         let id = rpc_request.id.clone();
 
-                        match rpc_router.call(rpc_request).await {
+                        let response_json = match rpc_router.call(rpc_request).await {
                             Ok(call_response) => {
-                                if !call_response.value.is_null() {
+                                if call_response.value.is_null() {
+                                    None
+                                } else {
                                     let response = JsonRpcResponse::new(id, call_response.value);
-                                    if let Ok(response_json) = serde_json::to_string(&response) {
-                                        log::debug!("Response: {}", response_json);
-                                        log::info!("{}", response_json);
-                                    }
+                                    serde_json::to_string(&response).ok()
                                 }
                             }
-                            Err(error) => match &error.error {
-                                rpc_router::Error::Handler(handler) => {
-                                    if let Some(error_value) = handler.get::<Value>() {
-                                        let json_error = json!({
-                                            "jsonrpc": JSONRPC_VERSION,
-                                            "error": error_value,
-                                            "id": id
-                                        });
-                                        if let Ok(response) = serde_json::to_string(&json_error) {
-                                            log::error!("Error response: {}", response);
-                                        }
-                                    }
-                                }
-                                _ => {
+                            Err(error) => {
+                                let error_value = match &error.error {
+                                    rpc_router::Error::Handler(handler) => handler.get::<Value>().cloned(),
+                                    _ => None,
+                                };
+                                let error_value = error_value.unwrap_or_else(|| {
                                     log::error!("Unexpected error: {:?}", error);
-                                    let json_error = json!({
-                                        "jsonrpc": JSONRPC_VERSION,
-                                        "error": {
-                                            "code": -1,
-                                            "message": "Invalid JSON-RPC call"
-                                        },
-                                        "id": id
-                                    });
-                                    if let Ok(response) = serde_json::to_string(&json_error) {
-                                        log::error!("Invalid JSON-RPC call: {}", response);
-                                    }
-                                }
-                            },
+                                    json!({
+                                        "code": -1,
+                                        "message": "Invalid JSON-RPC call"
+                                    })
+                                });
+                                let json_error = json!({
+                                    "jsonrpc": JSONRPC_VERSION,
+                                    "error": error_value,
+                                    "id": id
+                                });
+                                log::error!("Error response: {}", json_error);
+                                serde_json::to_string(&json_error).ok()
+                            }
+                        };
+
+                        // Responses are newline-delimited on stdout; logs go to stderr
+                        if let Some(response_json) = response_json {
+                            log::debug!("Response: {}", response_json);
+                            stdout.write_all(response_json.as_bytes()).await?;
+                            stdout.write_all(b"\n").await?;
+                            stdout.flush().await?;
                         }
                     }
                 }
@@ -252,7 +254,7 @@ pub async fn run_http_server(plugin_manager: PluginManager, bind_addr: &str) ->
     info!("Starting MCP JSON-RPC server (HTTP mode on {})", bind_addr);
 
     // Build RPC router with lock-free plugin manager
-    let rpc_router = Arc::new(build_rpc_router(plugin_manager));
+    let rpc_router = Arc::new(build_rpc_router(plugin_manager.clone()));
 
     // Bind TCP listener
     let listener = TcpListener::bind(bind_addr)
@@ -266,9 +268,10 @@ pub async fn run_http_server(plugin_manager: PluginManager, bind_addr: &str) ->
             Ok((stream, addr)) => {
                 debug!("New HTTP connection from {}", addr);
                 let router = rpc_router.clone();
+                let plugin_manager = plugin_manager.clone();
 
                 tokio::spawn(async move {
-                    if let Err(e) = handle_http_connection(stream, router).await {
+                    if let Err(e) = handle_http_connection(stream, router, plugin_manager).await {
                         error!("Failed to handle HTTP connection: {}", e);
                     }
                 });
@@ -284,6 +287,7 @@ pub async fn run_http_server(plugin_manager: PluginManager, bind_addr: &str) ->
 async fn handle_http_connection(
     mut stream: tokio::net::TcpStream,
     rpc_router: Arc<RpcRouter>,
+    plugin_manager: PluginManager,
 ) -> Result<()> {
     use tokio::io::AsyncReadExt;
 
@@ -314,15 +318,37 @@ async fn handle_http_connection(
     };
 
     // Route based on path prefix
+    // /metrics → Prometheus plugin pool statistics
     // /v1/* → OpenAI REST API (OPENAI_2 handler)
     // Other → JSON-RPC (existing MCP protocol)
-    if path.starts_with("/v1/") {
+    if path == "/metrics" {
+        handle_metrics_request(&plugin_manager, &mut stream).await
+    } else if path.starts_with("/v1/") {
         handle_openai_request(path, body, &mut stream).await
     } else {
         handle_jsonrpc_request(body, rpc_router, &mut stream).await
     }
 }
 
+/// Serve plugin instance pool statistics in the Prometheus text format
+async fn handle_metrics_request(
+    plugin_manager: &PluginManager,
+    stream: &mut tokio::net::TcpStream,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let metrics = plugin_manager.render_metrics();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+        metrics.len(),
+        metrics
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+
+    Ok(())
+}
+
 /// Handle OpenAI-compatible REST API requests
 ///
 /// Routes:
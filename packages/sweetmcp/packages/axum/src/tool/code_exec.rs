@@ -0,0 +1,327 @@
+//! Gateway-native `code_exec` tools backed by cylo's global instance manager.
+//!
+//! Unlike WASM plugins these run in the server process, so they can drive the
+//! real sandbox backends (LandLock, FireCracker, Apple containers, Windows
+//! AppContainers) and keep interpreter sessions alive between calls.
+
+use std::{collections::HashMap, time::Duration};
+
+use cylo::{
+    Cylo, CyloError, ExecutionRequest, ExecutionResult, get_available_backends,
+    get_recommended_backend, global_instance_manager,
+};
+use serde_json::{Value, json};
+
+use super::model::{
+    CallToolResult, CallToolResultContent, Tool, ToolCallRequestParams, ToolInputSchema,
+    ToolInputSchemaProperty,
+};
+
+pub const EXECUTE: &str = "code_exec_execute";
+pub const CREATE_SESSION: &str = "code_exec_create_session";
+pub const EXECUTE_IN_SESSION: &str = "code_exec_execute_in_session";
+pub const LIST_BACKENDS: &str = "code_exec_list_backends";
+
+/// Environment variable selecting the default backend (`LandLock`, `FireCracker`, `Apple`,
+/// `Windows`)
+const BACKEND_ENV: &str = "SWEETMCP_CODE_EXEC_BACKEND";
+/// Environment variable with the default backend's jail path, sandbox root or image
+const BACKEND_CONFIG_ENV: &str = "SWEETMCP_CODE_EXEC_CONFIG";
+
+/// Whether `name` is one of the native code execution tools
+pub fn is_code_exec_tool(name: &str) -> bool {
+    matches!(name, EXECUTE | CREATE_SESSION | EXECUTE_IN_SESSION | LIST_BACKENDS)
+}
+
+/// Tool descriptions advertised alongside plugin tools
+pub fn tools() -> Vec<Tool> {
+    let backend_props = || {
+        [
+            (
+                "backend",
+                prop(
+                    "string",
+                    "Backend to run on (LandLock, FireCracker, Apple, Windows); defaults to the server's configured backend",
+                ),
+            ),
+            (
+                "backend_config",
+                prop(
+                    "string",
+                    "Jail directory (LandLock), sandbox root (Windows) or image (FireCracker, Apple)",
+                ),
+            ),
+        ]
+    };
+
+    vec![
+        tool(
+            EXECUTE,
+            "Execute code in an isolated sandbox and return its stdout, stderr and exit code.",
+            [
+                ("code", prop("string", "Source code to execute")),
+                (
+                    "language",
+                    prop(
+                        "string",
+                        "Language: python, javascript, typescript, ruby, rust, go, java, c, cpp or bash",
+                    ),
+                ),
+                ("stdin", prop("string", "Data written to the program's stdin")),
+                ("timeout_secs", prop("integer", "Wall-clock timeout in seconds")),
+            ]
+            .into_iter()
+            .chain(backend_props()),
+            &["code", "language"],
+        ),
+        tool(
+            CREATE_SESSION,
+            "Start a persistent python or javascript interpreter whose variables and imports survive between calls.",
+            [(
+                "language",
+                prop("string", "Interpreter language: python or javascript"),
+            )]
+            .into_iter()
+            .chain(backend_props()),
+            &["language"],
+        ),
+        tool(
+            EXECUTE_IN_SESSION,
+            "Execute a snippet in a session created with code_exec_create_session.",
+            [
+                ("session_id", prop("string", "Session identifier")),
+                ("code", prop("string", "Snippet to execute")),
+            ],
+            &["session_id", "code"],
+        ),
+        tool(
+            LIST_BACKENDS,
+            "List sandbox backends available on this server, plus live instances and sessions.",
+            [],
+            &[],
+        ),
+    ]
+}
+
+/// Execute a native code execution tool call
+pub async fn call(params: ToolCallRequestParams) -> CallToolResult {
+    let args = params.arguments.unwrap_or_else(|| json!({}));
+
+    let outcome = match params.name.as_str() {
+        EXECUTE => execute(&args).await,
+        CREATE_SESSION => create_session(&args).await,
+        EXECUTE_IN_SESSION => execute_in_session(&args).await,
+        LIST_BACKENDS => list_backends(),
+        other => Err(format!("Unknown code_exec tool: {other}")),
+    };
+
+    match outcome {
+        Ok((value, is_error)) => CallToolResult {
+            content: vec![CallToolResultContent::Text {
+                text: value.to_string(),
+            }],
+            is_error,
+        },
+        Err(message) => CallToolResult {
+            content: vec![CallToolResultContent::Text { text: message }],
+            is_error: true,
+        },
+    }
+}
+
+async fn execute(args: &Value) -> Result<(Value, bool), String> {
+    let code = required_str(args, "code")?;
+    let language = required_str(args, "language")?;
+    let instance_id = ensure_instance(args).await?;
+
+    let mut request = ExecutionRequest::new(code, language);
+    if let Some(stdin) = args.get("stdin").and_then(Value::as_str) {
+        request = request.with_stdin(stdin.as_bytes().to_vec());
+    }
+    if let Some(secs) = args.get("timeout_secs").and_then(Value::as_u64) {
+        request = request.with_timeout(Duration::from_secs(secs));
+    }
+
+    let result = global_instance_manager()
+        .execute(&instance_id, request)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    Ok(result_json(&instance_id, &result))
+}
+
+async fn create_session(args: &Value) -> Result<(Value, bool), String> {
+    let language = required_str(args, "language")?;
+    let instance_id = ensure_instance(args).await?;
+
+    let handle = global_instance_manager()
+        .create_session(&instance_id, language)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let value = serde_json::to_value(&handle).map_err(|e| e.to_string())?;
+    Ok((value, false))
+}
+
+async fn execute_in_session(args: &Value) -> Result<(Value, bool), String> {
+    let session_id = required_str(args, "session_id")?;
+    let code = required_str(args, "code")?;
+
+    let result = global_instance_manager()
+        .execute_in_session(session_id, code)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let instance_id = global_instance_manager()
+        .list_sessions()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|handle| handle.id == session_id)
+        .map(|handle| handle.instance_id)
+        .unwrap_or_default();
+    Ok(result_json(&instance_id, &result))
+}
+
+fn list_backends() -> Result<(Value, bool), String> {
+    let manager = global_instance_manager();
+    let instances = manager.list_instances().map_err(|e| e.to_string())?;
+    let sessions = manager.list_sessions().map_err(|e| e.to_string())?;
+
+    Ok((
+        json!({
+            "available": get_available_backends(),
+            "recommended": get_recommended_backend(),
+            "default": default_backend().map(|(backend, _)| backend),
+            "instances": instances,
+            "sessions": sessions,
+        }),
+        false,
+    ))
+}
+
+/// Register (once) the instance for the requested or default backend
+///
+/// One instance is kept per backend; the config of the first registration wins.
+///
+/// # Returns
+/// Instance ID, e.g. `LandLock:code-exec-landlock`
+async fn ensure_instance(args: &Value) -> Result<String, String> {
+    let requested = args.get("backend").and_then(Value::as_str);
+    let (backend, config) = match requested {
+        Some(backend) => {
+            let config = args
+                .get("backend_config")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or_else(|| default_config(backend))
+                .ok_or_else(|| format!("backend_config is required for backend {backend}"))?;
+            (backend.to_string(), config)
+        }
+        None => default_backend()
+            .ok_or_else(|| "No code execution backend is available on this server".to_string())?,
+    };
+
+    let env = match backend.to_lowercase().as_str() {
+        "landlock" => Cylo::LandLock(config),
+        "firecracker" => Cylo::FireCracker(config),
+        "apple" => Cylo::Apple(config),
+        "windows" => Cylo::Windows(config),
+        _ => return Err(format!("Unknown backend: {backend}")),
+    };
+
+    let instance = env.instance(format!("code-exec-{}", backend.to_lowercase()));
+    let instance_id = instance.id();
+
+    match global_instance_manager().register_instance(instance).await {
+        Ok(Ok(())) | Ok(Err(CyloError::InstanceConflict { .. })) => Ok(instance_id),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Backend and config from the environment, else the platform's pick
+fn default_backend() -> Option<(String, String)> {
+    let backend = std::env::var(BACKEND_ENV)
+        .ok()
+        .or_else(get_recommended_backend)
+        .or_else(|| get_available_backends().into_iter().next())?;
+    let config = std::env::var(BACKEND_CONFIG_ENV)
+        .ok()
+        .or_else(|| default_config(&backend))?;
+    Some((backend, config))
+}
+
+fn default_config(backend: &str) -> Option<String> {
+    match backend.to_lowercase().as_str() {
+        "landlock" => Some("/tmp/sweetmcp-code-exec".to_string()),
+        "firecracker" | "apple" => Some("python:alpine3.20".to_string()),
+        "windows" => Some(
+            std::env::temp_dir()
+                .join("sweetmcp-code-exec")
+                .display()
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+fn result_json(instance_id: &str, result: &ExecutionResult) -> (Value, bool) {
+    (
+        json!({
+            "instance": instance_id,
+            "exit_code": result.exit_code,
+            "stdout": result.stdout,
+            "stderr": result.stderr,
+            "duration_ms": result.duration.as_millis() as u64,
+            "limit_exceeded": result.limit_exceeded.map(|limit| limit.reason()),
+            "failure": result.failure().map(|failure| failure.as_str()),
+            "signal": result.termination_signal(),
+            "cpu_time_ms": result.resource_usage.cpu_time_ms,
+            "peak_memory": result.resource_usage.peak_memory,
+            "stdout_truncated": result.stdout_truncated,
+            "stderr_truncated": result.stderr_truncated,
+            "seccomp_violation": result.seccomp_violation,
+            "session_id": result.metadata.get("session_id"),
+        }),
+        !result.is_success(),
+    )
+}
+
+fn required_str<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing required string argument '{key}'"))
+}
+
+fn prop(type_name: &str, description: &str) -> ToolInputSchemaProperty {
+    ToolInputSchemaProperty {
+        type_name: Some(type_name.to_string()),
+        enum_values: None,
+        description: Some(description.to_string()),
+        deprecated: None,
+    }
+}
+
+fn tool<'a>(
+    name: &str,
+    description: &str,
+    properties: impl IntoIterator<Item = (&'a str, ToolInputSchemaProperty)>,
+    required: &[&str],
+) -> Tool {
+    Tool {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        input_schema: ToolInputSchema {
+            type_name: "object".to_string(),
+            properties: properties
+                .into_iter()
+                .map(|(key, prop)| (key.to_string(), prop))
+                .collect::<HashMap<_, _>>(),
+            required: required.iter().map(|key| key.to_string()).collect(),
+        },
+        meta: None,
+    }
+}
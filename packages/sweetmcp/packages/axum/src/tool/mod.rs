@@ -1,3 +1,4 @@
+pub mod code_exec;
 pub mod model;
 pub mod notifications;
 pub mod persistence;
@@ -14,15 +14,34 @@ pub fn tools_list_stream(
     let (tx, rx) = mpsc::channel(16);
 
     tokio::spawn(async move {
+        // Native code execution tools come first and are never routed to plugins
+        for tool in super::code_exec::tools() {
+            if tx.send(Ok(tool)).await.is_err() {
+                return;
+            }
+        }
+
         // Lock-free operations using DashMap
         pm.tool_to_plugin.clear();
 
-        for mut plugin_entry in pm.plugins.iter_mut() {
-            let plugin_name = plugin_entry.key().clone();
-            let plugin = plugin_entry.value_mut();
-            match plugin.call::<&str, &str>("describe", "") {
+        // Clone the pools out so no map guard is held across awaits
+        let pools: Vec<_> = pm
+            .plugins
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+
+        for (plugin_name, pool) in pools {
+            let mut plugin = match pool.checkout(None).await {
+                Ok(plugin) => plugin,
+                Err(e) => {
+                    log::error!("tool {} describe() error: {}", plugin_name, e);
+                    continue;
+                }
+            };
+            match plugin.call::<&str, String>("describe", "") {
                 Ok(result) => {
-                    match serde_json::from_str::<ListToolsResult>(result) {
+                    match serde_json::from_str::<ListToolsResult>(&result) {
                         Ok(parsed) => {
                             for tool in parsed.tools {
                                 pm.tool_to_plugin
@@ -65,6 +84,11 @@ pub fn tools_call_pending(
         let tool_name = request.name.as_str();
         log::info!("request: {:?}", request);
 
+        if super::code_exec::is_code_exec_tool(tool_name) {
+            let _ = tx.send(Ok(super::code_exec::call(request).await));
+            return;
+        }
+
         let call_payload = serde_json::json!({
             "params": request.clone(),
         });
@@ -77,11 +101,16 @@ pub fn tools_call_pending(
             }
         };
 
-        let result = if let Some(plugin_name_entry) = pm.tool_to_plugin.get(tool_name) {
-            let plugin_name = plugin_name_entry.value();
-            if let Some(mut plugin_entry) = pm.plugins.get_mut(plugin_name) {
-                match plugin_entry.call::<&str, &str>("call", &json_string) {
-                    Ok(result) => match serde_json::from_str::<CallToolResult>(result) {
+        let plugin_name = pm
+            .tool_to_plugin
+            .get(tool_name)
+            .map(|entry| entry.value().clone());
+        let pool = plugin_name.as_deref().and_then(|name| pm.pool(name));
+
+        let result = if let (Some(plugin_name), Some(pool)) = (plugin_name, pool) {
+            match pool.checkout(Some(tool_name)).await {
+                Ok(mut plugin) => match plugin.call::<&str, String>("call", &json_string) {
+                    Ok(result) => match serde_json::from_str::<CallToolResult>(&result) {
                         Ok(parsed) => Ok(parsed),
                         Err(e) => {
                             log::error!("Failed to deserialize data: {} with {}", result, e);
@@ -98,17 +127,21 @@ pub fn tools_call_pending(
                             e,
                             request
                         );
+                        // A failed call may leave the instance unusable
+                        plugin.discard();
                         Err(
                             serde_json::json!({"code": -32602, "message": format!("Failed to execute plugin {}: {}", plugin_name, e)})
                                 .into_handler_error(),
                         )
                     }
+                },
+                Err(e) => {
+                    log::error!("Failed to start plugin {}: {}", plugin_name, e);
+                    Err(
+                        serde_json::json!({"code": -32603, "message": format!("Failed to start plugin {}: {}", plugin_name, e)})
+                            .into_handler_error(),
+                    )
                 }
-            } else {
-                Err(
-                    serde_json::json!({"code": -32602, "message": format!("Tool '{}' not found in any plugin", tool_name)})
-                        .into_handler_error(),
-                )
             }
         } else {
             Err(
@@ -257,7 +257,12 @@ pub struct Tool {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Plugins built with the plugin builder describe it as `input_schema`
+    #[serde(alias = "input_schema")]
     pub input_schema: ToolInputSchema,
+    /// Version and deprecation of the tool, passed through as plugins list them
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -278,10 +283,14 @@ pub struct ToolInputSchemaProperty {
     pub enum_values: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, RpcParams, Debug, Clone)]
 pub struct CallToolRequest {
+    /// `name` and `arguments` sit directly in the JSON-RPC params
+    #[serde(flatten)]
     pub params: ToolCallRequestParams,
     #[serde(rename = "_meta", skip_serializing_if = "Option::is_none")]
     pub meta: Option<MetaParams>,
@@ -310,7 +319,14 @@ pub enum CallToolResultContent {
     #[serde(rename = "image")]
     Image {
         data: String,
-        #[serde(rename = "mimeType")]
+        // Plugins built with sweetmcp-plugin-builder send `mime_type`
+        #[serde(rename = "mimeType", alias = "mime_type")]
+        mime_type: String,
+    },
+    #[serde(rename = "audio")]
+    Audio {
+        data: String,
+        #[serde(rename = "mimeType", alias = "mime_type")]
         mime_type: String,
     },
     #[serde(rename = "resource")]
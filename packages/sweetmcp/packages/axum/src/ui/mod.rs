@@ -83,6 +83,11 @@ pub struct ServeArgs {
     /// Enable systemd integration
     #[arg(long)]
     pub systemd: bool,
+
+    /// Serve JSON-RPC over HTTP on this address (e.g. 127.0.0.1:8080)
+    /// instead of stdin/stdout
+    #[arg(long, value_name = "ADDR")]
+    pub http: Option<String>,
 }
 
 /// Parse CLI arguments only (no side effects)
@@ -0,0 +1,188 @@
+//! Connection, TLS and timeout configuration for [`McpCapnProtoClient`]
+//!
+//! The built client keeps one `reqwest::Client`, so connections to the
+//! gateway are pooled and reused across requests.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use crate::McpCapnProtoClient;
+
+/// Default time allowed to establish a connection to the gateway
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time allowed for a whole request, including reading the response
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// PEM data given inline or read from a file when the client is built
+#[derive(Debug, Clone)]
+enum Pem {
+    Inline(Vec<u8>),
+    File(PathBuf),
+}
+
+impl Pem {
+    fn load(&self) -> Result<Vec<u8>> {
+        match self {
+            Pem::Inline(pem) => Ok(pem.clone()),
+            Pem::File(path) => std::fs::read(path)
+                .with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+}
+
+/// Builder for an [`McpCapnProtoClient`]
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use sweetmcp_capnp_client::McpCapnProtoClient;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let client = McpCapnProtoClient::builder("https://gateway.internal:8443")
+///     .connect_timeout(Duration::from_secs(5))
+///     .request_timeout(Duration::from_secs(60))
+///     .root_certificate_file("/etc/sweetmcp/ca.crt")
+///     .identity_file("/etc/sweetmcp/client.pem")
+///     .pool_max_idle_per_host(8)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct McpCapnProtoClientBuilder {
+    base_url: String,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    root_certificates: Vec<Pem>,
+    built_in_roots: bool,
+    identity: Option<Pem>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    tcp_keepalive: Option<Duration>,
+}
+
+impl McpCapnProtoClientBuilder {
+    pub(crate) fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            root_certificates: Vec::new(),
+            built_in_roots: true,
+            identity: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            tcp_keepalive: None,
+        }
+    }
+
+    /// Time allowed to establish a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Time allowed for a whole request, including reading the response
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Trust an extra CA certificate, given as PEM
+    pub fn root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(Pem::Inline(pem.into()));
+        self
+    }
+
+    /// Trust an extra CA certificate read from a PEM file
+    pub fn root_certificate_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.root_certificates.push(Pem::File(path.into()));
+        self
+    }
+
+    /// Trust only the CA certificates added to this builder
+    ///
+    /// Use this when the gateway's certificate is issued by a private CA that
+    /// nothing else should be trusted alongside.
+    pub fn only_custom_roots(mut self) -> Self {
+        self.built_in_roots = false;
+        self
+    }
+
+    /// Present a client certificate for mTLS with the gateway
+    ///
+    /// `pem` holds the certificate chain followed by its PKCS#8 or RSA
+    /// private key.
+    pub fn identity_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.identity = Some(Pem::Inline(pem.into()));
+        self
+    }
+
+    /// Present a client certificate for mTLS read from a PEM file
+    ///
+    /// The file holds the certificate chain followed by its private key.
+    pub fn identity_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.identity = Some(Pem::File(path.into()));
+        self
+    }
+
+    /// Most idle connections kept open to the gateway
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// How long an idle pooled connection is kept, or `None` to keep it indefinitely
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send TCP keepalive probes on pooled connections at this interval
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Build the client
+    ///
+    /// Fails when a certificate or identity cannot be read or parsed.
+    pub fn build(self) -> Result<McpCapnProtoClient> {
+        let mut builder = reqwest::Client::builder().connect_timeout(self.connect_timeout);
+
+        if !self.root_certificates.is_empty() || self.identity.is_some() || !self.built_in_roots {
+            builder = builder
+                .use_rustls_tls()
+                .tls_built_in_root_certs(self.built_in_roots);
+        }
+        for source in &self.root_certificates {
+            let certificate = reqwest::Certificate::from_pem(&source.load()?)
+                .context("Invalid root certificate")?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(source) = &self.identity {
+            let identity = reqwest::Identity::from_pem(&source.load()?)
+                .context("Invalid client identity")?;
+            builder = builder.identity(identity);
+        }
+
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+
+        let client = builder.build().context("Failed to build HTTP client")?;
+        Ok(McpCapnProtoClient {
+            base_url: self.base_url,
+            client,
+            request_timeout: self.request_timeout,
+        })
+    }
+}
@@ -8,7 +8,7 @@ use capnp::{message, serialize_packed};
 use log::{debug, error};
 use serde_json::Value;
 use std::io::Cursor;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 // Generated Cap'n Proto code
@@ -16,21 +16,49 @@ capnp::generated_code!(pub mod mcp_request_capnp);
 
 pub use mcp_request_capnp::{mcp_tool_request, mcp_tool_response};
 
+mod builder;
+pub use builder::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT, McpCapnProtoClientBuilder};
+
 /// Cap'n Proto client for MCP tool requests
 pub struct McpCapnProtoClient {
     base_url: String,
     client: reqwest::Client,
+    request_timeout: Duration,
 }
 
 impl McpCapnProtoClient {
-    /// Create a new client with the SweetMCP server base URL
+    /// Create a new client with the SweetMCP server base URL and default timeouts
+    ///
+    /// Use [`builder`](Self::builder) for TLS, mTLS or connection pool settings.
     pub fn new(base_url: &str) -> Self {
         Self {
             base_url: base_url.to_string(),
-            client: reqwest::Client::new(),
+            client: reqwest::Client::builder()
+                .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
         }
     }
 
+    /// Start configuring a client for the SweetMCP server at `base_url`
+    pub fn builder(base_url: &str) -> McpCapnProtoClientBuilder {
+        McpCapnProtoClientBuilder::new(base_url)
+    }
+
+    /// Use a preconfigured HTTP client
+    ///
+    /// The request timeout still applies on top of any timeout set on `client`.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Get the server URL
+    pub fn server_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Create a Cap'n Proto request for the time tool
     pub fn create_time_request() -> Result<Vec<u8>> {
         let mut message = message::Builder::new_default();
@@ -126,6 +154,7 @@ impl McpCapnProtoClient {
             .post(format!("{}/mcp", self.base_url))
             .header("Content-Type", "application/capnp")
             .body(capnp_request)
+            .timeout(self.request_timeout)
             .send()
             .await
             .context("Failed to send request to SweetMCP server")?;
@@ -0,0 +1,148 @@
+//! Binaries and plugins under test
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+/// Plugins loaded into the plugin host, as (directory under `plugins/`, wasm file stem)
+pub const PLUGINS: &[(&str, &str)] = &[
+    ("hash", "sweetmcp_plugin_hash"),
+    ("qr-code", "sweetmcp_plugin_qr_code"),
+];
+
+/// Prebuilt gateway binary
+const GATEWAY_BIN_ENV: &str = "SWEETMCP_E2E_GATEWAY_BIN";
+/// Prebuilt plugin host binary
+const PLUGIN_HOST_BIN_ENV: &str = "SWEETMCP_E2E_PLUGIN_HOST_BIN";
+/// Directory holding prebuilt `<stem>.wasm` plugins
+const PLUGIN_DIR_ENV: &str = "SWEETMCP_E2E_PLUGIN_DIR";
+
+const PLUGIN_TARGET: &str = "wasm32-wasip1";
+
+/// Paths to everything a [`Stack`](crate::Stack) runs
+///
+/// Each path comes from its environment variable when set
+/// (`SWEETMCP_E2E_GATEWAY_BIN`, `SWEETMCP_E2E_PLUGIN_HOST_BIN`,
+/// `SWEETMCP_E2E_PLUGIN_DIR`) and is otherwise built with cargo into
+/// `target/e2e`. That target directory is separate from the one running the
+/// tests, so the nested build doesn't wait on cargo's build lock.
+#[derive(Debug, Clone)]
+pub struct Artifacts {
+    pub gateway_bin: PathBuf,
+    pub plugin_host_bin: PathBuf,
+    /// Plugin name and wasm path
+    pub plugins: Vec<(String, PathBuf)>,
+}
+
+impl Artifacts {
+    /// Resolve the artifacts, building them once per test binary
+    pub fn get() -> Result<&'static Artifacts> {
+        static ARTIFACTS: OnceLock<Result<Artifacts, String>> = OnceLock::new();
+        ARTIFACTS
+            .get_or_init(|| Self::resolve().map_err(|e| format!("{:#}", e)))
+            .as_ref()
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    fn resolve() -> Result<Self> {
+        let root = repo_root()?;
+        let target_dir = root.join("target").join("e2e");
+
+        let (gateway_bin, plugin_host_bin) = match (
+            env::var_os(GATEWAY_BIN_ENV),
+            env::var_os(PLUGIN_HOST_BIN_ENV),
+        ) {
+            (Some(gateway), Some(plugin_host)) => (gateway.into(), plugin_host.into()),
+            (gateway, plugin_host) => {
+                let mut command = cargo(&root);
+                command
+                    .args([
+                        "build",
+                        "-p",
+                        "sweetmcp-pingora",
+                        "--bin",
+                        "sweetmcp_server",
+                    ])
+                    .args(["-p", "sweetmcp-axum", "--bin", "sweet"])
+                    .arg("--target-dir")
+                    .arg(&target_dir);
+                run(command)?;
+                let debug_dir = target_dir.join("debug");
+                (
+                    gateway.map_or_else(|| debug_dir.join("sweetmcp_server"), PathBuf::from),
+                    plugin_host.map_or_else(|| debug_dir.join("sweet"), PathBuf::from),
+                )
+            }
+        };
+
+        let plugin_dir = match env::var_os(PLUGIN_DIR_ENV) {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                let plugin_target_dir = target_dir.join("plugins");
+                for (name, _) in PLUGINS {
+                    let manifest = root
+                        .join("packages/sweetmcp/plugins")
+                        .join(name)
+                        .join("Cargo.toml");
+                    let mut command = cargo(&root);
+                    command
+                        .args(["build", "--release", "--target", PLUGIN_TARGET])
+                        .arg("--manifest-path")
+                        .arg(manifest)
+                        .arg("--target-dir")
+                        .arg(&plugin_target_dir);
+                    run(command)?;
+                }
+                plugin_target_dir.join(PLUGIN_TARGET).join("release")
+            }
+        };
+
+        let plugins = PLUGINS
+            .iter()
+            .map(|(name, stem)| (name.to_string(), plugin_dir.join(format!("{}.wasm", stem))))
+            .collect::<Vec<_>>();
+
+        for path in [&gateway_bin, &plugin_host_bin]
+            .into_iter()
+            .chain(plugins.iter().map(|(_, path)| path))
+        {
+            if !path.is_file() {
+                bail!("{} does not exist", path.display());
+            }
+        }
+
+        Ok(Self {
+            gateway_bin,
+            plugin_host_bin,
+            plugins,
+        })
+    }
+}
+
+/// Root of the repository (the directory holding the top-level `Cargo.toml`)
+fn repo_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../..")
+        .canonicalize()
+        .context("Failed to locate the repository root")
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(root);
+    command
+}
+
+fn run(mut command: Command) -> Result<()> {
+    log::info!("Building e2e artifacts: {:?}", command);
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run {:?}", command))?;
+    if !status.success() {
+        bail!("{:?} failed with {}", command, status);
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Throwaway certificate authority for the gateway's TLS listeners
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair,
+    KeyUsagePurpose,
+};
+
+/// Certificates the gateway loads from `$XDG_CONFIG_HOME/sweetmcp/certs`
+/// plus a client identity issued by the same CA
+pub struct TestCerts {
+    /// CA certificate clients trust
+    pub ca_pem: String,
+    /// Client certificate followed by its PKCS#8 key, as `reqwest::Identity::from_pem` expects
+    pub client_identity_pem: String,
+}
+
+impl TestCerts {
+    /// Generate a CA, a server certificate for `localhost` / `127.0.0.1` and a
+    /// client certificate, writing `ca.crt`, `ca.key`, `server.crt` and
+    /// `server.key` into `cert_dir`
+    pub fn generate(cert_dir: &Path) -> Result<Self> {
+        let ca_key = KeyPair::generate()?;
+        let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "SweetMCP e2e CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        let ca_pem = ca_params.self_signed(&ca_key)?.pem();
+        let ca_key_pem = ca_key.serialize_pem();
+        let ca = Issuer::new(ca_params, ca_key);
+
+        let (server_pem, server_key_pem) = issue(
+            &ca,
+            &["localhost", "127.0.0.1"],
+            ExtendedKeyUsagePurpose::ServerAuth,
+        )?;
+        let (client_pem, client_key_pem) = issue(
+            &ca,
+            &["sweetmcp-e2e-client"],
+            ExtendedKeyUsagePurpose::ClientAuth,
+        )?;
+
+        fs::create_dir_all(cert_dir)
+            .with_context(|| format!("Failed to create {}", cert_dir.display()))?;
+        for (name, contents) in [
+            ("ca.crt", &ca_pem),
+            ("ca.key", &ca_key_pem),
+            ("server.crt", &server_pem),
+            ("server.key", &server_key_pem),
+        ] {
+            let path = cert_dir.join(name);
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        Ok(Self {
+            ca_pem,
+            client_identity_pem: format!("{}{}", client_pem, client_key_pem),
+        })
+    }
+}
+
+/// Issue a leaf certificate for `names`, returning the certificate and key PEM
+fn issue(
+    ca: &Issuer<'_, KeyPair>,
+    names: &[&str],
+    usage: ExtendedKeyUsagePurpose,
+) -> Result<(String, String)> {
+    let key = KeyPair::generate()?;
+    let mut params = CertificateParams::new(
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>(),
+    )?;
+    params.distinguished_name.push(DnType::CommonName, names[0]);
+    params.extended_key_usages = vec![usage];
+    let cert = params.signed_by(&key, ca)?;
+    Ok((cert.pem(), key.serialize_pem()))
+}
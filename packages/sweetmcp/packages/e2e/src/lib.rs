@@ -0,0 +1,18 @@
+//! End-to-end harness for SweetMCP
+//!
+//! [`Stack::start`] boots the real `sweetmcp_server` gateway and the `sweet`
+//! plugin host with compiled WebAssembly plugins, each in its own temporary
+//! directory with throwaway certificates and free ports. Tests then drive the
+//! JSON, SSE and stdio clients against it, so protocol regressions anywhere
+//! between a client and a plugin fail the build.
+//!
+//! Binaries and plugins are built on first use; see [`Artifacts`] for the
+//! environment variables that point at prebuilt ones instead.
+
+mod artifacts;
+mod certs;
+mod stack;
+
+pub use artifacts::{Artifacts, PLUGINS};
+pub use certs::TestCerts;
+pub use stack::Stack;
@@ -0,0 +1,263 @@
+//! A running gateway and plugin host
+
+use std::env;
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use tempfile::TempDir;
+use tokio::net::TcpStream;
+use tokio::process::{Child, Command};
+
+use crate::artifacts::Artifacts;
+use crate::certs::TestCerts;
+
+/// How long a process may take to start listening
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// `sweetmcp_server` in front of a `sweet` plugin host, torn down on drop
+///
+/// Everything lives in a temporary directory: the gateway's config file,
+/// certificates (via `XDG_CONFIG_HOME`), Unix socket and the plugin host
+/// config. Ports are picked free per stack, so tests can run in parallel.
+pub struct Stack {
+    gateway: Child,
+    plugin_host: Child,
+    certs: TestCerts,
+    token: String,
+    tcp_port: u16,
+    mcp_port: u16,
+    plugin_host_bin: PathBuf,
+    plugin_host_config: PathBuf,
+    // Dropped last, after both processes are killed
+    dir: TempDir,
+}
+
+impl Stack {
+    /// Build what's missing, boot both processes and wait until they listen
+    pub async fn start() -> Result<Self> {
+        let artifacts = tokio::task::spawn_blocking(|| Artifacts::get().cloned()).await??;
+
+        let dir = tempfile::tempdir()?;
+        let config_home = dir.path().join("config");
+        let certs = TestCerts::generate(&config_home.join("sweetmcp").join("certs"))?;
+        let [plugin_host_port, tcp_port, mcp_port, metrics_port] = free_ports()?;
+
+        let plugin_host_config = dir.path().join("plugin-host.json");
+        let plugins = artifacts
+            .plugins
+            .iter()
+            .map(|(name, path)| serde_json::json!({ "name": name, "path": path }))
+            .collect::<Vec<_>>();
+        std::fs::write(
+            &plugin_host_config,
+            serde_json::to_string_pretty(&serde_json::json!({ "plugins": plugins }))?,
+        )?;
+
+        let mut plugin_host = spawn(
+            Command::new(&artifacts.plugin_host_bin)
+                .arg("--config")
+                .arg(&plugin_host_config)
+                .args(["--log-level", "warn", "serve", "--http"])
+                .arg(format!("127.0.0.1:{}", plugin_host_port)),
+        )?;
+        wait_for_port(&mut plugin_host, "plugin host", plugin_host_port).await?;
+
+        let secret: Vec<u8> = [uuid::Uuid::new_v4(), uuid::Uuid::new_v4()]
+            .iter()
+            .flat_map(|id| *id.as_bytes())
+            .collect();
+        let gateway_config = dir.path().join("sweetmcp.toml");
+        std::fs::write(
+            &gateway_config,
+            format!(
+                "tcp_bind = \"127.0.0.1:{}\"\n\
+                 mcp_bind = \"127.0.0.1:{}\"\n\
+                 metrics_bind = \"127.0.0.1:{}\"\n\
+                 uds_path = {}\n\
+                 workers = 1\n\
+                 plugin_host_url = \"http://127.0.0.1:{}/rpc\"\n",
+                tcp_port,
+                mcp_port,
+                metrics_port,
+                toml_string(&dir.path().join("sweetmcp.sock")),
+                plugin_host_port,
+            ),
+        )?;
+
+        let mut gateway_command = Command::new(&artifacts.gateway_bin);
+        // Only the generated config applies, not the developer's environment
+        for (key, _) in env::vars_os() {
+            if key.to_string_lossy().starts_with("SWEETMCP_") {
+                gateway_command.env_remove(key);
+            }
+        }
+        let mut gateway = spawn(
+            gateway_command
+                .arg("--config")
+                .arg(&gateway_config)
+                .env("SWEETMCP_JWT_SECRET", base64_url::encode(&secret))
+                .env("XDG_CONFIG_HOME", &config_home),
+        )?;
+        wait_for_port(&mut gateway, "gateway", tcp_port).await?;
+        wait_for_port(&mut gateway, "gateway", mcp_port).await?;
+
+        Ok(Self {
+            gateway,
+            plugin_host,
+            certs,
+            token: mint_token(&secret)?,
+            tcp_port,
+            mcp_port,
+            plugin_host_bin: artifacts.plugin_host_bin,
+            plugin_host_config,
+            dir,
+        })
+    }
+
+    /// Base URL of the gateway's main TLS listener
+    pub fn gateway_url(&self) -> String {
+        format!("https://127.0.0.1:{}", self.tcp_port)
+    }
+
+    /// Streamable HTTP endpoint on the gateway's MCP listener
+    pub fn mcp_url(&self) -> String {
+        format!("https://127.0.0.1:{}/mcp", self.mcp_port)
+    }
+
+    /// Bearer token the gateway accepts
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// HTTP client trusting the test CA, presenting the client certificate
+    /// and sending the bearer token
+    pub fn http_client(&self) -> Result<reqwest::Client> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", self.token))?,
+        );
+        Ok(self.tls_client()?.default_headers(headers).build()?)
+    }
+
+    /// Like [`http_client`](Self::http_client) but without a bearer token
+    pub fn anonymous_http_client(&self) -> Result<reqwest::Client> {
+        Ok(self.tls_client()?.build()?)
+    }
+
+    /// Command and arguments serving the same plugins over stdin/stdout
+    pub fn stdio_command(&self) -> (String, Vec<String>) {
+        (
+            self.plugin_host_bin.to_string_lossy().into_owned(),
+            vec![
+                "--config".to_string(),
+                self.plugin_host_config.to_string_lossy().into_owned(),
+                "--log-level".to_string(),
+                "warn".to_string(),
+                "serve".to_string(),
+            ],
+        )
+    }
+
+    /// Directory holding this stack's files
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn tls_client(&self) -> Result<reqwest::ClientBuilder> {
+        Ok(reqwest::Client::builder()
+            .use_rustls_tls()
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(reqwest::Certificate::from_pem(
+                self.certs.ca_pem.as_bytes(),
+            )?)
+            .identity(reqwest::Identity::from_pem(
+                self.certs.client_identity_pem.as_bytes(),
+            )?)
+            .timeout(Duration::from_secs(30)))
+    }
+}
+
+impl Drop for Stack {
+    fn drop(&mut self) {
+        // Children are also killed on drop; this just makes the order explicit
+        let _ = self.gateway.start_kill();
+        let _ = self.plugin_host.start_kill();
+    }
+}
+
+fn spawn(command: &mut Command) -> Result<Child> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to spawn {:?}", command.as_std().get_program()))
+}
+
+/// Wait until `port` accepts connections, failing early if `child` exits
+async fn wait_for_port(child: &mut Child, name: &str, port: u16) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            bail!("{} exited during startup with {}", name, status);
+        }
+        if TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+            .await
+            .is_ok()
+        {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            bail!(
+                "{} did not listen on port {} within {:?}",
+                name,
+                port,
+                STARTUP_TIMEOUT
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Ports free at the time of the call; held together so they are distinct
+fn free_ports<const N: usize>() -> Result<[u16; N]> {
+    let listeners = (0..N)
+        .map(|_| TcpListener::bind((Ipv4Addr::LOCALHOST, 0)))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let mut ports = [0; N];
+    for (port, listener) in ports.iter_mut().zip(&listeners) {
+        *port = listener.local_addr()?.port();
+    }
+    Ok(ports)
+}
+
+/// Quote a path as a TOML basic string (JSON escapes are valid TOML)
+fn toml_string(path: &Path) -> String {
+    serde_json::Value::from(path.to_string_lossy()).to_string()
+}
+
+/// HS256 token with the claims the gateway's JWT validation expects
+fn mint_token(secret: &[u8]) -> Result<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let claims = serde_json::json!({
+        "sub": "sweetmcp-e2e",
+        "iat": now,
+        "exp": now + 3600,
+        "jti": uuid::Uuid::new_v4().to_string(),
+        "roles": ["user"],
+        "permissions": ["tools:access"],
+        "session_id": uuid::Uuid::new_v4().to_string(),
+    });
+    Ok(jsonwebtoken::encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(secret),
+    )?)
+}
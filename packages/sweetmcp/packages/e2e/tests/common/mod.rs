@@ -0,0 +1,67 @@
+//! Shared expectations for the client suites
+
+#![allow(dead_code)]
+
+use base64::Engine;
+use serde_json::Value;
+
+/// SHA-256 of `"abc"` as the hash plugin reports it
+pub const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Convert a `sweet_mcp_type` value to a `serde_json` one for assertions
+pub fn to_serde(value: &simd_json::OwnedValue) -> Value {
+    serde_json::to_value(value).expect("simd-json value converts to serde_json")
+}
+
+/// Assert `initialize` answered with the plugin host's server info
+pub fn assert_initialized(result: &Value) {
+    assert_eq!(
+        result["serverInfo"]["name"], "sweet-mcp-server",
+        "{}",
+        result
+    );
+    assert!(result["protocolVersion"].is_string(), "{}", result);
+}
+
+/// Assert a `tools/list` result names every plugin tool
+pub fn assert_lists_plugin_tools(result: &Value) {
+    let names = result["tools"]
+        .as_array()
+        .unwrap_or_else(|| panic!("tools/list without tools: {}", result))
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect::<Vec<_>>();
+    for (name, _) in sweetmcp_e2e::PLUGINS {
+        assert!(names.contains(name), "{} missing from {:?}", name, names);
+    }
+}
+
+/// Assert a hash `tools/call` result holds the SHA-256 of `"abc"`
+pub fn assert_abc_sha256(result: &Value) {
+    assert_eq!(result["content"][0]["type"], "text", "{}", result);
+    assert_eq!(result["content"][0]["text"], ABC_SHA256, "{}", result);
+}
+
+/// Assert a qr-code `tools/call` result holds a base64 PNG image
+pub fn assert_png_image(result: &Value) {
+    let content = &result["content"][0];
+    assert_eq!(content["type"], "image", "{}", result);
+    assert_eq!(content["mimeType"], "image/png", "{}", result);
+    let data = content["data"]
+        .as_str()
+        .unwrap_or_else(|| panic!("image without data: {}", result));
+    let png = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .expect("image data is base64");
+    assert!(png.starts_with(PNG_MAGIC), "image data is not a PNG");
+}
+
+pub fn hash_abc_args() -> Value {
+    serde_json::json!({ "data": "abc", "algorithm": "sha256" })
+}
+
+pub fn qr_code_args() -> Value {
+    serde_json::json!({ "data": "https://example.com/sweetmcp" })
+}
@@ -0,0 +1,102 @@
+//! `JsonClient` through the gateway's TLS listener to the plugin host
+
+mod common;
+
+use common::*;
+use mcp_client_traits::McpClient;
+use sweet_mcp_type::Implementation;
+use sweetmcp_e2e::Stack;
+use sweetmcp_json_client::JsonClient;
+
+fn client(stack: &Stack) -> JsonClient {
+    JsonClient::new(&stack.gateway_url())
+        .expect("valid gateway URL")
+        .with_http_client(stack.http_client().expect("TLS client"))
+}
+
+fn simd(value: serde_json::Value) -> simd_json::OwnedValue {
+    simd_json::serde::to_owned_value(value).expect("serde_json value converts to simd-json")
+}
+
+#[tokio::test]
+async fn initialize_list_and_call_tools() {
+    let stack = Stack::start().await.expect("stack starts");
+    let client = client(&stack);
+
+    let response = client
+        .initialize(
+            simd(serde_json::json!({})),
+            Implementation {
+                name: "sweetmcp-e2e".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+        )
+        .await
+        .expect("initialize");
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_initialized(&to_serde(response.result.as_ref().expect("result")));
+
+    let tools = client.list_tools().await.expect("tools/list");
+    for (name, _) in sweetmcp_e2e::PLUGINS {
+        assert!(
+            tools.iter().any(|tool| tool.name == *name),
+            "{} missing from tools/list",
+            name
+        );
+    }
+
+    let response = client
+        .call_tool("hash", simd(hash_abc_args()))
+        .await
+        .expect("hash call");
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_abc_sha256(&to_serde(response.result.as_ref().expect("result")));
+
+    let response = client
+        .call_tool("qr-code", simd(qr_code_args()))
+        .await
+        .expect("qr-code call");
+    assert!(response.error.is_none(), "{:?}", response.error);
+    assert_png_image(&to_serde(response.result.as_ref().expect("result")));
+}
+
+#[tokio::test]
+async fn tool_failures_are_json_rpc_errors() {
+    let stack = Stack::start().await.expect("stack starts");
+    let client = client(&stack);
+
+    let response = client
+        .call_tool("no-such-tool", simd(serde_json::json!({})))
+        .await
+        .expect("unknown tool still gets a response");
+    assert!(response.error.is_some(), "{:?}", response.result);
+
+    let response = client
+        .call_tool(
+            "hash",
+            simd(serde_json::json!({ "data": "abc", "algorithm": "rot13" })),
+        )
+        .await
+        .expect("plugin error still gets a response");
+    assert!(response.error.is_some(), "{:?}", response.result);
+}
+
+#[tokio::test]
+async fn requests_without_a_token_are_rejected() {
+    let stack = Stack::start().await.expect("stack starts");
+
+    let response = stack
+        .anonymous_http_client()
+        .expect("TLS client")
+        .post(format!("{}/mcp", stack.gateway_url()))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/list",
+            "params": {},
+        }))
+        .send()
+        .await
+        .expect("gateway answers");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
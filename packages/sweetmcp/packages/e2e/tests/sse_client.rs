@@ -0,0 +1,103 @@
+//! `SseClient` over Streamable HTTP on the gateway's MCP listener
+
+mod common;
+
+use common::*;
+use sweetmcp_e2e::Stack;
+use sweetmcp_sse_client::{MultiplexConfig, SseClient, SseClientError};
+
+fn client(stack: &Stack) -> SseClient {
+    SseClient::streamable_http(&stack.mcp_url())
+        .expect("valid MCP URL")
+        .with_http_client(stack.http_client().expect("TLS client"))
+}
+
+#[tokio::test]
+async fn initialize_list_and_call_tools() {
+    let stack = Stack::start().await.expect("stack starts");
+    let client = client(&stack);
+
+    let result = client
+        .send_request(
+            "initialize",
+            serde_json::json!({
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "sweetmcp-e2e", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        )
+        .await
+        .expect("initialize");
+    assert_initialized(&result);
+
+    let result = client
+        .send_request("tools/list", serde_json::json!({}))
+        .await
+        .expect("tools/list");
+    assert_lists_plugin_tools(&result);
+
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "hash", "arguments": hash_abc_args() }),
+        )
+        .await
+        .expect("hash call");
+    assert_abc_sha256(&result);
+
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "qr-code", "arguments": qr_code_args() }),
+        )
+        .await
+        .expect("qr-code call");
+    assert_png_image(&result);
+}
+
+#[tokio::test]
+async fn tool_failures_are_json_rpc_errors() {
+    let stack = Stack::start().await.expect("stack starts");
+    let client = client(&stack);
+
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "no-such-tool", "arguments": {} }),
+        )
+        .await;
+    assert!(
+        matches!(result, Err(SseClientError::JsonRpcError(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn multiplexed_calls_run_concurrently() {
+    let stack = Stack::start().await.expect("stack starts");
+    let client = client(&stack).with_multiplexing(MultiplexConfig {
+        max_concurrent_requests: 4,
+        ..MultiplexConfig::default()
+    });
+
+    let mut calls = tokio::task::JoinSet::new();
+    for _ in 0..12 {
+        let client = client.clone();
+        calls.spawn(async move {
+            client
+                .send_request(
+                    "tools/call",
+                    serde_json::json!({ "name": "hash", "arguments": hash_abc_args() }),
+                )
+                .await
+        });
+    }
+
+    let mut answered = 0;
+    while let Some(result) = calls.join_next().await {
+        assert_abc_sha256(&result.expect("call task").expect("hash call"));
+        answered += 1;
+    }
+    assert_eq!(answered, 12);
+}
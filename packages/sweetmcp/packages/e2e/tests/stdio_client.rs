@@ -0,0 +1,73 @@
+//! `StdioClient` against `sweet serve` over stdin/stdout
+
+mod common;
+
+use common::*;
+use sweet_mcp_type::Implementation;
+use sweetmcp_e2e::Stack;
+use sweetmcp_stdio_client::{StdioClient, StdioClientError};
+
+#[tokio::test]
+async fn initialize_list_and_call_tools() {
+    let stack = Stack::start().await.expect("stack starts");
+    let (command, args) = stack.stdio_command();
+    let client = StdioClient::connect_and_initialize(
+        &command,
+        &args,
+        &[],
+        Implementation {
+            name: "sweetmcp-e2e".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        serde_json::json!({}),
+    )
+    .await
+    .expect("handshake with the plugin host");
+    assert_eq!(client.server_info().name, "sweet-mcp-server");
+    assert!(client.has_capability("tools"));
+
+    let result = client
+        .send_request("tools/list", serde_json::json!({}))
+        .await
+        .expect("tools/list");
+    assert_lists_plugin_tools(&result);
+
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "hash", "arguments": hash_abc_args() }),
+        )
+        .await
+        .expect("hash call");
+    assert_abc_sha256(&result);
+
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "qr-code", "arguments": qr_code_args() }),
+        )
+        .await
+        .expect("qr-code call");
+    assert_png_image(&result);
+
+    // A failed call must not desynchronise the line-delimited stream
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "no-such-tool", "arguments": {} }),
+        )
+        .await;
+    assert!(
+        matches!(result, Err(StdioClientError::ReceiveError(_))),
+        "{:?}",
+        result
+    );
+    let result = client
+        .send_request(
+            "tools/call",
+            serde_json::json!({ "name": "hash", "arguments": hash_abc_args() }),
+        )
+        .await
+        .expect("hash call after an error");
+    assert_abc_sha256(&result);
+}
@@ -45,7 +45,7 @@ use uuid::Uuid;
 use anyhow::{Context, Result};
 
 // Import sweet-mcp-type and client traits
-use sweet_mcp_type::{Request, Response, RequestId, JsonValue, Message, Implementation};
+use sweet_mcp_type::{Request, Response, JsonValue, McpError, Message, Implementation};
 use mcp_client_traits::{
     McpClient, ProtocolClient, ClientError, ToolInfo,
 };
@@ -56,6 +56,14 @@ use value_trait::prelude::*;
 pub mod schema;
 use schema::{Query, Mutation, McpClientContext, create_schema};
 
+mod typed;
+pub use typed::{ErrorLocation, GraphQLError, QueryError, TypedResponse};
+
+mod variables;
+pub use variables::{
+    coerce_arguments, tool_mutation, tool_request, CoercionError, EXECUTE_TOOL_MUTATION,
+};
+
 /// GraphQL client for SweetMCP protocol
 ///
 /// This client provides a GraphQL interface to MCP tools, automatically
@@ -118,6 +126,40 @@ impl GraphQLClient {
         query: &str,
         variables: Option<Variables>,
     ) -> Result<String, ClientError> {
+        let response = self.execute_graphql(query, variables).await;
+
+        // Convert GraphQL response to JSON
+        serde_json::to_string_pretty(&response)
+            .map_err(|e| ClientError::response_parse(
+                format!("Failed to serialize GraphQL response: {}", e),
+                "GraphQL response serialization",
+            ))
+    }
+
+    /// Execute a GraphQL query and deserialize its `data` payload
+    ///
+    /// # Arguments
+    /// * `query` - GraphQL query string
+    /// * `variables` - Optional query variables
+    ///
+    /// # Returns
+    /// The typed data plus response extensions, or a [`QueryError`] carrying
+    /// the structured GraphQL errors
+    pub async fn execute_typed<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Option<Variables>,
+    ) -> Result<TypedResponse<T>, QueryError> {
+        let response = self.execute_graphql(query, variables).await;
+        TypedResponse::from_graphql(response)
+    }
+
+    /// Run a query against the schema with this client as resolver context
+    async fn execute_graphql(
+        &self,
+        query: &str,
+        variables: Option<Variables>,
+    ) -> async_graphql::Response {
         debug!("Executing GraphQL query: {}", query);
 
         let request = GraphQLRequest::new(query);
@@ -133,13 +175,7 @@ impl GraphQLClient {
             .await;
 
         debug!("GraphQL response: {:?}", response);
-
-        // Convert GraphQL response to JSON
-        serde_json::to_string_pretty(&response)
-            .map_err(|e| ClientError::response_parse(
-                format!("Failed to serialize GraphQL response: {}", e),
-                "GraphQL response serialization",
-            ))
+        response
     }
 
     /// Get the GraphQL schema SDL (Schema Definition Language)
@@ -153,26 +189,14 @@ impl GraphQLClient {
     }
 
     /// Send a JSON-RPC 2.0 request to the SweetMCP server (internal method)
-    async fn send_request(
-        &self,
-        method: &str,
-        params: JsonValue,
-        request_id: Option<RequestId>,
-    ) -> Result<Response, ClientError> {
-        let id = request_id.unwrap_or_else(|| RequestId::Str(Uuid::new_v4().to_string()));
-        
-        // Build JSON-RPC 2.0 request using sweet-mcp-type
-        let request = Request {
-            id: id.clone(),
-            method: method.to_string(),
-            params,
-            meta: None,
-        };
-
-        debug!("Sending JSON-RPC request via GraphQL client: method={}, id={:?}", method, id);
+    async fn send_request(&self, request: Request) -> Result<Response, ClientError> {
+        debug!(
+            "Sending JSON-RPC request via GraphQL client: method={}, id={:?}",
+            request.method, request.id
+        );
 
         // Serialize request using sweet-mcp-type Message serialization
-        let message = Message::Req(request);
+        let message = Message::from(request);
         let request_body = message.to_json().into_bytes();
 
         // Send HTTP request
@@ -237,11 +261,7 @@ impl McpClient for GraphQLClient {
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         let name = name.to_string();
         Box::pin(async move {
-            let mut params = HashMap::new();
-            params.insert("name".to_string(), JsonValue::from(name));
-            params.insert("arguments".to_string(), args);
-            
-            self.send_request("tools/call", JsonValue::from(params), None).await
+            self.send_request(Request::tool_call(name).arguments(args).build()?).await
         })
     }
 
@@ -249,7 +269,7 @@ impl McpClient for GraphQLClient {
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            let response = self.send_request("tools/list", JsonValue::from(HashMap::<String, JsonValue>::new()), None).await?;
+            let response = self.send_request(Request::builder("tools/list").build()?).await?;
             
             // Extract tools from response
             if let Some(result) = response.result
@@ -285,14 +305,16 @@ impl McpClient for GraphQLClient {
         client_info: Implementation,
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            let mut params = HashMap::new();
-            params.insert("capabilities".to_string(), client_capabilities);
-            params.insert("clientInfo".to_string(), JsonValue::from([
-                ("name", JsonValue::from(client_info.name)),
-                ("version", JsonValue::from(client_info.version)),
-            ].iter().map(|(k, v)| (k.to_string(), v.clone())).collect::<HashMap<String, JsonValue>>()));
-            
-            self.send_request("initialize", JsonValue::from(params), None).await
+            let client_info: HashMap<String, JsonValue> = [
+                ("name".to_string(), JsonValue::from(client_info.name)),
+                ("version".to_string(), JsonValue::from(client_info.version)),
+            ].into_iter().collect();
+            let request = Request::builder("initialize")
+                .param("capabilities", client_capabilities)
+                .param("clientInfo", client_info)
+                .build()?;
+
+            self.send_request(request).await
         })
     }
 
@@ -300,7 +322,7 @@ impl McpClient for GraphQLClient {
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            self.send_request("ping", JsonValue::from(HashMap::<String, JsonValue>::new()), None).await
+            self.send_request(Request::builder("ping").build()?).await
         })
     }
 }
@@ -415,23 +437,29 @@ impl ProtocolClient for GraphQLClient {
             ))?;
         
         // Note: Both data and errors can be present (GraphQL partial success)
-        Ok(Response {
-            id: RequestId::Str(Uuid::new_v4().to_string()),
-            result: Some(result_value),
-            error: None,
-        })
+        Ok(Response::success(Uuid::new_v4().to_string(), result_value))
     }
 
+    /// Convert an MCP request into an `executeTool` mutation.
+    ///
+    /// A `tools/call` runs the named tool with its `arguments`; any other
+    /// request runs a tool named after the method with the params as
+    /// arguments. Arguments are sent as GraphQL variables, never spliced into
+    /// the query text. Use [`tool_request`] to also coerce them to the tool's
+    /// input schema.
     fn from_mcp_request(&self, request: Request) -> Result<Self::Request, ClientError> {
-        // Convert MCP Request to GraphQL Request
-        // This is a simplified conversion - you would typically need more sophisticated mapping
-        let query = format!(
-            "mutation {{ execute_tool(tool_name: \"{}\", args_json: \"{}\") {{ ... on GenericResult {{ success content }} }} }}",
-            request.method,
-            request.params.encode().replace('"', "\\\"")
-        );
-        
-        Ok(GraphQLRequest::new(query))
+        let (tool_name, arguments) = if request.method == "tools/call" {
+            let name = request.params.get_str("name")
+                .ok_or(McpError::BadField("name"))?
+                .to_string();
+            let arguments = request.params.get("arguments").cloned()
+                .unwrap_or_else(JsonValue::object);
+            (name, arguments)
+        } else {
+            (request.method, request.params)
+        };
+
+        Ok(tool_mutation(&tool_name, sweet_mcp_type::to_serde_json(arguments)))
     }
 }
 
@@ -463,4 +491,50 @@ mod tests {
         assert!(schema_sdl.contains("TimeResult"));
         assert!(schema_sdl.contains("HashResult"));
     }
+
+    #[tokio::test]
+    async fn test_execute_typed() {
+        #[derive(serde::Deserialize)]
+        struct Info {
+            #[serde(rename = "serverInfo")]
+            server_info: String,
+        }
+
+        let client = GraphQLClient::new("https://localhost:8443").await.unwrap();
+        let response = client.execute_typed::<Info>("{ serverInfo }", None).await.unwrap();
+        assert!(!response.data.server_info.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_from_mcp_request_uses_variables() {
+        let client = GraphQLClient::new("https://localhost:8443").await.unwrap();
+        let request = Request::tool_call("echo")
+            .arg("text", "say \"hi\" }")
+            .build()
+            .unwrap();
+
+        let graphql = client.from_mcp_request(request).unwrap();
+        assert_eq!(graphql.query, EXECUTE_TOOL_MUTATION);
+        let variables = graphql.variables.clone().into_value().into_json().unwrap();
+        assert_eq!(variables["toolName"], "echo");
+        assert_eq!(variables["args"]["text"], "say \"hi\" }");
+
+        // The mutation validates against the schema; only the call itself fails
+        let response = client.send(graphql).await.unwrap();
+        assert!(response.errors[0].message.starts_with("Tool execution failed"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_typed_surfaces_errors() {
+        let client = GraphQLClient::new("https://localhost:8443").await.unwrap();
+        let result = client.execute_typed::<serde_json::Value>("{ noSuchField }", None).await;
+
+        match result {
+            Err(QueryError::GraphQL { errors, .. }) => {
+                assert!(!errors.is_empty());
+                assert!(!errors[0].locations.is_empty());
+            }
+            other => panic!("expected GraphQL error, got {:?}", other.map(|r| r.data)),
+        }
+    }
 }
\ No newline at end of file
@@ -3,10 +3,10 @@
 //! This module defines a GraphQL schema that maps MCP tools to GraphQL
 //! queries and mutations, providing a type-safe interface for tool execution.
 
-use async_graphql::{Context, Object, Result, Schema, SimpleObject, Union};
+use async_graphql::{Context, Json, Object, Result, Schema, SimpleObject, Union};
 use std::collections::HashMap;
 
-use sweet_mcp_type::{JsonValue, Response as McpResponse};
+use sweet_mcp_type::{from_serde_json, JsonValue, Response as McpResponse};
 use mcp_client_traits::McpClient;
 
 /// Root Query type for MCP operations
@@ -179,16 +179,23 @@ impl Mutation {
         ctx: &Context<'_>,
         #[graphql(desc = "Tool name to execute")]
         tool_name: String,
-        #[graphql(desc = "Tool arguments as JSON string")]
-        args_json: String,
+        #[graphql(desc = "Tool arguments as a JSON object")]
+        args: Option<Json<serde_json::Value>>,
+        #[graphql(desc = "Tool arguments as a JSON string (prefer `args`)")]
+        args_json: Option<String>,
     ) -> Result<ToolResult> {
         use crate::GraphQLClient;
         let client_ctx = ctx.data::<McpClientContext<GraphQLClient>>()?;
         
-        // Parse arguments JSON
-        let mut args_bytes = args_json.as_bytes().to_vec();
-        let args = simd_json::to_owned_value(&mut args_bytes)
-            .map_err(|e| async_graphql::Error::new(format!("Invalid JSON arguments: {}", e)))?;
+        let args = match (args, args_json) {
+            (Some(Json(args)), _) => from_serde_json(args),
+            (None, Some(args_json)) => {
+                let mut args_bytes = args_json.into_bytes();
+                simd_json::to_owned_value(&mut args_bytes)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid JSON arguments: {}", e)))?
+            }
+            (None, None) => JsonValue::object(),
+        };
 
         let response = client_ctx.client.call_tool(&tool_name, args).await
             .map_err(|e| async_graphql::Error::new(format!("Tool execution failed: {}", e)))?;
@@ -0,0 +1,123 @@
+//! Typed GraphQL response shaping
+//!
+//! Deserializes the `data` payload of a GraphQL response into caller-defined
+//! structs, surfacing GraphQL errors and extensions as structured values
+//! instead of a pretty-printed JSON string.
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Location of an error in the query document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorLocation {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column number
+    pub column: usize,
+}
+
+/// A single error from the GraphQL `errors` array
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQLError {
+    /// Human-readable error message
+    pub message: String,
+    /// Response path of the field that failed (field names and list indices)
+    pub path: Vec<Value>,
+    /// Positions in the query that caused the error
+    pub locations: Vec<ErrorLocation>,
+    /// Error extensions (e.g. `code`)
+    pub extensions: Option<Value>,
+}
+
+impl GraphQLError {
+    fn from_server_error(error: &async_graphql::ServerError) -> Self {
+        Self {
+            message: error.message.clone(),
+            path: error
+                .path
+                .iter()
+                .filter_map(|segment| serde_json::to_value(segment).ok())
+                .collect(),
+            locations: error
+                .locations
+                .iter()
+                .map(|pos| ErrorLocation { line: pos.line, column: pos.column })
+                .collect(),
+            extensions: error
+                .extensions
+                .as_ref()
+                .and_then(|ext| serde_json::to_value(ext).ok()),
+        }
+    }
+
+    /// Error code from the `code` extension, if any
+    pub fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+}
+
+/// Errors from [`GraphQLClient::execute_typed`](crate::GraphQLClient::execute_typed)
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    /// The server reported one or more GraphQL errors
+    ///
+    /// `data` carries whatever partial result was produced alongside them.
+    #[error("GraphQL query failed: {}", first_message(.errors))]
+    GraphQL {
+        /// Errors from the response `errors` array
+        errors: Vec<GraphQLError>,
+        /// Partial `data` payload, if any
+        data: Option<Value>,
+        /// Response extensions
+        extensions: Map<String, Value>,
+    },
+
+    /// The response had neither data nor errors
+    #[error("GraphQL response contained no data")]
+    MissingData,
+
+    /// The `data` payload did not match the requested type
+    #[error("Failed to deserialize GraphQL data: {0}")]
+    Deserialize(#[source] serde_json::Error),
+}
+
+fn first_message(errors: &[GraphQLError]) -> &str {
+    errors.first().map_or("unknown error", |e| e.message.as_str())
+}
+
+/// Successful GraphQL response with `data` deserialized into `T`
+#[derive(Debug, Clone)]
+pub struct TypedResponse<T> {
+    /// Deserialized `data` payload
+    pub data: T,
+    /// Response extensions (timing, tracing, ...), keyed by extension name
+    pub extensions: Map<String, Value>,
+}
+
+impl<T: DeserializeOwned> TypedResponse<T> {
+    /// Shape an executed GraphQL response into `T`
+    pub(crate) fn from_graphql(response: async_graphql::Response) -> Result<Self, QueryError> {
+        let extensions = match serde_json::to_value(&response.extensions) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        let data = serde_json::to_value(&response.data)
+            .map_err(QueryError::Deserialize)?;
+
+        if !response.errors.is_empty() {
+            return Err(QueryError::GraphQL {
+                errors: response.errors.iter().map(GraphQLError::from_server_error).collect(),
+                data: (!data.is_null()).then_some(data),
+                extensions,
+            });
+        }
+        if data.is_null() {
+            return Err(QueryError::MissingData);
+        }
+
+        Ok(Self {
+            data: serde_json::from_value(data).map_err(QueryError::Deserialize)?,
+            extensions,
+        })
+    }
+}
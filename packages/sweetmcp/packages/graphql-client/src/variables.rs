@@ -0,0 +1,362 @@
+//! GraphQL variables for tool calls
+//!
+//! Tool arguments travel in a `JSON` variable instead of being spliced into
+//! the query text, so quotes, newlines and nested objects need no escaping.
+//! Before they are sent, arguments are coerced to the types the tool's JSON
+//! Schema asks for: `"42"` becomes `42` for an `integer`, `"true"` becomes
+//! `true` for a `boolean`, a lone value becomes a one-element list for an
+//! `array`, and so on through nested objects and lists.
+
+use async_graphql::{Request as GraphQLRequest, Variables};
+use mcp_client_traits::{ClientError, ToolInfo};
+use serde_json::{json, Map, Number, Value};
+use sweet_mcp_type::{to_serde_json, JsonValue};
+
+/// Mutation running one tool with its arguments passed as variables
+pub const EXECUTE_TOOL_MUTATION: &str = "mutation ExecuteTool($toolName: String!, $args: JSON) { \
+    executeTool(toolName: $toolName, args: $args) { \
+    ... on GenericResult { toolName success content errorMessage } } }";
+
+/// An argument that could not be coerced to its schema type
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CoercionError {
+    /// The value has a type the schema does not allow and cannot be converted
+    #[error("{path}: expected {expected}, got {found}")]
+    Mismatch {
+        /// Location of the value, e.g. `arguments.items[0]`
+        path: String,
+        /// Allowed schema types
+        expected: String,
+        /// JSON type of the value
+        found: &'static str,
+    },
+
+    /// A property listed in `required` is absent
+    #[error("{path}: missing required argument")]
+    Missing {
+        /// Location of the missing property
+        path: String,
+    },
+
+    /// The value is not one of the schema's `enum` values
+    #[error("{path}: {value} is not an allowed value")]
+    NotAllowed {
+        /// Location of the value
+        path: String,
+        /// The rejected value, JSON-encoded
+        value: String,
+    },
+}
+
+/// Build the `executeTool` request for `tool`, coercing `arguments` first
+pub fn tool_request(tool: &ToolInfo, arguments: JsonValue) -> Result<GraphQLRequest, ClientError> {
+    let schema = to_serde_json(tool.input_schema.clone());
+    let arguments = coerce_arguments(to_serde_json(arguments), &schema).map_err(|e| {
+        ClientError::RequestBuild(format!("Invalid arguments for tool '{}': {}", tool.name, e))
+    })?;
+    Ok(tool_mutation(&tool.name, arguments))
+}
+
+/// Build the `executeTool` request for `tool_name` with `arguments` as-is
+pub fn tool_mutation(tool_name: &str, arguments: Value) -> GraphQLRequest {
+    GraphQLRequest::new(EXECUTE_TOOL_MUTATION).variables(Variables::from_json(json!({
+        "toolName": tool_name,
+        "args": arguments,
+    })))
+}
+
+/// Coerce tool `arguments` to the types declared by `input_schema`
+///
+/// Missing arguments (`null`) are treated as an empty object. Properties the
+/// schema does not describe are passed through unchanged.
+pub fn coerce_arguments(arguments: Value, input_schema: &Value) -> Result<Value, CoercionError> {
+    let arguments = match arguments {
+        Value::Null => Value::Object(Map::new()),
+        other => other,
+    };
+    coerce(arguments, input_schema, "arguments")
+}
+
+fn coerce(value: Value, schema: &Value, path: &str) -> Result<Value, CoercionError> {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+
+    let value = if types.is_empty() {
+        value
+    } else if let Some(ty) = types.iter().find(|ty| has_type(&value, ty)) {
+        // A value already of an allowed type is only coerced inside
+        coerce_to(value, ty, schema, path)?
+    } else {
+        types
+            .iter()
+            .find_map(|ty| coerce_to(value.clone(), ty, schema, path).ok())
+            .ok_or_else(|| CoercionError::Mismatch {
+                path: path.to_string(),
+                expected: types.join(" or "),
+                found: kind(&value),
+            })?
+    };
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(&value)
+    {
+        return Err(CoercionError::NotAllowed {
+            path: path.to_string(),
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn coerce_to(value: Value, ty: &str, schema: &Value, path: &str) -> Result<Value, CoercionError> {
+    let mismatch = |value: &Value| CoercionError::Mismatch {
+        path: path.to_string(),
+        expected: ty.to_string(),
+        found: kind(value),
+    };
+
+    match (ty, value) {
+        ("string", Value::String(s)) => Ok(Value::String(s)),
+        ("string", value @ (Value::Number(_) | Value::Bool(_))) => {
+            Ok(Value::String(value.to_string()))
+        }
+        ("integer", Value::Number(n)) => {
+            if n.is_i64() || n.is_u64() {
+                return Ok(Value::Number(n));
+            }
+            match n.as_f64() {
+                Some(f) if f.fract() == 0.0 && (i64::MIN as f64..=i64::MAX as f64).contains(&f) => {
+                    Ok(Value::from(f as i64))
+                }
+                _ => Err(mismatch(&Value::Number(n))),
+            }
+        }
+        ("integer", Value::String(s)) => s
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| mismatch(&Value::String(s))),
+        ("number", Value::Number(n)) => Ok(Value::Number(n)),
+        ("number", Value::String(s)) => {
+            let trimmed = s.trim();
+            trimmed
+                .parse::<i64>()
+                .ok()
+                .map(Value::from)
+                .or_else(|| {
+                    trimmed
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(Number::from_f64)
+                        .map(Value::Number)
+                })
+                .ok_or_else(|| mismatch(&Value::String(s)))
+        }
+        ("boolean", Value::Bool(b)) => Ok(Value::Bool(b)),
+        ("boolean", Value::String(s)) => match s.trim() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(mismatch(&Value::String(s))),
+        },
+        ("null", Value::Null) => Ok(Value::Null),
+        ("array", Value::Array(items)) => coerce_items(items, schema, path),
+        ("array", Value::Null) => Err(mismatch(&Value::Null)),
+        ("array", value) => coerce_items(vec![value], schema, path),
+        ("object", Value::Object(map)) => coerce_object(map, schema, path),
+        (_, value) => Err(mismatch(&value)),
+    }
+}
+
+fn coerce_items(items: Vec<Value>, schema: &Value, path: &str) -> Result<Value, CoercionError> {
+    let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) else {
+        return Ok(Value::Array(items));
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| coerce(item, item_schema, &format!("{path}[{i}]")))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
+fn coerce_object(
+    map: Map<String, Value>,
+    schema: &Value,
+    path: &str,
+) -> Result<Value, CoercionError> {
+    let required = schema.get("required").and_then(Value::as_array);
+    for name in required.into_iter().flatten().filter_map(Value::as_str) {
+        if !map.contains_key(name) {
+            return Err(CoercionError::Missing {
+                path: format!("{path}.{name}"),
+            });
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    map.into_iter()
+        .map(
+            |(key, value)| match properties.and_then(|props| props.get(&key)) {
+                Some(property) => {
+                    let value = coerce(value, property, &format!("{path}.{key}"))?;
+                    Ok((key, value))
+                }
+                None => Ok((key, value)),
+            },
+        )
+        .collect::<Result<Map<_, _>, _>>()
+        .map(Value::Object)
+}
+
+/// Whether `value` already has the JSON Schema type `ty`
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "count": {"type": "integer"},
+                "ratio": {"type": "number"},
+                "verbose": {"type": "boolean"},
+                "label": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "mode": {"type": "string", "enum": ["fast", "safe"]},
+                "limit": {"type": ["integer", "null"]},
+                "filter": {
+                    "type": "object",
+                    "properties": {"depth": {"type": "integer"}},
+                    "required": ["depth"]
+                }
+            },
+            "required": ["count"]
+        })
+    }
+
+    #[test]
+    fn test_coerces_scalars_to_schema_types() {
+        let args = json!({
+            "count": "42",
+            "ratio": "0.5",
+            "verbose": "true",
+            "label": 7,
+            "tags": "solo",
+            "limit": null,
+            "filter": {"depth": 2.0}
+        });
+
+        let coerced = coerce_arguments(args, &schema()).unwrap();
+        assert_eq!(
+            coerced,
+            json!({
+                "count": 42,
+                "ratio": 0.5,
+                "verbose": true,
+                "label": "7",
+                "tags": ["solo"],
+                "limit": null,
+                "filter": {"depth": 2}
+            })
+        );
+    }
+
+    #[test]
+    fn test_keeps_text_that_needs_escaping_intact() {
+        let label = "say \"hi\"\n{ nested: [1, 2] } \\ done";
+        let args = json!({"count": 1, "label": label, "extra": {"deep": ["x"]}});
+
+        let coerced = coerce_arguments(args, &schema()).unwrap();
+        assert_eq!(coerced["label"], label);
+        assert_eq!(coerced["extra"], json!({"deep": ["x"]}));
+    }
+
+    #[test]
+    fn test_reports_where_coercion_failed() {
+        let err = coerce_arguments(json!({"count": "many"}), &schema()).unwrap_err();
+        assert_eq!(
+            err,
+            CoercionError::Mismatch {
+                path: "arguments.count".to_string(),
+                expected: "integer".to_string(),
+                found: "string",
+            }
+        );
+
+        let err = coerce_arguments(json!({"count": 1, "filter": {}}), &schema()).unwrap_err();
+        assert_eq!(
+            err,
+            CoercionError::Missing {
+                path: "arguments.filter.depth".to_string()
+            }
+        );
+
+        let err = coerce_arguments(json!({"count": 1, "mode": "reckless"}), &schema()).unwrap_err();
+        assert!(
+            matches!(err, CoercionError::NotAllowed { ref path, .. } if path == "arguments.mode")
+        );
+
+        assert!(matches!(
+            coerce_arguments(Value::Null, &schema()),
+            Err(CoercionError::Missing { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tool_mutation_passes_arguments_as_variables() {
+        let args = json!({"query": "a \"quoted\" value"});
+        let request = tool_mutation("search", args.clone());
+
+        assert_eq!(request.query, EXECUTE_TOOL_MUTATION);
+        let variables = request.variables.into_value().into_json().unwrap();
+        assert_eq!(variables["toolName"], "search");
+        assert_eq!(variables["args"], args);
+    }
+
+    #[test]
+    fn test_tool_request_rejects_bad_arguments() {
+        let tool = ToolInfo {
+            name: "count".to_string(),
+            description: None,
+            input_schema: sweet_mcp_type::from_serde_json(schema()),
+        };
+        let bad = sweet_mcp_type::from_serde_json(json!({"count": "lots"}));
+
+        match tool_request(&tool, bad) {
+            Err(ClientError::RequestBuild(message)) => {
+                assert!(message.contains("arguments.count"), "{message}");
+            }
+            other => panic!(
+                "expected RequestBuild error, got {:?}",
+                other.map(|r| r.query)
+            ),
+        }
+    }
+}
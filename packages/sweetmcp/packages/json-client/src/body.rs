@@ -0,0 +1,124 @@
+//! Size-guarded response body handling
+//!
+//! Bodies are read chunk by chunk so an oversized response is rejected as
+//! soon as it crosses the limit instead of after it has been buffered.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use mcp_client_traits::ClientError;
+use sweet_mcp_type::Response;
+use tokio::io::AsyncWriteExt;
+
+/// Reject a body whose declared `Content-Length` already exceeds `limit`
+fn check_content_length(response: &reqwest::Response, limit: u64) -> Result<(), ClientError> {
+    match response.content_length() {
+        Some(len) if len > limit => Err(ClientError::payload_too_large("response body", len, limit)),
+        _ => Ok(()),
+    }
+}
+
+/// Buffer a response body in memory, failing once it exceeds `limit` bytes
+pub(crate) async fn read_limited(
+    mut response: reqwest::Response,
+    limit: u64,
+) -> Result<Vec<u8>, ClientError> {
+    check_content_length(&response, limit)?;
+
+    // Content-Length is within the limit here, so the hint is bounded too
+    let mut body = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
+    while let Some(chunk) = response.chunk().await.map_err(ClientError::Transport)? {
+        let size = (body.len() + chunk.len()) as u64;
+        if size > limit {
+            return Err(ClientError::payload_too_large("response body", size, limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Stream a response body into an anonymous temp file
+///
+/// `limit` of `None` spools bodies of any size.
+pub(crate) async fn spool(
+    mut response: reqwest::Response,
+    limit: Option<u64>,
+) -> Result<SpooledResponse, ClientError> {
+    if let Some(limit) = limit {
+        check_content_length(&response, limit)?;
+    }
+
+    let file = tempfile::tempfile()
+        .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to create spool file: {}", e)))?;
+    let mut file = tokio::fs::File::from_std(file);
+    let mut size = 0u64;
+    while let Some(chunk) = response.chunk().await.map_err(ClientError::Transport)? {
+        size += chunk.len() as u64;
+        if let Some(limit) = limit
+            && size > limit
+        {
+            return Err(ClientError::payload_too_large("response body", size, limit));
+        }
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to write spool file: {}", e)))?;
+    }
+    file.flush()
+        .await
+        .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to flush spool file: {}", e)))?;
+
+    let mut file = file.into_std().await;
+    file.seek(SeekFrom::Start(0))
+        .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to rewind spool file: {}", e)))?;
+    Ok(SpooledResponse { file, size })
+}
+
+/// Spool an in-memory body, for responses that never touched the network
+pub(crate) fn spool_bytes(bytes: &[u8]) -> Result<SpooledResponse, ClientError> {
+    let mut file = tempfile::tempfile()
+        .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to create spool file: {}", e)))?;
+    file.write_all(bytes)
+        .and_then(|()| file.seek(SeekFrom::Start(0)).map(|_| ()))
+        .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to write spool file: {}", e)))?;
+    Ok(SpooledResponse { file, size: bytes.len() as u64 })
+}
+
+/// Raw JSON-RPC response body spooled to a temporary file
+///
+/// Returned by the streaming request methods for results too large to hold
+/// in memory comfortably (e.g. fetch screenshots). The file is unlinked on
+/// creation and disappears once the handle is dropped.
+#[derive(Debug)]
+pub struct SpooledResponse {
+    file: File,
+    size: u64,
+}
+
+impl SpooledResponse {
+    /// Size of the spooled body in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Handle to the spooled body, positioned at the start
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Take ownership of the spooled body, positioned at the start
+    pub fn into_file(self) -> File {
+        self.file
+    }
+
+    /// Read the body back and parse it as a JSON-RPC response
+    ///
+    /// This loads the full body into memory; check [`size`](Self::size) first
+    /// when the result may be large.
+    pub fn into_response(mut self) -> Result<Response, ClientError> {
+        let mut bytes = Vec::with_capacity(self.size as usize);
+        self.file
+            .read_to_end(&mut bytes)
+            .map_err(|e| ClientError::Serialization(anyhow::anyhow!("Failed to read spool file: {}", e)))?;
+        crate::parse_response(&bytes)
+    }
+}
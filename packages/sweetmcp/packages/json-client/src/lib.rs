@@ -25,24 +25,57 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! # Offline development
+//!
+//! Record a session against a running gateway with
+//! [`JsonClient::with_recording`], then replay it without one:
+//!
+//! ```rust,no_run
+//! use sweetmcp_json_client::JsonClient;
+//! use mcp_client_traits::McpClient;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = JsonClient::offline("fixtures/")?;
+//! let tools = client.list_tools().await?;
+//! # Ok(())
+//! # }
+//! ```
 
 use std::pin::Pin;
 use std::future::Future;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
 
 use reqwest::Client;
 use log::{debug, error, info};
-use uuid::Uuid;
 use anyhow::{Context, Result};
 
 // Import sweet-mcp-type and client traits
-use sweet_mcp_type::{Request, Response, RequestId, JsonValue, Message, Implementation};
+use sweet_mcp_type::{Request, Response, JsonValue, Message, Implementation};
 use mcp_client_traits::{
     McpClient, ProtocolClient, ClientError, ToolInfo,
 };
 
 use value_trait::prelude::*;
 
+mod body;
+pub use body::SpooledResponse;
+
+mod offline;
+use offline::{Fixtures, Recorder};
+
+/// MCP protocol revision sent in `initialize`
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Default cap on serialized request bodies (16 MiB)
+const DEFAULT_MAX_REQUEST_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Default cap on buffered response bodies (64 MiB)
+const DEFAULT_MAX_RESPONSE_SIZE: u64 = 64 * 1024 * 1024;
+
 /// Direct JSON-RPC 2.0 client for SweetMCP protocol
 ///
 /// This client communicates directly with SweetMCP servers using JSON-RPC 2.0
@@ -55,6 +88,16 @@ pub struct JsonClient {
     http_client: Client,
     /// Default timeout for requests (in milliseconds)
     default_timeout_ms: u64,
+    /// Largest serialized request body that will be sent
+    max_request_size: u64,
+    /// Largest response body buffered in memory
+    max_response_size: u64,
+    /// Largest response body spooled to disk (`None` for unlimited)
+    max_spool_size: Option<u64>,
+    /// Recorded exchanges answering requests instead of a server
+    offline: Option<Arc<Fixtures>>,
+    /// Transcript every live exchange is appended to
+    recorder: Option<Arc<Recorder>>,
 }
 
 impl JsonClient {
@@ -75,9 +118,55 @@ impl JsonClient {
             base_url: base_url.to_string(),
             http_client: Client::new(),
             default_timeout_ms: 30000, // 30 seconds default
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            max_spool_size: None,
+            offline: None,
+            recorder: None,
         })
     }
 
+    /// Create a client that answers from recorded transcripts
+    ///
+    /// No server is contacted: `tools/list` and `tools/call` are served from
+    /// the fixtures with their recorded latencies, results and errors. See
+    /// [`with_recording`](Self::with_recording) for the transcript format.
+    ///
+    /// # Arguments
+    /// * `fixtures` - A transcript file, or a directory of `.jsonl` transcripts
+    ///
+    /// # Returns
+    /// An offline `JsonClient` or an error if a fixture can't be read
+    pub fn offline(fixtures: impl AsRef<Path>) -> Result<Self> {
+        let fixtures = fixtures.as_ref();
+        let loaded = Fixtures::load(fixtures)?;
+        info!("Creating offline JSON client from fixtures at {}", fixtures.display());
+
+        Ok(Self {
+            base_url: format!("offline:{}", fixtures.display()),
+            http_client: Client::new(),
+            default_timeout_ms: 30000,
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            max_spool_size: None,
+            offline: Some(Arc::new(loaded)),
+            recorder: None,
+        })
+    }
+
+    /// Append every exchange to a transcript for later offline use
+    ///
+    /// Each line holds the request, the response and the latency in
+    /// milliseconds; lines can also be written by hand with an
+    /// `"http_error": {"status": 503, "body": "..."}` in place of the response.
+    ///
+    /// # Arguments
+    /// * `path` - Transcript file, created if missing and appended to otherwise
+    pub fn with_recording(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        self.recorder = Some(Arc::new(Recorder::open(path.as_ref())?));
+        Ok(self)
+    }
+
     /// Set default timeout for requests
     ///
     /// # Arguments
@@ -87,6 +176,47 @@ impl JsonClient {
         self
     }
 
+    /// Set the largest request body the client will send
+    ///
+    /// # Arguments
+    /// * `bytes` - Limit on the serialized JSON-RPC request
+    pub fn with_max_request_size(mut self, bytes: u64) -> Self {
+        self.max_request_size = bytes;
+        self
+    }
+
+    /// Set the largest response body buffered in memory
+    ///
+    /// Larger responses fail with [`ClientError::PayloadTooLarge`]; use the
+    /// streaming methods (e.g. [`call_tool_streaming`](Self::call_tool_streaming))
+    /// for results that legitimately exceed it.
+    ///
+    /// # Arguments
+    /// * `bytes` - Limit on the response body
+    pub fn with_max_response_size(mut self, bytes: u64) -> Self {
+        self.max_response_size = bytes;
+        self
+    }
+
+    /// Set the largest response body the streaming methods spool to disk
+    ///
+    /// # Arguments
+    /// * `bytes` - Limit on the spooled body, or `None` for unlimited (the default)
+    pub fn with_max_spool_size(mut self, bytes: Option<u64>) -> Self {
+        self.max_spool_size = bytes;
+        self
+    }
+
+    /// Use a preconfigured HTTP client
+    ///
+    /// # Arguments
+    /// * `http_client` - Client carrying e.g. custom root certificates, a client
+    ///   identity for mTLS, or a default `Authorization` header
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
     /// Get the server URL
     ///
     /// # Returns
@@ -111,39 +241,88 @@ impl JsonClient {
         true
     }
 
+    /// Call a tool, spooling its response body to a temp file
+    ///
+    /// Use this for results too large to buffer (screenshots, file dumps);
+    /// the body is written to disk as it arrives and is only subject to
+    /// [`with_max_spool_size`](Self::with_max_spool_size).
+    ///
+    /// # Arguments
+    /// * `name` - The tool name
+    /// * `args` - The tool arguments
+    ///
+    /// # Returns
+    /// The spooled response body or an error
+    pub async fn call_tool_streaming(
+        &self,
+        name: &str,
+        args: JsonValue,
+    ) -> Result<SpooledResponse, ClientError> {
+        let request = Request::tool_call(name).arguments(args).build()?;
+
+        self.send_request_streaming(&request).await
+    }
+
+    /// Send a JSON-RPC 2.0 request, spooling the response body to a temp file
+    ///
+    /// # Arguments
+    /// * `request` - The request to send
+    ///
+    /// # Returns
+    /// The spooled response body or an error
+    pub async fn send_request_streaming(&self, request: &Request) -> Result<SpooledResponse, ClientError> {
+        if let Some(fixtures) = &self.offline {
+            let response = fixtures.respond(request, self.default_timeout_ms).await?;
+            return body::spool_bytes(Message::Res(response).to_json().as_bytes());
+        }
+
+        let response = self.post(request).await?;
+        let spooled = body::spool(response, self.max_spool_size).await?;
+        debug!("Spooled JSON-RPC response: id={:?}, {} bytes", request.id, spooled.size());
+        Ok(spooled)
+    }
+
     /// Send a JSON-RPC 2.0 request to the SweetMCP server
     ///
     /// # Arguments
-    /// * `method` - The JSON-RPC method name
-    /// * `params` - The method parameters as JsonValue
-    /// * `request_id` - Optional request ID (generates UUID if None)
+    /// * `request` - The request to send
     ///
     /// # Returns
     /// The MCP Response or an error
-    async fn send_request(
-        &self,
-        method: &str,
-        params: JsonValue,
-        request_id: Option<RequestId>,
-    ) -> Result<Response, ClientError> {
-        let id = request_id.unwrap_or_else(|| RequestId::Str(Uuid::new_v4().to_string()));
-        
-        // Build JSON-RPC 2.0 request using sweet-mcp-type
-        let request = Request {
-            id: id.clone(),
-            method: method.to_string(),
-            params,
-            meta: None,
-        };
+    async fn send_request(&self, request: Request) -> Result<Response, ClientError> {
+        if let Some(fixtures) = &self.offline {
+            return fixtures.respond(&request, self.default_timeout_ms).await;
+        }
 
-        debug!("Sending JSON-RPC request: method={}, id={:?}", method, id);
+        let started = Instant::now();
+        let response = self.post(&request).await?;
+
+        // Parse response
+        let response_bytes = body::read_limited(response, self.max_response_size).await?;
+
+        let parsed_response = parse_response(&response_bytes)?;
+        debug!("JSON-RPC response: {:?}", parsed_response);
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&request, &parsed_response, started.elapsed());
+        }
+        Ok(parsed_response)
+    }
+
+    /// POST a request and return the successful HTTP response, body unread
+    async fn post(&self, request: &Request) -> Result<reqwest::Response, ClientError> {
+        debug!("Sending JSON-RPC request: method={}, id={:?}", request.method, request.id);
 
         // Serialize request using sweet-mcp-type JSON serialization
-        let request_body = self.serialize_request(&request)
+        let request_body = self.serialize_request(request)
             .map_err(|e| ClientError::RequestBuild(
                 format!("Failed to serialize request: {}", e)
             ))?;
 
+        let request_size = request_body.len() as u64;
+        if request_size > self.max_request_size {
+            return Err(ClientError::payload_too_large("request body", request_size, self.max_request_size));
+        }
+
         // Send HTTP request
         let response = self
             .http_client
@@ -165,13 +344,7 @@ impl JsonClient {
             return Err(ClientError::RequestBuild(error_msg));
         }
 
-        // Parse response
-        let response_bytes = response.bytes().await
-            .map_err(ClientError::Transport)?;
-
-        let parsed_response = self.parse_response(&response_bytes)?;
-        debug!("JSON-RPC response: {:?}", parsed_response);
-        Ok(parsed_response)
+        Ok(response)
     }
 
     /// Serialize a Request to JSON bytes using sweet-mcp-type
@@ -181,29 +354,29 @@ impl JsonClient {
         let json_string = message.to_json();
         Ok(json_string.into_bytes())
     }
+}
 
-    /// Parse response bytes into sweet-mcp-type Response
-    fn parse_response(&self, bytes: &[u8]) -> Result<Response, ClientError> {
-        // Parse using sweet-mcp-type Message parsing
-        let json_str = std::str::from_utf8(bytes)
-            .map_err(|e| ClientError::response_parse(
-                format!("Invalid UTF-8 in response: {}", e),
-                "JSON-RPC response encoding",
-            ))?;
+/// Parse response bytes into sweet-mcp-type Response
+fn parse_response(bytes: &[u8]) -> Result<Response, ClientError> {
+    // Parse using sweet-mcp-type Message parsing
+    let json_str = std::str::from_utf8(bytes)
+        .map_err(|e| ClientError::response_parse(
+            format!("Invalid UTF-8 in response: {}", e),
+            "JSON-RPC response encoding",
+        ))?;
 
-        let message = Message::from_json(json_str)
-            .map_err(|e| ClientError::response_parse(
-                format!("Invalid JSON-RPC response: {:?}", e),
-                "JSON-RPC response parsing",
-            ))?;
+    let message = Message::from_json(json_str)
+        .map_err(|e| ClientError::response_parse(
+            format!("Invalid JSON-RPC response: {:?}", e),
+            "JSON-RPC response parsing",
+        ))?;
 
-        match message {
-            Message::Res(response) => Ok(response),
-            _ => Err(ClientError::response_parse(
-                "Expected response, got request or notification".to_string(),
-                "JSON-RPC message type",
-            )),
-        }
+    match message {
+        Message::Res(response) => Ok(response),
+        _ => Err(ClientError::response_parse(
+            "Expected response, got request or notification".to_string(),
+            "JSON-RPC message type",
+        )),
     }
 }
 
@@ -216,11 +389,7 @@ impl McpClient for JsonClient {
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         let name = name.to_string();
         Box::pin(async move {
-            let mut params = HashMap::new();
-            params.insert("name".to_string(), JsonValue::from(name));
-            params.insert("arguments".to_string(), args);
-            
-            self.send_request("tools/call", JsonValue::from(params), None).await
+            self.send_request(Request::tool_call(name).arguments(args).build()?).await
         })
     }
 
@@ -228,7 +397,7 @@ impl McpClient for JsonClient {
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            let response = self.send_request("tools/list", JsonValue::from(HashMap::<String, JsonValue>::new()), None).await?;
+            let response = self.send_request(Request::builder("tools/list").build()?).await?;
             
             // Extract tools from response
             if let Some(result) = response.result
@@ -264,14 +433,17 @@ impl McpClient for JsonClient {
         client_info: Implementation,
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            let mut params = HashMap::new();
-            params.insert("capabilities".to_string(), client_capabilities);
-            params.insert("clientInfo".to_string(), JsonValue::from([
-                ("name", JsonValue::from(client_info.name)),
-                ("version", JsonValue::from(client_info.version)),
-            ].iter().map(|(k, v)| (k.to_string(), v.clone())).collect::<HashMap<String, JsonValue>>()));
-            
-            self.send_request("initialize", JsonValue::from(params), None).await
+            let client_info: HashMap<String, JsonValue> = [
+                ("name".to_string(), JsonValue::from(client_info.name)),
+                ("version".to_string(), JsonValue::from(client_info.version)),
+            ].into_iter().collect();
+            let request = Request::builder("initialize")
+                .param("protocolVersion", PROTOCOL_VERSION)
+                .param("capabilities", client_capabilities)
+                .param("clientInfo", client_info)
+                .build()?;
+
+            self.send_request(request).await
         })
     }
 
@@ -279,7 +451,7 @@ impl McpClient for JsonClient {
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            self.send_request("ping", JsonValue::from(HashMap::<String, JsonValue>::new()), None).await
+            self.send_request(Request::builder("ping").build()?).await
         })
     }
 }
@@ -296,7 +468,7 @@ impl ProtocolClient for JsonClient {
         request: Self::Request,
     ) -> Pin<Box<dyn Future<Output = Result<Self::Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            self.send_request(&request.method, request.params, Some(request.id)).await
+            self.send_request(request).await
         })
     }
 
@@ -0,0 +1,301 @@
+//! Offline mode: answer requests from recorded transcripts
+//!
+//! Fixtures are JSON Lines files, one recorded exchange per line, as written
+//! by [`JsonClient::with_recording`](crate::JsonClient::with_recording):
+//!
+//! ```text
+//! {"request":{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}},"response":{"jsonrpc":"2.0","id":1,"result":{"tools":[]}},"latency_ms":12}
+//! {"request":{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"fetch","arguments":{}}},"http_error":{"status":503,"body":"draining"},"latency_ms":3}
+//! ```
+//!
+//! A request is answered by the exchanges with the same method and tool,
+//! preferring those whose params match exactly; repeated requests cycle
+//! through them in recorded order. Each answer waits for the recorded latency
+//! and carries the request's own id. Requests without a recording get a
+//! JSON-RPC error, except `initialize`, `ping` and `tools/list`, which have
+//! canned answers built from the fixtures.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use mcp_client_traits::ClientError;
+use sweet_mcp_type::{JsonRpcError, JsonValue, Message, Request, Response};
+use value_trait::prelude::*;
+
+/// JSON-RPC error code for requests without a recording
+const NOT_RECORDED_CODE: i64 = -32601;
+
+/// What the server did with a recorded request
+#[derive(Debug, Clone)]
+enum Outcome {
+    Response(Response),
+    HttpError { status: u64, body: String },
+}
+
+/// One recorded request and its answer
+#[derive(Debug, Clone)]
+struct Exchange {
+    request: Request,
+    outcome: Outcome,
+    latency: Duration,
+}
+
+/// Recorded exchanges served by an offline client
+#[derive(Debug)]
+pub(crate) struct Fixtures {
+    source: PathBuf,
+    exchanges: Vec<Exchange>,
+    /// Next exchange to replay, per group of matching exchanges
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl Fixtures {
+    /// Load a transcript file, or every `.jsonl` file in a directory
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let files = if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read fixture directory {}", path.display()))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "jsonl"))
+                .collect();
+            files.sort();
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+
+        let mut exchanges = Vec::new();
+        for file in &files {
+            let contents = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read fixture {}", file.display()))?;
+            for (index, line) in contents.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let exchange = parse_exchange(line)
+                    .with_context(|| format!("Invalid fixture {}:{}", file.display(), index + 1))?;
+                exchanges.push(exchange);
+            }
+        }
+
+        Ok(Self {
+            source: path.to_path_buf(),
+            exchanges,
+            cursors: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Answer a request as the recording did, after the recorded latency
+    ///
+    /// Latencies longer than `timeout_ms` fail with a timeout, as a live
+    /// request would.
+    pub(crate) async fn respond(
+        &self,
+        request: &Request,
+        timeout_ms: u64,
+    ) -> Result<Response, ClientError> {
+        let Some(exchange) = self.next_exchange(request) else {
+            return Ok(self.unrecorded(request));
+        };
+
+        let timeout = Duration::from_millis(timeout_ms);
+        if exchange.latency > timeout {
+            tokio::time::sleep(timeout).await;
+            return Err(ClientError::timeout(
+                format!("offline {}", request.method),
+                timeout_ms,
+            ));
+        }
+        tokio::time::sleep(exchange.latency).await;
+
+        match &exchange.outcome {
+            Outcome::Response(response) => {
+                let mut response = response.clone();
+                response.id = request.id.clone();
+                Ok(response)
+            }
+            Outcome::HttpError { status, body } => Err(ClientError::RequestBuild(format!(
+                "Server returned HTTP {}: {}",
+                status, body
+            ))),
+        }
+    }
+
+    /// The next recorded exchange answering `request`
+    fn next_exchange(&self, request: &Request) -> Option<&Exchange> {
+        let tool = tool_name(&request.params);
+        let same_call: Vec<&Exchange> = self
+            .exchanges
+            .iter()
+            .filter(|exchange| {
+                exchange.request.method == request.method
+                    && tool_name(&exchange.request.params) == tool
+            })
+            .collect();
+        let exact: Vec<&Exchange> = same_call
+            .iter()
+            .copied()
+            .filter(|exchange| exchange.request.params == request.params)
+            .collect();
+
+        let (group, candidates) = if exact.is_empty() {
+            (
+                format!("{}\n{}", request.method, tool.unwrap_or_default()),
+                same_call,
+            )
+        } else {
+            (
+                format!("{}\n{}", request.method, request.params.encode()),
+                exact,
+            )
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let mut cursors = self.cursors.lock().ok()?;
+        let cursor = cursors.entry(group).or_insert(0);
+        let exchange = candidates[*cursor % candidates.len()];
+        *cursor += 1;
+        Some(exchange)
+    }
+
+    /// Canned answer for a request the fixtures don't cover
+    fn unrecorded(&self, request: &Request) -> Response {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            "initialize" => Response::success(
+                id,
+                simd_json::json!({
+                    "protocolVersion": crate::PROTOCOL_VERSION,
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "sweetmcp-offline", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            ),
+            "ping" => Response::success(id, simd_json::json!({})),
+            // Every tool with a recorded call, with a permissive schema
+            "tools/list" => {
+                let names: BTreeSet<&str> = self
+                    .exchanges
+                    .iter()
+                    .filter(|exchange| exchange.request.method == "tools/call")
+                    .filter_map(|exchange| tool_name(&exchange.request.params))
+                    .collect();
+                let tools: Vec<JsonValue> = names
+                    .into_iter()
+                    .map(|name| {
+                        simd_json::json!({
+                            "name": name,
+                            "description": "Recorded in offline fixtures",
+                            "inputSchema": { "type": "object" }
+                        })
+                    })
+                    .collect();
+                Response::success(id, simd_json::json!({ "tools": tools }))
+            }
+            "tools/call" => Response::error(
+                id,
+                JsonRpcError::new(
+                    NOT_RECORDED_CODE,
+                    format!(
+                        "No recorded call to tool '{}' in {}",
+                        tool_name(&request.params).unwrap_or_default(),
+                        self.source.display()
+                    ),
+                ),
+            ),
+            method => Response::error(
+                id,
+                JsonRpcError::new(
+                    NOT_RECORDED_CODE,
+                    format!(
+                        "No recorded {} request in {}",
+                        method,
+                        self.source.display()
+                    ),
+                ),
+            ),
+        }
+    }
+}
+
+/// Appends every exchange of a live client to a transcript file
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    file: Mutex<File>,
+}
+
+impl Recorder {
+    /// Open `path` for appending, creating it if needed
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open recording {}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append one exchange; failures are logged, never returned
+    pub(crate) fn record(&self, request: &Request, response: &Response, latency: Duration) {
+        let line = format!(
+            "{{\"request\":{},\"response\":{},\"latency_ms\":{}}}\n",
+            Message::Req(request.clone()).to_json(),
+            Message::Res(response.clone()).to_json(),
+            latency.as_millis()
+        );
+        let written = self
+            .file
+            .lock()
+            .map_err(|_| std::io::Error::other("recording lock poisoned"))
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(e) = written {
+            log::warn!("Failed to record {} exchange: {}", request.method, e);
+        }
+    }
+}
+
+/// Parse one transcript line
+fn parse_exchange(line: &str) -> Result<Exchange> {
+    let mut bytes = line.as_bytes().to_vec();
+    let value = simd_json::to_owned_value(&mut bytes).context("not a JSON object")?;
+
+    let request = value.get("request").context("missing `request`")?;
+    let request =
+        match Message::from_json(&request.encode()).map_err(|e| anyhow::anyhow!("{:?}", e))? {
+            Message::Req(request) => request,
+            _ => anyhow::bail!("`request` is not a JSON-RPC request"),
+        };
+
+    let outcome = match (value.get("response"), value.get("http_error")) {
+        (Some(response), _) => Outcome::Response(
+            crate::parse_response(response.encode().as_bytes())
+                .map_err(|e| anyhow::anyhow!("invalid `response`: {}", e))?,
+        ),
+        (None, Some(error)) => Outcome::HttpError {
+            status: error
+                .get_u64("status")
+                .context("missing `http_error.status`")?,
+            body: error.get_str("body").unwrap_or_default().to_string(),
+        },
+        (None, None) => anyhow::bail!("needs `response` or `http_error`"),
+    };
+
+    Ok(Exchange {
+        request,
+        outcome,
+        latency: Duration::from_millis(value.get_u64("latency_ms").unwrap_or(0)),
+    })
+}
+
+/// Tool named by `tools/call` params
+fn tool_name(params: &JsonValue) -> Option<&str> {
+    params.get_str("name")
+}
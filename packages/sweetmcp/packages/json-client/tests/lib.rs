@@ -33,3 +33,120 @@ async fn test_request_serialization() {
     let serialized = client.serialize_request(&request).unwrap();
     assert!(!serialized.is_empty());
 }
+
+#[tokio::test]
+async fn test_request_size_guard() {
+    use mcp_client_traits::{ClientError, McpClient};
+
+    // Rejected before any connection is attempted
+    let client = JsonClient::new("https://localhost:8443")
+        .unwrap()
+        .with_max_request_size(16);
+
+    match client.call_tool("time", JsonValue::from("get_time_utc")).await {
+        Err(ClientError::PayloadTooLarge { payload, limit, .. }) => {
+            assert_eq!(payload, "request body");
+            assert_eq!(limit, 16);
+        }
+        other => panic!("expected PayloadTooLarge, got {:?}", other),
+    }
+}
+
+const FIXTURE: &str = r#"{"request":{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"time","arguments":{"name":"get_time_utc"}}},"response":{"jsonrpc":"2.0","id":1,"result":{"content":[{"type":"text","text":"2025-01-01T00:00:00Z"}]}},"latency_ms":40}
+{"request":{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"time","arguments":{"name":"parse_time"}}},"response":{"jsonrpc":"2.0","id":2,"error":{"code":-32602,"message":"missing time_string"}},"latency_ms":0}
+{"request":{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"fetch","arguments":{"url":"https://example.com"}}},"http_error":{"status":503,"body":"draining"},"latency_ms":0}
+{"request":{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"hash","arguments":{"data":"a"}}},"response":{"jsonrpc":"2.0","id":4,"result":{"content":[{"type":"text","text":"first"}]}},"latency_ms":0}
+{"request":{"jsonrpc":"2.0","id":5,"method":"tools/call","params":{"name":"hash","arguments":{"data":"b"}}},"response":{"jsonrpc":"2.0","id":5,"result":{"content":[{"type":"text","text":"second"}]}},"latency_ms":0}
+"#;
+
+fn offline_client() -> (tempfile::TempDir, JsonClient) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("session.jsonl"), FIXTURE).unwrap();
+    let client = JsonClient::offline(dir.path()).unwrap();
+    (dir, client)
+}
+
+fn arguments(pairs: &[(&str, &str)]) -> JsonValue {
+    JsonValue::from(pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), JsonValue::from(*v)))
+        .collect::<HashMap<String, JsonValue>>())
+}
+
+fn text(response: &sweet_mcp_type::Response) -> String {
+    use value_trait::prelude::*;
+
+    let result = response.result.as_ref().expect("result");
+    result.get("content").and_then(|c| c.as_array()).and_then(|c| c.first())
+        .and_then(|c| c.get_str("text"))
+        .expect("text content")
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_offline_lists_recorded_tools() {
+    use mcp_client_traits::McpClient;
+
+    let (_dir, client) = offline_client();
+    assert!(client.server_url().starts_with("offline:"));
+
+    let names: Vec<String> = client.list_tools().await.unwrap().into_iter().map(|t| t.name).collect();
+    assert_eq!(names, ["fetch", "hash", "time"]);
+}
+
+#[tokio::test]
+async fn test_offline_replays_recorded_calls() {
+    use mcp_client_traits::McpClient;
+
+    let (_dir, client) = offline_client();
+
+    // Exact arguments, with the recorded latency
+    let started = std::time::Instant::now();
+    let response = client.call_tool("time", arguments(&[("name", "get_time_utc")])).await.unwrap();
+    assert!(started.elapsed() >= std::time::Duration::from_millis(40));
+    assert_eq!(text(&response), "2025-01-01T00:00:00Z");
+
+    // Recorded JSON-RPC errors come back as error responses
+    let response = client.call_tool("time", arguments(&[("name", "parse_time")])).await.unwrap();
+    assert_eq!(response.error.expect("error").code, -32602);
+
+    // Unrecorded arguments cycle through the tool's recordings
+    let first = client.call_tool("hash", arguments(&[("data", "z")])).await.unwrap();
+    let second = client.call_tool("hash", arguments(&[("data", "z")])).await.unwrap();
+    let third = client.call_tool("hash", arguments(&[("data", "z")])).await.unwrap();
+    assert_eq!([text(&first), text(&second), text(&third)], ["first", "second", "first"]);
+}
+
+#[tokio::test]
+async fn test_offline_errors() {
+    use mcp_client_traits::{ClientError, McpClient};
+
+    let (dir, client) = offline_client();
+
+    // Recorded HTTP failures fail the call
+    match client.call_tool("fetch", arguments(&[("url", "https://example.com")])).await {
+        Err(ClientError::RequestBuild(message)) => assert!(message.contains("503"), "{}", message),
+        other => panic!("expected RequestBuild, got {:?}", other),
+    }
+
+    // Tools without a recording answer with a JSON-RPC error
+    let response = client.call_tool("weather", arguments(&[])).await.unwrap();
+    assert_eq!(response.error.expect("error").code, -32601);
+
+    // Recorded latencies beyond the client's timeout time out
+    let client = JsonClient::offline(dir.path().join("session.jsonl")).unwrap().with_timeout(10);
+    match client.call_tool("time", arguments(&[("name", "get_time_utc")])).await {
+        Err(ClientError::Timeout { timeout_ms, .. }) => assert_eq!(timeout_ms, 10),
+        other => panic!("expected Timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_offline_rejects_invalid_fixtures() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.jsonl");
+    std::fs::write(&path, "{\"request\":{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}}\n").unwrap();
+
+    let error = JsonClient::offline(&path).unwrap_err();
+    assert!(format!("{:#}", error).contains("broken.jsonl:1"), "{:#}", error);
+}
@@ -0,0 +1,222 @@
+//! `async fn` forms of the client traits
+//!
+//! [`McpClient`] and [`ProtocolClient`] return boxed futures so they stay
+//! object safe. The traits here express the same operations with
+//! return-position `impl Future`, which lets implementors write plain
+//! `async fn` bodies.
+//!
+//! The two forms convert freely:
+//! - every [`McpClient`] / [`ProtocolClient`] is also an [`AsyncMcpClient`] /
+//!   [`AsyncProtocolClient`] through a blanket impl
+//! - wrapping an async implementation in [`Compat`] yields the boxed form, so
+//!   it works with `dyn McpClient`, [`RequestBuilder`](crate::RequestBuilder)
+//!   and [`McpToolOperations`](crate::McpToolOperations)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mcp_client_traits::{AsyncMcpClient, ClientError, Compat, McpClient};
+//! use sweet_mcp_type::{Implementation, JsonValue, Response, ToolInfo};
+//!
+//! struct MyTransport;
+//!
+//! impl AsyncMcpClient for MyTransport {
+//!     async fn call_tool(&self, name: &str, args: JsonValue) -> Result<Response, ClientError> {
+//!         todo!("send {name} with {args:?}")
+//!     }
+//!     async fn list_tools(&self) -> Result<Vec<ToolInfo>, ClientError> {
+//!         todo!()
+//!     }
+//!     async fn initialize(
+//!         &self,
+//!         client_capabilities: JsonValue,
+//!         client_info: Implementation,
+//!     ) -> Result<Response, ClientError> {
+//!         todo!()
+//!     }
+//!     async fn ping(&self) -> Result<Response, ClientError> {
+//!         todo!()
+//!     }
+//! }
+//!
+//! let client: Box<dyn McpClient> = Box::new(Compat::new(MyTransport));
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use sweet_mcp_type::{Implementation, JsonValue, Request, Response, ToolInfo};
+
+use crate::errors::ClientError;
+use crate::traits::{McpClient, ProtocolClient};
+
+/// [`McpClient`] with `async fn` methods
+///
+/// Not object safe; wrap implementations in [`Compat`] where a
+/// `dyn McpClient` is needed.
+pub trait AsyncMcpClient: Send + Sync {
+    /// Execute a tool call with the specified name and arguments
+    fn call_tool(
+        &self,
+        name: &str,
+        args: JsonValue,
+    ) -> impl Future<Output = Result<Response, ClientError>> + Send;
+
+    /// List all available tools from the MCP server
+    fn list_tools(&self) -> impl Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send;
+
+    /// Initialize the MCP session with server capability negotiation
+    fn initialize(
+        &self,
+        client_capabilities: JsonValue,
+        client_info: Implementation,
+    ) -> impl Future<Output = Result<Response, ClientError>> + Send;
+
+    /// Send a ping request to test server connectivity
+    fn ping(&self) -> impl Future<Output = Result<Response, ClientError>> + Send;
+}
+
+/// [`ProtocolClient`] with an `async fn` send
+pub trait AsyncProtocolClient: Send + Sync {
+    /// The protocol-specific request type
+    type Request: Send;
+
+    /// The protocol-specific response type
+    type Response: Send;
+
+    /// Send a protocol-specific request and receive a response
+    fn send(
+        &self,
+        request: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ClientError>> + Send;
+
+    /// Convert a protocol-specific response to MCP Response
+    fn to_mcp_response(&self, response: Self::Response) -> Result<Response, ClientError>;
+
+    /// Convert MCP Request to protocol-specific request
+    #[allow(clippy::wrong_self_convention)]
+    fn from_mcp_request(&self, request: Request) -> Result<Self::Request, ClientError>;
+}
+
+// Every boxed-future client is usable where the async form is expected
+impl<T: McpClient + ?Sized> AsyncMcpClient for T {
+    fn call_tool(
+        &self,
+        name: &str,
+        args: JsonValue,
+    ) -> impl Future<Output = Result<Response, ClientError>> + Send {
+        McpClient::call_tool(self, name, args)
+    }
+
+    fn list_tools(&self) -> impl Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send {
+        McpClient::list_tools(self)
+    }
+
+    fn initialize(
+        &self,
+        client_capabilities: JsonValue,
+        client_info: Implementation,
+    ) -> impl Future<Output = Result<Response, ClientError>> + Send {
+        McpClient::initialize(self, client_capabilities, client_info)
+    }
+
+    fn ping(&self) -> impl Future<Output = Result<Response, ClientError>> + Send {
+        McpClient::ping(self)
+    }
+}
+
+impl<T: ProtocolClient + ?Sized> AsyncProtocolClient for T {
+    type Request = T::Request;
+    type Response = T::Response;
+
+    fn send(
+        &self,
+        request: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ClientError>> + Send {
+        ProtocolClient::send(self, request)
+    }
+
+    fn to_mcp_response(&self, response: Self::Response) -> Result<Response, ClientError> {
+        ProtocolClient::to_mcp_response(self, response)
+    }
+
+    fn from_mcp_request(&self, request: Request) -> Result<Self::Request, ClientError> {
+        ProtocolClient::from_mcp_request(self, request)
+    }
+}
+
+/// Adapter exposing an `async fn` implementation through the boxed-future traits
+///
+/// `Compat<T>` implements [`McpClient`] when `T: AsyncMcpClient` and
+/// [`ProtocolClient`] when `T: AsyncProtocolClient`.
+#[derive(Debug, Clone, Default)]
+pub struct Compat<T>(T);
+
+impl<T> Compat<T> {
+    /// Wrap an `async fn` client
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Borrow the wrapped client
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the wrapped client
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: AsyncMcpClient> McpClient for Compat<T> {
+    fn call_tool(
+        &self,
+        name: &str,
+        args: JsonValue,
+    ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        // The boxed form only borrows `self`, so the name must be owned
+        let name = name.to_string();
+        Box::pin(async move { self.0.call_tool(&name, args).await })
+    }
+
+    fn list_tools(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send + '_>> {
+        Box::pin(self.0.list_tools())
+    }
+
+    fn initialize(
+        &self,
+        client_capabilities: JsonValue,
+        client_info: Implementation,
+    ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        Box::pin(self.0.initialize(client_capabilities, client_info))
+    }
+
+    fn ping(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        Box::pin(self.0.ping())
+    }
+}
+
+impl<T: AsyncProtocolClient> ProtocolClient for Compat<T> {
+    type Request = T::Request;
+    type Response = T::Response;
+
+    fn send(
+        &self,
+        request: Self::Request,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Response, ClientError>> + Send + '_>> {
+        Box::pin(self.0.send(request))
+    }
+
+    fn to_mcp_response(&self, response: Self::Response) -> Result<Response, ClientError> {
+        self.0.to_mcp_response(response)
+    }
+
+    fn from_mcp_request(&self, request: Request) -> Result<Self::Request, ClientError> {
+        self.0.from_mcp_request(request)
+    }
+}
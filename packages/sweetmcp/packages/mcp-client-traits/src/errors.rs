@@ -89,6 +89,17 @@ pub enum ClientError {
         timeout_ms: u64,
     },
 
+    /// Request or response body exceeded a configured size limit
+    #[error("{payload} of {size} bytes exceeds the limit of {limit} bytes")]
+    PayloadTooLarge {
+        /// Which payload was rejected ("request body", "response body", ...)
+        payload: String,
+        /// Observed size in bytes (a lower bound when the body was cut off)
+        size: u64,
+        /// Configured limit in bytes
+        limit: u64,
+    },
+
     /// Configuration error
     #[error("Configuration error: {0}")]
     Configuration(String),
@@ -168,6 +179,24 @@ impl ClientError {
         }
     }
 
+    /// Create a payload size error
+    ///
+    /// # Arguments
+    /// * `payload` - Which payload was rejected
+    /// * `size` - Observed size in bytes
+    /// * `limit` - Configured limit in bytes
+    pub fn payload_too_large(
+        payload: impl Into<String>,
+        size: u64,
+        limit: u64,
+    ) -> Self {
+        Self::PayloadTooLarge {
+            payload: payload.into(),
+            size,
+            limit,
+        }
+    }
+
     /// Create a capability error
     ///
     /// # Arguments
@@ -228,6 +257,7 @@ impl ClientError {
             Self::ResponseParse { .. } => "error",
             Self::InvalidArgument { .. } => "warning",
             Self::Capability { .. } => "warning",
+            Self::PayloadTooLarge { .. } => "error",
             Self::Configuration(_) => "error",
             Self::RequestBuild(_) => "warning",
             Self::Serialization(_) => "error",
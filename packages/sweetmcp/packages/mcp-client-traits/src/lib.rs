@@ -11,6 +11,8 @@
 //! - [`McpToolOperations`] - Convenience methods for common tools (time, hash)
 //! - [`ProtocolClient`] - Protocol-specific client implementation interface
 //! - [`RequestBuilder`] - Fluent API for building tool requests
+//! - [`AsyncMcpClient`] / [`AsyncProtocolClient`] - `async fn` forms of the
+//!   client traits, bridged to the boxed forms by [`Compat`]
 //!
 //! # Example
 //!
@@ -35,12 +37,14 @@ pub mod traits;
 pub mod builders;
 pub mod errors;
 pub mod response;
+pub mod compat;
 
 // Re-export main types for convenience
 pub use traits::{McpClient, McpToolOperations, ProtocolClient, ClientCapabilities};
 pub use builders::{RequestBuilder, ToolRequestBuilder};
 pub use errors::ClientError;
 pub use response::{ResponseAdapter, ContentExtractor};
+pub use compat::{AsyncMcpClient, AsyncProtocolClient, Compat};
 
 // Re-export sweet-mcp-type for client implementations
 pub use sweet_mcp_type::{
@@ -0,0 +1,20 @@
+//! Raw request bodies through Cap'n Proto validation, parsing and the full
+//! normalizer, which is what the gateway runs on every binary body.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sweetmcp::normalize::conversion::to_json_rpc;
+use sweetmcp::normalize::parsers::{capnp_to_json_rpc, parse_capnp_message, validate_capnp_format};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = validate_capnp_format(data);
+    let _ = parse_capnp_message(data);
+
+    if let Ok(request) = capnp_to_json_rpc(data, "fuzz") {
+        assert_eq!(request["jsonrpc"], "2.0");
+        assert!(request["method"].is_string());
+    }
+
+    let _ = to_json_rpc("fuzz", data);
+});
@@ -0,0 +1,19 @@
+//! GraphQL documents through parsing, query limits, fragment resolution and
+//! conversion to a JSON-RPC call, both directly and as a GraphQL HTTP body.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::json;
+use sweetmcp::normalize::conversion::to_json_rpc;
+use sweetmcp::normalize::parsers::graphql_to_json_rpc;
+
+fuzz_target!(|query: &str| {
+    if let Ok(request) = graphql_to_json_rpc(query, json!({}), None, "fuzz") {
+        assert_eq!(request["jsonrpc"], "2.0");
+        assert!(request["method"].is_string());
+    }
+
+    let body = json!({ "query": query, "variables": {} }).to_string();
+    let _ = to_json_rpc("fuzz", body.as_bytes());
+});
@@ -0,0 +1,14 @@
+//! Drain status admin endpoint handler
+
+use anyhow::{Context, Result};
+
+use crate::shutdown::ShutdownCoordinator;
+
+/// Admin path serving the current drain status
+pub const DRAIN_STATUS_PATH: &str = "/admin/drain";
+
+/// Handle /admin/drain endpoint, returning the drain status as JSON
+pub fn handle_drain_request(coordinator: &ShutdownCoordinator) -> Result<String> {
+    serde_json::to_string(&coordinator.drain_status())
+        .context("Failed to serialize drain status")
+}
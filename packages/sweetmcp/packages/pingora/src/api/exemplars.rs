@@ -0,0 +1,12 @@
+//! Tool metric exemplars admin endpoint handler
+
+use anyhow::{Context, Result};
+
+/// Admin path serving the latest trace-linked tool metric observations
+pub const TOOL_EXEMPLARS_PATH: &str = "/admin/metrics/exemplars";
+
+/// Handle /admin/metrics/exemplars endpoint, returning the exemplars as JSON
+pub fn handle_exemplars_request() -> Result<String> {
+    serde_json::to_string(&crate::metrics::tool_exemplars())
+        .context("Failed to serialize tool exemplars")
+}
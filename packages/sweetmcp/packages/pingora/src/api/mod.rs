@@ -1,3 +1,7 @@
 //! API endpoint handlers for peer discovery and management
 
+pub mod drain;
+pub mod exemplars;
 pub mod peers;
+pub mod tenants;
+pub mod tokens;
@@ -0,0 +1,110 @@
+//! Tenant admin endpoint handlers
+
+use serde_json::json;
+
+use crate::tenants::{CreateTenantRequest, TenantStore, UpdateTenantRequest};
+
+/// Admin path listing (`GET`) and creating (`POST`) tenants
+///
+/// `/admin/tenants/{id}` shows (`GET`), updates (`PATCH`) or deletes
+/// (`DELETE`) one tenant.
+pub const TENANTS_PATH: &str = "/admin/tenants";
+
+/// Largest create or update request body that is read
+pub const MAX_TENANT_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Whether `path` is served by [`handle_tenants_request`]
+pub fn is_tenants_path(path: &str) -> bool {
+    path == TENANTS_PATH || tenant_id(path).is_some()
+}
+
+/// Tenant id of a `/admin/tenants/{id}` path
+fn tenant_id(path: &str) -> Option<&str> {
+    path.strip_prefix(TENANTS_PATH)?
+        .strip_prefix('/')
+        .filter(|id| !id.is_empty() && !id.contains('/'))
+}
+
+/// Handle /admin/tenants endpoints, returning the status and JSON body
+pub fn handle_tenants_request(
+    store: &TenantStore,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> (u16, String) {
+    if !store.is_enabled() {
+        return error(404, "tenants are disabled");
+    }
+
+    match (method, tenant_id(path)) {
+        ("GET", None) => (200, json!({ "tenants": store.list() }).to_string()),
+        ("POST", None) => {
+            let request: CreateTenantRequest = match serde_json::from_slice(body) {
+                Ok(request) => request,
+                Err(e) => return error(400, &format!("invalid tenant request: {}", e)),
+            };
+            if let Some(problem) = request.problem() {
+                return error(400, &problem);
+            }
+            match store.create(request) {
+                Ok(Some(tenant)) => {
+                    log::info!("Created tenant {} for {}", tenant.id, tenant.name);
+                    (201, json!(tenant).to_string())
+                }
+                Ok(None) => error(409, "a tenant with this id already exists"),
+                Err(e) => {
+                    log::error!("Failed to create tenant: {:#}", e);
+                    error(500, "failed to store tenant")
+                }
+            }
+        }
+        ("GET", Some(id)) => match store.get(id) {
+            Some(tenant) => (200, json!(tenant).to_string()),
+            None => error(404, "no such tenant"),
+        },
+        ("PATCH", Some(id)) => {
+            let request: UpdateTenantRequest = match serde_json::from_slice(body) {
+                Ok(request) => request,
+                Err(e) => return error(400, &format!("invalid tenant request: {}", e)),
+            };
+            if let Some(problem) = request.problem() {
+                return error(400, &problem);
+            }
+            match store.update(id, request) {
+                Ok(Some(tenant)) => {
+                    log::info!(
+                        "Updated tenant {} ({})",
+                        tenant.id,
+                        if tenant.is_active() {
+                            "active"
+                        } else {
+                            "suspended"
+                        }
+                    );
+                    (200, json!(tenant).to_string())
+                }
+                Ok(None) => error(404, "no such tenant"),
+                Err(e) => {
+                    log::error!("Failed to update tenant {}: {:#}", id, e);
+                    error(500, "failed to store tenant")
+                }
+            }
+        }
+        ("DELETE", Some(id)) => match store.remove(id) {
+            Ok(Some(removed)) => {
+                log::info!("Deleted tenant {} ({})", removed.id, removed.name);
+                (200, json!(removed).to_string())
+            }
+            Ok(None) => error(404, "no such tenant"),
+            Err(e) => {
+                log::error!("Failed to delete tenant {}: {:#}", id, e);
+                error(500, "failed to store tenant")
+            }
+        },
+        _ => error(405, "method not allowed"),
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, String) {
+    (status, json!({ "error": message }).to_string())
+}
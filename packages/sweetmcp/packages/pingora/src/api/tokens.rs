@@ -0,0 +1,93 @@
+//! Client token admin endpoint handlers
+
+use serde_json::json;
+
+use crate::client_tokens::{ClientTokenStore, IssueTokenRequest};
+use crate::tenants::TenantStore;
+
+/// Admin path listing (`GET`) and issuing (`POST`) client tokens
+///
+/// `DELETE` on `/admin/tokens/{id}` revokes a token.
+pub const CLIENT_TOKENS_PATH: &str = "/admin/tokens";
+
+/// Largest issue request body that is read
+pub const MAX_TOKEN_REQUEST_BYTES: usize = 16 * 1024;
+
+/// Whether `path` is served by [`handle_client_tokens_request`]
+pub fn is_client_tokens_path(path: &str) -> bool {
+    path == CLIENT_TOKENS_PATH || token_id(path).is_some()
+}
+
+/// Token id of a `/admin/tokens/{id}` path
+fn token_id(path: &str) -> Option<&str> {
+    path.strip_prefix(CLIENT_TOKENS_PATH)?
+        .strip_prefix('/')
+        .filter(|id| !id.is_empty() && !id.contains('/'))
+}
+
+/// Handle /admin/tokens endpoints, returning the status and JSON body
+///
+/// With tenancy enabled every token is issued for an existing tenant.
+pub fn handle_client_tokens_request(
+    store: &ClientTokenStore,
+    tenants: &TenantStore,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> (u16, String) {
+    if !store.is_enabled() {
+        return error(404, "client tokens are disabled");
+    }
+
+    match (method, token_id(path)) {
+        ("GET", None) => (200, json!({ "tokens": store.list() }).to_string()),
+        ("POST", None) => {
+            let request: IssueTokenRequest = match serde_json::from_slice(body) {
+                Ok(request) => request,
+                Err(e) => return error(400, &format!("invalid token request: {}", e)),
+            };
+            if let Some(problem) = request.problem(store.config()) {
+                return error(400, &problem);
+            }
+            match &request.tenant {
+                Some(tenant) if tenants.get(tenant).is_none() => {
+                    return error(400, &format!("unknown tenant `{}`", tenant));
+                }
+                None if tenants.is_enabled() => {
+                    return error(400, "tenant is required while tenants are enabled");
+                }
+                _ => {}
+            }
+            match store.issue(request) {
+                Ok(issued) => {
+                    log::info!(
+                        "Issued client token {} for {}",
+                        issued.client.id,
+                        issued.client.name
+                    );
+                    (201, json!(issued).to_string())
+                }
+                Err(e) => {
+                    log::error!("Failed to issue client token: {:#}", e);
+                    error(500, "failed to store client token")
+                }
+            }
+        }
+        ("DELETE", Some(id)) => match store.revoke(id) {
+            Ok(Some(revoked)) => {
+                log::info!("Revoked client token {} for {}", revoked.id, revoked.name);
+                (200, json!(revoked).to_string())
+            }
+            Ok(None) => error(404, "no such client token"),
+            Err(e) => {
+                log::error!("Failed to revoke client token {}: {:#}", id, e);
+                error(500, "failed to store client token")
+            }
+        },
+        _ => error(405, "method not allowed"),
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, String) {
+    (status, json!({ "error": message }).to_string())
+}
@@ -1,7 +1,7 @@
 //! Circuit breaker implementation for preventing cascading failures
 //!
 //! This module provides:
-//! - Hystrix-style circuit breaker per peer
+//! - Hystrix-style circuit breaker per peer and per plugin tool
 //! - Configurable error thresholds
 //! - Half-open state testing
 //! - Metrics emission for state changes
@@ -143,19 +143,37 @@ impl CircuitBreaker {
                 // Check if we should transition to half-open
                 if self.should_attempt_reset().await {
                     self.transition_to_half_open().await;
-                    true
+                    // This request is the first trial
+                    self.take_half_open_slot()
                 } else {
                     false
                 }
             }
             CircuitState::HalfOpen => {
                 // Allow limited requests in half-open state
-                let remaining = self.half_open_remaining.fetch_sub(1, Ordering::SeqCst);
-                remaining > 0
+                self.take_half_open_slot()
             }
         }
     }
 
+    /// Claim one of the trial requests allowed while half-open
+    fn take_half_open_slot(&self) -> bool {
+        self.half_open_remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Time left before an open circuit lets a trial request through
+    ///
+    /// `None` unless the circuit is open.
+    pub async fn retry_after(&self) -> Option<Duration> {
+        if *self.state.read().await != CircuitState::Open {
+            return None;
+        }
+        let opened_at = (*self.opened_at.read().await)?;
+        Some(self.config.sleep_window.saturating_sub(opened_at.elapsed()))
+    }
+
     /// Record a successful request
     pub async fn record_success(&self) {
         self.total_requests.fetch_add(1, Ordering::SeqCst);
@@ -309,39 +327,59 @@ impl CircuitBreaker {
     }
 }
 
-/// Circuit breaker manager for all peers
+/// Prefix of the breaker keys guarding plugin tools, e.g. `tool:fs`
+pub const TOOL_BREAKER_PREFIX: &str = "tool:";
+
+/// Circuit breaker manager for all peers and plugin tools
 pub struct CircuitBreakerManager {
-    /// Circuit breakers per peer
+    /// Circuit breakers per peer and per tool
     breakers: Arc<RwLock<std::collections::HashMap<String, Arc<CircuitBreaker>>>>,
     /// Default configuration
     default_config: CircuitBreakerConfig,
+    /// Configuration for tool breakers
+    tool_config: CircuitBreakerConfig,
 }
 
 impl CircuitBreakerManager {
-    /// Create a new manager
+    /// Create a new manager; tool breakers use `default_config` too
     pub fn new(default_config: CircuitBreakerConfig) -> Self {
         Self {
             breakers: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            tool_config: default_config.clone(),
             default_config,
         }
     }
 
+    /// Use `tool_config` for the breakers guarding plugin tools
+    pub fn with_tool_config(mut self, tool_config: CircuitBreakerConfig) -> Self {
+        self.tool_config = tool_config;
+        self
+    }
+
     /// Get or create circuit breaker for a peer
     pub async fn get_breaker(&self, peer_id: &str) -> Arc<CircuitBreaker> {
+        self.get_or_create(peer_id, &self.default_config).await
+    }
+
+    /// Get or create circuit breaker for a plugin tool
+    pub async fn get_tool_breaker(&self, tool: &str) -> Arc<CircuitBreaker> {
+        self.get_or_create(&tool_breaker_key(tool), &self.tool_config)
+            .await
+    }
+
+    async fn get_or_create(&self, key: &str, config: &CircuitBreakerConfig) -> Arc<CircuitBreaker> {
         let breakers = self.breakers.read().await;
-        if let Some(breaker) = breakers.get(peer_id) {
+        if let Some(breaker) = breakers.get(key) {
             return breaker.clone();
         }
         drop(breakers);
 
-        // Create new breaker
+        // Create new breaker unless another task won the race
         let mut breakers = self.breakers.write().await;
-        let breaker = Arc::new(CircuitBreaker::new(
-            peer_id.to_string(),
-            self.default_config.clone(),
-        ));
-        breakers.insert(peer_id.to_string(), breaker.clone());
-        breaker
+        breakers
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(key.to_string(), config.clone())))
+            .clone()
     }
 
     /// Remove a circuit breaker
@@ -359,3 +397,8 @@ impl CircuitBreakerManager {
             .collect()
     }
 }
+
+/// Breaker key for a plugin tool
+pub fn tool_breaker_key(tool: &str) -> String {
+    format!("{}{}", TOOL_BREAKER_PREFIX, tool)
+}
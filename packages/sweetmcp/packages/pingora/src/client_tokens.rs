@@ -0,0 +1,425 @@
+//! Scoped client tokens issued through the admin API
+//!
+//! Teams onboard agent clients with tokens of their own instead of sharing
+//! the discovery token. Each token names the tools it may call, when it
+//! expires and the rate tier it is held to; clients send it in the
+//! `x-api-key` header. The store keeps only a SHA-256 hash of every token, in
+//! a small JSON file that is replaced atomically on each change, so a token
+//! is shown once when it is issued and stays valid across restarts until it
+//! expires or is revoked.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Prefix of every issued token, so a leaked token is easy to recognise
+pub const TOKEN_PREFIX: &str = "smcp_";
+
+/// How long revoked and expired tokens stay listed before they are dropped
+pub const RETENTION: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Client token settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientTokensConfig {
+    /// Accept client tokens and serve the token admin endpoints
+    pub enabled: bool,
+    /// JSON file the token store is kept in
+    pub store_path: PathBuf,
+    /// Lifetime of a token issued without `expires_in_secs`
+    pub default_ttl: Duration,
+    /// Longest lifetime a token may be issued with
+    pub max_ttl: Duration,
+    /// Requests per minute of the `low` tier
+    pub low_rpm: u32,
+    /// Requests per minute of the `standard` tier
+    pub standard_rpm: u32,
+    /// Requests per minute of the `high` tier
+    pub high_rpm: u32,
+}
+
+impl Default for ClientTokensConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            store_path: default_store_path(),
+            default_ttl: Duration::from_secs(30 * 24 * 3600),
+            max_ttl: Duration::from_secs(365 * 24 * 3600),
+            low_rpm: 60,
+            standard_rpm: 600,
+            high_rpm: 6000,
+        }
+    }
+}
+
+impl ClientTokensConfig {
+    /// Requests per minute allowed by `tier`
+    pub fn rpm(&self, tier: RateTier) -> u32 {
+        match tier {
+            RateTier::Low => self.low_rpm,
+            RateTier::Standard => self.standard_rpm,
+            RateTier::High => self.high_rpm,
+        }
+    }
+}
+
+/// `client_tokens.json` next to the TLS certificate directory
+pub fn default_store_path() -> PathBuf {
+    crate::get_cert_dir().with_file_name("client_tokens.json")
+}
+
+/// Request rate a token is held to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateTier {
+    Low,
+    #[default]
+    Standard,
+    High,
+}
+
+/// A token's scope and lifetime, as listed by the admin API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientToken {
+    pub id: String,
+    /// Who the token was issued to
+    pub name: String,
+    /// Tool names, or prefixes ending in `*`, the token may call
+    pub allowed_tools: Vec<String>,
+    pub rate_tier: RateTier,
+    /// Tenant the token's requests belong to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// Unix seconds
+    pub created_at: u64,
+    /// Unix seconds
+    pub expires_at: u64,
+    /// Unix seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<u64>,
+}
+
+impl ClientToken {
+    /// Whether the token may call `tool`
+    pub fn allows_tool(&self, tool: &str) -> bool {
+        matches_tool(&self.allowed_tools, tool)
+    }
+
+    /// Whether the token is neither revoked nor expired at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at
+    }
+
+    /// First `tools/call` in a JSON-RPC message or batch the token may not make
+    pub fn first_denied_tool(&self, body: &[u8]) -> Option<String> {
+        first_denied_tool(body, |tool| self.allows_tool(tool))
+    }
+}
+
+/// Whether `tool` matches one of `patterns`, tool names or prefixes ending in `*`
+pub fn matches_tool(patterns: &[String], tool: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => tool.starts_with(prefix),
+            None => tool == pattern,
+        })
+}
+
+/// What is wrong with a list of tool patterns, if anything
+pub fn tool_patterns_problem(patterns: &[String]) -> Option<String> {
+    if patterns.is_empty() {
+        return Some("allowed_tools must name at least one tool".to_string());
+    }
+    patterns
+        .iter()
+        .find(|tool| tool.is_empty() || tool.strip_suffix('*').unwrap_or(tool).contains('*'))
+        .map(|tool| {
+            format!(
+                "tool `{}` must be a tool name or a prefix ending in `*`",
+                tool
+            )
+        })
+}
+
+/// First `tools/call` in a JSON-RPC message or batch that `allows` refuses
+///
+/// Bodies that are not JSON are left for the peer to reject.
+pub fn first_denied_tool(body: &[u8], allows: impl Fn(&str) -> bool) -> Option<String> {
+    let request: Value = serde_json::from_slice(body).ok()?;
+    let messages: Vec<&Value> = match &request {
+        Value::Array(batch) => batch.iter().collect(),
+        message => vec![message],
+    };
+    messages
+        .into_iter()
+        .filter(|message| message.get("method").and_then(Value::as_str) == Some("tools/call"))
+        .map(|message| {
+            message
+                .pointer("/params/name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        })
+        .find(|tool| !allows(tool))
+        .map(str::to_string)
+}
+
+/// Body of a token issue request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IssueTokenRequest {
+    pub name: String,
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub rate_tier: RateTier,
+    /// Tenant the token's requests belong to
+    #[serde(default)]
+    pub tenant: Option<String>,
+    /// Defaults to `client_tokens.default_ttl`
+    #[serde(default)]
+    pub expires_in_secs: Option<u64>,
+}
+
+impl IssueTokenRequest {
+    /// What is wrong with the request, if anything
+    pub fn problem(&self, config: &ClientTokensConfig) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("name must not be empty".to_string());
+        }
+        if let Some(problem) = tool_patterns_problem(&self.allowed_tools) {
+            return Some(problem);
+        }
+        match self.expires_in_secs {
+            Some(0) => Some("expires_in_secs must be greater than 0".to_string()),
+            Some(secs) if secs > config.max_ttl.as_secs() => Some(format!(
+                "expires_in_secs must be at most {}",
+                config.max_ttl.as_secs()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A newly issued token; `token` is never shown again
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedToken {
+    pub token: String,
+    #[serde(flatten)]
+    pub client: ClientToken,
+}
+
+/// A token as written to the store file
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredToken {
+    hash: String,
+    #[serde(flatten)]
+    client: ClientToken,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreFile {
+    tokens: Vec<StoredToken>,
+}
+
+/// Issued tokens, keyed by hash, and their request counts
+pub struct ClientTokenStore {
+    config: ClientTokensConfig,
+    tokens: RwLock<HashMap<String, ClientToken>>,
+    // Requests in the current one-minute window per token id: (count, window_start)
+    windows: DashMap<String, (u32, Instant)>,
+}
+
+impl ClientTokenStore {
+    /// Open the store, loading the tokens saved at `config.store_path`
+    ///
+    /// A missing file is an empty store; an unreadable one is an error, so a
+    /// damaged store never silently revokes every client.
+    pub fn open(config: ClientTokensConfig) -> Result<Self> {
+        let mut tokens = HashMap::new();
+        if config.enabled {
+            match std::fs::read(&config.store_path) {
+                Ok(bytes) => {
+                    let file: StoreFile = serde_json::from_slice(&bytes).with_context(|| {
+                        format!("Invalid client token store {}", config.store_path.display())
+                    })?;
+                    for stored in file.tokens {
+                        tokens.insert(stored.hash, stored.client);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!(
+                            "Failed to read client token store {}",
+                            config.store_path.display()
+                        )
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            config,
+            tokens: RwLock::new(tokens),
+            windows: DashMap::new(),
+        })
+    }
+
+    /// Whether client tokens are accepted
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &ClientTokensConfig {
+        &self.config
+    }
+
+    /// Issue a token for a request that passed [`IssueTokenRequest::problem`]
+    pub fn issue(&self, request: IssueTokenRequest) -> Result<IssuedToken> {
+        let mut secret = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .map_err(|_| anyhow::anyhow!("Failed to generate client token"))?;
+        let token = format!("{}{}", TOKEN_PREFIX, base64_url::encode(&secret));
+
+        let now = unix_now();
+        let ttl = request
+            .expires_in_secs
+            .unwrap_or(self.config.default_ttl.as_secs())
+            .min(self.config.max_ttl.as_secs());
+        let client = ClientToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: request.name.trim().to_string(),
+            allowed_tools: request.allowed_tools,
+            rate_tier: request.rate_tier,
+            tenant: request.tenant,
+            created_at: now,
+            expires_at: now + ttl,
+            revoked_at: None,
+        };
+
+        let hash = hash_token(&token);
+        let mut tokens = self.tokens.write().unwrap_or_else(|e| e.into_inner());
+        tokens.insert(hash.clone(), client.clone());
+        if let Err(e) = self.save(&mut tokens) {
+            tokens.remove(&hash);
+            return Err(e);
+        }
+        Ok(IssuedToken { token, client })
+    }
+
+    /// Every stored token, oldest first
+    pub fn list(&self) -> Vec<ClientToken> {
+        let tokens = self.tokens.read().unwrap_or_else(|e| e.into_inner());
+        let mut list: Vec<ClientToken> = tokens.values().cloned().collect();
+        list.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        list
+    }
+
+    /// Revoke the token with `id`; `None` if there is no such token
+    pub fn revoke(&self, id: &str) -> Result<Option<ClientToken>> {
+        let mut tokens = self.tokens.write().unwrap_or_else(|e| e.into_inner());
+        let Some(client) = tokens.values_mut().find(|client| client.id == id) else {
+            return Ok(None);
+        };
+        if client.revoked_at.is_some() {
+            return Ok(Some(client.clone()));
+        }
+        client.revoked_at = Some(unix_now());
+        let revoked = client.clone();
+
+        if let Err(e) = self.save(&mut tokens) {
+            if let Some(client) = tokens.values_mut().find(|client| client.id == id) {
+                client.revoked_at = None;
+            }
+            return Err(e);
+        }
+        self.windows.remove(id);
+        Ok(Some(revoked))
+    }
+
+    /// The active token `token` was issued as, if any
+    pub fn authenticate(&self, token: &str) -> Option<ClientToken> {
+        if !self.config.enabled || !token.starts_with(TOKEN_PREFIX) {
+            return None;
+        }
+        let tokens = self.tokens.read().unwrap_or_else(|e| e.into_inner());
+        tokens
+            .get(&hash_token(token))
+            .filter(|client| client.is_active(unix_now()))
+            .cloned()
+    }
+
+    /// Count a request against the token's rate tier; `false` once it is spent
+    pub fn check_rate(&self, client: &ClientToken) -> bool {
+        let limit = self.config.rpm(client.rate_tier);
+        let now = Instant::now();
+        let mut window = self.windows.entry(client.id.clone()).or_insert((0, now));
+        if now.duration_since(window.1) >= Duration::from_secs(60) {
+            *window = (0, now);
+        }
+        if window.0 >= limit {
+            return false;
+        }
+        window.0 += 1;
+        true
+    }
+
+    /// Drop long-gone tokens and write the rest to the store file
+    fn save(&self, tokens: &mut HashMap<String, ClientToken>) -> Result<()> {
+        let cutoff = unix_now().saturating_sub(RETENTION.as_secs());
+        tokens.retain(|_, client| {
+            let ended_at = client
+                .revoked_at
+                .map_or(client.expires_at, |revoked| revoked.min(client.expires_at));
+            ended_at > cutoff
+        });
+
+        let file = StoreFile {
+            tokens: tokens
+                .iter()
+                .map(|(hash, client)| StoredToken {
+                    hash: hash.clone(),
+                    client: client.clone(),
+                })
+                .collect(),
+        };
+        let bytes =
+            serde_json::to_vec_pretty(&file).context("Failed to serialize client tokens")?;
+        write_atomically(&self.config.store_path, &bytes)
+    }
+}
+
+/// Replace `path` with `bytes` through a staging file
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let staging = path.with_extension("json.tmp");
+    std::fs::write(&staging, bytes)
+        .with_context(|| format!("Failed to write {}", staging.display()))?;
+    std::fs::rename(&staging, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Hex SHA-256 of a token, the only form in which tokens are stored
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
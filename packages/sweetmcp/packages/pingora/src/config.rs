@@ -1,11 +1,32 @@
 //! Configuration management for SweetMCP Server
-
-use std::{env, sync::Arc, time::Duration};
+//!
+//! Settings come from environment variables, then an optional TOML or YAML
+//! file (`--config` or `SWEETMCP_CONFIG`), then built-in defaults.
+
+use std::{
+    env,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::rate_limit::{
+    algorithms::RateLimitAlgorithmConfig, distributed::EndpointRateConfig,
+    limiter::TokenBucketConfig,
+};
+
+/// Environment variable naming the configuration file
+pub const CONFIG_FILE_ENV: &str = "SWEETMCP_CONFIG";
+
+/// Where the gateway forwards local MCP requests unless configured otherwise
+pub const DEFAULT_PLUGIN_HOST_URL: &str = "http://localhost:8080/rpc";
+
 /// Authentication configuration
 #[derive(Clone, Debug)]
 pub struct AuthConfig {
@@ -81,8 +102,62 @@ pub struct Config {
     /// Request timeout duration
     pub request_timeout: Duration,
 
+    /// Deadline for draining in-flight requests during shutdown
+    pub drain_timeout: Duration,
+
+    /// Log level; `None` keeps the logger's default
+    #[serde(skip)]
+    pub log_level: Option<log::LevelFilter>,
+
     /// Rate limiting configuration
     pub rate_limit: RateLimitConfig,
+
+    /// GraphQL depth and complexity limits applied by the normalizer
+    #[serde(default)]
+    pub graphql_limits: crate::normalize::QueryLimits,
+
+    /// JSON-RPC batch size limit applied by the normalizer
+    #[serde(default)]
+    pub jsonrpc_batch: crate::normalize::BatchLimits,
+
+    /// MCP bridge queue capacity and overflow policy
+    #[serde(default)]
+    pub bridge_queue: crate::mcp_bridge::BridgeQueueConfig,
+
+    /// Aggregated `tools/list` cache
+    #[serde(default)]
+    pub tool_list_cache: crate::tool_cache::ToolListCacheConfig,
+
+    /// `Idempotency-Key` replay window and store size
+    #[serde(default)]
+    pub idempotency: crate::idempotency::IdempotencyConfig,
+
+    /// Streaming of large tool results to the client
+    #[serde(default)]
+    pub result_streaming: crate::streaming::ResultStreamingConfig,
+
+    /// Sticky routing of MCP sessions to one peer
+    #[serde(default)]
+    pub session_affinity: crate::session_affinity::SessionAffinityConfig,
+
+    /// Rewriting of tool call arguments before dispatch
+    #[serde(default)]
+    pub request_transforms: crate::transform::TransformConfig,
+
+    /// Scoped client tokens issued through the admin API
+    #[serde(default)]
+    pub client_tokens: crate::client_tokens::ClientTokensConfig,
+
+    /// Tenant namespaces managed through the admin API
+    #[serde(default)]
+    pub tenants: crate::tenants::TenantsConfig,
+
+    /// JSON-RPC endpoint of the local plugin host (`sweet serve --http`)
+    pub plugin_host_url: String,
+
+    /// Upstream MCP servers federated through the gateway
+    #[serde(default)]
+    pub mcp_upstreams: Vec<crate::upstream::UpstreamServerConfig>,
 }
 
 /// Rate limiting configuration
@@ -123,80 +198,302 @@ impl Default for Config {
             health_check_interval: Duration::from_secs(5),
             circuit_breaker_threshold: 50,
             request_timeout: Duration::from_secs(30),
+            drain_timeout: Duration::from_secs(30),
+            log_level: None,
             rate_limit: RateLimitConfig {
                 per_user_rps: 100,
                 per_ip_rps: 1000,
                 burst_capacity: 50,
             },
+            graphql_limits: crate::normalize::QueryLimits::default(),
+            jsonrpc_batch: crate::normalize::BatchLimits::default(),
+            bridge_queue: crate::mcp_bridge::BridgeQueueConfig::default(),
+            tool_list_cache: crate::tool_cache::ToolListCacheConfig::default(),
+            idempotency: crate::idempotency::IdempotencyConfig::default(),
+            result_streaming: crate::streaming::ResultStreamingConfig::default(),
+            session_affinity: crate::session_affinity::SessionAffinityConfig::default(),
+            request_transforms: crate::transform::TransformConfig::default(),
+            client_tokens: crate::client_tokens::ClientTokensConfig::default(),
+            tenants: crate::tenants::TenantsConfig::default(),
+            plugin_host_url: DEFAULT_PLUGIN_HOST_URL.to_string(),
+            mcp_upstreams: Vec::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Per-IP token bucket applied to endpoints without a dedicated limit
+    ///
+    /// Buckets hold `burst_capacity` tokens and refill at `per_ip_rps`.
+    pub fn endpoint_config(&self) -> EndpointRateConfig {
+        EndpointRateConfig {
+            algorithm: RateLimitAlgorithmConfig::TokenBucket(TokenBucketConfig {
+                capacity: self.burst_capacity,
+                refill_rate: f64::from(self.per_ip_rps),
+                initial_tokens: self.burst_capacity,
+            }),
+            per_peer: true,
+            trusted_multiplier: 1.0,
         }
     }
 }
 
+/// Settings read from a TOML or YAML configuration file
+///
+/// Every key is optional; environment variables override the file and
+/// built-in defaults fill the rest. Secrets (`SWEETMCP_JWT_SECRET`,
+/// `SWEETMCP_DISCOVERY_TOKEN`) are only read from the environment.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub inflight_max: Option<u64>,
+    pub upstreams: Option<Vec<String>>,
+    pub tcp_bind: Option<String>,
+    pub mcp_bind: Option<String>,
+    pub uds_path: Option<String>,
+    pub workers: Option<usize>,
+    pub metrics_bind: Option<String>,
+    /// Duration such as "1h"
+    pub jwt_expiry: Option<String>,
+    pub health_check_interval: Option<String>,
+    pub circuit_breaker_threshold: Option<u32>,
+    pub request_timeout: Option<String>,
+    pub drain_timeout: Option<String>,
+    /// One of "off", "error", "warn", "info", "debug" or "trace"
+    pub log_level: Option<String>,
+    pub rate_limit: RateLimitSection,
+    pub graphql_limits: GraphqlLimitsSection,
+    pub jsonrpc_batch: JsonrpcBatchSection,
+    pub bridge_queue: BridgeQueueSection,
+    pub tool_list_cache: ToolListCacheSection,
+    pub idempotency: IdempotencySection,
+    pub result_streaming: ResultStreamingSection,
+    pub session_affinity: SessionAffinitySection,
+    pub request_transforms: RequestTransformsSection,
+    pub client_tokens: ClientTokensSection,
+    pub tenants: TenantsSection,
+    pub plugin_host_url: Option<String>,
+    /// JSON list of upstream MCP servers, relative to the config file
+    pub mcp_upstreams_file: Option<PathBuf>,
+    pub require_https: Option<bool>,
+    pub max_auth_attempts: Option<u32>,
+}
+
+/// `[rate_limit]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitSection {
+    pub per_user_rps: Option<u32>,
+    pub per_ip_rps: Option<u32>,
+    pub burst_capacity: Option<u32>,
+}
+
+/// `[graphql_limits]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphqlLimitsSection {
+    pub max_depth: Option<usize>,
+    pub max_complexity: Option<usize>,
+}
+
+/// `[jsonrpc_batch]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JsonrpcBatchSection {
+    pub max_batch_size: Option<usize>,
+}
+
+/// `[bridge_queue]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BridgeQueueSection {
+    pub capacity: Option<usize>,
+    /// "reject" or "shed_oldest"
+    pub overflow_policy: Option<String>,
+}
+
+/// `[tool_list_cache]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolListCacheSection {
+    pub enabled: Option<bool>,
+    /// Duration such as "5m"
+    pub ttl: Option<String>,
+}
+
+/// `[idempotency]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdempotencySection {
+    pub enabled: Option<bool>,
+    /// Duration such as "24h"
+    pub ttl: Option<String>,
+    pub max_entries: Option<usize>,
+    /// Duration such as "30s"
+    pub wait_timeout: Option<String>,
+    pub max_response_bytes: Option<usize>,
+}
+
+/// `[result_streaming]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResultStreamingSection {
+    pub enabled: Option<bool>,
+    pub threshold_bytes: Option<usize>,
+    pub chunk_bytes: Option<usize>,
+}
+
+/// `[session_affinity]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionAffinitySection {
+    pub enabled: Option<bool>,
+    /// `tools/call` argument names such as ["session_id"]
+    pub arguments: Option<Vec<String>>,
+}
+
+/// `[client_tokens]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientTokensSection {
+    pub enabled: Option<bool>,
+    pub store_path: Option<PathBuf>,
+    /// Duration such as "30d"
+    pub default_ttl: Option<String>,
+    /// Duration such as "365d"
+    pub max_ttl: Option<String>,
+    pub low_rpm: Option<u32>,
+    pub standard_rpm: Option<u32>,
+    pub high_rpm: Option<u32>,
+}
+
+/// `[tenants]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TenantsSection {
+    pub enabled: Option<bool>,
+    pub store_path: Option<PathBuf>,
+    pub default_rpm: Option<u32>,
+}
+
+/// `[request_transforms]` section of the configuration file
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestTransformsSection {
+    pub dry_run: Option<bool>,
+    /// `[[request_transforms.rules]]` tables, applied in order
+    pub rules: Option<Vec<crate::transform::TransformRule>>,
+}
+
+impl ConfigFile {
+    /// Read a configuration file; the format follows the extension
+    /// (`.toml`, `.yaml` or `.yml`)
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut file: Self = ::config::Config::builder()
+            .add_source(::config::File::from(path))
+            .build()
+            .and_then(|settings| settings.try_deserialize())
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        if let (Some(upstreams_file), Some(dir)) = (&file.mcp_upstreams_file, path.parent())
+            && upstreams_file.is_relative()
+        {
+            file.mcp_upstreams_file = Some(dir.join(upstreams_file));
+        }
+        Ok(file)
+    }
+}
+
 impl Config {
     /// Load configuration from environment variables
+    #[allow(dead_code)]
     pub fn from_env() -> Result<Self> {
         // Load .env file if present
         dotenvy::dotenv().ok();
 
+        Self::from_sources(&ConfigFile::default())
+    }
+
+    /// Load configuration from a file layered under environment variables
+    ///
+    /// `path` falls back to `SWEETMCP_CONFIG`; without either, only the
+    /// environment is read. Every invalid value is reported in one error.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        // Load .env file if present
+        dotenvy::dotenv().ok();
+
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| env::var_os(CONFIG_FILE_ENV).map(PathBuf::from));
+        match path {
+            Some(path) => Self::from_sources(&ConfigFile::load(&path)?),
+            None => Self::from_sources(&ConfigFile::default()),
+        }
+    }
+
+    /// Resolve every setting from the environment, then `file`, then defaults
+    fn from_sources(file: &ConfigFile) -> Result<Self> {
+        let mut settings = Resolver::default();
+
         // JWT secret handling - auto-generate in dev mode if not provided
-        let secret_b64 = match env::var("SWEETMCP_JWT_SECRET") {
-            Ok(secret) => secret,
+        let secret = match env::var("SWEETMCP_JWT_SECRET") {
+            Ok(secret_b64) => decode_jwt_secret(&secret_b64).unwrap_or_else(|e| {
+                settings.errors.push(format!("{:#}", e));
+                [0u8; 32]
+            }),
             Err(_) => {
                 // Check if in development mode
                 let is_dev_mode = cfg!(debug_assertions) || env::var("SWEETMCP_DEV_MODE").is_ok();
 
+                let mut secret_bytes = [0u8; 32];
                 if is_dev_mode {
                     // Generate random 32 bytes
                     let mut rng = rand::rng();
-                    let mut secret_bytes = [0u8; 32];
                     rng.fill(&mut secret_bytes);
                     let generated_secret = base64_url::encode(&secret_bytes);
 
                     // Print warning in dev mode only
-                    log::warn!("No JWT secret configured - using auto-generated secret (DEVELOPMENT ONLY)");
+                    log::warn!(
+                        "No JWT secret configured - using auto-generated secret (DEVELOPMENT ONLY)"
+                    );
                     log::warn!("Set SWEETMCP_JWT_SECRET in production!");
                     log::debug!("Generated JWT secret: {}", generated_secret);
-
-                    generated_secret
                 } else {
-                    return Err(anyhow::anyhow!(
-                        "SWEETMCP_JWT_SECRET environment variable is required"
-                    ));
+                    settings.errors.push(
+                        "SWEETMCP_JWT_SECRET environment variable is required \
+                         (32 random bytes, base64url-encoded)"
+                            .to_string(),
+                    );
                 }
+                secret_bytes
             }
         };
 
-        let secret_vec = base64_url::decode(&secret_b64)
-            .context("Invalid base64 encoding in SWEETMCP_JWT_SECRET")?;
-
-        anyhow::ensure!(
-            secret_vec.len() == 32,
-            "JWT secret must be exactly 32 bytes, got {} bytes",
-            secret_vec.len()
-        );
-
-        let mut secret = [0u8; 32];
-        secret.copy_from_slice(&secret_vec);
-
         // Parse other configuration values with defaults
-        let inflight_max = env::var("SWEETMCP_INFLIGHT_MAX")
-            .unwrap_or_else(|_| "400".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_INFLIGHT_MAX value")?;
-
-        let upstreams: Vec<String> = env::var("SWEETMCP_UPSTREAMS")
-            .unwrap_or_default()
-            .split(',')
-            .filter(|s| !s.trim().is_empty())
-            .map(|s| s.trim().to_string())
-            .collect();
+        let inflight_max = settings.value("SWEETMCP_INFLIGHT_MAX", file.inflight_max, 400);
+
+        let upstreams: Vec<String> = match env::var("SWEETMCP_UPSTREAMS") {
+            Ok(upstreams) => upstreams
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().to_string())
+                .collect(),
+            Err(_) => file.upstreams.clone().unwrap_or_default(),
+        };
 
-        let tcp_bind = env::var("SWEETMCP_TCP_BIND").unwrap_or_else(|_| "0.0.0.0:8443".to_string());
+        let tcp_bind = settings.value(
+            "SWEETMCP_TCP_BIND",
+            file.tcp_bind.clone(),
+            "0.0.0.0:8443".to_string(),
+        );
 
-        let mcp_bind =
-            env::var("SWEETMCP_MCP_BIND").unwrap_or_else(|_| "0.0.0.0:33399".to_string());
+        let mcp_bind = settings.value(
+            "SWEETMCP_MCP_BIND",
+            file.mcp_bind.clone(),
+            "0.0.0.0:33399".to_string(),
+        );
 
-        let uds_path = env::var("SWEETMCP_UDS_PATH").unwrap_or_else(|_| {
+        let uds_path = settings.value("SWEETMCP_UDS_PATH", file.uds_path.clone(), {
             let xdg_config = env::var("XDG_CONFIG_HOME").unwrap_or_else(|_| {
                 let home = env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
                 format!("{}/.config", home)
@@ -204,53 +501,282 @@ impl Config {
             format!("{}/sweetmcp/sugora.sock", xdg_config)
         });
 
-        let workers = env::var("SWEETMCP_WORKERS")
-            .unwrap_or_else(|_| "4".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_WORKERS value")?;
+        let workers = settings.value("SWEETMCP_WORKERS", file.workers, 4);
 
-        let metrics_bind =
-            env::var("SWEETMCP_METRICS_BIND").unwrap_or_else(|_| "127.0.0.1:9090".to_string());
+        let metrics_bind = settings.value(
+            "SWEETMCP_METRICS_BIND",
+            file.metrics_bind.clone(),
+            "127.0.0.1:9090".to_string(),
+        );
 
-        let jwt_expiry_str = env::var("SWEETMCP_JWT_EXPIRY").unwrap_or_else(|_| "1h".to_string());
         let jwt_expiry =
-            parse_duration(&jwt_expiry_str).context("Invalid SWEETMCP_JWT_EXPIRY format")?;
+            settings.duration("SWEETMCP_JWT_EXPIRY", "jwt_expiry", &file.jwt_expiry, "1h");
+
+        let health_check_interval = settings.duration(
+            "SWEETMCP_HEALTH_CHECK_INTERVAL",
+            "health_check_interval",
+            &file.health_check_interval,
+            "5s",
+        );
 
-        let health_check_interval_str =
-            env::var("SWEETMCP_HEALTH_CHECK_INTERVAL").unwrap_or_else(|_| "5s".to_string());
-        let health_check_interval = parse_duration(&health_check_interval_str)
-            .context("Invalid SWEETMCP_HEALTH_CHECK_INTERVAL format")?;
+        let circuit_breaker_threshold = settings.value(
+            "SWEETMCP_CIRCUIT_BREAKER_THRESHOLD",
+            file.circuit_breaker_threshold,
+            50,
+        );
 
-        let circuit_breaker_threshold = env::var("SWEETMCP_CIRCUIT_BREAKER_THRESHOLD")
-            .unwrap_or_else(|_| "50".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_CIRCUIT_BREAKER_THRESHOLD value")?;
+        let request_timeout = settings.duration(
+            "SWEETMCP_REQUEST_TIMEOUT",
+            "request_timeout",
+            &file.request_timeout,
+            "30s",
+        );
 
-        let request_timeout_str =
-            env::var("SWEETMCP_REQUEST_TIMEOUT").unwrap_or_else(|_| "30s".to_string());
-        let request_timeout = parse_duration(&request_timeout_str)
-            .context("Invalid SWEETMCP_REQUEST_TIMEOUT format")?;
+        let drain_timeout = settings.duration(
+            "SWEETMCP_DRAIN_TIMEOUT",
+            "drain_timeout",
+            &file.drain_timeout,
+            "30s",
+        );
+
+        let log_level = settings.parsed(
+            "SWEETMCP_LOG_LEVEL",
+            "log_level",
+            &file.log_level,
+            None,
+            |level| {
+                level
+                    .parse::<log::LevelFilter>()
+                    .map(Some)
+                    .map_err(|_| anyhow::anyhow!("expected off, error, warn, info, debug or trace"))
+            },
+        );
 
         // Rate limiting configuration
-        let per_user_rps = env::var("SWEETMCP_RATE_LIMIT_USER_RPS")
-            .unwrap_or_else(|_| "100".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_RATE_LIMIT_USER_RPS value")?;
+        let rate_limit = RateLimitConfig {
+            per_user_rps: settings.value(
+                "SWEETMCP_RATE_LIMIT_USER_RPS",
+                file.rate_limit.per_user_rps,
+                100,
+            ),
+            per_ip_rps: settings.value(
+                "SWEETMCP_RATE_LIMIT_IP_RPS",
+                file.rate_limit.per_ip_rps,
+                1000,
+            ),
+            burst_capacity: settings.value(
+                "SWEETMCP_RATE_LIMIT_BURST",
+                file.rate_limit.burst_capacity,
+                50,
+            ),
+        };
 
-        let per_ip_rps = env::var("SWEETMCP_RATE_LIMIT_IP_RPS")
-            .unwrap_or_else(|_| "1000".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_RATE_LIMIT_IP_RPS value")?;
+        // GraphQL query limits
+        let graphql_limits = crate::normalize::QueryLimits {
+            max_depth: settings.value(
+                "SWEETMCP_GRAPHQL_MAX_DEPTH",
+                file.graphql_limits.max_depth,
+                15,
+            ),
+            max_complexity: settings.value(
+                "SWEETMCP_GRAPHQL_MAX_COMPLEXITY",
+                file.graphql_limits.max_complexity,
+                1000,
+            ),
+        };
 
-        let burst_capacity = env::var("SWEETMCP_RATE_LIMIT_BURST")
-            .unwrap_or_else(|_| "50".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_RATE_LIMIT_BURST value")?;
+        // JSON-RPC batch limits
+        let jsonrpc_batch = crate::normalize::BatchLimits {
+            max_batch_size: settings.value(
+                "SWEETMCP_JSONRPC_MAX_BATCH_SIZE",
+                file.jsonrpc_batch.max_batch_size,
+                50,
+            ),
+        };
 
-        let rate_limit = RateLimitConfig {
-            per_user_rps,
-            per_ip_rps,
-            burst_capacity,
+        // MCP bridge queue
+        let bridge_queue = crate::mcp_bridge::BridgeQueueConfig {
+            capacity: settings.value(
+                "SWEETMCP_BRIDGE_QUEUE_CAPACITY",
+                file.bridge_queue.capacity,
+                1024,
+            ),
+            overflow_policy: settings.parsed(
+                "SWEETMCP_BRIDGE_OVERFLOW_POLICY",
+                "bridge_queue.overflow_policy",
+                &file.bridge_queue.overflow_policy,
+                crate::mcp_bridge::OverflowPolicy::Reject,
+                str::parse,
+            ),
+        };
+
+        // Aggregated tools/list cache
+        let tool_list_cache = crate::tool_cache::ToolListCacheConfig {
+            enabled: settings.value(
+                "SWEETMCP_TOOL_LIST_CACHE_ENABLED",
+                file.tool_list_cache.enabled,
+                true,
+            ),
+            ttl: settings.duration(
+                "SWEETMCP_TOOL_LIST_CACHE_TTL",
+                "tool_list_cache.ttl",
+                &file.tool_list_cache.ttl,
+                "5m",
+            ),
+        };
+
+        // Idempotency-Key replay
+        let idempotency = crate::idempotency::IdempotencyConfig {
+            enabled: settings.value(
+                "SWEETMCP_IDEMPOTENCY_ENABLED",
+                file.idempotency.enabled,
+                true,
+            ),
+            ttl: settings.duration(
+                "SWEETMCP_IDEMPOTENCY_TTL",
+                "idempotency.ttl",
+                &file.idempotency.ttl,
+                "24h",
+            ),
+            max_entries: settings.value(
+                "SWEETMCP_IDEMPOTENCY_MAX_ENTRIES",
+                file.idempotency.max_entries,
+                10_000,
+            ),
+            wait_timeout: settings.duration(
+                "SWEETMCP_IDEMPOTENCY_WAIT_TIMEOUT",
+                "idempotency.wait_timeout",
+                &file.idempotency.wait_timeout,
+                "30s",
+            ),
+            max_response_bytes: settings.value(
+                "SWEETMCP_IDEMPOTENCY_MAX_RESPONSE_BYTES",
+                file.idempotency.max_response_bytes,
+                1024 * 1024,
+            ),
+        };
+
+        // Streaming of large tool results
+        let result_streaming = crate::streaming::ResultStreamingConfig {
+            enabled: settings.value(
+                "SWEETMCP_RESULT_STREAMING_ENABLED",
+                file.result_streaming.enabled,
+                true,
+            ),
+            threshold_bytes: settings.value(
+                "SWEETMCP_RESULT_STREAMING_THRESHOLD_BYTES",
+                file.result_streaming.threshold_bytes,
+                64 * 1024,
+            ),
+            chunk_bytes: settings.value(
+                "SWEETMCP_RESULT_STREAMING_CHUNK_BYTES",
+                file.result_streaming.chunk_bytes,
+                16 * 1024,
+            ),
+        };
+
+        // Sticky routing of MCP sessions
+        let session_affinity = crate::session_affinity::SessionAffinityConfig {
+            enabled: settings.value(
+                "SWEETMCP_SESSION_AFFINITY_ENABLED",
+                file.session_affinity.enabled,
+                true,
+            ),
+            arguments: match env::var("SWEETMCP_SESSION_AFFINITY_ARGUMENTS") {
+                Ok(arguments) => arguments
+                    .split(',')
+                    .filter(|s| !s.trim().is_empty())
+                    .map(|s| s.trim().to_string())
+                    .collect(),
+                Err(_) => file.session_affinity.arguments.clone().unwrap_or_else(|| {
+                    crate::session_affinity::SessionAffinityConfig::default().arguments
+                }),
+            },
+        };
+
+        // Tool call rewriting; rules are only read from the file
+        let request_transforms = crate::transform::TransformConfig {
+            dry_run: settings.value(
+                "SWEETMCP_REQUEST_TRANSFORMS_DRY_RUN",
+                file.request_transforms.dry_run,
+                false,
+            ),
+            rules: file.request_transforms.rules.clone().unwrap_or_default(),
+        };
+
+        // Scoped client tokens
+        let client_tokens = crate::client_tokens::ClientTokensConfig {
+            enabled: settings.value(
+                "SWEETMCP_CLIENT_TOKENS_ENABLED",
+                file.client_tokens.enabled,
+                true,
+            ),
+            store_path: env::var_os("SWEETMCP_CLIENT_TOKENS_STORE_PATH")
+                .map(PathBuf::from)
+                .or_else(|| file.client_tokens.store_path.clone())
+                .unwrap_or_else(crate::client_tokens::default_store_path),
+            default_ttl: settings.duration(
+                "SWEETMCP_CLIENT_TOKENS_DEFAULT_TTL",
+                "client_tokens.default_ttl",
+                &file.client_tokens.default_ttl,
+                "30d",
+            ),
+            max_ttl: settings.duration(
+                "SWEETMCP_CLIENT_TOKENS_MAX_TTL",
+                "client_tokens.max_ttl",
+                &file.client_tokens.max_ttl,
+                "365d",
+            ),
+            low_rpm: settings.value(
+                "SWEETMCP_CLIENT_TOKENS_LOW_RPM",
+                file.client_tokens.low_rpm,
+                60,
+            ),
+            standard_rpm: settings.value(
+                "SWEETMCP_CLIENT_TOKENS_STANDARD_RPM",
+                file.client_tokens.standard_rpm,
+                600,
+            ),
+            high_rpm: settings.value(
+                "SWEETMCP_CLIENT_TOKENS_HIGH_RPM",
+                file.client_tokens.high_rpm,
+                6000,
+            ),
+        };
+
+        // Tenant namespaces
+        let tenants = crate::tenants::TenantsConfig {
+            enabled: settings.value("SWEETMCP_TENANTS_ENABLED", file.tenants.enabled, false),
+            store_path: env::var_os("SWEETMCP_TENANTS_STORE_PATH")
+                .map(PathBuf::from)
+                .or_else(|| file.tenants.store_path.clone())
+                .unwrap_or_else(crate::tenants::default_store_path),
+            default_rpm: settings.value(
+                "SWEETMCP_TENANTS_DEFAULT_RPM",
+                file.tenants.default_rpm,
+                6000,
+            ),
+        };
+
+        let plugin_host_url = settings.value(
+            "SWEETMCP_PLUGIN_HOST_URL",
+            file.plugin_host_url.clone(),
+            DEFAULT_PLUGIN_HOST_URL.to_string(),
+        );
+
+        // Upstream MCP servers (JSON file listing stdio/http upstreams)
+        let mcp_upstreams_file = env::var_os("SWEETMCP_MCP_UPSTREAMS_FILE")
+            .map(PathBuf::from)
+            .or_else(|| file.mcp_upstreams_file.clone());
+        let mcp_upstreams = match mcp_upstreams_file {
+            Some(path) => crate::upstream::load_upstream_configs(&path).unwrap_or_else(|e| {
+                settings.errors.push(format!(
+                    "mcp_upstreams_file (SWEETMCP_MCP_UPSTREAMS_FILE): {:#}",
+                    e
+                ));
+                Vec::new()
+            }),
+            None => Vec::new(),
         };
 
         // Discovery token for peer authentication
@@ -258,16 +784,11 @@ impl Config {
             .unwrap_or_else(|_| "dev-discovery-token".to_string());
 
         // Require HTTPS (default to false in dev mode)
-        let require_https = env::var("SWEETMCP_REQUIRE_HTTPS")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse()
-            .unwrap_or(false);
+        let require_https = settings.value("SWEETMCP_REQUIRE_HTTPS", file.require_https, false);
 
         // Maximum auth attempts per minute
-        let max_auth_attempts = env::var("SWEETMCP_MAX_AUTH_ATTEMPTS")
-            .unwrap_or_else(|_| "10".to_string())
-            .parse()
-            .context("Invalid SWEETMCP_MAX_AUTH_ATTEMPTS value")?;
+        let max_auth_attempts =
+            settings.value("SWEETMCP_MAX_AUTH_ATTEMPTS", file.max_auth_attempts, 10);
 
         let auth = AuthConfig {
             jwt_secret: Arc::new(secret),
@@ -277,7 +798,7 @@ impl Config {
             max_auth_attempts_per_minute: max_auth_attempts,
         };
 
-        Ok(Self {
+        let config = Self {
             jwt_secret: Arc::new(secret),
             auth,
             inflight_max,
@@ -291,43 +812,326 @@ impl Config {
             health_check_interval,
             circuit_breaker_threshold,
             request_timeout,
+            drain_timeout,
+            log_level,
             rate_limit,
-        })
+            graphql_limits,
+            jsonrpc_batch,
+            bridge_queue,
+            tool_list_cache,
+            idempotency,
+            result_streaming,
+            session_affinity,
+            request_transforms,
+            client_tokens,
+            tenants,
+            plugin_host_url,
+            mcp_upstreams,
+        };
+
+        let mut problems = settings.errors;
+        // Semantic checks would only repeat problems on values replaced by defaults
+        if problems.is_empty() {
+            problems = config.check();
+        }
+        if !problems.is_empty() {
+            anyhow::bail!(format_problems(&problems));
+        }
+        Ok(config)
     }
 
-    /// Validate the configuration
-    #[allow(dead_code)]
-    pub fn validate(&self) -> Result<()> {
-        if self.inflight_max == 0 {
-            anyhow::bail!("inflight_max must be greater than 0");
+    /// Check values that parse but can't work, one actionable message per problem
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut require = |ok: bool, problem: String| {
+            if !ok {
+                problems.push(problem);
+            }
+        };
+
+        require(
+            self.inflight_max > 0,
+            "inflight_max (SWEETMCP_INFLIGHT_MAX) must be greater than 0".to_string(),
+        );
+        require(
+            self.workers > 0,
+            "workers (SWEETMCP_WORKERS) must be greater than 0".to_string(),
+        );
+
+        for (key, env_key, addr) in [
+            ("tcp_bind", "SWEETMCP_TCP_BIND", &self.tcp_bind),
+            ("mcp_bind", "SWEETMCP_MCP_BIND", &self.mcp_bind),
+            ("metrics_bind", "SWEETMCP_METRICS_BIND", &self.metrics_bind),
+        ] {
+            let valid = addr
+                .rsplit_once(':')
+                .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+            require(
+                valid,
+                format!(
+                    "{} ({}) must be host:port such as 0.0.0.0:8443, got `{}`",
+                    key, env_key, addr
+                ),
+            );
+        }
+
+        for (key, env_key, duration) in [
+            ("jwt_expiry", "SWEETMCP_JWT_EXPIRY", self.jwt_expiry),
+            (
+                "health_check_interval",
+                "SWEETMCP_HEALTH_CHECK_INTERVAL",
+                self.health_check_interval,
+            ),
+            (
+                "request_timeout",
+                "SWEETMCP_REQUEST_TIMEOUT",
+                self.request_timeout,
+            ),
+            (
+                "drain_timeout",
+                "SWEETMCP_DRAIN_TIMEOUT",
+                self.drain_timeout,
+            ),
+        ] {
+            require(
+                duration.as_secs() > 0,
+                format!("{} ({}) must be at least 1s", key, env_key),
+            );
+        }
+
+        require(
+            self.circuit_breaker_threshold <= 100,
+            format!(
+                "circuit_breaker_threshold (SWEETMCP_CIRCUIT_BREAKER_THRESHOLD) is a failure \
+                 percentage from 0 to 100, got {}",
+                self.circuit_breaker_threshold
+            ),
+        );
+
+        for upstream in &self.upstreams {
+            let problem = match url::Url::parse(upstream) {
+                Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+                    Some(format!("must use http or https, got `{}`", url.scheme()))
+                }
+                Ok(url) if url.host_str().is_none() => Some("has no host".to_string()),
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(problem) = problem {
+                require(
+                    false,
+                    format!(
+                        "upstreams (SWEETMCP_UPSTREAMS) entry `{}` {}, e.g. https://10.0.0.5:8443",
+                        upstream, problem
+                    ),
+                );
+            }
         }
 
-        if self.workers == 0 {
-            anyhow::bail!("workers must be greater than 0");
+        let plugin_host_ok = url::Url::parse(&self.plugin_host_url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+        require(
+            plugin_host_ok,
+            format!(
+                "plugin_host_url (SWEETMCP_PLUGIN_HOST_URL) must be an http or https URL \
+                 such as {}, got `{}`",
+                DEFAULT_PLUGIN_HOST_URL, self.plugin_host_url
+            ),
+        );
+
+        require(
+            self.rate_limit.per_ip_rps > 0 && self.rate_limit.burst_capacity > 0,
+            "rate_limit.per_ip_rps (SWEETMCP_RATE_LIMIT_IP_RPS) and rate_limit.burst_capacity \
+             (SWEETMCP_RATE_LIMIT_BURST) must be greater than 0"
+                .to_string(),
+        );
+        require(
+            self.graphql_limits.max_depth > 0 && self.graphql_limits.max_complexity > 0,
+            "graphql_limits.max_depth (SWEETMCP_GRAPHQL_MAX_DEPTH) and \
+             graphql_limits.max_complexity (SWEETMCP_GRAPHQL_MAX_COMPLEXITY) must be greater than 0"
+                .to_string(),
+        );
+        require(
+            self.jsonrpc_batch.max_batch_size > 0,
+            "jsonrpc_batch.max_batch_size (SWEETMCP_JSONRPC_MAX_BATCH_SIZE) must be greater than 0"
+                .to_string(),
+        );
+        require(
+            self.bridge_queue.capacity > 0,
+            "bridge_queue.capacity (SWEETMCP_BRIDGE_QUEUE_CAPACITY) must be greater than 0"
+                .to_string(),
+        );
+
+        if self.tool_list_cache.enabled {
+            require(
+                self.tool_list_cache.ttl.as_secs() > 0,
+                "tool_list_cache.ttl (SWEETMCP_TOOL_LIST_CACHE_TTL) must be at least 1s"
+                    .to_string(),
+            );
         }
 
-        if self.jwt_expiry.as_secs() == 0 {
-            anyhow::bail!("jwt_expiry must be greater than 0");
+        if self.idempotency.enabled {
+            require(
+                self.idempotency.ttl.as_secs() > 0 && self.idempotency.wait_timeout.as_secs() > 0,
+                "idempotency.ttl (SWEETMCP_IDEMPOTENCY_TTL) and idempotency.wait_timeout \
+                 (SWEETMCP_IDEMPOTENCY_WAIT_TIMEOUT) must be at least 1s"
+                    .to_string(),
+            );
+            require(
+                self.idempotency.max_entries > 0,
+                "idempotency.max_entries (SWEETMCP_IDEMPOTENCY_MAX_ENTRIES) must be greater than 0"
+                    .to_string(),
+            );
         }
 
-        if self.health_check_interval.as_secs() == 0 {
-            anyhow::bail!("health_check_interval must be greater than 0");
+        if self.result_streaming.enabled {
+            require(
+                self.result_streaming.chunk_bytes > 0,
+                "result_streaming.chunk_bytes (SWEETMCP_RESULT_STREAMING_CHUNK_BYTES) must be \
+                 greater than 0"
+                    .to_string(),
+            );
         }
 
-        if self.request_timeout.as_secs() == 0 {
-            anyhow::bail!("request_timeout must be greater than 0");
+        if self.client_tokens.enabled {
+            require(
+                self.client_tokens.default_ttl.as_secs() > 0
+                    && self.client_tokens.default_ttl <= self.client_tokens.max_ttl,
+                "client_tokens.default_ttl (SWEETMCP_CLIENT_TOKENS_DEFAULT_TTL) must be at least \
+                 1s and no longer than client_tokens.max_ttl (SWEETMCP_CLIENT_TOKENS_MAX_TTL)"
+                    .to_string(),
+            );
+            require(
+                self.client_tokens.low_rpm > 0
+                    && self.client_tokens.standard_rpm > 0
+                    && self.client_tokens.high_rpm > 0,
+                "client_tokens.low_rpm, standard_rpm and high_rpm \
+                 (SWEETMCP_CLIENT_TOKENS_*_RPM) must be greater than 0"
+                    .to_string(),
+            );
         }
 
-        // Validate upstream URLs
-        for upstream in &self.upstreams {
-            url::Url::parse(upstream)
-                .with_context(|| format!("Invalid upstream URL: {}", upstream))?;
+        if self.tenants.enabled {
+            require(
+                self.tenants.default_rpm > 0,
+                "tenants.default_rpm (SWEETMCP_TENANTS_DEFAULT_RPM) must be greater than 0"
+                    .to_string(),
+            );
         }
 
+        for (index, rule) in self.request_transforms.rules.iter().enumerate() {
+            if let Some(problem) = rule.problem() {
+                require(false, format!("request_transforms.rules[{}]: {}", index, problem));
+            }
+        }
+
+        // Validate upstream MCP servers
+        for upstream in &self.mcp_upstreams {
+            if let Err(e) = upstream.validate() {
+                require(
+                    false,
+                    format!("mcp_upstreams_file (SWEETMCP_MCP_UPSTREAMS_FILE): {:#}", e),
+                );
+            }
+        }
+
+        problems
+    }
+
+    /// Validate the configuration
+    #[allow(dead_code)]
+    pub fn validate(&self) -> Result<()> {
+        let problems = self.check();
+        if !problems.is_empty() {
+            anyhow::bail!(format_problems(&problems));
+        }
         Ok(())
     }
 }
 
+/// Collects settings and every invalid value found while resolving them
+#[derive(Default)]
+struct Resolver {
+    errors: Vec<String>,
+}
+
+impl Resolver {
+    /// `env_key` if set, else the typed file value, else `default`
+    fn value<T>(&mut self, env_key: &str, file: Option<T>, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match env::var(env_key) {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|e| {
+                self.errors
+                    .push(format!("Invalid {} value `{}`: {}", env_key, raw, e));
+                default
+            }),
+            Err(_) => file.unwrap_or(default),
+        }
+    }
+
+    /// Setting written as a string both in the environment and the file
+    fn parsed<T, E: Display>(
+        &mut self,
+        env_key: &str,
+        file_key: &str,
+        file: &Option<String>,
+        default: T,
+        parse: impl Fn(&str) -> Result<T, E>,
+    ) -> T {
+        let (key, raw, origin) = match (env::var(env_key), file) {
+            (Ok(raw), _) => (env_key, raw, ""),
+            (Err(_), Some(raw)) => (file_key, raw.clone(), " in the config file"),
+            (Err(_), None) => return default,
+        };
+        parse(raw.trim()).unwrap_or_else(|e| {
+            self.errors.push(format!(
+                "Invalid {} value `{}`{}: {:#}",
+                key, raw, origin, e
+            ));
+            default
+        })
+    }
+
+    /// Duration setting such as "30s", "5m" or "1h"
+    fn duration(
+        &mut self,
+        env_key: &str,
+        file_key: &str,
+        file: &Option<String>,
+        default: &str,
+    ) -> Duration {
+        let fallback = parse_duration(default).unwrap_or_default();
+        self.parsed(env_key, file_key, file, fallback, parse_duration)
+    }
+}
+
+fn decode_jwt_secret(secret_b64: &str) -> Result<[u8; 32]> {
+    let secret_vec =
+        base64_url::decode(secret_b64).context("Invalid base64 encoding in SWEETMCP_JWT_SECRET")?;
+
+    anyhow::ensure!(
+        secret_vec.len() == 32,
+        "JWT secret must be exactly 32 bytes, got {} bytes",
+        secret_vec.len()
+    );
+
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&secret_vec);
+    Ok(secret)
+}
+
+fn format_problems(problems: &[String]) -> String {
+    let mut message = format!("{} configuration problem(s):", problems.len());
+    for problem in problems {
+        message.push_str("\n  - ");
+        message.push_str(problem);
+    }
+    message
+}
+
 /// Parse duration strings like "1h", "30m", "5s"
 fn parse_duration(s: &str) -> Result<Duration> {
     let s = s.trim();
@@ -15,6 +15,8 @@ pub struct AuthContext {
     pub auth_method: AuthMethod,
     pub user_claims: Option<UserClaims>,
     pub client_ip: Option<String>,
+    /// Scope of the client token the request was authenticated with
+    pub client_token: Option<crate::client_tokens::ClientToken>,
 }
 
 /// User claims extracted from authentication tokens
@@ -26,6 +28,8 @@ pub struct UserClaims {
     pub permissions: Vec<String>,
     pub expires_at: u64,
     pub issued_at: u64,
+    /// Tenant the request belongs to
+    pub tenant: Option<String>,
 }
 
 /// Authentication method used for the request
@@ -39,6 +43,8 @@ pub enum AuthMethod {
     DiscoveryToken,
     /// API key authentication
     ApiKey,
+    /// Scoped client token issued through the admin API
+    ClientToken,
 }
 
 impl AuthContext {
@@ -49,6 +55,7 @@ impl AuthContext {
             auth_method: AuthMethod::None,
             user_claims: None,
             client_ip: None,
+            client_token: None,
         }
     }
 
@@ -59,6 +66,7 @@ impl AuthContext {
             auth_method,
             user_claims: Some(user_claims),
             client_ip: None,
+            client_token: None,
         }
     }
 
@@ -73,6 +81,12 @@ impl AuthContext {
         self
     }
 
+    /// Set the client token the request was authenticated with
+    pub fn with_client_token(mut self, client_token: crate::client_tokens::ClientToken) -> Self {
+        self.client_token = Some(client_token);
+        self
+    }
+
     /// Check if user has specific permission with fast permission lookup
     pub fn has_permission(&self, permission: &str) -> bool {
         self.user_claims
@@ -109,6 +123,13 @@ impl AuthContext {
             .map(|claims| claims.username.as_str())
     }
 
+    /// Get the tenant the request belongs to
+    pub fn tenant(&self) -> Option<&str> {
+        self.user_claims
+            .as_ref()
+            .and_then(|claims| claims.tenant.as_deref())
+    }
+
     /// Get all user roles
     pub fn roles(&self) -> Vec<&str> {
         self.user_claims
@@ -206,7 +227,14 @@ impl UserClaims {
             permissions,
             expires_at,
             issued_at,
+            tenant: None,
         }
     }
 
+    /// Set the tenant the claims belong to
+    pub fn with_tenant(mut self, tenant: Option<String>) -> Self {
+        self.tenant = tenant;
+        self
+    }
+
 }
@@ -406,6 +406,11 @@ impl AuthHandler {
             })
             .unwrap_or_default();
 
+        // Extract tenant (optional)
+        let tenant = json[crate::tenants::TENANT_CLAIM]
+            .as_str()
+            .map(|s| s.to_string());
+
         // Validate expiry timestamp
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -431,6 +436,7 @@ impl AuthHandler {
             permissions,
             expires_at,
             issued_at,
+            tenant,
         })
     }
 
@@ -479,6 +485,26 @@ impl AuthHandler {
 
         // Try API key authentication
         if let Some(api_key) = Self::extract_api_key(session) {
+            // Scoped client tokens issued through the admin API
+            if let Some(client_token) = service.client_tokens.authenticate(api_key) {
+                debug!(
+                    "Client token authentication successful for: {} ({})",
+                    client_token.name, client_token.id
+                );
+
+                let claims = UserClaims::new(
+                    format!("client:{}", client_token.id),
+                    client_token.name.clone(),
+                    vec!["client".to_string()],
+                    vec!["tools:access".to_string()],
+                    client_token.expires_at,
+                )
+                .with_tenant(client_token.tenant.clone());
+                return Ok(AuthContext::authenticated(AuthMethod::ClientToken, claims)
+                    .with_client_ip(client_ip.unwrap_or_default())
+                    .with_client_token(client_token));
+            }
+
             if Self::validate_api_key(service, api_key) {
                 // Extract client ID from API key for audit logging
                 let client_id = Self::extract_client_id_from_api_key(api_key)
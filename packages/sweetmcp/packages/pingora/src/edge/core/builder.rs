@@ -6,13 +6,10 @@
 //! of EdgeService instances with zero allocation patterns and blazing-fast
 //! performance.
 
-use std::collections::{BTreeSet, HashMap};
-use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
 use arc_swap::ArcSwap;
-use pingora_load_balancing::Backend;
 use tokio::sync::mpsc::Sender;
 use log::{debug, error, info};
 
@@ -108,27 +105,7 @@ impl EdgeServiceBuilder {
         })?;
 
         // Parse URLs and build backends (from EdgeService::new logic)
-        let mut backends = BTreeSet::new();
-        let mut url_map = HashMap::new();
-        for url in &cfg.upstreams {
-            match url::Url::parse(url) {
-                Ok(parsed) => {
-                    if let Some(host) = parsed.host_str() {
-                        let port = parsed.port().unwrap_or(
-                            if parsed.scheme() == "https" { 443 } else { 80 }
-                        );
-                        let addr_str = format!("{}:{}", host, port);
-                        if let Ok(backend) = Backend::new(&addr_str) {
-                            backends.insert(backend.clone());
-                            if let Ok(sock_addr) = addr_str.parse::<SocketAddr>() {
-                                url_map.insert(sock_addr, url.clone());
-                            }
-                        }
-                    }
-                }
-                Err(e) => error!("Failed to parse upstream URL {}: {}", url, e),
-            }
-        }
+        let (backends, url_map) = super::service::parse_upstreams(&cfg.upstreams);
 
         // Create circuit breaker manager
         let circuit_config = crate::circuit_breaker::CircuitBreakerConfig {
@@ -160,6 +137,20 @@ impl EdgeServiceBuilder {
             }
         });
 
+        // Load the client tokens issued through the admin API
+        let client_tokens = Arc::new(
+            crate::client_tokens::ClientTokenStore::open(cfg.client_tokens.clone()).map_err(|e| {
+                EdgeServiceError::Configuration(format!("Client token store: {:#}", e))
+            })?,
+        );
+
+        // Load the tenants managed through the admin API
+        let tenants = Arc::new(
+            crate::tenants::TenantStore::open(cfg.tenants.clone()).map_err(|e| {
+                EdgeServiceError::Configuration(format!("Tenant store: {:#}", e))
+            })?,
+        );
+
         // Initialize health checking (following EdgeService::new pattern)
         let health_check_config = super::service::HealthCheckConfig::default();
         
@@ -207,7 +198,7 @@ impl EdgeServiceBuilder {
             rate_limit_manager,
             shutdown_coordinator,
             circuit_breaker_manager,
-            upstream_urls: Arc::new(url_map),
+            upstream_urls: Arc::new(ArcSwap::from_pointee(url_map)),
             metrics: Arc::new(super::service::AtomicMetrics::new()),
             start_time: Instant::now(),
             token_manager,
@@ -215,6 +206,11 @@ impl EdgeServiceBuilder {
             backend_health,
             health_checker,
             health_check_config,
+            idempotency: Arc::new(crate::idempotency::IdempotencyStore::new(
+                cfg.idempotency.clone(),
+            )),
+            client_tokens,
+            tenants,
         };
 
         // Validate the built service
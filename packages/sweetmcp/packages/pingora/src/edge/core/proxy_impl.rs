@@ -15,7 +15,19 @@ use serde_json;
 use log::{warn, info};
 
 use crate::edge::auth::AuthHandler;
+use crate::edge::auth::core::AuthMethod;
+use crate::api::drain::{handle_drain_request, DRAIN_STATUS_PATH};
+use crate::api::exemplars::{handle_exemplars_request, TOOL_EXEMPLARS_PATH};
 use crate::api::peers::handle_peers_request;
+use crate::api::tenants::{handle_tenants_request, is_tenants_path, MAX_TENANT_REQUEST_BYTES};
+use crate::api::tokens::{
+    handle_client_tokens_request, is_client_tokens_path, MAX_TOKEN_REQUEST_BYTES,
+};
+use crate::idempotency::{Claim, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, StoredResponse};
+use crate::session_affinity::{
+    self, SessionAffinityConfig, SessionKey, LEGACY_SESSION_ID_HEADER, MAX_PEEK_BYTES,
+    SESSION_ID_HEADER,
+};
 use super::service::EdgeService;
 
 /// Per-request context with protocol conversion support
@@ -34,6 +46,23 @@ pub struct EdgeContext {
     pub request_size: usize,
     pub response_size: usize,
     pub status_code: u16,
+
+    // Idempotency-Key handling: the key scoped to the caller, and the
+    // fingerprint of the request while this request owns the key
+    pub idempotency_key: Option<String>,
+    pub idempotency_fingerprint: Option<crate::idempotency::Fingerprint>,
+    pub response_content_type: Option<String>,
+
+    // Session affinity: the request's MCP session, and the peers that
+    // failed to connect so a retry fails over to the next peer
+    pub session_key: Option<SessionKey>,
+    pub failed_peers: Vec<String>,
+
+    // Scope of the client token the request was authenticated with
+    pub client_token: Option<crate::client_tokens::ClientToken>,
+
+    // Tenant the request belongs to while tenancy is enabled
+    pub tenant: Option<crate::tenants::Tenant>,
 }
 
 #[async_trait]
@@ -54,6 +83,13 @@ impl ProxyHttp for EdgeService {
             request_size: 0,
             response_size: 0,
             status_code: 200,
+            idempotency_key: None,
+            idempotency_fingerprint: None,
+            response_content_type: None,
+            session_key: None,
+            failed_peers: Vec::new(),
+            client_token: None,
+            tenant: None,
         }
     }
 
@@ -61,6 +97,8 @@ impl ProxyHttp for EdgeService {
     ///
     /// This is the core routing logic that determines which backend
     /// should handle this request based on circuit breaker state and metrics.
+    /// Requests of an MCP session go to the session's peer on the
+    /// consistent-hash ring, or the next one when it is down.
     fn upstream_peer<'life0, 'life1, 'life2, 'async_trait>(
         &'life0 self,
         _session: &'life1 mut Session,
@@ -76,16 +114,29 @@ impl ProxyHttp for EdgeService {
             use pingora::protocols::l4::socket::SocketAddr as PingoraSocketAddr;
 
             let current_picker = self.picker.load();
+
+            // Sessions walk the ring from their pinned peer; other requests
+            // keep the configured backend order
+            let ordered: Vec<&pingora_load_balancing::Backend> = match &ctx.session_key {
+                Some(key) => current_picker.consistent_order(&key.ring_key()),
+                None => current_picker.backends.iter().collect(),
+            };
             
             // Try each backend until we find one with closed/half-open circuit
             let mut candidate_backend = None;
             
-            for backend in &current_picker.backends {
+            for &backend in &ordered {
                 // Get peer_id for circuit breaker lookup
                 let peer_id = match &backend.addr {
                     PingoraSocketAddr::Inet(addr) => format!("{}:{}", addr.ip(), addr.port()),
                     PingoraSocketAddr::Unix(_) => continue, // Skip unix sockets
                 };
+
+                // Peers that already failed this request are not retried
+                if ctx.failed_peers.contains(&peer_id) {
+                    log::debug!("Skipping backend {} - failed to connect for this request", peer_id);
+                    continue;
+                }
                 
                 // Check circuit breaker state
                 let breaker = self.circuit_breaker_manager.get_breaker(&peer_id).await;
@@ -99,7 +150,7 @@ impl ProxyHttp for EdgeService {
             // If all circuits open, fall back to round-robin
             let (backend, peer_id) = candidate_backend.or_else(|| {
                 log::warn!("All circuits open - using fallback backend");
-                current_picker.backends.first().map(|b| {
+                ordered.first().map(|&b| {
                     let id = match &b.addr {
                         PingoraSocketAddr::Inet(addr) => format!("{}:{}", addr.ip(), addr.port()),
                         PingoraSocketAddr::Unix(_) => "unix".to_string(),
@@ -108,6 +159,12 @@ impl ProxyHttp for EdgeService {
                 })
             }).ok_or_else(|| Error::new(ConnectNoRoute))?;
             
+            if let Some(key) = &ctx.session_key
+                && !std::ptr::eq(backend, ordered[0])
+            {
+                info!("Session {:?} failed over to {}", key.source, peer_id);
+            }
+
             // Store peer_id in context for later tracking
             ctx.peer_id = Some(peer_id);
             
@@ -171,6 +228,36 @@ impl ProxyHttp for EdgeService {
             // Increment active requests metric
             crate::metrics::increment_active_requests(&_ctx.method, &_ctx.endpoint);
 
+            // Reject new sessions while draining; the drain status endpoint stays reachable
+            if self.shutdown_coordinator.is_shutting_down() && path != DRAIN_STATUS_PATH {
+                info!("Rejecting new request to {} - gateway is draining", path);
+                _ctx.status_code = 503;
+
+                // Record metrics before returning
+                let duration_secs = _ctx.request_start.elapsed().as_secs_f64();
+                crate::metrics::record_http_request(
+                    &_ctx.method,
+                    &_ctx.endpoint,
+                    503,
+                    duration_secs,
+                    _ctx.request_size,
+                    0,
+                );
+                crate::metrics::decrement_active_requests(&_ctx.method, &_ctx.endpoint);
+
+                let mut response = ResponseHeader::build(503, None)?;
+                response.insert_header("Connection", "close")?;
+                response.insert_header("Retry-After", "5")?;
+                response.insert_header("Content-Length", "0")?;
+                session.as_mut()
+                    .write_response_header(Box::new(response))
+                    .await?;
+                session.as_mut()
+                    .write_response_body(bytes::Bytes::new(), true)
+                    .await?;
+                return Ok(true);
+            }
+
             // Validate HTTPS requirement from config
             if !AuthHandler::validate_https_requirement(self, session) {
                 warn!("HTTPS required - rejecting HTTP request");
@@ -529,10 +616,161 @@ impl ProxyHttp for EdgeService {
                     }
                     
                     // Log authenticated user for audit
-                    info!("Request from user: {} ({})", 
+                    info!("Request from user: {} ({}) tenant: {}", 
                         auth_context.username().unwrap_or("unknown"),
-                        auth_context.user_id().unwrap_or("unknown")
+                        auth_context.user_id().unwrap_or("unknown"),
+                        auth_context.tenant().unwrap_or("-")
                     );
+                    _ctx.client_token = auth_context.client_token.clone();
+
+                    // Outside the admin API every request belongs to an active tenant;
+                    // peers authenticated with the discovery token are trusted gateways
+                    if self.tenants.is_enabled()
+                        && !path.starts_with("/admin")
+                        && auth_context.auth_method != AuthMethod::DiscoveryToken
+                    {
+                        match auth_context.tenant().and_then(|tenant| self.tenants.resolve(tenant)) {
+                            Some(tenant) => _ctx.tenant = Some(tenant),
+                            None => {
+                                warn!("Tenant {:?} of user {:?} is unknown or suspended",
+                                    auth_context.tenant(),
+                                    auth_context.user_id());
+                                let json_body = serde_json::json!({
+                                    "error": "request does not belong to an active tenant"
+                                })
+                                .to_string();
+                                respond_early(
+                                    session,
+                                    _ctx,
+                                    403,
+                                    Some("application/json"),
+                                    bytes::Bytes::from(json_body),
+                                    &[],
+                                )
+                                .await?;
+                                return Ok(true);
+                            }
+                        }
+                    }
+
+                    // Idempotency keys are scoped to the caller so clients can't collide
+                    if self.idempotency.is_enabled()
+                        && let Some(raw_key) = session.req_header().headers.get(IDEMPOTENCY_KEY_HEADER)
+                    {
+                        match raw_key.to_str().ok().filter(|key| crate::idempotency::is_valid_key(key)) {
+                            Some(key) => {
+                                let scope = auth_context.user_id().unwrap_or(client_ip.as_str());
+                                _ctx.idempotency_key = Some(format!("{}\n{}", scope, key));
+                            }
+                            None => {
+                                warn!("Rejecting malformed {} header", IDEMPOTENCY_KEY_HEADER);
+                                _ctx.status_code = 400;
+
+                                // Record metrics before returning
+                                let duration_secs = _ctx.request_start.elapsed().as_secs_f64();
+                                crate::metrics::record_http_request(
+                                    &_ctx.method,
+                                    &_ctx.endpoint,
+                                    400,
+                                    duration_secs,
+                                    _ctx.request_size,
+                                    0,
+                                );
+                                crate::metrics::decrement_active_requests(&_ctx.method, &_ctx.endpoint);
+
+                                session.respond_error(400).await?;
+                                return Ok(true);
+                            }
+                        }
+                    }
+
+                    // Client token administration (admin role enforced above)
+                    if is_client_tokens_path(&path) {
+                        let mut body = Vec::new();
+                        if method == pingora::http::Method::POST {
+                            while body.len() <= MAX_TOKEN_REQUEST_BYTES
+                                && let Some(chunk) = session.as_mut().read_request_body().await?
+                            {
+                                body.extend_from_slice(&chunk);
+                            }
+                        }
+                        let (status, json_body) = if body.len() > MAX_TOKEN_REQUEST_BYTES {
+                            (413, serde_json::json!({ "error": "token request too large" }).to_string())
+                        } else {
+                            handle_client_tokens_request(&self.client_tokens, &self.tenants, method.as_str(), &path, &body)
+                        };
+                        respond_early(
+                            session,
+                            _ctx,
+                            status,
+                            Some("application/json"),
+                            bytes::Bytes::from(json_body),
+                            &[],
+                        )
+                        .await?;
+                        return Ok(true);
+                    }
+
+                    // Tenant administration (admin role enforced above)
+                    if is_tenants_path(&path) {
+                        let mut body = Vec::new();
+                        if method == pingora::http::Method::POST || method == pingora::http::Method::PATCH {
+                            while body.len() <= MAX_TENANT_REQUEST_BYTES
+                                && let Some(chunk) = session.as_mut().read_request_body().await?
+                            {
+                                body.extend_from_slice(&chunk);
+                            }
+                        }
+                        let (status, json_body) = if body.len() > MAX_TENANT_REQUEST_BYTES {
+                            (413, serde_json::json!({ "error": "tenant request too large" }).to_string())
+                        } else {
+                            handle_tenants_request(&self.tenants, method.as_str(), &path, &body)
+                        };
+                        respond_early(
+                            session,
+                            _ctx,
+                            status,
+                            Some("application/json"),
+                            bytes::Bytes::from(json_body),
+                            &[],
+                        )
+                        .await?;
+                        return Ok(true);
+                    }
+
+                    // Admin drain status and tool exemplar endpoints (admin role enforced above)
+                    let admin_body = match path.as_str() {
+                        _ if method != pingora::http::Method::GET => None,
+                        DRAIN_STATUS_PATH => Some(handle_drain_request(&self.shutdown_coordinator)),
+                        TOOL_EXEMPLARS_PATH => Some(handle_exemplars_request()),
+                        _ => None,
+                    };
+                    if let Some(admin_body) = admin_body {
+                        let json_body = match admin_body {
+                            Ok(json) => json,
+                            Err(e) => {
+                                warn!("Failed to build {} response: {}", path, e);
+                                _ctx.status_code = 500;
+                                session.respond_error(500).await?;
+                                return Ok(true);
+                            }
+                        };
+
+                        let json_body_len = json_body.len();
+                        let mut response_header = ResponseHeader::build(200, None)?;
+                        response_header.insert_header("Content-Type", "application/json")?;
+                        response_header.insert_header("Content-Length", json_body_len.to_string())?;
+                        session.as_mut()
+                            .write_response_header(Box::new(response_header))
+                            .await?;
+                        session.as_mut()
+                            .write_response_body(bytes::Bytes::from(json_body), true)
+                            .await?;
+
+                        _ctx.status_code = 200;
+                        _ctx.response_size = json_body_len;
+                        return Ok(true);
+                    }
                 }
                 Ok(_) | Err(_) => {
                     // Authentication failed - send 401 and stop processing
@@ -559,10 +797,15 @@ impl ProxyHttp for EdgeService {
             // PHASE 3: Rate Limiting
             let client_addr = session.client_addr()
                 .ok_or_else(|| Error::new(InternalError))?;
-            let client_id = client_addr
+            let peer_ip = client_addr
                 .as_inet()
                 .map(|addr| addr.ip().to_string())
                 .unwrap_or_else(|| "unknown".to_string());
+            // Tenants get buckets of their own, even behind a shared address
+            let client_id = match &_ctx.tenant {
+                Some(tenant) => format!("{}/{}", tenant.id, peer_ip),
+                None => peer_ip,
+            };
 
             // Check rate limit
             if !self.rate_limit_manager.check_request(&path, Some(&client_id), 1) {
@@ -587,6 +830,59 @@ impl ProxyHttp for EdgeService {
                 return Ok(true); // Response sent, stop here
             }
 
+            // Client tokens are further held to their rate tier
+            if let Some(client_token) = &_ctx.client_token
+                && !self.client_tokens.check_rate(client_token)
+            {
+                warn!("Rate tier {:?} exceeded for client token: {}", client_token.rate_tier, client_token.id);
+
+                // Record metrics before returning
+                let duration_secs = _ctx.request_start.elapsed().as_secs_f64();
+                _ctx.status_code = 429;
+                crate::metrics::record_http_request(
+                    &_ctx.method,
+                    &_ctx.endpoint,
+                    429,
+                    duration_secs,
+                    _ctx.request_size,
+                    0,
+                );
+                crate::metrics::decrement_active_requests(&_ctx.method, &_ctx.endpoint);
+                crate::metrics::record_rate_limit_rejection(&_ctx.endpoint);
+
+                session.respond_error(429).await?;
+                return Ok(true);
+            }
+
+            // All of a tenant's clients share its quota
+            if let Some(tenant) = &_ctx.tenant
+                && !self.tenants.check_rate(tenant)
+            {
+                warn!("Quota exceeded for tenant: {}", tenant.id);
+
+                // Record metrics before returning
+                let duration_secs = _ctx.request_start.elapsed().as_secs_f64();
+                _ctx.status_code = 429;
+                crate::metrics::record_http_request(
+                    &_ctx.method,
+                    &_ctx.endpoint,
+                    429,
+                    duration_secs,
+                    _ctx.request_size,
+                    0,
+                );
+                crate::metrics::decrement_active_requests(&_ctx.method, &_ctx.endpoint);
+                crate::metrics::record_rate_limit_rejection(&_ctx.endpoint);
+
+                session.respond_error(429).await?;
+                return Ok(true);
+            }
+
+            // Pin the request's MCP session to one peer
+            if self.cfg.session_affinity.enabled {
+                _ctx.session_key = session_key(session, &self.cfg.session_affinity).await?;
+            }
+
             // All checks passed - continue to upstream_peer()
             Ok(false)
         })
@@ -615,6 +911,45 @@ impl ProxyHttp for EdgeService {
         }
         
         if end_of_stream && !ctx.request_buffer.is_empty() {
+            // Client tokens and tenants may only call the tools they were given
+            if let Some(denied) = denied_tool(ctx, &ctx.request_buffer) {
+                ctx.request_buffer.clear();
+                return reject_denied_tool(session, ctx, denied).await;
+            }
+
+            // JSON-RPC batches are answered here, one bridge message per element
+            if let Some(items) = crate::normalize::batch::parse_batch(&ctx.request_buffer) {
+                ctx.request_buffer.clear();
+                return respond_to_batch(
+                    session,
+                    ctx,
+                    &self.bridge_tx,
+                    &self.cfg.request_transforms,
+                    &self.cfg.result_streaming,
+                    items,
+                )
+                .await;
+            }
+
+            // tools/list is served from the bridge's tool list cache; a tenant's
+            // pages are all answered there so they can be filtered
+            let bridge_request = match &ctx.tenant {
+                Some(_) => crate::tool_cache::bridge_request(&ctx.request_buffer)
+                    .or_else(|| crate::tool_cache::tools_list_request(&ctx.request_buffer)),
+                None => crate::tool_cache::bridge_request(&ctx.request_buffer),
+            };
+            if let Some(request) = bridge_request {
+                ctx.request_buffer.clear();
+                return respond_from_bridge(
+                    session,
+                    ctx,
+                    &self.bridge_tx,
+                    &self.cfg.result_streaming,
+                    request,
+                )
+                .await;
+            }
+
             // Get request headers for protocol detection
             let req_header = session.req_header();
             
@@ -655,6 +990,36 @@ impl ProxyHttp for EdgeService {
                                 }
                             }
                             Err(e) => {
+                                // Queries over the depth/complexity limits never reach the bridge
+                                if let Some(limit_error @ crate::normalize::types::ConversionError::QueryLimitExceeded { .. }) =
+                                    e.downcast_ref::<crate::normalize::types::ConversionError>()
+                                {
+                                    log::warn!("Rejecting GraphQL query: {}", limit_error);
+                                    let error_body = serde_json::to_vec(
+                                        &crate::normalize::complexity::limit_error_response(limit_error),
+                                    )
+                                    .unwrap_or_default();
+
+                                    ctx.status_code = 400;
+                                    ctx.response_size = error_body.len();
+                                    ctx.request_buffer.clear();
+
+                                    let mut response = ResponseHeader::build(400, None)?;
+                                    response.insert_header("Content-Type", "application/json")?;
+                                    response.insert_header("Content-Length", error_body.len().to_string())?;
+                                    session.as_mut()
+                                        .write_response_header(Box::new(response))
+                                        .await?;
+                                    session.as_mut()
+                                        .write_response_body(bytes::Bytes::from(error_body), true)
+                                        .await?;
+
+                                    return Err(Error::explain(
+                                        ErrorType::HTTPStatus(400),
+                                        "GraphQL query exceeds depth/complexity limits",
+                                    ));
+                                }
+
                                 log::warn!("Protocol conversion failed: {}", e);
                                 // Forward original body on conversion failure
                                 *body = Some(bytes::Bytes::from(ctx.request_buffer.clone()));
@@ -675,6 +1040,90 @@ impl ProxyHttp for EdgeService {
             
             // Clear buffer after processing
             ctx.request_buffer.clear();
+
+            // Converted GraphQL and Cap'n Proto requests are checked as JSON-RPC
+            if ctx.protocol_context.is_some()
+                && let Some(denied) = denied_tool(ctx, body.as_deref().unwrap_or_default())
+            {
+                *body = None;
+                return reject_denied_tool(session, ctx, denied).await;
+            }
+
+            // Replay, wait for or claim the request's Idempotency-Key
+            if let Some(key) = ctx.idempotency_key.clone() {
+                let protocol = ctx
+                    .protocol_context
+                    .as_ref()
+                    .map(|proto_ctx| format!("{:?}", proto_ctx.protocol))
+                    .unwrap_or_else(|| format!("{:?}", Proto::JsonRpc));
+                let fingerprint = crate::idempotency::fingerprint(
+                    &[&ctx.method, &ctx.endpoint, &protocol],
+                    body.as_deref().unwrap_or_default(),
+                );
+
+                match self.idempotency.acquire(&key, &fingerprint).await {
+                    Claim::Acquired => ctx.idempotency_fingerprint = Some(fingerprint),
+                    Claim::Bypassed => {
+                        log::warn!("Idempotency store is full; forwarding request without replay protection");
+                        ctx.idempotency_key = None;
+                    }
+                    Claim::Replay(stored) => {
+                        log::info!("Replaying stored response for {} {}", ctx.method, ctx.endpoint);
+                        respond_early(
+                            session,
+                            ctx,
+                            stored.status,
+                            stored.content_type.as_deref(),
+                            stored.body.clone(),
+                            &[(IDEMPOTENT_REPLAYED_HEADER, "true")],
+                        )
+                        .await?;
+                        return Err(Error::explain(
+                            ErrorType::HTTPStatus(stored.status),
+                            "Replayed stored response for Idempotency-Key",
+                        ));
+                    }
+                    Claim::Mismatch => {
+                        log::warn!("{} reused for a different request", IDEMPOTENCY_KEY_HEADER);
+                        let error_body = idempotency_error_body(
+                            "Idempotency-Key was already used for a different request",
+                        );
+                        respond_early(session, ctx, 422, Some("application/json"), error_body, &[])
+                            .await?;
+                        return Err(Error::explain(
+                            ErrorType::HTTPStatus(422),
+                            "Idempotency-Key reused for a different request",
+                        ));
+                    }
+                    Claim::InProgress => {
+                        let error_body = idempotency_error_body(
+                            "A request with this Idempotency-Key is still in progress",
+                        );
+                        respond_early(
+                            session,
+                            ctx,
+                            409,
+                            Some("application/json"),
+                            error_body,
+                            &[("Retry-After", "1")],
+                        )
+                        .await?;
+                        return Err(Error::explain(
+                            ErrorType::HTTPStatus(409),
+                            "Idempotency-Key request still in progress",
+                        ));
+                    }
+                }
+            }
+
+            // Operator rules rewrite tool calls after the fingerprint is taken,
+            // so a retry still matches the client's original request
+            if let Some(transformed) = body
+                .as_deref()
+                .and_then(|request| self.cfg.request_transforms.transform_body(request))
+            {
+                *body = Some(bytes::Bytes::from(transformed));
+            }
         }
         
         Ok(())
@@ -694,6 +1143,15 @@ impl ProxyHttp for EdgeService {
         Self::CTX: Send + Sync,
     {
         use crate::normalize::Proto;
+
+        // Remembered for storing the response under its Idempotency-Key
+        if ctx.idempotency_fingerprint.is_some() {
+            ctx.response_content_type = upstream_response
+                .headers
+                .get("Content-Type")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+        }
         
         // Only modify headers if we converted the request
         if let Some(proto_ctx) = &ctx.protocol_context
@@ -726,7 +1184,8 @@ impl ProxyHttp for EdgeService {
     /// Buffer and convert response body chunks
     ///
     /// Accumulates response body chunks from upstream and converts
-    /// back to original protocol when full response received.
+    /// back to original protocol when full response received. Responses
+    /// that need no conversion stream through as they arrive.
     fn response_body_filter(
         &self,
         _session: &mut Session,
@@ -738,7 +1197,29 @@ impl ProxyHttp for EdgeService {
         Self::CTX: Send + Sync,
     {
         use crate::normalize::{from_json_rpc, Proto};
-        
+
+        let converting = ctx
+            .protocol_context
+            .as_ref()
+            .is_some_and(|proto_ctx| proto_ctx.protocol != Proto::JsonRpc);
+        if self.cfg.result_streaming.enabled && !converting {
+            if let Some(b) = body {
+                ctx.response_size += b.len();
+                // A copy is only kept for the Idempotency-Key store, and only
+                // until it is too large to be stored anyway
+                if ctx.idempotency_fingerprint.is_some()
+                    && ctx.response_buffer.len() <= self.cfg.idempotency.max_response_bytes
+                {
+                    ctx.response_buffer.extend_from_slice(&b[..]);
+                }
+            }
+            if end_of_stream {
+                let stored = bytes::Bytes::from(std::mem::take(&mut ctx.response_buffer));
+                self.store_idempotent_response(ctx, stored);
+            }
+            return Ok(None);
+        }
+
         // Buffer incoming response chunks
         if let Some(b) = body {
             ctx.response_buffer.extend_from_slice(&b[..]);
@@ -798,6 +1279,10 @@ impl ProxyHttp for EdgeService {
             // Clear buffer after processing
             ctx.response_buffer.clear();
         }
+
+        if end_of_stream {
+            self.store_idempotent_response(ctx, body.clone().unwrap_or_default());
+        }
         
         // No delay needed
         Ok(None)
@@ -809,6 +1294,13 @@ impl ProxyHttp for EdgeService {
     where
         Self::CTX: Send + Sync,
     {
+        // A request that never produced a response lets its retries run again
+        if let (Some(key), Some(fingerprint)) =
+            (_ctx.idempotency_key.as_ref(), _ctx.idempotency_fingerprint.take())
+        {
+            self.idempotency.release(key, &fingerprint);
+        }
+
         // Calculate final duration
         let duration_secs = _ctx.request_start.elapsed().as_secs_f64();
         
@@ -824,6 +1316,11 @@ impl ProxyHttp for EdgeService {
         
         // Decrement active requests
         crate::metrics::decrement_active_requests(&_ctx.method, &_ctx.endpoint);
+
+        // Partition request counts by tenant
+        if let Some(tenant) = &_ctx.tenant {
+            crate::metrics::record_tenant_request(&tenant.id, _ctx.status_code);
+        }
         
         // Track total requests
         self.metrics.total_requests.fetch_add(1, Ordering::Relaxed);
@@ -876,12 +1373,15 @@ impl ProxyHttp for EdgeService {
     }
 
     /// Record circuit breaker failure on connection errors
+    ///
+    /// Requests of an MCP session are retried on the next peer of the
+    /// session's ring.
     fn fail_to_connect(
         &self,
         _session: &mut Session,
         _peer: &HttpPeer,
         ctx: &mut Self::CTX,
-        e: Box<Error>,
+        mut e: Box<Error>,
     ) -> Box<Error>
     where
         Self::CTX: Send + Sync,
@@ -896,7 +1396,296 @@ impl ProxyHttp for EdgeService {
                 log::error!("Circuit breaker recorded connection failure for {}", peer_id);
             });
         }
+
+        // upstream_peer skips peers that already failed this request
+        if let Some(peer_id) = ctx.peer_id.clone() {
+            ctx.failed_peers.push(peer_id);
+        }
+        if ctx.session_key.is_some() {
+            e.set_retry(true);
+        }
         
         e
     }
 }
+
+impl EdgeService {
+    /// Store the final response for retries; server errors stay retryable
+    fn store_idempotent_response(&self, ctx: &mut EdgeContext, body: bytes::Bytes) {
+        let (Some(key), Some(fingerprint)) =
+            (ctx.idempotency_key.as_ref(), ctx.idempotency_fingerprint.take())
+        else {
+            return;
+        };
+        if ctx.status_code < 500 {
+            self.idempotency.complete(
+                key,
+                &fingerprint,
+                StoredResponse {
+                    status: ctx.status_code,
+                    content_type: ctx.response_content_type.take(),
+                    body,
+                },
+            );
+        } else {
+            self.idempotency.release(key, &fingerprint);
+        }
+    }
+}
+
+/// Session key of a request
+///
+/// The MCP session header wins; otherwise a small JSON body is read ahead of
+/// routing and searched for a session argument. The body read here stays in
+/// the retry buffer and is still sent upstream.
+async fn session_key(
+    session: &mut Session,
+    config: &SessionAffinityConfig,
+) -> Result<Option<SessionKey>> {
+    let headers = &session.req_header().headers;
+    let header_key = headers
+        .get(SESSION_ID_HEADER)
+        .or_else(|| headers.get(LEGACY_SESSION_ID_HEADER))
+        .and_then(|value| value.to_str().ok())
+        .and_then(session_affinity::from_header);
+    if header_key.is_some() {
+        return Ok(header_key);
+    }
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+    let content_type = headers.get("content-type").and_then(|value| value.to_str().ok());
+    if config.arguments.is_empty() || !session_affinity::should_peek(content_length, content_type) {
+        return Ok(None);
+    }
+
+    session.as_mut().enable_retry_buffering();
+    let mut body = Vec::new();
+    while body.len() <= MAX_PEEK_BYTES
+        && let Some(chunk) = session.as_mut().read_request_body().await?
+    {
+        body.extend_from_slice(&chunk);
+    }
+    Ok(session_affinity::from_tool_arguments(&body, &config.arguments))
+}
+
+/// Write a complete response from the gateway without contacting the upstream
+async fn respond_early(
+    session: &mut Session,
+    ctx: &mut EdgeContext,
+    status: u16,
+    content_type: Option<&str>,
+    body: bytes::Bytes,
+    extra_headers: &[(&'static str, &str)],
+) -> Result<()> {
+    ctx.status_code = status;
+    ctx.response_size = body.len();
+
+    let mut response = ResponseHeader::build(status, None)?;
+    if let Some(content_type) = content_type {
+        response.insert_header("Content-Type", content_type)?;
+    }
+    response.insert_header("Content-Length", body.len().to_string())?;
+    for (name, value) in extra_headers {
+        response.insert_header(*name, *value)?;
+    }
+    session.as_mut()
+        .write_response_header(Box::new(response))
+        .await?;
+    session.as_mut()
+        .write_response_body(body, true)
+        .await?;
+    Ok(())
+}
+
+/// Write a large JSON response from the gateway with chunked transfer encoding
+///
+/// The body is serialized piece by piece, see
+/// [`response_chunks`](crate::streaming::response_chunks).
+async fn respond_streaming(
+    session: &mut Session,
+    ctx: &mut EdgeContext,
+    response: &serde_json::Value,
+    chunk_bytes: usize,
+) -> Result<()> {
+    ctx.status_code = 200;
+    ctx.response_size = 0;
+
+    let mut header = ResponseHeader::build(200, None)?;
+    header.insert_header("Content-Type", "application/json")?;
+    header.insert_header("Transfer-Encoding", "chunked")?;
+    session.as_mut()
+        .write_response_header(Box::new(header))
+        .await?;
+    for chunk in crate::streaming::response_chunks(response, chunk_bytes) {
+        ctx.response_size += chunk.len();
+        session.as_mut()
+            .write_response_body(chunk, false)
+            .await?;
+    }
+    session.as_mut()
+        .write_response_body(bytes::Bytes::new(), true)
+        .await?;
+    Ok(())
+}
+
+/// Answer a JSON-RPC batch through the MCP bridge instead of proxying it
+///
+/// The reply, or the error rejecting the whole batch, is written before this
+/// returns; the returned error only stops the request from being proxied.
+async fn respond_to_batch(
+    session: &mut Session,
+    ctx: &mut EdgeContext,
+    bridge_tx: &tokio::sync::mpsc::Sender<crate::mcp_bridge::BridgeMsg>,
+    transforms: &crate::transform::TransformConfig,
+    streaming: &crate::streaming::ResultStreamingConfig,
+    mut items: Vec<serde_json::Value>,
+) -> Result<()> {
+    use crate::normalize::{batch, Proto, ProtocolContext};
+
+    for item in &mut items {
+        transforms.transform(item);
+    }
+
+    let entries = match batch::split_batch(items, &batch::batch_limits()) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Rejecting JSON-RPC batch: {}", e);
+            let error_body =
+                serde_json::to_vec(&batch::batch_error_response(&e)).unwrap_or_default();
+            respond_early(session, ctx, 400, Some("application/json"), error_body.into(), &[])
+                .await?;
+            return Err(Error::explain(
+                ErrorType::HTTPStatus(400),
+                "JSON-RPC batch rejected",
+            ));
+        }
+    };
+
+    let count = entries.len();
+    let proto_ctx = ProtocolContext::new(Proto::JsonRpc, uuid::Uuid::new_v4().to_string());
+    let mut responses = batch::dispatch_batch(bridge_tx, &proto_ctx, entries).await;
+    if let (Some(tenant), Some(responses)) = (&ctx.tenant, responses.as_mut()) {
+        tenant.filter_tool_lists(responses);
+    }
+    match responses {
+        Some(responses) if streaming.should_stream(&responses) => {
+            respond_streaming(session, ctx, &responses, streaming.chunk_bytes).await?;
+        }
+        Some(responses) => {
+            let body = serde_json::to_vec(&responses).unwrap_or_default();
+            respond_early(session, ctx, 200, Some("application/json"), body.into(), &[])
+                .await?;
+        }
+        // A batch of only notifications gets no reply body
+        None => respond_early(session, ctx, 202, None, bytes::Bytes::new(), &[]).await?,
+    }
+    log::info!("Answered JSON-RPC batch of {} messages", count);
+
+    Err(Error::explain(
+        ErrorType::HTTPStatus(ctx.status_code),
+        "JSON-RPC batch answered at the edge",
+    ))
+}
+
+/// Answer a single JSON-RPC message through the MCP bridge instead of proxying it
+///
+/// Notifications are acknowledged with `202` once the bridge has handled
+/// them. As with batches, the returned error only stops the proxying.
+async fn respond_from_bridge(
+    session: &mut Session,
+    ctx: &mut EdgeContext,
+    bridge_tx: &tokio::sync::mpsc::Sender<crate::mcp_bridge::BridgeMsg>,
+    streaming: &crate::streaming::ResultStreamingConfig,
+    request: serde_json::Value,
+) -> Result<()> {
+    use crate::normalize::{batch, Proto, ProtocolContext};
+
+    let method = request
+        .get("method")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let proto_ctx = ProtocolContext::new(Proto::JsonRpc, uuid::Uuid::new_v4().to_string());
+    let entries = vec![batch::BatchEntry::Call(request)];
+    let mut responses = batch::dispatch_batch(bridge_tx, &proto_ctx, entries).await;
+    if let (Some(tenant), Some(responses)) = (&ctx.tenant, responses.as_mut()) {
+        tenant.filter_tool_lists(responses);
+    }
+    match responses {
+        Some(serde_json::Value::Array(mut responses)) if !responses.is_empty() => {
+            let response = responses.swap_remove(0);
+            if streaming.should_stream(&response) {
+                respond_streaming(session, ctx, &response, streaming.chunk_bytes).await?;
+            } else {
+                let body = serde_json::to_vec(&response).unwrap_or_default();
+                respond_early(session, ctx, 200, Some("application/json"), body.into(), &[])
+                    .await?;
+            }
+        }
+        _ => respond_early(session, ctx, 202, None, bytes::Bytes::new(), &[]).await?,
+    }
+    log::debug!("Answered {} at the edge", method);
+
+    Err(Error::explain(
+        ErrorType::HTTPStatus(ctx.status_code),
+        "JSON-RPC message answered at the edge",
+    ))
+}
+
+/// A tool call refused by the request's client token or tenant
+struct DeniedTool {
+    tool: String,
+    /// "client token" or "tenant"
+    scope: &'static str,
+    scope_id: String,
+}
+
+/// First tool call in `body` the request's client token or tenant may not make
+fn denied_tool(ctx: &EdgeContext, body: &[u8]) -> Option<DeniedTool> {
+    if let Some(client_token) = &ctx.client_token
+        && let Some(tool) = client_token.first_denied_tool(body)
+    {
+        return Some(DeniedTool { tool, scope: "client token", scope_id: client_token.id.clone() });
+    }
+    let tenant = ctx.tenant.as_ref()?;
+    tenant
+        .first_denied_tool(body)
+        .map(|tool| DeniedTool { tool, scope: "tenant", scope_id: tenant.id.clone() })
+}
+
+/// Refuse a tool call the request's client token or tenant does not allow
+///
+/// As with batches, the returned error only stops the request from being proxied.
+async fn reject_denied_tool(session: &mut Session, ctx: &mut EdgeContext, denied: DeniedTool) -> Result<()> {
+    log::warn!("Refusing tool '{}' for {} {}", denied.tool, denied.scope, denied.scope_id);
+    let error = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": -32600,
+            "message": format!("Tool '{}' is not allowed for this {}", denied.tool, denied.scope),
+        }
+    });
+    let error_body = bytes::Bytes::from(serde_json::to_vec(&error).unwrap_or_default());
+    respond_early(session, ctx, 403, Some("application/json"), error_body, &[]).await?;
+    Err(Error::explain(
+        ErrorType::HTTPStatus(403),
+        "Tool not allowed for client token or tenant",
+    ))
+}
+
+/// JSON-RPC error body for a rejected Idempotency-Key
+fn idempotency_error_body(message: &str) -> bytes::Bytes {
+    let error = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": -32600,
+            "message": message,
+        }
+    });
+    bytes::Bytes::from(serde_json::to_vec(&error).unwrap_or_default())
+}
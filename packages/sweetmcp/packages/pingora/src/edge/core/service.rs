@@ -21,12 +21,15 @@ use log::{error, info};
 use crate::{
     auth::JwtAuth,
     circuit_breaker::CircuitBreakerManager,
+    client_tokens::ClientTokenStore,
     config::Config,
     crypto::core::TokenManager,
+    idempotency::IdempotencyStore,
     load::Load, metric_picker::MetricPicker,
     peer_discovery::{PeerDiscovery, PeerRegistry},
     rate_limit::{RateLimiter, DistributedRateLimitManager},
     shutdown::ShutdownCoordinator,
+    tenants::TenantStore,
 };
 
 /// Atomic metrics for thread-safe request tracking
@@ -97,8 +100,9 @@ pub struct EdgeService {
     pub rate_limit_manager: RateLimiter,
     pub shutdown_coordinator: Arc<ShutdownCoordinator>,
     pub circuit_breaker_manager: Arc<CircuitBreakerManager>,
-    /// Maps backend SocketAddr to original upstream URL for TLS detection
-    pub upstream_urls: Arc<HashMap<SocketAddr, String>>,
+    /// Maps backend SocketAddr to original upstream URL for TLS detection;
+    /// swapped when upstreams are reloaded
+    pub upstream_urls: Arc<ArcSwap<HashMap<SocketAddr, String>>>,
     /// Atomic metrics for request tracking
     pub metrics: Arc<AtomicMetrics>,
     /// Service start time for uptime calculation
@@ -114,6 +118,52 @@ pub struct EdgeService {
     pub health_checker: Arc<TcpHealthCheck>,
     /// Health check configuration
     pub health_check_config: HealthCheckConfig,
+    /// Responses stored for `Idempotency-Key` replay
+    pub idempotency: Arc<IdempotencyStore>,
+    /// Scoped client tokens issued through the admin API
+    pub client_tokens: Arc<ClientTokenStore>,
+    /// Tenant namespaces managed through the admin API
+    pub tenants: Arc<TenantStore>,
+}
+
+/// Parse upstream URLs into backends and a map from backend address to URL
+///
+/// Unparseable URLs are logged and skipped.
+pub fn parse_upstreams(
+    upstreams: &[String],
+) -> (BTreeSet<Backend>, HashMap<SocketAddr, String>) {
+    let mut backends = BTreeSet::new();
+    let mut url_map = HashMap::new();
+
+    for url in upstreams {
+        match url::Url::parse(url) {
+            Ok(parsed) => {
+                if let Some(host) = parsed.host_str() {
+                    // Determine port based on scheme
+                    let port = parsed.port().unwrap_or(
+                        if parsed.scheme() == "https" { 443 } else { 80 }
+                    );
+
+                    // Create backend address
+                    let addr_str = format!("{}:{}", host, port);
+                    if let Ok(backend) = Backend::new(&addr_str) {
+                        // Store backend
+                        backends.insert(backend.clone());
+
+                        // Map SocketAddr to original URL for TLS lookup
+                        if let Ok(sock_addr) = addr_str.parse::<SocketAddr>() {
+                            url_map.insert(sock_addr, url.clone());
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to parse upstream URL {}: {}", url, e);
+            }
+        }
+    }
+
+    (backends, url_map)
 }
 
 impl EdgeService {
@@ -125,40 +175,11 @@ impl EdgeService {
         circuit_breaker_manager: Arc<CircuitBreakerManager>,
     ) -> Self {
         // Parse URLs and build both backends and URL map
-        let mut backends = BTreeSet::new();
-        let mut url_map = HashMap::new();
-
-        for url in &cfg.upstreams {
-            match url::Url::parse(url) {
-                Ok(parsed) => {
-                    if let Some(host) = parsed.host_str() {
-                        // Determine port based on scheme
-                        let port = parsed.port().unwrap_or(
-                            if parsed.scheme() == "https" { 443 } else { 80 }
-                        );
-                        
-                        // Create backend address
-                        let addr_str = format!("{}:{}", host, port);
-                        if let Ok(backend) = Backend::new(&addr_str) {
-                            // Store backend
-                            backends.insert(backend.clone());
-                            
-                            // Map SocketAddr to original URL for TLS lookup
-                            if let Ok(sock_addr) = addr_str.parse::<SocketAddr>() {
-                                url_map.insert(sock_addr, url.clone());
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to parse upstream URL {}: {}", url, e);
-                }
-            }
-        }
+        let (backends, url_map) = parse_upstreams(&cfg.upstreams);
 
-        let upstream_urls = Arc::new(url_map);
+        let upstream_urls = Arc::new(ArcSwap::from_pointee(url_map));
         info!("Initialized EdgeService with {} backends, {} URL mappings", 
-             backends.len(), upstream_urls.len());
+             backends.len(), upstream_urls.load().len());
 
         // Initialize health checking (Pingora pattern)
         let health_check_config = HealthCheckConfig::default();
@@ -228,6 +249,26 @@ impl EdgeService {
             }
         });
 
+        let idempotency = Arc::new(IdempotencyStore::new(cfg.idempotency.clone()));
+
+        // A damaged token store must not start the gateway with every client revoked
+        let client_tokens = match ClientTokenStore::open(cfg.client_tokens.clone()) {
+            Ok(store) => Arc::new(store),
+            Err(e) => {
+                error!("Failed to open client token store: {:#}", e);
+                panic!("Failed to open client token store: {:#}", e);
+            }
+        };
+
+        // Likewise a damaged tenant store must not lock every team out
+        let tenants = match TenantStore::open(cfg.tenants.clone()) {
+            Ok(store) => Arc::new(store),
+            Err(e) => {
+                error!("Failed to open tenant store: {:#}", e);
+                panic!("Failed to open tenant store: {:#}", e);
+            }
+        };
+
         Self {
             cfg,
             auth,
@@ -247,6 +288,9 @@ impl EdgeService {
             backend_health,
             health_checker,
             health_check_config,
+            idempotency,
+            client_tokens,
+            tenants,
         }
     }
 
@@ -288,7 +332,7 @@ impl EdgeService {
     /// Extract TLS configuration from upstream URL
     /// Returns (use_tls, sni_hostname)
     pub fn get_tls_config(&self, addr: &SocketAddr) -> (bool, String) {
-        if let Some(url_str) = self.upstream_urls.get(addr)
+        if let Some(url_str) = self.upstream_urls.load().get(addr)
             && let Ok(url) = url::Url::parse(url_str) {
                 let use_tls = url.scheme() == "https";
                 let sni = url.host_str().unwrap_or("").to_string();
@@ -424,6 +468,9 @@ impl EdgeService {
             backend_health: self.backend_health.clone(),
             health_checker: self.health_checker.clone(),
             health_check_config: self.health_check_config.clone(),
+            idempotency: self.idempotency.clone(),
+            client_tokens: self.client_tokens.clone(),
+            tenants: self.tenants.clone(),
         };
 
         temp_service.validate_config()?;
@@ -456,6 +503,9 @@ impl EdgeService {
             backend_health: self.backend_health.clone(),
             health_checker: self.health_checker.clone(),
             health_check_config: self.health_check_config.clone(),
+            idempotency: self.idempotency.clone(),
+            client_tokens: self.client_tokens.clone(),
+            tenants: self.tenants.clone(),
         }
     }
 }
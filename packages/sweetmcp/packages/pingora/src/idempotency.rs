@@ -0,0 +1,306 @@
+//! Idempotency keys for safe client retries
+//!
+//! A client may send an `Idempotency-Key` header with a request. The first
+//! request carrying a key runs normally and its response is stored for the
+//! configured TTL; a retry with the same key and the same request replays the
+//! stored response instead of calling the plugin again. A retry that arrives
+//! while the first request is still running waits for it to finish. Reusing a
+//! key for a different request is rejected.
+//!
+//! Requests are compared by a fingerprint of their canonical form: the HTTP
+//! method, path and client protocol plus the JSON-RPC body with object keys
+//! sorted and the JSON-RPC `id` removed, so a retry that renumbers its request
+//! still matches.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tokio::sync::Notify;
+
+/// Request header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Longest accepted idempotency key
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Idempotency store sizing and timing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Honour `Idempotency-Key` headers
+    pub enabled: bool,
+    /// How long a completed response is replayed
+    pub ttl: Duration,
+    /// Maximum number of tracked keys; new keys are passed through when full
+    pub max_entries: usize,
+    /// How long a retry waits for an identical in-flight request
+    pub wait_timeout: Duration,
+    /// Largest response body that is stored for replay
+    pub max_response_bytes: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(24 * 3600),
+            max_entries: 10_000,
+            wait_timeout: Duration::from_secs(30),
+            max_response_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// SHA-256 of a canonical request
+pub type Fingerprint = [u8; 32];
+
+/// Fingerprint a request from its identifying parts and JSON-RPC body
+///
+/// `parts` are the values that must also match, such as method and path.
+/// Bodies that are not JSON are hashed as-is.
+pub fn fingerprint(parts: &[&str], body: &[u8]) -> Fingerprint {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    match serde_json::from_slice::<Value>(body) {
+        Ok(value) => {
+            let canonical = serde_json::to_vec(&canonicalize(strip_ids(value)))
+                .unwrap_or_else(|_| body.to_vec());
+            hasher.update(&canonical);
+        }
+        Err(_) => hasher.update(body),
+    }
+    hasher.finalize().into()
+}
+
+/// Remove the JSON-RPC `id` from a request or every request in a batch
+fn strip_ids(value: Value) -> Value {
+    match value {
+        Value::Object(mut object) => {
+            object.remove("id");
+            Value::Object(object)
+        }
+        Value::Array(batch) => Value::Array(batch.into_iter().map(strip_ids).collect()),
+        other => other,
+    }
+}
+
+/// Rebuild every object with its keys in sorted order
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+/// Check a client-supplied key: 1 to 255 visible ASCII characters
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Response stored for replay
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Bytes,
+}
+
+/// Outcome of claiming a key for a request
+#[derive(Debug)]
+pub enum Claim {
+    /// The caller owns the key and must `complete` or `release` it
+    Acquired,
+    /// A response stored for an identical request
+    Replay(Arc<StoredResponse>),
+    /// The key was already used for a different request
+    Mismatch,
+    /// An identical request is still running after the wait timeout
+    InProgress,
+    /// The store is full; the request runs without idempotency protection
+    Bypassed,
+}
+
+enum Slot {
+    InFlight {
+        fingerprint: Fingerprint,
+        done: Arc<Notify>,
+    },
+    Completed {
+        fingerprint: Fingerprint,
+        response: Arc<StoredResponse>,
+        expires_at: Instant,
+    },
+}
+
+/// Keys seen within the TTL and the responses stored for them
+pub struct IdempotencyStore {
+    entries: DashMap<String, Slot>,
+    config: IdempotencyConfig,
+}
+
+impl IdempotencyStore {
+    pub fn new(config: IdempotencyConfig) -> Self {
+        Self {
+            entries: DashMap::new(),
+            config,
+        }
+    }
+
+    /// Whether `Idempotency-Key` headers are honoured
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Number of tracked keys, including expired ones not yet swept
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Claim `key` for a request, waiting for an identical in-flight request
+    pub async fn acquire(&self, key: &str, fingerprint: &Fingerprint) -> Claim {
+        let deadline = tokio::time::Instant::now() + self.config.wait_timeout;
+        loop {
+            let done = match self.try_claim(key, fingerprint) {
+                Ok(claim) => return claim,
+                Err(done) => done,
+            };
+
+            // Register before re-checking so a release in between isn't missed
+            let notified = done.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if !self.is_in_flight(key, &done) {
+                continue;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return Claim::InProgress;
+            }
+        }
+    }
+
+    /// Claim `key` without waiting; `Err` carries the in-flight request's signal
+    fn try_claim(&self, key: &str, fingerprint: &Fingerprint) -> Result<Claim, Arc<Notify>> {
+        if !self.entries.contains_key(key) && self.entries.len() >= self.config.max_entries {
+            self.sweep();
+            if self.entries.len() >= self.config.max_entries {
+                return Ok(Claim::Bypassed);
+            }
+        }
+
+        match self.entries.entry(key.to_string()) {
+            Entry::Occupied(mut occupied) => match occupied.get() {
+                Slot::InFlight {
+                    fingerprint: running,
+                    done,
+                } => {
+                    if running == fingerprint {
+                        Err(done.clone())
+                    } else {
+                        Ok(Claim::Mismatch)
+                    }
+                }
+                Slot::Completed { expires_at, .. } if *expires_at <= Instant::now() => {
+                    occupied.insert(Slot::InFlight {
+                        fingerprint: *fingerprint,
+                        done: Arc::new(Notify::new()),
+                    });
+                    Ok(Claim::Acquired)
+                }
+                Slot::Completed {
+                    fingerprint: stored,
+                    response,
+                    ..
+                } => {
+                    if stored == fingerprint {
+                        Ok(Claim::Replay(response.clone()))
+                    } else {
+                        Ok(Claim::Mismatch)
+                    }
+                }
+            },
+            Entry::Vacant(vacant) => {
+                vacant.insert(Slot::InFlight {
+                    fingerprint: *fingerprint,
+                    done: Arc::new(Notify::new()),
+                });
+                Ok(Claim::Acquired)
+            }
+        }
+    }
+
+    fn is_in_flight(&self, key: &str, done: &Arc<Notify>) -> bool {
+        self.entries.get(key).is_some_and(|slot| {
+            matches!(&*slot, Slot::InFlight { done: current, .. } if Arc::ptr_eq(current, done))
+        })
+    }
+
+    /// Store the response of an acquired key and wake waiting retries
+    ///
+    /// Bodies over `max_response_bytes` are not stored; the key is released.
+    pub fn complete(&self, key: &str, fingerprint: &Fingerprint, response: StoredResponse) {
+        if response.body.len() > self.config.max_response_bytes {
+            self.release(key, fingerprint);
+            return;
+        }
+        if let Some(mut slot) = self.entries.get_mut(key)
+            && let Slot::InFlight {
+                fingerprint: running,
+                done,
+            } = &*slot
+            && running == fingerprint
+        {
+            let done = done.clone();
+            *slot = Slot::Completed {
+                fingerprint: *fingerprint,
+                response: Arc::new(response),
+                expires_at: Instant::now() + self.config.ttl,
+            };
+            drop(slot);
+            done.notify_waiters();
+        }
+    }
+
+    /// Forget an acquired key so a retry runs again, waking waiting retries
+    pub fn release(&self, key: &str, fingerprint: &Fingerprint) {
+        let removed = self.entries.remove_if(key, |_, slot| {
+            matches!(slot, Slot::InFlight { fingerprint: running, .. } if running == fingerprint)
+        });
+        if let Some((_, Slot::InFlight { done, .. })) = removed {
+            done.notify_waiters();
+        }
+    }
+
+    /// Drop completed entries past their TTL
+    pub fn sweep(&self) {
+        let now = Instant::now();
+        self.entries.retain(|_, slot| match slot {
+            Slot::InFlight { .. } => true,
+            Slot::Completed { expires_at, .. } => *expires_at > now,
+        });
+    }
+}
@@ -1,20 +1,29 @@
 pub mod api;
 pub mod auth;
 pub mod circuit_breaker;
+pub mod client_tokens;
 pub mod config;
 pub mod crypto;
 pub mod dns_discovery;
+pub mod idempotency;
 pub mod mdns_discovery;
 pub mod metrics;
 pub mod normalize;
 pub mod peer_discovery;
 pub mod rate_limit;
+pub mod reload;
+pub mod session_affinity;
 pub mod shutdown;
+pub mod streaming;
+pub mod tenants;
 pub mod tls;
+pub mod tool_cache;
+pub mod transform;
 pub mod edge;
 pub mod load;
 pub mod metric_picker;
 pub mod mcp_bridge;
+pub mod upstream;
 
 /// Get the directory where TLS certificates are stored
 pub fn get_cert_dir() -> std::path::PathBuf {
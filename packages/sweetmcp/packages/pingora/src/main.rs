@@ -18,12 +18,17 @@ pub use sweetmcp::metrics as metrics;
 pub use sweetmcp::normalize;
 mod peer_discovery;
 pub use sweetmcp::rate_limit;
+mod reload;
 mod shutdown;
 mod tls;
+mod tool_cache;
+mod upstream;
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use clap::Parser;
 use config::Config;
 use opentelemetry::global;
 use opentelemetry_prometheus::PrometheusExporter;
@@ -41,13 +46,32 @@ fn get_cert_dir() -> std::path::PathBuf {
         .join("certs")
 }
 
+/// SweetMCP Server command line
+#[derive(Parser)]
+#[command(version, about = "SweetMCP Server - Sugora Gateway")]
+struct Cli {
+    /// TOML or YAML configuration file; environment variables override it
+    /// (defaults to SWEETMCP_CONFIG)
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Validate the configuration, report every problem and exit
+    #[arg(long)]
+    check_config: bool,
+}
+
 fn main() {
     use std::io::Write;
     use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
-    env_logger::init();
+    let cli = Cli::parse();
+    let default_log_level = reload::init_logging();
 
-    if let Err(e) = run_server() {
+    if cli.check_config {
+        std::process::exit(check_config(cli.config.as_deref()));
+    }
+
+    if let Err(e) = run_server(cli.config, default_log_level) {
         let mut stderr = StandardStream::stderr(ColorChoice::Auto);
         let _ = stderr.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true));
         let _ = writeln!(&mut stderr, "🚫 SweetMCP Server failed to start: {}", e);
@@ -57,19 +81,58 @@ fn main() {
     }
 }
 
-fn run_server() -> Result<()> {
+/// Load and validate the configuration, print the result and return the exit code
+fn check_config(path: Option<&Path>) -> i32 {
+    use std::io::Write;
+    use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+    match Config::load(path) {
+        Ok(_) => {
+            let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+            let _ = stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true));
+            let _ = match path {
+                Some(path) => writeln!(&mut stdout, "✅ Configuration OK: {}", path.display()),
+                None => writeln!(&mut stdout, "✅ Configuration OK"),
+            };
+            let _ = stdout.reset();
+            0
+        }
+        Err(e) => {
+            let mut stderr = StandardStream::stderr(ColorChoice::Auto);
+            let _ = stderr.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true));
+            let _ = writeln!(&mut stderr, "🚫 Invalid configuration");
+            let _ = stderr.reset();
+            let _ = writeln!(&mut stderr, "{:#}", e);
+            1
+        }
+    }
+}
+
+fn run_server(
+    config_path: Option<PathBuf>,
+    default_log_level: Option<log::LevelFilter>,
+) -> Result<()> {
     log::info!("🍬 Starting SweetMCP Server with Sugora Gateway");
 
     // Load configuration
-    let cfg = Arc::new(Config::from_env()?);
+    let cfg = Arc::new(Config::load(config_path.as_deref())?);
     log::info!("✅ Configuration loaded successfully");
 
     // Initialize OpenTelemetry
     let _exporter = init_otel()?;
     log::info!("📊 OpenTelemetry initialized");
 
-    // Setup MCP bridge
-    let (bridge_tx, bridge_rx) = mpsc::channel::<mcp_bridge::BridgeMsg>(1024);
+    // Setup MCP bridge; the channel only hands off to the bridge's prioritized queue
+    let (bridge_tx, bridge_rx) = mpsc::channel::<mcp_bridge::BridgeMsg>(64);
+
+    // Register upstream MCP servers federated through the bridge
+    let upstream_registry = Arc::new(
+        upstream::UpstreamRegistry::from_configs(&cfg.mcp_upstreams)
+            .context("Failed to register upstream MCP servers")?,
+    );
+    if !upstream_registry.is_empty() {
+        log::info!("🌐 Federating {} upstream MCP servers", upstream_registry.len());
+    }
 
     // Create server with default options
     let mut server =
@@ -84,7 +147,18 @@ fn run_server() -> Result<()> {
         half_open_requests: 3,
         metrics_window: std::time::Duration::from_secs(10),
     };
-    let circuit_breaker_manager = Arc::new(circuit_breaker::CircuitBreakerManager::new(circuit_config));
+    // Plugin tools trip after a handful of failed calls and stay open longer
+    let tool_circuit_config = circuit_breaker::CircuitBreakerConfig {
+        error_threshold_percentage: cfg.circuit_breaker_threshold,
+        request_volume_threshold: 5,
+        sleep_window: std::time::Duration::from_secs(15),
+        half_open_requests: 1,
+        metrics_window: std::time::Duration::from_secs(60),
+    };
+    let circuit_breaker_manager = Arc::new(
+        circuit_breaker::CircuitBreakerManager::new(circuit_config)
+            .with_tool_config(tool_circuit_config),
+    );
 
     // Create peer registry with circuit breaker manager
     let peer_registry = peer_discovery::PeerRegistry::new(circuit_breaker_manager.clone());
@@ -103,6 +177,7 @@ fn run_server() -> Result<()> {
     );
     shutdown_coordinator.set_local_port(local_port);
     shutdown_coordinator.set_peer_registry(peer_registry.clone());
+    shutdown_coordinator.set_drain_timeout(cfg.drain_timeout);
 
     let shutdown_coordinator = Arc::new(shutdown_coordinator);
 
@@ -117,6 +192,13 @@ fn run_server() -> Result<()> {
         "mcp-bridge",
         McpBridgeService {
             rx: Some(bridge_rx),
+            upstreams: upstream_registry,
+            shutdown: shutdown_coordinator.clone(),
+            queue_config: cfg.bridge_queue.clone(),
+            plugin_host_url: cfg.plugin_host_url.clone(),
+            breakers: circuit_breaker_manager.clone(),
+            tool_timeout: cfg.request_timeout,
+            tool_cache: Arc::new(tool_cache::ToolListCache::new(cfg.tool_list_cache.clone())),
         },
     );
 
@@ -213,6 +295,25 @@ fn run_server() -> Result<()> {
     let static_upstreams: Vec<pingora_load_balancing::Backend> = edge_service.picker().load()
         .backends
         .to_vec();
+    let static_upstreams = Arc::new(arc_swap::ArcSwap::from_pointee(static_upstreams));
+
+    // Apply reloadable settings (log level, GraphQL and rate limits, upstreams)
+    // now and again on every SIGHUP
+    let reloader = Arc::new(reload::ConfigReloader::new(
+        config_path,
+        cfg.clone(),
+        reload::ReloadTargets {
+            rate_limiter: edge_service.rate_limit_manager().as_distributed(),
+            static_upstreams: static_upstreams.clone(),
+            upstream_urls: edge_service.upstream_urls.clone(),
+        },
+        default_log_level,
+    ));
+    reloader.apply_current();
+    server.add_service(background_service(
+        "config-reload",
+        ConfigReloadService { reloader },
+    ));
 
     let backend_update = background_service(
         "backend-update",
@@ -352,6 +453,13 @@ use pingora::services::background::{background_service, BackgroundService};
 
 struct McpBridgeService {
     rx: Option<mpsc::Receiver<mcp_bridge::BridgeMsg>>,
+    upstreams: Arc<upstream::UpstreamRegistry>,
+    shutdown: Arc<shutdown::ShutdownCoordinator>,
+    queue_config: mcp_bridge::BridgeQueueConfig,
+    plugin_host_url: String,
+    breakers: Arc<circuit_breaker::CircuitBreakerManager>,
+    tool_timeout: Duration,
+    tool_cache: Arc<tool_cache::ToolListCache>,
 }
 
 impl BackgroundService for McpBridgeService {
@@ -377,11 +485,28 @@ impl BackgroundService for McpBridgeService {
             let this = self as *const Self as *mut Self;
             (*this).rx.take().unwrap()
         };
+        let upstreams = self.upstreams.clone();
+        let coordinator = self.shutdown.clone();
+        let queue_config = self.queue_config.clone();
+        let plugin_host_url = self.plugin_host_url.clone();
+        let breakers = self.breakers.clone();
+        let tool_timeout = self.tool_timeout;
+        let tool_cache = self.tool_cache.clone();
 
         Box::pin(async move {
             log::info!("🔌 Starting MCP bridge");
+            let bridge = mcp_bridge::run(
+                rx,
+                upstreams,
+                coordinator,
+                queue_config,
+                plugin_host_url,
+                breakers,
+                tool_timeout,
+                tool_cache,
+            );
             tokio::select! {
-                _ = mcp_bridge::run(rx) => {
+                _ = bridge => {
                     log::info!("MCP bridge stopped");
                 }
                 _ = shutdown.changed() => {
@@ -487,6 +612,33 @@ impl BackgroundService for PeerDiscoveryService {
     }
 }
 
+struct ConfigReloadService {
+    reloader: Arc<reload::ConfigReloader>,
+}
+
+impl BackgroundService for ConfigReloadService {
+    fn start<'life0, 'async_trait>(
+        &'life0 self,
+        mut shutdown: ShutdownWatch,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        let reloader = self.reloader.clone();
+
+        Box::pin(async move {
+            log::info!("♻️ Listening for SIGHUP to reload configuration");
+            tokio::select! {
+                _ = reloader.listen_for_sighup() => {}
+                _ = shutdown.changed() => {
+                    log::info!("Config reload service shutting down");
+                }
+            }
+        })
+    }
+}
+
 struct RateLimitCleanupService {
     rate_limiter: Arc<rate_limit::distributed::DistributedRateLimitManager>,
 }
@@ -525,7 +677,8 @@ impl BackgroundService for RateLimitCleanupService {
 struct BackendUpdateService {
     picker_swap: Arc<arc_swap::ArcSwap<metric_picker::MetricPicker>>,
     peer_discovery: Arc<peer_discovery::PeerDiscovery>,
-    static_upstreams: Vec<pingora_load_balancing::Backend>,
+    /// Upstreams from the configuration, replaced on reload
+    static_upstreams: Arc<arc_swap::ArcSwap<Vec<pingora_load_balancing::Backend>>>,
 }
 
 impl BackgroundService for BackendUpdateService {
@@ -552,6 +705,7 @@ impl BackgroundService for BackendUpdateService {
                         match peer_discovery.discover().await {
                             Ok((discovered_peers, _)) => {
                                 // Merge static upstreams + discovered peers
+                                let static_upstreams = static_upstreams.load();
                                 let discovered_count = discovered_peers.len();
                                 let mut all_backends = std::collections::BTreeSet::new();
                                 all_backends.extend(static_upstreams.iter().cloned());
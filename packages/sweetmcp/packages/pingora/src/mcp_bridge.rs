@@ -1,7 +1,19 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sweetmcp_axum::JSONRPC_VERSION;
-use tokio::sync::{mpsc, oneshot};
-use log::{error, info};
+use tokio::sync::{mpsc, oneshot, Notify};
+use log::{error, info, warn};
+
+use crate::circuit_breaker::CircuitBreakerManager;
+use crate::shutdown::{RequestGuard, ShutdownCoordinator};
+use crate::tool_cache::{self, CachedTools, ToolListCache, TOOLS_LIST_CHANGED};
+use crate::upstream::UpstreamRegistry;
 
 // Bridge message type for communication between Pingora and MCP handler
 pub type BridgeMsg = (
@@ -10,52 +22,585 @@ pub type BridgeMsg = (
     oneshot::Sender<Value>,
 );
 
+/// JSON-RPC error code returned when the bridge queue is saturated
+pub const BRIDGE_OVERLOADED_CODE: i64 = -32000;
+
+/// JSON-RPC error code returned while a tool's circuit breaker is open
+pub const TOOL_CIRCUIT_OPEN_CODE: i64 = -32001;
+
+/// JSON-RPC error code returned when a tool call exceeds its timeout
+pub const TOOL_TIMEOUT_CODE: i64 = -32002;
+
+/// Scheduling priority of a bridge message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BridgePriority {
+    /// Lifecycle traffic: ping, initialize and notifications
+    High = 0,
+    /// Discovery traffic: list/read style methods
+    Normal = 1,
+    /// Tool execution and other potentially long-running calls
+    Low = 2,
+}
+
+impl BridgePriority {
+    const ALL: [BridgePriority; 3] = [Self::High, Self::Normal, Self::Low];
+
+    /// Classify a JSON-RPC method
+    pub fn for_method(method: &str) -> Self {
+        match method {
+            "ping" | "initialize" => Self::High,
+            m if m.starts_with("notifications/") => Self::High,
+            m if m.ends_with("/list") || m == "resources/read" || m == "prompts/get" => {
+                Self::Normal
+            }
+            _ => Self::Low,
+        }
+    }
+
+    /// Metric label for this priority
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+}
+
+/// What to do with a message that arrives while the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverflowPolicy {
+    /// Answer the incoming message with an overload error
+    Reject,
+    /// Drop the oldest queued message of equal or lower priority to make room
+    ShedOldest,
+}
+
+impl OverflowPolicy {
+    /// Metric label for this policy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::ShedOldest => "shed-oldest",
+        }
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "shed-oldest" | "shed_oldest" => Ok(Self::ShedOldest),
+            other => anyhow::bail!("Unknown bridge overflow policy: {}", other),
+        }
+    }
+}
+
+/// Bridge queue sizing and overflow behaviour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeQueueConfig {
+    /// Maximum number of queued messages across all priorities
+    pub capacity: usize,
+    /// Overflow policy applied when the queue is full
+    pub overflow_policy: OverflowPolicy,
+}
+
+impl Default for BridgeQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            overflow_policy: OverflowPolicy::Reject,
+        }
+    }
+}
+
+/// A queued message, counted as in-flight for shutdown draining
+struct QueuedMsg {
+    msg: BridgeMsg,
+    in_flight: Option<RequestGuard>,
+}
+
+/// Bounded priority queue feeding the bridge worker
+pub struct BridgeQueue {
+    lanes: Mutex<[VecDeque<QueuedMsg>; 3]>,
+    config: BridgeQueueConfig,
+    notify: Notify,
+    closed: AtomicBool,
+}
+
+impl BridgeQueue {
+    /// Create an empty queue
+    pub fn new(config: BridgeQueueConfig) -> Self {
+        Self {
+            lanes: Mutex::new([VecDeque::new(), VecDeque::new(), VecDeque::new()]),
+            config,
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Total number of queued messages
+    pub fn len(&self) -> usize {
+        self.lock_lanes().iter().map(VecDeque::len).sum()
+    }
+
+    /// Whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Enqueue a message, applying the overflow policy when full
+    pub fn push(&self, msg: BridgeMsg, in_flight: Option<RequestGuard>) {
+        let priority = BridgePriority::for_method(
+            msg.0.get("method").and_then(Value::as_str).unwrap_or_default(),
+        );
+        let policy = self.config.overflow_policy;
+        let mut incoming = Some(QueuedMsg {
+            msg,
+            in_flight,
+        });
+
+        let mut lanes = self.lock_lanes();
+        let queued: usize = lanes.iter().map(VecDeque::len).sum();
+
+        if queued >= self.config.capacity {
+            let victim = match policy {
+                OverflowPolicy::Reject => incoming.take().map(|m| (m, priority)),
+                OverflowPolicy::ShedOldest => BridgePriority::ALL
+                    .iter()
+                    .rev()
+                    .filter(|lane| **lane >= priority)
+                    .find_map(|lane| lanes[*lane as usize].pop_front().map(|m| (m, *lane)))
+                    // Only higher-priority work is queued: reject the newcomer instead
+                    .or_else(|| incoming.take().map(|m| (m, priority))),
+            };
+
+            if let Some((victim, lane)) = victim {
+                warn!(
+                    "Bridge queue full ({} messages), dropping {} priority message ({})",
+                    queued,
+                    lane.as_str(),
+                    policy.as_str()
+                );
+                crate::metrics::record_bridge_overflow(lane.as_str(), policy.as_str());
+                send_overloaded(victim.msg);
+            }
+        }
+
+        let enqueued = incoming.is_some();
+        if let Some(queued_msg) = incoming {
+            lanes[priority as usize].push_back(queued_msg);
+        }
+
+        for lane in BridgePriority::ALL {
+            crate::metrics::set_bridge_queue_depth(lane.as_str(), lanes[lane as usize].len());
+        }
+        drop(lanes);
+
+        if enqueued {
+            self.notify.notify_one();
+        }
+    }
+
+    /// Stop accepting messages; `pop` returns `None` once the queue is empty
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.notify.notify_one();
+    }
+
+    /// Dequeue the highest-priority message, waiting until one is available
+    pub async fn pop(&self) -> Option<(BridgeMsg, Option<RequestGuard>)> {
+        loop {
+            let notified = self.notify.notified();
+
+            {
+                let mut lanes = self.lock_lanes();
+                let next = BridgePriority::ALL
+                    .iter()
+                    .find_map(|lane| lanes[*lane as usize].pop_front().map(|m| (m, *lane)));
+                if let Some((queued, lane)) = next {
+                    crate::metrics::set_bridge_queue_depth(
+                        lane.as_str(),
+                        lanes[lane as usize].len(),
+                    );
+                    return Some((queued.msg, queued.in_flight));
+                }
+            }
+
+            if self.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+
+            notified.await;
+        }
+    }
+
+    fn lock_lanes(&self) -> std::sync::MutexGuard<'_, [VecDeque<QueuedMsg>; 3]> {
+        self.lanes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Answer a dropped message with a JSON-RPC overload error
+fn send_overloaded((request, _protocol_ctx, tx): BridgeMsg) {
+    let response = serde_json::json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "error": {
+            "code": BRIDGE_OVERLOADED_CODE,
+            "message": "Server overloaded: MCP bridge queue is full, retry later"
+        },
+        "id": request.get("id").cloned().unwrap_or(Value::Null)
+    });
+
+    if tx.send(response).is_err() {
+        error!("Failed to send overload response back through bridge");
+    }
+}
+
+/// Times a `tools/call` message for the per-tool metrics
+struct ToolCallTimer {
+    tool: String,
+    trace_id: Option<String>,
+    request_size: usize,
+    started: Instant,
+}
+
+impl ToolCallTimer {
+    /// Start timing `request` if it is a tool call
+    fn start(request: &Value, protocol_ctx: &crate::normalize::ProtocolContext) -> Option<Self> {
+        if request.get("method").and_then(Value::as_str) != Some("tools/call") {
+            return None;
+        }
+        Some(Self {
+            tool: request
+                .pointer("/params/name")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string(),
+            trace_id: trace_id(request, protocol_ctx),
+            request_size: serde_json::to_vec(request).map(|b| b.len()).unwrap_or(0),
+            started: Instant::now(),
+        })
+    }
+
+    /// Record the call's latency, payload sizes and error class
+    fn finish(self, response: &Value) {
+        let response_size = serde_json::to_vec(response).map(|b| b.len()).unwrap_or(0);
+        crate::metrics::record_tool_call(
+            &self.tool,
+            self.started.elapsed().as_secs_f64(),
+            self.request_size,
+            response_size,
+            tool_error(response).as_ref(),
+            self.trace_id.as_deref(),
+        );
+    }
+}
+
+/// Failure reported by a `tools/call` response, if any
+pub fn tool_error(response: &Value) -> Option<crate::metrics::ToolError> {
+    if let Some(error) = response.get("error") {
+        let code = error.get("code").and_then(Value::as_i64);
+        return Some(crate::metrics::ToolError {
+            code,
+            class: code.map_or("internal_error", crate::metrics::json_rpc_error_class),
+        });
+    }
+    if response.pointer("/result/isError").and_then(Value::as_bool) == Some(true) {
+        return Some(crate::metrics::ToolError {
+            code: None,
+            class: "tool_error",
+        });
+    }
+    None
+}
+
+/// Trace ID of a message, from `params._meta.traceparent` or a `traceparent` header
+pub fn trace_id(request: &Value, protocol_ctx: &crate::normalize::ProtocolContext) -> Option<String> {
+    request
+        .pointer("/params/_meta/traceparent")
+        .and_then(Value::as_str)
+        .or_else(|| {
+            protocol_ctx
+                .metadata
+                .custom_headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("traceparent"))
+                .map(|(_, value)| value.as_str())
+        })
+        .and_then(parse_traceparent)
+        .map(str::to_string)
+}
+
+/// Trace ID field of a W3C `traceparent` value (`00-<trace-id>-<span-id>-<flags>`)
+pub fn parse_traceparent(traceparent: &str) -> Option<&str> {
+    let mut fields = traceparent.trim().split('-');
+    let version = fields.next()?;
+    let trace_id = fields.next()?;
+    let span_id = fields.next()?;
+    let flags = fields.next()?;
+
+    let is_hex = |field: &str, len: usize| {
+        field.len() == len && field.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    };
+    let valid = is_hex(version, 2)
+        && version != "ff"
+        && is_hex(trace_id, 32)
+        && trace_id.bytes().any(|b| b != b'0')
+        && is_hex(span_id, 16)
+        && is_hex(flags, 2);
+    valid.then_some(trace_id)
+}
+
 // Run the MCP bridge that processes incoming messages
-pub async fn run(mut rx: mpsc::Receiver<BridgeMsg>) {
-    info!("MCP bridge started and ready to process messages");
-
-    while let Some((request, _protocol_ctx, tx)) = rx.recv().await {
-        // Forward JSON-RPC request to sweetmcp-axum via HTTP
-        let client = reqwest::Client::new();
-
-        let response = match client
-            .post("http://localhost:8080/rpc")
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await
-        {
-            Ok(http_response) => match http_response.json::<Value>().await {
-                Ok(json_response) => json_response,
-                Err(e) => {
-                    error!("Failed to parse JSON response from Axum: {:?}", e);
-                    serde_json::json!({
-                        "jsonrpc": JSONRPC_VERSION,
-                        "error": {
-                            "code": -32603,
-                            "message": "Internal error: invalid response from backend"
-                        },
-                        "id": request.get("id").cloned().unwrap_or(Value::Null)
-                    })
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    mut rx: mpsc::Receiver<BridgeMsg>,
+    upstreams: Arc<UpstreamRegistry>,
+    shutdown: Arc<ShutdownCoordinator>,
+    queue_config: BridgeQueueConfig,
+    plugin_host_url: String,
+    breakers: Arc<CircuitBreakerManager>,
+    tool_timeout: Duration,
+    tool_cache: Arc<ToolListCache>,
+) {
+    info!(
+        "MCP bridge started and ready to process messages ({} upstream MCP servers, queue capacity {}, overflow {})",
+        upstreams.len(),
+        queue_config.capacity,
+        queue_config.overflow_policy.as_str()
+    );
+
+    let queue = Arc::new(BridgeQueue::new(queue_config));
+
+    // Ingress: move channel messages into the prioritized queue as soon as they arrive
+    let ingress_queue = queue.clone();
+    let ingress = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            // Counted until the response is sent so shutdown can drain it
+            let in_flight = shutdown.bridge_message_start();
+            ingress_queue.push(msg, Some(in_flight));
+        }
+        ingress_queue.close();
+    });
+
+    let client = reqwest::Client::new();
+
+    while let Some(((request, protocol_ctx, tx), _in_flight)) = queue.pop().await {
+        let method = request.get("method").and_then(Value::as_str).unwrap_or_default();
+        let timer = ToolCallTimer::start(&request, &protocol_ctx);
+
+        let response = match method {
+            // Calls to namespaced upstream tools bypass the local plugin host
+            "tools/call" => {
+                guarded_tool_call(&breakers, tool_timeout, &request, async {
+                    match upstreams.call_tool(&request).await {
+                        Some(response) => response,
+                        None => forward_to_backend(&client, &plugin_host_url, &request).await,
+                    }
+                })
+                .await
+            }
+            // The whole tool list is served from the cache
+            "tools/list" if tool_cache::is_cacheable(&request) => {
+                cached_tools_list(&client, &plugin_host_url, &upstreams, &tool_cache, &request)
+                    .await
+            }
+            // Later pages merge local tools with every upstream's namespaced tools
+            "tools/list" if !upstreams.is_empty() => {
+                let mut response = forward_to_backend(&client, &plugin_host_url, &request).await;
+                let upstream_tools = upstreams.list_tools().await;
+                if let Some(tools) = response
+                    .pointer_mut("/result/tools")
+                    .and_then(Value::as_array_mut)
+                {
+                    tools.extend(upstream_tools);
                 }
-            },
+                response
+            }
+            TOOLS_LIST_CHANGED => {
+                info!("Tool list changed, dropping cached tools/list");
+                tool_cache.invalidate();
+                Value::Null
+            }
+            _ => forward_to_backend(&client, &plugin_host_url, &request).await,
+        };
+
+        if let Some(timer) = timer {
+            timer.finish(&response);
+        }
+
+        if let Err(e) = tx.send(response) {
+            error!("Failed to send response back through bridge: {:?}", e);
+        }
+    }
+
+    ingress.abort();
+    info!("MCP bridge shutting down");
+}
+
+/// Answer a whole-list `tools/list`, from the cache when it is fresh
+///
+/// On a miss the plugin host's tools are merged with every upstream's and
+/// cached. Error responses, paginated plugin host results and lists missing an
+/// unreachable upstream are returned without being cached.
+async fn cached_tools_list(
+    client: &reqwest::Client,
+    plugin_host_url: &str,
+    upstreams: &UpstreamRegistry,
+    cache: &ToolListCache,
+    request: &Value,
+) -> Value {
+    if upstreams.take_tools_changed() {
+        info!("Upstream tool list changed, dropping cached tools/list");
+        cache.invalidate();
+    }
+    if let Some(cached) = cache.get() {
+        return tool_cache::tools_list_response(request, &cached);
+    }
+
+    let generation = cache.generation();
+    let mut response = forward_to_backend(client, plugin_host_url, request).await;
+    let paginated = response.pointer("/result/nextCursor").is_some();
+    let Some(local_tools) = response
+        .pointer_mut("/result/tools")
+        .and_then(Value::as_array_mut)
+    else {
+        return response;
+    };
+
+    let (upstream_tools, complete) = upstreams.collect_tools().await;
+    if paginated {
+        local_tools.extend(upstream_tools);
+        return response;
+    }
+
+    let mut tools = std::mem::take(local_tools);
+    tools.extend(upstream_tools);
+    let cached = if complete {
+        cache.store(generation, tools)
+    } else {
+        CachedTools {
+            etag: tool_cache::tools_etag(&tools),
+            tools,
+        }
+    };
+    tool_cache::tools_list_response(request, &cached)
+}
+
+/// Run a tool call behind its circuit breaker and timeout
+///
+/// While the tool's breaker is open the call fails fast with
+/// [`TOOL_CIRCUIT_OPEN_CODE`] and a `retryAfter` hint in seconds. Timeouts,
+/// internal and server errors count as failures; invalid arguments and tool
+/// results flagged `isError` do not.
+async fn guarded_tool_call(
+    breakers: &CircuitBreakerManager,
+    timeout: Duration,
+    request: &Value,
+    call: impl std::future::Future<Output = Value>,
+) -> Value {
+    let tool = request
+        .pointer("/params/name")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let breaker = breakers.get_tool_breaker(tool).await;
+
+    if !breaker.should_allow_request().await {
+        let retry_after = breaker.retry_after().await.unwrap_or_default();
+        warn!(
+            "Circuit open for tool {}, rejecting call (retry after {:?})",
+            tool, retry_after
+        );
+        return tool_error_response(
+            request,
+            TOOL_CIRCUIT_OPEN_CODE,
+            format!("Tool {} is temporarily unavailable after repeated failures", tool),
+            serde_json::json!({
+                "tool": tool,
+                "retryAfter": retry_after.as_secs_f64().ceil() as u64,
+            }),
+        );
+    }
+
+    let response = match tokio::time::timeout(timeout, call).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!("Tool {} timed out after {:?}", tool, timeout);
+            tool_error_response(
+                request,
+                TOOL_TIMEOUT_CODE,
+                format!("Tool {} timed out after {}s", tool, timeout.as_secs()),
+                serde_json::json!({ "tool": tool }),
+            )
+        }
+    };
+
+    match tool_error(&response).map(|error| error.class) {
+        Some("internal_error" | "server_error" | "timeout") => breaker.record_failure().await,
+        // Don't keep a breaker for every name a client makes up
+        Some("method_not_found") => {
+            breakers
+                .remove_breaker(&crate::circuit_breaker::tool_breaker_key(tool))
+                .await
+        }
+        _ => breaker.record_success().await,
+    }
+    response
+}
+
+/// JSON-RPC error answering `request`
+fn tool_error_response(request: &Value, code: i64, message: String, data: Value) -> Value {
+    serde_json::json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "error": {
+            "code": code,
+            "message": message,
+            "data": data
+        },
+        "id": request.get("id").cloned().unwrap_or(Value::Null)
+    })
+}
+
+// Forward JSON-RPC request to the sweetmcp-axum plugin host via HTTP
+async fn forward_to_backend(
+    client: &reqwest::Client,
+    plugin_host_url: &str,
+    request: &Value,
+) -> Value {
+    match client
+        .post(plugin_host_url)
+        .header("content-type", "application/json")
+        .json(request)
+        .send()
+        .await
+    {
+        Ok(http_response) => match http_response.json::<Value>().await {
+            Ok(json_response) => json_response,
             Err(e) => {
-                error!("Failed to forward request to Axum: {:?}", e);
+                error!("Failed to parse JSON response from Axum: {:?}", e);
                 serde_json::json!({
                     "jsonrpc": JSONRPC_VERSION,
                     "error": {
                         "code": -32603,
-                        "message": "Internal error: backend unavailable"
+                        "message": "Internal error: invalid response from backend"
                     },
                     "id": request.get("id").cloned().unwrap_or(Value::Null)
                 })
             }
-        };
-
-        if let Err(e) = tx.send(response) {
-            error!("Failed to send response back through bridge: {:?}", e);
+        },
+        Err(e) => {
+            error!("Failed to forward request to Axum: {:?}", e);
+            serde_json::json!({
+                "jsonrpc": JSONRPC_VERSION,
+                "error": {
+                    "code": -32603,
+                    "message": "Internal error: backend unavailable"
+                },
+                "id": request.get("id").cloned().unwrap_or(Value::Null)
+            })
         }
     }
-
-    info!("MCP bridge shutting down");
 }
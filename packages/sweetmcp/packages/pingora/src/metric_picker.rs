@@ -15,11 +15,16 @@ use std::{
 use pingora::protocols::l4::socket::SocketAddr;
 use pingora_load_balancing::Backend;
 
+/// Points each backend places on the consistent-hash ring
+const RING_POINTS_PER_BACKEND: usize = 160;
+
 /// Metrics-based backend picker that selects the backend with lowest load
 pub struct MetricPicker {
     pub backends: Vec<Backend>,
     pub load_values: Vec<Arc<AtomicU64>>, // f64 bits representation
     round_robin_counter: AtomicUsize,
+    /// Consistent-hash ring of (point, backend index), sorted by point
+    ring: Vec<(u64, usize)>,
 }
 
 impl MetricPicker {
@@ -31,10 +36,27 @@ impl MetricPicker {
             .map(|_| Arc::new(AtomicU64::new(0)))
             .collect();
 
+        // Points derive from the backend address, so a backend keeps its
+        // place on the ring when other backends join or leave
+        let mut ring: Vec<(u64, usize)> = backends_vec
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, backend)| {
+                let addr = backend.addr.to_string();
+                (0..RING_POINTS_PER_BACKEND)
+                    .map(move |point| {
+                        let point_key = format!("{}#{}", addr, point);
+                        (seahash::hash(point_key.as_bytes()), idx)
+                    })
+            })
+            .collect();
+        ring.sort_unstable();
+
         Self {
             backends: backends_vec,
             load_values,
             round_robin_counter: AtomicUsize::new(0),
+            ring,
         }
     }
 
@@ -124,4 +146,35 @@ impl MetricPicker {
         
         Some(&self.backends[idx])
     }
+
+    /// Pick the backend owning `key` on the consistent-hash ring
+    #[inline]
+    pub fn pick_consistent(&self, key: &str) -> Option<&Backend> {
+        self.consistent_order(key).into_iter().next()
+    }
+
+    /// Every backend in ring order starting from the owner of `key`
+    ///
+    /// The first backend owns the key; when it can't serve, the next one is
+    /// the failover target. Only keys owned by a removed backend move.
+    pub fn consistent_order(&self, key: &str) -> Vec<&Backend> {
+        if self.ring.is_empty() {
+            return Vec::new();
+        }
+
+        let hash = seahash::hash(key.as_bytes());
+        let start = self.ring.partition_point(|&(point, _)| point < hash);
+        let mut seen = vec![false; self.backends.len()];
+        let mut order = Vec::with_capacity(self.backends.len());
+        for &(_, idx) in self.ring[start..].iter().chain(&self.ring[..start]) {
+            if !seen[idx] {
+                seen[idx] = true;
+                order.push(&self.backends[idx]);
+                if order.len() == self.backends.len() {
+                    break;
+                }
+            }
+        }
+        order
+    }
 }
@@ -68,6 +68,26 @@ pub static RATE_LIMITER_FAILURES: Lazy<CounterVec> = Lazy::new(|| {
     })
 });
 
+/// Requests per tenant
+pub static TENANT_REQUESTS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "sweetmcp_tenant_requests_total",
+        "Total number of requests per tenant by status code",
+        &["tenant", "status_code"]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register tenant request counter: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Record a completed request of `tenant`
+pub fn record_tenant_request(tenant: &str, status_code: u16) {
+    TENANT_REQUESTS
+        .with_label_values(&[tenant, &status_code.to_string()])
+        .inc();
+}
+
 /// Record a discovery operation
 pub fn record_discovery(operation: &str, success: bool, duration_secs: f64) {
     let status = if success { "success" } else { "failure" };
@@ -270,3 +290,234 @@ pub fn decrement_active_requests(method: &str, endpoint: &str) {
         .dec();
     HTTP_REQUESTS_CONCURRENT.dec();
 }
+
+// ============================================================================
+// MCP Bridge Queue Metrics
+// ============================================================================
+
+/// Bridge queue depth per priority class
+pub static BRIDGE_QUEUE_DEPTH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "sweetmcp_bridge_queue_depth",
+        "Number of MCP bridge messages waiting per priority class",
+        &["priority"]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register bridge queue depth gauge: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Bridge messages rejected or shed on overflow
+pub static BRIDGE_OVERFLOW: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "sweetmcp_bridge_overflow_total",
+        "Total number of MCP bridge messages dropped on queue overflow",
+        &["priority", "policy"]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register bridge overflow counter: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Update bridge queue depth for a priority class
+pub fn set_bridge_queue_depth(priority: &str, depth: usize) {
+    BRIDGE_QUEUE_DEPTH
+        .with_label_values(&[priority])
+        .set(depth as i64);
+}
+
+/// Record a bridge message dropped by the overflow policy
+pub fn record_bridge_overflow(priority: &str, policy: &str) {
+    BRIDGE_OVERFLOW
+        .with_label_values(&[priority, policy])
+        .inc();
+}
+
+// ============================================================================
+// MCP Tool Call Metrics
+// ============================================================================
+
+/// Most distinct tool names given their own label; the rest share `other`
+const MAX_TOOL_LABELS: usize = 512;
+
+/// Tool call counter per outcome
+pub static TOOL_CALLS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "sweetmcp_tool_calls_total",
+        "Total number of MCP tool calls per tool and outcome",
+        &["tool", "outcome"]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register tool call counter: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Failed tool calls per JSON-RPC error code and class
+pub static TOOL_ERRORS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "sweetmcp_tool_errors_total",
+        "Total number of failed MCP tool calls per JSON-RPC error code and class",
+        &["tool", "code", "class"]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register tool error counter: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Tool call latency histogram
+pub static TOOL_CALL_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "sweetmcp_tool_call_duration_seconds",
+        "MCP tool call duration in seconds",
+        &["tool"],
+        vec![0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register tool call duration histogram: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Tool call request and response payload sizes
+pub static TOOL_PAYLOAD_SIZE: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "sweetmcp_tool_payload_size_bytes",
+        "MCP tool call payload size in bytes",
+        &["tool", "direction"],
+        vec![64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0]
+    )
+    .unwrap_or_else(|e| {
+        log::error!("Failed to register tool payload size histogram: {}", e);
+        std::process::exit(1)
+    })
+});
+
+/// Tool names that have their own label
+static TOOL_LABELS: Lazy<dashmap::DashSet<String>> = Lazy::new(dashmap::DashSet::new);
+
+/// Latest traced observation per tool and metric
+static TOOL_EXEMPLARS: Lazy<dashmap::DashMap<(String, &'static str), ToolExemplar>> =
+    Lazy::new(dashmap::DashMap::new);
+
+/// Failure of a tool call as reported in its JSON-RPC response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolError {
+    /// JSON-RPC error code, or `None` for a result flagged `isError`
+    pub code: Option<i64>,
+    /// Error class label, see [`json_rpc_error_class`]
+    pub class: &'static str,
+}
+
+/// Observation linking a metric value to the trace that produced it
+///
+/// The Prometheus text format has no exemplars, so the latest traced value
+/// per tool is kept here and served by the exemplars admin endpoint.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ToolExemplar {
+    pub tool: String,
+    /// `sweetmcp_tool_call_duration_seconds` or `sweetmcp_tool_errors_total`
+    pub metric: &'static str,
+    pub trace_id: String,
+    pub value: f64,
+    /// Seconds since the Unix epoch
+    pub timestamp: f64,
+}
+
+/// Class label for a JSON-RPC error code
+pub fn json_rpc_error_class(code: i64) -> &'static str {
+    match code {
+        -32700 => "parse_error",
+        -32600 => "invalid_request",
+        -32601 => "method_not_found",
+        -32602 => "invalid_params",
+        -32603 => "internal_error",
+        crate::mcp_bridge::BRIDGE_OVERLOADED_CODE => "overloaded",
+        crate::mcp_bridge::TOOL_CIRCUIT_OPEN_CODE => "circuit_open",
+        crate::mcp_bridge::TOOL_TIMEOUT_CODE => "timeout",
+        -32099..=-32001 => "server_error",
+        _ => "application_error",
+    }
+}
+
+/// Label for `tool`, bounded so arbitrary client-supplied names can't explode cardinality
+fn tool_label(tool: &str) -> String {
+    if TOOL_LABELS.contains(tool) {
+        return tool.to_string();
+    }
+    if TOOL_LABELS.len() < MAX_TOOL_LABELS {
+        TOOL_LABELS.insert(tool.to_string());
+        return tool.to_string();
+    }
+    "other".to_string()
+}
+
+/// Record a completed tool call
+pub fn record_tool_call(
+    tool: &str,
+    duration_secs: f64,
+    request_size_bytes: usize,
+    response_size_bytes: usize,
+    error: Option<&ToolError>,
+    trace_id: Option<&str>,
+) {
+    let tool = tool_label(tool);
+    let outcome = if error.is_some() { "error" } else { "ok" };
+
+    TOOL_CALLS.with_label_values(&[tool.as_str(), outcome]).inc();
+    TOOL_CALL_DURATION
+        .with_label_values(&[tool.as_str()])
+        .observe(duration_secs);
+    TOOL_PAYLOAD_SIZE
+        .with_label_values(&[tool.as_str(), "request"])
+        .observe(request_size_bytes as f64);
+    TOOL_PAYLOAD_SIZE
+        .with_label_values(&[tool.as_str(), "response"])
+        .observe(response_size_bytes as f64);
+
+    if let Some(error) = error {
+        let code = error
+            .code
+            .map(|code| code.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        TOOL_ERRORS
+            .with_label_values(&[tool.as_str(), code.as_str(), error.class])
+            .inc();
+    }
+
+    if let Some(trace_id) = trace_id {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64())
+            .unwrap_or_default();
+        let mut record = |metric: &'static str, value: f64| {
+            TOOL_EXEMPLARS.insert(
+                (tool.clone(), metric),
+                ToolExemplar {
+                    tool: tool.clone(),
+                    metric,
+                    trace_id: trace_id.to_string(),
+                    value,
+                    timestamp,
+                },
+            );
+        };
+        record("sweetmcp_tool_call_duration_seconds", duration_secs);
+        if error.is_some() {
+            record("sweetmcp_tool_errors_total", 1.0);
+        }
+    }
+}
+
+/// Latest exemplar per tool and metric, ordered by tool
+pub fn tool_exemplars() -> Vec<ToolExemplar> {
+    let mut exemplars: Vec<ToolExemplar> = TOOL_EXEMPLARS
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect();
+    exemplars.sort_by(|a, b| a.tool.cmp(&b.tool).then(a.metric.cmp(b.metric)));
+    exemplars
+}
@@ -0,0 +1,189 @@
+//! JSON-RPC batch requests
+//!
+//! A batch is a JSON array of JSON-RPC messages. The edge splits it into one
+//! bridge message per element, leaving each element's id untouched, and folds
+//! the replies back into a single array in the order the elements arrived.
+//! Notifications get no entry in the reply, so a batch of only notifications
+//! gets no reply at all.
+
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+
+use super::conversion::{create_error_response, validate_json_rpc};
+use super::types::{ConversionError, ConversionResult, ProtocolContext};
+use crate::mcp_bridge::BridgeMsg;
+
+/// JSON-RPC code for an element that is not a valid request
+const INVALID_REQUEST_CODE: i32 = -32600;
+
+/// JSON-RPC code for an element the bridge could not answer
+const INTERNAL_ERROR_CODE: i32 = -32603;
+
+/// Configurable limits applied to every JSON-RPC batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchLimits {
+    /// Maximum number of messages in one batch
+    pub max_batch_size: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self { max_batch_size: 50 }
+    }
+}
+
+/// Process-wide limits used by the normalizer
+static BATCH_LIMITS: Lazy<ArcSwap<BatchLimits>> =
+    Lazy::new(|| ArcSwap::from_pointee(BatchLimits::default()));
+
+/// Replace the batch limits applied by the normalizer
+pub fn set_batch_limits(limits: BatchLimits) {
+    BATCH_LIMITS.store(Arc::new(limits));
+}
+
+/// Current batch limits applied by the normalizer
+pub fn batch_limits() -> BatchLimits {
+    **BATCH_LIMITS.load()
+}
+
+/// One element of a split batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchEntry {
+    /// A request or notification to send over the bridge
+    Call(Value),
+    /// An invalid element, answered at the edge with this error response
+    Rejected(Value),
+}
+
+/// Elements of `body` when it is a JSON-RPC batch
+///
+/// GraphQL batches, arrays of `{"query": ...}` objects, are left alone.
+pub fn parse_batch(body: &[u8]) -> Option<Vec<Value>> {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(items))
+            if items.is_empty() || !items.iter().all(|item| item.get("query").is_some()) =>
+        {
+            Some(items)
+        }
+        _ => None,
+    }
+}
+
+/// Split a batch into bridge calls and edge-rejected elements, in order
+///
+/// The batch as a whole is rejected when it is empty or longer than
+/// `limits.max_batch_size`; a single invalid element only rejects itself.
+pub fn split_batch(items: Vec<Value>, limits: &BatchLimits) -> ConversionResult<Vec<BatchEntry>> {
+    check_batch_size(items.len(), limits)?;
+    Ok(items.into_iter().map(split_entry).collect())
+}
+
+/// Reject an empty batch or one longer than `limits.max_batch_size`
+pub fn check_batch_size(len: usize, limits: &BatchLimits) -> ConversionResult<()> {
+    if len == 0 {
+        return Err(ConversionError::EmptyBatch);
+    }
+    if len > limits.max_batch_size {
+        return Err(ConversionError::BatchTooLarge {
+            actual: len,
+            limit: limits.max_batch_size,
+        });
+    }
+    Ok(())
+}
+
+fn split_entry(item: Value) -> BatchEntry {
+    let problem = match validate_json_rpc(&item) {
+        Err(e) => Some(e.to_string()),
+        Ok(()) if item.get("method").is_none() => Some("Missing method field".to_string()),
+        Ok(()) => None,
+    };
+
+    match problem {
+        None => BatchEntry::Call(item),
+        Some(reason) => {
+            let id = item.get("id").cloned();
+            BatchEntry::Rejected(create_error_response(
+                id,
+                INVALID_REQUEST_CODE,
+                "Invalid Request",
+                Some(Value::String(reason)),
+            ))
+        }
+    }
+}
+
+/// Single error response rejecting a whole batch
+pub fn batch_error_response(error: &ConversionError) -> Value {
+    error.to_jsonrpc_error(None)
+}
+
+/// Send each call over the bridge and collect the replies as one array
+///
+/// Calls are queued before any reply is awaited, so the bridge can work on
+/// them concurrently; replies are still returned in batch order. Returns
+/// `None` when nothing in the batch expects a reply.
+pub async fn dispatch_batch(
+    bridge_tx: &mpsc::Sender<BridgeMsg>,
+    ctx: &ProtocolContext,
+    entries: Vec<BatchEntry>,
+) -> Option<Value> {
+    enum Pending {
+        Reply(Option<Value>, oneshot::Receiver<Value>),
+        Ready(Option<Value>),
+    }
+
+    let mut pending = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match entry {
+            BatchEntry::Rejected(response) => pending.push(Pending::Ready(Some(response))),
+            BatchEntry::Call(request) => {
+                // Notifications carry no id and are never answered
+                let id = request.get("id").cloned();
+                let (tx, rx) = oneshot::channel();
+                match bridge_tx.send((request, ctx.clone(), tx)).await {
+                    Ok(()) => pending.push(Pending::Reply(id, rx)),
+                    Err(_) => {
+                        log::error!("MCP bridge closed while dispatching a batch");
+                        pending.push(Pending::Ready(id.map(bridge_unavailable)));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut responses = Vec::with_capacity(pending.len());
+    for entry in pending {
+        let response = match entry {
+            Pending::Ready(response) => response,
+            Pending::Reply(id, rx) => {
+                let reply = rx.await;
+                match id {
+                    None => None,
+                    Some(id) => Some(reply.unwrap_or_else(|_| bridge_unavailable(id))),
+                }
+            }
+        };
+        responses.extend(response);
+    }
+
+    if responses.is_empty() {
+        None
+    } else {
+        Some(Value::Array(responses))
+    }
+}
+
+fn bridge_unavailable(id: Value) -> Value {
+    create_error_response(
+        Some(id),
+        INTERNAL_ERROR_CODE,
+        "Internal error: bridge unavailable",
+        None,
+    )
+}
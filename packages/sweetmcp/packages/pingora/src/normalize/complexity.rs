@@ -0,0 +1,211 @@
+//! GraphQL query depth and complexity limiting
+//!
+//! This module scores parsed GraphQL documents before they are converted to
+//! JSON-RPC so that deeply nested or fan-out heavy queries are rejected at the
+//! edge instead of reaching the MCP bridge.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use async_graphql::parser::types::{ExecutableDocument, Selection, SelectionSet};
+use async_graphql_value::Value as GraphQLValue;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::types::{ConversionError, ConversionResult};
+
+/// Pagination arguments whose integer value multiplies the cost of a field's children
+const MULTIPLIER_ARGUMENTS: &[&str] = &["first", "last", "limit", "take"];
+
+/// Upper bound applied to any single pagination multiplier
+const MAX_MULTIPLIER: usize = 1000;
+
+/// Configurable limits applied to every GraphQL operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryLimits {
+    /// Maximum selection nesting depth
+    pub max_depth: usize,
+    /// Maximum complexity score
+    pub max_complexity: usize,
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 15,
+            max_complexity: 1000,
+        }
+    }
+}
+
+/// Measured cost of a GraphQL document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryCost {
+    /// Deepest selection nesting level
+    pub depth: usize,
+    /// Complexity score (one per field, multiplied by pagination arguments)
+    pub complexity: usize,
+}
+
+/// Process-wide limits used by the normalizer
+static QUERY_LIMITS: Lazy<ArcSwap<QueryLimits>> =
+    Lazy::new(|| ArcSwap::from_pointee(QueryLimits::default()));
+
+/// Replace the limits applied by the normalizer
+pub fn set_query_limits(limits: QueryLimits) {
+    QUERY_LIMITS.store(Arc::new(limits));
+}
+
+/// Current limits applied by the normalizer
+pub fn query_limits() -> QueryLimits {
+    **QUERY_LIMITS.load()
+}
+
+/// Compute depth and complexity across every operation in the document
+pub fn analyze_query(doc: &ExecutableDocument) -> QueryCost {
+    let mut cost = QueryCost::default();
+
+    // Fragment costs are depth-independent, so each fragment is scored once;
+    // re-scoring every spread is exponential in the number of fragments
+    let mut fragment_costs = HashMap::new();
+
+    for (_, operation) in doc.operations.iter() {
+        let mut scorer = Scorer {
+            doc,
+            visiting: HashSet::new(),
+            fragment_costs: &mut fragment_costs,
+        };
+        let op_cost = scorer.score_selection_set(&operation.node.selection_set.node, 1);
+        cost.depth = cost.depth.max(op_cost.depth);
+        cost.complexity = cost.complexity.saturating_add(op_cost.complexity);
+    }
+
+    cost
+}
+
+/// Score a document and reject it when it exceeds `limits`
+pub fn enforce_query_limits(doc: &ExecutableDocument, limits: &QueryLimits) -> ConversionResult<QueryCost> {
+    let cost = analyze_query(doc);
+
+    if cost.depth > limits.max_depth {
+        return Err(ConversionError::QueryLimitExceeded {
+            metric: "depth".to_string(),
+            actual: cost.depth,
+            limit: limits.max_depth,
+        });
+    }
+
+    if cost.complexity > limits.max_complexity {
+        return Err(ConversionError::QueryLimitExceeded {
+            metric: "complexity".to_string(),
+            actual: cost.complexity,
+            limit: limits.max_complexity,
+        });
+    }
+
+    Ok(cost)
+}
+
+/// Build a spec-shaped GraphQL error body for a limit violation
+pub fn limit_error_response(error: &ConversionError) -> Value {
+    match error {
+        ConversionError::QueryLimitExceeded { metric, actual, limit } => json!({
+            "data": null,
+            "errors": [{
+                "message": format!("Query {} of {} exceeds the maximum of {}", metric, actual, limit),
+                "extensions": {
+                    "code": "QUERY_TOO_COMPLEX",
+                    "metric": metric,
+                    "actual": actual,
+                    "limit": limit
+                }
+            }]
+        }),
+        other => json!({
+            "data": null,
+            "errors": [{ "message": other.to_string() }]
+        }),
+    }
+}
+
+/// Walks selection sets, caching each fragment's cost relative to its spread
+struct Scorer<'a> {
+    doc: &'a ExecutableDocument,
+    /// Fragments on the current spread path
+    visiting: HashSet<String>,
+    /// Fragment name -> (levels below the spread, complexity)
+    fragment_costs: &'a mut HashMap<String, QueryCost>,
+}
+
+impl Scorer<'_> {
+    fn score_selection_set(&mut self, selection_set: &SelectionSet, depth: usize) -> QueryCost {
+        let mut cost = QueryCost {
+            depth: if selection_set.items.is_empty() { depth - 1 } else { depth },
+            complexity: 0,
+        };
+
+        for selection in &selection_set.items {
+            let child = match &selection.node {
+                Selection::Field(field) => {
+                    let nested = self.score_selection_set(&field.node.selection_set.node, depth + 1);
+                    let multiplier = field
+                        .node
+                        .arguments
+                        .iter()
+                        .filter(|(name, _)| MULTIPLIER_ARGUMENTS.contains(&name.node.as_str()))
+                        .filter_map(|(_, value)| match &value.node {
+                            GraphQLValue::Number(n) => n.as_u64().map(|v| v as usize),
+                            _ => None,
+                        })
+                        .max()
+                        .unwrap_or(1)
+                        .clamp(1, MAX_MULTIPLIER);
+
+                    QueryCost {
+                        depth: nested.depth.max(depth),
+                        complexity: 1usize.saturating_add(nested.complexity.saturating_mul(multiplier)),
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.score_selection_set(&fragment.node.selection_set.node, depth)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.to_string();
+                    if let Some(relative) = self.fragment_costs.get(&name) {
+                        QueryCost {
+                            depth: depth + relative.depth,
+                            complexity: relative.complexity,
+                        }
+                    } else {
+                        // Cycles are reported by fragment resolution; just avoid infinite recursion here
+                        if !self.visiting.insert(name.clone()) {
+                            continue;
+                        }
+                        let scored = self
+                            .doc
+                            .fragments
+                            .get(&spread.node.fragment_name.node)
+                            .map(|fragment| self.score_selection_set(&fragment.node.selection_set.node, depth))
+                            .unwrap_or_default();
+                        self.visiting.remove(&name);
+                        self.fragment_costs.insert(
+                            name,
+                            QueryCost {
+                                depth: scored.depth.saturating_sub(depth),
+                                complexity: scored.complexity,
+                            },
+                        );
+                        scored
+                    }
+                }
+            };
+
+            cost.depth = cost.depth.max(child.depth);
+            cost.complexity = cost.complexity.saturating_add(child.complexity);
+        }
+
+        cost
+    }
+}
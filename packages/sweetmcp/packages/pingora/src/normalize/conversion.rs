@@ -52,7 +52,12 @@ pub fn detect_protocol(
 ) -> ConversionResult<ProtocolDetection> {
     // Try JSON-RPC first (most specific)
     if let Ok(v) = serde_json::from_slice::<Value>(body) {
-        if v.get("jsonrpc").is_some() {
+        // A batch is an array of JSON-RPC messages
+        let first = match &v {
+            Value::Array(items) => items.first().unwrap_or(&Value::Null),
+            single => single,
+        };
+        if first.get("jsonrpc").is_some() {
             return Ok(ProtocolDetection::new(
                 Proto::JsonRpc,
                 1.0,
@@ -166,6 +171,13 @@ fn detect_from_headers(header: &pingora::http::RequestHeader) -> Option<Protocol
 fn handle_json_rpc(body: &[u8], request_id: String) -> Result<(ProtocolContext, Value)> {
     let v = serde_json::from_slice::<Value>(body).context("Failed to parse JSON-RPC body")?;
 
+    // Batches pass through whole once they are within the size limit; the
+    // edge splits them into bridge messages
+    if let Value::Array(items) = &v {
+        super::batch::check_batch_size(items.len(), &super::batch::batch_limits())?;
+        return Ok((ProtocolContext::new(Proto::JsonRpc, request_id), v));
+    }
+
     // Validate it's proper JSON-RPC
     let _method = v
         .get("method")
@@ -276,8 +288,8 @@ fn is_capnp_binary(body: &[u8]) -> bool {
     }
 
     // Calculate minimum required message size
-    // Segment table size: segment_count.div_ceil(2) * 8 bytes (rounded up to word boundary)
-    let segment_table_words = segment_count.div_ceil(2);
+    // Segment table size: 4-byte count plus 4 bytes per segment, rounded up to a word
+    let segment_table_words = segment_count / 2 + 1;
     let segment_table_bytes = segment_table_words * 8;
 
     // Add first segment content size (in bytes, segments are measured in words)
@@ -293,9 +305,10 @@ fn is_capnp_binary(body: &[u8]) -> bool {
     if segment_count > 1 && segment_count <= 4 {
         // For small segment counts, validate the remaining segment lengths
         let mut total_segment_bytes = first_segment_bytes;
-        let mut byte_offset = 8; // Start after first segment length
 
         for i in 1..segment_count {
+            // Segment lengths follow the count back to back
+            let byte_offset = 4 + i * 4;
             if byte_offset + 4 > body.len() {
                 return false;
             }
@@ -316,13 +329,6 @@ fn is_capnp_binary(body: &[u8]) -> bool {
                 Some(total) => total,
                 None => return false, // Overflow protection
             };
-
-            byte_offset += 4;
-
-            // Skip padding byte if needed (segment table is word-aligned)
-            if i % 2 == 1 && i < segment_count - 1 {
-                byte_offset += 4; // Skip padding word
-            }
         }
 
         // Verify total message size matches expectations
@@ -8,6 +8,8 @@
 
 
 
+pub mod batch;
+pub mod complexity;
 pub mod conversion;
 pub mod parsers;
 pub mod schema_introspection;
@@ -15,6 +17,8 @@ pub mod types;
 
 
 // Re-export key types and functions for ergonomic usage
+pub use batch::{BatchEntry, BatchLimits, set_batch_limits};
+pub use complexity::{QueryCost, QueryLimits, set_query_limits};
 pub use conversion::{
     detect_protocol, from_json_rpc, to_json_rpc_with_headers,
 };
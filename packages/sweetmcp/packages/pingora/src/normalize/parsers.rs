@@ -51,6 +51,10 @@ pub fn graphql_to_json_rpc_with_schema(
     // Parse GraphQL query
     let doc = parse_query(query).map_err(|e| anyhow::anyhow!("GraphQL parse error: {}", e))?;
 
+    // Reject overly deep or expensive queries before any further work
+    let cost = super::complexity::enforce_query_limits(&doc, &super::complexity::query_limits())?;
+    debug!("GraphQL query cost: depth={}, complexity={}", cost.depth, cost.complexity);
+
     // Phase 1: Collect all fragment definitions
     let mut fragment_registry = FragmentRegistry::new();
     for (name, fragment) in &doc.fragments {
@@ -1115,8 +1119,8 @@ pub fn validate_capnp_format(body: &[u8]) -> ConversionResult<()> {
         )));
     }
 
-    // Calculate segment table size (each segment length is 4 bytes, padded to 8-byte boundary)
-    let segment_table_words = segment_count.div_ceil(2); // Round up to word boundary
+    // Calculate segment table size (4-byte count plus 4 bytes per segment, padded to 8-byte boundary)
+    let segment_table_words = segment_count / 2 + 1;
     let segment_table_bytes = segment_table_words * 8;
 
     if body.len() < segment_table_bytes {
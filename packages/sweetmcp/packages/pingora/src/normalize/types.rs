@@ -386,6 +386,19 @@ pub enum ConversionError {
 
     #[error("Fragment validation failed: {0}")]
     FragmentValidationError(String),
+
+    #[error("GraphQL query {metric} {actual} exceeds limit {limit}")]
+    QueryLimitExceeded {
+        metric: String,
+        actual: usize,
+        limit: usize,
+    },
+
+    #[error("JSON-RPC batch is empty")]
+    EmptyBatch,
+
+    #[error("JSON-RPC batch of {actual} messages exceeds limit {limit}")]
+    BatchTooLarge { actual: usize, limit: usize },
 }
 
 impl ConversionError {
@@ -405,6 +418,9 @@ impl ConversionError {
             ConversionError::CircularFragmentDependency { .. } => false,
             ConversionError::TypeConditionError(_) => true,
             ConversionError::FragmentValidationError(_) => true,
+            ConversionError::QueryLimitExceeded { .. } => false,
+            ConversionError::EmptyBatch => false,
+            ConversionError::BatchTooLarge { .. } => false,
         }
     }
 
@@ -424,6 +440,9 @@ impl ConversionError {
             ConversionError::CircularFragmentDependency { .. } => ErrorSeverity::Error,
             ConversionError::TypeConditionError(_) => ErrorSeverity::Warning,
             ConversionError::FragmentValidationError(_) => ErrorSeverity::Warning,
+            ConversionError::QueryLimitExceeded { .. } => ErrorSeverity::Warning,
+            ConversionError::EmptyBatch => ErrorSeverity::Info,
+            ConversionError::BatchTooLarge { .. } => ErrorSeverity::Warning,
         }
     }
 
@@ -457,6 +476,15 @@ impl ConversionError {
             ConversionError::FragmentValidationError(msg) => {
                 (-32602, format!("Fragment validation failed: {}", msg))
             }
+            ConversionError::QueryLimitExceeded { metric, actual, limit } => (
+                -32602,
+                format!("Invalid params: query {} {} exceeds limit {}", metric, actual, limit),
+            ),
+            ConversionError::EmptyBatch => (-32600, "Invalid Request: empty batch".to_string()),
+            ConversionError::BatchTooLarge { actual, limit } => (
+                -32600,
+                format!("Invalid Request: batch of {} exceeds limit {}", actual, limit),
+            ),
         };
 
         serde_json::json!({
@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use arc_swap::ArcSwapOption;
 use dashmap::DashMap;
 use prometheus::core::{Atomic, AtomicF64};
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,9 @@ use super::algorithms::{AlgorithmState, RateLimiter};
 use super::algorithms::RateLimitAlgorithmConfig;
 use super::limiter::{SlidingWindowConfig, TokenBucketConfig};
 
+/// Limiter key shared by every endpoint that falls back to the default limit
+const DEFAULT_ENDPOINT: &str = "*";
+
 /// Endpoint-specific rate limiting configuration with advanced settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointRateConfig {
@@ -41,6 +45,8 @@ pub struct EndpointRateConfig {
 pub struct DistributedRateLimitManager {
     /// Per-endpoint rate limiting configurations
     endpoint_configs: Arc<DashMap<String, EndpointRateConfig>>,
+    /// Limit for endpoints without their own configuration, shared across them
+    default_config: Arc<ArcSwapOption<EndpointRateConfig>>,
     /// Per-endpoint rate limiters with zero allocation lookup
     endpoint_limiters: Arc<DashMap<String, RateLimiter>>,
     /// Per-peer rate limiters organized by endpoint with optimized nested access
@@ -111,6 +117,7 @@ impl DistributedRateLimitManager {
 
         Self {
             endpoint_configs: Arc::new(endpoint_configs),
+            default_config: Arc::new(ArcSwapOption::empty()),
             endpoint_limiters: Arc::new(DashMap::new()),
             peer_limiters: Arc::new(DashMap::new()),
             load_multiplier: Arc::new(AtomicF64::new(1.0)),
@@ -120,15 +127,20 @@ impl DistributedRateLimitManager {
 
     /// Check if request should be allowed with advanced distributed coordination
     pub fn check_request(&self, endpoint: &str, peer_ip: Option<&str>, tokens: u32) -> bool {
-        let config = match self.endpoint_configs.get(endpoint) {
-            Some(config) => config.clone(),
-            None => {
-                debug!(
-                    "No rate limit config for endpoint {}, allowing request",
-                    endpoint
-                );
-                return true;
-            }
+        let (endpoint, config) = match self.endpoint_configs.get(endpoint) {
+            Some(config) => (endpoint, config.clone()),
+            None => match self.default_config.load_full() {
+                // One limiter for all unconfigured endpoints, so arbitrary
+                // paths can't each mint a fresh bucket
+                Some(config) => (DEFAULT_ENDPOINT, (*config).clone()),
+                None => {
+                    debug!(
+                        "No rate limit config for endpoint {}, allowing request",
+                        endpoint
+                    );
+                    return true;
+                }
+            },
         };
 
         // Apply load-based adjustment using lock-free atomic operation
@@ -284,20 +296,42 @@ impl DistributedRateLimitManager {
     pub fn configure_endpoint(&self, endpoint: String, config: EndpointRateConfig) {
         self.endpoint_configs
             .insert(endpoint.clone(), config.clone());
+        self.update_limiters(&endpoint, &config);
+
+        info!("Updated rate limit configuration for endpoint {}", endpoint);
+    }
 
+    /// Set the limit for endpoints without their own configuration
+    ///
+    /// `None` lets requests to those endpoints through unlimited.
+    pub fn set_default_config(&self, config: Option<EndpointRateConfig>) {
+        match &config {
+            Some(config) => {
+                self.update_limiters(DEFAULT_ENDPOINT, config);
+                info!("Updated default rate limit configuration");
+            }
+            None => {
+                self.endpoint_limiters.remove(DEFAULT_ENDPOINT);
+                self.peer_limiters.remove(DEFAULT_ENDPOINT);
+                info!("Removed default rate limit configuration");
+            }
+        }
+        self.default_config.store(config.map(Arc::new));
+    }
+
+    /// Apply a changed configuration to the live limiters of `endpoint`
+    fn update_limiters(&self, endpoint: &str, config: &EndpointRateConfig) {
         // Update existing limiter if it exists
-        if let Some(mut entry) = self.endpoint_limiters.get_mut(&endpoint) {
+        if let Some(mut entry) = self.endpoint_limiters.get_mut(endpoint) {
             entry.update_config(&config.algorithm);
         }
 
         // Update peer limiters for this endpoint
-        if let Some(endpoint_limiters) = self.peer_limiters.get(&endpoint) {
+        if let Some(endpoint_limiters) = self.peer_limiters.get(endpoint) {
             for mut entry in endpoint_limiters.iter_mut() {
                 entry.update_config(&config.algorithm);
             }
         }
-
-        info!("Updated rate limit configuration for endpoint {}", endpoint);
     }
 
     /// Get current rate limit statistics using lock-free operations
@@ -0,0 +1,243 @@
+//! Configuration hot reload
+//!
+//! On SIGHUP the configuration is loaded again from the same file and
+//! environment. Rate limits, static upstreams, GraphQL limits and the log
+//! level take effect immediately; every other changed setting is reported as
+//! needing a restart and keeps its running value. An invalid configuration
+//! is rejected as a whole and the running one stays in place.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use log::{LevelFilter, error, info, warn};
+use pingora_load_balancing::Backend;
+
+use crate::config::Config;
+use crate::rate_limit::DistributedRateLimitManager;
+
+/// Initialize logging, returning the level used when `log_level` is unset
+///
+/// When `RUST_LOG` is set it keeps full control of filtering and `None` is
+/// returned. Otherwise the logger passes every record and the global max
+/// level does the filtering, so `log_level` can change at runtime.
+pub fn init_logging() -> Option<LevelFilter> {
+    if std::env::var_os("RUST_LOG").is_some() {
+        env_logger::init();
+        return None;
+    }
+    env_logger::Builder::new()
+        .filter_level(LevelFilter::Trace)
+        .init();
+    // env_logger's own default without RUST_LOG
+    log::set_max_level(LevelFilter::Error);
+    Some(LevelFilter::Error)
+}
+
+/// Live state that reloadable settings are applied to
+pub struct ReloadTargets {
+    /// Limiter receiving the `rate_limit` default
+    pub rate_limiter: Option<Arc<DistributedRateLimitManager>>,
+    /// Static upstreams merged with discovered peers by the backend updater
+    pub static_upstreams: Arc<ArcSwap<Vec<Backend>>>,
+    /// Upstream URLs consulted for TLS and SNI
+    pub upstream_urls: Arc<ArcSwap<HashMap<SocketAddr, String>>>,
+}
+
+/// Result of a reload
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReloadOutcome {
+    /// Changed settings now in effect
+    pub applied: Vec<&'static str>,
+    /// Changed settings that only take effect after a restart
+    pub restart_required: Vec<&'static str>,
+}
+
+/// Settings that can be applied to a running server
+const RELOADABLE: &[&str] = &[
+    "upstreams",
+    "rate_limit",
+    "graphql_limits",
+    "jsonrpc_batch",
+    "log_level",
+];
+
+/// Compare two configurations setting by setting
+pub fn diff(old: &Config, new: &Config) -> ReloadOutcome {
+    let mut outcome = ReloadOutcome::default();
+    let mut record = |name: &'static str, changed: bool| {
+        if !changed {
+            return;
+        }
+        if RELOADABLE.contains(&name) {
+            outcome.applied.push(name);
+        } else {
+            outcome.restart_required.push(name);
+        }
+    };
+
+    macro_rules! compare {
+        ($($field:ident).+) => {
+            record(
+                stringify!($($field).+),
+                format!("{:?}", old.$($field).+) != format!("{:?}", new.$($field).+),
+            )
+        };
+    }
+
+    compare!(upstreams);
+    compare!(rate_limit);
+    compare!(graphql_limits);
+    compare!(jsonrpc_batch);
+    compare!(log_level);
+    compare!(inflight_max);
+    compare!(tcp_bind);
+    compare!(mcp_bind);
+    compare!(uds_path);
+    compare!(workers);
+    compare!(metrics_bind);
+    compare!(jwt_expiry);
+    compare!(health_check_interval);
+    compare!(circuit_breaker_threshold);
+    compare!(request_timeout);
+    compare!(drain_timeout);
+    compare!(bridge_queue);
+    compare!(tool_list_cache);
+    compare!(idempotency);
+    compare!(result_streaming);
+    compare!(session_affinity);
+    compare!(request_transforms);
+    compare!(client_tokens);
+    compare!(tenants);
+    compare!(plugin_host_url);
+    compare!(mcp_upstreams);
+    compare!(auth.discovery_token);
+    compare!(auth.require_https);
+    compare!(auth.max_auth_attempts_per_minute);
+    // The JWT secret is left out: development mode generates a fresh one on
+    // every load, and rotating it live would invalidate issued tokens
+
+    outcome
+}
+
+/// Reloads the configuration and applies reloadable settings
+pub struct ConfigReloader {
+    path: Option<PathBuf>,
+    current: ArcSwap<Config>,
+    targets: ReloadTargets,
+    default_log_level: Option<LevelFilter>,
+}
+
+impl ConfigReloader {
+    /// Create a reloader for the configuration loaded from `path`
+    ///
+    /// `default_log_level` is the value returned by [`init_logging`].
+    pub fn new(
+        path: Option<PathBuf>,
+        config: Arc<Config>,
+        targets: ReloadTargets,
+        default_log_level: Option<LevelFilter>,
+    ) -> Self {
+        Self {
+            path,
+            current: ArcSwap::new(config),
+            targets,
+            default_log_level,
+        }
+    }
+
+    /// Configuration currently in effect
+    #[allow(dead_code)]
+    pub fn current(&self) -> Arc<Config> {
+        self.current.load_full()
+    }
+
+    /// Apply the reloadable settings of the current configuration
+    pub fn apply_current(&self) {
+        self.apply(&self.current.load());
+    }
+
+    fn apply(&self, config: &Config) {
+        if let Some(default_level) = self.default_log_level {
+            log::set_max_level(config.log_level.unwrap_or(default_level));
+        } else if config.log_level.is_some() {
+            warn!("log_level is ignored while RUST_LOG is set");
+        }
+
+        crate::normalize::set_query_limits(config.graphql_limits);
+        crate::normalize::set_batch_limits(config.jsonrpc_batch);
+
+        if let Some(rate_limiter) = &self.targets.rate_limiter {
+            rate_limiter.set_default_config(Some(config.rate_limit.endpoint_config()));
+        }
+
+        let (backends, url_map) = crate::edge::core::service::parse_upstreams(&config.upstreams);
+        self.targets
+            .static_upstreams
+            .store(Arc::new(backends.into_iter().collect()));
+        self.targets.upstream_urls.store(Arc::new(url_map));
+    }
+
+    /// Load the configuration again and apply what changed
+    ///
+    /// Settings that need a restart keep their running values, so they are
+    /// reported again on the next reload until the server restarts.
+    pub fn reload(&self) -> Result<ReloadOutcome> {
+        let loaded = Config::load(self.path.as_deref())?;
+        let current = self.current.load_full();
+        let outcome = diff(&current, &loaded);
+
+        let mut next = (*current).clone();
+        next.upstreams = loaded.upstreams;
+        next.rate_limit = loaded.rate_limit;
+        next.graphql_limits = loaded.graphql_limits;
+        next.jsonrpc_batch = loaded.jsonrpc_batch;
+        next.log_level = loaded.log_level;
+
+        self.apply(&next);
+        self.current.store(Arc::new(next));
+        Ok(outcome)
+    }
+
+    /// Reload on every SIGHUP until the process exits
+    pub async fn listen_for_sighup(&self) {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sighup = match signal(SignalKind::hangup()) {
+            Ok(signal) => signal,
+            Err(e) => {
+                error!("Failed to register SIGHUP handler: {}", e);
+                return;
+            }
+        };
+
+        while sighup.recv().await.is_some() {
+            info!("Received SIGHUP, reloading configuration");
+            match self.reload() {
+                Ok(outcome) => {
+                    if outcome.applied.is_empty() {
+                        info!("Configuration reloaded, no reloadable settings changed");
+                    } else {
+                        info!(
+                            "Configuration reloaded, applied: {}",
+                            outcome.applied.join(", ")
+                        );
+                    }
+                    if !outcome.restart_required.is_empty() {
+                        warn!(
+                            "Changed settings need a restart to take effect: {}",
+                            outcome.restart_required.join(", ")
+                        );
+                    }
+                }
+                Err(e) => error!(
+                    "Configuration reload rejected, keeping current settings: {:#}",
+                    e
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Sticky routing for stateful plugins
+//!
+//! Plugins that keep per-session state, such as an open browser or a REPL,
+//! only work when every request of a session reaches the same peer. Requests
+//! are keyed by their MCP session, taken from the `Mcp-Session-Id` header or,
+//! for clients that track sessions themselves, from a session argument of a
+//! `tools/call` request. The key is routed through the
+//! [`MetricPicker`](crate::metric_picker::MetricPicker)'s consistent-hash
+//! ring, so a session stays on its peer while the peer set is unchanged and
+//! moves to the next peer on the ring when its peer fails.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// MCP Streamable HTTP session header
+pub const SESSION_ID_HEADER: &str = "Mcp-Session-Id";
+
+/// Session header used by older MCP clients
+pub const LEGACY_SESSION_ID_HEADER: &str = "x-mcp-session-id";
+
+/// Largest request body read ahead of routing to find a session argument
+///
+/// Matches the body pingora buffers for retries, so a body read ahead can
+/// still be replayed to a failover peer.
+pub const MAX_PEEK_BYTES: usize = 64 * 1024;
+
+/// Longest session key used for routing; longer values are ignored
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Session affinity settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionAffinityConfig {
+    /// Route requests of one session to the same peer
+    pub enabled: bool,
+    /// `tools/call` arguments that carry a session, checked in order
+    pub arguments: Vec<String>,
+}
+
+impl Default for SessionAffinityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            arguments: vec!["session_id".to_string(), "sessionId".to_string()],
+        }
+    }
+}
+
+/// Where a session key came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSource {
+    /// The MCP session header
+    Header,
+    /// A `tools/call` argument
+    Argument,
+}
+
+/// Routing key of one session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionKey {
+    pub source: SessionSource,
+    pub value: String,
+}
+
+impl SessionKey {
+    /// Key hashed onto the ring
+    ///
+    /// Header and argument sessions are hashed apart so an argument can't
+    /// be chosen to land on another client's session.
+    pub fn ring_key(&self) -> String {
+        match self.source {
+            SessionSource::Header => format!("header:{}", self.value),
+            SessionSource::Argument => format!("argument:{}", self.value),
+        }
+    }
+}
+
+/// Session key from an MCP session header value
+pub fn from_header(value: &str) -> Option<SessionKey> {
+    usable(value).map(|value| SessionKey {
+        source: SessionSource::Header,
+        value,
+    })
+}
+
+/// Session key from the first matching argument of a `tools/call` request
+///
+/// String and integer arguments are used; other bodies give `None`.
+pub fn from_tool_arguments(body: &[u8], arguments: &[String]) -> Option<SessionKey> {
+    if arguments.is_empty() {
+        return None;
+    }
+    let request: Value = serde_json::from_slice(body).ok()?;
+    if request.get("method")?.as_str()? != "tools/call" {
+        return None;
+    }
+    let call_arguments = request.get("params")?.get("arguments")?.as_object()?;
+    arguments.iter().find_map(|name| {
+        let value = match call_arguments.get(name)? {
+            Value::String(value) => value.clone(),
+            Value::Number(value) if value.is_i64() || value.is_u64() => value.to_string(),
+            _ => return None,
+        };
+        usable(&value).map(|value| SessionKey {
+            source: SessionSource::Argument,
+            value,
+        })
+    })
+}
+
+/// Whether a request body is worth reading ahead for a session argument
+pub fn should_peek(content_length: Option<usize>, content_type: Option<&str>) -> bool {
+    let json = content_type.is_none_or(|content_type| content_type.contains("json"));
+    json && matches!(content_length, Some(len) if len > 0 && len <= MAX_PEEK_BYTES)
+}
+
+fn usable(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty() && value.len() <= MAX_KEY_LEN).then(|| value.to_string())
+}
@@ -12,8 +12,8 @@
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
@@ -26,6 +26,7 @@ use tokio::time::{sleep, timeout};
 use log::{debug, error, info, warn};
 
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const DRAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
 const STATE_FILE: &str = "sweetmcp_state.json";
 const MDNS_MULTICAST_ADDR: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
 const MDNS_PORT: u16 = 5353;
@@ -48,6 +49,56 @@ pub struct ShutdownCoordinator {
     local_port: u16,
     /// Peer registry reference
     peer_registry: Option<crate::peer_discovery::PeerRegistry>,
+    /// Number of bridge messages accepted but not yet answered
+    bridge_in_flight: Arc<AtomicU64>,
+    /// Deadline for draining in-flight work before exiting
+    drain_timeout: Duration,
+    /// Current drain phase (see `DrainPhase`)
+    drain_phase: Arc<AtomicU8>,
+    /// When draining started
+    drain_started: Arc<OnceLock<Instant>>,
+}
+
+/// Phase of the shutdown drain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainPhase {
+    /// Accepting new sessions normally
+    Running = 0,
+    /// Rejecting new sessions while in-flight work completes
+    Draining = 1,
+    /// All in-flight work completed before the deadline
+    Drained = 2,
+    /// Deadline reached with work still in flight
+    TimedOut = 3,
+}
+
+impl DrainPhase {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Draining,
+            2 => Self::Drained,
+            3 => Self::TimedOut,
+            _ => Self::Running,
+        }
+    }
+}
+
+/// Snapshot of drain progress exposed through the admin API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainStatus {
+    /// Current drain phase
+    pub phase: DrainPhase,
+    /// Proxied requests still in flight
+    pub active_requests: u64,
+    /// Bridge messages still awaiting a response
+    pub in_flight_bridge_messages: u64,
+    /// Milliseconds since draining started
+    pub elapsed_ms: Option<u64>,
+    /// Milliseconds left before the drain deadline
+    pub remaining_ms: Option<u64>,
+    /// Configured drain deadline in milliseconds
+    pub drain_timeout_ms: u64,
 }
 
 /// Server state to preserve across restarts
@@ -89,9 +140,18 @@ impl ShutdownCoordinator {
             data_dir,
             local_port: 8443, // Default, should be set via set_local_port
             peer_registry: None,
+            bridge_in_flight: Arc::new(AtomicU64::new(0)),
+            drain_timeout: SHUTDOWN_TIMEOUT,
+            drain_phase: Arc::new(AtomicU8::new(DrainPhase::Running as u8)),
+            drain_started: Arc::new(OnceLock::new()),
         }
     }
 
+    /// Set the deadline for draining in-flight work during shutdown
+    pub fn set_drain_timeout(&mut self, drain_timeout: Duration) {
+        self.drain_timeout = drain_timeout;
+    }
+
     /// Set the local service port
     pub fn set_local_port(&mut self, port: u16) {
         self.local_port = port;
@@ -133,6 +193,40 @@ impl ShutdownCoordinator {
         self.active_requests.load(Ordering::SeqCst)
     }
 
+    /// Track a bridge message; always counted because it was already accepted
+    pub fn bridge_message_start(&self) -> RequestGuard {
+        self.bridge_in_flight.fetch_add(1, Ordering::SeqCst);
+        RequestGuard {
+            counter: self.bridge_in_flight.clone(),
+            active: true,
+        }
+    }
+
+    /// Get number of bridge messages awaiting a response
+    pub fn bridge_in_flight_count(&self) -> u64 {
+        self.bridge_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Current drain phase
+    pub fn drain_phase(&self) -> DrainPhase {
+        DrainPhase::from_u8(self.drain_phase.load(Ordering::SeqCst))
+    }
+
+    /// Snapshot of the current drain progress
+    pub fn drain_status(&self) -> DrainStatus {
+        let elapsed = self.drain_started.get().map(|started| started.elapsed());
+
+        DrainStatus {
+            phase: self.drain_phase(),
+            active_requests: self.active_request_count(),
+            in_flight_bridge_messages: self.bridge_in_flight_count(),
+            elapsed_ms: elapsed.map(|e| e.as_millis() as u64),
+            remaining_ms: elapsed
+                .map(|e| self.drain_timeout.saturating_sub(e).as_millis() as u64),
+            drain_timeout_ms: self.drain_timeout.as_millis() as u64,
+        }
+    }
+
     /// Update state to preserve
     pub async fn update_state<F>(&self, updater: F)
     where
@@ -214,6 +308,9 @@ impl ShutdownCoordinator {
             }
 
             self.initiate_shutdown().await;
+
+            info!("Drain finished ({:?}), exiting", self.drain_phase());
+            std::process::exit(0);
         });
     }
 
@@ -228,26 +325,36 @@ impl ShutdownCoordinator {
         let shutdown_start = Instant::now();
 
         // Step 1: Stop accepting new requests
+        let _ = self.drain_started.set(shutdown_start);
+        self.drain_phase
+            .store(DrainPhase::Draining as u8, Ordering::SeqCst);
         let _ = self.shutdown_tx.send(());
-        info!("Stopped accepting new requests");
+        info!(
+            "Stopped accepting new requests, draining for up to {:?}",
+            self.drain_timeout
+        );
 
         // Step 2: Deregister from discovery
         if let Err(e) = self.deregister_from_discovery().await {
             error!("Failed to deregister from discovery: {}", e);
         }
 
-        // Step 3: Wait for active requests to complete
-        let drain_result = timeout(SHUTDOWN_TIMEOUT, self.drain_connections()).await;
+        // Step 3: Wait for active requests and bridge messages to complete
+        let drain_result = timeout(self.drain_timeout, self.drain_connections()).await;
 
         match drain_result {
             Ok(_) => {
+                self.drain_phase
+                    .store(DrainPhase::Drained as u8, Ordering::SeqCst);
                 info!("All connections drained in {:?}", shutdown_start.elapsed());
             }
             Err(_) => {
-                let remaining = self.active_request_count();
+                self.drain_phase
+                    .store(DrainPhase::TimedOut as u8, Ordering::SeqCst);
                 warn!(
-                    "Shutdown timeout reached, {} requests still active",
-                    remaining
+                    "Drain deadline reached, {} requests and {} bridge messages still active",
+                    self.active_request_count(),
+                    self.bridge_in_flight_count()
                 );
             }
         }
@@ -459,18 +566,23 @@ impl ShutdownCoordinator {
         Ok(())
     }
 
-    /// Wait for all active connections to drain
+    /// Wait for all active connections and bridge messages to drain
     async fn drain_connections(&self) {
         let check_interval = Duration::from_millis(100);
-        let mut last_count = self.active_request_count();
+        let mut last_progress = Instant::now();
 
-        while self.active_request_count() > 0 {
+        while self.active_request_count() > 0 || self.bridge_in_flight_count() > 0 {
             sleep(check_interval).await;
 
-            let current_count = self.active_request_count();
-            if current_count != last_count {
-                info!("Waiting for {} active requests to complete", current_count);
-                last_count = current_count;
+            if last_progress.elapsed() >= DRAIN_PROGRESS_INTERVAL {
+                let status = self.drain_status();
+                info!(
+                    "Draining: {} active requests, {} bridge messages, {}ms until deadline",
+                    status.active_requests,
+                    status.in_flight_bridge_messages,
+                    status.remaining_ms.unwrap_or(0)
+                );
+                last_progress = Instant::now();
             }
         }
     }
@@ -0,0 +1,176 @@
+//! Streaming of large tool results to the client
+//!
+//! Responses proxied from a peer that need no protocol conversion are passed
+//! on chunk by chunk as the peer produces them. Responses answered at the
+//! edge from the MCP bridge are written with chunked transfer encoding once
+//! their content is large: the JSON-RPC envelope first, then each item of
+//! `result.content` (or each element of a batch) serialized on its own and
+//! cut into slices of at most `chunk_bytes`. The client sees the first bytes
+//! without waiting for the whole body, and the gateway never holds more than
+//! one serialized item at a time.
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Result streaming settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultStreamingConfig {
+    /// Stream large results instead of buffering them
+    pub enabled: bool,
+    /// Content size above which a bridge response is streamed
+    pub threshold_bytes: usize,
+    /// Largest body chunk written at once
+    pub chunk_bytes: usize,
+}
+
+impl Default for ResultStreamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_bytes: 64 * 1024,
+            chunk_bytes: 16 * 1024,
+        }
+    }
+}
+
+impl ResultStreamingConfig {
+    /// Whether `response` is large enough to be streamed
+    pub fn should_stream(&self, response: &Value) -> bool {
+        self.enabled && content_size(response) > self.threshold_bytes
+    }
+}
+
+/// Approximate size of the content carried by a response or batch
+///
+/// Counts the text and base64 payloads of `result.content` items, which
+/// dominate large results such as screenshots and crawls.
+pub fn content_size(response: &Value) -> usize {
+    if let Value::Array(responses) = response {
+        return responses.iter().map(content_size).sum();
+    }
+    response
+        .pointer("/result/content")
+        .and_then(Value::as_array)
+        .map_or(0, |items| items.iter().map(item_size).sum())
+}
+
+fn item_size(item: &Value) -> usize {
+    let payload = |value: &Value| {
+        ["text", "data", "blob"]
+            .iter()
+            .filter_map(|key| value.get(*key).and_then(Value::as_str))
+            .map(str::len)
+            .sum::<usize>()
+    };
+    payload(item) + item.get("resource").map_or(0, payload)
+}
+
+/// Serialize a response piece by piece, in chunks of at most `chunk_bytes`
+///
+/// The concatenated chunks are the JSON of `response`, with object keys in
+/// the order the value stores them.
+pub fn response_chunks(response: &Value, chunk_bytes: usize) -> ResponseChunks<'_> {
+    let chunk_bytes = chunk_bytes.max(1);
+    match response {
+        Value::Array(responses) => ResponseChunks {
+            head: Some(Bytes::from_static(b"[")),
+            items: responses.iter(),
+            first: true,
+            tail: Some(Bytes::from_static(b"]")),
+            pending: Bytes::new(),
+            chunk_bytes,
+        },
+        Value::Object(fields) => match fields
+            .get("result")
+            .and_then(Value::as_object)
+            .filter(|result| result.get("content").is_some_and(Value::is_array))
+        {
+            Some(result) => {
+                let mut head = vec![b'{'];
+                for (key, value) in fields.iter().filter(|(key, _)| *key != "result") {
+                    write_field(&mut head, key, value);
+                }
+                head.extend_from_slice(b"\"result\":{");
+                for (key, value) in result.iter().filter(|(key, _)| *key != "content") {
+                    write_field(&mut head, key, value);
+                }
+                head.extend_from_slice(b"\"content\":[");
+                let items = result
+                    .get("content")
+                    .and_then(Value::as_array)
+                    .map_or_else(Default::default, |items| items.iter());
+                ResponseChunks {
+                    head: Some(Bytes::from(head)),
+                    items,
+                    first: true,
+                    tail: Some(Bytes::from_static(b"]}}")),
+                    pending: Bytes::new(),
+                    chunk_bytes,
+                }
+            }
+            None => ResponseChunks::whole(response, chunk_bytes),
+        },
+        _ => ResponseChunks::whole(response, chunk_bytes),
+    }
+}
+
+/// Write `"key":value,` to `buf`
+fn write_field(buf: &mut Vec<u8>, key: &str, value: &Value) {
+    // Serializing a string or a `Value` into a Vec cannot fail
+    let _ = serde_json::to_writer(&mut *buf, key);
+    buf.push(b':');
+    let _ = serde_json::to_writer(&mut *buf, value);
+    buf.push(b',');
+}
+
+/// Chunks of a serialized response, see [`response_chunks`]
+pub struct ResponseChunks<'a> {
+    head: Option<Bytes>,
+    items: std::slice::Iter<'a, Value>,
+    first: bool,
+    tail: Option<Bytes>,
+    pending: Bytes,
+    chunk_bytes: usize,
+}
+
+impl ResponseChunks<'_> {
+    /// A response without items to stream, serialized at once
+    fn whole(response: &Value, chunk_bytes: usize) -> Self {
+        Self {
+            head: Some(Bytes::from(
+                serde_json::to_vec(response).unwrap_or_default(),
+            )),
+            items: Default::default(),
+            first: true,
+            tail: None,
+            pending: Bytes::new(),
+            chunk_bytes,
+        }
+    }
+}
+
+impl Iterator for ResponseChunks<'_> {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Bytes> {
+        loop {
+            if !self.pending.is_empty() {
+                let len = self.chunk_bytes.min(self.pending.len());
+                return Some(self.pending.split_to(len));
+            }
+            if let Some(head) = self.head.take() {
+                self.pending = head;
+            } else if let Some(item) = self.items.next() {
+                let mut buf = if self.first { Vec::new() } else { vec![b','] };
+                self.first = false;
+                let _ = serde_json::to_writer(&mut buf, item);
+                self.pending = Bytes::from(buf);
+            } else if let Some(tail) = self.tail.take() {
+                self.pending = tail;
+            } else {
+                return None;
+            }
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Tenant namespaces sharing one gateway
+//!
+//! With tenancy enabled every request must belong to a tenant: JWTs carry it
+//! in the `tenant` claim and client tokens are issued for one. A tenant names
+//! the tools, local plugin tools or namespaced upstream tools, its requests
+//! may call and see in `tools/list`, and the requests per minute all of its
+//! clients share. Tenants are managed through the admin API and kept in a
+//! small JSON file that is replaced atomically on each change.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::client_tokens::{
+    first_denied_tool, matches_tool, tool_patterns_problem, unix_now, write_atomically,
+};
+
+/// JWT claim naming the tenant a request belongs to
+pub const TENANT_CLAIM: &str = "tenant";
+
+/// Longest tenant id
+pub const MAX_TENANT_ID_LEN: usize = 64;
+
+/// Tenancy settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantsConfig {
+    /// Require a tenant on every request and serve the tenant admin endpoints
+    pub enabled: bool,
+    /// JSON file the tenant store is kept in
+    pub store_path: PathBuf,
+    /// Requests per minute of a tenant created without `rpm`
+    pub default_rpm: u32,
+}
+
+impl Default for TenantsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            store_path: default_store_path(),
+            default_rpm: 6000,
+        }
+    }
+}
+
+/// `tenants.json` next to the TLS certificate directory
+pub fn default_store_path() -> PathBuf {
+    crate::get_cert_dir().with_file_name("tenants.json")
+}
+
+/// A tenant's scope and quota, as listed by the admin API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tenant {
+    pub id: String,
+    /// Team the tenant belongs to
+    pub name: String,
+    /// Tool names, or prefixes ending in `*`, the tenant may call and list
+    pub allowed_tools: Vec<String>,
+    /// Requests per minute shared by all of the tenant's clients;
+    /// `tenants.default_rpm` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpm: Option<u32>,
+    /// Unix seconds
+    pub created_at: u64,
+    /// Unix seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspended_at: Option<u64>,
+}
+
+impl Tenant {
+    /// Whether the tenant may call `tool`
+    pub fn allows_tool(&self, tool: &str) -> bool {
+        matches_tool(&self.allowed_tools, tool)
+    }
+
+    /// Whether the tenant's requests are accepted
+    pub fn is_active(&self) -> bool {
+        self.suspended_at.is_none()
+    }
+
+    /// First `tools/call` in a JSON-RPC message or batch the tenant may not make
+    pub fn first_denied_tool(&self, body: &[u8]) -> Option<String> {
+        first_denied_tool(body, |tool| self.allows_tool(tool))
+    }
+
+    /// Drop the tools the tenant may not call from `tools/list` results
+    ///
+    /// `response` is a JSON-RPC response or a batch of them; other results
+    /// are left alone.
+    pub fn filter_tool_lists(&self, response: &mut Value) {
+        let responses: Vec<&mut Value> = match response {
+            Value::Array(batch) => batch.iter_mut().collect(),
+            response => vec![response],
+        };
+        for response in responses {
+            if let Some(tools) = response
+                .pointer_mut("/result/tools")
+                .and_then(Value::as_array_mut)
+            {
+                tools.retain(|tool| {
+                    tool.get("name")
+                        .and_then(Value::as_str)
+                        .is_some_and(|name| self.allows_tool(name))
+                });
+            }
+        }
+    }
+}
+
+/// Body of a tenant create request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTenantRequest {
+    pub id: String,
+    pub name: String,
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub rpm: Option<u32>,
+}
+
+impl CreateTenantRequest {
+    /// What is wrong with the request, if anything
+    pub fn problem(&self) -> Option<String> {
+        if !is_valid_tenant_id(&self.id) {
+            return Some(format!(
+                "id must be 1 to {} lowercase letters, digits, `-` or `_`",
+                MAX_TENANT_ID_LEN
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Some("name must not be empty".to_string());
+        }
+        if self.rpm == Some(0) {
+            return Some("rpm must be greater than 0".to_string());
+        }
+        tool_patterns_problem(&self.allowed_tools)
+    }
+}
+
+/// Body of a tenant update request; fields left out are unchanged
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateTenantRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    pub rpm: Option<u32>,
+    /// Suspend (`true`) or reinstate (`false`) the tenant
+    #[serde(default)]
+    pub suspended: Option<bool>,
+}
+
+impl UpdateTenantRequest {
+    /// What is wrong with the request, if anything
+    pub fn problem(&self) -> Option<String> {
+        if self
+            .name
+            .as_deref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Some("name must not be empty".to_string());
+        }
+        if self.rpm == Some(0) {
+            return Some("rpm must be greater than 0".to_string());
+        }
+        self.allowed_tools
+            .as_deref()
+            .and_then(tool_patterns_problem)
+    }
+}
+
+/// Whether `id` can name a tenant
+pub fn is_valid_tenant_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_TENANT_ID_LEN
+        && id
+            .bytes()
+            .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_'))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreFile {
+    tenants: Vec<Tenant>,
+}
+
+/// Tenants, keyed by id, and their request counts
+pub struct TenantStore {
+    config: TenantsConfig,
+    tenants: RwLock<BTreeMap<String, Tenant>>,
+    // Requests in the current one-minute window per tenant: (count, window_start)
+    windows: DashMap<String, (u32, Instant)>,
+}
+
+impl TenantStore {
+    /// Open the store, loading the tenants saved at `config.store_path`
+    ///
+    /// A missing file is an empty store; an unreadable one is an error, so a
+    /// damaged store never silently drops every tenant.
+    pub fn open(config: TenantsConfig) -> Result<Self> {
+        let mut tenants = BTreeMap::new();
+        if config.enabled {
+            match std::fs::read(&config.store_path) {
+                Ok(bytes) => {
+                    let file: StoreFile = serde_json::from_slice(&bytes).with_context(|| {
+                        format!("Invalid tenant store {}", config.store_path.display())
+                    })?;
+                    for tenant in file.tenants {
+                        tenants.insert(tenant.id.clone(), tenant);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!(
+                            "Failed to read tenant store {}",
+                            config.store_path.display()
+                        )
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            config,
+            tenants: RwLock::new(tenants),
+            windows: DashMap::new(),
+        })
+    }
+
+    /// Whether requests must belong to a tenant
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn config(&self) -> &TenantsConfig {
+        &self.config
+    }
+
+    /// Create a tenant for a request that passed [`CreateTenantRequest::problem`]
+    ///
+    /// `None` if a tenant with the same id exists.
+    pub fn create(&self, request: CreateTenantRequest) -> Result<Option<Tenant>> {
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        if tenants.contains_key(&request.id) {
+            return Ok(None);
+        }
+        let tenant = Tenant {
+            id: request.id,
+            name: request.name.trim().to_string(),
+            allowed_tools: request.allowed_tools,
+            rpm: request.rpm,
+            created_at: unix_now(),
+            suspended_at: None,
+        };
+
+        tenants.insert(tenant.id.clone(), tenant.clone());
+        if let Err(e) = self.save(&tenants) {
+            tenants.remove(&tenant.id);
+            return Err(e);
+        }
+        Ok(Some(tenant))
+    }
+
+    /// Every tenant, by id
+    pub fn list(&self) -> Vec<Tenant> {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants.values().cloned().collect()
+    }
+
+    /// The tenant with `id`, suspended or not
+    pub fn get(&self, id: &str) -> Option<Tenant> {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants.get(id).cloned()
+    }
+
+    /// Apply a request that passed [`UpdateTenantRequest::problem`]
+    ///
+    /// `None` if there is no such tenant.
+    pub fn update(&self, id: &str, request: UpdateTenantRequest) -> Result<Option<Tenant>> {
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        let Some(tenant) = tenants.get_mut(id) else {
+            return Ok(None);
+        };
+        let previous = tenant.clone();
+
+        if let Some(name) = request.name {
+            tenant.name = name.trim().to_string();
+        }
+        if let Some(allowed_tools) = request.allowed_tools {
+            tenant.allowed_tools = allowed_tools;
+        }
+        if request.rpm.is_some() {
+            tenant.rpm = request.rpm;
+        }
+        match request.suspended {
+            Some(true) if tenant.suspended_at.is_none() => tenant.suspended_at = Some(unix_now()),
+            Some(false) => tenant.suspended_at = None,
+            _ => {}
+        }
+        let updated = tenant.clone();
+
+        if let Err(e) = self.save(&tenants) {
+            tenants.insert(id.to_string(), previous);
+            return Err(e);
+        }
+        Ok(Some(updated))
+    }
+
+    /// Delete the tenant with `id`; `None` if there is no such tenant
+    ///
+    /// Client tokens issued for it stop authenticating requests.
+    pub fn remove(&self, id: &str) -> Result<Option<Tenant>> {
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        let Some(removed) = tenants.remove(id) else {
+            return Ok(None);
+        };
+        if let Err(e) = self.save(&tenants) {
+            tenants.insert(id.to_string(), removed);
+            return Err(e);
+        }
+        self.windows.remove(id);
+        Ok(Some(removed))
+    }
+
+    /// The active tenant with `id`, if any
+    pub fn resolve(&self, id: &str) -> Option<Tenant> {
+        self.get(id).filter(Tenant::is_active)
+    }
+
+    /// Count a request against the tenant's quota; `false` once it is spent
+    pub fn check_rate(&self, tenant: &Tenant) -> bool {
+        let limit = tenant.rpm.unwrap_or(self.config.default_rpm);
+        let now = Instant::now();
+        let mut window = self.windows.entry(tenant.id.clone()).or_insert((0, now));
+        if now.duration_since(window.1) >= Duration::from_secs(60) {
+            *window = (0, now);
+        }
+        if window.0 >= limit {
+            return false;
+        }
+        window.0 += 1;
+        true
+    }
+
+    /// Write every tenant to the store file
+    fn save(&self, tenants: &BTreeMap<String, Tenant>) -> Result<()> {
+        let file = StoreFile {
+            tenants: tenants.values().cloned().collect(),
+        };
+        let bytes = serde_json::to_vec_pretty(&file).context("Failed to serialize tenants")?;
+        write_atomically(&self.config.store_path, &bytes)
+    }
+}
@@ -0,0 +1,182 @@
+//! Aggregated `tools/list` cache
+//!
+//! Listing tools fans out to the plugin host and every upstream MCP server.
+//! The bridge keeps the merged list here and serves it until it is
+//! invalidated: by a `notifications/tools/list_changed` from the plugin host
+//! or an upstream, or by the configured TTL running out as a backstop.
+//!
+//! Every `tools/list` result carries a hash of the tool list in
+//! `result._meta.etag`. A client that sends that hash back in
+//! `params._meta.ifNoneMatch` gets `notModified: true` and an empty tool list
+//! while nothing has changed, instead of the full tool descriptions.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sweetmcp_axum::JSONRPC_VERSION;
+
+/// Notification a server sends when its tool list changes
+pub const TOOLS_LIST_CHANGED: &str = "notifications/tools/list_changed";
+
+/// Tool list cache settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolListCacheConfig {
+    /// Serve `tools/list` from the cache
+    pub enabled: bool,
+    /// Longest a cached list is served without a `list_changed` notification
+    pub ttl: Duration,
+}
+
+impl Default for ToolListCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+/// A merged tool list and its hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedTools {
+    /// Tools from the plugin host followed by every upstream's tools
+    pub tools: Vec<Value>,
+    /// Hash of `tools`, see [`tools_etag`]
+    pub etag: String,
+}
+
+struct Entry {
+    tools: CachedTools,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct State {
+    /// Bumped on every invalidation so a fetch that raced one is not stored
+    generation: u64,
+    entry: Option<Entry>,
+}
+
+/// Cache of the aggregated tool list
+pub struct ToolListCache {
+    config: ToolListCacheConfig,
+    state: Mutex<State>,
+}
+
+impl ToolListCache {
+    pub fn new(config: ToolListCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Cached tool list, unless caching is off or the list is missing or stale
+    pub fn get(&self) -> Option<CachedTools> {
+        if !self.config.enabled {
+            return None;
+        }
+        let state = self.lock_state();
+        state
+            .entry
+            .as_ref()
+            .filter(|entry| entry.stored_at.elapsed() < self.config.ttl)
+            .map(|entry| entry.tools.clone())
+    }
+
+    /// Current generation, taken before fetching a list to [`store`](Self::store)
+    pub fn generation(&self) -> u64 {
+        self.lock_state().generation
+    }
+
+    /// Cache a freshly fetched list and return it with its hash
+    ///
+    /// The list is not kept when caching is off or the cache was invalidated
+    /// since `generation` was taken, as it may predate the change.
+    pub fn store(&self, generation: u64, tools: Vec<Value>) -> CachedTools {
+        let cached = CachedTools {
+            etag: tools_etag(&tools),
+            tools,
+        };
+        let mut state = self.lock_state();
+        if self.config.enabled && state.generation == generation {
+            state.entry = Some(Entry {
+                tools: cached.clone(),
+                stored_at: Instant::now(),
+            });
+        }
+        cached
+    }
+
+    /// Drop the cached list
+    pub fn invalidate(&self) {
+        let mut state = self.lock_state();
+        state.generation += 1;
+        state.entry = None;
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Hash identifying a tool list, hex-encoded
+pub fn tools_etag(tools: &[Value]) -> String {
+    let bytes = serde_json::to_vec(tools).unwrap_or_default();
+    hex::encode(&Sha256::digest(&bytes)[..16])
+}
+
+/// Whether `request` asks for the whole tool list
+///
+/// Paginated requests carry a `cursor` and are passed through uncached.
+pub fn is_cacheable(request: &Value) -> bool {
+    request.get("method").and_then(Value::as_str) == Some("tools/list")
+        && request.pointer("/params/cursor").is_none()
+}
+
+/// JSON-RPC response to a `tools/list` request served from `cached`
+pub fn tools_list_response(request: &Value, cached: &CachedTools) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let if_none_match = request
+        .pointer("/params/_meta/ifNoneMatch")
+        .and_then(Value::as_str);
+
+    let result = if if_none_match == Some(cached.etag.as_str()) {
+        json!({
+            "tools": [],
+            "_meta": { "etag": cached.etag, "notModified": true }
+        })
+    } else {
+        json!({
+            "tools": cached.tools,
+            "_meta": { "etag": cached.etag }
+        })
+    };
+
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "result": result,
+        "id": id
+    })
+}
+
+/// Single JSON-RPC message in `body` that the edge answers through the bridge
+///
+/// These are whole-list `tools/list` requests, served from the cache, and
+/// `list_changed` notifications that invalidate it.
+pub fn bridge_request(body: &[u8]) -> Option<Value> {
+    let request = serde_json::from_slice::<Value>(body).ok()?;
+    let method = request.get("method")?.as_str()?;
+    (method == TOOLS_LIST_CHANGED || is_cacheable(&request)).then_some(request)
+}
+
+/// Single `tools/list` request in `body`, whole-list or a later page
+pub fn tools_list_request(body: &[u8]) -> Option<Value> {
+    let request = serde_json::from_slice::<Value>(body).ok()?;
+    (request.get("method")?.as_str()? == "tools/list").then_some(request)
+}
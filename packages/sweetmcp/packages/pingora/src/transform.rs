@@ -0,0 +1,245 @@
+//! Operator-defined rewriting of tool calls
+//!
+//! Rules in the `[request_transforms]` configuration section change
+//! `tools/call` requests at the edge before they are dispatched, so operators
+//! can inject defaults such as proxy settings or a filesystem root without
+//! changing plugins. Each rule matches a tool name, either exactly or by a
+//! prefix ending in `*`, and then fills in missing arguments, overwrites
+//! arguments, removes arguments and sets `_meta` fields, in that order. Every
+//! matching rule applies, in configuration order.
+//!
+//! In dry-run mode the changes are only logged and requests are forwarded
+//! unmodified.
+
+use std::fmt;
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Request transformation rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransformConfig {
+    /// Log the changes rules would make instead of making them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Rules applied to every `tools/call`, in order
+    #[serde(default)]
+    pub rules: Vec<TransformRule>,
+}
+
+/// Changes made to calls of matching tools
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransformRule {
+    /// Tool name, or a prefix ending in `*` such as `github__*`
+    pub tool: String,
+    /// Arguments set when the caller left them out
+    #[serde(default)]
+    pub defaults: Map<String, Value>,
+    /// Arguments set whatever the caller sent
+    #[serde(default)]
+    pub set: Map<String, Value>,
+    /// Arguments removed
+    #[serde(default)]
+    pub strip: Vec<String>,
+    /// Fields set in the request's `params._meta`
+    #[serde(default)]
+    pub meta: Map<String, Value>,
+}
+
+/// One change made to a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A missing argument was filled in
+    Default(String),
+    /// An argument was overwritten or added
+    Set(String),
+    /// An argument was removed
+    Strip(String),
+    /// A `_meta` field was set
+    Meta(String),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Default(field) => write!(f, "default arguments.{}", field),
+            Change::Set(field) => write!(f, "set arguments.{}", field),
+            Change::Strip(field) => write!(f, "strip arguments.{}", field),
+            Change::Meta(field) => write!(f, "set _meta.{}", field),
+        }
+    }
+}
+
+impl TransformRule {
+    /// Whether the rule applies to calls of `tool`
+    pub fn matches(&self, tool: &str) -> bool {
+        match self.tool.strip_suffix('*') {
+            Some(prefix) => tool.starts_with(prefix),
+            None => tool == self.tool,
+        }
+    }
+
+    /// What is wrong with the rule, if anything
+    pub fn problem(&self) -> Option<String> {
+        let pattern = self.tool.strip_suffix('*').unwrap_or(&self.tool);
+        if self.tool.is_empty() || pattern.contains('*') {
+            return Some(format!(
+                "tool `{}` must be a tool name or a prefix ending in `*`",
+                self.tool
+            ));
+        }
+        if self.defaults.is_empty()
+            && self.set.is_empty()
+            && self.strip.is_empty()
+            && self.meta.is_empty()
+        {
+            return Some(format!(
+                "rule for `{}` needs defaults, set, strip or meta",
+                self.tool
+            ));
+        }
+        None
+    }
+
+    /// Apply the rule to the arguments and `_meta` of one call
+    fn apply(&self, params: &mut Map<String, Value>, changes: &mut Vec<Change>) {
+        if !self.defaults.is_empty() || !self.set.is_empty() {
+            let arguments = object_entry(params, "arguments");
+            for (field, value) in &self.defaults {
+                if !arguments.contains_key(field) {
+                    arguments.insert(field.clone(), value.clone());
+                    changes.push(Change::Default(field.clone()));
+                }
+            }
+            for (field, value) in &self.set {
+                if arguments.get(field) != Some(value) {
+                    arguments.insert(field.clone(), value.clone());
+                    changes.push(Change::Set(field.clone()));
+                }
+            }
+        }
+
+        if let Some(arguments) = params.get_mut("arguments").and_then(Value::as_object_mut) {
+            for field in &self.strip {
+                if arguments.remove(field).is_some() {
+                    changes.push(Change::Strip(field.clone()));
+                }
+            }
+        }
+
+        if !self.meta.is_empty() {
+            let meta = object_entry(params, "_meta");
+            for (field, value) in &self.meta {
+                if meta.get(field) != Some(value) {
+                    meta.insert(field.clone(), value.clone());
+                    changes.push(Change::Meta(field.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl TransformConfig {
+    /// Whether any rule is configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply every matching rule to a `tools/call` request
+    ///
+    /// Returns the changes made; other requests are left alone. An
+    /// `arguments` or `_meta` value that is not an object is replaced when a
+    /// rule needs to set a field in it.
+    pub fn apply(&self, request: &mut Value) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if request.get("method").and_then(Value::as_str) != Some("tools/call") {
+            return changes;
+        }
+        let Some(params) = request.get_mut("params").and_then(Value::as_object_mut) else {
+            return changes;
+        };
+        let Some(tool) = params
+            .get("name")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+        else {
+            return changes;
+        };
+
+        for rule in self.rules.iter().filter(|rule| rule.matches(&tool)) {
+            rule.apply(params, &mut changes);
+        }
+        changes
+    }
+
+    /// Transform a request in place, or only log the changes in dry-run mode
+    ///
+    /// Returns whether the request was modified.
+    pub fn transform(&self, request: &mut Value) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let tool = request
+            .pointer("/params/name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        if self.dry_run {
+            let changes = self.apply(&mut request.clone());
+            if !changes.is_empty() {
+                info!(
+                    "[dry run] Would transform call to {}: {}",
+                    tool,
+                    describe(&changes)
+                );
+            }
+            return false;
+        }
+
+        let changes = self.apply(request);
+        if !changes.is_empty() {
+            info!("Transformed call to {}: {}", tool, describe(&changes));
+        }
+        !changes.is_empty()
+    }
+
+    /// Transform a JSON-RPC request body
+    ///
+    /// Returns the new body when the request was modified; bodies that are
+    /// not JSON are left alone.
+    pub fn transform_body(&self, body: &[u8]) -> Option<Vec<u8>> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let mut request: Value = serde_json::from_slice(body).ok()?;
+        if !self.transform(&mut request) {
+            return None;
+        }
+        serde_json::to_vec(&request).ok()
+    }
+}
+
+/// The object under `key`, created or replaced when missing or not an object
+fn object_entry<'a>(params: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    let entry = params
+        .entry(key)
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !entry.is_object() {
+        *entry = Value::Object(Map::new());
+    }
+    match entry {
+        Value::Object(map) => map,
+        _ => unreachable!("entry was just made an object"),
+    }
+}
+
+fn describe(changes: &[Change]) -> String {
+    changes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,474 @@
+//! Upstream MCP server federation
+//!
+//! This module provides:
+//! - Registry of remote MCP servers (stdio commands or HTTP(S) endpoints)
+//! - Tool namespace prefixing so upstream tools never collide with local plugins
+//! - Aggregation of `tools/list` results across all upstreams
+//! - Proxying of `tools/call` requests to the upstream that owns the tool
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sweetmcp_axum::JSONRPC_VERSION;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+use crate::tool_cache::TOOLS_LIST_CHANGED;
+
+/// Separator between the upstream prefix and the upstream's own tool name
+pub const NAMESPACE_SEPARATOR: &str = "__";
+
+/// MCP protocol version announced during the upstream handshake
+const UPSTREAM_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Transport used to reach an upstream MCP server
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "transport", rename_all = "lowercase")]
+pub enum UpstreamTransport {
+    /// Spawn a local command and speak newline-delimited JSON-RPC over stdio
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        env: HashMap<String, String>,
+    },
+    /// POST JSON-RPC requests to an HTTP(S) endpoint
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+}
+
+/// Configuration for a single upstream MCP server
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UpstreamServerConfig {
+    /// Unique upstream name
+    pub name: String,
+
+    /// Namespace prefix for this upstream's tools (defaults to `name`)
+    #[serde(default)]
+    pub prefix: Option<String>,
+
+    /// Transport settings
+    #[serde(flatten)]
+    pub transport: UpstreamTransport,
+
+    /// Per-request timeout in seconds
+    #[serde(default = "default_upstream_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_upstream_timeout_secs() -> u64 {
+    30
+}
+
+impl UpstreamServerConfig {
+    /// Effective namespace prefix for this upstream
+    pub fn namespace(&self) -> &str {
+        self.prefix.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Validate the upstream configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("upstream name must not be empty");
+        }
+
+        if self.namespace().contains(NAMESPACE_SEPARATOR) {
+            anyhow::bail!(
+                "upstream prefix '{}' must not contain '{}'",
+                self.namespace(),
+                NAMESPACE_SEPARATOR
+            );
+        }
+
+        if self.timeout_secs == 0 {
+            anyhow::bail!("upstream '{}' timeout must be greater than 0", self.name);
+        }
+
+        match &self.transport {
+            UpstreamTransport::Stdio { command, .. } => {
+                if command.trim().is_empty() {
+                    anyhow::bail!("upstream '{}' has an empty stdio command", self.name);
+                }
+            }
+            UpstreamTransport::Http { url, .. } => {
+                let parsed = url::Url::parse(url)
+                    .with_context(|| format!("Invalid upstream MCP URL: {}", url))?;
+                if parsed.scheme() != "https" && parsed.scheme() != "http" {
+                    anyhow::bail!("upstream '{}' URL must be http or https", self.name);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Load upstream server definitions from a JSON file
+pub fn load_upstream_configs(path: &Path) -> Result<Vec<UpstreamServerConfig>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read upstream config {}", path.display()))?;
+    let configs: Vec<UpstreamServerConfig> = serde_json::from_str(&raw)
+        .with_context(|| format!("Invalid upstream config {}", path.display()))?;
+    Ok(configs)
+}
+
+/// Build the namespaced tool name exposed to gateway clients
+pub fn namespaced_tool_name(prefix: &str, tool: &str) -> String {
+    format!("{}{}{}", prefix, NAMESPACE_SEPARATOR, tool)
+}
+
+/// Split a namespaced tool name into `(prefix, tool)`
+pub fn split_namespaced_tool(name: &str) -> Option<(&str, &str)> {
+    name.split_once(NAMESPACE_SEPARATOR)
+        .filter(|(prefix, tool)| !prefix.is_empty() && !tool.is_empty())
+}
+
+/// Live stdio process for an upstream
+struct StdioProcess {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// Connection to an upstream MCP server
+enum UpstreamConnection {
+    Stdio(Mutex<Option<StdioProcess>>),
+    Http(reqwest::Client),
+}
+
+/// A single registered upstream MCP server
+pub struct UpstreamServer {
+    config: UpstreamServerConfig,
+    connection: UpstreamConnection,
+    next_id: AtomicU64,
+    /// Set when the upstream announces `notifications/tools/list_changed`
+    tools_changed: AtomicBool,
+}
+
+impl UpstreamServer {
+    /// Create an upstream from its configuration; stdio processes are spawned lazily
+    pub fn new(config: UpstreamServerConfig) -> Result<Self> {
+        config.validate()?;
+
+        let connection = match &config.transport {
+            UpstreamTransport::Stdio { .. } => UpstreamConnection::Stdio(Mutex::new(None)),
+            UpstreamTransport::Http { .. } => UpstreamConnection::Http(
+                reqwest::Client::builder()
+                    .timeout(Duration::from_secs(config.timeout_secs))
+                    .build()
+                    .context("Failed to build upstream HTTP client")?,
+            ),
+        };
+
+        Ok(Self {
+            config,
+            connection,
+            next_id: AtomicU64::new(1),
+            tools_changed: AtomicBool::new(false),
+        })
+    }
+
+    /// Upstream name
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Namespace prefix applied to this upstream's tools
+    pub fn namespace(&self) -> &str {
+        self.config.namespace()
+    }
+
+    /// Send a JSON-RPC request to the upstream and return its response
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        tokio::time::timeout(timeout, self.send(request, Some(id)))
+            .await
+            .with_context(|| format!("Upstream '{}' timed out on {}", self.name(), method))?
+    }
+
+    async fn send(&self, request: Value, id: Option<u64>) -> Result<Value> {
+        match (&self.connection, &self.config.transport) {
+            (UpstreamConnection::Http(client), UpstreamTransport::Http { url, headers }) => {
+                let mut builder = client
+                    .post(url)
+                    .header("content-type", "application/json")
+                    .header("accept", "application/json");
+                for (key, value) in headers {
+                    builder = builder.header(key, value);
+                }
+                let response = builder
+                    .json(&request)
+                    .send()
+                    .await
+                    .with_context(|| format!("Upstream '{}' unreachable", self.name()))?;
+                response
+                    .json::<Value>()
+                    .await
+                    .with_context(|| format!("Upstream '{}' returned invalid JSON", self.name()))
+            }
+            (UpstreamConnection::Stdio(process), UpstreamTransport::Stdio { .. }) => {
+                let mut guard = process.lock().await;
+                if guard.is_none() {
+                    *guard = Some(self.spawn().await?);
+                }
+
+                let result = match guard.as_mut() {
+                    Some(proc) => self.exchange(proc, &request, id).await,
+                    None => Err(anyhow::anyhow!("Upstream '{}' process unavailable", self.name())),
+                };
+
+                // Drop a broken process so the next request respawns it
+                if result.is_err() {
+                    *guard = None;
+                }
+                result
+            }
+            _ => Err(anyhow::anyhow!(
+                "Upstream '{}' transport mismatch",
+                self.name()
+            )),
+        }
+    }
+
+    async fn spawn(&self) -> Result<StdioProcess> {
+        let UpstreamTransport::Stdio { command, args, env } = &self.config.transport else {
+            anyhow::bail!("Upstream '{}' is not a stdio upstream", self.name());
+        };
+
+        info!("Spawning upstream MCP server '{}': {}", self.name(), command);
+
+        let mut child = Command::new(command)
+            .args(args)
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn upstream '{}'", self.name()))?;
+
+        let stdin = child.stdin.take().context("Upstream stdin unavailable")?;
+        let stdout = child.stdout.take().context("Upstream stdout unavailable")?;
+        let mut process = StdioProcess {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        };
+
+        // MCP handshake: initialize request followed by the initialized notification
+        let init_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let initialize = json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "id": init_id,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": UPSTREAM_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "sweetmcp-gateway", "version": env!("CARGO_PKG_VERSION") }
+            }
+        });
+        let response = self.exchange(&mut process, &initialize, Some(init_id)).await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("Upstream '{}' rejected initialize: {}", self.name(), error);
+        }
+
+        let initialized = json!({
+            "jsonrpc": JSONRPC_VERSION,
+            "method": "notifications/initialized",
+        });
+        self.exchange(&mut process, &initialized, None).await?;
+
+        Ok(process)
+    }
+
+    /// Write one request line and, when an id is given, read until the matching response
+    ///
+    /// A tool `list_changed` notification read along the way is remembered
+    /// for [`take_tools_changed`](Self::take_tools_changed).
+    async fn exchange(
+        &self,
+        process: &mut StdioProcess,
+        request: &Value,
+        id: Option<u64>,
+    ) -> Result<Value> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        process.stdin.write_all(line.as_bytes()).await?;
+        process.stdin.flush().await?;
+
+        let Some(id) = id else {
+            return Ok(Value::Null);
+        };
+
+        let mut buffer = String::new();
+        loop {
+            buffer.clear();
+            if process.stdout.read_line(&mut buffer).await? == 0 {
+                anyhow::bail!("Upstream process closed stdout");
+            }
+
+            let Ok(message) = serde_json::from_str::<Value>(buffer.trim()) else {
+                debug!("Ignoring non-JSON upstream output: {}", buffer.trim());
+                continue;
+            };
+
+            if message.get("method").and_then(Value::as_str) == Some(TOOLS_LIST_CHANGED) {
+                debug!("Upstream '{}' tool list changed", self.name());
+                self.tools_changed.store(true, Ordering::Relaxed);
+                continue;
+            }
+
+            if message.get("id").and_then(Value::as_u64) == Some(id) {
+                return Ok(message);
+            }
+        }
+    }
+
+    /// Whether the upstream announced a tool list change since the last call
+    pub fn take_tools_changed(&self) -> bool {
+        self.tools_changed.swap(false, Ordering::Relaxed)
+    }
+
+    /// List this upstream's tools with names rewritten into the gateway namespace
+    pub async fn list_tools(&self) -> Result<Vec<Value>> {
+        let response = self.request("tools/list", json!({})).await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("Upstream '{}' tools/list failed: {}", self.name(), error);
+        }
+
+        let tools = response
+            .pointer("/result/tools")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(tools
+            .into_iter()
+            .filter_map(|mut tool| {
+                let name = tool.get("name")?.as_str()?.to_string();
+                tool["name"] = Value::String(namespaced_tool_name(self.namespace(), &name));
+                Some(tool)
+            })
+            .collect())
+    }
+}
+
+/// Registry of all upstream MCP servers known to the gateway
+#[derive(Default)]
+pub struct UpstreamRegistry {
+    servers: HashMap<String, Arc<UpstreamServer>>,
+}
+
+impl UpstreamRegistry {
+    /// Build a registry from configuration, rejecting duplicate namespaces
+    pub fn from_configs(configs: &[UpstreamServerConfig]) -> Result<Self> {
+        let mut servers = HashMap::new();
+        for config in configs {
+            let server = UpstreamServer::new(config.clone())?;
+            let namespace = server.namespace().to_string();
+            if servers.insert(namespace.clone(), Arc::new(server)).is_some() {
+                anyhow::bail!("Duplicate upstream namespace '{}'", namespace);
+            }
+        }
+        Ok(Self { servers })
+    }
+
+    /// Whether any upstreams are registered
+    pub fn is_empty(&self) -> bool {
+        self.servers.is_empty()
+    }
+
+    /// Number of registered upstreams
+    pub fn len(&self) -> usize {
+        self.servers.len()
+    }
+
+    /// Resolve a namespaced tool name to its upstream and original tool name
+    pub fn resolve(&self, tool_name: &str) -> Option<(Arc<UpstreamServer>, String)> {
+        let (prefix, tool) = split_namespaced_tool(tool_name)?;
+        self.servers
+            .get(prefix)
+            .map(|server| (server.clone(), tool.to_string()))
+    }
+
+    /// Aggregate tools across all upstreams; unreachable upstreams are skipped
+    pub async fn list_tools(&self) -> Vec<Value> {
+        self.collect_tools().await.0
+    }
+
+    /// Aggregate tools across all upstreams, and whether every upstream answered
+    pub async fn collect_tools(&self) -> (Vec<Value>, bool) {
+        let futures = self.servers.values().map(|server| async move {
+            match server.list_tools().await {
+                Ok(tools) => Some(tools),
+                Err(e) => {
+                    warn!("Skipping upstream '{}' in tools/list: {}", server.name(), e);
+                    None
+                }
+            }
+        });
+
+        let results = futures::future::join_all(futures).await;
+        let complete = results.iter().all(Option::is_some);
+        (results.into_iter().flatten().flatten().collect(), complete)
+    }
+
+    /// Whether any upstream announced a tool list change since the last call
+    pub fn take_tools_changed(&self) -> bool {
+        // Visit every server so each flag is cleared
+        self.servers
+            .values()
+            .fold(false, |changed, server| server.take_tools_changed() || changed)
+    }
+
+    /// Proxy a `tools/call` request to its upstream, preserving the client's request id
+    pub async fn call_tool(&self, request: &Value) -> Option<Value> {
+        let tool_name = request.pointer("/params/name")?.as_str()?;
+        let (server, tool) = self.resolve(tool_name)?;
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+
+        let mut params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+        params["name"] = Value::String(tool);
+
+        let response = match server.request("tools/call", params).await {
+            Ok(mut response) => {
+                response["id"] = id;
+                response
+            }
+            Err(e) => {
+                warn!("Upstream '{}' tools/call failed: {}", server.name(), e);
+                json!({
+                    "jsonrpc": JSONRPC_VERSION,
+                    "error": {
+                        "code": -32603,
+                        "message": format!("Internal error: upstream '{}' unavailable", server.name())
+                    },
+                    "id": id
+                })
+            }
+        };
+
+        Some(response)
+    }
+}
@@ -0,0 +1,226 @@
+//! Integration tests for the configuration file layer and hot reload
+//!
+//! These tests only write config files; they assume no `SWEETMCP_*`
+//! variables are set, since those override the file.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use sweetmcp::config::Config;
+use sweetmcp::rate_limit::DistributedRateLimitManager;
+use sweetmcp::reload::{self, ConfigReloader, ReloadTargets};
+
+fn write_config(dir: &Path, name: &str, contents: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, contents).expect("write config file");
+    path
+}
+
+fn load_error(dir: &Path, name: &str, contents: &str) -> String {
+    let path = write_config(dir, name, contents);
+    let error = Config::load(Some(&path)).expect_err("config should be rejected");
+    format!("{:#}", error)
+}
+
+#[test]
+fn test_toml_file_fills_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(
+        dir.path(),
+        "sweetmcp.toml",
+        r#"
+            workers = 8
+            upstreams = ["https://10.0.0.5:8443"]
+            jwt_expiry = "2h"
+            log_level = "debug"
+            plugin_host_url = "http://127.0.0.1:18080/rpc"
+
+            [rate_limit]
+            per_ip_rps = 25
+
+            [bridge_queue]
+            overflow_policy = "shed_oldest"
+        "#,
+    );
+
+    let config = Config::load(Some(&path)).unwrap();
+    assert_eq!(config.workers, 8);
+    assert_eq!(config.upstreams, ["https://10.0.0.5:8443"]);
+    assert_eq!(config.jwt_expiry, Duration::from_secs(7200));
+    assert_eq!(config.log_level, Some(log::LevelFilter::Debug));
+    assert_eq!(config.plugin_host_url, "http://127.0.0.1:18080/rpc");
+    assert_eq!(config.rate_limit.per_ip_rps, 25);
+    // Unset keys keep their defaults
+    assert_eq!(config.rate_limit.burst_capacity, 50);
+    assert_eq!(config.tcp_bind, "0.0.0.0:8443");
+}
+
+#[test]
+fn test_yaml_file_fills_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(
+        dir.path(),
+        "sweetmcp.yaml",
+        "inflight_max: 64\ngraphql_limits:\n  max_depth: 4\n",
+    );
+
+    let config = Config::load(Some(&path)).unwrap();
+    assert_eq!(config.inflight_max, 64);
+    assert_eq!(config.graphql_limits.max_depth, 4);
+}
+
+#[test]
+fn test_request_transform_rules_are_read_and_checked() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(
+        dir.path(),
+        "transforms.toml",
+        r#"
+            [request_transforms]
+            dry_run = true
+
+            [[request_transforms.rules]]
+            tool = "fetch"
+            defaults = { proxy = "http://proxy:3128" }
+            strip = ["headers"]
+
+            [[request_transforms.rules]]
+            tool = "fs_*"
+            set = { root = "/srv/data" }
+        "#,
+    );
+
+    let config = Config::load(Some(&path)).unwrap();
+    let transforms = &config.request_transforms;
+    assert!(transforms.dry_run);
+    assert_eq!(transforms.rules.len(), 2);
+    assert_eq!(transforms.rules[0].defaults["proxy"], "http://proxy:3128");
+    assert_eq!(transforms.rules[1].tool, "fs_*");
+
+    let error = load_error(
+        dir.path(),
+        "bad_transforms.toml",
+        "[[request_transforms.rules]]\ntool = \"fs_*_write\"\nstrip = [\"root\"]\n\n\
+         [[request_transforms.rules]]\ntool = \"fetch\"\n",
+    );
+    assert!(error.contains("request_transforms.rules[0]"), "{}", error);
+    assert!(error.contains("rules[1]: rule for `fetch` needs"), "{}", error);
+}
+
+#[test]
+fn test_unknown_keys_and_bad_types_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let error = load_error(dir.path(), "typo.toml", "[rate_limits]\nper_ip_rps = 5\n");
+    assert!(error.contains("rate_limits"), "{}", error);
+
+    let error = load_error(dir.path(), "type.toml", "workers = \"many\"\n");
+    assert!(error.contains("workers"), "{}", error);
+}
+
+#[test]
+fn test_every_problem_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let error = load_error(
+        dir.path(),
+        "values.toml",
+        "jwt_expiry = \"5 fortnights\"\nlog_level = \"loud\"\n",
+    );
+    assert!(error.contains("2 configuration problem(s)"), "{}", error);
+    assert!(error.contains("jwt_expiry"), "{}", error);
+    assert!(error.contains("log_level"), "{}", error);
+
+    let error = load_error(
+        dir.path(),
+        "semantics.toml",
+        r#"
+            workers = 0
+            tcp_bind = "8443"
+            circuit_breaker_threshold = 150
+            upstreams = ["https://", "ftp://10.0.0.5"]
+        "#,
+    );
+    assert!(error.contains("5 configuration problem(s)"), "{}", error);
+    for expected in [
+        "workers (SWEETMCP_WORKERS)",
+        "tcp_bind (SWEETMCP_TCP_BIND) must be host:port",
+        "circuit_breaker_threshold",
+        "entry `https://`",
+        "must use http or https",
+    ] {
+        assert!(
+            error.contains(expected),
+            "missing `{}` in {}",
+            expected,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_diff_splits_reloadable_settings() {
+    let old = Config::default();
+    let mut new = old.clone();
+    new.rate_limit.per_ip_rps = 5;
+    new.log_level = Some(log::LevelFilter::Info);
+    new.tcp_bind = "0.0.0.0:9443".to_string();
+    new.jwt_secret = Arc::new([7u8; 32]);
+
+    let outcome = reload::diff(&old, &new);
+    assert_eq!(outcome.applied, ["rate_limit", "log_level"]);
+    assert_eq!(outcome.restart_required, ["tcp_bind"]);
+}
+
+#[test]
+fn test_reload_applies_reloadable_settings_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_config(dir.path(), "sweetmcp.toml", "workers = 2\n");
+    let config = Arc::new(Config::load(Some(&path)).unwrap());
+
+    let static_upstreams = Arc::new(ArcSwap::from_pointee(Vec::new()));
+    let upstream_urls = Arc::new(ArcSwap::from_pointee(Default::default()));
+    let rate_limiter = Arc::new(DistributedRateLimitManager::new());
+    let reloader = ConfigReloader::new(
+        Some(path.clone()),
+        config,
+        ReloadTargets {
+            rate_limiter: Some(rate_limiter.clone()),
+            static_upstreams: static_upstreams.clone(),
+            upstream_urls: upstream_urls.clone(),
+        },
+        None,
+    );
+
+    write_config(
+        dir.path(),
+        "sweetmcp.toml",
+        r#"
+            workers = 16
+            upstreams = ["http://127.0.0.1:8080"]
+
+            [rate_limit]
+            per_ip_rps = 1
+            burst_capacity = 1
+        "#,
+    );
+    let outcome = reloader.reload().unwrap();
+    assert_eq!(outcome.applied, ["upstreams", "rate_limit"]);
+    assert_eq!(outcome.restart_required, ["workers"]);
+
+    assert_eq!(static_upstreams.load().len(), 1);
+    assert_eq!(upstream_urls.load().len(), 1);
+    assert!(rate_limiter.check_request("/mcp", Some("10.0.0.1"), 1));
+    assert!(!rate_limiter.check_request("/mcp", Some("10.0.0.1"), 1));
+
+    let current = reloader.current();
+    assert_eq!(current.workers, 2);
+    assert_eq!(current.rate_limit.burst_capacity, 1);
+
+    // A broken file is rejected and the running configuration stays
+    write_config(dir.path(), "sweetmcp.toml", "workers = 0\n");
+    assert!(reloader.reload().is_err());
+    assert_eq!(reloader.current().upstreams, ["http://127.0.0.1:8080"]);
+}
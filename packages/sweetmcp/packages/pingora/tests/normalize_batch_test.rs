@@ -0,0 +1,148 @@
+use serde_json::{json, Value};
+use sweetmcp::mcp_bridge::BridgeMsg;
+use sweetmcp::normalize::batch::{
+    batch_error_response, dispatch_batch, parse_batch, split_batch, BatchEntry, BatchLimits,
+};
+use sweetmcp::normalize::types::ConversionError;
+use sweetmcp::normalize::{normalize_to_jsonrpc, quick_detect_protocol, test_context, Proto};
+use tokio::sync::mpsc;
+
+/// Bridge stand-in answering every request with its method name
+fn echo_bridge() -> mpsc::Sender<BridgeMsg> {
+    let (tx, mut rx) = mpsc::channel::<BridgeMsg>(16);
+    tokio::spawn(async move {
+        while let Some((request, _ctx, reply)) = rx.recv().await {
+            let _ = reply.send(json!({
+                "jsonrpc": "2.0",
+                "id": request.get("id").cloned().unwrap_or(Value::Null),
+                "result": request["method"],
+            }));
+        }
+    });
+    tx
+}
+
+#[test]
+fn test_parse_batch_skips_single_messages_and_graphql_batches() {
+    assert!(parse_batch(br#"{"jsonrpc":"2.0","method":"ping","id":1}"#).is_none());
+    assert!(parse_batch(br#"[{"query":"{ a }"},{"query":"{ b }"}]"#).is_none());
+    assert_eq!(parse_batch(b"[]"), Some(vec![]));
+    assert_eq!(parse_batch(b"[1,2]").map(|items| items.len()), Some(2));
+}
+
+#[test]
+fn test_split_batch_rejects_invalid_elements_in_place() {
+    let items = vec![
+        json!({"jsonrpc": "2.0", "method": "ping", "id": 1}),
+        json!({"jsonrpc": "2.0", "id": 2}),
+        json!(3),
+    ];
+    let entries = split_batch(items, &BatchLimits::default()).expect("within limits");
+
+    assert!(matches!(&entries[0], BatchEntry::Call(request) if request["id"] == 1));
+    let BatchEntry::Rejected(missing_method) = &entries[1] else {
+        panic!("element without a method should be rejected");
+    };
+    assert_eq!(missing_method["id"], 2);
+    assert_eq!(missing_method["error"]["code"], -32600);
+    let BatchEntry::Rejected(not_an_object) = &entries[2] else {
+        panic!("non-object element should be rejected");
+    };
+    assert_eq!(not_an_object["id"], Value::Null);
+}
+
+#[test]
+fn test_split_batch_enforces_size_limits() {
+    let limits = BatchLimits { max_batch_size: 2 };
+    let ping = json!({"jsonrpc": "2.0", "method": "ping", "id": 1});
+
+    assert!(matches!(
+        split_batch(vec![], &limits),
+        Err(ConversionError::EmptyBatch)
+    ));
+    let error = split_batch(vec![ping.clone(), ping.clone(), ping], &limits)
+        .expect_err("batch over the limit");
+    assert!(matches!(
+        error,
+        ConversionError::BatchTooLarge {
+            actual: 3,
+            limit: 2
+        }
+    ));
+
+    let response = batch_error_response(&error);
+    assert_eq!(response["id"], Value::Null);
+    assert_eq!(response["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_dispatch_batch_preserves_order_and_ids() {
+    let bridge = echo_bridge();
+    let entries = split_batch(
+        vec![
+            json!({"jsonrpc": "2.0", "method": "tools/list", "id": "a"}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": "bad"}),
+            json!({"jsonrpc": "2.0", "method": "ping", "id": 7}),
+        ],
+        &BatchLimits::default(),
+    )
+    .expect("within limits");
+
+    let responses = dispatch_batch(&bridge, &test_context(Proto::JsonRpc), entries)
+        .await
+        .expect("batch has requests");
+    let responses = responses.as_array().expect("array reply");
+
+    // The notification gets no entry; everything else keeps its position
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0]["id"], "a");
+    assert_eq!(responses[0]["result"], "tools/list");
+    assert_eq!(responses[1]["id"], "bad");
+    assert_eq!(responses[1]["error"]["code"], -32600);
+    assert_eq!(responses[2]["id"], 7);
+    assert_eq!(responses[2]["result"], "ping");
+}
+
+#[tokio::test]
+async fn test_dispatch_batch_of_notifications_has_no_reply() {
+    let bridge = echo_bridge();
+    let entries = split_batch(
+        vec![json!({"jsonrpc": "2.0", "method": "notifications/initialized"})],
+        &BatchLimits::default(),
+    )
+    .expect("within limits");
+
+    assert!(
+        dispatch_batch(&bridge, &test_context(Proto::JsonRpc), entries)
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_dispatch_batch_answers_when_bridge_is_closed() {
+    let (bridge, rx) = mpsc::channel::<BridgeMsg>(1);
+    drop(rx);
+    let entries = vec![BatchEntry::Call(
+        json!({"jsonrpc": "2.0", "method": "ping", "id": 1}),
+    )];
+
+    let responses = dispatch_batch(&bridge, &test_context(Proto::JsonRpc), entries)
+        .await
+        .expect("request expects a reply");
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["error"]["code"], -32603);
+}
+
+#[test]
+fn test_normalizer_accepts_batches() {
+    let body = br#"[{"jsonrpc":"2.0","method":"ping","id":1},{"jsonrpc":"2.0","method":"tools/list","id":2}]"#;
+
+    assert_eq!(quick_detect_protocol(body, None).unwrap(), Proto::JsonRpc);
+    let (ctx, batch) = normalize_to_jsonrpc("user", body, None).expect("batch normalizes");
+    assert_eq!(ctx.protocol, Proto::JsonRpc);
+    assert_eq!(batch.as_array().map(Vec::len), Some(2));
+
+    assert!(normalize_to_jsonrpc("user", b"[]", None).is_err());
+}
@@ -0,0 +1,88 @@
+use async_graphql::parser::parse_query;
+use sweetmcp::normalize::complexity::{
+    analyze_query, enforce_query_limits, limit_error_response, QueryLimits,
+};
+use sweetmcp::normalize::types::ConversionError;
+
+#[test]
+fn test_depth_and_complexity_scoring() {
+    let doc = parse_query("{ user { id posts { title } } }").expect("valid query");
+    let cost = analyze_query(&doc);
+
+    assert_eq!(cost.depth, 3);
+    assert_eq!(cost.complexity, 4);
+}
+
+#[test]
+fn test_pagination_arguments_multiply_complexity() {
+    let doc = parse_query("{ users(first: 10) { id name } }").expect("valid query");
+    let cost = analyze_query(&doc);
+
+    assert_eq!(cost.depth, 2);
+    assert_eq!(cost.complexity, 1 + 10 * 2);
+}
+
+#[test]
+fn test_fragments_count_towards_depth() {
+    let doc = parse_query(
+        r#"
+        query { user { ...Details } }
+        fragment Details on User { profile { avatar { url } } }
+        "#,
+    )
+    .expect("valid query");
+
+    assert_eq!(analyze_query(&doc).depth, 4);
+}
+
+#[test]
+fn test_deep_query_rejected_with_graphql_error() {
+    let doc = parse_query("{ a { b { c { d { e } } } } }").expect("valid query");
+    let limits = QueryLimits {
+        max_depth: 3,
+        max_complexity: 1000,
+    };
+
+    let error = enforce_query_limits(&doc, &limits).expect_err("depth limit should trip");
+    assert!(matches!(
+        &error,
+        ConversionError::QueryLimitExceeded { metric, actual: 5, limit: 3 } if metric == "depth"
+    ));
+
+    let body = limit_error_response(&error);
+    assert!(body["data"].is_null());
+    assert_eq!(body["errors"][0]["extensions"]["code"], "QUERY_TOO_COMPLEX");
+    assert_eq!(body["errors"][0]["extensions"]["metric"], "depth");
+}
+
+#[test]
+fn test_complex_query_rejected() {
+    let doc = parse_query("{ users(first: 500) { friends(first: 500) { id } } }").expect("valid query");
+
+    let error = enforce_query_limits(&doc, &QueryLimits::default()).expect_err("complexity limit should trip");
+    assert!(matches!(
+        error,
+        ConversionError::QueryLimitExceeded { ref metric, .. } if metric == "complexity"
+    ));
+}
+
+#[test]
+fn test_fragment_fan_out_scored_without_re_walking() {
+    // F0 spreads F1 twice, F1 spreads F2 twice, ...: 2^40 paths to the leaf field
+    let mut query = String::from("query { ...F0 }\n");
+    for i in 0..40 {
+        query.push_str(&format!("fragment F{} on Query {{ ...F{} ...F{} }}\n", i, i + 1, i + 1));
+    }
+    query.push_str("fragment F40 on Query { leaf }\n");
+    let doc = parse_query(&query).expect("valid query");
+
+    let cost = analyze_query(&doc);
+    assert_eq!(cost.depth, 1);
+    assert_eq!(cost.complexity, 1usize << 40);
+
+    let error = enforce_query_limits(&doc, &QueryLimits::default()).expect_err("complexity limit should trip");
+    assert!(matches!(
+        error,
+        ConversionError::QueryLimitExceeded { ref metric, .. } if metric == "complexity"
+    ));
+}
@@ -238,3 +238,41 @@ async fn test_hybrid_algorithm_endpoint_and_peer_consistency() {
     // First request from peer2 should be allowed (separate limiter)
     assert!(manager2.check_request(endpoint, peer2, 1));
 }
+
+#[tokio::test]
+async fn test_default_limit_covers_unconfigured_endpoints() {
+    use sweetmcp::rate_limit::{
+        algorithms::RateLimitAlgorithmConfig,
+        distributed::{DistributedRateLimitManager, EndpointRateConfig},
+    };
+
+    let manager = DistributedRateLimitManager::new();
+    let peer = Some("10.0.0.1");
+
+    // Without a default, unconfigured endpoints are unlimited
+    for _ in 0..10 {
+        assert!(manager.check_request("/unconfigured", peer, 1));
+    }
+
+    manager.set_default_config(Some(EndpointRateConfig {
+        algorithm: RateLimitAlgorithmConfig::TokenBucket(TokenBucketConfig {
+            capacity: 2,
+            refill_rate: 0.01,
+            initial_tokens: 2,
+        }),
+        per_peer: true,
+        trusted_multiplier: 1.0,
+    }));
+
+    // One bucket per peer, shared by every unconfigured endpoint
+    assert!(manager.check_request("/a", peer, 1));
+    assert!(manager.check_request("/b", peer, 1));
+    assert!(!manager.check_request("/c", peer, 1));
+    assert!(manager.check_request("/a", Some("10.0.0.2"), 1));
+
+    // Configured endpoints keep their own limits
+    assert!(manager.check_request("/api/peers", peer, 1));
+
+    manager.set_default_config(None);
+    assert!(manager.check_request("/c", peer, 1));
+}
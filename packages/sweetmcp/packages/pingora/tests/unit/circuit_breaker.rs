@@ -126,4 +126,42 @@ async fn test_window_reset() {
     let (total, failed) = breaker.get_metrics();
     assert_eq!(total, 0);
     assert_eq!(failed, 0);
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_retry_after_while_open() {
+    let config = CircuitBreakerConfig {
+        request_volume_threshold: 1,
+        sleep_window: Duration::from_secs(30),
+        ..Default::default()
+    };
+
+    let breaker = CircuitBreaker::new("tool:fs".to_string(), config);
+    assert_eq!(breaker.retry_after().await, None);
+
+    breaker.record_failure().await;
+    let retry_after = breaker.retry_after().await.expect("circuit should be open");
+    assert!(retry_after <= Duration::from_secs(30));
+    assert!(retry_after > Duration::from_secs(25));
+}
+
+#[tokio::test]
+async fn test_tool_breakers_use_tool_config() {
+    let tool_config = CircuitBreakerConfig {
+        request_volume_threshold: 2,
+        ..Default::default()
+    };
+    let manager = CircuitBreakerManager::new(Default::default()).with_tool_config(tool_config);
+
+    let tool = manager.get_tool_breaker("fs").await;
+    assert!(Arc::ptr_eq(&tool, &manager.get_breaker("tool:fs").await));
+    assert!(!Arc::ptr_eq(&tool, &manager.get_breaker("fs").await));
+
+    // Two failures trip a tool breaker but not a peer breaker
+    let peer = manager.get_breaker("fs").await;
+    for breaker in [&tool, &peer] {
+        breaker.record_failure().await;
+        breaker.record_failure().await;
+    }
+    assert_eq!(tool.get_state().await, CircuitState::Open);
+    assert_eq!(peer.get_state().await, CircuitState::Closed);
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use sweetmcp::api::tokens::{handle_client_tokens_request, is_client_tokens_path};
+use sweetmcp::client_tokens::{
+    ClientTokenStore, ClientTokensConfig, IssueTokenRequest, RateTier, TOKEN_PREFIX,
+};
+use sweetmcp::tenants::{TenantStore, TenantsConfig};
+use tempfile::tempdir;
+
+fn config(dir: &Path) -> ClientTokensConfig {
+    ClientTokensConfig {
+        store_path: dir.join("client_tokens.json"),
+        low_rpm: 2,
+        ..ClientTokensConfig::default()
+    }
+}
+
+fn request(tools: &[&str]) -> IssueTokenRequest {
+    IssueTokenRequest {
+        name: "ci-agent".to_string(),
+        allowed_tools: tools.iter().map(|tool| tool.to_string()).collect(),
+        rate_tier: RateTier::Low,
+        tenant: None,
+        expires_in_secs: None,
+    }
+}
+
+#[test]
+fn test_issued_token_authenticates_and_survives_reopen() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = ClientTokenStore::open(config(dir.path())).unwrap();
+    let issued = store.issue(request(&["fetch"])).unwrap();
+    assert!(issued.token.starts_with(TOKEN_PREFIX));
+    assert_eq!(
+        store.authenticate(&issued.token),
+        Some(issued.client.clone())
+    );
+    assert_eq!(store.authenticate("smcp_not-a-token"), None);
+
+    // Only the hash is persisted
+    let saved = std::fs::read_to_string(dir.path().join("client_tokens.json")).unwrap();
+    assert!(!saved.contains(&issued.token));
+
+    let reopened = ClientTokenStore::open(config(dir.path())).unwrap();
+    assert_eq!(reopened.authenticate(&issued.token), Some(issued.client));
+}
+
+#[test]
+fn test_revoked_token_is_rejected_but_listed() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = ClientTokenStore::open(config(dir.path())).unwrap();
+    let issued = store.issue(request(&["fetch"])).unwrap();
+
+    let revoked = store.revoke(&issued.client.id).unwrap().unwrap();
+    assert!(revoked.revoked_at.is_some());
+    assert_eq!(store.authenticate(&issued.token), None);
+    assert_eq!(store.list().len(), 1);
+    assert!(store.revoke("missing").unwrap().is_none());
+
+    let reopened = ClientTokenStore::open(config(dir.path())).unwrap();
+    assert_eq!(reopened.authenticate(&issued.token), None);
+}
+
+#[test]
+fn test_unreadable_store_is_an_error() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    std::fs::write(dir.path().join("client_tokens.json"), "not json").unwrap();
+    assert!(ClientTokenStore::open(config(dir.path())).is_err());
+}
+
+#[test]
+fn test_allowed_tools_and_denied_calls() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = ClientTokenStore::open(config(dir.path())).unwrap();
+    let client = store.issue(request(&["fetch", "fs_*"])).unwrap().client;
+
+    assert!(client.allows_tool("fetch"));
+    assert!(client.allows_tool("fs_read"));
+    assert!(!client.allows_tool("fetcher"));
+
+    let allowed = br#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"fs_read"}}"#;
+    assert_eq!(client.first_denied_tool(allowed), None);
+    let listing = br#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+    assert_eq!(client.first_denied_tool(listing), None);
+    let batch = br#"[
+        {"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"fetch"}},
+        {"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"eval_sh"}}
+    ]"#;
+    assert_eq!(client.first_denied_tool(batch), Some("eval_sh".to_string()));
+}
+
+#[test]
+fn test_rate_tier_limits_requests_per_minute() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = ClientTokenStore::open(config(dir.path())).unwrap();
+    let client = store.issue(request(&["fetch"])).unwrap().client;
+
+    assert!(store.check_rate(&client));
+    assert!(store.check_rate(&client));
+    assert!(!store.check_rate(&client));
+}
+
+#[test]
+fn test_issue_request_problems() {
+    let config = ClientTokensConfig::default();
+    assert!(request(&["fetch", "fs_*"]).problem(&config).is_none());
+    assert!(request(&[]).problem(&config).is_some());
+    assert!(request(&["f*s"]).problem(&config).is_some());
+
+    let too_long = IssueTokenRequest {
+        expires_in_secs: Some(config.max_ttl.as_secs() + 1),
+        ..request(&["fetch"])
+    };
+    assert!(too_long.problem(&config).is_some());
+}
+
+#[test]
+fn test_admin_handler_issues_lists_and_revokes() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = ClientTokenStore::open(config(dir.path())).unwrap();
+    let tenants = TenantStore::open(TenantsConfig {
+        store_path: dir.path().join("tenants.json"),
+        ..TenantsConfig::default()
+    })
+    .unwrap();
+    assert!(is_client_tokens_path("/admin/tokens"));
+    assert!(is_client_tokens_path("/admin/tokens/abc"));
+    assert!(!is_client_tokens_path("/admin/tokensx"));
+
+    let (status, body) = handle_client_tokens_request(
+        &store,
+        &tenants,
+        "POST",
+        "/admin/tokens",
+        br#"{"name":"ci-agent","allowed_tools":["fetch"]}"#,
+    );
+    assert_eq!(status, 201);
+    let issued: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let id = issued["id"].as_str().unwrap();
+    assert_eq!(issued["rate_tier"], "standard");
+
+    let (status, body) =
+        handle_client_tokens_request(&store, &tenants, "GET", "/admin/tokens", b"");
+    assert_eq!(status, 200);
+    assert!(!body.contains(issued["token"].as_str().unwrap()));
+
+    let path = format!("/admin/tokens/{}", id);
+    assert_eq!(
+        handle_client_tokens_request(&store, &tenants, "DELETE", &path, b"").0,
+        200
+    );
+    assert_eq!(
+        handle_client_tokens_request(&store, &tenants, "DELETE", "/admin/tokens/missing", b"").0,
+        404
+    );
+    assert_eq!(
+        handle_client_tokens_request(&store, &tenants, "POST", "/admin/tokens", b"{}").0,
+        400
+    );
+}
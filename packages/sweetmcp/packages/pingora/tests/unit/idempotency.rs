@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use sweetmcp::idempotency::{
+    Claim, IdempotencyConfig, IdempotencyStore, StoredResponse, fingerprint, is_valid_key,
+};
+
+fn store() -> IdempotencyStore {
+    IdempotencyStore::new(IdempotencyConfig {
+        wait_timeout: Duration::from_millis(50),
+        ..IdempotencyConfig::default()
+    })
+}
+
+fn response(body: &'static str) -> StoredResponse {
+    StoredResponse {
+        status: 200,
+        content_type: Some("application/json".to_string()),
+        body: Bytes::from_static(body.as_bytes()),
+    }
+}
+
+#[test]
+fn test_fingerprint_ignores_id_and_key_order() {
+    let first = fingerprint(
+        &["POST", "/rpc"],
+        br#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"fs","arguments":{"a":1,"b":2}}}"#,
+    );
+    let retry = fingerprint(
+        &["POST", "/rpc"],
+        br#"{"params":{"arguments":{"b":2,"a":1},"name":"fs"},"method":"tools/call","id":7,"jsonrpc":"2.0"}"#,
+    );
+    let other = fingerprint(
+        &["POST", "/rpc"],
+        br#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"fs","arguments":{"a":2}}}"#,
+    );
+    assert_eq!(first, retry);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn test_key_validation() {
+    assert!(is_valid_key("4f1c2a9e-retry"));
+    assert!(!is_valid_key(""));
+    assert!(!is_valid_key("has space"));
+    assert!(!is_valid_key(&"k".repeat(256)));
+}
+
+#[tokio::test]
+async fn test_replay_after_completion() {
+    let store = store();
+    let print = fingerprint(&["POST", "/rpc"], b"{}");
+
+    assert!(matches!(store.acquire("user\nkey", &print).await, Claim::Acquired));
+    store.complete("user\nkey", &print, response("done"));
+
+    match store.acquire("user\nkey", &print).await {
+        Claim::Replay(stored) => assert_eq!(stored.body, Bytes::from_static(b"done")),
+        other => panic!("expected replay, got {:?}", other),
+    }
+
+    let different = fingerprint(&["POST", "/rpc"], b"[]");
+    assert!(matches!(store.acquire("user\nkey", &different).await, Claim::Mismatch));
+}
+
+#[tokio::test]
+async fn test_in_flight_retry_times_out_then_runs_after_release() {
+    let store = store();
+    let print = fingerprint(&["POST", "/rpc"], b"{}");
+
+    assert!(matches!(store.acquire("key", &print).await, Claim::Acquired));
+    assert!(matches!(store.acquire("key", &print).await, Claim::InProgress));
+
+    store.release("key", &print);
+    assert!(matches!(store.acquire("key", &print).await, Claim::Acquired));
+}
+
+#[tokio::test]
+async fn test_waiting_retry_receives_stored_response() {
+    let store = std::sync::Arc::new(IdempotencyStore::new(IdempotencyConfig::default()));
+    let print = fingerprint(&["POST", "/rpc"], b"{}");
+    assert!(matches!(store.acquire("key", &print).await, Claim::Acquired));
+
+    let waiter = tokio::spawn({
+        let store = store.clone();
+        async move { store.acquire("key", &print).await }
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    store.complete("key", &print, response("once"));
+
+    match waiter.await.expect("waiter panicked") {
+        Claim::Replay(stored) => assert_eq!(stored.body, Bytes::from_static(b"once")),
+        other => panic!("expected replay, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_full_store_bypasses_new_keys() {
+    let store = IdempotencyStore::new(IdempotencyConfig {
+        max_entries: 1,
+        ..IdempotencyConfig::default()
+    });
+    let print = fingerprint(&["POST", "/rpc"], b"{}");
+
+    assert!(matches!(store.acquire("a", &print).await, Claim::Acquired));
+    assert!(matches!(store.acquire("b", &print).await, Claim::Bypassed));
+}
@@ -0,0 +1,137 @@
+use serde_json::json;
+use sweetmcp::mcp_bridge::{
+    BRIDGE_OVERLOADED_CODE, BridgeMsg, BridgePriority, BridgeQueue, BridgeQueueConfig,
+    OverflowPolicy,
+};
+use sweetmcp::normalize::{ProtocolContext, Proto};
+use tokio::sync::oneshot;
+
+fn message(id: u64, method: &str) -> (BridgeMsg, oneshot::Receiver<serde_json::Value>) {
+    let (tx, rx) = oneshot::channel();
+    let request = json!({"jsonrpc": "2.0", "id": id, "method": method});
+    (
+        (request, ProtocolContext::new(Proto::JsonRpc, id.to_string()), tx),
+        rx,
+    )
+}
+
+#[test]
+fn test_method_priorities() {
+    assert_eq!(BridgePriority::for_method("ping"), BridgePriority::High);
+    assert_eq!(BridgePriority::for_method("initialize"), BridgePriority::High);
+    assert_eq!(
+        BridgePriority::for_method("notifications/initialized"),
+        BridgePriority::High
+    );
+    assert_eq!(BridgePriority::for_method("tools/list"), BridgePriority::Normal);
+    assert_eq!(BridgePriority::for_method("tools/call"), BridgePriority::Low);
+}
+
+#[test]
+fn test_overflow_policy_parsing() {
+    assert_eq!("reject".parse::<OverflowPolicy>().ok(), Some(OverflowPolicy::Reject));
+    assert_eq!(
+        "shed-oldest".parse::<OverflowPolicy>().ok(),
+        Some(OverflowPolicy::ShedOldest)
+    );
+    assert!("drop-everything".parse::<OverflowPolicy>().is_err());
+}
+
+#[tokio::test]
+async fn test_high_priority_dequeued_first() {
+    let queue = BridgeQueue::new(BridgeQueueConfig::default());
+    let (call, _call_rx) = message(1, "tools/call");
+    let (ping, _ping_rx) = message(2, "ping");
+    queue.push(call, None);
+    queue.push(ping, None);
+
+    let ((first, _, _), _) = queue.pop().await.expect("queue should not be empty");
+    assert_eq!(first["method"], "ping");
+    assert_eq!(queue.len(), 1);
+}
+
+#[tokio::test]
+async fn test_reject_policy_answers_overflow() {
+    let queue = BridgeQueue::new(BridgeQueueConfig {
+        capacity: 1,
+        overflow_policy: OverflowPolicy::Reject,
+    });
+    let (first, _first_rx) = message(1, "tools/call");
+    let (second, second_rx) = message(2, "tools/call");
+    queue.push(first, None);
+    queue.push(second, None);
+
+    let rejected = second_rx.await.expect("overflow response expected");
+    assert_eq!(rejected["error"]["code"], BRIDGE_OVERLOADED_CODE);
+    assert_eq!(rejected["id"], 2);
+    assert_eq!(queue.len(), 1);
+}
+
+#[tokio::test]
+async fn test_shed_oldest_policy_drops_lower_priority() {
+    let queue = BridgeQueue::new(BridgeQueueConfig {
+        capacity: 1,
+        overflow_policy: OverflowPolicy::ShedOldest,
+    });
+    let (call, call_rx) = message(1, "tools/call");
+    let (ping, _ping_rx) = message(2, "ping");
+    queue.push(call, None);
+    queue.push(ping, None);
+
+    let shed = call_rx.await.expect("shed response expected");
+    assert_eq!(shed["error"]["code"], BRIDGE_OVERLOADED_CODE);
+
+    let ((kept, _, _), _) = queue.pop().await.expect("queue should not be empty");
+    assert_eq!(kept["method"], "ping");
+}
+
+#[test]
+fn test_traceparent_parsing() {
+    use sweetmcp::mcp_bridge::parse_traceparent;
+
+    assert_eq!(
+        parse_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+    assert_eq!(
+        parse_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+        None
+    );
+    assert_eq!(parse_traceparent("00-4BF92F35-00f067aa0ba902b7-01"), None);
+    assert_eq!(parse_traceparent("not a traceparent"), None);
+}
+
+#[test]
+fn test_trace_id_from_meta() {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "fs",
+            "_meta": {"traceparent": "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"}
+        }
+    });
+    let ctx = ProtocolContext::new(Proto::JsonRpc, "1".to_string());
+    assert_eq!(
+        sweetmcp::mcp_bridge::trace_id(&request, &ctx).as_deref(),
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+}
+
+#[test]
+fn test_tool_error_classification() {
+    use sweetmcp::mcp_bridge::tool_error;
+
+    let not_found = tool_error(&json!({"error": {"code": -32601, "message": "no such tool"}}))
+        .expect("error response");
+    assert_eq!(not_found.code, Some(-32601));
+    assert_eq!(not_found.class, "method_not_found");
+
+    let flagged = tool_error(&json!({"result": {"content": [], "isError": true}}))
+        .expect("isError result");
+    assert_eq!(flagged.code, None);
+    assert_eq!(flagged.class, "tool_error");
+
+    assert!(tool_error(&json!({"result": {"content": []}})).is_none());
+}
@@ -1,10 +1,19 @@
 mod auth;
 mod circuit_breaker;
+mod client_tokens;
 mod config;
 mod crypto;
 mod dns_discovery;
+mod idempotency;
+mod mcp_bridge;
 mod mdns_discovery;
 mod peer_discovery;
 mod rate_limit;
+mod session_affinity;
 mod shutdown;
-mod tls_manager;
\ No newline at end of file
+mod streaming;
+mod tenants;
+mod tls_manager;
+mod tool_cache;
+mod transform;
+mod upstream;
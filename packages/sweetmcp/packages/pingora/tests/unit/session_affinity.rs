@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use pingora_load_balancing::Backend;
+use serde_json::json;
+use sweetmcp::metric_picker::MetricPicker;
+use sweetmcp::session_affinity::{
+    MAX_PEEK_BYTES, SessionAffinityConfig, SessionSource, from_header, from_tool_arguments,
+    should_peek,
+};
+
+fn picker(ports: &[u16]) -> MetricPicker {
+    let backends: BTreeSet<Backend> = ports
+        .iter()
+        .map(|port| Backend::new(&format!("10.0.0.1:{}", port)).expect("backend"))
+        .collect();
+    MetricPicker::from_backends(&backends)
+}
+
+fn session_keys() -> impl Iterator<Item = String> {
+    (0..500).map(|n| format!("header:session-{}", n))
+}
+
+#[test]
+fn test_session_key_from_header() {
+    let key = from_header(" 1868a90c-f3a1 ").expect("key");
+    assert_eq!(key.source, SessionSource::Header);
+    assert_eq!(key.value, "1868a90c-f3a1");
+    assert!(from_header("  ").is_none());
+    assert!(from_header(&"x".repeat(256)).is_none());
+}
+
+#[test]
+fn test_session_key_from_tool_arguments() {
+    let arguments = SessionAffinityConfig::default().arguments;
+    let call = |args: serde_json::Value| {
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+               "params": {"name": "browser_click", "arguments": args}})
+        .to_string()
+    };
+
+    let key = from_tool_arguments(call(json!({"sessionId": "tab-7"})).as_bytes(), &arguments)
+        .expect("key");
+    assert_eq!(key.source, SessionSource::Argument);
+    assert_eq!(key.value, "tab-7");
+
+    let key =
+        from_tool_arguments(call(json!({"session_id": 42})).as_bytes(), &arguments).expect("key");
+    assert_eq!(key.value, "42");
+
+    assert!(
+        from_tool_arguments(call(json!({"session_id": true})).as_bytes(), &arguments).is_none()
+    );
+    assert!(from_tool_arguments(call(json!({"url": "x"})).as_bytes(), &arguments).is_none());
+    assert!(from_tool_arguments(call(json!({"session_id": "a"})).as_bytes(), &[]).is_none());
+
+    let list = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list",
+                      "params": {"arguments": {"session_id": "a"}}});
+    assert!(from_tool_arguments(list.to_string().as_bytes(), &arguments).is_none());
+    assert!(from_tool_arguments(b"not json", &arguments).is_none());
+}
+
+#[test]
+fn test_header_and_argument_sessions_hash_apart() {
+    let header = from_header("abc").expect("key");
+    let argument = from_tool_arguments(
+        br#"{"method":"tools/call","params":{"arguments":{"session_id":"abc"}}}"#,
+        &["session_id".to_string()],
+    )
+    .expect("key");
+    assert_ne!(header.ring_key(), argument.ring_key());
+}
+
+#[test]
+fn test_should_peek_only_small_json_bodies() {
+    assert!(should_peek(Some(512), Some("application/json")));
+    assert!(should_peek(Some(512), None));
+    assert!(!should_peek(Some(512), Some("application/x-capnp")));
+    assert!(!should_peek(None, Some("application/json")));
+    assert!(!should_peek(Some(0), Some("application/json")));
+    assert!(!should_peek(
+        Some(MAX_PEEK_BYTES + 1),
+        Some("application/json")
+    ));
+}
+
+#[test]
+fn test_consistent_order_is_stable_and_covers_every_backend() {
+    let first = picker(&[8001, 8002, 8003]);
+    let rebuilt = picker(&[8003, 8001, 8002]);
+
+    for key in session_keys().take(20) {
+        let order = first.consistent_order(&key);
+        assert_eq!(order.len(), 3);
+        assert_eq!(order.iter().collect::<BTreeSet<_>>().len(), 3);
+        assert_eq!(order, rebuilt.consistent_order(&key));
+        assert_eq!(first.pick_consistent(&key), Some(order[0]));
+    }
+
+    assert!(picker(&[]).pick_consistent("header:a").is_none());
+}
+
+#[test]
+fn test_consistent_hash_spreads_sessions() {
+    let four = picker(&[8001, 8002, 8003, 8004]);
+    let mut counts = std::collections::HashMap::new();
+    for key in session_keys() {
+        *counts
+            .entry(four.pick_consistent(&key).expect("backend").clone())
+            .or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 4);
+    assert!(
+        counts.values().all(|&count| count > 60),
+        "{:?}",
+        counts.values()
+    );
+}
+
+#[test]
+fn test_removing_a_backend_only_moves_its_sessions() {
+    let before = picker(&[8001, 8002, 8003]);
+    let after = picker(&[8001, 8003]);
+    let removed = Backend::new("10.0.0.1:8002").expect("backend");
+
+    for key in session_keys() {
+        let order = before.consistent_order(&key);
+        let owner = after.pick_consistent(&key).expect("backend");
+        if order[0] == &removed {
+            // Sessions of the removed peer fail over to their next peer
+            assert_eq!(owner, order[1]);
+        } else {
+            assert_eq!(owner, order[0]);
+        }
+    }
+}
@@ -93,4 +93,26 @@ async fn test_shutdown_signal_subscription() {
 
     // Should receive signal
     assert!(receiver.recv().await.is_ok());
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_drain_waits_for_bridge_messages() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let mut coordinator = ShutdownCoordinator::new(temp_dir.path().to_path_buf());
+    coordinator.set_drain_timeout(std::time::Duration::from_millis(300));
+    let coordinator = Arc::new(coordinator);
+
+    assert_eq!(coordinator.drain_phase(), DrainPhase::Running);
+    assert!(coordinator.drain_status().elapsed_ms.is_none());
+
+    // A bridge message that never completes forces the drain deadline
+    let _bridge_guard = coordinator.bridge_message_start();
+    assert_eq!(coordinator.bridge_in_flight_count(), 1);
+
+    coordinator.initiate_shutdown().await;
+
+    let status = coordinator.drain_status();
+    assert_eq!(status.phase, DrainPhase::TimedOut);
+    assert_eq!(status.in_flight_bridge_messages, 1);
+    assert_eq!(status.drain_timeout_ms, 300);
+    assert!(status.elapsed_ms.is_some());
+}
@@ -0,0 +1,77 @@
+use serde_json::{json, Value};
+use sweetmcp::streaming::{content_size, response_chunks, ResultStreamingConfig};
+
+fn screenshot_response() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "result": {
+            "isError": false,
+            "content": [
+                {"type": "text", "text": "Captured example.com"},
+                {"type": "image", "mimeType": "image/png", "data": "A".repeat(5000)},
+                {"type": "resource", "resource": {"uri": "file:///page.html", "text": "<html></html>"}}
+            ]
+        }
+    })
+}
+
+fn reassemble(response: &Value, chunk_bytes: usize) -> (Value, usize) {
+    let chunks: Vec<_> = response_chunks(response, chunk_bytes).collect();
+    assert!(chunks
+        .iter()
+        .all(|chunk| !chunk.is_empty() && chunk.len() <= chunk_bytes));
+    let body: Vec<u8> = chunks
+        .iter()
+        .flat_map(|chunk| chunk.iter().copied())
+        .collect();
+    (
+        serde_json::from_slice(&body).expect("chunks form valid JSON"),
+        chunks.len(),
+    )
+}
+
+#[test]
+fn test_chunks_reassemble_to_the_response() {
+    let response = screenshot_response();
+    let (body, count) = reassemble(&response, 1024);
+    assert_eq!(body, response);
+    assert!(count > 5);
+
+    let batch = json!([response, {"jsonrpc": "2.0", "id": 8, "result": {}}]);
+    assert_eq!(reassemble(&batch, 100).0, batch);
+
+    let error = json!({"jsonrpc": "2.0", "id": 9, "error": {"code": -32603, "message": "boom"}});
+    assert_eq!(reassemble(&error, 16).0, error);
+}
+
+#[test]
+fn test_content_size_counts_payloads() {
+    let response = screenshot_response();
+    let expected = "Captured example.com".len() + 5000 + "<html></html>".len();
+    assert_eq!(content_size(&response), expected);
+    assert_eq!(
+        content_size(&json!([response.clone(), response])),
+        expected * 2
+    );
+    assert_eq!(
+        content_size(&json!({"jsonrpc": "2.0", "id": 1, "result": {}})),
+        0
+    );
+}
+
+#[test]
+fn test_should_stream_above_threshold() {
+    let config = ResultStreamingConfig {
+        threshold_bytes: 4096,
+        ..ResultStreamingConfig::default()
+    };
+    assert!(config.should_stream(&screenshot_response()));
+    assert!(!config.should_stream(&json!({"jsonrpc": "2.0", "id": 1, "result": {"content": []}})));
+
+    let disabled = ResultStreamingConfig {
+        enabled: false,
+        ..config
+    };
+    assert!(!disabled.should_stream(&screenshot_response()));
+}
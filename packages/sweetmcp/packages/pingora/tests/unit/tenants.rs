@@ -0,0 +1,263 @@
+use std::path::Path;
+
+use serde_json::json;
+use sweetmcp::api::tenants::{handle_tenants_request, is_tenants_path};
+use sweetmcp::api::tokens::handle_client_tokens_request;
+use sweetmcp::client_tokens::{ClientTokenStore, ClientTokensConfig};
+use sweetmcp::tenants::{
+    CreateTenantRequest, TenantStore, TenantsConfig, UpdateTenantRequest, is_valid_tenant_id,
+};
+use tempfile::tempdir;
+
+fn config(dir: &Path) -> TenantsConfig {
+    TenantsConfig {
+        enabled: true,
+        store_path: dir.join("tenants.json"),
+        default_rpm: 2,
+    }
+}
+
+fn request(id: &str, tools: &[&str]) -> CreateTenantRequest {
+    CreateTenantRequest {
+        id: id.to_string(),
+        name: "Search".to_string(),
+        allowed_tools: tools.iter().map(|tool| tool.to_string()).collect(),
+        rpm: None,
+    }
+}
+
+#[test]
+fn test_created_tenant_resolves_and_survives_reopen() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = TenantStore::open(config(dir.path())).unwrap();
+    let tenant = store
+        .create(request("search", &["fetch"]))
+        .unwrap()
+        .unwrap();
+    assert_eq!(store.resolve("search"), Some(tenant.clone()));
+    assert_eq!(store.resolve("missing"), None);
+
+    // Ids are unique
+    assert!(
+        store
+            .create(request("search", &["fetch"]))
+            .unwrap()
+            .is_none()
+    );
+
+    let reopened = TenantStore::open(config(dir.path())).unwrap();
+    assert_eq!(reopened.list(), vec![tenant]);
+}
+
+#[test]
+fn test_suspended_and_removed_tenants_do_not_resolve() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = TenantStore::open(config(dir.path())).unwrap();
+    store.create(request("search", &["fetch"])).unwrap();
+
+    let suspend = UpdateTenantRequest {
+        suspended: Some(true),
+        ..UpdateTenantRequest::default()
+    };
+    let suspended = store.update("search", suspend).unwrap().unwrap();
+    assert!(suspended.suspended_at.is_some());
+    assert_eq!(store.resolve("search"), None);
+    assert!(store.get("search").is_some());
+
+    let reinstate = UpdateTenantRequest {
+        suspended: Some(false),
+        rpm: Some(10),
+        ..UpdateTenantRequest::default()
+    };
+    let reinstated = store.update("search", reinstate).unwrap().unwrap();
+    assert_eq!(reinstated.rpm, Some(10));
+    assert!(store.resolve("search").is_some());
+
+    assert!(store.remove("search").unwrap().is_some());
+    assert!(store.remove("search").unwrap().is_none());
+    assert!(
+        store
+            .update("search", UpdateTenantRequest::default())
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        TenantStore::open(config(dir.path()))
+            .unwrap()
+            .list()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_unreadable_store_is_an_error() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    std::fs::write(dir.path().join("tenants.json"), "not json").unwrap();
+    assert!(TenantStore::open(config(dir.path())).is_err());
+}
+
+#[test]
+fn test_tenant_tools_and_tool_lists() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = TenantStore::open(config(dir.path())).unwrap();
+    let tenant = store
+        .create(request("search", &["fetch", "github__*"]))
+        .unwrap()
+        .unwrap();
+
+    assert!(tenant.allows_tool("github__search_issues"));
+    assert!(!tenant.allows_tool("eval_sh"));
+    let batch = br#"[
+        {"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"fetch"}},
+        {"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"eval_sh"}}
+    ]"#;
+    assert_eq!(tenant.first_denied_tool(batch), Some("eval_sh".to_string()));
+
+    let mut listing = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {"tools": [
+            {"name": "fetch"},
+            {"name": "eval_sh"},
+            {"name": "github__search_issues"}
+        ]}
+    });
+    tenant.filter_tool_lists(&mut listing);
+    assert_eq!(
+        listing["result"]["tools"],
+        json!([{"name": "fetch"}, {"name": "github__search_issues"}])
+    );
+
+    let mut call = json!([{"jsonrpc": "2.0", "id": 2, "result": {"content": []}}]);
+    let unchanged = call.clone();
+    tenant.filter_tool_lists(&mut call);
+    assert_eq!(call, unchanged);
+}
+
+#[test]
+fn test_quota_is_shared_per_tenant() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = TenantStore::open(config(dir.path())).unwrap();
+    let search = store
+        .create(request("search", &["fetch"]))
+        .unwrap()
+        .unwrap();
+    let billing = store
+        .create(CreateTenantRequest {
+            rpm: Some(1),
+            ..request("billing", &["fetch"])
+        })
+        .unwrap()
+        .unwrap();
+
+    assert!(store.check_rate(&search));
+    assert!(store.check_rate(&search));
+    assert!(!store.check_rate(&search));
+    assert!(store.check_rate(&billing));
+    assert!(!store.check_rate(&billing));
+}
+
+#[test]
+fn test_request_problems() {
+    assert!(request("search-team_2", &["fetch"]).problem().is_none());
+    assert!(request("Search", &["fetch"]).problem().is_some());
+    assert!(request("search", &[]).problem().is_some());
+    assert!(
+        CreateTenantRequest {
+            rpm: Some(0),
+            ..request("search", &["fetch"])
+        }
+        .problem()
+        .is_some()
+    );
+    assert!(!is_valid_tenant_id(""));
+    assert!(!is_valid_tenant_id(&"a".repeat(65)));
+
+    let bad_tools = UpdateTenantRequest {
+        allowed_tools: Some(vec!["f*s".to_string()]),
+        ..UpdateTenantRequest::default()
+    };
+    assert!(bad_tools.problem().is_some());
+    assert!(UpdateTenantRequest::default().problem().is_none());
+}
+
+#[test]
+fn test_admin_handler_manages_tenants() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let store = TenantStore::open(config(dir.path())).unwrap();
+    assert!(is_tenants_path("/admin/tenants"));
+    assert!(is_tenants_path("/admin/tenants/search"));
+    assert!(!is_tenants_path("/admin/tenantsx"));
+
+    let body = br#"{"id":"search","name":"Search","allowed_tools":["fetch"]}"#;
+    assert_eq!(
+        handle_tenants_request(&store, "POST", "/admin/tenants", body).0,
+        201
+    );
+    assert_eq!(
+        handle_tenants_request(&store, "POST", "/admin/tenants", body).0,
+        409
+    );
+
+    let (status, body) = handle_tenants_request(
+        &store,
+        "PATCH",
+        "/admin/tenants/search",
+        br#"{"suspended":true}"#,
+    );
+    assert_eq!(status, 200);
+    let updated: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert!(updated["suspended_at"].is_u64());
+
+    let (status, body) = handle_tenants_request(&store, "GET", "/admin/tenants", b"");
+    assert_eq!(status, 200);
+    assert!(body.contains("\"search\""));
+
+    assert_eq!(
+        handle_tenants_request(&store, "GET", "/admin/tenants/missing", b"").0,
+        404
+    );
+    assert_eq!(
+        handle_tenants_request(&store, "DELETE", "/admin/tenants/search", b"").0,
+        200
+    );
+    assert_eq!(
+        handle_tenants_request(&store, "PUT", "/admin/tenants/search", b"{}").0,
+        405
+    );
+
+    let disabled = TenantStore::open(TenantsConfig::default()).unwrap();
+    assert_eq!(
+        handle_tenants_request(&disabled, "GET", "/admin/tenants", b"").0,
+        404
+    );
+}
+
+#[test]
+fn test_client_tokens_belong_to_existing_tenants() {
+    let dir = tempdir().expect("Failed to create temp directory");
+    let tenants = TenantStore::open(config(dir.path())).unwrap();
+    tenants.create(request("search", &["fetch"])).unwrap();
+    let tokens = ClientTokenStore::open(ClientTokensConfig {
+        store_path: dir.path().join("client_tokens.json"),
+        ..ClientTokensConfig::default()
+    })
+    .unwrap();
+
+    let issue = |body: &[u8]| {
+        handle_client_tokens_request(&tokens, &tenants, "POST", "/admin/tokens", body)
+    };
+    assert_eq!(issue(br#"{"name":"ci","allowed_tools":["fetch"]}"#).0, 400);
+    assert_eq!(
+        issue(br#"{"name":"ci","allowed_tools":["fetch"],"tenant":"billing"}"#).0,
+        400
+    );
+
+    let (status, body) = issue(br#"{"name":"ci","allowed_tools":["fetch"],"tenant":"search"}"#);
+    assert_eq!(status, 201);
+    let issued: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let client = tokens
+        .authenticate(issued["token"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(client.tenant.as_deref(), Some("search"));
+}
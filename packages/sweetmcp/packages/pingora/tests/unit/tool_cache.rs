@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use sweetmcp::tool_cache::{
+    bridge_request, is_cacheable, tools_etag, tools_list_response, ToolListCache,
+    ToolListCacheConfig,
+};
+
+fn tools() -> Vec<Value> {
+    vec![
+        json!({"name": "fetch", "inputSchema": {"type": "object"}}),
+        json!({"name": "github__create_issue", "inputSchema": {"type": "object"}}),
+    ]
+}
+
+#[test]
+fn test_cache_serves_stored_list_until_invalidated() {
+    let cache = ToolListCache::new(ToolListCacheConfig::default());
+    assert!(cache.get().is_none());
+
+    let stored = cache.store(cache.generation(), tools());
+    assert_eq!(cache.get(), Some(stored));
+
+    cache.invalidate();
+    assert!(cache.get().is_none());
+}
+
+#[test]
+fn test_fetch_racing_an_invalidation_is_not_stored() {
+    let cache = ToolListCache::new(ToolListCacheConfig::default());
+    let generation = cache.generation();
+    cache.invalidate();
+
+    let fetched = cache.store(generation, tools());
+    assert_eq!(fetched.etag, tools_etag(&tools()));
+    assert!(cache.get().is_none());
+}
+
+#[test]
+fn test_disabled_or_expired_cache_is_bypassed() {
+    let disabled = ToolListCache::new(ToolListCacheConfig {
+        enabled: false,
+        ..Default::default()
+    });
+    disabled.store(disabled.generation(), tools());
+    assert!(disabled.get().is_none());
+
+    let expired = ToolListCache::new(ToolListCacheConfig {
+        enabled: true,
+        ttl: Duration::ZERO,
+    });
+    expired.store(expired.generation(), tools());
+    assert!(expired.get().is_none());
+}
+
+#[test]
+fn test_etag_tracks_tool_list_contents() {
+    let mut changed = tools();
+    changed.pop();
+
+    assert_eq!(tools_etag(&tools()), tools_etag(&tools()));
+    assert_ne!(tools_etag(&tools()), tools_etag(&changed));
+}
+
+#[test]
+fn test_conditional_tools_list_response() {
+    let cache = ToolListCache::new(ToolListCacheConfig::default());
+    let cached = cache.store(cache.generation(), tools());
+
+    let full = tools_list_response(
+        &json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}),
+        &cached,
+    );
+    assert_eq!(full["id"], 1);
+    assert_eq!(full["result"]["tools"].as_array().map(Vec::len), Some(2));
+    assert_eq!(full["result"]["_meta"]["etag"], cached.etag.as_str());
+    assert!(full["result"]["_meta"].get("notModified").is_none());
+
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "tools/list",
+        "params": {"_meta": {"ifNoneMatch": cached.etag}}
+    });
+    let unchanged = tools_list_response(&request, &cached);
+    assert_eq!(unchanged["id"], 2);
+    assert_eq!(unchanged["result"]["tools"], json!([]));
+    assert_eq!(unchanged["result"]["_meta"]["notModified"], true);
+
+    let stale = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/list",
+        "params": {"_meta": {"ifNoneMatch": "outdated"}}
+    });
+    assert_eq!(
+        tools_list_response(&stale, &cached)["result"]["tools"]
+            .as_array()
+            .map(Vec::len),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_edge_routes_tool_list_traffic_to_the_bridge() {
+    assert!(bridge_request(br#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#).is_some());
+    assert!(
+        bridge_request(br#"{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}"#)
+            .is_some()
+    );
+
+    // Later pages and other methods are proxied as before
+    let paged =
+        json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {"cursor": "abc"}});
+    assert!(!is_cacheable(&paged));
+    assert!(bridge_request(&serde_json::to_vec(&paged).unwrap_or_default()).is_none());
+    assert!(bridge_request(br#"{"jsonrpc":"2.0","id":1,"method":"tools/call"}"#).is_none());
+    assert!(bridge_request(b"query { tools }").is_none());
+}
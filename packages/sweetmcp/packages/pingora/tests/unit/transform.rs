@@ -0,0 +1,109 @@
+use serde_json::{Value, json};
+use sweetmcp::transform::{Change, TransformConfig, TransformRule};
+
+fn rule(tool: &str, rule: Value) -> TransformRule {
+    let mut rule = rule;
+    rule["tool"] = json!(tool);
+    serde_json::from_value(rule).expect("rule")
+}
+
+fn call(tool: &str, arguments: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+           "params": {"name": tool, "arguments": arguments}})
+}
+
+#[test]
+fn test_rules_fill_set_strip_and_tag_matching_calls() {
+    let config = TransformConfig {
+        dry_run: false,
+        rules: vec![
+            rule(
+                "fetch",
+                json!({"defaults": {"proxy": "http://proxy:3128", "timeout": 30},
+                       "strip": ["headers"],
+                       "meta": {"tenant": "acme"}}),
+            ),
+            rule("*", json!({"set": {"audit": true}})),
+        ],
+    };
+
+    let mut request = call(
+        "fetch",
+        json!({"url": "https://example.com", "timeout": 5, "headers": {"x": "y"}}),
+    );
+    let changes = config.apply(&mut request);
+    assert_eq!(
+        changes,
+        [
+            Change::Default("proxy".to_string()),
+            Change::Strip("headers".to_string()),
+            Change::Meta("tenant".to_string()),
+            Change::Set("audit".to_string()),
+        ]
+    );
+    assert_eq!(
+        request["params"]["arguments"],
+        json!({"url": "https://example.com", "timeout": 5,
+               "proxy": "http://proxy:3128", "audit": true})
+    );
+    assert_eq!(request["params"]["_meta"], json!({"tenant": "acme"}));
+
+    // Applying again changes nothing
+    assert!(config.apply(&mut request).is_empty());
+}
+
+#[test]
+fn test_rules_match_names_and_prefixes() {
+    let exact = rule("fs_read", json!({"set": {"root": "/srv"}}));
+    assert!(exact.matches("fs_read"));
+    assert!(!exact.matches("fs_read_dir"));
+
+    let prefix = rule("fs_*", json!({"set": {"root": "/srv"}}));
+    assert!(prefix.matches("fs_read_dir"));
+    assert!(!prefix.matches("fetch"));
+
+    let config = TransformConfig {
+        dry_run: false,
+        rules: vec![prefix],
+    };
+    let mut other = call("fetch", json!({}));
+    assert!(config.apply(&mut other).is_empty());
+
+    let mut list = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list",
+                          "params": {"name": "fs_read"}});
+    assert!(config.apply(&mut list).is_empty());
+
+    let mut without_arguments =
+        json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "fs_read"}});
+    assert_eq!(config.apply(&mut without_arguments).len(), 1);
+    assert_eq!(without_arguments["params"]["arguments"]["root"], "/srv");
+}
+
+#[test]
+fn test_dry_run_leaves_requests_unmodified() {
+    let mut config = TransformConfig {
+        dry_run: true,
+        rules: vec![rule("fetch", json!({"strip": ["headers"]}))],
+    };
+    let body = serde_json::to_vec(&call("fetch", json!({"headers": {}}))).unwrap();
+
+    assert!(config.transform_body(&body).is_none());
+
+    config.dry_run = false;
+    let transformed: Value =
+        serde_json::from_slice(&config.transform_body(&body).expect("transformed")).unwrap();
+    assert_eq!(transformed["params"]["arguments"], json!({}));
+    assert!(config.transform_body(b"not json").is_none());
+}
+
+#[test]
+fn test_rule_problems() {
+    assert!(rule("fetch", json!({"strip": ["a"]})).problem().is_none());
+    assert!(rule("fetch", json!({})).problem().is_some());
+    assert!(rule("", json!({"strip": ["a"]})).problem().is_some());
+    assert!(
+        rule("fs_*_dir", json!({"strip": ["a"]}))
+            .problem()
+            .is_some()
+    );
+}
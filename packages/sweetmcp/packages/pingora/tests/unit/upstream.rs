@@ -0,0 +1,72 @@
+use sweetmcp::upstream::{
+    UpstreamRegistry, UpstreamServerConfig, UpstreamTransport, namespaced_tool_name,
+    split_namespaced_tool,
+};
+
+fn http_upstream(name: &str, prefix: Option<&str>) -> UpstreamServerConfig {
+    UpstreamServerConfig {
+        name: name.to_string(),
+        prefix: prefix.map(str::to_string),
+        transport: UpstreamTransport::Http {
+            url: "https://mcp.example.com/rpc".to_string(),
+            headers: Default::default(),
+        },
+        timeout_secs: 30,
+    }
+}
+
+#[test]
+fn test_namespaced_tool_round_trip() {
+    let name = namespaced_tool_name("github", "create_issue");
+    assert_eq!(name, "github__create_issue");
+    assert_eq!(split_namespaced_tool(&name), Some(("github", "create_issue")));
+
+    assert_eq!(split_namespaced_tool("plain_tool"), None);
+    assert_eq!(split_namespaced_tool("__tool"), None);
+}
+
+#[test]
+fn test_upstream_config_parsing() -> Result<(), Box<dyn std::error::Error>> {
+    let raw = r#"[
+        {"name": "fs", "transport": "stdio", "command": "mcp-fs", "args": ["--root", "/tmp"]},
+        {"name": "remote", "prefix": "gh", "transport": "http", "url": "https://mcp.example.com/rpc"}
+    ]"#;
+    let configs: Vec<UpstreamServerConfig> = serde_json::from_str(raw)?;
+
+    assert_eq!(configs.len(), 2);
+    assert_eq!(configs[0].namespace(), "fs");
+    assert_eq!(configs[1].namespace(), "gh");
+    assert_eq!(configs[0].timeout_secs, 30);
+    assert!(matches!(configs[0].transport, UpstreamTransport::Stdio { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_upstream_validation() {
+    assert!(http_upstream("remote", None).validate().is_ok());
+    assert!(http_upstream("remote", Some("bad__prefix")).validate().is_err());
+    assert!(http_upstream("", None).validate().is_err());
+}
+
+#[test]
+fn test_registry_resolution() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = UpstreamRegistry::from_configs(&[
+        http_upstream("remote", Some("gh")),
+        http_upstream("other", None),
+    ])?;
+
+    assert_eq!(registry.len(), 2);
+    let (server, tool) = registry.resolve("gh__search").ok_or("tool not resolved")?;
+    assert_eq!(server.name(), "remote");
+    assert_eq!(tool, "search");
+    assert!(registry.resolve("unknown__search").is_none());
+
+    let duplicate = UpstreamRegistry::from_configs(&[
+        http_upstream("a", Some("same")),
+        http_upstream("b", Some("same")),
+    ]);
+    assert!(duplicate.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,303 @@
+//! Compatibility checks between two versions of a tool
+//!
+//! Clients build calls from the schema they listed. A change that makes one
+//! of those calls fail or mean something else is breaking: a tool or
+//! parameter that disappears, a parameter whose type changes or that becomes
+//! required, an enum value that is dropped. New optional parameters, extra
+//! enum values and deprecations are compatible.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{ListToolsResult, ToolDescription};
+
+/// Whether a change breaks existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Breaking,
+    Compatible,
+}
+
+/// One difference between two versions of a tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub tool: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Every difference found, breaking or not
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompatReport {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl CompatReport {
+    /// Whether any change breaks existing clients
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity == Severity::Breaking)
+    }
+
+    fn push(&mut self, tool: &str, severity: Severity, message: String) {
+        self.changes.push(SchemaChange {
+            tool: tool.to_string(),
+            severity,
+            message,
+        });
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for change in &self.changes {
+            let severity = match change.severity {
+                Severity::Breaking => "breaking",
+                Severity::Compatible => "compatible",
+            };
+            writeln!(f, "{}: {}: {}", severity, change.tool, change.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Diff two tool lists, such as the `describe` output of two plugin releases
+pub fn check_tools(old: &ListToolsResult, new: &ListToolsResult) -> CompatReport {
+    let new_tools: BTreeMap<&str, &ToolDescription> = new
+        .tools
+        .iter()
+        .map(|tool| (tool.name.as_str(), tool))
+        .collect();
+    let old_names: BTreeSet<&str> = old.tools.iter().map(|tool| tool.name.as_str()).collect();
+
+    let mut report = CompatReport::default();
+    for tool in &old.tools {
+        match new_tools.get(tool.name.as_str()) {
+            Some(new_tool) => report.changes.extend(check_tool(tool, new_tool).changes),
+            None => report.push(
+                &tool.name,
+                Severity::Breaking,
+                "tool was removed".to_string(),
+            ),
+        }
+    }
+    for tool in &new.tools {
+        if !old_names.contains(tool.name.as_str()) {
+            report.push(&tool.name, Severity::Compatible, "new tool".to_string());
+        }
+    }
+    report
+}
+
+/// Diff two versions of one tool
+///
+/// Breaking changes without a major version bump are themselves reported
+/// as breaking, so a release pipeline can fail on [`CompatReport::is_breaking`].
+pub fn check_tool(old: &ToolDescription, new: &ToolDescription) -> CompatReport {
+    let mut report = CompatReport::default();
+    let tool = new.name.as_str();
+    if old.name != new.name {
+        report.push(
+            tool,
+            Severity::Breaking,
+            format!("tool was renamed from `{}`", old.name),
+        );
+    }
+
+    check_schema(&mut report, tool, &old.input_schema, &new.input_schema);
+
+    if new.meta.deprecated && !old.meta.deprecated {
+        let message = match &new.meta.replaced_by {
+            Some(replacement) => format!("tool is deprecated in favour of `{}`", replacement),
+            None => "tool is deprecated".to_string(),
+        };
+        report.push(tool, Severity::Compatible, message);
+    }
+
+    if report.is_breaking() {
+        let old_major = old.meta.version.as_deref().and_then(major_version);
+        let new_major = new.meta.version.as_deref().and_then(major_version);
+        match (old_major, new_major) {
+            (Some(old_major), Some(new_major)) if new_major > old_major => {}
+            (_, None) => report.push(
+                tool,
+                Severity::Breaking,
+                "breaking changes need a version; set one with DescriptionBuilder::version"
+                    .to_string(),
+            ),
+            _ => report.push(
+                tool,
+                Severity::Breaking,
+                format!(
+                    "breaking changes need a major version bump ({} -> {})",
+                    old.meta.version.as_deref().unwrap_or("unversioned"),
+                    new.meta.version.as_deref().unwrap_or("unversioned"),
+                ),
+            ),
+        }
+    }
+    report
+}
+
+fn check_schema(report: &mut CompatReport, tool: &str, old: &Value, new: &Value) {
+    let old_properties = properties(old);
+    let new_properties = properties(new);
+    let old_required = required(old);
+    let new_required = required(new);
+
+    for (name, old_property) in &old_properties {
+        let Some(new_property) = new_properties.get(name) else {
+            report.push(
+                tool,
+                Severity::Breaking,
+                format!("parameter `{}` was removed", name),
+            );
+            continue;
+        };
+        check_property(report, tool, name, old_property, new_property);
+
+        match (old_required.contains(*name), new_required.contains(*name)) {
+            (false, true) => report.push(
+                tool,
+                Severity::Breaking,
+                format!("parameter `{}` is now required", name),
+            ),
+            (true, false) => report.push(
+                tool,
+                Severity::Compatible,
+                format!("parameter `{}` is now optional", name),
+            ),
+            _ => {}
+        }
+    }
+
+    for name in new_properties.keys() {
+        if old_properties.contains_key(name) {
+            continue;
+        }
+        if new_required.contains(*name) {
+            report.push(
+                tool,
+                Severity::Breaking,
+                format!("new required parameter `{}`", name),
+            );
+        } else {
+            report.push(
+                tool,
+                Severity::Compatible,
+                format!("new optional parameter `{}`", name),
+            );
+        }
+    }
+}
+
+fn check_property(report: &mut CompatReport, tool: &str, name: &str, old: &Value, new: &Value) {
+    let old_type = old.get("type");
+    let new_type = new.get("type");
+    if old_type != new_type {
+        report.push(
+            tool,
+            Severity::Breaking,
+            format!(
+                "parameter `{}` changed type from {} to {}",
+                name,
+                old_type.unwrap_or(&Value::Null),
+                new_type.unwrap_or(&Value::Null),
+            ),
+        );
+    }
+
+    match (enum_values(old), enum_values(new)) {
+        (Some(old_values), Some(new_values)) => {
+            for value in old_values.iter().filter(|v| !new_values.contains(v)) {
+                report.push(
+                    tool,
+                    Severity::Breaking,
+                    format!("parameter `{}` no longer accepts {}", name, value),
+                );
+            }
+            for value in new_values.iter().filter(|v| !old_values.contains(v)) {
+                report.push(
+                    tool,
+                    Severity::Compatible,
+                    format!("parameter `{}` now accepts {}", name, value),
+                );
+            }
+        }
+        (None, Some(_)) => report.push(
+            tool,
+            Severity::Breaking,
+            format!("parameter `{}` is now restricted to a set of values", name),
+        ),
+        (Some(_), None) => report.push(
+            tool,
+            Severity::Compatible,
+            format!("parameter `{}` accepts any value", name),
+        ),
+        (None, None) => {}
+    }
+
+    if is_deprecated(new) && !is_deprecated(old) {
+        report.push(
+            tool,
+            Severity::Compatible,
+            format!("parameter `{}` is deprecated", name),
+        );
+    }
+}
+
+/// Parameters by name, so changes are reported in a stable order
+fn properties(schema: &Value) -> BTreeMap<&str, &Value> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| (name.as_str(), property))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn required(schema: &Value) -> BTreeSet<String> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn enum_values(property: &Value) -> Option<&Vec<Value>> {
+    property.get("enum").and_then(Value::as_array)
+}
+
+fn is_deprecated(property: &Value) -> bool {
+    property.get("deprecated").and_then(Value::as_bool) == Some(true)
+}
+
+/// Major version of "2.1.0" or "v2"
+fn major_version(version: &str) -> Option<u64> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
@@ -4,14 +4,19 @@
 
 use std::marker::PhantomData;
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use extism_pdk::*;
 use log::{debug, error, info, trace};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+pub mod compat;
+
 pub mod prelude {
     pub use super::{
-        ContentBuilder, DescriptionBuilder, McpPlugin, McpTool, SchemaBuilder, mcp_plugin,
+        ContentBuilder, ContentParts, DescriptionBuilder, McpPlugin, McpTool, SchemaBuilder,
+        ToolMetadata, mcp_plugin,
     };
 }
 
@@ -41,6 +46,8 @@ pub struct Content {
     pub mime_type: Option<String>,
     pub data: Option<String>,
     pub annotations: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<EmbeddedResource>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -50,6 +57,111 @@ pub enum ContentType {
     Text,
     #[serde(rename = "image")]
     Image,
+    #[serde(rename = "audio")]
+    Audio,
+    #[serde(rename = "resource")]
+    Resource,
+}
+
+/// Resource contents embedded in a tool result, as text or a base64 blob
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedResource {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+impl EmbeddedResource {
+    /// Text resource
+    pub fn text(
+        uri: impl Into<String>,
+        mime_type: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            text: Some(text.into()),
+            blob: None,
+        }
+    }
+
+    /// Binary resource, base64 encoded
+    pub fn blob(uri: impl Into<String>, mime_type: impl Into<String>, bytes: &[u8]) -> Self {
+        Self {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            text: None,
+            blob: Some(BASE64.encode(bytes)),
+        }
+    }
+}
+
+impl Content {
+    /// Plain text part
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::text_as(text, "text/plain")
+    }
+
+    /// Text part of another mime type, such as markdown or JSON
+    pub fn text_as(text: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self {
+            r#type: ContentType::Text,
+            text: Some(text.into()),
+            mime_type: Some(mime_type.into()),
+            data: None,
+            annotations: None,
+            resource: None,
+        }
+    }
+
+    /// Structured JSON part
+    pub fn json(value: &Value) -> Self {
+        Self::text_as(value.to_string(), "application/json")
+    }
+
+    /// Image part from raw bytes
+    pub fn image(bytes: &[u8], mime_type: impl Into<String>) -> Self {
+        Self::image_base64(BASE64.encode(bytes), mime_type)
+    }
+
+    /// Image part from data that is already base64 encoded
+    pub fn image_base64(data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self::binary(ContentType::Image, data, mime_type)
+    }
+
+    /// Audio part from data that is already base64 encoded
+    pub fn audio_base64(data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self::binary(ContentType::Audio, data, mime_type)
+    }
+
+    /// Embedded resource part
+    pub fn resource(resource: EmbeddedResource) -> Self {
+        Self {
+            r#type: ContentType::Resource,
+            text: None,
+            mime_type: resource.mime_type.clone(),
+            data: None,
+            annotations: None,
+            resource: Some(resource),
+        }
+    }
+
+    fn binary(r#type: ContentType, data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self {
+            r#type,
+            text: None,
+            mime_type: Some(mime_type.into()),
+            data: Some(data.into()),
+            annotations: None,
+            resource: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +174,36 @@ pub struct ToolDescription {
     pub name: String,
     pub description: String,
     pub input_schema: Value,
+    /// Version and deprecation, listed as the tool's `_meta`
+    #[serde(
+        rename = "_meta",
+        default,
+        skip_serializing_if = "ToolMetadata::is_empty"
+    )]
+    pub meta: ToolMetadata,
+}
+
+/// Version and deprecation of a tool's interface
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolMetadata {
+    /// Semantic version of the tool's schema, e.g. "2.1.0"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_reason: Option<String>,
+    /// Tool clients should move to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
+}
+
+impl ToolMetadata {
+    /// Whether there is nothing to list
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Type states for compile-time safety
@@ -82,6 +224,7 @@ struct ToolDef {
     name: String,
     description: String,
     schema: Value,
+    meta: ToolMetadata,
     handler: Box<dyn Fn(Value) -> Result<CallToolResult, Error> + Send + Sync>,
 }
 
@@ -116,10 +259,15 @@ impl McpPlugin<Described> {
     pub fn tool<T: McpTool>(mut self) -> Self {
         debug!("Registering tool: {}", T::NAME);
         let description = T::description(DescriptionBuilder::default());
+        let meta = description.metadata();
+        if meta.deprecated {
+            info!("Tool '{}' is deprecated", T::NAME);
+        }
         self.tools.push(ToolDef {
             name: T::NAME.to_string(),
             description: description.build(),
             schema: T::schema(SchemaBuilder::default()),
+            meta,
             handler: Box::new(T::execute),
         });
         self
@@ -171,6 +319,7 @@ impl McpPlugin<Ready> {
                     name: tool.name.clone(),
                     description: tool.description.clone(),
                     input_schema: tool.schema.clone(),
+                    meta: tool.meta.clone(),
                 }
             })
             .collect();
@@ -198,6 +347,9 @@ pub struct DescriptionBuilder {
     prerequisites: Vec<String>,
     limitations: Vec<String>,
     always_use_for: Vec<String>,
+    version: Option<String>,
+    deprecation_reason: Option<String>,
+    replaced_by: Option<String>,
 }
 
 impl DescriptionBuilder {
@@ -243,10 +395,55 @@ impl DescriptionBuilder {
         self
     }
 
+    /// Version of the tool's schema, e.g. "2.1.0"
+    ///
+    /// Bump the major version for changes [`compat::check_tool`] reports as
+    /// breaking.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Deprecate the tool, saying why
+    pub fn deprecated(mut self, reason: impl Into<String>) -> Self {
+        self.deprecation_reason = Some(reason.into());
+        self
+    }
+
+    /// Deprecate the tool in favour of `tool`
+    pub fn replaced_by(mut self, tool: impl Into<String>) -> Self {
+        self.replaced_by = Some(tool.into());
+        self
+    }
+
+    /// Version and deprecation listed with the tool
+    pub fn metadata(&self) -> ToolMetadata {
+        ToolMetadata {
+            version: self.version.clone(),
+            deprecated: self.deprecation_reason.is_some() || self.replaced_by.is_some(),
+            deprecation_reason: self.deprecation_reason.clone(),
+            replaced_by: self.replaced_by.clone(),
+        }
+    }
+
     /// Build the description following MCP best practices
+    ///
+    /// Deprecated tools lead with a notice so models pick the replacement.
     pub fn build(self) -> String {
         let mut parts = Vec::new();
 
+        if self.deprecation_reason.is_some() || self.replaced_by.is_some() {
+            let mut notice = "DEPRECATED".to_string();
+            if let Some(reason) = &self.deprecation_reason {
+                notice.push_str(&format!(": {}", reason.trim_end_matches('.')));
+            }
+            notice.push('.');
+            if let Some(tool) = &self.replaced_by {
+                notice.push_str(&format!(" Use `{}` instead.", tool));
+            }
+            parts.push(notice);
+        }
+
         if let Some(primary) = self.primary_function {
             parts.push(format!("{}.", primary));
         }
@@ -387,6 +584,40 @@ impl SchemaBuilder {
         self
     }
 
+    /// Required integer parameter
+    pub fn required_integer(mut self, name: impl Into<String>, desc: impl Into<String>) -> Self {
+        let name = name.into();
+        self.properties.insert(
+            name.clone(),
+            serde_json::json!({
+                "type": "integer",
+                "description": desc.into()
+            }),
+        );
+        self.required.push(name);
+        self
+    }
+
+    /// Mark the parameter `name`, added earlier, as deprecated
+    ///
+    /// Sets the JSON Schema `deprecated` keyword and notes `reason` in the
+    /// parameter's description.
+    pub fn deprecated(mut self, name: &str, reason: impl Into<String>) -> Self {
+        match self.properties.get_mut(name).and_then(Value::as_object_mut) {
+            Some(property) => {
+                let reason = reason.into();
+                let description = match property.get("description").and_then(Value::as_str) {
+                    Some(description) => format!("{} (deprecated: {})", description, reason),
+                    None => format!("Deprecated: {}", reason),
+                };
+                property.insert("description".to_string(), Value::String(description));
+                property.insert("deprecated".to_string(), Value::Bool(true));
+            }
+            None => debug!("Cannot deprecate unknown parameter: {}", name),
+        }
+        self
+    }
+
     /// Build the schema
     pub fn build(self) -> Value {
         serde_json::json!({
@@ -403,43 +634,104 @@ pub struct ContentBuilder;
 impl ContentBuilder {
     /// Successful text response
     pub fn text(content: impl Into<String>) -> CallToolResult {
-        CallToolResult {
-            is_error: Some(false),
-            content: vec![Content {
-                r#type: ContentType::Text,
-                text: Some(content.into()),
-                mime_type: Some("text/plain".into()),
-                data: None,
-                annotations: None,
-            }],
-        }
+        Self::parts().text(content).build()
     }
 
     /// Error response
     pub fn error(message: impl Into<String>) -> CallToolResult {
-        CallToolResult {
-            is_error: Some(true),
-            content: vec![Content {
-                r#type: ContentType::Text,
-                text: Some(message.into()),
-                mime_type: Some("text/plain".into()),
-                data: None,
-                annotations: None,
-            }],
-        }
+        Self::parts().text(message).is_error(true).build()
     }
 
     /// Base64 data response
     pub fn data(data: impl Into<String>, mime_type: impl Into<String>) -> CallToolResult {
+        Self::parts()
+            .part(Content::image_base64(data, mime_type))
+            .build()
+    }
+
+    /// Image response from raw bytes
+    pub fn image(bytes: &[u8], mime_type: impl Into<String>) -> CallToolResult {
+        Self::parts().image(bytes, mime_type).build()
+    }
+
+    /// Base64 audio response
+    pub fn audio(data: impl Into<String>, mime_type: impl Into<String>) -> CallToolResult {
+        Self::parts()
+            .part(Content::audio_base64(data, mime_type))
+            .build()
+    }
+
+    /// Structured JSON response
+    pub fn json(value: &Value) -> CallToolResult {
+        Self::parts().json(value).build()
+    }
+
+    /// Embedded resource response
+    pub fn resource(resource: EmbeddedResource) -> CallToolResult {
+        Self::parts().resource(resource).build()
+    }
+
+    /// Start a response of several parts, e.g. text plus an image plus JSON
+    pub fn parts() -> ContentParts {
+        ContentParts::default()
+    }
+}
+
+/// Multi-part response, built by chaining parts in order
+#[derive(Debug, Clone, Default)]
+pub struct ContentParts {
+    content: Vec<Content>,
+    is_error: bool,
+}
+
+impl ContentParts {
+    /// Plain text part
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.part(Content::text(text))
+    }
+
+    /// Text part of another mime type
+    pub fn text_as(self, text: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        self.part(Content::text_as(text, mime_type))
+    }
+
+    /// Structured JSON part
+    pub fn json(self, value: &Value) -> Self {
+        self.part(Content::json(value))
+    }
+
+    /// Image part from raw bytes
+    pub fn image(self, bytes: &[u8], mime_type: impl Into<String>) -> Self {
+        self.part(Content::image(bytes, mime_type))
+    }
+
+    /// Image part from base64 data
+    pub fn image_base64(self, data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        self.part(Content::image_base64(data, mime_type))
+    }
+
+    /// Embedded resource part
+    pub fn resource(self, resource: EmbeddedResource) -> Self {
+        self.part(Content::resource(resource))
+    }
+
+    /// Any prepared part
+    pub fn part(mut self, content: Content) -> Self {
+        self.content.push(content);
+        self
+    }
+
+    /// Mark the response as a tool error
+    pub fn is_error(mut self, is_error: bool) -> Self {
+        self.is_error = is_error;
+        self
+    }
+
+    /// Finish the response
+    pub fn build(self) -> CallToolResult {
         CallToolResult {
-            is_error: Some(false),
-            content: vec![Content {
-                r#type: ContentType::Image,
-                text: None,
-                data: Some(data.into()),
-                mime_type: Some(mime_type.into()),
-                annotations: None,
-            }],
+            is_error: Some(self.is_error),
+            content: self.content,
         }
     }
 }
@@ -503,4 +795,3 @@ macro_rules! try_input_json {
         }
     }};
 }
-
@@ -0,0 +1,175 @@
+use serde_json::{Value, json};
+use sweetmcp_plugin_builder::compat::{Severity, check_tool, check_tools};
+use sweetmcp_plugin_builder::{ListToolsResult, SchemaBuilder, ToolDescription, ToolMetadata};
+
+fn tool(name: &str, version: &str, schema: Value) -> ToolDescription {
+    ToolDescription {
+        name: name.to_string(),
+        description: "Hash input".to_string(),
+        input_schema: schema,
+        meta: ToolMetadata {
+            version: Some(version.to_string()),
+            ..ToolMetadata::default()
+        },
+    }
+}
+
+fn messages(
+    report: &sweetmcp_plugin_builder::compat::CompatReport,
+    severity: Severity,
+) -> Vec<&str> {
+    report
+        .changes
+        .iter()
+        .filter(|change| change.severity == severity)
+        .map(|change| change.message.as_str())
+        .collect()
+}
+
+fn v1_schema() -> Value {
+    SchemaBuilder::default()
+        .required_string("data", "Data to hash")
+        .optional_enum("algorithm", "Digest", &["sha256", "sha512", "md5"])
+        .build()
+}
+
+#[test]
+fn test_identical_tools_are_compatible() {
+    let report = check_tool(
+        &tool("hash", "1.0.0", v1_schema()),
+        &tool("hash", "1.0.0", v1_schema()),
+    );
+
+    assert!(report.changes.is_empty());
+    assert!(!report.is_breaking());
+}
+
+#[test]
+fn test_additions_are_compatible() {
+    let new = SchemaBuilder::default()
+        .required_string("data", "Data to hash")
+        .optional_enum(
+            "algorithm",
+            "Digest",
+            &["sha256", "sha512", "md5", "blake3"],
+        )
+        .optional_string("encoding", "Output encoding")
+        .build();
+    let report = check_tool(
+        &tool("hash", "1.0.0", v1_schema()),
+        &tool("hash", "1.1.0", new),
+    );
+
+    assert!(!report.is_breaking());
+    assert_eq!(
+        messages(&report, Severity::Compatible),
+        vec![
+            "parameter `algorithm` now accepts \"blake3\"",
+            "new optional parameter `encoding`"
+        ]
+    );
+}
+
+#[test]
+fn test_breaking_changes_reported() {
+    let new = json!({
+        "type": "object",
+        "properties": {
+            "data": {"type": "integer"},
+            "algorithm": {"type": "string", "enum": ["sha256", "sha512"]},
+            "key": {"type": "string"}
+        },
+        "required": ["data", "algorithm", "key"]
+    });
+    let report = check_tool(
+        &tool("hash", "1.0.0", v1_schema()),
+        &tool("hash", "2.0.0", new),
+    );
+
+    assert_eq!(
+        messages(&report, Severity::Breaking),
+        vec![
+            "parameter `algorithm` no longer accepts \"md5\"",
+            "parameter `algorithm` is now required",
+            "parameter `data` changed type from \"string\" to \"integer\"",
+            "new required parameter `key`",
+        ]
+    );
+}
+
+#[test]
+fn test_breaking_change_needs_major_bump() {
+    let new = SchemaBuilder::default()
+        .required_string("data", "Data to hash")
+        .build();
+
+    let minor = check_tool(
+        &tool("hash", "1.0.0", v1_schema()),
+        &tool("hash", "1.1.0", new.clone()),
+    );
+    assert_eq!(
+        messages(&minor, Severity::Breaking),
+        vec![
+            "parameter `algorithm` was removed",
+            "breaking changes need a major version bump (1.0.0 -> 1.1.0)"
+        ]
+    );
+
+    let major = check_tool(
+        &tool("hash", "1.0.0", v1_schema()),
+        &tool("hash", "2.0.0", new),
+    );
+    assert_eq!(
+        messages(&major, Severity::Breaking),
+        vec!["parameter `algorithm` was removed"]
+    );
+}
+
+#[test]
+fn test_deprecations_are_compatible() {
+    let new_schema = SchemaBuilder::default()
+        .required_string("data", "Data to hash")
+        .optional_enum("algorithm", "Digest", &["sha256", "sha512", "md5"])
+        .deprecated("algorithm", "always sha256")
+        .build();
+    let mut new = tool("hash", "1.1.0", new_schema);
+    new.meta.deprecated = true;
+    new.meta.replaced_by = Some("digest".to_string());
+
+    let report = check_tool(&tool("hash", "1.0.0", v1_schema()), &new);
+    assert!(!report.is_breaking());
+    assert_eq!(
+        messages(&report, Severity::Compatible),
+        vec![
+            "parameter `algorithm` is deprecated",
+            "tool is deprecated in favour of `digest`"
+        ]
+    );
+}
+
+#[test]
+fn test_tool_lists_diffed_by_name() {
+    let old = ListToolsResult {
+        tools: vec![
+            tool("hash", "1.0.0", v1_schema()),
+            tool(
+                "encode",
+                "1.0.0",
+                json!({"type": "object", "properties": {}}),
+            ),
+        ],
+    };
+    let new = ListToolsResult {
+        tools: vec![
+            tool("hash", "1.0.0", v1_schema()),
+            tool("digest", "1.0.0", v1_schema()),
+        ],
+    };
+    let report = check_tools(&old, &new);
+
+    assert!(report.is_breaking());
+    assert_eq!(
+        report.to_string(),
+        "breaking: encode: tool was removed\ncompatible: digest: new tool\n"
+    );
+}
@@ -34,3 +34,130 @@ fn test_fluent_plugin_builder() {
     assert_eq!(tools.tools.len(), 1);
     assert_eq!(tools.tools[0].name, "test");
 }
+
+#[test]
+fn test_required_integer_schema() {
+    let schema = SchemaBuilder::default()
+        .required_integer("count", "How many")
+        .optional_string("label", "Optional label")
+        .build();
+
+    assert_eq!(schema["properties"]["count"]["type"], "integer");
+    assert_eq!(schema["required"], serde_json::json!(["count"]));
+}
+
+#[test]
+fn test_audio_content() {
+    let result = ContentBuilder::audio("UklGRg==", "audio/wav");
+    let json = serde_json::to_value(&result).unwrap();
+
+    assert_eq!(json["content"][0]["type"], "audio");
+    assert_eq!(json["content"][0]["data"], "UklGRg==");
+}
+
+#[test]
+fn test_image_content_from_bytes() {
+    let result = ContentBuilder::image(b"\x89PNG", "image/png");
+    let json = serde_json::to_value(&result).unwrap();
+
+    assert_eq!(json["is_error"], false);
+    assert_eq!(json["content"][0]["type"], "image");
+    assert_eq!(json["content"][0]["data"], "iVBORw==");
+    assert_eq!(json["content"][0]["mime_type"], "image/png");
+}
+
+#[test]
+fn test_multi_part_content() {
+    let result = ContentBuilder::parts()
+        .text_as("# Title", "text/markdown")
+        .image_base64("UklGRg==", "image/png")
+        .json(&serde_json::json!({"status": 200}))
+        .resource(sweetmcp_plugin_builder::EmbeddedResource::text(
+            "file:///notes.txt",
+            "text/plain",
+            "notes",
+        ))
+        .is_error(true)
+        .build();
+    let json = serde_json::to_value(&result).unwrap();
+
+    assert_eq!(json["is_error"], true);
+    let parts = json["content"].as_array().unwrap();
+    assert_eq!(parts.len(), 4);
+    assert_eq!(parts[0]["mime_type"], "text/markdown");
+    assert_eq!(parts[1]["type"], "image");
+    assert_eq!(parts[2]["text"], r#"{"status":200}"#);
+    assert_eq!(parts[2]["mime_type"], "application/json");
+    assert_eq!(parts[3]["type"], "resource");
+    assert_eq!(parts[3]["resource"]["uri"], "file:///notes.txt");
+    assert_eq!(parts[3]["resource"]["mimeType"], "text/plain");
+    assert!(parts[3]["resource"].get("blob").is_none());
+    assert!(parts[0].get("resource").is_none());
+}
+
+struct LegacyTool;
+
+impl McpTool for LegacyTool {
+    const NAME: &'static str = "legacy";
+
+    fn description(builder: DescriptionBuilder) -> DescriptionBuilder {
+        builder
+            .does("Hash input the old way")
+            .version("1.4.0")
+            .deprecated("Weak digest")
+            .replaced_by("test")
+    }
+
+    fn schema(builder: SchemaBuilder) -> Value {
+        builder
+            .required_string("input", "Text to hash")
+            .optional_string("salt", "Salt to mix in")
+            .deprecated("salt", "ignored since 1.2")
+            .build()
+    }
+
+    fn execute(_args: Value) -> Result<sweetmcp_plugin_builder::CallToolResult, Error> {
+        Ok(ContentBuilder::text("Legacy result"))
+    }
+}
+
+#[test]
+fn test_tool_metadata_listed() {
+    let plugin = mcp_plugin("test-plugin")
+        .description("A test plugin")
+        .tool::<TestTool>()
+        .tool::<LegacyTool>()
+        .serve();
+
+    let json = serde_json::to_value(plugin.describe().unwrap()).unwrap();
+    assert!(json["tools"][0].get("_meta").is_none());
+
+    let legacy = &json["tools"][1];
+    assert_eq!(
+        legacy["_meta"],
+        serde_json::json!({
+            "version": "1.4.0",
+            "deprecated": true,
+            "deprecationReason": "Weak digest",
+            "replacedBy": "test"
+        })
+    );
+    assert!(
+        legacy["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("DEPRECATED: Weak digest. Use `test` instead.")
+    );
+}
+
+#[test]
+fn test_deprecated_schema_property() {
+    let schema = LegacyTool::schema(SchemaBuilder::default());
+
+    assert_eq!(schema["properties"]["salt"]["deprecated"], true);
+    assert_eq!(
+        schema["properties"]["salt"]["description"],
+        "Salt to mix in (deprecated: ignored since 1.2)"
+    );
+    assert!(schema["properties"]["input"].get("deprecated").is_none());
+}
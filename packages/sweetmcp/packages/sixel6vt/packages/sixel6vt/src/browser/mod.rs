@@ -1,11 +1,12 @@
 use anyhow::Result;
 use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
 use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotFormat;
 use chromiumoxide::page::ScreenshotParams;
+use chromiumoxide::Page;
 use futures::StreamExt;
-use std::sync::Arc;
 use image::RgbImage;
-use tokio::sync::mpsc::Sender;
+use tempfile::TempDir;
 
 /// A snapshot of browser content including both the rendered image and page title
 pub struct BrowserSnapshot {
@@ -13,54 +14,98 @@ pub struct BrowserSnapshot {
     pub title: String,
 }
 
-/// Streams screenshots as BrowserSnapshot to the provided channel, given a URL.
-pub async fn stream_screenshots(url: &str, tx: Sender<BrowserSnapshot>) -> Result<()> {
-    // Create a temporary directory for Chrome
-    let temp_dir = Arc::new(tempfile::Builder::new()
-        .prefix("rio-ext-browser")
-        .tempdir()?);
-    let config = BrowserConfig::builder()
-        .user_data_dir(temp_dir.path())
-        .args(vec!["--no-sandbox", "--disable-dev-shm-usage"])
-        .build()
-        .map_err(|e| anyhow::anyhow!("Failed to build browser config: {}", e))?;
-    let (mut browser, mut handler) = Browser::launch(config).await?;
-    // Spawn handler for browser events
-    tokio::spawn(async move {
-        while let Some(h) = handler.next().await {
-            let _ = h;
-        }
-    });
-    // Create a new page and navigate
-    let page = browser.new_page(url).await?;
-    
-    // Fetch the page title with fallback to URL
-    let title = page.get_title().await
-        .ok()
-        .flatten()
-        .unwrap_or_else(|| url.to_string());
-    
-    // Take a screenshot (could be looped for periodic shots)
-    let screenshot_data = page.screenshot(
-        ScreenshotParams::builder()
-            .format(CaptureScreenshotFormat::Png)
-            .full_page(true)
-            .build(),
-    ).await?;
-    // Decode PNG to RgbImage
-    let img = image::load_from_memory(&screenshot_data)?.to_rgb8();
-    
-    // Create and send snapshot with both image and title
-    let snapshot = BrowserSnapshot {
-        image: img,
-        title,
-    };
-    let _ = tx.send(snapshot).await;
-    
-    // Properly close the browser to avoid background kill warning
-    browser.close().await?;
-    
-    Ok(())
+/// Headless browser kept open between captures
+///
+/// Launching Chrome takes seconds, so one session serves every capture; the
+/// page only navigates when the URL changes and otherwise re-renders in place.
+pub struct BrowserSession {
+    browser: Browser,
+    page: Page,
+    url: Option<String>,
+    viewport: Option<(u32, u32)>,
+    // Chrome's profile directory, removed when the session is dropped
+    _profile: TempDir,
 }
 
-// (Legacy WebBrowser struct and impl removed for modular interface)
+impl BrowserSession {
+    /// Launch the browser with a blank page
+    pub async fn launch() -> Result<Self> {
+        // Create a temporary directory for Chrome
+        let profile = tempfile::Builder::new()
+            .prefix("rio-ext-browser")
+            .tempdir()?;
+        let config = BrowserConfig::builder()
+            .user_data_dir(profile.path())
+            .args(vec!["--no-sandbox", "--disable-dev-shm-usage"])
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build browser config: {}", e))?;
+        let (browser, mut handler) = Browser::launch(config).await?;
+        // Spawn handler for browser events
+        tokio::spawn(async move {
+            while let Some(h) = handler.next().await {
+                let _ = h;
+            }
+        });
+        let page = browser.new_page("about:blank").await?;
+
+        Ok(Self {
+            browser,
+            page,
+            url: None,
+            viewport: None,
+            _profile: profile,
+        })
+    }
+
+    /// Capture `url` as rendered in a `viewport`-sized window
+    ///
+    /// Navigates when `url` differs from the last capture and reloads
+    /// otherwise, so a refresh picks up new content.
+    pub async fn capture(&mut self, url: &str, viewport: (u32, u32)) -> Result<BrowserSnapshot> {
+        if self.viewport != Some(viewport) {
+            self.page
+                .execute(SetDeviceMetricsOverrideParams::new(
+                    viewport.0, viewport.1, 1.0, false,
+                ))
+                .await?;
+            self.viewport = Some(viewport);
+        }
+
+        if self.url.as_deref() == Some(url) {
+            self.page.reload().await?;
+        } else {
+            self.page.goto(url).await?;
+            self.url = Some(url.to_string());
+        }
+
+        // Fetch the page title with fallback to URL
+        let title = self
+            .page
+            .get_title()
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| url.to_string());
+
+        let screenshot_data = self
+            .page
+            .screenshot(
+                ScreenshotParams::builder()
+                    .format(CaptureScreenshotFormat::Png)
+                    .full_page(false)
+                    .build(),
+            )
+            .await?;
+        // Decode PNG to RgbImage
+        let image = image::load_from_memory(&screenshot_data)?.to_rgb8();
+
+        Ok(BrowserSnapshot { image, title })
+    }
+
+    /// Close the browser; dropping the session instead leaves Chrome to be
+    /// killed in the background
+    pub async fn close(mut self) -> Result<()> {
+        self.browser.close().await?;
+        Ok(())
+    }
+}
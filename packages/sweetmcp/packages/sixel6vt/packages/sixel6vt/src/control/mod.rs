@@ -0,0 +1,93 @@
+//! Line-based control socket for the graphics pane
+//!
+//! Each line a client sends is parsed as a [`DisplayCommand`] and answered
+//! with `ok` or `error: <reason>`, so a shell in the terminal pane can drive
+//! the display with `echo "open https://example.com" | nc 127.0.0.1 7766`.
+
+use anyhow::Result;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::Sender;
+
+use crate::display::DisplayCommand;
+
+/// Environment variable overriding [`DEFAULT_CONTROL_ADDR`]
+pub const CONTROL_ADDR_ENV: &str = "SIXEL6VT_CONTROL_ADDR";
+
+/// Loopback address the control socket listens on by default
+pub const DEFAULT_CONTROL_ADDR: &str = "127.0.0.1:7766";
+
+/// Address to listen on, from [`CONTROL_ADDR_ENV`] when set
+pub fn control_addr() -> String {
+    std::env::var(CONTROL_ADDR_ENV).unwrap_or_else(|_| DEFAULT_CONTROL_ADDR.to_string())
+}
+
+/// Accept control connections on `listener` until `commands` is closed
+pub async fn serve(listener: TcpListener, commands: Sender<DisplayCommand>) -> Result<()> {
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = commands.closed() => return Ok(()),
+        };
+        tracing::debug!("Control connection from {}", peer);
+
+        let commands = commands.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, commands).await {
+                tracing::warn!("Control connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, commands: Sender<DisplayCommand>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match line.parse::<DisplayCommand>() {
+            Ok(command) => {
+                tracing::info!("Control command: {:?}", command);
+                match commands.send(command).await {
+                    Ok(()) => "ok".to_string(),
+                    Err(_) => "error: display is shutting down".to_string(),
+                }
+            }
+            Err(e) => format!("error: {}", e),
+        };
+        writer.write_all(reply.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_commands_are_forwarded_and_acknowledged() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, mut rx) = mpsc::channel(4);
+        tokio::spawn(serve(listener, tx));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut replies = BufReader::new(reader).lines();
+
+        writer.write_all(b"split 0.3\nzoom 2\n").await.unwrap();
+        assert_eq!(replies.next_line().await.unwrap().as_deref(), Some("ok"));
+        assert!(replies
+            .next_line()
+            .await
+            .unwrap()
+            .unwrap()
+            .starts_with("error: unknown command"));
+        assert_eq!(rx.recv().await, Some(DisplayCommand::Split(0.3)));
+    }
+}
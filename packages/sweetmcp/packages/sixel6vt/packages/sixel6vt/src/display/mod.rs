@@ -0,0 +1,362 @@
+//! Graphics pane state: runtime commands, layout and change detection
+//!
+//! The graphics pane shows a browser screenshot beside the terminal. It is
+//! driven by [`DisplayCommand`]s arriving at runtime, re-captured on an
+//! optional timer, and only re-rendered when the new frame differs from the
+//! one on screen so periodic refreshes of a static page cost nothing.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use image::{imageops, RgbImage, Rgba, RgbaImage};
+
+/// Side length in pixels of the tiles compared by [`changed_fraction`]
+const DIFF_TILE: u32 = 16;
+
+/// Mean channel difference above which a tile counts as changed
+const TILE_TOLERANCE: f32 = 2.0;
+
+/// Changed tile fraction above which a new frame is rendered
+pub const REDRAW_THRESHOLD: f32 = 0.001;
+
+/// Shortest accepted auto-refresh interval
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Instruction for the graphics pane
+#[derive(Clone, PartialEq)]
+pub enum DisplayCommand {
+    /// Capture `url` and show it
+    Open(String),
+    /// Show an already rendered image, such as a chart
+    Image(Arc<RgbImage>),
+    /// Capture the current URL again
+    Refresh,
+    /// Re-capture the current URL on this interval, or stop with `None`
+    AutoRefresh(Option<Duration>),
+    /// Give the graphics pane this fraction of the window width
+    Split(f32),
+}
+
+impl fmt::Debug for DisplayCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open(url) => f.debug_tuple("Open").field(url).finish(),
+            // Pixel data would flood the logs
+            Self::Image(image) => write!(f, "Image({}x{})", image.width(), image.height()),
+            Self::Refresh => f.write_str("Refresh"),
+            Self::AutoRefresh(interval) => f.debug_tuple("AutoRefresh").field(interval).finish(),
+            Self::Split(fraction) => f.debug_tuple("Split").field(fraction).finish(),
+        }
+    }
+}
+
+/// Error for a command line that isn't a [`DisplayCommand`]
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ParseCommandError {
+    #[error("empty command")]
+    Empty,
+    #[error("unknown command `{0}` (expected open, refresh, auto or split)")]
+    Unknown(String),
+    #[error("`{0}` needs an argument")]
+    MissingArgument(&'static str),
+    #[error("invalid argument for `{command}`: {reason}")]
+    InvalidArgument {
+        command: &'static str,
+        reason: String,
+    },
+}
+
+impl FromStr for DisplayCommand {
+    type Err = ParseCommandError;
+
+    /// Parse one line of the control protocol:
+    /// `open <url>`, `refresh`, `auto <seconds>|off` or `split <fraction>`
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().ok_or(ParseCommandError::Empty)?;
+        let argument = parts.next();
+
+        match command.to_ascii_lowercase().as_str() {
+            "open" => {
+                let url = argument.ok_or(ParseCommandError::MissingArgument("open"))?;
+                if !url.contains("://") {
+                    return Err(ParseCommandError::InvalidArgument {
+                        command: "open",
+                        reason: format!("`{}` is not an absolute URL", url),
+                    });
+                }
+                Ok(Self::Open(url.to_string()))
+            }
+            "refresh" => Ok(Self::Refresh),
+            "auto" => match argument.ok_or(ParseCommandError::MissingArgument("auto"))? {
+                "off" | "0" => Ok(Self::AutoRefresh(None)),
+                seconds => {
+                    let seconds: f64 =
+                        seconds
+                            .parse()
+                            .map_err(|_| ParseCommandError::InvalidArgument {
+                                command: "auto",
+                                reason: format!("`{}` is not a number of seconds", seconds),
+                            })?;
+                    let interval = Duration::try_from_secs_f64(seconds)
+                        .ok()
+                        .filter(|interval| *interval >= MIN_REFRESH_INTERVAL)
+                        .ok_or_else(|| ParseCommandError::InvalidArgument {
+                            command: "auto",
+                            reason: format!(
+                                "interval must be at least {}s",
+                                MIN_REFRESH_INTERVAL.as_secs_f64()
+                            ),
+                        })?;
+                    Ok(Self::AutoRefresh(Some(interval)))
+                }
+            },
+            "split" => {
+                let fraction = argument.ok_or(ParseCommandError::MissingArgument("split"))?;
+                let fraction: f32 = fraction
+                    .parse()
+                    .ok()
+                    .filter(|f| Layout::SPLIT_RANGE.contains(f))
+                    .ok_or_else(|| ParseCommandError::InvalidArgument {
+                        command: "split",
+                        reason: format!(
+                            "fraction must be between {} and {}",
+                            Layout::SPLIT_RANGE.start(),
+                            Layout::SPLIT_RANGE.end()
+                        ),
+                    })?;
+                Ok(Self::Split(fraction))
+            }
+            other => Err(ParseCommandError::Unknown(other.to_string())),
+        }
+    }
+}
+
+/// Split between the terminal (left) and the graphics pane (right)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    /// Fraction of the window width given to the graphics pane
+    pub pane_fraction: f32,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self { pane_fraction: 0.5 }
+    }
+}
+
+impl Layout {
+    /// Accepted pane fractions; the terminal always keeps a usable width
+    pub const SPLIT_RANGE: std::ops::RangeInclusive<f32> = 0.2..=0.8;
+
+    /// Pixel size of the graphics pane in a window of `window` pixels
+    pub fn pane_size(&self, window: (u32, u32)) -> (u32, u32) {
+        let fraction = self
+            .pane_fraction
+            .clamp(*Self::SPLIT_RANGE.start(), *Self::SPLIT_RANGE.end());
+        let width = ((window.0 as f32 * fraction).round() as u32).max(1);
+        (width, window.1.max(1))
+    }
+
+    /// Frame for a `window`-sized display with `screenshot` in the pane
+    ///
+    /// The screenshot is scaled to the pane width and cropped to the window
+    /// height; the terminal side stays transparent so its text shows through.
+    pub fn compose(&self, screenshot: &RgbImage, window: (u32, u32)) -> RgbaImage {
+        let (pane_width, pane_height) = self.pane_size(window);
+        let mut frame = RgbaImage::from_pixel(pane_width.max(window.0), pane_height, Rgba([0; 4]));
+        if screenshot.width() == 0 || screenshot.height() == 0 {
+            return frame;
+        }
+
+        let scaled_height = ((u64::from(screenshot.height()) * u64::from(pane_width))
+            / u64::from(screenshot.width()))
+        .max(1) as u32;
+        let scaled = if screenshot.width() == pane_width {
+            screenshot.clone()
+        } else {
+            imageops::resize(
+                screenshot,
+                pane_width,
+                scaled_height,
+                imageops::FilterType::Triangle,
+            )
+        };
+
+        let left = frame.width() - pane_width;
+        for (x, y, pixel) in scaled.enumerate_pixels() {
+            if y >= pane_height {
+                break;
+            }
+            let [r, g, b] = pixel.0;
+            frame.put_pixel(left + x, y, Rgba([r, g, b, 255]));
+        }
+        frame
+    }
+}
+
+/// Fraction of [`DIFF_TILE`]-sized tiles whose average color changed
+///
+/// Frames of different sizes count as entirely changed.
+pub fn changed_fraction(previous: &RgbaImage, next: &RgbaImage) -> f32 {
+    if previous.dimensions() != next.dimensions() {
+        return 1.0;
+    }
+    let (width, height) = next.dimensions();
+    if width == 0 || height == 0 {
+        return 0.0;
+    }
+
+    let tiles_x = width.div_ceil(DIFF_TILE);
+    let tiles_y = height.div_ceil(DIFF_TILE);
+    let mut changed = 0u32;
+    for tile_y in 0..tiles_y {
+        for tile_x in 0..tiles_x {
+            let x0 = tile_x * DIFF_TILE;
+            let y0 = tile_y * DIFF_TILE;
+            let x1 = (x0 + DIFF_TILE).min(width);
+            let y1 = (y0 + DIFF_TILE).min(height);
+
+            let mut difference = 0u64;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let a = previous.get_pixel(x, y).0;
+                    let b = next.get_pixel(x, y).0;
+                    difference += a
+                        .iter()
+                        .zip(b.iter())
+                        .map(|(a, b)| u64::from(a.abs_diff(*b)))
+                        .sum::<u64>();
+                }
+            }
+            let samples = u64::from((x1 - x0) * (y1 - y0) * 4);
+            if difference as f32 / samples as f32 > TILE_TOLERANCE {
+                changed += 1;
+            }
+        }
+    }
+    changed as f32 / (tiles_x * tiles_y) as f32
+}
+
+/// Remembers the frame on screen to skip redundant re-renders
+#[derive(Debug, Default)]
+pub struct FrameTracker {
+    shown: Option<RgbaImage>,
+}
+
+impl FrameTracker {
+    /// Whether `frame` differs enough from the shown frame to render it
+    ///
+    /// Returns `true` and records `frame` as shown when it does.
+    pub fn update(&mut self, frame: &RgbaImage) -> bool {
+        let changed = self
+            .shown
+            .as_ref()
+            .is_none_or(|shown| changed_fraction(shown, frame) > REDRAW_THRESHOLD);
+        if changed {
+            self.shown = Some(frame.clone());
+        }
+        changed
+    }
+
+    /// Forget the shown frame so the next one always renders
+    pub fn invalidate(&mut self) {
+        self.shown = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            "open https://example.com".parse(),
+            Ok(DisplayCommand::Open("https://example.com".to_string()))
+        );
+        assert_eq!(" REFRESH ".parse(), Ok(DisplayCommand::Refresh));
+        assert_eq!(
+            "auto 2.5".parse(),
+            Ok(DisplayCommand::AutoRefresh(Some(Duration::from_millis(
+                2500
+            ))))
+        );
+        assert_eq!("auto off".parse(), Ok(DisplayCommand::AutoRefresh(None)));
+        assert_eq!("split 0.4".parse(), Ok(DisplayCommand::Split(0.4)));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_commands() {
+        assert_eq!("".parse::<DisplayCommand>(), Err(ParseCommandError::Empty));
+        assert!(matches!(
+            "zoom 2".parse::<DisplayCommand>(),
+            Err(ParseCommandError::Unknown(_))
+        ));
+        assert_eq!(
+            "open".parse::<DisplayCommand>(),
+            Err(ParseCommandError::MissingArgument("open"))
+        );
+        assert!("open example.com".parse::<DisplayCommand>().is_err());
+        assert!("auto 0.1".parse::<DisplayCommand>().is_err());
+        assert!("split 0.95".parse::<DisplayCommand>().is_err());
+    }
+
+    #[test]
+    fn test_compose_places_screenshot_in_right_pane() {
+        let screenshot = RgbImage::from_pixel(200, 400, image::Rgb([10, 20, 30]));
+        let layout = Layout {
+            pane_fraction: 0.25,
+        };
+
+        let frame = layout.compose(&screenshot, (400, 300));
+
+        assert_eq!(frame.dimensions(), (400, 300));
+        assert_eq!(
+            frame.get_pixel(0, 0).0[3],
+            0,
+            "terminal side is transparent"
+        );
+        assert_eq!(frame.get_pixel(299, 299).0[3], 0);
+        assert_eq!(frame.get_pixel(300, 0).0, [10, 20, 30, 255]);
+        assert_eq!(frame.get_pixel(399, 199).0, [10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_frame_tracker_skips_unchanged_frames() {
+        let mut tracker = FrameTracker::default();
+        let mut frame = RgbaImage::from_pixel(64, 64, Rgba([255; 4]));
+
+        assert!(tracker.update(&frame), "first frame always renders");
+        assert!(!tracker.update(&frame.clone()));
+
+        // One changed pixel stays within the tile tolerance
+        frame.put_pixel(3, 3, Rgba([250, 255, 255, 255]));
+        assert!(!tracker.update(&frame));
+
+        for y in 16..32 {
+            for x in 16..32 {
+                frame.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+            }
+        }
+        assert!(tracker.update(&frame));
+
+        tracker.invalidate();
+        assert!(tracker.update(&frame));
+    }
+
+    #[test]
+    fn test_changed_fraction_counts_tiles() {
+        let previous = RgbaImage::from_pixel(32, 32, Rgba([0, 0, 0, 255]));
+        let mut next = previous.clone();
+        for y in 0..16 {
+            for x in 0..16 {
+                next.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+        assert_eq!(changed_fraction(&previous, &next), 0.25);
+        assert_eq!(changed_fraction(&previous, &RgbaImage::new(16, 16)), 1.0);
+    }
+}
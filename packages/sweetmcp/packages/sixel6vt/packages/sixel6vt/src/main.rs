@@ -2,6 +2,9 @@
 // Uses rioterm's Application with wrapper to intercept window creation
 
 mod browser;
+mod control;
+mod display;
+mod mcp;
 mod renderer;
 
 use anyhow::Result;
@@ -14,24 +17,41 @@ use rio_window::event::WindowEvent;
 use rio_window::event_loop::{ActiveEventLoop, EventLoop};
 use rio_window::window::WindowId;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc as tokio_mpsc;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
+use tokio::time::{Instant, Interval, MissedTickBehavior};
 use tracing_subscriber;
 
+use display::{DisplayCommand, FrameTracker, Layout};
+
+/// Page shown when no URL is given on the command line
+const DEFAULT_URL: &str = "https://github.com/trending";
+
+/// Window size assumed until the first resize event arrives
+const DEFAULT_WINDOW_SIZE: (u32, u32) = (1280, 800);
+
 /// Wrapper around rioterm::Application that intercepts window creation
-/// to send the window ID to async tasks via oneshot channel
+/// to send the window ID to async tasks via oneshot channel, and resizes
+/// so the graphics pane can follow the window size
 struct ApplicationWrapper<'a> {
     app: rioterm::Application<'a>,
     window_tx: Option<oneshot::Sender<WindowId>>,
+    size_tx: watch::Sender<(u32, u32)>,
 }
 
 impl<'a> ApplicationWrapper<'a> {
-    fn new(app: rioterm::Application<'a>, window_tx: oneshot::Sender<WindowId>) -> Self {
+    fn new(
+        app: rioterm::Application<'a>,
+        window_tx: oneshot::Sender<WindowId>,
+        size_tx: watch::Sender<(u32, u32)>,
+    ) -> Self {
         Self {
             app,
             window_tx: Some(window_tx),
+            size_tx,
         }
     }
 }
@@ -89,6 +109,11 @@ impl ApplicationHandler<EventPayload> for ApplicationWrapper<'_> {
         window_id: WindowId,
         event: WindowEvent,
     ) {
+        if let WindowEvent::Resized(size) = &event {
+            if size.width > 0 && size.height > 0 {
+                self.size_tx.send_replace((size.width, size.height));
+            }
+        }
         self.app.window_event(event_loop, window_id, event);
     }
 
@@ -106,19 +131,12 @@ impl ApplicationHandler<EventPayload> for ApplicationWrapper<'_> {
 /// Atomic counter for generating unique GraphicIds as fallback
 static GRAPHIC_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
-/// Convert image::RgbImage to Rio's GraphicData format (RGBA)
+/// Convert an RGBA frame to Rio's GraphicData format
 /// This matches the format Rio's sixel parser produces at crosswords/mod.rs:2959
-fn rgb_image_to_graphic_data(rgb_img: &image::RgbImage) -> GraphicData {
-    let width = rgb_img.width() as usize;
-    let height = rgb_img.height() as usize;
-    
-    // Convert RGB (3 bytes/pixel) to RGBA (4 bytes/pixel) using iterator chains
-    // Rio's graphics pipeline expects RGBA (verified in graphics.rs:190)
-    let rgba_pixels: Vec<u8> = rgb_img
-        .pixels()
-        .flat_map(|p| [p[0], p[1], p[2], 255])
-        .collect();
-    
+fn rgba_image_to_graphic_data(frame: &image::RgbaImage) -> GraphicData {
+    let width = frame.width() as usize;
+    let height = frame.height() as usize;
+
     // Create unique ID using timestamp (same approach as Rio's next_id())
     // Fallback to atomic counter if system time is unavailable
     let id = GraphicId(
@@ -130,31 +148,219 @@ fn rgb_image_to_graphic_data(rgb_img: &image::RgbImage) -> GraphicData {
                 let pid = std::process::id() as u64;
                 let counter = GRAPHIC_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
                 (pid << 32) | counter
-            })
+            }),
     );
-    
+
     GraphicData {
         id,
         width,
         height,
+        // Rio's graphics pipeline expects RGBA (verified in graphics.rs:190)
         color_type: ColorType::Rgba,
-        pixels: rgba_pixels,
-        is_opaque: true,
+        pixels: frame.as_raw().clone(),
+        // The terminal side of the frame is transparent
+        is_opaque: false,
         resize: None,
     }
 }
 
+/// Wait for the next auto-refresh tick, or forever when auto-refresh is off
+async fn next_refresh(refresh: &mut Option<Interval>) {
+    match refresh {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// What the graphics pane shows
+enum Source {
+    /// A page re-captured on every refresh
+    Url(String),
+    /// A fixed image pushed over MCP
+    Image(Arc<image::RgbImage>),
+}
+
+/// Graphics pane state driven by the display loop
+struct Display {
+    proxy: EventProxy,
+    window_id: WindowId,
+    session: browser::BrowserSession,
+    layout: Layout,
+    frames: FrameTracker,
+    shown: Option<GraphicId>,
+}
+
+impl Display {
+    /// Lay out `source` at the current pane size and render it if it changed
+    async fn show(&mut self, source: &Source, window: (u32, u32)) {
+        let snapshot;
+        let (image, title) = match source {
+            Source::Url(url) => {
+                snapshot = match self
+                    .session
+                    .capture(url, self.layout.pane_size(window))
+                    .await
+                {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        tracing::error!("Failed to capture {}: {}", url, e);
+                        return;
+                    }
+                };
+                (&snapshot.image, snapshot.title.as_str())
+            }
+            Source::Image(image) => (image.as_ref(), "image"),
+        };
+
+        let frame = self.layout.compose(image, window);
+        if !self.frames.update(&frame) {
+            tracing::debug!("{} unchanged, skipping render", title);
+            return;
+        }
+
+        let graphic_data = rgba_image_to_graphic_data(&frame);
+        tracing::info!(
+            "Rendering {}: {}x{}, ID {:?}",
+            title,
+            graphic_data.width,
+            graphic_data.height,
+            graphic_data.id
+        );
+
+        // Replace the previous frame rather than stacking on top of it
+        let id = graphic_data.id;
+        let queues = UpdateQueues {
+            pending: vec![graphic_data],
+            remove_queue: self.shown.replace(id).into_iter().collect(),
+        };
+
+        self.proxy.send_event(
+            RioEventType::Rio(RioEvent::UpdateGraphics {
+                route_id: 0,
+                queues,
+            }),
+            self.window_id,
+        );
+    }
+}
+
+/// Show `url`, then re-capture on commands, auto-refresh ticks and resizes
+async fn run_display(
+    proxy: EventProxy,
+    window_id: WindowId,
+    url: String,
+    mut commands: tokio_mpsc::Receiver<DisplayCommand>,
+    mut window_size: watch::Receiver<(u32, u32)>,
+) -> Result<()> {
+    tracing::info!("Starting browser session...");
+    let mut display = Display {
+        proxy,
+        window_id,
+        session: browser::BrowserSession::launch().await?,
+        layout: Layout::default(),
+        frames: FrameTracker::default(),
+        shown: None,
+    };
+    let mut refresh: Option<Interval> = None;
+    let mut source = Source::Url(url);
+
+    let window = *window_size.borrow_and_update();
+    display.show(&source, window).await;
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(DisplayCommand::Open(url)) => source = Source::Url(url),
+                Some(DisplayCommand::Image(image)) => source = Source::Image(image),
+                Some(DisplayCommand::Refresh) => {}
+                Some(DisplayCommand::AutoRefresh(period)) => {
+                    refresh = period.map(|period| {
+                        let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+                        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                        interval
+                    });
+                    continue;
+                }
+                Some(DisplayCommand::Split(fraction)) => {
+                    display.layout.pane_fraction = fraction;
+                    display.frames.invalidate();
+                }
+                None => break,
+            },
+            _ = next_refresh(&mut refresh) => {}
+            changed = window_size.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                display.frames.invalidate();
+            }
+        }
+
+        let window = *window_size.borrow_and_update();
+        display.show(&source, window).await;
+    }
+
+    display.session.close().await
+}
+
+/// Capture `url` once and write it to this terminal in the best graphics
+/// protocol it supports, without opening a window
+async fn print_url(url: &str) -> Result<()> {
+    let protocol = renderer::GraphicsProtocol::detect();
+    if protocol == renderer::GraphicsProtocol::None {
+        anyhow::bail!(
+            "Terminal supports no known graphics protocol; set {} to kitty, iterm2 or sixel to override",
+            renderer::GRAPHICS_ENV
+        );
+    }
+    tracing::info!("Printing {} using {:?} graphics", url, protocol);
+
+    let mut session = browser::BrowserSession::launch().await?;
+    let snapshot = session.capture(url, DEFAULT_WINDOW_SIZE).await;
+    session.close().await?;
+    let snapshot = snapshot?;
+
+    if let Some(graphics) = renderer::encode(&snapshot.image, protocol) {
+        use std::io::Write;
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(graphics.as_bytes())?;
+        writeln!(stdout)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
-    // Setup logging
+    // `--mcp` serves display tools on stdin/stdout and `--print` writes one
+    // capture to the current terminal; any other argument is the start URL
+    let mut serve_mcp = false;
+    let mut print = false;
+    let mut url = DEFAULT_URL.to_string();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--mcp" => serve_mcp = true,
+            "--print" => print = true,
+            _ => url = arg,
+        }
+    }
+
+    // Setup logging; stdout carries MCP messages, so logs go to stderr
     tracing_subscriber::fmt()
         .with_env_filter("sixel6vt=debug,rioterm=info,rio_backend=info")
+        .with_writer(std::io::stderr)
         .init();
 
-    tracing::info!("Starting sixel6vt using rioterm");
-
     // Create tokio runtime (spawns background worker threads)
     let runtime = Runtime::new()?;
 
+    if print {
+        return runtime.block_on(print_url(&url));
+    }
+
+    tracing::info!("Starting sixel6vt using rioterm");
+
     // Create oneshot channel for window_id
     let (window_tx, window_rx) = oneshot::channel::<WindowId>();
 
@@ -173,6 +379,36 @@ fn main() -> Result<()> {
     // Clone event proxy for async task
     let proxy_clone = event_proxy.clone();
 
+    // Display commands from the control socket and MCP clients
+    let (command_tx, command_rx) = tokio_mpsc::channel::<DisplayCommand>(16);
+    let control_addr = control::control_addr();
+    let control_tx = command_tx.clone();
+    runtime.spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(&control_addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("Failed to bind control socket {}: {}", control_addr, e);
+                return;
+            }
+        };
+        tracing::info!("Control socket listening on {}", control_addr);
+        if let Err(e) = control::serve(listener, control_tx).await {
+            tracing::error!("Control socket failed: {}", e);
+        }
+    });
+
+    // Display commands from MCP clients
+    if serve_mcp {
+        runtime.spawn(async move {
+            if let Err(e) = mcp::serve_stdio(command_tx).await {
+                tracing::error!("MCP server failed: {}", e);
+            }
+        });
+    }
+
+    // Window size updates from the event loop
+    let (size_tx, size_rx) = watch::channel(DEFAULT_WINDOW_SIZE);
+
     // Spawn async task on runtime's worker threads
     runtime.spawn(async move {
         // Await window_id from oneshot channel (no blocking!)
@@ -190,58 +426,16 @@ fn main() -> Result<()> {
         // Wait for window initialization
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        tracing::info!("Starting browser screenshot capture...");
-
-        // Create channel for screenshots
-        let (tx, mut rx) = tokio_mpsc::channel(1);
-
-        // Spawn browser screenshot task
-        let url = "https://github.com/trending";
-        tokio::spawn(async move {
-            if let Err(e) = browser::stream_screenshots(url, tx).await {
-                tracing::error!("Failed to stream screenshots: {}", e);
-            }
-        });
-
-        // Await screenshot
-        if let Some(snapshot) = rx.recv().await {
-            tracing::info!("Screenshot received: {}", snapshot.title);
-
-            // Convert to GraphicData
-            let graphic_data = rgb_image_to_graphic_data(&snapshot.image);
-            tracing::info!(
-                "GraphicData created: {}x{}, {} bytes, ID {:?}",
-                graphic_data.width,
-                graphic_data.height,
-                graphic_data.pixels.len(),
-                graphic_data.id
-            );
-
-            // Send UpdateGraphics event via EventProxy
-            let queues = UpdateQueues {
-                pending: vec![graphic_data],
-                remove_queue: Vec::new(),
-            };
-
-            proxy_clone.send_event(
-                RioEventType::Rio(RioEvent::UpdateGraphics {
-                    route_id: 0,
-                    queues,
-                }),
-                window_id,
-            );
-
-            tracing::info!("✓ Graphics injected via UpdateGraphics event!");
-        } else {
-            tracing::warn!("Screenshot channel closed without data");
+        if let Err(e) = run_display(proxy_clone, window_id, url, command_rx, size_rx).await {
+            tracing::error!("Display failed: {}", e);
         }
     });
 
     // Create rioterm Application
     let app = rioterm::Application::new(config, None, &event_loop);
 
-    // Wrap with oneshot sender
-    let mut app_wrapper = ApplicationWrapper::new(app, window_tx);
+    // Wrap with oneshot sender and resize forwarding
+    let mut app_wrapper = ApplicationWrapper::new(app, window_tx, size_tx);
 
     tracing::info!("Running rioterm application...");
 
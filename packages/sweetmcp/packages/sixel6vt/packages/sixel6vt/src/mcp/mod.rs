@@ -0,0 +1,308 @@
+//! MCP server exposing the graphics pane as tools
+//!
+//! With `--mcp`, sixel6vt answers MCP JSON-RPC on stdin/stdout alongside its
+//! window, so agents and gateway clients can push pages and rendered images
+//! (screenshots, charts) into the user's terminal:
+//! - `display_url`: capture a web page into the pane
+//! - `display_image`: show a PNG, JPEG or other image given as base64 `data`
+//!   or as a `path` on this machine
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use base64::Engine;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc::Sender;
+
+use crate::display::DisplayCommand;
+
+/// MCP protocol revision spoken by the sweetmcp gateway
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+const JSONRPC_VERSION: &str = "2.0";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve MCP on stdin/stdout until stdin closes
+pub async fn serve_stdio(commands: Sender<DisplayCommand>) -> Result<()> {
+    tracing::info!("Serving MCP display tools on stdin/stdout");
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(message, &commands).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                PARSE_ERROR,
+                format!("Parse error: {}", e),
+            )),
+        };
+        if let Some(response) = response {
+            let mut response = serde_json::to_vec(&response)?;
+            response.push(b'\n');
+            stdout.write_all(&response).await?;
+            stdout.flush().await?;
+        }
+    }
+
+    tracing::info!("MCP client disconnected");
+    Ok(())
+}
+
+/// Answer one JSON-RPC message; notifications get no response
+pub async fn handle_message(message: Value, commands: &Sender<DisplayCommand>) -> Option<Value> {
+    let id = message.get("id").cloned()?;
+    let method = message
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "capabilities": { "tools": {} },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(&params, commands).await,
+        other => Err((METHOD_NOT_FOUND, format!("Method not found: {}", other))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": JSONRPC_VERSION, "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Tool definitions returned by `tools/list`
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "display_url",
+            "description": "Show a web page in the graphics pane of the user's terminal window. \
+                Use this when the user should see a page, dashboard or report while they work.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "url": { "type": "string", "description": "Absolute URL of the page" },
+                },
+                "required": ["url"],
+            },
+        },
+        {
+            "name": "display_image",
+            "description": "Show an image such as a screenshot or chart in the graphics pane \
+                of the user's terminal window. Give the image as base64 `data` or as a `path` \
+                on the machine running the terminal.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "data": { "type": "string", "description": "Base64-encoded PNG, JPEG, GIF or WebP image" },
+                    "path": { "type": "string", "description": "Image file path, used when data is omitted" },
+                },
+            },
+        },
+    ])
+}
+
+/// Run a tool; failures the caller can fix are error results, not RPC errors
+async fn call_tool(
+    params: &Value,
+    commands: &Sender<DisplayCommand>,
+) -> Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+    let argument = |key: &str| arguments.get(key).and_then(Value::as_str);
+
+    let command = match name {
+        "display_url" => match argument("url") {
+            Some(url) => format!("open {}", url)
+                .parse::<DisplayCommand>()
+                .map_err(|e| e.to_string()),
+            None => Err("`url` is required".to_string()),
+        },
+        "display_image" => load_image(argument("data"), argument("path"))
+            .map(|image| DisplayCommand::Image(Arc::new(image))),
+        other => return Err((INVALID_PARAMS, format!("Unknown tool: {}", other))),
+    };
+
+    let command = match command {
+        Ok(command) => command,
+        Err(message) => return Ok(tool_result(message, true)),
+    };
+    let summary = match &command {
+        DisplayCommand::Open(url) => format!("Displaying {}", url),
+        DisplayCommand::Image(image) => {
+            format!("Displaying {}x{} image", image.width(), image.height())
+        }
+        other => format!("{:?}", other),
+    };
+    if commands.send(command).await.is_err() {
+        return Ok(tool_result("Display is shutting down".to_string(), true));
+    }
+    Ok(tool_result(summary, false))
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+/// Decode an image from base64 `data`, or else read it from `path`
+fn load_image(data: Option<&str>, path: Option<&str>) -> Result<image::RgbImage, String> {
+    let image = match (data, path) {
+        (Some(data), _) => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(data.trim())
+                .map_err(|e| format!("Invalid base64 image data: {}", e))?;
+            image::load_from_memory(&bytes).map_err(|e| format!("Unreadable image data: {}", e))?
+        }
+        (None, Some(path)) => {
+            image::open(path).map_err(|e| format!("Unreadable image {}: {}", path, e))?
+        }
+        (None, None) => return Err("Either `data` or `path` is required".to_string()),
+    };
+    Ok(image.to_rgb8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tokio::sync::mpsc;
+
+    fn png_base64(width: u32, height: u32) -> String {
+        let mut png = Vec::new();
+        image::RgbImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        base64::engine::general_purpose::STANDARD.encode(png)
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let (tx, _rx) = mpsc::channel(1);
+
+        let response = handle_message(
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+
+        let response = handle_message(
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        let names: Vec<_> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["display_url", "display_image"]);
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(handle_message(notification, &tx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_become_display_commands() {
+        let (tx, mut rx) = mpsc::channel(2);
+
+        let response = handle_message(
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {
+                "name": "display_url",
+                "arguments": {"url": "https://example.com"}
+            }}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        assert_eq!(
+            rx.recv().await,
+            Some(DisplayCommand::Open("https://example.com".to_string()))
+        );
+
+        handle_message(
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+                "name": "display_image",
+                "arguments": {"data": png_base64(3, 2)}
+            }}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        match rx.recv().await {
+            Some(DisplayCommand::Image(image)) => assert_eq!(image.dimensions(), (3, 2)),
+            other => panic!("expected an image, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bad_arguments_are_tool_errors() {
+        let (tx, mut rx) = mpsc::channel(1);
+
+        for arguments in [json!({"url": "example.com"}), json!({})] {
+            let response = handle_message(
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {
+                    "name": "display_url", "arguments": arguments
+                }}),
+                &tx,
+            )
+            .await
+            .unwrap();
+            assert_eq!(response["result"]["isError"], true);
+        }
+
+        let response = handle_message(
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+                "name": "display_image", "arguments": {"data": "bm90IGFuIGltYWdl"}
+            }}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        assert!(rx.try_recv().is_err());
+
+        let response = handle_message(
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"}),
+            &tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
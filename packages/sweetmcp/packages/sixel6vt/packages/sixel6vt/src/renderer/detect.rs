@@ -0,0 +1,187 @@
+//! Terminal graphics protocol detection
+//!
+//! Detection runs in three steps, stopping at the first answer:
+//! 1. `SIXEL6VT_GRAPHICS` overrides everything (`kitty`, `iterm2`, `sixel` or `none`)
+//! 2. Terminal identity from the environment (`TERM`, `TERM_PROGRAM`, ...)
+//! 3. A query on the controlling terminal: a Kitty graphics probe followed by
+//!    DA1 (`ESC [ c`). Kitty-capable terminals answer the probe before the DA1
+//!    reply; sixel terminals list attribute `4` in the DA1 reply.
+//!
+//! Full-color protocols are preferred over sixel, whose palette is 16 colors.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Environment variable forcing a protocol
+pub const GRAPHICS_ENV: &str = "SIXEL6VT_GRAPHICS";
+
+/// How long to wait for the terminal to answer the capability query
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Kitty graphics probe (query a 1x1 RGB image with id 31) followed by DA1
+const QUERY: &[u8] = b"\x1B_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1B\\\x1B[c";
+
+/// Image protocol a terminal understands, in order of preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    /// Kitty graphics protocol (APC `G`)
+    Kitty,
+    /// iTerm2 inline images (OSC 1337)
+    Iterm2,
+    /// DEC sixel graphics
+    Sixel,
+    /// No image support; graphics are skipped
+    None,
+}
+
+impl FromStr for GraphicsProtocol {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "kitty" => Ok(Self::Kitty),
+            "iterm2" | "iterm" => Ok(Self::Iterm2),
+            "sixel" => Ok(Self::Sixel),
+            "none" | "off" => Ok(Self::None),
+            other => Err(format!(
+                "unknown graphics protocol `{}` (expected kitty, iterm2, sixel or none)",
+                other
+            )),
+        }
+    }
+}
+
+impl GraphicsProtocol {
+    /// Detect the protocol of the terminal this process writes to
+    pub fn detect() -> Self {
+        if let Ok(forced) = std::env::var(GRAPHICS_ENV) {
+            match forced.parse() {
+                Ok(protocol) => return protocol,
+                Err(e) => tracing::warn!("Ignoring {}: {}", GRAPHICS_ENV, e),
+            }
+        }
+        if let Some(protocol) = Self::from_env(|key| std::env::var(key).ok()) {
+            tracing::debug!("Graphics protocol from environment: {:?}", protocol);
+            return protocol;
+        }
+        match query_terminal() {
+            Some(response) => {
+                let protocol = Self::from_query_response(&response);
+                tracing::debug!("Graphics protocol from terminal query: {:?}", protocol);
+                protocol
+            }
+            None => Self::None,
+        }
+    }
+
+    /// Protocol implied by well-known terminal identification variables
+    ///
+    /// Returns `None` for terminals that need to be asked, such as xterm,
+    /// whose sixel support depends on how it was built.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if var("KITTY_WINDOW_ID").is_some() {
+            return Some(Self::Kitty);
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        // Multiplexers hide the outer terminal's capabilities
+        if var("TMUX").is_some() || term.starts_with("screen") {
+            return None;
+        }
+
+        match program.as_str() {
+            "ghostty" => return Some(Self::Kitty),
+            // WezTerm's Kitty support is opt-in; its iTerm2 support is not
+            "iTerm.app" | "WezTerm" | "mintty" => return Some(Self::Iterm2),
+            _ => {}
+        }
+        if var("LC_TERMINAL").as_deref() == Some("iTerm2") {
+            return Some(Self::Iterm2);
+        }
+        match term.as_str() {
+            "xterm-kitty" | "xterm-ghostty" => Some(Self::Kitty),
+            "foot" | "foot-extra" | "mlterm" | "contour" | "rio" => Some(Self::Sixel),
+            _ => None,
+        }
+    }
+
+    /// Protocol implied by the terminal's answer to [`QUERY`]
+    pub fn from_query_response(response: &[u8]) -> Self {
+        let response = String::from_utf8_lossy(response);
+        if response.contains("\x1B_Gi=31;OK") {
+            return Self::Kitty;
+        }
+
+        // DA1 reply: ESC [ ? <class> ; <attr> ; ... c
+        let attributes = response
+            .rfind("\x1B[?")
+            .map(|start| &response[start + 3..])
+            .and_then(|reply| reply.split('c').next())
+            .unwrap_or_default();
+        if attributes
+            .split(';')
+            .skip(1)
+            .any(|attribute| attribute == "4")
+        {
+            Self::Sixel
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Send [`QUERY`] to the controlling terminal and collect its reply
+///
+/// Returns `None` without a terminal, or when it doesn't answer in time.
+#[cfg(unix)]
+fn query_terminal() -> Option<Vec<u8>> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+
+    // Raw mode so the reply isn't echoed or line-buffered; reads return
+    // after at most 100ms (VTIME is in tenths of a second)
+    // SAFETY: `fd` is an open terminal and `original` is fully written by
+    // tcgetattr before use
+    let mut original: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+        return None;
+    }
+    let mut raw = original;
+    unsafe { libc::cfmakeraw(&mut raw) };
+    raw.c_cc[libc::VMIN] = 0;
+    raw.c_cc[libc::VTIME] = 1;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+        return None;
+    }
+
+    let mut response = Vec::new();
+    if tty.write_all(QUERY).and_then(|()| tty.flush()).is_ok() {
+        let deadline = Instant::now() + QUERY_TIMEOUT;
+        let mut buffer = [0u8; 256];
+        // The DA1 reply ends with `c` and comes last
+        while Instant::now() < deadline && !response.ends_with(b"c") {
+            match tty.read(&mut buffer) {
+                Ok(read) => response.extend_from_slice(&buffer[..read]),
+                Err(_) => break,
+            }
+        }
+    }
+
+    // SAFETY: restores the settings read above on the same descriptor
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+    (!response.is_empty()).then_some(response)
+}
+
+#[cfg(not(unix))]
+fn query_terminal() -> Option<Vec<u8>> {
+    None
+}
@@ -0,0 +1,23 @@
+//! iTerm2 inline image encoding
+//!
+//! The image travels as a base64 PNG file inside a single OSC 1337 sequence:
+//! `ESC ] 1337 ; File=inline=1;size=<bytes>;width=<w>px;height=<h>px : <base64> BEL`.
+//!
+//! Supported by iTerm2, WezTerm, mintty and Konsole, in full color.
+//! See <https://iterm2.com/documentation-images.html>.
+
+use base64::Engine;
+
+/// Encode an image as an iTerm2 inline image sequence displayed at the cursor
+pub fn encode_iterm2(img: &image::RgbImage) -> String {
+    let png = super::encode_png(img);
+    let payload = base64::engine::general_purpose::STANDARD.encode(&png);
+
+    format!(
+        "\x1B]1337;File=inline=1;size={};width={}px;height={}px;preserveAspectRatio=1:{}\x07",
+        png.len(),
+        img.width(),
+        img.height(),
+        payload
+    )
+}
@@ -0,0 +1,40 @@
+//! Kitty graphics protocol encoding
+//!
+//! Images travel as base64 PNG inside APC escapes, split into chunks of at
+//! most 4096 base64 bytes as the protocol requires:
+//! `ESC _ G a=T,f=100,q=2,m=1 ; <chunk> ESC \` followed by `ESC _ G m=<0|1> ; <chunk> ESC \`.
+//!
+//! Supported by kitty, Ghostty, WezTerm and Konsole, in full color.
+//! See <https://sw.kovidgoyal.net/kitty/graphics-protocol/>.
+
+use base64::Engine;
+
+/// Largest base64 payload the protocol allows per escape sequence
+const CHUNK_SIZE: usize = 4096;
+
+/// Encode an image as Kitty graphics escapes that display it at the cursor
+///
+/// `a=T` transmits and displays in one step, `f=100` marks PNG data and
+/// `q=2` suppresses the terminal's OK/error replies so they don't end up
+/// on the shell's input.
+pub fn encode_kitty(img: &image::RgbImage) -> String {
+    let payload = base64::engine::general_purpose::STANDARD.encode(super::encode_png(img));
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(CHUNK_SIZE).collect();
+
+    let mut result = String::with_capacity(payload.len() + chunks.len() * 16 + 32);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        result.push_str("\x1B_G");
+        if i == 0 {
+            result.push_str("a=T,f=100,q=2,");
+        }
+        result.push_str(&format!("m={};", more));
+        // Base64 output is ASCII
+        result.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        result.push_str("\x1B\\");
+    }
+    if chunks.is_empty() {
+        result.push_str("\x1B_Ga=T,f=100,q=2,m=0;\x1B\\");
+    }
+    result
+}
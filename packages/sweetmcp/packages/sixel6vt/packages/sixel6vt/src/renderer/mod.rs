@@ -68,6 +68,20 @@
 //! - [VT340 Programmer Reference](https://vt100.net/docs/vt3xx-gp/chapter14.html)
 //! - [Sixel Wikipedia](https://en.wikipedia.org/wiki/Sixel)
 //! - [ITU-R BT.601 Color Standard](https://www.itu.int/rec/r-rec-bt.601/)
+//!
+//! # Other Protocols
+//!
+//! Terminals without sixel often speak the Kitty graphics protocol or iTerm2
+//! inline images, which also carry full color. [`GraphicsProtocol::detect`]
+//! picks one for the current terminal and [`encode`] produces its escapes.
+
+mod detect;
+mod iterm2;
+mod kitty;
+
+pub use detect::{GraphicsProtocol, GRAPHICS_ENV};
+pub use iterm2::encode_iterm2;
+pub use kitty::encode_kitty;
 
 /// VT340-compatible 16-color palette for sixel encoding
 ///
@@ -674,6 +688,32 @@ pub fn encode_sixel(img: &image::RgbImage) -> String {
     regions_to_sixel(&merged_regions, img.width(), img.height())
 }
 
+/// Encode an image for display at the cursor using `protocol`
+///
+/// Returns `None` for [`GraphicsProtocol::None`].
+pub fn encode(img: &image::RgbImage, protocol: GraphicsProtocol) -> Option<String> {
+    match protocol {
+        GraphicsProtocol::Kitty => Some(encode_kitty(img)),
+        GraphicsProtocol::Iterm2 => Some(encode_iterm2(img)),
+        GraphicsProtocol::Sixel => Some(encode_sixel(img)),
+        GraphicsProtocol::None => None,
+    }
+}
+
+/// PNG file for the Kitty and iTerm2 encoders; empty for empty images
+fn encode_png(img: &image::RgbImage) -> Vec<u8> {
+    let mut png = std::io::Cursor::new(Vec::new());
+    if let Err(e) = img.write_to(&mut png, image::ImageFormat::Png) {
+        tracing::warn!(
+            "Failed to encode {}x{} image as PNG: {}",
+            img.width(),
+            img.height(),
+            e
+        );
+    }
+    png.into_inner()
+}
+
 /// Column-based encoding - O(w*h) complexity, reliable for all image types
 /// 
 /// This is the proven reference implementation used as fallback for large or 
@@ -947,4 +987,88 @@ mod tests {
         let row_count = sixel.matches('-').count();
         assert_eq!(row_count, 2, "Should have 2 sixel rows for height=7");
     }
+
+    #[test]
+    fn test_kitty_encoding_is_chunked() {
+        // Noise doesn't compress, so the PNG needs several 4096-byte chunks
+        let img = image::RgbImage::from_fn(64, 64, |x, y| {
+            let v = (x.wrapping_mul(7919) ^ y.wrapping_mul(104729)) as u8;
+            image::Rgb([v, v.wrapping_mul(3), v.wrapping_add(y as u8)])
+        });
+
+        let kitty = encode_kitty(&img);
+
+        let chunks: Vec<&str> = kitty.split("\x1B\\").filter(|c| !c.is_empty()).collect();
+        assert!(chunks.len() > 1, "Should split into several escapes");
+        assert!(chunks[0].starts_with("\x1B_Ga=T,f=100,q=2,m=1;"));
+        assert!(chunks[1..chunks.len() - 1]
+            .iter()
+            .all(|c| c.starts_with("\x1B_Gm=1;")));
+        assert!(chunks[chunks.len() - 1].starts_with("\x1B_Gm=0;"));
+        assert!(chunks.iter().all(|c| c.len() <= 4096 + 32));
+    }
+
+    #[test]
+    fn test_iterm2_encoding() {
+        let img = image::RgbImage::from_pixel(4, 3, image::Rgb([255u8, 0u8, 0u8]));
+
+        let iterm2 = encode_iterm2(&img);
+
+        assert!(iterm2.starts_with("\x1B]1337;File=inline=1;size="));
+        assert!(iterm2.contains(";width=4px;height=3px;"));
+        assert!(iterm2.ends_with('\x07'));
+        assert_eq!(encode(&img, GraphicsProtocol::Iterm2), Some(iterm2));
+        assert_eq!(encode(&img, GraphicsProtocol::None), None);
+    }
+
+    #[test]
+    fn test_protocol_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "xterm-kitty")])),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "foot")])),
+            Some(GraphicsProtocol::Sixel)
+        );
+        // Needs a query: xterm may or may not be built with sixel
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "xterm-256color")])),
+            None
+        );
+        assert_eq!(
+            GraphicsProtocol::from_env(env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_protocol_from_query_response() {
+        assert_eq!(
+            GraphicsProtocol::from_query_response(b"\x1B_Gi=31;OK\x1B\\\x1B[?62;22c"),
+            GraphicsProtocol::Kitty
+        );
+        assert_eq!(
+            GraphicsProtocol::from_query_response(b"\x1B[?63;1;2;4;6;9;15;22c"),
+            GraphicsProtocol::Sixel
+        );
+        assert_eq!(
+            GraphicsProtocol::from_query_response(b"\x1B[?64;1;2;6;22c"),
+            GraphicsProtocol::None
+        );
+        assert_eq!("Kitty".parse(), Ok(GraphicsProtocol::Kitty));
+        assert!("braille".parse::<GraphicsProtocol>().is_err());
+    }
 }
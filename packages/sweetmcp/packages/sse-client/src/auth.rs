@@ -0,0 +1,187 @@
+//! Per-request authentication for the SSE client
+//!
+//! Credentials are applied when each request is built rather than baked into
+//! the client, so tokens rotated by an identity provider take effect without
+//! recreating the client mid-session.
+
+use log::{debug, info};
+use reqwest::RequestBuilder;
+use reqwest::header::HeaderMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+
+use crate::SseClientError;
+
+/// Future returned by a bearer token refresh callback
+pub type TokenFuture = Pin<Box<dyn Future<Output = Result<String, SseClientError>> + Send>>;
+
+/// Callback fetching a fresh bearer token, e.g. from an OAuth token endpoint
+pub type TokenRefresh = Arc<dyn Fn() -> TokenFuture + Send + Sync>;
+
+/// Callback computing headers for each request
+pub type HeaderProvider = Arc<dyn Fn() -> Result<HeaderMap, SseClientError> + Send + Sync>;
+
+/// How an [`SseClient`](crate::SseClient) authenticates its requests
+#[derive(Clone)]
+pub enum SseAuth {
+    /// `Authorization: Bearer <token>`
+    ///
+    /// With a refresh callback, the token is fetched before the first request
+    /// when none is given, and refreshed once when the server answers
+    /// `401 Unauthorized`, after which the request is retried.
+    Bearer(BearerToken),
+    /// `Authorization: Basic <base64(username:password)>`
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// Headers computed by a callback on every request
+    Headers(HeaderProvider),
+}
+
+impl SseAuth {
+    /// Fixed bearer token
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::Bearer(BearerToken {
+            token: Arc::new(RwLock::new(Some(token.into()))),
+            refresh: None,
+            refresh_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// Bearer token obtained and rotated through `refresh`
+    ///
+    /// # Arguments
+    /// * `initial` - Token to start with; `None` calls `refresh` before the first request
+    /// * `refresh` - Returns a fresh token whenever the current one is rejected
+    pub fn bearer_with_refresh<F, Fut>(initial: Option<String>, refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, SseClientError>> + Send + 'static,
+    {
+        Self::Bearer(BearerToken {
+            token: Arc::new(RwLock::new(initial)),
+            refresh: Some(Arc::new(move || Box::pin(refresh()) as TokenFuture)),
+            refresh_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// HTTP Basic credentials
+    pub fn basic(username: impl Into<String>, password: Option<String>) -> Self {
+        Self::Basic {
+            username: username.into(),
+            password,
+        }
+    }
+
+    /// Headers from `provider`, evaluated for every request
+    pub fn headers<F>(provider: F) -> Self
+    where
+        F: Fn() -> Result<HeaderMap, SseClientError> + Send + Sync + 'static,
+    {
+        Self::Headers(Arc::new(provider))
+    }
+
+    /// Add credentials to `request`, returning what was applied so a
+    /// rejected token can be told apart from one refreshed meanwhile
+    pub(crate) async fn apply(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<(RequestBuilder, Option<String>), SseClientError> {
+        match self {
+            Self::Bearer(bearer) => {
+                let token = bearer.current_or_fetch().await?;
+                Ok((request.bearer_auth(&token), Some(token)))
+            }
+            Self::Basic { username, password } => {
+                Ok((request.basic_auth(username, password.as_ref()), None))
+            }
+            Self::Headers(provider) => Ok((request.headers(provider()?), None)),
+        }
+    }
+
+    /// React to a `401 Unauthorized` for a request sent with `rejected`;
+    /// returns whether the request is worth retrying
+    pub(crate) async fn unauthorized(
+        &self,
+        rejected: Option<&str>,
+    ) -> Result<bool, SseClientError> {
+        match self {
+            Self::Bearer(bearer) if bearer.refresh.is_some() => {
+                bearer.refresh_after(rejected).await
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+impl fmt::Debug for SseAuth {
+    // Never print credentials
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bearer(bearer) => f
+                .debug_struct("Bearer")
+                .field("refreshable", &bearer.refresh.is_some())
+                .finish_non_exhaustive(),
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Self::Headers(_) => f.write_str("Headers(..)"),
+        }
+    }
+}
+
+/// Shared bearer token state; clones of a client share one token
+#[derive(Clone)]
+pub struct BearerToken {
+    token: Arc<RwLock<Option<String>>>,
+    refresh: Option<TokenRefresh>,
+    /// Serializes refreshes so concurrent 401s trigger one refresh
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+impl BearerToken {
+    /// Token currently in use, if any
+    pub fn current(&self) -> Option<String> {
+        self.token.read().ok().and_then(|token| token.clone())
+    }
+
+    /// Replace the token, e.g. from an identity provider push notification
+    pub fn set(&self, token: impl Into<String>) {
+        if let Ok(mut current) = self.token.write() {
+            *current = Some(token.into());
+        }
+    }
+
+    async fn current_or_fetch(&self) -> Result<String, SseClientError> {
+        if let Some(token) = self.current() {
+            return Ok(token);
+        }
+        self.refresh_after(None).await?;
+        self.current()
+            .ok_or_else(|| SseClientError::AuthError("no bearer token available".to_string()))
+    }
+
+    async fn refresh_after(&self, rejected: Option<&str>) -> Result<bool, SseClientError> {
+        let Some(refresh) = &self.refresh else {
+            return Ok(false);
+        };
+
+        let _guard = self.refresh_lock.lock().await;
+        // Another request refreshed while we waited; retry with its token
+        let current = self.current();
+        if current.is_some() && current.as_deref() != rejected {
+            debug!("Bearer token already refreshed by a concurrent request");
+            return Ok(true);
+        }
+
+        let token = refresh().await?;
+        info!("Bearer token refreshed");
+        self.set(token);
+        Ok(true)
+    }
+}
@@ -0,0 +1,211 @@
+//! Large binary tool results saved to files
+//!
+//! Images, audio and blobs arrive base64-encoded inside the JSON result, so a
+//! screenshot can hold several megabytes of text for as long as the result is
+//! kept. With downloads enabled, content parts above a size threshold are
+//! decoded in chunks into files and replaced by `resource_link` parts pointing
+//! at them, and the encoded text is dropped.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use log::debug;
+use serde_json::{Value, json};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::SseClientError;
+
+/// Base64 characters decoded at a time; a multiple of 4
+const DECODE_CHUNK: usize = 64 * 1024;
+
+static NEXT_FILE: AtomicU64 = AtomicU64::new(0);
+
+/// When and where tool result content is saved to files
+#[derive(Debug, Clone)]
+pub struct DownloadConfig {
+    /// Decoded size, in bytes, from which a content part is saved to a file
+    pub threshold: usize,
+    /// Directory the files are created in
+    pub dir: PathBuf,
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 256 * 1024,
+            dir: std::env::temp_dir(),
+        }
+    }
+}
+
+/// A content part saved to a file
+///
+/// The file belongs to the caller; it is not removed when this is dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedFile {
+    /// Position of the part in the result's `content`
+    pub index: usize,
+    pub path: PathBuf,
+    pub mime_type: String,
+    /// Decoded size in bytes
+    pub size: u64,
+}
+
+impl DownloadedFile {
+    /// Open the file for reading
+    pub async fn open(&self) -> std::io::Result<tokio::fs::File> {
+        tokio::fs::File::open(&self.path).await
+    }
+
+    /// Delete the file
+    pub async fn remove(self) -> std::io::Result<()> {
+        tokio::fs::remove_file(&self.path).await
+    }
+}
+
+/// Save the large content parts of a `tools/call` result to files
+///
+/// Each saved part becomes a `resource_link` to a `file://` URI carrying the
+/// part's MIME type and decoded size. Parts whose data is not valid base64
+/// are an error; files already written for the result are kept.
+pub(crate) async fn offload(
+    result: &mut Value,
+    config: &DownloadConfig,
+) -> Result<Vec<DownloadedFile>, SseClientError> {
+    let Some(content) = result.get_mut("content").and_then(Value::as_array_mut) else {
+        return Ok(Vec::new());
+    };
+
+    let mut files = Vec::new();
+    for (index, part) in content.iter_mut().enumerate() {
+        let Some((data, mime_type)) = take_large_data(part, config.threshold) else {
+            continue;
+        };
+        let path = config.dir.join(file_name(&mime_type));
+        let size = write_decoded(path.clone(), data).await?;
+        debug!(
+            "Saved {} byte {} tool result to {}",
+            size,
+            mime_type,
+            path.display()
+        );
+
+        *part = json!({
+            "type": "resource_link",
+            "uri": format!("file://{}", path.display()),
+            "name": path.file_name().map(|name| name.to_string_lossy().into_owned()),
+            "mimeType": mime_type,
+            "size": size,
+        });
+        files.push(DownloadedFile {
+            index,
+            path,
+            mime_type,
+            size,
+        });
+    }
+    Ok(files)
+}
+
+/// Take the base64 data out of an image, audio or blob resource part at least
+/// `threshold` bytes long once decoded
+fn take_large_data(part: &mut Value, threshold: usize) -> Option<(String, String)> {
+    let is_resource = match part.get("type").and_then(Value::as_str)? {
+        "image" | "audio" => false,
+        "resource" => true,
+        _ => return None,
+    };
+    let holder = if is_resource {
+        part.get_mut("resource")?
+    } else {
+        part
+    };
+    let key = if holder.get("blob").is_some() {
+        "blob"
+    } else {
+        "data"
+    };
+    let encoded = holder.get(key).and_then(Value::as_str)?;
+    if encoded.len() / 4 * 3 < threshold {
+        return None;
+    }
+
+    let mime_type = holder
+        .get("mimeType")
+        .and_then(Value::as_str)
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    match holder.get_mut(key).map(Value::take) {
+        Some(Value::String(data)) => Some((data, mime_type)),
+        _ => None,
+    }
+}
+
+/// Decode `data` into a new file at `path`, returning the decoded size
+///
+/// A partly written file is removed.
+async fn write_decoded(path: PathBuf, mut data: String) -> Result<u64, SseClientError> {
+    tokio::task::spawn_blocking(move || {
+        data.retain(|c| !c.is_ascii_whitespace());
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| download_error(&path, e))?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        let written = decode_into(&mut writer, &data).and_then(|size| {
+            writer.flush().map_err(|e| e.to_string())?;
+            Ok(size)
+        });
+        written.map_err(|e| {
+            let _ = std::fs::remove_file(&path);
+            download_error(&path, e)
+        })
+    })
+    .await
+    .map_err(|e| SseClientError::DownloadError(e.to_string()))?
+}
+
+/// Decode `data` a chunk at a time, so the decoded bytes are never all in memory
+fn decode_into(writer: &mut impl Write, data: &str) -> Result<u64, String> {
+    let mut size = 0;
+    for chunk in data.as_bytes().chunks(DECODE_CHUNK) {
+        let bytes = BASE64.decode(chunk).map_err(|e| e.to_string())?;
+        writer.write_all(&bytes).map_err(|e| e.to_string())?;
+        size += bytes.len() as u64;
+    }
+    Ok(size)
+}
+
+/// Unique name with an extension matching `mime_type`
+fn file_name(mime_type: &str) -> String {
+    let extension = match mime_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "audio/wav" | "audio/x-wav" => "wav",
+        "audio/mpeg" => "mp3",
+        "audio/ogg" => "ogg",
+        "application/pdf" => "pdf",
+        _ => "bin",
+    };
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    format!(
+        "mcp-result-{}-{}-{}.{}",
+        std::process::id(),
+        millis,
+        NEXT_FILE.fetch_add(1, Ordering::Relaxed),
+        extension
+    )
+}
+
+fn download_error(path: &Path, error: impl std::fmt::Display) -> SseClientError {
+    SseClientError::DownloadError(format!("{}: {}", path.display(), error))
+}
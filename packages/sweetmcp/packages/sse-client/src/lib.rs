@@ -7,10 +7,24 @@ use reqwest::{Client, Response};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 use mcp_client_traits::{ClientError, McpClient};
-use sweet_mcp_type::{JsonValue, Response as McpResponse, ToolInfo, RequestId, Implementation};
+use sweet_mcp_type::{
+    Implementation, JsonValue, McpError, Request, Response as McpResponse, ToolInfo,
+    from_serde_json,
+};
+
+mod auth;
+pub use auth::{BearerToken, HeaderProvider, SseAuth, TokenFuture, TokenRefresh};
+
+mod download;
+pub use download::{DownloadConfig, DownloadedFile};
+
+mod multiplex;
+pub use multiplex::MultiplexConfig;
+use multiplex::{EventParser, Multiplexer};
 
 #[derive(Debug, Error)]
 pub enum SseClientError {
@@ -28,13 +42,38 @@ pub enum SseClientError {
     
     #[error("Missing result field in response")]
     MissingResult,
+
+    #[error("Authentication failed: {0}")]
+    AuthError(String),
+
+    #[error("Invalid JSON-RPC message: {0}")]
+    InvalidMessage(#[from] McpError),
+
+    #[error("Request channel closed")]
+    ChannelClosed,
+
+    #[error("Failed to save tool result content: {0}")]
+    DownloadError(String),
 }
 
+/// Header carrying the Streamable HTTP session assigned by the server
+const SESSION_HEADER: &str = "Mcp-Session-Id";
+
 /// MCP client that communicates via Server-Sent Events
 #[derive(Debug, Clone)]
 pub struct SseClient {
     base_url: String,
+    /// URL JSON-RPC requests are POSTed to
+    endpoint: String,
     http_client: Client,    headers: HashMap<String, String>,
+    /// Credentials applied to every request
+    auth: Option<SseAuth>,
+    /// Session ID from the server's last `Mcp-Session-Id` header
+    session_id: Arc<RwLock<Option<String>>>,
+    /// Concurrency cap and response routing when multiplexing
+    multiplexer: Option<Arc<Multiplexer>>,
+    /// Where large binary tool results are saved, when enabled
+    downloads: Option<DownloadConfig>,
 }
 
 impl SseClient {
@@ -42,40 +81,211 @@ impl SseClient {
     pub fn new(base_url: &str) -> Result<Self, SseClientError> {
         Ok(Self {
             base_url: base_url.to_string(),
+            endpoint: format!("{}/mcp", base_url),
             http_client: Client::new(),
             headers: HashMap::new(),
+            auth: None,
+            session_id: Arc::new(RwLock::new(None)),
+            multiplexer: None,
+            downloads: None,
         })
     }
+
+    /// Create a client for a Streamable HTTP endpoint
+    ///
+    /// Unlike `new`, `url` is the full MCP endpoint (e.g. `https://host/mcp`)
+    /// and is used as-is.
+    pub fn streamable_http(url: &str) -> Result<Self, SseClientError> {
+        Ok(Self {
+            base_url: url.trim_end_matches('/').to_string(),
+            endpoint: url.to_string(),
+            http_client: Client::new(),
+            headers: HashMap::new(),
+            auth: None,
+            session_id: Arc::new(RwLock::new(None)),
+            multiplexer: None,
+            downloads: None,
+        })
+    }
+
+    /// Create a Streamable HTTP client that multiplexes requests over HTTP/2
+    ///
+    /// For custom TLS settings, build a client from
+    /// [`MultiplexConfig::client_builder`] and use `streamable_http`,
+    /// `with_http_client` and `with_multiplexing` instead.
+    pub fn multiplexed(url: &str, config: MultiplexConfig) -> Result<Self, SseClientError> {
+        let http_client = config.client_builder().build()?;
+        Ok(Self::streamable_http(url)?
+            .with_http_client(http_client)
+            .with_multiplexing(config))
+    }
+
+    /// Session ID assigned by the server, if any
+    pub fn session_id(&self) -> Option<String> {
+        self.session_id.read().ok().and_then(|id| id.clone())
+    }
     
+    /// Use a preconfigured HTTP client (custom root certificates, mTLS identity, ...)
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Cap concurrent requests and route streamed responses by request ID
+    ///
+    /// Clones of the client share the cap. Requests share one connection
+    /// when the HTTP client speaks HTTP/2; see [`MultiplexConfig::client_builder`].
+    pub fn with_multiplexing(mut self, config: MultiplexConfig) -> Self {
+        self.multiplexer = Some(Arc::new(Multiplexer::new(&config)));
+        self
+    }
+
+    /// Save large image, audio and blob content of tool results to files
+    ///
+    /// Parts above `config.threshold` are replaced by `resource_link` parts
+    /// pointing at the saved files; use `call_tool_with_files` to get the
+    /// files themselves.
+    pub fn with_downloads(mut self, config: DownloadConfig) -> Self {
+        self.downloads = Some(config);
+        self
+    }
+
     /// Add custom header
     pub fn with_header(mut self, key: &str, value: &str) -> Self {
         self.headers.insert(key.to_string(), value.to_string());
         self
     }
+
+    /// Authenticate every request with `auth`
+    ///
+    /// Credentials are evaluated per request, so rotated tokens are picked up
+    /// without recreating the client.
+    pub fn with_auth(mut self, auth: SseAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Add static headers and credentials, returning the bearer token used
+    async fn authorize(
+        &self,
+        mut request_builder: reqwest::RequestBuilder,
+    ) -> Result<(reqwest::RequestBuilder, Option<String>), SseClientError> {
+        for (key, value) in &self.headers {
+            request_builder = request_builder.header(key, value);
+        }
+        match &self.auth {
+            Some(auth) => auth.apply(request_builder).await,
+            None => Ok((request_builder, None)),
+        }
+    }
     
     /// Send JSON-RPC request via POST and receive response
+    ///
+    /// `null` params are sent as an empty object.
     pub async fn send_request(&self, method: &str, params: Value) -> Result<Value, SseClientError> {
-        let request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": method,
-            "params": params,
-            "id": 1
-        });
-        
-        let mut request_builder = self.http_client
-            .post(format!("{}/mcp", self.base_url))
-            .header("Content-Type", "application/json");        
-        for (key, value) in &self.headers {
-            request_builder = request_builder.header(key, value);
+        let mut request = Request::builder(method);
+        if !params.is_null() {
+            request = request.params(from_serde_json(params));
         }
-        
-        let response = request_builder
-            .json(&request)
-            .send()
-            .await?;
-        
-        let response_json: Value = response.json().await?;
-        
+        self.send(request.build()?).await
+    }
+
+    /// Send a built request via POST and receive its result
+    ///
+    /// `tools/call` results are saved to files as configured by `with_downloads`.
+    pub async fn send(&self, request: Request) -> Result<Value, SseClientError> {
+        let is_tool_call = request.method == "tools/call";
+        let mut result = self.post(request).await?;
+        if is_tool_call && let Some(downloads) = &self.downloads {
+            download::offload(&mut result, downloads).await?;
+        }
+        Ok(result)
+    }
+
+    /// Call a tool, saving large binary content of its result to files
+    ///
+    /// Uses the `with_downloads` settings, or the defaults when downloads
+    /// are not enabled. Returns the result, with `resource_link` parts in
+    /// place of the saved content, and the saved files.
+    pub async fn call_tool_with_files(
+        &self,
+        name: &str,
+        arguments: JsonValue,
+    ) -> Result<(Value, Vec<DownloadedFile>), SseClientError> {
+        let request = Request::tool_call(name).arguments(arguments).build()?;
+        let mut result = self.post(request).await?;
+        let default_downloads = DownloadConfig::default();
+        let downloads = self.downloads.as_ref().unwrap_or(&default_downloads);
+        let files = download::offload(&mut result, downloads).await?;
+        Ok((result, files))
+    }
+
+    async fn post(&self, request: Request) -> Result<Value, SseClientError> {
+        let method = request.method.as_str();
+
+        // Multiplexed requests wait for a free slot, then register for their
+        // response before it can arrive on another request's stream
+        let _slot = match &self.multiplexer {
+            Some(multiplexer) => Some(multiplexer.acquire().await?),
+            None => None,
+        };
+        let pending = match &self.multiplexer {
+            Some(multiplexer) => Some(multiplexer.register(&serde_json::to_value(&request.id)?)),
+            None => None,
+        };
+
+        let mut retried = false;
+        let response = loop {
+            let mut request_builder = self.http_client
+                .post(&self.endpoint)
+                .header("Content-Type", "application/json")
+                .header("Accept", "application/json, text/event-stream");
+            if let Some(session_id) = self.session_id() {
+                request_builder = request_builder.header(SESSION_HEADER, session_id);
+            }
+            let (request_builder, token) = self.authorize(request_builder).await?;
+
+            let response = request_builder
+                .json(&request)
+                .send()
+                .await?;
+
+            // A rejected token gets one refresh and retry
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED
+                && !retried
+                && let Some(auth) = &self.auth
+                && auth.unauthorized(token.as_deref()).await?
+            {
+                debug!("Retrying {} with refreshed credentials", method);
+                retried = true;
+                continue;
+            }
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(SseClientError::AuthError(format!(
+                    "{} rejected credentials for {}",
+                    self.endpoint, method
+                )));
+            }
+            break response;
+        };
+
+        if let Some(session_id) = response.headers().get(SESSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            && let Ok(mut current) = self.session_id.write()
+        {
+            *current = Some(session_id.to_string());
+        }
+
+        // Streamable HTTP servers may answer with an event stream instead of a JSON body
+        let is_event_stream = response.headers().get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        let response_json: Value = match pending {
+            Some(pending) if is_event_stream => pending.read(response).await?,
+            _ if is_event_stream => parse_event_stream_response(&response.text().await?)?,
+            _ => response.json().await?,
+        };
+
         // Check for JSON-RPC error
         if let Some(error) = response_json.get("error") {
             let error_msg = error.to_string();
@@ -93,13 +303,10 @@ impl SseClient {
     pub async fn open_stream(&self) -> Result<SseStream, SseClientError> {
         info!("SSE stream connected to {}", self.base_url);
 
-        let mut request_builder = self.http_client
+        let request_builder = self.http_client
             .get(format!("{}/sse", self.base_url))
             .header("Accept", "text/event-stream");
-
-        for (key, value) in &self.headers {
-            request_builder = request_builder.header(key, value);
-        }
+        let (request_builder, _) = self.authorize(request_builder).await?;
 
         let response = request_builder.send().await?;
         debug!("SSE event received: {:?}", response);
@@ -155,114 +362,62 @@ impl McpClient for SseClient {
     fn call_tool(&self, name: &str, arguments: JsonValue) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<McpResponse, ClientError>> + Send + '_>> {
         let name = name.to_string();
         Box::pin(async move {
-            let args_serde = convert_sweet_to_serde(arguments);
-            
-            let params = serde_json::json!({
-                "name": &name,
-                "arguments": args_serde
-            });
-            
-            let result = self.send_request("tools/call", params).await
+            let request = Request::tool_call(name).arguments(arguments).build()?;
+            let id = request.id.clone();
+
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
-            
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(McpResponse {
-                id: RequestId::Str(format!("sse_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
+
+            Ok(McpResponse::success(id, from_serde_json(result)))
         })
     }
     
     fn initialize(&self, client_capabilities: JsonValue, client_info: Implementation) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<McpResponse, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            // Build parameters for initialize request
-            let params = serde_json::json!({
-                "protocolVersion": "2025-03-26",
-                "capabilities": convert_sweet_to_serde(client_capabilities),
-                "clientInfo": {
-                    "name": client_info.name,
-                    "version": client_info.version,
-                }
+            let client_info = serde_json::json!({
+                "name": client_info.name,
+                "version": client_info.version,
             });
-            
+            let request = Request::builder("initialize")
+                .param("protocolVersion", "2025-03-26")
+                .param("capabilities", client_capabilities)
+                .param("clientInfo", from_serde_json(client_info))
+                .build()?;
+            let id = request.id.clone();
+
             // Send initialize request to MCP server
-            let result = self.send_request("initialize", params).await
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
-            
-            // Convert result to Response
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(McpResponse {
-                id: RequestId::Str(format!("initialize_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
+
+            Ok(McpResponse::success(id, from_serde_json(result)))
         })
     }
     
     fn ping(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<McpResponse, ClientError>> + Send + '_>> {
         Box::pin(async move {
+            let request = Request::builder("ping").build()?;
+            let id = request.id.clone();
+
             // Send ping request to MCP server
-            let result = self.send_request("ping", serde_json::Value::Null).await
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
-            
-            // Convert result to Response
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(McpResponse {
-                id: RequestId::Str(format!("ping_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
+
+            Ok(McpResponse::success(id, from_serde_json(result)))
         })
     }
 }
 
-fn convert_sweet_to_serde(value: JsonValue) -> Value {
-    use simd_json::StaticNode;
-    match value {
-        JsonValue::Static(StaticNode::Null) => Value::Null,
-        JsonValue::Static(StaticNode::Bool(b)) => Value::Bool(b),
-        JsonValue::Static(StaticNode::I64(n)) => Value::Number(n.into()),
-        JsonValue::Static(StaticNode::U64(n)) => Value::Number(n.into()),
-        JsonValue::Static(StaticNode::F64(f)) => {
-            Value::Number(serde_json::Number::from_f64(f).unwrap_or_else(|| 0.into()))
-        }
-        JsonValue::String(s) => Value::String(s),
-        JsonValue::Array(arr) => {
-            Value::Array(arr.into_iter().map(convert_sweet_to_serde).collect())
-        }
-        JsonValue::Object(obj) => {
-            Value::Object(obj.into_iter().map(|(k, v)| (k, convert_sweet_to_serde(v))).collect())
-        }
-    }
-}
+/// Pick the JSON-RPC response out of an SSE body
+///
+/// Each event's `data:` lines are joined; server requests and notifications
+/// sent ahead of the response (messages without `result`/`error`) are skipped.
+fn parse_event_stream_response(body: &str) -> Result<Value, SseClientError> {
+    let mut parser = EventParser::default();
+    let mut events = parser.push(body.as_bytes());
+    events.extend(parser.finish());
 
-fn convert_serde_to_sweet(value: Value) -> JsonValue {
-    match value {
-        Value::Null => JsonValue::Static(simd_json::StaticNode::Null),
-        Value::Bool(b) => JsonValue::Static(simd_json::StaticNode::Bool(b)),
-        Value::Number(n) => {
-            if let Some(i) = n.as_i64() {
-                JsonValue::from(i)
-            } else if let Some(f) = n.as_f64() {
-                JsonValue::from(f)
-            } else {
-                JsonValue::Static(simd_json::StaticNode::Null)
-            }
-        }
-        Value::String(s) => JsonValue::String(s),
-        Value::Array(arr) => {
-            let vec: Vec<JsonValue> = arr.into_iter().map(convert_serde_to_sweet).collect();
-            JsonValue::Array(vec.into())
-        }
-        Value::Object(obj) => {
-            let map: simd_json::value::owned::Object = obj.into_iter()
-                .map(|(k, v)| (k, convert_serde_to_sweet(v)))
-                .collect();
-            JsonValue::Object(map.into())
-        }
-    }
-}
\ No newline at end of file
+    events.iter()
+        .filter_map(|event| serde_json::from_str::<Value>(event).ok())
+        .find(|message| message.get("result").is_some() || message.get("error").is_some())
+        .ok_or_else(|| SseClientError::ParseError("No JSON-RPC response in event stream".to_string()))
+}
@@ -0,0 +1,235 @@
+//! Concurrent requests over one HTTP/2 connection
+//!
+//! With multiplexing enabled, concurrent POSTs share an HTTP/2 connection as
+//! separate streams instead of queueing for pooled HTTP/1.1 connections, and
+//! the number of requests in flight is capped. Event stream responses are read
+//! as they arrive: a request completes as soon as the event answering it is
+//! received, and a response a server sends on another request's stream is
+//! handed to the request it answers by JSON-RPC id.
+
+use futures::StreamExt;
+use log::debug;
+use reqwest::{ClientBuilder, Response};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::{Semaphore, SemaphorePermit, oneshot};
+
+use crate::SseClientError;
+
+/// Concurrency and HTTP/2 flow control for a multiplexed client
+#[derive(Debug, Clone)]
+pub struct MultiplexConfig {
+    /// Requests in flight at once; further requests wait for a free slot
+    pub max_concurrent_requests: usize,
+    /// Initial HTTP/2 flow control window of each stream, in bytes
+    pub stream_window: u32,
+    /// Initial HTTP/2 flow control window of the connection, in bytes
+    pub connection_window: u32,
+    /// Grow the windows with the measured bandwidth-delay product; overrides
+    /// `stream_window` and `connection_window`
+    pub adaptive_window: bool,
+    /// Speak HTTP/2 without negotiation, for `http://` servers that support it
+    pub prior_knowledge: bool,
+}
+
+impl Default for MultiplexConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: 32,
+            stream_window: 1024 * 1024,
+            connection_window: 4 * 1024 * 1024,
+            adaptive_window: false,
+            prior_knowledge: false,
+        }
+    }
+}
+
+impl MultiplexConfig {
+    /// HTTP client builder set up for multiplexing
+    ///
+    /// Add TLS settings as needed, then pass the built client to
+    /// [`SseClient::with_http_client`](crate::SseClient::with_http_client).
+    /// HTTPS servers are asked for HTTP/2 during the TLS handshake.
+    pub fn client_builder(&self) -> ClientBuilder {
+        let builder = reqwest::Client::builder()
+            .http2_initial_stream_window_size(self.stream_window)
+            .http2_initial_connection_window_size(self.connection_window)
+            .http2_adaptive_window(self.adaptive_window);
+        if self.prior_knowledge {
+            builder.http2_prior_knowledge()
+        } else {
+            builder
+        }
+    }
+}
+
+/// Request slots and responses awaited by id
+#[derive(Debug)]
+pub(crate) struct Multiplexer {
+    slots: Semaphore,
+    waiting: Mutex<HashMap<String, oneshot::Sender<Value>>>,
+}
+
+impl Multiplexer {
+    pub(crate) fn new(config: &MultiplexConfig) -> Self {
+        Self {
+            slots: Semaphore::new(config.max_concurrent_requests.max(1)),
+            waiting: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a free request slot
+    pub(crate) async fn acquire(&self) -> Result<SemaphorePermit<'_>, SseClientError> {
+        self.slots
+            .acquire()
+            .await
+            .map_err(|_| SseClientError::ChannelClosed)
+    }
+
+    /// Start waiting for the response to the request with `id`
+    pub(crate) fn register(&self, id: &Value) -> PendingResponse<'_> {
+        let key = id.to_string();
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut waiting) = self.waiting.lock() {
+            waiting.insert(key.clone(), tx);
+        }
+        PendingResponse {
+            multiplexer: self,
+            key,
+            rx,
+        }
+    }
+
+    /// Hand a response to the request waiting for it; `false` when no
+    /// request is waiting
+    fn deliver(&self, key: &str, message: Value) -> bool {
+        let tx = self
+            .waiting
+            .lock()
+            .ok()
+            .and_then(|mut waiting| waiting.remove(key));
+        match tx {
+            Some(tx) => {
+                debug!("Routing response {} from another request's stream", key);
+                // The waiter may have given up already
+                let _ = tx.send(message);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Response awaited by one multiplexed request
+pub(crate) struct PendingResponse<'a> {
+    multiplexer: &'a Multiplexer,
+    key: String,
+    rx: oneshot::Receiver<Value>,
+}
+
+impl PendingResponse<'_> {
+    /// Read an event stream until this request's response arrives on it or
+    /// on another request's stream
+    pub(crate) async fn read(mut self, response: Response) -> Result<Value, SseClientError> {
+        let mut stream = response.bytes_stream();
+        let mut parser = EventParser::default();
+        let mut registered = true;
+        loop {
+            tokio::select! {
+                delivered = &mut self.rx, if registered => match delivered {
+                    Ok(message) => return Ok(message),
+                    Err(_) => registered = false,
+                },
+                chunk = stream.next() => {
+                    let Some(chunk) = chunk else { break };
+                    for event in parser.push(&chunk?) {
+                        if let Some(message) = self.route(&event) {
+                            return Ok(message);
+                        }
+                    }
+                }
+            }
+        }
+        for event in parser.finish() {
+            if let Some(message) = self.route(&event) {
+                return Ok(message);
+            }
+        }
+
+        match self.rx.try_recv() {
+            Ok(message) if registered => Ok(message),
+            _ => Err(SseClientError::ParseError(
+                "No JSON-RPC response in event stream".to_string(),
+            )),
+        }
+    }
+
+    /// This request's response, after routing responses to other requests
+    fn route(&self, event: &str) -> Option<Value> {
+        let message: Value = serde_json::from_str(event).ok()?;
+        if message.get("result").is_none() && message.get("error").is_none() {
+            // Server requests and notifications sent ahead of the response
+            return None;
+        }
+        let key = message.get("id")?.to_string();
+        if key == self.key {
+            return Some(message);
+        }
+        if !self.multiplexer.deliver(&key, message) {
+            debug!("Dropping response {} that no request is waiting for", key);
+        }
+        None
+    }
+}
+
+impl Drop for PendingResponse<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut waiting) = self.multiplexer.waiting.lock() {
+            waiting.remove(&self.key);
+        }
+    }
+}
+
+/// Incremental parser for `text/event-stream` bodies
+///
+/// Each event's `data:` lines are joined with newlines.
+#[derive(Debug, Default)]
+pub(crate) struct EventParser {
+    line: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl EventParser {
+    /// Feed a chunk of the body, returning the events it completes
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if byte == b'\n' {
+                let line = std::mem::take(&mut self.line);
+                let line = String::from_utf8_lossy(&line);
+                self.line_done(line.strip_suffix('\r').unwrap_or(&line), &mut events);
+            } else {
+                self.line.push(byte);
+            }
+        }
+        events
+    }
+
+    /// End of the body: complete a trailing event without a blank line
+    pub(crate) fn finish(&mut self) -> Vec<String> {
+        self.push(b"\n\n")
+    }
+
+    fn line_done(&mut self, line: &str, events: &mut Vec<String>) {
+        if line.is_empty() {
+            if !self.data.is_empty() {
+                events.push(self.data.join("\n"));
+                self.data.clear();
+            }
+        } else if let Some(value) = line.strip_prefix("data:") {
+            self.data
+                .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! stdio-client: MCP JSON-RPC client over subprocess stdin/stdout
 //!
-//! Implements newline-delimited JSON-RPC protocol for MCP stdio transport.
+//! Implements newline-delimited JSON-RPC protocol for MCP stdio transport,
+//! plus the same framing over Unix domain sockets (`UdsClient`).
 
 use log::{debug, info, warn};
 use serde_json::Value;
@@ -12,7 +13,24 @@ use tokio::sync::Mutex;
 use std::sync::Arc;
 
 use mcp_client_traits::{ClientError, McpClient};
-use sweet_mcp_type::{JsonValue, Response, ToolInfo, RequestId, Implementation};
+use sweet_mcp_type::{
+    Implementation, JsonValue, McpError, Notification, Request, Response, ToolInfo,
+    from_serde_json,
+};
+
+mod session;
+pub use session::{StdioSession, SUPPORTED_PROTOCOL_VERSIONS};
+
+mod registry;
+pub use registry::{ClientRegistry, ServerConfig, ServersConfig};
+
+#[cfg(unix)]
+mod uds;
+#[cfg(unix)]
+pub use uds::UdsClient;
+
+/// MCP protocol revision requested during `initialize`
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
 
 #[derive(Debug, Error)]
 pub enum StdioClientError {
@@ -30,6 +48,21 @@ pub enum StdioClientError {
     
     #[error("Failed to receive response: {0}")]
     ReceiveError(String),
+
+    #[error("MCP handshake failed: {0}")]
+    HandshakeError(String),
+
+    #[error("Invalid JSON-RPC message: {0}")]
+    InvalidMessage(#[from] McpError),
+
+    #[error("Invalid server config: {0}")]
+    ConfigError(String),
+
+    #[error("No MCP server named '{0}'")]
+    UnknownServer(String),
+
+    #[error("MCP server '{0}' is disabled")]
+    ServerDisabled(String),
 }
 
 /// MCP client that communicates via subprocess stdin/stdout
@@ -53,15 +86,28 @@ impl StdioClient {
         env: &[(&str, &str)],
     ) -> Result<Self, StdioClientError> {
         let mut cmd = Command::new(command);
-        cmd.args(args)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::inherit())
-            .kill_on_drop(true);  // Ensure subprocess is killed when client is dropped
-
+        cmd.args(args);
         for (key, value) in env {
             cmd.env(key, value);
         }
+        Self::spawn(cmd)
+    }
+
+    /// Spawn the server a [`ServerConfig`] describes, in its `cwd` if set
+    pub async fn from_config(config: &ServerConfig) -> Result<Self, StdioClientError> {
+        let mut cmd = Command::new(&config.command);
+        cmd.args(&config.args).envs(&config.env);
+        if let Some(cwd) = &config.cwd {
+            cmd.current_dir(cwd);
+        }
+        Self::spawn(cmd)
+    }
+
+    fn spawn(mut cmd: Command) -> Result<Self, StdioClientError> {
+        cmd.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);  // Ensure subprocess is killed when client is dropped
 
         info!("Spawning STDIO process: {:?}", cmd);
         let mut child = cmd.spawn()?;
@@ -78,15 +124,61 @@ impl StdioClient {
         })
     }
     
+    /// Spawn a server and complete the MCP initialization handshake
+    ///
+    /// Sends `initialize` and then `notifications/initialized`, returning a
+    /// session that is ready for tool, resource and prompt requests.
+    ///
+    /// # Arguments
+    /// * `command` - Command to execute
+    /// * `args` - Command arguments
+    /// * `env` - Environment variables as array of tuples
+    /// * `client_info` - Name and version reported to the server
+    /// * `capabilities` - Client capabilities (`{}` for none)
+    pub async fn connect_and_initialize(
+        command: &str,
+        args: &[String],
+        env: &[(&str, &str)],
+        client_info: Implementation,
+        capabilities: Value,
+    ) -> Result<StdioSession, StdioClientError> {
+        let client = Self::new(command, args, env).await?;
+        StdioSession::initialize(client, client_info, capabilities).await
+    }
+
+    /// Send a JSON-RPC notification; no response is read
+    pub async fn send_notification(&self, method: &str, params: Value) -> Result<(), StdioClientError> {
+        let notification = Notification::builder(method)
+            .params(from_serde_json(params))
+            .build()?;
+
+        let notification_str = serde_json::to_string(&notification)?;
+
+        debug!("STDIO input: {}", notification_str);
+
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(notification_str.as_bytes()).await
+            .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+        stdin.write_all(b"\n").await
+            .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+        stdin.flush().await
+            .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+        Ok(())
+    }
+
     /// Send a JSON-RPC request and receive response
+    ///
+    /// `null` params are sent as an empty object.
     pub async fn send_request(&self, method: &str, params: Value) -> Result<Value, StdioClientError> {
-        let request = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": method,
-            "params": params,
-            "id": 1
-        });
-        
+        let mut request = Request::builder(method);
+        if !params.is_null() {
+            request = request.params(from_serde_json(params));
+        }
+        self.send(request.build()?).await
+    }
+
+    /// Send a built request and receive its result
+    pub async fn send(&self, request: Request) -> Result<Value, StdioClientError> {
         let request_str = serde_json::to_string(&request)?;
 
         debug!("STDIO input: {}", request_str);
@@ -128,6 +220,38 @@ impl StdioClient {
             .ok_or_else(|| StdioClientError::ReceiveError("Missing result field".to_string()))
     }
     
+    /// Wait for the next notification the server sends on its own
+    ///
+    /// Returns the notification's method and params (`null` when absent).
+    /// Responses and server requests are skipped, so only call this on a
+    /// client with no requests in flight; it shares stdout with `send`.
+    /// Returns `None` once the server closes stdout.
+    pub async fn next_notification(&self) -> Result<Option<(String, Value)>, StdioClientError> {
+        let mut stdout = self.stdout.lock().await;
+        loop {
+            let mut line = String::new();
+            let read = stdout.read_line(&mut line).await
+                .map_err(|e| StdioClientError::ReceiveError(e.to_string()))?;
+            if read == 0 {
+                return Ok(None);
+            }
+
+            debug!("STDIO output: {}", line.trim());
+
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                warn!("Skipping non-JSON STDIO output: {}", line.trim());
+                continue;
+            };
+            if message.get("id").is_some() {
+                continue;
+            }
+            if let Some(method) = message.get("method").and_then(Value::as_str) {
+                let params = message.get("params").cloned().unwrap_or(Value::Null);
+                return Ok(Some((method.to_string(), params)));
+            }
+        }
+    }
+
     /// Gracefully shutdown the STDIO client and wait for process exit
     ///
     /// This method waits for the subprocess to exit and logs the exit status.
@@ -185,114 +309,49 @@ impl McpClient for StdioClient {
     fn call_tool(&self, name: &str, arguments: JsonValue) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
         let name = name.to_string();
         Box::pin(async move {
-            let args_serde = convert_sweet_to_serde(arguments);
-            
-            let params = serde_json::json!({
-                "name": &name,
-                "arguments": args_serde
-            });
-            
-            let result = self.send_request("tools/call", params).await
+            let request = Request::tool_call(name).arguments(arguments).build()?;
+            let id = request.id.clone();
+
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
             
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(Response {
-                id: RequestId::Str(format!("stdio_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
+            Ok(Response::success(id, from_serde_json(result)))
         })
     }
     
+    // Request half of the handshake only; `StdioClient::connect_and_initialize`
+    // also sends `notifications/initialized`
     fn initialize(&self, client_capabilities: JsonValue, client_info: Implementation) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
-            // Build parameters for initialize request
-            let params = serde_json::json!({
-                "protocolVersion": "2025-03-26",
-                "capabilities": convert_sweet_to_serde(client_capabilities),
-                "clientInfo": {
-                    "name": client_info.name,
-                    "version": client_info.version,
-                }
+            let client_info = serde_json::json!({
+                "name": client_info.name,
+                "version": client_info.version,
             });
-            
+            let request = Request::builder("initialize")
+                .param("protocolVersion", PROTOCOL_VERSION)
+                .param("capabilities", client_capabilities)
+                .param("clientInfo", from_serde_json(client_info))
+                .build()?;
+            let id = request.id.clone();
+
             // Send initialize request to MCP server
-            let result = self.send_request("initialize", params).await
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
-            
-            // Convert result to Response
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(Response {
-                id: RequestId::Str(format!("initialize_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
+
+            Ok(Response::success(id, from_serde_json(result)))
         })
     }
     
     fn ping(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
         Box::pin(async move {
+            let request = Request::builder("ping").build()?;
+            let id = request.id.clone();
+
             // Send ping request to MCP server
-            let result = self.send_request("ping", serde_json::Value::Null).await
+            let result = self.send(request).await
                 .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
-            
-            // Convert result to Response
-            let response_data = convert_serde_to_sweet(result);
-            
-            Ok(Response {
-                id: RequestId::Str(format!("ping_{}", uuid::Uuid::new_v4())),
-                result: Some(response_data),
-                error: None,
-            })
-        })
-    }
-}
 
-fn convert_sweet_to_serde(value: JsonValue) -> Value {
-    use simd_json::StaticNode;
-    match value {
-        JsonValue::Static(StaticNode::Null) => Value::Null,
-        JsonValue::Static(StaticNode::Bool(b)) => Value::Bool(b),
-        JsonValue::Static(StaticNode::I64(n)) => Value::Number(n.into()),
-        JsonValue::Static(StaticNode::U64(n)) => Value::Number(n.into()),
-        JsonValue::Static(StaticNode::F64(f)) => {
-            Value::Number(serde_json::Number::from_f64(f).unwrap_or_else(|| 0.into()))
-        }
-        JsonValue::String(s) => Value::String(s),
-        JsonValue::Array(arr) => {
-            Value::Array(arr.into_iter().map(convert_sweet_to_serde).collect())
-        }
-        JsonValue::Object(obj) => {
-            Value::Object(obj.into_iter().map(|(k, v)| (k, convert_sweet_to_serde(v))).collect())
-        }
+            Ok(Response::success(id, from_serde_json(result)))
+        })
     }
 }
-
-fn convert_serde_to_sweet(value: Value) -> JsonValue {
-    match value {
-        Value::Null => JsonValue::Static(simd_json::StaticNode::Null),
-        Value::Bool(b) => JsonValue::Static(simd_json::StaticNode::Bool(b)),
-        Value::Number(n) => {
-            if let Some(i) = n.as_i64() {
-                JsonValue::from(i)
-            } else if let Some(f) = n.as_f64() {
-                JsonValue::from(f)
-            } else {
-                JsonValue::Static(simd_json::StaticNode::Null)
-            }
-        }
-        Value::String(s) => JsonValue::String(s),
-        Value::Array(arr) => {
-            let vec: Vec<JsonValue> = arr.into_iter().map(convert_serde_to_sweet).collect();
-            JsonValue::Array(vec.into())
-        }
-        Value::Object(obj) => {
-            let map: simd_json::value::owned::Object = obj.into_iter()
-                .map(|(k, v)| (k, convert_serde_to_sweet(v)))
-                .collect();
-            JsonValue::Object(map.into())
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,236 @@
+//! Named MCP servers loaded from an agent host's config file
+//!
+//! Hosts such as Claude Desktop describe their stdio servers under
+//! `mcpServers`, each with a command, arguments, environment and working
+//! directory. [`ServersConfig`] reads that layout from JSON or TOML and
+//! [`ClientRegistry`] starts each server the first time it is asked for,
+//! so servers that are never used are never spawned.
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Mutex;
+
+use sweet_mcp_type::Implementation;
+
+use crate::{StdioClient, StdioClientError, StdioSession};
+
+/// How to start one MCP server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Set on top of the host's environment
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Working directory; the host's when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Listed but not started until enabled
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// Every server a host knows about, by name
+///
+/// ```json
+/// {"mcpServers": {"files": {"command": "mcp-files", "args": ["--root", "."]}}}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServersConfig {
+    #[serde(rename = "mcpServers", alias = "mcp_servers", default)]
+    pub servers: BTreeMap<String, ServerConfig>,
+}
+
+impl ServersConfig {
+    /// Read a config file; `.toml` files are TOML, anything else JSON
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StdioClientError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            StdioClientError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let config = if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml(&text)
+        } else {
+            Self::from_json(&text)
+        };
+        config.map_err(|e| match e {
+            StdioClientError::ConfigError(message) => {
+                StdioClientError::ConfigError(format!("{}: {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, StdioClientError> {
+        let config: Self =
+            serde_json::from_str(text).map_err(|e| StdioClientError::ConfigError(e.to_string()))?;
+        config.validate()
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, StdioClientError> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| StdioClientError::ConfigError(e.to_string()))?;
+        config.validate()
+    }
+
+    fn validate(self) -> Result<Self, StdioClientError> {
+        if let Some((name, _)) = self
+            .servers
+            .iter()
+            .find(|(_, server)| server.command.trim().is_empty())
+        {
+            return Err(StdioClientError::ConfigError(format!(
+                "server '{}' has no command",
+                name
+            )));
+        }
+        Ok(self)
+    }
+}
+
+struct Entry {
+    config: ServerConfig,
+    enabled: AtomicBool,
+    // Held while spawning, so concurrent first requests start one process
+    session: Mutex<Option<Arc<StdioSession>>>,
+}
+
+/// Named servers, each spawned and initialized on first use
+pub struct ClientRegistry {
+    servers: BTreeMap<String, Entry>,
+    client_info: Implementation,
+    capabilities: Value,
+}
+
+impl ClientRegistry {
+    /// Registry over `config`; nothing is spawned yet
+    ///
+    /// # Arguments
+    /// * `client_info` - Name and version reported to every server
+    /// * `capabilities` - Client capabilities (`{}` for none)
+    pub fn new(config: ServersConfig, client_info: Implementation, capabilities: Value) -> Self {
+        let servers = config
+            .servers
+            .into_iter()
+            .map(|(name, config)| {
+                let entry = Entry {
+                    enabled: AtomicBool::new(!config.disabled),
+                    config,
+                    session: Mutex::new(None),
+                };
+                (name, entry)
+            })
+            .collect();
+        Self {
+            servers,
+            client_info,
+            capabilities,
+        }
+    }
+
+    /// Registry over the servers in the config file at `path`
+    pub fn load(
+        path: impl AsRef<Path>,
+        client_info: Implementation,
+        capabilities: Value,
+    ) -> Result<Self, StdioClientError> {
+        Ok(Self::new(
+            ServersConfig::load(path)?,
+            client_info,
+            capabilities,
+        ))
+    }
+
+    /// Every configured server, enabled or not, by name
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.servers.keys().map(String::as_str)
+    }
+
+    /// Configured servers that are enabled
+    pub fn enabled(&self) -> impl Iterator<Item = &str> {
+        self.servers
+            .iter()
+            .filter(|(_, entry)| entry.enabled.load(Ordering::Acquire))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// How `name` is started, if it is configured
+    pub fn config(&self, name: &str) -> Option<&ServerConfig> {
+        self.servers.get(name).map(|entry| &entry.config)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.servers
+            .get(name)
+            .is_some_and(|entry| entry.enabled.load(Ordering::Acquire))
+    }
+
+    /// Whether `name` has a running session
+    pub async fn is_running(&self, name: &str) -> bool {
+        match self.servers.get(name) {
+            Some(entry) => entry.session.lock().await.is_some(),
+            None => false,
+        }
+    }
+
+    /// Enable or disable `name`
+    ///
+    /// Disabling stops the running server once callers holding its session
+    /// let go of it.
+    pub async fn set_enabled(&self, name: &str, enabled: bool) -> Result<(), StdioClientError> {
+        let entry = self.entry(name)?;
+        entry.enabled.store(enabled, Ordering::Release);
+        if !enabled {
+            entry.session.lock().await.take();
+        }
+        info!(
+            "MCP server '{}' {}",
+            name,
+            if enabled { "enabled" } else { "disabled" }
+        );
+        Ok(())
+    }
+
+    /// The initialized session for `name`, spawning the server on first use
+    pub async fn get(&self, name: &str) -> Result<Arc<StdioSession>, StdioClientError> {
+        let entry = self.entry(name)?;
+        if !entry.enabled.load(Ordering::Acquire) {
+            return Err(StdioClientError::ServerDisabled(name.to_string()));
+        }
+
+        let mut session = entry.session.lock().await;
+        if let Some(session) = session.as_ref() {
+            return Ok(Arc::clone(session));
+        }
+
+        info!("Starting MCP server '{}'", name);
+        let client = StdioClient::from_config(&entry.config).await?;
+        let started = Arc::new(
+            StdioSession::initialize(client, self.client_info.clone(), self.capabilities.clone())
+                .await?,
+        );
+        *session = Some(Arc::clone(&started));
+        Ok(started)
+    }
+
+    /// Drop the running session for `name`, if any
+    ///
+    /// The next [`get`](Self::get) spawns the server again, e.g. after it
+    /// exited with [`StdioClientError::ProcessTerminated`].
+    pub async fn stop(&self, name: &str) -> Result<(), StdioClientError> {
+        self.entry(name)?.session.lock().await.take();
+        Ok(())
+    }
+
+    fn entry(&self, name: &str) -> Result<&Entry, StdioClientError> {
+        self.servers
+            .get(name)
+            .ok_or_else(|| StdioClientError::UnknownServer(name.to_string()))
+    }
+}
@@ -0,0 +1,158 @@
+//! MCP lifecycle handshake over the stdio transport
+//!
+//! A spec-compliant session starts with an `initialize` request, after which
+//! the client must send `notifications/initialized` before any other request.
+//! [`StdioSession`] performs both steps and keeps what the server negotiated.
+
+use log::{debug, info};
+use serde_json::Value;
+use std::ops::Deref;
+
+use sweet_mcp_type::Implementation;
+
+use crate::{PROTOCOL_VERSION, StdioClient, StdioClientError};
+
+/// Protocol revisions this client can speak, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[PROTOCOL_VERSION, "2024-11-05"];
+
+/// An initialized MCP session over a [`StdioClient`]
+///
+/// Dereferences to the client, so requests are sent with
+/// `session.send_request(..)` or the [`McpClient`](mcp_client_traits::McpClient)
+/// methods once the handshake has completed.
+#[derive(Debug)]
+pub struct StdioSession {
+    client: StdioClient,
+    protocol_version: String,
+    server_info: Implementation,
+    capabilities: Value,
+    instructions: Option<String>,
+}
+
+impl StdioSession {
+    /// Run the handshake on an already spawned client
+    ///
+    /// Sends `initialize`, checks the negotiated protocol version, records the
+    /// server's capabilities and then sends `notifications/initialized`.
+    ///
+    /// # Arguments
+    /// * `client` - Client whose server has not been initialized yet
+    /// * `client_info` - Name and version reported to the server
+    /// * `capabilities` - Client capabilities, e.g. `{"roots": {"listChanged": true}}`
+    pub async fn initialize(
+        client: StdioClient,
+        client_info: Implementation,
+        capabilities: Value,
+    ) -> Result<Self, StdioClientError> {
+        let result = client
+            .send_request(
+                "initialize",
+                serde_json::json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": capabilities,
+                    "clientInfo": {
+                        "name": client_info.name,
+                        "version": client_info.version,
+                    }
+                }),
+            )
+            .await?;
+        debug!("initialize result: {}", result);
+
+        let protocol_version = result
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .ok_or_else(|| handshake_error("initialize result has no protocolVersion"))?
+            .to_string();
+        // The server answers with a version it supports; if we don't, the spec
+        // says to disconnect rather than carry on with mismatched semantics
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&protocol_version.as_str()) {
+            return Err(handshake_error(format!(
+                "server selected unsupported protocol version {}",
+                protocol_version
+            )));
+        }
+
+        let server_info = result
+            .get("serverInfo")
+            .and_then(|info| {
+                Some(Implementation {
+                    name: info.get("name")?.as_str()?.to_string(),
+                    version: info.get("version")?.as_str()?.to_string(),
+                })
+            })
+            .ok_or_else(|| handshake_error("initialize result has no serverInfo"))?;
+
+        let capabilities = result
+            .get("capabilities")
+            .filter(|capabilities| capabilities.is_object())
+            .cloned()
+            .ok_or_else(|| handshake_error("initialize result has no capabilities"))?;
+
+        let instructions = result
+            .get("instructions")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        client
+            .send_notification("notifications/initialized", Value::Null)
+            .await?;
+
+        info!(
+            "MCP session initialized with {} {} (protocol {})",
+            server_info.name, server_info.version, protocol_version
+        );
+
+        Ok(Self {
+            client,
+            protocol_version,
+            server_info,
+            capabilities,
+            instructions,
+        })
+    }
+
+    /// Protocol revision the server selected
+    pub fn protocol_version(&self) -> &str {
+        &self.protocol_version
+    }
+
+    /// Server name and version from `initialize`
+    pub fn server_info(&self) -> &Implementation {
+        &self.server_info
+    }
+
+    /// Capabilities object the server advertised
+    pub fn server_capabilities(&self) -> &Value {
+        &self.capabilities
+    }
+
+    /// Whether the server advertised `capability` (e.g. `"tools"`, `"prompts"`)
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities
+            .get(capability)
+            .is_some_and(|value| !value.is_null())
+    }
+
+    /// Usage hints the server provided, if any
+    pub fn instructions(&self) -> Option<&str> {
+        self.instructions.as_deref()
+    }
+
+    /// Give up the session and return the underlying client
+    pub fn into_client(self) -> StdioClient {
+        self.client
+    }
+}
+
+impl Deref for StdioSession {
+    type Target = StdioClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+fn handshake_error(message: impl Into<String>) -> StdioClientError {
+    StdioClientError::HandshakeError(message.into())
+}
@@ -0,0 +1,167 @@
+//! MCP JSON-RPC client over a Unix domain socket
+//!
+//! Uses the same newline-delimited framing as the stdio transport, so it can
+//! talk to a SweetMCP daemon socket or any MCP server listening on a UDS path.
+
+use log::{debug, info};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::Mutex;
+
+use mcp_client_traits::{ClientError, McpClient};
+use sweet_mcp_type::{
+    Implementation, JsonValue, Request, Response, ToolInfo, from_serde_json,
+};
+
+use crate::StdioClientError;
+
+/// MCP client that communicates over a Unix domain socket
+#[derive(Debug)]
+pub struct UdsClient {
+    path: PathBuf,
+    writer: Arc<Mutex<OwnedWriteHalf>>,
+    reader: Arc<Mutex<BufReader<OwnedReadHalf>>>,
+}
+
+impl UdsClient {
+    /// Connect to an MCP server listening on `path`
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, StdioClientError> {
+        let path = path.as_ref().to_path_buf();
+        info!("Connecting to MCP socket: {}", path.display());
+
+        let stream = UnixStream::connect(&path).await?;
+        let (reader, writer) = stream.into_split();
+
+        Ok(Self {
+            path,
+            writer: Arc::new(Mutex::new(writer)),
+            reader: Arc::new(Mutex::new(BufReader::new(reader))),
+        })
+    }
+
+    /// Socket path this client is connected to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Send a JSON-RPC request and receive response
+    ///
+    /// `null` params are sent as an empty object.
+    pub async fn send_request(&self, method: &str, params: Value) -> Result<Value, StdioClientError> {
+        let mut request = Request::builder(method);
+        if !params.is_null() {
+            request = request.params(from_serde_json(params));
+        }
+        self.send(request.build()?).await
+    }
+
+    /// Send a built request and receive its result
+    pub async fn send(&self, request: Request) -> Result<Value, StdioClientError> {
+        let request_str = serde_json::to_string(&request)?;
+
+        debug!("UDS input: {}", request_str);
+
+        // Hold the reader across the write so concurrent callers cannot
+        // consume each other's responses
+        let mut reader = self.reader.lock().await;
+        {
+            let mut writer = self.writer.lock().await;
+            writer.write_all(request_str.as_bytes()).await
+                .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+            writer.write_all(b"\n").await
+                .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+            writer.flush().await
+                .map_err(|e| StdioClientError::SendError(e.to_string()))?;
+        }
+
+        let mut response_line = String::new();
+        reader.read_line(&mut response_line).await
+            .map_err(|e| StdioClientError::ReceiveError(e.to_string()))?;
+
+        if response_line.is_empty() {
+            return Err(StdioClientError::ProcessTerminated);
+        }
+
+        debug!("UDS output: {}", response_line.trim());
+
+        let response: Value = serde_json::from_str(&response_line)?;
+
+        // Check for JSON-RPC error
+        if let Some(error) = response.get("error") {
+            return Err(StdioClientError::ReceiveError(
+                format!("JSON-RPC error: {}", error)
+            ));
+        }
+
+        // Return result field
+        response.get("result")
+            .cloned()
+            .ok_or_else(|| StdioClientError::ReceiveError("Missing result field".to_string()))
+    }
+}
+
+impl McpClient for UdsClient {
+    fn list_tools(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<ToolInfo>, ClientError>> + Send + '_>> {
+        Box::pin(async move {
+            let result = self.send_request("tools/list", Value::Null).await
+                .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
+
+            let tools_value = result.get("tools")
+                .ok_or_else(|| ClientError::response_parse("Missing 'tools' field", "list_tools response"))?;
+
+            let tools: Vec<ToolInfo> = serde_json::from_value(tools_value.clone())
+                .map_err(|e| ClientError::response_parse(format!("Failed to parse tools: {}", e), "ToolInfo deserialization"))?;
+
+            Ok(tools)
+        })
+    }
+
+    fn call_tool(&self, name: &str, arguments: JsonValue) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        let name = name.to_string();
+        Box::pin(async move {
+            let request = Request::tool_call(name).arguments(arguments).build()?;
+            let id = request.id.clone();
+
+            let result = self.send(request).await
+                .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
+
+            Ok(Response::success(id, from_serde_json(result)))
+        })
+    }
+
+    fn initialize(&self, client_capabilities: JsonValue, client_info: Implementation) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        Box::pin(async move {
+            let client_info = serde_json::json!({
+                "name": client_info.name,
+                "version": client_info.version,
+            });
+            let request = Request::builder("initialize")
+                .param("protocolVersion", "2025-03-26")
+                .param("capabilities", client_capabilities)
+                .param("clientInfo", from_serde_json(client_info))
+                .build()?;
+            let id = request.id.clone();
+
+            let result = self.send(request).await
+                .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
+
+            Ok(Response::success(id, from_serde_json(result)))
+        })
+    }
+
+    fn ping(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ClientError>> + Send + '_>> {
+        Box::pin(async move {
+            let request = Request::builder("ping").build()?;
+            let id = request.id.clone();
+
+            let result = self.send(request).await
+                .map_err(|e| ClientError::RequestBuild(e.to_string()))?;
+
+            Ok(Response::success(id, from_serde_json(result)))
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! Initialization handshake against a scripted `sh` server
+
+#![cfg(unix)]
+
+use serde_json::json;
+use sweet_mcp_type::Implementation;
+use sweetmcp_stdio_client::{StdioClient, StdioClientError};
+
+/// Answers `initialize` with `version`, then reports whether the next line
+/// was the `notifications/initialized` notification in its reply to the
+/// following request
+fn fake_server(version: &str) -> Vec<String> {
+    let script = format!(
+        r#"read -r init
+printf '%s\n' '{{"jsonrpc":"2.0","id":1,"result":{{"protocolVersion":"{version}","capabilities":{{"tools":{{}}}},"serverInfo":{{"name":"fake","version":"1.0"}},"instructions":"be nice"}}}}'
+read -r note
+case "$note" in *'"method":"notifications/initialized"'*) ok=true ;; *) ok=false ;; esac
+read -r request
+printf '{{"jsonrpc":"2.0","id":1,"result":{{"initialized":%s}}}}\n' "$ok"
+"#
+    );
+    vec!["-c".to_string(), script]
+}
+
+fn client_info() -> Implementation {
+    Implementation {
+        name: "handshake-test".to_string(),
+        version: "0.1.0".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn connect_and_initialize_sends_initialized_notification() {
+    let session = StdioClient::connect_and_initialize(
+        "sh",
+        &fake_server("2025-03-26"),
+        &[],
+        client_info(),
+        json!({}),
+    )
+    .await
+    .expect("handshake succeeds");
+
+    assert_eq!(session.protocol_version(), "2025-03-26");
+    assert_eq!(session.server_info().name, "fake");
+    assert_eq!(session.server_info().version, "1.0");
+    assert!(session.has_capability("tools"));
+    assert!(!session.has_capability("prompts"));
+    assert_eq!(session.instructions(), Some("be nice"));
+
+    let result = session.send_request("ping", json!({})).await.expect("ping");
+    assert_eq!(result["initialized"], true);
+}
+
+#[tokio::test]
+async fn unsupported_protocol_version_fails_handshake() {
+    let result = StdioClient::connect_and_initialize(
+        "sh",
+        &fake_server("1999-01-01"),
+        &[],
+        client_info(),
+        json!({}),
+    )
+    .await;
+
+    assert!(
+        matches!(result, Err(StdioClientError::HandshakeError(_))),
+        "{:?}",
+        result
+    );
+}
@@ -0,0 +1,31 @@
+//! Server-initiated notifications from a scripted `sh` server
+
+#![cfg(unix)]
+
+use serde_json::json;
+use sweetmcp_stdio_client::StdioClient;
+
+#[tokio::test]
+async fn next_notification_skips_other_messages() {
+    let script = r#"printf '%s\n' '{"jsonrpc":"2.0","id":7,"result":{}}'
+printf '%s\n' 'not json'
+printf '%s\n' '{"jsonrpc":"2.0","method":"notifications/resources/updated","params":{"uri":"file:///tmp/a"}}'
+printf '%s\n' '{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}'
+"#;
+    let client = StdioClient::new("sh", &["-c".to_string(), script.to_string()], &[])
+        .await
+        .expect("spawn server");
+
+    assert_eq!(
+        client.next_notification().await.expect("read"),
+        Some((
+            "notifications/resources/updated".to_string(),
+            json!({"uri": "file:///tmp/a"})
+        ))
+    );
+    assert_eq!(
+        client.next_notification().await.expect("read"),
+        Some(("notifications/tools/list_changed".to_string(), json!(null)))
+    );
+    assert_eq!(client.next_notification().await.expect("read"), None);
+}
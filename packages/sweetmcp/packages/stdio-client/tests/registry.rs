@@ -0,0 +1,180 @@
+//! Server profiles from host config files and the lazy client registry
+
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sweet_mcp_type::Implementation;
+use sweetmcp_stdio_client::{ClientRegistry, ServerConfig, ServersConfig, StdioClientError};
+
+const CLAUDE_DESKTOP_CONFIG: &str = r#"{
+  "mcpServers": {
+    "files": {
+      "command": "npx",
+      "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+      "env": {"DEBUG": "1"}
+    },
+    "search": {"command": "mcp-search", "cwd": "/srv/search", "disabled": true}
+  }
+}"#;
+
+fn client_info() -> Implementation {
+    Implementation {
+        name: "registry-test".to_string(),
+        version: "0.1.0".to_string(),
+    }
+}
+
+/// Scratch directory unique to one test
+fn scratch_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "stdio-client-registry-{}-{}",
+        test,
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).expect("create scratch dir");
+    dir.canonicalize().expect("canonical scratch dir")
+}
+
+#[test]
+fn parses_claude_desktop_json() {
+    let config = ServersConfig::from_json(CLAUDE_DESKTOP_CONFIG).expect("valid config");
+
+    let files = &config.servers["files"];
+    assert_eq!(files.command, "npx");
+    assert_eq!(files.args.len(), 3);
+    assert_eq!(files.env["DEBUG"], "1");
+    assert_eq!(files.cwd, None);
+    assert!(!files.disabled);
+
+    let search = &config.servers["search"];
+    assert!(search.args.is_empty());
+    assert_eq!(search.cwd, Some(PathBuf::from("/srv/search")));
+    assert!(search.disabled);
+}
+
+#[test]
+fn parses_toml() {
+    let config = ServersConfig::from_toml(
+        r#"
+[mcp_servers.files]
+command = "mcp-files"
+args = ["--root", "."]
+env = { RUST_LOG = "debug" }
+cwd = "/work"
+"#,
+    )
+    .expect("valid config");
+
+    assert_eq!(
+        config.servers["files"],
+        ServerConfig {
+            command: "mcp-files".to_string(),
+            args: vec!["--root".to_string(), ".".to_string()],
+            env: [("RUST_LOG".to_string(), "debug".to_string())].into(),
+            cwd: Some(PathBuf::from("/work")),
+            disabled: false,
+        }
+    );
+}
+
+#[test]
+fn rejects_server_without_command() {
+    let result = ServersConfig::from_json(r#"{"mcpServers": {"broken": {"command": " "}}}"#);
+
+    assert!(
+        matches!(&result, Err(StdioClientError::ConfigError(message)) if message.contains("broken")),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+fn load_picks_format_from_extension() {
+    let dir = scratch_dir("load");
+    let toml_path = dir.join("servers.toml");
+    std::fs::write(&toml_path, "[mcpServers.echo]\ncommand = \"cat\"\n").unwrap();
+    let json_path = dir.join("servers.json");
+    std::fs::write(&json_path, CLAUDE_DESKTOP_CONFIG).unwrap();
+
+    let toml_config = ServersConfig::load(&toml_path).expect("toml config");
+    assert_eq!(toml_config.servers["echo"].command, "cat");
+    let json_config = ServersConfig::load(&json_path).expect("json config");
+    assert_eq!(json_config.servers.len(), 2);
+
+    let missing = ServersConfig::load(dir.join("missing.json"));
+    assert!(matches!(missing, Err(StdioClientError::ConfigError(_))));
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[tokio::test]
+async fn disabled_and_unknown_servers_are_not_started() {
+    let config = ServersConfig::from_json(CLAUDE_DESKTOP_CONFIG).unwrap();
+    let registry = ClientRegistry::new(config, client_info(), json!({}));
+
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["files", "search"]);
+    assert_eq!(registry.enabled().collect::<Vec<_>>(), ["files"]);
+    assert!(!registry.is_running("files").await);
+
+    let disabled = registry.get("search").await;
+    assert!(
+        matches!(&disabled, Err(StdioClientError::ServerDisabled(name)) if name == "search"),
+        "{:?}",
+        disabled
+    );
+    let unknown = registry.get("nope").await;
+    assert!(matches!(unknown, Err(StdioClientError::UnknownServer(_))));
+    assert!(matches!(
+        registry.set_enabled("nope", true).await,
+        Err(StdioClientError::UnknownServer(_))
+    ));
+}
+
+/// Completes the handshake, then answers one request with its working
+/// directory and `$GREETING`
+#[cfg(unix)]
+fn fake_server(cwd: PathBuf) -> ServerConfig {
+    let script = r#"read -r init
+printf '%s\n' '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{},"serverInfo":{"name":"fake","version":"1.0"}}}'
+read -r note
+read -r request
+printf '{"jsonrpc":"2.0","id":2,"result":{"cwd":"%s","greeting":"%s"}}\n' "$PWD" "$GREETING"
+"#;
+    ServerConfig {
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), script.to_string()],
+        env: [("GREETING".to_string(), "hello".to_string())].into(),
+        cwd: Some(cwd),
+        disabled: false,
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn servers_start_on_first_use_and_stop_when_disabled() {
+    let dir = scratch_dir("lazy");
+    let config = ServersConfig {
+        servers: [("fake".to_string(), fake_server(dir.clone()))].into(),
+    };
+    let registry = ClientRegistry::new(config, client_info(), json!({}));
+    assert!(!registry.is_running("fake").await);
+
+    let session = registry.get("fake").await.expect("server starts");
+    assert!(registry.is_running("fake").await);
+    assert_eq!(session.server_info().name, "fake");
+    let again = registry.get("fake").await.expect("cached session");
+    assert!(Arc::ptr_eq(&session, &again));
+
+    let result = session.send_request("ping", json!({})).await.expect("ping");
+    assert_eq!(result["cwd"], dir.to_str().unwrap());
+    assert_eq!(result["greeting"], "hello");
+
+    registry.set_enabled("fake", false).await.unwrap();
+    assert!(!registry.is_running("fake").await);
+    assert!(matches!(
+        registry.get("fake").await,
+        Err(StdioClientError::ServerDisabled(_))
+    ));
+
+    std::fs::remove_dir_all(dir).ok();
+}
@@ -0,0 +1,18 @@
+//! Arbitrary input through `Message::from_json`; anything it accepts must
+//! serialize to JSON it accepts again, and re-serialize to the same bytes.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sweet_mcp_type::Message;
+
+fuzz_target!(|src: &str| {
+    let Ok(message) = Message::from_json(src) else {
+        return;
+    };
+
+    let json = message.to_json();
+    let reparsed = Message::from_json(&json)
+        .unwrap_or_else(|e| panic!("re-parse of {} failed: {}", json, e));
+    assert_eq!(json, reparsed.to_json());
+});
@@ -14,6 +14,12 @@ pub use mcp::{
     LoggingCapability, PromptsCapability, ResourcesCapability, 
     ToolsCapability, CompletionsCapability,
 };
+pub use mcp::builder::{NotificationBuilder, RequestBuilder, ToolCallBuilder};
 
 // Re-export JsonValue from simd-json for client usage
 pub use simd_json::value::owned::Value as JsonValue;
+
+// serde_json interop (JsonValue is foreign to this crate, so these are
+// functions rather than `From` impls)
+#[cfg(feature = "serde")]
+pub use mcp::interop::{from_serde_json, to_serde_json};
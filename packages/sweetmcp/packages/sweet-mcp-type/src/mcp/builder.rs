@@ -0,0 +1,395 @@
+//=========================================================================
+//  src/mcp/builder.rs   –   Fluent construction of JSON-RPC envelopes
+//  * Request / Notification builders with params validation
+//  * `tools/call` builder taking arguments one at a time
+//  * Response and JsonRpcError constructors for the standard codes
+//=========================================================================
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use simd_json::{value::owned::Value as JsonValue, StaticNode};
+use value_trait::prelude::*;
+
+use super::{JsonRpcError, McpError, Message, Notification, Request, RequestId, Response};
+
+/// Source of ids for requests built without an explicit one.
+static NEXT_REQUEST_ID: AtomicI64 = AtomicI64::new(1);
+
+#[inline]
+fn null() -> JsonValue {
+    JsonValue::Static(StaticNode::Null)
+}
+
+/// JSON-RPC params must be structured: an object, an array, or absent.
+#[inline]
+fn valid_params(params: &JsonValue) -> bool {
+    params.is_null() || params.is_object() || params.is_array()
+}
+
+/// Insert `key` into `target`, turning `null` into an empty object first.
+/// Returns `false` if `target` holds something other than an object.
+fn insert_field(target: &mut JsonValue, key: String, value: JsonValue) -> bool {
+    if target.is_null() {
+        *target = JsonValue::object();
+    }
+    match target.as_object_mut() {
+        Some(obj) => {
+            obj.insert(key, value);
+            true
+        }
+        None => false,
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  RequestId
+//─────────────────────────────────────────────────────────────────────────
+
+impl RequestId {
+    /// A numeric id unique within this process.
+    pub fn next() -> Self {
+        RequestId::Num(NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl From<i64> for RequestId {
+    fn from(n: i64) -> Self {
+        RequestId::Num(n)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(s: &str) -> Self {
+        RequestId::Str(s.to_string())
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(s: String) -> Self {
+        RequestId::Str(s)
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Envelope → Message
+//─────────────────────────────────────────────────────────────────────────
+
+impl From<Request> for Message {
+    fn from(r: Request) -> Self {
+        Message::Req(r)
+    }
+}
+
+impl From<Response> for Message {
+    fn from(r: Response) -> Self {
+        Message::Res(r)
+    }
+}
+
+impl From<Notification> for Message {
+    fn from(n: Notification) -> Self {
+        Message::Notif(n)
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Request
+//─────────────────────────────────────────────────────────────────────────
+
+/// Builder for a [`Request`]; see [`Request::builder`].
+#[derive(Clone, Debug)]
+pub struct RequestBuilder {
+    id: Option<RequestId>,
+    method: String,
+    params: JsonValue,
+    meta: JsonValue,
+    params_conflict: bool,
+}
+
+impl Request {
+    /// Start a request for `method`, with empty params and a fresh numeric id.
+    pub fn builder(method: impl Into<String>) -> RequestBuilder {
+        RequestBuilder {
+            id: None,
+            method: method.into(),
+            params: JsonValue::object(),
+            meta: null(),
+            params_conflict: false,
+        }
+    }
+
+    /// Start a `tools/call` request for the tool `name`.
+    pub fn tool_call(name: impl Into<String>) -> ToolCallBuilder {
+        ToolCallBuilder {
+            name: name.into(),
+            arguments: null(),
+            arguments_conflict: false,
+            request: Request::builder("tools/call"),
+        }
+    }
+}
+
+impl RequestBuilder {
+    /// Use `id` instead of a generated one.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<RequestId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set one named param.
+    #[must_use]
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        if !insert_field(&mut self.params, key.into(), value.into()) {
+            self.params_conflict = true;
+        }
+        self
+    }
+
+    /// Replace the params wholesale (an object, an array, or `null`).
+    #[must_use]
+    pub fn params(mut self, params: impl Into<JsonValue>) -> Self {
+        self.params = params.into();
+        self.params_conflict = false;
+        self
+    }
+
+    /// Set one `_meta` field, e.g. a `progressToken`.
+    #[must_use]
+    pub fn meta(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        insert_field(&mut self.meta, key.into(), value.into());
+        self
+    }
+
+    /// Finish the request.
+    ///
+    /// Fails with `BadField("method")` for an empty method and
+    /// `BadField("params")` when params are neither an object, an array nor
+    /// `null`, or when named params were added to positional ones.
+    pub fn build(self) -> Result<Request, McpError> {
+        if self.method.is_empty() {
+            return Err(McpError::BadField("method"));
+        }
+        if self.params_conflict || !valid_params(&self.params) {
+            return Err(McpError::BadField("params"));
+        }
+        Ok(Request {
+            id: self.id.unwrap_or_else(RequestId::next),
+            method: self.method,
+            params: self.params,
+            meta: if self.meta.is_null() {
+                None
+            } else {
+                Some(self.meta)
+            },
+        })
+    }
+}
+
+/// Builder for a `tools/call` [`Request`]; see [`Request::tool_call`].
+#[derive(Clone, Debug)]
+pub struct ToolCallBuilder {
+    name: String,
+    arguments: JsonValue,
+    arguments_conflict: bool,
+    request: RequestBuilder,
+}
+
+impl ToolCallBuilder {
+    /// Use `id` instead of a generated one.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<RequestId>) -> Self {
+        self.request = self.request.id(id);
+        self
+    }
+
+    /// Set one tool argument.
+    #[must_use]
+    pub fn arg(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        if !insert_field(&mut self.arguments, key.into(), value.into()) {
+            self.arguments_conflict = true;
+        }
+        self
+    }
+
+    /// Set several tool arguments.
+    #[must_use]
+    pub fn args<K, V>(self, args: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<JsonValue>,
+    {
+        args.into_iter()
+            .fold(self, |builder, (k, v)| builder.arg(k, v))
+    }
+
+    /// Replace the arguments wholesale (an object or `null`).
+    #[must_use]
+    pub fn arguments(mut self, arguments: impl Into<JsonValue>) -> Self {
+        self.arguments = arguments.into();
+        self.arguments_conflict = false;
+        self
+    }
+
+    /// Set one `_meta` field, e.g. a `progressToken`.
+    #[must_use]
+    pub fn meta(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.request = self.request.meta(key, value);
+        self
+    }
+
+    /// Finish the request.
+    ///
+    /// Fails with `BadField("name")` for an empty tool name and
+    /// `BadField("arguments")` when the arguments are not an object.
+    pub fn build(self) -> Result<Request, McpError> {
+        if self.name.is_empty() {
+            return Err(McpError::BadField("name"));
+        }
+        let arguments = if self.arguments.is_null() {
+            JsonValue::object()
+        } else {
+            self.arguments
+        };
+        if self.arguments_conflict || !arguments.is_object() {
+            return Err(McpError::BadField("arguments"));
+        }
+        self.request
+            .param("name", self.name)
+            .param("arguments", arguments)
+            .build()
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Notification
+//─────────────────────────────────────────────────────────────────────────
+
+/// Builder for a [`Notification`]; see [`Notification::builder`].
+#[derive(Clone, Debug)]
+pub struct NotificationBuilder {
+    method: String,
+    params: JsonValue,
+    params_conflict: bool,
+}
+
+impl Notification {
+    /// Start a notification for `method`, with no params.
+    pub fn builder(method: impl Into<String>) -> NotificationBuilder {
+        NotificationBuilder {
+            method: method.into(),
+            params: null(),
+            params_conflict: false,
+        }
+    }
+}
+
+impl NotificationBuilder {
+    /// Set one named param.
+    #[must_use]
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        if !insert_field(&mut self.params, key.into(), value.into()) {
+            self.params_conflict = true;
+        }
+        self
+    }
+
+    /// Replace the params wholesale (an object, an array, or `null`).
+    #[must_use]
+    pub fn params(mut self, params: impl Into<JsonValue>) -> Self {
+        self.params = params.into();
+        self.params_conflict = false;
+        self
+    }
+
+    /// Finish the notification; validated like [`RequestBuilder::build`].
+    pub fn build(self) -> Result<Notification, McpError> {
+        if self.method.is_empty() {
+            return Err(McpError::BadField("method"));
+        }
+        if self.params_conflict || !valid_params(&self.params) {
+            return Err(McpError::BadField("params"));
+        }
+        Ok(Notification {
+            method: self.method,
+            params: self.params,
+        })
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Response & errors
+//─────────────────────────────────────────────────────────────────────────
+
+impl Response {
+    /// A successful response carrying `result`.
+    pub fn success(id: impl Into<RequestId>, result: impl Into<JsonValue>) -> Self {
+        Response {
+            id: id.into(),
+            result: Some(result.into()),
+            error: None,
+        }
+    }
+
+    /// A failed response carrying `error`.
+    pub fn error(id: impl Into<RequestId>, error: JsonRpcError) -> Self {
+        Response {
+            id: id.into(),
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl JsonRpcError {
+    /// Invalid JSON was received.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The JSON sent is not a valid request object.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method does not exist or is not available.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// Invalid method parameters.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// Internal JSON-RPC error.
+    pub const INTERNAL_ERROR: i64 = -32603;
+
+    /// An error with any `code`, without data.
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        JsonRpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Attach `data` to the error.
+    #[must_use]
+    pub fn with_data(mut self, data: impl Into<JsonValue>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    pub fn parse_error(message: impl Into<String>) -> Self {
+        Self::new(Self::PARSE_ERROR, message)
+    }
+
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_REQUEST, message)
+    }
+
+    /// `Method not found: <method>`.
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(
+            Self::METHOD_NOT_FOUND,
+            format!("Method not found: {method}"),
+        )
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    pub fn internal_error(message: impl Into<String>) -> Self {
+        Self::new(Self::INTERNAL_ERROR, message)
+    }
+}
@@ -0,0 +1,173 @@
+//=========================================================================
+//  src/mcp/interop.rs   –   Optional serde support (feature `serde`)
+//  * Serialize/Deserialize for the JSON-RPC envelopes, same wire shape
+//    as `Message::to_json` / `Message::from_json`
+//  * JsonValue ↔ serde_json::Value conversions
+//=========================================================================
+
+use serde::de::{Deserialize, Deserializer, Error as _};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use simd_json::{value::owned::Value as JsonValue, StaticNode};
+
+use super::{id_from_json, Message, Notification, Request, RequestId, Response};
+
+/// Convert a simd-json value into a `serde_json` value.
+///
+/// Non-finite floats, which JSON cannot represent, become `null`.
+pub fn to_serde_json(value: JsonValue) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        JsonValue::Static(StaticNode::Null) => Value::Null,
+        JsonValue::Static(StaticNode::Bool(b)) => Value::Bool(b),
+        JsonValue::Static(StaticNode::I64(n)) => Value::Number(n.into()),
+        JsonValue::Static(StaticNode::U64(n)) => Value::Number(n.into()),
+        JsonValue::Static(StaticNode::F64(f)) => {
+            serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number)
+        }
+        JsonValue::String(s) => Value::String(s),
+        JsonValue::Array(arr) => Value::Array(arr.into_iter().map(to_serde_json).collect()),
+        JsonValue::Object(obj) => Value::Object(
+            obj.into_iter()
+                .map(|(k, v)| (k, to_serde_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a `serde_json` value into a simd-json value.
+pub fn from_serde_json(value: serde_json::Value) -> JsonValue {
+    use serde_json::Value;
+    match value {
+        Value::Null => JsonValue::Static(StaticNode::Null),
+        Value::Bool(b) => JsonValue::Static(StaticNode::Bool(b)),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                JsonValue::from(i)
+            } else if let Some(u) = n.as_u64() {
+                JsonValue::from(u)
+            } else if let Some(f) = n.as_f64() {
+                JsonValue::from(f)
+            } else {
+                JsonValue::Static(StaticNode::Null)
+            }
+        }
+        Value::String(s) => JsonValue::String(s),
+        Value::Array(arr) => {
+            let vec: Vec<JsonValue> = arr.into_iter().map(from_serde_json).collect();
+            JsonValue::Array(vec.into())
+        }
+        Value::Object(obj) => {
+            let map: simd_json::value::owned::Object = obj
+                .into_iter()
+                .map(|(k, v)| (k, from_serde_json(v)))
+                .collect();
+            JsonValue::Object(map.into())
+        }
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Serialize
+//─────────────────────────────────────────────────────────────────────────
+
+impl Serialize for RequestId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RequestId::Num(n) => serializer.serialize_i64(*n),
+            RequestId::Str(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl Serialize for Request {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("jsonrpc", "2.0")?;
+        map.serialize_entry("id", &self.id)?;
+        map.serialize_entry("method", &self.method)?;
+        map.serialize_entry("params", &self.params)?;
+        if let Some(meta) = &self.meta {
+            map.serialize_entry("_meta", meta)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Response {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("jsonrpc", "2.0")?;
+        map.serialize_entry("id", &self.id)?;
+        // An error wins over a result, as in `Message::to_json`
+        match &self.error {
+            Some(error) => map.serialize_entry("error", error)?,
+            None => map.serialize_entry("result", &self.result)?,
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Notification {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("jsonrpc", "2.0")?;
+        map.serialize_entry("method", &self.method)?;
+        if !matches!(self.params, JsonValue::Static(StaticNode::Null)) {
+            map.serialize_entry("params", &self.params)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Message::Req(r) => r.serialize(serializer),
+            Message::Res(r) => r.serialize(serializer),
+            Message::Notif(n) => n.serialize(serializer),
+        }
+    }
+}
+
+//─────────────────────────────────────────────────────────────────────────
+//  Deserialize (validated by the same rules as `Message::from_json`)
+//─────────────────────────────────────────────────────────────────────────
+
+impl<'de> Deserialize<'de> for RequestId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        id_from_json(&JsonValue::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Message::from_value(JsonValue::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Request {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Message::deserialize(deserializer)? {
+            Message::Req(r) => Ok(r),
+            _ => Err(D::Error::custom("expected a JSON-RPC request")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Response {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Message::deserialize(deserializer)? {
+            Message::Res(r) => Ok(r),
+            _ => Err(D::Error::custom("expected a JSON-RPC response")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Notification {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Message::deserialize(deserializer)? {
+            Message::Notif(n) => Ok(n),
+            _ => Err(D::Error::custom("expected a JSON-RPC notification")),
+        }
+    }
+}
@@ -17,6 +17,12 @@ use super::{
     Response,
 };
 
+/// Deepest array/object nesting accepted by [`Message::from_json`]
+///
+/// simd-json builds the DOM recursively, so unbounded nesting from an
+/// untrusted peer would overflow the stack. Matches serde_json's limit.
+pub const MAX_JSON_DEPTH: usize = 128;
+
 impl Message {
     //───────────────────────────────────────────────────────────────────
     //  Parse JSON → Message
@@ -24,14 +30,24 @@ impl Message {
     #[inline(always)]
     pub fn from_json(src: &str) -> Result<Self, McpError> {
         trace!("Parsing JSON message");
+        check_depth(src.as_bytes())?;
+
         // 0. Copy src into a stack buffer (≤4 KiB stays on stack)
         const STACK_CAP: usize = 4096;
         let mut buf: SmallVec<[u8; STACK_CAP]> = SmallVec::new();
         buf.extend_from_slice(src.as_bytes());
 
         // 1. SIMD parse (mutates buffer in-place)
-        let mut dom: JsonValue =
+        let dom: JsonValue =
             to_owned_value(buf.as_mut_slice()).map_err(|e| McpError::Parse(e.to_string()))?;
+        Self::from_value(dom)
+    }
+
+    //───────────────────────────────────────────────────────────────────
+    //  Parsed DOM → Message
+    //───────────────────────────────────────────────────────────────────
+    #[inline(always)]
+    pub(crate) fn from_value(mut dom: JsonValue) -> Result<Self, McpError> {
         let obj = dom.as_object_mut().ok_or(McpError::BadTop)?;
 
         // 2. Validate JSON-RPC version
@@ -161,4 +177,44 @@ impl Message {
 
         out
     }
-}
\ No newline at end of file
+}
+
+/// Reject input nesting arrays/objects deeper than [`MAX_JSON_DEPTH`]
+///
+/// Brackets inside strings are skipped; malformed input is left for the
+/// parser to report.
+#[inline(always)]
+fn check_depth(src: &[u8]) -> Result<(), McpError> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for &b in src {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > MAX_JSON_DEPTH {
+                    return Err(McpError::Parse(format!(
+                        "nesting exceeds {} levels",
+                        MAX_JSON_DEPTH
+                    )));
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
@@ -13,6 +13,9 @@ use value_trait::prelude::*;
 // Re-export format-specific modules:
 pub mod json;
 pub mod toml;
+pub mod builder;
+#[cfg(feature = "serde")]
+pub mod interop;
 
 //─────────────────────────────────────────────────────────────────────────
 //  Common Primitives & Domain Types
@@ -326,9 +329,11 @@ pub struct Notification {
 
 /// JSON-RPC 2.0 Error object.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JsonRpcError {
     pub code: i64,
     pub message: String,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
     pub data: Option<JsonValue>,
 }
 
@@ -0,0 +1,158 @@
+//! tests/builders.rs
+//! ─────────────────────────
+//! Request / Notification / Response builders and JSON-RPC error
+//! constructors.
+
+use simd_json::value::owned::Value as JsonValue;
+use sweet_mcp_type::mcp::{
+    JsonRpcError, McpError, Message, Notification, Request, RequestId, Response,
+};
+use value_trait::prelude::*;
+
+#[test]
+fn tool_call_builds_name_and_arguments() {
+    let request = Request::tool_call("fetch")
+        .arg("url", "https://example.com")
+        .arg("timeout", 30)
+        .id(7)
+        .build()
+        .expect("valid tool call");
+
+    assert_eq!(request.id, RequestId::Num(7));
+    assert_eq!(request.method, "tools/call");
+    assert_eq!(request.params.get_str("name"), Some("fetch"));
+    let arguments = request.params.get("arguments").expect("arguments");
+    assert_eq!(arguments.get_str("url"), Some("https://example.com"));
+    assert_eq!(arguments.get_i64("timeout"), Some(30));
+}
+
+#[test]
+fn tool_call_without_arguments_sends_empty_object() {
+    let request = Request::tool_call("ping").build().expect("valid tool call");
+    let arguments = request.params.get("arguments").expect("arguments");
+    assert!(arguments.as_object().is_some_and(|obj| obj.is_empty()));
+}
+
+#[test]
+fn tool_call_rejects_bad_name_and_arguments() {
+    assert!(matches!(
+        Request::tool_call("").build(),
+        Err(McpError::BadField("name"))
+    ));
+    assert!(matches!(
+        Request::tool_call("fetch")
+            .arguments("not an object")
+            .build(),
+        Err(McpError::BadField("arguments"))
+    ));
+}
+
+#[test]
+fn request_builder_validates_params() {
+    let request = Request::builder("resources/read")
+        .param("uri", "file:///tmp/a.txt")
+        .build()
+        .expect("valid request");
+    assert_eq!(request.params.get_str("uri"), Some("file:///tmp/a.txt"));
+
+    assert!(matches!(
+        Request::builder("").build(),
+        Err(McpError::BadField("method"))
+    ));
+    assert!(matches!(
+        Request::builder("ping").params(42).build(),
+        Err(McpError::BadField("params"))
+    ));
+    // Named params cannot be added to positional ones
+    let positional = JsonValue::from(vec![JsonValue::from(1)]);
+    assert!(matches!(
+        Request::builder("sum")
+            .params(positional)
+            .param("x", 2)
+            .build(),
+        Err(McpError::BadField("params"))
+    ));
+}
+
+#[test]
+fn request_builder_defaults_to_empty_params_and_fresh_ids() {
+    let first = Request::builder("tools/list")
+        .build()
+        .expect("valid request");
+    let second = Request::builder("tools/list")
+        .build()
+        .expect("valid request");
+
+    assert!(first.params.as_object().is_some_and(|obj| obj.is_empty()));
+    assert!(first.meta.is_none());
+    assert_ne!(first.id, second.id);
+}
+
+#[test]
+fn request_builder_sets_meta() {
+    let request = Request::builder("tools/call")
+        .meta("progressToken", "abc")
+        .build()
+        .expect("valid request");
+    let meta = request.meta.expect("meta");
+    assert_eq!(meta.get_str("progressToken"), Some("abc"));
+}
+
+#[test]
+fn built_request_round_trips() {
+    let request = Request::tool_call("hash")
+        .arg("data", "hello")
+        .id("req-1")
+        .build()
+        .expect("valid tool call");
+    let message = Message::from(request);
+
+    let parsed = Message::from_json(&message.to_json()).expect("parses");
+    assert_eq!(parsed, message);
+}
+
+#[test]
+fn notification_builder_omits_null_params() {
+    let notification = Notification::builder("notifications/initialized")
+        .build()
+        .expect("valid notification");
+    assert!(!Message::from(notification).to_json().contains("params"));
+
+    let progress = Notification::builder("notifications/progress")
+        .param("progress", 50)
+        .build()
+        .expect("valid notification");
+    assert_eq!(progress.params.get_i64("progress"), Some(50));
+
+    assert!(matches!(
+        Notification::builder("").build(),
+        Err(McpError::BadField("method"))
+    ));
+}
+
+#[test]
+fn response_constructors() {
+    let success = Response::success(1, "pong");
+    assert_eq!(success.id, RequestId::Num(1));
+    assert_eq!(success.result, Some(JsonValue::from("pong")));
+    assert!(success.error.is_none());
+
+    let failure = Response::error("req-2", JsonRpcError::method_not_found("tools/nope"));
+    assert_eq!(failure.id, RequestId::Str("req-2".into()));
+    assert!(failure.result.is_none());
+    let error = failure.error.expect("error");
+    assert_eq!(error.code, JsonRpcError::METHOD_NOT_FOUND);
+    assert_eq!(error.message, "Method not found: tools/nope");
+}
+
+#[test]
+fn standard_error_codes() {
+    assert_eq!(JsonRpcError::parse_error("x").code, -32700);
+    assert_eq!(JsonRpcError::invalid_request("x").code, -32600);
+    assert_eq!(JsonRpcError::method_not_found("x").code, -32601);
+    assert_eq!(JsonRpcError::invalid_params("x").code, -32602);
+    assert_eq!(JsonRpcError::internal_error("x").code, -32603);
+
+    let error = JsonRpcError::invalid_params("missing url").with_data("url");
+    assert_eq!(error.data, Some(JsonValue::from("url")));
+}
@@ -108,3 +108,26 @@ fn error_response_roundtrip() {
     assert_toml_roundtrip(&m);
     assert_cross_roundtrip(&m);
 }
+
+#[test]
+fn deeply_nested_json_rejected() {
+    let nested = |depth: usize| {
+        format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":{}{}}}"#,
+            "[".repeat(depth),
+            "]".repeat(depth)
+        )
+    };
+
+    // The envelope object counts as one level
+    assert!(Message::from_json(&nested(127)).is_ok());
+    assert!(Message::from_json(&nested(128)).is_err());
+    assert!(Message::from_json(&nested(100_000)).is_err());
+
+    // Brackets inside strings don't count
+    let in_string = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"method":"ping","params":"\"{}"}}"#,
+        "[".repeat(1000)
+    );
+    assert!(Message::from_json(&in_string).is_ok());
+}
@@ -0,0 +1,92 @@
+//! tests/serde_interop.rs
+//! ─────────────────────────
+//! serde support (feature `serde`): envelopes serialize to the same wire
+//! shape as `Message::to_json`, and JsonValue converts to/from serde_json.
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use simd_json::{value::owned::Value as JsonValue, StaticNode};
+use sweet_mcp_type::mcp::{JsonRpcError, Message, Notification, Request, RequestId, Response};
+use sweet_mcp_type::{from_serde_json, to_serde_json};
+
+#[test]
+fn request_serializes_like_to_json() {
+    let request = Request {
+        id: RequestId::Str("a1".into()),
+        method: "tools/call".into(),
+        params: from_serde_json(json!({"name": "hash", "arguments": {"n": 3}})),
+        meta: Some(from_serde_json(json!({"progressToken": 7}))),
+    };
+
+    let via_serde = serde_json::to_value(&request).unwrap();
+    let via_wire: serde_json::Value =
+        serde_json::from_str(&Message::Req(request.clone()).to_json()).unwrap();
+    assert_eq!(via_serde, via_wire);
+    assert_eq!(via_serde["jsonrpc"], "2.0");
+    assert_eq!(via_serde["_meta"]["progressToken"], 7);
+
+    let back: Request = serde_json::from_value(via_serde).unwrap();
+    assert_eq!(back, request);
+}
+
+#[test]
+fn responses_and_notifications_round_trip() {
+    let error = Response {
+        id: RequestId::Num(4),
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32601,
+            message: "method not found".into(),
+            data: None,
+        }),
+    };
+    let value = serde_json::to_value(&error).unwrap();
+    assert_eq!(
+        value,
+        json!({"jsonrpc": "2.0", "id": 4, "error": {"code": -32601, "message": "method not found"}})
+    );
+    assert_eq!(serde_json::from_value::<Response>(value).unwrap(), error);
+
+    let notification = Notification {
+        method: "notifications/initialized".into(),
+        params: JsonValue::Static(StaticNode::Null),
+    };
+    let value = serde_json::to_value(&notification).unwrap();
+    assert_eq!(
+        value,
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"})
+    );
+    let message: Message = serde_json::from_value(value).unwrap();
+    assert_eq!(message, Message::Notif(notification));
+}
+
+#[test]
+fn deserialize_applies_wire_validation() {
+    let wrong_version = json!({"jsonrpc": "1.0", "id": 1, "method": "ping"});
+    assert!(serde_json::from_value::<Message>(wrong_version).is_err());
+
+    let notification = json!({"jsonrpc": "2.0", "method": "ping"});
+    assert!(serde_json::from_value::<Request>(notification).is_err());
+
+    let bad_id = json!({"jsonrpc": "2.0", "id": [1], "result": null});
+    assert!(serde_json::from_value::<Response>(bad_id).is_err());
+}
+
+#[test]
+fn json_values_convert_both_ways() {
+    let original = json!({
+        "null": null,
+        "flag": true,
+        "neg": -5,
+        "big": u64::MAX,
+        "pi": 3.5,
+        "text": "héllo",
+        "list": [1, "two", [3.0]],
+    });
+
+    let sweet = from_serde_json(original.clone());
+    assert_eq!(to_serde_json(sweet), original);
+
+    let nan = JsonValue::from(f64::NAN);
+    assert_eq!(to_serde_json(nan), serde_json::Value::Null);
+}
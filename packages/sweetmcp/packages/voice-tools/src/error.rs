@@ -27,6 +27,15 @@ pub enum VoiceError {
     #[error("Invalid duration: {0} seconds (must be between 1-300)")]
     InvalidDuration(u32),
 
+    #[error("Invalid audio: {0}")]
+    InvalidAudio(String),
+
+    #[error("Invalid SSML: {0}")]
+    InvalidSsml(String),
+
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
+
     #[error("Network error: {0}")]
     NetworkError(String),
 
@@ -39,3 +48,25 @@ pub enum VoiceError {
     #[error("Other error: {0}")]
     Other(#[from] anyhow::Error),
 }
+
+impl VoiceError {
+    /// Stable machine-readable code for protocol error responses
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ServiceUnavailable(_) => "service_unavailable",
+            Self::InvalidVoiceId(_) => "invalid_voice_id",
+            Self::InvalidMicrophone(_) => "invalid_microphone",
+            Self::TranscriptionFailed(_) => "transcription_failed",
+            Self::SynthesisFailed(_) => "synthesis_failed",
+            Self::PermissionDenied(_) => "permission_denied",
+            Self::InvalidDuration(_) => "invalid_duration",
+            Self::InvalidAudio(_) => "invalid_audio",
+            Self::InvalidSsml(_) => "invalid_ssml",
+            Self::Unsupported(_) => "unsupported",
+            Self::NetworkError(_) => "network_error",
+            Self::SerializationError(_) => "serialization_error",
+            Self::IoError(_) => "io_error",
+            Self::Other(_) => "other",
+        }
+    }
+}
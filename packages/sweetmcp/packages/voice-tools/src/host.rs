@@ -0,0 +1,100 @@
+//! Extism host functions serving voice requests to WASM plugins
+//!
+//! The voice plugin has no audio access inside its sandbox. Each tool call
+//! sends a JSON [`VoiceRequest`] through the `voice_request` host function
+//! and receives the JSON [`VoiceResponse`] produced by the host's
+//! [`VoiceService`].
+
+use std::sync::Arc;
+
+use extism::convert::Json;
+use extism::{PluginBuilder, UserData, ValType};
+use tokio::runtime::Handle;
+
+use crate::VoiceService;
+use crate::error::VoiceError;
+use crate::protocol::{self, VoiceRequest, VoiceResponse};
+
+/// Name of the host function imported by the voice plugin
+pub const VOICE_REQUEST_FUNCTION: &str = "voice_request";
+
+type Dispatch = dyn Fn(VoiceRequest) -> VoiceResponse + Send + Sync;
+
+/// Voice backend exposed to plugins as host functions
+///
+/// Cloning shares the backend.
+#[derive(Clone)]
+pub struct VoiceHost {
+    dispatch: Arc<Dispatch>,
+}
+
+impl std::fmt::Debug for VoiceHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoiceHost").finish_non_exhaustive()
+    }
+}
+
+impl VoiceHost {
+    /// Serve requests with `service` on the current tokio runtime
+    ///
+    /// Host functions are synchronous, so the calling thread blocks until
+    /// the request finishes while the service runs on the runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics when called outside a tokio runtime.
+    pub fn new<V: VoiceService + 'static>(service: V) -> Self {
+        let service = Arc::new(service);
+        let runtime = Handle::current();
+        let dispatch = move |request: VoiceRequest| {
+            let service = Arc::clone(&service);
+            let (tx, rx) = std::sync::mpsc::channel();
+            runtime.spawn(async move {
+                let _ = tx.send(protocol::dispatch(service.as_ref(), request).await);
+            });
+            rx.recv().unwrap_or_else(|_| {
+                VoiceResponse::error(&VoiceError::ServiceUnavailable(
+                    "voice request was cancelled".to_string(),
+                ))
+            })
+        };
+        Self {
+            dispatch: Arc::new(dispatch),
+        }
+    }
+
+    /// Answer every request with a `service_unavailable` error
+    ///
+    /// Lets the voice plugin load and list its tools on hosts without audio.
+    pub fn unavailable() -> Self {
+        Self {
+            dispatch: Arc::new(|_| {
+                VoiceResponse::error(&VoiceError::ServiceUnavailable(
+                    "no voice service is configured on this host".to_string(),
+                ))
+            }),
+        }
+    }
+
+    /// Serve one request
+    pub fn handle(&self, request: VoiceRequest) -> VoiceResponse {
+        (self.dispatch)(request)
+    }
+
+    /// Register the voice host functions with a plugin under construction
+    pub fn register<'a>(&self, builder: PluginBuilder<'a>) -> PluginBuilder<'a> {
+        let host = self.clone();
+        builder.with_function(
+            VOICE_REQUEST_FUNCTION,
+            [ValType::I64], // Input: memory pointer to JSON request
+            [ValType::I64], // Output: memory pointer to JSON response
+            UserData::new(()),
+            move |plugin, inputs, outputs, _user_data| {
+                let Json(request): Json<VoiceRequest> = plugin.memory_get_val(&inputs[0])?;
+                let response = host.handle(request);
+                plugin.memory_set_val(&mut outputs[0], Json(response))?;
+                Ok(())
+            },
+        )
+    }
+}
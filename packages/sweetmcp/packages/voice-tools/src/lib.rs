@@ -1,49 +1,31 @@
-//! MCP Voice Tools - Tool definitions for voice operations
+//! MCP Voice Tools - Shared types for voice operations
 //!
-//! This crate provides the MCP tool definitions for text-to-speech (TTS)
-//! and speech-to-text (STT) operations, enabling LLMs to interact with
-//! voice capabilities through a clean, intuitive interface.
-
-use std::collections::HashMap;
-
-use log::info;
-use serde::{Deserialize, Serialize};
+//! This crate provides the [`VoiceService`] trait, request/response protocol
+//! and parameter types for text-to-speech (TTS) and speech-to-text (STT)
+//! operations. The `speak` and `listen` MCP tools themselves live in the
+//! `voice` WASM plugin, which reaches the host's [`VoiceService`] through
+//! the host functions in `host` (enabled by the `host` feature).
 
 pub mod error;
+#[cfg(feature = "host")]
+pub mod host;
 pub mod protocol;
-pub mod tools;
+pub mod ssml;
 pub mod types;
+pub mod vad;
 
 // Re-export commonly used types
 pub use error::{VoiceError, VoiceResult};
+#[cfg(feature = "host")]
+pub use host::VoiceHost;
 pub use protocol::{VoiceRequest, VoiceResponse};
-pub use tools::{listen_tool, speak_tool};
-pub use types::{ListenParams, ListenResult, SpeakParams, VoiceConfig};
-
-/// MCP Tool definition structure (matching sweetmcp-axum types)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Tool {
-    pub name: String,
-    pub description: Option<String>,
-    pub input_schema: ToolInputSchema,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ToolInputSchema {
-    #[serde(rename = "type")]
-    pub type_name: String,
-    pub properties: HashMap<String, ToolInputSchemaProperty>,
-    pub required: Vec<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ToolInputSchemaProperty {
-    #[serde(rename = "type")]
-    pub type_name: Option<String>,
-    #[serde(rename = "enum")]
-    pub enum_values: Option<Vec<String>>,
-    pub description: Option<String>,
-}
+pub use ssml::{Prosody, SpeechPart};
+pub use types::{
+    ListenEvent, ListenParams, ListenResult, ListenStreamParams, SpeakParams, SynthesisResult,
+    SynthesizeParams, TranscribeFileParams, TranscriptOptions, TranscriptSegment, VoiceConfig,
+    WordTimestamp,
+};
+pub use vad::{VadEvent, VoiceActivityDetector};
 
 /// Voice service trait that implementations must provide
 ///
@@ -51,20 +33,63 @@ pub struct ToolInputSchemaProperty {
 /// Implementors should use `async fn` or return boxed futures.
 pub trait VoiceService: Send + Sync {
     /// Synthesize speech from text
-    fn speak(&self, params: SpeakParams) -> impl std::future::Future<Output = VoiceResult<()>> + Send;
+    fn speak(
+        &self,
+        params: SpeakParams,
+    ) -> impl std::future::Future<Output = VoiceResult<()>> + Send;
 
     /// Listen for speech and transcribe to text
-    fn listen(&self, params: ListenParams) -> impl std::future::Future<Output = VoiceResult<ListenResult>> + Send;
+    fn listen(
+        &self,
+        params: ListenParams,
+    ) -> impl std::future::Future<Output = VoiceResult<ListenResult>> + Send;
+
+    /// Listen for one utterance, reporting progress as audio arrives
+    ///
+    /// Sends [`ListenEvent`]s to `events` while listening and resolves with
+    /// the final transcript once voice activity detection finds the end of
+    /// the utterance. Send failures are ignored, so dropping the receiver
+    /// only silences the progress reports.
+    ///
+    /// The default records for the full `max_duration_seconds` with
+    /// [`VoiceService::listen`] and reports no progress.
+    fn listen_stream(
+        &self,
+        params: ListenStreamParams,
+        _events: tokio::sync::mpsc::UnboundedSender<ListenEvent>,
+    ) -> impl std::future::Future<Output = VoiceResult<ListenResult>> + Send {
+        self.listen(params.into())
+    }
+
+    /// Transcribe a recorded audio file
+    ///
+    /// The default reports [`VoiceError::Unsupported`].
+    fn transcribe_file(
+        &self,
+        _params: TranscribeFileParams,
+    ) -> impl std::future::Future<Output = VoiceResult<ListenResult>> + Send {
+        std::future::ready(Err(VoiceError::Unsupported(
+            "audio file transcription".to_string(),
+        )))
+    }
+
+    /// Synthesize speech to a WAV file instead of playing it
+    ///
+    /// The default reports [`VoiceError::Unsupported`].
+    fn synthesize(
+        &self,
+        _params: SynthesizeParams,
+    ) -> impl std::future::Future<Output = VoiceResult<SynthesisResult>> + Send {
+        std::future::ready(Err(VoiceError::Unsupported(
+            "speech synthesis to files".to_string(),
+        )))
+    }
 
     /// Get available voice IDs
     fn list_voices(&self) -> impl std::future::Future<Output = VoiceResult<Vec<String>>> + Send;
 
     /// Get available microphone devices
-    fn list_microphones(&self) -> impl std::future::Future<Output = VoiceResult<Vec<String>>> + Send;
-}
-
-/// Tool registry helper
-pub fn register_voice_tools() -> Vec<Tool> {
-    info!("Registering voice tools");
-    vec![speak_tool(), listen_tool()]
+    fn list_microphones(
+        &self,
+    ) -> impl std::future::Future<Output = VoiceResult<Vec<String>>> + Send;
 }
@@ -3,7 +3,12 @@
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::types::{ListenParams, ListenResult, SpeakParams};
+use crate::VoiceService;
+use crate::error::VoiceError;
+use crate::types::{
+    ListenEvent, ListenParams, ListenResult, ListenStreamParams, SpeakParams, SynthesisResult,
+    SynthesizeParams, TranscribeFileParams,
+};
 
 /// Request types for voice operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +20,16 @@ pub enum VoiceRequest {
     /// Request to listen for audio
     Listen(ListenParams),
 
+    /// Request to listen for one utterance, answered by `ListenEvent`
+    /// responses and then a `ListenResult`
+    ListenStream(ListenStreamParams),
+
+    /// Request to transcribe an audio file, answered by a `ListenResult`
+    TranscribeFile(TranscribeFileParams),
+
+    /// Request to synthesize speech to a file
+    Synthesize(SynthesizeParams),
+
     /// Request list of available voices
     ListVoices,
 
@@ -32,6 +47,12 @@ pub enum VoiceResponse {
     /// Listen operation result
     ListenResult(ListenResult),
 
+    /// Progress of a streaming listen operation
+    ListenEvent(ListenEvent),
+
+    /// Synthesized speech file
+    Synthesized(SynthesisResult),
+
     /// List of available voice IDs
     VoiceList(Vec<String>),
 
@@ -42,9 +63,60 @@ pub enum VoiceResponse {
     Error { code: String, message: String },
 }
 
+impl VoiceResponse {
+    /// Error response describing `error`
+    pub fn error(error: &VoiceError) -> Self {
+        Self::Error {
+            code: error.code().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Serve one request with `service`
+///
+/// A `ListenStream` request is answered with its final `ListenResult` only;
+/// transports that can carry several responses should call
+/// [`VoiceService::listen_stream`] themselves to forward the events.
+pub async fn dispatch<V: VoiceService>(service: &V, request: VoiceRequest) -> VoiceResponse {
+    debug!("Dispatching voice request: {:?}", request);
+    let response = match request {
+        VoiceRequest::Speak(params) => service
+            .speak(params)
+            .await
+            .map(|()| VoiceResponse::SpeakComplete),
+        VoiceRequest::Listen(params) => service
+            .listen(params)
+            .await
+            .map(VoiceResponse::ListenResult),
+        VoiceRequest::ListenStream(params) => {
+            let (events, _) = tokio::sync::mpsc::unbounded_channel();
+            service
+                .listen_stream(params, events)
+                .await
+                .map(VoiceResponse::ListenResult)
+        }
+        VoiceRequest::TranscribeFile(params) => service
+            .transcribe_file(params)
+            .await
+            .map(VoiceResponse::ListenResult),
+        VoiceRequest::Synthesize(params) => service
+            .synthesize(params)
+            .await
+            .map(VoiceResponse::Synthesized),
+        VoiceRequest::ListVoices => service.list_voices().await.map(VoiceResponse::VoiceList),
+        VoiceRequest::ListMicrophones => service
+            .list_microphones()
+            .await
+            .map(VoiceResponse::MicrophoneList),
+    };
+    response.unwrap_or_else(|e| VoiceResponse::error(&e))
+}
+
 /// Create a voice service client endpoint
 pub fn voice_endpoint() -> String {
-    let endpoint = std::env::var("VOICE_SERVICE_ENDPOINT").unwrap_or_else(|_| "localhost:33336".to_string());
+    let endpoint =
+        std::env::var("VOICE_SERVICE_ENDPOINT").unwrap_or_else(|_| "localhost:33336".to_string());
     debug!("Voice service endpoint: {}", endpoint);
     endpoint
 }
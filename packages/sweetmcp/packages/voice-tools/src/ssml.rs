@@ -0,0 +1,571 @@
+//! SSML input and prosody for speech synthesis
+//!
+//! Text to speak is turned into [`SpeechPart`]s: runs of text with the
+//! [`Prosody`] to speak them with, and pauses. Plain text becomes one part
+//! spoken with the prosody of the request. SSML supports a subset of the
+//! standard: `<break>`, `<prosody>`, `<say-as>` and `<emphasis>` inside an
+//! optional `<speak>` root. Other elements are ignored and their text is
+//! spoken as is, so markup written for richer engines still works.
+//!
+//! Services map the parts onto their speech engine and skip what it cannot
+//! do; a service without pitch control, for example, speaks at the voice's
+//! natural pitch.
+
+use std::time::Duration;
+
+use crate::error::{VoiceError, VoiceResult};
+
+/// Slowest and fastest speaking rate multipliers
+pub const RATE_RANGE: (f32, f32) = (0.5, 2.0);
+
+/// Largest pitch shift in either direction, in semitones
+pub const MAX_PITCH_SEMITONES: f32 = 12.0;
+
+/// Loudest volume as a gain on the voice's natural level
+pub const MAX_VOLUME: f32 = 2.0;
+
+/// Longest pause a `<break>` can ask for
+pub const MAX_BREAK: Duration = Duration::from_secs(10);
+
+/// How speech is delivered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prosody {
+    /// Speaking rate multiplier, 1.0 being the voice's natural pace
+    pub rate: f32,
+    /// Pitch shift in semitones, 0.0 being the voice's natural pitch
+    pub pitch_semitones: f32,
+    /// Gain on the voice's natural level, 0.0 being silent
+    pub volume: f32,
+}
+
+impl Default for Prosody {
+    fn default() -> Self {
+        Self {
+            rate: 1.0,
+            pitch_semitones: 0.0,
+            volume: 1.0,
+        }
+    }
+}
+
+impl Prosody {
+    /// Prosody from request fields, checking each is in range
+    ///
+    /// Unset fields keep the voice's natural delivery.
+    pub fn new(speed: Option<f32>, pitch: Option<f32>, volume: Option<f32>) -> VoiceResult<Self> {
+        let prosody = Self {
+            rate: speed.unwrap_or(1.0),
+            pitch_semitones: pitch.unwrap_or(0.0),
+            volume: volume.unwrap_or(1.0),
+        };
+        if !(RATE_RANGE.0..=RATE_RANGE.1).contains(&prosody.rate) {
+            return Err(VoiceError::SynthesisFailed(format!(
+                "speed {} is outside {}-{}",
+                prosody.rate, RATE_RANGE.0, RATE_RANGE.1
+            )));
+        }
+        if !(-MAX_PITCH_SEMITONES..=MAX_PITCH_SEMITONES).contains(&prosody.pitch_semitones) {
+            return Err(VoiceError::SynthesisFailed(format!(
+                "pitch {} is outside -{}-{} semitones",
+                prosody.pitch_semitones, MAX_PITCH_SEMITONES, MAX_PITCH_SEMITONES
+            )));
+        }
+        if !(0.0..=MAX_VOLUME).contains(&prosody.volume) {
+            return Err(VoiceError::SynthesisFailed(format!(
+                "volume {} is outside 0-{}",
+                prosody.volume, MAX_VOLUME
+            )));
+        }
+        Ok(prosody)
+    }
+
+    /// Apply a change relative to this prosody, clamped to the valid ranges
+    fn adjusted(self, change: ProsodyChange) -> Self {
+        Self {
+            rate: (self.rate * change.rate).clamp(RATE_RANGE.0, RATE_RANGE.1),
+            pitch_semitones: (self.pitch_semitones + change.pitch_semitones)
+                .clamp(-MAX_PITCH_SEMITONES, MAX_PITCH_SEMITONES),
+            volume: (self.volume * change.volume).clamp(0.0, MAX_VOLUME),
+        }
+    }
+}
+
+/// Piece of speech to synthesize
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeechPart {
+    /// Text spoken with the given prosody
+    Speech { text: String, prosody: Prosody },
+
+    /// Silence
+    Break(Duration),
+}
+
+/// Turn the text of a speak or synthesize request into speech parts
+///
+/// `text` is parsed as SSML when `ssml` is set, and the SSML prosody is
+/// relative to `prosody`.
+pub fn speech_parts(text: &str, ssml: bool, prosody: Prosody) -> VoiceResult<Vec<SpeechPart>> {
+    if ssml {
+        return parse(text, prosody);
+    }
+    let text = collapse_whitespace(text);
+    Ok(if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![SpeechPart::Speech { text, prosody }]
+    })
+}
+
+/// Text of the parts without prosody or pauses, for engines that only
+/// take plain text
+pub fn plain_text(parts: &[SpeechPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            SpeechPart::Speech { text, .. } => Some(text.as_str()),
+            SpeechPart::Break(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse SSML into speech parts, with `prosody` as the outermost delivery
+///
+/// Malformed markup, mismatched tags and invalid attribute values fail with
+/// [`VoiceError::InvalidSsml`].
+pub fn parse(ssml: &str, prosody: Prosody) -> VoiceResult<Vec<SpeechPart>> {
+    let mut parser = Parser {
+        parts: Vec::new(),
+        stack: vec![Frame {
+            name: String::new(),
+            prosody,
+            say_as: None,
+        }],
+        text: String::new(),
+    };
+
+    let mut rest = ssml;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            parser.push_text(&decode_entities(rest));
+            break;
+        };
+        if open > 0 {
+            parser.push_text(&decode_entities(&rest[..open]));
+        }
+        rest = &rest[open..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or_else(|| invalid("unterminated comment"))?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+        let close = rest.find('>').ok_or_else(|| invalid("unterminated tag"))?;
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            // XML declaration or doctype
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            parser.end(name.trim())?;
+        } else if let Some(tag) = tag.strip_suffix('/') {
+            let (name, attributes) = parse_tag(tag)?;
+            parser.start(&name, &attributes, true)?;
+        } else {
+            let (name, attributes) = parse_tag(tag)?;
+            parser.start(&name, &attributes, false)?;
+        }
+    }
+
+    if let Some(frame) = parser.stack.get(1) {
+        return Err(invalid(&format!("<{}> is never closed", frame.name)));
+    }
+    parser.flush();
+    Ok(parser.parts)
+}
+
+/// Open element and the delivery inside it
+struct Frame {
+    name: String,
+    prosody: Prosody,
+    say_as: Option<SayAs>,
+}
+
+struct Parser {
+    parts: Vec<SpeechPart>,
+    stack: Vec<Frame>,
+    /// Text collected for the current prosody
+    text: String,
+}
+
+impl Parser {
+    fn current(&self) -> &Frame {
+        self.stack.last().expect("root frame is never popped")
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let say_as = self.current().say_as;
+        match say_as {
+            Some(say_as) => self.text.push_str(&say_as.render(text)),
+            None => self.text.push_str(text),
+        }
+    }
+
+    /// Finish the text collected so far as one part
+    fn flush(&mut self) {
+        let text = collapse_whitespace(&std::mem::take(&mut self.text));
+        if text.is_empty() {
+            return;
+        }
+        let prosody = self.current().prosody;
+        match self.parts.last_mut() {
+            Some(SpeechPart::Speech {
+                text: previous,
+                prosody: previous_prosody,
+            }) if *previous_prosody == prosody => {
+                previous.push(' ');
+                previous.push_str(&text);
+            }
+            _ => self.parts.push(SpeechPart::Speech { text, prosody }),
+        }
+    }
+
+    fn start(
+        &mut self,
+        name: &str,
+        attributes: &[(String, String)],
+        empty: bool,
+    ) -> VoiceResult<()> {
+        let attribute = |key: &str| {
+            attributes
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let current = self.current();
+        let mut frame = Frame {
+            name: name.to_string(),
+            prosody: current.prosody,
+            say_as: current.say_as,
+        };
+        match name {
+            "break" => {
+                self.flush();
+                let pause = match (attribute("time"), attribute("strength")) {
+                    (Some(time), _) => parse_time(time)?,
+                    (None, Some(strength)) => break_strength(strength)?,
+                    (None, None) => break_strength("medium")?,
+                };
+                if !pause.is_zero() {
+                    self.parts.push(SpeechPart::Break(pause.min(MAX_BREAK)));
+                }
+            }
+            "prosody" => {
+                self.flush();
+                let change = ProsodyChange {
+                    rate: attribute("rate")
+                        .map(parse_rate)
+                        .transpose()?
+                        .unwrap_or(1.0),
+                    pitch_semitones: attribute("pitch")
+                        .map(parse_pitch)
+                        .transpose()?
+                        .unwrap_or(0.0),
+                    volume: attribute("volume")
+                        .map(parse_volume)
+                        .transpose()?
+                        .unwrap_or(1.0),
+                };
+                frame.prosody = frame.prosody.adjusted(change);
+            }
+            "emphasis" => {
+                self.flush();
+                let change = emphasis(attribute("level").unwrap_or("moderate"))?;
+                frame.prosody = frame.prosody.adjusted(change);
+            }
+            "say-as" => {
+                let interpret_as = attribute("interpret-as")
+                    .ok_or_else(|| invalid("<say-as> needs an interpret-as attribute"))?;
+                frame.say_as = Some(SayAs::parse(interpret_as));
+            }
+            "p" | "s" => self.text.push(' '),
+            _ => {}
+        }
+
+        if !empty && name != "break" {
+            self.stack.push(frame);
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, name: &str) -> VoiceResult<()> {
+        if name == "break" {
+            return Ok(());
+        }
+        if self.stack.len() < 2 || self.current().name != name {
+            return Err(invalid(&format!("unexpected </{}>", name)));
+        }
+        if matches!(name, "prosody" | "emphasis") {
+            self.flush();
+        }
+        if matches!(name, "p" | "s") {
+            self.text.push(' ');
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+/// Relative change of prosody made by an element
+#[derive(Debug, Clone, Copy)]
+struct ProsodyChange {
+    rate: f32,
+    pitch_semitones: f32,
+    volume: f32,
+}
+
+/// How `<say-as>` content is read out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SayAs {
+    /// One letter at a time
+    Characters,
+    /// One digit at a time
+    Digits,
+    /// Left to the speech engine
+    Verbatim,
+}
+
+impl SayAs {
+    fn parse(interpret_as: &str) -> Self {
+        match interpret_as {
+            "characters" | "spell-out" | "letters" | "verbatim" => Self::Characters,
+            "digits" | "telephone" => Self::Digits,
+            _ => Self::Verbatim,
+        }
+    }
+
+    fn render(self, text: &str) -> String {
+        let spaced = |keep: fn(char) -> bool| {
+            let mut out = String::with_capacity(text.len() * 2);
+            for c in text.chars().filter(|c| !c.is_whitespace()) {
+                if keep(c) && !out.is_empty() {
+                    out.push(' ');
+                }
+                out.push(c);
+            }
+            out
+        };
+        match self {
+            Self::Characters => spaced(|_| true),
+            Self::Digits => spaced(|c| c.is_ascii_digit()),
+            Self::Verbatim => text.to_string(),
+        }
+    }
+}
+
+fn invalid(message: &str) -> VoiceError {
+    VoiceError::InvalidSsml(message.to_string())
+}
+
+/// Split a start tag into its name and attributes
+fn parse_tag(tag: &str) -> VoiceResult<(String, Vec<(String, String)>)> {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = &tag[..name_end];
+    if name.is_empty() {
+        return Err(invalid("empty tag"));
+    }
+
+    let mut attributes = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let (key, after) = rest
+            .split_once('=')
+            .ok_or_else(|| invalid(&format!("attribute without value in <{}>", name)))?;
+        let after = after.trim_start();
+        let quote = after
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| invalid(&format!("unquoted attribute in <{}>", name)))?;
+        let value_end = after[1..]
+            .find(quote)
+            .ok_or_else(|| invalid(&format!("unterminated attribute in <{}>", name)))?;
+        attributes.push((
+            key.trim().to_string(),
+            decode_entities(&after[1..1 + value_end]),
+        ));
+        rest = after[value_end + 2..].trim_start();
+    }
+    Ok((name.to_string(), attributes))
+}
+
+/// Replace XML character references with the characters they stand for
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|n| n.parse::<u32>()))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_number(value: &str, what: &str) -> VoiceResult<f32> {
+    value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| invalid(&format!("invalid {} {:?}", what, value)))
+}
+
+/// `<break time>` such as "500ms" or "1.5s"
+fn parse_time(time: &str) -> VoiceResult<Duration> {
+    let time = time.trim();
+    let seconds = if let Some(millis) = time.strip_suffix("ms") {
+        parse_number(millis, "break time")? / 1000.0
+    } else if let Some(seconds) = time.strip_suffix('s') {
+        parse_number(seconds, "break time")?
+    } else {
+        return Err(invalid(&format!("invalid break time {:?}", time)));
+    };
+    if seconds < 0.0 {
+        return Err(invalid(&format!("negative break time {:?}", time)));
+    }
+    Ok(Duration::from_secs_f32(
+        seconds.min(MAX_BREAK.as_secs_f32()),
+    ))
+}
+
+fn break_strength(strength: &str) -> VoiceResult<Duration> {
+    let millis = match strength {
+        "none" => 0,
+        "x-weak" => 100,
+        "weak" => 250,
+        "medium" => 400,
+        "strong" => 750,
+        "x-strong" => 1200,
+        other => return Err(invalid(&format!("invalid break strength {:?}", other))),
+    };
+    Ok(Duration::from_millis(millis))
+}
+
+/// `<prosody rate>`: a keyword, a percentage or a multiplier
+fn parse_rate(rate: &str) -> VoiceResult<f32> {
+    let rate = rate.trim();
+    Ok(match rate {
+        "x-slow" => 0.5,
+        "slow" => 0.75,
+        "medium" | "default" => 1.0,
+        "fast" => 1.25,
+        "x-fast" => 1.5,
+        _ => match rate.strip_suffix('%') {
+            // "+20%" and "-20%" are relative, "120%" is absolute
+            Some(percent) if percent.starts_with(['+', '-']) => {
+                1.0 + parse_number(percent, "rate")? / 100.0
+            }
+            Some(percent) => parse_number(percent, "rate")? / 100.0,
+            None => parse_number(rate, "rate")?,
+        },
+    }
+    .max(0.0))
+}
+
+/// `<prosody pitch>` as a shift in semitones
+///
+/// Absolute frequencies depend on the voice and are ignored.
+fn parse_pitch(pitch: &str) -> VoiceResult<f32> {
+    let pitch = pitch.trim();
+    Ok(match pitch {
+        "x-low" => -6.0,
+        "low" => -3.0,
+        "medium" | "default" => 0.0,
+        "high" => 3.0,
+        "x-high" => 6.0,
+        _ if pitch.ends_with("Hz") => 0.0,
+        _ => {
+            if let Some(semitones) = pitch.strip_suffix("st") {
+                parse_number(semitones, "pitch")?
+            } else if let Some(percent) = pitch.strip_suffix('%') {
+                let ratio = 1.0 + parse_number(percent, "pitch")? / 100.0;
+                if ratio <= 0.0 {
+                    return Err(invalid(&format!("invalid pitch {:?}", pitch)));
+                }
+                12.0 * ratio.log2()
+            } else {
+                return Err(invalid(&format!("invalid pitch {:?}", pitch)));
+            }
+        }
+    })
+}
+
+/// `<prosody volume>` as a gain
+fn parse_volume(volume: &str) -> VoiceResult<f32> {
+    let volume = volume.trim();
+    Ok(match volume {
+        "silent" => 0.0,
+        "x-soft" => 0.25,
+        "soft" => 0.5,
+        "medium" | "default" => 1.0,
+        "loud" => 1.5,
+        "x-loud" => 2.0,
+        _ => match volume.strip_suffix("dB") {
+            Some(decibels) => 10f32.powf(parse_number(decibels, "volume")? / 20.0),
+            // Plain numbers run from 0 to 100, the voice's natural level
+            None => (parse_number(volume, "volume")? / 100.0).max(0.0),
+        },
+    })
+}
+
+fn emphasis(level: &str) -> VoiceResult<ProsodyChange> {
+    let (rate, pitch_semitones, volume) = match level {
+        "strong" => (0.85, 2.0, 1.4),
+        "moderate" => (0.92, 1.0, 1.2),
+        "none" => (1.0, 0.0, 1.0),
+        "reduced" => (1.1, -1.0, 0.8),
+        other => return Err(invalid(&format!("invalid emphasis level {:?}", other))),
+    };
+    Ok(ProsodyChange {
+        rate,
+        pitch_semitones,
+        volume,
+    })
+}
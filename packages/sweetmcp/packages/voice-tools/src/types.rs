@@ -1,7 +1,18 @@
 //! Type definitions for voice operations
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::VoiceResult;
+use crate::ssml::{self, Prosody, SpeechPart};
+
+/// Silence that ends a streamed utterance when none is requested
+pub const DEFAULT_SILENCE_TIMEOUT_MS: u32 = 800;
+
+/// Interval between partial transcripts when none is requested
+pub const DEFAULT_PARTIAL_INTERVAL_MS: u32 = 1000;
+
 /// Parameters for the speak operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeakParams {
@@ -15,6 +26,26 @@ pub struct SpeakParams {
     /// Optional speed modifier (0.5 to 2.0, default 1.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speed: Option<f32>,
+
+    /// Optional pitch shift in semitones (-12 to 12, default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+
+    /// Optional volume as a gain (0.0 to 2.0, default 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f32>,
+
+    /// Whether `text` is SSML; see [`ssml`] for the supported elements
+    #[serde(default)]
+    pub ssml: bool,
+}
+
+impl SpeakParams {
+    /// Check the prosody fields and split the text into speech parts
+    pub fn speech_parts(&self) -> VoiceResult<Vec<SpeechPart>> {
+        let prosody = Prosody::new(self.speed, self.pitch, self.volume)?;
+        ssml::speech_parts(&self.text, self.ssml, prosody)
+    }
 }
 
 /// Parameters for the listen operation
@@ -29,6 +60,71 @@ pub struct ListenParams {
     /// Optional wake word to listen for
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wake_word: Option<String>,
+
+    /// Extra transcript detail to produce
+    #[serde(flatten)]
+    pub options: TranscriptOptions,
+}
+
+/// Extra transcript detail requested with a listen or transcription
+///
+/// Every toggle is off by default, and services that cannot produce a
+/// detail leave it out of the [`ListenResult`] rather than fail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptOptions {
+    /// Split the transcript into segments labelled by speaker
+    #[serde(default)]
+    pub diarize: bool,
+
+    /// Identify the spoken language and report a confidence for it
+    #[serde(default)]
+    pub detect_language: bool,
+
+    /// Report when each word was spoken
+    #[serde(default)]
+    pub word_timestamps: bool,
+}
+
+impl TranscriptOptions {
+    /// Whether the transcript has to be split into timed segments
+    pub fn wants_segments(&self) -> bool {
+        self.diarize || self.word_timestamps
+    }
+}
+
+/// Stretch of a transcript spoken without a pause
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Offset of the start from the start of the audio, in seconds
+    pub start_seconds: f32,
+
+    /// Offset of the end from the start of the audio, in seconds
+    pub end_seconds: f32,
+
+    /// Text spoken in the segment
+    pub text: String,
+
+    /// Speaker label such as "SPEAKER_1", when diarization was requested;
+    /// segments with the same label were spoken by the same voice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
+/// Word of a transcript with its timing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    /// The word as transcribed, punctuation included
+    pub word: String,
+
+    /// Offset of the start from the start of the audio, in seconds
+    pub start_seconds: f32,
+
+    /// Offset of the end from the start of the audio, in seconds
+    pub end_seconds: f32,
+
+    /// Confidence score (0.0 to 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
 }
 
 /// Result of a listen operation
@@ -48,6 +144,180 @@ pub struct ListenResult {
     /// Detected language (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+
+    /// Confidence in the detected language (0.0 to 1.0), when language
+    /// detection was requested and the service can identify languages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_confidence: Option<f32>,
+
+    /// Timed segments in order, when diarization or word timestamps were
+    /// requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<TranscriptSegment>>,
+
+    /// Timed words in order, when word timestamps were requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTimestamp>>,
+}
+
+/// Parameters for a streaming listen operation
+///
+/// Listening stops at the end of the first utterance, found by voice
+/// activity detection, or after `max_duration_seconds`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenStreamParams {
+    /// Microphone device ID (e.g., "default", "USB Microphone")
+    pub microphone_id: String,
+
+    /// Longest time to listen in seconds (1-300)
+    pub max_duration_seconds: u32,
+
+    /// Silence that ends the utterance, in milliseconds (default 800)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub silence_timeout_ms: Option<u32>,
+
+    /// VAD sensitivity (0.0 to 1.0, defaults to the service configuration)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vad_sensitivity: Option<f32>,
+
+    /// Interval between partial transcripts, in milliseconds (default 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_interval_ms: Option<u32>,
+
+    /// Optional wake word to listen for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wake_word: Option<String>,
+
+    /// Extra detail for the final transcript; partials are plain text
+    #[serde(flatten)]
+    pub options: TranscriptOptions,
+}
+
+impl ListenStreamParams {
+    /// Silence that ends the utterance
+    pub fn silence_timeout(&self) -> Duration {
+        Duration::from_millis(u64::from(
+            self.silence_timeout_ms
+                .unwrap_or(DEFAULT_SILENCE_TIMEOUT_MS),
+        ))
+    }
+
+    /// Interval between partial transcripts
+    pub fn partial_interval(&self) -> Duration {
+        Duration::from_millis(u64::from(
+            self.partial_interval_ms
+                .unwrap_or(DEFAULT_PARTIAL_INTERVAL_MS),
+        ))
+    }
+}
+
+impl From<ListenStreamParams> for ListenParams {
+    fn from(params: ListenStreamParams) -> Self {
+        Self {
+            microphone_id: params.microphone_id,
+            duration_seconds: params.max_duration_seconds,
+            wake_word: params.wake_word,
+            options: params.options,
+        }
+    }
+}
+
+/// Progress of a streaming listen operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ListenEvent {
+    /// Voice activity began
+    SpeechStarted,
+
+    /// Transcript of the speech so far; later partials replace earlier ones
+    Partial { text: String },
+
+    /// The speaker stopped for the silence timeout
+    SpeechEnded,
+}
+
+/// Parameters for transcribing a recorded audio file
+///
+/// Exactly one of `path` and `audio_base64` must be given. WAV, MP3 and Ogg
+/// files are accepted at any sample rate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscribeFileParams {
+    /// Path of the file on the machine running the voice service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Base64-encoded file contents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_base64: Option<String>,
+
+    /// Optional wake word to look for in the transcript
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wake_word: Option<String>,
+
+    /// Extra transcript detail to produce
+    #[serde(flatten)]
+    pub options: TranscriptOptions,
+}
+
+/// Parameters for synthesizing speech to a WAV file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynthesizeParams {
+    /// Text to synthesize into speech
+    pub text: String,
+
+    /// Optional voice ID (defaults to system default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice_id: Option<String>,
+
+    /// Optional speed modifier (0.5 to 2.0, default 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+
+    /// Optional pitch shift in semitones (-12 to 12, default 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+
+    /// Optional volume as a gain (0.0 to 2.0, default 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f32>,
+
+    /// Whether `text` is SSML; see [`ssml`] for the supported elements
+    #[serde(default)]
+    pub ssml: bool,
+
+    /// Where to write the file on the machine running the voice service;
+    /// the audio is returned as base64 when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+}
+
+impl SynthesizeParams {
+    /// Check the prosody fields and split the text into speech parts
+    pub fn speech_parts(&self) -> VoiceResult<Vec<SpeechPart>> {
+        let prosody = Prosody::new(self.speed, self.pitch, self.volume)?;
+        ssml::speech_parts(&self.text, self.ssml, prosody)
+    }
+}
+
+/// Result of a synthesize operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynthesisResult {
+    /// Path the file was written to, when one was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Base64-encoded file contents, when no path was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_base64: Option<String>,
+
+    /// Media type of the file
+    pub mime_type: String,
+
+    /// Sample rate of the audio in Hz
+    pub sample_rate: u32,
+
+    /// Length of the audio in seconds
+    pub duration_seconds: f32,
 }
 
 /// Voice service configuration
@@ -0,0 +1,131 @@
+//! Energy-based voice activity detection
+//!
+//! [`VoiceActivityDetector`] splits mono audio into 20 ms frames and calls a
+//! frame voiced when its RMS level clears an adaptive noise floor by a margin
+//! set by the sensitivity. Speech starts after a few voiced frames in a row
+//! and ends once the silence timeout passes without one, which is how
+//! streaming listeners find the end of an utterance.
+
+use std::time::Duration;
+
+/// Frames per second of analysis
+const FRAMES_PER_SECOND: u32 = 50;
+
+/// Consecutive voiced frames needed to start speech
+const SPEECH_START_FRAMES: u32 = 3;
+
+/// Frames below this RMS level are never voiced
+const MIN_SPEECH_RMS: f32 = 0.003;
+
+/// How fast the noise floor rises towards louder background noise
+const NOISE_RISE: f32 = 0.02;
+
+/// How fast the noise floor falls towards quieter background noise
+const NOISE_FALL: f32 = 0.5;
+
+/// Change in speech state reported by [`VoiceActivityDetector::push`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    /// Speech began
+    SpeechStarted,
+    /// Speech was followed by the silence timeout
+    SpeechEnded,
+}
+
+/// Streaming voice activity detector
+#[derive(Debug, Clone)]
+pub struct VoiceActivityDetector {
+    frame_len: usize,
+    /// Voiced frames must exceed the noise floor by this factor
+    margin: f32,
+    silence_frames: u32,
+    noise_floor: Option<f32>,
+    pending: Vec<f32>,
+    speaking: bool,
+    voiced_run: u32,
+    silent_run: u32,
+}
+
+impl VoiceActivityDetector {
+    /// Create a detector for audio at `sample_rate`
+    ///
+    /// `sensitivity` runs from 0.0 (only loud speech counts) to 1.0 (anything
+    /// above the background noise counts) and is clamped to that range.
+    /// Speech ends after `silence_timeout` without a voiced frame.
+    pub fn new(sample_rate: u32, sensitivity: f32, silence_timeout: Duration) -> Self {
+        let sensitivity = if sensitivity.is_finite() {
+            sensitivity.clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        let frame_ms = 1000 / FRAMES_PER_SECOND;
+        Self {
+            frame_len: (sample_rate / FRAMES_PER_SECOND).max(1) as usize,
+            margin: 1.5 + 6.0 * (1.0 - sensitivity),
+            silence_frames: (silence_timeout.as_millis() as u32)
+                .div_ceil(frame_ms)
+                .max(1),
+            noise_floor: None,
+            pending: Vec::new(),
+            speaking: false,
+            voiced_run: 0,
+            silent_run: 0,
+        }
+    }
+
+    /// Whether speech is in progress
+    pub fn is_speaking(&self) -> bool {
+        self.speaking
+    }
+
+    /// Feed the next samples and return the state changes they caused
+    pub fn push(&mut self, samples: &[f32]) -> Vec<VadEvent> {
+        self.pending.extend_from_slice(samples);
+
+        let mut events = Vec::new();
+        let frames = self.pending.len() / self.frame_len;
+        for frame in 0..frames {
+            let start = frame * self.frame_len;
+            let level = rms(&self.pending[start..start + self.frame_len]);
+            if let Some(event) = self.observe(level) {
+                events.push(event);
+            }
+        }
+        self.pending.drain(..frames * self.frame_len);
+        events
+    }
+
+    fn observe(&mut self, level: f32) -> Option<VadEvent> {
+        let floor = *self.noise_floor.get_or_insert(level);
+        let voiced = level >= MIN_SPEECH_RMS && level > floor * self.margin;
+
+        if voiced {
+            self.voiced_run += 1;
+            self.silent_run = 0;
+        } else {
+            self.voiced_run = 0;
+            self.silent_run += 1;
+            let rate = if level < floor {
+                NOISE_FALL
+            } else {
+                NOISE_RISE
+            };
+            self.noise_floor = Some(floor + (level - floor) * rate);
+        }
+
+        if !self.speaking && self.voiced_run >= SPEECH_START_FRAMES {
+            self.speaking = true;
+            return Some(VadEvent::SpeechStarted);
+        }
+        if self.speaking && self.silent_run >= self.silence_frames {
+            self.speaking = false;
+            return Some(VadEvent::SpeechEnded);
+        }
+        None
+    }
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    let energy: f32 = samples.iter().map(|s| s * s).sum();
+    (energy / samples.len() as f32).sqrt()
+}
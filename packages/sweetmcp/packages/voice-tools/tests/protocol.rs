@@ -0,0 +1,162 @@
+use sweetmcp_voice_tools::protocol::dispatch;
+use sweetmcp_voice_tools::{
+    ListenParams, ListenResult, SpeakParams, TranscribeFileParams, TranscriptOptions,
+    TranscriptSegment, VoiceError, VoiceRequest, VoiceResponse, VoiceResult, VoiceService,
+    WordTimestamp,
+};
+
+/// Service that hears "hello" and only knows the "default" microphone
+struct MockVoice;
+
+impl VoiceService for MockVoice {
+    async fn speak(&self, params: SpeakParams) -> VoiceResult<()> {
+        match params.voice_id.as_deref() {
+            None => Ok(()),
+            Some(voice) => Err(VoiceError::InvalidVoiceId(voice.to_string())),
+        }
+    }
+
+    async fn listen(&self, params: ListenParams) -> VoiceResult<ListenResult> {
+        if params.microphone_id != "default" {
+            return Err(VoiceError::InvalidMicrophone(params.microphone_id));
+        }
+        Ok(ListenResult {
+            text: "hello".to_string(),
+            wake_word_detected: None,
+            confidence: Some(0.9),
+            language: None,
+            language_confidence: None,
+            segments: None,
+            words: None,
+        })
+    }
+
+    async fn list_voices(&self) -> VoiceResult<Vec<String>> {
+        Ok(vec!["narrator".to_string()])
+    }
+
+    async fn list_microphones(&self) -> VoiceResult<Vec<String>> {
+        Ok(vec!["default".to_string()])
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_serves_requests() {
+    let speak = VoiceRequest::Speak(SpeakParams {
+        text: "hi".to_string(),
+        voice_id: None,
+        speed: None,
+        pitch: None,
+        volume: None,
+        ssml: false,
+    });
+    assert!(matches!(
+        dispatch(&MockVoice, speak).await,
+        VoiceResponse::SpeakComplete
+    ));
+
+    let listen = VoiceRequest::Listen(ListenParams {
+        microphone_id: "default".to_string(),
+        duration_seconds: 5,
+        wake_word: None,
+        options: TranscriptOptions::default(),
+    });
+    match dispatch(&MockVoice, listen).await {
+        VoiceResponse::ListenResult(result) => assert_eq!(result.text, "hello"),
+        other => panic!("unexpected response: {:?}", other),
+    }
+
+    match dispatch(&MockVoice, VoiceRequest::ListVoices).await {
+        VoiceResponse::VoiceList(voices) => assert_eq!(voices, ["narrator"]),
+        other => panic!("unexpected response: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_reports_errors() {
+    let listen = VoiceRequest::Listen(ListenParams {
+        microphone_id: "USB".to_string(),
+        duration_seconds: 5,
+        wake_word: None,
+        options: TranscriptOptions::default(),
+    });
+    let response = dispatch(&MockVoice, listen).await;
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["type"], "Error");
+    assert_eq!(json["data"]["code"], "invalid_microphone");
+    assert_eq!(json["data"]["message"], "Invalid microphone: USB");
+}
+
+#[tokio::test]
+async fn test_dispatch_file_operations_default_to_unsupported() {
+    let transcribe = VoiceRequest::TranscribeFile(TranscribeFileParams {
+        path: Some("memo.wav".to_string()),
+        audio_base64: None,
+        wake_word: None,
+        options: TranscriptOptions::default(),
+    });
+    match dispatch(&MockVoice, transcribe).await {
+        VoiceResponse::Error { code, .. } => assert_eq!(code, "unsupported"),
+        other => panic!("unexpected response: {:?}", other),
+    }
+}
+
+#[test]
+fn test_request_wire_format() {
+    let request: VoiceRequest =
+        serde_json::from_str(r#"{"type":"Speak","params":{"text":"Hello"}}"#).unwrap();
+    match request {
+        VoiceRequest::Speak(params) => {
+            assert_eq!(params.text, "Hello");
+            assert!(params.voice_id.is_none());
+        }
+        other => panic!("unexpected request: {:?}", other),
+    }
+}
+
+#[test]
+fn test_transcript_options_and_detail_wire_format() {
+    let params: ListenParams = serde_json::from_str(
+        r#"{"microphone_id":"default","duration_seconds":60,"diarize":true,"word_timestamps":true}"#,
+    )
+    .unwrap();
+    assert!(params.options.diarize);
+    assert!(!params.options.detect_language);
+    assert!(params.options.wants_segments());
+
+    // Toggles are flattened into the parameters and off when omitted
+    let plain: TranscribeFileParams = serde_json::from_str(r#"{"path":"memo.wav"}"#).unwrap();
+    assert_eq!(plain.options, TranscriptOptions::default());
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(json["diarize"], true);
+    assert!(json.get("options").is_none());
+
+    let result = ListenResult {
+        text: "hi there".to_string(),
+        wake_word_detected: None,
+        confidence: None,
+        language: Some("en".to_string()),
+        language_confidence: Some(0.5),
+        segments: Some(vec![TranscriptSegment {
+            start_seconds: 0.0,
+            end_seconds: 1.0,
+            text: "hi there".to_string(),
+            speaker: Some("SPEAKER_1".to_string()),
+        }]),
+        words: Some(vec![WordTimestamp {
+            word: "hi".to_string(),
+            start_seconds: 0.0,
+            end_seconds: 0.5,
+            confidence: None,
+        }]),
+    };
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["segments"][0]["speaker"], "SPEAKER_1");
+    assert_eq!(json["words"][0]["word"], "hi");
+    assert!(json["words"][0].get("confidence").is_none());
+
+    // Results from services without the details still parse
+    let old: ListenResult = serde_json::from_str(r#"{"text":"hello"}"#).unwrap();
+    assert!(old.segments.is_none() && old.words.is_none());
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use sweetmcp_voice_tools::ssml::{self, MAX_BREAK};
+use sweetmcp_voice_tools::{Prosody, SpeakParams, SpeechPart, VoiceError};
+
+fn speech(text: &str, rate: f32, pitch_semitones: f32, volume: f32) -> SpeechPart {
+    SpeechPart::Speech {
+        text: text.to_string(),
+        prosody: Prosody {
+            rate,
+            pitch_semitones,
+            volume,
+        },
+    }
+}
+
+#[test]
+fn test_ssml_breaks_prosody_and_say_as() {
+    let parts = ssml::parse(
+        r#"<?xml version="1.0"?>
+        <speak>
+          Hello <break time="500ms"/>
+          <prosody rate="slow" pitch="+2st" volume="-6dB">take it easy</prosody>
+          <!-- a comment -->
+          call <say-as interpret-as="digits">555</say-as> or
+          <say-as interpret-as="characters">abc</say-as> &amp; more
+        </speak>"#,
+        Prosody::default(),
+    )
+    .expect("valid ssml");
+
+    assert_eq!(parts.len(), 4, "{parts:?}");
+    assert_eq!(parts[0], speech("Hello", 1.0, 0.0, 1.0));
+    assert_eq!(parts[1], SpeechPart::Break(Duration::from_millis(500)));
+    match &parts[2] {
+        SpeechPart::Speech { text, prosody } => {
+            assert_eq!(text, "take it easy");
+            assert_eq!(prosody.rate, 0.75);
+            assert_eq!(prosody.pitch_semitones, 2.0);
+            assert!((prosody.volume - 0.501).abs() < 0.01);
+        }
+        other => panic!("unexpected part: {:?}", other),
+    }
+    assert_eq!(
+        parts[3],
+        speech("call 5 5 5 or a b c & more", 1.0, 0.0, 1.0)
+    );
+}
+
+#[test]
+fn test_ssml_is_relative_to_request_prosody_and_clamped() {
+    let base = Prosody::new(Some(1.5), Some(-2.0), None).expect("valid prosody");
+    let parts = ssml::parse(
+        r#"<prosody rate="200%"><emphasis level="strong">now</emphasis></prosody>"#,
+        base,
+    )
+    .expect("valid ssml");
+
+    match &parts[..] {
+        [SpeechPart::Speech { text, prosody }] => {
+            assert_eq!(text, "now");
+            // 1.5 * 2.0 is clamped to the fastest rate before emphasis slows it
+            assert!((prosody.rate - 1.7).abs() < 1e-6);
+            assert_eq!(prosody.pitch_semitones, 0.0);
+            assert!((prosody.volume - 1.4).abs() < 1e-6);
+        }
+        other => panic!("unexpected parts: {:?}", other),
+    }
+
+    let long = ssml::parse(r#"<break time="1m"/>"#, base);
+    assert!(matches!(long, Err(VoiceError::InvalidSsml(_))));
+    let capped = ssml::parse(r#"<break time="60s"/>"#, base).expect("valid ssml");
+    assert_eq!(capped, [SpeechPart::Break(MAX_BREAK)]);
+}
+
+#[test]
+fn test_ssml_errors_and_unknown_elements() {
+    for bad in [
+        "<speak>unclosed",
+        "<prosody>mismatched</speak>",
+        r#"<prosody rate="quickly">x</prosody>"#,
+        r#"<break strength="huge"/>"#,
+        "<say-as>missing interpret-as</say-as>",
+        "<break",
+    ] {
+        let error = ssml::parse(bad, Prosody::default()).expect_err(bad);
+        assert_eq!(error.code(), "invalid_ssml", "{bad}");
+    }
+
+    // Unsupported elements keep their text
+    let parts = ssml::parse(
+        r#"<speak><p><s>One.</s><s>Two <mark name="x"/>three.</s></p></speak>"#,
+        Prosody::default(),
+    )
+    .expect("valid ssml");
+    assert_eq!(parts, [speech("One. Two three.", 1.0, 0.0, 1.0)]);
+}
+
+#[test]
+fn test_speak_params_validate_prosody() {
+    let mut params: SpeakParams =
+        serde_json::from_str(r#"{"text":"<b>bold</b> text","pitch":3,"volume":0.5}"#)
+            .expect("params");
+    assert!(!params.ssml);
+    assert_eq!(
+        params.speech_parts().expect("plain text"),
+        [speech("<b>bold</b> text", 1.0, 3.0, 0.5)]
+    );
+
+    params.volume = Some(3.0);
+    assert_eq!(
+        params.speech_parts().expect_err("too loud").code(),
+        "synthesis_failed"
+    );
+    params.volume = None;
+    params.pitch = Some(-13.0);
+    assert!(params.speech_parts().is_err());
+
+    params.pitch = None;
+    params.ssml = true;
+    params.text = "Wait<break strength='strong'/>go".to_string();
+    let parts = params.speech_parts().expect("ssml");
+    assert_eq!(parts[1], SpeechPart::Break(Duration::from_millis(750)));
+    assert_eq!(ssml::plain_text(&parts), "Wait go");
+}
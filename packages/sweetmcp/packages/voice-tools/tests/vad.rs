@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use sweetmcp_voice_tools::{
+    ListenEvent, ListenParams, ListenStreamParams, VadEvent, VoiceActivityDetector,
+};
+
+const RATE: u32 = 16_000;
+
+/// `ms` of a 220 Hz tone at `amplitude`
+fn tone(ms: u32, amplitude: f32) -> Vec<f32> {
+    (0..RATE * ms / 1000)
+        .map(|i| (i as f32 * 220.0 * std::f32::consts::TAU / RATE as f32).sin() * amplitude)
+        .collect()
+}
+
+#[test]
+fn test_vad_detects_utterance_boundaries() {
+    let mut vad = VoiceActivityDetector::new(RATE, 0.5, Duration::from_millis(300));
+
+    assert!(vad.push(&tone(500, 0.001)).is_empty());
+    assert_eq!(vad.push(&tone(400, 0.3)), [VadEvent::SpeechStarted]);
+    assert!(vad.is_speaking());
+
+    // A pause shorter than the timeout keeps the utterance going
+    assert!(vad.push(&tone(200, 0.001)).is_empty());
+    assert!(vad.push(&tone(300, 0.3)).is_empty());
+
+    assert_eq!(vad.push(&tone(400, 0.001)), [VadEvent::SpeechEnded]);
+    assert!(!vad.is_speaking());
+}
+
+#[test]
+fn test_vad_handles_odd_chunk_sizes() {
+    let mut vad = VoiceActivityDetector::new(RATE, 0.5, Duration::from_millis(100));
+    let mut audio = tone(300, 0.0);
+    audio.extend(tone(300, 0.5));
+    audio.extend(tone(300, 0.0));
+
+    let events: Vec<VadEvent> = audio.chunks(77).flat_map(|chunk| vad.push(chunk)).collect();
+    assert_eq!(events, [VadEvent::SpeechStarted, VadEvent::SpeechEnded]);
+}
+
+#[test]
+fn test_vad_sensitivity_and_noise_floor() {
+    // Steady background noise never counts as speech
+    let mut vad = VoiceActivityDetector::new(RATE, 1.0, Duration::from_millis(300));
+    assert!(vad.push(&tone(2000, 0.05)).is_empty());
+
+    // Speech barely above the noise only counts at high sensitivity
+    let mut quiet = tone(500, 0.02);
+    quiet.extend(tone(500, 0.08));
+    let mut sensitive = VoiceActivityDetector::new(RATE, 1.0, Duration::from_millis(300));
+    let mut strict = VoiceActivityDetector::new(RATE, 0.0, Duration::from_millis(300));
+    assert_eq!(sensitive.push(&quiet), [VadEvent::SpeechStarted]);
+    assert!(strict.push(&quiet).is_empty());
+}
+
+#[test]
+fn test_listen_stream_params_defaults_and_wire_format() {
+    let params: ListenStreamParams =
+        serde_json::from_str(r#"{"microphone_id":"default","max_duration_seconds":30}"#)
+            .expect("params");
+    assert_eq!(params.silence_timeout(), Duration::from_millis(800));
+    assert_eq!(params.partial_interval(), Duration::from_millis(1000));
+
+    let one_shot: ListenParams = params.into();
+    assert_eq!(one_shot.duration_seconds, 30);
+
+    let event = ListenEvent::Partial {
+        text: "hello".to_string(),
+    };
+    assert_eq!(
+        serde_json::to_string(&event).expect("event"),
+        r#"{"event":"partial","text":"hello"}"#
+    );
+}
@@ -4,6 +4,8 @@ use serde_json::Value;
 use sweetmcp_plugin_builder::prelude::*;
 use sweetmcp_plugin_builder::{CallToolResult, Ready};
 
+mod sandbox;
+
 #[derive(Debug, Serialize)]
 struct ShellExecuteRequest {
     command: String,
@@ -53,6 +55,11 @@ impl McpTool for ShellTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| Error::msg("Missing 'command' parameter"))?;
 
+        // Screen the command against the host's sandbox profile, if any
+        if let Some(reason) = sandbox::refusal(command) {
+            return Ok(ContentBuilder::error(format!("Command refused: {}", reason)));
+        }
+
         // Prepare request for host function
         let request = ShellExecuteRequest {
             command: command.to_string(),
@@ -0,0 +1,100 @@
+//! Shell restrictions of the host's sandbox profile
+//!
+//! Hosts running the plugin under a Cylo sandbox profile pass the profile's
+//! shell policy as JSON in the `sandbox_shell` configuration key. The rules
+//! match Cylo's `ShellPolicy`; without the key every command is allowed.
+
+use extism_pdk::config;
+use serde::Deserialize;
+
+/// Configuration key holding the shell policy
+const SHELL_POLICY_KEY: &str = "sandbox_shell";
+
+/// Shell policy of the sandbox profile
+#[derive(Debug, Deserialize)]
+struct ShellPolicy {
+    #[serde(default = "default_true")]
+    enabled: bool,
+    #[serde(default)]
+    allowed_commands: Vec<String>,
+    #[serde(default = "default_true")]
+    allow_writes: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Why the sandbox profile refuses `command`, if it does
+pub fn refusal(command: &str) -> Option<String> {
+    let policy = match config::get(SHELL_POLICY_KEY) {
+        Ok(Some(policy)) => policy,
+        _ => return None,
+    };
+    let policy: ShellPolicy = match serde_json::from_str(&policy) {
+        Ok(policy) => policy,
+        // A policy that cannot be read must not silently allow everything
+        Err(e) => return Some(format!("invalid sandbox shell policy: {}", e)),
+    };
+
+    if !policy.enabled {
+        return Some("shell commands are disabled by the sandbox profile".to_string());
+    }
+    if !policy.allow_writes {
+        if let Some(target) = file_redirect(command) {
+            return Some(format!(
+                "writing to '{}' is not allowed by the sandbox profile",
+                target
+            ));
+        }
+    }
+    if policy.allowed_commands.is_empty() {
+        return None;
+    }
+
+    if command.contains("$(") || command.contains('`') {
+        return Some("command substitution is not allowed by the sandbox profile".to_string());
+    }
+    // `2>&1` is a redirection, not a background `&`
+    let command = command.replace(">&", ">");
+    for segment in command.split(['|', ';', '&', '\n']) {
+        let Some(program) = program_name(segment) else {
+            continue;
+        };
+        if !policy.allowed_commands.iter().any(|allowed| allowed == program) {
+            return Some(format!(
+                "'{}' is not an allowed command under the sandbox profile",
+                program
+            ));
+        }
+    }
+    None
+}
+
+/// First file a command line redirects output into, other than `/dev/null`
+fn file_redirect(command: &str) -> Option<String> {
+    let mut rest = command;
+    while let Some(index) = rest.find('>') {
+        rest = rest[index..].trim_start_matches('>');
+        if rest.starts_with('&') {
+            continue;
+        }
+        let target: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| !c.is_whitespace() && !matches!(c, ';' | '|' | '&'))
+            .collect();
+        if target != "/dev/null" {
+            return Some(target);
+        }
+    }
+    None
+}
+
+/// Program a pipeline segment runs, skipping leading `NAME=value` assignments
+fn program_name(segment: &str) -> Option<&str> {
+    let word = segment
+        .split_whitespace()
+        .find(|word| !(word.contains('=') && !word.starts_with('=')))?;
+    Some(word.rsplit('/').next().unwrap_or(word))
+}
@@ -0,0 +1,110 @@
+//! Per-request resource limits shared by every fetcher
+//!
+//! A limit that triggers cuts the fetch short instead of failing it: the
+//! content gathered so far is returned, marked as truncated with the limit
+//! that stopped it.
+
+use std::fmt;
+use std::time::Duration;
+
+use html5ever::parse_document;
+use html5ever::serialize::{SerializeOpts, serialize};
+use html5ever::tendril::TendrilSink;
+use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
+use serde::Serialize;
+
+/// Resource limits for one fetch; `None` leaves a resource unlimited
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchBudget {
+    /// Most bytes of page body downloaded
+    pub max_bytes: Option<usize>,
+    /// Longest time spent loading and rendering the page
+    pub max_render_time: Option<Duration>,
+    /// Most elements kept from the page's DOM
+    pub max_dom_nodes: Option<usize>,
+}
+
+/// The limit that cut a fetch short
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Truncation {
+    MaxBytes,
+    MaxRenderTime,
+    MaxDomNodes,
+}
+
+impl fmt::Display for Truncation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Truncation::MaxBytes => write!(f, "download stopped at max_bytes"),
+            Truncation::MaxRenderTime => write!(f, "page captured when max_render_time ran out"),
+            Truncation::MaxDomNodes => write!(f, "elements beyond max_dom_nodes dropped"),
+        }
+    }
+}
+
+impl FetchBudget {
+    /// Time allowed for a stage that is never given more than `cap`
+    pub fn render_timeout(&self, cap: Duration) -> Duration {
+        self.max_render_time.map_or(cap, |limit| limit.min(cap))
+    }
+
+    /// Whether `len` bytes reach the download limit
+    pub fn bytes_exhausted(&self, len: usize) -> bool {
+        self.max_bytes.is_some_and(|max| len >= max)
+    }
+
+    /// Cut `content` to `max_bytes`, at a character boundary
+    pub fn clip_bytes(&self, content: &mut String) -> Option<Truncation> {
+        let max = self.max_bytes?;
+        if content.len() <= max {
+            return None;
+        }
+        let mut end = max;
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content.truncate(end);
+        Some(Truncation::MaxBytes)
+    }
+
+    /// Keep only the first `max_dom_nodes` elements of an HTML document, in
+    /// document order
+    pub fn clip_dom(&self, html: &mut String) -> Option<Truncation> {
+        let max = self.max_dom_nodes?;
+        let dom = parse_document(RcDom::default(), Default::default())
+            .from_utf8()
+            .read_from(&mut html.as_bytes())
+            .ok()?;
+
+        let mut remaining = max;
+        if !prune_elements(&dom.document, &mut remaining) {
+            return None;
+        }
+
+        let mut bytes = Vec::with_capacity(html.len());
+        let document: SerializableHandle = dom.document.clone().into();
+        serialize(&mut bytes, &document, SerializeOpts::default()).ok()?;
+        *html = String::from_utf8(bytes).ok()?;
+        Some(Truncation::MaxDomNodes)
+    }
+}
+
+/// Drop every element after the first `remaining`; returns whether any were
+/// dropped
+fn prune_elements(node: &Handle, remaining: &mut usize) -> bool {
+    let mut pruned = false;
+    node.children.borrow_mut().retain(|child| {
+        if !matches!(child.data, NodeData::Element { .. }) {
+            return true;
+        }
+        if *remaining == 0 {
+            pruned = true;
+            return false;
+        }
+        *remaining -= 1;
+        pruned |= prune_elements(child, remaining);
+        true
+    });
+    pruned
+}
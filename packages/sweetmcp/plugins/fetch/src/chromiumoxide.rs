@@ -9,6 +9,9 @@ use chromiumoxide::{Browser, BrowserConfig};
 pub use chromiumoxide::Page;
 use futures::StreamExt;
 use log::{debug, warn};
+use serde_json::Value;
+
+use crate::budget::{FetchBudget, Truncation};
 
 #[derive(Debug)]
 pub enum ChromiumFetchError {
@@ -37,13 +40,84 @@ pub struct FetchResult {
     pub content: String,
     pub screenshot_base64: Option<String>,
     pub content_type: String,
+    /// JSON result of the caller's `evaluate` snippet (browser fetches only)
+    pub evaluation: Option<Value>,
+    /// Limit that cut the fetch short, if any
+    pub truncated: Option<Truncation>,
+}
+
+/// Page readiness condition to reach before capturing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitUntil {
+    /// The `load` event has fired (`document.readyState == "complete"`)
+    Load,
+    /// The DOM has been parsed (`document.readyState != "loading"`)
+    DomContentLoaded,
+    /// No new network resources for `NETWORK_IDLE_MS`
+    NetworkIdle,
+}
+
+impl std::str::FromStr for WaitUntil {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "load" => Ok(WaitUntil::Load),
+            "domcontentloaded" => Ok(WaitUntil::DomContentLoaded),
+            "networkidle" => Ok(WaitUntil::NetworkIdle),
+            _ => Err(format!("Invalid wait_until condition: {}", s)),
+        }
+    }
 }
 
+/// Page interaction controls for browser fetches
+#[derive(Debug, Clone)]
+pub struct BrowserOptions {
+    /// Readiness condition; `None` keeps the fixed settle delay
+    pub wait_until: Option<WaitUntil>,
+    /// CSS selector that must be present before capturing
+    pub wait_for_selector: Option<String>,
+    /// How long to wait for `wait_for_selector` to appear
+    pub selector_timeout: Duration,
+    /// JavaScript expression whose JSON result is returned with the content
+    pub evaluate: Option<String>,
+    /// Keep scrolling until the page stops growing (infinite scroll)
+    pub scroll_to_bottom: bool,
+}
+
+impl Default for BrowserOptions {
+    fn default() -> Self {
+        Self {
+            wait_until: None,
+            wait_for_selector: None,
+            selector_timeout: Duration::from_secs(10),
+            evaluate: None,
+            scroll_to_bottom: false,
+        }
+    }
+}
+
+/// Upper bound on any readiness wait
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Quiet period without new resources that counts as network idle
+const NETWORK_IDLE_MS: u64 = 500;
+
+/// Interval between readiness and selector polls
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Scroll rounds before giving up on a page that keeps growing
+const MAX_SCROLL_ROUNDS: usize = 20;
+
+/// Pause after each scroll for lazy content to load
+const SCROLL_PAUSE: Duration = Duration::from_millis(500);
+
 #[async_trait]
 pub trait ContentFetcher {
     async fn fetch_content(
         &self,
         url: &str,
+        budget: &FetchBudget,
     ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>>;
 }
 
@@ -98,6 +172,101 @@ pub async fn take_screenshot(page: &Page) -> Result<String, ChromiumFetchError>
 }
 
 impl ChromiumFetcher {
+    // Evaluate a JS expression and return its JSON value (undefined becomes null)
+    async fn evaluate_json(page: &Page, js: &str) -> Result<Value, ChromiumFetchError> {
+        let result = page
+            .evaluate(js)
+            .await
+            .map_err(|e| ChromiumFetchError::Content(format!("Failed to evaluate script: {}", e)))?;
+        Ok(result.value().cloned().unwrap_or(Value::Null))
+    }
+
+    // Poll until the page reaches the requested readiness condition
+    async fn wait_until(page: &Page, condition: WaitUntil) -> Result<(), ChromiumFetchError> {
+        let wait = async {
+            match condition {
+                WaitUntil::Load | WaitUntil::DomContentLoaded => {
+                    let ready = if condition == WaitUntil::Load {
+                        "document.readyState === 'complete'"
+                    } else {
+                        "document.readyState !== 'loading'"
+                    };
+                    while Self::evaluate_json(page, ready).await? != Value::Bool(true) {
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                }
+                WaitUntil::NetworkIdle => {
+                    let count_js = "performance.getEntriesByType('resource').length";
+                    let mut last = Self::evaluate_json(page, count_js).await?;
+                    let mut quiet_ms = 0;
+                    while quiet_ms < NETWORK_IDLE_MS {
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                        let count = Self::evaluate_json(page, count_js).await?;
+                        if count == last {
+                            quiet_ms += POLL_INTERVAL.as_millis() as u64;
+                        } else {
+                            quiet_ms = 0;
+                            last = count;
+                        }
+                    }
+                }
+            }
+            Ok::<(), ChromiumFetchError>(())
+        };
+
+        tokio::time::timeout(READY_TIMEOUT, wait)
+            .await
+            .map_err(|_| ChromiumFetchError::Timeout(format!("Page did not reach {:?}", condition)))?
+    }
+
+    // Poll until `selector` matches an element or `timeout` elapses
+    async fn wait_for_selector(
+        page: &Page,
+        selector: &str,
+        timeout: Duration,
+    ) -> Result<(), ChromiumFetchError> {
+        let wait = async {
+            while page.find_element(selector).await.is_err() {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            ChromiumFetchError::Timeout(format!(
+                "Selector '{}' not found within {}ms",
+                selector,
+                timeout.as_millis()
+            ))
+        })
+    }
+
+    // Scroll to the bottom repeatedly until the document height settles, or
+    // the page outgrows `max_dom_nodes`
+    async fn scroll_to_bottom(
+        page: &Page,
+        max_dom_nodes: Option<usize>,
+    ) -> Result<(), ChromiumFetchError> {
+        let scroll_js = "window.scrollTo(0, document.body.scrollHeight); document.body.scrollHeight";
+        let count_js = "document.getElementsByTagName('*').length";
+        let mut last_height = Value::Null;
+        for _ in 0..MAX_SCROLL_ROUNDS {
+            let height = Self::evaluate_json(page, scroll_js).await?;
+            if height == last_height {
+                return Ok(());
+            }
+            if let Some(max) = max_dom_nodes
+                && Self::evaluate_json(page, count_js).await?.as_u64().unwrap_or(0) > max as u64
+            {
+                debug!("Chromiumoxide: Page outgrew {} elements, stopped scrolling", max);
+                return Ok(());
+            }
+            last_height = height;
+            tokio::time::sleep(SCROLL_PAUSE).await;
+        }
+        debug!("Chromiumoxide: Page still growing after {} scrolls", MAX_SCROLL_ROUNDS);
+        Ok(())
+    }
+
     // Get page content with scripts and styles removed
     async fn get_cleaned_content(page: &Page) -> Result<String, ChromiumFetchError> {
         // Execute JavaScript to get HTML content with script and style tags removed
@@ -134,11 +303,16 @@ impl ChromiumFetcher {
     }
 }
 
-#[async_trait]
-impl ContentFetcher for ChromiumFetcher {
-    async fn fetch_content(
+impl ChromiumFetcher {
+    /// Fetch `url` applying the wait, scroll and evaluation controls in `options`
+    ///
+    /// Navigation, waits and scrolling share `budget.max_render_time`; when
+    /// it runs out the page is captured as it stands.
+    pub async fn fetch_with_options(
         &self,
         url: &str,
+        options: &BrowserOptions,
+        budget: &FetchBudget,
     ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>> {
         debug!("Chromiumoxide: Launching browser for {}", url);
         // Launch browser
@@ -151,27 +325,70 @@ impl ContentFetcher for ChromiumFetcher {
             .await
             .map_err(|e| ChromiumFetchError::Browser(format!("Failed to create page: {}", e)))?;
 
-        // Navigate to the URL with a timeout
-        debug!("Chromiumoxide: Navigating to {}", url);
-        let navigation_result = tokio::time::timeout(Duration::from_secs(30), page.goto(url)).await;
+        let render = async {
+            // Navigate to the URL with a timeout
+            debug!("Chromiumoxide: Navigating to {}", url);
+            let navigation_result =
+                tokio::time::timeout(Duration::from_secs(30), page.goto(url)).await;
 
-        // Check for timeout or navigation error
-        match navigation_result {
-            Ok(result) => {
-                result.map_err(|e| {
-                    ChromiumFetchError::Navigation(format!("Failed to navigate to URL: {}", e))
-                })?;
+            // Check for timeout or navigation error
+            match navigation_result {
+                Ok(result) => {
+                    result.map_err(|e| {
+                        ChromiumFetchError::Navigation(format!("Failed to navigate to URL: {}", e))
+                    })?;
+                }
+                Err(_) => {
+                    return Err(ChromiumFetchError::Timeout(format!(
+                        "Navigation to {} timed out ",
+                        url
+                    )));
+                }
             }
-            Err(_) => {
-                return Err(Box::new(ChromiumFetchError::Timeout(format!(
-                    "Navigation to {} timed out ",
-                    url
-                ))));
+
+            // Wait for the requested readiness, or a fixed settle delay by default
+            match options.wait_until {
+                Some(condition) => {
+                    debug!("Chromiumoxide: Waiting for {:?}", condition);
+                    Self::wait_until(&page, condition).await?;
+                }
+                None => tokio::time::sleep(Duration::from_secs(2)).await,
             }
+
+            if let Some(selector) = &options.wait_for_selector {
+                debug!("Chromiumoxide: Waiting for selector {}", selector);
+                Self::wait_for_selector(&page, selector, options.selector_timeout).await?;
+            }
+
+            if options.scroll_to_bottom {
+                debug!("Chromiumoxide: Scrolling to bottom");
+                Self::scroll_to_bottom(&page, budget.max_dom_nodes).await?;
+            }
+            Ok::<(), ChromiumFetchError>(())
+        };
+
+        let mut truncated = None;
+        match budget.max_render_time {
+            Some(limit) => match tokio::time::timeout(limit, render).await {
+                Ok(rendered) => rendered?,
+                Err(_) => {
+                    debug!(
+                        "Chromiumoxide: Render budget of {}ms spent, capturing the page as is",
+                        limit.as_millis()
+                    );
+                    truncated = Some(Truncation::MaxRenderTime);
+                }
+            },
+            None => render.await?,
         }
 
-        // Wait for page to be fully loaded
-        tokio::time::sleep(Duration::from_secs(2)).await;
+        let evaluation = match &options.evaluate {
+            Some(js) => {
+                debug!("Chromiumoxide: Evaluating caller script");
+                Some(Self::evaluate_json(&page, js).await?)
+            }
+            None => None,
+        };
 
         // Take screenshot
         debug!("Chromiumoxide: Taking screenshot");
@@ -195,6 +412,19 @@ impl ContentFetcher for ChromiumFetcher {
             content,
             screenshot_base64: Some(screenshot_base64),
             content_type,
+            evaluation,
+            truncated,
         })
     }
 }
+
+#[async_trait]
+impl ContentFetcher for ChromiumFetcher {
+    async fn fetch_content(
+        &self,
+        url: &str,
+        budget: &FetchBudget,
+    ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>> {
+        self.fetch_with_options(url, &BrowserOptions::default(), budget).await
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(not(target_family = "wasm"))]
 use crate::chromiumoxide::{create_browser, take_screenshot};
 
+use crate::budget::FetchBudget;
 use crate::hyper::{ContentFetcher, FetchResult};
 
 #[derive(Debug)]
@@ -142,7 +143,7 @@ impl FirecrawlFetcher {
 
     // Real Firecrawl API integration (native only)
     #[cfg(not(target_family = "wasm"))]
-    async fn fetch_with_firecrawl(url: &str) -> Result<String, FirecrawlError> {
+    async fn fetch_with_firecrawl(url: &str, timeout: Duration) -> Result<String, FirecrawlError> {
         use hyper::{Request, Method, StatusCode};
         use hyper_util::client::legacy::Client;
         use hyper_rustls::HttpsConnectorBuilder;
@@ -243,15 +244,15 @@ impl FirecrawlFetcher {
             Ok(html_content)
         };
         
-        // Apply 30-second timeout for API calls
-        tokio::time::timeout(Duration::from_secs(30), fetch_future)
+        // Apply the caller's timeout (at most 30 seconds) for API calls
+        tokio::time::timeout(timeout, fetch_future)
             .await
             .map_err(|_| FirecrawlError::Timeout(format!("Firecrawl API timeout for {}", url)))?
     }
 
     // WASM version: Firecrawl not available
     #[cfg(target_family = "wasm")]
-    async fn fetch_with_firecrawl(_url: &str) -> Result<String, FirecrawlError> {
+    async fn fetch_with_firecrawl(_url: &str, _timeout: Duration) -> Result<String, FirecrawlError> {
         Err(FirecrawlError::Internal(
             "Firecrawl API not available in WASM build. Requires native HTTPS client.".to_string()
         ))
@@ -263,12 +264,17 @@ impl ContentFetcher for FirecrawlFetcher {
     async fn fetch_content(
         &self,
         url: &str,
+        budget: &FetchBudget,
     ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>> {
-        // Fetch content using Firecrawl
-        let html_content = Self::fetch_with_firecrawl(url)
+        // Fetch content using Firecrawl; the API answers with the whole page,
+        // so only its time can be bounded here and max_bytes trims afterwards
+        let timeout = budget.render_timeout(Duration::from_secs(30));
+        let mut html_content = Self::fetch_with_firecrawl(url, timeout)
             .await
             .map_err(|e| FirecrawlError::Network(format!("Failed to fetch content: {}", e)))?;
 
+        let truncated = budget.clip_bytes(&mut html_content);
+
         // Clean the HTML (remove scripts and styles)
         let cleaned_html = Self::clean_html(&html_content);
 
@@ -281,6 +287,8 @@ impl ContentFetcher for FirecrawlFetcher {
             content: cleaned_html,
             screenshot_base64,
             content_type: "text/html".to_string(),
+            evaluation: None,
+            truncated,
         })
     }
 }
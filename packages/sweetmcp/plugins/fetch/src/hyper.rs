@@ -19,6 +19,7 @@ use gloo_timers::future::TimeoutFuture;
 #[cfg(target_family = "wasm")]
 use futures::{select, FutureExt};
 
+use crate::budget::{FetchBudget, Truncation};
 #[cfg(not(target_family = "wasm"))]
 pub use crate::chromiumoxide::{ContentFetcher, FetchResult};
 
@@ -28,12 +29,18 @@ pub struct FetchResult {
     pub content: String,
     pub screenshot_base64: Option<String>,
     pub content_type: String,
+    pub evaluation: Option<serde_json::Value>,
+    pub truncated: Option<Truncation>,
 }
 
 #[cfg(target_family = "wasm")]
 #[async_trait]
 pub trait ContentFetcher {
-    async fn fetch_content(&self, url: &str) -> Result<FetchResult, Box<dyn StdError + Send + Sync>>;
+    async fn fetch_content(
+        &self,
+        url: &str,
+        budget: &FetchBudget,
+    ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>>;
 }
 
 #[derive(Debug)]
@@ -96,8 +103,17 @@ impl From<std::io::Error> for FetchError {
 pub struct HyperFetcher;
 
 impl HyperFetcher {
+    /// Fetch the body of `url`, stopping early once the budget's byte or
+    /// time limit is reached
     #[cfg(not(target_family = "wasm"))]
-    pub async fn fetch(url: &str) -> Result<String, FetchError> {
+    pub async fn fetch(
+        url: &str,
+        budget: &FetchBudget,
+    ) -> Result<(String, Option<Truncation>), FetchError> {
+        let deadline = budget
+            .max_render_time
+            .map(|limit| tokio::time::Instant::now() + limit);
+
         // Parse the URL
         let uri: Uri = url.parse()?;
 
@@ -175,8 +191,15 @@ impl HyperFetcher {
             .header(hyper::header::ACCEPT_ENCODING, "identity")
             .body(Empty::<Bytes>::new())?;
 
-        // Send request
-        let response = sender.send_request(request).await?;
+        // Send request; without a response there is nothing partial to return
+        let response = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, sender.send_request(request))
+                .await
+                .map_err(|_| {
+                    FetchError::Other(format!("No response from {} within max_render_time", url))
+                })??,
+            None => sender.send_request(request).await?,
+        };
         let status = response.status();
 
         if !status.is_success() {
@@ -194,23 +217,51 @@ impl HyperFetcher {
             .and_then(|v| v.to_str().ok())
             .and_then(|s| s.parse::<usize>().ok());
 
-        let mut body_bytes = if let Some(len) = content_length {
-            Vec::with_capacity(len.min(10 * 1024 * 1024)) // Cap at 10MB pre-allocation
-        } else {
-            Vec::with_capacity(64 * 1024) // 64KB default
-        };
+        let capacity = content_length
+            .unwrap_or(64 * 1024) // 64KB default
+            .min(budget.max_bytes.unwrap_or(usize::MAX));
+        // Cap at 10MB pre-allocation
+        let mut body_bytes = Vec::with_capacity(capacity.min(10 * 1024 * 1024));
 
+        let mut truncated = None;
         let mut body = response.into_body();
-        while let Some(frame) = body.frame().await {
+        loop {
+            let frame = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, body.frame()).await {
+                    Ok(frame) => frame,
+                    Err(_) => {
+                        truncated = Some(Truncation::MaxRenderTime);
+                        break;
+                    }
+                },
+                None => body.frame().await,
+            };
+            let Some(frame) = frame else { break };
             let frame = frame.map_err(|e| FetchError::Other(format!("Frame error: {}", e)))?;
             if let Some(chunk) = frame.data_ref() {
-                body_bytes.extend_from_slice(chunk);
+                let room = budget
+                    .max_bytes
+                    .map_or(chunk.len(), |max| max.saturating_sub(body_bytes.len()));
+                body_bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+                if budget.bytes_exhausted(body_bytes.len()) {
+                    truncated = Some(Truncation::MaxBytes);
+                    break;
+                }
             }
         }
 
+        // A cut body may end inside a multi-byte character
+        if truncated.is_some()
+            && let Err(e) = std::str::from_utf8(&body_bytes)
+            && e.error_len().is_none()
+        {
+            body_bytes.truncate(e.valid_up_to());
+        }
+
         // Convert to string without re-allocation
-        String::from_utf8(body_bytes)
-            .map_err(|e| FetchError::Other(format!("Invalid UTF-8: {}", e)))
+        let content = String::from_utf8(body_bytes)
+            .map_err(|e| FetchError::Other(format!("Invalid UTF-8: {}", e)))?;
+        Ok((content, truncated))
     }
 
     // WASM version: uses browser's fetch API via gloo-net
@@ -218,7 +269,10 @@ impl HyperFetcher {
     // CORS restrictions apply as per browser security policies
     // Timeout: 30 seconds (consistent with chromiumoxide pattern)
     #[cfg(target_family = "wasm")]
-    pub async fn fetch(url: &str) -> Result<String, FetchError> {
+    pub async fn fetch(
+        url: &str,
+        budget: &FetchBudget,
+    ) -> Result<(String, Option<Truncation>), FetchError> {
         // Validate URL scheme (browser enforces HTTPS for secure contexts)
         let uri: Uri = url.parse()?;
         let scheme = uri
@@ -251,20 +305,28 @@ impl HyperFetcher {
                 )));
             }
 
-            // Extract response body as text
-            response
+            // Extract response body as text; the browser reads it whole, so
+            // max_bytes only trims it afterwards
+            let mut content = response
                 .text()
                 .await
-                .map_err(|e| FetchError::Other(format!("Failed to read response body: {}", e)))
+                .map_err(|e| FetchError::Other(format!("Failed to read response body: {}", e)))?;
+            let truncated = budget.clip_bytes(&mut content);
+            Ok((content, truncated))
         };
 
-        let timeout_future = TimeoutFuture::new(30_000);
+        let timeout = budget.render_timeout(std::time::Duration::from_secs(30));
+        let timeout_future = TimeoutFuture::new(timeout.as_millis() as u32);
 
         // Race fetch against timeout
         select! {
             result = fetch_future.fuse() => result,
             _ = timeout_future.fuse() => {
-                Err(FetchError::Other(format!("Request to {} timed out after 30 seconds", url)))
+                Err(FetchError::Other(format!(
+                    "Request to {} timed out after {}ms",
+                    url,
+                    timeout.as_millis()
+                )))
             },
         }
     }
@@ -310,9 +372,10 @@ impl ContentFetcher for HyperFetcher {
     async fn fetch_content(
         &self,
         url: &str,
+        budget: &FetchBudget,
     ) -> Result<FetchResult, Box<dyn StdError + Send + Sync>> {
         // Fetch HTML content using hyper
-        let content = Self::fetch(url)
+        let (content, truncated) = Self::fetch(url, budget)
             .await
             .map_err(|e| Box::new(e) as Box<dyn StdError + Send + Sync>)?;
 
@@ -325,6 +388,8 @@ impl ContentFetcher for HyperFetcher {
             content: cleaned_content,
             screenshot_base64: None,
             content_type: "text/html".to_string(),
+            evaluation: None,
+            truncated,
         })
     }
 }
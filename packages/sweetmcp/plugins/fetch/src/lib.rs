@@ -1,3 +1,4 @@
+mod budget;
 #[cfg(not(target_family = "wasm"))]
 mod chromiumoxide;
 mod hyper;
@@ -20,7 +21,7 @@ use htmd::HtmlToMarkdown;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use sweetmcp_plugin_builder::prelude::*;
-use sweetmcp_plugin_builder::{CallToolResult, Content, ContentType, Ready};
+use sweetmcp_plugin_builder::{CallToolResult, Ready};
 #[cfg(not(target_family = "wasm"))]
 use syntect::{highlighting::ThemeSet, html::highlighted_html_for_string, parsing::SyntaxSet};
 
@@ -30,6 +31,7 @@ use html5ever::tendril::TendrilSink;
 use markup5ever_rcdom::{Node, NodeData, RcDom};
 
 // use async_trait::async_trait;
+use crate::budget::{FetchBudget, Truncation};
 use crate::hyper::HyperFetcher;
 
 /// Encode an RGB image to Sixel format (based on sixel6vt implementation)
@@ -179,6 +181,9 @@ impl FromStr for ContentFormat {
 #[derive(Debug, Deserialize)]
 struct FetchOptions {
     url: String,
+    #[cfg(not(target_family = "wasm"))]
+    #[serde(skip)]
+    browser: chromiumoxide::BrowserOptions,
     #[serde(default)]
     screenshot_format: ScreenshotFormat,
     #[serde(default)]
@@ -187,6 +192,8 @@ struct FetchOptions {
     syntax_highlighting: bool,
     #[serde(default)]
     theme: Option<String>,
+    #[serde(skip)]
+    budget: FetchBudget,
 }
 
 #[derive(Debug, Serialize)]
@@ -194,6 +201,8 @@ struct FetchResponse {
     screenshot: String,
     content: String,
     content_type: String,
+    evaluation: Option<Value>,
+    truncated: Option<Truncation>,
 }
 
 /// Fetch tool using plugin-builder
@@ -208,8 +217,10 @@ impl McpTool for FetchTool {
             .when("you need to scrape web pages and extract content in multiple formats (markdown, JSON, plain text)")
             .when("you need to take screenshots of web pages for visual documentation")
             .when("you need to process dynamic websites with JavaScript rendering")
+            .when("you need to wait for a selector, scroll an infinite feed, or run a script against the rendered page")
             .when("you need to handle complex websites with multiple fallback strategies (Bevy, Chromium, Firecrawl)")
             .when("you need to apply syntax highlighting to extracted code content")
+            .when("you need to bound the bytes, render time or DOM size a page may cost, accepting a truncated result")
             .perfect_for("web scraping, content analysis, competitive research, and automated documentation")
     }
 
@@ -231,6 +242,39 @@ impl McpTool for FetchTool {
                 "Whether to apply syntax highlighting to the content",
             )
             .optional_string("theme", "Theme to use for syntax highlighting")
+            .optional_enum(
+                "wait_until",
+                "Page readiness to wait for before capturing (default: fixed 2s settle)",
+                &["load", "domcontentloaded", "networkidle"],
+            )
+            .optional_string(
+                "wait_for_selector",
+                "CSS selector that must appear before capturing",
+            )
+            .optional_number(
+                "selector_timeout_ms",
+                "How long to wait for wait_for_selector in milliseconds (default 10000)",
+            )
+            .optional_string(
+                "evaluate",
+                "JavaScript expression evaluated in the page; its JSON result is returned alongside the content",
+            )
+            .optional_bool(
+                "scroll_to_bottom",
+                "Scroll until the page stops growing, for infinite-scroll pages",
+            )
+            .optional_number(
+                "max_bytes",
+                "Stop downloading the page after this many bytes",
+            )
+            .optional_number(
+                "max_render_time_ms",
+                "Capture the page as it stands after this many milliseconds of loading and rendering",
+            )
+            .optional_number(
+                "max_dom_nodes",
+                "Keep only this many elements of the page, in document order",
+            )
             .build()
     }
 
@@ -240,23 +284,40 @@ impl McpTool for FetchTool {
             Error::msg("Expected arguments to be an object")
         })?;
         let options = parse_options(obj.clone())?;
+        let sixel = matches!(options.screenshot_format, ScreenshotFormat::Sixel);
 
         // Run the async fetching process
-        let fetch_result = block_on_fetch(options.url.as_str())?;
+        let fetch_result = enforce_budget(block_on_fetch(&options)?, &options.budget);
 
         // Process results based on user preferences
         let response = process_fetch_result(fetch_result, options)?;
 
-        Ok(CallToolResult {
-            is_error: None,
-            content: vec![Content {
-                annotations: None,
-                text: Some(response.content),
-                mime_type: Some(response.content_type),
-                r#type: ContentType::Text,
-                data: Some(response.screenshot),
-            }],
-        })
+        let mut result = ContentBuilder::parts().text_as(response.content, response.content_type);
+
+        // A limit that cut the page short is reported right after the content
+        if let Some(reason) = response.truncated {
+            result = result.json(&json!({
+                "truncated": true,
+                "reason": reason,
+                "detail": reason.to_string(),
+            }));
+        }
+
+        // The screenshot travels as its own part: PNG data, or sixel text for terminals
+        if !response.screenshot.is_empty() {
+            result = if sixel {
+                result.text_as(response.screenshot, "text/x-sixel")
+            } else {
+                result.image_base64(response.screenshot, "image/png")
+            };
+        }
+
+        // The evaluate result travels as its own JSON part
+        if let Some(evaluation) = response.evaluation {
+            result = result.json(&evaluation);
+        }
+
+        Ok(result.build())
     }
 }
 
@@ -285,21 +346,86 @@ fn parse_options(args: serde_json::Map<String, Value>) -> Result<FetchOptions, E
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        #[cfg(not(target_family = "wasm"))]
+        let browser = parse_browser_options(&args)?;
+
+        let budget = FetchBudget {
+            max_bytes: parse_limit(&args, "max_bytes")?.map(|n| n as usize),
+            max_render_time: parse_limit(&args, "max_render_time_ms")?
+                .map(std::time::Duration::from_millis),
+            max_dom_nodes: parse_limit(&args, "max_dom_nodes")?.map(|n| n as usize),
+        };
+
         Ok(FetchOptions {
             url: url.clone(),
+            #[cfg(not(target_family = "wasm"))]
+            browser,
             screenshot_format,
             content_format,
             syntax_highlighting,
             theme,
+            budget,
         })
     } else {
         Err(Error::msg("Please provide a url"))
     }
 }
 
+// Parse an optional positive resource limit
+fn parse_limit(args: &serde_json::Map<String, Value>, name: &str) -> Result<Option<u64>, Error> {
+    match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(limit) if limit > 0 => Ok(Some(limit)),
+            _ => Err(Error::msg(format!("{} must be a positive integer", name))),
+        },
+    }
+}
+
+// Apply the DOM and byte limits to whatever the fetcher returned, keeping the
+// fetcher's own reason when it already stopped early
+fn enforce_budget(mut result: hyper::FetchResult, budget: &FetchBudget) -> hyper::FetchResult {
+    let dom = budget.clip_dom(&mut result.content);
+    let bytes = budget.clip_bytes(&mut result.content);
+    result.truncated = result.truncated.or(dom).or(bytes);
+    result
+}
+
+// Parse the page interaction controls used by the browser fetcher
+#[cfg(not(target_family = "wasm"))]
+fn parse_browser_options(
+    args: &serde_json::Map<String, Value>,
+) -> Result<chromiumoxide::BrowserOptions, Error> {
+    let mut browser = chromiumoxide::BrowserOptions::default();
+
+    if let Some(wait_until) = args.get("wait_until").and_then(|v| v.as_str()) {
+        browser.wait_until = Some(
+            chromiumoxide::WaitUntil::from_str(wait_until).map_err(Error::msg)?,
+        );
+    }
+    browser.wait_for_selector = args
+        .get("wait_for_selector")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    if let Some(timeout_ms) = args.get("selector_timeout_ms").and_then(|v| v.as_u64()) {
+        browser.selector_timeout = std::time::Duration::from_millis(timeout_ms);
+    }
+    browser.evaluate = args
+        .get("evaluate")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    browser.scroll_to_bottom = args
+        .get("scroll_to_bottom")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    Ok(browser)
+}
+
 // Helper function to run async code from the sync world
 #[cfg(not(target_family = "wasm"))]
-fn block_on_fetch(url: &str) -> Result<chromiumoxide::FetchResult, Error> {
+fn block_on_fetch(options: &FetchOptions) -> Result<chromiumoxide::FetchResult, Error> {
+    let url = options.url.as_str();
     debug!("Starting fetch for URL: {}", url);
     
     // Set up a minimal runtime for async execution
@@ -313,18 +439,22 @@ fn block_on_fetch(url: &str) -> Result<chromiumoxide::FetchResult, Error> {
 
         // 1. First attempt: Use chromiumoxide (headless browser)
         debug!("Attempting fetch with chromiumoxide for: {}", url);
-        let chromium_result = chromiumoxide::ChromiumFetcher.fetch_content(url).await;
+        let chromium_result = chromiumoxide::ChromiumFetcher
+            .fetch_with_options(url, &options.browser, &options.budget)
+            .await;
 
         if let Ok(result) = chromium_result {
             info!("Successfully fetched with chromiumoxide: {}", url);
             return Ok(result);
+        } else if options.browser.evaluate.is_some() {
+            warn!("Chromiumoxide fetch failed for {}, trying hyper without evaluate", url);
         } else {
             warn!("Chromiumoxide fetch failed for {}, trying hyper", url);
         }
 
         // 2. Second attempt: Use hyper (HTTP client)
         debug!("Attempting fetch with hyper for: {}", url);
-        let hyper_result = HyperFetcher.fetch_content(url).await;
+        let hyper_result = HyperFetcher.fetch_content(url, &options.budget).await;
 
         if let Ok(result) = hyper_result {
             info!("Fallback to hyper successful for: {}", url);
@@ -335,7 +465,7 @@ fn block_on_fetch(url: &str) -> Result<chromiumoxide::FetchResult, Error> {
 
         // 3. Final contingency: Use firecrawl
         debug!("Attempting fetch with firecrawl for: {}", url);
-        let firecrawl_result = firecrawl::FirecrawlFetcher.fetch_content(url).await;
+        let firecrawl_result = firecrawl::FirecrawlFetcher.fetch_content(url, &options.budget).await;
 
         match firecrawl_result {
             Ok(result) => {
@@ -355,7 +485,8 @@ fn block_on_fetch(url: &str) -> Result<chromiumoxide::FetchResult, Error> {
 
 // WASM version: simplified fetching without browser automation
 #[cfg(target_family = "wasm")]
-fn block_on_fetch(url: &str) -> Result<hyper::FetchResult, Error> {
+fn block_on_fetch(options: &FetchOptions) -> Result<hyper::FetchResult, Error> {
+    let url = options.url.as_str();
     debug!("Starting WASM fetch for URL: {}", url);
     
     // Set up a minimal runtime for async execution
@@ -369,7 +500,7 @@ fn block_on_fetch(url: &str) -> Result<hyper::FetchResult, Error> {
 
         // 1. First attempt: Use hyper (HTTP client)
         debug!("Attempting WASM fetch with hyper for: {}", url);
-        let hyper_result = HyperFetcher.fetch_content(url).await;
+        let hyper_result = HyperFetcher.fetch_content(url, &options.budget).await;
 
         if let Ok(result) = hyper_result {
             info!("Successfully fetched with hyper in WASM: {}", url);
@@ -380,7 +511,7 @@ fn block_on_fetch(url: &str) -> Result<hyper::FetchResult, Error> {
 
         // 2. Final contingency: Use firecrawl
         debug!("Attempting WASM fetch with firecrawl for: {}", url);
-        let firecrawl_result = firecrawl::FirecrawlFetcher.fetch_content(url).await;
+        let firecrawl_result = firecrawl::FirecrawlFetcher.fetch_content(url, &options.budget).await;
 
         match firecrawl_result {
             Ok(result) => {
@@ -470,6 +601,8 @@ fn process_fetch_result(
         screenshot,
         content: final_content,
         content_type,
+        evaluation: result.evaluation,
+        truncated: result.truncated,
     })
 }
 
@@ -525,6 +658,8 @@ fn process_fetch_result(
         screenshot,
         content: final_content,
         content_type,
+        evaluation: result.evaluation,
+        truncated: result.truncated,
     })
 }
 
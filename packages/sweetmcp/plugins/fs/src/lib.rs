@@ -1,10 +1,13 @@
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 use std::time::SystemTime;
 
 use extism_pdk::*;
 use log::{debug, warn};
 use serde_json::{Value, json};
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 use similar::TextDiff;
 use sweetmcp_plugin_builder::prelude::*;
 use sweetmcp_plugin_builder::{CallToolRequest, CallToolResult, ListToolsResult, Ready};
@@ -33,7 +36,8 @@ impl McpTool for FsTool {
             .operation("mkdir", "Create directories (with parent directory support)")
             .operation("list", "List contents of a directory with detailed information")
             .operation("search", "Search for files by name pattern or content")
-            .operation("read_metadata", "Get detailed file metadata and properties")
+            .operation("read_metadata", "Get detailed file metadata: owner, mode, timestamps, MIME type and optional content hash")
+            .operation("dir_size", "Compute recursive directory sizes (du-style) down to an optional depth")
             .requires("File system access permissions for the target paths")
             .not_for("operations outside of allowed directories or system files")
     }
@@ -52,6 +56,7 @@ impl McpTool for FsTool {
                     "list",
                     "search",
                     "read_metadata",
+                    "dir_size",
                 ],
             )
             .optional_string(
@@ -72,6 +77,15 @@ impl McpTool for FsTool {
                 "count",
                 "Number of occurrences to replace (optional for edit operation, default: all)",
             )
+            .optional_enum(
+                "hash_algorithm",
+                "Content hash to include in read_metadata output (optional)",
+                HASH_ALGORITHMS,
+            )
+            .optional_number(
+                "max_depth",
+                "Deepest level of subdirectories reported by dir_size (optional, default: 0 = total only)",
+            )
             .build()
     }
 
@@ -90,6 +104,7 @@ impl McpTool for FsTool {
             "list" => list_dir(&args),
             "search" => search_files(&args),
             "read_metadata" => get_file_info(&args),
+            "dir_size" => dir_size(&args),
             _ => Ok(ContentBuilder::error(format!(
                 "Unknown fs operation: {}",
                 operation
@@ -469,6 +484,85 @@ fn search_files(args: &Value) -> Result<CallToolResult, Error> {
     }
 }
 
+/// Content hash algorithms, matching the hash plugin's digest set
+const HASH_ALGORITHMS: &[&str] = &["sha256", "sha512", "sha384", "sha224", "sha1", "md5"];
+
+/// Feed a file to `consume` in fixed-size chunks
+fn read_chunks(path: &str, mut consume: impl FnMut(&[u8])) -> std::io::Result<()> {
+    let mut file = fs::File::open(path)?;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        consume(&buf[..n]);
+    }
+}
+
+/// Stream a file through `D` and return the hex digest
+fn digest_file<D: Digest>(path: &str) -> std::io::Result<String> {
+    let mut hasher = D::new();
+    read_chunks(path, |chunk| hasher.update(chunk))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash file contents with one of `HASH_ALGORITHMS`
+fn hash_file(path: &str, algorithm: &str) -> Result<String, String> {
+    debug!("Hashing file {} with algorithm: {}", path, algorithm);
+
+    let result = match algorithm {
+        "sha256" => digest_file::<Sha256>(path),
+        "sha512" => digest_file::<Sha512>(path),
+        "sha384" => digest_file::<Sha384>(path),
+        "sha224" => digest_file::<Sha224>(path),
+        "sha1" => {
+            warn!("SHA-1 is cryptographically weak and deprecated, consider using SHA-256 or higher");
+            digest_file::<Sha1>(path)
+        }
+        "md5" => {
+            warn!("MD5 is cryptographically weak and broken, consider using SHA-256 or higher");
+            let mut context = md5::Context::new();
+            read_chunks(path, |chunk| context.consume(chunk))
+                .map(|()| format!("{:x}", context.compute()))
+        }
+        _ => return Err(format!("Unsupported hash algorithm: {}", algorithm)),
+    };
+    result.map_err(|e| format!("Failed to hash {}: {}", path, e))
+}
+
+/// Seconds since the Unix epoch, if the platform reports the timestamp
+fn unix_secs(time: std::io::Result<SystemTime>) -> Option<u64> {
+    time.ok()
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+/// Detect a MIME type from magic bytes, falling back to the file extension
+fn detect_mime(path: &str) -> Option<String> {
+    if let Ok(Some(kind)) = infer::get_from_path(path) {
+        return Some(kind.mime_type().to_string());
+    }
+    mime_guess::from_path(path).first().map(|mime| mime.to_string())
+}
+
+/// POSIX ownership and permission bits (null where the platform has none)
+#[cfg(unix)]
+fn posix_fields(metadata: &fs::Metadata) -> Value {
+    use std::os::unix::fs::MetadataExt;
+
+    json!({
+        "uid": metadata.uid(),
+        "gid": metadata.gid(),
+        "mode": format!("{:o}", metadata.mode() & 0o7777),
+    })
+}
+
+#[cfg(not(unix))]
+fn posix_fields(_metadata: &fs::Metadata) -> Value {
+    json!({ "uid": null, "gid": null, "mode": null })
+}
+
 /// Get file metadata
 fn get_file_info(args: &Value) -> Result<CallToolResult, Error> {
     let path = args
@@ -476,14 +570,26 @@ fn get_file_info(args: &Value) -> Result<CallToolResult, Error> {
         .and_then(|v| v.as_str())
         .ok_or_else(|| Error::msg("path parameter required for read_metadata operation"))?;
 
+    let hash_algorithm = args.get("hash_algorithm").and_then(|v| v.as_str());
+
     match fs::metadata(path) {
         Ok(metadata) => {
-            let modified = metadata
-                .modified()
-                .ok()
-                .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
-                .map(|duration| duration.as_secs())
-                .unwrap_or(0);
+            let modified = unix_secs(metadata.modified()).unwrap_or(0);
+            let posix = posix_fields(&metadata);
+
+            let mime_type = if metadata.is_file() {
+                detect_mime(path)
+            } else {
+                None
+            };
+
+            let hash = match hash_algorithm {
+                Some(algorithm) if metadata.is_file() => match hash_file(path, algorithm) {
+                    Ok(value) => Some(json!({ "algorithm": algorithm, "value": value })),
+                    Err(e) => return Ok(ContentBuilder::error(e)),
+                },
+                _ => None,
+            };
 
             Ok(ContentBuilder::text(
                 json!({
@@ -491,12 +597,127 @@ fn get_file_info(args: &Value) -> Result<CallToolResult, Error> {
                     "size": metadata.len(),
                     "is_file": metadata.is_file(),
                     "is_dir": metadata.is_dir(),
+                    "is_symlink": fs::symlink_metadata(path).map(|m| m.file_type().is_symlink()).unwrap_or(false),
                     "modified_timestamp": modified,
+                    "created_timestamp": unix_secs(metadata.created()),
+                    "accessed_timestamp": unix_secs(metadata.accessed()),
                     "readonly": metadata.permissions().readonly(),
+                    "uid": posix["uid"],
+                    "gid": posix["gid"],
+                    "mode": posix["mode"],
+                    "mime_type": mime_type,
+                    "hash": hash,
+                })
+                .to_string(),
+            ))
+        }
+        Err(e) => Ok(ContentBuilder::error(format!(
+            "Failed to get metadata for {}: {}",
+            path, e
+        ))),
+    }
+}
+
+/// Running totals for a directory subtree
+#[derive(Default)]
+struct DirTotals {
+    bytes: u64,
+    files: u64,
+    dirs: u64,
+    /// Entries that could not be read (permissions, races)
+    errors: u64,
+}
+
+/// Sum sizes under `path`, recording subdirectories up to `max_depth`
+///
+/// Symlinks are counted by their own size and never followed.
+fn walk_dir(
+    path: &Path,
+    depth: u64,
+    max_depth: u64,
+    report: &mut Vec<Value>,
+) -> DirTotals {
+    let mut totals = DirTotals::default();
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("Failed to read directory {}: {}", path.display(), e);
+            totals.errors += 1;
+            return totals;
+        }
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => {
+                totals.errors += 1;
+                continue;
+            }
+        };
+        let metadata = match fs::symlink_metadata(entry.path()) {
+            Ok(meta) => meta,
+            Err(_) => {
+                totals.errors += 1;
+                continue;
+            }
+        };
+
+        if metadata.is_dir() {
+            let child = walk_dir(&entry.path(), depth + 1, max_depth, report);
+            totals.bytes += child.bytes;
+            totals.files += child.files;
+            totals.dirs += child.dirs + 1;
+            totals.errors += child.errors;
+        } else {
+            totals.bytes += metadata.len();
+            totals.files += 1;
+        }
+    }
+
+    if depth <= max_depth {
+        report.push(json!({
+            "path": path.to_string_lossy(),
+            "depth": depth,
+            "size": totals.bytes,
+            "file_count": totals.files,
+            "dir_count": totals.dirs,
+        }));
+    }
+
+    totals
+}
+
+/// Recursive directory size (du-style)
+fn dir_size(args: &Value) -> Result<CallToolResult, Error> {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+    let max_depth = args.get("max_depth").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    debug!("Computing directory size: {} (max_depth {})", path, max_depth);
+
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => {
+            let mut report = Vec::new();
+            let totals = walk_dir(Path::new(path), 0, max_depth, &mut report);
+
+            // Directories are recorded after their contents; put each parent first
+            report.reverse();
+
+            Ok(ContentBuilder::text(
+                json!({
+                    "path": path,
+                    "size": totals.bytes,
+                    "file_count": totals.files,
+                    "dir_count": totals.dirs,
+                    "unreadable": totals.errors,
+                    "max_depth": max_depth,
+                    "directories": report,
                 })
                 .to_string(),
             ))
         }
+        Ok(_) => Ok(ContentBuilder::error(format!("{} is not a directory", path))),
         Err(e) => Ok(ContentBuilder::error(format!(
             "Failed to get metadata for {}: {}",
             path, e
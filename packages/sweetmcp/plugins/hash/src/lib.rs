@@ -1,7 +1,10 @@
+use std::fs;
+use std::io::Read;
+
 use base64::Engine;
 use extism_pdk::*;
 use log::{debug, trace, warn};
-use serde_json::Value;
+use serde_json::{Value, json};
 use sha1::Sha1;
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 use sweetmcp_plugin_builder::prelude::*;
@@ -83,6 +86,178 @@ fn compute_hash(data: &str, algorithm: &str) -> Result<String, String> {
     }
 }
 
+/// Digest algorithms usable by `verify` and `multi_hash`
+const DIGEST_ALGORITHMS: &[&str] = &["sha256", "sha512", "sha384", "sha224", "sha1", "md5"];
+
+/// Incremental digest state for one algorithm
+enum DigestState {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Sha384(Sha384),
+    Sha224(Sha224),
+    Sha1(Sha1),
+    Md5(md5::Context),
+}
+
+impl DigestState {
+    fn new(algorithm: &str) -> Result<Self, String> {
+        match algorithm {
+            "sha256" => Ok(Self::Sha256(Sha256::new())),
+            "sha512" => Ok(Self::Sha512(Sha512::new())),
+            "sha384" => Ok(Self::Sha384(Sha384::new())),
+            "sha224" => Ok(Self::Sha224(Sha224::new())),
+            "sha1" => {
+                warn!("SHA-1 is cryptographically weak and deprecated, consider using SHA-256 or higher");
+                Ok(Self::Sha1(Sha1::new()))
+            }
+            "md5" => {
+                warn!("MD5 is cryptographically weak and broken, consider using SHA-256 or higher");
+                Ok(Self::Md5(md5::Context::new()))
+            }
+            _ => Err(format!(
+                "Unsupported digest algorithm: {} (expected one of {})",
+                algorithm,
+                DIGEST_ALGORITHMS.join(", ")
+            )),
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        match self {
+            Self::Sha256(h) => h.update(chunk),
+            Self::Sha512(h) => h.update(chunk),
+            Self::Sha384(h) => h.update(chunk),
+            Self::Sha224(h) => h.update(chunk),
+            Self::Sha1(h) => h.update(chunk),
+            Self::Md5(ctx) => ctx.consume(chunk),
+        }
+    }
+
+    fn finalize(self) -> String {
+        match self {
+            Self::Sha256(h) => format!("{:x}", h.finalize()),
+            Self::Sha512(h) => format!("{:x}", h.finalize()),
+            Self::Sha384(h) => format!("{:x}", h.finalize()),
+            Self::Sha224(h) => format!("{:x}", h.finalize()),
+            Self::Sha1(h) => format!("{:x}", h.finalize()),
+            Self::Md5(ctx) => format!("{:x}", ctx.compute()),
+        }
+    }
+}
+
+/// Run every algorithm over the input (`path` file or `data` string) in a single pass
+///
+/// Returns `(algorithm, hex digest)` pairs in request order plus the byte count.
+fn digest_input(args: &Value, algorithms: &[&str]) -> Result<(Vec<(String, String)>, u64), String> {
+    let mut states = algorithms
+        .iter()
+        .map(|alg| DigestState::new(alg).map(|state| (alg.to_string(), state)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut total = 0u64;
+    let mut feed = |chunk: &[u8]| {
+        total += chunk.len() as u64;
+        for (_, state) in states.iter_mut() {
+            state.update(chunk);
+        }
+    };
+
+    if let Some(path) = args.get("path").and_then(|v| v.as_str()) {
+        debug!("Digesting file {} with {:?}", path, algorithms);
+        let mut file =
+            fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = file
+                .read(&mut buf)
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            if n == 0 {
+                break;
+            }
+            feed(&buf[..n]);
+        }
+    } else if let Some(data) = args.get("data").and_then(|v| v.as_str()) {
+        debug!("Digesting {} bytes with {:?}", data.len(), algorithms);
+        feed(data.as_bytes());
+    } else {
+        return Err("data or path parameter required".to_string());
+    }
+
+    let digests = states
+        .into_iter()
+        .map(|(alg, state)| (alg, state.finalize()))
+        .collect();
+    Ok((digests, total))
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Check the input against an expected hex digest
+fn verify(args: &Value) -> Result<CallToolResult, Error> {
+    let algorithm = args
+        .get("algorithm")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::msg("algorithm parameter required for verify"))?;
+    let expected = args
+        .get("expected")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::msg("expected parameter required for verify"))?;
+
+    let (digests, bytes) = digest_input(args, &[algorithm]).map_err(Error::msg)?;
+    let actual = &digests[0].1;
+
+    // Digests are emitted lowercase; accept uppercase or padded expectations
+    let expected = expected.trim().to_ascii_lowercase();
+    let matches = constant_time_eq(actual.as_bytes(), expected.as_bytes());
+    debug!("Verification with {} {}", algorithm, if matches { "passed" } else { "failed" });
+
+    Ok(ContentBuilder::text(
+        json!({
+            "algorithm": algorithm,
+            "matches": matches,
+            "expected": expected,
+            "actual": actual,
+            "bytes": bytes,
+        })
+        .to_string(),
+    ))
+}
+
+/// Compute several digests in one pass over the input
+fn multi_hash(args: &Value) -> Result<CallToolResult, Error> {
+    let algorithms: Vec<&str> = match args.get("algorithms").and_then(|v| v.as_str()) {
+        Some(list) => list
+            .split(',')
+            .map(str::trim)
+            .filter(|alg| !alg.is_empty())
+            .collect(),
+        None => DIGEST_ALGORITHMS.to_vec(),
+    };
+    if algorithms.is_empty() {
+        return Err(Error::msg("algorithms parameter must name at least one algorithm"));
+    }
+
+    let (digests, bytes) = digest_input(args, &algorithms).map_err(Error::msg)?;
+    let digests: serde_json::Map<String, Value> = digests
+        .into_iter()
+        .map(|(alg, digest)| (alg, Value::String(digest)))
+        .collect();
+
+    Ok(ContentBuilder::text(
+        json!({
+            "digests": digests,
+            "bytes": bytes,
+        })
+        .to_string(),
+    ))
+}
+
 /// Hash tool using plugin-builder
 struct HashTool;
 
@@ -97,23 +272,51 @@ impl McpTool for HashTool {
             .when("you need to encode data in base64 format for transmission")
             .when("you need to encode data in base32 format for URLs or identifiers")
             .when("you need to verify data integrity before storage or transmission")
+            .when("you need to check a downloaded artifact against a published checksum")
+            .when("you need several digests of a large file without reading it repeatedly")
             .perfect_for("data integrity checks, password verification, API authentication, and encoding binary data for text protocols")
     }
 
     fn schema(builder: SchemaBuilder) -> Value {
         builder
-            .required_string("data", "data to convert to hash or encoded format")
-            .required_enum(
+            .optional_enum(
+                "operation",
+                "hash (default), verify against an expected digest, or multi_hash",
+                &["hash", "verify", "multi_hash"],
+            )
+            .optional_string("data", "data to convert to hash or encoded format")
+            .optional_string(
+                "path",
+                "file to digest instead of data (verify and multi_hash)",
+            )
+            .optional_enum(
                 "algorithm",
-                "algorithm to use for hashing or encoding",
+                "algorithm to use for hashing or encoding (required for hash and verify)",
                 &[
                     "sha256", "sha512", "sha384", "sha224", "sha1", "md5", "base32", "base64",
                 ],
             )
+            .optional_string("expected", "expected hex digest (required for verify)")
+            .optional_string(
+                "algorithms",
+                "comma-separated digest algorithms for multi_hash (default: all)",
+            )
             .build()
     }
 
     fn execute(args: Value) -> Result<CallToolResult, Error> {
+        match args.get("operation").and_then(|v| v.as_str()).unwrap_or("hash") {
+            "hash" => {}
+            "verify" => return verify(&args),
+            "multi_hash" => return multi_hash(&args),
+            operation => {
+                return Ok(ContentBuilder::error(format!(
+                    "Unknown hash operation: {}",
+                    operation
+                )));
+            }
+        }
+
         let data = args
             .get("data")
             .and_then(|v| v.as_str())
@@ -16,7 +16,13 @@ use hyper_rustls::HttpsConnectorBuilder;
 #[cfg(not(target_arch = "wasm32"))]
 use http_body_util::{BodyExt, Empty};
 #[cfg(not(target_arch = "wasm32"))]
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
+#[cfg(not(target_arch = "wasm32"))]
+use hickory_resolver::TokioAsyncResolver;
+#[cfg(not(target_arch = "wasm32"))]
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+#[cfg(not(target_arch = "wasm32"))]
+use hickory_resolver::proto::rr::{RData, RecordType};
 
 /// IP operations tool using plugin-builder
 struct IpTool;
@@ -33,6 +39,7 @@ impl McpTool for IpTool {
             .when("you need to create IP addresses programmatically")
             .when("you need to perform CIDR subnet calculations")
             .when("you need to analyze network ranges and memberships")
+            .when("you need to resolve DNS records or check whether a host's port is reachable")
             .perfect_for("network administration, security analysis, subnet planning, and IP address management")
             .operation("get_public_ip", "Get the public IP address of the current system")
             .operation("validate_ip", "Validate if a string is a proper IP address and determine its type")
@@ -42,6 +49,8 @@ impl McpTool for IpTool {
             .operation("create_ipv4", "Create IPv4 address from octets and analyze properties")
             .operation("create_ipv6", "Create IPv6 address from segments and analyze properties")
             .operation("cidr_contains", "Check if an IP address is within a CIDR range")
+            .operation("resolve", "Look up A/AAAA/TXT/MX records with per-record TTLs, optionally via a custom resolver")
+            .operation("check_port", "Test TCP reachability of host:port with a timeout, optionally reporting TLS handshake details")
     }
 
     fn schema(builder: SchemaBuilder) -> Value {
@@ -55,6 +64,8 @@ impl McpTool for IpTool {
             "create_ipv4",
             "create_ipv6",
             "cidr_contains",
+            "resolve",
+            "check_port",
         ];
         
         #[cfg(target_arch = "wasm32")]
@@ -79,6 +90,19 @@ impl McpTool for IpTool {
                 "cidr",
                 "CIDR notation for subnet operations (e.g., '192.168.1.0/24')",
             )
+            .optional_string("host", "Hostname to resolve or connect to (resolve, check_port)")
+            .optional_enum(
+                "record_type",
+                "DNS record type for resolve (default: A)",
+                &["A", "AAAA", "TXT", "MX"],
+            )
+            .optional_string(
+                "resolver",
+                "Nameserver to query instead of the system resolver (e.g., '1.1.1.1' or '9.9.9.9:53')",
+            )
+            .optional_number("port", "TCP port for check_port")
+            .optional_number("timeout_ms", "Connect timeout for check_port in milliseconds (default 5000)")
+            .optional_bool("tls", "Perform a TLS handshake after connecting and report its details")
             .build()
     }
 
@@ -102,6 +126,10 @@ impl McpTool for IpTool {
             "create_ipv4" => create_ipv4(args_map),
             "create_ipv6" => create_ipv6(args_map),
             "cidr_contains" => cidr_contains(args_map),
+            #[cfg(not(target_arch = "wasm32"))]
+            "resolve" => resolve(args_map),
+            #[cfg(not(target_arch = "wasm32"))]
+            "check_port" => check_port(args_map),
             _ => {
                 debug!("Unknown IP operation requested: {}", name);
                 Ok(ContentBuilder::error(format!(
@@ -194,6 +222,230 @@ fn get_public_ip() -> Result<CallToolResult, Error> {
     Err(Error::msg("All public IP services failed"))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+/// Build a resolver for the system configuration or an explicit nameserver
+fn build_resolver(resolver: Option<&str>) -> Result<TokioAsyncResolver, Error> {
+    match resolver {
+        None => TokioAsyncResolver::tokio_from_system_conf()
+            .map_err(|e| Error::msg(format!("Failed to load system resolver config: {}", e))),
+        Some(server) => {
+            let (ip, port) = match server.parse::<std::net::SocketAddr>() {
+                Ok(addr) => (addr.ip(), addr.port()),
+                Err(_) => (
+                    server
+                        .parse::<IpAddr>()
+                        .map_err(|e| Error::msg(format!("Invalid resolver '{}': {}", server, e)))?,
+                    53,
+                ),
+            };
+            let nameservers = NameServerConfigGroup::from_ips_clear(&[ip], port, true);
+            Ok(TokioAsyncResolver::tokio(
+                ResolverConfig::from_parts(None, vec![], nameservers),
+                ResolverOpts::default(),
+            ))
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Render record data as JSON
+fn rdata_to_json(rdata: &RData) -> Value {
+    match rdata {
+        RData::A(a) => json!(a.to_string()),
+        RData::AAAA(aaaa) => json!(aaaa.to_string()),
+        RData::TXT(txt) => json!(
+            txt.iter()
+                .map(|part| String::from_utf8_lossy(part).into_owned())
+                .collect::<String>()
+        ),
+        RData::MX(mx) => json!({
+            "preference": mx.preference(),
+            "exchange": mx.exchange().to_string()
+        }),
+        other => json!(other.to_string()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Resolve DNS records for a host
+fn resolve(args: serde_json::Map<String, Value>) -> Result<CallToolResult, Error> {
+    let host = args
+        .get("host")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::msg("host parameter required for resolve"))?;
+
+    let record_type = match args
+        .get("record_type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("A")
+        .to_uppercase()
+        .as_str()
+    {
+        "A" => RecordType::A,
+        "AAAA" => RecordType::AAAA,
+        "TXT" => RecordType::TXT,
+        "MX" => RecordType::MX,
+        other => {
+            return Ok(ContentBuilder::error(format!(
+                "Unsupported record type: {}",
+                other
+            )));
+        }
+    };
+    let resolver_addr = args.get("resolver").and_then(|v| v.as_str());
+
+    debug!("Resolving {} {:?} via {}", host, record_type, resolver_addr.unwrap_or("system resolver"));
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| Error::msg(format!("Tokio runtime failed: {}", e)))?;
+
+    rt.block_on(async {
+        let resolver = build_resolver(resolver_addr)?;
+
+        match resolver.lookup(host, record_type).await {
+            Ok(lookup) => {
+                let records: Vec<Value> = lookup
+                    .record_iter()
+                    .filter(|record| record.record_type() == record_type)
+                    .filter_map(|record| {
+                        record.data().map(|data| {
+                            json!({
+                                "name": record.name().to_string(),
+                                "type": record.record_type().to_string(),
+                                "ttl": record.ttl(),
+                                "value": rdata_to_json(data)
+                            })
+                        })
+                    })
+                    .collect();
+                trace!("Resolved {} records for {}", records.len(), host);
+
+                Ok(ContentBuilder::text(
+                    json!({
+                        "host": host,
+                        "record_type": record_type.to_string(),
+                        "resolver": resolver_addr.unwrap_or("system"),
+                        "records": records,
+                        "count": records.len()
+                    })
+                    .to_string(),
+                ))
+            }
+            Err(e) => {
+                debug!("DNS lookup failed for {}: {}", host, e);
+                Ok(ContentBuilder::error(format!(
+                    "DNS lookup for {} {} failed: {}",
+                    host, record_type, e
+                )))
+            }
+        }
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Perform a TLS handshake on an open connection and describe the session
+async fn tls_handshake(stream: tokio::net::TcpStream, host: &str, budget: Duration) -> Value {
+    use std::sync::Arc;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = match rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+    {
+        Ok(builder) => builder
+            .with_root_certificates(rustls::RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.into(),
+            })
+            .with_no_client_auth(),
+        Err(e) => return json!({ "ok": false, "error": format!("TLS config failed: {}", e) }),
+    };
+
+    let server_name = match rustls::pki_types::ServerName::try_from(host.to_string()) {
+        Ok(name) => name,
+        Err(e) => return json!({ "ok": false, "error": format!("Invalid TLS server name: {}", e) }),
+    };
+
+    let started = Instant::now();
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    match timeout(budget, connector.connect(server_name, stream)).await {
+        Ok(Ok(tls)) => {
+            let (_, session) = tls.get_ref();
+            json!({
+                "ok": true,
+                "handshake_ms": started.elapsed().as_millis() as u64,
+                "protocol_version": session.protocol_version().map(|v| format!("{:?}", v)),
+                "cipher_suite": session.negotiated_cipher_suite().map(|cs| format!("{:?}", cs.suite())),
+                "alpn": session.alpn_protocol().map(|p| String::from_utf8_lossy(p).into_owned()),
+                "peer_certificates": session.peer_certificates().map_or(0, |certs| certs.len())
+            })
+        }
+        Ok(Err(e)) => json!({ "ok": false, "error": e.to_string() }),
+        Err(_) => json!({ "ok": false, "error": format!("TLS handshake timed out after {}ms", budget.as_millis()) }),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Check TCP reachability of host:port
+fn check_port(args: serde_json::Map<String, Value>) -> Result<CallToolResult, Error> {
+    let host = args
+        .get("host")
+        .or_else(|| args.get("ip"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::msg("host parameter required for check_port"))?;
+
+    let port = args
+        .get("port")
+        .and_then(|v| v.as_u64())
+        .and_then(|p| u16::try_from(p).ok())
+        .ok_or_else(|| Error::msg("port parameter (0-65535) required for check_port"))?;
+
+    let timeout_ms = args.get("timeout_ms").and_then(|v| v.as_u64()).unwrap_or(5000);
+    let tls = args.get("tls").and_then(|v| v.as_bool()).unwrap_or(false);
+    let budget = Duration::from_millis(timeout_ms);
+
+    debug!("Checking reachability of {}:{} (timeout {}ms, tls {})", host, port, timeout_ms, tls);
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| Error::msg(format!("Tokio runtime failed: {}", e)))?;
+
+    let result = rt.block_on(async {
+        let started = Instant::now();
+        match timeout(budget, tokio::net::TcpStream::connect((host, port))).await {
+            Ok(Ok(stream)) => {
+                let connect_ms = started.elapsed().as_millis() as u64;
+                let peer = stream.peer_addr().ok().map(|addr| addr.to_string());
+                let tls_detail = if tls {
+                    Some(tls_handshake(stream, host, budget).await)
+                } else {
+                    None
+                };
+                json!({
+                    "host": host,
+                    "port": port,
+                    "reachable": true,
+                    "connect_ms": connect_ms,
+                    "peer_addr": peer,
+                    "tls": tls_detail
+                })
+            }
+            Ok(Err(e)) => json!({
+                "host": host,
+                "port": port,
+                "reachable": false,
+                "error": e.to_string()
+            }),
+            Err(_) => json!({
+                "host": host,
+                "port": port,
+                "reachable": false,
+                "error": format!("Connection timed out after {}ms", timeout_ms)
+            }),
+        }
+    });
+
+    trace!("check_port result: {}", result);
+    Ok(ContentBuilder::text(result.to_string()))
+}
+
 /// Validate IP address format
 fn validate_ip(args: serde_json::Map<String, Value>) -> Result<CallToolResult, Error> {
     let ip_str = args
@@ -1,4 +1,3 @@
-use base64::Engine;
 use extism_pdk::*;
 use log::{debug, trace};
 use qrcode_png::{Color, QrCode, QrCodeEcc};
@@ -55,19 +54,9 @@ impl McpTool for QrCodeTool {
         debug!("Error correction level: {:?}", ecc);
 
         match generate_qr_code(data, ecc) {
-            Ok(base64_data) => {
-                debug!("QR code generation successful, encoded as base64");
-                use sweetmcp_plugin_builder::{CallToolResult, Content, ContentType};
-                Ok(CallToolResult {
-                    is_error: None,
-                    content: vec![Content {
-                        annotations: None,
-                        text: None,
-                        mime_type: Some("image/png".into()),
-                        r#type: ContentType::Image,
-                        data: Some(base64_data),
-                    }],
-                })
+            Ok(png_bytes) => {
+                debug!("QR code generation successful");
+                Ok(ContentBuilder::image(&png_bytes, "image/png"))
             }
             Err(e) => Ok(ContentBuilder::error(&format!(
                 "Failed to generate QR code: {}",
@@ -77,8 +66,8 @@ impl McpTool for QrCodeTool {
     }
 }
 
-/// Generate QR code and return base64 encoded PNG
-fn generate_qr_code(data: &str, ecc: QrCodeEcc) -> Result<String, Box<dyn std::error::Error>> {
+/// Generate QR code as PNG bytes
+fn generate_qr_code(data: &str, ecc: QrCodeEcc) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     trace!("Encoding data into QR code structure");
     let mut code = QrCode::new(data, ecc)?;
     debug!("QR code matrix created");
@@ -91,12 +80,8 @@ fn generate_qr_code(data: &str, ecc: QrCodeEcc) -> Result<String, Box<dyn std::e
     trace!("Generating PNG image with grayscale colors");
     let png_bytes = code.generate(Color::Grayscale(0, 255))?;
     debug!("PNG generation complete: {} bytes", png_bytes.len());
-    
-    trace!("Encoding PNG to base64");
-    let base64_data = base64::engine::general_purpose::STANDARD.encode(png_bytes);
-    trace!("Base64 encoding complete: {} chars", base64_data.len());
 
-    Ok(base64_data)
+    Ok(png_bytes)
 }
 
 /// Convert numeric ECC level to QrCodeEcc enum
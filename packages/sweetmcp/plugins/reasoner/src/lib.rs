@@ -1,10 +1,10 @@
 // Import Extism PDK for plugin development
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use std::sync::OnceLock;
 
 use extism_pdk::*;
-use log::{debug, info};
+use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -40,6 +40,114 @@ pub struct ReasoningRequest {
     pub beam_width: Option<usize>, // Number of top paths to maintain (n-sampling)
     #[serde(rename = "numSimulations")]
     pub num_simulations: Option<usize>, // Number of MCTS simulations to run
+    #[serde(rename = "weightPreset")]
+    pub weight_preset: Option<String>, // Named scoring weights to use
+    #[serde(rename = "scoringWeights")]
+    pub scoring_weights: Option<WeightOverrides>, // Adjustments on top of the preset
+}
+
+/// Relative weights of the components of a thought's score
+///
+/// Weights are normalized to sum to 1 before use.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoringWeights {
+    pub logical: f64,   // Logical connectors and expressions
+    pub coherence: f64, // Terms shared with the parent thought
+    pub depth: f64,     // Preference for shallow thoughts
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            logical: 0.4,
+            coherence: 0.4,
+            depth: 0.2,
+        }
+    }
+}
+
+impl ScoringWeights {
+    /// The weights scaled to sum to 1, or an error if they can't be
+    fn normalized(self) -> Result<Self, String> {
+        let weights = [self.logical, self.coherence, self.depth];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(format!("Scoring weights must be non-negative numbers: {:?}", self));
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err("Scoring weights must not all be zero".to_string());
+        }
+        Ok(Self {
+            logical: self.logical / total,
+            coherence: self.coherence / total,
+            depth: self.depth / total,
+        })
+    }
+
+    /// Each weight relative to its default, for strategies that scale
+    /// their own score components
+    fn relative_to_default(self) -> Self {
+        let default = Self::default();
+        Self {
+            logical: self.logical / default.logical,
+            coherence: self.coherence / default.coherence,
+            depth: self.depth / default.depth,
+        }
+    }
+}
+
+/// Per-request changes to individual scoring weights
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeightOverrides {
+    pub logical: Option<f64>,
+    pub coherence: Option<f64>,
+    pub depth: Option<f64>,
+}
+
+impl WeightOverrides {
+    fn apply(&self, weights: ScoringWeights) -> ScoringWeights {
+        ScoringWeights {
+            logical: self.logical.unwrap_or(weights.logical),
+            coherence: self.coherence.unwrap_or(weights.coherence),
+            depth: self.depth.unwrap_or(weights.depth),
+        }
+    }
+}
+
+/// Preset used when a request names none
+const DEFAULT_PRESET: &str = "balanced";
+
+/// Presets available without configuration
+fn builtin_presets() -> HashMap<String, ScoringWeights> {
+    HashMap::from([
+        (DEFAULT_PRESET.to_string(), ScoringWeights::default()),
+        // Loosely tied to the parent, rewarding exploration
+        (
+            "creative".to_string(),
+            ScoringWeights {
+                logical: 0.25,
+                coherence: 0.15,
+                depth: 0.6,
+            },
+        ),
+        // Demands explicit reasoning that follows from the parent
+        (
+            "rigorous".to_string(),
+            ScoringWeights {
+                logical: 0.55,
+                coherence: 0.4,
+                depth: 0.05,
+            },
+        ),
+    ])
+}
+
+/// Input of the `register_preset` plugin function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetRegistration {
+    pub name: String,
+    pub weights: ScoringWeights,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +167,26 @@ pub struct ReasoningResponse {
     pub best_score: Option<f64>,
     #[serde(rename = "strategyUsed")]
     pub strategy_used: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ThoughtWarning>, // Loops and contradictions with ancestors
+    #[serde(rename = "scoringWeights")]
+    pub scoring_weights: Option<ScoringWeights>, // Normalized weights the score used
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    Loop,          // Restates an ancestor
+    Contradiction, // Negates an ancestor
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThoughtWarning {
+    pub kind: WarningKind,
+    #[serde(rename = "nodeId")]
+    pub node_id: String, // Ancestor the thought loops back to or contradicts
+    pub similarity: f64, // Jaccard similarity of the two thoughts' terms
+    pub message: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,12 +234,14 @@ trait WasmStrategy: Send + Sync {
 // Beam Search WASM Strategy
 struct BeamSearchWasm {
     beam_width: usize,
+    weights: ScoringWeights,
 }
 
 impl BeamSearchWasm {
-    fn new(beam_width: Option<usize>) -> Self {
+    fn new(beam_width: Option<usize>, weights: ScoringWeights) -> Self {
         Self {
             beam_width: beam_width.unwrap_or(3),
+            weights,
         }
     }
     
@@ -140,8 +270,6 @@ impl BeamSearchWasm {
     }
     
     fn coherence_score(&self, thought: &str, parent_thought: &str) -> f64 {
-        use std::collections::HashSet;
-        
         let parent_terms: HashSet<String> = parent_thought
             .to_lowercase()
             .split(|c: char| !c.is_alphanumeric())
@@ -187,8 +315,11 @@ impl WasmStrategy for BeamSearchWasm {
             .unwrap_or(0.5);
         let depth_penalty = (1.0 - (depth as f64 / 10.0) * 0.2).max(0.0);
         
-        // Beam search favors logical structure and coherence
-        (logical * 0.4 + coherence * 0.4 + depth_penalty * 0.2).min(1.0)
+        // Beam search favors logical structure and coherence by default
+        (logical * self.weights.logical
+            + coherence * self.weights.coherence
+            + depth_penalty * self.weights.depth)
+            .min(1.0)
     }
 }
 
@@ -196,13 +327,15 @@ impl WasmStrategy for BeamSearchWasm {
 struct MCTSWasm {
     num_simulations: usize,
     exploration_constant: f64,
+    scale: ScoringWeights, // Weights relative to the defaults
 }
 
 impl MCTSWasm {
-    fn new(num_simulations: Option<usize>) -> Self {
+    fn new(num_simulations: Option<usize>, weights: ScoringWeights) -> Self {
         Self {
             num_simulations: num_simulations.unwrap_or(50),
             exploration_constant: 1.414, // sqrt(2)
+            scale: weights.relative_to_default(),
         }
     }
 }
@@ -236,7 +369,7 @@ impl WasmStrategy for MCTSWasm {
             } else {
                 0.1
             };
-            length_score + logical_score + coherence
+            length_score + logical_score * self.scale.logical + coherence * self.scale.coherence
         };
         
         // Exploration: UCB1 formula
@@ -250,7 +383,7 @@ impl WasmStrategy for MCTSWasm {
         };
         
         // Depth penalty
-        let depth_penalty = (1.0 - (depth as f64 / 10.0) * 0.15).max(0.0);
+        let depth_penalty = (1.0 - (depth as f64 / 10.0) * 0.15 * self.scale.depth).max(0.0);
         
         ((quality + exploration_bonus * 0.3) * depth_penalty).min(1.0)
     }
@@ -262,9 +395,9 @@ struct MCTS002AlphaWasm {
 }
 
 impl MCTS002AlphaWasm {
-    fn new(num_simulations: Option<usize>) -> Self {
+    fn new(num_simulations: Option<usize>, weights: ScoringWeights) -> Self {
         Self {
-            base: MCTSWasm::new(num_simulations),
+            base: MCTSWasm::new(num_simulations, weights),
         }
     }
 }
@@ -294,9 +427,9 @@ struct MCTS002AltAlphaWasm {
 }
 
 impl MCTS002AltAlphaWasm {
-    fn new(num_simulations: Option<usize>) -> Self {
+    fn new(num_simulations: Option<usize>, weights: ScoringWeights) -> Self {
         Self {
-            base: MCTSWasm::new(num_simulations),
+            base: MCTSWasm::new(num_simulations, weights),
         }
     }
 }
@@ -320,32 +453,180 @@ impl WasmStrategy for MCTS002AltAlphaWasm {
     }
 }
 
+// Thought analysis: flags thoughts that restate or negate an ancestor so the
+// driving agent can prune degenerate branches
+
+/// Jaccard similarity at which a thought restates an ancestor
+const LOOP_SIMILARITY: f64 = 0.75;
+
+/// Jaccard similarity at which a thought with the opposite polarity negates
+/// an ancestor
+const CONTRADICTION_SIMILARITY: f64 = 0.6;
+
+/// Thoughts with fewer content terms are too short to compare reliably
+const MIN_ANALYSIS_TERMS: usize = 3;
+
+/// Words carrying no content of their own
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "of", "to", "in", "on", "at", "for", "with", "by", "it", "its",
+    "this", "that", "these", "those", "we", "i", "so", "then", "therefore", "thus", "hence",
+    "also", "because", "since", "be", "been",
+];
+
+/// Content terms of a thought and whether it is negated
+///
+/// Negations ("not", "never", "isn't", "false", ...) are removed from the
+/// terms and counted instead; an odd count makes the thought negated, so
+/// "x is not y" and "x isn't y" compare equal and both oppose "x is y".
+fn analyze_terms(thought: &str) -> (HashSet<String>, bool) {
+    let mut terms = HashSet::new();
+    let mut negations = 0;
+    let lower = thought.to_lowercase().replace('\u{2019}', "'");
+    for word in lower
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+    {
+        let term = match word {
+            "not" | "no" | "never" | "none" | "neither" | "nor" | "nothing" => {
+                negations += 1;
+                continue;
+            }
+            "cannot" | "can't" => "can",
+            "won't" => "will",
+            "false" => "true",
+            "incorrect" => "correct",
+            "invalid" => "valid",
+            "impossible" => "possible",
+            _ => match word.strip_suffix("n't") {
+                Some(stem) => stem,
+                None => {
+                    terms.insert(word.to_string());
+                    continue;
+                }
+            },
+        };
+        negations += 1;
+        terms.insert(term.to_string());
+    }
+    terms.retain(|term| !STOPWORDS.contains(&term.as_str()));
+    (terms, negations % 2 == 1)
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Compare a thought with its ancestors, nearest first
+fn analyze_thought(thought: &str, ancestors: &[&ThoughtNode]) -> Vec<ThoughtWarning> {
+    let (terms, negated) = analyze_terms(thought);
+    if terms.len() < MIN_ANALYSIS_TERMS {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    for ancestor in ancestors {
+        let (ancestor_terms, ancestor_negated) = analyze_terms(&ancestor.thought);
+        if ancestor_terms.len() < MIN_ANALYSIS_TERMS {
+            continue;
+        }
+        let similarity = jaccard(&terms, &ancestor_terms);
+
+        let warning = if negated != ancestor_negated && similarity >= CONTRADICTION_SIMILARITY {
+            Some((
+                WarningKind::Contradiction,
+                format!("Contradicts thought at depth {}: \"{}\"", ancestor.depth, ancestor.thought),
+            ))
+        } else if negated == ancestor_negated && similarity >= LOOP_SIMILARITY {
+            Some((
+                WarningKind::Loop,
+                format!("Restates thought at depth {}: \"{}\"", ancestor.depth, ancestor.thought),
+            ))
+        } else {
+            None
+        };
+
+        if let Some((kind, message)) = warning {
+            warnings.push(ThoughtWarning {
+                kind,
+                node_id: ancestor.id.clone(),
+                similarity,
+                message,
+            });
+        }
+    }
+    warnings
+}
+
 // Strategy factory function
-fn create_strategy(request: &ReasoningRequest) -> Box<dyn WasmStrategy> {
+fn create_strategy(request: &ReasoningRequest, weights: ScoringWeights) -> Box<dyn WasmStrategy> {
     let strategy_type = request.strategy_type.as_deref().unwrap_or("beam_search");
     
     match strategy_type {
-        "beam_search" => Box::new(BeamSearchWasm::new(request.beam_width)),
-        "mcts" => Box::new(MCTSWasm::new(request.num_simulations)),
-        "mcts_002_alpha" => Box::new(MCTS002AlphaWasm::new(request.num_simulations)),
-        "mcts_002alt_alpha" => Box::new(MCTS002AltAlphaWasm::new(request.num_simulations)),
-        _ => Box::new(BeamSearchWasm::new(request.beam_width)), // Default fallback
+        "beam_search" => Box::new(BeamSearchWasm::new(request.beam_width, weights)),
+        "mcts" => Box::new(MCTSWasm::new(request.num_simulations, weights)),
+        "mcts_002_alpha" => Box::new(MCTS002AlphaWasm::new(request.num_simulations, weights)),
+        "mcts_002alt_alpha" => Box::new(MCTS002AltAlphaWasm::new(request.num_simulations, weights)),
+        _ => Box::new(BeamSearchWasm::new(request.beam_width, weights)), // Default fallback
     }
 }
 
 // WASM-compatible reasoner with strategy pattern implementation
 pub struct SimpleReasoner {
     nodes: HashMap<String, ThoughtNode>,
+    presets: HashMap<String, ScoringWeights>, // Normalized, by name
+    default_preset: String,
 }
 
 impl SimpleReasoner {
     pub fn new() -> Self {
         Self {
             nodes: HashMap::new(),
+            presets: builtin_presets(),
+            default_preset: DEFAULT_PRESET.to_string(),
+        }
+    }
+
+    /// Add or replace a named weight preset
+    pub fn register_preset(&mut self, name: &str, weights: ScoringWeights) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Preset name must not be empty".to_string());
+        }
+        let weights = weights.normalized()?;
+        info!("Registered scoring preset '{}': {:?}", name, weights);
+        self.presets.insert(name.to_string(), weights);
+        Ok(())
+    }
+
+    /// Use a registered preset for requests that name none
+    pub fn set_default_preset(&mut self, name: &str) -> Result<(), String> {
+        if !self.presets.contains_key(name) {
+            return Err(format!("Unknown scoring preset '{}'", name));
+        }
+        self.default_preset = name.to_string();
+        Ok(())
+    }
+
+    /// Weights for a request: its preset, then its individual overrides
+    fn scoring_weights(&self, request: &ReasoningRequest) -> Result<ScoringWeights, String> {
+        let name = request.weight_preset.as_deref().unwrap_or(&self.default_preset);
+        let preset = self.presets.get(name).copied().ok_or_else(|| {
+            let mut known: Vec<&str> = self.presets.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            format!("Unknown scoring preset '{}' (known: {})", name, known.join(", "))
+        })?;
+        match &request.scoring_weights {
+            Some(overrides) => overrides.apply(preset).normalized(),
+            None => Ok(preset),
         }
     }
 
-    pub fn process_thought(&mut self, request: ReasoningRequest) -> ReasoningResponse {
+    pub fn process_thought(&mut self, request: ReasoningRequest) -> Result<ReasoningResponse, String> {
         let node_id = Uuid::new_v4().to_string();
         
         info!(
@@ -355,7 +636,8 @@ impl SimpleReasoner {
         );
         
         // Create strategy based on request
-        let strategy = create_strategy(&request);
+        let weights = self.scoring_weights(&request)?;
+        let strategy = create_strategy(&request, weights);
         debug!(
             "Using strategy: {} for thought processing", 
             strategy.name()
@@ -377,6 +659,12 @@ impl SimpleReasoner {
             &request,
         );
         debug!("Thought score: {:.3}", score);
+
+        // Check the thought against its ancestors for loops and contradictions
+        let warnings = analyze_thought(&request.thought, &self.ancestors(request.parent_id.as_deref()));
+        for warning in &warnings {
+            info!("Thought warning ({:?}): {}", warning.kind, warning.message);
+        }
         
         // Create the node
         let node = ThoughtNode {
@@ -407,7 +695,7 @@ impl SimpleReasoner {
             !request.next_thought_needed
         );
         
-        ReasoningResponse {
+        Ok(ReasoningResponse {
             node_id,
             thought: request.thought,
             score,
@@ -417,7 +705,24 @@ impl SimpleReasoner {
             possible_paths: Some(1),
             best_score: Some(score),
             strategy_used: Some(strategy.name().to_string()),
+            warnings,
+            scoring_weights: Some(weights),
+        })
+    }
+
+    /// The node with `parent_id` and its ancestors, nearest first
+    fn ancestors(&self, parent_id: Option<&str>) -> Vec<&ThoughtNode> {
+        let mut ancestors = Vec::new();
+        let mut current = parent_id;
+        while let Some(node) = current.and_then(|id| self.nodes.get(id)) {
+            // Guard against a malformed parent chain
+            if ancestors.len() > self.nodes.len() {
+                break;
+            }
+            ancestors.push(node);
+            current = node.parent_id.as_deref();
         }
+        ancestors
     }
 
     pub fn get_stats(&self, strategy_types: Vec<&str>) -> ReasoningStats {
@@ -477,7 +782,37 @@ impl SimpleReasoner {
 static REASONER: OnceLock<Mutex<SimpleReasoner>> = OnceLock::new();
 
 fn get_reasoner() -> &'static Mutex<SimpleReasoner> {
-    REASONER.get_or_init(|| Mutex::new(SimpleReasoner::new()))
+    REASONER.get_or_init(|| {
+        let mut reasoner = SimpleReasoner::new();
+        load_scoring_config(&mut reasoner);
+        Mutex::new(reasoner)
+    })
+}
+
+/// Apply the plugin config's scoring settings
+///
+/// `weight_presets` is a JSON object of preset name to weights, and
+/// `default_preset` names the preset used when a request names none.
+/// Invalid settings are logged and skipped.
+fn load_scoring_config(reasoner: &mut SimpleReasoner) {
+    if let Ok(Some(presets)) = config::get("weight_presets") {
+        match serde_json::from_str::<HashMap<String, ScoringWeights>>(&presets) {
+            Ok(presets) => {
+                for (name, weights) in presets {
+                    if let Err(e) = reasoner.register_preset(&name, weights) {
+                        warn!("Ignoring weight preset '{}' from config: {}", name, e);
+                    }
+                }
+            }
+            Err(e) => warn!("Ignoring invalid weight_presets config: {}", e),
+        }
+    }
+
+    if let Ok(Some(name)) = config::get("default_preset") {
+        if let Err(e) = reasoner.set_default_preset(name.trim()) {
+            warn!("Ignoring default_preset config: {}", e);
+        }
+    }
 }
 
 // Extism plugin exports
@@ -496,6 +831,10 @@ struct EnhancedResponse {
     score: f64,
     #[serde(rename = "strategyUsed")]
     strategy_used: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<ThoughtWarning>,
+    #[serde(rename = "scoringWeights")]
+    scoring_weights: Option<ScoringWeights>,
     stats: ReasoningStats,
 }
 
@@ -521,6 +860,19 @@ pub fn process_thought(input: String) -> FnResult<String> {
             .to_string());
         }
     };
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            return Ok(serde_json::json!({
+                "is_error": true,
+                "content": [{
+                    "type": "text",
+                    "text": e
+                }]
+            })
+            .to_string());
+        }
+    };
 
     // Get stats for the used strategy
     let strategy = response
@@ -550,6 +902,8 @@ pub fn process_thought(input: String) -> FnResult<String> {
         node_id: response.node_id,
         score: response.score,
         strategy_used: strategy,
+        warnings: response.warnings,
+        scoring_weights: response.scoring_weights,
         stats,
     };
 
@@ -575,6 +929,26 @@ pub fn clear(_: String) -> FnResult<String> {
     Ok("Reasoner state cleared".to_string())
 }
 
+#[plugin_fn]
+pub fn register_preset(input: String) -> FnResult<String> {
+    let registration: PresetRegistration = serde_json::from_str(&input)?;
+
+    let reasoner = get_reasoner();
+    let registered = match reasoner.lock() {
+        Ok(mut reasoner) => reasoner.register_preset(&registration.name, registration.weights),
+        Err(e) => {
+            return Err(extism_pdk::Error::msg(format!(
+                "Failed to lock reasoner for preset registration: {}",
+                e
+            ))
+            .into());
+        }
+    };
+    registered.map_err(extism_pdk::Error::msg)?;
+
+    Ok(format!("Scoring preset '{}' registered", registration.name.trim()))
+}
+
 // Plugin manifest for tool definition
 #[plugin_fn]
 pub fn manifest(_: String) -> FnResult<String> {
@@ -594,6 +968,18 @@ pub fn manifest(_: String) -> FnResult<String> {
                     "description": "Enhanced reasoning response",
                 }]
             },
+            {
+                "name": "register_preset",
+                "description": "Register a named set of scoring weights",
+                "inputs": [{
+                    "name": "registration",
+                    "description": "Preset name and its logical, coherence and depth weights",
+                }],
+                "outputs": [{
+                    "name": "message",
+                    "description": "Status message",
+                }]
+            },
             {
                 "name": "clear",
                 "description": "Clear the reasoner state",
@@ -644,6 +1030,20 @@ pub fn manifest(_: String) -> FnResult<String> {
                     "description": "Number of MCTS simulations to run. Defaults if null",
                     "minimum": 1,
                     "maximum": 150
+                },
+                "weightPreset": {
+                    "type": ["string", "null"],
+                    "description": "Named scoring weights: balanced, creative, rigorous or a registered preset. Defaults if null"
+                },
+                "scoringWeights": {
+                    "type": ["object", "null"],
+                    "description": "Weights overriding the preset's; normalized to sum to 1",
+                    "properties": {
+                        "logical": { "type": "number", "minimum": 0 },
+                        "coherence": { "type": "number", "minimum": 0 },
+                        "depth": { "type": "number", "minimum": 0 }
+                    },
+                    "additionalProperties": false
                 }
             },
             "required": [
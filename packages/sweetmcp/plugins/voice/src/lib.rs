@@ -0,0 +1,259 @@
+use extism_pdk::*;
+use log::debug;
+use serde_json::Value;
+use sweetmcp_plugin_builder::prelude::*;
+use sweetmcp_plugin_builder::{CallToolResult, Ready};
+use sweetmcp_voice_tools::{
+    ListenParams, SpeakParams, SynthesizeParams, TranscribeFileParams, VoiceRequest, VoiceResponse,
+};
+
+// Declare host function import; the gateway answers with the audio devices
+// of the machine it runs on
+#[host_fn]
+extern "ExtismHost" {
+    fn voice_request(input: String) -> String;
+}
+
+/// Send a request to the host's voice service
+fn request(request: VoiceRequest) -> Result<VoiceResponse, Error> {
+    let request_json = serde_json::to_string(&request)
+        .map_err(|e| Error::msg(format!("Request serialization failed: {}", e)))?;
+
+    let response_json = unsafe { voice_request(request_json)? };
+
+    serde_json::from_str(&response_json)
+        .map_err(|e| Error::msg(format!("Response parsing failed: {}", e)))
+}
+
+/// Tool result for a response other than the one expected
+fn unexpected(response: VoiceResponse) -> CallToolResult {
+    match response {
+        VoiceResponse::Error { code, message } => {
+            debug!("Voice service error {}: {}", code, message);
+            ContentBuilder::error(format!("Voice service error ({}): {}", code, message))
+        }
+        other => ContentBuilder::error(format!("Unexpected voice service response: {:?}", other)),
+    }
+}
+
+/// Text-to-speech tool
+struct SpeakTool;
+
+impl McpTool for SpeakTool {
+    const NAME: &'static str = "speak";
+
+    fn description(builder: DescriptionBuilder) -> DescriptionBuilder {
+        builder
+            .does("Convert text to speech and play it through the system audio")
+            .when("you need to speak a response out loud")
+            .when("you need to read content to the user")
+            .when("you need to give audio feedback")
+            .perfect_for("voice assistants and hands-free interactions")
+    }
+
+    fn schema(builder: SchemaBuilder) -> Value {
+        builder
+            .required_string("text", "Text to convert to speech")
+            .optional_string(
+                "voice_id",
+                "Voice ID to use (optional, defaults to system voice)",
+            )
+            .optional_number("speed", "Speech speed (0.5-2.0, default 1.0)")
+            .optional_number("pitch", "Pitch shift in semitones (-12 to 12, default 0)")
+            .optional_number("volume", "Volume as a gain (0.0-2.0, default 1.0)")
+            .optional_bool(
+                "ssml",
+                "Treat text as SSML with <break>, <prosody>, <say-as> and <emphasis>",
+            )
+            .build()
+    }
+
+    fn execute(args: Value) -> Result<CallToolResult, Error> {
+        let params: SpeakParams = serde_json::from_value(args)
+            .map_err(|e| Error::msg(format!("Invalid speak arguments: {}", e)))?;
+
+        debug!("Speaking {} characters", params.text.len());
+        match request(VoiceRequest::Speak(params))? {
+            VoiceResponse::SpeakComplete => Ok(ContentBuilder::text("Finished speaking")),
+            other => Ok(unexpected(other)),
+        }
+    }
+}
+
+/// Speech-to-text tool
+struct ListenTool;
+
+impl McpTool for ListenTool {
+    const NAME: &'static str = "listen";
+
+    fn description(builder: DescriptionBuilder) -> DescriptionBuilder {
+        builder
+            .does("Listen to audio from the microphone and transcribe it to text")
+            .when("you need to hear what the user is saying")
+            .when("you need to capture voice commands")
+            .when("you need to wait for a wake word before acting")
+            .perfect_for("voice-based interactions and hands-free activation")
+            .perfect_for("meeting transcripts with speakers and word timings")
+    }
+
+    fn schema(builder: SchemaBuilder) -> Value {
+        builder
+            .required_string(
+                "microphone_id",
+                "Microphone device to use (e.g., 'default', 'USB Microphone')",
+            )
+            .required_integer("duration_seconds", "How long to listen in seconds (1-300)")
+            .optional_string(
+                "wake_word",
+                "Optional wake word to listen for (e.g., 'hey assistant')",
+            )
+            .optional_bool(
+                "diarize",
+                "Split the transcript into timed segments labelled by speaker",
+            )
+            .optional_bool(
+                "detect_language",
+                "Identify the spoken language and report a confidence for it",
+            )
+            .optional_bool("word_timestamps", "Report when each word was spoken")
+            .build()
+    }
+
+    fn execute(args: Value) -> Result<CallToolResult, Error> {
+        let params: ListenParams = serde_json::from_value(args)
+            .map_err(|e| Error::msg(format!("Invalid listen arguments: {}", e)))?;
+
+        debug!(
+            "Listening on {} for {}s",
+            params.microphone_id, params.duration_seconds
+        );
+        match request(VoiceRequest::Listen(params))? {
+            VoiceResponse::ListenResult(result) => {
+                let result_json = serde_json::to_string(&result)
+                    .map_err(|e| Error::msg(format!("Result serialization failed: {}", e)))?;
+                Ok(ContentBuilder::text(result_json))
+            }
+            other => Ok(unexpected(other)),
+        }
+    }
+}
+
+/// Audio file transcription tool
+struct TranscribeFileTool;
+
+impl McpTool for TranscribeFileTool {
+    const NAME: &'static str = "transcribe_file";
+
+    fn description(builder: DescriptionBuilder) -> DescriptionBuilder {
+        builder
+            .does("Transcribe a recorded WAV, MP3 or Ogg audio file to text")
+            .when("you need the words spoken in a voice memo or recording")
+            .when("you need speech-to-text on a server without a microphone")
+            .perfect_for("meeting recordings, voice notes, and uploaded audio")
+            .requires("Either a file path on the voice host or base64-encoded audio")
+    }
+
+    fn schema(builder: SchemaBuilder) -> Value {
+        builder
+            .optional_string("path", "Path of the audio file on the voice host")
+            .optional_string(
+                "audio_base64",
+                "Base64-encoded audio file, used instead of path",
+            )
+            .optional_string(
+                "wake_word",
+                "Optional wake word to look for in the transcript",
+            )
+            .optional_bool(
+                "diarize",
+                "Split the transcript into timed segments labelled by speaker",
+            )
+            .optional_bool(
+                "detect_language",
+                "Identify the spoken language and report a confidence for it",
+            )
+            .optional_bool("word_timestamps", "Report when each word was spoken")
+            .build()
+    }
+
+    fn execute(args: Value) -> Result<CallToolResult, Error> {
+        let params: TranscribeFileParams = serde_json::from_value(args)
+            .map_err(|e| Error::msg(format!("Invalid transcribe_file arguments: {}", e)))?;
+
+        match request(VoiceRequest::TranscribeFile(params))? {
+            VoiceResponse::ListenResult(result) => {
+                let result_json = serde_json::to_string(&result)
+                    .map_err(|e| Error::msg(format!("Result serialization failed: {}", e)))?;
+                Ok(ContentBuilder::text(result_json))
+            }
+            other => Ok(unexpected(other)),
+        }
+    }
+}
+
+/// Text-to-speech file tool
+struct SynthesizeTool;
+
+impl McpTool for SynthesizeTool {
+    const NAME: &'static str = "synthesize";
+
+    fn description(builder: DescriptionBuilder) -> DescriptionBuilder {
+        builder
+            .does("Convert text to speech and save it as a WAV file instead of playing it")
+            .when("you need an audio file to send, attach, or play elsewhere")
+            .when("you need text-to-speech on a server without speakers")
+            .perfect_for("voice messages, narration, and audio content generation")
+    }
+
+    fn schema(builder: SchemaBuilder) -> Value {
+        builder
+            .required_string("text", "Text to convert to speech")
+            .optional_string(
+                "voice_id",
+                "Voice ID to use (optional, defaults to system voice)",
+            )
+            .optional_number("speed", "Speech speed (0.5-2.0, default 1.0)")
+            .optional_number("pitch", "Pitch shift in semitones (-12 to 12, default 0)")
+            .optional_number("volume", "Volume as a gain (0.0-2.0, default 1.0)")
+            .optional_bool(
+                "ssml",
+                "Treat text as SSML with <break>, <prosody>, <say-as> and <emphasis>",
+            )
+            .optional_string(
+                "output_path",
+                "Where to save the WAV file on the voice host (returned as base64 when omitted)",
+            )
+            .build()
+    }
+
+    fn execute(args: Value) -> Result<CallToolResult, Error> {
+        let params: SynthesizeParams = serde_json::from_value(args)
+            .map_err(|e| Error::msg(format!("Invalid synthesize arguments: {}", e)))?;
+
+        match request(VoiceRequest::Synthesize(params))? {
+            VoiceResponse::Synthesized(result) => match result.audio_base64 {
+                Some(audio) => Ok(ContentBuilder::audio(audio, result.mime_type)),
+                None => {
+                    let result_json = serde_json::to_string(&result)
+                        .map_err(|e| Error::msg(format!("Result serialization failed: {}", e)))?;
+                    Ok(ContentBuilder::text(result_json))
+                }
+            },
+            other => Ok(unexpected(other)),
+        }
+    }
+}
+
+/// Create the plugin instance
+fn plugin() -> McpPlugin<Ready> {
+    mcp_plugin("voice")
+        .description("Text-to-speech and speech-to-text through the host's audio devices")
+        .tool::<SpeakTool>()
+        .tool::<ListenTool>()
+        .tool::<TranscribeFileTool>()
+        .tool::<SynthesizeTool>()
+        .serve()
+}
+
+// Generate standard MCP entry points
+sweetmcp_plugin_builder::generate_mcp_functions!(plugin);